@@ -13,9 +13,6 @@ DATA_ROOT=${2:-data}
 CONSUMER=${3:-parser_${VENUE_ID}}
 OUTPUT_FILE=${4:-parsed_${VENUE_ID}.ndjson}
 
-# Ensure bypass cadence for development testing
-export SMS_BYPASS_CADENCE=1
-
 echo "[1/2] Fetching via GatewayOnce (bypass cadence) for venue=${VENUE_ID} ..."
 cargo run --bin sms_scraper -- gateway-once \
   --source-id "${VENUE_ID}" \
@@ -0,0 +1,204 @@
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+/// A GraphQL query: its document, its variables and the top-level field its result is read from
+pub trait Query {
+    /// The result, decoded from `data.<FIELD>`
+    type Output: DeserializeOwned;
+    /// The query's only top-level field
+    const FIELD: &'static str;
+
+    fn document(&self) -> String;
+
+    /// Variables for the document; arguments left unset are omitted and take the server's default
+    fn variables(&self) -> serde_json::Value;
+}
+
+/// One entry of a response's `errors`
+#[derive(Debug, Clone, Deserialize)]
+pub struct GraphQLError {
+    pub message: String,
+}
+
+#[derive(Error, Debug)]
+pub enum ClientError {
+    #[error("request failed: {0}")]
+    Http(#[from] reqwest::Error),
+
+    #[error("failed to decode {field}: {source} - response: {body}")]
+    Decode { field: &'static str, source: serde_json::Error, body: String },
+
+    #[error("GraphQL errors: {}", .0.iter().map(|e| e.message.as_str()).collect::<Vec<_>>().join("; "))]
+    GraphQL(Vec<GraphQLError>),
+
+    #[error("no {0} in response")]
+    MissingData(&'static str),
+}
+
+#[derive(Serialize)]
+struct Request<'a> {
+    query: &'a str,
+    variables: serde_json::Value,
+}
+
+#[derive(Deserialize)]
+struct Response {
+    data: Option<serde_json::Map<String, serde_json::Value>>,
+    #[serde(default)]
+    errors: Vec<GraphQLError>,
+}
+
+/// Client for one GraphQL endpoint, e.g. `http://127.0.0.1:8080/graphql`. Cheap to clone.
+#[derive(Clone, Debug)]
+pub struct Client {
+    http: reqwest::Client,
+    url: String,
+    token: Option<String>,
+}
+
+impl Client {
+    pub fn new(url: impl Into<String>) -> Self {
+        Self::with_http(reqwest::Client::new(), url)
+    }
+
+    /// Share an existing HTTP client (and its connection pool)
+    pub fn with_http(http: reqwest::Client, url: impl Into<String>) -> Self {
+        Self { http, url: url.into(), token: None }
+    }
+
+    /// Send `Authorization: Bearer <token>`, which the server requires for mutations
+    pub fn with_token(mut self, token: impl Into<String>) -> Self {
+        self.token = Some(token.into());
+        self
+    }
+
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// Run `query` and decode its result. Any `errors` in the response fail the query, even
+    /// alongside partial data.
+    pub async fn query<Q: Query>(&self, query: &Q) -> Result<Q::Output, ClientError> {
+        let document = query.document();
+        let mut request = self.http.post(&self.url).json(&Request { query: &document, variables: query.variables() });
+        if let Some(token) = &self.token {
+            request = request.bearer_auth(token);
+        }
+        let body = request.send().await?.text().await?;
+
+        let response: Response = serde_json::from_str(&body).map_err(|source| ClientError::Decode {
+            field: Q::FIELD,
+            source,
+            body: body.clone(),
+        })?;
+        if !response.errors.is_empty() {
+            return Err(ClientError::GraphQL(response.errors));
+        }
+        let value = response
+            .data
+            .and_then(|mut data| data.remove(Q::FIELD))
+            .ok_or(ClientError::MissingData(Q::FIELD))?;
+        serde_json::from_value(value).map_err(|source| ClientError::Decode { field: Q::FIELD, source, body })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::queries::{SearchArtists, Venues};
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    /// Answer one request with `status` and `body`; the handle yields the raw request
+    async fn serve_once(status: &'static str, body: &'static str) -> (String, tokio::task::JoinHandle<String>) {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/graphql", listener.local_addr().unwrap());
+        let server = tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut request = Vec::new();
+            let mut buf = [0u8; 4096];
+            // Read the headers, then as much body as they announce
+            loop {
+                let n = socket.read(&mut buf).await.unwrap();
+                request.extend_from_slice(&buf[..n]);
+                let text = String::from_utf8_lossy(&request).to_string();
+                if let Some(end) = text.find("\r\n\r\n") {
+                    let length = text[..end]
+                        .lines()
+                        .find_map(|l| l.to_ascii_lowercase().strip_prefix("content-length:").map(|v| v.trim().parse().unwrap()))
+                        .unwrap_or(0usize);
+                    if request.len() >= end + 4 + length || n == 0 {
+                        break;
+                    }
+                }
+            }
+            let response = format!(
+                "HTTP/1.1 {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                status,
+                body.len(),
+                body
+            );
+            let _ = socket.write_all(response.as_bytes()).await;
+            String::from_utf8_lossy(&request).into_owned()
+        });
+        (url, server)
+    }
+
+    #[tokio::test]
+    async fn query_sends_document_variables_and_token_and_decodes_its_field() {
+        let body = r#"{"data":{"searchArtists":[{"id":"a1","name":"The Thermals","nameSlug":"the-thermals","bio":null,"artistImageUrl":null}]}}"#;
+        let (url, server) = serve_once("200 OK", body).await;
+
+        let query = SearchArtists::new("thermals").limit(3);
+        let artists = Client::new(url).with_token("s3cret").query(&query).await.unwrap();
+        assert_eq!(artists.len(), 1);
+        assert_eq!(artists[0].name, "The Thermals");
+
+        let request = server.await.unwrap();
+        assert!(request.to_ascii_lowercase().contains("authorization: bearer s3cret"));
+        let sent: serde_json::Value = serde_json::from_str(&request[request.find("\r\n\r\n").unwrap() + 4..]).unwrap();
+        assert_eq!(sent["query"], query.document());
+        assert_eq!(sent["variables"], serde_json::json!({ "q": "thermals", "limit": 3 }));
+    }
+
+    #[tokio::test]
+    async fn graphql_errors_fail_the_query_even_with_data() {
+        let body = r#"{"data":{"venues":[]},"errors":[{"message":"rate limited"},{"message":"try later"}]}"#;
+        let (url, _server) = serve_once("200 OK", body).await;
+        match Client::new(url).query(&Venues::new()).await {
+            Err(err @ ClientError::GraphQL(_)) => assert_eq!(err.to_string(), "GraphQL errors: rate limited; try later"),
+            other => panic!("expected GraphQL errors, got {:?}", other),
+        }
+
+        let (url, _server) = serve_once("200 OK", r#"{"data":null,"errors":[{"message":"unknown field"}]}"#).await;
+        assert!(matches!(Client::new(url).query(&Venues::new()).await, Err(ClientError::GraphQL(_))));
+    }
+
+    #[tokio::test]
+    async fn missing_or_undecodable_data_is_reported_with_its_field() {
+        let (url, _server) = serve_once("200 OK", r#"{"data":{}}"#).await;
+        assert!(matches!(Client::new(url).query(&Venues::new()).await, Err(ClientError::MissingData("venues"))));
+
+        let (url, _server) = serve_once("200 OK", r#"{"data":{"venues":[{"id":1}]}}"#).await;
+        assert!(matches!(
+            Client::new(url).query(&Venues::new()).await,
+            Err(ClientError::Decode { field: "venues", .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn http_failures_surface_as_errors() {
+        // A proxy's error page isn't a GraphQL response; the body is kept for the message
+        let (url, _server) = serve_once("502 Bad Gateway", "upstream down").await;
+        match Client::new(url).query(&Venues::new()).await {
+            Err(ClientError::Decode { body, .. }) => assert_eq!(body, "upstream down"),
+            other => panic!("expected a decode error, got {:?}", other),
+        }
+
+        // Nothing listening
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/graphql", listener.local_addr().unwrap());
+        drop(listener);
+        assert!(matches!(Client::new(url).query(&Venues::new()).await, Err(ClientError::Http(_))));
+    }
+}
@@ -0,0 +1,24 @@
+//! Typed client for the SMS GraphQL API, so consumers don't hand-write query strings.
+//!
+//! - [`queries`]: one builder per query, declaring its arguments and the response type it
+//!   decodes to
+//! - [`types`]: the response structs, mirroring `sms-graphql/schema.graphql`
+//!
+//! ```no_run
+//! # async fn run() -> Result<(), sms_client::ClientError> {
+//! let client = sms_client::Client::new("http://127.0.0.1:8080/graphql");
+//! let events = client.query(&sms_client::queries::UpcomingEvents::new().days(7)).await?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! Each response struct's `selection()` is the selection set its queries ask for, so a field
+//! is added to a struct and its selection together, in one place. The server's `check-schema`
+//! fails on removed or changed fields, which is when this crate needs updating too.
+
+pub mod client;
+pub mod queries;
+pub mod types;
+
+pub use client::{Client, ClientError, GraphQLError, Query};
+pub use types::{Artist, Event, EventDay, SourceStatus, Venue, VenueOrderBy};
@@ -0,0 +1,412 @@
+// One builder per query: required arguments go to `new`, optional ones are set by name and
+// left out of the variables when unset, so the server's defaults apply.
+
+use chrono::NaiveDate;
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::client::Query;
+use crate::types::{Artist, Event, EventDay, SourceStatus, Venue, VenueOrderBy};
+
+/// `query($a: T, ...) { field(a: $a, ...) { selection } }`
+fn document(field: &str, arguments: &[(&str, &str)], selection: &str) -> String {
+    if arguments.is_empty() {
+        return format!("query {{ {} {{ {} }} }}", field, selection);
+    }
+    let declared: Vec<String> = arguments.iter().map(|(name, ty)| format!("${}: {}", name, ty)).collect();
+    let passed: Vec<String> = arguments.iter().map(|(name, _)| format!("{}: ${}", name, name)).collect();
+    format!("query({}) {{ {}({}) {{ {} }} }}", declared.join(", "), field, passed.join(", "), selection)
+}
+
+fn variables<T: Serialize>(arguments: &T) -> Value {
+    serde_json::to_value(arguments).expect("query arguments serialize to JSON")
+}
+
+/// The `tag`, `free` and `allAges` filters the event listing queries share
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EventFilters {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tag: Option<String>,
+    /// Only free (true) or only paid (false) shows
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub free: Option<bool>,
+    /// Only all-ages (true) or only age-restricted and unlabeled (false) shows
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub all_ages: Option<bool>,
+}
+
+const FILTER_ARGUMENTS: [(&str, &str); 3] = [("tag", "String"), ("free", "Boolean"), ("allAges", "Boolean")];
+
+macro_rules! filter_setters {
+    () => {
+        pub fn tag(mut self, tag: impl Into<String>) -> Self {
+            self.filters.tag = Some(tag.into());
+            self
+        }
+
+        pub fn free(mut self, free: bool) -> Self {
+            self.filters.free = Some(free);
+            self
+        }
+
+        pub fn all_ages(mut self, all_ages: bool) -> Self {
+            self.filters.all_ages = Some(all_ages);
+            self
+        }
+    };
+}
+
+/// `upcomingEvents`: events in the next 30 days, or `days`
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct UpcomingEvents {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub days: Option<i32>,
+    #[serde(flatten)]
+    pub filters: EventFilters,
+}
+
+impl UpcomingEvents {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn days(mut self, days: i32) -> Self {
+        self.days = Some(days);
+        self
+    }
+
+    filter_setters!();
+}
+
+impl Query for UpcomingEvents {
+    type Output = Vec<Event>;
+    const FIELD: &'static str = "upcomingEvents";
+
+    fn document(&self) -> String {
+        let mut arguments = vec![("days", "Int")];
+        arguments.extend(FILTER_ARGUMENTS);
+        document(Self::FIELD, &arguments, &Event::selection())
+    }
+
+    fn variables(&self) -> Value {
+        variables(self)
+    }
+}
+
+/// `eventsByDay`: every day from `from` to `to` (inclusive, at most 92 days) with its events
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct EventsByDay {
+    pub from: NaiveDate,
+    pub to: NaiveDate,
+    #[serde(flatten)]
+    pub filters: EventFilters,
+}
+
+impl EventsByDay {
+    pub fn new(from: NaiveDate, to: NaiveDate) -> Self {
+        Self { from, to, filters: EventFilters::default() }
+    }
+
+    filter_setters!();
+}
+
+impl Query for EventsByDay {
+    type Output = Vec<EventDay>;
+    const FIELD: &'static str = "eventsByDay";
+
+    fn document(&self) -> String {
+        let mut arguments = vec![("from", "NaiveDate!"), ("to", "NaiveDate!")];
+        arguments.extend(FILTER_ARGUMENTS);
+        document(Self::FIELD, &arguments, &EventDay::selection())
+    }
+
+    fn variables(&self) -> Value {
+        variables(self)
+    }
+}
+
+/// `searchEvents`: future events whose title matches `search`, at venues matching `venue`
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct SearchEvents {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub search: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub venue: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub offset: Option<i32>,
+}
+
+impl SearchEvents {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn search(mut self, search: impl Into<String>) -> Self {
+        self.search = Some(search.into());
+        self
+    }
+
+    pub fn venue(mut self, venue: impl Into<String>) -> Self {
+        self.venue = Some(venue.into());
+        self
+    }
+
+    pub fn page(mut self, limit: i32, offset: i32) -> Self {
+        self.limit = Some(limit);
+        self.offset = Some(offset);
+        self
+    }
+}
+
+impl Query for SearchEvents {
+    type Output = Vec<Event>;
+    const FIELD: &'static str = "searchEvents";
+
+    fn document(&self) -> String {
+        let arguments = [("search", "String"), ("venue", "String"), ("limit", "Int"), ("offset", "Int")];
+        document(Self::FIELD, &arguments, &Event::selection())
+    }
+
+    fn variables(&self) -> Value {
+        variables(self)
+    }
+}
+
+/// `event`: one event by id, `None` when there's no such event
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct EventById {
+    pub id: String,
+}
+
+impl EventById {
+    pub fn new(id: impl Into<String>) -> Self {
+        Self { id: id.into() }
+    }
+}
+
+impl Query for EventById {
+    type Output = Option<Event>;
+    const FIELD: &'static str = "event";
+
+    fn document(&self) -> String {
+        document(Self::FIELD, &[("id", "ID!")], &Event::selection())
+    }
+
+    fn variables(&self) -> Value {
+        variables(self)
+    }
+}
+
+/// `venues`: every venue, by name unless `order_by` says otherwise, with upcoming event counts
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Venues {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub offset: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub order_by: Option<VenueOrderBy>,
+}
+
+impl Venues {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn page(mut self, limit: i32, offset: i32) -> Self {
+        self.limit = Some(limit);
+        self.offset = Some(offset);
+        self
+    }
+
+    pub fn order_by(mut self, order_by: VenueOrderBy) -> Self {
+        self.order_by = Some(order_by);
+        self
+    }
+}
+
+impl Query for Venues {
+    type Output = Vec<Venue>;
+    const FIELD: &'static str = "venues";
+
+    fn document(&self) -> String {
+        let arguments = [("limit", "Int"), ("offset", "Int"), ("orderBy", "VenueOrderBy")];
+        document(Self::FIELD, &arguments, &Venue::selection_with_counts())
+    }
+
+    fn variables(&self) -> Value {
+        variables(self)
+    }
+}
+
+/// `venue`: one venue by id with its upcoming event count, `None` when there's no such venue
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct VenueById {
+    pub id: String,
+}
+
+impl VenueById {
+    pub fn new(id: impl Into<String>) -> Self {
+        Self { id: id.into() }
+    }
+}
+
+impl Query for VenueById {
+    type Output = Option<Venue>;
+    const FIELD: &'static str = "venue";
+
+    fn document(&self) -> String {
+        document(Self::FIELD, &[("id", "ID!")], &Venue::selection_with_counts())
+    }
+
+    fn variables(&self) -> Value {
+        variables(self)
+    }
+}
+
+/// `artist`: one artist by id, `None` when there's no such artist
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ArtistById {
+    pub id: String,
+}
+
+impl ArtistById {
+    pub fn new(id: impl Into<String>) -> Self {
+        Self { id: id.into() }
+    }
+}
+
+impl Query for ArtistById {
+    type Output = Option<Artist>;
+    const FIELD: &'static str = "artist";
+
+    fn document(&self) -> String {
+        document(Self::FIELD, &[("id", "ID!")], &Artist::selection())
+    }
+
+    fn variables(&self) -> Value {
+        variables(self)
+    }
+}
+
+/// `searchArtists`: artists by name, tolerating typos; best match first
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SearchArtists {
+    pub q: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<i32>,
+}
+
+impl SearchArtists {
+    pub fn new(q: impl Into<String>) -> Self {
+        Self { q: q.into(), limit: None }
+    }
+
+    pub fn limit(mut self, limit: i32) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+}
+
+impl Query for SearchArtists {
+    type Output = Vec<Artist>;
+    const FIELD: &'static str = "searchArtists";
+
+    fn document(&self) -> String {
+        document(Self::FIELD, &[("q", "String!"), ("limit", "Int")], &Artist::selection())
+    }
+
+    fn variables(&self) -> Value {
+        variables(self)
+    }
+}
+
+/// `sourceStatus`: crawl status of every source with recorded history, or of `source_id`
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SourceStatuses {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub source_id: Option<String>,
+}
+
+impl SourceStatuses {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn source(mut self, source_id: impl Into<String>) -> Self {
+        self.source_id = Some(source_id.into());
+        self
+    }
+}
+
+impl Query for SourceStatuses {
+    type Output = Vec<SourceStatus>;
+    const FIELD: &'static str = "sourceStatus";
+
+    fn document(&self) -> String {
+        document(Self::FIELD, &[("sourceId", "String")], &SourceStatus::selection())
+    }
+
+    fn variables(&self) -> Value {
+        variables(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn document_declares_and_passes_each_argument() {
+        assert_eq!(document("venues", &[], "id name"), "query { venues { id name } }");
+        assert_eq!(
+            document("venue", &[("id", "ID!")], "id"),
+            "query($id: ID!) { venue(id: $id) { id } }"
+        );
+        assert_eq!(
+            document("searchArtists", &[("q", "String!"), ("limit", "Int")], "name"),
+            "query($q: String!, $limit: Int) { searchArtists(q: $q, limit: $limit) { name } }"
+        );
+        assert!(UpcomingEvents::new().document().starts_with(
+            "query($days: Int, $tag: String, $free: Boolean, $allAges: Boolean) \
+             { upcomingEvents(days: $days, tag: $tag, free: $free, allAges: $allAges) { id title"
+        ));
+    }
+
+    #[test]
+    fn unset_arguments_are_left_out_of_the_variables() {
+        assert_eq!(UpcomingEvents::new().variables(), json!({}));
+        assert_eq!(Venues::new().variables(), json!({}));
+        assert_eq!(SourceStatuses::new().variables(), json!({}));
+        assert_eq!(SearchArtists::new("thermals").variables(), json!({ "q": "thermals" }));
+    }
+
+    #[test]
+    fn set_arguments_use_the_schemas_names_and_formats() {
+        assert_eq!(
+            UpcomingEvents::new().days(7).tag("punk").all_ages(true).variables(),
+            json!({ "days": 7, "tag": "punk", "allAges": true })
+        );
+        let from = NaiveDate::from_ymd_opt(2025, 3, 1).unwrap();
+        let to = NaiveDate::from_ymd_opt(2025, 3, 31).unwrap();
+        assert_eq!(
+            EventsByDay::new(from, to).free(false).variables(),
+            json!({ "from": "2025-03-01", "to": "2025-03-31", "free": false })
+        );
+        assert_eq!(
+            Venues::new().page(10, 20).order_by(VenueOrderBy::UpcomingEventCount).variables(),
+            json!({ "limit": 10, "offset": 20, "orderBy": "UPCOMING_EVENT_COUNT" })
+        );
+        assert_eq!(SourceStatuses::new().source("neumos").variables(), json!({ "sourceId": "neumos" }));
+        assert_eq!(
+            SearchEvents::new().search("tacocat").page(5, 0).variables(),
+            json!({ "search": "tacocat", "limit": 5, "offset": 0 })
+        );
+    }
+}
@@ -0,0 +1,124 @@
+// Response structs for the queries in `queries`, field for field from the schema's types.
+// Only the fields listed in each `selection()` are requested, and only those are decoded.
+
+use chrono::{DateTime, NaiveDate, NaiveTime, Utc};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Venue {
+    pub id: String,
+    pub name: String,
+    pub address: String,
+    pub city: String,
+    pub neighborhood: Option<String>,
+    pub latitude: f64,
+    pub longitude: f64,
+    pub venue_url: Option<String>,
+    pub venue_image_url: Option<String>,
+    pub provisional: bool,
+    /// Events from today on; only requested by the venue list and lookup queries, where the
+    /// server counts them in one grouped query
+    #[serde(default)]
+    pub upcoming_events_count: Option<i32>,
+}
+
+impl Venue {
+    pub fn selection() -> String {
+        "id name address city neighborhood latitude longitude venueUrl venueImageUrl provisional".to_string()
+    }
+
+    /// `selection()` plus the upcoming event count
+    pub fn selection_with_counts() -> String {
+        format!("{} upcomingEventsCount", Self::selection())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Artist {
+    pub id: String,
+    pub name: String,
+    pub name_slug: String,
+    pub bio: Option<String>,
+    pub artist_image_url: Option<String>,
+}
+
+impl Artist {
+    pub fn selection() -> String {
+        "id name nameSlug bio artistImageUrl".to_string()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Event {
+    pub id: String,
+    pub title: String,
+    pub event_day: NaiveDate,
+    pub start_time: Option<NaiveTime>,
+    pub doors_time: Option<NaiveTime>,
+    pub event_url: Option<String>,
+    pub description: Option<String>,
+    pub event_image_url: Option<String>,
+    pub tags: Vec<String>,
+    pub is_free: bool,
+    pub is_all_ages: bool,
+    pub venue: Option<Venue>,
+    pub artists: Vec<Artist>,
+}
+
+impl Event {
+    pub fn selection() -> String {
+        format!(
+            "id title eventDay startTime doorsTime eventUrl description eventImageUrl tags isFree isAllAges \
+             venue {{ {} }} artists {{ {} }}",
+            Venue::selection(),
+            Artist::selection()
+        )
+    }
+}
+
+/// A calendar day and its events, as grouped by `eventsByDay`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EventDay {
+    pub day: NaiveDate,
+    pub events: Vec<Event>,
+}
+
+impl EventDay {
+    pub fn selection() -> String {
+        format!("day events {{ {} }}", Event::selection())
+    }
+}
+
+/// Crawl status of one source
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SourceStatus {
+    pub source_id: String,
+    pub last_fetched_at: Option<DateTime<Utc>>,
+    pub last_success_at: Option<DateTime<Utc>>,
+    pub consecutive_failures: i32,
+    pub last_error: Option<String>,
+    pub envelopes_pending_parse: i32,
+    pub records_cataloged_last_run: Option<i32>,
+    pub last_run_finished_at: Option<DateTime<Utc>>,
+}
+
+impl SourceStatus {
+    pub fn selection() -> String {
+        "sourceId lastFetchedAt lastSuccessAt consecutiveFailures lastError envelopesPendingParse \
+         recordsCatalogedLastRun lastRunFinishedAt"
+            .to_string()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum VenueOrderBy {
+    /// Alphabetically by name
+    Name,
+    /// Most upcoming events first, then by name
+    UpcomingEventCount,
+}
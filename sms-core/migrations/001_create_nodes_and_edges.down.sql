@@ -0,0 +1,5 @@
+-- Revert 001: drop the graph tables (their indexes go with them)
+DROP TRIGGER IF EXISTS edges_updated_at;
+DROP TRIGGER IF EXISTS nodes_updated_at;
+DROP TABLE IF EXISTS edges;
+DROP TABLE IF EXISTS nodes;
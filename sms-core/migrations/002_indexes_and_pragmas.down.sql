@@ -0,0 +1,6 @@
+-- Revert 002: drop the uniqueness indexes
+DROP INDEX IF EXISTS idx_nodes_external_id_key;
+DROP INDEX IF EXISTS idx_nodes_event_key;
+DROP INDEX IF EXISTS idx_nodes_artist_slug;
+DROP INDEX IF EXISTS idx_nodes_venue_slug;
+DROP INDEX IF EXISTS idx_edges_src_dst_rel;
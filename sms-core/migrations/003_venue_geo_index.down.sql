@@ -0,0 +1,2 @@
+-- Revert 003: drop the venue coordinate index
+DROP INDEX IF EXISTS idx_nodes_venue_lat_lng;
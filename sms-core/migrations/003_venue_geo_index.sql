@@ -0,0 +1,6 @@
+-- Venue coordinates (stored in node JSON data) for bounding-box "near me" queries
+CREATE INDEX IF NOT EXISTS idx_nodes_venue_lat_lng
+  ON nodes(
+    json_extract(data, '$.latitude'),
+    json_extract(data, '$.longitude')
+  ) WHERE label = 'venue';
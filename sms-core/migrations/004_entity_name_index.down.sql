@@ -0,0 +1,2 @@
+-- Revert 004: drop the venue name index
+DROP INDEX IF EXISTS idx_nodes_venue_name;
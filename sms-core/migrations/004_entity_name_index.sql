@@ -0,0 +1,8 @@
+-- Non-provisional venue names are unique ignoring case, so two concurrent catalog runs
+-- can't both create the same venue: the later batch fails on this index and is restaged
+-- onto the venue the earlier one wrote. Artists stay unique by name_slug (002) alone, since
+-- slug allocation gives distinct artists who share a name suffixed slugs. Duplicate venues
+-- already cataloged have to be merged (`catalog merge`) before this applies.
+CREATE UNIQUE INDEX IF NOT EXISTS idx_nodes_venue_name
+  ON nodes(label, lower(json_extract(data, '$.name')))
+  WHERE label = 'venue' AND coalesce(json_extract(data, '$.provisional'), 0) = 0;
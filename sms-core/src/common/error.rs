@@ -24,6 +24,10 @@ pub enum ScraperError {
     #[cfg(feature = "db")]
     #[error("Database error: {message}")]
     Database { message: String },
+
+    /// A batch tried to create an entity another writer created first under its own id
+    #[error("{entity} {name:?} already exists as {existing_id}")]
+    Duplicate { entity: &'static str, name: String, existing_id: String },
 }
 
 pub type Result<T> = std::result::Result<T, ScraperError>;
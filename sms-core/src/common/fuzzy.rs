@@ -0,0 +1,101 @@
+// Trigram-based fuzzy name matching for curation search, tolerant of typos and punctuation
+
+use std::collections::HashSet;
+
+/// Scores below this are not considered matches (the pg_trgm default)
+pub const MIN_FUZZY_SCORE: f64 = 0.3;
+
+/// Lowercase, drop punctuation and collapse whitespace, so "AC/DC" and "acdc" or
+/// "Guns N' Roses" and "guns n roses" compare equal
+pub fn normalize_for_search(s: &str) -> String {
+    s.to_lowercase()
+        .chars()
+        .filter(|c| c.is_alphanumeric() || c.is_whitespace())
+        .collect::<String>()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Trigrams of each word, padded with two leading spaces and one trailing space like pg_trgm
+fn trigrams(normalized: &str) -> HashSet<String> {
+    let mut grams = HashSet::new();
+    for word in normalized.split_whitespace() {
+        let padded: Vec<char> = format!("  {} ", word).chars().collect();
+        for window in padded.windows(3) {
+            grams.insert(window.iter().collect());
+        }
+    }
+    grams
+}
+
+/// Share of trigrams two strings have in common (Jaccard similarity)
+fn similarity(a: &HashSet<String>, b: &HashSet<String>) -> f64 {
+    let union = a.union(b).count();
+    if union == 0 {
+        return 0.0;
+    }
+    a.intersection(b).count() as f64 / union as f64
+}
+
+/// How well `candidate` matches `query`, from 0.0 to 1.0. A query contained in the name
+/// scores 1.0. Otherwise the best trigram similarity against the whole name or any run of
+/// its words as long as the query, so a typo'd "crocodle" still finds "The Crocodile Belltown".
+pub fn fuzzy_score(query: &str, candidate: &str) -> f64 {
+    let query = normalize_for_search(query);
+    let candidate = normalize_for_search(candidate);
+    if query.is_empty() || candidate.is_empty() {
+        return 0.0;
+    }
+    if candidate.contains(&query) || candidate.replace(' ', "").contains(&query.replace(' ', "")) {
+        return 1.0;
+    }
+    let query_grams = trigrams(&query);
+    let words: Vec<&str> = candidate.split_whitespace().collect();
+    let span = query.split_whitespace().count().min(words.len());
+    words
+        .windows(span)
+        .map(|run| similarity(&query_grams, &trigrams(&run.join(" "))))
+        .fold(similarity(&query_grams, &trigrams(&candidate)), f64::max)
+}
+
+/// Items whose name matches `query`, best match first (ties by name)
+pub fn rank_by_name<T>(query: &str, items: Vec<T>, name: impl Fn(&T) -> &str) -> Vec<T> {
+    let mut scored: Vec<(f64, T)> = items
+        .into_iter()
+        .map(|item| (fuzzy_score(query, name(&item)), item))
+        .filter(|(score, _)| *score >= MIN_FUZZY_SCORE)
+        .collect();
+    scored.sort_by(|(sa, a), (sb, b)| sb.total_cmp(sa).then_with(|| name(a).cmp(name(b))));
+    scored.into_iter().map(|(_, item)| item).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn punctuation_and_case_are_ignored() {
+        assert_eq!(normalize_for_search("  Guns N'   Roses "), "guns n roses");
+        assert_eq!(fuzzy_score("acdc", "AC/DC"), 1.0);
+        assert_eq!(fuzzy_score("crocodile", "The Crocodile Belltown"), 1.0);
+        assert_eq!(fuzzy_score("", "The Crocodile"), 0.0);
+        assert_eq!(fuzzy_score("!!", "The Crocodile"), 0.0);
+    }
+
+    #[test]
+    fn typos_still_match_a_run_of_words() {
+        let typo = fuzzy_score("crocodle", "The Crocodile Belltown");
+        assert!((MIN_FUZZY_SCORE..1.0).contains(&typo), "{typo}");
+        assert!(fuzzy_score("crocodle", "Neumos") < MIN_FUZZY_SCORE);
+    }
+
+    #[test]
+    fn ranks_best_match_first_and_drops_non_matches() {
+        let names = vec!["Neumos", "The Crocodile", "Crocodile Cafe", "Crocodle Rock"];
+        let ranked = rank_by_name("crocodile", names, |n| n);
+        assert_eq!(ranked[..2], ["Crocodile Cafe", "The Crocodile"]);
+        assert!(ranked.contains(&"Crocodle Rock"));
+        assert!(!ranked.contains(&"Neumos"));
+    }
+}
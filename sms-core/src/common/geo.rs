@@ -0,0 +1,106 @@
+// Great-circle distance and bounding boxes for "near me" venue lookups
+
+/// Mean Earth radius in kilometres
+pub const EARTH_RADIUS_KM: f64 = 6371.0;
+
+/// Latitude/longitude rectangle, used as a cheap index-friendly prefilter before
+/// exact distance checks
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GeoBounds {
+    pub min_lat: f64,
+    pub max_lat: f64,
+    pub min_lng: f64,
+    pub max_lng: f64,
+}
+
+impl GeoBounds {
+    /// Smallest box containing every point within `radius_km` of (lat, lng).
+    /// Near the poles the longitude span is widened to the full range.
+    pub fn around(lat: f64, lng: f64, radius_km: f64) -> Self {
+        let lat_delta = (radius_km / EARTH_RADIUS_KM).to_degrees();
+        let min_lat = (lat - lat_delta).max(-90.0);
+        let max_lat = (lat + lat_delta).min(90.0);
+
+        let cos_lat = lat.to_radians().cos();
+        let (min_lng, max_lng) = if min_lat <= -90.0 || max_lat >= 90.0 || cos_lat <= f64::EPSILON {
+            (-180.0, 180.0)
+        } else {
+            let lng_delta = (radius_km / (EARTH_RADIUS_KM * cos_lat)).to_degrees();
+            if lng_delta >= 180.0 {
+                (-180.0, 180.0)
+            } else {
+                ((lng - lng_delta).max(-180.0), (lng + lng_delta).min(180.0))
+            }
+        };
+
+        Self { min_lat, max_lat, min_lng, max_lng }
+    }
+
+    pub fn contains(&self, lat: f64, lng: f64) -> bool {
+        (self.min_lat..=self.max_lat).contains(&lat) && (self.min_lng..=self.max_lng).contains(&lng)
+    }
+}
+
+/// Haversine distance between two points in kilometres
+pub fn haversine_km(lat1: f64, lng1: f64, lat2: f64, lng2: f64) -> f64 {
+    let d_lat = (lat2 - lat1).to_radians();
+    let d_lng = (lng2 - lng1).to_radians();
+    let a = (d_lat / 2.0).sin().powi(2)
+        + lat1.to_radians().cos() * lat2.to_radians().cos() * (d_lng / 2.0).sin().powi(2);
+    2.0 * EARTH_RADIUS_KM * a.sqrt().asin()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Pike Place Market and Capitol Hill, about 2.3 km apart
+    const PIKE_PLACE: (f64, f64) = (47.6097, -122.3422);
+    const CAPITOL_HILL: (f64, f64) = (47.6253, -122.3222);
+
+    #[test]
+    fn haversine_matches_known_distances() {
+        assert_eq!(haversine_km(PIKE_PLACE.0, PIKE_PLACE.1, PIKE_PLACE.0, PIKE_PLACE.1), 0.0);
+        let d = haversine_km(PIKE_PLACE.0, PIKE_PLACE.1, CAPITOL_HILL.0, CAPITOL_HILL.1);
+        assert!((d - 2.29).abs() < 0.05, "{d}");
+        assert_eq!(d, haversine_km(CAPITOL_HILL.0, CAPITOL_HILL.1, PIKE_PLACE.0, PIKE_PLACE.1));
+        // One degree of latitude along a meridian
+        assert!((haversine_km(0.0, 0.0, 1.0, 0.0) - 111.19).abs() < 0.01);
+        // Across the antimeridian the short way round
+        assert!((haversine_km(0.0, 179.5, 0.0, -179.5) - 111.19).abs() < 0.01);
+    }
+
+    #[test]
+    fn bounds_contain_the_radius_and_their_own_edges() {
+        let (lat, lng) = PIKE_PLACE;
+        let bounds = GeoBounds::around(lat, lng, 5.0);
+        assert!(bounds.contains(lat, lng));
+        // The edges sit exactly `radius_km` out along each axis and count as inside
+        assert!((haversine_km(lat, lng, bounds.max_lat, lng) - 5.0).abs() < 1e-6);
+        assert!((haversine_km(lat, lng, bounds.min_lat, lng) - 5.0).abs() < 1e-6);
+        assert!(bounds.contains(bounds.max_lat, lng));
+        assert!(bounds.contains(lat, bounds.min_lng));
+        assert!(!bounds.contains(bounds.max_lat + 1e-6, lng));
+        assert!(!bounds.contains(lat, bounds.max_lng + 1e-6));
+        // Longitude degrees are shorter this far north, so the box is wider than it is tall
+        assert!(bounds.max_lng - bounds.min_lng > bounds.max_lat - bounds.min_lat);
+
+        // A corner is inside the box but further away than the radius; callers filter those
+        // out by exact distance
+        assert!(bounds.contains(bounds.max_lat, bounds.max_lng));
+        assert!(haversine_km(lat, lng, bounds.max_lat, bounds.max_lng) > 5.0);
+    }
+
+    #[test]
+    fn bounds_clamp_at_the_poles_and_the_antimeridian() {
+        let polar = GeoBounds::around(89.99, 0.0, 50.0);
+        assert_eq!((polar.max_lat, polar.min_lng, polar.max_lng), (90.0, -180.0, 180.0));
+
+        let dateline = GeoBounds::around(0.0, 179.9, 50.0);
+        assert_eq!(dateline.max_lng, 180.0);
+        assert!(dateline.min_lng < 179.9);
+
+        let huge = GeoBounds::around(45.0, 0.0, 20_000.0);
+        assert_eq!(huge, GeoBounds { min_lat: -90.0, max_lat: 90.0, min_lng: -180.0, max_lng: 180.0 });
+    }
+}
@@ -2,6 +2,9 @@
 
 pub mod constants;
 pub mod error;
+pub mod fuzzy;
+pub mod geo;
+pub mod record_key;
 pub mod types;
 
 // Re-export commonly used items at module root for convenience
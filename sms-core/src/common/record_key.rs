@@ -0,0 +1,214 @@
+// One identity for a parsed record across parse, normalize, quality gate, conflation and the
+// dead-letter queue
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fmt;
+use std::str::FromStr;
+
+/// Record path used for keys that stand for a whole envelope, e.g. a payload that failed to parse
+pub const ENVELOPE_PATH: &str = "$";
+
+/// Hex digits of the record path's SHA-256 kept in a key
+const PATH_HASH_LEN: usize = 16;
+
+/// Identifies one record within one envelope of one source. The record path is hashed so keys
+/// have a bounded length whatever the parser's paths look like.
+///
+/// Encoded as `source_id:envelope_id:path_hash`; `%` and `:` in the source id are
+/// percent-escaped, and envelope ids may contain anything, so the encoding always round-trips.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct RecordKey {
+    source_id: String,
+    envelope_id: String,
+    path_hash: String,
+}
+
+impl RecordKey {
+    pub fn new(source_id: &str, envelope_id: &str, record_path: &str) -> Self {
+        Self {
+            source_id: source_id.to_string(),
+            envelope_id: envelope_id.to_string(),
+            path_hash: Self::hash_path(record_path),
+        }
+    }
+
+    /// Key standing for the envelope as a whole rather than one record in it
+    pub fn envelope(source_id: &str, envelope_id: &str) -> Self {
+        Self::new(source_id, envelope_id, ENVELOPE_PATH)
+    }
+
+    pub fn source_id(&self) -> &str {
+        &self.source_id
+    }
+
+    pub fn envelope_id(&self) -> &str {
+        &self.envelope_id
+    }
+
+    pub fn path_hash(&self) -> &str {
+        &self.path_hash
+    }
+
+    /// Whether this key was made from `record_path`
+    pub fn matches_path(&self, record_path: &str) -> bool {
+        self.path_hash == Self::hash_path(record_path)
+    }
+
+    /// Leading hex digits of the path's SHA-256
+    pub fn hash_path(record_path: &str) -> String {
+        let digest = Sha256::digest(record_path.as_bytes());
+        let mut hex = String::with_capacity(PATH_HASH_LEN);
+        for byte in digest.iter().take(PATH_HASH_LEN / 2) {
+            hex.push_str(&format!("{:02x}", byte));
+        }
+        hex
+    }
+}
+
+impl fmt::Display for RecordKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}:{}", escape_source_id(&self.source_id), self.envelope_id, self.path_hash)
+    }
+}
+
+/// Why a string isn't an encoded `RecordKey`
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("invalid record key {0:?}")]
+pub struct InvalidRecordKey(pub String);
+
+impl FromStr for RecordKey {
+    type Err = InvalidRecordKey;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || InvalidRecordKey(s.to_string());
+        let (source_id, rest) = s.split_once(':').ok_or_else(invalid)?;
+        let (envelope_id, path_hash) = rest.rsplit_once(':').ok_or_else(invalid)?;
+        if source_id.is_empty()
+            || path_hash.len() != PATH_HASH_LEN
+            || !path_hash.bytes().all(|b| b.is_ascii_digit() || (b'a'..=b'f').contains(&b))
+        {
+            return Err(invalid());
+        }
+        Ok(Self {
+            source_id: unescape_source_id(source_id),
+            envelope_id: envelope_id.to_string(),
+            path_hash: path_hash.to_string(),
+        })
+    }
+}
+
+impl TryFrom<String> for RecordKey {
+    type Error = InvalidRecordKey;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
+impl From<RecordKey> for String {
+    fn from(key: RecordKey) -> Self {
+        key.to_string()
+    }
+}
+
+/// Identifies a record within its source across fetches: the source's own id for it, or one
+/// read from its fields (`id:..`, `title:..|date`, `content:..`). Where a [`RecordKey`] names
+/// one record of one envelope, this stays the same each time the source lists the record, so
+/// the durable indexes (delta snapshots, catalog keys, canonical id resolution) key by it.
+///
+/// Encoded as `source_id:key`, the source id escaped as in a `RecordKey`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct SourceKey {
+    source_id: String,
+    key: String,
+}
+
+impl SourceKey {
+    pub fn new(source_id: &str, key: impl Into<String>) -> Self {
+        Self { source_id: source_id.to_string(), key: key.into() }
+    }
+
+    pub fn source_id(&self) -> &str {
+        &self.source_id
+    }
+
+    /// The key within the source, without the source id
+    pub fn key(&self) -> &str {
+        &self.key
+    }
+}
+
+impl fmt::Display for SourceKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", escape_source_id(&self.source_id), self.key)
+    }
+}
+
+impl FromStr for SourceKey {
+    type Err = InvalidRecordKey;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.split_once(':') {
+            Some((source_id, key)) if !source_id.is_empty() && !key.is_empty() => {
+                Ok(Self { source_id: unescape_source_id(source_id), key: key.to_string() })
+            }
+            _ => Err(InvalidRecordKey(s.to_string())),
+        }
+    }
+}
+
+impl TryFrom<String> for SourceKey {
+    type Error = InvalidRecordKey;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
+impl From<SourceKey> for String {
+    fn from(key: SourceKey) -> Self {
+        key.to_string()
+    }
+}
+
+fn escape_source_id(source_id: &str) -> String {
+    source_id.replace('%', "%25").replace(':', "%3A")
+}
+
+fn unescape_source_id(source_id: &str) -> String {
+    source_id.replace("%3A", ":").replace("%25", "%")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn record_keys_round_trip_through_their_encoding() {
+        let key = RecordKey::new("test_source", "test_envelope", "$.venues[0]");
+        let encoded = key.to_string();
+        assert!(encoded.starts_with("test_source:test_envelope:"));
+        assert_eq!(encoded.parse::<RecordKey>().unwrap(), key);
+        assert!(key.matches_path("$.venues[0]"));
+        assert_ne!(key, RecordKey::new("test_source", "test_envelope", "$.venues[1]"));
+
+        let awkward = RecordKey::new("odd:source%", "env:1", "$");
+        assert_eq!(awkward.to_string().parse::<RecordKey>().unwrap(), awkward);
+        assert_eq!(awkward, RecordKey::envelope("odd:source%", "env:1"));
+        assert!("test_source:env".parse::<RecordKey>().is_err());
+        assert!(":env:0123456789abcdef".parse::<RecordKey>().is_err());
+    }
+
+    #[test]
+    fn source_keys_round_trip_through_their_encoding() {
+        let key = SourceKey::new("odd:source%", "title:late show|2026-11-02");
+        assert_eq!(key.to_string(), "odd%3Asource%25:title:late show|2026-11-02");
+        assert_eq!(key.to_string().parse::<SourceKey>().unwrap(), key);
+        assert_eq!(serde_json::to_string(&SourceKey::new("kexp", "id:42")).unwrap(), "\"kexp:id:42\"");
+        assert!("kexp:".parse::<SourceKey>().is_err());
+        assert!("kexp".parse::<SourceKey>().is_err());
+    }
+}
@@ -20,6 +20,9 @@ pub struct EventArgs {
     pub title: String,
     pub event_day: NaiveDate,
     pub start_time: Option<NaiveTime>,
+    /// When doors open, when the listing gives it separately from the show time
+    #[serde(default)]
+    pub doors_time: Option<NaiveTime>,
     pub event_url: Option<String>,
     pub description: Option<String>,
     pub event_image_url: Option<String>,
@@ -1,10 +1,108 @@
 use crate::common::error::{Result, ScraperError};
+use crate::common::geo::GeoBounds;
+use crate::migrations::{self, MigrationStatus};
+use chrono::NaiveDate;
 use libsql::{Builder, Connection, Database};
 use std::env;
-use tracing::info;
+use tracing::{info, warn};
+
+// Explicit ON CONFLICT(id) DO UPDATE avoids destructive REPLACE semantics
+const UPSERT_NODE_SQL: &str = "INSERT INTO nodes (id, label, data, created_at, updated_at)
+     VALUES (?1, ?2, ?3, COALESCE((SELECT created_at FROM nodes WHERE id = ?1), datetime('now')), datetime('now'))
+     ON CONFLICT(id) DO UPDATE SET
+       data = excluded.data,
+       updated_at = excluded.updated_at";
+
+// Unique (source_id, target_id, relation) makes edge upserts idempotent
+const UPSERT_EDGE_SQL: &str = "INSERT INTO edges (id, source_id, target_id, relation, data, created_at, updated_at)
+     VALUES (?1, ?2, ?3, ?4, ?5, COALESCE((SELECT created_at FROM edges WHERE source_id = ?2 AND target_id = ?3 AND relation = ?4), datetime('now')), datetime('now'))
+     ON CONFLICT(source_id, target_id, relation) DO UPDATE SET
+       data = excluded.data,
+       updated_at = excluded.updated_at";
+
+/// Insert artist node `?1` unless another artist already holds slug `?3`; one statement, so
+/// concurrent writers in any process can't both take the slug
+const INSERT_ARTIST_IF_SLUG_FREE_SQL: &str = "INSERT INTO nodes (id, label, data, created_at, updated_at)
+     SELECT ?1, 'artist', ?2, datetime('now'), datetime('now')
+     WHERE NOT EXISTS (
+       SELECT 1 FROM nodes WHERE label = 'artist' AND lower(json_extract(data, '$.name_slug')) = lower(?3)
+     )";
+
+const DELETE_EDGES_TO_SQL: &str = "DELETE FROM edges WHERE target_id = ?1 AND relation = ?2";
+
+/// The `label` node whose unique key node `?2`, with data `?3`, collided with: a venue
+/// holding its slug or (both non-provisional) its name, or an artist holding its slug.
+/// Returns its id and the name node `?2` was written under.
+const CONFLICTING_NODE_SQL: &str = "SELECT id, coalesce(json_extract(?3, '$.name'), '') FROM nodes
+     WHERE label = ?1 AND id != ?2 AND (
+       (label = 'venue' AND (
+         lower(json_extract(data, '$.slug')) = lower(json_extract(?3, '$.slug'))
+         OR (lower(json_extract(data, '$.name')) = lower(json_extract(?3, '$.name'))
+           AND coalesce(json_extract(data, '$.provisional'), 0) = 0
+           AND coalesce(json_extract(?3, '$.provisional'), 0) = 0)))
+       OR (label = 'artist'
+         AND lower(json_extract(data, '$.name_slug')) = lower(json_extract(?3, '$.name_slug'))))
+     LIMIT 1";
+
+/// One statement of a [`DatabaseManager::write_batch`] transaction
+#[derive(Debug, Clone)]
+pub enum GraphWrite {
+    Node { id: String, label: &'static str, data: String },
+    Edge { id: String, source_id: String, target_id: String, relation: &'static str, data: Option<String> },
+    DeleteEdgesTo { target_id: String, relation: &'static str },
+}
+
+/// SQLite primary result codes for a write that lost a race with another writer
+const SQLITE_BUSY: i32 = 5;
+const SQLITE_LOCKED: i32 = 6;
+
+/// Whether a failed write broke a unique index
+fn is_unique_violation(error: &libsql::Error) -> bool {
+    error.to_string().contains("UNIQUE constraint failed")
+}
+
+/// Whether a failed write lost a race with another writer and is worth retrying
+fn is_write_conflict(error: &libsql::Error) -> bool {
+    // Extended codes (SQLITE_BUSY_SNAPSHOT, ...) carry the primary code in the low byte
+    let conflict = |code: i32| matches!(code & 0xff, SQLITE_BUSY | SQLITE_LOCKED);
+    match error {
+        libsql::Error::SqliteFailure(code, _) => conflict(*code),
+        libsql::Error::RemoteSqliteFailure(code, extended_code, _) => conflict(*code) || conflict(*extended_code),
+        // Remote (Hrana) stream errors only expose the result code by name, as `code: "SQLITE_BUSY"`
+        libsql::Error::Hrana(e) => {
+            let message = e.to_string();
+            ["\"SQLITE_BUSY", "\"SQLITE_LOCKED"].iter().any(|code| message.contains(code))
+        }
+        _ => false,
+    }
+}
+
+/// Why one attempt at a batch failed
+enum BatchFailure {
+    /// Lost a race with another writer; worth retrying
+    Conflict(ScraperError),
+    Fatal(ScraperError),
+}
+
+impl BatchFailure {
+    fn database(context: &str, e: libsql::Error) -> Self {
+        let conflict = is_write_conflict(&e);
+        let error = ScraperError::Database { message: format!("{context}: {e}") };
+        if conflict {
+            Self::Conflict(error)
+        } else {
+            Self::Fatal(error)
+        }
+    }
+}
+
+/// How long a connection to a local file waits on another connection's lock before
+/// failing with SQLITE_BUSY; Turso serializes remote writers itself
+const LOCAL_BUSY_TIMEOUT_MS: u32 = 5_000;
 
 pub struct DatabaseManager {
     db: Database,
+    local: bool,
 }
 
 impl DatabaseManager {
@@ -27,55 +125,148 @@ impl DatabaseManager {
                 message: format!("Failed to connect to database: {e}"),
             })?;
 
-        Ok(Self { db })
+        Ok(Self { db, local: false })
+    }
+
+    /// A database in a local SQLite file
+    #[cfg(test)]
+    pub(crate) async fn open_local(path: &std::path::Path) -> Result<Self> {
+        let db = Builder::new_local(path).build().await.map_err(|e| ScraperError::Database {
+            message: format!("Failed to open local database: {e}"),
+        })?;
+        Ok(Self { db, local: true })
     }
 
     /// Get a connection to the database
     pub async fn get_connection(&self) -> Result<Connection> {
-        self.db.connect().map_err(|e| ScraperError::Database {
+        let conn = self.db.connect().map_err(|e| ScraperError::Database {
             message: format!("Failed to get database connection: {e}"),
-        })
+        })?;
+        if self.local {
+            conn.query(&format!("PRAGMA busy_timeout = {LOCAL_BUSY_TIMEOUT_MS}"), ())
+                .await
+                .map_err(|e| ScraperError::Database {
+                    message: format!("Failed to set busy timeout: {e}"),
+                })?;
+        }
+        Ok(conn)
     }
 
-    /// Run database migrations
+    /// Apply every pending migration, bringing the schema to the latest version
     pub async fn run_migrations(&self) -> Result<()> {
         info!("Running database migrations...");
+        let applied = self.migrate_up(None).await?;
+        info!(
+            "Database migrations completed successfully ({} applied, schema at v{})",
+            applied.len(),
+            migrations::latest_version()
+        );
+        Ok(())
+    }
+
+    /// Known migrations and when each was applied
+    pub async fn migration_status(&self) -> Result<Vec<MigrationStatus>> {
+        let conn = self.get_connection().await?;
+        let applied = Self::applied_migrations(&conn).await?;
+        Ok(migrations::MIGRATIONS
+            .iter()
+            .map(|m| MigrationStatus {
+                version: m.version,
+                name: m.name,
+                applied_at: applied
+                    .iter()
+                    .find(|(version, _)| *version == m.version)
+                    .map(|(_, at)| at.clone()),
+            })
+            .collect())
+    }
 
+    /// Apply pending migrations up to `target` (latest when `None`), returning the versions applied
+    pub async fn migrate_up(&self, target: Option<u32>) -> Result<Vec<u32>> {
         let conn = self.get_connection().await?;
+        let applied: Vec<u32> = Self::applied_migrations(&conn).await?.into_iter().map(|(v, _)| v).collect();
+
+        let mut done = Vec::new();
+        for migration in migrations::pending_up(&applied, target) {
+            info!("Applying migration {:03}_{}", migration.version, migration.name);
+            Self::apply_migration(&conn, migration, true).await?;
+            done.push(migration.version);
+        }
+        Ok(done)
+    }
+
+    /// Revert applied migrations newer than `target`, newest first, returning the versions reverted
+    pub async fn migrate_down(&self, target: u32) -> Result<Vec<u32>> {
+        let conn = self.get_connection().await?;
+        let applied: Vec<u32> = Self::applied_migrations(&conn).await?.into_iter().map(|(v, _)| v).collect();
+
+        let mut done = Vec::new();
+        for migration in migrations::pending_down(&applied, target) {
+            info!("Reverting migration {:03}_{}", migration.version, migration.name);
+            Self::apply_migration(&conn, migration, false).await?;
+            done.push(migration.version);
+        }
+        Ok(done)
+    }
+
+    /// Run one migration's up (or down) script and its schema_version bookkeeping in a single
+    /// transaction, so a failing statement leaves neither half applied
+    async fn apply_migration(conn: &Connection, migration: &migrations::Migration, up: bool) -> Result<()> {
+        let (verb, script) = if up { ("apply", migration.up) } else { ("revert", migration.down) };
+        let failed = |e: libsql::Error| ScraperError::Database {
+            message: format!("Failed to {verb} migration {:03}_{}: {e}", migration.version, migration.name),
+        };
+
+        let tx = conn.transaction().await.map_err(failed)?;
+        tx.execute_batch(script).await.map_err(failed)?;
+        let recorded = if up {
+            tx.execute(
+                "INSERT OR REPLACE INTO schema_version (version, name, applied_at) VALUES (?1, ?2, datetime('now'))",
+                libsql::params![migration.version, migration.name],
+            )
+            .await
+        } else {
+            tx.execute("DELETE FROM schema_version WHERE version = ?1", libsql::params![migration.version]).await
+        };
+        recorded.map_err(failed)?;
+        tx.commit().await.map_err(failed)
+    }
 
-        // Apply base schema
-        let migration_sql_001 = include_str!("../migrations/001_create_nodes_and_edges.sql");
-        conn.execute_batch(migration_sql_001)
+    /// (version, applied_at) rows from schema_version, creating the table on first use
+    async fn applied_migrations(conn: &Connection) -> Result<Vec<(u32, String)>> {
+        conn.execute(migrations::SCHEMA_VERSION_DDL, ())
             .await
             .map_err(|e| ScraperError::Database {
-                message: format!("Failed to run base migration: {e}"),
+                message: format!("Failed to create schema_version table: {e}"),
             })?;
 
-        // Apply indexes and PRAGMAs
-        let migration_sql_002 = include_str!("../migrations/002_indexes_and_pragmas.sql");
-        conn.execute_batch(migration_sql_002)
+        let mut rows = conn
+            .query("SELECT version, applied_at FROM schema_version ORDER BY version", ())
             .await
             .map_err(|e| ScraperError::Database {
-                message: format!("Failed to run index migration: {e}"),
+                message: format!("Failed to query schema_version: {e}"),
             })?;
 
-        info!("Database migrations completed successfully");
-        Ok(())
+        let mut applied = Vec::new();
+        while let Some(row) = rows.next().await.map_err(|e| ScraperError::Database {
+            message: format!("Failed to read row: {e}"),
+        })? {
+            let version: u32 = row.get(0).map_err(|e| ScraperError::Database {
+                message: format!("Failed to get version: {e}"),
+            })?;
+            let applied_at: String = row.get(1).map_err(|e| ScraperError::Database {
+                message: format!("Failed to get applied_at: {e}"),
+            })?;
+            applied.push((version, applied_at));
+        }
+        Ok(applied)
     }
 
     /// Create or update a node in the database (upsert)
     pub async fn create_node(&self, id: &str, label: &str, data: &str) -> Result<()> {
         let conn = self.get_connection().await?;
 
-        // Use explicit ON CONFLICT(id) DO UPDATE to avoid destructive REPLACE semantics
-        conn.execute(
-            "INSERT INTO nodes (id, label, data, created_at, updated_at)
-             VALUES (?1, ?2, ?3, COALESCE((SELECT created_at FROM nodes WHERE id = ?1), datetime('now')), datetime('now'))
-             ON CONFLICT(id) DO UPDATE SET
-               data = excluded.data,
-               updated_at = excluded.updated_at",
-            libsql::params![id, label, data]
-        )
+        conn.execute(UPSERT_NODE_SQL, libsql::params![id, label, data])
         .await
         .map_err(|e| ScraperError::Database {
             message: format!("Failed to upsert node: {e}")
@@ -84,6 +275,17 @@ impl DatabaseManager {
         Ok(())
     }
 
+    /// Insert an artist node unless another artist holds `slug`, returning whether it was inserted
+    pub async fn create_artist_node_if_slug_free(&self, id: &str, slug: &str, data: &str) -> Result<bool> {
+        let conn = self.get_connection().await?;
+        match conn.execute(INSERT_ARTIST_IF_SLUG_FREE_SQL, libsql::params![id, data, slug]).await {
+            Ok(inserted) => Ok(inserted > 0),
+            // The unique slug index (migration 002) caught a writer that got in first
+            Err(e) if e.to_string().contains("UNIQUE constraint failed") => Ok(false),
+            Err(e) => Err(ScraperError::Database { message: format!("Failed to insert artist node: {e}") }),
+        }
+    }
+
     /// Create or update an edge in the database (upsert)
     pub async fn create_edge(
         &self,
@@ -95,15 +297,7 @@ impl DatabaseManager {
     ) -> Result<()> {
         let conn = self.get_connection().await?;
 
-        // Use unique (source_id, target_id, relation) to idempotently upsert edges
-        conn.execute(
-            "INSERT INTO edges (id, source_id, target_id, relation, data, created_at, updated_at)
-             VALUES (?1, ?2, ?3, ?4, ?5, COALESCE((SELECT created_at FROM edges WHERE source_id = ?2 AND target_id = ?3 AND relation = ?4), datetime('now')), datetime('now'))
-             ON CONFLICT(source_id, target_id, relation) DO UPDATE SET
-               data = excluded.data,
-               updated_at = excluded.updated_at",
-            libsql::params![id, source_id, target_id, relation, data]
-        )
+        conn.execute(UPSERT_EDGE_SQL, libsql::params![id, source_id, target_id, relation, data])
         .await
         .map_err(|e| ScraperError::Database {
             message: format!("Failed to upsert edge: {e}")
@@ -112,6 +306,89 @@ impl DatabaseManager {
         Ok(())
     }
 
+    /// Apply `writes` in a single transaction, retrying with backoff up to `max_attempts`
+    /// times when it conflicts with another writer. Returns the attempts it took.
+    pub async fn write_batch(&self, writes: &[GraphWrite], max_attempts: u32) -> Result<u32> {
+        let conn = self.get_connection().await?;
+        let mut attempt = 1;
+        loop {
+            match Self::write_batch_once(&conn, writes).await {
+                Ok(()) => return Ok(attempt),
+                Err(BatchFailure::Conflict(e)) if attempt < max_attempts => {
+                    warn!("Batch write conflicted (attempt {}/{}), retrying: {}", attempt, max_attempts, e);
+                    tokio::time::sleep(std::time::Duration::from_millis(50 * 2u64.pow(attempt - 1))).await;
+                    attempt += 1;
+                }
+                Err(BatchFailure::Conflict(e) | BatchFailure::Fatal(e)) => return Err(e),
+            }
+        }
+    }
+
+    async fn write_batch_once(conn: &Connection, writes: &[GraphWrite]) -> std::result::Result<(), BatchFailure> {
+        let tx = conn
+            .transaction()
+            .await
+            .map_err(|e| BatchFailure::database("Failed to begin transaction", e))?;
+        for write in writes {
+            let result = match write {
+                GraphWrite::Node { id, label, data } => {
+                    match tx.execute(UPSERT_NODE_SQL, libsql::params![id.as_str(), *label, data.as_str()]).await {
+                        // Another writer cataloged the same venue or artist first (the name and
+                        // slug indexes, migrations 002 and 004); the caller restages onto it
+                        Err(e) if is_unique_violation(&e) => match Self::conflicting_node(&tx, label, id, data).await {
+                            Ok(Some((existing_id, name))) => {
+                                let _ = tx.rollback().await;
+                                return Err(BatchFailure::Fatal(ScraperError::Duplicate { entity: label, name, existing_id }));
+                            }
+                            _ => Err(e),
+                        },
+                        result => result,
+                    }
+                }
+                GraphWrite::Edge { id, source_id, target_id, relation, data } => {
+                    tx.execute(
+                        UPSERT_EDGE_SQL,
+                        libsql::params![id.as_str(), source_id.as_str(), target_id.as_str(), *relation, data.as_deref()],
+                    )
+                    .await
+                }
+                GraphWrite::DeleteEdgesTo { target_id, relation } => {
+                    tx.execute(DELETE_EDGES_TO_SQL, libsql::params![target_id.as_str(), *relation]).await
+                }
+            };
+            if let Err(e) = result {
+                // Rolling back is best effort; the transaction is abandoned either way
+                let _ = tx.rollback().await;
+                return Err(BatchFailure::database("Failed to apply batch write", e));
+            }
+        }
+        tx.commit().await.map_err(|e| BatchFailure::database("Failed to commit batch", e))
+    }
+
+    async fn conflicting_node(
+        tx: &libsql::Transaction,
+        label: &str,
+        id: &str,
+        data: &str,
+    ) -> std::result::Result<Option<(String, String)>, libsql::Error> {
+        let mut rows = tx.query(CONFLICTING_NODE_SQL, libsql::params![label, id, data]).await?;
+        match rows.next().await? {
+            Some(row) => Ok(Some((row.get::<String>(0)?, row.get::<String>(1)?))),
+            None => Ok(None),
+        }
+    }
+
+    /// Delete every `relation` edge pointing at `target_id`
+    pub async fn delete_edges_to(&self, target_id: &str, relation: &str) -> Result<()> {
+        let conn = self.get_connection().await?;
+        conn.execute(DELETE_EDGES_TO_SQL, libsql::params![target_id, relation])
+        .await
+        .map_err(|e| ScraperError::Database {
+            message: format!("Failed to delete edges: {e}"),
+        })?;
+        Ok(())
+    }
+
     /// Get a node by ID
     pub async fn get_node(&self, id: &str) -> Result<Option<(String, String, String)>> {
         let conn = self.get_connection().await?;
@@ -145,6 +422,31 @@ impl DatabaseManager {
         }
     }
 
+    /// Distinct CAS references of the payloads raw data rows were ingested from
+    pub async fn get_raw_data_payload_refs(&self) -> Result<Vec<String>> {
+        let conn = self.get_connection().await?;
+        let mut rows = conn
+            .query(
+                "SELECT DISTINCT json_extract(data, '$.origin.payload_ref') FROM nodes
+                 WHERE label = 'raw_data' AND json_extract(data, '$.origin.payload_ref') IS NOT NULL",
+                (),
+            )
+            .await
+            .map_err(|e| ScraperError::Database {
+                message: format!("Failed to query raw data payload refs: {e}"),
+            })?;
+
+        let mut refs = Vec::new();
+        while let Some(row) = rows.next().await.map_err(|e| ScraperError::Database {
+            message: format!("Failed to read row: {e}"),
+        })? {
+            refs.push(row.get::<String>(0).map_err(|e| ScraperError::Database {
+                message: format!("Failed to get payload ref: {e}"),
+            })?);
+        }
+        Ok(refs)
+    }
+
     /// Get all nodes by label
     pub async fn get_nodes_by_label(&self, label: &str) -> Result<Vec<(String, String, String)>> {
         let conn = self.get_connection().await?;
@@ -179,6 +481,150 @@ impl DatabaseManager {
         Ok(results)
     }
 
+    /// Get the nodes with `label` among `ids` in one query; ids with no such node are skipped
+    pub async fn get_nodes_by_ids(&self, label: &str, ids: &[String]) -> Result<Vec<(String, String, String)>> {
+        let conn = self.get_connection().await?;
+        let ids = serde_json::to_string(ids).map_err(|e| ScraperError::Database {
+            message: format!("Failed to encode node ids: {e}"),
+        })?;
+
+        let mut rows = conn
+            .query(
+                "SELECT id, label, data FROM nodes
+                 WHERE label = ?1 AND id IN (SELECT value FROM json_each(?2))",
+                libsql::params![label, ids],
+            )
+            .await
+            .map_err(|e| ScraperError::Database {
+                message: format!("Failed to query nodes: {e}"),
+            })?;
+
+        let mut results = Vec::new();
+        while let Some(row) = rows.next().await.map_err(|e| ScraperError::Database {
+            message: format!("Failed to read row: {e}"),
+        })? {
+            results.push(node_from_row(&row)?);
+        }
+
+        Ok(results)
+    }
+
+    /// Get the target nodes of every `relation` edge leaving one of `source_ids`, in one
+    /// query, each paired with the id of the node the edge leaves from
+    pub async fn get_edge_targets(
+        &self,
+        relation: &str,
+        source_ids: &[String],
+    ) -> Result<Vec<(String, (String, String, String))>> {
+        let conn = self.get_connection().await?;
+        let source_ids = serde_json::to_string(source_ids).map_err(|e| ScraperError::Database {
+            message: format!("Failed to encode node ids: {e}"),
+        })?;
+
+        let mut rows = conn
+            .query(
+                "SELECT n.id, n.label, n.data, e.source_id FROM edges e
+                 JOIN nodes n ON n.id = e.target_id
+                 WHERE e.relation = ?1 AND e.source_id IN (SELECT value FROM json_each(?2))",
+                libsql::params![relation, source_ids],
+            )
+            .await
+            .map_err(|e| ScraperError::Database {
+                message: format!("Failed to query edge targets: {e}"),
+            })?;
+
+        let mut results = Vec::new();
+        while let Some(row) = rows.next().await.map_err(|e| ScraperError::Database {
+            message: format!("Failed to read row: {e}"),
+        })? {
+            let source_id: String = row.get(3).map_err(|e| ScraperError::Database {
+                message: format!("Failed to get source_id: {e}"),
+            })?;
+            results.push((source_id, node_from_row(&row)?));
+        }
+
+        Ok(results)
+    }
+
+    /// Get venue nodes whose coordinates fall inside the given box
+    pub async fn get_venue_nodes_in_bounds(&self, bounds: GeoBounds) -> Result<Vec<(String, String, String)>> {
+        let conn = self.get_connection().await?;
+
+        // Matches idx_nodes_venue_lat_lng so the scan stays on the index; placeholders only
+        // carry default coordinates, so they're left out
+        let mut rows = conn
+            .query(
+                "SELECT id, label, data FROM nodes
+                 WHERE label = 'venue'
+                   AND json_extract(data, '$.latitude') BETWEEN ?1 AND ?2
+                   AND json_extract(data, '$.longitude') BETWEEN ?3 AND ?4
+                   AND coalesce(json_extract(data, '$.provisional'), 0) = 0",
+                libsql::params![bounds.min_lat, bounds.max_lat, bounds.min_lng, bounds.max_lng],
+            )
+            .await
+            .map_err(|e| ScraperError::Database {
+                message: format!("Failed to query venues in bounds: {e}"),
+            })?;
+
+        let mut results = Vec::new();
+        while let Some(row) = rows.next().await.map_err(|e| ScraperError::Database {
+            message: format!("Failed to read row: {e}"),
+        })? {
+            let id: String = row.get(0).map_err(|e| ScraperError::Database {
+                message: format!("Failed to get id: {e}"),
+            })?;
+            let label: String = row.get(1).map_err(|e| ScraperError::Database {
+                message: format!("Failed to get label: {e}"),
+            })?;
+            let data: String = row.get(2).map_err(|e| ScraperError::Database {
+                message: format!("Failed to get data: {e}"),
+            })?;
+
+            results.push((id, label, data));
+        }
+
+        Ok(results)
+    }
+
+    /// Number of shown events each venue hosts on or after `from`, grouped in one query.
+    /// Venues hosting none are left out.
+    pub async fn count_hosted_events_since(&self, from: NaiveDate) -> Result<Vec<(String, u64)>> {
+        let conn = self.get_connection().await?;
+
+        // event_day is stored as an ISO date, so string comparison orders it by day
+        let mut rows = conn
+            .query(
+                "SELECT e.source_id, COUNT(*) FROM edges e
+                 JOIN nodes n ON n.id = e.target_id
+                 WHERE e.relation = 'hosts'
+                   AND n.label = 'event'
+                   AND json_extract(n.data, '$.event_day') >= ?1
+                   AND json_extract(n.data, '$.show_event') = 1
+                 GROUP BY e.source_id",
+                libsql::params![from.to_string()],
+            )
+            .await
+            .map_err(|e| ScraperError::Database {
+                message: format!("Failed to count hosted events: {e}"),
+            })?;
+
+        let mut results = Vec::new();
+        while let Some(row) = rows.next().await.map_err(|e| ScraperError::Database {
+            message: format!("Failed to read row: {e}"),
+        })? {
+            let venue_id: String = row.get(0).map_err(|e| ScraperError::Database {
+                message: format!("Failed to get venue id: {e}"),
+            })?;
+            let count: i64 = row.get(1).map_err(|e| ScraperError::Database {
+                message: format!("Failed to get count: {e}"),
+            })?;
+
+            results.push((venue_id, count as u64));
+        }
+
+        Ok(results)
+    }
+
     /// Clear all data from the database (useful for development)
     pub async fn clear_all_data(&self) -> Result<()> {
         let conn = self.get_connection().await?;
@@ -465,3 +911,200 @@ impl DatabaseManager {
         Ok(())
     }
 }
+
+/// The `(id, label, data)` of a node row selected in that column order
+fn node_from_row(row: &libsql::Row) -> Result<(String, String, String)> {
+    let id: String = row.get(0).map_err(|e| ScraperError::Database {
+        message: format!("Failed to get id: {e}"),
+    })?;
+    let label: String = row.get(1).map_err(|e| ScraperError::Database {
+        message: format!("Failed to get label: {e}"),
+    })?;
+    let data: String = row.get(2).map_err(|e| ScraperError::Database {
+        message: format!("Failed to get data: {e}"),
+    })?;
+    Ok((id, label, data))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_busy_and_locked_result_codes_are_write_conflicts() {
+        assert!(is_write_conflict(&libsql::Error::SqliteFailure(SQLITE_BUSY, "database is locked".into())));
+        // SQLITE_BUSY_SNAPSHOT
+        assert!(is_write_conflict(&libsql::Error::SqliteFailure(517, "snapshot is stale".into())));
+        assert!(is_write_conflict(&libsql::Error::RemoteSqliteFailure(SQLITE_LOCKED, 262, "locked".into())));
+        assert!(is_write_conflict(&libsql::Error::Hrana("stream error: `Error { message: \"database is locked\", code: \"SQLITE_BUSY\" }`".into())));
+
+        // SQLITE_CONSTRAINT_UNIQUE, whatever the message says
+        assert!(!is_write_conflict(&libsql::Error::SqliteFailure(2067, "UNIQUE constraint failed: busy_venues.name".into())));
+        assert!(!is_write_conflict(&libsql::Error::Hrana("stream error: `Error { message: \"conflict\", code: \"SQLITE_CONSTRAINT\" }`".into())));
+        assert!(!is_write_conflict(&libsql::Error::ConnectionFailed("busy".into())));
+    }
+
+    async fn schema_objects(db: &DatabaseManager, name: &str) -> usize {
+        let conn = db.get_connection().await.unwrap();
+        let mut rows = conn
+            .query("SELECT count(*) FROM sqlite_master WHERE name = ?1", libsql::params![name])
+            .await
+            .unwrap();
+        let count: i64 = rows.next().await.unwrap().unwrap().get(0).unwrap();
+        count as usize
+    }
+
+    #[tokio::test]
+    async fn migrations_apply_and_revert_in_order() {
+        let tmp = tempfile::tempdir().unwrap();
+        let db = DatabaseManager::open_local(&tmp.path().join("sms.db")).await.unwrap();
+
+        assert_eq!(db.migrate_up(Some(3)).await.unwrap(), vec![1, 2, 3]);
+        assert_eq!(db.migrate_up(None).await.unwrap(), vec![4]);
+        assert!(db.migrate_up(None).await.unwrap().is_empty());
+        assert!(db.migration_status().await.unwrap().iter().all(|m| m.applied_at.is_some()));
+        assert_eq!(schema_objects(&db, "idx_nodes_venue_lat_lng").await, 1);
+        assert_eq!(schema_objects(&db, "idx_nodes_venue_name").await, 1);
+
+        assert_eq!(db.migrate_down(2).await.unwrap(), vec![4, 3]);
+        assert_eq!(schema_objects(&db, "idx_nodes_venue_lat_lng").await, 0);
+        assert_eq!(schema_objects(&db, "idx_nodes_venue_name").await, 0);
+        assert_eq!(schema_objects(&db, "idx_nodes_venue_slug").await, 1);
+        let applied: Vec<u32> = db
+            .migration_status()
+            .await
+            .unwrap()
+            .iter()
+            .filter(|m| m.applied_at.is_some())
+            .map(|m| m.version)
+            .collect();
+        assert_eq!(applied, vec![1, 2]);
+
+        assert_eq!(db.migrate_up(None).await.unwrap(), vec![3, 4]);
+    }
+
+    #[tokio::test]
+    async fn an_artist_slug_is_claimed_once() {
+        let tmp = tempfile::tempdir().unwrap();
+        let db = DatabaseManager::open_local(&tmp.path().join("sms.db")).await.unwrap();
+        db.migrate_up(None).await.unwrap();
+
+        let data = |name: &str, slug: &str| format!(r#"{{"name":"{name}","name_slug":"{slug}"}}"#);
+        assert!(db.create_artist_node_if_slug_free("a1", "tim-eric", &data("Tim & Eric", "tim-eric")).await.unwrap());
+        assert!(!db.create_artist_node_if_slug_free("a2", "Tim-Eric", &data("Tim Eric", "Tim-Eric")).await.unwrap());
+        assert!(db.create_artist_node_if_slug_free("a2", "tim-eric-2", &data("Tim Eric", "tim-eric-2")).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn a_second_connection_cataloging_the_same_venue_or_artist_gets_a_duplicate() {
+        let tmp = tempfile::tempdir().unwrap();
+        let path = tmp.path().join("sms.db");
+        let first = DatabaseManager::open_local(&path).await.unwrap();
+        first.migrate_up(None).await.unwrap();
+        let second = DatabaseManager::open_local(&path).await.unwrap();
+
+        let node = |id: &str, label: &'static str, data: &str| GraphWrite::Node { id: id.into(), label, data: data.into() };
+        let duplicate_of = |result: Result<u32>| match result {
+            Err(ScraperError::Duplicate { existing_id, name, .. }) => (existing_id, name),
+            other => panic!("expected a duplicate, got {other:?}"),
+        };
+
+        first.write_batch(&[node("v1", "venue", r#"{"name":"Neumos","slug":"neumos"}"#)], 3).await.unwrap();
+        let renamed = second.write_batch(&[node("v2", "venue", r#"{"name":"NEUMOS","slug":"neumos-capitol-hill"}"#)], 3).await;
+        assert_eq!(duplicate_of(renamed), ("v1".to_string(), "NEUMOS".to_string()));
+        let reslugged = second.write_batch(&[node("v3", "venue", r#"{"name":"Neumos Cafe","slug":"neumos"}"#)], 3).await;
+        assert_eq!(duplicate_of(reslugged).0, "v1");
+        // A placeholder may share a real venue's name until it is resolved
+        second.write_batch(&[node("v4", "venue", r#"{"name":"Neumos","slug":"neumos-2","provisional":true}"#)], 3).await.unwrap();
+
+        first.write_batch(&[node("a1", "artist", r#"{"name":"Tim & Eric","name_slug":"tim-eric"}"#)], 3).await.unwrap();
+        let claimed = second.write_batch(&[node("a2", "artist", r#"{"name":"Tim Eric","name_slug":"tim-eric"}"#)], 3).await;
+        assert_eq!(duplicate_of(claimed).0, "a1");
+        // Two artists who share a name get their own slugs from allocate_artist
+        second.write_batch(&[node("a3", "artist", r#"{"name":"Tim & Eric","name_slug":"tim-eric-2"}"#)], 3).await.unwrap();
+        // Rewriting a node under its own id is an update, not a duplicate
+        second.write_batch(&[node("v1", "venue", r#"{"name":"Neumos","slug":"neumos","capacity":650}"#)], 3).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn raw_data_payload_refs_come_from_gateway_origins() {
+        let tmp = tempfile::tempdir().unwrap();
+        let db = DatabaseManager::open_local(&tmp.path().join("sms.db")).await.unwrap();
+        db.migrate_up(None).await.unwrap();
+
+        let origin = |payload_ref: &str| format!(r#"{{"origin":{{"envelope_id":"env","payload_ref":"{payload_ref}"}}}}"#);
+        db.create_node("r1", "raw_data", &origin("cas:sha256:aa")).await.unwrap();
+        db.create_node("r2", "raw_data", &origin("cas:sha256:aa")).await.unwrap();
+        db.create_node("r3", "raw_data", "{}").await.unwrap();
+        db.create_node("e1", "event", &origin("cas:sha256:bb")).await.unwrap();
+
+        assert_eq!(db.get_raw_data_payload_refs().await.unwrap(), vec!["cas:sha256:aa".to_string()]);
+    }
+
+    #[tokio::test]
+    async fn placeholder_venues_are_left_out_of_bounds_queries() {
+        let tmp = tempfile::tempdir().unwrap();
+        let db = DatabaseManager::open_local(&tmp.path().join("sms.db")).await.unwrap();
+        db.migrate_up(None).await.unwrap();
+
+        let data = |name: &str, provisional: bool| {
+            format!(r#"{{"name":"{name}","latitude":47.6062,"longitude":-122.3321,"provisional":{provisional}}}"#)
+        };
+        db.create_node("v1", "venue", &data("The Crocodile", false)).await.unwrap();
+        db.create_node("v2", "venue", &data("Crocodile", true)).await.unwrap();
+        db.create_node("v3", "venue", r#"{"name":"Neumos","latitude":47.614,"longitude":-122.319}"#).await.unwrap();
+
+        let found = db.get_venue_nodes_in_bounds(GeoBounds::around(47.6062, -122.3321, 5.0)).await.unwrap();
+        let mut ids: Vec<&str> = found.iter().map(|(id, _, _)| id.as_str()).collect();
+        ids.sort();
+        assert_eq!(ids, vec!["v1", "v3"]);
+    }
+
+    #[tokio::test]
+    async fn batch_lookups_fetch_nodes_and_edge_targets_for_many_ids() {
+        let tmp = tempfile::tempdir().unwrap();
+        let db = DatabaseManager::open_local(&tmp.path().join("sms.db")).await.unwrap();
+        db.migrate_up(None).await.unwrap();
+
+        db.create_node("v1", "venue", "{}").await.unwrap();
+        db.create_node("v2", "venue", "{}").await.unwrap();
+        db.create_node("a1", "artist", "{}").await.unwrap();
+        for (event, venue) in [("e1", "v1"), ("e2", "v1"), ("e3", "v2")] {
+            db.create_node(event, "event", "{}").await.unwrap();
+            db.create_edge(&format!("{venue}-{event}"), venue, event, "hosts", None).await.unwrap();
+        }
+        db.create_edge("a1-e3", "a1", "e3", "performs_at", None).await.unwrap();
+
+        let ids = |ids: &[&str]| ids.iter().map(|id| id.to_string()).collect::<Vec<_>>();
+        let mut venues: Vec<String> = db.get_nodes_by_ids("venue", &ids(&["v1", "v2", "a1", "missing"])).await.unwrap()
+            .into_iter().map(|(id, _, _)| id).collect();
+        venues.sort();
+        assert_eq!(venues, vec!["v1", "v2"]);
+
+        let mut hosted: Vec<(String, String)> = db.get_edge_targets("hosts", &ids(&["v1", "v2", "a1"])).await.unwrap()
+            .into_iter().map(|(source, (id, _, _))| (source, id)).collect();
+        hosted.sort();
+        let pair = |s: &str, t: &str| (s.to_string(), t.to_string());
+        assert_eq!(hosted, vec![pair("v1", "e1"), pair("v1", "e2"), pair("v2", "e3")]);
+        assert!(db.get_edge_targets("hosts", &[]).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn a_failing_migration_leaves_nothing_behind() {
+        let tmp = tempfile::tempdir().unwrap();
+        let db = DatabaseManager::open_local(&tmp.path().join("sms.db")).await.unwrap();
+        db.migrate_up(None).await.unwrap();
+
+        let broken = migrations::Migration {
+            version: 99,
+            name: "broken",
+            up: "CREATE TABLE scratch (id INTEGER); INSERT INTO no_such_table VALUES (1);",
+            down: "DROP TABLE IF EXISTS scratch;",
+        };
+        let conn = db.get_connection().await.unwrap();
+        assert!(DatabaseManager::apply_migration(&conn, &broken, true).await.is_err());
+        assert_eq!(schema_objects(&db, "scratch").await, 0);
+        let versions: Vec<u32> = DatabaseManager::applied_migrations(&conn).await.unwrap().into_iter().map(|(v, _)| v).collect();
+        assert_eq!(versions, vec![1, 2, 3, 4]);
+    }
+}
@@ -0,0 +1,82 @@
+//! Minimal entities for tests: a Seattle venue, an artist and a listed show, with every
+//! optional detail empty. Tests override what they care about with struct update syntax,
+//! e.g. `Event { finalized: true, ..Event::fixture("Show", day, venue_id) }`.
+
+use super::{Artist, Event, Venue};
+use chrono::{NaiveDate, Utc};
+use uuid::Uuid;
+
+impl Venue {
+    /// An unsaved, listed venue named `name`, slugged by its lowercased, hyphenated name
+    pub fn fixture(name: &str) -> Self {
+        Venue {
+            id: None,
+            name: name.to_string(),
+            name_lower: name.to_lowercase(),
+            slug: name.to_lowercase().replace(' ', "-"),
+            latitude: 47.6,
+            longitude: -122.3,
+            address: "1 Pike St".to_string(),
+            postal_code: "98101".to_string(),
+            city: "Seattle".to_string(),
+            venue_url: None,
+            venue_image_url: None,
+            description: None,
+            neighborhood: None,
+            show_venue: true,
+            created_at: Utc::now(),
+            attributions: Vec::new(),
+            provisional: false,
+            aliases: Vec::new(),
+            age_restriction: None,
+            accessibility_notes: None,
+            moderation: None,
+        }
+    }
+}
+
+impl Artist {
+    /// An unsaved artist named `name`, slugged by its lowercased, hyphenated name
+    pub fn fixture(name: &str) -> Self {
+        Artist {
+            id: None,
+            name: name.to_string(),
+            name_slug: name.to_lowercase().replace(' ', "-"),
+            bio: None,
+            artist_image_url: None,
+            created_at: Utc::now(),
+            attributions: Vec::new(),
+            detail_origins: Default::default(),
+            aliases: Vec::new(),
+        }
+    }
+}
+
+impl Event {
+    /// An unsaved, listed and unfinalized show at `venue_id` with no artists
+    pub fn fixture(title: &str, event_day: NaiveDate, venue_id: Uuid) -> Self {
+        Event {
+            id: None,
+            title: title.to_string(),
+            event_day,
+            start_time: None,
+            doors_time: None,
+            event_url: None,
+            description: None,
+            event_image_url: None,
+            venue_id,
+            artist_ids: Vec::new(),
+            show_event: true,
+            finalized: false,
+            created_at: Utc::now(),
+            attributions: Vec::new(),
+            tags: Vec::new(),
+            price: None,
+            series_id: None,
+            age_restriction: None,
+            accessibility_notes: None,
+            moderation: None,
+            external_ids: Vec::new(),
+        }
+    }
+}
@@ -1,7 +1,44 @@
-use chrono::{DateTime, NaiveDate, NaiveTime, Utc};
+use chrono::{DateTime, NaiveDate, NaiveTime, Utc, Weekday};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+#[cfg(any(test, feature = "test-support"))]
+mod fixtures;
+
+/// Licensing terms and required credit for data contributed by a source
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Attribution {
+    pub source_id: String,
+    pub license_id: String,
+    /// Credit line the frontend must render, when the source requires one
+    #[serde(default)]
+    pub text: Option<String>,
+}
+
+impl Attribution {
+    /// Add to an entity's attribution list unless that source is already credited
+    pub fn add_to(self, attributions: &mut Vec<Attribution>) {
+        if !attributions.iter().any(|a| a.source_id == self.source_id) {
+            attributions.push(self);
+        }
+    }
+}
+
+/// A source's own stable id for an entity, e.g. a Wix or ticketing event id
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExternalId {
+    pub source_id: String,
+    pub id: String,
+}
+
+impl ExternalId {
+    /// Add to an entity's external ids, replacing any earlier id from the same source
+    pub fn add_to(self, external_ids: &mut Vec<ExternalId>) {
+        external_ids.retain(|e| e.source_id != self.source_id);
+        external_ids.push(self);
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Venue {
     pub id: Option<Uuid>,
@@ -19,6 +56,68 @@ pub struct Venue {
     pub neighborhood: Option<String>,
     pub show_venue: bool,
     pub created_at: DateTime<Utc>,
+    #[serde(default)]
+    pub attributions: Vec<Attribution>,
+    /// Stand-in for a venue a source named but that isn't cataloged yet; its events are
+    /// rebound to the real venue once that venue is cataloged
+    #[serde(default)]
+    pub provisional: bool,
+    /// Door policy for the venue's shows, when it has a standing one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub age_restriction: Option<AgeRestriction>,
+    /// Step-free access, accessible restrooms, seating and the like
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub accessibility_notes: Option<String>,
+    /// Set while a moderator has the venue hidden; re-ingesting it never shows it again
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub moderation: Option<ModerationHold>,
+    /// Names of venues a curator merged into this one; name lookups match them too
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub aliases: Vec<String>,
+}
+
+impl Venue {
+    /// Whether `name` is this venue's name or one of its aliases, ignoring case
+    pub fn answers_to(&self, name: &str) -> bool {
+        answers_to(&self.name, &self.aliases, name)
+    }
+
+    /// Hidden "TBA" venue for events whose venue we only know by name. The id is derived
+    /// from the name so every mention of the same venue lands on the same placeholder.
+    pub fn placeholder(name: &str) -> Self {
+        let name = name.trim();
+        let name = if name.is_empty() { "TBA" } else { name };
+        let name_slug = name
+            .to_lowercase()
+            .split(|c: char| !c.is_alphanumeric())
+            .filter(|part| !part.is_empty())
+            .collect::<Vec<_>>()
+            .join("-");
+        let slug = format!("tba-{}", name_slug);
+        Self {
+            id: Some(Uuid::new_v5(&Uuid::NAMESPACE_DNS, slug.as_bytes())),
+            name: name.to_string(),
+            name_lower: name.to_lowercase(),
+            slug,
+            latitude: 47.6062, // Seattle until the real venue is known
+            longitude: -122.3321,
+            address: "TBA".to_string(),
+            postal_code: String::new(),
+            city: "Seattle".to_string(),
+            venue_url: None,
+            venue_image_url: None,
+            description: None,
+            neighborhood: None,
+            show_venue: false,
+            created_at: Utc::now(),
+            attributions: Vec::new(),
+            provisional: true,
+            aliases: Vec::new(),
+            age_restriction: None,
+            accessibility_notes: None,
+            moderation: None,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -29,6 +128,73 @@ pub struct Artist {
     pub bio: Option<String>,
     pub artist_image_url: Option<String>,
     pub created_at: DateTime<Utc>,
+    #[serde(default)]
+    pub attributions: Vec<Attribution>,
+    /// Where `artist_image_url` and `bio` came from, so better sources can replace them
+    #[serde(default)]
+    pub detail_origins: ArtistDetailOrigins,
+    /// Names of artists a curator merged into this one; name lookups match them too
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub aliases: Vec<String>,
+}
+
+/// Where an artist detail came from, lowest precedence first
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ArtistDetailSource {
+    /// The image or description of an event the artist headlines
+    HeadlinerEvent,
+    /// Set by a curator; only curation replaces it
+    Curated,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ArtistDetailOrigins {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub image: Option<ArtistDetailSource>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bio: Option<ArtistDetailSource>,
+}
+
+impl Artist {
+    /// Whether `name` is this artist's name or one of its aliases, ignoring case
+    pub fn answers_to(&self, name: &str) -> bool {
+        answers_to(&self.name, &self.aliases, name)
+    }
+
+    /// Offer an image and bio from `source`. Each takes a blank field, or replaces one set by
+    /// a lower-precedence source; curation always replaces. Details already on the artist with
+    /// no recorded origin rank as scraped. Returns whether anything changed.
+    pub fn offer_details(&mut self, image_url: Option<&str>, bio: Option<&str>, source: ArtistDetailSource) -> bool {
+        let image = offer(&mut self.artist_image_url, &mut self.detail_origins.image, image_url, source);
+        let bio = offer(&mut self.bio, &mut self.detail_origins.bio, bio, source);
+        image || bio
+    }
+}
+
+fn answers_to(own: &str, aliases: &[String], name: &str) -> bool {
+    let name = name.to_lowercase();
+    own.to_lowercase() == name || aliases.iter().any(|a| a.to_lowercase() == name)
+}
+
+fn offer(
+    field: &mut Option<String>,
+    origin: &mut Option<ArtistDetailSource>,
+    offered: Option<&str>,
+    source: ArtistDetailSource,
+) -> bool {
+    let Some(offered) = offered.map(str::trim).filter(|v| !v.is_empty()) else {
+        return false;
+    };
+    let blank = field.as_deref().is_none_or(|v| v.trim().is_empty());
+    let current = origin.unwrap_or(ArtistDetailSource::HeadlinerEvent);
+    let takes = blank || source > current || source == ArtistDetailSource::Curated;
+    if !takes || (field.as_deref() == Some(offered) && *origin == Some(source)) {
+        return false;
+    }
+    *field = Some(offered.to_string());
+    *origin = Some(source);
+    true
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -36,7 +202,11 @@ pub struct Event {
     pub id: Option<Uuid>,
     pub title: String,
     pub event_day: NaiveDate,
+    /// When the show starts; the doors time for listings that give only that
     pub start_time: Option<NaiveTime>,
+    /// When doors open, for listings that give it separately
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub doors_time: Option<NaiveTime>,
     pub event_url: Option<String>,
     pub description: Option<String>,
     pub event_image_url: Option<String>,
@@ -45,6 +215,163 @@ pub struct Event {
     pub show_event: bool,
     pub finalized: bool,
     pub created_at: DateTime<Utc>,
+    #[serde(default)]
+    pub attributions: Vec<Attribution>,
+    /// Genre/category tags (e.g. "rock", "dj", "open_mic") assigned by classification
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// Ticket price as advertised by the source, when it lists one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub price: Option<EventPrice>,
+    /// The recurring series (weekly open mic, trivia night) this event is an instance of
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub series_id: Option<Uuid>,
+    /// Who the listing says may attend
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub age_restriction: Option<AgeRestriction>,
+    /// Accessibility details the listing gives, e.g. ASL interpretation or seating
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub accessibility_notes: Option<String>,
+    /// Set while a moderator has the event hidden; re-ingesting it never shows it again
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub moderation: Option<ModerationHold>,
+    /// Ids the sources listing this event know it by
+    #[serde(default)]
+    pub external_ids: Vec<ExternalId>,
+}
+
+impl Event {
+    /// Carry a moderator's hide over from the cataloged copy of this event, so a fresh
+    /// listing of it doesn't show it again
+    pub fn keep_moderation(&mut self, existing: &Event) {
+        if existing.moderation.is_some() {
+            self.moderation = existing.moderation.clone();
+            self.show_event = false;
+        }
+    }
+
+    /// Whether the event carries `tag`, ignoring case
+    pub fn has_tag(&self, tag: &str) -> bool {
+        self.tags.iter().any(|t| t.eq_ignore_ascii_case(tag.trim()))
+    }
+
+    /// Whether the source advertises the event as free
+    pub fn is_free(&self) -> bool {
+        self.price.as_ref().is_some_and(EventPrice::is_free)
+    }
+
+    /// Whether the listing says minors may attend
+    pub fn is_all_ages(&self) -> bool {
+        self.age_restriction.is_some_and(AgeRestriction::is_all_ages)
+    }
+}
+
+/// A moderator's decision to keep an event or venue out of public listings. The entity stays
+/// in the catalog (a soft delete) and is only shown again by a moderator.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ModerationHold {
+    pub reason: String,
+    pub hidden_at: DateTime<Utc>,
+}
+
+/// Who may attend a show, as listings advertise it ("All Ages", "21+", "18+ w/ ID")
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AgeRestriction {
+    AllAges,
+    /// Minors admitted with a parent or guardian
+    AllAgesWithGuardian,
+    SixteenPlus,
+    EighteenPlus,
+    TwentyOnePlus,
+}
+
+impl AgeRestriction {
+    /// Minors may attend, with a guardian or not
+    pub fn is_all_ages(self) -> bool {
+        matches!(self, AgeRestriction::AllAges | AgeRestriction::AllAgesWithGuardian)
+    }
+
+    /// Youngest age admitted on their own; 0 for all-ages shows
+    pub fn minimum_age(self) -> u8 {
+        match self {
+            AgeRestriction::AllAges | AgeRestriction::AllAgesWithGuardian => 0,
+            AgeRestriction::SixteenPlus => 16,
+            AgeRestriction::EighteenPlus => 18,
+            AgeRestriction::TwentyOnePlus => 21,
+        }
+    }
+}
+
+/// An advertised ticket price, in minor units (cents) of `currency`. Several listed prices
+/// (advance and door, tiers) collapse to their range.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EventPrice {
+    /// Lowest listed price; 0 for free shows
+    pub min_cents: u32,
+    /// Highest listed price, when it differs from the lowest
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_cents: Option<u32>,
+    /// ISO 4217 code, e.g. "USD"
+    pub currency: String,
+}
+
+impl EventPrice {
+    /// No charge at all: "free before 9, $5 after" is not free
+    pub fn is_free(&self) -> bool {
+        self.min_cents == 0 && self.max_cents.unwrap_or(0) == 0
+    }
+}
+
+/// An event a venue repeats on a weekly cadence, such as an open mic every Tuesday. The
+/// instances stay ordinary events; each carries the series id in `Event::series_id`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EventSeries {
+    pub id: Option<Uuid>,
+    pub venue_id: Uuid,
+    /// Title of the most recent instance
+    pub title: String,
+    pub weekday: Weekday,
+    /// Weeks between instances: 1 for weekly, 2 for every other week
+    pub interval_weeks: u32,
+    /// The usual start time across instances
+    pub start_time: Option<NaiveTime>,
+    pub first_day: NaiveDate,
+    pub last_day: NaiveDate,
+    pub event_ids: Vec<Uuid>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl EventSeries {
+    /// Stable id for a venue's series of `title_key` on `weekday`, so re-detecting the series
+    /// on a later run updates it instead of creating another
+    pub fn derive_id(venue_id: Uuid, title_key: &str, weekday: Weekday) -> Uuid {
+        let name = format!("series|{}|{}|{}", venue_id, title_key, weekday);
+        Uuid::new_v5(&Uuid::NAMESPACE_OID, name.as_bytes())
+    }
+
+    /// "every Tuesday", "every other Tuesday" or "every 3 weeks on Tuesday"
+    pub fn cadence(&self) -> String {
+        let day = weekday_name(self.weekday);
+        match self.interval_weeks {
+            0 | 1 => format!("every {}", day),
+            2 => format!("every other {}", day),
+            n => format!("every {} weeks on {}", n, day),
+        }
+    }
+}
+
+/// "Monday", "Tuesday", ...
+pub fn weekday_name(weekday: Weekday) -> &'static str {
+    match weekday {
+        Weekday::Mon => "Monday",
+        Weekday::Tue => "Tuesday",
+        Weekday::Wed => "Wednesday",
+        Weekday::Thu => "Thursday",
+        Weekday::Fri => "Friday",
+        Weekday::Sat => "Saturday",
+        Weekday::Sun => "Sunday",
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -59,6 +386,20 @@ pub struct RawData {
     pub processed: bool,
     pub event_id: Option<Uuid>,
     pub created_at: DateTime<Utc>,
+    /// The envelope this row was ingested in, when it came through the gateway
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub origin: Option<RawDataOrigin>,
+}
+
+/// Where a gateway-ingested raw data row's payload was accepted
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RawDataOrigin {
+    pub envelope_id: String,
+    /// CAS reference of the payload (`cas:sha256:<hex>`)
+    pub payload_ref: String,
+    /// Set on multi-endpoint sources
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub endpoint_id: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -82,6 +423,9 @@ pub struct ProcessRecord {
     pub venue_id: Option<Uuid>,
     pub artist_id: Option<Uuid>,
     pub created_at: DateTime<Utc>,
+    /// The entity as JSON before this change was written, so an UPDATE can be rolled back
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub previous_state: Option<String>,
 }
 
 impl RawData {
@@ -1,9 +1,12 @@
 pub mod common;
 pub mod domain;
+pub mod pipeline_api;
 pub mod storage;
 
 #[cfg(feature = "db")]
 pub mod database;
+#[cfg(feature = "db")]
+pub mod migrations;
 
 pub use domain::*;
 
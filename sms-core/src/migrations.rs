@@ -0,0 +1,79 @@
+// Versioned schema migrations embedded from sms-core/migrations.
+//
+// To add one, create `NNN_name.sql` and `NNN_name.down.sql` next to the existing
+// files and append an entry to `MIGRATIONS`. Applied versions are recorded in the
+// `schema_version` table, so every crate that opens the database sees the same schema.
+
+/// One schema change and its inverse
+#[derive(Debug, Clone, Copy)]
+pub struct Migration {
+    pub version: u32,
+    pub name: &'static str,
+    pub up: &'static str,
+    pub down: &'static str,
+}
+
+/// All migrations in version order
+pub const MIGRATIONS: &[Migration] = &[
+    Migration {
+        version: 1,
+        name: "create_nodes_and_edges",
+        up: include_str!("../migrations/001_create_nodes_and_edges.sql"),
+        down: include_str!("../migrations/001_create_nodes_and_edges.down.sql"),
+    },
+    Migration {
+        version: 2,
+        name: "indexes_and_pragmas",
+        up: include_str!("../migrations/002_indexes_and_pragmas.sql"),
+        down: include_str!("../migrations/002_indexes_and_pragmas.down.sql"),
+    },
+    Migration {
+        version: 3,
+        name: "venue_geo_index",
+        up: include_str!("../migrations/003_venue_geo_index.sql"),
+        down: include_str!("../migrations/003_venue_geo_index.down.sql"),
+    },
+    Migration {
+        version: 4,
+        name: "entity_name_index",
+        up: include_str!("../migrations/004_entity_name_index.sql"),
+        down: include_str!("../migrations/004_entity_name_index.down.sql"),
+    },
+];
+
+/// Bookkeeping table for applied migrations
+pub const SCHEMA_VERSION_DDL: &str = "CREATE TABLE IF NOT EXISTS schema_version (
+    version INTEGER PRIMARY KEY,
+    name TEXT NOT NULL,
+    applied_at TEXT NOT NULL DEFAULT (datetime('now'))
+)";
+
+/// A known migration and when it was applied, if it has been
+#[derive(Debug, Clone)]
+pub struct MigrationStatus {
+    pub version: u32,
+    pub name: &'static str,
+    pub applied_at: Option<String>,
+}
+
+pub fn latest_version() -> u32 {
+    MIGRATIONS.last().map(|m| m.version).unwrap_or(0)
+}
+
+/// Migrations to apply, in order, to reach `target` (latest when `None`)
+pub fn pending_up(applied: &[u32], target: Option<u32>) -> Vec<&'static Migration> {
+    let target = target.unwrap_or_else(latest_version);
+    MIGRATIONS
+        .iter()
+        .filter(|m| m.version <= target && !applied.contains(&m.version))
+        .collect()
+}
+
+/// Applied migrations to revert, newest first, to get back down to `target`
+pub fn pending_down(applied: &[u32], target: u32) -> Vec<&'static Migration> {
+    MIGRATIONS
+        .iter()
+        .rev()
+        .filter(|m| m.version > target && applied.contains(&m.version))
+        .collect()
+}
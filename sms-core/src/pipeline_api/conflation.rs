@@ -0,0 +1,217 @@
+// Conflation stage: enriched records resolve to stable canonical entities
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+use super::enrich::EnrichedRecord;
+
+/// A conflated record that represents a stable canonical entity after entity resolution
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConflatedRecord {
+    /// The canonical entity ID that this record resolves to
+    pub canonical_entity_id: EntityId,
+    /// The enriched record that was processed
+    pub enriched_record: EnrichedRecord,
+    /// Conflation metadata about the resolution process
+    pub conflation: ConflationMetadata,
+    /// When this conflation was performed
+    pub conflated_at: DateTime<Utc>,
+}
+
+/// A stable, durable entity identifier that persists across data updates
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub struct EntityId {
+    /// The unique identifier for this canonical entity
+    pub id: Uuid,
+    /// The type of entity (venue, event, artist)
+    pub entity_type: EntityType,
+    /// Version of this entity (increments with updates)
+    pub version: u64,
+}
+
+/// Types of entities that can be conflated
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub enum EntityType {
+    Venue,
+    Event,
+    Artist,
+}
+
+// Display EntityType as its lowercase partition name
+impl std::fmt::Display for EntityType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            EntityType::Venue => write!(f, "venue"),
+            EntityType::Event => write!(f, "event"),
+            EntityType::Artist => write!(f, "artist"),
+        }
+    }
+}
+
+/// Metadata about the conflation process and entity resolution decisions
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConflationMetadata {
+    /// The resolution decision made during conflation
+    pub resolution_decision: ResolutionDecision,
+    /// Confidence in the entity resolution (0.0 to 1.0)
+    pub confidence: f64,
+    /// The strategy/algorithm used for conflation
+    pub strategy: String,
+    /// Alternative entities that were considered but not matched
+    pub alternatives: Vec<AlternativeMatch>,
+    /// Previous entity ID if this is an update to existing entity
+    pub previous_entity_id: Option<EntityId>,
+    /// Source identifiers that contributed to this canonical entity
+    pub contributing_sources: Vec<String>,
+    /// Similarity scores with matched entities
+    pub similarity_scores: HashMap<String, f64>,
+    /// Warnings or notes from the conflation process
+    pub warnings: Vec<String>,
+    /// Deduplication metadata
+    pub deduplication: DeduplicationMetadata,
+    /// Thresholds and tie-break strategy the decision was made under
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub config: Option<ConflatorConfig>,
+}
+
+/// The decision made during entity resolution
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub enum ResolutionDecision {
+    /// This is a new, previously unseen entity
+    NewEntity,
+    /// This matches an existing canonical entity (ID provided)
+    MatchedExisting(EntityId),
+    /// This updates an existing entity with new information
+    UpdatedExisting(EntityId),
+    /// This is a duplicate of an existing entity (no new information)
+    Duplicate(EntityId),
+    /// Conflation was uncertain - manual review may be needed
+    Uncertain,
+}
+
+/// Information about alternative matches that were considered
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AlternativeMatch {
+    /// The entity ID that was considered as a match
+    pub entity_id: EntityId,
+    /// Similarity score with this alternative (0.0 to 1.0)
+    pub similarity_score: f64,
+    /// Reason this alternative was not selected
+    pub rejection_reason: String,
+}
+
+/// Metadata about deduplication analysis
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeduplicationMetadata {
+    /// Whether this record was identified as a potential duplicate
+    pub is_potential_duplicate: bool,
+    /// Entity IDs of potential duplicates
+    pub potential_duplicates: Vec<EntityId>,
+    /// The deduplication strategy used
+    pub deduplication_strategy: String,
+    /// Key attributes used for deduplication matching
+    pub key_attributes: Vec<String>,
+    /// Hash or signature used for duplicate detection
+    pub deduplication_signature: Option<String>,
+}
+
+/// Match thresholds and tie-breaking for entity resolution, as set from the CLI or pipeline config
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ConflatorConfig {
+    /// Minimum similarity for a venue to match an existing entity
+    pub venue_threshold: f64,
+    /// Minimum similarity for an event to match an existing entity
+    pub event_threshold: f64,
+    /// Minimum similarity for an artist to match an existing entity
+    pub artist_threshold: f64,
+    /// What to do when several candidates share the best score
+    #[serde(default)]
+    pub tie_break: TieBreakStrategy,
+    /// Venues within this many meters of each other count as the same location
+    #[serde(default = "default_venue_radius_m")]
+    pub venue_radius_m: f64,
+}
+
+/// Default geofence radius for venue matching
+pub const DEFAULT_VENUE_RADIUS_M: f64 = 50.0;
+
+fn default_venue_radius_m() -> f64 {
+    DEFAULT_VENUE_RADIUS_M
+}
+
+impl Default for ConflatorConfig {
+    fn default() -> Self {
+        Self::uniform(0.8)
+    }
+}
+
+impl ConflatorConfig {
+    /// The same threshold for every entity type
+    pub fn uniform(threshold: f64) -> Self {
+        Self {
+            venue_threshold: threshold,
+            event_threshold: threshold,
+            artist_threshold: threshold,
+            tie_break: TieBreakStrategy::default(),
+            venue_radius_m: DEFAULT_VENUE_RADIUS_M,
+        }
+    }
+
+    pub fn threshold_for(&self, entity_type: &EntityType) -> f64 {
+        match entity_type {
+            EntityType::Venue => self.venue_threshold,
+            EntityType::Event => self.event_threshold,
+            EntityType::Artist => self.artist_threshold,
+        }
+    }
+}
+
+/// How to choose between candidates with the same best similarity score
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TieBreakStrategy {
+    /// Match the candidate with the lowest canonical id, so reruns resolve the same way
+    #[default]
+    LowestId,
+    /// Don't guess: mint a new entity and flag the record as uncertain for review
+    Uncertain,
+}
+
+impl std::str::FromStr for TieBreakStrategy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "lowest_id" | "lowest-id" => Ok(Self::LowestId),
+            "uncertain" => Ok(Self::Uncertain),
+            other => Err(format!("unknown tie-break strategy '{}' (expected lowest_id or uncertain)", other)),
+        }
+    }
+}
+
+/// Trait for performing entity conflation and resolution
+pub trait Conflator {
+    /// Conflate an enriched record, resolving it to a canonical entity
+    fn conflate(&self, record: &EnrichedRecord) -> anyhow::Result<ConflatedRecord>;
+    
+    /// Find potential matches for an enriched record
+    fn find_potential_matches(&self, record: &EnrichedRecord) -> anyhow::Result<Vec<PotentialMatch>>;
+    
+    /// Calculate similarity between two records
+    fn calculate_similarity(&self, record1: &EnrichedRecord, record2: &EnrichedRecord) -> f64;
+}
+
+/// A potential match found during conflation
+#[derive(Debug, Clone)]
+pub struct PotentialMatch {
+    /// The entity ID of the potential match
+    pub entity_id: EntityId,
+    /// Similarity score (0.0 to 1.0)
+    pub similarity_score: f64,
+    /// Breakdown of similarity by attribute
+    pub similarity_breakdown: HashMap<String, f64>,
+    /// The enriched record of the potential match
+    pub matched_record: EnrichedRecord,
+}
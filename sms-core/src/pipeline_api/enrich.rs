@@ -0,0 +1,95 @@
+// Enrich stage: accepted records gain geographic and routing context
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::quality_gate::QualityAssessedRecord;
+
+/// An enriched record that has passed through quality gate and been enhanced
+/// with contextual information like spatial bins, city tags, and routing labels
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EnrichedRecord {
+    /// The original quality-assessed record
+    pub quality_assessed_record: QualityAssessedRecord,
+    /// Enrichment metadata and contextual additions
+    pub enrichment: EnrichmentMetadata,
+    /// When this enrichment was performed
+    pub enriched_at: DateTime<Utc>,
+}
+
+/// Enrichment metadata containing contextual additions to the record
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EnrichmentMetadata {
+    /// The city or municipal area this record belongs to
+    pub city: Option<String>,
+    /// District or neighborhood within the city
+    pub district: Option<String>,
+    /// Administrative region (county, state, etc.)
+    pub region: Option<String>,
+    /// Spatial bin for quick geographic lookups (e.g., "seattle_grid_42_13")
+    pub spatial_bin: Option<String>,
+    /// Tags for partitioning and routing (e.g., ["music", "nightlife", "downtown"])
+    pub tags: Vec<String>,
+    /// Computed geographical properties
+    pub geo_properties: GeoProperties,
+    /// Reference data versions used for enrichment
+    pub reference_versions: ReferenceVersions,
+    /// The enrichment strategy used
+    pub strategy: String,
+    /// Confidence in the enrichment (0.0 to 1.0)
+    pub confidence: f64,
+    /// Any warnings from the enrichment process
+    pub warnings: Vec<String>,
+}
+
+/// Geographical properties computed during enrichment
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GeoProperties {
+    /// Whether coordinates are within expected city bounds
+    pub within_city_bounds: bool,
+    /// Distance from city center in kilometers
+    pub distance_from_center_km: Option<f64>,
+    /// Population density category for the area
+    pub population_density: PopulationDensity,
+    /// Transit accessibility score (0.0 to 1.0)
+    pub transit_accessibility: Option<f64>,
+    /// Nearby landmarks or points of interest
+    pub nearby_landmarks: Vec<String>,
+}
+
+/// Population density categories for spatial enrichment
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub enum PopulationDensity {
+    /// Rural or very low density
+    Rural,
+    /// Suburban or low density
+    Suburban,
+    /// Urban or medium density
+    Urban,
+    /// Dense urban or high density
+    Dense,
+    /// Unknown density
+    Unknown,
+}
+
+/// Reference data versions used during enrichment
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReferenceVersions {
+    /// City boundaries data version
+    pub city_boundaries: Option<String>,
+    /// Administrative boundaries version
+    pub admin_boundaries: Option<String>,
+    /// Spatial grid version
+    pub spatial_grid: Option<String>,
+    /// Points of interest data version
+    pub poi_data: Option<String>,
+    /// Neighborhood boundaries version
+    #[serde(default)]
+    pub neighborhoods: Option<String>,
+}
+
+/// Trait for enriching quality-assessed records with contextual information
+pub trait Enricher {
+    /// Enrich a quality-assessed record with contextual metadata
+    fn enrich(&self, record: &QualityAssessedRecord) -> anyhow::Result<EnrichedRecord>;
+}
@@ -0,0 +1,76 @@
+//! Typed interfaces of the processing pipeline, for crates that supply their own stages.
+//!
+//! Each stage is a trait over the record type the previous stage produces:
+//! payload bytes → [`Parser`] → [`ParsedRecord`] → [`SourceNormalizer`] → [`NormalizedRecord`]
+//! → [`QualityGate`] → [`QualityAssessedRecord`] → [`Enricher`] → [`EnrichedRecord`]
+//! → [`Conflator`] → [`ConflatedRecord`]. The scraper's built-in stages implement the same
+//! traits; implementations registered through [`register`] are picked up at runtime.
+
+pub mod conflation;
+pub mod enrich;
+pub mod normalize;
+pub mod parse;
+pub mod plugins;
+pub mod quality_gate;
+
+use std::sync::Arc;
+
+pub use conflation::{ConflatedRecord, Conflator, ConflatorConfig, EntityId, EntityType, PotentialMatch};
+pub use enrich::{EnrichedRecord, Enricher};
+pub use normalize::{NormalizedEntity, NormalizedRecord, SourceNormalizer};
+pub use parse::{ParsedRecord, Parser, RecordChange};
+pub use plugins::{plugins, register, ParserConstructor, PluginRegistry};
+pub use quality_gate::{QualityAssessedRecord, QualityDecision, QualityGate};
+
+// Boxed and shared stages are stages too, so registered plugins slot in wherever a concrete
+// implementation would
+
+impl<P: Parser + ?Sized> Parser for Box<P> {
+    fn parse(&self, bytes: &[u8]) -> anyhow::Result<Vec<ParsedRecord>> {
+        (**self).parse(bytes)
+    }
+
+    fn parse_reader(&self, reader: &mut dyn std::io::BufRead) -> anyhow::Result<Vec<ParsedRecord>> {
+        (**self).parse_reader(reader)
+    }
+}
+
+impl<N: SourceNormalizer + ?Sized> SourceNormalizer for Arc<N> {
+    fn normalize(&self, record: &ParsedRecord) -> anyhow::Result<Vec<NormalizedRecord>> {
+        (**self).normalize(record)
+    }
+
+    fn source_id(&self) -> &str {
+        (**self).source_id()
+    }
+
+    fn name(&self) -> &str {
+        (**self).name()
+    }
+}
+
+impl<G: QualityGate + ?Sized> QualityGate for Arc<G> {
+    fn assess(&self, record: &NormalizedRecord) -> anyhow::Result<QualityAssessedRecord> {
+        (**self).assess(record)
+    }
+}
+
+impl<E: Enricher + ?Sized> Enricher for Arc<E> {
+    fn enrich(&self, record: &QualityAssessedRecord) -> anyhow::Result<EnrichedRecord> {
+        (**self).enrich(record)
+    }
+}
+
+impl<C: Conflator + ?Sized> Conflator for Arc<C> {
+    fn conflate(&self, record: &EnrichedRecord) -> anyhow::Result<ConflatedRecord> {
+        (**self).conflate(record)
+    }
+
+    fn find_potential_matches(&self, record: &EnrichedRecord) -> anyhow::Result<Vec<PotentialMatch>> {
+        (**self).find_potential_matches(record)
+    }
+
+    fn calculate_similarity(&self, record1: &EnrichedRecord, record2: &EnrichedRecord) -> f64 {
+        (**self).calculate_similarity(record1, record2)
+    }
+}
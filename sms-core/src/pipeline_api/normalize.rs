@@ -0,0 +1,95 @@
+// Normalize stage: parsed records in, canonical domain entities with lineage out
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::parse::{ParsedRecord, RecordChange};
+use crate::common::record_key::{RecordKey, SourceKey};
+use crate::domain::{Artist, Attribution, Event, Venue};
+
+/// A normalized record that has been converted into canonical domain shapes
+/// but retains lineage back to the source envelope
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NormalizedRecord {
+    /// The canonical domain entity extracted from the raw record
+    pub entity: NormalizedEntity,
+    /// Provenance information linking back to the source
+    pub provenance: RecordProvenance,
+    /// Normalization metadata (confidence, processing time, etc.)
+    pub normalization: NormalizationMetadata,
+}
+
+/// The canonical domain entities that can be extracted from source records
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum NormalizedEntity {
+    Event(Event),
+    Venue(Venue),
+    Artist(Artist),
+}
+
+/// Provenance information tracking the lineage of this normalized record
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecordProvenance {
+    /// The envelope ID that introduced this record
+    pub envelope_id: String,
+    /// The source system that provided the data
+    pub source_id: String,
+    /// Reference to the raw payload in the content-addressed store
+    pub payload_ref: String,
+    /// JSONPath to this specific record within the payload
+    pub record_path: String,
+    /// When this record was processed into its canonical form
+    pub normalized_at: DateTime<Utc>,
+    /// License/attribution carried over from the parsed record
+    #[serde(default)]
+    pub attribution: Option<Attribution>,
+    /// Change against the source's previous payload, carried over from the parsed record
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub change: Option<RecordChange>,
+    /// The parsed record's stable key within its source (see `delta::record_key`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub record_key: Option<String>,
+    /// Endpoint the record was fetched from, on sources with several
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub endpoint_id: Option<String>,
+    /// The source's own id for the record, carried over from the parsed record
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub external_id: Option<String>,
+}
+
+impl RecordProvenance {
+    /// Identity of the parsed record this came from, shared with the parse stage
+    pub fn key(&self) -> RecordKey {
+        RecordKey::new(&self.source_id, &self.envelope_id, &self.record_path)
+    }
+
+    /// The parsed record's key within its source across fetches, when it was given one
+    pub fn source_key(&self) -> Option<SourceKey> {
+        self.record_key.as_deref().map(|key| SourceKey::new(&self.source_id, key))
+    }
+}
+
+/// Metadata about the normalization process
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NormalizationMetadata {
+    /// Confidence level in the normalization (0.0 to 1.0)
+    pub confidence: f64,
+    /// Any warnings or notes from the normalization process
+    pub warnings: Vec<String>,
+    /// Whether coordinates were geocoded from an address
+    pub geocoded: bool,
+    /// The normalization strategy used
+    pub strategy: String,
+}
+
+/// Base trait for source-specific normalizers
+pub trait SourceNormalizer: Send + Sync {
+    /// Normalize a parsed record into normalized entities
+    fn normalize(&self, record: &ParsedRecord) -> anyhow::Result<Vec<NormalizedRecord>>;
+    
+    /// Get the source ID this normalizer handles
+    fn source_id(&self) -> &str;
+    
+    /// Get a human-readable name for this normalizer
+    fn name(&self) -> &str;
+}
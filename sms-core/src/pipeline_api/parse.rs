@@ -0,0 +1,59 @@
+// Parse stage: payload bytes in, one JSON record per event (or venue, artist) out
+
+use serde::{Deserialize, Serialize};
+
+use crate::common::record_key::RecordKey;
+use crate::domain::Attribution;
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ParsedRecord {
+    pub source_id: String,
+    pub envelope_id: String,
+    pub payload_ref: String,
+    pub record_path: String,
+    pub record: serde_json::Value,
+    /// License/attribution of the source, stamped on after parsing
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub attribution: Option<Attribution>,
+    /// How this record compares with the source's previous payload, once diffed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub change: Option<RecordChange>,
+    /// Endpoint of a multi-endpoint source whose payload this record came from
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub endpoint_id: Option<String>,
+    /// The source's own stable id for the event (a Wix or ticketing id, or one taken from
+    /// the listing's detail URL), when the payload has one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub external_id: Option<String>,
+}
+
+impl ParsedRecord {
+    /// Identity of this record in every later stage
+    pub fn key(&self) -> RecordKey {
+        RecordKey::new(&self.source_id, &self.envelope_id, &self.record_path)
+    }
+}
+
+/// A record's status relative to the same source's previous payload
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RecordChange {
+    Added,
+    Changed,
+    Unchanged,
+    /// In the previous payload but missing from this one
+    Removed,
+}
+
+pub trait Parser {
+    fn parse(&self, bytes: &[u8]) -> anyhow::Result<Vec<ParsedRecord>>;
+
+    /// Parse a payload as it is read. Parsers that can work incrementally override this
+    /// so a large payload is never held whole alongside what's parsed from it; the
+    /// default reads everything and calls [`Parser::parse`].
+    fn parse_reader(&self, reader: &mut dyn std::io::BufRead) -> anyhow::Result<Vec<ParsedRecord>> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes)?;
+        self.parse(&bytes)
+    }
+}
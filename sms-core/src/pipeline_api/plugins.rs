@@ -0,0 +1,104 @@
+// Runtime registry for stages supplied by other crates
+
+use std::collections::HashMap;
+use std::sync::{Arc, OnceLock, RwLock, RwLockReadGuard};
+
+use super::conflation::Conflator;
+use super::enrich::Enricher;
+use super::normalize::SourceNormalizer;
+use super::parse::Parser;
+use super::quality_gate::QualityGate;
+
+/// Builds a parser for one payload from its source id, envelope id and payload ref
+pub type ParserConstructor = Arc<dyn Fn(&str, &str, &str) -> Box<dyn Parser + Send> + Send + Sync>;
+
+/// Stages registered by extension crates. The scraper consults it when it picks a stage:
+/// registered parsers serve parse plans it doesn't know, registered normalizers take over
+/// their source, and a registered quality gate, enricher or conflator replaces the default.
+#[derive(Default)]
+pub struct PluginRegistry {
+    parsers: HashMap<String, ParserConstructor>,
+    normalizers: HashMap<String, Arc<dyn SourceNormalizer>>,
+    quality_gate: Option<Arc<dyn QualityGate + Send + Sync>>,
+    enricher: Option<Arc<dyn Enricher + Send + Sync>>,
+    conflator: Option<Arc<dyn Conflator + Send + Sync>>,
+}
+
+impl PluginRegistry {
+    /// Serve `parse_plan` (e.g. `parse_plan:my_venue_v1`) with parsers built by `constructor`
+    pub fn register_parser(
+        &mut self,
+        parse_plan: &str,
+        constructor: impl Fn(&str, &str, &str) -> Box<dyn Parser + Send> + Send + Sync + 'static,
+    ) {
+        self.parsers.insert(parse_plan.to_string(), Arc::new(constructor));
+    }
+
+    /// Normalize the records of `normalizer.source_id()` with `normalizer`
+    pub fn register_normalizer(&mut self, normalizer: impl SourceNormalizer + 'static) {
+        self.normalizers.insert(normalizer.source_id().to_string(), Arc::new(normalizer));
+    }
+
+    pub fn set_quality_gate(&mut self, gate: impl QualityGate + Send + Sync + 'static) {
+        self.quality_gate = Some(Arc::new(gate));
+    }
+
+    pub fn set_enricher(&mut self, enricher: impl Enricher + Send + Sync + 'static) {
+        self.enricher = Some(Arc::new(enricher));
+    }
+
+    pub fn set_conflator(&mut self, conflator: impl Conflator + Send + Sync + 'static) {
+        self.conflator = Some(Arc::new(conflator));
+    }
+
+    /// A parser for one payload under `parse_plan`, if one is registered
+    pub fn parser(&self, parse_plan: &str, source_id: &str, envelope_id: &str, payload_ref: &str) -> Option<Box<dyn Parser + Send>> {
+        self.parsers.get(parse_plan).map(|constructor| constructor(source_id, envelope_id, payload_ref))
+    }
+
+    pub fn has_parser(&self, parse_plan: &str) -> bool {
+        self.parsers.contains_key(parse_plan)
+    }
+
+    /// Registered normalizers by source id
+    pub fn normalizers(&self) -> impl Iterator<Item = (&str, Arc<dyn SourceNormalizer>)> + '_ {
+        self.normalizers.iter().map(|(source_id, n)| (source_id.as_str(), n.clone()))
+    }
+
+    pub fn quality_gate(&self) -> Option<Arc<dyn QualityGate + Send + Sync>> {
+        self.quality_gate.clone()
+    }
+
+    pub fn enricher(&self) -> Option<Arc<dyn Enricher + Send + Sync>> {
+        self.enricher.clone()
+    }
+
+    pub fn conflator(&self) -> Option<Arc<dyn Conflator + Send + Sync>> {
+        self.conflator.clone()
+    }
+}
+
+fn registry() -> &'static RwLock<PluginRegistry> {
+    static PLUGINS: OnceLock<RwLock<PluginRegistry>> = OnceLock::new();
+    PLUGINS.get_or_init(Default::default)
+}
+
+/// Register stages, typically once at startup before the pipeline runs:
+///
+/// ```ignore
+/// sms_core::pipeline_api::register(|plugins| {
+///     plugins.register_parser("parse_plan:my_venue_v1", |source, envelope, payload_ref| {
+///         Box::new(MyVenueParser::new(source, envelope, payload_ref))
+///     });
+///     plugins.register_normalizer(MyVenueNormalizer);
+/// });
+/// ```
+pub fn register(f: impl FnOnce(&mut PluginRegistry)) {
+    let mut plugins = registry().write().unwrap_or_else(|poisoned| poisoned.into_inner());
+    f(&mut plugins);
+}
+
+/// The registered stages
+pub fn plugins() -> RwLockReadGuard<'static, PluginRegistry> {
+    registry().read().unwrap_or_else(|poisoned| poisoned.into_inner())
+}
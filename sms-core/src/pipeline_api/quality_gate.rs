@@ -0,0 +1,96 @@
+// Quality gate stage: each normalized record is accepted, accepted with warnings or quarantined
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::normalize::NormalizedRecord;
+
+/// A quality-assessed record that has passed through the Quality Gate checkpoint
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QualityAssessedRecord {
+    /// The original normalized record
+    pub normalized_record: NormalizedRecord,
+    /// The quality assessment result
+    pub quality_assessment: QualityAssessment,
+    /// When this quality assessment was performed
+    pub assessed_at: DateTime<Utc>,
+}
+
+/// Quality assessment result from the Quality Gate
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QualityAssessment {
+    /// The quality gate decision
+    pub decision: QualityDecision,
+    /// Overall quality score (0.0 to 1.0)
+    pub quality_score: f64,
+    /// Specific quality issues found
+    pub issues: Vec<QualityIssue>,
+    /// The quality rule set version used
+    pub rule_version: String,
+}
+
+/// Quality Gate decision for a record
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub enum QualityDecision {
+    /// Record meets quality standards and proceeds to next stage
+    Accept,
+    /// Record has quality concerns but proceeds with warnings
+    AcceptWithWarnings,
+    /// Record fails quality checks and is quarantined for review
+    Quarantine,
+}
+
+/// Individual quality issue found during assessment
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QualityIssue {
+    /// The type of quality issue
+    pub issue_type: QualityIssueType,
+    /// Severity level of the issue
+    pub severity: QualitySeverity,
+    /// Human-readable description of the issue
+    pub description: String,
+    /// Field or attribute that triggered this issue
+    pub field: Option<String>,
+    /// Expected or suggested value
+    pub suggestion: Option<String>,
+}
+
+/// Types of quality issues that can be detected
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum QualityIssueType {
+    /// Missing required data
+    MissingData,
+    /// Invalid format or structure
+    InvalidFormat,
+    /// Data outside expected ranges
+    OutOfRange,
+    /// Suspicious or anomalous values
+    SuspiciousValue,
+    /// Incomplete coordinate information
+    IncompleteGeography,
+    /// Date/time inconsistencies
+    TemporalInconsistency,
+    /// Confidence below threshold
+    LowConfidence,
+    /// Duplicate detection concerns
+    DuplicationConcern,
+}
+
+/// Severity levels for quality issues
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, PartialOrd)]
+pub enum QualitySeverity {
+    /// Minor issue, record can proceed
+    Info,
+    /// Notable issue worth flagging
+    Warning,
+    /// Significant issue requiring attention
+    Error,
+    /// Critical issue requiring quarantine
+    Critical,
+}
+
+/// Trait for implementing Quality Gate assessment logic
+pub trait QualityGate {
+    /// Assess the quality of a normalized record
+    fn assess(&self, record: &NormalizedRecord) -> anyhow::Result<QualityAssessedRecord>;
+}
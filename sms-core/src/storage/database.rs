@@ -3,7 +3,13 @@ use super::traits::Storage;
 #[cfg(feature = "db")]
 use crate::common::error::{Result, ScraperError};
 #[cfg(feature = "db")]
-use crate::database::DatabaseManager;
+use crate::database::{DatabaseManager, GraphWrite};
+#[cfg(feature = "db")]
+use super::traits::{BatchWriteStats, WriteBatch};
+#[cfg(feature = "db")]
+use crate::common::geo::GeoBounds;
+#[cfg(feature = "db")]
+use crate::common::fuzzy::rank_by_name;
 #[cfg(feature = "db")]
 use crate::domain::*;
 #[cfg(feature = "db")]
@@ -11,13 +17,17 @@ use async_trait::async_trait;
 #[cfg(feature = "db")]
 use chrono::NaiveDate;
 #[cfg(feature = "db")]
+use std::collections::HashMap;
+#[cfg(feature = "db")]
 use std::sync::Arc;
 #[cfg(feature = "db")]
-use tracing::{debug, info};
+use tracing::{debug, info, warn};
 #[cfg(feature = "db")]
 use uuid::Uuid;
+
+/// How many times a conflicting batch transaction is attempted before giving up
 #[cfg(feature = "db")]
-use libsql::Value;
+const BATCH_WRITE_ATTEMPTS: u32 = 3;
 
 /// Database storage implementation using Turso/libSQL with nodes and edges schema
 #[cfg(feature = "db")]
@@ -36,6 +46,14 @@ impl DatabaseStorage {
         })
     }
 
+    /// Storage over a migrated SQLite file at `path`; each instance opens its own connection
+    #[cfg(test)]
+    async fn open_local(path: &std::path::Path) -> Result<Self> {
+        let db_manager = DatabaseManager::open_local(path).await?;
+        db_manager.run_migrations().await?;
+        Ok(Self { db: Arc::new(db_manager) })
+    }
+
     /// Convert venue to node data
     fn venue_to_node_data(venue: &Venue) -> Result<String> {
         serde_json::to_string(venue).map_err(|e| ScraperError::Database {
@@ -129,12 +147,85 @@ impl DatabaseStorage {
         })
     }
 
+    /// Node and edge writes for an event, mirroring `create_event` (and `update_event`
+    /// when `replace_hosts`, which drops the event's previous venue edge first)
+    fn event_writes(event: &Event, replace_hosts: bool) -> Result<Vec<GraphWrite>> {
+        let id = event.id.ok_or_else(|| ScraperError::Api {
+            message: "Cannot write event without ID".to_string(),
+        })?.to_string();
+        let mut writes = vec![GraphWrite::Node { id: id.clone(), label: "event", data: Self::event_to_node_data(event)? }];
+        if replace_hosts {
+            writes.push(GraphWrite::DeleteEdgesTo { target_id: id.clone(), relation: "hosts" });
+        }
+        if event.venue_id != Uuid::nil() {
+            writes.push(GraphWrite::Edge {
+                id: Uuid::new_v4().to_string(),
+                source_id: event.venue_id.to_string(),
+                target_id: id.clone(),
+                relation: "hosts",
+                data: None,
+            });
+        }
+        for artist_id in event.artist_ids.iter().filter(|a| **a != Uuid::nil()) {
+            writes.push(GraphWrite::Edge {
+                id: Uuid::new_v4().to_string(),
+                source_id: artist_id.to_string(),
+                target_id: id.clone(),
+                relation: "performs_at",
+                data: None,
+            });
+        }
+        Ok(writes)
+    }
+
+    /// Convert event series to node data
+    fn event_series_to_node_data(series: &EventSeries) -> Result<String> {
+        serde_json::to_string(series).map_err(|e| ScraperError::Database {
+            message: format!("Failed to serialize event series: {e}"),
+        })
+    }
+
+    /// Convert node data to event series
+    fn node_data_to_event_series(id: &str, data: &str) -> Result<EventSeries> {
+        let mut series: EventSeries = serde_json::from_str(data).map_err(|e| ScraperError::Database {
+            message: format!("Failed to deserialize event series: {e}"),
+        })?;
+        series.id = Some(Uuid::parse_str(id).map_err(|e| ScraperError::Database {
+            message: format!("Invalid event series UUID: {e}"),
+        })?);
+        Ok(series)
+    }
+
     /// Convert process record to node data
     fn process_record_to_node_data(record: &ProcessRecord) -> Result<String> {
         serde_json::to_string(record).map_err(|e| ScraperError::Database {
             message: format!("Failed to serialize process record: {e}"),
         })
     }
+
+    /// Events reached by `relation` edges from each of `source_ids`, in date order, fetched
+    /// in one query; sources with none map to an empty list
+    async fn get_events_by_edge_source(&self, relation: &str, source_ids: Vec<Uuid>) -> Result<HashMap<Uuid, Vec<Event>>> {
+        let ids: Vec<String> = source_ids.iter().map(Uuid::to_string).collect();
+        let targets = self.db.get_edge_targets(relation, &ids).await?;
+
+        let mut by_source: HashMap<Uuid, Vec<Event>> = source_ids.into_iter().map(|id| (id, Vec::new())).collect();
+        for (source_id, (id, label, data)) in targets {
+            if label != "event" {
+                continue;
+            }
+            let source_id = Uuid::parse_str(&source_id).map_err(|e| ScraperError::Database {
+                message: format!("Invalid edge source UUID: {e}"),
+            })?;
+            if let Some(events) = by_source.get_mut(&source_id) {
+                events.push(Self::node_data_to_event(&id, &data)?);
+            }
+        }
+        for events in by_source.values_mut() {
+            events.sort_by_key(|a| a.event_day);
+        }
+        Ok(by_source)
+    }
 }
 
 #[cfg(feature = "db")]
@@ -167,14 +258,33 @@ impl Storage for DatabaseStorage {
                 message: format!("Failed to query venues: {e}"),
             })?;
 
+        // A venue's own name wins over another venue's alias
+        let mut aliased = None;
         for (id, _label, data) in venues_data.into_iter() {
             let venue = Self::node_data_to_venue(&id, &data)?;
             if venue.name.to_lowercase() == name.to_lowercase() {
                 return Ok(Some(venue));
             }
+            if aliased.is_none() && venue.answers_to(name) {
+                aliased = Some(venue);
+            }
         }
 
-        Ok(None)
+        Ok(aliased)
+    }
+
+    async fn delete_venue(&self, venue_id: Uuid) -> Result<()> {
+        debug!("Deleting venue with ID: {}", venue_id);
+
+        // Deleting the node drops its edges with it
+        self.db
+            .delete_node(&venue_id.to_string())
+            .await
+            .map_err(|e| ScraperError::Database {
+                message: format!("Failed to delete venue node: {e}"),
+            })?;
+
+        Ok(())
     }
 
     async fn create_artist(&self, artist: &mut Artist) -> Result<()> {
@@ -195,6 +305,17 @@ impl Storage for DatabaseStorage {
         Ok(())
     }
 
+    async fn create_artist_if_slug_free(&self, artist: &mut Artist) -> Result<bool> {
+        let id = artist.id.unwrap_or_else(Uuid::new_v4);
+        let node_data = Self::artist_to_node_data(&Artist { id: Some(id), ..artist.clone() })?;
+        let created = self.db.create_artist_node_if_slug_free(&id.to_string(), &artist.name_slug, &node_data).await?;
+        if created {
+            artist.id = Some(id);
+            info!("Created artist: {} with id {}", artist.name, id);
+        }
+        Ok(created)
+    }
+
     async fn get_artist_by_name(&self, name: &str) -> Result<Option<Artist>> {
         let artists_data = self
             .db
@@ -204,14 +325,19 @@ impl Storage for DatabaseStorage {
                 message: format!("Failed to query artists: {e}"),
             })?;
 
+        // A artist's own name wins over another artist's alias
+        let mut aliased = None;
         for (id, _label, data) in artists_data.into_iter() {
             let artist = Self::node_data_to_artist(&id, &data)?;
             if artist.name.to_lowercase() == name.to_lowercase() {
                 return Ok(Some(artist));
             }
+            if aliased.is_none() && artist.answers_to(name) {
+                aliased = Some(artist);
+            }
         }
 
-        Ok(None)
+        Ok(aliased)
     }
 
     async fn get_artist_by_slug(&self, slug: &str) -> Result<Option<Artist>> {
@@ -231,6 +357,37 @@ impl Storage for DatabaseStorage {
         Ok(None)
     }
 
+    async fn update_artist(&self, artist: &Artist) -> Result<()> {
+        let artist_id = artist.id.ok_or_else(|| ScraperError::Api {
+            message: "Cannot update artist without ID".to_string(),
+        })?;
+        let node_data = Self::artist_to_node_data(artist)?;
+
+        self.db
+            .create_node(&artist_id.to_string(), "artist", &node_data)
+            .await
+            .map_err(|e| ScraperError::Database {
+                message: format!("Failed to update artist node: {e}"),
+            })?;
+
+        debug!("Updated artist: {} with id {}", artist.name, artist_id);
+        Ok(())
+    }
+
+    async fn delete_artist(&self, artist_id: Uuid) -> Result<()> {
+        debug!("Deleting artist with ID: {}", artist_id);
+
+        // Deleting the node drops its edges with it
+        self.db
+            .delete_node(&artist_id.to_string())
+            .await
+            .map_err(|e| ScraperError::Database {
+                message: format!("Failed to delete artist node: {e}"),
+            })?;
+
+        Ok(())
+    }
+
     async fn create_event(&self, event: &mut Event) -> Result<()> {
         debug!("[DATABASE] create_event called for: {}", event.title);
         
@@ -359,6 +516,28 @@ impl Storage for DatabaseStorage {
             }
         }
 
+        // The event may have moved venues (e.g. off a provisional placeholder), so replace its hosts edge
+        self.db
+            .delete_edges_to(&event_id.to_string(), "hosts")
+            .await
+            .map_err(|e| ScraperError::Database {
+                message: format!("Failed to clear venue-event edges: {e}"),
+            })?;
+        if event.venue_id != Uuid::nil() {
+            self.db
+                .create_edge(
+                    &Uuid::new_v4().to_string(),
+                    &event.venue_id.to_string(),
+                    &event_id.to_string(),
+                    "hosts",
+                    None,
+                )
+                .await
+                .map_err(|e| ScraperError::Database {
+                    message: format!("Failed to upsert venue-event edge: {e}"),
+                })?;
+        }
+
         // Delete existing artist-event edges and recreate them
         // This ensures we have the correct artist linkages
         // Note: In a production system, you'd want to diff and only update changed edges
@@ -372,7 +551,7 @@ impl Storage for DatabaseStorage {
             })?;
         
         // Delete old performs_at edges (where event is target)
-        for (edge_id, source_id, target_id, relation, _) in existing_edges {
+        for (edge_id, _source_id, target_id, relation, _) in existing_edges {
             if relation == "performs_at" && target_id == event_id.to_string() {
                 // Note: The database doesn't have a delete_edge method, so we can't delete
                 // Instead, we'll just create new edges (the upsert will overwrite)
@@ -447,7 +626,7 @@ impl Storage for DatabaseStorage {
         }
 
         // Sort by event_day to process chronologically
-        filtered_data.sort_by(|a, b| a.event_day.cmp(&b.event_day));
+        filtered_data.sort_by_key(|a| a.event_day);
         Ok(filtered_data)
     }
 
@@ -477,7 +656,7 @@ impl Storage for DatabaseStorage {
         }
 
         // Sort by event_day to process chronologically
-        filtered_data.sort_by(|a, b| a.event_day.cmp(&b.event_day));
+        filtered_data.sort_by_key(|a| a.event_day);
         Ok(filtered_data)
     }
 
@@ -506,8 +685,13 @@ impl Storage for DatabaseStorage {
         Ok(())
     }
 
+    async fn get_raw_data_payload_refs(&self) -> Result<Vec<String>> {
+        self.db.get_raw_data_payload_refs().await
+    }
+
     async fn create_process_run(&self, run: &mut ProcessRun) -> Result<()> {
-        let id = Uuid::new_v4();
+        // Respect existing ID if provided, e.g. the pipeline run's own id; otherwise generate
+        let id = run.id.unwrap_or_else(Uuid::new_v4);
         run.id = Some(id);
 
         let node_data = Self::process_run_to_node_data(run)?;
@@ -574,6 +758,85 @@ impl Storage for DatabaseStorage {
         Ok(())
     }
 
+    async fn get_process_run(&self, run_id: Uuid) -> Result<Option<ProcessRun>> {
+        let Some((_id, label, data)) = self.db.get_node(&run_id.to_string()).await.map_err(|e| {
+            ScraperError::Database { message: format!("Failed to get process run node: {e}") }
+        })?
+        else {
+            return Ok(None);
+        };
+        if label != "process_run" {
+            return Ok(None);
+        }
+        let mut run: ProcessRun = serde_json::from_str(&data).map_err(|e| ScraperError::Database {
+            message: format!("Failed to deserialize process run: {e}"),
+        })?;
+        run.id = Some(run_id);
+        Ok(Some(run))
+    }
+
+    async fn get_process_records_for_run(&self, run_id: Uuid) -> Result<Vec<ProcessRecord>> {
+        let nodes = self
+            .db
+            .get_nodes_by_label("process_record")
+            .await
+            .map_err(|e| ScraperError::Database {
+                message: format!("Failed to get process record nodes: {e}"),
+            })?;
+        let mut records = Vec::new();
+        for (id, _label, data) in nodes {
+            match serde_json::from_str::<ProcessRecord>(&data) {
+                Ok(mut record) if record.process_run_id == run_id => {
+                    record.id = Uuid::parse_str(&id).ok().or(record.id);
+                    records.push(record);
+                }
+                Ok(_) => {}
+                Err(e) => warn!("Skipping unreadable process record {}: {}", id, e),
+            }
+        }
+        records.sort_by_key(|r| r.created_at);
+        Ok(records)
+    }
+
+    async fn write_batch(&self, batch: &mut WriteBatch) -> Result<BatchWriteStats> {
+        let mut writes = Vec::new();
+        for venue in &mut batch.venues {
+            let id = *venue.id.get_or_insert_with(Uuid::new_v4);
+            writes.push(GraphWrite::Node { id: id.to_string(), label: "venue", data: Self::venue_to_node_data(venue)? });
+        }
+        for artist in &mut batch.artists {
+            let id = *artist.id.get_or_insert_with(Uuid::new_v4);
+            writes.push(GraphWrite::Node { id: id.to_string(), label: "artist", data: Self::artist_to_node_data(artist)? });
+        }
+        for event in &mut batch.events {
+            event.id.get_or_insert_with(Uuid::new_v4);
+            writes.extend(Self::event_writes(event, false)?);
+        }
+        for event in &batch.event_updates {
+            writes.extend(Self::event_writes(event, true)?);
+        }
+        for record in &mut batch.process_records {
+            let id = Uuid::new_v4();
+            record.id = Some(id);
+            writes.push(GraphWrite::Node {
+                id: id.to_string(),
+                label: "process_record",
+                data: Self::process_record_to_node_data(record)?,
+            });
+            writes.push(GraphWrite::Edge {
+                id: Uuid::new_v4().to_string(),
+                source_id: record.process_run_id.to_string(),
+                target_id: id.to_string(),
+                relation: "has_record",
+                data: None,
+            });
+        }
+
+        let attempts = self.db.write_batch(&writes, BATCH_WRITE_ATTEMPTS).await?;
+        info!("Wrote batch of {} entities ({} statements) in {} attempt(s)", batch.len(), writes.len(), attempts);
+        Ok(BatchWriteStats { entities: batch.len(), attempts })
+    }
+
     // Additional GraphQL query methods
     async fn get_venue_by_id(&self, venue_id: Uuid) -> Result<Option<Venue>> {
         if let Some((id, _label, data)) = self
@@ -650,6 +913,17 @@ impl Storage for DatabaseStorage {
         Ok(venues.get(offset..end).unwrap_or(&[]).to_vec())
     }
 
+    async fn get_venues_in_bounds(&self, bounds: GeoBounds) -> Result<Vec<Venue>> {
+        let venues_data = self.db.get_venue_nodes_in_bounds(bounds).await?;
+
+        let mut venues = Vec::new();
+        for (id, _label, data) in venues_data.into_iter() {
+            venues.push(Self::node_data_to_venue(&id, &data)?);
+        }
+        debug!("Found {} venues in bounds {:?}", venues.len(), bounds);
+        Ok(venues)
+    }
+
     async fn get_all_artists(
         &self,
         limit: Option<usize>,
@@ -698,7 +972,7 @@ impl Storage for DatabaseStorage {
             events.push(Self::node_data_to_event(&id, &data)?);
         }
 
-        events.sort_by(|a, b| a.event_day.cmp(&b.event_day));
+        events.sort_by_key(|a| a.event_day);
 
         let offset = offset.unwrap_or(0);
         let end = if let Some(limit) = limit {
@@ -738,10 +1012,23 @@ impl Storage for DatabaseStorage {
             }
         }
 
-        venue_events.sort_by(|a, b| a.event_day.cmp(&b.event_day));
+        venue_events.sort_by_key(|a| a.event_day);
         Ok(venue_events)
     }
 
+    async fn count_upcoming_events_by_venue(&self, from: NaiveDate) -> Result<HashMap<Uuid, u64>> {
+        let counts = self.db.count_hosted_events_since(from).await?;
+
+        let mut by_venue = HashMap::new();
+        for (venue_id, count) in counts {
+            let venue_id = Uuid::parse_str(&venue_id).map_err(|e| ScraperError::Database {
+                message: format!("Invalid venue UUID: {e}"),
+            })?;
+            by_venue.insert(venue_id, count);
+        }
+        Ok(by_venue)
+    }
+
     async fn get_events_by_artist_id(&self, artist_id: Uuid) -> Result<Vec<Event>> {
         // Use graph edges to efficiently find events where this artist performs
         let edges = self
@@ -770,7 +1057,7 @@ impl Storage for DatabaseStorage {
             }
         }
 
-        artist_events.sort_by(|a, b| a.event_day.cmp(&b.event_day));
+        artist_events.sort_by_key(|a| a.event_day);
         Ok(artist_events)
     }
 
@@ -795,7 +1082,7 @@ impl Storage for DatabaseStorage {
             }
         }
 
-        filtered_events.sort_by(|a, b| a.event_day.cmp(&b.event_day));
+        filtered_events.sort_by_key(|a| a.event_day);
         Ok(filtered_events)
     }
 
@@ -808,57 +1095,97 @@ impl Storage for DatabaseStorage {
                 message: format!("Failed to query artists: {e}"),
             })?;
 
-        let query_lower = query.to_lowercase();
-        let mut matching_artists = Vec::new();
+        let mut artists = Vec::new();
         for (id, _label, data) in artists_data.into_iter() {
-            let artist = Self::node_data_to_artist(&id, &data)?;
-            if artist.name.to_lowercase().contains(&query_lower) {
-                matching_artists.push(artist);
-            }
+            artists.push(Self::node_data_to_artist(&id, &data)?);
         }
 
-        matching_artists.sort_by(|a, b| a.name.cmp(&b.name));
-        Ok(matching_artists)
+        Ok(rank_by_name(query, artists, |a| &a.name))
+    }
+
+    async fn search_venues(&self, query: &str) -> Result<Vec<Venue>> {
+        let venues_data = self
+            .db
+            .get_nodes_by_label("venue")
+            .await
+            .map_err(|e| ScraperError::Database {
+                message: format!("Failed to query venues: {e}"),
+            })?;
+
+        let mut venues = Vec::new();
+        for (id, _label, data) in venues_data.into_iter() {
+            venues.push(Self::node_data_to_venue(&id, &data)?);
+        }
+
+        Ok(rank_by_name(query, venues, |v| &v.name))
     }
 
     async fn get_venues_by_ids(&self, venue_ids: Vec<Uuid>) -> Result<Vec<Venue>> {
+        let ids: Vec<String> = venue_ids.iter().map(Uuid::to_string).collect();
         let mut venues = Vec::new();
-        
-        for venue_id in venue_ids {
-            if let Some((id, _label, data)) = self
-                .db
-                .get_node(&venue_id.to_string())
-                .await
-                .map_err(|e| ScraperError::Database {
-                    message: format!("Failed to get venue node: {e}"),
-                })?
-            {
-                venues.push(Self::node_data_to_venue(&id, &data)?);
-            }
+        for (id, _label, data) in self.db.get_nodes_by_ids("venue", &ids).await? {
+            venues.push(Self::node_data_to_venue(&id, &data)?);
         }
-        
         Ok(venues)
     }
 
     async fn get_artists_by_ids(&self, artist_ids: Vec<Uuid>) -> Result<Vec<Artist>> {
+        let ids: Vec<String> = artist_ids.iter().map(Uuid::to_string).collect();
         let mut artists = Vec::new();
-        
-        for artist_id in artist_ids {
-            if let Some((id, _label, data)) = self
-                .db
-                .get_node(&artist_id.to_string())
-                .await
-                .map_err(|e| ScraperError::Database {
-                    message: format!("Failed to get artist node: {e}"),
-                })?
-            {
-                artists.push(Self::node_data_to_artist(&id, &data)?);
-            }
+        for (id, _label, data) in self.db.get_nodes_by_ids("artist", &ids).await? {
+            artists.push(Self::node_data_to_artist(&id, &data)?);
         }
-        
         Ok(artists)
     }
 
+    async fn get_events_by_ids(&self, event_ids: Vec<Uuid>) -> Result<Vec<Event>> {
+        let ids: Vec<String> = event_ids.iter().map(Uuid::to_string).collect();
+        let mut events = Vec::new();
+        for (id, _label, data) in self.db.get_nodes_by_ids("event", &ids).await? {
+            events.push(Self::node_data_to_event(&id, &data)?);
+        }
+        Ok(events)
+    }
+
+    async fn get_event_series_by_ids(&self, series_ids: Vec<Uuid>) -> Result<Vec<EventSeries>> {
+        let ids: Vec<String> = series_ids.iter().map(Uuid::to_string).collect();
+        let mut series = Vec::new();
+        for (id, _label, data) in self.db.get_nodes_by_ids("event_series", &ids).await? {
+            series.push(Self::node_data_to_event_series(&id, &data)?);
+        }
+        Ok(series)
+    }
+
+    async fn get_events_by_venue_ids(&self, venue_ids: Vec<Uuid>) -> Result<HashMap<Uuid, Vec<Event>>> {
+        self.get_events_by_edge_source("hosts", venue_ids).await
+    }
+
+    async fn get_events_by_artist_ids(&self, artist_ids: Vec<Uuid>) -> Result<HashMap<Uuid, Vec<Event>>> {
+        self.get_events_by_edge_source("performs_at", artist_ids).await
+    }
+
+    async fn get_event_series_by_venue_ids(&self, venue_ids: Vec<Uuid>) -> Result<HashMap<Uuid, Vec<EventSeries>>> {
+        let series_data = self
+            .db
+            .get_nodes_by_label("event_series")
+            .await
+            .map_err(|e| ScraperError::Database {
+                message: format!("Failed to query event series: {e}"),
+            })?;
+
+        let mut by_venue: HashMap<Uuid, Vec<EventSeries>> = venue_ids.into_iter().map(|id| (id, Vec::new())).collect();
+        for (id, _label, data) in series_data.into_iter() {
+            let s = Self::node_data_to_event_series(&id, &data)?;
+            if let Some(venue_series) = by_venue.get_mut(&s.venue_id) {
+                venue_series.push(s);
+            }
+        }
+        for venue_series in by_venue.values_mut() {
+            venue_series.sort_by_key(|s| (s.weekday.num_days_from_monday(), s.title.clone()));
+        }
+        Ok(by_venue)
+    }
+
     async fn delete_event(&self, event_id: Uuid) -> Result<()> {
         debug!("Deleting event with ID: {}", event_id);
         
@@ -872,4 +1199,86 @@ impl Storage for DatabaseStorage {
             
         Ok(())
     }
+
+    async fn upsert_event_series(&self, series: &mut EventSeries) -> Result<()> {
+        let id = series.id.unwrap_or_else(Uuid::new_v4);
+        series.id = Some(id);
+
+        let node_data = Self::event_series_to_node_data(series)?;
+
+        self.db
+            .create_node(&id.to_string(), "event_series", &node_data)
+            .await
+            .map_err(|e| ScraperError::Database {
+                message: format!("Failed to upsert event series node: {e}"),
+            })?;
+
+        debug!("Upserted event series: {} with id {}", series.title, id);
+        Ok(())
+    }
+
+    async fn get_event_series_by_id(&self, series_id: Uuid) -> Result<Option<EventSeries>> {
+        match self
+            .db
+            .get_node(&series_id.to_string())
+            .await
+            .map_err(|e| ScraperError::Database {
+                message: format!("Failed to get event series node: {e}"),
+            })? {
+            Some((id, label, data)) if label == "event_series" => Ok(Some(Self::node_data_to_event_series(&id, &data)?)),
+            _ => Ok(None),
+        }
+    }
+
+    async fn get_event_series_by_venue_id(&self, venue_id: Uuid) -> Result<Vec<EventSeries>> {
+        let series_data = self
+            .db
+            .get_nodes_by_label("event_series")
+            .await
+            .map_err(|e| ScraperError::Database {
+                message: format!("Failed to query event series: {e}"),
+            })?;
+
+        let mut series = Vec::new();
+        for (id, _label, data) in series_data.into_iter() {
+            let s = Self::node_data_to_event_series(&id, &data)?;
+            if s.venue_id == venue_id {
+                series.push(s);
+            }
+        }
+        series.sort_by_key(|s| (s.weekday.num_days_from_monday(), s.title.clone()));
+        Ok(series)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn two_connections_cataloging_the_same_venue_write_it_once() {
+        let tmp = tempfile::tempdir().unwrap();
+        let path = tmp.path().join("sms.db");
+        let (first, second) = (DatabaseStorage::open_local(&path).await.unwrap(), DatabaseStorage::open_local(&path).await.unwrap());
+
+        // Both spellings slug to "the-band"
+        let batch = |name: &str| WriteBatch { venues: vec![Venue::fixture("Neumos")], artists: vec![Artist::fixture(name)], ..WriteBatch::new() };
+        let (mut a, mut b) = (batch("The Band"), batch("THE BAND"));
+        let (ra, rb) = tokio::join!(first.write_batch(&mut a), second.write_batch(&mut b));
+
+        // The loser is told who won, so the catalogger can restage onto it
+        let (winner, loser) = match (ra, rb) {
+            (Ok(_), Err(e)) => (a, e),
+            (Err(e), Ok(_)) => (b, e),
+            other => panic!("expected exactly one batch to be written, got {other:?}"),
+        };
+        match loser {
+            ScraperError::Duplicate { entity: "venue", existing_id, .. } => {
+                assert_eq!(existing_id, winner.venues[0].id.unwrap().to_string());
+            }
+            other => panic!("expected a duplicate venue, got {other:?}"),
+        }
+        assert_eq!(first.get_all_venues(None, None).await.unwrap().len(), 1);
+        assert_eq!(second.get_all_artists(None, None).await.unwrap().len(), 1);
+    }
 }
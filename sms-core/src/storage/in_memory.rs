@@ -1,9 +1,11 @@
-use super::traits::Storage;
+use super::traits::{BatchWriteStats, Storage, WriteBatch};
 use crate::domain::*;
 use crate::common::error::{Result, ScraperError};
+use crate::common::fuzzy::rank_by_name;
+use crate::common::geo::GeoBounds;
 use async_trait::async_trait;
 use chrono::NaiveDate;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::sync::{Arc, Mutex};
 use tracing::debug;
 use uuid::Uuid;
@@ -13,6 +15,7 @@ pub struct InMemoryStorage {
     venues: Arc<Mutex<HashMap<Uuid, Venue>>>,
     artists: Arc<Mutex<HashMap<Uuid, Artist>>>,
     events: Arc<Mutex<HashMap<Uuid, Event>>>,
+    event_series: Arc<Mutex<HashMap<Uuid, EventSeries>>>,
     raw_data: Arc<Mutex<HashMap<Uuid, RawData>>>,
     process_runs: Arc<Mutex<HashMap<Uuid, ProcessRun>>>,
     process_records: Arc<Mutex<HashMap<Uuid, ProcessRecord>>>,
@@ -30,6 +33,7 @@ impl InMemoryStorage {
             venues: Arc::new(Mutex::new(HashMap::new())),
             artists: Arc::new(Mutex::new(HashMap::new())),
             events: Arc::new(Mutex::new(HashMap::new())),
+            event_series: Arc::new(Mutex::new(HashMap::new())),
             raw_data: Arc::new(Mutex::new(HashMap::new())),
             process_runs: Arc::new(Mutex::new(HashMap::new())),
             process_records: Arc::new(Mutex::new(HashMap::new())),
@@ -40,7 +44,7 @@ impl InMemoryStorage {
 #[async_trait]
 impl Storage for InMemoryStorage {
     async fn create_venue(&self, venue: &mut Venue) -> Result<()> {
-        let id = Uuid::new_v4();
+        let id = venue.id.unwrap_or_else(Uuid::new_v4);
         venue.id = Some(id);
 
         let mut venues = self.venues.lock().unwrap();
@@ -55,12 +59,20 @@ impl Storage for InMemoryStorage {
         let venue = venues
             .values()
             .find(|v| v.name.to_lowercase() == name.to_lowercase())
+            .or_else(|| venues.values().find(|v| v.answers_to(name)))
             .cloned();
         Ok(venue)
     }
 
+    async fn delete_venue(&self, venue_id: Uuid) -> Result<()> {
+        let mut venues = self.venues.lock().unwrap();
+        venues.remove(&venue_id);
+        debug!("Deleted venue with ID: {}", venue_id);
+        Ok(())
+    }
+
     async fn create_artist(&self, artist: &mut Artist) -> Result<()> {
-        let id = Uuid::new_v4();
+        let id = artist.id.unwrap_or_else(Uuid::new_v4);
         artist.id = Some(id);
 
         let mut artists = self.artists.lock().unwrap();
@@ -70,11 +82,23 @@ impl Storage for InMemoryStorage {
         Ok(())
     }
 
+    async fn create_artist_if_slug_free(&self, artist: &mut Artist) -> Result<bool> {
+        let mut artists = self.artists.lock().unwrap();
+        if artists.values().any(|a| a.name_slug == artist.name_slug) {
+            return Ok(false);
+        }
+        let id = artist.id.unwrap_or_else(Uuid::new_v4);
+        artist.id = Some(id);
+        artists.insert(id, artist.clone());
+        Ok(true)
+    }
+
     async fn get_artist_by_name(&self, name: &str) -> Result<Option<Artist>> {
         let artists = self.artists.lock().unwrap();
         let artist = artists
             .values()
             .find(|a| a.name.to_lowercase() == name.to_lowercase())
+            .or_else(|| artists.values().find(|a| a.answers_to(name)))
             .cloned();
         Ok(artist)
     }
@@ -88,8 +112,27 @@ impl Storage for InMemoryStorage {
         Ok(artist)
     }
 
+    async fn update_artist(&self, artist: &Artist) -> Result<()> {
+        let artist_id = artist.id.ok_or_else(|| ScraperError::Api {
+            message: "Cannot update artist without ID".to_string(),
+        })?;
+
+        let mut artists = self.artists.lock().unwrap();
+        artists.insert(artist_id, artist.clone());
+
+        debug!("Updated artist: {} with id {}", artist.name, artist_id);
+        Ok(())
+    }
+
+    async fn delete_artist(&self, artist_id: Uuid) -> Result<()> {
+        let mut artists = self.artists.lock().unwrap();
+        artists.remove(&artist_id);
+        debug!("Deleted artist with ID: {}", artist_id);
+        Ok(())
+    }
+
     async fn create_event(&self, event: &mut Event) -> Result<()> {
-        let id = Uuid::new_v4();
+        let id = event.id.unwrap_or_else(Uuid::new_v4);
         event.id = Some(id);
 
         let mut events = self.events.lock().unwrap();
@@ -157,7 +200,7 @@ impl Storage for InMemoryStorage {
             .collect();
 
         // Sort by event_day for consistent processing order
-        raw_data.sort_by(|a, b| a.event_day.cmp(&b.event_day));
+        raw_data.sort_by_key(|a| a.event_day);
         Ok(raw_data)
     }
 
@@ -178,7 +221,7 @@ impl Storage for InMemoryStorage {
             .collect();
 
         // Sort by event_day for consistent processing order
-        raw_data.sort_by(|a, b| a.event_day.cmp(&b.event_day));
+        raw_data.sort_by_key(|a| a.event_day);
         Ok(raw_data)
     }
 
@@ -193,8 +236,14 @@ impl Storage for InMemoryStorage {
         Ok(())
     }
 
+    async fn get_raw_data_payload_refs(&self) -> Result<Vec<String>> {
+        let raw_data = self.raw_data.lock().unwrap();
+        let refs: HashSet<String> = raw_data.values().filter_map(|r| r.origin.as_ref()).map(|o| o.payload_ref.clone()).collect();
+        Ok(refs.into_iter().collect())
+    }
+
     async fn create_process_run(&self, run: &mut ProcessRun) -> Result<()> {
-        let id = Uuid::new_v4();
+        let id = run.id.unwrap_or_else(Uuid::new_v4);
         run.id = Some(id);
 
         let mut runs = self.process_runs.lock().unwrap();
@@ -227,6 +276,76 @@ impl Storage for InMemoryStorage {
         Ok(())
     }
 
+    async fn get_process_run(&self, run_id: Uuid) -> Result<Option<ProcessRun>> {
+        Ok(self.process_runs.lock().unwrap().get(&run_id).cloned())
+    }
+
+    async fn get_process_records_for_run(&self, run_id: Uuid) -> Result<Vec<ProcessRecord>> {
+        let mut records: Vec<ProcessRecord> = self
+            .process_records
+            .lock()
+            .unwrap()
+            .values()
+            .filter(|r| r.process_run_id == run_id)
+            .cloned()
+            .collect();
+        records.sort_by_key(|r| r.created_at);
+        Ok(records)
+    }
+
+    async fn write_batch(&self, batch: &mut WriteBatch) -> Result<BatchWriteStats> {
+        // Both maps stay locked from the duplicate check through the inserts
+        let mut venues = self.venues.lock().unwrap();
+        let mut artists = self.artists.lock().unwrap();
+        for venue in batch.venues.iter().filter(|v| !v.provisional) {
+            if venue.id.is_some_and(|id| venues.contains_key(&id)) {
+                continue;
+            }
+            let name = venue.name.to_lowercase();
+            if let Some(existing) = venues.values().find(|v| !v.provisional && v.name.to_lowercase() == name) {
+                return Err(duplicate("venue", &venue.name, existing.id));
+            }
+        }
+        for artist in &batch.artists {
+            if artist.id.is_some_and(|id| artists.contains_key(&id)) {
+                continue;
+            }
+            // Distinct artists may share a name under suffixed slugs (see `allocate_artist`)
+            if let Some(existing) = artists.values().find(|a| a.name_slug.eq_ignore_ascii_case(&artist.name_slug)) {
+                return Err(duplicate("artist", &artist.name, existing.id));
+            }
+        }
+
+        for venue in &mut batch.venues {
+            venues.insert(*venue.id.get_or_insert_with(Uuid::new_v4), venue.clone());
+        }
+        for artist in &mut batch.artists {
+            artists.insert(*artist.id.get_or_insert_with(Uuid::new_v4), artist.clone());
+        }
+        drop((venues, artists));
+
+        let mut events = self.events.lock().unwrap();
+        for event in &mut batch.events {
+            events.insert(*event.id.get_or_insert_with(Uuid::new_v4), event.clone());
+        }
+        for event in &batch.event_updates {
+            let id = event.id.ok_or_else(|| ScraperError::Api {
+                message: "Cannot update event without ID".to_string(),
+            })?;
+            events.insert(id, event.clone());
+        }
+        drop(events);
+
+        let mut records = self.process_records.lock().unwrap();
+        for record in &mut batch.process_records {
+            let id = Uuid::new_v4();
+            record.id = Some(id);
+            records.insert(id, record.clone());
+        }
+        debug!("Wrote batch of {} entities", batch.len());
+        Ok(BatchWriteStats { entities: batch.len(), attempts: 1 })
+    }
+
     // Query methods implementation
     async fn get_venue_by_id(&self, venue_id: Uuid) -> Result<Option<Venue>> {
         let venues = self.venues.lock().unwrap();
@@ -262,6 +381,15 @@ impl Storage for InMemoryStorage {
         Ok(all_venues.get(offset..end).unwrap_or(&[]).to_vec())
     }
 
+    async fn get_venues_in_bounds(&self, bounds: GeoBounds) -> Result<Vec<Venue>> {
+        let venues = self.venues.lock().unwrap();
+        Ok(venues
+            .values()
+            .filter(|v| !v.provisional && bounds.contains(v.latitude, v.longitude))
+            .cloned()
+            .collect())
+    }
+
     async fn get_all_artists(
         &self,
         limit: Option<usize>,
@@ -288,7 +416,7 @@ impl Storage for InMemoryStorage {
     ) -> Result<Vec<Event>> {
         let events = self.events.lock().unwrap();
         let mut all_events: Vec<Event> = events.values().cloned().collect();
-        all_events.sort_by(|a, b| a.event_day.cmp(&b.event_day));
+        all_events.sort_by_key(|a| a.event_day);
 
         let offset = offset.unwrap_or(0);
         let end = if let Some(limit) = limit {
@@ -307,10 +435,19 @@ impl Storage for InMemoryStorage {
             .filter(|e| e.venue_id == venue_id)
             .cloned()
             .collect();
-        venue_events.sort_by(|a, b| a.event_day.cmp(&b.event_day));
+        venue_events.sort_by_key(|a| a.event_day);
         Ok(venue_events)
     }
 
+    async fn count_upcoming_events_by_venue(&self, from: NaiveDate) -> Result<HashMap<Uuid, u64>> {
+        let events = self.events.lock().unwrap();
+        let mut counts = HashMap::new();
+        for event in events.values().filter(|e| e.show_event && e.event_day >= from) {
+            *counts.entry(event.venue_id).or_insert(0) += 1;
+        }
+        Ok(counts)
+    }
+
     async fn get_events_by_artist_id(&self, artist_id: Uuid) -> Result<Vec<Event>> {
         let events = self.events.lock().unwrap();
         let mut artist_events: Vec<Event> = events
@@ -318,7 +455,7 @@ impl Storage for InMemoryStorage {
             .filter(|e| e.artist_ids.contains(&artist_id))
             .cloned()
             .collect();
-        artist_events.sort_by(|a, b| a.event_day.cmp(&b.event_day));
+        artist_events.sort_by_key(|a| a.event_day);
         Ok(artist_events)
     }
 
@@ -333,20 +470,18 @@ impl Storage for InMemoryStorage {
             .filter(|e| e.event_day >= start_date && e.event_day <= end_date)
             .cloned()
             .collect();
-        date_events.sort_by(|a, b| a.event_day.cmp(&b.event_day));
+        date_events.sort_by_key(|a| a.event_day);
         Ok(date_events)
     }
 
     async fn search_artists(&self, query: &str) -> Result<Vec<Artist>> {
         let artists = self.artists.lock().unwrap();
-        let query_lower = query.to_lowercase();
-        let mut matching_artists: Vec<Artist> = artists
-            .values()
-            .filter(|a| a.name.to_lowercase().contains(&query_lower))
-            .cloned()
-            .collect();
-        matching_artists.sort_by(|a, b| a.name.cmp(&b.name));
-        Ok(matching_artists)
+        Ok(rank_by_name(query, artists.values().cloned().collect(), |a| &a.name))
+    }
+
+    async fn search_venues(&self, query: &str) -> Result<Vec<Venue>> {
+        let venues = self.venues.lock().unwrap();
+        Ok(rank_by_name(query, venues.values().cloned().collect(), |v| &v.name))
     }
 
     async fn get_venues_by_ids(&self, venue_ids: Vec<Uuid>) -> Result<Vec<Venue>> {
@@ -367,10 +502,135 @@ impl Storage for InMemoryStorage {
         Ok(result)
     }
 
+    async fn get_events_by_ids(&self, event_ids: Vec<Uuid>) -> Result<Vec<Event>> {
+        let events = self.events.lock().unwrap();
+        let result = event_ids
+            .into_iter()
+            .filter_map(|id| events.get(&id).cloned())
+            .collect();
+        Ok(result)
+    }
+
+    async fn get_event_series_by_ids(&self, series_ids: Vec<Uuid>) -> Result<Vec<EventSeries>> {
+        let event_series = self.event_series.lock().unwrap();
+        let result = series_ids
+            .into_iter()
+            .filter_map(|id| event_series.get(&id).cloned())
+            .collect();
+        Ok(result)
+    }
+
+    async fn get_events_by_venue_ids(&self, venue_ids: Vec<Uuid>) -> Result<HashMap<Uuid, Vec<Event>>> {
+        let events = self.events.lock().unwrap();
+        let mut by_venue: HashMap<Uuid, Vec<Event>> = venue_ids.into_iter().map(|id| (id, Vec::new())).collect();
+        for event in events.values() {
+            if let Some(venue_events) = by_venue.get_mut(&event.venue_id) {
+                venue_events.push(event.clone());
+            }
+        }
+        for venue_events in by_venue.values_mut() {
+            venue_events.sort_by_key(|a| a.event_day);
+        }
+        Ok(by_venue)
+    }
+
+    async fn get_events_by_artist_ids(&self, artist_ids: Vec<Uuid>) -> Result<HashMap<Uuid, Vec<Event>>> {
+        let events = self.events.lock().unwrap();
+        let mut by_artist: HashMap<Uuid, Vec<Event>> = artist_ids.into_iter().map(|id| (id, Vec::new())).collect();
+        for (artist_id, artist_events) in by_artist.iter_mut() {
+            artist_events.extend(events.values().filter(|e| e.artist_ids.contains(artist_id)).cloned());
+            artist_events.sort_by_key(|a| a.event_day);
+        }
+        Ok(by_artist)
+    }
+
+    async fn get_event_series_by_venue_ids(&self, venue_ids: Vec<Uuid>) -> Result<HashMap<Uuid, Vec<EventSeries>>> {
+        let event_series = self.event_series.lock().unwrap();
+        let mut by_venue: HashMap<Uuid, Vec<EventSeries>> = venue_ids.into_iter().map(|id| (id, Vec::new())).collect();
+        for series in event_series.values() {
+            if let Some(venue_series) = by_venue.get_mut(&series.venue_id) {
+                venue_series.push(series.clone());
+            }
+        }
+        for venue_series in by_venue.values_mut() {
+            venue_series.sort_by_key(|s| (s.weekday.num_days_from_monday(), s.title.clone()));
+        }
+        Ok(by_venue)
+    }
+
     async fn delete_event(&self, event_id: Uuid) -> Result<()> {
         let mut events = self.events.lock().unwrap();
         events.remove(&event_id);
         debug!("Deleted event with ID: {}", event_id);
         Ok(())
     }
+
+    async fn upsert_event_series(&self, series: &mut EventSeries) -> Result<()> {
+        let id = series.id.unwrap_or_else(Uuid::new_v4);
+        series.id = Some(id);
+
+        let mut event_series = self.event_series.lock().unwrap();
+        event_series.insert(id, series.clone());
+
+        debug!("Upserted event series: {} with id {}", series.title, id);
+        Ok(())
+    }
+
+    async fn get_event_series_by_id(&self, series_id: Uuid) -> Result<Option<EventSeries>> {
+        let event_series = self.event_series.lock().unwrap();
+        Ok(event_series.get(&series_id).cloned())
+    }
+
+    async fn get_event_series_by_venue_id(&self, venue_id: Uuid) -> Result<Vec<EventSeries>> {
+        let event_series = self.event_series.lock().unwrap();
+        let mut result: Vec<EventSeries> = event_series.values().filter(|s| s.venue_id == venue_id).cloned().collect();
+        result.sort_by_key(|s| (s.weekday.num_days_from_monday(), s.title.clone()));
+        Ok(result)
+    }
+}
+
+fn duplicate(entity: &'static str, name: &str, existing_id: Option<Uuid>) -> ScraperError {
+    ScraperError::Duplicate {
+        entity,
+        name: name.to_string(),
+        existing_id: existing_id.map(|id| id.to_string()).unwrap_or_default(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn upcoming_counts_skip_past_and_hidden_events() {
+        let storage = InMemoryStorage::new();
+        let mut ids = Vec::new();
+        for name in ["Neumos", "Barboza", "The Crocodile"] {
+            let mut v = Venue::fixture(name);
+            storage.create_venue(&mut v).await.unwrap();
+            ids.push(v.id.unwrap());
+        }
+        let (neumos, barboza, crocodile) = (ids[0], ids[1], ids[2]);
+        let today = NaiveDate::from_ymd_opt(2025, 3, 10).unwrap();
+        let day = |d| NaiveDate::from_ymd_opt(2025, 3, d).unwrap();
+        for (title, d, venue_id, show_event) in [
+            ("Yesterday", 9, neumos, true),
+            ("Today", 10, neumos, true),
+            ("Next week", 17, neumos, true),
+            ("Hidden", 12, neumos, false),
+            ("Only past", 1, barboza, true),
+            ("Only hidden", 20, barboza, false),
+        ] {
+            let mut e = Event { show_event, ..Event::fixture(title, day(d), venue_id) };
+            storage.create_event(&mut e).await.unwrap();
+        }
+
+        let counts = storage.count_upcoming_events_by_venue(today).await.unwrap();
+        // `from` itself counts as upcoming
+        assert_eq!(counts.get(&neumos), Some(&2));
+        // Venues whose events are all past or hidden, or that host none, are absent
+        assert_eq!(counts.get(&barboza), None);
+        assert_eq!(counts.get(&crocodile), None);
+        assert_eq!(counts.len(), 1);
+    }
 }
@@ -2,13 +2,15 @@
 
 pub mod traits;
 pub mod in_memory;
+pub mod slug_allocator;
 
 #[cfg(feature = "db")]
 pub mod database;
 
 // Re-export the main trait and implementations at module root
-pub use traits::Storage;
+pub use traits::{BatchWriteStats, Storage, WriteBatch};
 pub use in_memory::InMemoryStorage;
+pub use slug_allocator::{allocate_artist, canonical_slug, SlugAllocation};
 
 #[cfg(feature = "db")]
 pub use database::DatabaseStorage;
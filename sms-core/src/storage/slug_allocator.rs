@@ -0,0 +1,96 @@
+// Artist slug allocation: one canonical slug per artist, even when sources spell names differently
+
+use super::traits::Storage;
+use crate::common::error::{Result, ScraperError};
+use crate::domain::Artist;
+use tracing::debug;
+use uuid::Uuid;
+
+/// Most `-N` suffixes tried before giving up on a slug
+const MAX_SUFFIX: usize = 1000;
+
+/// Canonical URL-safe slug for an artist name: lowercase alphanumerics joined by single dashes
+pub fn canonical_slug(name: &str) -> String {
+    name.to_lowercase()
+        .chars()
+        .map(|c| if c.is_alphanumeric() { c } else { '-' })
+        .collect::<String>()
+        .split('-')
+        .filter(|s| !s.is_empty())
+        .collect::<Vec<_>>()
+        .join("-")
+}
+
+/// What happened to an artist handed to [`allocate_artist`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SlugAllocation {
+    /// Stored as a new artist under `slug`, suffixed when a distinct artist already had the
+    /// canonical one
+    Created { id: Uuid, slug: String, suffixed: bool },
+    /// Judged to be an artist already stored under `slug`; its attributions were merged in
+    Merged { id: Uuid, slug: String },
+}
+
+impl SlugAllocation {
+    pub fn id(&self) -> Uuid {
+        match self {
+            Self::Created { id, .. } | Self::Merged { id, .. } => *id,
+        }
+    }
+}
+
+/// Store `artist` under a free slug, or fold it into the artist it duplicates.
+///
+/// Starting from the canonical slug of its name, each taken slug's owner is offered to
+/// `same_artist`: a match merges into that artist, otherwise the next `-2`, `-3`, ... suffix
+/// is tried. A free slug is claimed through [`Storage::create_artist_if_slug_free`], so when
+/// another writer (in this process or another) takes it first, its artist is judged instead.
+/// On return `artist.id` and `artist.name_slug` point at the stored entity.
+pub async fn allocate_artist(
+    storage: &dyn Storage,
+    artist: &mut Artist,
+    same_artist: impl Fn(&Artist, &Artist) -> bool,
+) -> Result<SlugAllocation> {
+    let base = canonical_slug(&artist.name);
+    if base.is_empty() {
+        return Err(ScraperError::Api { message: format!("artist name {:?} has no slug", artist.name) });
+    }
+
+    for n in 1..=MAX_SUFFIX {
+        let slug = if n == 1 { base.clone() } else { format!("{}-{}", base, n) };
+        let mut existing = match storage.get_artist_by_slug(&slug).await? {
+            Some(existing) => existing,
+            None => {
+                artist.name_slug = slug.clone();
+                if storage.create_artist_if_slug_free(artist).await? {
+                    let id = artist.id.ok_or_else(|| ScraperError::Api { message: "created artist has no id".to_string() })?;
+                    if n > 1 {
+                        debug!("Slug '{}' taken by a different artist; '{}' stored as '{}'", base, artist.name, slug);
+                    }
+                    return Ok(SlugAllocation::Created { id, slug, suffixed: n > 1 });
+                }
+                // Another writer took the slug between the lookup and the insert
+                storage.get_artist_by_slug(&slug).await?.ok_or_else(|| ScraperError::Api {
+                    message: format!("slug '{}' was taken but its artist can't be read", slug),
+                })?
+            }
+        };
+        if !same_artist(&existing, artist) {
+            continue;
+        }
+        let id = existing.id.ok_or_else(|| ScraperError::Api { message: format!("artist '{}' has no id", slug) })?;
+        let before = existing.attributions.len();
+        for attribution in artist.attributions.drain(..) {
+            attribution.add_to(&mut existing.attributions);
+        }
+        if existing.attributions.len() != before {
+            storage.update_artist(&existing).await?;
+        }
+        debug!("'{}' merged into existing artist '{}' ({})", artist.name, existing.name, slug);
+        artist.id = Some(id);
+        artist.name_slug = slug.clone();
+        artist.attributions = existing.attributions;
+        return Ok(SlugAllocation::Merged { id, slug });
+    }
+    Err(ScraperError::Api { message: format!("no free slug for artist '{}' after {} attempts", artist.name, MAX_SUFFIX) })
+}
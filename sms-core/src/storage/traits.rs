@@ -1,21 +1,79 @@
 use crate::domain::*;
 use crate::common::error::Result;
+use crate::common::geo::GeoBounds;
 use async_trait::async_trait;
 use chrono::NaiveDate;
+use std::collections::HashMap;
 use uuid::Uuid;
 
+/// Catalog writes staged to be flushed together by [`Storage::write_batch`]
+#[derive(Debug, Default, Clone)]
+pub struct WriteBatch {
+    pub venues: Vec<Venue>,
+    pub artists: Vec<Artist>,
+    /// Events to create
+    pub events: Vec<Event>,
+    /// Existing events to overwrite
+    pub event_updates: Vec<Event>,
+    pub process_records: Vec<ProcessRecord>,
+}
+
+impl WriteBatch {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Number of staged entities, process records included
+    pub fn len(&self) -> usize {
+        self.venues.len()
+            + self.artists.len()
+            + self.events.len()
+            + self.event_updates.len()
+            + self.process_records.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// Outcome of a flushed [`WriteBatch`]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct BatchWriteStats {
+    pub entities: usize,
+    /// Transaction attempts it took, 1 unless a conflict forced a retry
+    pub attempts: u32,
+}
+
 /// Storage trait for persisting domain data (venues, artists, events, raw data, and process runs/records)
 #[async_trait]
 #[allow(dead_code)]
 pub trait Storage: Send + Sync {
     // Venue operations
     async fn create_venue(&self, venue: &mut Venue) -> Result<()>;
+    /// Venue named `name`, or failing that one with `name` among its aliases (ignoring case)
     async fn get_venue_by_name(&self, name: &str) -> Result<Option<Venue>>;
+    /// Remove a venue; events still pointing at it are left as they are
+    async fn delete_venue(&self, venue_id: Uuid) -> Result<()>;
     
     // Artist operations
     async fn create_artist(&self, artist: &mut Artist) -> Result<()>;
+    /// Artist named `name`, or failing that one with `name` among its aliases (ignoring case)
     async fn get_artist_by_name(&self, name: &str) -> Result<Option<Artist>>;
     async fn get_artist_by_slug(&self, slug: &str) -> Result<Option<Artist>>;
+    async fn update_artist(&self, artist: &Artist) -> Result<()>;
+    /// Create `artist` unless another artist already holds its `name_slug`, returning whether
+    /// it was created. Backends shared between processes check and insert in one statement, so
+    /// two writers can't both take a slug.
+    async fn create_artist_if_slug_free(&self, artist: &mut Artist) -> Result<bool> {
+        if self.get_artist_by_slug(&artist.name_slug).await?.is_some() {
+            return Ok(false);
+        }
+        self.create_artist(artist).await?;
+        Ok(true)
+    }
+    /// Remove an artist; events still linking to it are left as they are
+    async fn delete_artist(&self, artist_id: Uuid) -> Result<()>;
     
     // Event operations
     async fn create_event(&self, event: &mut Event) -> Result<()>;
@@ -27,6 +85,12 @@ pub trait Storage: Send + Sync {
     ) -> Result<Option<Event>>;
     async fn update_event(&self, event: &Event) -> Result<()>;
     async fn delete_event(&self, event_id: Uuid) -> Result<()>;
+
+    // Recurring series operations
+    /// Create or overwrite a series, assigning an id when it has none
+    async fn upsert_event_series(&self, series: &mut EventSeries) -> Result<()>;
+    async fn get_event_series_by_id(&self, series_id: Uuid) -> Result<Option<EventSeries>>;
+    async fn get_event_series_by_venue_id(&self, venue_id: Uuid) -> Result<Vec<EventSeries>>;
     
     // Raw data operations
     async fn create_raw_data(&self, raw_data: &mut RawData) -> Result<()>;
@@ -41,12 +105,40 @@ pub trait Storage: Send + Sync {
         min_date: Option<NaiveDate>
     ) -> Result<Vec<RawData>>;
     async fn mark_raw_data_processed(&self, raw_data_id: Uuid) -> Result<()>;
+    /// CAS references (`cas:sha256:<hex>`) of the payloads gateway-ingested raw data came from
+    async fn get_raw_data_payload_refs(&self) -> Result<Vec<String>>;
     
     // Processing operations
     async fn create_process_run(&self, run: &mut ProcessRun) -> Result<()>;
     async fn update_process_run(&self, run: &ProcessRun) -> Result<()>;
     
     async fn create_process_record(&self, record: &mut ProcessRecord) -> Result<()>;
+    async fn get_process_run(&self, run_id: Uuid) -> Result<Option<ProcessRun>>;
+    /// Process records written by a run, oldest first
+    async fn get_process_records_for_run(&self, run_id: Uuid) -> Result<Vec<ProcessRecord>>;
+
+    /// Persist everything in `batch`, assigning ids to new entities the same way the
+    /// `create_*` methods do. Backends that support it write the batch in one transaction,
+    /// and refuse it with [`ScraperError::Duplicate`](crate::common::error::ScraperError::Duplicate) when another writer took a new
+    /// venue's name (ignoring case; provisional venues aside) or a new artist's slug in the meantime.
+    async fn write_batch(&self, batch: &mut WriteBatch) -> Result<BatchWriteStats> {
+        for venue in &mut batch.venues {
+            self.create_venue(venue).await?;
+        }
+        for artist in &mut batch.artists {
+            self.create_artist(artist).await?;
+        }
+        for event in &mut batch.events {
+            self.create_event(event).await?;
+        }
+        for event in &batch.event_updates {
+            self.update_event(event).await?;
+        }
+        for record in &mut batch.process_records {
+            self.create_process_record(record).await?;
+        }
+        Ok(BatchWriteStats { entities: batch.len(), attempts: 1 })
+    }
 
     // Additional query methods for GraphQL
     async fn get_venue_by_id(&self, venue_id: Uuid) -> Result<Option<Venue>>;
@@ -67,16 +159,35 @@ pub trait Storage: Send + Sync {
         limit: Option<usize>,
         offset: Option<usize>,
     ) -> Result<Vec<Event>>;
+    /// Venues whose coordinates fall inside `bounds` (unordered); provisional venues are left
+    /// out since their coordinates are only defaults
+    async fn get_venues_in_bounds(&self, bounds: GeoBounds) -> Result<Vec<Venue>>;
     async fn get_events_by_venue_id(&self, venue_id: Uuid) -> Result<Vec<Event>>;
+    /// Shown events on or after `from` per venue, counted in one pass over all venues;
+    /// venues with none are absent
+    async fn count_upcoming_events_by_venue(&self, from: NaiveDate) -> Result<HashMap<Uuid, u64>>;
     async fn get_events_by_artist_id(&self, artist_id: Uuid) -> Result<Vec<Event>>;
     async fn get_events_by_date_range(
         &self,
         start_date: NaiveDate,
         end_date: NaiveDate,
     ) -> Result<Vec<Event>>;
+    /// Artists whose name fuzzily matches `query`, best match first
     async fn search_artists(&self, query: &str) -> Result<Vec<Artist>>;
+    /// Venues whose name fuzzily matches `query`, best match first
+    async fn search_venues(&self, query: &str) -> Result<Vec<Venue>>;
 
     // Batch loading methods for GraphQL DataLoader optimization
     async fn get_venues_by_ids(&self, venue_ids: Vec<Uuid>) -> Result<Vec<Venue>>;
     async fn get_artists_by_ids(&self, artist_ids: Vec<Uuid>) -> Result<Vec<Artist>>;
+    async fn get_events_by_ids(&self, event_ids: Vec<Uuid>) -> Result<Vec<Event>>;
+    async fn get_event_series_by_ids(&self, series_ids: Vec<Uuid>) -> Result<Vec<EventSeries>>;
+    /// Each venue's events in date order, as `get_events_by_venue_id` would return them;
+    /// venues hosting none map to an empty list
+    async fn get_events_by_venue_ids(&self, venue_ids: Vec<Uuid>) -> Result<HashMap<Uuid, Vec<Event>>>;
+    /// Each artist's events in date order; artists with none map to an empty list
+    async fn get_events_by_artist_ids(&self, artist_ids: Vec<Uuid>) -> Result<HashMap<Uuid, Vec<Event>>>;
+    /// Each venue's recurring series, ordered as `get_event_series_by_venue_id` orders them;
+    /// venues with none map to an empty list
+    async fn get_event_series_by_venue_ids(&self, venue_ids: Vec<Uuid>) -> Result<HashMap<Uuid, Vec<EventSeries>>>;
 }
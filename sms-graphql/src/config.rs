@@ -0,0 +1,77 @@
+use sms_scraper::observability::push::PushConfig;
+use std::env;
+use std::path::PathBuf;
+use std::time::Duration;
+
+/// Request bodies larger than this are rejected unless configured otherwise
+pub const DEFAULT_MAX_BODY_BYTES: usize = 1024 * 1024;
+
+/// Server settings, read from the environment and overridden by CLI flags
+#[derive(Debug, Clone)]
+pub struct AppConfig {
+    pub port: u16,
+    /// Scraper data directory used for crawl status (ingest log, meta.db)
+    pub data_root: PathBuf,
+    /// Bearer token required for mutation operations; mutations are refused when unset
+    pub api_token: Option<String>,
+    /// Origins allowed to call the API from a browser; `*` allows any
+    pub allowed_origins: Vec<String>,
+    pub max_body_bytes: usize,
+    /// How long `upcomingEvents` results are shared across requests; zero turns caching off
+    pub cache_ttl: Duration,
+    /// Where request metrics are pushed, if anywhere; they're always served at /metrics
+    pub push: PushConfig,
+}
+
+impl Default for AppConfig {
+    fn default() -> Self {
+        Self {
+            port: 8080,
+            data_root: PathBuf::from("data"),
+            api_token: None,
+            allowed_origins: Vec::new(),
+            max_body_bytes: DEFAULT_MAX_BODY_BYTES,
+            cache_ttl: Duration::ZERO,
+            push: PushConfig::disabled(),
+        }
+    }
+}
+
+impl AppConfig {
+    /// Defaults overlaid with `GRAPHQL_API_TOKEN`, `GRAPHQL_ALLOWED_ORIGINS` (comma-separated),
+    /// `GRAPHQL_MAX_BODY_BYTES`, `GRAPHQL_CACHE_TTL_SECS` and the metrics push settings
+    /// (`SMS_PUSHGATEWAY_URL`, `SMS_METRICS_PUSH`, `SMS_ENV`), which are dropped when invalid
+    pub fn from_env() -> Self {
+        let mut config = Self::default();
+        if let Ok(token) = env::var("GRAPHQL_API_TOKEN") {
+            config.api_token = Some(token);
+        }
+        if let Ok(origins) = env::var("GRAPHQL_ALLOWED_ORIGINS") {
+            config.allowed_origins = split_origins(&origins);
+        }
+        if let Some(bytes) = env::var("GRAPHQL_MAX_BODY_BYTES").ok().and_then(|s| s.parse().ok()) {
+            config.max_body_bytes = bytes;
+        }
+        if let Some(secs) = env::var("GRAPHQL_CACHE_TTL_SECS").ok().and_then(|s| s.parse().ok()) {
+            config.cache_ttl = Duration::from_secs(secs);
+        }
+        match PushConfig::from_env() {
+            Ok(push) => config.push = push,
+            Err(e) => tracing::warn!("Not pushing metrics: {:#}", e),
+        }
+        config.api_token = config.api_token.filter(|t| !t.trim().is_empty());
+        config
+    }
+
+    pub fn allows_any_origin(&self) -> bool {
+        self.allowed_origins.iter().any(|o| o == "*")
+    }
+}
+
+pub fn split_origins(value: &str) -> Vec<String> {
+    value
+        .split(',')
+        .map(|o| o.trim().trim_end_matches('/').to_string())
+        .filter(|o| !o.is_empty())
+        .collect()
+}
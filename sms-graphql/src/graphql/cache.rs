@@ -0,0 +1,111 @@
+use sms_scraper::observability::metrics;
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Results of a hot query shared across requests for `ttl`, so a burst of identical
+/// requests costs one storage read. A zero TTL turns the cache off.
+pub struct TtlCache<V> {
+    name: &'static str,
+    ttl: Duration,
+    entries: Mutex<HashMap<String, (Instant, V)>>,
+}
+
+impl<V: Clone> TtlCache<V> {
+    pub fn new(name: &'static str, ttl: Duration) -> Self {
+        Self { name, ttl, entries: Mutex::new(HashMap::new()) }
+    }
+
+    /// The cached value for `key` if it's younger than the TTL, otherwise the result of
+    /// `load`, which is cached when it succeeds
+    pub async fn get_or_load<E, F, Fut>(&self, key: String, load: F) -> Result<V, E>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<V, E>>,
+    {
+        if self.ttl.is_zero() {
+            return load().await;
+        }
+        let cached = self
+            .entries
+            .lock()
+            .unwrap()
+            .get(&key)
+            .filter(|(stored_at, _)| stored_at.elapsed() < self.ttl)
+            .map(|(_, value)| value.clone());
+        metrics::graphql::cache_lookup(self.name, cached.is_some());
+        if let Some(value) = cached {
+            return Ok(value);
+        }
+
+        let value = load().await?;
+        let mut entries = self.entries.lock().unwrap();
+        entries.retain(|_, (stored_at, _)| stored_at.elapsed() < self.ttl);
+        entries.insert(key, (Instant::now(), value.clone()));
+        Ok(value)
+    }
+
+    /// Drop every entry, e.g. after a mutation changed what the cached queries return
+    pub fn clear(&self) {
+        self.entries.lock().unwrap().clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    /// Caches `key` through `cache`, counting how often the loader actually runs
+    async fn load(cache: &TtlCache<u32>, loads: &AtomicUsize, key: &str) -> u32 {
+        cache
+            .get_or_load(key.to_string(), || async {
+                Ok::<_, ()>(loads.fetch_add(1, Ordering::SeqCst) as u32)
+            })
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn repeated_keys_are_served_from_the_cache() {
+        let cache = TtlCache::new("test", Duration::from_secs(60));
+        let loads = AtomicUsize::new(0);
+
+        assert_eq!(load(&cache, &loads, "a").await, 0);
+        assert_eq!(load(&cache, &loads, "a").await, 0);
+        assert_eq!(load(&cache, &loads, "b").await, 1);
+        assert_eq!(loads.load(Ordering::SeqCst), 2);
+
+        let failed: Result<u32, &str> = cache.get_or_load("c".to_string(), || async { Err("down") }).await;
+        assert_eq!(failed, Err("down"));
+        assert_eq!(load(&cache, &loads, "c").await, 2, "failures aren't cached");
+    }
+
+    #[tokio::test]
+    async fn entries_expire_after_the_ttl() {
+        let cache = TtlCache::new("test", Duration::from_millis(20));
+        let loads = AtomicUsize::new(0);
+
+        assert_eq!(load(&cache, &loads, "a").await, 0);
+        tokio::time::sleep(Duration::from_millis(30)).await;
+        assert_eq!(load(&cache, &loads, "a").await, 1);
+        assert_eq!(load(&cache, &loads, "a").await, 1);
+
+        let disabled = TtlCache::new("test", Duration::ZERO);
+        assert_eq!(load(&disabled, &loads, "a").await, 2);
+        assert_eq!(load(&disabled, &loads, "a").await, 3);
+    }
+
+    #[tokio::test]
+    async fn clear_forgets_every_entry() {
+        let cache = TtlCache::new("test", Duration::from_secs(60));
+        let loads = AtomicUsize::new(0);
+
+        load(&cache, &loads, "a").await;
+        load(&cache, &loads, "b").await;
+        cache.clear();
+        assert_eq!(load(&cache, &loads, "a").await, 2);
+        assert_eq!(load(&cache, &loads, "b").await, 3);
+    }
+}
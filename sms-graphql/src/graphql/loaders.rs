@@ -1,38 +1,71 @@
-use sms_core::{Artist, Venue};
+use sms_core::{Artist, Event, EventSeries, Venue};
 use sms_core::storage::Storage;
-use async_graphql::dataloader::{DataLoader, Loader};
+use async_graphql::dataloader::{DataLoader, HashMapCache, Loader};
 use async_trait::async_trait;
+use sms_scraper::observability::metrics;
 use std::collections::HashMap;
 use std::sync::Arc;
 use uuid::Uuid;
 
-/// DataLoader for batching venue lookups
-pub struct VenueLoader {
-    storage: Arc<dyn Storage>,
+/// A DataLoader that remembers every key it loaded for as long as it lives
+pub type RequestLoader<T> = DataLoader<T, HashMapCache>;
+
+fn request_loader<T: Loader<Uuid>>(loader: T) -> RequestLoader<T> {
+    DataLoader::with_cache(loader, tokio::spawn, HashMapCache::default())
 }
 
-impl VenueLoader {
-    pub fn new(storage: Arc<dyn Storage>) -> DataLoader<Self> {
-        DataLoader::new(Self { storage }, tokio::spawn)
+/// The DataLoaders for one GraphQL request. Nested fields resolved in the same tick are
+/// batched into one storage call per loader, and a key loaded once (say, a venue shared by
+/// fifty events) is served from the loader's cache for the rest of the request. Built per
+/// request, so no cached entity outlives it.
+pub struct Loaders {
+    pub venue: RequestLoader<VenueLoader>,
+    pub artist: RequestLoader<ArtistLoader>,
+    pub event: RequestLoader<EventLoader>,
+    pub event_series: RequestLoader<EventSeriesLoader>,
+    pub venue_events: RequestLoader<VenueEventsLoader>,
+    pub artist_events: RequestLoader<ArtistEventsLoader>,
+    pub venue_series: RequestLoader<VenueSeriesLoader>,
+    pub upcoming_event_count: RequestLoader<UpcomingEventCountLoader>,
+}
+
+impl Loaders {
+    pub fn new(storage: Arc<dyn Storage>) -> Self {
+        Self {
+            venue: request_loader(VenueLoader { storage: storage.clone() }),
+            artist: request_loader(ArtistLoader { storage: storage.clone() }),
+            event: request_loader(EventLoader { storage: storage.clone() }),
+            event_series: request_loader(EventSeriesLoader { storage: storage.clone() }),
+            venue_events: request_loader(VenueEventsLoader { storage: storage.clone() }),
+            artist_events: request_loader(ArtistEventsLoader { storage: storage.clone() }),
+            venue_series: request_loader(VenueSeriesLoader { storage: storage.clone() }),
+            upcoming_event_count: request_loader(UpcomingEventCountLoader { storage }),
+        }
     }
 }
 
+/// DataLoader for batching venue lookups
+pub struct VenueLoader {
+    storage: Arc<dyn Storage>,
+}
+
 #[async_trait]
 impl Loader<Uuid> for VenueLoader {
     type Value = Venue;
     type Error = String;
 
     async fn load(&self, keys: &[Uuid]) -> Result<HashMap<Uuid, Self::Value>, Self::Error> {
+        metrics::graphql::loader_batch("venue", keys.len());
         let venues = self.storage.get_venues_by_ids(keys.to_vec()).await
             .map_err(|e| e.to_string())?;
-        
+
         let mut map = HashMap::new();
         for venue in venues {
             if let Some(id) = venue.id {
                 map.insert(id, venue);
             }
         }
-        
+
         Ok(map)
     }
 }
@@ -42,28 +75,321 @@ pub struct ArtistLoader {
     storage: Arc<dyn Storage>,
 }
 
-impl ArtistLoader {
-    pub fn new(storage: Arc<dyn Storage>) -> DataLoader<Self> {
-        DataLoader::new(Self { storage }, tokio::spawn)
-    }
-}
-
 #[async_trait]
 impl Loader<Uuid> for ArtistLoader {
     type Value = Artist;
     type Error = String;
 
     async fn load(&self, keys: &[Uuid]) -> Result<HashMap<Uuid, Self::Value>, Self::Error> {
+        metrics::graphql::loader_batch("artist", keys.len());
         let artists = self.storage.get_artists_by_ids(keys.to_vec()).await
             .map_err(|e| e.to_string())?;
-        
+
         let mut map = HashMap::new();
         for artist in artists {
             if let Some(id) = artist.id {
                 map.insert(id, artist);
             }
         }
-        
+
+        Ok(map)
+    }
+}
+
+/// DataLoader for event lookups by id, such as a series' instances
+pub struct EventLoader {
+    storage: Arc<dyn Storage>,
+}
+
+#[async_trait]
+impl Loader<Uuid> for EventLoader {
+    type Value = Event;
+    type Error = String;
+
+    async fn load(&self, keys: &[Uuid]) -> Result<HashMap<Uuid, Self::Value>, Self::Error> {
+        metrics::graphql::loader_batch("event", keys.len());
+        let events = self.storage.get_events_by_ids(keys.to_vec()).await
+            .map_err(|e| e.to_string())?;
+
+        let mut map = HashMap::new();
+        for event in events {
+            if let Some(id) = event.id {
+                map.insert(id, event);
+            }
+        }
+
+        Ok(map)
+    }
+}
+
+/// DataLoader for the series events belong to
+pub struct EventSeriesLoader {
+    storage: Arc<dyn Storage>,
+}
+
+#[async_trait]
+impl Loader<Uuid> for EventSeriesLoader {
+    type Value = EventSeries;
+    type Error = String;
+
+    async fn load(&self, keys: &[Uuid]) -> Result<HashMap<Uuid, Self::Value>, Self::Error> {
+        metrics::graphql::loader_batch("event_series", keys.len());
+        let series = self.storage.get_event_series_by_ids(keys.to_vec()).await
+            .map_err(|e| e.to_string())?;
+
+        let mut map = HashMap::new();
+        for s in series {
+            if let Some(id) = s.id {
+                map.insert(id, s);
+            }
+        }
+
         Ok(map)
     }
-}
\ No newline at end of file
+}
+
+/// DataLoader for each venue's events, hidden ones included
+pub struct VenueEventsLoader {
+    storage: Arc<dyn Storage>,
+}
+
+#[async_trait]
+impl Loader<Uuid> for VenueEventsLoader {
+    type Value = Vec<Event>;
+    type Error = String;
+
+    async fn load(&self, keys: &[Uuid]) -> Result<HashMap<Uuid, Self::Value>, Self::Error> {
+        metrics::graphql::loader_batch("venue_events", keys.len());
+        self.storage.get_events_by_venue_ids(keys.to_vec()).await
+            .map_err(|e| e.to_string())
+    }
+}
+
+/// DataLoader for each artist's events, hidden ones included
+pub struct ArtistEventsLoader {
+    storage: Arc<dyn Storage>,
+}
+
+#[async_trait]
+impl Loader<Uuid> for ArtistEventsLoader {
+    type Value = Vec<Event>;
+    type Error = String;
+
+    async fn load(&self, keys: &[Uuid]) -> Result<HashMap<Uuid, Self::Value>, Self::Error> {
+        metrics::graphql::loader_batch("artist_events", keys.len());
+        self.storage.get_events_by_artist_ids(keys.to_vec()).await
+            .map_err(|e| e.to_string())
+    }
+}
+
+/// DataLoader for each venue's recurring series
+pub struct VenueSeriesLoader {
+    storage: Arc<dyn Storage>,
+}
+
+#[async_trait]
+impl Loader<Uuid> for VenueSeriesLoader {
+    type Value = Vec<EventSeries>;
+    type Error = String;
+
+    async fn load(&self, keys: &[Uuid]) -> Result<HashMap<Uuid, Self::Value>, Self::Error> {
+        metrics::graphql::loader_batch("venue_series", keys.len());
+        self.storage.get_event_series_by_venue_ids(keys.to_vec()).await
+            .map_err(|e| e.to_string())
+    }
+}
+
+/// DataLoader for venues' upcoming event counts; each batch is one aggregated count
+/// across all venues rather than a query per venue
+pub struct UpcomingEventCountLoader {
+    storage: Arc<dyn Storage>,
+}
+
+#[async_trait]
+impl Loader<Uuid> for UpcomingEventCountLoader {
+    type Value = u64;
+    type Error = String;
+
+    async fn load(&self, keys: &[Uuid]) -> Result<HashMap<Uuid, Self::Value>, Self::Error> {
+        metrics::graphql::loader_batch("upcoming_event_count", keys.len());
+        let today = chrono::Utc::now().date_naive();
+        let counts = self.storage.count_upcoming_events_by_venue(today).await
+            .map_err(|e| e.to_string())?;
+
+        Ok(keys.iter().map(|id| (*id, counts.get(id).copied().unwrap_or(0))).collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::graphql::schema::{create_schema, with_loaders};
+    use async_trait::async_trait;
+    use chrono::{NaiveDate, Utc, Weekday};
+    use sms_core::common::error::Result;
+    use sms_core::common::geo::GeoBounds;
+    use sms_core::domain::*;
+    use sms_core::storage::{BatchWriteStats, InMemoryStorage, Storage, WriteBatch};
+    use std::collections::HashMap;
+    use std::path::PathBuf;
+    use std::sync::{Arc, Mutex};
+    use std::time::Duration;
+    use uuid::Uuid;
+
+    /// Storage that counts how often each method is called
+    #[derive(Default)]
+    struct CountingStorage {
+        inner: InMemoryStorage,
+        calls: Mutex<HashMap<&'static str, usize>>,
+    }
+
+    impl CountingStorage {
+        fn calls(&self, name: &str) -> usize {
+            self.calls.lock().unwrap().get(name).copied().unwrap_or(0)
+        }
+    }
+
+    macro_rules! counting_storage {
+        ($(async fn $name:ident(&self $(, $arg:ident: $ty:ty)*) -> $ret:ty;)*) => {
+            #[async_trait]
+            impl Storage for CountingStorage {
+                $(
+                    async fn $name(&self $(, $arg: $ty)*) -> Result<$ret> {
+                        *self.calls.lock().unwrap().entry(stringify!($name)).or_insert(0) += 1;
+                        self.inner.$name($($arg),*).await
+                    }
+                )*
+            }
+        };
+    }
+
+    counting_storage! {
+        async fn create_venue(&self, venue: &mut Venue) -> ();
+        async fn get_venue_by_name(&self, name: &str) -> Option<Venue>;
+        async fn delete_venue(&self, venue_id: Uuid) -> ();
+        async fn create_artist(&self, artist: &mut Artist) -> ();
+        async fn get_artist_by_name(&self, name: &str) -> Option<Artist>;
+        async fn get_artist_by_slug(&self, slug: &str) -> Option<Artist>;
+        async fn update_artist(&self, artist: &Artist) -> ();
+        async fn delete_artist(&self, artist_id: Uuid) -> ();
+        async fn create_event(&self, event: &mut Event) -> ();
+        async fn get_event_by_venue_date_title(&self, venue_id: Uuid, date: NaiveDate, title: &str) -> Option<Event>;
+        async fn update_event(&self, event: &Event) -> ();
+        async fn delete_event(&self, event_id: Uuid) -> ();
+        async fn upsert_event_series(&self, series: &mut EventSeries) -> ();
+        async fn get_event_series_by_id(&self, series_id: Uuid) -> Option<EventSeries>;
+        async fn get_event_series_by_venue_id(&self, venue_id: Uuid) -> Vec<EventSeries>;
+        async fn create_raw_data(&self, raw_data: &mut RawData) -> ();
+        async fn get_unprocessed_raw_data(&self, api_name: &str, min_date: Option<NaiveDate>) -> Vec<RawData>;
+        async fn get_processed_raw_data(&self, api_name: &str, min_date: Option<NaiveDate>) -> Vec<RawData>;
+        async fn mark_raw_data_processed(&self, raw_data_id: Uuid) -> ();
+        async fn get_raw_data_payload_refs(&self) -> Vec<String>;
+        async fn create_process_run(&self, run: &mut ProcessRun) -> ();
+        async fn update_process_run(&self, run: &ProcessRun) -> ();
+        async fn create_process_record(&self, record: &mut ProcessRecord) -> ();
+        async fn get_process_run(&self, run_id: Uuid) -> Option<ProcessRun>;
+        async fn get_process_records_for_run(&self, run_id: Uuid) -> Vec<ProcessRecord>;
+        async fn write_batch(&self, batch: &mut WriteBatch) -> BatchWriteStats;
+        async fn get_venue_by_id(&self, venue_id: Uuid) -> Option<Venue>;
+        async fn get_artist_by_id(&self, artist_id: Uuid) -> Option<Artist>;
+        async fn get_event_by_id(&self, event_id: Uuid) -> Option<Event>;
+        async fn get_all_venues(&self, limit: Option<usize>, offset: Option<usize>) -> Vec<Venue>;
+        async fn get_all_artists(&self, limit: Option<usize>, offset: Option<usize>) -> Vec<Artist>;
+        async fn get_all_events(&self, limit: Option<usize>, offset: Option<usize>) -> Vec<Event>;
+        async fn get_venues_in_bounds(&self, bounds: GeoBounds) -> Vec<Venue>;
+        async fn get_events_by_venue_id(&self, venue_id: Uuid) -> Vec<Event>;
+        async fn count_upcoming_events_by_venue(&self, from: NaiveDate) -> HashMap<Uuid, u64>;
+        async fn get_events_by_artist_id(&self, artist_id: Uuid) -> Vec<Event>;
+        async fn get_events_by_date_range(&self, start_date: NaiveDate, end_date: NaiveDate) -> Vec<Event>;
+        async fn search_artists(&self, query: &str) -> Vec<Artist>;
+        async fn search_venues(&self, query: &str) -> Vec<Venue>;
+        async fn get_venues_by_ids(&self, venue_ids: Vec<Uuid>) -> Vec<Venue>;
+        async fn get_artists_by_ids(&self, artist_ids: Vec<Uuid>) -> Vec<Artist>;
+        async fn get_events_by_ids(&self, event_ids: Vec<Uuid>) -> Vec<Event>;
+        async fn get_event_series_by_ids(&self, series_ids: Vec<Uuid>) -> Vec<EventSeries>;
+        async fn get_events_by_venue_ids(&self, venue_ids: Vec<Uuid>) -> HashMap<Uuid, Vec<Event>>;
+        async fn get_events_by_artist_ids(&self, artist_ids: Vec<Uuid>) -> HashMap<Uuid, Vec<Event>>;
+        async fn get_event_series_by_venue_ids(&self, venue_ids: Vec<Uuid>) -> HashMap<Uuid, Vec<EventSeries>>;
+    }
+
+    #[tokio::test]
+    async fn nested_fields_make_one_storage_call_per_loader() {
+        let storage = Arc::new(CountingStorage::default());
+        let mut venue_ids = Vec::new();
+        for name in ["Neumos", "Barboza", "The Crocodile"] {
+            let mut v = Venue::fixture(name);
+            storage.create_venue(&mut v).await.unwrap();
+            venue_ids.push(v.id.unwrap());
+        }
+        let mut artist_ids = Vec::new();
+        for name in ["The Thermals", "Tacocat", "Chastity Belt", "La Luz"] {
+            let mut a = Artist::fixture(name);
+            storage.create_artist(&mut a).await.unwrap();
+            artist_ids.push(a.id.unwrap());
+        }
+        let series_id = Uuid::new_v4();
+        let mut instance_ids = Vec::new();
+        for day in 1..=9 {
+            let venue_id = venue_ids[day as usize % 3];
+            let artists = vec![artist_ids[day as usize % 4], artist_ids[(day as usize + 1) % 4]];
+            let series = (venue_id == venue_ids[0]).then_some(series_id);
+            let event_day = NaiveDate::from_ymd_opt(2025, 3, day).unwrap();
+            let mut e = Event { artist_ids: artists, series_id: series, ..Event::fixture(&format!("Show {day}"), event_day, venue_id) };
+            storage.create_event(&mut e).await.unwrap();
+            if series.is_some() {
+                instance_ids.push(e.id.unwrap());
+            }
+        }
+        let mut series = EventSeries {
+            id: Some(series_id),
+            venue_id: venue_ids[0],
+            title: "Open Mic".to_string(),
+            weekday: Weekday::Mon,
+            interval_weeks: 1,
+            start_time: None,
+            first_day: NaiveDate::from_ymd_opt(2025, 3, 3).unwrap(),
+            last_day: NaiveDate::from_ymd_opt(2025, 3, 9).unwrap(),
+            event_ids: instance_ids,
+            created_at: Utc::now(),
+        };
+        storage.upsert_event_series(&mut series).await.unwrap();
+
+        let schema = create_schema(storage.clone(), PathBuf::new(), Duration::ZERO);
+        let query = r#"{ allEvents {
+            title
+            venue { name events { title } recurringSeries { title } }
+            artists { name events { title } }
+            series { title events { title } }
+        } }"#;
+        let response = schema.execute(with_loaders(async_graphql::Request::new(query), storage.clone())).await;
+        assert!(response.errors.is_empty(), "{:?}", response.errors);
+
+        let data = response.data.into_json().unwrap();
+        let events = data["allEvents"].as_array().unwrap();
+        assert_eq!(events.len(), 9);
+        assert!(events.iter().all(|e| e["artists"].as_array().unwrap().len() == 2));
+        assert_eq!(events[0]["venue"]["events"].as_array().unwrap().len(), 3);
+
+        for batched in [
+            "get_venues_by_ids",
+            "get_artists_by_ids",
+            "get_events_by_ids",
+            "get_event_series_by_ids",
+            "get_events_by_venue_ids",
+            "get_events_by_artist_ids",
+            "get_event_series_by_venue_ids",
+        ] {
+            assert_eq!(storage.calls(batched), 1, "{batched}");
+        }
+        for per_key in [
+            "get_venue_by_id",
+            "get_artist_by_id",
+            "get_event_by_id",
+            "get_event_series_by_id",
+            "get_events_by_venue_id",
+            "get_events_by_artist_id",
+            "get_event_series_by_venue_id",
+        ] {
+            assert_eq!(storage.calls(per_key), 0, "{per_key}");
+        }
+    }
+}
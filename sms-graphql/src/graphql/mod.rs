@@ -1,5 +1,8 @@
+pub mod cache;
 pub mod loaders;
 pub mod resolvers;
 pub mod schema;
 pub mod types;
+pub mod visibility;
 
+pub mod schema_check;
@@ -1,5 +1,11 @@
 use crate::graphql::schema::GraphQLContext;
+use crate::graphql::types::{Artist, MergeOutcome, ModerationOutcome};
 use async_graphql::{Context, FieldResult, Object, ID};
+use sms_core::ArtistDetailSource;
+use sms_scraper::app::merge_use_case::{MergeUseCase, MergedEntity};
+use sms_scraper::app::moderation_use_case::{ModeratedEntity, ModerationUseCase};
+use sms_scraper::pipeline::processing::resolution_index::ResolutionIndex;
+use std::sync::Arc;
 use uuid::Uuid;
 
 /// Root mutation object for GraphQL
@@ -40,6 +46,7 @@ impl Mutation {
                 }
             }
         }
+        context.invalidate_caches();
         
         Ok(deleted_count)
     }
@@ -53,9 +60,122 @@ impl Mutation {
         match context.storage.delete_event(event_id).await {
             Ok(_) => {
                 tracing::info!("Deleted event with ID: {}", event_id);
+                context.invalidate_caches();
                 Ok(true)
             }
             Err(e) => Err(async_graphql::Error::new(format!("Failed to delete event: {}", e))),
         }
     }
+
+    /// Set an artist's image and/or bio by hand. Curated values take precedence over anything
+    /// scraped; an empty string clears the field and lets scraped details fill it again.
+    /// Omitted fields are left as they are.
+    async fn curate_artist(
+        &self,
+        ctx: &Context<'_>,
+        id: ID,
+        artist_image_url: Option<String>,
+        bio: Option<String>,
+    ) -> FieldResult<Artist> {
+        let context = ctx.data::<GraphQLContext>()?;
+        let artist_id = Uuid::parse_str(&id)
+            .map_err(|e| async_graphql::Error::new(format!("Invalid UUID: {}", e)))?;
+        let mut artist = context.storage.get_artist_by_id(artist_id).await
+            .map_err(|e| async_graphql::Error::new(format!("Failed to get artist: {}", e)))?
+            .ok_or_else(|| async_graphql::Error::new(format!("Artist {} not found", artist_id)))?;
+
+        if let Some(url) = artist_image_url {
+            if url.trim().is_empty() {
+                artist.artist_image_url = None;
+                artist.detail_origins.image = None;
+            } else {
+                artist.offer_details(Some(&url), None, ArtistDetailSource::Curated);
+            }
+        }
+        if let Some(bio) = bio {
+            if bio.trim().is_empty() {
+                artist.bio = None;
+                artist.detail_origins.bio = None;
+            } else {
+                artist.offer_details(None, Some(&bio), ArtistDetailSource::Curated);
+            }
+        }
+
+        context.storage.update_artist(&artist).await
+            .map_err(|e| async_graphql::Error::new(format!("Failed to update artist: {}", e)))?;
+        tracing::info!("Curated details for artist {} ({})", artist.name, artist_id);
+        Ok(artist.into())
+    }
+
+    /// Hide an event from every public query, keeping it in the catalog. Later scrapes of
+    /// the event leave it hidden. Recorded with `reason` in the moderation audit trail.
+    async fn hide_event(&self, ctx: &Context<'_>, id: ID, reason: String) -> FieldResult<ModerationOutcome> {
+        let outcome = moderation(ctx)?.hide(ModeratedEntity::Event, Uuid::parse_str(&id)?, &reason).await?;
+        invalidate_caches(ctx)?;
+        tracing::info!("Hid event {} ({}): {}", outcome.name, outcome.entity_id, reason);
+        Ok(outcome.into())
+    }
+
+    /// Show an event hidden by a moderator or a catalog rollback again
+    async fn show_event(&self, ctx: &Context<'_>, id: ID, reason: Option<String>) -> FieldResult<ModerationOutcome> {
+        let outcome = moderation(ctx)?.show(ModeratedEntity::Event, Uuid::parse_str(&id)?, reason.as_deref()).await?;
+        invalidate_caches(ctx)?;
+        tracing::info!("Showed event {} ({})", outcome.name, outcome.entity_id);
+        Ok(outcome.into())
+    }
+
+    /// Hide a venue from every public query, keeping it in the catalog. Its events are
+    /// moderated separately.
+    async fn hide_venue(&self, ctx: &Context<'_>, id: ID, reason: String) -> FieldResult<ModerationOutcome> {
+        let outcome = moderation(ctx)?.hide(ModeratedEntity::Venue, Uuid::parse_str(&id)?, &reason).await?;
+        invalidate_caches(ctx)?;
+        tracing::info!("Hid venue {} ({}): {}", outcome.name, outcome.entity_id, reason);
+        Ok(outcome.into())
+    }
+
+    /// Show a hidden venue again
+    async fn show_venue(&self, ctx: &Context<'_>, id: ID, reason: Option<String>) -> FieldResult<ModerationOutcome> {
+        let outcome = moderation(ctx)?.show(ModeratedEntity::Venue, Uuid::parse_str(&id)?, reason.as_deref()).await?;
+        invalidate_caches(ctx)?;
+        tracing::info!("Showed venue {} ({})", outcome.name, outcome.entity_id);
+        Ok(outcome.into())
+    }
+
+    /// Merge artist `remove` into artist `keep` when conflation wrongly kept them apart. Its
+    /// events move to `keep`, which answers to its name from then on, and later scrapes
+    /// resolve its id to `keep`. Recorded with each changed entity's previous state.
+    async fn merge_artists(&self, ctx: &Context<'_>, keep: ID, remove: ID, reason: Option<String>) -> FieldResult<MergeOutcome> {
+        let outcome = merge(ctx)?
+            .merge(MergedEntity::Artist, Uuid::parse_str(&keep)?, Uuid::parse_str(&remove)?, reason.as_deref())
+            .await?;
+        invalidate_caches(ctx)?;
+        tracing::info!("Merged artist {} into {} ({})", outcome.removed_name, outcome.kept_name, outcome.kept_id);
+        Ok(outcome.into())
+    }
+
+    /// Merge venue `remove` into venue `keep`, moving its events and recurring series
+    async fn merge_venues(&self, ctx: &Context<'_>, keep: ID, remove: ID, reason: Option<String>) -> FieldResult<MergeOutcome> {
+        let outcome = merge(ctx)?
+            .merge(MergedEntity::Venue, Uuid::parse_str(&keep)?, Uuid::parse_str(&remove)?, reason.as_deref())
+            .await?;
+        invalidate_caches(ctx)?;
+        tracing::info!("Merged venue {} into {} ({})", outcome.removed_name, outcome.kept_name, outcome.kept_id);
+        Ok(outcome.into())
+    }
+}
+
+fn invalidate_caches(ctx: &Context<'_>) -> FieldResult<()> {
+    ctx.data::<GraphQLContext>()?.invalidate_caches();
+    Ok(())
+}
+
+fn moderation(ctx: &Context<'_>) -> FieldResult<ModerationUseCase> {
+    let context = ctx.data::<GraphQLContext>()?;
+    Ok(ModerationUseCase::new(context.storage.clone()))
+}
+
+fn merge(ctx: &Context<'_>) -> FieldResult<MergeUseCase> {
+    let context = ctx.data::<GraphQLContext>()?;
+    let index = ResolutionIndex::open_at_root(&context.data_root)?;
+    Ok(MergeUseCase::new(context.storage.clone()).with_resolution_index(Arc::new(index)))
 }
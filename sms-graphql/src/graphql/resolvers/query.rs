@@ -1,7 +1,17 @@
 use crate::graphql::schema::GraphQLContext;
-use crate::graphql::types::{Artist, Event, Venue};
+use crate::graphql::visibility::{retain_shown_events, retain_shown_venues, venue_shown_on_event};
+use crate::graphql::types::{
+    Artist, Event, EventDay, EventSeries, PipelineRun, Provenance, QualityStats, QuarantinedRecordPage, SourceStatus, StatsWindow,
+    Venue, VenueOrderBy,
+};
 use async_graphql::{Context, FieldResult, Object, ID};
 use chrono::NaiveDate;
+use sms_core::common::error::ScraperError;
+use sms_core::common::geo::{haversine_km, GeoBounds};
+use sms_scraper::pipeline::ingestion::ingest_meta::IngestMeta;
+use sms_scraper::pipeline::ingestion::source_status::collect_source_statuses;
+use sms_scraper::pipeline::processing::catalog::provenance::LineageStore;
+use sms_scraper::pipeline::processing::quality_gate::outcomes::QualityOutcomeStore;
 use uuid::Uuid;
 
 /// Root query object for GraphQL
@@ -15,27 +25,42 @@ impl Query {
         let venue_id = Uuid::parse_str(&id)?;
 
         match context.storage.get_venue_by_id(venue_id).await {
-            Ok(venue) => Ok(venue.map(|v| v.into())),
+            Ok(venue) => Ok(venue.filter(|v| v.show_venue).map(|v| v.into())),
             Err(e) => Err(e.into()),
         }
     }
 
-    /// Get all venues with optional pagination
+    /// Get all venues with optional pagination, by name unless `order_by` says otherwise
     async fn venues(
         &self,
         ctx: &Context<'_>,
         limit: Option<i32>,
         offset: Option<i32>,
+        order_by: Option<VenueOrderBy>,
     ) -> FieldResult<Vec<Venue>> {
         let context = ctx.data::<GraphQLContext>()?;
 
         let limit = limit.map(|l| l as usize);
         let offset = offset.map(|o| o as usize);
 
-        match context.storage.get_all_venues(limit, offset).await {
-            Ok(venues) => Ok(venues.into_iter().map(|v| v.into()).collect()),
-            Err(e) => Err(e.into()),
+        // Hidden venues are dropped before paging so pages stay full
+        let mut venues = context.storage.get_all_venues(None, None).await?;
+        retain_shown_venues(&mut venues);
+
+        if order_by == Some(VenueOrderBy::UpcomingEventCount) {
+            // Counts for every venue come from one aggregated query; venues arrive sorted by
+            // name, and the stable sort keeps that order among equal counts
+            let today = chrono::Utc::now().date_naive();
+            let counts = context.storage.count_upcoming_events_by_venue(today).await?;
+            venues.sort_by_key(|v| std::cmp::Reverse(v.id.and_then(|id| counts.get(&id)).copied().unwrap_or(0)));
         }
+
+        Ok(venues
+            .into_iter()
+            .skip(offset.unwrap_or(0))
+            .take(limit.unwrap_or(usize::MAX))
+            .map(|v| v.into())
+            .collect())
     }
 
     /// Get venues by city
@@ -48,7 +73,7 @@ impl Query {
             Ok(venues) => {
                 let filtered: Vec<Venue> = venues
                     .into_iter()
-                    .filter(|v| v.city.to_lowercase() == city.to_lowercase())
+                    .filter(|v| v.show_venue && v.city.to_lowercase() == city.to_lowercase())
                     .map(|v| v.into())
                     .collect();
                 Ok(filtered)
@@ -86,12 +111,24 @@ impl Query {
         }
     }
 
-    /// Search artists by name
-    async fn search_artists(&self, ctx: &Context<'_>, query: String) -> FieldResult<Vec<Artist>> {
+    /// Search artists by name, tolerating typos and punctuation differences; best match first
+    async fn search_artists(&self, ctx: &Context<'_>, q: String, limit: Option<i32>) -> FieldResult<Vec<Artist>> {
         let context = ctx.data::<GraphQLContext>()?;
+        let limit = limit.unwrap_or(20).max(1) as usize;
 
-        match context.storage.search_artists(&query).await {
-            Ok(artists) => Ok(artists.into_iter().map(|a| a.into()).collect()),
+        match context.storage.search_artists(&q).await {
+            Ok(artists) => Ok(artists.into_iter().take(limit).map(|a| a.into()).collect()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Search venues by name, tolerating typos and punctuation differences; best match first
+    async fn search_venues(&self, ctx: &Context<'_>, q: String, limit: Option<i32>) -> FieldResult<Vec<Venue>> {
+        let context = ctx.data::<GraphQLContext>()?;
+        let limit = limit.unwrap_or(20).max(1) as usize;
+
+        match context.storage.search_venues(&q).await {
+            Ok(venues) => Ok(venues.into_iter().filter(|v| v.show_venue).take(limit).map(|v| v.into()).collect()),
             Err(e) => Err(e.into()),
         }
     }
@@ -102,18 +139,36 @@ impl Query {
         let event_id = Uuid::parse_str(&id)?;
 
         match context.storage.get_event_by_id(event_id).await {
-            Ok(event) => Ok(event.map(|e| e.into())),
+            Ok(event) => Ok(event.filter(|e| e.show_event).map(|e| e.into())),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Get a recurring event series by ID
+    async fn event_series(&self, ctx: &Context<'_>, id: ID) -> FieldResult<Option<EventSeries>> {
+        let context = ctx.data::<GraphQLContext>()?;
+        let series_id = Uuid::parse_str(&id)?;
+
+        match context.storage.get_event_series_by_id(series_id).await {
+            Ok(series) => Ok(series.map(|s| s.into())),
             Err(e) => Err(e.into()),
         }
     }
 
-    /// Get events with optional pagination (defaults to future events only)
+    /// Get events with optional pagination (defaults to future events only), optionally
+    /// only those carrying `tag`; `free` keeps only free (true) or only paid (false) shows,
+    /// `allAges` only all-ages (true) or only age-restricted and unlabeled (false) shows
+    // Each parameter is a GraphQL argument
+    #[allow(clippy::too_many_arguments)]
     async fn events(
         &self,
         ctx: &Context<'_>,
         limit: Option<i32>,
         offset: Option<i32>,
         include_past: Option<bool>,
+        tag: Option<String>,
+        free: Option<bool>,
+        all_ages: Option<bool>,
     ) -> FieldResult<Vec<Event>> {
         let context = ctx.data::<GraphQLContext>()?;
 
@@ -123,11 +178,15 @@ impl Query {
 
         match context.storage.get_all_events(None, None).await {
             Ok(mut events) => {
+                retain_shown_events(&mut events);
                 // Filter out past events unless explicitly requested
                 if !include_past {
                     let today = chrono::Utc::now().date_naive();
                     events.retain(|e| e.event_day >= today);
                 }
+                retain_tagged(&mut events, tag.as_deref());
+                retain_free(&mut events, free);
+                retain_all_ages(&mut events, all_ages);
                 
                 // Apply pagination
                 let total = events.len();
@@ -161,8 +220,16 @@ impl Query {
         let limit = limit.map(|l| l as usize);
         let offset = offset.map(|o| o as usize);
 
-        match context.storage.get_all_events(limit, offset).await {
-            Ok(events) => Ok(events.into_iter().map(|e| e.into()).collect()),
+        match context.storage.get_all_events(None, None).await {
+            Ok(mut events) => {
+                retain_shown_events(&mut events);
+                Ok(events
+                    .into_iter()
+                    .skip(offset.unwrap_or(0))
+                    .take(limit.unwrap_or(usize::MAX))
+                    .map(|e| e.into())
+                    .collect())
+            }
             Err(e) => Err(e.into()),
         }
     }
@@ -175,6 +242,7 @@ impl Query {
 
         match context.storage.get_events_by_venue_id(venue_uuid).await {
             Ok(mut events) => {
+                retain_shown_events(&mut events);
                 // Filter out past events unless explicitly requested
                 if !include_past {
                     let today = chrono::Utc::now().date_naive();
@@ -186,12 +254,16 @@ impl Query {
         }
     }
 
-    /// Get events in a date range
+    /// Get events in a date range, optionally only those carrying `tag` and matching `free`
+    /// and `allAges`
     async fn events_by_date_range(
         &self,
         ctx: &Context<'_>,
         start_date: NaiveDate,
         end_date: NaiveDate,
+        tag: Option<String>,
+        free: Option<bool>,
+        all_ages: Option<bool>,
     ) -> FieldResult<Vec<Event>> {
         let context = ctx.data::<GraphQLContext>()?;
 
@@ -200,16 +272,77 @@ impl Query {
             .get_events_by_date_range(start_date, end_date)
             .await
         {
-            Ok(events) => Ok(events.into_iter().map(|e| e.into()).collect()),
+            Ok(mut events) => {
+                retain_shown_events(&mut events);
+                retain_tagged(&mut events, tag.as_deref());
+                retain_free(&mut events, free);
+                retain_all_ages(&mut events, all_ages);
+                Ok(events.into_iter().map(|e| e.into()).collect())
+            }
             Err(e) => Err(e.into()),
         }
     }
 
-    /// Get upcoming events (next 30 days by default)
+    /// Get every day from `from` to `to` (inclusive, at most 92 days) with its events,
+    /// for calendar views. Days without events are included with an empty list.
+    /// With `tag`, only events carrying it are listed; with `free`, only free (true) or
+    /// paid (false) shows; with `allAges`, only all-ages (true) or other (false) shows.
+    async fn events_by_day(
+        &self,
+        ctx: &Context<'_>,
+        from: NaiveDate,
+        to: NaiveDate,
+        tag: Option<String>,
+        free: Option<bool>,
+        all_ages: Option<bool>,
+    ) -> FieldResult<Vec<EventDay>> {
+        let context = ctx.data::<GraphQLContext>()?;
+
+        if to < from {
+            return Err("`to` must not be before `from`".into());
+        }
+        if (to - from).num_days() >= 92 {
+            return Err("date range is limited to 92 days".into());
+        }
+
+        let mut by_day: std::collections::BTreeMap<NaiveDate, Vec<sms_core::Event>> =
+            from.iter_days().take_while(|d| *d <= to).map(|d| (d, Vec::new())).collect();
+        let mut events = context.storage.get_events_by_date_range(from, to).await?;
+        retain_shown_events(&mut events);
+        retain_tagged(&mut events, tag.as_deref());
+        retain_free(&mut events, free);
+        retain_all_ages(&mut events, all_ages);
+        for event in events {
+            if let Some(day) = by_day.get_mut(&event.event_day) {
+                day.push(event);
+            }
+        }
+
+        Ok(by_day
+            .into_iter()
+            .map(|(day, mut events)| {
+                // Untimed events sort last within their day
+                events.sort_by(|a, b| {
+                    a.start_time
+                        .is_none()
+                        .cmp(&b.start_time.is_none())
+                        .then(a.start_time.cmp(&b.start_time))
+                        .then(a.title.cmp(&b.title))
+                });
+                EventDay { day, events: events.into_iter().map(Event::from).collect() }
+            })
+            .collect())
+    }
+
+    /// Get upcoming events (next 30 days by default), optionally only those carrying `tag`
+    /// and matching `free` and `allAges`
     async fn upcoming_events(
         &self,
         ctx: &Context<'_>,
         days: Option<i32>,
+        tag: Option<String>,
+        free: Option<bool>,
+        all_ages: Option<bool>,
     ) -> FieldResult<Vec<Event>> {
         let context = ctx.data::<GraphQLContext>()?;
         let days = days.unwrap_or(30);
@@ -217,16 +350,89 @@ impl Query {
         let start_date = chrono::Utc::now().date_naive();
         let end_date = start_date + chrono::Duration::days(days as i64);
 
-        match context
-            .storage
-            .get_events_by_date_range(start_date, end_date)
-            .await
-        {
-            Ok(events) => Ok(events.into_iter().map(|e| e.into()).collect()),
+        // Shared across requests for the cache TTL; mutations that hide or delete events clear it
+        let shown = context
+            .upcoming_events_cache
+            .get_or_load(format!("{}..{}", start_date, end_date), || async {
+                let mut events = context.storage.get_events_by_date_range(start_date, end_date).await?;
+                retain_shown_events(&mut events);
+                Ok::<_, ScraperError>(events)
+            })
+            .await;
+        match shown {
+            Ok(mut events) => {
+                retain_tagged(&mut events, tag.as_deref());
+                retain_free(&mut events, free);
+                retain_all_ages(&mut events, all_ages);
+                Ok(events.into_iter().map(|e| e.into()).collect())
+            }
             Err(e) => Err(e.into()),
         }
     }
 
+    /// Get events at venues within `radius_km` of a point, ordered by day then distance.
+    /// `start_date` defaults to today; `end_date` is open-ended when omitted.
+    async fn events_near(
+        &self,
+        ctx: &Context<'_>,
+        lat: f64,
+        lng: f64,
+        radius_km: f64,
+        start_date: Option<NaiveDate>,
+        end_date: Option<NaiveDate>,
+    ) -> FieldResult<Vec<Event>> {
+        let context = ctx.data::<GraphQLContext>()?;
+
+        if !(-90.0..=90.0).contains(&lat) || !(-180.0..=180.0).contains(&lng) {
+            return Err("lat/lng out of range".into());
+        }
+        if !radius_km.is_finite() || radius_km <= 0.0 {
+            return Err("radiusKm must be positive".into());
+        }
+
+        let start_date = start_date.unwrap_or_else(|| chrono::Utc::now().date_naive());
+
+        // Cheap box prefilter in storage, then exact great-circle distance
+        let candidates = context
+            .storage
+            .get_venues_in_bounds(GeoBounds::around(lat, lng, radius_km))
+            .await?;
+        let nearby: Vec<(Uuid, f64)> = candidates
+            .into_iter()
+            .filter(venue_shown_on_event)
+            .filter_map(|v| {
+                let distance = haversine_km(lat, lng, v.latitude, v.longitude);
+                (distance <= radius_km).then_some((v.id?, distance))
+            })
+            .collect();
+
+        // One batch lookup for every nearby venue's events
+        let mut by_venue = context
+            .storage
+            .get_events_by_venue_ids(nearby.iter().map(|(id, _)| *id).collect())
+            .await?;
+        let mut events = Vec::new();
+        for (venue_id, distance) in nearby {
+            for event in by_venue.remove(&venue_id).unwrap_or_default() {
+                let in_range = event.show_event
+                    && event.event_day >= start_date
+                    && end_date.is_none_or(|end| event.event_day <= end);
+                if in_range {
+                    events.push((event, distance));
+                }
+            }
+        }
+
+        events.sort_by(|(a, da), (b, db)| {
+            a.event_day
+                .cmp(&b.event_day)
+                .then(da.total_cmp(db))
+                .then(a.start_time.cmp(&b.start_time))
+        });
+
+        Ok(events.into_iter().map(|(e, _)| e.into()).collect())
+    }
+
     /// Search events by title and optionally filter by venue name (only returns future events)
     async fn search_events(
         &self,
@@ -254,22 +460,22 @@ impl Query {
         let mut filtered_events: Vec<_> = all_events
             .into_iter()
             .filter(|event| {
-                // Filter out past events
-                if event.event_day < today {
+                // Filter out hidden and past events
+                if !event.show_event || event.event_day < today {
                     return false;
                 }
                 
                 // Filter by search term in title
-                let title_matches = if let Some(search_term) = &search {
+                
+
+                if let Some(search_term) = &search {
                     event
                         .title
                         .to_lowercase()
                         .contains(&search_term.to_lowercase())
                 } else {
                     true
-                };
-
-                title_matches
+                }
             })
             .collect();
 
@@ -308,4 +514,331 @@ impl Query {
 
         Ok(paginated_events)
     }
+
+    /// Which scrape produced an event: its conflation, quality, normalization and parse
+    /// steps down to the envelope and CAS payload. Null if the event has no recorded lineage.
+    async fn provenance(&self, ctx: &Context<'_>, event_id: ID) -> FieldResult<Option<Provenance>> {
+        let context = ctx.data::<GraphQLContext>()?;
+        let event_uuid = Uuid::parse_str(&event_id)?;
+        let data_root = context.data_root.clone();
+
+        let lineage = tokio::task::spawn_blocking(move || {
+            LineageStore::open_at_root(&data_root)?.latest(event_uuid)
+        })
+        .await??;
+
+        Ok(lineage.map(|l| l.into()))
+    }
+
+    /// Crawl status per source: fetch history, parse backlog and last run output.
+    /// Returns every source with recorded history unless `source_id` is given.
+    async fn source_status(
+        &self,
+        ctx: &Context<'_>,
+        source_id: Option<String>,
+    ) -> FieldResult<Vec<SourceStatus>> {
+        let context = ctx.data::<GraphQLContext>()?;
+        let data_root = context.data_root.clone();
+        let source_ids = source_id.map(|id| vec![id]);
+
+        let statuses = tokio::task::spawn_blocking(move || {
+            collect_source_statuses(&data_root, source_ids.as_deref())
+        })
+        .await??;
+
+        Ok(statuses.into_iter().map(|s| s.into()).collect())
+    }
+
+    /// Recent pipeline runs, newest first, with per-stage counts, durations and errors.
+    /// Covers every source unless `source_id` is given.
+    async fn runs(
+        &self,
+        ctx: &Context<'_>,
+        first: Option<i32>,
+        source_id: Option<String>,
+    ) -> FieldResult<Vec<PipelineRun>> {
+        let context = ctx.data::<GraphQLContext>()?;
+        let data_root = context.data_root.clone();
+        let first = first.unwrap_or(20).clamp(1, 100) as usize;
+
+        let reports = tokio::task::spawn_blocking(move || {
+            IngestMeta::open_at_root(&data_root)?.recent_run_reports(source_id.as_deref(), first)
+        })
+        .await??;
+
+        Ok(reports.into_iter().map(|r| r.into()).collect())
+    }
+
+    /// A pipeline run by its run id
+    async fn run(&self, ctx: &Context<'_>, id: ID) -> FieldResult<Option<PipelineRun>> {
+        let context = ctx.data::<GraphQLContext>()?;
+        let data_root = context.data_root.clone();
+
+        let report = tokio::task::spawn_blocking(move || {
+            IngestMeta::open_at_root(&data_root)?.get_run_report(&id)
+        })
+        .await??;
+
+        Ok(report.map(|r| r.into()))
+    }
+
+    /// Quality-gate outcomes (accepted, warned, quarantined) over `window`, default the last
+    /// week. Covers every source unless `source_id` is given.
+    async fn quality_stats(
+        &self,
+        ctx: &Context<'_>,
+        source_id: Option<String>,
+        window: Option<StatsWindow>,
+    ) -> FieldResult<QualityStats> {
+        let context = ctx.data::<GraphQLContext>()?;
+        let data_root = context.data_root.clone();
+        let since = window.unwrap_or(StatsWindow::Week).since(chrono::Utc::now());
+
+        let stats = tokio::task::spawn_blocking(move || {
+            QualityOutcomeStore::open_at_root(&data_root)?.stats(source_id.as_deref(), since)
+        })
+        .await??;
+
+        Ok(stats.into())
+    }
+
+    /// Records the quality gate quarantined, newest first. Pass the previous page's
+    /// `endCursor` as `after` to continue.
+    async fn quarantined_records(
+        &self,
+        ctx: &Context<'_>,
+        first: Option<i32>,
+        after: Option<ID>,
+    ) -> FieldResult<QuarantinedRecordPage> {
+        let context = ctx.data::<GraphQLContext>()?;
+        let data_root = context.data_root.clone();
+        let first = first.unwrap_or(20).clamp(1, 100) as usize;
+        let after = after.map(|c| c.parse::<i64>()).transpose()?;
+
+        let (records, has_next_page) = tokio::task::spawn_blocking(move || {
+            QualityOutcomeStore::open_at_root(&data_root)?.quarantined(first, after)
+        })
+        .await??;
+
+        Ok(QuarantinedRecordPage {
+            records: records.into_iter().map(|r| r.into()).collect(),
+            has_next_page,
+        })
+    }
+}
+
+/// Keep only events carrying `tag` (ignoring case); no-op without one
+fn retain_tagged(events: &mut Vec<sms_core::Event>, tag: Option<&str>) {
+    if let Some(tag) = tag.map(str::trim).filter(|t| !t.is_empty()) {
+        events.retain(|e| e.has_tag(tag));
+    }
+}
+
+/// Keep only free shows, or only those not advertised as free; no-op without `free`.
+/// Events with no listed price count as not free.
+fn retain_free(events: &mut Vec<sms_core::Event>, free: Option<bool>) {
+    if let Some(free) = free {
+        events.retain(|e| e.is_free() == free);
+    }
+}
+
+/// Keep only shows advertised as all ages (true) or only the rest (false); no-op without
+/// `all_ages`. Events whose listing gives no age policy count as not all ages.
+fn retain_all_ages(events: &mut Vec<sms_core::Event>, all_ages: Option<bool>) {
+    if let Some(all_ages) = all_ages {
+        events.retain(|e| e.is_all_ages() == all_ages);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::graphql::schema::{create_schema, with_loaders};
+    use chrono::{NaiveDate, Utc};
+    use sms_core::storage::{InMemoryStorage, Storage};
+    use std::path::PathBuf;
+    use std::sync::Arc;
+    use std::time::Duration;
+    use uuid::Uuid;
+
+    async fn execute(storage: Arc<dyn Storage>, query: &str) -> serde_json::Value {
+        let schema = create_schema(storage.clone(), PathBuf::new(), Duration::ZERO);
+        let response = schema.execute(with_loaders(async_graphql::Request::new(query), storage)).await;
+        assert!(response.errors.is_empty(), "{:?}", response.errors);
+        response.data.into_json().unwrap()
+    }
+
+    async fn execute_err(storage: Arc<dyn Storage>, query: &str) -> String {
+        let schema = create_schema(storage.clone(), PathBuf::new(), Duration::ZERO);
+        let response = schema.execute(with_loaders(async_graphql::Request::new(query), storage)).await;
+        response.errors.first().map(|e| e.message.clone()).unwrap_or_default()
+    }
+
+    fn titles(data: &serde_json::Value, field: &str) -> Vec<String> {
+        data[field].as_array().unwrap().iter().map(|v| v["title"].as_str().unwrap().to_string()).collect()
+    }
+
+    fn names(data: &serde_json::Value, field: &str) -> Vec<String> {
+        data[field].as_array().unwrap().iter().map(|v| v["name"].as_str().unwrap().to_string()).collect()
+    }
+
+    async fn create_venue(storage: &InMemoryStorage, mut venue: sms_core::Venue) -> Uuid {
+        storage.create_venue(&mut venue).await.unwrap();
+        venue.id.unwrap()
+    }
+
+    #[tokio::test]
+    async fn search_artists_ranks_fuzzy_matches() {
+        let storage = Arc::new(InMemoryStorage::new());
+        for name in ["The Thermals", "Thermal Shock", "Neumos House Band"] {
+            storage.create_artist(&mut sms_core::Artist::fixture(name)).await.unwrap();
+        }
+
+        let data = execute(storage, r#"{ searchArtists(q: "thermls", limit: 5) { name } }"#).await;
+        let names: Vec<&str> = data["searchArtists"].as_array().unwrap().iter().map(|a| a["name"].as_str().unwrap()).collect();
+        assert_eq!(names.first(), Some(&"The Thermals"));
+        assert!(!names.contains(&"Neumos House Band"));
+    }
+
+    #[tokio::test]
+    async fn venues_order_by_upcoming_event_count_breaks_ties_by_name() {
+        let storage = Arc::new(InMemoryStorage::new());
+        create_venue(&storage, sms_core::Venue::fixture("Sunset Tavern")).await;
+        let crocodile = create_venue(&storage, sms_core::Venue::fixture("The Crocodile")).await;
+        let neumos = create_venue(&storage, sms_core::Venue::fixture("Neumos")).await;
+        let barboza = create_venue(&storage, sms_core::Venue::fixture("Barboza")).await;
+        let hidden = create_venue(&storage, sms_core::Venue { show_venue: false, ..sms_core::Venue::fixture("Closed Room") }).await;
+
+        let today = Utc::now().date_naive();
+        let in_days = |d| today + chrono::Duration::days(d);
+        for mut e in [
+            sms_core::Event::fixture("Tonight", today, neumos),
+            sms_core::Event::fixture("Next week", in_days(7), neumos),
+            // Past and hidden shows don't count, so Neumos stays ahead on its two
+            sms_core::Event::fixture("Last week", in_days(-7), barboza),
+            sms_core::Event::fixture("Last month", in_days(-30), barboza),
+            sms_core::Event::fixture("Next month", in_days(30), barboza),
+            sms_core::Event { show_event: false, ..sms_core::Event::fixture("Cancelled", in_days(2), crocodile) },
+            sms_core::Event::fixture("Friday", in_days(3), crocodile),
+            sms_core::Event::fixture("Hidden venue", in_days(1), hidden),
+        ] {
+            storage.create_event(&mut e).await.unwrap();
+        }
+
+        let data = execute(storage.clone(), "{ venues(orderBy: UPCOMING_EVENT_COUNT) { name } }").await;
+        // Barboza and The Crocodile tie on one upcoming show and keep name order;
+        // Sunset Tavern has none and comes last
+        assert_eq!(names(&data, "venues"), ["Neumos", "Barboza", "The Crocodile", "Sunset Tavern"]);
+
+        let data = execute(storage, "{ venues(orderBy: UPCOMING_EVENT_COUNT, limit: 2, offset: 1) { name } }").await;
+        assert_eq!(names(&data, "venues"), ["Barboza", "The Crocodile"]);
+    }
+
+    #[tokio::test]
+    async fn events_near_keeps_venues_within_the_radius_nearest_first() {
+        let storage = Arc::new(InMemoryStorage::new());
+        let (lat, lng) = (47.6097, -122.3422);
+        let at = |name, dlat, dlng| sms_core::Venue { latitude: lat + dlat, longitude: lng + dlng, ..sms_core::Venue::fixture(name) };
+        // About 0.5 km and 2.3 km away
+        let near = create_venue(&storage, at("Near", 0.0045, 0.0)).await;
+        let far = create_venue(&storage, at("Far", 0.0156, 0.02)).await;
+        // Inside the 3 km bounding box's corner but about 3.9 km away
+        let corner = create_venue(&storage, at("Corner", 0.025, 0.037)).await;
+        let hidden = create_venue(&storage, sms_core::Venue { show_venue: false, ..at("Hidden", 0.001, 0.0) }).await;
+
+        let today = Utc::now().date_naive();
+        let in_days = |d| today + chrono::Duration::days(d);
+        for mut e in [
+            sms_core::Event::fixture("Far tonight", today, far),
+            sms_core::Event::fixture("Near tonight", today, near),
+            sms_core::Event::fixture("Near tomorrow", in_days(1), near),
+            sms_core::Event::fixture("Near last week", in_days(-7), near),
+            sms_core::Event { show_event: false, ..sms_core::Event::fixture("Near cancelled", today, near) },
+            sms_core::Event::fixture("Corner tonight", today, corner),
+            sms_core::Event::fixture("Hidden tonight", today, hidden),
+            sms_core::Event::fixture("Far next month", in_days(30), far),
+        ] {
+            storage.create_event(&mut e).await.unwrap();
+        }
+
+        let query =
+            |radius: f64, extra: &str| format!("{{ eventsNear(lat: {lat}, lng: {lng}, radiusKm: {radius:?}{extra}) {{ title }} }}");
+        // By day, then nearest venue first within a day
+        let data = execute(storage.clone(), &query(3.0, "")).await;
+        assert_eq!(titles(&data, "eventsNear"), ["Near tonight", "Far tonight", "Near tomorrow", "Far next month"]);
+
+        let end = in_days(7);
+        let data = execute(storage.clone(), &query(3.0, &format!(", endDate: \"{end}\""))).await;
+        assert_eq!(titles(&data, "eventsNear"), ["Near tonight", "Far tonight", "Near tomorrow"]);
+
+        // Widening the radius takes in the corner venue
+        let data = execute(storage.clone(), &query(4.5, "")).await;
+        assert!(titles(&data, "eventsNear").contains(&"Corner tonight".to_string()));
+    }
+
+    #[tokio::test]
+    async fn events_near_rejects_bad_radius_and_coordinates() {
+        let storage: Arc<dyn Storage> = Arc::new(InMemoryStorage::new());
+        for radius in ["0", "-1.5"] {
+            let query = format!("{{ eventsNear(lat: 47.6, lng: -122.3, radiusKm: {radius}) {{ title }} }}");
+            assert_eq!(execute_err(storage.clone(), &query).await, "radiusKm must be positive");
+        }
+        let query = "{ eventsNear(lat: 91, lng: -122.3, radiusKm: 5) { title } }";
+        assert_eq!(execute_err(storage.clone(), query).await, "lat/lng out of range");
+        let query = "{ eventsNear(lat: 47.6, lng: 181, radiusKm: 5) { title } }";
+        assert_eq!(execute_err(storage, query).await, "lat/lng out of range");
+    }
+
+    #[tokio::test]
+    async fn events_by_day_lists_every_day_in_range_across_month_ends() {
+        let storage = Arc::new(InMemoryStorage::new());
+        let neumos = create_venue(&storage, sms_core::Venue::fixture("Neumos")).await;
+        let day = |m, d| NaiveDate::from_ymd_opt(2025, m, d).unwrap();
+        let at = |h, m| chrono::NaiveTime::from_hms_opt(h, m, 0);
+        for mut e in [
+            sms_core::Event::fixture("Untimed", day(3, 31), neumos),
+            sms_core::Event { start_time: at(21, 0), ..sms_core::Event::fixture("Late", day(3, 31), neumos) },
+            sms_core::Event { start_time: at(19, 0), ..sms_core::Event::fixture("Early", day(3, 31), neumos) },
+            // 23:30 in Seattle is already the next day in UTC; it stays on its local day
+            sms_core::Event { start_time: at(23, 30), ..sms_core::Event::fixture("Midnight", day(4, 1), neumos) },
+            sms_core::Event { show_event: false, ..sms_core::Event::fixture("Cancelled", day(4, 1), neumos) },
+            sms_core::Event::fixture("Before", day(3, 29), neumos),
+            sms_core::Event::fixture("After", day(4, 3), neumos),
+        ] {
+            storage.create_event(&mut e).await.unwrap();
+        }
+
+        let data = execute(
+            storage,
+            r#"{ eventsByDay(from: "2025-03-30", to: "2025-04-02") { day events { title } } }"#,
+        )
+        .await;
+        let days: Vec<(String, Vec<String>)> = data["eventsByDay"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|d| (d["day"].as_str().unwrap().to_string(), titles(d, "events")))
+            .collect();
+        let expected: Vec<(String, Vec<String>)> = [
+            ("2025-03-30", vec![]),
+            ("2025-03-31", vec!["Early", "Late", "Untimed"]),
+            ("2025-04-01", vec!["Midnight"]),
+            ("2025-04-02", vec![]),
+        ]
+        .into_iter()
+        .map(|(d, t)| (d.to_string(), t.into_iter().map(String::from).collect()))
+        .collect();
+        assert_eq!(days, expected);
+    }
+
+    #[tokio::test]
+    async fn events_by_day_limits_the_range_to_92_days() {
+        let storage: Arc<dyn Storage> = Arc::new(InMemoryStorage::new());
+        let data = execute(storage.clone(), r#"{ eventsByDay(from: "2025-01-01", to: "2025-04-02") { day } }"#).await;
+        assert_eq!(data["eventsByDay"].as_array().unwrap().len(), 92);
+
+        let query = r#"{ eventsByDay(from: "2025-01-01", to: "2025-04-03") { day } }"#;
+        assert_eq!(execute_err(storage.clone(), query).await, "date range is limited to 92 days");
+        let query = r#"{ eventsByDay(from: "2025-04-03", to: "2025-04-02") { day } }"#;
+        assert_eq!(execute_err(storage, query).await, "`to` must not be before `from`");
+    }
 }
@@ -1,32 +1,52 @@
-use crate::graphql::loaders::{ArtistLoader, VenueLoader};
+use crate::graphql::cache::TtlCache;
+use crate::graphql::loaders::Loaders;
 use crate::graphql::resolvers::{Query, Mutation};
 use sms_core::storage::Storage;
-use async_graphql::dataloader::DataLoader;
-use async_graphql::{EmptySubscription, Schema};
+use sms_core::Event;
+use async_graphql::{EmptySubscription, Request, Schema};
+use std::path::PathBuf;
 use std::sync::Arc;
+use std::time::Duration;
 
 /// GraphQL context containing shared application state
 pub struct GraphQLContext {
     pub storage: Arc<dyn Storage>,
-    pub venue_loader: DataLoader<VenueLoader>,
-    pub artist_loader: DataLoader<ArtistLoader>,
+    /// Root of the scraper data directory (ingest log, CAS, meta.db)
+    pub data_root: PathBuf,
+    /// Shown events per `upcomingEvents` date range, before the tag and price filters
+    pub upcoming_events_cache: TtlCache<Vec<Event>>,
+}
+
+impl GraphQLContext {
+    /// Forget cached query results after a mutation changed events or venues
+    pub fn invalidate_caches(&self) {
+        self.upcoming_events_cache.clear();
+    }
 }
 
 /// The complete GraphQL schema
 #[allow(dead_code)]
 pub type GraphQLSchema = Schema<Query, Mutation, EmptySubscription>;
 
-/// Create a new GraphQL schema with the given storage
+/// Create a new GraphQL schema with the given storage. `cache_ttl` is how long hot query
+/// results are shared across requests; zero disables the cache.
 #[allow(dead_code)]
-pub fn create_schema(storage: Arc<dyn Storage>) -> GraphQLSchema {
-    let venue_loader = VenueLoader::new(storage.clone());
-    let artist_loader = ArtistLoader::new(storage.clone());
-    
+pub fn create_schema(storage: Arc<dyn Storage>, data_root: PathBuf, cache_ttl: Duration) -> GraphQLSchema {
     Schema::build(Query, Mutation, EmptySubscription)
-        .data(GraphQLContext { 
+        .data(GraphQLContext {
             storage,
-            venue_loader,
-            artist_loader,
+            data_root,
+            upcoming_events_cache: TtlCache::new("upcoming_events", cache_ttl),
         })
         .finish()
 }
+
+/// Give a request its own DataLoaders; every request executed against the schema needs them
+pub fn with_loaders(request: Request, storage: Arc<dyn Storage>) -> Request {
+    request.data(Loaders::new(storage))
+}
+
+/// The schema's SDL. Resolvers aren't run, so no storage is needed.
+pub fn schema_sdl() -> String {
+    Schema::build(Query, Mutation, EmptySubscription).finish().sdl()
+}
@@ -0,0 +1,212 @@
+use anyhow::{Context, Result};
+use async_graphql::parser::types::{
+    BaseType, FieldDefinition, InputValueDefinition, Type, TypeDefinition, TypeKind, TypeSystemDefinition,
+};
+use async_graphql::parser::{parse_schema, Positioned};
+use std::collections::BTreeMap;
+use std::fmt;
+
+/// One change that can break a client written against the old schema
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BreakingChange {
+    /// `Type`, `Type.field` or `Type.field(arg)`
+    pub path: String,
+    pub reason: String,
+}
+
+impl fmt::Display for BreakingChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.path, self.reason)
+    }
+}
+
+/// Compare two SDL documents and list what `new` removes or changes incompatibly from `old`.
+/// Additions, description edits and loosening changes (an output field becoming non-null,
+/// an argument becoming nullable) are not reported.
+pub fn breaking_changes(old_sdl: &str, new_sdl: &str) -> Result<Vec<BreakingChange>> {
+    let old = types(old_sdl).context("parsing the baseline schema")?;
+    let new = types(new_sdl).context("parsing the current schema")?;
+    let mut changes = Vec::new();
+
+    for (name, old_kind) in &old {
+        let Some(new_kind) = new.get(name) else {
+            changes.push(change(name.clone(), "type removed"));
+            continue;
+        };
+        match (old_kind, new_kind) {
+            (TypeKind::Scalar, TypeKind::Scalar) => {}
+            (TypeKind::Object(o), TypeKind::Object(n)) => compare_fields(name, &o.fields, &n.fields, &mut changes),
+            (TypeKind::Interface(o), TypeKind::Interface(n)) => {
+                compare_fields(name, &o.fields, &n.fields, &mut changes)
+            }
+            (TypeKind::InputObject(o), TypeKind::InputObject(n)) => {
+                compare_inputs(name, &o.fields, &n.fields, &mut changes)
+            }
+            (TypeKind::Enum(o), TypeKind::Enum(n)) => {
+                for value in &o.values {
+                    let value = value.node.value.node.as_str();
+                    if !n.values.iter().any(|v| v.node.value.node.as_str() == value) {
+                        changes.push(change(format!("{}.{}", name, value), "enum value removed"));
+                    }
+                }
+            }
+            (TypeKind::Union(o), TypeKind::Union(n)) => {
+                for member in &o.members {
+                    if !n.members.iter().any(|m| m.node == member.node) {
+                        changes.push(change(name.clone(), format!("no longer includes {}", member.node)));
+                    }
+                }
+            }
+            (o, n) => changes.push(change(
+                name.clone(),
+                format!("changed from {} to {}", kind_name(o), kind_name(n)),
+            )),
+        }
+    }
+    Ok(changes)
+}
+
+fn types(sdl: &str) -> Result<BTreeMap<String, TypeKind>> {
+    let document = parse_schema(sdl)?;
+    Ok(document
+        .definitions
+        .into_iter()
+        .filter_map(|definition| match definition {
+            TypeSystemDefinition::Type(Positioned { node: TypeDefinition { name, kind, .. }, .. }) => {
+                Some((name.node.to_string(), kind))
+            }
+            _ => None,
+        })
+        .collect())
+}
+
+fn compare_fields(
+    type_name: &str,
+    old: &[Positioned<FieldDefinition>],
+    new: &[Positioned<FieldDefinition>],
+    changes: &mut Vec<BreakingChange>,
+) {
+    for field in old {
+        let field = &field.node;
+        let path = format!("{}.{}", type_name, field.name.node);
+        let Some(current) = new.iter().find(|f| f.node.name.node == field.name.node) else {
+            changes.push(change(path, "field removed"));
+            continue;
+        };
+        let current = &current.node;
+        if !output_compatible(&field.ty.node, &current.ty.node) {
+            changes.push(change(path.clone(), format!("type changed from {} to {}", field.ty.node, current.ty.node)));
+        }
+        compare_inputs(&path, &field.arguments, &current.arguments, changes);
+    }
+}
+
+/// Arguments of a field, or fields of an input object
+fn compare_inputs(
+    owner: &str,
+    old: &[Positioned<InputValueDefinition>],
+    new: &[Positioned<InputValueDefinition>],
+    changes: &mut Vec<BreakingChange>,
+) {
+    for input in new {
+        let input = &input.node;
+        let is_new = !old.iter().any(|o| o.node.name.node == input.name.node);
+        if is_new && !input.ty.node.nullable && input.default_value.is_none() {
+            changes.push(change(format!("{}({})", owner, input.name.node), "new required input"));
+        }
+    }
+    for input in old {
+        let input = &input.node;
+        let path = format!("{}({})", owner, input.name.node);
+        match new.iter().find(|n| n.node.name.node == input.name.node) {
+            None => changes.push(change(path, "input removed")),
+            Some(current) if !input_compatible(&input.ty.node, &current.node.ty.node) => changes.push(change(
+                path,
+                format!("type changed from {} to {}", input.ty.node, current.node.ty.node),
+            )),
+            Some(_) => {}
+        }
+    }
+}
+
+/// Clients reading `old` still work if `new` is the same type or only drops nullability
+fn output_compatible(old: &Type, new: &Type) -> bool {
+    (old.nullable || !new.nullable) && same_base(&old.base, &new.base, output_compatible)
+}
+
+/// Clients sending `old` still work if `new` is the same type or only allows null
+fn input_compatible(old: &Type, new: &Type) -> bool {
+    (new.nullable || !old.nullable) && same_base(&old.base, &new.base, input_compatible)
+}
+
+fn same_base(old: &BaseType, new: &BaseType, inner: fn(&Type, &Type) -> bool) -> bool {
+    match (old, new) {
+        (BaseType::Named(o), BaseType::Named(n)) => o == n,
+        (BaseType::List(o), BaseType::List(n)) => inner(o, n),
+        _ => false,
+    }
+}
+
+fn kind_name(kind: &TypeKind) -> &'static str {
+    match kind {
+        TypeKind::Scalar => "scalar",
+        TypeKind::Object(_) => "object",
+        TypeKind::Interface(_) => "interface",
+        TypeKind::Union(_) => "union",
+        TypeKind::Enum(_) => "enum",
+        TypeKind::InputObject(_) => "input object",
+    }
+}
+
+fn change(path: String, reason: impl Into<String>) -> BreakingChange {
+    BreakingChange { path, reason: reason.into() }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BASELINE: &str = r#"
+        type Query { event(id: ID!): Event  events(limit: Int): [Event!]! }
+        type Event { id: ID!  title: String  status: Status! }
+        enum Status { LIVE  HIDDEN }
+    "#;
+
+    fn paths(new_sdl: &str) -> Vec<String> {
+        breaking_changes(BASELINE, new_sdl).unwrap().into_iter().map(|c| c.path).collect()
+    }
+
+    #[test]
+    fn committed_schema_matches_the_served_one() {
+        let committed = include_str!("../../schema.graphql");
+        assert_eq!(
+            committed.trim_end(),
+            crate::graphql::schema::schema_sdl().trim_end(),
+            "schema.graphql is stale; re-export it with `sms-graphql export-schema`"
+        );
+    }
+
+    #[test]
+    fn additions_and_loosening_are_compatible() {
+        let new_sdl = r#"
+            type Query { event(id: ID, at: String): Event  events(limit: Int, offset: Int = 0): [Event!]!  venues: [String] }
+            type Event { id: ID!  title: String!  status: Status!  url: String }
+            enum Status { LIVE  HIDDEN  DRAFT }
+        "#;
+        assert!(paths(new_sdl).is_empty());
+    }
+
+    #[test]
+    fn removals_and_tightening_are_breaking() {
+        let new_sdl = r#"
+            type Query { event(id: ID!, at: String!): Event  events(limit: Int!): [Event!] }
+            type Event { id: ID!  status: Status! }
+            enum Status { LIVE }
+        "#;
+        assert_eq!(
+            paths(new_sdl),
+            ["Event.title", "Query.event(at)", "Query.events", "Query.events(limit)", "Status.HIDDEN"]
+        );
+        assert_eq!(paths("type Query { event(id: ID!): Event }"), ["Event", "Query.events", "Status"]);
+    }
+}
@@ -0,0 +1,25 @@
+use sms_core::AgeRestriction as DomainAgeRestriction;
+use async_graphql::Enum;
+
+/// Who may attend, as the listing or venue advertises it
+#[derive(Enum, Copy, Clone, Debug, PartialEq, Eq)]
+pub enum AgeRestriction {
+    AllAges,
+    /// Minors admitted with a parent or guardian
+    AllAgesWithGuardian,
+    SixteenPlus,
+    EighteenPlus,
+    TwentyOnePlus,
+}
+
+impl From<DomainAgeRestriction> for AgeRestriction {
+    fn from(age: DomainAgeRestriction) -> Self {
+        match age {
+            DomainAgeRestriction::AllAges => AgeRestriction::AllAges,
+            DomainAgeRestriction::AllAgesWithGuardian => AgeRestriction::AllAgesWithGuardian,
+            DomainAgeRestriction::SixteenPlus => AgeRestriction::SixteenPlus,
+            DomainAgeRestriction::EighteenPlus => AgeRestriction::EighteenPlus,
+            DomainAgeRestriction::TwentyOnePlus => AgeRestriction::TwentyOnePlus,
+        }
+    }
+}
@@ -1,6 +1,24 @@
-use sms_core::Artist as DomainArtist;
-use crate::graphql::schema::GraphQLContext;
-use async_graphql::{Context, FieldResult, Object, ID};
+use sms_core::{Artist as DomainArtist, ArtistDetailSource};
+use crate::graphql::loaders::Loaders;
+use async_graphql::{Context, Enum, FieldResult, Object, ID};
+
+/// Where an artist's image or bio came from
+#[derive(Enum, Copy, Clone, Debug, PartialEq, Eq)]
+pub enum DetailSource {
+    /// Taken from an event the artist headlines
+    HeadlinerEvent,
+    /// Set with the curateArtist mutation
+    Curated,
+}
+
+impl From<ArtistDetailSource> for DetailSource {
+    fn from(source: ArtistDetailSource) -> Self {
+        match source {
+            ArtistDetailSource::HeadlinerEvent => DetailSource::HeadlinerEvent,
+            ArtistDetailSource::Curated => DetailSource::Curated,
+        }
+    }
+}
 
 /// GraphQL representation of an Artist
 #[derive(Clone)]
@@ -26,6 +44,11 @@ impl Artist {
         &self.inner.name
     }
 
+    /// Names of artists a curator merged into this one
+    async fn aliases(&self) -> &[String] {
+        &self.inner.aliases
+    }
+
     /// The artist's name as a URL-friendly slug
     async fn name_slug(&self) -> &str {
         &self.inner.name_slug
@@ -41,18 +64,33 @@ impl Artist {
         self.inner.artist_image_url.as_deref()
     }
 
+    /// Where the image came from
+    async fn artist_image_source(&self) -> Option<DetailSource> {
+        self.inner.detail_origins.image.map(Into::into)
+    }
+
+    /// Where the bio came from
+    async fn bio_source(&self) -> Option<DetailSource> {
+        self.inner.detail_origins.bio.map(Into::into)
+    }
+
     /// When the artist was created
     async fn created_at(&self) -> chrono::DateTime<chrono::Utc> {
         self.inner.created_at
     }
 
+    /// Licenses and credit lines for the sources this artist's data came from
+    async fn attributions(&self) -> Vec<super::Attribution> {
+        self.inner.attributions.iter().cloned().map(Into::into).collect()
+    }
+
     /// Events where this artist is performing
     async fn events(&self, ctx: &Context<'_>) -> FieldResult<Vec<super::event::Event>> {
-        let context = ctx.data::<GraphQLContext>()?;
+        let loaders = ctx.data::<Loaders>()?;
         let artist_id = self.inner.id.ok_or("Artist ID not available")?;
 
-        match context.storage.get_events_by_artist_id(artist_id).await {
-            Ok(events) => Ok(events.into_iter().map(|e| e.into()).collect()),
+        match loaders.artist_events.load_one(artist_id).await {
+            Ok(events) => Ok(events.unwrap_or_default().into_iter().filter(|e| e.show_event).map(|e| e.into()).collect()),
             Err(e) => Err(e.into()),
         }
     }
@@ -0,0 +1,32 @@
+use sms_core::Attribution as DomainAttribution;
+use async_graphql::Object;
+
+/// License and credit line for data contributed by a source
+#[derive(Clone)]
+pub struct Attribution {
+    pub inner: DomainAttribution,
+}
+
+impl From<DomainAttribution> for Attribution {
+    fn from(attribution: DomainAttribution) -> Self {
+        Self { inner: attribution }
+    }
+}
+
+#[Object]
+impl Attribution {
+    /// The registry identifier of the source the data came from
+    async fn source_id(&self) -> &str {
+        &self.inner.source_id
+    }
+
+    /// The license the source's data is used under
+    async fn license_id(&self) -> &str {
+        &self.inner.license_id
+    }
+
+    /// Credit text that must be shown alongside the data, if the source requires one
+    async fn text(&self) -> Option<&str> {
+        self.inner.text.as_deref()
+    }
+}
@@ -1,5 +1,6 @@
 use sms_core::Event as DomainEvent;
-use crate::graphql::schema::GraphQLContext;
+use crate::graphql::loaders::Loaders;
+use crate::graphql::visibility::venue_shown_on_event;
 use async_graphql::{Context, FieldResult, Object, ID};
 
 /// GraphQL representation of an Event
@@ -31,11 +32,16 @@ impl Event {
         self.inner.event_day
     }
 
-    /// The start time of the event
+    /// When the show starts, or when doors open for listings that give only that
     async fn start_time(&self) -> Option<chrono::NaiveTime> {
         self.inner.start_time
     }
 
+    /// When doors open, for listings that give it separately from the show
+    async fn doors_time(&self) -> Option<chrono::NaiveTime> {
+        self.inner.doors_time
+    }
+
     /// URL to the event page
     async fn event_url(&self) -> Option<&str> {
         self.inner.event_url.as_deref()
@@ -66,13 +72,67 @@ impl Event {
         self.inner.created_at
     }
 
+    /// Genre/category tags such as "rock", "dj" or "open_mic"
+    async fn tags(&self) -> &[String] {
+        &self.inner.tags
+    }
+
+    /// Ticket price as advertised by the source, if it lists one
+    async fn price(&self) -> Option<super::EventPrice> {
+        self.inner.price.clone().map(Into::into)
+    }
+
+    /// Whether the source advertises the event as free
+    async fn is_free(&self) -> bool {
+        self.inner.is_free()
+    }
+
+    /// Who the listing says may attend, if it says
+    async fn age_restriction(&self) -> Option<super::AgeRestriction> {
+        self.inner.age_restriction.map(Into::into)
+    }
+
+    /// Whether the listing says minors may attend
+    async fn is_all_ages(&self) -> bool {
+        self.inner.is_all_ages()
+    }
+
+    /// Accessibility details from the listing, e.g. ASL interpretation or seating
+    async fn accessibility_notes(&self) -> Option<&str> {
+        self.inner.accessibility_notes.as_deref()
+    }
+
+    /// The recurring series this event is an instance of, such as a weekly open mic
+    async fn series(&self, ctx: &Context<'_>) -> FieldResult<Option<super::EventSeries>> {
+        let Some(series_id) = self.inner.series_id else {
+            return Ok(None);
+        };
+        let loaders = ctx.data::<Loaders>()?;
+
+        match loaders.event_series.load_one(series_id).await {
+            Ok(series) => Ok(series.map(Into::into)),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Licenses and credit lines for the sources this event's data came from
+    async fn attributions(&self) -> Vec<super::Attribution> {
+        self.inner.attributions.iter().cloned().map(Into::into).collect()
+    }
+
+    /// The ids the sources listing this event know it by
+    async fn external_ids(&self) -> Vec<super::ExternalId> {
+        self.inner.external_ids.iter().cloned().map(Into::into).collect()
+    }
+
     /// The venue where this event takes place
     async fn venue(&self, ctx: &Context<'_>) -> FieldResult<Option<super::venue::Venue>> {
-        let context = ctx.data::<GraphQLContext>()?;
+        let loaders = ctx.data::<Loaders>()?;
 
         // Use DataLoader to batch venue lookups
-        match context.venue_loader.load_one(self.inner.venue_id).await {
-            Ok(Some(venue)) => Ok(Some(venue.into())),
+        match loaders.venue.load_one(self.inner.venue_id).await {
+            Ok(Some(venue)) if venue_shown_on_event(&venue) => Ok(Some(venue.into())),
+            Ok(Some(_)) => Ok(None),
             Ok(None) => Ok(None),
             Err(e) => Err(e.into()),
         }
@@ -80,10 +140,10 @@ impl Event {
 
     /// Artists performing at this event
     async fn artists(&self, ctx: &Context<'_>) -> FieldResult<Vec<super::artist::Artist>> {
-        let context = ctx.data::<GraphQLContext>()?;
+        let loaders = ctx.data::<Loaders>()?;
         
         // Use DataLoader to batch artist lookups
-        let artists = context.artist_loader.load_many(self.inner.artist_ids.clone()).await
+        let artists = loaders.artist.load_many(self.inner.artist_ids.clone()).await
             .map_err(|e| async_graphql::Error::new(format!("Failed to load artists: {}", e)))?;
         
         // Convert to GraphQL types, preserving order and skipping missing artists
@@ -0,0 +1,23 @@
+use crate::graphql::types::Event;
+use async_graphql::Object;
+use chrono::NaiveDate;
+
+/// One calendar day and the events on it, ordered by start time
+#[derive(Clone)]
+pub struct EventDay {
+    pub day: NaiveDate,
+    pub events: Vec<Event>,
+}
+
+#[Object]
+impl EventDay {
+    /// The calendar date
+    async fn day(&self) -> NaiveDate {
+        self.day
+    }
+
+    /// Events on this day; empty when nothing is scheduled
+    async fn events(&self) -> &[Event] {
+        &self.events
+    }
+}
@@ -0,0 +1,32 @@
+use sms_core::EventPrice as DomainEventPrice;
+use async_graphql::Object;
+
+/// An advertised ticket price; amounts are in minor units (cents) of `currency`
+#[derive(Clone)]
+pub struct EventPrice {
+    pub inner: DomainEventPrice,
+}
+
+impl From<DomainEventPrice> for EventPrice {
+    fn from(price: DomainEventPrice) -> Self {
+        Self { inner: price }
+    }
+}
+
+#[Object]
+impl EventPrice {
+    /// Lowest listed price; 0 for free shows
+    async fn min_cents(&self) -> u32 {
+        self.inner.min_cents
+    }
+
+    /// Highest listed price (e.g. the door price), when it differs from the lowest
+    async fn max_cents(&self) -> Option<u32> {
+        self.inner.max_cents
+    }
+
+    /// ISO 4217 currency code, e.g. "USD"
+    async fn currency(&self) -> &str {
+        &self.inner.currency
+    }
+}
@@ -0,0 +1,86 @@
+use sms_core::{weekday_name, EventSeries as DomainEventSeries};
+use crate::graphql::loaders::Loaders;
+use crate::graphql::visibility::venue_shown_on_event;
+use async_graphql::{Context, FieldResult, Object, ID};
+
+/// An event a venue repeats on a weekly cadence, such as an open mic every Tuesday
+#[derive(Clone)]
+pub struct EventSeries {
+    pub inner: DomainEventSeries,
+}
+
+impl From<DomainEventSeries> for EventSeries {
+    fn from(series: DomainEventSeries) -> Self {
+        Self { inner: series }
+    }
+}
+
+#[Object]
+impl EventSeries {
+    /// The unique identifier for the series
+    async fn id(&self) -> ID {
+        ID(self.inner.id.unwrap_or_default().to_string())
+    }
+
+    /// Title of the most recent instance
+    async fn title(&self) -> &str {
+        &self.inner.title
+    }
+
+    /// Day of the week the series happens on, e.g. "Tuesday"
+    async fn weekday(&self) -> &str {
+        weekday_name(self.inner.weekday)
+    }
+
+    /// Weeks between instances: 1 for weekly, 2 for every other week
+    async fn interval_weeks(&self) -> u32 {
+        self.inner.interval_weeks
+    }
+
+    /// The cadence as display text: "every Tuesday", "every other Tuesday"
+    async fn cadence(&self) -> String {
+        self.inner.cadence()
+    }
+
+    /// The usual start time across instances
+    async fn start_time(&self) -> Option<chrono::NaiveTime> {
+        self.inner.start_time
+    }
+
+    /// Date of the earliest known instance
+    async fn first_day(&self) -> chrono::NaiveDate {
+        self.inner.first_day
+    }
+
+    /// Date of the latest known instance
+    async fn last_day(&self) -> chrono::NaiveDate {
+        self.inner.last_day
+    }
+
+    /// The venue hosting the series
+    async fn venue(&self, ctx: &Context<'_>) -> FieldResult<Option<super::venue::Venue>> {
+        let loaders = ctx.data::<Loaders>()?;
+
+        match loaders.venue.load_one(self.inner.venue_id).await {
+            Ok(Some(venue)) if venue_shown_on_event(&venue) => Ok(Some(venue.into())),
+            Ok(Some(_)) => Ok(None),
+            Ok(None) => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// The series' instances, in date order
+    async fn events(&self, ctx: &Context<'_>) -> FieldResult<Vec<super::event::Event>> {
+        let loaders = ctx.data::<Loaders>()?;
+
+        let mut events: Vec<_> = loaders
+            .event
+            .load_many(self.inner.event_ids.iter().copied())
+            .await?
+            .into_values()
+            .filter(|e| e.show_event)
+            .collect();
+        events.sort_by_key(|e| (e.event_day, e.start_time));
+        Ok(events.into_iter().map(Into::into).collect())
+    }
+}
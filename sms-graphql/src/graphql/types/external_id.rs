@@ -0,0 +1,27 @@
+use sms_core::ExternalId as DomainExternalId;
+use async_graphql::Object;
+
+/// The id a source lists an event under, for tracing it back to the source's own page or API
+#[derive(Clone)]
+pub struct ExternalId {
+    pub inner: DomainExternalId,
+}
+
+impl From<DomainExternalId> for ExternalId {
+    fn from(external_id: DomainExternalId) -> Self {
+        Self { inner: external_id }
+    }
+}
+
+#[Object]
+impl ExternalId {
+    /// The registry identifier of the source
+    async fn source_id(&self) -> &str {
+        &self.inner.source_id
+    }
+
+    /// The source's own id for the event
+    async fn id(&self) -> &str {
+        &self.inner.id
+    }
+}
@@ -0,0 +1,61 @@
+use sms_scraper::app::merge_use_case::MergeOutcome as DomainMergeOutcome;
+use async_graphql::{Object, ID};
+
+/// A merge of two artists or venues as applied and recorded
+#[derive(Clone)]
+pub struct MergeOutcome {
+    pub inner: DomainMergeOutcome,
+}
+
+impl From<DomainMergeOutcome> for MergeOutcome {
+    fn from(outcome: DomainMergeOutcome) -> Self {
+        Self { inner: outcome }
+    }
+}
+
+#[Object]
+impl MergeOutcome {
+    /// "artist" or "venue"
+    async fn entity_type(&self) -> &str {
+        self.inner.entity.as_str()
+    }
+
+    /// The surviving artist or venue
+    async fn kept_id(&self) -> ID {
+        ID(self.inner.kept_id.to_string())
+    }
+
+    async fn kept_name(&self) -> &str {
+        &self.inner.kept_name
+    }
+
+    /// The deleted artist or venue; later scrapes resolve it to `keptId`
+    async fn removed_id(&self) -> ID {
+        ID(self.inner.removed_id.to_string())
+    }
+
+    /// Now one of the kept entity's aliases
+    async fn removed_name(&self) -> &str {
+        &self.inner.removed_name
+    }
+
+    /// Why the curator merged them
+    async fn reason(&self) -> Option<&str> {
+        self.inner.reason.as_deref()
+    }
+
+    /// Events moved to the kept entity
+    async fn events_relinked(&self) -> usize {
+        self.inner.events_relinked
+    }
+
+    /// Recurring series moved to the kept venue
+    async fn series_relinked(&self) -> usize {
+        self.inner.series_relinked
+    }
+
+    /// Process run holding the audit records and every changed entity's previous state
+    async fn audit_run_id(&self) -> ID {
+        ID(self.inner.audit_run_id.to_string())
+    }
+}
@@ -1,7 +1,31 @@
+pub mod age_restriction;
 pub mod artist;
+pub mod attribution;
 pub mod event;
+pub mod event_day;
+pub mod event_price;
+pub mod event_series;
+pub mod external_id;
+pub mod merge;
+pub mod moderation;
+pub mod pipeline_run;
+pub mod provenance;
+pub mod quality;
+pub mod source_status;
 pub mod venue;
 
+pub use age_restriction::AgeRestriction;
 pub use artist::Artist;
+pub use attribution::Attribution;
 pub use event::Event;
-pub use venue::Venue;
+pub use event_day::EventDay;
+pub use event_price::EventPrice;
+pub use event_series::EventSeries;
+pub use external_id::ExternalId;
+pub use merge::MergeOutcome;
+pub use moderation::ModerationOutcome;
+pub use pipeline_run::PipelineRun;
+pub use provenance::Provenance;
+pub use quality::{QualityStats, QuarantinedRecordPage, StatsWindow};
+pub use source_status::SourceStatus;
+pub use venue::{Venue, VenueOrderBy};
@@ -0,0 +1,47 @@
+use sms_scraper::app::moderation_use_case::ModerationOutcome as DomainModerationOutcome;
+use async_graphql::{Object, ID};
+
+/// A moderation decision as applied and recorded
+#[derive(Clone)]
+pub struct ModerationOutcome {
+    pub inner: DomainModerationOutcome,
+}
+
+impl From<DomainModerationOutcome> for ModerationOutcome {
+    fn from(outcome: DomainModerationOutcome) -> Self {
+        Self { inner: outcome }
+    }
+}
+
+#[Object]
+impl ModerationOutcome {
+    /// "event" or "venue"
+    async fn entity_type(&self) -> &str {
+        self.inner.entity.as_str()
+    }
+
+    /// The moderated event or venue
+    async fn entity_id(&self) -> ID {
+        ID(self.inner.entity_id.to_string())
+    }
+
+    /// The event's title or the venue's name
+    async fn name(&self) -> &str {
+        &self.inner.name
+    }
+
+    /// Whether the entity is now hidden from public queries
+    async fn hidden(&self) -> bool {
+        self.inner.hidden
+    }
+
+    /// Why the moderator made the change
+    async fn reason(&self) -> Option<&str> {
+        self.inner.reason.as_deref()
+    }
+
+    /// Process run holding the audit record and the entity's previous state
+    async fn audit_run_id(&self) -> ID {
+        ID(self.inner.audit_run_id.to_string())
+    }
+}
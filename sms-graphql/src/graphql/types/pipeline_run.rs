@@ -0,0 +1,109 @@
+use sms_scraper::observability::run_tracker::StageTiming;
+use sms_scraper::pipeline::ingestion::ingest_meta::RunReportEntry;
+use async_graphql::{Object, SimpleObject, ID};
+use chrono::{DateTime, TimeZone, Utc};
+
+/// GraphQL representation of one persisted pipeline run for a source
+#[derive(Clone)]
+pub struct PipelineRun {
+    pub inner: RunReportEntry,
+}
+
+impl From<RunReportEntry> for PipelineRun {
+    fn from(report: RunReportEntry) -> Self {
+        Self { inner: report }
+    }
+}
+
+/// Time a run spent in one stage
+#[derive(SimpleObject, Clone)]
+pub struct RunStage {
+    /// Stage name, e.g. `parse`, `normalize`, `quality_gate`
+    pub stage: String,
+    /// Times the stage ran (once per raw data item for per-item stages)
+    pub calls: i64,
+    pub duration_ms: i64,
+}
+
+impl From<&StageTiming> for RunStage {
+    fn from(timing: &StageTiming) -> Self {
+        Self { stage: timing.stage.clone(), calls: timing.calls as i64, duration_ms: timing.duration_ms as i64 }
+    }
+}
+
+fn ts_to_utc(ts: i64) -> Option<DateTime<Utc>> {
+    Utc.timestamp_opt(ts, 0).single()
+}
+
+#[Object]
+impl PipelineRun {
+    /// The run id, as in logs and `--json` output
+    async fn id(&self) -> ID {
+        ID(self.inner.run_id.clone())
+    }
+
+    async fn source_id(&self) -> &str {
+        &self.inner.source_id
+    }
+
+    async fn started_at(&self) -> Option<DateTime<Utc>> {
+        ts_to_utc(self.inner.started_at)
+    }
+
+    async fn finished_at(&self) -> Option<DateTime<Utc>> {
+        ts_to_utc(self.inner.finished_at)
+    }
+
+    /// Wall-clock length of the run, to the second
+    async fn duration_ms(&self) -> i64 {
+        (self.inner.finished_at - self.inner.started_at).max(0) * 1000
+    }
+
+    /// Whether the run completed; items can still have failed in a completed run
+    async fn success(&self) -> bool {
+        self.inner.error.is_none()
+    }
+
+    /// Why the run failed outright
+    async fn error(&self) -> Option<&str> {
+        self.inner.error.as_deref()
+    }
+
+    /// Raw data items the run picked up
+    async fn items_total(&self) -> i64 {
+        self.inner.items_total as i64
+    }
+
+    async fn records_parsed(&self) -> i64 {
+        self.inner.records_parsed as i64
+    }
+
+    async fn records_cataloged(&self) -> i64 {
+        self.inner.records_cataloged as i64
+    }
+
+    /// Raw data items that failed to process
+    async fn records_failed(&self) -> i64 {
+        self.inner.records_failed as i64
+    }
+
+    /// Events folded into an already-cataloged duplicate
+    async fn duplicates_suppressed(&self) -> i64 {
+        self.inner.duplicates_suppressed as i64
+    }
+
+    /// Time spent per stage, in the order stages first ran
+    async fn stages(&self) -> Vec<RunStage> {
+        self.inner.stages.iter().map(RunStage::from).collect()
+    }
+
+    /// Per-item errors (the first 50)
+    async fn errors(&self) -> &[String] {
+        &self.inner.errors
+    }
+
+    /// Warnings about the run as a whole, such as payload schema drift
+    async fn notes(&self) -> &[String] {
+        &self.inner.notes
+    }
+}
@@ -0,0 +1,181 @@
+use sms_scraper::pipeline::processing::catalog::provenance::{
+    ConflationLineage, NormalizationLineage, ParsedLineage, QualityLineage, RecordLineage,
+};
+use async_graphql::{Object, ID};
+
+/// GraphQL representation of the pipeline stages behind a cataloged entity
+#[derive(Clone)]
+pub struct Provenance {
+    pub inner: RecordLineage,
+}
+
+impl From<RecordLineage> for Provenance {
+    fn from(lineage: RecordLineage) -> Self {
+        Self { inner: lineage }
+    }
+}
+
+#[Object]
+impl Provenance {
+    /// The canonical entity this lineage belongs to
+    async fn entity_id(&self) -> ID {
+        ID(self.inner.entity_id.to_string())
+    }
+
+    /// The entity type (event, venue, artist)
+    async fn entity_type(&self) -> &str {
+        &self.inner.entity_type
+    }
+
+    /// When the record was written to the catalog
+    async fn cataloged_at(&self) -> chrono::DateTime<chrono::Utc> {
+        self.inner.cataloged_at
+    }
+
+    /// How conflation resolved the record to this entity
+    async fn conflation(&self) -> ConflationStep {
+        ConflationStep { inner: self.inner.conflation.clone() }
+    }
+
+    /// The quality gate assessment the record passed
+    async fn quality(&self) -> QualityStep {
+        QualityStep { inner: self.inner.quality.clone() }
+    }
+
+    /// How the parsed record was normalized
+    async fn normalization(&self) -> NormalizationStep {
+        NormalizationStep { inner: self.inner.normalization.clone() }
+    }
+
+    /// The parsed record, its envelope and the raw payload in the content-addressed store
+    async fn parsed(&self) -> ParsedStep {
+        ParsedStep { inner: self.inner.parsed.clone() }
+    }
+}
+
+/// Conflation stage of a record's lineage
+pub struct ConflationStep {
+    inner: ConflationLineage,
+}
+
+#[Object]
+impl ConflationStep {
+    /// When conflation ran
+    async fn conflated_at(&self) -> chrono::DateTime<chrono::Utc> {
+        self.inner.conflated_at
+    }
+
+    /// Version of the canonical entity after this record
+    async fn entity_version(&self) -> i64 {
+        self.inner.entity_version as i64
+    }
+
+    /// Resolution decision (new_entity, matched_existing, updated_existing, duplicate, uncertain)
+    async fn decision(&self) -> &str {
+        &self.inner.decision
+    }
+
+    /// Confidence in the resolution (0.0 to 1.0)
+    async fn confidence(&self) -> f64 {
+        self.inner.confidence
+    }
+
+    /// The matching strategy used
+    async fn strategy(&self) -> &str {
+        &self.inner.strategy
+    }
+
+    /// Sources that contributed to the canonical entity
+    async fn contributing_sources(&self) -> &[String] {
+        &self.inner.contributing_sources
+    }
+}
+
+/// Quality gate stage of a record's lineage
+pub struct QualityStep {
+    inner: QualityLineage,
+}
+
+#[Object]
+impl QualityStep {
+    /// When the record was assessed
+    async fn assessed_at(&self) -> chrono::DateTime<chrono::Utc> {
+        self.inner.assessed_at
+    }
+
+    /// Gate decision (accept, accept_with_warnings, quarantine)
+    async fn decision(&self) -> &str {
+        &self.inner.decision
+    }
+
+    /// Overall quality score (0.0 to 1.0)
+    async fn score(&self) -> f64 {
+        self.inner.score
+    }
+
+    /// Version of the quality rules applied
+    async fn rule_version(&self) -> &str {
+        &self.inner.rule_version
+    }
+
+    /// Descriptions of the issues found
+    async fn issues(&self) -> &[String] {
+        &self.inner.issues
+    }
+}
+
+/// Normalization stage of a record's lineage
+pub struct NormalizationStep {
+    inner: NormalizationLineage,
+}
+
+#[Object]
+impl NormalizationStep {
+    /// When the record was normalized
+    async fn normalized_at(&self) -> chrono::DateTime<chrono::Utc> {
+        self.inner.normalized_at
+    }
+
+    /// Normalization confidence (0.0 to 1.0)
+    async fn confidence(&self) -> f64 {
+        self.inner.confidence
+    }
+
+    /// The normalization strategy used
+    async fn strategy(&self) -> &str {
+        &self.inner.strategy
+    }
+
+    /// Warnings raised while normalizing
+    async fn warnings(&self) -> &[String] {
+        &self.inner.warnings
+    }
+}
+
+/// Parse stage of a record's lineage, down to the scraped payload
+pub struct ParsedStep {
+    inner: ParsedLineage,
+}
+
+#[Object]
+impl ParsedStep {
+    /// The registry source that was scraped
+    async fn source_id(&self) -> &str {
+        &self.inner.source_id
+    }
+
+    /// The ingest envelope the record was parsed from
+    async fn envelope_id(&self) -> &str {
+        &self.inner.envelope_id
+    }
+
+    /// Reference to the raw payload in the content-addressed store
+    async fn payload_ref(&self) -> &str {
+        &self.inner.payload_ref
+    }
+
+    /// Path to the record within the payload
+    async fn record_path(&self) -> &str {
+        &self.inner.record_path
+    }
+}
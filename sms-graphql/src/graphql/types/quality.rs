@@ -0,0 +1,190 @@
+use sms_scraper::pipeline::processing::quality_gate::outcomes::{
+    QualityStats as DomainQualityStats, QuarantinedRecord as DomainQuarantinedRecord,
+};
+use async_graphql::{Enum, Object, ID};
+
+/// How far back quality stats reach
+#[derive(Enum, Copy, Clone, Debug, PartialEq, Eq)]
+pub enum StatsWindow {
+    Day,
+    Week,
+    Month,
+    All,
+}
+
+impl StatsWindow {
+    /// Start of the window, or `None` for all time
+    pub fn since(&self, now: chrono::DateTime<chrono::Utc>) -> Option<chrono::DateTime<chrono::Utc>> {
+        match self {
+            StatsWindow::Day => Some(now - chrono::Duration::days(1)),
+            StatsWindow::Week => Some(now - chrono::Duration::weeks(1)),
+            StatsWindow::Month => Some(now - chrono::Duration::days(30)),
+            StatsWindow::All => None,
+        }
+    }
+}
+
+/// GraphQL representation of aggregated quality-gate outcomes
+#[derive(Clone)]
+pub struct QualityStats {
+    pub inner: DomainQualityStats,
+}
+
+impl From<DomainQualityStats> for QualityStats {
+    fn from(stats: DomainQualityStats) -> Self {
+        Self { inner: stats }
+    }
+}
+
+#[Object]
+impl QualityStats {
+    /// Records assessed in the window
+    async fn total(&self) -> i64 {
+        self.inner.total as i64
+    }
+
+    /// Records accepted without issues
+    async fn accepted(&self) -> i64 {
+        self.inner.accepted as i64
+    }
+
+    /// Records accepted with warnings
+    async fn accepted_with_warnings(&self) -> i64 {
+        self.inner.accepted_with_warnings as i64
+    }
+
+    /// Records quarantined for review
+    async fn quarantined(&self) -> i64 {
+        self.inner.quarantined as i64
+    }
+
+    /// Share of assessed records that were quarantined (0.0 to 1.0)
+    async fn quarantine_rate(&self) -> f64 {
+        self.inner.quarantine_rate()
+    }
+
+    /// Mean quality score, null when nothing was assessed
+    async fn average_score(&self) -> Option<f64> {
+        self.inner.average_score
+    }
+
+    /// The most frequent issues, most frequent first
+    async fn top_issues(&self) -> Vec<QualityIssueCount> {
+        self.inner
+            .top_issues
+            .iter()
+            .map(|(description, count)| QualityIssueCount { description: description.clone(), count: *count })
+            .collect()
+    }
+}
+
+/// How often one quality issue was reported
+#[derive(Clone)]
+pub struct QualityIssueCount {
+    pub description: String,
+    pub count: u64,
+}
+
+#[Object]
+impl QualityIssueCount {
+    async fn description(&self) -> &str {
+        &self.description
+    }
+
+    async fn count(&self) -> i64 {
+        self.count as i64
+    }
+}
+
+/// GraphQL representation of a record the quality gate quarantined
+#[derive(Clone)]
+pub struct QuarantinedRecord {
+    pub inner: DomainQuarantinedRecord,
+}
+
+impl From<DomainQuarantinedRecord> for QuarantinedRecord {
+    fn from(record: DomainQuarantinedRecord) -> Self {
+        Self { inner: record }
+    }
+}
+
+#[Object]
+impl QuarantinedRecord {
+    /// Opaque cursor for this record, usable as `after`
+    async fn cursor(&self) -> ID {
+        ID(self.inner.id.to_string())
+    }
+
+    /// The source the record was scraped from
+    async fn source_id(&self) -> &str {
+        &self.inner.source_id
+    }
+
+    /// The envelope the record was parsed from
+    async fn envelope_id(&self) -> &str {
+        &self.inner.envelope_id
+    }
+
+    /// Path of the record within the payload
+    async fn record_path(&self) -> &str {
+        &self.inner.record_path
+    }
+
+    /// Stable identity of the record across pipeline stages (`source:envelope:path-hash`)
+    async fn record_key(&self) -> String {
+        self.inner.record_key.to_string()
+    }
+
+    /// The entity type (event, venue, artist)
+    async fn entity_type(&self) -> &str {
+        &self.inner.entity_type
+    }
+
+    /// Quality score (0.0 to 1.0)
+    async fn score(&self) -> f64 {
+        self.inner.score
+    }
+
+    /// The quality rule set version used
+    async fn rule_version(&self) -> &str {
+        &self.inner.rule_version
+    }
+
+    /// Issues that led to the quarantine
+    async fn issues(&self) -> &[String] {
+        &self.inner.issues
+    }
+
+    /// When the record was assessed
+    async fn assessed_at(&self) -> chrono::DateTime<chrono::Utc> {
+        self.inner.assessed_at
+    }
+
+    /// The full assessed record as JSON
+    async fn record_json(&self) -> &str {
+        &self.inner.record_json
+    }
+}
+
+/// One page of quarantined records, newest first
+#[derive(Clone)]
+pub struct QuarantinedRecordPage {
+    pub records: Vec<QuarantinedRecord>,
+    pub has_next_page: bool,
+}
+
+#[Object]
+impl QuarantinedRecordPage {
+    async fn records(&self) -> &[QuarantinedRecord] {
+        &self.records
+    }
+
+    /// Cursor of the last record on this page
+    async fn end_cursor(&self) -> Option<ID> {
+        self.records.last().map(|r| ID(r.inner.id.to_string()))
+    }
+
+    async fn has_next_page(&self) -> bool {
+        self.has_next_page
+    }
+}
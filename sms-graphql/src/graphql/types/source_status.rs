@@ -0,0 +1,57 @@
+use sms_scraper::pipeline::ingestion::source_status::SourceStatus as DomainSourceStatus;
+use async_graphql::Object;
+
+/// GraphQL representation of a source's crawl status
+#[derive(Clone)]
+pub struct SourceStatus {
+    pub inner: DomainSourceStatus,
+}
+
+impl From<DomainSourceStatus> for SourceStatus {
+    fn from(status: DomainSourceStatus) -> Self {
+        Self { inner: status }
+    }
+}
+
+#[Object]
+impl SourceStatus {
+    /// The registry identifier of the source
+    async fn source_id(&self) -> &str {
+        &self.inner.source_id
+    }
+
+    /// When the source was last fetched (successfully or not)
+    async fn last_fetched_at(&self) -> Option<chrono::DateTime<chrono::Utc>> {
+        self.inner.last_fetched_at
+    }
+
+    /// When the source was last fetched successfully
+    async fn last_success_at(&self) -> Option<chrono::DateTime<chrono::Utc>> {
+        self.inner.last_success_at
+    }
+
+    /// Number of failed fetches since the last success
+    async fn consecutive_failures(&self) -> i32 {
+        self.inner.consecutive_failures as i32
+    }
+
+    /// The error from the most recent failed fetch, if still failing
+    async fn last_error(&self) -> Option<&str> {
+        self.inner.last_error.as_deref()
+    }
+
+    /// Envelopes in the ingest log that the parser has not consumed yet
+    async fn envelopes_pending_parse(&self) -> i64 {
+        self.inner.envelopes_pending_parse as i64
+    }
+
+    /// Records cataloged by the most recent pipeline run
+    async fn records_cataloged_last_run(&self) -> Option<i64> {
+        self.inner.records_cataloged_last_run.map(|n| n as i64)
+    }
+
+    /// When the most recent pipeline run finished
+    async fn last_run_finished_at(&self) -> Option<chrono::DateTime<chrono::Utc>> {
+        self.inner.last_run_finished_at
+    }
+}
@@ -1,6 +1,15 @@
 use sms_core::Venue as DomainVenue;
-use crate::graphql::schema::GraphQLContext;
-use async_graphql::{Context, FieldResult, Object, ID};
+use crate::graphql::loaders::Loaders;
+use async_graphql::{Context, Enum, FieldResult, Object, ID};
+
+/// How a venue list is ordered
+#[derive(Enum, Copy, Clone, Debug, PartialEq, Eq)]
+pub enum VenueOrderBy {
+    /// Alphabetically by name
+    Name,
+    /// Most upcoming events first, then by name
+    UpcomingEventCount,
+}
 
 /// GraphQL representation of a Venue
 #[derive(Clone)]
@@ -26,6 +35,11 @@ impl Venue {
         &self.inner.name
     }
 
+    /// Names of venues a curator merged into this one
+    async fn aliases(&self) -> &[String] {
+        &self.inner.aliases
+    }
+
     /// The venue's address
     async fn address(&self) -> &str {
         &self.inner.address
@@ -76,18 +90,61 @@ impl Venue {
         self.inner.show_venue
     }
 
+    /// Whether this is a placeholder for a venue that hasn't been cataloged yet
+    async fn provisional(&self) -> bool {
+        self.inner.provisional
+    }
+
+    /// The venue's standing door policy, if it has one
+    async fn age_restriction(&self) -> Option<super::AgeRestriction> {
+        self.inner.age_restriction.map(Into::into)
+    }
+
+    /// Step-free access, accessible restrooms, seating and the like
+    async fn accessibility_notes(&self) -> Option<&str> {
+        self.inner.accessibility_notes.as_deref()
+    }
+
     /// When the venue was created
     async fn created_at(&self) -> chrono::DateTime<chrono::Utc> {
         self.inner.created_at
     }
 
+    /// Licenses and credit lines for the sources this venue's data came from
+    async fn attributions(&self) -> Vec<super::Attribution> {
+        self.inner.attributions.iter().cloned().map(Into::into).collect()
+    }
+
+    /// Events the venue repeats on a weekly cadence, by weekday
+    async fn recurring_series(&self, ctx: &Context<'_>) -> FieldResult<Vec<super::EventSeries>> {
+        let loaders = ctx.data::<Loaders>()?;
+        let venue_id = self.inner.id.ok_or("Venue ID not available")?;
+
+        match loaders.venue_series.load_one(venue_id).await {
+            Ok(series) => Ok(series.unwrap_or_default().into_iter().map(Into::into).collect()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Number of events at this venue from today on
+    async fn upcoming_events_count(&self, ctx: &Context<'_>) -> FieldResult<u64> {
+        let loaders = ctx.data::<Loaders>()?;
+        let venue_id = self.inner.id.ok_or("Venue ID not available")?;
+
+        // Batched so a venue list costs one aggregated count, not one query per venue
+        match loaders.upcoming_event_count.load_one(venue_id).await {
+            Ok(count) => Ok(count.unwrap_or(0)),
+            Err(e) => Err(e.into()),
+        }
+    }
+
     /// Events happening at this venue
     async fn events(&self, ctx: &Context<'_>) -> FieldResult<Vec<super::event::Event>> {
-        let context = ctx.data::<GraphQLContext>()?;
+        let loaders = ctx.data::<Loaders>()?;
         let venue_id = self.inner.id.ok_or("Venue ID not available")?;
 
-        match context.storage.get_events_by_venue_id(venue_id).await {
-            Ok(events) => Ok(events.into_iter().map(|e| e.into()).collect()),
+        match loaders.venue_events.load_one(venue_id).await {
+            Ok(events) => Ok(events.unwrap_or_default().into_iter().filter(|e| e.show_event).map(|e| e.into()).collect()),
             Err(e) => Err(e.into()),
         }
     }
@@ -0,0 +1,17 @@
+use sms_core::{Event, Venue};
+
+/// Drop events hidden from listings by a moderator or a catalog rollback
+pub fn retain_shown_events(events: &mut Vec<Event>) {
+    events.retain(|e| e.show_event);
+}
+
+/// Drop venues hidden from listings, placeholders for uncataloged venues included
+pub fn retain_shown_venues(venues: &mut Vec<Venue>) {
+    venues.retain(|v| v.show_venue);
+}
+
+/// Whether an event may name `venue` as where it happens. Placeholder venues stay out of
+/// listings but are still named on their events, unless a moderator hid them.
+pub fn venue_shown_on_event(venue: &Venue) -> bool {
+    venue.show_venue || (venue.provisional && venue.moderation.is_none())
+}
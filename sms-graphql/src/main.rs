@@ -1,11 +1,17 @@
-use clap::Parser;
+use clap::{Parser, Subcommand};
+use anyhow::Context;
 use tracing::info;
 use std::sync::Arc;
 
+mod config;
 mod graphql;
 mod server;
 
+use config::AppConfig;
+
 use sms_core::{storage::Storage, storage::DatabaseStorage, database::DatabaseManager};
+use sms_scraper::observability::{metrics, push::PushConfig};
+use sms_scraper::pipeline::ingestion::consumer_lag::{spawn_consumer_lag_monitor, ConsumerLagConfig};
 
 #[derive(Parser)]
 #[command(name = "sms-graphql")]
@@ -15,11 +21,65 @@ struct Cli {
     /// Port to run the server on
     #[arg(short, long, default_value = "8080")]
     port: u16,
+
+    /// Scraper data directory used for crawl status (ingest log, meta.db)
+    #[arg(long, default_value = "data")]
+    data_root: std::path::PathBuf,
+
+    /// Bearer token required for mutations (overrides GRAPHQL_API_TOKEN)
+    #[arg(long)]
+    api_token: Option<String>,
+
+    /// Comma-separated origins allowed by CORS, or `*` (overrides GRAPHQL_ALLOWED_ORIGINS)
+    #[arg(long)]
+    allowed_origins: Option<String>,
+
+    /// Largest accepted request body in bytes (overrides GRAPHQL_MAX_BODY_BYTES)
+    #[arg(long)]
+    max_body_bytes: Option<usize>,
+
+    /// Seconds upcomingEvents results are shared across requests, 0 to disable
+    /// (overrides GRAPHQL_CACHE_TTL_SECS)
+    #[arg(long)]
+    cache_ttl_secs: Option<u64>,
+
+    /// Push metrics to the Pushgateway, failing at startup if none is configured
+    /// (overrides SMS_METRICS_PUSH)
+    #[arg(long, overrides_with = "no_push")]
+    push: bool,
+
+    /// Never push metrics, even when SMS_PUSHGATEWAY_URL is set
+    #[arg(long, overrides_with = "push")]
+    no_push: bool,
+
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+/// Schema maintenance; without a subcommand the server runs
+#[derive(Subcommand)]
+enum Command {
+    /// Write the schema SDL to a file
+    ExportSchema {
+        #[arg(long, default_value = "sms-graphql/schema.graphql")]
+        out: std::path::PathBuf,
+    },
+    /// Fail if the current schema removes or incompatibly changes anything in a saved SDL file
+    CheckSchema {
+        #[arg(long, default_value = "sms-graphql/schema.graphql")]
+        against: std::path::PathBuf,
+    },
 }
 
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
     let cli = Cli::parse();
+
+    match &cli.command {
+        Some(Command::ExportSchema { out }) => return export_schema(out),
+        Some(Command::CheckSchema { against }) => return check_schema(against),
+        None => {}
+    }
     
     // Load environment variables
     dotenv::dotenv().ok();
@@ -27,6 +87,32 @@ async fn main() -> anyhow::Result<()> {
     // Initialize logging
     tracing_subscriber::fmt::init();
 
+    let mut config = AppConfig::from_env();
+    config.port = cli.port;
+    config.data_root = cli.data_root;
+    if let Some(token) = cli.api_token.filter(|t| !t.trim().is_empty()) {
+        config.api_token = Some(token);
+    }
+    if let Some(origins) = cli.allowed_origins {
+        config.allowed_origins = config::split_origins(&origins);
+    }
+    if let Some(bytes) = cli.max_body_bytes {
+        config.max_body_bytes = bytes;
+    }
+    if let Some(secs) = cli.cache_ttl_secs {
+        config.cache_ttl = std::time::Duration::from_secs(secs);
+    }
+    let push_flag = if cli.push { Some(true) } else if cli.no_push { Some(false) } else { None };
+    if let Some(push) = push_flag {
+        config.push = PushConfig::resolve(Some(push), |name| std::env::var(name).ok())
+            .context("Invalid metrics push configuration")?;
+    }
+
+    // Request metrics are served at /metrics
+    if let Err(e) = metrics::init_with_push_config(&config.push, Some("sms_graphql"), None) {
+        tracing::warn!("Metrics disabled: {}", e);
+    }
+
     println!("🚀 Starting SMS GraphQL API server on port {}...", cli.port);
 
     // Initialize database storage
@@ -40,10 +126,38 @@ async fn main() -> anyhow::Result<()> {
     println!("   GraphQL API: http://localhost:{}/graphql", cli.port);
     println!("   GraphiQL UI: http://localhost:{}/graphiql", cli.port);
     println!("   Health check: http://localhost:{}/health", cli.port);
+    println!("   Metrics: http://localhost:{}/metrics", cli.port);
     println!();
 
+    // Keep ingest log consumer lag current on /metrics
+    let _lag = spawn_consumer_lag_monitor(config.data_root.clone(), ConsumerLagConfig::from_env());
+
     // Start the server
-    server::start_server(storage, cli.port).await?;
+    server::start_server(storage, &config).await?;
     
     Ok(())
-}
\ No newline at end of file
+}
+
+fn export_schema(out: &std::path::Path) -> anyhow::Result<()> {
+    if let Some(dir) = out.parent().filter(|d| !d.as_os_str().is_empty()) {
+        std::fs::create_dir_all(dir)?;
+    }
+    std::fs::write(out, graphql::schema::schema_sdl())?;
+    println!("Wrote schema SDL to {}", out.display());
+    Ok(())
+}
+
+fn check_schema(against: &std::path::Path) -> anyhow::Result<()> {
+    let baseline = std::fs::read_to_string(against)
+        .with_context(|| format!("reading {}", against.display()))?;
+    let changes = graphql::schema_check::breaking_changes(&baseline, &graphql::schema::schema_sdl())?;
+    if changes.is_empty() {
+        println!("No breaking changes against {}", against.display());
+        return Ok(());
+    }
+    println!("{} breaking change(s) against {}:", changes.len(), against.display());
+    for change in &changes {
+        println!("   {}", change);
+    }
+    anyhow::bail!("schema has breaking changes; re-export it once consumers are updated")
+}
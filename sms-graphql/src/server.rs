@@ -1,18 +1,75 @@
 use sms_core::storage::Storage;
-use crate::graphql::schema::{create_schema, GraphQLSchema};
+use crate::config::AppConfig;
+use crate::graphql::schema::{create_schema, with_loaders, GraphQLSchema};
 
+use async_graphql::parser::types::{DocumentOperations, OperationType};
 use axum::{
-    response::{Html, IntoResponse, Json},
+    extract::{DefaultBodyLimit, MatchedPath, Request},
+    http::{header, HeaderMap, HeaderValue, Method, StatusCode},
+    middleware::{self, Next},
+    response::{Html, IntoResponse, Json, Response},
     routing::get,
     Extension, Router,
 };
-use std::sync::Arc;
+use sms_scraper::observability::metrics;
+use std::collections::HashSet;
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+use tower_http::cors::{AllowOrigin, Any, CorsLayer};
+
+/// Token mutations must present, shared with the handler
+#[derive(Clone)]
+struct ApiToken(Option<Arc<str>>);
+
+/// Operation a GraphQL response answered, left on the response for the metrics middleware
+#[derive(Clone)]
+struct GraphqlOperation(String);
+
+/// Distinct operation names the metrics label before new ones are counted as `other`
+const MAX_OPERATION_LABELS: usize = 100;
+
+/// Operation names already in use as metric labels. Clients pick operation names freely, so
+/// only the first `cap` distinct ones get a series of their own.
+#[derive(Clone)]
+struct OperationLabels {
+    seen: Arc<Mutex<HashSet<String>>>,
+    cap: usize,
+}
+
+impl OperationLabels {
+    fn new(cap: usize) -> Self {
+        Self { seen: Arc::new(Mutex::new(HashSet::new())), cap }
+    }
+
+    /// The request's `operation_label`, or `other` once the cap is reached with other names
+    fn label(&self, request: &async_graphql::Request) -> String {
+        let label = operation_label(request);
+        if label == "anonymous" || label == "invalid" {
+            return label;
+        }
+        let mut seen = self.seen.lock().unwrap_or_else(|e| e.into_inner());
+        if seen.contains(&label) || seen.len() < self.cap {
+            seen.insert(label.clone());
+            label
+        } else {
+            "other".to_string()
+        }
+    }
+}
 
 /// Health check endpoint
 async fn health() -> impl IntoResponse {
     "OK"
 }
 
+/// Prometheus scrape endpoint for the API's request metrics
+async fn prometheus_metrics() -> Response {
+    match metrics::render() {
+        Some(body) => ([(header::CONTENT_TYPE, "text/plain; version=0.0.4")], body).into_response(),
+        None => (StatusCode::SERVICE_UNAVAILABLE, "metrics recorder not installed").into_response(),
+    }
+}
+
 /// GraphiQL IDE endpoint
 async fn graphiql() -> impl IntoResponse {
     Html(async_graphql::http::GraphiQLSource::build().endpoint("/graphql").finish())
@@ -21,42 +78,251 @@ async fn graphiql() -> impl IntoResponse {
 /// GraphQL endpoint handler
 async fn graphql_handler(
     Extension(schema): Extension<GraphQLSchema>,
+    Extension(token): Extension<ApiToken>,
+    Extension(storage): Extension<Arc<dyn Storage>>,
+    Extension(labels): Extension<OperationLabels>,
+    headers: HeaderMap,
     req: String,
-) -> impl IntoResponse {
+) -> Response {
     let request = match serde_json::from_str::<async_graphql::Request>(&req) {
         Ok(req) => req,
-        Err(_) => return Json(serde_json::json!({"error": "Invalid request"})),
+        Err(_) => return Json(serde_json::json!({"error": "Invalid request"})).into_response(),
     };
-    
-    let response = schema.execute(request).await;
-    Json(serde_json::to_value(response).unwrap_or_default())
+
+    let operation = GraphqlOperation(labels.label(&request));
+    if is_mutation(&request) {
+        if let Err((status, message)) = authorize(&token, &headers) {
+            let mut response = (status, Json(serde_json::json!({"errors": [{"message": message}]}))).into_response();
+            response.extensions_mut().insert(operation);
+            return response;
+        }
+    }
+
+    let response = schema.execute(with_loaders(request, storage)).await;
+    let mut response = Json(serde_json::to_value(response).unwrap_or_default()).into_response();
+    response.extensions_mut().insert(operation);
+    response
+}
+
+/// Metrics label for the request's operation: its name, `anonymous` when unnamed, or
+/// `invalid` for names that aren't plain GraphQL identifiers (keeps label values bounded)
+fn operation_label(request: &async_graphql::Request) -> String {
+    let name = request.operation_name.clone().or_else(|| {
+        let doc = async_graphql::parser::parse_query(&request.query).ok()?;
+        match doc.operations {
+            DocumentOperations::Single(_) => None,
+            DocumentOperations::Multiple(ops) if ops.len() == 1 => ops.keys().next().map(|n| n.to_string()),
+            DocumentOperations::Multiple(_) => None,
+        }
+    });
+    match name {
+        None => "anonymous".to_string(),
+        Some(name) if name.len() <= 64 && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') => name,
+        Some(_) => "invalid".to_string(),
+    }
+}
+
+/// Count and time every request by route, method, status and GraphQL operation
+async fn track_metrics(req: Request, next: Next) -> Response {
+    let started = Instant::now();
+    let route = req
+        .extensions()
+        .get::<MatchedPath>()
+        .map_or_else(|| "unmatched".to_string(), |p| p.as_str().to_string());
+    let method = req.method().clone();
+    let response = next.run(req).await;
+    let operation = response.extensions().get::<GraphqlOperation>().map_or("none", |o| o.0.as_str());
+    metrics::graphql::request(&route, method.as_str(), response.status().as_u16(), operation, started.elapsed().as_secs_f64());
+    response
+}
+
+/// Whether the request would run a mutation. Unparseable queries are left to the schema,
+/// which rejects them without executing anything.
+fn is_mutation(request: &async_graphql::Request) -> bool {
+    let Ok(doc) = async_graphql::parser::parse_query(&request.query) else {
+        return false;
+    };
+    match &doc.operations {
+        DocumentOperations::Single(op) => op.node.ty == OperationType::Mutation,
+        DocumentOperations::Multiple(ops) => match &request.operation_name {
+            Some(name) => ops.get(name.as_str()).is_some_and(|op| op.node.ty == OperationType::Mutation),
+            None => ops.values().any(|op| op.node.ty == OperationType::Mutation),
+        },
+    }
+}
+
+/// Check the `Authorization: Bearer <token>` header against the configured API token
+fn authorize(token: &ApiToken, headers: &HeaderMap) -> Result<(), (StatusCode, &'static str)> {
+    let Some(expected) = token.0.as_deref() else {
+        return Err((StatusCode::FORBIDDEN, "Mutations are disabled: no API token is configured"));
+    };
+    let presented = headers
+        .get(header::AUTHORIZATION)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.strip_prefix("Bearer "))
+        .map(str::trim);
+    match presented {
+        Some(presented) if constant_time_eq(presented.as_bytes(), expected.as_bytes()) => Ok(()),
+        Some(_) => Err((StatusCode::UNAUTHORIZED, "Invalid API token")),
+        None => Err((StatusCode::UNAUTHORIZED, "Mutations require an Authorization: Bearer token")),
+    }
+}
+
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+/// CORS for browser clients: only the configured origins, or any origin for `*`
+fn cors_layer(config: &AppConfig) -> CorsLayer {
+    let origins = if config.allows_any_origin() {
+        AllowOrigin::from(Any)
+    } else {
+        let origins: Vec<HeaderValue> = config
+            .allowed_origins
+            .iter()
+            .filter_map(|o| match HeaderValue::from_str(o) {
+                Ok(value) => Some(value),
+                Err(_) => {
+                    tracing::warn!("Ignoring invalid CORS origin {:?}", o);
+                    None
+                }
+            })
+            .collect();
+        AllowOrigin::list(origins)
+    };
+    CorsLayer::new()
+        .allow_origin(origins)
+        .allow_methods([Method::GET, Method::POST])
+        .allow_headers([header::CONTENT_TYPE, header::AUTHORIZATION])
 }
 
 /// Create the HTTP server router
-pub fn create_server(storage: Arc<dyn Storage>) -> Router {
-    let schema = create_schema(storage.clone());
+pub fn create_server(storage: Arc<dyn Storage>, config: &AppConfig) -> Router {
+    let schema = create_schema(storage.clone(), config.data_root.clone(), config.cache_ttl);
+    let token = ApiToken(config.api_token.as_deref().map(Arc::from));
 
     Router::new()
         .route("/health", get(health))
+        .route("/metrics", get(prometheus_metrics))
         .route("/graphiql", get(graphiql))
         .route(
             "/graphql",
             get(graphiql).post(graphql_handler),
         )
         .layer(Extension(schema))
+        .layer(Extension(token))
+        .layer(Extension(storage))
+        .layer(Extension(OperationLabels::new(MAX_OPERATION_LABELS)))
+        .layer(DefaultBodyLimit::max(config.max_body_bytes))
+        .layer(middleware::from_fn(track_metrics))
+        .layer(cors_layer(config))
 }
 
 /// Start the HTTP server
-pub async fn start_server(storage: Arc<dyn Storage>, port: u16) -> anyhow::Result<()> {
-    let app = create_server(storage);
-    let addr = format!("0.0.0.0:{}", port);
+pub async fn start_server(storage: Arc<dyn Storage>, config: &AppConfig) -> anyhow::Result<()> {
+    let app = create_server(storage, config);
+    let addr = format!("0.0.0.0:{}", config.port);
     
     println!("🚀 HTTP server running on http://{}", addr);
     println!("💚 Health check: http://{}/health", addr);
+    println!("📈 Metrics:      http://{}/metrics", addr);
     println!("🔎 GraphQL:      http://{}/graphql", addr);
     println!("🧪 GraphiQL UI:  http://{}/graphiql", addr);
+    if config.api_token.is_none() {
+        println!("🔒 No API token configured: mutations are disabled (set GRAPHQL_API_TOKEN)");
+    }
 
     let listener = tokio::net::TcpListener::bind(&addr).await?;
     axum::serve(listener, app.into_make_service()).await?;
     Ok(())
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::body::Body;
+    use tower::ServiceExt;
+
+    fn request(query: &str, operation_name: Option<&str>) -> async_graphql::Request {
+        let request = async_graphql::Request::new(query);
+        match operation_name {
+            Some(name) => request.operation_name(name),
+            None => request,
+        }
+    }
+
+    fn bearer(value: &str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(header::AUTHORIZATION, HeaderValue::from_str(value).unwrap());
+        headers
+    }
+
+    #[test]
+    fn mutations_are_recognized_by_the_operation_that_runs() {
+        assert!(is_mutation(&request("mutation { hideEvent(id: 1) }", None)));
+        assert!(!is_mutation(&request("{ events { id } }", None)));
+        assert!(!is_mutation(&request("query Q { events { id } }", None)));
+        assert!(!is_mutation(&request("not graphql", None)));
+
+        let both = "query List { events { id } } mutation Hide { hideEvent(id: 1) }";
+        assert!(!is_mutation(&request(both, Some("List"))));
+        assert!(is_mutation(&request(both, Some("Hide"))));
+        // Without a name the schema refuses to pick one, but the request is still treated as writing
+        assert!(is_mutation(&request(both, None)));
+    }
+
+    #[test]
+    fn mutations_need_the_configured_bearer_token() {
+        let token = ApiToken(Some(Arc::from("s3cret")));
+        assert!(authorize(&token, &bearer("Bearer s3cret")).is_ok());
+        assert!(authorize(&token, &bearer("Bearer  s3cret ")).is_ok());
+        assert_eq!(authorize(&token, &bearer("Bearer wrong")).unwrap_err().0, StatusCode::UNAUTHORIZED);
+        assert_eq!(authorize(&token, &bearer("Basic s3cret")).unwrap_err().0, StatusCode::UNAUTHORIZED);
+        assert_eq!(authorize(&token, &HeaderMap::new()).unwrap_err().0, StatusCode::UNAUTHORIZED);
+        assert_eq!(authorize(&ApiToken(None), &bearer("Bearer s3cret")).unwrap_err().0, StatusCode::FORBIDDEN);
+    }
+
+    async fn allowed_origin(config: &AppConfig, origin: &str) -> Option<String> {
+        let app = Router::new().route("/graphql", get(health)).layer(cors_layer(config));
+        let preflight = Request::builder()
+            .method(Method::OPTIONS)
+            .uri("/graphql")
+            .header(header::ORIGIN, origin)
+            .header(header::ACCESS_CONTROL_REQUEST_METHOD, "POST")
+            .body(Body::empty())
+            .unwrap();
+        let response = app.oneshot(preflight).await.unwrap();
+        response
+            .headers()
+            .get(header::ACCESS_CONTROL_ALLOW_ORIGIN)
+            .map(|v| v.to_str().unwrap().to_string())
+    }
+
+    #[test]
+    fn operation_labels_are_plain_names_and_capped() {
+        assert_eq!(operation_label(&request("query Events { events { id } }", None)), "Events");
+        assert_eq!(operation_label(&request("{ events { id } }", None)), "anonymous");
+        assert_eq!(operation_label(&request("{ events { id } }", Some("Named"))), "Named");
+        assert_eq!(operation_label(&request("{ events { id } }", Some("drop table;"))), "invalid");
+        assert_eq!(operation_label(&request("{ events { id } }", Some(&"A".repeat(65)))), "invalid");
+
+        let labels = OperationLabels::new(2);
+        let named = |name: &str| labels.label(&request("{ events { id } }", Some(name)));
+        assert_eq!((named("One"), named("Two"), named("Three")), ("One".into(), "Two".into(), "other".into()));
+        assert_eq!(named("One"), "One");
+        assert_eq!(labels.label(&request("{ events { id } }", None)), "anonymous");
+    }
+
+    #[tokio::test]
+    async fn cors_allows_only_configured_origins() {
+        let config = AppConfig {
+            allowed_origins: vec!["https://app.example".to_string(), "bad\norigin".to_string()],
+            ..AppConfig::default()
+        };
+        assert_eq!(allowed_origin(&config, "https://app.example").await.as_deref(), Some("https://app.example"));
+        assert_eq!(allowed_origin(&config, "https://evil.example").await, None);
+        assert_eq!(allowed_origin(&AppConfig::default(), "https://app.example").await, None);
+
+        let any = AppConfig { allowed_origins: vec!["*".to_string()], ..AppConfig::default() };
+        assert_eq!(allowed_origin(&any, "https://evil.example").await.as_deref(), Some("*"));
+    }
+}
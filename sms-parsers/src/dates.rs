@@ -0,0 +1,136 @@
+//! Event days from listing text that leaves out the year ("Dec 31", "MUSIC 7.12").
+
+use chrono::{Datelike, Duration, NaiveDate, Utc};
+use chrono_tz::Tz;
+
+/// How far back a listing may still show a date: calendars run forward from today, with
+/// recent shows sometimes left up, so a date further back than this belongs to next year
+pub const LOOKBACK_DAYS: i64 = 90;
+
+/// Month names by language: each month's full name and the abbreviations sites use
+const MONTHS_EN: [&[&str]; 12] = [
+    &["january", "jan"], &["february", "feb"], &["march", "mar"], &["april", "apr"],
+    &["may"], &["june", "jun"], &["july", "jul"], &["august", "aug"],
+    &["september", "sep", "sept"], &["october", "oct"], &["november", "nov"], &["december", "dec"],
+];
+const MONTHS_ES: [&[&str]; 12] = [
+    &["enero", "ene"], &["febrero", "feb"], &["marzo", "mar"], &["abril", "abr"],
+    &["mayo", "may"], &["junio", "jun"], &["julio", "jul"], &["agosto", "ago"],
+    &["septiembre", "setiembre", "sep", "sept", "set"], &["octubre", "oct"], &["noviembre", "nov"], &["diciembre", "dic"],
+];
+const MONTHS_FR: [&[&str]; 12] = [
+    &["janvier", "janv", "jan"], &["février", "fevrier", "févr", "fevr", "fév", "fev"], &["mars", "mar"], &["avril", "avr"],
+    &["mai"], &["juin"], &["juillet", "juil"], &["août", "aout"],
+    &["septembre", "sept", "sep"], &["octobre", "oct"], &["novembre", "nov"], &["décembre", "decembre", "déc", "dec"],
+];
+const MONTHS_DE: [&[&str]; 12] = [
+    &["januar", "jänner", "jan", "jän"], &["februar", "feb"], &["märz", "maerz", "mär", "mrz"], &["april", "apr"],
+    &["mai"], &["juni", "jun"], &["juli", "jul"], &["august", "aug"],
+    &["september", "sep", "sept"], &["oktober", "okt"], &["november", "nov"], &["dezember", "dez"],
+];
+
+/// A source's time zone and locale, which decide what "today" is when a listing's dates
+/// carry no year and what its month names mean
+#[derive(Debug, Clone, PartialEq)]
+pub struct DateHints {
+    pub timezone: Tz,
+    /// BCP 47 tag such as "en-US"; only the language is used
+    pub locale: String,
+    /// Day to resolve years against instead of today in `timezone`
+    pub reference: Option<NaiveDate>,
+}
+
+impl Default for DateHints {
+    fn default() -> Self {
+        Self { timezone: Tz::UTC, locale: "en".to_string(), reference: None }
+    }
+}
+
+impl DateHints {
+    pub fn new(timezone: Tz, locale: impl Into<String>) -> Self {
+        Self { timezone, locale: locale.into(), reference: None }
+    }
+
+    /// Resolve years against `day` rather than today, e.g. the day a payload was captured
+    pub fn with_reference(mut self, day: NaiveDate) -> Self {
+        self.reference = Some(day);
+        self
+    }
+
+    /// The day years are resolved against
+    pub fn today(&self) -> NaiveDate {
+        self.reference
+            .unwrap_or_else(|| Utc::now().with_timezone(&self.timezone).date_naive())
+    }
+
+    /// Month number for a name or abbreviation in the source's language ("Dec", "dic.",
+    /// "Décembre"), falling back to English for languages without a table
+    pub fn month(&self, name: &str) -> Option<u32> {
+        let name = name.trim().trim_end_matches('.').to_lowercase();
+        let language = self.locale.split(['-', '_']).next().unwrap_or("en").to_lowercase();
+        let table = match language.as_str() {
+            "es" => &MONTHS_ES,
+            "fr" => &MONTHS_FR,
+            "de" => &MONTHS_DE,
+            _ => &MONTHS_EN,
+        };
+        table
+            .iter()
+            .position(|names| names.contains(&name.as_str()))
+            .map(|index| index as u32 + 1)
+    }
+
+    /// The day `month`/`day` falls on: the earliest year that puts it no more than
+    /// `LOOKBACK_DAYS` before today, so a December calendar's "Jan 3" lands in the new year
+    /// and a January calendar's "Dec 30" stays in the old one
+    pub fn resolve(&self, month: u32, day: u32) -> Option<NaiveDate> {
+        let today = self.today();
+        let earliest = today - Duration::days(LOOKBACK_DAYS);
+        (today.year() - 1..=today.year() + 1)
+            .filter_map(|year| NaiveDate::from_ymd_opt(year, month, day))
+            .find(|date| *date >= earliest)
+    }
+
+    /// `resolve` for a month given by name
+    pub fn resolve_named(&self, month: &str, day: u32) -> Option<NaiveDate> {
+        self.resolve(self.month(month)?, day)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn on(day: &str) -> DateHints {
+        DateHints::new(chrono_tz::America::Los_Angeles, "en-US").with_reference(day.parse().unwrap())
+    }
+
+    #[test]
+    fn years_roll_over_at_the_turn_of_the_year() {
+        assert_eq!(on("2025-12-20").resolve(1, 3), NaiveDate::from_ymd_opt(2026, 1, 3));
+        assert_eq!(on("2026-01-10").resolve(12, 30), NaiveDate::from_ymd_opt(2025, 12, 30));
+        assert_eq!(on("2026-06-01").resolve(6, 15), NaiveDate::from_ymd_opt(2026, 6, 15));
+        // Further back than the lookback is next year's show
+        assert_eq!(on("2026-06-01").resolve(2, 1), NaiveDate::from_ymd_opt(2027, 2, 1));
+        assert_eq!(on("2026-06-01").resolve(2, 30), None);
+    }
+
+    #[test]
+    fn month_names_follow_the_locale() {
+        let hints = |locale: &str| DateHints::new(Tz::UTC, locale);
+        assert_eq!(hints("en-US").month("Sept."), Some(9));
+        assert_eq!(hints("es-MX").month("dic."), Some(12));
+        assert_eq!(hints("fr-FR").month("Févr"), Some(2));
+        assert_eq!(hints("de_DE").month("Mär"), Some(3));
+        assert_eq!(hints("es-MX").month("Dec"), None);
+        // Languages without a table read English
+        assert_eq!(hints("pt-BR").month("Aug"), Some(8));
+        assert_eq!(on("2025-12-20").resolve_named("Jan", 3), NaiveDate::from_ymd_opt(2026, 1, 3));
+    }
+
+    #[test]
+    fn today_is_the_reference_day_when_given() {
+        assert_eq!(on("2026-03-01").today(), NaiveDate::from_ymd_opt(2026, 3, 1).unwrap());
+        assert_eq!(DateHints::default().locale, "en");
+    }
+}
@@ -0,0 +1,348 @@
+// Parses a public Google Calendar, either its ICS feed
+// (`calendar.google.com/calendar/ical/<id>/public/basic.ics`, no key needed) or a Calendar API
+// events list (`googleapis.com/calendar/v3/calendars/<id>/events`, public calendars with an
+// API key). Both come out as the same flat records, tagged `"format": "google_calendar"`.
+//
+// Recurring events are only expanded by the API (`singleEvents=true`); an ICS feed yields the
+// first occurrence of each RRULE series.
+
+use chrono::{DateTime, NaiveDate, NaiveDateTime, TimeZone, Utc};
+use chrono_tz::Tz;
+use serde_json::{json, Value};
+use tracing::{debug, info};
+
+use crate::{ParsedRecord, Parser};
+
+/// `format` of every record this parser emits, which normalizers key off
+pub const GOOGLE_CALENDAR_FORMAT: &str = "google_calendar";
+
+pub struct GoogleCalendarV1Parser {
+    pub source_id: String,
+    pub envelope_id: String,
+    pub payload_ref: String,
+    /// Time zone for calendars that don't name one; event times are read in the calendar's zone
+    pub dates: crate::DateHints,
+}
+
+impl GoogleCalendarV1Parser {
+    pub fn new(source_id: String, envelope_id: String, payload_ref: String, dates: crate::DateHints) -> Self {
+        Self {
+            source_id,
+            envelope_id,
+            payload_ref,
+            dates,
+        }
+    }
+
+    fn record(&self, path: &str, calendar: Option<&str>, event: CalendarEvent) -> ParsedRecord {
+        let mut record = json!({
+            "format": GOOGLE_CALENDAR_FORMAT,
+            "title": event.title,
+            "event_day": event.day.to_string(),
+            "status": event.status.to_ascii_lowercase(),
+        });
+        let optional = [
+            ("id", event.id.as_deref()),
+            ("calendar", calendar),
+            ("description", event.description.as_deref()),
+            ("location", event.location.as_deref()),
+            ("event_url", event.url.as_deref()),
+        ];
+        for (key, value) in optional {
+            if let Some(value) = value.map(str::trim).filter(|v| !v.is_empty()) {
+                record[key] = json!(value);
+            }
+        }
+        if let Some(time) = event.time {
+            record["start_time"] = json!(time.format("%H:%M:%S").to_string());
+        }
+        ParsedRecord {
+            source_id: self.source_id.clone(),
+            envelope_id: self.envelope_id.clone(),
+            payload_ref: self.payload_ref.clone(),
+            record_path: path.to_string(),
+            external_id: crate::ids::from_json(record.get("id")),
+            record,
+            attribution: None,
+            change: None,
+            endpoint_id: None,
+        }
+    }
+
+    fn parse_api(&self, v: &Value) -> anyhow::Result<Vec<ParsedRecord>> {
+        let items = v
+            .get("items")
+            .and_then(|i| i.as_array())
+            .ok_or_else(|| anyhow::anyhow!("Invalid Google Calendar API response: no items"))?;
+        let calendar = v.get("summary").and_then(|s| s.as_str());
+        let zone = zone(v.get("timeZone").and_then(|z| z.as_str())).unwrap_or(self.dates.timezone);
+        info!("GoogleCalendarV1Parser: found API items count={}", items.len());
+
+        let mut out = Vec::new();
+        for item in items {
+            let text = |key: &str| item.get(key).and_then(|v| v.as_str()).map(str::to_string);
+            let start = item.get("start");
+            let start_text = |key: &str| start.and_then(|s| s.get(key)).and_then(|v| v.as_str());
+            let when = match (start_text("dateTime"), start_text("date")) {
+                (Some(at), _) => DateTime::parse_from_rfc3339(at)
+                    .ok()
+                    .map(|at| at.with_timezone(&zone).naive_local())
+                    .map(|at| (at.date(), Some(at.time()))),
+                (None, Some(day)) => NaiveDate::parse_from_str(day, "%Y-%m-%d").ok().map(|day| (day, None)),
+                (None, None) => None,
+            };
+            let (Some(title), Some((day, time))) = (text("summary"), when) else {
+                debug!("GoogleCalendarV1Parser: skipping item without a title or start");
+                continue;
+            };
+            let event = CalendarEvent {
+                id: text("id"),
+                title,
+                description: text("description"),
+                location: text("location"),
+                url: text("htmlLink"),
+                status: text("status").unwrap_or_else(|| "confirmed".to_string()),
+                day,
+                time,
+            };
+            out.push(self.record("$.items[*]", calendar, event));
+        }
+        Ok(out)
+    }
+
+    fn parse_ics(&self, text: &str) -> Vec<ParsedRecord> {
+        let mut calendar: Option<String> = None;
+        let mut zone_name: Option<String> = None;
+        let mut events: Vec<Vec<Property>> = Vec::new();
+        // Components we're inside; a VEVENT's own properties are the ones read at its depth
+        let mut stack: Vec<String> = Vec::new();
+        for line in unfold(text) {
+            let Some(property) = Property::parse(&line) else { continue };
+            match property.name.as_str() {
+                "BEGIN" => {
+                    if property.value.eq_ignore_ascii_case("VEVENT") {
+                        events.push(Vec::new());
+                    }
+                    stack.push(property.value.to_ascii_uppercase());
+                }
+                "END" => {
+                    stack.pop();
+                }
+                "X-WR-CALNAME" if stack.len() == 1 => calendar = Some(unescape(&property.value)),
+                "X-WR-TIMEZONE" if stack.len() == 1 => zone_name = Some(property.value),
+                _ if stack.last().is_some_and(|c| c == "VEVENT") => {
+                    if let Some(event) = events.last_mut() {
+                        event.push(property);
+                    }
+                }
+                _ => {}
+            }
+        }
+        let zone = zone(zone_name.as_deref()).unwrap_or(self.dates.timezone);
+        info!("GoogleCalendarV1Parser: found ICS events count={}", events.len());
+
+        let mut out = Vec::new();
+        for properties in events {
+            let get = |name: &str| properties.iter().find(|p| p.name == name);
+            let text = |name: &str| get(name).map(|p| unescape(&p.value));
+            let (Some(title), Some((day, time))) = (text("SUMMARY"), get("DTSTART").and_then(|p| ics_start(p, zone))) else {
+                debug!("GoogleCalendarV1Parser: skipping VEVENT without a SUMMARY or DTSTART");
+                continue;
+            };
+            let event = CalendarEvent {
+                id: text("UID"),
+                title,
+                description: text("DESCRIPTION"),
+                location: text("LOCATION"),
+                url: text("URL"),
+                status: text("STATUS").unwrap_or_else(|| "confirmed".to_string()),
+                day,
+                time,
+            };
+            out.push(self.record("VCALENDAR.VEVENT[*]", calendar.as_deref(), event));
+        }
+        out
+    }
+}
+
+impl Parser for GoogleCalendarV1Parser {
+    fn parse(&self, bytes: &[u8]) -> anyhow::Result<Vec<ParsedRecord>> {
+        self.parse_reader(&mut &*bytes)
+    }
+
+    fn parse_reader(&self, reader: &mut dyn std::io::BufRead) -> anyhow::Result<Vec<ParsedRecord>> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes)?;
+        debug!("GoogleCalendarV1Parser: start bytes_len={}", bytes.len());
+        let text = String::from_utf8_lossy(&bytes);
+        let body = text.trim_start_matches('\u{feff}').trim_start();
+        if body.starts_with("BEGIN:VCALENDAR") {
+            Ok(self.parse_ics(body))
+        } else {
+            self.parse_api(&serde_json::from_str(body)?)
+        }
+    }
+}
+
+/// One event as both formats describe it, start already in the calendar's local time
+struct CalendarEvent {
+    id: Option<String>,
+    title: String,
+    description: Option<String>,
+    location: Option<String>,
+    url: Option<String>,
+    status: String,
+    day: NaiveDate,
+    /// `None` for all-day events
+    time: Option<chrono::NaiveTime>,
+}
+
+/// An ICS content line: `NAME;PARAM=value:VALUE`
+struct Property {
+    name: String,
+    params: Vec<(String, String)>,
+    value: String,
+}
+
+impl Property {
+    fn parse(line: &str) -> Option<Self> {
+        // The value starts at the first colon outside a quoted parameter value
+        let mut quoted = false;
+        let split = line.char_indices().find_map(|(i, c)| match c {
+            '"' => {
+                quoted = !quoted;
+                None
+            }
+            ':' if !quoted => Some(i),
+            _ => None,
+        })?;
+        let (head, value) = (&line[..split], &line[split + 1..]);
+        let mut parts = head.split(';');
+        let name = parts.next()?.trim().to_ascii_uppercase();
+        let params = parts
+            .filter_map(|p| p.split_once('='))
+            .map(|(k, v)| (k.trim().to_ascii_uppercase(), v.trim_matches('"').to_string()))
+            .collect();
+        Some(Self { name, params, value: value.to_string() })
+    }
+
+    fn param(&self, name: &str) -> Option<&str> {
+        self.params.iter().find(|(k, _)| k == name).map(|(_, v)| v.as_str())
+    }
+}
+
+/// Join folded ICS lines (continuations start with a space or tab)
+fn unfold(text: &str) -> Vec<String> {
+    let mut lines: Vec<String> = Vec::new();
+    for line in text.split('\n') {
+        let line = line.strip_suffix('\r').unwrap_or(line);
+        match (line.strip_prefix([' ', '\t']), lines.last_mut()) {
+            (Some(rest), Some(last)) => last.push_str(rest),
+            _ => lines.push(line.to_string()),
+        }
+    }
+    lines
+}
+
+/// Undo ICS TEXT escaping
+fn unescape(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            out.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n' | 'N') => out.push('\n'),
+            Some(other) => out.push(other),
+            None => out.push('\\'),
+        }
+    }
+    out
+}
+
+/// Local day and time of a DTSTART: a date, a UTC time (`Z`), or a local time in its TZID
+/// or else the calendar's zone
+fn ics_start(property: &Property, calendar_zone: Tz) -> Option<(NaiveDate, Option<chrono::NaiveTime>)> {
+    let value = property.value.trim();
+    if property.param("VALUE") == Some("DATE") || value.len() == 8 {
+        return NaiveDate::parse_from_str(value, "%Y%m%d").ok().map(|day| (day, None));
+    }
+    let local = if let Some(utc) = value.strip_suffix('Z') {
+        let at = NaiveDateTime::parse_from_str(utc, "%Y%m%dT%H%M%S").ok()?;
+        Utc.from_utc_datetime(&at).with_timezone(&calendar_zone).naive_local()
+    } else {
+        let at = NaiveDateTime::parse_from_str(value, "%Y%m%dT%H%M%S").ok()?;
+        match zone(property.param("TZID")) {
+            // Times in a named zone are shown in the calendar's
+            Some(tz) => tz.from_local_datetime(&at).earliest()?.with_timezone(&calendar_zone).naive_local(),
+            None => at,
+        }
+    };
+    Some((local.date(), Some(local.time())))
+}
+
+fn zone(name: Option<&str>) -> Option<Tz> {
+    name.and_then(|n| n.trim().parse().ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parser(dates: crate::DateHints) -> GoogleCalendarV1Parser {
+        GoogleCalendarV1Parser::new("ballard_corner_bar".into(), "env-1".into(), "cas:sha256:abcd".into(), dates)
+    }
+
+    #[test]
+    fn feeds_and_api_responses_parse_alike() {
+        let ics = "BEGIN:VCALENDAR\r\nX-WR-CALNAME:Ballard Corner Bar\r\nX-WR-TIMEZONE:America/Los_Angeles\r\n\
+BEGIN:VEVENT\r\nUID:abc123@google.com\r\nDTSTART:20250502T030000Z\r\nSUMMARY:The Dip\\, live\r\n\
+DESCRIPTION:Doors at 7\\nAll ages\r\nLOCATION:Ballard Corner Bar\\, 5300 Ballard Ave NW\\, Seattle\r\n\
+BEGIN:VALARM\r\nDESCRIPTION:Reminder\r\nEND:VALARM\r\nEND:VEVENT\r\n\
+BEGIN:VEVENT\r\nUID:def456@google.com\r\nDTSTART;VALUE=DATE:20250503\r\nSUMMARY:Closed for a priv\r\n ate event\r\n\
+STATUS:CANCELLED\r\nEND:VEVENT\r\nEND:VCALENDAR\r\n";
+        let parser = parser(crate::DateHints::default());
+        let records = parser.parse(ics.as_bytes()).unwrap();
+        // Read a few bytes at a time, the way the parse step streams a payload
+        let streamed = parser.parse_reader(&mut std::io::BufReader::with_capacity(7, ics.as_bytes())).unwrap();
+        assert_eq!(serde_json::to_value(&streamed).unwrap(), serde_json::to_value(&records).unwrap());
+        assert_eq!(
+            records.iter().map(|r| r.external_id.as_deref()).collect::<Vec<_>>(),
+            [Some("abc123@google.com"), Some("def456@google.com")]
+        );
+        let from_ics: Vec<_> = records.into_iter().map(|r| r.record).collect();
+        assert_eq!(from_ics.len(), 2);
+        // 03:00 UTC is the evening before in the calendar's zone
+        assert_eq!(
+            (from_ics[0]["title"].as_str(), from_ics[0]["event_day"].as_str(), from_ics[0]["start_time"].as_str()),
+            (Some("The Dip, live"), Some("2025-05-01"), Some("20:00:00"))
+        );
+        assert_eq!(from_ics[0]["description"], "Doors at 7\nAll ages");
+        assert_eq!(from_ics[0]["location"], "Ballard Corner Bar, 5300 Ballard Ave NW, Seattle");
+        assert_eq!(from_ics[0]["calendar"], "Ballard Corner Bar");
+        assert_eq!(from_ics[1]["title"], "Closed for a private event");
+        assert_eq!((from_ics[1]["status"].as_str(), from_ics[1].get("start_time")), (Some("cancelled"), None));
+
+        let api = br#"{"kind":"calendar#events","summary":"Ballard Corner Bar","timeZone":"America/Los_Angeles","items":[
+            {"id":"abc123","status":"confirmed","summary":"The Dip, live","htmlLink":"https://www.google.com/calendar/event?eid=abc",
+             "location":"Ballard Corner Bar, 5300 Ballard Ave NW, Seattle","start":{"dateTime":"2025-05-02T03:00:00Z"}},
+            {"id":"def456","status":"cancelled","summary":"Closed for a private event","start":{"date":"2025-05-03"}}]}"#;
+        let from_api: Vec<_> = parser.parse(api).unwrap().into_iter().map(|r| r.record).collect();
+        for (api, ics) in from_api.iter().zip(&from_ics) {
+            for key in ["title", "event_day", "start_time", "location", "status", "calendar"] {
+                assert_eq!(api.get(key), ics.get(key), "{}", key);
+            }
+        }
+        assert_eq!(from_api[0]["event_url"], "https://www.google.com/calendar/event?eid=abc");
+    }
+
+    #[test]
+    fn calendars_without_a_zone_are_read_in_the_sources() {
+        let utc_times = "BEGIN:VCALENDAR\nBEGIN:VEVENT\nDTSTART:20250502T030000Z\nSUMMARY:Late\nEND:VEVENT\nEND:VCALENDAR\n";
+        let parser = parser(crate::DateHints::new(chrono_tz::America::Los_Angeles, "en-US"));
+        let records = parser.parse(utc_times.as_bytes()).unwrap();
+        assert_eq!(records[0].record["event_day"], "2025-05-01");
+    }
+}
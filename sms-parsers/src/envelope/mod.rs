@@ -0,0 +1,887 @@
+// Parsers for gateway envelopes, producing one ParsedRecord per event found in the payload
+
+use crate::{ParsedRecord, Parser};
+
+pub mod google_calendar;
+pub mod newsletter;
+pub mod venuepilot_graphql;
+pub use google_calendar::{GoogleCalendarV1Parser, GOOGLE_CALENDAR_FORMAT};
+pub use newsletter::{NewsletterEmailV1Parser, NEWSLETTER_FORMAT};
+pub use venuepilot_graphql::VenuePilotGraphQLV1Parser;
+
+pub struct WixCalendarV1Parser {
+    pub source_id: String,
+    pub envelope_id: String,
+    pub payload_ref: String,
+}
+
+impl WixCalendarV1Parser {
+    pub fn new(source_id: String, envelope_id: String, payload_ref: String) -> Self {
+        Self {
+            source_id,
+            envelope_id,
+            payload_ref,
+        }
+    }
+}
+
+impl Parser for WixCalendarV1Parser {
+    fn parse(&self, bytes: &[u8]) -> anyhow::Result<Vec<ParsedRecord>> {
+        tracing::debug!("WixCalendarV1Parser: start bytes_len={}", bytes.len());
+        self.parse_reader(&mut &*bytes)
+    }
+
+    fn parse_reader(&self, reader: &mut dyn std::io::BufRead) -> anyhow::Result<Vec<ParsedRecord>> {
+        use tracing::{info, warn};
+        
+        // The payload for Blue Moon is often a JSON with `eventsByDates` mapping; also support plain `events`.
+        let v: serde_json::Value = serde_json::from_reader(reader)?;
+        let mut out = Vec::new();
+
+        if let Some(events) = v.get("events").and_then(|e| e.as_array()) {
+            info!(
+                "WixCalendarV1Parser: found events array count={}",
+                events.len()
+            );
+            for ev in events {
+                out.push(ParsedRecord {
+                    source_id: self.source_id.clone(),
+                    envelope_id: self.envelope_id.clone(),
+                    payload_ref: self.payload_ref.clone(),
+                    record_path: "$.events[*]".to_string(),
+                    record: ev.clone(),
+                    attribution: None,
+                    change: None,
+                    endpoint_id: None,
+                    external_id: crate::ids::from_json(ev.get("id")),
+                });
+            }
+            return Ok(out);
+        }
+
+        if let Some(obj) = v.get("eventsByDates").and_then(|e| e.as_object()) {
+            let mut total = 0usize;
+            for (day, events_val) in obj {
+                if let Some(arr) = events_val.as_array() {
+                    total += arr.len();
+                    for ev in arr {
+                        let mut ev_clone = ev.clone();
+                        // Inject event_day for downstream convenience (as apis/blue_moon does)
+                        ev_clone["event_day"] = serde_json::Value::String(day.clone());
+                        out.push(ParsedRecord {
+                            source_id: self.source_id.clone(),
+                            envelope_id: self.envelope_id.clone(),
+                            payload_ref: self.payload_ref.clone(),
+                            record_path: format!("$.eventsByDates.{}[*]", day),
+                            record: ev_clone,
+                            attribution: None,
+                            change: None,
+                            endpoint_id: None,
+                            external_id: crate::ids::from_json(ev.get("id")),
+                        });
+                    }
+                }
+            }
+            info!(
+                "WixCalendarV1Parser: aggregated events from eventsByDates total={}",
+                total
+            );
+            return Ok(out);
+        }
+
+        warn!("WixCalendarV1Parser: neither 'events' nor 'eventsByDates' found; emitting fallback record");
+        // Fallback: emit entire doc as a single record for visibility
+        out.push(ParsedRecord {
+            source_id: self.source_id.clone(),
+            envelope_id: self.envelope_id.clone(),
+            payload_ref: self.payload_ref.clone(),
+            record_path: "$".to_string(),
+            record: v,
+            attribution: None,
+            change: None,
+            endpoint_id: None,
+            external_id: None,
+        });
+        Ok(out)
+    }
+}
+
+// Parses HTML pages that embed a Wix warmup JSON under a script tag with id 'wix-warmup-data'.
+pub struct WixWarmupV1Parser {
+    pub source_id: String,
+    pub envelope_id: String,
+    pub payload_ref: String,
+}
+
+impl WixWarmupV1Parser {
+    pub fn new(source_id: String, envelope_id: String, payload_ref: String) -> Self {
+        Self {
+            source_id,
+            envelope_id,
+            payload_ref,
+        }
+    }
+}
+
+impl Parser for WixWarmupV1Parser {
+    fn parse(&self, bytes: &[u8]) -> anyhow::Result<Vec<ParsedRecord>> {
+        self.parse_reader(&mut &*bytes)
+    }
+
+    fn parse_reader(&self, reader: &mut dyn std::io::BufRead) -> anyhow::Result<Vec<ParsedRecord>> {
+        use scraper::Selector;
+        use tracing::{debug, info, warn};
+        let (document, html_len) = crate::read_html(reader)?;
+        debug!("WixWarmupV1Parser: start bytes_len={}", html_len);
+        let selector =
+            Selector::parse("script[type=\"application/json\"]#wix-warmup-data").unwrap();
+
+        let mut out = Vec::new();
+        if let Some(element) = document.select(&selector).next() {
+            let json_text = element.inner_html();
+            let data: serde_json::Value = serde_json::from_str(&json_text)?;
+            let mut total = 0usize;
+            if let Some(apps_data) = data["appsWarmupData"].as_object() {
+                for (_, app_data) in apps_data {
+                    if let Some(widgets) = app_data.as_object() {
+                        for (widget_key, widget_data) in widgets {
+                            if widget_key.starts_with("widget") {
+                                if let Some(events_data) =
+                                    widget_data.get("events").and_then(|e| e.get("events"))
+                                {
+                                    if let Some(events_array) = events_data.as_array() {
+                                        total += events_array.len();
+                                        for ev in events_array {
+                                            out.push(ParsedRecord {
+                                                source_id: self.source_id.clone(),
+                                                envelope_id: self.envelope_id.clone(),
+                                                payload_ref: self.payload_ref.clone(),
+                                                record_path: format!(
+                                                    "$.appsWarmupData.*.{}.events.events[*]",
+                                                    widget_key
+                                                ),
+                                                record: ev.clone(),
+                                                attribution: None,
+                                                change: None,
+                                                endpoint_id: None,
+                                                external_id: crate::ids::from_json(ev.get("id")),
+                                            });
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+            info!(
+                "WixWarmupV1Parser: extracted total events={} from warmup JSON",
+                total
+            );
+        }
+        if out.is_empty() {
+            warn!(
+                "WixWarmupV1Parser: no events extracted; emitting fallback record with html_len={}",
+                html_len
+            );
+            // Fall back: emit entire HTML if nothing parsed for troubleshooting
+            out.push(ParsedRecord {
+                source_id: self.source_id.clone(),
+                envelope_id: self.envelope_id.clone(),
+                payload_ref: self.payload_ref.clone(),
+                record_path: "html".to_string(),
+                record: serde_json::json!({"html_len": html_len}),
+                attribution: None,
+                change: None,
+                endpoint_id: None,
+                external_id: None,
+            });
+        }
+        Ok(out)
+    }
+}
+
+// Parses Darrell's Tavern HTML schedule into basic records with title and event_day.
+pub struct DarrellsHtmlV1Parser {
+    pub source_id: String,
+    pub envelope_id: String,
+    pub payload_ref: String,
+    pub dates: crate::DateHints,
+}
+
+impl DarrellsHtmlV1Parser {
+    pub fn new(source_id: String, envelope_id: String, payload_ref: String, dates: crate::DateHints) -> Self {
+        Self {
+            source_id,
+            envelope_id,
+            payload_ref,
+            dates,
+        }
+    }
+}
+
+impl Parser for DarrellsHtmlV1Parser {
+    fn parse(&self, bytes: &[u8]) -> anyhow::Result<Vec<ParsedRecord>> {
+        self.parse_reader(&mut &*bytes)
+    }
+
+    fn parse_reader(&self, reader: &mut dyn std::io::BufRead) -> anyhow::Result<Vec<ParsedRecord>> {
+        use chrono::NaiveDate;
+        use scraper::Selector;
+        use tracing::{debug, info, warn};
+
+        fn parse_date(text: &str, dates: &crate::DateHints) -> Option<NaiveDate> {
+            // Expecting like: "MUSIC 7.12" (from earlier logic: header h1 with date)
+            let parts: Vec<&str> = text.split_whitespace().collect();
+            if parts.len() < 2 {
+                return None;
+            }
+            let date_part = parts[1];
+            let comps: Vec<&str> = date_part.split('.').collect();
+            if comps.len() != 2 {
+                return None;
+            }
+            let month: u32 = comps[0].parse().ok()?;
+            let day: u32 = comps[1].parse().ok()?;
+            dates.resolve(month, day)
+        }
+
+        fn extract_performers(element: &scraper::ElementRef) -> Vec<String> {
+            let link_sel = Selector::parse("a").unwrap();
+            let mut performers = Vec::new();
+            for link in element.select(&link_sel) {
+                let name = link.text().collect::<String>().trim().to_string();
+                if !name.is_empty() {
+                    performers.push(name);
+                }
+            }
+            let text_content = element.text().collect::<String>();
+            for line in text_content.split('\n') {
+                let line = line.trim();
+                if line.is_empty() {
+                    continue;
+                }
+                if performers.iter().any(|p| line.contains(p)) {
+                    continue;
+                }
+                if line.contains("DOORS") || line.contains("SHOW") || line.contains("$") {
+                    continue;
+                }
+                performers.push(line.to_string());
+            }
+            performers
+        }
+
+        let (document, html_len) = crate::read_html(reader)?;
+        debug!("DarrellsHtmlV1Parser: start bytes_len={}", html_len);
+        let entry_sel = Selector::parse("div.entry-content").unwrap();
+
+        let mut out = Vec::new();
+        if let Some(entry) = document.select(&entry_sel).next() {
+            let mut current_date: Option<NaiveDate> = None;
+            let nodes: Vec<_> = entry.children().collect();
+            let mut i = 0;
+            while i < nodes.len() {
+                let node = nodes[i];
+                if let Some(el) = node.value().as_element() {
+                    if el.name() == "h1" {
+                        let element_ref = scraper::ElementRef::wrap(node).unwrap();
+                        let date_text = element_ref.text().collect::<String>();
+                        debug!("DarrellsHtmlV1Parser: found header date='{}'", date_text);
+                        current_date = parse_date(&date_text, &self.dates);
+                    } else if el.name() == "p" && current_date.is_some() {
+                        let element_ref = scraper::ElementRef::wrap(node).unwrap();
+                        let performers = extract_performers(&element_ref);
+                        for perf in performers {
+                            let rec = serde_json::json!({
+                                "title": perf,
+                                "event_day": current_date.unwrap().to_string(),
+                            });
+                            out.push(ParsedRecord {
+                                source_id: self.source_id.clone(),
+                                envelope_id: self.envelope_id.clone(),
+                                payload_ref: self.payload_ref.clone(),
+                                record_path: "entry-content".to_string(),
+                                record: rec,
+                                attribution: None,
+                                change: None,
+                                endpoint_id: None,
+                                external_id: None,
+                            });
+                        }
+                    }
+                }
+                i += 1;
+            }
+        }
+        if out.is_empty() {
+            warn!("DarrellsHtmlV1Parser: no events extracted; emitting fallback record with html_len={}", html_len);
+            out.push(ParsedRecord {
+                source_id: self.source_id.clone(),
+                envelope_id: self.envelope_id.clone(),
+                payload_ref: self.payload_ref.clone(),
+                record_path: "html".to_string(),
+                record: serde_json::json!({"html_len": html_len}),
+                attribution: None,
+                change: None,
+                endpoint_id: None,
+                external_id: None,
+            });
+        } else {
+            info!("DarrellsHtmlV1Parser: extracted events count={}", out.len());
+        }
+        Ok(out)
+    }
+}
+
+// Parses KEXP HTML pages for live event listings
+pub struct KexpHtmlV1Parser {
+    pub source_id: String,
+    pub envelope_id: String,
+    pub payload_ref: String,
+}
+
+impl KexpHtmlV1Parser {
+    pub fn new(source_id: String, envelope_id: String, payload_ref: String) -> Self {
+        Self {
+            source_id,
+            envelope_id,
+            payload_ref,
+        }
+    }
+}
+
+impl Parser for KexpHtmlV1Parser {
+    fn parse(&self, bytes: &[u8]) -> anyhow::Result<Vec<ParsedRecord>> {
+        self.parse_reader(&mut &*bytes)
+    }
+
+    fn parse_reader(&self, reader: &mut dyn std::io::BufRead) -> anyhow::Result<Vec<ParsedRecord>> {
+        use scraper::Selector;
+        use tracing::{debug, info, warn};
+
+        let (document, html_len) = crate::read_html(reader)?;
+        debug!("KexpHtmlV1Parser: start bytes_len={}", html_len);
+        
+        // KEXP events are in article.EventItem containers with h2 date headers
+        let _event_selector = Selector::parse("article.EventItem").unwrap();
+        let title_selector = Selector::parse(".EventItem-body h3 a").unwrap();
+        let time_selector = Selector::parse(".EventItem-DateTime h5").unwrap();
+        let location_selector = Selector::parse(".EventItem-body .u-h3 a").unwrap();
+        let description_selector = Selector::parse(".EventItem-description").unwrap();
+        let _date_selector = Selector::parse("h2").unwrap();
+
+        let mut out = Vec::new();
+        let mut current_date: Option<String> = None;
+        
+        // Process the document sequentially to match dates with events (like the working KEXP API does)
+        for element in document.select(&Selector::parse("h2, article.EventItem").unwrap()) {
+            if element.value().name() == "h2" {
+                // This is a date header
+                let date_text = element.text().collect::<Vec<_>>().join(" ");
+                current_date = Some(date_text.clone());
+                debug!("KexpHtmlV1Parser: found date header: {}", date_text);
+            } else if element.value().name() == "article" {
+                // This is an event item
+                let title = element
+                    .select(&title_selector)
+                    .next()
+                    .map(|el| el.text().collect::<String>().trim().to_string())
+                    .unwrap_or_else(|| "Unknown Event".to_string());
+                let detail_url = element
+                    .select(&title_selector)
+                    .next()
+                    .and_then(|el| el.value().attr("href"))
+                    .map(str::to_string);
+                    
+                let time = element
+                    .select(&time_selector)
+                    .next()
+                    .map(|el| el.text().collect::<String>().trim().to_string())
+                    .unwrap_or_else(|| "".to_string());
+                    
+                let location = element
+                    .select(&location_selector)
+                    .next()
+                    .map(|el| el.text().collect::<String>().trim().to_string())
+                    .unwrap_or_else(|| "KEXP Studio".to_string());
+                    
+                let description = element
+                    .select(&description_selector)
+                    .next()
+                    .map(|el| el.text().collect::<String>().trim().to_string())
+                    .unwrap_or_else(|| "".to_string());
+
+                // Skip events that don't have meaningful content
+                if title.is_empty() || title == "Unknown Event" {
+                    continue;
+                }
+
+                // Create the record in the same format as other parsers
+                let record = serde_json::json!({
+                    "title": title,
+                    "event_day": current_date.clone().unwrap_or_default(),
+                    "event_time": time,
+                    "location": location,
+                    "description": description,
+                    "source": "kexp",
+                    "public": true
+                });
+                
+                debug!("KexpHtmlV1Parser: extracted event title='{}' date='{}'", title, current_date.as_deref().unwrap_or("unknown"));
+                
+                out.push(ParsedRecord {
+                    source_id: self.source_id.clone(),
+                    envelope_id: self.envelope_id.clone(),
+                    payload_ref: self.payload_ref.clone(),
+                    record_path: "article.EventItem".to_string(),
+                    record,
+                    attribution: None,
+                    change: None,
+                    endpoint_id: None,
+                    external_id: detail_url.as_deref().and_then(crate::ids::from_detail_url),
+                });
+            }
+        }
+        
+        if out.is_empty() {
+            warn!("KexpHtmlV1Parser: no events extracted; emitting fallback record with html_len={}", html_len);
+            // Fall back: emit entire HTML if nothing parsed for troubleshooting
+            out.push(ParsedRecord {
+                source_id: self.source_id.clone(),
+                envelope_id: self.envelope_id.clone(),
+                payload_ref: self.payload_ref.clone(),
+                record_path: "html".to_string(),
+                record: serde_json::json!({"html_len": html_len}),
+                attribution: None,
+                change: None,
+                endpoint_id: None,
+                external_id: None,
+            });
+        } else {
+            info!("KexpHtmlV1Parser: extracted events count={}", out.len());
+        }
+        Ok(out)
+    }
+}
+
+// Parses Barboza HTML event listings
+pub struct BarbozaHtmlV1Parser {
+    pub source_id: String,
+    pub envelope_id: String,
+    pub payload_ref: String,
+    pub dates: crate::DateHints,
+}
+
+impl BarbozaHtmlV1Parser {
+    pub fn new(source_id: String, envelope_id: String, payload_ref: String, dates: crate::DateHints) -> Self {
+        Self {
+            source_id,
+            envelope_id,
+            payload_ref,
+            dates,
+        }
+    }
+}
+
+impl Parser for BarbozaHtmlV1Parser {
+    fn parse(&self, bytes: &[u8]) -> anyhow::Result<Vec<ParsedRecord>> {
+        self.parse_reader(&mut &*bytes)
+    }
+
+    fn parse_reader(&self, reader: &mut dyn std::io::BufRead) -> anyhow::Result<Vec<ParsedRecord>> {
+        use scraper::Selector;
+        use tracing::{debug, info, warn};
+
+        let (document, html_len) = crate::read_html(reader)?;
+        debug!("BarbozaHtmlV1Parser: start bytes_len={}", html_len);
+        
+        // Parse events using the actual HTML structure: div.eventItem
+        let event_selector = Selector::parse("div.eventItem").unwrap();
+        let title_selector = Selector::parse("h3.title a").unwrap();
+        let tagline_selector = Selector::parse("h4.tagline").unwrap();
+        let promotion_selector = Selector::parse("div.promotion-text").unwrap();
+        let month_selector = Selector::parse(".m-date__month").unwrap();
+        let day_selector = Selector::parse(".m-date__day").unwrap();
+        let time_selector = Selector::parse(".meta .time").unwrap();
+        let age_selector = Selector::parse(".meta .age").unwrap();
+        let location_selector = Selector::parse(".meta .location").unwrap();
+        let ticket_link_selector = Selector::parse("a.tickets").unwrap();
+        let image_selector = Selector::parse(".thumb img").unwrap();
+
+        let mut out = Vec::new();
+        
+        for event_element in document.select(&event_selector) {
+            let mut record = serde_json::json!({});
+            
+            // Extract main title (headliner)
+            if let Some(title_elem) = event_element.select(&title_selector).next() {
+                let title = title_elem.text().collect::<String>().trim().to_string();
+                record["title"] = serde_json::json!(title);
+                
+                // Also get the event detail URL
+                if let Some(href) = title_elem.value().attr("href") {
+                    record["detail_url"] = serde_json::json!(href);
+                    // Extract event ID from URL if possible
+                    if let Some(id_match) = href.split('/').next_back() {
+                        record["id"] = serde_json::json!(id_match.to_string());
+                    }
+                }
+            }
+
+            // Extract tagline (supporting acts)
+            if let Some(tagline_elem) = event_element.select(&tagline_selector).next() {
+                let tagline = tagline_elem.text().collect::<String>().trim().to_string();
+                if !tagline.is_empty() {
+                    record["supporting_acts"] = serde_json::json!(tagline);
+                }
+            }
+
+            // Extract promotion text (e.g., "Barboza Presents")
+            if let Some(promo_elem) = event_element.select(&promotion_selector).next() {
+                let promo = promo_elem.text().collect::<String>().trim().to_string();
+                record["promoter"] = serde_json::json!(promo);
+            }
+
+            // Extract date (month and day)
+            let mut month_str = String::new();
+            let mut day_str = String::new();
+            
+            if let Some(month_elem) = event_element.select(&month_selector).next() {
+                month_str = month_elem.text().collect::<String>().trim().to_string();
+            }
+            
+            if let Some(day_elem) = event_element.select(&day_selector).next() {
+                day_str = day_elem.text().collect::<String>().trim().to_string();
+            }
+            
+            // Parse date and format as YYYY-MM-DD
+            if !month_str.is_empty() && !day_str.is_empty() {
+                if let Some(date) = day_str.parse::<u32>().ok().and_then(|day| self.dates.resolve_named(&month_str, day)) {
+                    record["event_day"] = serde_json::json!(date.to_string());
+                    record["date_text"] = serde_json::json!(format!("{} {}", month_str, day_str));
+                }
+            }
+
+            // Extract time (e.g., "Doors: 6:00 PM")
+            if let Some(time_elem) = event_element.select(&time_selector).next() {
+                let time_text = time_elem.text().collect::<String>().trim().to_string();
+                record["time_text"] = serde_json::json!(time_text);
+                // Try to extract just the time part
+                if time_text.contains(":") {
+                    let cleaned = time_text.replace("Doors: ", "").replace("doors: ", "");
+                    record["event_time"] = serde_json::json!(cleaned);
+                }
+            }
+
+            // Extract age restriction
+            if let Some(age_elem) = event_element.select(&age_selector).next() {
+                let age_text = age_elem.text().collect::<String>().trim().to_string();
+                record["age_restriction"] = serde_json::json!(age_text);
+            }
+
+            // Extract location/venue
+            if let Some(location_elem) = event_element.select(&location_selector).next() {
+                let location = location_elem.text().collect::<String>().trim().to_string();
+                record["venue"] = serde_json::json!(location);
+            } else {
+                record["venue"] = serde_json::json!("The Barboza");
+            }
+
+            // Extract ticket purchase link
+            if let Some(ticket_elem) = event_element.select(&ticket_link_selector).next() {
+                if let Some(href) = ticket_elem.value().attr("href") {
+                    record["ticket_url"] = serde_json::json!(href);
+                }
+                // Check if tickets are on sale
+                let class_attr = ticket_elem.value().attr("class").unwrap_or("");
+                record["tickets_on_sale"] = serde_json::json!(class_attr.contains("onsalenow"));
+            }
+
+            // Extract event image
+            if let Some(img_elem) = event_element.select(&image_selector).next() {
+                if let Some(src) = img_elem.value().attr("src") {
+                    record["image_url"] = serde_json::json!(src);
+                }
+            }
+
+            // Mark all events as public
+            record["public"] = serde_json::json!(true);
+            record["source"] = serde_json::json!("barboza");
+
+            // Only add if we have at least a title or date
+            if record.get("title").is_some() || record.get("event_day").is_some() {
+                let external_id = record["detail_url"].as_str().and_then(crate::ids::from_detail_url);
+                out.push(ParsedRecord {
+                    source_id: self.source_id.clone(),
+                    envelope_id: self.envelope_id.clone(),
+                    payload_ref: self.payload_ref.clone(),
+                    record_path: "div.eventItem".to_string(),
+                    record,
+                    attribution: None,
+                    change: None,
+                    endpoint_id: None,
+                    external_id,
+                });
+            }
+        }
+        
+        if out.is_empty() {
+            warn!("BarbozaHtmlV1Parser: no events extracted; emitting fallback record with html_len={}", html_len);
+            out.push(ParsedRecord {
+                source_id: self.source_id.clone(),
+                envelope_id: self.envelope_id.clone(),
+                payload_ref: self.payload_ref.clone(),
+                record_path: "html".to_string(),
+                record: serde_json::json!({"html_len": html_len}),
+                attribution: None,
+                change: None,
+                endpoint_id: None,
+                external_id: None,
+            });
+        } else {
+            info!("BarbozaHtmlV1Parser: extracted events count={}", out.len());
+        }
+        Ok(out)
+    }
+}
+
+// Parses Neumos HTML event listings
+pub struct NeumosHtmlV1Parser {
+    pub source_id: String,
+    pub envelope_id: String,
+    pub payload_ref: String,
+    pub dates: crate::DateHints,
+}
+
+impl NeumosHtmlV1Parser {
+    pub fn new(source_id: String, envelope_id: String, payload_ref: String, dates: crate::DateHints) -> Self {
+        Self {
+            source_id,
+            envelope_id,
+            payload_ref,
+            dates,
+        }
+    }
+}
+
+impl Parser for NeumosHtmlV1Parser {
+    fn parse(&self, bytes: &[u8]) -> anyhow::Result<Vec<ParsedRecord>> {
+        self.parse_reader(&mut &*bytes)
+    }
+
+    fn parse_reader(&self, reader: &mut dyn std::io::BufRead) -> anyhow::Result<Vec<ParsedRecord>> {
+        use scraper::Selector;
+        use tracing::{debug, info, warn};
+
+        let (document, html_len) = crate::read_html(reader)?;
+        debug!("NeumosHtmlV1Parser: start bytes_len={}", html_len);
+        
+        // Parse events using the actual HTML structure: div.eventItem
+        let event_selector = Selector::parse("div.eventItem").unwrap();
+        let title_selector = Selector::parse("h3.title a").unwrap();
+        let tagline_selector = Selector::parse("h4.tagline").unwrap();
+        let tour_selector = Selector::parse("div.promotion-text.tour").unwrap();
+        let promotion_selector = Selector::parse("div.promotion-text:not(.tour)").unwrap();
+        let month_selector = Selector::parse(".m-date__month").unwrap();
+        let day_selector = Selector::parse(".m-date__day").unwrap();
+        let time_selector = Selector::parse(".meta .time").unwrap();
+        let age_selector = Selector::parse(".meta .age").unwrap();
+        let ticket_link_selector = Selector::parse("a.tickets").unwrap();
+        let image_selector = Selector::parse(".thumb img").unwrap();
+
+        let mut out = Vec::new();
+        
+        for event_element in document.select(&event_selector) {
+            let mut record = serde_json::json!({});
+            
+            // Extract main title (headliner)
+            if let Some(title_elem) = event_element.select(&title_selector).next() {
+                let title = title_elem.text().collect::<String>().trim().to_string();
+                record["title"] = serde_json::json!(title);
+                
+                // Also get the event detail URL
+                if let Some(href) = title_elem.value().attr("href") {
+                    record["detail_url"] = serde_json::json!(href);
+                    // Extract event ID from URL if possible
+                    if let Some(id_match) = href.split('/').next_back() {
+                        record["id"] = serde_json::json!(id_match.to_string());
+                    }
+                }
+            }
+
+            // Extract tagline (supporting acts)
+            if let Some(tagline_elem) = event_element.select(&tagline_selector).next() {
+                let tagline = tagline_elem.text().collect::<String>().trim().to_string();
+                if !tagline.is_empty() {
+                    record["supporting_acts"] = serde_json::json!(tagline);
+                }
+            }
+
+            // Extract tour name if present
+            if let Some(tour_elem) = event_element.select(&tour_selector).next() {
+                let tour = tour_elem.text().collect::<String>().trim().to_string();
+                if !tour.is_empty() {
+                    record["tour_name"] = serde_json::json!(tour);
+                }
+            }
+
+            // Extract promotion text (e.g., "Neumos Presents")
+            if let Some(promo_elem) = event_element.select(&promotion_selector).next() {
+                let promo = promo_elem.text().collect::<String>().trim().to_string();
+                // Only save if it's not a tour name
+                if !promo.is_empty() && !event_element.select(&tour_selector).any(|t| t.text().collect::<String>().trim() == promo) {
+                    record["promoter"] = serde_json::json!(promo);
+                }
+            }
+
+            // Extract date (month and day)
+            let mut month_str = String::new();
+            let mut day_str = String::new();
+            
+            if let Some(month_elem) = event_element.select(&month_selector).next() {
+                month_str = month_elem.text().collect::<String>().trim().to_string();
+            }
+            
+            if let Some(day_elem) = event_element.select(&day_selector).next() {
+                day_str = day_elem.text().collect::<String>().trim().to_string();
+            }
+            
+            // Parse date and format as YYYY-MM-DD
+            if !month_str.is_empty() && !day_str.is_empty() {
+                if let Some(date) = day_str.parse::<u32>().ok().and_then(|day| self.dates.resolve_named(&month_str, day)) {
+                    record["event_day"] = serde_json::json!(date.to_string());
+                    record["date_text"] = serde_json::json!(format!("{} {}", month_str, day_str));
+                }
+            }
+
+            // Extract time (e.g., "Doors: 7:00 PM")
+            if let Some(time_elem) = event_element.select(&time_selector).next() {
+                let time_text = time_elem.text().collect::<String>().trim().to_string();
+                record["time_text"] = serde_json::json!(time_text);
+                // Try to extract just the time part
+                if time_text.contains(":") {
+                    let cleaned = time_text.replace("Doors: ", "").replace("doors: ", "");
+                    record["event_time"] = serde_json::json!(cleaned);
+                }
+            }
+
+            // Extract age restriction
+            if let Some(age_elem) = event_element.select(&age_selector).next() {
+                let age_text = age_elem.text().collect::<String>().trim().to_string();
+                record["age_restriction"] = serde_json::json!(age_text);
+            }
+
+            // Set venue to Neumos
+            record["venue"] = serde_json::json!("Neumos");
+
+            // Extract ticket purchase link
+            if let Some(ticket_elem) = event_element.select(&ticket_link_selector).next() {
+                if let Some(href) = ticket_elem.value().attr("href") {
+                    record["ticket_url"] = serde_json::json!(href);
+                }
+                // Check if tickets are on sale
+                let class_attr = ticket_elem.value().attr("class").unwrap_or("");
+                record["tickets_on_sale"] = serde_json::json!(class_attr.contains("onsalenow"));
+            }
+
+            // Extract event image
+            if let Some(img_elem) = event_element.select(&image_selector).next() {
+                if let Some(src) = img_elem.value().attr("src") {
+                    record["image_url"] = serde_json::json!(src);
+                }
+            }
+
+            // Mark all events as public
+            record["public"] = serde_json::json!(true);
+            record["source"] = serde_json::json!("neumos");
+
+            // Only add if we have at least a title or date
+            if record.get("title").is_some() || record.get("event_day").is_some() {
+                let external_id = record["detail_url"].as_str().and_then(crate::ids::from_detail_url);
+                out.push(ParsedRecord {
+                    source_id: self.source_id.clone(),
+                    envelope_id: self.envelope_id.clone(),
+                    payload_ref: self.payload_ref.clone(),
+                    record_path: "div.eventItem".to_string(),
+                    record,
+                    attribution: None,
+                    change: None,
+                    endpoint_id: None,
+                    external_id,
+                });
+            }
+        }
+        
+        if out.is_empty() {
+            warn!("NeumosHtmlV1Parser: no events extracted; emitting fallback record with html_len={}", html_len);
+            out.push(ParsedRecord {
+                source_id: self.source_id.clone(),
+                envelope_id: self.envelope_id.clone(),
+                payload_ref: self.payload_ref.clone(),
+                record_path: "html".to_string(),
+                record: serde_json::json!({"html_len": html_len}),
+                attribution: None,
+                change: None,
+                endpoint_id: None,
+                external_id: None,
+            });
+        } else {
+            info!("NeumosHtmlV1Parser: extracted events count={}", out.len());
+        }
+        Ok(out)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::DateHints;
+
+    fn parsed(parser: impl Parser, bytes: &[u8]) -> Vec<ParsedRecord> {
+        parser.parse(bytes).unwrap()
+    }
+
+    fn event_days(records: &[ParsedRecord]) -> Vec<&str> {
+        records.iter().map(|r| r.record["event_day"].as_str().unwrap()).collect()
+    }
+
+    fn external_ids(records: &[ParsedRecord]) -> Vec<Option<&str>> {
+        records.iter().map(|r| r.external_id.as_deref()).collect()
+    }
+
+    #[test]
+    fn source_date_hints_roll_listing_years_over() {
+        let hints = |day: &str| {
+            DateHints::new(chrono_tz::America::Los_Angeles, "en-US").with_reference(day.parse().unwrap())
+        };
+        let barboza = br#"<div class="eventItem"><h3 class="title"><a href="/e/1">Late Show</a></h3><span class="m-date__month">Dec</span><span class="m-date__day">30</span></div>
+<div class="eventItem"><h3 class="title"><a href="/e/2">New Year</a></h3><span class="m-date__month">Jan</span><span class="m-date__day">3</span></div>"#;
+        let parser = BarbozaHtmlV1Parser::new("barboza".into(), "env-1".into(), "cas:sha256:abcd".into(), hints("2025-12-20"));
+        assert_eq!(event_days(&parsed(parser, barboza)), ["2025-12-30", "2026-01-03"]);
+
+        // A January calendar still showing last week's shows keeps them in the old year
+        let darrells = b"<div class=\"entry-content\"><h1>MUSIC 12.30</h1><p><a href=\"#\">The Band</a></p><h1>MUSIC 1.9</h1><p><a href=\"#\">Openers</a></p></div>";
+        let parser = DarrellsHtmlV1Parser::new("darrells_tavern".into(), "env-1".into(), "cas:sha256:abcd".into(), hints("2026-01-02"));
+        assert_eq!(event_days(&parsed(parser, darrells)), ["2025-12-30", "2026-01-09"]);
+    }
+
+    #[test]
+    fn parsers_extract_stable_external_ids() {
+        let wix = br#"{"events":[{"id":"a1b2-c3","title":"The Band"},{"title":"No Id"}]}"#;
+        assert_eq!(external_ids(&parsed(WixCalendarV1Parser::new("src".into(), "env-1".into(), "cas:sha256:abcd".into()), wix)), [Some("a1b2-c3"), None]);
+
+        let neumos = br#"<div class="eventItem"><h3 class="title"><a href="https://www.neumos.com/events/detail/1234567/">Show</a></h3></div>
+<div class="eventItem"><h3 class="title"><a href="/events/detail/?event_id=89&amp;utm=x">Other</a></h3></div>"#;
+        let parser = NeumosHtmlV1Parser::new("src".into(), "env-1".into(), "cas:sha256:abcd".into(), DateHints::default());
+        assert_eq!(external_ids(&parsed(parser, neumos)), [Some("1234567"), Some("89")]);
+
+        let kexp = br#"<h2>Fri, Aug 15</h2><article class="EventItem"><div class="EventItem-body"><h3><a href="/events/kexp-live-42">Live on KEXP</a></h3></div></article>"#;
+        assert_eq!(external_ids(&parsed(KexpHtmlV1Parser::new("src".into(), "env-1".into(), "cas:sha256:abcd".into()), kexp)), [Some("kexp-live-42")]);
+
+        // Darrell's listing has no per-event page to take an id from
+        let darrells = br##"<div class="entry-content"><h1>MUSIC 7.12</h1><p><a href="#">The Band</a></p></div>"##;
+        let parser = DarrellsHtmlV1Parser::new("src".into(), "env-1".into(), "cas:sha256:abcd".into(), DateHints::default());
+        assert_eq!(external_ids(&parsed(parser, darrells)), [None]);
+    }
+}
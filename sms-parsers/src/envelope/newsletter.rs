@@ -0,0 +1,490 @@
+// Parses venue newsletters: one raw RFC 822 message per envelope, as mailbox sources store
+// them. The message's HTML part (or its plain text one) is split into blocks, and each block
+// that names a day is an event, titled by the rest of the block or, when the date stands alone
+// as a heading, by the block after it.
+
+use std::sync::OnceLock;
+
+use base64::Engine;
+use chrono::{DateTime, FixedOffset, NaiveDate, NaiveTime};
+use regex::Regex;
+use scraper::{ElementRef, Html, Selector};
+use serde_json::json;
+use tracing::{debug, info};
+
+use crate::schedule::split_show_times;
+use crate::{DateHints, ParsedRecord, Parser};
+
+/// `format` of every record this parser emits, which normalizers key off
+pub const NEWSLETTER_FORMAT: &str = "newsletter_email";
+
+/// Blocks longer than this are prose that happens to mention a day, not listings
+const MAX_BLOCK_CHARS: usize = 300;
+
+pub struct NewsletterEmailV1Parser {
+    pub source_id: String,
+    pub envelope_id: String,
+    pub payload_ref: String,
+    /// Language of month names; years are resolved against the day the message was sent
+    pub dates: DateHints,
+}
+
+impl NewsletterEmailV1Parser {
+    pub fn new(source_id: String, envelope_id: String, payload_ref: String, dates: DateHints) -> Self {
+        Self {
+            source_id,
+            envelope_id,
+            payload_ref,
+            dates,
+        }
+    }
+}
+
+impl Parser for NewsletterEmailV1Parser {
+    fn parse(&self, bytes: &[u8]) -> anyhow::Result<Vec<ParsedRecord>> {
+        debug!("NewsletterEmailV1Parser: start bytes_len={}", bytes.len());
+        let (headers, _) = split_message(bytes);
+        let header = |name: &str| find_header(&headers, name).map(decode_words);
+        let sent_at = header("Date").and_then(|d| parse_date_header(&d));
+        let message_id = header("Message-ID").map(|id| id.trim().trim_matches(['<', '>']).to_string());
+        let (sender, sender_address) = header("From").map(|from| split_address(&from)).unwrap_or_default();
+
+        // Years come from when the newsletter went out, not from when it is parsed
+        let mut dates = self.dates.clone();
+        if let (None, Some(sent_at)) = (dates.reference, sent_at) {
+            let sent_on = sent_at.with_timezone(&dates.timezone).date_naive();
+            dates = dates.with_reference(sent_on);
+        }
+
+        let Some(body) = message_body(bytes) else {
+            anyhow::bail!("message has no text/html or text/plain part");
+        };
+        let blocks = match body {
+            Body::Html(html) => html_blocks(&html),
+            Body::Plain(text) => text_blocks(&text),
+        };
+        info!("NewsletterEmailV1Parser: found blocks count={}", blocks.len());
+
+        let mut out: Vec<ParsedRecord> = Vec::new();
+        let mut seen = std::collections::HashSet::new();
+        for (i, block) in blocks.iter().enumerate() {
+            if block.text.chars().count() > MAX_BLOCK_CHARS {
+                continue;
+            }
+            let Some((day, rest)) = find_day(&block.text, &dates) else {
+                continue;
+            };
+            // A date on its own line heads the block that names the show
+            let next = blocks.get(i + 1).filter(|next| find_day(&next.text, &dates).is_none());
+            let (title, description, url) = match title_of(&rest) {
+                Some(title) => (title, block.text.clone(), block.url.clone()),
+                None => match next.and_then(|next| title_of(&next.text).map(|title| (title, next))) {
+                    Some((title, next)) => (title, format!("{}\n{}", block.text, next.text), next.url.clone().or(block.url.clone())),
+                    None => continue,
+                },
+            };
+            if !seen.insert((day, title.to_lowercase())) {
+                continue;
+            }
+            let times = split_show_times(&description);
+
+            let mut record = json!({
+                "format": NEWSLETTER_FORMAT,
+                "title": title,
+                "event_day": day.to_string(),
+                "description": description,
+            });
+            let time = |t: Option<NaiveTime>| t.map(|t| t.format("%H:%M:%S").to_string());
+            let id = message_id.as_ref().map(|m| format!("{}:{}:{}", m, day, title.to_lowercase()));
+            let optional = [
+                ("id", id),
+                ("start_time", time(times.start_time())),
+                ("doors_time", time(times.doors.filter(|_| times.show.is_some()))),
+                ("event_url", url),
+                ("newsletter", header("Subject")),
+                ("sender", sender.clone()),
+                ("sender_address", sender_address.clone()),
+                ("sent_at", sent_at.map(|at| at.to_rfc3339())),
+                ("message_id", message_id.clone()),
+            ];
+            for (key, value) in optional {
+                if let Some(value) = value.filter(|v| !v.trim().is_empty()) {
+                    record[key] = json!(value);
+                }
+            }
+            out.push(ParsedRecord {
+                source_id: self.source_id.clone(),
+                envelope_id: self.envelope_id.clone(),
+                payload_ref: self.payload_ref.clone(),
+                record_path: format!("$.blocks[{}]", i),
+                external_id: crate::ids::from_json(record.get("id")),
+                record,
+                attribution: None,
+                change: None,
+                endpoint_id: None,
+            });
+        }
+        info!("NewsletterEmailV1Parser: parsed events count={}", out.len());
+        Ok(out)
+    }
+}
+
+/// One paragraph, list item or table cell of the newsletter, with its first link
+struct Block {
+    text: String,
+    url: Option<String>,
+}
+
+enum Body {
+    Html(String),
+    Plain(String),
+}
+
+/// Unfolded headers and the body that follows the first blank line
+fn split_message(bytes: &[u8]) -> (Vec<(String, String)>, &[u8]) {
+    // A part may have no headers at all, starting straight with the blank line
+    for blank in [&b"\r\n"[..], b"\n"] {
+        if let Some(body) = bytes.strip_prefix(blank) {
+            return (Vec::new(), body);
+        }
+    }
+    let split = bytes
+        .windows(4)
+        .position(|w| w == b"\r\n\r\n")
+        .map(|at| (at, at + 4))
+        .into_iter()
+        .chain(bytes.windows(2).position(|w| w == b"\n\n").map(|at| (at, at + 2)))
+        .min_by_key(|(at, _)| *at);
+    let (head, body) = match split {
+        Some((end, body_start)) => (&bytes[..end], &bytes[body_start..]),
+        None => (bytes, &bytes[bytes.len()..]),
+    };
+    let mut headers: Vec<(String, String)> = Vec::new();
+    for line in String::from_utf8_lossy(head).lines() {
+        match (line.starts_with([' ', '\t']), headers.last_mut()) {
+            (true, Some((_, value))) => {
+                value.push(' ');
+                value.push_str(line.trim());
+            }
+            _ => {
+                if let Some((name, value)) = line.split_once(':') {
+                    headers.push((name.trim().to_string(), value.trim().to_string()));
+                }
+            }
+        }
+    }
+    (headers, body)
+}
+
+fn find_header<'h>(headers: &'h [(String, String)], name: &str) -> Option<&'h str> {
+    headers.iter().find(|(n, _)| n.eq_ignore_ascii_case(name)).map(|(_, v)| v.as_str())
+}
+
+/// `type/subtype` lowercased and the `key=value` parameters of a Content-Type header
+fn content_type(headers: &[(String, String)]) -> (String, Vec<(String, String)>) {
+    let value = find_header(headers, "Content-Type").unwrap_or("text/plain");
+    let mut parts = value.split(';');
+    let mime = parts.next().unwrap_or_default().trim().to_ascii_lowercase();
+    let params = parts
+        .filter_map(|p| p.split_once('='))
+        .map(|(k, v)| (k.trim().to_ascii_lowercase(), v.trim().trim_matches('"').to_string()))
+        .collect();
+    (mime, params)
+}
+
+/// The HTML part of a message, else its first plain text part
+fn message_body(message: &[u8]) -> Option<Body> {
+    let mut parts = Vec::new();
+    collect_text_parts(message, 0, &mut parts);
+    let html = parts.iter().position(|part| matches!(part, Body::Html(_)));
+    match html {
+        Some(index) => Some(parts.swap_remove(index)),
+        None => parts.into_iter().next(),
+    }
+}
+
+fn collect_text_parts(entity: &[u8], depth: usize, out: &mut Vec<Body>) {
+    // Forwards nest messages in messages; real newsletters don't go this deep
+    if depth > 8 {
+        return;
+    }
+    let (headers, body) = split_message(entity);
+    let (mime, params) = content_type(&headers);
+    let param = |name: &str| params.iter().find(|(k, _)| k == name).map(|(_, v)| v.as_str());
+    if mime.starts_with("multipart/") {
+        if let Some(boundary) = param("boundary") {
+            for part in multipart_parts(body, boundary) {
+                collect_text_parts(part, depth + 1, out);
+            }
+        }
+        return;
+    }
+    let encoding = find_header(&headers, "Content-Transfer-Encoding").unwrap_or("7bit").to_ascii_lowercase();
+    let decoded = match encoding.as_str() {
+        "base64" => decode_base64(body),
+        "quoted-printable" => decode_quoted_printable(body),
+        _ => body.to_vec(),
+    };
+    match mime.as_str() {
+        "text/html" => out.push(Body::Html(decode_charset(&decoded, param("charset")))),
+        "text/plain" => out.push(Body::Plain(decode_charset(&decoded, param("charset")))),
+        "message/rfc822" => collect_text_parts(&decoded, depth + 1, out),
+        _ => {}
+    }
+}
+
+/// The entities between a multipart body's `--boundary` lines, up to the closing one
+fn multipart_parts<'a>(body: &'a [u8], boundary: &str) -> Vec<&'a [u8]> {
+    let delimiter = format!("--{}", boundary);
+    let mut parts = Vec::new();
+    let mut start: Option<usize> = None;
+    let mut pos = 0;
+    for line in body.split_inclusive(|b| *b == b'\n') {
+        let trimmed = line.strip_suffix(b"\n").unwrap_or(line);
+        let trimmed = trimmed.strip_suffix(b"\r").unwrap_or(trimmed);
+        if let Some(after) = trimmed.strip_prefix(delimiter.as_bytes()) {
+            if let Some(s) = start {
+                // The line break before a delimiter belongs to the delimiter
+                let part = &body[s..pos];
+                let part = part.strip_suffix(b"\n").unwrap_or(part);
+                parts.push(part.strip_suffix(b"\r").unwrap_or(part));
+            }
+            if after.starts_with(b"--") {
+                return parts;
+            }
+            start = Some(pos + line.len());
+        }
+        pos += line.len();
+    }
+    parts.extend(start.map(|s| &body[s..]));
+    parts
+}
+
+fn decode_base64(body: &[u8]) -> Vec<u8> {
+    let compact: Vec<u8> = body.iter().copied().filter(|b| !b.is_ascii_whitespace()).collect();
+    let engine = base64::engine::GeneralPurpose::new(
+        &base64::alphabet::STANDARD,
+        base64::engine::GeneralPurposeConfig::new().with_decode_padding_mode(base64::engine::DecodePaddingMode::Indifferent),
+    );
+    engine.decode(compact).unwrap_or_else(|_| body.to_vec())
+}
+
+fn decode_quoted_printable(body: &[u8]) -> Vec<u8> {
+    let mut out = Vec::with_capacity(body.len());
+    let mut i = 0;
+    while i < body.len() {
+        if body[i] != b'=' {
+            out.push(body[i]);
+            i += 1;
+            continue;
+        }
+        // Soft line breaks join lines the sender wrapped
+        if body[i + 1..].starts_with(b"\r\n") {
+            i += 3;
+        } else if body[i + 1..].starts_with(b"\n") {
+            i += 2;
+        } else if let Some(byte) = body.get(i + 1..i + 3).and_then(|hex| u8::from_str_radix(std::str::from_utf8(hex).ok()?, 16).ok()) {
+            out.push(byte);
+            i += 3;
+        } else {
+            out.push(b'=');
+            i += 1;
+        }
+    }
+    out
+}
+
+/// Text in `charset`; Latin-1 and its Windows superset are mapped byte for byte, anything else
+/// is read as UTF-8
+fn decode_charset(bytes: &[u8], charset: Option<&str>) -> String {
+    match charset.map(|c| c.to_ascii_lowercase()).as_deref() {
+        Some("iso-8859-1" | "latin1" | "windows-1252" | "cp1252") => bytes.iter().map(|&b| b as char).collect(),
+        _ => String::from_utf8_lossy(bytes).into_owned(),
+    }
+}
+
+/// Decode RFC 2047 encoded words (`=?utf-8?Q?Caf=C3=A9?=`) in a header value
+fn decode_words(value: &str) -> String {
+    static WORD: OnceLock<Regex> = OnceLock::new();
+    let word = WORD.get_or_init(|| Regex::new(r"=\?([^?]+)\?([bBqQ])\?([^?]*)\?=").unwrap());
+    let mut out = String::new();
+    let mut last = 0;
+    for caps in word.captures_iter(value) {
+        let whole = caps.get(0).unwrap();
+        let between = &value[last..whole.start()];
+        // Whitespace between adjacent encoded words is not part of the text
+        if !(last > 0 && between.trim().is_empty()) {
+            out.push_str(between);
+        }
+        let bytes = match &caps[2] {
+            "b" | "B" => decode_base64(caps[3].as_bytes()),
+            _ => decode_quoted_printable(caps[3].replace('_', " ").as_bytes()),
+        };
+        out.push_str(&decode_charset(&bytes, Some(&caps[1])));
+        last = whole.end();
+    }
+    out.push_str(&value[last..]);
+    out
+}
+
+/// Display name and address of a `From` header: `"Ballard Corner Bar" <news@bcb.example>`
+fn split_address(from: &str) -> (Option<String>, Option<String>) {
+    match from.rsplit_once('<') {
+        Some((name, address)) => {
+            let name = name.trim().trim_matches('"').trim();
+            let address = address.trim_end_matches('>').trim();
+            (Some(name.to_string()).filter(|n| !n.is_empty()), Some(address.to_string()))
+        }
+        None => (None, Some(from.trim().to_string())),
+    }
+}
+
+fn parse_date_header(value: &str) -> Option<DateTime<FixedOffset>> {
+    // Mailers append the zone's name as a comment: "... -0700 (PDT)"
+    let value = value.split('(').next().unwrap_or(value).trim();
+    DateTime::parse_from_rfc2822(value).ok()
+}
+
+/// Leaf blocks of the HTML in document order, with whitespace collapsed
+fn html_blocks(html: &str) -> Vec<Block> {
+    static BLOCK: OnceLock<Selector> = OnceLock::new();
+    static LINK: OnceLock<Selector> = OnceLock::new();
+    let block = BLOCK.get_or_init(|| Selector::parse("h1, h2, h3, h4, h5, h6, p, li, td, th, div, blockquote").unwrap());
+    let link = LINK.get_or_init(|| Selector::parse("a[href]").unwrap());
+    let document = Html::parse_document(html);
+    document
+        .select(block)
+        .filter(|el| !el.descendants().skip(1).filter_map(ElementRef::wrap).any(|inner| block.matches(&inner)))
+        .filter_map(|el| {
+            let text = el.text().collect::<Vec<_>>().join(" ").split_whitespace().collect::<Vec<_>>().join(" ");
+            let url = el
+                .select(link)
+                .filter_map(|a| a.value().attr("href"))
+                .find(|href| href.starts_with("http"))
+                .map(str::to_string);
+            Some(Block { text, url }).filter(|b| !b.text.is_empty())
+        })
+        .collect()
+}
+
+/// Non-empty lines of a plain text newsletter
+fn text_blocks(text: &str) -> Vec<Block> {
+    text.lines()
+        .map(|line| line.split_whitespace().collect::<Vec<_>>().join(" "))
+        .filter(|line| !line.is_empty())
+        .map(|text| Block { text, url: None })
+        .collect()
+}
+
+/// The first day named in `text` ("Fri May 2", "May 2nd, 2025", "5/2") and the text with it
+/// cut out
+fn find_day(text: &str, dates: &DateHints) -> Option<(NaiveDate, String)> {
+    static NAMED: OnceLock<[Regex; 2]> = OnceLock::new();
+    static NUMERIC: OnceLock<Regex> = OnceLock::new();
+    let named = NAMED.get_or_init(|| {
+        let weekday = r"(?:\b(?:mon|tue|wed|thu|fri|sat|sun)[a-z]*\.?,?\s+)?";
+        let month = r"(?P<month>[a-zéûä]{3,10})";
+        let year = r"(?:,?\s+(?P<year>\d{4}))?";
+        [
+            Regex::new(&format!(r"(?i){}\b{}\.?\s+(?P<day>\d{{1,2}})(?:st|nd|rd|th)?\b{}", weekday, month, year)).unwrap(),
+            Regex::new(&format!(r"(?i){}\b(?P<day>\d{{1,2}})(?:st|nd|rd|th|er)?\.?\s+{}\b\.?{}", weekday, month, year)).unwrap(),
+        ]
+    });
+    let numeric = NUMERIC.get_or_init(|| Regex::new(r"\b(?P<a>\d{1,2})/(?P<b>\d{1,2})(?:/(?P<year>\d{2,4}))?\b").unwrap());
+    let year = |caps: &regex::Captures| caps.name("year").and_then(|y| y.as_str().parse::<i32>().ok()).map(|y| if y < 100 { 2000 + y } else { y });
+    let day_of = |month: u32, day: u32, year: Option<i32>| match year {
+        Some(year) => NaiveDate::from_ymd_opt(year, month, day),
+        None => dates.resolve(month, day),
+    };
+
+    // "May 2" or "2 May", whichever comes first, then numeric dates
+    let found = named
+        .iter()
+        .flat_map(|pattern| pattern.captures_iter(text))
+        .filter_map(|caps| {
+            let month = dates.month(&caps["month"])?;
+            let day = day_of(month, caps["day"].parse().ok()?, year(&caps))?;
+            Some((day, caps.get(0)?.range()))
+        })
+        .min_by_key(|(_, range)| range.start)
+        .or_else(|| {
+            let caps = numeric.captures(text)?;
+            let (a, b): (u32, u32) = (caps["a"].parse().ok()?, caps["b"].parse().ok()?);
+            // Month first in English listings, day first elsewhere
+            let (month, day) = if dates.locale.to_ascii_lowercase().starts_with("en") { (a, b) } else { (b, a) };
+            Some((day_of(month, day, year(&caps))?, caps.get(0)?.range()))
+        })?;
+    let (day, range) = found;
+    Some((day, format!("{} {}", &text[..range.start], &text[range.end..])))
+}
+
+/// The show's name in what's left of a block once its date is cut out: the first piece
+/// between separators that isn't a time, price or age limit
+fn title_of(text: &str) -> Option<String> {
+    static SEPARATORS: OnceLock<Regex> = OnceLock::new();
+    static DETAIL: OnceLock<Regex> = OnceLock::new();
+    let separators = SEPARATORS.get_or_init(|| Regex::new(r"\s[-–—|•·]\s|[|•·–—]|\s@\s").unwrap());
+    let detail = DETAIL.get_or_init(|| {
+        Regex::new(r"(?i)^(?:doors?|show|music|starts?|tickets?|free|all ages|\d{2}\+|21 and over|\$|\d{1,2}(?::\d{2})?\s*[ap]\.?m)").unwrap()
+    });
+    separators
+        .split(text)
+        .map(|piece| piece.trim().trim_matches([',', ':', ';', '-', ' ']).trim())
+        .find(|piece| piece.chars().any(char::is_alphabetic) && !detail.is_match(piece))
+        .map(str::to_string)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn emails_yield_one_event_per_dated_block() {
+        // The HTML part, base64 encoded:
+        // <h2>Coming up at the Tavern</h2>
+        // <p>Fri May 2 - The Dip with Kingdom of Birds | Doors 7pm, show 8pm | $15</p>
+        // <p><strong>Saturday, May 3</strong></p><p><a href="https://sunsettavern.com/e/42">Whitney Ballen</a> - all ages</p>
+        // <p>Thanks for reading! We opened on May 1, 2004 and ... (prose, too long to be a listing)</p>
+        let message = "From: =?UTF-8?Q?Sunset_Tavern?= <news@sunsettavern.com>\r\n\
+Subject: =?UTF-8?B?VGhpcyB3ZWVr4oCZcyBzaG93cw==?=\r\nDate: Wed, 30 Apr 2025 09:00:00 -0700 (PDT)\r\nMessage-ID: <abc@mail.example>\r\n\
+MIME-Version: 1.0\r\nContent-Type: multipart/alternative; boundary=\"b1\"\r\n\r\n\
+--b1\r\nContent-Type: text/plain; charset=utf-8\r\nContent-Transfer-Encoding: quoted-printable\r\n\r\n\
+Fri May 2 =E2=80=93 plain text version\r\n\
+--b1\r\nContent-Type: text/html; charset=utf-8\r\nContent-Transfer-Encoding: base64\r\n\r\n\
+PGh0bWw+PGJvZHk+PGgyPkNvbWluZyB1cCBhdCB0aGUgVGF2ZXJuPC9oMj48cD5GcmkgTWF5IDIg\r\n\
+LSBUaGUgRGlwIHdpdGggS2luZ2RvbSBvZiBCaXJkcyB8IERvb3JzIDdwbSwgc2hvdyA4cG0gfCAk\r\n\
+MTU8L3A+PHA+PHN0cm9uZz5TYXR1cmRheSwgTWF5IDM8L3N0cm9uZz48L3A+PHA+PGEgaHJlZj0i\r\n\
+aHR0cHM6Ly9zdW5zZXR0YXZlcm4uY29tL2UvNDIiPldoaXRuZXkgQmFsbGVuPC9hPiAtIGFsbCBh\r\n\
+Z2VzPC9wPjxwPlRoYW5rcyBmb3IgcmVhZGluZyEgV2Ugb3BlbmVkIG9uIE1heSAxLCAyMDA0IGFu\r\n\
+ZCBoYXZlIGxvdmVkIGV2ZXJ5IG5pZ2h0IHNpbmNlLiBUd2VudHktb25lIHllYXJzIG9uLCB0aGUg\r\n\
+YmFjayByb29tIHN0aWxsIGhhcyB0aGUgc2FtZSBzdGlja3kgZmxvb3IsIHRoZSBzYW1lIHdvYmJs\r\n\
+eSBzdG9vbHMgYW5kIHRoZSBzYW1lIGJvb2tlciB3aG8gYW5zd2VycyBldmVyeSBlbWFpbCwgc28g\r\n\
+a2VlcCBzZW5kaW5nIHVzIHlvdXIgZGVtb3MsIHlvdXIgdG91ciByb3V0aW5nIGFuZCB5b3VyIHN0\r\n\
+b3JpZXMgYWJvdXQgdGhlIGZpcnN0IHNob3cgeW91IGV2ZXIgc2F3IGhlcmUuPC9wPjwvYm9keT48\r\n\
+L2h0bWw+\r\n--b1--\r\n";
+        let hints = DateHints::new(chrono_tz::America::Los_Angeles, "en-US");
+        let parser = NewsletterEmailV1Parser::new("sunset_tavern".into(), "env-1".into(), "cas:sha256:abcd".into(), hints);
+        let records = parser.parse(message.as_bytes()).unwrap();
+        let streamed = parser.parse_reader(&mut std::io::BufReader::with_capacity(7, message.as_bytes())).unwrap();
+        assert_eq!(serde_json::to_value(&streamed).unwrap(), serde_json::to_value(&records).unwrap());
+
+        let events: Vec<_> = records.iter().map(|r| &r.record).collect();
+        assert_eq!(events.iter().map(|e| e["event_day"].as_str().unwrap()).collect::<Vec<_>>(), ["2025-05-02", "2025-05-03"]);
+        assert_eq!(events[0]["title"], "The Dip with Kingdom of Birds");
+        assert_eq!((events[0]["start_time"].as_str(), events[0]["doors_time"].as_str()), (Some("20:00:00"), Some("19:00:00")));
+        // A date standing alone as a heading is titled by the block after it
+        assert_eq!(events[1]["title"], "Whitney Ballen");
+        assert_eq!(events[1]["event_url"], "https://sunsettavern.com/e/42");
+        assert_eq!(events[1]["description"], "Saturday, May 3\nWhitney Ballen - all ages");
+        assert_eq!((events[0]["sender"].as_str(), events[0]["sender_address"].as_str()), (Some("Sunset Tavern"), Some("news@sunsettavern.com")));
+        assert_eq!(events[0]["newsletter"], "This week’s shows");
+        assert_eq!(events[0]["sent_at"], "2025-04-30T09:00:00-07:00");
+        assert_eq!(records[0].external_id.as_deref(), Some("abc@mail.example:2025-05-02:the dip with kingdom of birds"));
+
+        // Without an HTML part, every line of the text is a block
+        let plain = "From: news@sunsettavern.com\nDate: Tue, 30 Dec 2025 09:00:00 -0800\n\nJan 3 - New Year Hangover Show\n";
+        let records = parser.parse(plain.as_bytes()).unwrap();
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].record["event_day"], "2026-01-03", "years roll over from the day the email was sent");
+    }
+}
@@ -2,7 +2,7 @@ use chrono::{Local, NaiveDate, NaiveTime};
 use serde_json::json;
 use tracing::info;
 
-use crate::pipeline::processing::parser::{Parser, ParsedRecord};
+use crate::{Parser, ParsedRecord};
 
 pub struct VenuePilotGraphQLV1Parser {
     pub source_id: String,
@@ -22,8 +22,11 @@ impl VenuePilotGraphQLV1Parser {
 
 impl Parser for VenuePilotGraphQLV1Parser {
     fn parse(&self, bytes: &[u8]) -> anyhow::Result<Vec<ParsedRecord>> {
-        let text = String::from_utf8_lossy(bytes);
-        let json_response: serde_json::Value = serde_json::from_str(&text)?;
+        self.parse_reader(&mut &*bytes)
+    }
+
+    fn parse_reader(&self, reader: &mut dyn std::io::BufRead) -> anyhow::Result<Vec<ParsedRecord>> {
+        let json_response: serde_json::Value = serde_json::from_reader(reader)?;
 
         // Extract events from the GraphQL response
         let events = json_response
@@ -51,16 +54,20 @@ impl Parser for VenuePilotGraphQLV1Parser {
                 // Parse date for validation
                 if let Ok(_date) = NaiveDate::parse_from_str(date_str, "%Y-%m-%d") {
                     // Parse start time and door time
+                    let door_time = event.get("doorTime")
+                        .and_then(|t| t.as_str())
+                        .and_then(|t| NaiveTime::parse_from_str(t, "%H:%M:%S").ok());
                     let start_time = event.get("startTime")
                         .and_then(|t| t.as_str())
                         .and_then(|t| NaiveTime::parse_from_str(t, "%H:%M:%S").ok())
-                        .or_else(|| event.get("doorTime")
-                            .and_then(|t| t.as_str())
-                            .and_then(|t| NaiveTime::parse_from_str(t, "%H:%M:%S").ok()));
+                        .or(door_time);
 
                     if let Some(time) = start_time {
                         record["start_time"] = json!(time.format("%H:%M:%S").to_string());
                     }
+                    if let Some(time) = door_time {
+                        record["doors_time"] = json!(time.format("%H:%M:%S").to_string());
+                    }
                 }
             }
 
@@ -150,9 +157,26 @@ impl Parser for VenuePilotGraphQLV1Parser {
                 payload_ref: self.payload_ref.clone(),
                 record_path: "data.paginatedEvents.collection".to_string(),
                 record,
+                attribution: None,
+                change: None,
+                endpoint_id: None,
+                external_id: crate::ids::from_json(event.get("id")),
             });
         }
 
         Ok(parsed_records)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn events_carry_the_venuepilot_id() {
+        let payload = br#"{"data":{"paginatedEvents":{"collection":[{"id":412301,"name":"Whitney Ballen","date":"2025-08-15"}]}}}"#;
+        let parser = VenuePilotGraphQLV1Parser::new("src".into(), "env-1".into(), "cas:sha256:abcd".into());
+        let records = parser.parse(payload).unwrap();
+        assert_eq!(records.iter().map(|r| r.external_id.as_deref()).collect::<Vec<_>>(), [Some("412301")]);
+    }
+}
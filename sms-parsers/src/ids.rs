@@ -0,0 +1,25 @@
+// Stable per-source ids for parsed records, used as their primary conflation key
+
+/// An id field of a JSON record, string or number
+pub fn from_json(value: Option<&serde_json::Value>) -> Option<String> {
+    match value? {
+        serde_json::Value::String(s) if !s.trim().is_empty() => Some(s.trim().to_string()),
+        serde_json::Value::Number(n) => Some(n.to_string()),
+        _ => None,
+    }
+}
+
+/// A listing's id from its detail URL: an `id`/`event_id` query parameter when there is
+/// one, else the last path segment (`/events/detail/12345/` gives `12345`)
+pub fn from_detail_url(url: &str) -> Option<String> {
+    let url = url.split('#').next().unwrap_or_default();
+    let (path, query) = url.split_once('?').unwrap_or((url, ""));
+    let param = query.split('&').find_map(|pair| match pair.split_once('=') {
+        Some(("id" | "event_id" | "eventId", value)) if !value.is_empty() => Some(value.to_string()),
+        _ => None,
+    });
+    param.or_else(|| {
+        let path = path.split_once("://").map_or(path, |(_, rest)| rest.split_once('/').map_or("", |(_, p)| p));
+        path.rsplit('/').find(|segment| !segment.is_empty()).map(str::to_string)
+    })
+}
@@ -0,0 +1,49 @@
+//! Payload parsers for every venue, in one place so a parsing fix lands once.
+//!
+//! - [`envelope`]: `Parser` implementations run against gateway envelopes by the
+//!   parse step, emitting [`ParsedRecord`]s
+//! - [`venue`]: `VenueParser` implementations used by the crawler-based full pipeline
+//! - [`schedule`]: door and show times from listing text, shared with the normalizers
+//! - [`dates`]: event days from listing dates without a year, per the source's time zone
+//!   and locale
+//! - [`ids`]: the source's own stable id for a record, from an id field or detail URL
+
+pub mod dates;
+pub mod envelope;
+pub mod ids;
+pub mod schedule;
+pub mod venue;
+
+pub use envelope::{
+    BarbozaHtmlV1Parser, DarrellsHtmlV1Parser, GoogleCalendarV1Parser, KexpHtmlV1Parser,
+    NeumosHtmlV1Parser, NewsletterEmailV1Parser, VenuePilotGraphQLV1Parser, WixCalendarV1Parser,
+    WixWarmupV1Parser,
+};
+pub use dates::DateHints;
+pub use venue::VenueParser;
+
+pub use sms_core::pipeline_api::parse::{ParsedRecord, Parser, RecordChange};
+
+/// Build an HTML document straight from `reader`, decoding invalid UTF-8 lossily the way
+/// `String::from_utf8_lossy` would. Returns the document and how many bytes were read.
+pub fn read_html(reader: &mut dyn std::io::BufRead) -> std::io::Result<(scraper::Html, usize)> {
+    use html5ever::tendril::TendrilSink;
+
+    struct Counting<'a> {
+        inner: &'a mut dyn std::io::BufRead,
+        read: usize,
+    }
+    impl std::io::Read for Counting<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            let n = self.inner.read(buf)?;
+            self.read += n;
+            Ok(n)
+        }
+    }
+
+    let mut counting = Counting { inner: reader, read: 0 };
+    let document = html5ever::parse_document(scraper::Html::new_document(), Default::default())
+        .from_utf8()
+        .read_from(&mut counting)?;
+    Ok((document, counting.read))
+}
@@ -0,0 +1,100 @@
+//! Door and show times from listing text like "Doors 7pm / Show 8pm".
+
+use chrono::NaiveTime;
+use regex::Regex;
+use std::sync::OnceLock;
+
+/// When doors open and when the show starts, as far as a listing says
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ShowTimes {
+    pub doors: Option<NaiveTime>,
+    pub show: Option<NaiveTime>,
+}
+
+impl ShowTimes {
+    /// The event's start time: the show time, or the doors time for listings that give only that
+    pub fn start_time(&self) -> Option<NaiveTime> {
+        self.show.or(self.doors)
+    }
+}
+
+/// Parse a clock time in any of the formats venue sites use ("19:30:00", "19:30", "7:30 PM",
+/// "7pm", "9 p.m.")
+pub fn parse_time(time_str: &str) -> Option<NaiveTime> {
+    let cleaned = time_str.trim().to_uppercase().replace('.', "");
+    ["%H:%M:%S", "%H:%M", "%I:%M %p", "%l:%M %p", "%I:%M%p", "%l:%M%p"]
+        .iter()
+        .find_map(|fmt| NaiveTime::parse_from_str(&cleaned, fmt).ok())
+        .or_else(|| {
+            // Hour-only times like "7 PM" or "7PM"
+            let (hour, meridiem) = cleaned.split_at(cleaned.find(['A', 'P'])?);
+            let hour: u32 = hour.trim().parse().ok().filter(|h| (1..=12).contains(h))?;
+            let hour = match meridiem.trim() {
+                "AM" => hour % 12,
+                "PM" => hour % 12 + 12,
+                _ => return None,
+            };
+            NaiveTime::from_hms_opt(hour, 0, 0)
+        })
+}
+
+/// Split listing text such as "Doors 7pm / Show 8pm", "Doors: 6:00 PM" or "doors open at
+/// 7, music at 8:30pm" into door and show times. A time labelled "doors" is the doors time;
+/// one labelled "show", "music" or "starts", or carrying no label, is the show time.
+pub fn split_show_times(text: &str) -> ShowTimes {
+    static PATTERN: OnceLock<Regex> = OnceLock::new();
+    let pattern = PATTERN.get_or_init(|| {
+        Regex::new(r"(?i)(?:\b(?P<label>doors?|show|music|starts?)\b(?:\s+open)?\s*(?:at|@|:|-)?\s*)?\b(?P<time>\d{1,2}(?::\d{2}){0,2}(?:\s*[ap]\.?\s?m\b\.?)?)")
+            .unwrap()
+    });
+
+    let mut times = ShowTimes::default();
+    for caps in pattern.captures_iter(text) {
+        let labelled = caps.name("label").map(|l| l.as_str().to_lowercase());
+        let time = caps["time"].replace(' ', "");
+        // A bare number is only a time when labelled ("doors at 7"); otherwise it's an age or price
+        let time = match parse_time(&time) {
+            Some(time) => Some(time),
+            None if labelled.is_some() => time.parse::<u32>().ok().filter(|h| (1..=11).contains(h)).and_then(|h| NaiveTime::from_hms_opt(h + 12, 0, 0)),
+            None => None,
+        };
+        let Some(time) = time else { continue };
+        let slot = match labelled.as_deref() {
+            Some(label) if label.starts_with("door") => &mut times.doors,
+            _ => &mut times.show,
+        };
+        slot.get_or_insert(time);
+    }
+    times
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn times_parse_in_every_listing_format() {
+        let t = |h, m| NaiveTime::from_hms_opt(h, m, 0);
+        assert_eq!(parse_time("19:30:00"), t(19, 30));
+        assert_eq!(parse_time("20:00"), t(20, 0));
+        assert_eq!(parse_time("7:30 PM"), t(19, 30));
+        assert_eq!(parse_time("8:00pm"), t(20, 0));
+        assert_eq!(parse_time("9 p.m."), t(21, 0));
+        assert_eq!(parse_time("12AM"), t(0, 0));
+        assert_eq!(parse_time("doors"), None);
+    }
+
+    #[test]
+    fn doors_and_show_times_split_apart() {
+        let t = |h, m| NaiveTime::from_hms_opt(h, m, 0);
+        let times = split_show_times("Doors 7pm / Show 8pm");
+        assert_eq!((times.doors, times.show), (t(19, 0), t(20, 0)));
+        let times = split_show_times("Doors: 6:00 PM");
+        assert_eq!((times.doors, times.show, times.start_time()), (t(18, 0), None, t(18, 0)));
+        let times = split_show_times("doors open at 7, music at 8:30 p.m.");
+        assert_eq!((times.doors, times.show), (t(19, 0), t(20, 30)));
+        let times = split_show_times("21+ | $15 | 9 PM");
+        assert_eq!((times.doors, times.show), (None, t(21, 0)));
+        assert_eq!(split_show_times("Aug 28, 2025"), ShowTimes::default());
+    }
+}
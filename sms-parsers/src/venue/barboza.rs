@@ -0,0 +1,146 @@
+use super::{event_day, page_events, VenueParser};
+use sms_core::common::constants::{BARBOZA_API, BARBOZA_VENUE_NAME};
+use sms_core::common::error::{Result, ScraperError};
+use sms_core::common::types::{EventArgs, RawDataInfo, RawEventData};
+use chrono::NaiveDate;
+use crate::dates::DateHints;
+use crate::envelope::BarbozaHtmlV1Parser;
+use crate::schedule::split_show_times;
+
+pub struct BarbozaParser {
+    dates: DateHints,
+}
+
+impl BarbozaParser {
+    pub fn new(dates: DateHints) -> Self {
+        Self { dates }
+    }
+
+    /// Parse date from format like "Aug 28" or "Sep 2", in the year that keeps it on an upcoming calendar
+    fn parse_date(&self, date_str: &str) -> Option<NaiveDate> {
+        let (month, day) = date_str.split_once(char::is_whitespace)?;
+        self.dates.resolve_named(month, day.trim().parse().ok()?)
+    }
+
+    /// The envelope parser's `event_day`, or the `date_text` of an event stored before it set one
+    fn event_day(&self, raw_data: &RawEventData) -> Result<NaiveDate> {
+        if let Some(day) = event_day(raw_data) {
+            return Ok(day);
+        }
+        let date_text = raw_data["date_text"]
+            .as_str()
+            .ok_or_else(|| ScraperError::MissingField("date_text not found".into()))?;
+        self.parse_date(date_text).ok_or_else(|| ScraperError::Api {
+            message: format!("Failed to parse date: {}", date_text),
+        })
+    }
+}
+
+#[async_trait::async_trait]
+impl VenueParser for BarbozaParser {
+    fn venue_name(&self) -> &'static str {
+        BARBOZA_VENUE_NAME
+    }
+
+    async fn parse_events(&self, payload: &[u8]) -> Result<Vec<RawEventData>> {
+        let parser = BarbozaHtmlV1Parser::new(BARBOZA_API.to_string(), String::new(), String::new(), self.dates.clone());
+        page_events(parser, payload)
+    }
+
+    fn extract_raw_data_info(&self, raw_data: &RawEventData) -> Result<RawDataInfo> {
+        let event_day = self.event_day(raw_data)?;
+
+        // Extract title or generate from date
+        let title = raw_data["title"]
+            .as_str()
+            .unwrap_or_else(|| raw_data["id"].as_str().unwrap_or("Unknown Event"));
+
+        // Generate event ID
+        let event_api_id = raw_data["id"]
+            .as_str()
+            .map(|s| s.to_string())
+            .unwrap_or_else(|| format!("barboza_{}", event_day.format("%Y%m%d")));
+
+        Ok(RawDataInfo {
+            event_api_id,
+            event_name: title.trim().to_string(),
+            venue_name: BARBOZA_VENUE_NAME.to_string(),
+            event_day,
+        })
+    }
+
+    fn extract_event_args(&self, raw_data: &RawEventData) -> Result<EventArgs> {
+        let event_day = self.event_day(raw_data)?;
+
+        // Extract title (headliner)
+        let title = raw_data["title"]
+            .as_str()
+            .unwrap_or_else(|| raw_data["id"].as_str().unwrap_or("TBA"))
+            .trim()
+            .to_string();
+
+        // Build a full title that includes supporting acts if available
+        let full_title = if let Some(supporting) = raw_data["supporting_acts"].as_str() {
+            format!("{} with {}", title, supporting)
+        } else {
+            title.clone()
+        };
+
+        // "Doors: 6:00 PM", sometimes with a separate show time
+        let times = raw_data["time_text"]
+            .as_str()
+            .map(split_show_times)
+            .unwrap_or_default();
+
+        // Extract ticket URL - prefer the ticket purchase link, fall back to detail URL
+        let event_url = raw_data["ticket_url"]
+            .as_str()
+            .or_else(|| raw_data["detail_url"].as_str())
+            .map(|s| {
+                if s.starts_with("http") {
+                    s.to_string()
+                } else {
+                    format!("https://www.thebarboza.com{}", s)
+                }
+            })
+            .or_else(|| Some("https://www.thebarboza.com/events".to_string()));
+
+        // Build description from available metadata
+        let mut description_parts = Vec::new();
+        
+        if let Some(promoter) = raw_data["promoter"].as_str() {
+            description_parts.push(promoter.to_string());
+        }
+        
+        if let Some(age) = raw_data["age_restriction"].as_str() {
+            description_parts.push(format!("Age: {}", age));
+        }
+        
+        if let Some(venue) = raw_data["venue"].as_str() {
+            if venue != BARBOZA_VENUE_NAME {
+                description_parts.push(format!("Venue: {}", venue));
+            }
+        }
+        
+        let description = if !description_parts.is_empty() {
+            Some(description_parts.join(" | "))
+        } else {
+            raw_data["description"].as_str().map(|s| s.to_string())
+        };
+
+        // Extract image URL if available
+        let event_image_url = raw_data["image_url"]
+            .as_str()
+            .map(|s| s.to_string());
+
+        Ok(EventArgs {
+            title: full_title,
+            event_day,
+            start_time: times.start_time(),
+            event_url,
+            description,
+            event_image_url,
+            doors_time: times.doors,
+        })
+    }
+}
\ No newline at end of file
@@ -1,12 +1,17 @@
-use super::super::base::VenueParser;
-use crate::common::constants::{BLUE_MOON_VENUE_NAME};
+use super::{plan_events, VenueParser};
+use crate::envelope::WixCalendarV1Parser;
+use sms_core::common::constants::{BLUE_MOON_API, BLUE_MOON_VENUE_NAME};
 use sms_core::common::error::{Result, ScraperError};
 use sms_core::common::types::{EventArgs, RawDataInfo, RawEventData};
-use serde_json::Value;
-use tracing::debug;
 
 pub struct BlueMoonParser;
 
+impl Default for BlueMoonParser {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl BlueMoonParser {
     pub fn new() -> Self {
         Self
@@ -20,23 +25,7 @@ impl VenueParser for BlueMoonParser {
     }
 
     async fn parse_events(&self, payload: &[u8]) -> Result<Vec<RawEventData>> {
-        let data: Value = serde_json::from_slice(payload)?;
-        let events_by_date = data["eventsByDates"].as_object().ok_or_else(|| {
-            ScraperError::MissingField("eventsByDates not found".into())
-        })?;
-
-        let mut all_events = Vec::new();
-        for (day, events) in events_by_date {
-            if let Some(event_array) = events.as_array() {
-                debug!("Processing {} events for date {}", event_array.len(), day);
-                for event in event_array {
-                    let mut event_clone = event.clone();
-                    event_clone["event_day"] = day.clone().into();
-                    all_events.push(event_clone);
-                }
-            }
-        }
-        Ok(all_events)
+        plan_events(WixCalendarV1Parser::new(BLUE_MOON_API.to_string(), String::new(), String::new()), payload)
     }
 
     fn extract_raw_data_info(&self, raw_data: &RawEventData) -> Result<RawDataInfo> {
@@ -99,6 +88,7 @@ impl VenueParser for BlueMoonParser {
             event_url: None,
             description: None,
             event_image_url: None,
+            doors_time: None,
         })
     }
 }
\ No newline at end of file
@@ -1,10 +1,18 @@
-use super::super::base::VenueParser;
-use crate::common::constants::CONOR_BYRNE_VENUE_NAME;
+use super::{plan_events, VenueParser};
+use chrono::NaiveTime;
+use crate::envelope::VenuePilotGraphQLV1Parser;
+use sms_core::common::constants::{CONOR_BYRNE_API, CONOR_BYRNE_VENUE_NAME};
 use sms_core::common::error::{Result, ScraperError};
 use sms_core::common::types::{EventArgs, RawDataInfo, RawEventData};
 
 pub struct ConorByrneParser;
 
+impl Default for ConorByrneParser {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl ConorByrneParser {
     pub fn new() -> Self {
         Self
@@ -17,9 +25,8 @@ impl VenueParser for ConorByrneParser {
         CONOR_BYRNE_VENUE_NAME
     }
 
-    async fn parse_events(&self, _payload: &[u8]) -> Result<Vec<RawEventData>> {
-        // Placeholder - would contain actual Conor Byrne parsing logic
-        Ok(vec![])
+    async fn parse_events(&self, payload: &[u8]) -> Result<Vec<RawEventData>> {
+        plan_events(VenuePilotGraphQLV1Parser::new(CONOR_BYRNE_API.to_string(), String::new(), String::new()), payload)
     }
 
     fn extract_raw_data_info(&self, raw_data: &RawEventData) -> Result<RawDataInfo> {
@@ -57,13 +64,20 @@ impl VenueParser for ConorByrneParser {
                 message: format!("Failed to parse event_day: {e}"),
             })?;
 
+        let time = |field: &str| {
+            raw_data[field]
+                .as_str()
+                .and_then(|t| NaiveTime::parse_from_str(t, "%H:%M:%S").ok())
+        };
+
         Ok(EventArgs {
             title: title.to_string(),
             event_day,
-            start_time: None,
-            event_url: None,
-            description: None,
+            start_time: time("start_time"),
+            event_url: raw_data["ticket_url"].as_str().map(str::to_string),
+            description: raw_data["description"].as_str().filter(|d| !d.is_empty()).map(str::to_string),
             event_image_url: None,
+            doors_time: time("doors_time"),
         })
     }
 }
\ No newline at end of file
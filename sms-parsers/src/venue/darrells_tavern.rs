@@ -0,0 +1,124 @@
+use super::{event_day, plan_events, read_payload, Payload, VenueParser};
+use sms_core::common::constants::{DARRELLS_TAVERN_API, DARRELLS_TAVERN_VENUE_NAME};
+use sms_core::common::error::{Result, ScraperError};
+use sms_core::common::types::{EventArgs, RawDataInfo, RawEventData};
+use chrono::NaiveDate;
+use serde_json::json;
+use crate::dates::DateHints;
+use crate::envelope::DarrellsHtmlV1Parser;
+
+pub struct DarrellsTavernParser {
+    dates: DateHints,
+}
+
+impl DarrellsTavernParser {
+    pub fn new(dates: DateHints) -> Self {
+        Self { dates }
+    }
+}
+
+#[async_trait::async_trait]
+impl VenueParser for DarrellsTavernParser {
+    fn venue_name(&self) -> &'static str {
+        DARRELLS_TAVERN_VENUE_NAME
+    }
+
+    async fn parse_events(&self, payload: &[u8]) -> Result<Vec<RawEventData>> {
+        match read_payload(payload) {
+            Payload::Events(events) => Ok(events),
+            Payload::Page(page) => {
+                let parser = DarrellsHtmlV1Parser::new(DARRELLS_TAVERN_API.to_string(), String::new(), String::new(), self.dates.clone());
+                Ok(nights(plan_events(parser, &page)?))
+            }
+        }
+    }
+
+    fn extract_raw_data_info(&self, raw_data: &RawEventData) -> Result<RawDataInfo> {
+        let title = raw_data["title"]
+            .as_str()
+            .ok_or_else(|| ScraperError::MissingField("title not found".into()))?;
+        let id = raw_data["id"]
+            .as_str()
+            .ok_or_else(|| ScraperError::MissingField("id not found".into()))?;
+        let event_day_str = raw_data["event_day"]
+            .as_str()
+            .ok_or_else(|| ScraperError::MissingField("event_day not found".into()))?;
+        let event_day = chrono::NaiveDate::parse_from_str(event_day_str, "%Y-%m-%d")
+            .map_err(|e| ScraperError::Api {
+                message: format!("Failed to parse event_day: {e}"),
+            })?;
+
+        Ok(RawDataInfo {
+            event_api_id: id.to_string(),
+            event_name: title.to_string(),
+            venue_name: DARRELLS_TAVERN_VENUE_NAME.to_string(),
+            event_day,
+        })
+    }
+
+    fn extract_event_args(&self, raw_data: &RawEventData) -> Result<EventArgs> {
+        let title = raw_data["title"]
+            .as_str()
+            .ok_or_else(|| ScraperError::MissingField("title not found".into()))?;
+        let event_day_str = raw_data["event_day"]
+            .as_str()
+            .ok_or_else(|| ScraperError::MissingField("event_day not found".into()))?;
+        let event_day = chrono::NaiveDate::parse_from_str(event_day_str, "%Y-%m-%d")
+            .map_err(|e| ScraperError::Api {
+                message: format!("Failed to parse event_day: {e}"),
+            })?;
+
+        Ok(EventArgs {
+            title: title.to_string(),
+            event_day,
+            start_time: None,
+            event_url: None,
+            description: None,
+            event_image_url: None,
+            doors_time: None,
+        })
+    }
+}
+
+/// One event per night from the envelope parser's record per performer, billed in listing order
+fn nights(performers: Vec<RawEventData>) -> Vec<RawEventData> {
+    let mut nights: Vec<(NaiveDate, Vec<String>)> = Vec::new();
+    for record in &performers {
+        let (Some(day), Some(band)) = (event_day(record), record["title"].as_str()) else {
+            continue;
+        };
+        match nights.iter_mut().find(|(night, _)| *night == day) {
+            Some((_, bands)) => bands.push(band.to_string()),
+            None => nights.push((day, vec![band.to_string()])),
+        }
+    }
+    nights
+        .into_iter()
+        .map(|(day, bands)| {
+            json!({
+                "id": format!("darrells_{}", day.format("%Y_%m_%d")),
+                "title": bands.join(" / "),
+                "event_day": day.format("%Y-%m-%d").to_string(),
+                "bands": bands,
+                "venue": DARRELLS_TAVERN_VENUE_NAME
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn each_night_on_the_page_is_one_event() {
+        let august = DateHints::new(chrono_tz::America::Los_Angeles, "en-US").with_reference("2025-08-01".parse().unwrap());
+        let parser = DarrellsTavernParser::new(august);
+        let page = br#"<div class="entry-content"><h1>FRI 08.15</h1><p><a href="https://a.example">Band A</a>
+Band B</p></div>"#;
+        let events = parser.parse_events(page).await.unwrap();
+        assert_eq!(events.len(), 1);
+        let info = parser.extract_raw_data_info(&events[0]).unwrap();
+        assert_eq!((info.event_api_id.as_str(), info.event_name.as_str()), ("darrells_2025_08_15", "Band A / Band B"));
+    }
+}
@@ -0,0 +1,121 @@
+use super::{event_day, page_events, VenueParser};
+use sms_core::common::constants::{KEXP_API, KEXP_VENUE_NAME};
+use sms_core::common::error::{Result, ScraperError};
+use sms_core::common::types::{EventArgs, RawDataInfo, RawEventData};
+use chrono::{NaiveDate, NaiveTime};
+use crate::dates::DateHints;
+use crate::envelope::KexpHtmlV1Parser;
+
+pub struct KexpParser {
+    dates: DateHints,
+}
+
+impl KexpParser {
+    pub fn new(dates: DateHints) -> Self {
+        Self { dates }
+    }
+}
+
+#[async_trait::async_trait]
+impl VenueParser for KexpParser {
+    fn venue_name(&self) -> &'static str {
+        KEXP_VENUE_NAME
+    }
+
+    async fn parse_events(&self, payload: &[u8]) -> Result<Vec<RawEventData>> {
+        page_events(KexpHtmlV1Parser::new(KEXP_API.to_string(), String::new(), String::new()), payload)
+    }
+
+    fn extract_raw_data_info(&self, raw_data: &RawEventData) -> Result<RawDataInfo> {
+        let title = raw_data["title"]
+            .as_str()
+            .ok_or_else(|| ScraperError::MissingField("title not found".into()))?;
+        let event_day = self.event_day(raw_data)?;
+        // Events without a detail page are keyed by title and day
+        let event_api_id = raw_data["id"]
+            .as_str()
+            .map(str::to_string)
+            .unwrap_or_else(|| format!("{}:{}", event_day, title));
+
+        Ok(RawDataInfo {
+            event_api_id,
+            event_name: title.to_string(),
+            venue_name: KEXP_VENUE_NAME.to_string(),
+            event_day,
+        })
+    }
+
+    fn extract_event_args(&self, raw_data: &RawEventData) -> Result<EventArgs> {
+        let title = raw_data["title"]
+            .as_str()
+            .ok_or_else(|| ScraperError::MissingField("title not found".into()))?;
+        let event_day = self.event_day(raw_data)?;
+
+        // The envelope parser's `event_time`, or the `start_time` of an event stored before it
+        let start_time = raw_data["event_time"].as_str().or(raw_data["start_time"].as_str()).and_then(|s| {
+            // Parse time strings like "noon", "12:00", etc.
+            if s.to_lowercase() == "noon" {
+                Some(NaiveTime::from_hms_opt(12, 0, 0).unwrap())
+            } else if let Ok(time) = NaiveTime::parse_from_str(s, "%H:%M") {
+                Some(time)
+            } else {
+                NaiveTime::parse_from_str(s, "%I:%M %p").ok()
+            }
+        });
+        let event_url = raw_data["event_url"].as_str().map(|s| s.to_string());
+        let description = raw_data["description"].as_str().filter(|s| !s.is_empty()).map(|s| s.to_string());
+        let event_image_url = raw_data["event_image_url"].as_str().map(|s| s.to_string());
+
+        Ok(EventArgs {
+            title: title.to_string(),
+            event_day,
+            start_time,
+            event_url,
+            description,
+            event_image_url,
+            doors_time: None,
+        })
+    }
+}
+
+impl KexpParser {
+    /// `event_day` as a date, or the listing's date header ("Fri, Aug 15", "Aug 31st") the
+    /// envelope parser keeps as it found it
+    fn event_day(&self, raw_data: &RawEventData) -> Result<NaiveDate> {
+        if let Some(day) = event_day(raw_data) {
+            return Ok(day);
+        }
+        let date_text = raw_data["event_day"]
+            .as_str()
+            .ok_or_else(|| ScraperError::MissingField("event_day not found".into()))?;
+        self.parse_date(date_text).ok_or_else(|| ScraperError::Api {
+            message: format!("Failed to parse event_day: {}", date_text),
+        })
+    }
+
+    /// A header's last two words as month and day, in the year that keeps it on an upcoming calendar
+    fn parse_date(&self, date_text: &str) -> Option<NaiveDate> {
+        let words: Vec<&str> = date_text.split_whitespace().map(|w| w.trim_matches(',')).collect();
+        let [.., month, day] = words.as_slice() else {
+            return None;
+        };
+        let day = day.trim_end_matches(|c: char| c.is_ascii_alphabetic()).parse().ok()?;
+        self.dates.resolve_named(month, day)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn pages_are_read_with_the_envelope_parser() {
+        let august = DateHints::new(chrono_tz::America::Los_Angeles, "en-US").with_reference("2025-08-01".parse().unwrap());
+        let parser = KexpParser::new(august);
+        let page = br#"<h2>Fri, Aug 15</h2><article class="EventItem"><div class="EventItem-body"><h3><a href="/events/kexp-live-42">Live on KEXP</a></h3></div></article>"#;
+        let events = parser.parse_events(page).await.unwrap();
+        assert_eq!(events.len(), 1);
+        let info = parser.extract_raw_data_info(&events[0]).unwrap();
+        assert_eq!((info.event_name.as_str(), info.event_day), ("Live on KEXP", NaiveDate::from_ymd_opt(2025, 8, 15).unwrap()));
+    }
+}
@@ -0,0 +1,97 @@
+// Parsers behind the legacy crawler path (`BaseCrawler` and the full pipeline), turning a
+// venue's raw payload into RawEventData items. Each reads pages with the venue's envelope
+// parser, so the crawler and the gateway share one implementation per venue.
+
+use sms_core::common::error::{Result, ScraperError};
+use sms_core::common::types::{EventArgs, RawDataInfo, RawEventData};
+use serde_json::Value;
+
+use crate::Parser;
+
+pub mod blue_moon;
+pub mod sea_monster;
+pub mod darrells_tavern;
+pub mod kexp;
+pub mod barboza;
+pub mod neumos;
+pub mod conor_byrne;
+
+pub use blue_moon::BlueMoonParser;
+pub use sea_monster::SeaMonsterParser;
+pub use darrells_tavern::DarrellsTavernParser;
+pub use kexp::KexpParser;
+pub use barboza::BarbozaParser;
+pub use neumos::NeumosParser;
+pub use conor_byrne::ConorByrneParser;
+
+/// Trait for venue-specific parsing logic
+#[async_trait::async_trait]
+pub trait VenueParser: Send + Sync {
+    /// Parse raw HTTP payload into structured event data
+    async fn parse_events(&self, payload: &[u8]) -> Result<Vec<RawEventData>>;
+    
+    /// Extract metadata for raw data storage
+    fn extract_raw_data_info(&self, raw_data: &RawEventData) -> Result<RawDataInfo>;
+    
+    /// Extract event arguments for processing
+    fn extract_event_args(&self, raw_data: &RawEventData) -> Result<EventArgs>;
+    
+    /// Get the venue name for this parser
+    fn venue_name(&self) -> &'static str;
+}
+
+/// What a crawler hands a parser: event JSON stored by an earlier crawl, or a page
+enum Payload {
+    Events(Vec<RawEventData>),
+    Page(Vec<u8>),
+}
+
+/// Stored event JSON passes through; a JSON string, HTML escaped by an AJAX endpoint, is
+/// unwrapped into the page it holds
+fn read_payload(payload: &[u8]) -> Payload {
+    match serde_json::from_slice::<Value>(payload) {
+        Ok(Value::Array(events)) => Payload::Events(events),
+        Ok(event @ Value::Object(_)) => Payload::Events(vec![event]),
+        Ok(Value::String(page)) => Payload::Page(page.into_bytes()),
+        _ => Payload::Page(payload.to_vec()),
+    }
+}
+
+/// The event records `parser`, the venue's envelope parser, reads from an HTML `payload`,
+/// with stored event JSON passed through. Records keep the parser's external id as `id`,
+/// and the placeholder a parser emits for a page without events is dropped.
+fn page_events(parser: impl Parser, payload: &[u8]) -> Result<Vec<RawEventData>> {
+    match read_payload(payload) {
+        Payload::Events(events) => Ok(events),
+        Payload::Page(page) => plan_events(parser, &page),
+    }
+}
+
+/// The event records `parser` reads from `payload`, as `page_events` leaves them; the
+/// whole-document record a JSON parser falls back to is dropped too
+fn plan_events(parser: impl Parser, payload: &[u8]) -> Result<Vec<RawEventData>> {
+    let records = parser.parse(payload).map_err(|e| ScraperError::Api { message: e.to_string() })?;
+    Ok(records
+        .into_iter()
+        .filter(|parsed| !is_placeholder(&parsed.record) && parsed.record_path != "$")
+        .map(|parsed| {
+            let mut record = parsed.record;
+            if let (Some(id), Some(fields)) = (parsed.external_id, record.as_object_mut()) {
+                fields.entry("id").or_insert(Value::String(id));
+            }
+            record
+        })
+        .collect())
+}
+
+/// The `{"html_len": n}` record envelope parsers emit when a page has no events
+fn is_placeholder(record: &Value) -> bool {
+    record.as_object().is_some_and(|o| o.len() == 1 && o.contains_key("html_len"))
+}
+
+/// A record's `YYYY-MM-DD` `event_day`
+fn event_day(raw_data: &RawEventData) -> Option<chrono::NaiveDate> {
+    raw_data["event_day"]
+        .as_str()
+        .and_then(|day| chrono::NaiveDate::parse_from_str(day, "%Y-%m-%d").ok())
+}
@@ -0,0 +1,164 @@
+use super::{event_day, page_events, VenueParser};
+use sms_core::common::constants::{NEUMOS_API, NEUMOS_VENUE_NAME};
+use sms_core::common::error::{Result, ScraperError};
+use sms_core::common::types::{EventArgs, RawDataInfo, RawEventData};
+use chrono::NaiveDate;
+use crate::dates::DateHints;
+use crate::envelope::NeumosHtmlV1Parser;
+use crate::schedule::split_show_times;
+
+pub struct NeumosParser {
+    dates: DateHints,
+}
+
+impl NeumosParser {
+    pub fn new(dates: DateHints) -> Self {
+        Self { dates }
+    }
+
+    /// Parse date from format like "Aug 29" or "Sep 5", in the year that keeps it on an upcoming calendar
+    fn parse_date(&self, date_str: &str) -> Option<NaiveDate> {
+        let (month, day) = date_str.split_once(char::is_whitespace)?;
+        self.dates.resolve_named(month, day.trim().parse().ok()?)
+    }
+
+    /// The envelope parser's `event_day`, or the `date_text` of an event stored before it set one
+    fn event_day(&self, raw_data: &RawEventData) -> Result<NaiveDate> {
+        if let Some(day) = event_day(raw_data) {
+            return Ok(day);
+        }
+        let date_text = raw_data["date_text"]
+            .as_str()
+            .ok_or_else(|| ScraperError::MissingField("date_text not found".into()))?;
+        self.parse_date(date_text).ok_or_else(|| ScraperError::Api {
+            message: format!("Failed to parse date: {}", date_text),
+        })
+    }
+}
+
+#[async_trait::async_trait]
+impl VenueParser for NeumosParser {
+    fn venue_name(&self) -> &'static str {
+        NEUMOS_VENUE_NAME
+    }
+
+    async fn parse_events(&self, payload: &[u8]) -> Result<Vec<RawEventData>> {
+        let parser = NeumosHtmlV1Parser::new(NEUMOS_API.to_string(), String::new(), String::new(), self.dates.clone());
+        page_events(parser, payload)
+    }
+
+    fn extract_raw_data_info(&self, raw_data: &RawEventData) -> Result<RawDataInfo> {
+        let event_day = self.event_day(raw_data)?;
+
+        // Extract title or generate from date
+        let title = raw_data["title"]
+            .as_str()
+            .unwrap_or_else(|| raw_data["id"].as_str().unwrap_or("Unknown Event"));
+
+        // Generate event ID
+        let event_api_id = raw_data["id"]
+            .as_str()
+            .map(|s| s.to_string())
+            .unwrap_or_else(|| format!("neumos_{}", event_day.format("%Y%m%d")));
+
+        Ok(RawDataInfo {
+            event_api_id,
+            event_name: title.trim().to_string(),
+            venue_name: NEUMOS_VENUE_NAME.to_string(),
+            event_day,
+        })
+    }
+
+    fn extract_event_args(&self, raw_data: &RawEventData) -> Result<EventArgs> {
+        let event_day = self.event_day(raw_data)?;
+
+        // Extract title (headliner)
+        let title = raw_data["title"]
+            .as_str()
+            .unwrap_or_else(|| raw_data["id"].as_str().unwrap_or("TBA"))
+            .trim()
+            .to_string();
+
+        // Build a full title that includes supporting acts if available
+        let full_title = if let Some(supporting) = raw_data["supporting_acts"].as_str() {
+            format!("{} with {}", title, supporting)
+        } else {
+            title.clone()
+        };
+
+        // "Doors: 6:00 PM", sometimes with a separate show time
+        let times = raw_data["time_text"]
+            .as_str()
+            .map(split_show_times)
+            .unwrap_or_default();
+
+        // Extract ticket URL - prefer the ticket purchase link, fall back to detail URL
+        let event_url = raw_data["ticket_url"]
+            .as_str()
+            .or_else(|| raw_data["detail_url"].as_str())
+            .map(|s| {
+                if s.starts_with("http") {
+                    s.to_string()
+                } else {
+                    format!("https://www.neumos.com{}", s)
+                }
+            })
+            .or_else(|| Some("https://www.neumos.com/events".to_string()));
+
+        // Build description from available metadata
+        let mut description_parts = Vec::new();
+        
+        if let Some(tour) = raw_data["tour_name"].as_str() {
+            description_parts.push(format!("Tour: {}", tour));
+        }
+        
+        if let Some(promoter) = raw_data["promoter"].as_str() {
+            description_parts.push(promoter.to_string());
+        }
+        
+        if let Some(age) = raw_data["age_restriction"].as_str() {
+            description_parts.push(format!("Age: {}", age));
+        }
+        
+        let description = if !description_parts.is_empty() {
+            Some(description_parts.join(" | "))
+        } else {
+            raw_data["description"].as_str().map(|s| s.to_string())
+        };
+
+        // Extract image URL if available
+        let event_image_url = raw_data["image_url"]
+            .as_str()
+            .map(|s| s.to_string());
+
+        Ok(EventArgs {
+            title: full_title,
+            event_day,
+            start_time: times.start_time(),
+            event_url,
+            description,
+            event_image_url,
+            doors_time: times.doors,
+        })
+    }
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn stored_date_text_is_read_with_the_source_hints() {
+        let december = DateHints::new(chrono_tz::America::Los_Angeles, "en-US").with_reference("2025-12-20".parse().unwrap());
+        let info = NeumosParser::new(december).extract_raw_data_info(&json!({ "title": "Headliner", "date_text": "Jan 3" })).unwrap();
+        assert_eq!(info.event_day, NaiveDate::from_ymd_opt(2026, 1, 3).unwrap());
+    }
+
+    #[tokio::test]
+    async fn stored_events_pass_through_and_empty_pages_yield_none() {
+        let parser = NeumosParser::new(DateHints::default());
+        let stored = json!([{ "title": "Headliner", "event_day": "2025-08-15" }]).to_string();
+        assert_eq!(parser.parse_events(stored.as_bytes()).await.unwrap().len(), 1);
+        assert!(parser.parse_events(b"<html></html>").await.unwrap().is_empty());
+    }
+}
@@ -0,0 +1,97 @@
+use super::{plan_events, VenueParser};
+use crate::envelope::WixWarmupV1Parser;
+use sms_core::common::constants::{SEA_MONSTER_API, SEA_MONSTER_VENUE_NAME};
+use sms_core::common::error::{Result, ScraperError};
+use sms_core::common::types::{EventArgs, RawDataInfo, RawEventData};
+
+pub struct SeaMonsterParser;
+
+impl Default for SeaMonsterParser {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SeaMonsterParser {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[async_trait::async_trait]
+impl VenueParser for SeaMonsterParser {
+    fn venue_name(&self) -> &'static str {
+        SEA_MONSTER_VENUE_NAME
+    }
+
+    async fn parse_events(&self, payload: &[u8]) -> Result<Vec<RawEventData>> {
+        plan_events(WixWarmupV1Parser::new(SEA_MONSTER_API.to_string(), String::new(), String::new()), payload)
+    }
+
+    fn extract_raw_data_info(&self, raw_data: &RawEventData) -> Result<RawDataInfo> {
+        let title = raw_data["title"]
+            .as_str()
+            .ok_or_else(|| ScraperError::MissingField("title not found".into()))?;
+        let slug = raw_data["slug"]
+            .as_str()
+            .ok_or_else(|| ScraperError::MissingField("slug not found".into()))?;
+        let start_date_str = raw_data["scheduling"]["startDateFormatted"]
+            .as_str()
+            .ok_or_else(|| ScraperError::MissingField("startDateFormatted not found".into()))?;
+        
+        let event_day = chrono::NaiveDate::parse_from_str(start_date_str, "%B %d, %Y")
+            .map_err(|e| ScraperError::Api {
+                message: format!("Failed to parse event_day: {e}"),
+            })?;
+
+        Ok(RawDataInfo {
+            event_api_id: slug.to_string(),
+            event_name: title.to_string(),
+            venue_name: SEA_MONSTER_VENUE_NAME.to_string(),
+            event_day,
+        })
+    }
+
+    fn extract_event_args(&self, raw_data: &RawEventData) -> Result<EventArgs> {
+        let title = raw_data["title"]
+            .as_str()
+            .ok_or_else(|| ScraperError::MissingField("title not found".into()))?;
+        let start_date_str = raw_data["scheduling"]["startDateFormatted"]
+            .as_str()
+            .ok_or_else(|| ScraperError::MissingField("startDateFormatted not found".into()))?;
+        
+        let event_day = chrono::NaiveDate::parse_from_str(start_date_str, "%B %d, %Y")
+            .map_err(|e| ScraperError::Api {
+                message: format!("Failed to parse event_day: {e}"),
+            })?;
+
+        let start_time_str = raw_data["scheduling"]["startTimeFormatted"]
+            .as_str()
+            .ok_or_else(|| ScraperError::MissingField("startTimeFormatted not found".into()))?;
+        let start_time = chrono::NaiveTime::parse_from_str(start_time_str, "%I:%M %p").ok();
+
+        let slug = raw_data["slug"]
+            .as_str()
+            .ok_or_else(|| ScraperError::MissingField("slug not found".into()))?;
+
+        let image_url = raw_data
+            .get("mainImage")
+            .and_then(|i| i.get("url"))
+            .and_then(|u| u.as_str())
+            .map(|s| s.to_string());
+
+        let event_url = Some(format!(
+            "https://www.seamonsterlounge.com/event-info/{slug}"
+        ));
+
+        Ok(EventArgs {
+            title: title.trim().to_string(),
+            event_day,
+            start_time,
+            event_url,
+            description: None,
+            event_image_url: image_url,
+            doors_time: None,
+        })
+    }
+}
\ No newline at end of file
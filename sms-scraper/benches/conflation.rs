@@ -0,0 +1,160 @@
+//! Conflation throughput over synthetic batches of enriched venue records.
+//!
+//! Run with `cargo bench -p sms-scraper --features bench --bench conflation`.
+
+use chrono::Utc;
+use criterion::{criterion_group, criterion_main, BatchSize, Criterion, Throughput};
+use sms_core::domain::Venue;
+use sms_scraper::pipeline::processing::conflation::{Conflator, DefaultConflator};
+use sms_scraper::pipeline::processing::enrich::{
+    EnrichedRecord, EnrichmentMetadata, GeoProperties, PopulationDensity, ReferenceVersions,
+};
+use sms_scraper::pipeline::processing::normalize::{
+    NormalizationMetadata, NormalizedEntity, NormalizedRecord, RecordProvenance,
+};
+use sms_scraper::pipeline::processing::quality_gate::{
+    QualityAssessedRecord, QualityAssessment, QualityDecision,
+};
+
+const BATCH_SIZE: usize = 10_000;
+/// Distinct venues in a batch; the rest are the same venues listed again by other
+/// sources with slightly different spellings and coordinates
+const DISTINCT_VENUES: usize = 2_000;
+
+fn venue_record(i: usize) -> EnrichedRecord {
+    let venue_no = i % DISTINCT_VENUES;
+    let listing = i / DISTINCT_VENUES;
+    let name = if listing.is_multiple_of(2) {
+        format!("The Venue {}", venue_no)
+    } else {
+        format!("Venue {}", venue_no)
+    };
+    let lat = 47.55 + (venue_no as f64) * 0.0001 + (listing as f64) * 0.00002;
+    let lng = -122.40 + (venue_no as f64) * 0.0001;
+    let source_id = format!("source_{}", listing);
+
+    let venue = Venue {
+        id: None,
+        name: name.clone(),
+        name_lower: name.to_lowercase(),
+        slug: name.to_lowercase().replace(' ', "-"),
+        latitude: lat,
+        longitude: lng,
+        address: format!("{} E Pike St", 100 + venue_no),
+        postal_code: "98122".to_string(),
+        city: "Seattle".to_string(),
+        venue_url: None,
+        venue_image_url: None,
+        description: None,
+        neighborhood: None,
+        show_venue: true,
+        created_at: Utc::now(),
+        attributions: Vec::new(),
+        provisional: false,
+        aliases: Vec::new(),
+        accessibility_notes: None,
+        moderation: None,
+        age_restriction: None,
+    };
+
+    let normalized_record = NormalizedRecord {
+        entity: NormalizedEntity::Venue(venue),
+        provenance: RecordProvenance {
+            envelope_id: format!("env_{}", listing),
+            source_id,
+            payload_ref: format!("sha256:{:064x}", listing),
+            record_path: format!("$.venues[{}]", venue_no),
+            normalized_at: Utc::now(),
+            attribution: None,
+            change: None,
+            record_key: None,
+            endpoint_id: None,
+            external_id: None,
+        },
+        normalization: NormalizationMetadata {
+            confidence: 1.0,
+            warnings: Vec::new(),
+            geocoded: false,
+            strategy: "bench".to_string(),
+        },
+    };
+
+    EnrichedRecord {
+        quality_assessed_record: QualityAssessedRecord {
+            normalized_record,
+            quality_assessment: QualityAssessment {
+                decision: QualityDecision::Accept,
+                quality_score: 0.95,
+                issues: Vec::new(),
+                rule_version: "v1.0.0".to_string(),
+            },
+            assessed_at: Utc::now(),
+        },
+        enrichment: EnrichmentMetadata {
+            city: Some("Seattle".to_string()),
+            district: None,
+            region: Some("King County, WA".to_string()),
+            spatial_bin: Some("seattle_grid_47_-122".to_string()),
+            tags: vec!["venue".to_string()],
+            geo_properties: GeoProperties {
+                within_city_bounds: true,
+                distance_from_center_km: Some(3.0),
+                population_density: PopulationDensity::Urban,
+                transit_accessibility: None,
+                nearby_landmarks: Vec::new(),
+            },
+            reference_versions: ReferenceVersions {
+                city_boundaries: None,
+                admin_boundaries: None,
+                spatial_grid: None,
+                poi_data: None,
+                neighborhoods: None,
+            },
+            strategy: "bench".to_string(),
+            confidence: 0.9,
+            warnings: Vec::new(),
+        },
+        enriched_at: Utc::now(),
+    }
+}
+
+fn conflation(c: &mut Criterion) {
+    let batch: Vec<EnrichedRecord> = (0..BATCH_SIZE).map(venue_record).collect();
+
+    let mut group = c.benchmark_group("conflation");
+    group.sample_size(10);
+    group.throughput(Throughput::Elements(BATCH_SIZE as u64));
+
+    // Every record is matched against what the batch has resolved so far, as in a full run
+    group.bench_function("venues_10k", |b| {
+        b.iter_batched(
+            DefaultConflator::new,
+            |mut conflator| {
+                for record in &batch {
+                    let conflated = conflator.conflate(record).unwrap();
+                    conflator.remember(conflated);
+                }
+                conflator
+            },
+            BatchSize::LargeInput,
+        )
+    });
+
+    // Matching only, against a store that already holds every venue
+    let mut warmed = DefaultConflator::new();
+    for record in &batch[..DISTINCT_VENUES] {
+        let conflated = warmed.conflate(record).unwrap();
+        warmed.remember(conflated);
+    }
+    group.bench_function("venues_10k_known", |b| {
+        b.iter(|| {
+            for record in &batch {
+                criterion::black_box(warmed.conflate(record).unwrap());
+            }
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, conflation);
+criterion_main!(benches);
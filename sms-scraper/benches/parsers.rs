@@ -0,0 +1,212 @@
+//! Parse throughput for every envelope parser on calendar-sized payloads shaped like the
+//! pages each source serves.
+//!
+//! Run with `cargo bench -p sms-scraper --features bench --bench parsers`.
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use serde_json::json;
+use sms_parsers::{
+    BarbozaHtmlV1Parser, DarrellsHtmlV1Parser, DateHints, KexpHtmlV1Parser, NeumosHtmlV1Parser, Parser,
+    VenuePilotGraphQLV1Parser, WixCalendarV1Parser, WixWarmupV1Parser,
+};
+
+/// Roughly three months of listings, the lookahead most sources publish
+const EVENTS: usize = 200;
+
+const MONTHS: [&str; 12] = [
+    "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+];
+
+fn page(body: &str) -> String {
+    format!(
+        "<!DOCTYPE html><html><head><title>Calendar</title>\
+         <link rel=\"stylesheet\" href=\"/site.css\"></head>\
+         <body><nav><ul><li><a href=\"/\">Home</a></li><li><a href=\"/events\">Events</a></li></ul></nav>\
+         {}<footer><p>&copy; Venue</p></footer></body></html>",
+        body
+    )
+}
+
+fn wix_event(i: usize) -> serde_json::Value {
+    json!({
+        "id": format!("evt-{}", i),
+        "title": format!("Artist {} with Support {}", i, i + 1),
+        "slug": format!("artist-{}", i),
+        "scheduling": {
+            "config": {
+                "startDate": format!("2025-{:02}-{:02}T03:00:00.000Z", i % 12 + 1, i % 28 + 1),
+                "timeZoneId": "America/Los_Angeles"
+            },
+            "formatted": "Doors 7 PM"
+        },
+        "location": {"name": "Blue Moon Tavern", "address": "712 NE 45th St, Seattle, WA"},
+        "description": "Live music all night. 21+. No cover before 9."
+    })
+}
+
+fn wix_calendar_json() -> Vec<u8> {
+    let mut by_date = serde_json::Map::new();
+    for i in 0..EVENTS {
+        let day = format!("2025-{:02}-{:02}", i % 12 + 1, i % 28 + 1);
+        by_date
+            .entry(day)
+            .or_insert_with(|| json!([]))
+            .as_array_mut()
+            .unwrap()
+            .push(wix_event(i));
+    }
+    serde_json::to_vec(&json!({ "eventsByDates": by_date })).unwrap()
+}
+
+fn wix_warmup_html() -> Vec<u8> {
+    let events: Vec<_> = (0..EVENTS).map(wix_event).collect();
+    let warmup = json!({
+        "appsWarmupData": {
+            "140603ad-af8d-84a5-2c80-a0f60cb47351": {
+                "widgetcomp-events": {"events": {"events": events, "hasMore": false}}
+            }
+        }
+    });
+    page(&format!(
+        "<main><div id=\"SITE_CONTAINER\"></div></main>\
+         <script type=\"application/json\" id=\"wix-warmup-data\">{}</script>",
+        warmup
+    ))
+    .into_bytes()
+}
+
+fn darrells_html() -> Vec<u8> {
+    let mut body = String::from("<div class=\"entry-content\">");
+    for day in 0..EVENTS / 4 {
+        body.push_str(&format!("<h1>MUSIC {}.{}</h1>", day % 12 + 1, day % 28 + 1));
+        body.push_str(&format!(
+            "<p><a href=\"https://example.com/a{0}\">Headliner {0}</a>\n\
+             <a href=\"https://example.com/b{0}\">Opener {0}</a>\n\
+             Local Support {0}\nDOORS 8PM / SHOW 9PM\n$12</p>",
+            day
+        ));
+    }
+    body.push_str("</div>");
+    page(&body).into_bytes()
+}
+
+fn kexp_html() -> Vec<u8> {
+    let mut body = String::from("<main>");
+    for i in 0..EVENTS {
+        if i % 3 == 0 {
+            body.push_str(&format!("<h2>Friday, {} {}</h2>", MONTHS[i % 12], i % 28 + 1));
+        }
+        body.push_str(&format!(
+            "<article class=\"EventItem\">\
+             <div class=\"EventItem-DateTime\"><h5>{}:00 PM</h5></div>\
+             <div class=\"EventItem-body\"><h3><a href=\"/events/{i}\">Live on KEXP: Artist {i}</a></h3>\
+             <div class=\"u-h3\"><a href=\"/studio\">KEXP Gathering Space</a></div>\
+             <div class=\"EventItem-description\"><p>Session {i} broadcast live from the studio.</p></div></div>\
+             </article>",
+            i % 12 + 1,
+            i = i
+        ));
+    }
+    body.push_str("</main>");
+    page(&body).into_bytes()
+}
+
+/// Barboza and Neumos share a ticketing platform and its markup
+fn event_item_html(venue: &str) -> Vec<u8> {
+    let mut body = String::from("<div class=\"eventList\">");
+    for i in 0..EVENTS {
+        body.push_str(&format!(
+            "<div class=\"eventItem\">\
+             <div class=\"thumb\"><img src=\"https://images.example.com/{i}.jpg\" alt=\"\"></div>\
+             <div class=\"m-date\"><span class=\"m-date__month\">{month}</span><span class=\"m-date__day\">{day}</span></div>\
+             <div class=\"promotion-text tour\">The {i} Tour</div>\
+             <div class=\"promotion-text\">Promoter {i} presents</div>\
+             <h3 class=\"title\"><a href=\"/events/detail/{i}\">Headliner {i}</a></h3>\
+             <h4 class=\"tagline\">with Support {i}</h4>\
+             <div class=\"meta\"><span class=\"time\">Doors: 7:00 PM</span><span class=\"age\">21 &amp; Over</span>\
+             <span class=\"location\">{venue}</span></div>\
+             <a class=\"tickets onsalenow\" href=\"https://tickets.example.com/{i}\">Buy Tickets</a>\
+             </div>",
+            i = i,
+            month = MONTHS[i % 12],
+            day = i % 28 + 1,
+            venue = venue
+        ));
+    }
+    body.push_str("</div>");
+    page(&body).into_bytes()
+}
+
+fn venuepilot_json() -> Vec<u8> {
+    let events: Vec<_> = (0..EVENTS)
+        .map(|i| {
+            json!({
+                "id": i,
+                "name": format!("Artist {}", i),
+                "date": format!("2025-{:02}-{:02}", i % 12 + 1, i % 28 + 1),
+                "doorTime": "19:00:00",
+                "startTime": "20:00:00",
+                "minimumAge": 21,
+                "promoter": "Conor Byrne",
+                "support": format!("Support {}", i),
+                "description": "An evening of songs.",
+                "ticketsUrl": format!("https://tickets.example.com/{}", i),
+                "status": "published",
+                "artists": [{"name": format!("Artist {}", i), "bio": "Seattle songwriter"}],
+                "announceArtists": [{"name": format!("Support {}", i)}],
+                "venue": {"name": "Conor Byrne Pub"}
+            })
+        })
+        .collect();
+    serde_json::to_vec(&json!({"data": {"paginatedEvents": {"collection": events}}})).unwrap()
+}
+
+fn parsers(c: &mut Criterion) {
+    let id = || ("bench".to_string(), "env".to_string(), "sha256:bench".to_string());
+    let cases: Vec<(&str, Box<dyn Parser>, Vec<u8>)> = vec![
+        ("wix_calendar_v1", {
+            let (s, e, p) = id();
+            Box::new(WixCalendarV1Parser::new(s, e, p))
+        }, wix_calendar_json()),
+        ("wix_warmup_v1", {
+            let (s, e, p) = id();
+            Box::new(WixWarmupV1Parser::new(s, e, p))
+        }, wix_warmup_html()),
+        ("darrells_html_v1", {
+            let (s, e, p) = id();
+            Box::new(DarrellsHtmlV1Parser::new(s, e, p, DateHints::default()))
+        }, darrells_html()),
+        ("kexp_html_v1", {
+            let (s, e, p) = id();
+            Box::new(KexpHtmlV1Parser::new(s, e, p))
+        }, kexp_html()),
+        ("barboza_html_v1", {
+            let (s, e, p) = id();
+            Box::new(BarbozaHtmlV1Parser::new(s, e, p, DateHints::default()))
+        }, event_item_html("The Barboza")),
+        ("neumos_html_v1", {
+            let (s, e, p) = id();
+            Box::new(NeumosHtmlV1Parser::new(s, e, p, DateHints::default()))
+        }, event_item_html("Neumos")),
+        ("venuepilot_graphql_v1", {
+            let (s, e, p) = id();
+            Box::new(VenuePilotGraphQLV1Parser::new(s, e, p))
+        }, venuepilot_json()),
+    ];
+
+    let mut group = c.benchmark_group("parsers");
+    for (name, parser, payload) in &cases {
+        // A fixture the parser no longer understands would only time the fallback path
+        let records = parser.parse(payload).unwrap();
+        assert!(records.len() > 1, "{} fixture produced {} records", name, records.len());
+
+        group.throughput(Throughput::Bytes(payload.len() as u64));
+        group.bench_with_input(BenchmarkId::from_parameter(name), payload, |b, payload| {
+            b.iter(|| parser.parse(criterion::black_box(payload)).unwrap())
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, parsers);
+criterion_main!(benches);
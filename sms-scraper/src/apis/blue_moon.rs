@@ -1,6 +1,6 @@
 use crate::common::constants::{BLUE_MOON_API, BLUE_MOON_VENUE_NAME};
 use sms_core::common::error::{Result, ScraperError};
-use crate::pipeline::ingestion::ingest_common::fetch_payload_and_log;
+use crate::pipeline::ingestion::ingest_common::fetch_payloads_and_log;
 use sms_core::common::types::{EventApi, EventArgs, RawDataInfo, RawEventData};
 use serde_json::Value;
 use tracing::{debug, info, instrument};
@@ -21,20 +21,10 @@ impl BlueMoonCrawler {
             _client: reqwest::Client::new(),
         }
     }
-}
-
-#[async_trait::async_trait]
-impl EventApi for BlueMoonCrawler {
-    fn api_name(&self) -> &'static str {
-        BLUE_MOON_API
-    }
-
-    #[instrument(skip(self))]
-    async fn get_event_list(&self) -> Result<Vec<RawEventData>> {
-        // New path: use shared ingestion helper, then parse
-        let payload = fetch_payload_and_log(BLUE_MOON_API).await?;
 
-        let data: Value = serde_json::from_slice(&payload)?;
+    /// Events in one fetched payload; multi-endpoint and paginated sources deliver several
+    fn events_from_payload(&self, payload: &[u8]) -> Result<Vec<RawEventData>> {
+        let data: Value = serde_json::from_slice(payload)?;
         let events_by_date = data["eventsByDates"].as_object().ok_or_else(|| {
             ScraperError::MissingField("eventsByDates not found".into())
         })?;
@@ -68,6 +58,23 @@ impl EventApi for BlueMoonCrawler {
         );
         Ok(all_events)
     }
+}
+
+#[async_trait::async_trait]
+impl EventApi for BlueMoonCrawler {
+    fn api_name(&self) -> &'static str {
+        BLUE_MOON_API
+    }
+
+    #[instrument(skip(self))]
+    async fn get_event_list(&self) -> Result<Vec<RawEventData>> {
+        // New path: use shared ingestion helper, then parse
+        let mut events = Vec::new();
+        for payload in fetch_payloads_and_log(BLUE_MOON_API).await? {
+            events.extend(self.events_from_payload(&payload)?);
+        }
+        Ok(events)
+    }
 
     fn get_raw_data_info(&self, raw_data: &RawEventData) -> Result<RawDataInfo> {
         let title = raw_data["title"]
@@ -129,6 +136,7 @@ impl EventApi for BlueMoonCrawler {
             event_url: None,
             description: None,
             event_image_url: None,
+            doors_time: None,
         })
     }
 }
@@ -1,6 +1,6 @@
 use crate::common::constants::{DARRELLS_TAVERN_API, DARRELLS_TAVERN_VENUE_NAME};
 use sms_core::common::error::{Result, ScraperError};
-use crate::pipeline::ingestion::ingest_common::fetch_payload_and_log;
+use crate::pipeline::ingestion::ingest_common::fetch_payloads_and_log;
 use sms_core::common::types::{EventApi, EventArgs, RawDataInfo, RawEventData};
 use chrono::{Datelike, NaiveDate, NaiveTime};
 use scraper::{Html, Selector};
@@ -70,21 +70,10 @@ impl DarrellsTavernCrawler {
 
         performers
     }
-}
-
-#[async_trait::async_trait]
-impl EventApi for DarrellsTavernCrawler {
-    fn api_name(&self) -> &'static str {
-        DARRELLS_TAVERN_API
-    }
 
-    async fn get_event_list(&self) -> Result<Vec<RawEventData>> {
-        info!("Fetching events from Darrell's Tavern via registry and gateway handoff");
-
-        // New path: use shared ingestion helper, then parse
-        let payload = fetch_payload_and_log(DARRELLS_TAVERN_API).await?;
-
-        let response = String::from_utf8_lossy(&payload).to_string();
+    /// Events in one fetched payload; multi-endpoint and paginated sources deliver several
+    fn events_from_payload(&self, payload: &[u8]) -> Result<Vec<RawEventData>> {
+        let response = String::from_utf8_lossy(payload).to_string();
         let document = Html::parse_document(&response);
         let entry_content_selector = Selector::parse("div.entry-content").unwrap();
         let mut events = Vec::new();
@@ -131,6 +120,24 @@ impl EventApi for DarrellsTavernCrawler {
 
         Ok(events)
     }
+}
+
+#[async_trait::async_trait]
+impl EventApi for DarrellsTavernCrawler {
+    fn api_name(&self) -> &'static str {
+        DARRELLS_TAVERN_API
+    }
+
+    async fn get_event_list(&self) -> Result<Vec<RawEventData>> {
+        info!("Fetching events from Darrell's Tavern via registry and gateway handoff");
+
+        // New path: use shared ingestion helper, then parse
+        let mut events = Vec::new();
+        for payload in fetch_payloads_and_log(DARRELLS_TAVERN_API).await? {
+            events.extend(self.events_from_payload(&payload)?);
+        }
+        Ok(events)
+    }
 
     fn get_raw_data_info(&self, raw_data: &RawEventData) -> Result<RawDataInfo> {
         let title = raw_data["title"]
@@ -175,6 +182,7 @@ impl EventApi for DarrellsTavernCrawler {
             event_url: None,
             description: None,
             event_image_url: None,
+            doors_time: None,
         })
     }
 }
@@ -1,12 +1,16 @@
 use crate::apis::base::{BaseCrawler, VenueParser};
-use crate::apis::parsers::*;
+use crate::apis::venue_pack::venues;
+use sms_parsers::venue::*;
 use crate::common::constants::*;
 use crate::registry::source_loader::SourceRegistry;
 use sms_core::common::types::EventApi;
+use sms_parsers::DateHints;
 use sms_core::common::error::Result;
 
-/// Factory function to create crawlers using the abstracted architecture
+/// Factory function to create crawlers using the abstracted architecture; venues installed
+/// from a [`VenuePack`](crate::apis::venue_pack::VenuePack) are served after the built-ins
 pub fn create_crawler(api_name: &str, source_registry: SourceRegistry) -> Result<Option<Box<dyn EventApi>>> {
+    let dates = source_registry.date_hints(api_name);
     let crawler = match api_name {
         BLUE_MOON_API => Some(Box::new(BaseCrawler::new(
             BLUE_MOON_API,
@@ -20,22 +24,22 @@ pub fn create_crawler(api_name: &str, source_registry: SourceRegistry) -> Result
         )) as Box<dyn EventApi>),
         DARRELLS_TAVERN_API => Some(Box::new(BaseCrawler::new(
             DARRELLS_TAVERN_API,
-            Box::new(DarrellsTavernParser::new()),
+            Box::new(DarrellsTavernParser::new(dates)),
             source_registry.clone(),
         )) as Box<dyn EventApi>),
         KEXP_API => Some(Box::new(BaseCrawler::new(
             KEXP_API,
-            Box::new(KexpParser::new()),
+            Box::new(KexpParser::new(dates)),
             source_registry.clone(),
         )) as Box<dyn EventApi>),
         BARBOZA_API => Some(Box::new(BaseCrawler::new(
             BARBOZA_API,
-            Box::new(BarbozaParser::new()),
+            Box::new(BarbozaParser::new(dates)),
             source_registry.clone(),
         )) as Box<dyn EventApi>),
         NEUMOS_API => Some(Box::new(BaseCrawler::new(
             NEUMOS_API,
-            Box::new(NeumosParser::new()),
+            Box::new(NeumosParser::new(dates)),
             source_registry.clone(),
         )) as Box<dyn EventApi>),
         CONOR_BYRNE_API => Some(Box::new(BaseCrawler::new(
@@ -43,22 +47,45 @@ pub fn create_crawler(api_name: &str, source_registry: SourceRegistry) -> Result
             Box::new(ConorByrneParser::new()),
             source_registry.clone(),
         )) as Box<dyn EventApi>),
-        _ => None,
+        _ => {
+            let venues = venues();
+            venues.api_name(api_name).zip(venues.parser(api_name)).map(|(api_name, parser)| {
+                Box::new(BaseCrawler::new(api_name, parser, source_registry.clone())) as Box<dyn EventApi>
+            })
+        }
     };
     
     Ok(crawler)
 }
 
-/// Factory function to create parsers directly
-pub fn create_parser(api_name: &str) -> Option<Box<dyn VenueParser>> {
+/// Factory function to create parsers directly; listings that print days without a year
+/// are read with `dates`, the source's date hints
+pub fn create_parser(api_name: &str, dates: &DateHints) -> Option<Box<dyn VenueParser>> {
     match api_name {
         BLUE_MOON_API => Some(Box::new(BlueMoonParser::new())),
         SEA_MONSTER_API => Some(Box::new(SeaMonsterParser::new())),
-        DARRELLS_TAVERN_API => Some(Box::new(DarrellsTavernParser::new())),
-        KEXP_API => Some(Box::new(KexpParser::new())),
-        BARBOZA_API => Some(Box::new(BarbozaParser::new())),
-        NEUMOS_API => Some(Box::new(NeumosParser::new())),
+        DARRELLS_TAVERN_API => Some(Box::new(DarrellsTavernParser::new(dates.clone()))),
+        KEXP_API => Some(Box::new(KexpParser::new(dates.clone()))),
+        BARBOZA_API => Some(Box::new(BarbozaParser::new(dates.clone()))),
+        NEUMOS_API => Some(Box::new(NeumosParser::new(dates.clone()))),
         CONOR_BYRNE_API => Some(Box::new(ConorByrneParser::new())),
-        _ => None,
+        _ => venues().parser(api_name),
     }
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn parsers_read_year_less_days_with_the_source_hints() {
+        let december = DateHints::new(chrono_tz::America::Los_Angeles, "en-US").with_reference("2025-12-20".parse().unwrap());
+        let parser = create_parser(NEUMOS_API, &december).unwrap();
+        let info = parser.extract_raw_data_info(&json!({ "title": "Headliner", "date_text": "Jan 3" })).unwrap();
+        assert_eq!(info.event_day, chrono::NaiveDate::from_ymd_opt(2026, 1, 3).unwrap());
+
+        let sources = SourceRegistry::load_from_directory(concat!(env!("CARGO_MANIFEST_DIR"), "/../registry/sources")).unwrap();
+        assert_eq!(sources.date_hints(NEUMOS_API).timezone, chrono_tz::America::Los_Angeles);
+        assert_eq!(sources.date_hints("no_such_source"), DateHints::default());
+    }
+}
@@ -1,6 +1,6 @@
 use crate::common::constants::{KEXP_API, KEXP_VENUE_NAME};
 use crate::common::error::{Result, ScraperError};
-use crate::pipeline::ingestion::ingest_common::fetch_payload_and_log;
+use crate::pipeline::ingestion::ingest_common::fetch_payloads_and_log;
 use crate::common::types::{EventApi, EventArgs, RawDataInfo, RawEventData};
 use scraper::{Html, Selector};
 use serde_json::{json, Value};
@@ -28,11 +28,11 @@ impl KexpCrawler {
         let time_clean = time_str.trim().to_lowercase();
         
         if time_clean == "noon" {
-            return Some(chrono::NaiveTime::from_hms_opt(12, 0, 0)?);
+            return chrono::NaiveTime::from_hms_opt(12, 0, 0);
         }
         
         if time_clean == "midnight" {
-            return Some(chrono::NaiveTime::from_hms_opt(0, 0, 0)?);
+            return chrono::NaiveTime::from_hms_opt(0, 0, 0);
         }
         
         // Parse "1 p.m." or "11 a.m." format
@@ -168,6 +168,15 @@ impl KexpCrawler {
             "description": description
         }))
     }
+
+    /// Events in one fetched payload; multi-endpoint and paginated sources deliver several
+    fn events_from_payload(&self, payload: &[u8]) -> Result<Vec<RawEventData>> {
+        let html = String::from_utf8_lossy(payload).to_string();
+        let events = self.extract_events_from_html(&html)?;
+        
+        info!("Successfully fetched {} events from KEXP", events.len());
+        Ok(events)
+    }
 }
 
 #[async_trait::async_trait]
@@ -181,12 +190,10 @@ impl EventApi for KexpCrawler {
         debug!("Starting KEXP event fetch");
         
         // Use the shared ingestion helper to fetch raw bytes
-        let payload = fetch_payload_and_log(KEXP_API).await?;
-        
-        let html = String::from_utf8_lossy(&payload).to_string();
-        let events = self.extract_events_from_html(&html)?;
-        
-        info!("Successfully fetched {} events from KEXP", events.len());
+        let mut events = Vec::new();
+        for payload in fetch_payloads_and_log(KEXP_API).await? {
+            events.extend(self.events_from_payload(&payload)?);
+        }
         Ok(events)
     }
 
@@ -1,7 +1,9 @@
 // New abstracted architecture
 pub mod base;
-pub mod parsers;
 pub mod factory;
+pub mod plan_venue;
+pub mod scaffolded;
+pub mod venue_pack;
 
 // Legacy crawlers (keeping for reference during migration)
 #[cfg(feature = "scraping")]
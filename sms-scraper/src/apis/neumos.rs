@@ -1,6 +1,6 @@
 use crate::common::constants::{NEUMOS_API, NEUMOS_VENUE_NAME};
 use crate::common::error::{Result, ScraperError};
-use crate::pipeline::ingestion::ingest_common::fetch_payload_and_log;
+use crate::pipeline::ingestion::ingest_common::fetch_payloads_and_log;
 use crate::common::types::{EventApi, EventArgs, RawDataInfo, RawEventData};
 use chrono::{Datelike, NaiveDate, NaiveTime};
 use scraper::{Html, Selector};
@@ -67,18 +67,10 @@ impl NeumosCrawler {
 
         NaiveDate::from_ymd_opt(year, month, day)
     }
-}
-
-#[async_trait::async_trait]
-impl EventApi for NeumosCrawler {
-    fn api_name(&self) -> &'static str {
-        NEUMOS_API
-    }
 
-    #[instrument(skip(self))]
-    async fn get_event_list(&self) -> Result<Vec<RawEventData>> {
-        let payload = fetch_payload_and_log(NEUMOS_API).await?;
-        let body = String::from_utf8_lossy(&payload).to_string();
+    /// Events in one fetched payload; multi-endpoint and paginated sources deliver several
+    fn events_from_payload(&self, payload: &[u8]) -> Result<Vec<RawEventData>> {
+        let body = String::from_utf8_lossy(payload).to_string();
         
         // Debug: Check if we have the expected content
         if body.contains("eventItem") {
@@ -123,7 +115,7 @@ impl EventApi for NeumosCrawler {
                 if let Some(href) = title_elem.value().attr("href") {
                     event_data["detail_url"] = json!(href);
                     // Extract event ID from URL if possible
-                    if let Some(id_match) = href.split('/').last() {
+                    if let Some(id_match) = href.split('/').next_back() {
                         event_data["id"] = json!(id_match.to_string());
                     }
                 }
@@ -230,6 +222,22 @@ impl EventApi for NeumosCrawler {
         
         Ok(events)
     }
+}
+
+#[async_trait::async_trait]
+impl EventApi for NeumosCrawler {
+    fn api_name(&self) -> &'static str {
+        NEUMOS_API
+    }
+
+    #[instrument(skip(self))]
+    async fn get_event_list(&self) -> Result<Vec<RawEventData>> {
+        let mut events = Vec::new();
+        for payload in fetch_payloads_and_log(NEUMOS_API).await? {
+            events.extend(self.events_from_payload(&payload)?);
+        }
+        Ok(events)
+    }
 
     fn get_raw_data_info(&self, raw_data: &RawEventData) -> Result<RawDataInfo> {
         // Extract date from the raw data
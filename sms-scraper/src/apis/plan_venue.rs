@@ -0,0 +1,131 @@
+// Crawler parsers for venues served by an envelope parse plan rather than a hand-written parser
+
+use chrono::{NaiveDate, NaiveTime};
+use sms_core::common::error::{Result, ScraperError};
+use sms_core::common::types::{EventArgs, RawDataInfo, RawEventData};
+use sms_core::pipeline_api::ParsedRecord;
+use sms_parsers::venue::VenueParser;
+
+use crate::app::ports::ParserFactory;
+use crate::infra::parser_factory::DefaultParserFactory;
+
+/// Parses a venue's payload with its parse plan and reads events from the records' `title`,
+/// `start_date`, `id`, `url`, `description` and `image_url` fields, the same ones the
+/// scaffolded normalizer starts from
+pub struct PlanVenueParser {
+    source_id: &'static str,
+    venue_name: &'static str,
+    plan: &'static str,
+}
+
+impl PlanVenueParser {
+    pub fn new(source_id: &'static str, venue_name: &'static str, plan: &'static str) -> Self {
+        Self { source_id, venue_name, plan }
+    }
+
+    fn title(raw_data: &RawEventData) -> Result<&str> {
+        raw_data["title"]
+            .as_str()
+            .ok_or_else(|| ScraperError::MissingField("title not found".into()))
+    }
+
+    /// `start_date` as a date or an RFC 3339 timestamp
+    fn event_day(raw_data: &RawEventData) -> Result<NaiveDate> {
+        let start = raw_data["start_date"]
+            .as_str()
+            .ok_or_else(|| ScraperError::MissingField("start_date not found".into()))?;
+        start
+            .get(..10)
+            .and_then(|day| NaiveDate::parse_from_str(day, "%Y-%m-%d").ok())
+            .ok_or_else(|| ScraperError::Api { message: format!("Failed to parse start_date: {}", start) })
+    }
+
+    fn start_time(raw_data: &RawEventData) -> Option<NaiveTime> {
+        let start = raw_data["start_date"].as_str()?;
+        chrono::DateTime::parse_from_rfc3339(start).ok().map(|dt| dt.time())
+    }
+
+    fn text(raw_data: &RawEventData, field: &str) -> Option<String> {
+        raw_data[field].as_str().filter(|s| !s.is_empty()).map(str::to_string)
+    }
+}
+
+#[async_trait::async_trait]
+impl VenueParser for PlanVenueParser {
+    async fn parse_events(&self, payload: &[u8]) -> Result<Vec<RawEventData>> {
+        let parser = DefaultParserFactory
+            .for_plan(self.plan)
+            .ok_or_else(|| ScraperError::Api { message: format!("no parser is registered for {}", self.plan) })?;
+        let lines = parser
+            .parse(self.source_id, "", "", payload)
+            .await
+            .map_err(|message| ScraperError::Api { message })?;
+        lines
+            .iter()
+            .map(|line| {
+                serde_json::from_str::<ParsedRecord>(line)
+                    .map(|parsed| parsed.record)
+                    .map_err(|e| ScraperError::Api { message: format!("Failed to read parsed record: {}", e) })
+            })
+            .collect()
+    }
+
+    fn extract_raw_data_info(&self, raw_data: &RawEventData) -> Result<RawDataInfo> {
+        let title = Self::title(raw_data)?;
+        let event_day = Self::event_day(raw_data)?;
+        let event_api_id = match &raw_data["id"] {
+            serde_json::Value::String(id) => id.clone(),
+            serde_json::Value::Number(id) => id.to_string(),
+            _ => format!("{}:{}", event_day, title),
+        };
+
+        Ok(RawDataInfo {
+            event_api_id,
+            event_name: title.to_string(),
+            venue_name: self.venue_name.to_string(),
+            event_day,
+        })
+    }
+
+    fn extract_event_args(&self, raw_data: &RawEventData) -> Result<EventArgs> {
+        Ok(EventArgs {
+            title: Self::title(raw_data)?.to_string(),
+            event_day: Self::event_day(raw_data)?,
+            start_time: Self::start_time(raw_data),
+            doors_time: None,
+            event_url: Self::text(raw_data, "url"),
+            description: Self::text(raw_data, "description"),
+            event_image_url: Self::text(raw_data, "image_url"),
+        })
+    }
+
+    fn venue_name(&self) -> &'static str {
+        self.venue_name
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn reads_events_from_plan_records() {
+        let parser = PlanVenueParser::new("new_venue", "New Venue", "parse_plan:wix_calendar_v1");
+        let record = json!({
+            "title": "Headliner",
+            "start_date": "2026-11-02T20:00:00-08:00",
+            "description": "All ages",
+        });
+
+        let info = parser.extract_raw_data_info(&record).unwrap();
+        assert_eq!(info.event_api_id, "2026-11-02:Headliner");
+        assert_eq!(info.venue_name, "New Venue");
+
+        let args = parser.extract_event_args(&record).unwrap();
+        assert_eq!(args.event_day, NaiveDate::from_ymd_opt(2026, 11, 2).unwrap());
+        assert_eq!(args.start_time, NaiveTime::from_hms_opt(20, 0, 0));
+        assert_eq!(args.description.as_deref(), Some("All ages"));
+        assert!(parser.extract_event_args(&json!({ "title": "No date" })).is_err());
+    }
+}
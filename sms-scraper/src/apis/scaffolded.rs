@@ -0,0 +1,23 @@
+// Venues added by `scaffold source`; each crawls through its parse plan
+
+use super::plan_venue::PlanVenueParser;
+use super::venue_pack::{VenuePack, VenueRegistry};
+
+/// Source id, venue name and parse plan of each scaffolded venue; `scaffold source` appends here
+pub const VENUES: &[(&str, &str, &str)] = &[
+];
+
+/// Installed with the venue registry, so the factory serves scaffolded venues like built-ins
+pub struct ScaffoldedVenues;
+
+impl VenuePack for ScaffoldedVenues {
+    fn name(&self) -> &str {
+        "scaffolded"
+    }
+
+    fn register(&self, venues: &mut VenueRegistry) {
+        for &(source_id, venue_name, plan) in VENUES {
+            venues.venue(source_id, move || Box::new(PlanVenueParser::new(source_id, venue_name, plan)));
+        }
+    }
+}
@@ -1,10 +1,9 @@
 use crate::common::constants::{SEA_MONSTER_API, SEA_MONSTER_VENUE_NAME};
 use sms_core::common::error::{Result, ScraperError};
-use crate::pipeline::ingestion::ingest_common::fetch_payload_and_log;
+use crate::pipeline::ingestion::ingest_common::fetch_payloads_and_log;
 use sms_core::common::types::{EventApi, EventArgs, RawDataInfo, RawEventData};
-use scraper::{Html, Selector};
 use serde_json::Value;
-use tracing::{debug, error, info, instrument};
+use tracing::{info, instrument};
 
 pub struct SeaMonsterCrawler {
     _client: reqwest::Client, // Prefixed with _ to suppress warning
@@ -22,6 +21,16 @@ impl SeaMonsterCrawler {
             _client: reqwest::Client::new(),
         }
     }
+
+    /// Events in one fetched payload; multi-endpoint and paginated sources deliver several
+    fn events_from_payload(&self, payload: &[u8]) -> Result<Vec<RawEventData>> {
+        info!("Successfully fetched {} bytes of raw HTML from Sea Monster Lounge", payload.len());
+        
+        // Store the raw HTML payload as a single raw data item
+        // The parser will handle extracting individual events from this HTML
+        let raw_html_value = Value::String(String::from_utf8_lossy(payload).to_string());
+        Ok(vec![raw_html_value])
+    }
 }
 
 #[async_trait::async_trait]
@@ -34,17 +43,14 @@ impl EventApi for SeaMonsterCrawler {
     async fn get_event_list(&self) -> Result<Vec<RawEventData>> {
         // Per Platonic Ideal: ingester should only fetch raw HTML/JSON bytes
         // The parsing should happen in the parser, not here
-        let payload = fetch_payload_and_log(SEA_MONSTER_API).await?;
-        
-        info!("Successfully fetched {} bytes of raw HTML from Sea Monster Lounge", payload.len());
-        
-        // Store the raw HTML payload as a single raw data item
-        // The parser will handle extracting individual events from this HTML
-        let raw_html_value = Value::String(String::from_utf8_lossy(&payload).to_string());
-        Ok(vec![raw_html_value])
+        let mut events = Vec::new();
+        for payload in fetch_payloads_and_log(SEA_MONSTER_API).await? {
+            events.extend(self.events_from_payload(&payload)?);
+        }
+        Ok(events)
     }
 
-    fn get_raw_data_info(&self, raw_data: &RawEventData) -> Result<RawDataInfo> {
+    fn get_raw_data_info(&self, _raw_data: &RawEventData) -> Result<RawDataInfo> {
         // For raw HTML data, we provide generic info since parsing happens later
         Ok(RawDataInfo {
             event_api_id: "raw_html_page".to_string(),
@@ -54,7 +60,7 @@ impl EventApi for SeaMonsterCrawler {
         })
     }
 
-    fn get_event_args(&self, raw_data: &RawEventData) -> Result<EventArgs> {
+    fn get_event_args(&self, _raw_data: &RawEventData) -> Result<EventArgs> {
         // This method is not used in the Platonic Ideal architecture
         // Event args are extracted during the parsing phase, not ingestion
         Err(ScraperError::Api {
@@ -62,7 +68,7 @@ impl EventApi for SeaMonsterCrawler {
         })
     }
 
-    fn should_skip(&self, raw_data: &RawEventData) -> (bool, String) {
+    fn should_skip(&self, _raw_data: &RawEventData) -> (bool, String) {
         // For raw HTML data, we don't skip at ingestion time
         // Skipping logic will be handled during parsing
         (false, String::new())
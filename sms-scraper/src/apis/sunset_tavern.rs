@@ -1,7 +1,7 @@
 use sms_core::common::constants::{SUNSET_TAVERN_API, SUNSET_TAVERN_VENUE_NAME};
 use sms_core::common::error::{Result, ScraperError};
 use sms_core::common::types::{EventApi, EventArgs, RawDataInfo, RawEventData};
-use chrono::{NaiveDate, NaiveTime};
+use chrono::NaiveDate;
 use reqwest::Client;
 use tracing::{info, instrument};
 
@@ -304,6 +304,7 @@ impl EventApi for SunsetTavernCrawler {
             event_url,
             description,
             event_image_url,
+            doors_time: None,
         })
     }
 }
\ No newline at end of file
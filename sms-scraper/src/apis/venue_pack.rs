@@ -0,0 +1,146 @@
+// Venue packs: crates outside this one contributing crawlers without editing the factory
+
+use std::collections::BTreeMap;
+use std::sync::{Arc, OnceLock, RwLock, RwLockReadGuard};
+
+use sms_core::pipeline_api::{self, SourceNormalizer};
+use sms_parsers::venue::VenueParser;
+
+/// Builds the parser behind a pack venue's crawler
+pub type VenueParserConstructor = Arc<dyn Fn() -> Box<dyn VenueParser> + Send + Sync>;
+
+/// A set of venues shipped together, e.g. by a community crate for one city:
+///
+/// ```ignore
+/// pub struct TacomaVenues;
+///
+/// impl VenuePack for TacomaVenues {
+///     fn name(&self) -> &str { "tacoma" }
+///
+///     fn register(&self, venues: &mut VenueRegistry) {
+///         venues
+///             .venue("spanish_ballroom", || Box::new(SpanishBallroomParser::new()))
+///             .normalizer(SpanishBallroomNormalizer::new());
+///     }
+/// }
+///
+/// sms_scraper::apis::venue_pack::install(&TacomaVenues);
+/// ```
+pub trait VenuePack {
+    fn name(&self) -> &str;
+    fn register(&self, venues: &mut VenueRegistry);
+}
+
+/// Venues added by packs, keyed by API name; venues from `scaffold source` are always installed. Built-in venues take precedence over a pack
+/// venue with the same name.
+#[derive(Default)]
+pub struct VenueRegistry {
+    venues: BTreeMap<&'static str, VenueParserConstructor>,
+}
+
+impl VenueRegistry {
+    /// Crawl `api_name` with parsers built by `parser`. The source also needs an entry in the
+    /// source registry for its URL, like any built-in venue.
+    pub fn venue(
+        &mut self,
+        api_name: &'static str,
+        parser: impl Fn() -> Box<dyn VenueParser> + Send + Sync + 'static,
+    ) -> &mut Self {
+        self.venues.insert(api_name, Arc::new(parser));
+        self
+    }
+
+    /// Normalize the venue's gateway records with `normalizer`; registered with
+    /// `sms_core::pipeline_api` under its source id
+    pub fn normalizer(&mut self, normalizer: impl SourceNormalizer + 'static) -> &mut Self {
+        pipeline_api::register(|plugins| plugins.register_normalizer(normalizer));
+        self
+    }
+
+    pub fn parser(&self, api_name: &str) -> Option<Box<dyn VenueParser>> {
+        self.venues.get(api_name).map(|parser| parser())
+    }
+
+    /// The API name as registered, for crawlers that need a `'static` name
+    pub fn api_name(&self, api_name: &str) -> Option<&'static str> {
+        self.venues.get_key_value(api_name).map(|(name, _)| *name)
+    }
+
+    pub fn api_names(&self) -> Vec<&'static str> {
+        self.venues.keys().copied().collect()
+    }
+}
+
+fn registry() -> &'static RwLock<VenueRegistry> {
+    static VENUES: OnceLock<RwLock<VenueRegistry>> = OnceLock::new();
+    VENUES.get_or_init(|| {
+        let mut venues = VenueRegistry::default();
+        super::scaffolded::ScaffoldedVenues.register(&mut venues);
+        RwLock::new(venues)
+    })
+}
+
+/// Add a pack's venues, typically at startup before any pipeline runs
+pub fn install(pack: &dyn VenuePack) {
+    let mut venues = registry().write().unwrap_or_else(|poisoned| poisoned.into_inner());
+    let before = venues.venues.len();
+    pack.register(&mut venues);
+    tracing::info!("Installed venue pack '{}' ({} venues)", pack.name(), venues.venues.len() - before);
+}
+
+/// Venues installed from packs
+pub fn venues() -> RwLockReadGuard<'static, VenueRegistry> {
+    registry().read().unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::apis::factory::create_parser;
+    use sms_core::common::error::{Result, ScraperError};
+    use sms_core::common::types::{EventArgs, RawDataInfo, RawEventData};
+
+    struct PackVenueParser;
+
+    #[async_trait::async_trait]
+    impl VenueParser for PackVenueParser {
+        async fn parse_events(&self, _payload: &[u8]) -> Result<Vec<RawEventData>> {
+            Ok(Vec::new())
+        }
+
+        fn extract_raw_data_info(&self, _raw_data: &RawEventData) -> Result<RawDataInfo> {
+            Err(ScraperError::Api { message: "unused".to_string() })
+        }
+
+        fn extract_event_args(&self, _raw_data: &RawEventData) -> Result<EventArgs> {
+            Err(ScraperError::Api { message: "unused".to_string() })
+        }
+
+        fn venue_name(&self) -> &'static str {
+            "Pack Venue"
+        }
+    }
+
+    struct TestPack;
+
+    impl VenuePack for TestPack {
+        fn name(&self) -> &str {
+            "test"
+        }
+
+        fn register(&self, venues: &mut VenueRegistry) {
+            venues.venue("pack_venue", || Box::new(PackVenueParser));
+        }
+    }
+
+    #[test]
+    fn installed_venues_reach_the_factory() {
+        assert!(create_parser("pack_venue", &sms_parsers::DateHints::default()).is_none());
+        install(&TestPack);
+
+        assert_eq!(create_parser("pack_venue", &sms_parsers::DateHints::default()).unwrap().venue_name(), "Pack Venue");
+        assert_eq!(create_parser("kexp", &sms_parsers::DateHints::default()).unwrap().venue_name(), "KEXP");
+        let apis = crate::common::constants::get_supported_apis();
+        assert!(apis.contains(&"pack_venue") && apis.contains(&"kexp"));
+    }
+}
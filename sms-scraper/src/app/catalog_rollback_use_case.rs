@@ -0,0 +1,258 @@
+use serde::Serialize;
+use sms_core::domain::{Artist, Event, ProcessRecord, ProcessRun, Venue};
+use sms_core::storage::Storage;
+use std::collections::HashMap;
+use std::sync::Arc;
+use uuid::Uuid;
+
+/// What rolling back does to one entity a run touched
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case", tag = "action")]
+pub enum RollbackAction {
+    /// The run created it: hide it from listings (it stays in the graph for reference)
+    Hide,
+    /// The run updated it: write back the state recorded before its first update
+    Restore { previous_state: String },
+    /// Nothing can be done, and why
+    Skip { reason: String },
+}
+
+/// One entity affected by the run and what rollback will do to it
+#[derive(Debug, Clone, Serialize)]
+pub struct RollbackStep {
+    pub entity_type: &'static str,
+    pub entity_id: Uuid,
+    /// Current name or title, when the entity is still in the catalog
+    pub name: Option<String>,
+    /// CREATE or UPDATE; an entity both created and updated in the run counts as created
+    pub change_type: String,
+    pub action: RollbackAction,
+}
+
+/// Everything `catalog rollback` would do for a run; printed as-is for `--dry-run`
+#[derive(Debug, Clone, Serialize)]
+pub struct RollbackPlan {
+    pub run: ProcessRun,
+    pub steps: Vec<RollbackStep>,
+}
+
+impl RollbackPlan {
+    pub fn count(&self, pred: impl Fn(&RollbackAction) -> bool) -> usize {
+        self.steps.iter().filter(|s| pred(&s.action)).count()
+    }
+}
+
+/// Totals after applying a plan
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct RollbackSummary {
+    pub hidden: usize,
+    pub restored: usize,
+    pub skipped: usize,
+}
+
+/// Undoes a catalog run from the process records it left: entities it created are hidden and
+/// entities it updated get their earlier state back. Later runs that touched the same entities
+/// are not considered, so roll back the most recent runs first.
+pub struct CatalogRollbackUseCase {
+    storage: Arc<dyn Storage>,
+}
+
+impl CatalogRollbackUseCase {
+    pub fn new(storage: Arc<dyn Storage>) -> Self {
+        Self { storage }
+    }
+
+    pub async fn plan(&self, run_id: Uuid) -> anyhow::Result<RollbackPlan> {
+        let run = self
+            .storage
+            .get_process_run(run_id)
+            .await?
+            .ok_or_else(|| anyhow::anyhow!("no process run {}", run_id))?;
+
+        // CREATE/UPDATE records per entity, entities in the order the run first wrote them
+        let mut order: Vec<(&'static str, Uuid)> = Vec::new();
+        let mut by_entity: HashMap<(&'static str, Uuid), Vec<ProcessRecord>> = HashMap::new();
+        for record in self.storage.get_process_records_for_run(run_id).await? {
+            if !matches!(record.change_type.as_str(), "CREATE" | "UPDATE") {
+                continue;
+            }
+            let Some(target) = target_of(&record) else { continue };
+            let records = by_entity.entry(target).or_insert_with(|| {
+                order.push(target);
+                Vec::new()
+            });
+            records.push(record);
+        }
+
+        let mut steps = Vec::with_capacity(order.len());
+        for target in order {
+            steps.push(self.plan_entity(target, &by_entity[&target]).await?);
+        }
+        Ok(RollbackPlan { run, steps })
+    }
+
+    async fn plan_entity(&self, (entity_type, entity_id): (&'static str, Uuid), records: &[ProcessRecord]) -> anyhow::Result<RollbackStep> {
+        let name = match entity_type {
+            "event" => self.storage.get_event_by_id(entity_id).await?.map(|e| e.title),
+            "venue" => self.storage.get_venue_by_id(entity_id).await?.map(|v| v.name),
+            _ => self.storage.get_artist_by_id(entity_id).await?.map(|a| a.name),
+        };
+        let created = records.iter().any(|r| r.change_type == "CREATE");
+        let action = if name.is_none() {
+            RollbackAction::Skip { reason: "no longer in the catalog".to_string() }
+        } else if created && entity_type == "artist" {
+            RollbackAction::Skip { reason: "artists can't be hidden; they drop out with their events".to_string() }
+        } else if created {
+            RollbackAction::Hide
+        } else {
+            match records.iter().find_map(|r| r.previous_state.clone()) {
+                Some(previous_state) => RollbackAction::Restore { previous_state },
+                None => RollbackAction::Skip { reason: "update was recorded without its previous state".to_string() },
+            }
+        };
+        Ok(RollbackStep {
+            entity_type,
+            entity_id,
+            name,
+            change_type: if created { "CREATE" } else { "UPDATE" }.to_string(),
+            action,
+        })
+    }
+
+    pub async fn apply(&self, plan: &RollbackPlan) -> anyhow::Result<RollbackSummary> {
+        let mut summary = RollbackSummary::default();
+        for step in &plan.steps {
+            match &step.action {
+                RollbackAction::Hide => {
+                    self.hide(step.entity_type, step.entity_id).await?;
+                    summary.hidden += 1;
+                }
+                RollbackAction::Restore { previous_state } => {
+                    self.restore(step.entity_type, step.entity_id, previous_state).await?;
+                    summary.restored += 1;
+                }
+                RollbackAction::Skip { .. } => summary.skipped += 1,
+            }
+        }
+        Ok(summary)
+    }
+
+    async fn hide(&self, entity_type: &str, id: Uuid) -> anyhow::Result<()> {
+        match entity_type {
+            "event" => {
+                if let Some(mut event) = self.storage.get_event_by_id(id).await? {
+                    event.show_event = false;
+                    self.storage.update_event(&event).await?;
+                }
+            }
+            "venue" => {
+                if let Some(mut venue) = self.storage.get_venue_by_id(id).await? {
+                    venue.show_venue = false;
+                    self.storage.create_venue(&mut venue).await?;
+                }
+            }
+            other => anyhow::bail!("can't hide {} {}", other, id),
+        }
+        Ok(())
+    }
+
+    async fn restore(&self, entity_type: &str, id: Uuid, previous_state: &str) -> anyhow::Result<()> {
+        match entity_type {
+            "event" => {
+                let mut event: Event = serde_json::from_str(previous_state)?;
+                event.id = Some(id);
+                self.storage.update_event(&event).await?;
+            }
+            "venue" => {
+                let mut venue: Venue = serde_json::from_str(previous_state)?;
+                venue.id = Some(id);
+                self.storage.create_venue(&mut venue).await?;
+            }
+            _ => {
+                let mut artist: Artist = serde_json::from_str(previous_state)?;
+                artist.id = Some(id);
+                self.storage.update_artist(&artist).await?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The entity a process record is about; event records also carry their venue's id
+fn target_of(record: &ProcessRecord) -> Option<(&'static str, Uuid)> {
+    if let Some(id) = record.event_id {
+        Some(("event", id))
+    } else if let Some(id) = record.artist_id {
+        Some(("artist", id))
+    } else {
+        record.venue_id.map(|id| ("venue", id))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{NaiveDate, Utc};
+    use sms_core::storage::InMemoryStorage;
+
+    fn record(run_id: Uuid, change_type: &str, event_id: Option<Uuid>, venue_id: Option<Uuid>, previous_state: Option<String>) -> ProcessRecord {
+        ProcessRecord {
+            id: None,
+            process_run_id: run_id,
+            api_name: "catalog".to_string(),
+            raw_data_id: None,
+            change_type: change_type.to_string(),
+            change_log: String::new(),
+            field_changed: String::new(),
+            event_id,
+            venue_id,
+            artist_id: None,
+            created_at: Utc::now(),
+            previous_state,
+        }
+    }
+
+    #[tokio::test]
+    async fn rollback_hides_created_entities_and_restores_updated_ones() {
+        let storage = Arc::new(InMemoryStorage::new());
+        let mut run = ProcessRun { id: None, name: "bad run".to_string(), created_at: Utc::now(), finished_at: None };
+        storage.create_process_run(&mut run).await.unwrap();
+        let run_id = run.id.unwrap();
+
+        // The run created a venue and updated an existing event's title
+        let mut neumos = Venue::fixture("Neumos");
+        storage.create_venue(&mut neumos).await.unwrap();
+        let venue_id = neumos.id.unwrap();
+        let mut show = Event::fixture("Original Title", NaiveDate::from_ymd_opt(2025, 6, 1).unwrap(), venue_id);
+        storage.create_event(&mut show).await.unwrap();
+        let before = serde_json::to_string(&show).unwrap();
+        show.title = "Mangled Title".to_string();
+        storage.update_event(&show).await.unwrap();
+        let event_id = show.id.unwrap();
+
+        for mut r in [
+            record(run_id, "CREATE", None, Some(venue_id), None),
+            record(run_id, "UPDATE", Some(event_id), Some(venue_id), Some(before)),
+            record(run_id, "NO_CHANGE", None, Some(Uuid::new_v4()), None),
+        ] {
+            storage.create_process_record(&mut r).await.unwrap();
+        }
+
+        let rollback = CatalogRollbackUseCase::new(storage.clone());
+        let plan = rollback.plan(run_id).await.unwrap();
+        let actions: Vec<_> = plan.steps.iter().map(|s| (s.entity_type, s.change_type.as_str())).collect();
+        assert_eq!(actions, [("venue", "CREATE"), ("event", "UPDATE")]);
+        assert_eq!(plan.steps[0].action, RollbackAction::Hide);
+        assert_eq!(plan.steps[1].name.as_deref(), Some("Mangled Title"));
+
+        // Planning alone changes nothing
+        assert!(storage.get_venue_by_id(venue_id).await.unwrap().unwrap().show_venue);
+
+        let summary = rollback.apply(&plan).await.unwrap();
+        assert_eq!(summary, RollbackSummary { hidden: 1, restored: 1, skipped: 0 });
+        assert!(!storage.get_venue_by_id(venue_id).await.unwrap().unwrap().show_venue);
+        assert_eq!(storage.get_event_by_id(event_id).await.unwrap().unwrap().title, "Original Title");
+
+        assert!(rollback.plan(Uuid::new_v4()).await.is_err());
+    }
+}
@@ -1,11 +1,14 @@
 use anyhow::Result;
 use std::sync::Arc;
-use tracing::{info, debug, error, warn};
+use tracing::{info, debug, error, warn, Instrument};
 
 use crate::app::ports::ConflationOutputPort;
+use crate::observability::logging;
+use sms_core::pipeline_api::plugins;
 use crate::observability::metrics::conflation;
 use crate::pipeline::processing::conflation::{ConflatedRecord, Conflator, DefaultConflator, ResolutionDecision};
 use crate::pipeline::processing::enrich::EnrichedRecord;
+use crate::pipeline::processing::resolution_index::ResolutionIndex;
 
 /// Use case for performing entity conflation on enriched records
 pub struct ConflationUseCase {
@@ -16,14 +19,31 @@ pub struct ConflationUseCase {
 }
 
 impl ConflationUseCase {
-    /// Create a new conflation use case with default conflator
+    /// Create a new conflation use case with default conflator, or the registered plugin
+    /// conflator if any
     pub fn new(output_port: Arc<dyn ConflationOutputPort>) -> Self {
+        let conflator: Box<dyn Conflator + Send + Sync> = match plugins().conflator() {
+            Some(conflator) => Box::new(conflator),
+            None => Box::new(DefaultConflator::new()),
+        };
         Self {
-            conflator: Box::new(DefaultConflator::new()),
+            conflator,
             output_port,
         }
     }
 
+    /// Create a conflation use case whose canonical ids persist under `data_root` between runs
+    pub fn with_resolution_index<P: AsRef<std::path::Path>>(
+        data_root: P,
+        output_port: Arc<dyn ConflationOutputPort>,
+    ) -> Result<Self> {
+        let index = ResolutionIndex::open_at_root(data_root)?;
+        Ok(Self {
+            conflator: Box::new(DefaultConflator::new().with_resolution_index(Arc::new(index))),
+            output_port,
+        })
+    }
+
     /// Create a conflation use case with a custom conflator
     pub fn with_conflator(
         conflator: Box<dyn Conflator + Send + Sync>,
@@ -37,6 +57,13 @@ impl ConflationUseCase {
 
     /// Process a single enriched record through conflation
     pub async fn conflate_record(&self, record: &EnrichedRecord) -> Result<ConflatedRecord> {
+        let envelope_id = &record.quality_assessed_record.normalized_record.provenance.envelope_id;
+        self.conflate_and_write(record)
+            .instrument(logging::envelope_span(envelope_id))
+            .await
+    }
+
+    async fn conflate_and_write(&self, record: &EnrichedRecord) -> Result<ConflatedRecord> {
         let start_time = std::time::Instant::now();
         
         info!(
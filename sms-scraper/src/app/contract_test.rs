@@ -0,0 +1,325 @@
+use crate::app::ports::ParserFactory;
+use crate::pipeline::ingestion::delta::is_fallback;
+use crate::pipeline::ingestion::registry::load_source_spec;
+use crate::pipeline::processing::normalize::{NormalizationRegistry, NormalizedEntity};
+use chrono::NaiveDate;
+use serde::Serialize;
+use sms_parsers::{DateHints, ParsedRecord};
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+
+/// Where `contract test` looks for `<source_id>/` fixture directories (the ones `scaffold
+/// source` creates)
+pub const DEFAULT_FIXTURES_DIR: &str = "tests/resources/sources";
+
+/// The scaffold's expected-output file, kept beside the payloads but not one itself
+const EXPECTED_RECORDS: &str = "expected_records.json";
+
+/// How far from the fixture's capture date an event may fall
+pub const EVENT_WINDOW_DAYS: i64 = 365;
+
+const PARSES: &str = "parses";
+const NORMALIZES: &str = "normalizes";
+const AT_LEAST_ONE_EVENT: &str = "at least one event";
+const EVENTS_WITHIN_A_YEAR: &str = "events within a year";
+const VENUE_NAMED: &str = "venue name non-empty";
+
+/// One invariant checked against one fixture
+#[derive(Debug, Clone, Serialize)]
+pub struct ContractCase {
+    pub fixture: String,
+    pub invariant: String,
+    pub duration_ms: f64,
+    /// Why the invariant doesn't hold; `None` when it passed
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub failure: Option<String>,
+}
+
+/// Every invariant checked for a source, fixture by fixture
+#[derive(Debug, Clone, Serialize)]
+pub struct ContractReport {
+    pub source_id: String,
+    pub parse_plan: String,
+    pub cases: Vec<ContractCase>,
+}
+
+impl ContractReport {
+    pub fn failures(&self) -> usize {
+        self.cases.iter().filter(|c| c.failure.is_some()).count()
+    }
+
+    pub fn passed(&self) -> bool {
+        self.failures() == 0
+    }
+
+    /// The report as a JUnit XML testsuite, one testcase per fixture and invariant
+    pub fn to_junit_xml(&self) -> String {
+        let suite = format!("contract.{}", self.source_id);
+        let total_secs: f64 = self.cases.iter().map(|c| c.duration_ms).sum::<f64>() / 1000.0;
+        let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+        xml.push_str(&format!(
+            "<testsuites tests=\"{}\" failures=\"{}\" time=\"{:.3}\">\n",
+            self.cases.len(),
+            self.failures(),
+            total_secs
+        ));
+        xml.push_str(&format!(
+            "  <testsuite name=\"{}\" tests=\"{}\" failures=\"{}\" time=\"{:.3}\">\n",
+            xml_escape(&suite),
+            self.cases.len(),
+            self.failures(),
+            total_secs
+        ));
+        xml.push_str(&format!(
+            "    <properties><property name=\"parse_plan\" value=\"{}\"/></properties>\n",
+            xml_escape(&self.parse_plan)
+        ));
+        for case in &self.cases {
+            let open = format!(
+                "    <testcase classname=\"{}\" name=\"{}\" time=\"{:.3}\"",
+                xml_escape(&suite),
+                xml_escape(&format!("{}: {}", case.fixture, case.invariant)),
+                case.duration_ms / 1000.0
+            );
+            match &case.failure {
+                None => xml.push_str(&format!("{}/>\n", open)),
+                Some(failure) => {
+                    let message = failure.lines().next().unwrap_or_default();
+                    xml.push_str(&format!(
+                        "{}>\n      <failure message=\"{}\">{}</failure>\n    </testcase>\n",
+                        open,
+                        xml_escape(message),
+                        xml_escape(failure)
+                    ));
+                }
+            }
+        }
+        xml.push_str("  </testsuite>\n</testsuites>\n");
+        xml
+    }
+}
+
+fn xml_escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+/// Runs a source's registered parser and normalizer over its committed fixture payloads and
+/// checks the invariants every scrape of it should meet, so a parser or normalizer change
+/// that breaks a source fails CI instead of the next crawl.
+///
+/// Fixtures live in `<fixtures_dir>/<source_id>/`, one payload per file. A file named after
+/// the date it was captured (`2025-03-01.json`) anchors the one-year event window there, and
+/// the years of dates it lists without one are resolved against that day; otherwise both are
+/// anchored at today.
+pub struct ContractTestUseCase<F: ParserFactory + ?Sized> {
+    pub parsers: Box<F>,
+}
+
+impl<F: ParserFactory + ?Sized> ContractTestUseCase<F> {
+    pub fn new(parsers: Box<F>) -> Self {
+        Self { parsers }
+    }
+
+    pub async fn run(&self, source_id: &str, registry_dir: &Path, fixtures_dir: &Path) -> Result<ContractReport, String> {
+        let spec_path = registry_dir.join(format!("{}.json", source_id));
+        let spec = load_source_spec(&spec_path).map_err(|e| format!("load {}: {}", spec_path.display(), e))?;
+        let plan = spec
+            .resolved_parse_plan()
+            .ok_or_else(|| format!("{} declares no parse plan", spec_path.display()))?;
+        let dates = spec.date_hints()?;
+
+        let fixtures = list_fixtures(&fixtures_dir.join(source_id))?;
+        let mut cases = Vec::new();
+        for fixture in fixtures {
+            cases.extend(self.check_fixture(source_id, &plan, &dates, &fixture).await);
+        }
+        Ok(ContractReport { source_id: source_id.to_string(), parse_plan: plan, cases })
+    }
+
+    async fn check_fixture(&self, source_id: &str, plan: &str, dates: &DateHints, path: &Path) -> Vec<ContractCase> {
+        let name = path.file_name().map(|n| n.to_string_lossy().into_owned()).unwrap_or_default();
+        let as_of = path
+            .file_stem()
+            .and_then(|s| s.to_str())
+            .and_then(|s| NaiveDate::parse_from_str(s, "%Y-%m-%d").ok())
+            .unwrap_or_else(|| chrono::Utc::now().date_naive());
+        let mut cases = Vec::new();
+        let mut check = |invariant: &str, started: Instant, failure: Option<String>| {
+            cases.push(ContractCase {
+                fixture: name.clone(),
+                invariant: invariant.to_string(),
+                duration_ms: started.elapsed().as_secs_f64() * 1000.0,
+                failure,
+            });
+        };
+
+        let started = Instant::now();
+        let dates = dates.clone().with_reference(as_of);
+        let records = match self.parse(source_id, plan, &dates, &name, path).await {
+            Ok(records) => {
+                check(PARSES, started, None);
+                records
+            }
+            Err(e) => {
+                check(PARSES, started, Some(e));
+                return cases;
+            }
+        };
+
+        let started = Instant::now();
+        // A fresh registry per fixture, so once-per-run entities like the venue are emitted again
+        let normalizers = NormalizationRegistry::new();
+        let mut entities = Vec::new();
+        let mut errors = Vec::new();
+        for record in records.iter().filter(|r| !is_fallback(&r.record)) {
+            match normalizers.normalize(record) {
+                Ok(normalized) => entities.extend(normalized.into_iter().map(|n| n.entity)),
+                Err(e) => errors.push(format!("{}: {:#}", record.record_path, e)),
+            }
+        }
+        check(NORMALIZES, started, (!errors.is_empty()).then(|| errors.join("\n")));
+
+        let started = Instant::now();
+        let events: Vec<_> = entities
+            .iter()
+            .filter_map(|e| match e {
+                NormalizedEntity::Event(event) => Some(event),
+                _ => None,
+            })
+            .collect();
+        check(
+            AT_LEAST_ONE_EVENT,
+            started,
+            events.is_empty().then(|| format!("{} records parsed, no events normalized", records.len())),
+        );
+
+        let started = Instant::now();
+        let outside: Vec<String> = events
+            .iter()
+            .filter(|e| (e.event_day - as_of).num_days().abs() > EVENT_WINDOW_DAYS)
+            .map(|e| format!("{} on {}", e.title, e.event_day))
+            .collect();
+        check(
+            EVENTS_WITHIN_A_YEAR,
+            started,
+            (!outside.is_empty()).then(|| format!("{} events more than a year from {}\n{}", outside.len(), as_of, outside.join("\n"))),
+        );
+
+        let started = Instant::now();
+        let venues: Vec<&str> = entities
+            .iter()
+            .filter_map(|e| match e {
+                NormalizedEntity::Venue(venue) => Some(venue.name.as_str()),
+                _ => None,
+            })
+            .collect();
+        let failure = if venues.is_empty() {
+            Some("no venue normalized".to_string())
+        } else if venues.iter().any(|name| name.trim().is_empty()) {
+            Some("a venue has an empty name".to_string())
+        } else {
+            None
+        };
+        check(VENUE_NAMED, started, failure);
+        cases
+    }
+
+    async fn parse(&self, source_id: &str, plan: &str, dates: &DateHints, name: &str, path: &Path) -> Result<Vec<ParsedRecord>, String> {
+        let parser = self.parsers.for_source(plan, dates).ok_or_else(|| format!("no_parser_for_plan:{}", plan))?;
+        let bytes = std::fs::read(path).map_err(|e| format!("read {}: {}", path.display(), e))?;
+        let lines = parser
+            .parse(source_id, &format!("contract:{}", name), &path.display().to_string(), &bytes)
+            .await?;
+        lines
+            .iter()
+            .map(|line| serde_json::from_str::<ParsedRecord>(line).map_err(|e| format!("parser emitted an unreadable record: {}", e)))
+            .collect()
+    }
+}
+
+/// Payload files in `dir`, by name; dotfiles and `expected_records.json` are skipped
+fn list_fixtures(dir: &Path) -> Result<Vec<PathBuf>, String> {
+    let entries = std::fs::read_dir(dir).map_err(|e| format!("read fixtures {}: {}", dir.display(), e))?;
+    let mut fixtures: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|p| p.is_file())
+        .filter(|p| {
+            let name = p.file_name().map(|n| n.to_string_lossy().into_owned()).unwrap_or_default();
+            !name.starts_with('.') && name != EXPECTED_RECORDS
+        })
+        .collect();
+    fixtures.sort();
+    if fixtures.is_empty() {
+        return Err(format!("no fixtures in {}", dir.display()));
+    }
+    Ok(fixtures)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infra::parser_factory::DefaultParserFactory;
+
+    fn write_source(root: &Path, events: serde_json::Value) {
+        let registry = root.join("registry");
+        std::fs::create_dir_all(&registry).unwrap();
+        std::fs::write(
+            registry.join("conor_byrne.json"),
+            serde_json::json!({
+                "source_id": "conor_byrne",
+                "enabled": true,
+                "endpoints": [{ "url": "https://www.venuepilot.co/graphql", "method": "POST" }],
+                "content": { "allowed_mime_types": ["application/json"], "max_payload_size_bytes": 1024 },
+                "parse_plan_ref": "parse_plan:venuepilot_graphql_v1",
+                "policy": { "license_id": "test" }
+            })
+            .to_string(),
+        )
+        .unwrap();
+        let fixtures = root.join("fixtures/conor_byrne");
+        std::fs::create_dir_all(&fixtures).unwrap();
+        let payload = serde_json::json!({ "data": { "paginatedEvents": { "collection": events } } });
+        std::fs::write(fixtures.join("2025-03-01.json"), payload.to_string()).unwrap();
+        std::fs::write(fixtures.join(EXPECTED_RECORDS), "[]\n").unwrap();
+    }
+
+    async fn run(root: &Path) -> ContractReport {
+        ContractTestUseCase::new(Box::new(DefaultParserFactory))
+            .run("conor_byrne", &root.join("registry"), &root.join("fixtures"))
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn fixture_meeting_every_invariant_passes() {
+        let tmp = tempfile::tempdir().unwrap();
+        write_source(tmp.path(), serde_json::json!([{ "name": "The Dip", "date": "2025-03-14", "startTime": "20:00:00" }]));
+
+        let report = run(tmp.path()).await;
+        assert!(report.passed(), "{:?}", report.cases);
+        assert_eq!(report.cases.len(), 5);
+        let xml = report.to_junit_xml();
+        assert!(xml.contains("<testsuite name=\"contract.conor_byrne\" tests=\"5\" failures=\"0\""));
+        assert!(xml.contains("name=\"2025-03-01.json: at least one event\""));
+    }
+
+    #[tokio::test]
+    async fn reports_events_outside_the_window_and_empty_payloads() {
+        let tmp = tempfile::tempdir().unwrap();
+        write_source(tmp.path(), serde_json::json!([{ "name": "Far <Future>", "date": "2027-01-01" }]));
+
+        let report = run(tmp.path()).await;
+        let failed: Vec<&str> = report.cases.iter().filter(|c| c.failure.is_some()).map(|c| c.invariant.as_str()).collect();
+        assert_eq!(failed, [EVENTS_WITHIN_A_YEAR]);
+        assert!(report.to_junit_xml().contains("Far &lt;Future&gt; on 2027-01-01"));
+
+        write_source(tmp.path(), serde_json::json!([]));
+        let report = run(tmp.path()).await;
+        let failed: Vec<&str> = report.cases.iter().filter(|c| c.failure.is_some()).map(|c| c.invariant.as_str()).collect();
+        assert_eq!(failed, [AT_LEAST_ONE_EVENT, VENUE_NAMED]);
+    }
+}
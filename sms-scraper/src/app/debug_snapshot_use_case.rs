@@ -0,0 +1,252 @@
+use crate::app::ports::{ParserFactory, PayloadStorePort, RegistryPort};
+use crate::pipeline::ingestion::envelope::StampedEnvelopeV2;
+use serde::Serialize;
+use serde_json::Value;
+use sms_parsers::ParsedRecord;
+use std::path::{Path, PathBuf};
+
+/// One payload part written to disk, with how the source's parser handled it
+#[derive(Debug, Clone, Serialize)]
+pub struct SnapshotPart {
+    pub payload_ref: String,
+    pub size_bytes: usize,
+    pub files: Vec<PathBuf>,
+    pub records: usize,
+    /// The parser found no events and emitted its `html_len` placeholder record instead
+    pub fallback: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// Why this part yielded no usable records, if it didn't
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub failure: Option<String>,
+}
+
+/// Everything written for one envelope; also saved as `meta.json` next to the payload files
+#[derive(Debug, Clone, Serialize)]
+pub struct Snapshot {
+    pub envelope_id: String,
+    pub source_id: String,
+    /// The original envelope whose payload was used, when this one was a dedupe marker
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub payload_from: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mime_type: Option<String>,
+    pub parse_plan: String,
+    pub parts: Vec<SnapshotPart>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub dir: PathBuf,
+}
+
+/// Materializes an envelope's CAS payload as files a person can open (pretty JSON, an HTML
+/// copy that loads in a browser) and records what the registered parser made of it.
+pub struct DebugSnapshotUseCase<R: RegistryPort + ?Sized, S: PayloadStorePort + ?Sized, F: ParserFactory + ?Sized> {
+    pub registry: Box<R>,
+    pub payloads: Box<S>,
+    pub parsers: Box<F>,
+}
+
+impl<R: RegistryPort + ?Sized, S: PayloadStorePort + ?Sized, F: ParserFactory + ?Sized> DebugSnapshotUseCase<R, S, F> {
+    pub fn new(registry: Box<R>, payloads: Box<S>, parsers: Box<F>) -> Self {
+        Self { registry, payloads, parsers }
+    }
+
+    /// Snapshot `envelope` into `out_dir/<envelope_id>/`. For a dedupe marker pass the original
+    /// envelope as `payload_source`; its payload is used instead.
+    pub async fn snapshot(&self, envelope: &StampedEnvelopeV2, payload_source: Option<&StampedEnvelopeV2>, out_dir: &Path) -> Result<Snapshot, String> {
+        let envelope_id = envelope.envelope_id.as_str();
+        let source_id = envelope.envelope.source_id.as_str();
+        let source = payload_source.unwrap_or(envelope);
+        let payload_refs = source.payload_refs();
+        if payload_refs.is_empty() {
+            return Err(format!("envelope {} has no payload_ref", envelope_id));
+        }
+        let non_empty = |s: &str| Some(s.to_string()).filter(|s| !s.is_empty());
+        let url = non_empty(&source.envelope.request.url);
+        let mime_type = non_empty(&source.envelope.payload_meta.mime_type);
+
+        let dir = out_dir.join(envelope_id);
+        std::fs::create_dir_all(&dir).map_err(|e| format!("create {}: {}", dir.display(), e))?;
+        let plan = self.registry.load_parse_plan(source_id).await?;
+        let parser = self.parsers.for_plan(&plan);
+
+        let mut parts = Vec::with_capacity(payload_refs.len());
+        for (i, payload_ref) in payload_refs.iter().enumerate() {
+            let bytes = self.payloads.get(payload_ref).await?;
+            let stem = if payload_refs.len() == 1 { "payload".to_string() } else { format!("payload-{}", i + 1) };
+            let mut files = Vec::new();
+            for (ext, contents) in render_payload(&bytes, mime_type.as_deref(), url.as_deref()) {
+                let path = dir.join(format!("{}.{}", stem, ext));
+                std::fs::write(&path, contents).map_err(|e| format!("write {}: {}", path.display(), e))?;
+                files.push(path);
+            }
+            let parsed = match &parser {
+                Some(parser) => parser.parse(source_id, envelope_id, payload_ref, &bytes).await,
+                None => Err(format!("no_parser_for_plan:{}", plan)),
+            };
+            let (records, fallback, error) = match parsed {
+                Ok(lines) => (lines.len(), is_fallback(&lines), None),
+                Err(e) => (0, false, Some(e)),
+            };
+            let failure = match &error {
+                Some(e) => Some(format!("parser error: {}", e)),
+                None if fallback => Some("no events extracted; parser emitted an html_len fallback record".to_string()),
+                None if records == 0 => Some("parser returned no records".to_string()),
+                None => None,
+            };
+            parts.push(SnapshotPart {
+                payload_ref: payload_ref.clone(),
+                size_bytes: bytes.len(),
+                files,
+                records,
+                fallback,
+                error,
+                failure,
+            });
+        }
+
+        let snapshot = Snapshot {
+            envelope_id: envelope_id.to_string(),
+            source_id: source_id.to_string(),
+            payload_from: payload_source.map(|s| s.envelope_id.clone()),
+            url,
+            mime_type,
+            parse_plan: plan,
+            parts,
+            created_at: chrono::Utc::now(),
+            dir: dir.clone(),
+        };
+        let meta = serde_json::json!({ "snapshot": &snapshot, "envelope": envelope });
+        let meta_path = dir.join("meta.json");
+        std::fs::write(&meta_path, serde_json::to_vec_pretty(&meta).map_err(|e| e.to_string())?)
+            .map_err(|e| format!("write {}: {}", meta_path.display(), e))?;
+        Ok(snapshot)
+    }
+}
+
+/// Files to write for one payload as (extension, contents): the exact bytes, plus pretty JSON
+/// when it parses as JSON, or an HTML copy with a `<base>` pointing at the fetched URL so
+/// relative stylesheets and images resolve when opened locally
+pub fn render_payload(bytes: &[u8], mime_type: Option<&str>, url: Option<&str>) -> Vec<(&'static str, Vec<u8>)> {
+    let mut files = vec![("raw", bytes.to_vec())];
+    if let Ok(json) = serde_json::from_slice::<Value>(bytes) {
+        if let Ok(pretty) = serde_json::to_vec_pretty(&json) {
+            files.push(("json", pretty));
+        }
+    } else if mime_type.is_some_and(|m| m.contains("html")) || looks_like_html(bytes) {
+        let html = String::from_utf8_lossy(bytes);
+        let html = match url {
+            Some(url) => with_base_href(&html, url),
+            None => html.into_owned(),
+        };
+        files.push(("html", html.into_bytes()));
+    }
+    files
+}
+
+fn looks_like_html(bytes: &[u8]) -> bool {
+    let head = String::from_utf8_lossy(&bytes[..bytes.len().min(512)]).trim_start().to_ascii_lowercase();
+    head.starts_with("<!doctype html") || head.starts_with("<html")
+}
+
+/// Insert `<base href>` right after `<head>` (or at the top when there is none)
+fn with_base_href(html: &str, url: &str) -> String {
+    let base = format!("<base href=\"{}\">", url.replace('"', "&quot;"));
+    // ASCII lowering keeps byte offsets valid for slicing `html`
+    let lower = html.to_ascii_lowercase();
+    let head = lower
+        .match_indices("<head")
+        .map(|(start, _)| start)
+        .find(|&start| lower[start + 5..].starts_with(|c: char| c == '>' || c.is_ascii_whitespace()));
+    match head.and_then(|start| lower[start..].find('>').map(|end| start + end + 1)) {
+        Some(at) => format!("{}{}{}", &html[..at], base, &html[at..]),
+        None => format!("{}{}", base, html),
+    }
+}
+
+/// True when every record is a parser's `{"html_len": n}` placeholder
+fn is_fallback(lines: &[String]) -> bool {
+    !lines.is_empty()
+        && lines.iter().all(|line| {
+            serde_json::from_str::<ParsedRecord>(line)
+                .map(|r| r.record.as_object().is_some_and(|o| o.len() == 1 && o.contains_key("html_len")))
+                .unwrap_or(false)
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::ports::ParserPort;
+    use crate::pipeline::ingestion::envelope::StampedEnvelopeV1;
+    use async_trait::async_trait;
+    use serde_json::json;
+
+    struct FixedRegistry;
+    #[async_trait]
+    impl RegistryPort for FixedRegistry {
+        async fn load_parse_plan(&self, _source_id: &str) -> Result<String, String> {
+            Ok("parse_plan:test_v1".to_string())
+        }
+    }
+
+    struct FixedPayloads;
+    #[async_trait]
+    impl PayloadStorePort for FixedPayloads {
+        async fn get(&self, _payload_ref: &str) -> Result<Vec<u8>, String> {
+            Ok(b"<!DOCTYPE html><html><head><title>Calendar</title></head><body></body></html>".to_vec())
+        }
+    }
+
+    struct FallbackParser;
+    #[async_trait]
+    impl ParserPort for FallbackParser {
+        async fn parse(&self, source_id: &str, envelope_id: &str, payload_ref: &str, bytes: &[u8]) -> Result<Vec<String>, String> {
+            let record = json!({
+                "source_id": source_id,
+                "envelope_id": envelope_id,
+                "payload_ref": payload_ref,
+                "record_path": "$.html",
+                "record": { "html_len": bytes.len() },
+            });
+            Ok(vec![record.to_string()])
+        }
+    }
+
+    struct Parsers;
+    impl ParserFactory for Parsers {
+        fn for_plan(&self, _plan: &str) -> Option<Box<dyn ParserPort>> {
+            Some(Box::new(FallbackParser))
+        }
+    }
+
+    #[tokio::test]
+    async fn snapshot_writes_browsable_html_and_explains_the_fallback() {
+        let tmp = tempfile::tempdir().unwrap();
+        let mut envelope = StampedEnvelopeV1::fixture("kexp", "cas:sha256:abcd");
+        envelope.envelope_id = "env-1".to_string();
+        envelope.envelope.payload_meta.mime_type = "text/html; charset=utf-8".to_string();
+        envelope.envelope.request.url = "https://www.kexp.org/events/".to_string();
+        let envelope = StampedEnvelopeV2::from(envelope);
+        let uc = DebugSnapshotUseCase::new(Box::new(FixedRegistry), Box::new(FixedPayloads), Box::new(Parsers));
+        let snapshot = uc.snapshot(&envelope, None, tmp.path()).await.unwrap();
+
+        let part = &snapshot.parts[0];
+        assert!(part.fallback);
+        assert!(part.failure.as_deref().unwrap().contains("html_len"));
+        let html = std::fs::read_to_string(tmp.path().join("env-1/payload.html")).unwrap();
+        assert!(html.starts_with("<!DOCTYPE html><html><head><base href=\"https://www.kexp.org/events/\"><title>"));
+        assert!(tmp.path().join("env-1/payload.raw").exists());
+        let meta: Value = serde_json::from_slice(&std::fs::read(tmp.path().join("env-1/meta.json")).unwrap()).unwrap();
+        assert_eq!(meta["snapshot"]["parse_plan"], "parse_plan:test_v1");
+    }
+
+    #[test]
+    fn json_payloads_are_pretty_printed() {
+        let files = render_payload(br#"{"events":[{"title":"Show"}]}"#, Some("application/json"), None);
+        let exts: Vec<_> = files.iter().map(|(ext, _)| *ext).collect();
+        assert_eq!(exts, vec!["raw", "json"]);
+        assert!(String::from_utf8_lossy(&files[1].1).contains("\n  \"events\""));
+    }
+}
@@ -0,0 +1,310 @@
+use crate::app::ports::ParserFactory;
+use crate::observability::push::{PushConfig, DEFAULT_PUSHGATEWAY_URL, PUSHGATEWAY_URL_ENV};
+use crate::pipeline::ingestion::encryption::{Keyring, ENCRYPTION_KEY_COMMAND_ENV, ENCRYPTION_KEY_ENV};
+use crate::pipeline::ingestion::registry::load_source_spec;
+use std::collections::HashMap;
+use std::path::Path;
+use std::time::Duration;
+
+const SUPABASE_VARS: [&str; 3] = ["SUPABASE_URL", "SUPABASE_SERVICE_ROLE_KEY", "SUPABASE_BUCKET"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CheckStatus {
+    Pass,
+    Warn,
+    Fail,
+}
+
+/// Outcome of one environment check, with a hint on how to fix it when it didn't pass
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct DoctorCheck {
+    pub name: String,
+    pub status: CheckStatus,
+    pub detail: String,
+    pub hint: Option<String>,
+}
+
+impl DoctorCheck {
+    fn pass(name: &str, detail: impl Into<String>) -> Self {
+        Self { name: name.to_string(), status: CheckStatus::Pass, detail: detail.into(), hint: None }
+    }
+
+    fn warn(name: &str, detail: impl Into<String>, hint: impl Into<String>) -> Self {
+        Self { name: name.to_string(), status: CheckStatus::Warn, detail: detail.into(), hint: Some(hint.into()) }
+    }
+
+    fn fail(name: &str, detail: impl Into<String>, hint: impl Into<String>) -> Self {
+        Self { name: name.to_string(), status: CheckStatus::Fail, detail: detail.into(), hint: Some(hint.into()) }
+    }
+}
+
+/// Required and optional environment variables. `lookup` is `std::env::var(..).ok()` outside tests.
+pub fn check_env_vars(lookup: impl Fn(&str) -> Option<String>) -> Vec<DoctorCheck> {
+    let set = |name: &str| lookup(name).is_some_and(|v| !v.trim().is_empty());
+    let mut checks = Vec::new();
+
+    for name in ["LIBSQL_URL", "LIBSQL_AUTH_TOKEN"] {
+        checks.push(if set(name) {
+            DoctorCheck::pass(&format!("env {}", name), "set")
+        } else {
+            DoctorCheck::fail(
+                &format!("env {}", name),
+                "not set",
+                format!("export {} (or add it to .env) with your Turso database credentials", name),
+            )
+        });
+    }
+
+    let name = format!("env {}", PUSHGATEWAY_URL_ENV);
+    checks.push(match PushConfig::resolve(None, &lookup) {
+        Ok(push) => match push.url() {
+            Some(url) => DoctorCheck::pass(&name, format!("metrics are pushed to {}", url)),
+            None if set(PUSHGATEWAY_URL_ENV) => DoctorCheck::pass(&name, "set, but pushing is turned off"),
+            None => DoctorCheck::warn(
+                &name,
+                "not set, metrics are not pushed",
+                format!("export {} (or SMS_ENV=development to push to {})", PUSHGATEWAY_URL_ENV, DEFAULT_PUSHGATEWAY_URL),
+            ),
+        },
+        Err(e) => DoctorCheck::fail(&name, format!("{:#}", e), "SMS_ENV takes development or production and SMS_METRICS_PUSH true or false; pushing needs a Pushgateway URL"),
+    });
+
+    // Supabase CAS only kicks in when fully configured; a partial setup silently falls back to disk
+    let has_url = set("SUPABASE_URL") || set("SUPABASE_PROJECT_REF");
+    let missing: Vec<&str> = SUPABASE_VARS
+        .iter()
+        .copied()
+        .filter(|name| if *name == "SUPABASE_URL" { !has_url } else { !set(name) })
+        .collect();
+    if missing.len() == SUPABASE_VARS.len() {
+        checks.push(DoctorCheck::pass("env SUPABASE_*", "not configured, payloads are stored on disk"));
+    } else if missing.is_empty() {
+        checks.push(DoctorCheck::pass("env SUPABASE_*", "configured, payloads are stored in Supabase"));
+    } else {
+        checks.push(DoctorCheck::warn(
+            "env SUPABASE_*",
+            format!("partially configured, missing {}", missing.join(", ")),
+            "set the missing variables (SUPABASE_PROJECT_REF can stand in for SUPABASE_URL) or unset the rest",
+        ));
+    }
+
+    checks
+}
+
+/// Every registry entry parses, has a unique source_id matching its file name and a known parser plan
+pub fn check_registry(registry_dir: &Path, parsers: &dyn ParserFactory) -> Vec<DoctorCheck> {
+    let name = "registry";
+    let entries = match std::fs::read_dir(registry_dir) {
+        Ok(entries) => entries,
+        Err(e) => {
+            return vec![DoctorCheck::fail(
+                name,
+                format!("cannot read {}: {}", registry_dir.display(), e),
+                "run from the repository root or pass --registry-dir",
+            )]
+        }
+    };
+
+    let mut paths: Vec<_> = entries
+        .filter_map(|e| e.ok().map(|e| e.path()))
+        .filter(|p| p.extension().and_then(|e| e.to_str()) == Some("json"))
+        .collect();
+    paths.sort();
+    if paths.is_empty() {
+        return vec![DoctorCheck::fail(
+            name,
+            format!("no source specs in {}", registry_dir.display()),
+            "add registry/sources/<source_id>.json (see ADDING_NEW_SCRAPING_PIPELINES.md)",
+        )];
+    }
+
+    let mut checks = Vec::new();
+    let mut seen: HashMap<String, String> = HashMap::new();
+    let mut enabled = 0usize;
+    for path in &paths {
+        let file = path.file_name().and_then(|f| f.to_str()).unwrap_or_default().to_string();
+        let entry = format!("registry {}", file);
+        let spec = match load_source_spec(path) {
+            Ok(spec) => spec,
+            Err(e) => {
+                checks.push(DoctorCheck::fail(&entry, format!("invalid spec: {}", e), "fix the JSON against registry/schema/source-spec.v1.json"));
+                continue;
+            }
+        };
+        if let Some(other) = seen.insert(spec.source_id.clone(), file.clone()) {
+            checks.push(DoctorCheck::fail(
+                &entry,
+                format!("source_id {} is also declared in {}", spec.source_id, other),
+                "give every registry entry a unique source_id",
+            ));
+            continue;
+        }
+        if path.file_stem().and_then(|s| s.to_str()) != Some(spec.source_id.as_str()) {
+            checks.push(DoctorCheck::warn(
+                &entry,
+                format!("source_id {} does not match the file name", spec.source_id),
+                format!("rename the file to {}.json; commands look specs up by source_id", spec.source_id),
+            ));
+            continue;
+        }
+        if let Err(e) = spec.date_hints() {
+            checks.push(DoctorCheck::fail(&entry, e, "set dates.timezone to an IANA name such as America/Los_Angeles"));
+            continue;
+        }
+        if spec.enabled {
+            match spec.resolved_parse_plan() {
+                Some(plan) if parsers.for_plan(&plan).is_none() => {
+                    checks.push(DoctorCheck::fail(
+                        &entry,
+                        format!("no parser registered for {}", plan),
+                        "register the plan in DefaultParserFactory or fix parser_plan",
+                    ));
+                    continue;
+                }
+                None => {
+                    checks.push(DoctorCheck::fail(&entry, "enabled but declares no parser_plan", "add a parser_plan or disable the source"));
+                    continue;
+                }
+                Some(_) => enabled += 1,
+            }
+        }
+    }
+
+    if checks.is_empty() {
+        checks.push(DoctorCheck::pass(name, format!("{} specs, {} enabled", paths.len(), enabled)));
+    }
+    checks
+}
+
+/// The local CAS root accepts writes
+pub fn check_cas(data_root: &Path) -> DoctorCheck {
+    let name = "cas";
+    let cas_root = data_root.join("cas");
+    let probe = cas_root.join(".doctor_probe");
+    let result = std::fs::create_dir_all(&cas_root)
+        .and_then(|_| std::fs::write(&probe, b"ok"))
+        .and_then(|_| std::fs::remove_file(&probe));
+    match result {
+        Ok(()) => DoctorCheck::pass(name, format!("{} is writable", cas_root.display())),
+        Err(e) => DoctorCheck::fail(
+            name,
+            format!("cannot write to {}: {}", cas_root.display(), e),
+            "check permissions on the data directory or pass --data-root",
+        ),
+    }
+}
+
+/// The `SMS_ENCRYPTION_*` keys load, so payloads and log lines can be sealed and read back
+pub fn check_encryption(lookup: impl Fn(&str) -> Option<String>) -> DoctorCheck {
+    let name = "encryption";
+    match Keyring::from_lookup(lookup) {
+        Ok(keyring) => match keyring.meta() {
+            Some(meta) => DoctorCheck::pass(
+                name,
+                format!("{} with key {} (can decrypt {})", meta.alg, meta.key_id, keyring.key_ids().join(", ")),
+            ),
+            None => DoctorCheck::pass(name, "off, payloads and the ingest log are stored in plaintext"),
+        },
+        Err(e) => DoctorCheck::fail(
+            name,
+            format!("{:#}", e),
+            format!("set {} to 64 hex chars (openssl rand -hex 32) or fix {}", ENCRYPTION_KEY_ENV, ENCRYPTION_KEY_COMMAND_ENV),
+        ),
+    }
+}
+
+/// The database is reachable with the configured credentials and its schema is current
+pub async fn check_database() -> DoctorCheck {
+    let name = "database";
+    let db = match sms_core::database::DatabaseManager::new().await {
+        Ok(db) => db,
+        Err(e) => return DoctorCheck::fail(name, e.to_string(), "check LIBSQL_URL and LIBSQL_AUTH_TOKEN"),
+    };
+    match db.migration_status().await {
+        Ok(status) => {
+            let pending = status.iter().filter(|m| m.applied_at.is_none()).count();
+            if pending == 0 {
+                DoctorCheck::pass(name, "connected, schema up to date")
+            } else {
+                DoctorCheck::warn(name, format!("connected, {} pending migrations", pending), "run `sms-scraper migrate up`")
+            }
+        }
+        Err(e) => DoctorCheck::fail(name, format!("query failed: {}", e), "check the database URL, token and network access"),
+    }
+}
+
+/// The Pushgateway answers its health endpoint
+pub async fn check_pushgateway(base_url: &str) -> DoctorCheck {
+    let name = "pushgateway";
+    let url = format!("{}/-/healthy", base_url.trim_end_matches('/'));
+    let client = match reqwest::Client::builder().timeout(Duration::from_secs(5)).build() {
+        Ok(client) => client,
+        Err(e) => return DoctorCheck::fail(name, e.to_string(), "check TLS/proxy configuration"),
+    };
+    match client.get(&url).send().await {
+        Ok(resp) if resp.status().is_success() => DoctorCheck::pass(name, format!("{} is healthy", base_url)),
+        Ok(resp) => DoctorCheck::warn(
+            name,
+            format!("{} answered {}", url, resp.status()),
+            "make sure SMS_PUSHGATEWAY_URL points at a Prometheus Pushgateway",
+        ),
+        Err(e) => DoctorCheck::warn(
+            name,
+            format!("{} unreachable: {}", base_url, e),
+            "start it with `docker compose -f docker-compose-local.yml up pushgateway` or set SMS_PUSHGATEWAY_URL; runs still succeed without it",
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infra::parser_factory::DefaultParserFactory;
+
+    fn statuses(checks: &[DoctorCheck]) -> Vec<(&str, CheckStatus)> {
+        checks.iter().map(|c| (c.name.as_str(), c.status)).collect()
+    }
+
+    #[test]
+    fn reports_env_registry_and_cas_problems_with_hints() {
+        let env: HashMap<&str, &str> = [("LIBSQL_URL", "libsql://db.turso.io"), ("SUPABASE_BUCKET", "raw")].into();
+        let checks = check_env_vars(|name| env.get(name).map(|v| v.to_string()));
+        assert_eq!(
+            statuses(&checks),
+            vec![
+                ("env LIBSQL_URL", CheckStatus::Pass),
+                ("env LIBSQL_AUTH_TOKEN", CheckStatus::Fail),
+                ("env SMS_PUSHGATEWAY_URL", CheckStatus::Warn),
+                ("env SUPABASE_*", CheckStatus::Warn),
+            ]
+        );
+        assert!(checks.iter().filter(|c| c.status != CheckStatus::Pass).all(|c| c.hint.is_some()));
+
+        let tmp = tempfile::tempdir().unwrap();
+        let spec = |id: &str, plan: &str| {
+            serde_json::json!({
+                "source_id": id,
+                "enabled": true,
+                "endpoints": [{ "url": "https://venue.example/", "method": "GET" }],
+                "content": { "allowed_mime_types": ["text/html"], "max_payload_size_bytes": 1024 },
+                "parse_plan_ref": plan,
+                "policy": { "license_id": "test" }
+            })
+            .to_string()
+        };
+        std::fs::write(tmp.path().join("neumos.json"), spec("neumos", "parse_plan:neumos_html_v1")).unwrap();
+        assert_eq!(statuses(&check_registry(tmp.path(), &DefaultParserFactory)), vec![("registry", CheckStatus::Pass)]);
+
+        std::fs::write(tmp.path().join("other.json"), spec("other", "parse_plan:missing_v1")).unwrap();
+        std::fs::write(tmp.path().join("broken.json"), "{").unwrap();
+        assert_eq!(
+            statuses(&check_registry(tmp.path(), &DefaultParserFactory)),
+            vec![("registry broken.json", CheckStatus::Fail), ("registry other.json", CheckStatus::Fail)]
+        );
+
+        assert_eq!(check_cas(tmp.path()).status, CheckStatus::Pass);
+        assert!(!tmp.path().join("cas/.doctor_probe").exists());
+        assert_eq!(check_registry(&tmp.path().join("missing"), &DefaultParserFactory)[0].status, CheckStatus::Fail);
+    }
+}
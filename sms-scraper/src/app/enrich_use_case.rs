@@ -1,5 +1,8 @@
 use anyhow::Result;
+use tracing::Instrument;
 use crate::app::ports::EnrichOutputPort;
+use crate::observability::logging;
+use sms_core::pipeline_api::plugins;
 use crate::pipeline::processing::enrich::{
     Enricher, EnrichedRecord, DefaultEnricher, MetricsEnricher
 };
@@ -22,16 +25,25 @@ impl EnrichUseCase {
         }
     }
 
-    /// Create a use case with the default enricher
+    /// Create a use case with the default enricher, or the registered plugin enricher if any
     pub fn with_default_enricher(output: Box<dyn EnrichOutputPort>) -> Self {
         Self {
-            enricher: Box::new(MetricsEnricher::new(DefaultEnricher::new())),
+            enricher: match plugins().enricher() {
+                Some(enricher) => Box::new(MetricsEnricher::new(enricher)),
+                None => Box::new(MetricsEnricher::new(DefaultEnricher::new())),
+            },
             output,
         }
     }
 
     /// Enrich a single quality-assessed record
     pub async fn enrich_record(&self, record: &QualityAssessedRecord) -> Result<EnrichedRecord> {
+        self.enrich_and_write(record)
+            .instrument(logging::envelope_span(&record.normalized_record.provenance.envelope_id))
+            .await
+    }
+
+    async fn enrich_and_write(&self, record: &QualityAssessedRecord) -> Result<EnrichedRecord> {
         // Apply enrichment logic (metrics are handled by MetricsEnricher wrapper)
         let enriched_record = self.enricher.enrich(record)?;
 
@@ -206,6 +218,12 @@ mod tests {
             neighborhood: None,
             show_venue: true,
             created_at: Utc::now(),
+            attributions: Vec::new(),
+            provisional: false,
+            aliases: Vec::new(),
+            age_restriction: None,
+            accessibility_notes: None,
+            moderation: None,
         };
 
         let quality_assessed_record = QualityAssessedRecord {
@@ -217,6 +235,11 @@ mod tests {
                     payload_ref: "test_payload".to_string(),
                     record_path: "$.venues[0]".to_string(),
                     normalized_at: Utc::now(),
+                    attribution: None,
+                    change: None,
+                    record_key: None,
+                    endpoint_id: None,
+                    external_id: None,
                 },
                 normalization: NormalizationMetadata {
                     confidence: 0.8,
@@ -40,7 +40,7 @@ impl<R: RateLimiterPort + ?Sized, C: CadencePort + ?Sized, H: HttpClientPort + ?
             source_id: source_id.to_string(),
             idempotency_key: format!("{}:{}:{}:{}", source_id, url, resp.etag.clone().unwrap_or_default(), sha_hex),
             payload_meta: PayloadMeta { mime_type: resp.content_type.clone(), size_bytes: resp.content_length, checksum: ChecksumMeta { sha256: sha_hex.clone() } },
-            request: RequestMeta { url: url.to_string(), method: method.to_string(), status: Some(resp.status), etag: resp.etag.clone(), last_modified: resp.last_modified.clone() },
+            request: RequestMeta { url: url.to_string(), method: method.to_string(), status: Some(resp.status), etag: resp.etag.clone(), last_modified: resp.last_modified.clone(), endpoint_id: None },
             timing: TimingMeta { fetched_at: chrono::Utc::now(), gateway_received_at: None },
             legal: LegalMeta { license_id: license_id.to_string() },
         };
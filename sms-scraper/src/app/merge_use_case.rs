@@ -0,0 +1,339 @@
+use crate::pipeline::processing::conflation::EntityType;
+use crate::pipeline::processing::resolution_index::ResolutionIndex;
+use chrono::Utc;
+use serde::Serialize;
+use sms_core::domain::{ProcessRecord, ProcessRun};
+use sms_core::storage::Storage;
+use std::sync::Arc;
+use uuid::Uuid;
+
+/// Name of the process runs merges are recorded under
+pub const MERGE_RUN_NAME: &str = "merge";
+
+/// What can be merged
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MergedEntity {
+    Artist,
+    Venue,
+}
+
+impl MergedEntity {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            MergedEntity::Artist => "artist",
+            MergedEntity::Venue => "venue",
+        }
+    }
+
+    fn entity_type(self) -> EntityType {
+        match self {
+            MergedEntity::Artist => EntityType::Artist,
+            MergedEntity::Venue => EntityType::Venue,
+        }
+    }
+}
+
+/// One merge as applied and recorded
+#[derive(Debug, Clone, Serialize)]
+pub struct MergeOutcome {
+    pub entity: MergedEntity,
+    pub kept_id: Uuid,
+    pub kept_name: String,
+    pub removed_id: Uuid,
+    pub removed_name: String,
+    pub reason: Option<String>,
+    /// Events re-linked from the removed entity to the kept one
+    pub events_relinked: usize,
+    /// Recurring series moved to the kept venue
+    pub series_relinked: usize,
+    /// Conflation resolution keys re-pointed at the kept id
+    pub resolution_keys_repointed: usize,
+    /// Process run holding the audit records, with every changed entity's previous state
+    pub audit_run_id: Uuid,
+}
+
+/// Merges two canonical artists or venues that conflation wrongly kept apart. Events (and a
+/// venue's recurring series) move to the kept entity, which takes the removed one's name and
+/// aliases as its own aliases so name lookups find it. The removed entity is deleted and its
+/// id tombstoned in the conflation resolution index, so later runs resolve it to the kept id.
+/// Every change is written as a process run whose records hold the previous states.
+pub struct MergeUseCase {
+    storage: Arc<dyn Storage>,
+    resolution_index: Option<Arc<ResolutionIndex>>,
+}
+
+/// Previous state of one changed entity, recorded once the merge is applied
+struct Change {
+    change_type: &'static str,
+    field: &'static str,
+    log: String,
+    artist_id: Option<Uuid>,
+    venue_id: Option<Uuid>,
+    event_id: Option<Uuid>,
+    previous_state: String,
+}
+
+impl MergeUseCase {
+    pub fn new(storage: Arc<dyn Storage>) -> Self {
+        Self { storage, resolution_index: None }
+    }
+
+    /// Tombstone removed ids in this resolution index; without one only the catalog changes
+    pub fn with_resolution_index(mut self, index: Arc<ResolutionIndex>) -> Self {
+        self.resolution_index = Some(index);
+        self
+    }
+
+    pub async fn merge(
+        &self,
+        entity: MergedEntity,
+        keep: Uuid,
+        remove: Uuid,
+        reason: Option<&str>,
+    ) -> anyhow::Result<MergeOutcome> {
+        if keep == remove {
+            anyhow::bail!("cannot merge {} {} into itself", entity.as_str(), keep);
+        }
+        let reason = reason.map(str::trim).filter(|r| !r.is_empty()).map(str::to_string);
+        let (kept_name, removed_name, events_relinked, series_relinked, changes) = match entity {
+            MergedEntity::Artist => self.merge_artists(keep, remove).await?,
+            MergedEntity::Venue => self.merge_venues(keep, remove).await?,
+        };
+
+        let resolution_keys_repointed = match &self.resolution_index {
+            Some(index) => index.record_merge(&entity.entity_type(), remove, keep, Utc::now().timestamp())?,
+            None => 0,
+        };
+        let audit_run_id = self.record(entity, keep, remove, &kept_name, reason.as_deref(), changes).await?;
+        Ok(MergeOutcome {
+            entity,
+            kept_id: keep,
+            kept_name,
+            removed_id: remove,
+            removed_name,
+            reason,
+            events_relinked,
+            series_relinked,
+            resolution_keys_repointed,
+            audit_run_id,
+        })
+    }
+
+    async fn merge_artists(&self, keep: Uuid, remove: Uuid) -> anyhow::Result<(String, String, usize, usize, Vec<Change>)> {
+        let mut kept = self.storage.get_artist_by_id(keep).await?.ok_or_else(|| anyhow::anyhow!("no artist {}", keep))?;
+        let removed = self.storage.get_artist_by_id(remove).await?.ok_or_else(|| anyhow::anyhow!("no artist {}", remove))?;
+        let mut changes = Vec::new();
+
+        let events = self.storage.get_events_by_artist_id(remove).await?;
+        for mut event in events.iter().cloned() {
+            let previous_state = serde_json::to_string(&event)?;
+            let mut artist_ids = Vec::with_capacity(event.artist_ids.len());
+            for id in event.artist_ids.iter().map(|&id| if id == remove { keep } else { id }) {
+                if !artist_ids.contains(&id) {
+                    artist_ids.push(id);
+                }
+            }
+            event.artist_ids = artist_ids;
+            self.storage.update_event(&event).await?;
+            changes.push(Change::event(&event, "artist_ids", format!("Re-linked from artist {} to {}", remove, keep), previous_state));
+        }
+
+        let previous_state = serde_json::to_string(&kept)?;
+        merge_aliases(&mut kept.aliases, &kept.name, &removed.name, &removed.aliases);
+        for attribution in removed.attributions.iter().cloned() {
+            attribution.add_to(&mut kept.attributions);
+        }
+        if kept.artist_image_url.is_none() && removed.artist_image_url.is_some() {
+            kept.artist_image_url = removed.artist_image_url.clone();
+            kept.detail_origins.image = removed.detail_origins.image;
+        }
+        if kept.bio.is_none() && removed.bio.is_some() {
+            kept.bio = removed.bio.clone();
+            kept.detail_origins.bio = removed.detail_origins.bio;
+        }
+        self.storage.update_artist(&kept).await?;
+        changes.push(Change {
+            change_type: "UPDATE",
+            field: "aliases",
+            log: format!("Absorbed artist {} ({})", removed.name, remove),
+            artist_id: Some(keep),
+            venue_id: None,
+            event_id: None,
+            previous_state,
+        });
+
+        self.storage.delete_artist(remove).await?;
+        changes.push(Change {
+            change_type: "MERGE",
+            field: "merged_into",
+            log: format!("Merged into artist {} ({})", kept.name, keep),
+            artist_id: Some(remove),
+            venue_id: None,
+            event_id: None,
+            previous_state: serde_json::to_string(&removed)?,
+        });
+        Ok((kept.name, removed.name, events.len(), 0, changes))
+    }
+
+    async fn merge_venues(&self, keep: Uuid, remove: Uuid) -> anyhow::Result<(String, String, usize, usize, Vec<Change>)> {
+        let mut kept = self.storage.get_venue_by_id(keep).await?.ok_or_else(|| anyhow::anyhow!("no venue {}", keep))?;
+        let removed = self.storage.get_venue_by_id(remove).await?.ok_or_else(|| anyhow::anyhow!("no venue {}", remove))?;
+        let mut changes = Vec::new();
+
+        let events = self.storage.get_events_by_venue_id(remove).await?;
+        for mut event in events.iter().cloned() {
+            let previous_state = serde_json::to_string(&event)?;
+            event.venue_id = keep;
+            self.storage.update_event(&event).await?;
+            changes.push(Change::event(&event, "venue_id", format!("Moved from venue {} to {}", remove, keep), previous_state));
+        }
+        let series = self.storage.get_event_series_by_venue_id(remove).await?;
+        for mut series in series.iter().cloned() {
+            series.venue_id = keep;
+            self.storage.upsert_event_series(&mut series).await?;
+        }
+
+        let previous_state = serde_json::to_string(&kept)?;
+        merge_aliases(&mut kept.aliases, &kept.name, &removed.name, &removed.aliases);
+        for attribution in removed.attributions.iter().cloned() {
+            attribution.add_to(&mut kept.attributions);
+        }
+        self.storage.create_venue(&mut kept).await?;
+        changes.push(Change {
+            change_type: "UPDATE",
+            field: "aliases",
+            log: format!("Absorbed venue {} ({})", removed.name, remove),
+            artist_id: None,
+            venue_id: Some(keep),
+            event_id: None,
+            previous_state,
+        });
+
+        self.storage.delete_venue(remove).await?;
+        changes.push(Change {
+            change_type: "MERGE",
+            field: "merged_into",
+            log: format!("Merged into venue {} ({})", kept.name, keep),
+            artist_id: None,
+            venue_id: Some(remove),
+            event_id: None,
+            previous_state: serde_json::to_string(&removed)?,
+        });
+        Ok((kept.name, removed.name, events.len(), series.len(), changes))
+    }
+
+    async fn record(
+        &self,
+        entity: MergedEntity,
+        keep: Uuid,
+        remove: Uuid,
+        kept_name: &str,
+        reason: Option<&str>,
+        changes: Vec<Change>,
+    ) -> anyhow::Result<Uuid> {
+        let now = Utc::now();
+        let mut run = ProcessRun { id: None, name: MERGE_RUN_NAME.to_string(), created_at: now, finished_at: Some(now) };
+        self.storage.create_process_run(&mut run).await?;
+        let run_id = run.id.ok_or_else(|| anyhow::anyhow!("process run was stored without an id"))?;
+
+        for change in changes {
+            let change_log = match reason {
+                Some(reason) => format!("{}: {}", change.log, reason),
+                None => change.log,
+            };
+            let mut record = ProcessRecord {
+                id: None,
+                process_run_id: run_id,
+                api_name: MERGE_RUN_NAME.to_string(),
+                raw_data_id: None,
+                change_type: change.change_type.to_string(),
+                change_log,
+                field_changed: change.field.to_string(),
+                event_id: change.event_id,
+                venue_id: change.venue_id,
+                artist_id: change.artist_id,
+                created_at: now,
+                previous_state: Some(change.previous_state),
+            };
+            self.storage.create_process_record(&mut record).await?;
+        }
+        tracing::info!("Merged {} {} into {} ({}), recorded in run {}", entity.as_str(), remove, kept_name, keep, run_id);
+        Ok(run_id)
+    }
+}
+
+impl Change {
+    fn event(event: &sms_core::domain::Event, field: &'static str, log: String, previous_state: String) -> Self {
+        Self { change_type: "UPDATE", field, log, artist_id: None, venue_id: None, event_id: event.id, previous_state }
+    }
+}
+
+/// Add the removed entity's name and aliases to the kept entity's aliases, skipping its own name
+fn merge_aliases(aliases: &mut Vec<String>, kept_name: &str, removed_name: &str, removed_aliases: &[String]) {
+    for name in std::iter::once(removed_name).chain(removed_aliases.iter().map(String::as_str)) {
+        let lower = name.to_lowercase();
+        if lower != kept_name.to_lowercase() && !aliases.iter().any(|a| a.to_lowercase() == lower) {
+            aliases.push(name.to_string());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+    use sms_core::common::record_key::SourceKey;
+    use sms_core::domain::{Artist, Event, Venue};
+    use sms_core::storage::InMemoryStorage;
+
+    #[tokio::test]
+    async fn merges_relink_events_alias_the_removed_name_and_tombstone_its_id() {
+        let storage = Arc::new(InMemoryStorage::new());
+        let tmp = tempfile::tempdir().unwrap();
+        let index = Arc::new(ResolutionIndex::open_at_root(tmp.path()).unwrap());
+        let (mut kept, mut removed) = (Artist::fixture("The Foo"), Artist::fixture("Foo"));
+        removed.bio = Some("Seattle trio".to_string());
+        storage.create_artist(&mut kept).await.unwrap();
+        storage.create_artist(&mut removed).await.unwrap();
+        let (keep, remove) = (kept.id.unwrap(), removed.id.unwrap());
+        index.record(&SourceKey::new("kexp", "foo"), &EntityType::Artist, remove, 1).unwrap();
+
+        let (mut venue, mut dupe) = (Venue::placeholder("Neumos"), Venue::placeholder("Neumo's"));
+        storage.create_venue(&mut venue).await.unwrap();
+        storage.create_venue(&mut dupe).await.unwrap();
+        let day = NaiveDate::from_ymd_opt(2025, 6, 1).unwrap();
+        let mut show = Event { artist_ids: vec![remove, keep], ..Event::fixture("Foo Fighters Tribute", day, dupe.id.unwrap()) };
+        storage.create_event(&mut show).await.unwrap();
+        let event_id = show.id.unwrap();
+
+        let merge = MergeUseCase::new(storage.clone()).with_resolution_index(index.clone());
+        assert!(merge.merge(MergedEntity::Artist, keep, keep, None).await.is_err());
+        let outcome = merge.merge(MergedEntity::Artist, keep, remove, Some("same band")).await.unwrap();
+        assert_eq!((outcome.events_relinked, outcome.resolution_keys_repointed), (1, 1));
+
+        let stored = storage.get_event_by_id(event_id).await.unwrap().unwrap();
+        assert_eq!(stored.artist_ids, vec![keep]);
+        assert!(storage.get_artist_by_id(remove).await.unwrap().is_none());
+        let kept = storage.get_artist_by_name("foo").await.unwrap().unwrap();
+        assert_eq!((kept.id, kept.aliases.clone(), kept.bio.as_deref()), (Some(keep), vec!["Foo".to_string()], Some("Seattle trio")));
+        assert_eq!(index.lookup(&SourceKey::new("kexp", "foo"), &EntityType::Artist).unwrap(), Some(keep));
+        assert_eq!(index.merged_into(&EntityType::Artist, remove).unwrap(), Some(keep));
+
+        // The audit trail holds what each entity looked like before
+        let records = storage.get_process_records_for_run(outcome.audit_run_id).await.unwrap();
+        let merged = records.iter().find(|r| r.change_type == "MERGE").unwrap();
+        assert_eq!(merged.artist_id, Some(remove));
+        assert!(merged.change_log.ends_with("same band"));
+        let before: Event = serde_json::from_str(
+            records.iter().find(|r| r.event_id == Some(event_id)).unwrap().previous_state.as_deref().unwrap(),
+        )
+        .unwrap();
+        assert_eq!(before.artist_ids, vec![remove, keep]);
+
+        let outcome = merge.merge(MergedEntity::Venue, venue.id.unwrap(), dupe.id.unwrap(), None).await.unwrap();
+        assert_eq!(outcome.events_relinked, 1);
+        assert_eq!(storage.get_event_by_id(event_id).await.unwrap().unwrap().venue_id, venue.id.unwrap());
+        assert_eq!(storage.get_venue_by_name("neumo's").await.unwrap().unwrap().id, venue.id);
+    }
+}
@@ -1,6 +1,22 @@
 pub mod ports;
+#[cfg(feature = "scraping")]
 pub mod parse_use_case;
+#[cfg(feature = "scraping")]
+pub mod parse_compare_use_case;
+#[cfg(feature = "scraping")]
+pub mod debug_snapshot_use_case;
+#[cfg(feature = "scraping")]
+pub mod snapshot_bundle_use_case;
+pub mod catalog_rollback_use_case;
+pub mod moderation_use_case;
+pub mod merge_use_case;
+#[cfg(feature = "scraping")]
+pub mod doctor;
+#[cfg(feature = "scraping")]
+pub mod contract_test;
+pub mod scaffold;
 pub mod ingest_use_case;
+#[cfg(feature = "scraping")]
 pub mod normalize_use_case;
 
 // These modules are complete implementations
@@ -0,0 +1,197 @@
+use chrono::Utc;
+use serde::Serialize;
+use sms_core::domain::{ModerationHold, ProcessRecord, ProcessRun};
+use sms_core::storage::Storage;
+use std::sync::Arc;
+use uuid::Uuid;
+
+/// Name of the process runs moderation decisions are recorded under
+pub const MODERATION_RUN_NAME: &str = "moderation";
+
+/// What can be moderated
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ModeratedEntity {
+    Event,
+    Venue,
+}
+
+impl ModeratedEntity {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ModeratedEntity::Event => "event",
+            ModeratedEntity::Venue => "venue",
+        }
+    }
+}
+
+/// One moderation decision as applied and recorded
+#[derive(Debug, Clone, Serialize)]
+pub struct ModerationOutcome {
+    pub entity: ModeratedEntity,
+    pub entity_id: Uuid,
+    /// The event's title or the venue's name
+    pub name: String,
+    /// Whether the entity is now hidden from public listings
+    pub hidden: bool,
+    pub reason: Option<String>,
+    /// Process run holding the audit record, with the entity's state before the change
+    pub audit_run_id: Uuid,
+}
+
+/// Hides events and venues from public listings (a soft delete) and shows them again. Hidden
+/// entities stay in the catalog carrying a [`ModerationHold`], which the catalog keeps when a
+/// source lists them again. Every decision is written as a process run with one HIDE or SHOW
+/// record holding the reason and the entity's previous state.
+pub struct ModerationUseCase {
+    storage: Arc<dyn Storage>,
+}
+
+impl ModerationUseCase {
+    pub fn new(storage: Arc<dyn Storage>) -> Self {
+        Self { storage }
+    }
+
+    pub async fn hide(&self, entity: ModeratedEntity, id: Uuid, reason: &str) -> anyhow::Result<ModerationOutcome> {
+        let reason = reason.trim();
+        if reason.is_empty() {
+            anyhow::bail!("a reason is required to hide a {}", entity.as_str());
+        }
+        self.moderate(entity, id, Some(reason.to_string()), true).await
+    }
+
+    /// Show a hidden entity again, whether a moderator or a catalog rollback hid it.
+    /// Placeholder venues stay out of listings until their real venue is cataloged.
+    pub async fn show(&self, entity: ModeratedEntity, id: Uuid, reason: Option<&str>) -> anyhow::Result<ModerationOutcome> {
+        let reason = reason.map(str::trim).filter(|r| !r.is_empty()).map(str::to_string);
+        self.moderate(entity, id, reason, false).await
+    }
+
+    async fn moderate(
+        &self,
+        entity: ModeratedEntity,
+        id: Uuid,
+        reason: Option<String>,
+        hide: bool,
+    ) -> anyhow::Result<ModerationOutcome> {
+        let hold = hide.then(|| ModerationHold { reason: reason.clone().unwrap_or_default(), hidden_at: Utc::now() });
+        let (name, previous_state, field) = match entity {
+            ModeratedEntity::Event => {
+                let mut event = self
+                    .storage
+                    .get_event_by_id(id)
+                    .await?
+                    .ok_or_else(|| anyhow::anyhow!("no event {}", id))?;
+                let previous_state = serde_json::to_string(&event)?;
+                event.show_event = !hide;
+                event.moderation = hold;
+                self.storage.update_event(&event).await?;
+                (event.title, previous_state, "show_event")
+            }
+            ModeratedEntity::Venue => {
+                let mut venue = self
+                    .storage
+                    .get_venue_by_id(id)
+                    .await?
+                    .ok_or_else(|| anyhow::anyhow!("no venue {}", id))?;
+                let previous_state = serde_json::to_string(&venue)?;
+                venue.show_venue = !hide && !venue.provisional;
+                venue.moderation = hold;
+                self.storage.create_venue(&mut venue).await?;
+                (venue.name, previous_state, "show_venue")
+            }
+        };
+
+        let audit_run_id = self.record(entity, id, hide, reason.as_deref(), field, previous_state).await?;
+        Ok(ModerationOutcome { entity, entity_id: id, name, hidden: hide, reason, audit_run_id })
+    }
+
+    async fn record(
+        &self,
+        entity: ModeratedEntity,
+        id: Uuid,
+        hide: bool,
+        reason: Option<&str>,
+        field: &str,
+        previous_state: String,
+    ) -> anyhow::Result<Uuid> {
+        let now = Utc::now();
+        let mut run = ProcessRun { id: None, name: MODERATION_RUN_NAME.to_string(), created_at: now, finished_at: Some(now) };
+        self.storage.create_process_run(&mut run).await?;
+        let run_id = run.id.ok_or_else(|| anyhow::anyhow!("process run was stored without an id"))?;
+
+        let mut record = ProcessRecord {
+            id: None,
+            process_run_id: run_id,
+            api_name: MODERATION_RUN_NAME.to_string(),
+            raw_data_id: None,
+            change_type: if hide { "HIDE" } else { "SHOW" }.to_string(),
+            change_log: reason.unwrap_or_default().to_string(),
+            field_changed: field.to_string(),
+            event_id: (entity == ModeratedEntity::Event).then_some(id),
+            venue_id: (entity == ModeratedEntity::Venue).then_some(id),
+            artist_id: None,
+            created_at: now,
+            previous_state: Some(previous_state),
+        };
+        self.storage.create_process_record(&mut record).await?;
+        Ok(run_id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+    use sms_core::domain::{Event, Venue};
+    use sms_core::storage::InMemoryStorage;
+
+    fn event(venue_id: Uuid) -> Event {
+        Event::fixture("Spam Night", NaiveDate::from_ymd_opt(2025, 6, 1).unwrap(), venue_id)
+    }
+
+    #[tokio::test]
+    async fn hide_and_show_are_audited_and_survive_a_relisting() {
+        let storage = Arc::new(InMemoryStorage::new());
+        let mut placeholder = Venue::placeholder("The Crocodile");
+        storage.create_venue(&mut placeholder).await.unwrap();
+        let mut show = event(placeholder.id.unwrap());
+        storage.create_event(&mut show).await.unwrap();
+        let event_id = show.id.unwrap();
+        let moderation = ModerationUseCase::new(storage.clone());
+
+        assert!(moderation.hide(ModeratedEntity::Event, event_id, "  ").await.is_err());
+        let hidden = moderation.hide(ModeratedEntity::Event, event_id, "spam listing").await.unwrap();
+        assert!(hidden.hidden);
+        let stored = storage.get_event_by_id(event_id).await.unwrap().unwrap();
+        assert!(!stored.show_event);
+        assert_eq!(stored.moderation.as_ref().unwrap().reason, "spam listing");
+
+        let records = storage.get_process_records_for_run(hidden.audit_run_id).await.unwrap();
+        assert_eq!(records.len(), 1);
+        assert_eq!((records[0].change_type.as_str(), records[0].change_log.as_str()), ("HIDE", "spam listing"));
+        assert_eq!(records[0].event_id, Some(event_id));
+        let before: Event = serde_json::from_str(records[0].previous_state.as_deref().unwrap()).unwrap();
+        assert!(before.show_event);
+
+        // The source lists the event again: the catalog keeps it hidden
+        let mut relisted = event(placeholder.id.unwrap());
+        relisted.keep_moderation(&stored);
+        assert!(!relisted.show_event);
+        assert!(relisted.moderation.is_some());
+
+        let shown = moderation.show(ModeratedEntity::Event, event_id, None).await.unwrap();
+        assert!(!shown.hidden);
+        let stored = storage.get_event_by_id(event_id).await.unwrap().unwrap();
+        assert!(stored.show_event && stored.moderation.is_none());
+
+        // A placeholder venue shown again still stays out of listings
+        let venue_id = placeholder.id.unwrap();
+        moderation.hide(ModeratedEntity::Venue, venue_id, "duplicate").await.unwrap();
+        moderation.show(ModeratedEntity::Venue, venue_id, Some("mistake")).await.unwrap();
+        let venue = storage.get_venue_by_id(venue_id).await.unwrap().unwrap();
+        assert!(!venue.show_venue && venue.moderation.is_none());
+
+        assert!(moderation.hide(ModeratedEntity::Venue, Uuid::new_v4(), "gone").await.is_err());
+    }
+}
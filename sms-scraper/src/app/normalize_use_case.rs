@@ -1,8 +1,10 @@
 use anyhow::Result;
+use tracing::Instrument;
 
 use crate::app::ports::NormalizeOutputPort;
+use crate::observability::logging;
 use crate::pipeline::processing::normalize::{NormalizedRecord, NormalizationRegistry};
-use crate::pipeline::processing::parser::ParsedRecord;
+use sms_parsers::ParsedRecord;
 
 /// Use case for normalizing parsed records into canonical domain entities
 pub struct NormalizeUseCase {
@@ -22,6 +24,12 @@ impl NormalizeUseCase {
 
     /// Normalize a single parsed record
     pub async fn normalize_record(&self, record: &ParsedRecord) -> Result<Vec<NormalizedRecord>> {
+        self.normalize_and_write(record)
+            .instrument(logging::envelope_span(&record.envelope_id))
+            .await
+    }
+
+    async fn normalize_and_write(&self, record: &ParsedRecord) -> Result<Vec<NormalizedRecord>> {
         // Apply normalization logic
         let normalized_records = self.registry.normalize(record)?;
 
@@ -112,6 +120,10 @@ mod tests {
                     "name": "Sea Monster Lounge"
                 }
             }),
+            attribution: None,
+            change: None,
+            endpoint_id: None,
+            external_id: None,
         };
 
         let result = use_case.normalize_record(&parsed_record).await;
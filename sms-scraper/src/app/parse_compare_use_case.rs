@@ -0,0 +1,270 @@
+use crate::app::ports::{ParserFactory, PayloadStorePort};
+use sms_parsers::ParsedRecord;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
+
+/// A field whose value differs between the baseline and candidate version of the same record
+/// (two parses of it, or a primary and shadow normalization)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FieldDiff {
+    pub record_path: String,
+    pub field: String,
+    pub baseline: Option<Value>,
+    pub candidate: Option<Value>,
+}
+
+/// How one envelope parsed under both plans
+#[derive(Debug, Clone, Serialize)]
+pub struct EnvelopeComparison {
+    pub envelope_id: String,
+    pub baseline_records: usize,
+    pub candidate_records: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub baseline_error: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub candidate_error: Option<String>,
+    pub only_in_baseline: Vec<String>,
+    pub only_in_candidate: Vec<String>,
+    pub field_diffs: Vec<FieldDiff>,
+}
+
+impl EnvelopeComparison {
+    pub fn is_identical(&self) -> bool {
+        self.baseline_error.is_none()
+            && self.candidate_error.is_none()
+            && self.baseline_records == self.candidate_records
+            && self.only_in_baseline.is_empty()
+            && self.only_in_candidate.is_empty()
+            && self.field_diffs.is_empty()
+    }
+}
+
+/// Result of parsing a source's envelopes with two parser plan versions
+#[derive(Debug, Clone, Serialize)]
+pub struct ParseComparison {
+    pub source_id: String,
+    pub baseline_plan: String,
+    pub candidate_plan: String,
+    pub compared_at: chrono::DateTime<chrono::Utc>,
+    pub envelopes: Vec<EnvelopeComparison>,
+}
+
+impl ParseComparison {
+    pub fn baseline_records(&self) -> usize {
+        self.envelopes.iter().map(|e| e.baseline_records).sum()
+    }
+
+    pub fn candidate_records(&self) -> usize {
+        self.envelopes.iter().map(|e| e.candidate_records).sum()
+    }
+
+    pub fn field_diffs(&self) -> usize {
+        self.envelopes.iter().map(|e| e.field_diffs.len()).sum()
+    }
+
+    pub fn changed_envelopes(&self) -> usize {
+        self.envelopes.iter().filter(|e| !e.is_identical()).count()
+    }
+}
+
+/// Re-parses stored payloads with a baseline and a candidate parser plan so a parser
+/// upgrade can be checked against real envelopes before the registry points at it.
+pub struct ParseCompareUseCase<S: PayloadStorePort + ?Sized, F: ParserFactory + ?Sized> {
+    pub payloads: Box<S>,
+    pub parsers: Box<F>,
+}
+
+impl<S: PayloadStorePort + ?Sized, F: ParserFactory + ?Sized> ParseCompareUseCase<S, F> {
+    pub fn new(payloads: Box<S>, parsers: Box<F>) -> Self {
+        Self { payloads, parsers }
+    }
+
+    /// Compare `envelopes` as (envelope_id, payload_ref) pairs under both plans
+    pub async fn compare(
+        &self,
+        source_id: &str,
+        envelopes: &[(String, String)],
+        baseline_plan: &str,
+        candidate_plan: &str,
+    ) -> Result<ParseComparison, String> {
+        let baseline = self
+            .parsers
+            .for_plan(baseline_plan)
+            .ok_or_else(|| format!("no_parser_for_plan:{}", baseline_plan))?;
+        let candidate = self
+            .parsers
+            .for_plan(candidate_plan)
+            .ok_or_else(|| format!("no_parser_for_plan:{}", candidate_plan))?;
+
+        let mut compared = Vec::with_capacity(envelopes.len());
+        for (envelope_id, payload_ref) in envelopes {
+            let bytes = match self.payloads.get(payload_ref).await {
+                Ok(bytes) => bytes,
+                Err(e) => {
+                    tracing::warn!("parse compare: payload unavailable envelope_id={} err={}", envelope_id, e);
+                    let error = format!("payload_unavailable:{}", e);
+                    compared.push(compare_records(envelope_id, Err(error.clone()), Err(error)));
+                    continue;
+                }
+            };
+            let base = baseline.parse(source_id, envelope_id, payload_ref, &bytes).await;
+            let cand = candidate.parse(source_id, envelope_id, payload_ref, &bytes).await;
+            compared.push(compare_records(envelope_id, base, cand));
+        }
+
+        Ok(ParseComparison {
+            source_id: source_id.to_string(),
+            baseline_plan: baseline_plan.to_string(),
+            candidate_plan: candidate_plan.to_string(),
+            compared_at: chrono::Utc::now(),
+            envelopes: compared,
+        })
+    }
+}
+
+/// Diff two parses of one envelope, pairing records by `record_path`
+pub fn compare_records(
+    envelope_id: &str,
+    baseline: Result<Vec<String>, String>,
+    candidate: Result<Vec<String>, String>,
+) -> EnvelopeComparison {
+    let (baseline, baseline_error) = split_parse(baseline);
+    let (candidate, candidate_error) = split_parse(candidate);
+
+    let mut comparison = EnvelopeComparison {
+        envelope_id: envelope_id.to_string(),
+        baseline_records: baseline.len(),
+        candidate_records: candidate.len(),
+        baseline_error,
+        candidate_error,
+        only_in_baseline: Vec::new(),
+        only_in_candidate: Vec::new(),
+        field_diffs: Vec::new(),
+    };
+
+    for (path, base) in &baseline {
+        let Some(cand) = candidate.get(path) else {
+            comparison.only_in_baseline.push(path.clone());
+            continue;
+        };
+        comparison.field_diffs.extend(diff_fields(path, base, cand));
+    }
+    comparison.only_in_candidate = candidate.keys().filter(|p| !baseline.contains_key(*p)).cloned().collect();
+    comparison
+}
+
+/// Leaf fields that differ between two versions of the same record
+pub fn diff_fields(record_path: &str, baseline: &Value, candidate: &Value) -> Vec<FieldDiff> {
+    let (base_fields, cand_fields) = (flatten(baseline), flatten(candidate));
+    let fields: std::collections::BTreeSet<&String> = base_fields.keys().chain(cand_fields.keys()).collect();
+    fields
+        .into_iter()
+        .filter_map(|field| {
+            let (b, c) = (base_fields.get(field), cand_fields.get(field));
+            (b != c).then(|| FieldDiff {
+                record_path: record_path.to_string(),
+                field: field.clone(),
+                baseline: b.cloned(),
+                candidate: c.cloned(),
+            })
+        })
+        .collect()
+}
+
+/// Parsed records keyed by record_path; repeated paths get a `#n` suffix so none are dropped
+fn split_parse(result: Result<Vec<String>, String>) -> (BTreeMap<String, Value>, Option<String>) {
+    let lines = match result {
+        Ok(lines) => lines,
+        Err(e) => return (BTreeMap::new(), Some(e)),
+    };
+    let mut records = BTreeMap::new();
+    for line in lines {
+        let Ok(record) = serde_json::from_str::<ParsedRecord>(&line) else {
+            continue;
+        };
+        let mut key = record.record_path.clone();
+        let mut n = 1;
+        while records.contains_key(&key) {
+            n += 1;
+            key = format!("{}#{}", record.record_path, n);
+        }
+        records.insert(key, record.record);
+    }
+    (records, None)
+}
+
+/// Leaf values of a record keyed by dotted path, e.g. `title` or `artists[0].name`
+fn flatten(value: &Value) -> BTreeMap<String, Value> {
+    fn walk(prefix: &str, value: &Value, out: &mut BTreeMap<String, Value>) {
+        match value {
+            Value::Object(map) if !map.is_empty() => {
+                for (k, v) in map {
+                    let path = if prefix.is_empty() { k.clone() } else { format!("{}.{}", prefix, k) };
+                    walk(&path, v, out);
+                }
+            }
+            Value::Array(items) if !items.is_empty() => {
+                for (i, v) in items.iter().enumerate() {
+                    walk(&format!("{}[{}]", prefix, i), v, out);
+                }
+            }
+            leaf => {
+                out.insert(prefix.to_string(), leaf.clone());
+            }
+        }
+    }
+    let mut out = BTreeMap::new();
+    if value.as_object().is_some_and(|m| m.is_empty()) {
+        return out;
+    }
+    walk("", value, &mut out);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn line(path: &str, record: Value) -> String {
+        json!({
+            "source_id": "neumos",
+            "envelope_id": "env-1",
+            "payload_ref": "cas:sha256:abcd",
+            "record_path": path,
+            "record": record,
+        })
+        .to_string()
+    }
+
+    #[test]
+    fn reports_count_and_field_level_differences() {
+        let baseline = vec![
+            line("$.events[0]", json!({ "title": "Show", "artists": ["A"], "price": null })),
+            line("$.events[1]", json!({ "title": "Gone" })),
+        ];
+        let candidate = vec![
+            line("$.events[0]", json!({ "title": "Show", "artists": ["A", "B"], "price": "$15" })),
+            line("$.events[2]", json!({ "title": "New" })),
+            line("$.events[2]", json!({ "title": "New again" })),
+        ];
+
+        let diff = compare_records("env-1", Ok(baseline), Ok(candidate));
+        assert_eq!((diff.baseline_records, diff.candidate_records), (2, 3));
+        assert_eq!(diff.only_in_baseline, vec!["$.events[1]"]);
+        assert_eq!(diff.only_in_candidate, vec!["$.events[2]", "$.events[2]#2"]);
+        let fields: Vec<_> = diff.field_diffs.iter().map(|d| d.field.as_str()).collect();
+        assert_eq!(fields, vec!["artists[1]", "price"]);
+        assert_eq!(diff.field_diffs[0].baseline, None);
+        assert_eq!(diff.field_diffs[1].candidate, Some(json!("$15")));
+        assert!(!diff.is_identical());
+
+        let same = compare_records("env-1", Ok(vec![line("$", json!({ "a": 1 }))]), Ok(vec![line("$", json!({ "a": 1 }))]));
+        assert!(same.is_identical());
+
+        let failed = compare_records("env-1", Ok(vec![line("$", json!({}))]), Err("boom".into()));
+        assert_eq!(failed.candidate_error.as_deref(), Some("boom"));
+        assert_eq!(failed.only_in_baseline, vec!["$"]);
+    }
+}
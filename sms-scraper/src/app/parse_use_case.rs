@@ -1,25 +1,521 @@
-use crate::app::ports::{ParserFactory, PayloadStorePort, RegistryPort};
+use crate::app::ports::{DeadLetterEntry, DeadLetterPort, LlmParserPort, ParserFactory, PayloadReader, PayloadStorePort, RegistryPort};
+use crate::infra::llm_parser::LLM_FALLBACK_FORMAT;
+use crate::observability::logging;
+use crate::pipeline::ingestion::{delta, text_extract};
+use sms_parsers::ParsedRecord;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll};
+use tokio::io::{AsyncRead, ReadBuf};
+use tracing::Instrument;
+
+const DEFAULT_RUN_BUDGET_USD: f64 = 1.0;
+
+/// Spend cap for one run's LLM calls; a call goes ahead only if its estimate still fits
+pub struct LlmBudget {
+    max_usd: f64,
+    spent_usd: Mutex<f64>,
+}
+
+impl LlmBudget {
+    pub fn new(max_usd: f64) -> Self {
+        Self { max_usd, spent_usd: Mutex::new(0.0) }
+    }
+
+    /// `SMS_LLM_RUN_BUDGET_USD`, default $1
+    pub fn from_env() -> Self {
+        Self::new(
+            std::env::var("SMS_LLM_RUN_BUDGET_USD")
+                .ok()
+                .and_then(|b| b.parse().ok())
+                .unwrap_or(DEFAULT_RUN_BUDGET_USD),
+        )
+    }
+
+    /// Set `estimate_usd` aside if it still fits. Checking and reserving under one lock keeps
+    /// concurrent calls from each seeing room for themselves and overspending together.
+    pub fn reserve(&self, estimate_usd: f64) -> bool {
+        let mut spent = self.spent_usd.lock().unwrap_or_else(|p| p.into_inner());
+        if *spent + estimate_usd > self.max_usd {
+            return false;
+        }
+        *spent += estimate_usd;
+        true
+    }
+
+    /// Replace a call's reservation with what the call actually cost
+    pub fn settle(&self, reserved_usd: f64, cost_usd: f64) {
+        *self.spent_usd.lock().unwrap_or_else(|p| p.into_inner()) += cost_usd - reserved_usd;
+    }
+
+    pub fn spent_usd(&self) -> f64 {
+        *self.spent_usd.lock().unwrap_or_else(|p| p.into_inner())
+    }
+}
+
+/// LLM parser tried when the deterministic parser finds no records
+pub struct LlmFallback {
+    pub parser: Box<dyn LlmParserPort>,
+    pub budget: LlmBudget,
+}
+
+impl LlmFallback {
+    /// The events the LLM reads off the text of `bytes`, each tagged with
+    /// [`LLM_FALLBACK_FORMAT`] so it's normalized by its shape rather than its source's.
+    /// `None` when the page has no text, the call wouldn't fit the budget, or it failed or
+    /// found nothing.
+    pub async fn extract(&self, source_id: &str, envelope_id: &str, payload_ref: &str, bytes: &[u8]) -> Option<Vec<serde_json::Value>> {
+        use crate::observability::metrics::parser::llm_fallback;
+        let text = text_extract::extract(payload_ref, &String::from_utf8_lossy(bytes), text_extract::DEFAULT_MAX_CHARS).text;
+        if text.is_empty() {
+            return None;
+        }
+        let estimate = self.parser.estimate_cost_usd(&text);
+        if !self.budget.reserve(estimate) {
+            tracing::warn!(
+                "parser: llm fallback skipped for envelope_id={}: ${:.4} would exceed the run budget (${:.4} spent)",
+                envelope_id, estimate, self.budget.spent_usd()
+            );
+            llm_fallback(source_id, "over_budget", 0.0);
+            return None;
+        }
+        match self.parser.extract_events(source_id, &text).await {
+            Ok(output) => {
+                self.budget.settle(estimate, output.cost_usd);
+                let outcome = if output.events.is_empty() { "empty" } else { "parsed" };
+                llm_fallback(source_id, outcome, output.cost_usd);
+                tracing::info!(
+                    "parser: llm fallback for envelope_id={} returned {} events ({} rejected by schema, ${:.4})",
+                    envelope_id, output.events.len(), output.rejected, output.cost_usd
+                );
+                if output.events.is_empty() {
+                    return None;
+                }
+                let mut events = output.events;
+                for event in &mut events {
+                    if let Some(fields) = event.as_object_mut() {
+                        fields.insert("format".to_string(), LLM_FALLBACK_FORMAT.into());
+                    }
+                }
+                Some(events)
+            }
+            Err(e) => {
+                // The request may have been billed; the estimate stays charged
+                llm_fallback(source_id, "failed", estimate);
+                tracing::warn!("parser: llm fallback failed for envelope_id={}: {}", envelope_id, e);
+                None
+            }
+        }
+    }
+}
+
+/// A payload reader that keeps a copy of every byte the parser pulls through it
+struct Captured {
+    inner: PayloadReader,
+    seen: Arc<Mutex<Vec<u8>>>,
+}
+
+impl AsyncRead for Captured {
+    fn poll_read(mut self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &mut ReadBuf<'_>) -> Poll<std::io::Result<()>> {
+        let before = buf.filled().len();
+        let polled = Pin::new(&mut self.inner).poll_read(cx, buf);
+        if let Poll::Ready(Ok(())) = polled {
+            self.seen.lock().unwrap_or_else(|e| e.into_inner()).extend_from_slice(&buf.filled()[before..]);
+        }
+        polled
+    }
+}
 
 pub struct ParseUseCase<R: RegistryPort + ?Sized, S: PayloadStorePort + ?Sized, F: ParserFactory + ?Sized> {
     pub registry: Box<R>,
     pub payloads: Box<S>,
     pub parsers: Box<F>,
+    pub dead_letters: Option<Box<dyn DeadLetterPort>>,
+    pub llm_fallback: Option<LlmFallback>,
 }
 
 impl<R: RegistryPort + ?Sized, S: PayloadStorePort + ?Sized, F: ParserFactory + ?Sized> ParseUseCase<R, S, F> {
     pub fn new(registry: Box<R>, payloads: Box<S>, parsers: Box<F>) -> Self {
-        Self { registry, payloads, parsers }
+        Self { registry, payloads, parsers, dead_letters: None, llm_fallback: None }
+    }
+
+    /// Send envelopes whose parser fails to a dead-letter store instead of only counting the error
+    pub fn with_dead_letters(mut self, dead_letters: Box<dyn DeadLetterPort>) -> Self {
+        self.dead_letters = Some(dead_letters);
+        self
+    }
+
+    /// When the parser yields no records, ask `parser` to read events from the page's text,
+    /// spending at most what `budget` allows across the run
+    pub fn with_llm_fallback(mut self, parser: Box<dyn LlmParserPort>, budget: LlmBudget) -> Self {
+        self.llm_fallback = Some(LlmFallback { parser, budget });
+        self
     }
 
     // Given a single ingest log item (source_id, envelope_id, payload_ref), resolve and parse.
     pub async fn parse_one(&self, source_id: &str, envelope_id: &str, payload_ref: &str) -> Result<Vec<String>, String> {
+        self.parse_endpoint(source_id, envelope_id, None, payload_ref).await
+    }
+
+    /// Like `parse_one`, for an envelope fetched from one endpoint of a multi-endpoint source:
+    /// every record is tagged with `endpoint_id` so normalization can tell the endpoints apart
+    pub async fn parse_endpoint(
+        &self,
+        source_id: &str,
+        envelope_id: &str,
+        endpoint_id: Option<&str>,
+        payload_ref: &str,
+    ) -> Result<Vec<String>, String> {
+        let lines = self
+            .parse_envelope(source_id, envelope_id, payload_ref)
+            .instrument(logging::envelope_span(envelope_id))
+            .await?;
+        match endpoint_id {
+            Some(endpoint_id) => tag_endpoint(lines, endpoint_id),
+            None => Ok(lines),
+        }
+    }
+
+    async fn parse_envelope(&self, source_id: &str, envelope_id: &str, payload_ref: &str) -> Result<Vec<String>, String> {
         let plan = self.registry.load_parse_plan(source_id).await?;
-        let bytes = self.payloads.get(payload_ref).await?;
-        let parser = self
-            .parsers
-            .for_plan(&plan)
-            .ok_or_else(|| format!("no_parser_for_plan:{}", plan))?;
-        parser.parse(source_id, envelope_id, payload_ref, &bytes).await
+        let dates = match self.registry.load_date_hints(source_id).await {
+            Ok(dates) => dates.unwrap_or_default(),
+            Err(e) => {
+                tracing::warn!("parser: no date hints for source_id={} err={}", source_id, e);
+                sms_parsers::DateHints::default()
+            }
+        };
+        let seen = self.llm_fallback.as_ref().map(|_| Arc::<Mutex<Vec<u8>>>::default());
+        let result = match self.parsers.for_source(&plan, &dates) {
+            Some(parser) => {
+                let reader = self.payloads.open(payload_ref).await?;
+                // Keep what streams past when the LLM may need the page text afterwards
+                let reader = match &seen {
+                    Some(seen) => Box::new(Captured { inner: reader, seen: seen.clone() }),
+                    None => reader,
+                };
+                parser.parse_stream(source_id, envelope_id, payload_ref, reader).await
+            }
+            None => Err(format!("no_parser_for_plan:{}", plan)),
+        };
+        match result {
+            Ok(lines) => {
+                let bytes = seen.map(|seen| std::mem::take(&mut *seen.lock().unwrap_or_else(|e| e.into_inner())));
+                let lines = self.llm_fallback_if_empty(source_id, envelope_id, payload_ref, bytes, lines).await;
+                self.stamp_attribution(source_id, lines).await
+            }
+            Err(error) => {
+                self.dead_letter(source_id, envelope_id, payload_ref, &plan, &error).await;
+                Err(error)
+            }
+        }
     }
+
+    /// Replace an empty parse (no lines, or only `html_len` placeholders) with the LLM's events
+    async fn llm_fallback_if_empty(
+        &self,
+        source_id: &str,
+        envelope_id: &str,
+        payload_ref: &str,
+        bytes: Option<Vec<u8>>,
+        lines: Vec<String>,
+    ) -> Vec<String> {
+        let (Some(fallback), Some(bytes)) = (&self.llm_fallback, bytes) else { return lines };
+        let empty = lines.iter().all(|line| {
+            serde_json::from_str::<ParsedRecord>(line).map(|r| delta::is_fallback(&r.record)).unwrap_or(false)
+        });
+        if !empty {
+            return lines;
+        }
+        let Some(events) = fallback.extract(source_id, envelope_id, payload_ref, &bytes).await else {
+            return lines;
+        };
+        events
+            .into_iter()
+            .enumerate()
+            .filter_map(|(i, record)| {
+                serde_json::to_string(&ParsedRecord {
+                    source_id: source_id.to_string(),
+                    envelope_id: envelope_id.to_string(),
+                    payload_ref: payload_ref.to_string(),
+                    record_path: format!("$.llm_fallback[{}]", i),
+                    record,
+                    attribution: None,
+                    change: None,
+                    endpoint_id: None,
+                    external_id: None,
+                })
+                .ok()
+            })
+            .collect()
+    }
+
+    /// Carry the source's license/attribution on every parsed record so it survives to the catalog
+    async fn stamp_attribution(&self, source_id: &str, lines: Vec<String>) -> Result<Vec<String>, String> {
+        let attribution = match self.registry.load_attribution(source_id).await {
+            Ok(Some(attribution)) => attribution,
+            Ok(None) => return Ok(lines),
+            Err(e) => {
+                tracing::warn!("parser: no attribution for source_id={} err={}", source_id, e);
+                return Ok(lines);
+            }
+        };
+        lines
+            .into_iter()
+            .map(|line| {
+                let mut record: ParsedRecord = serde_json::from_str(&line).map_err(|e| e.to_string())?;
+                record.attribution = Some(attribution.clone());
+                serde_json::to_string(&record).map_err(|e| e.to_string())
+            })
+            .collect()
+    }
+
+    async fn dead_letter(&self, source_id: &str, envelope_id: &str, payload_ref: &str, plan: &str, error: &str) {
+        let Some(dlq) = &self.dead_letters else { return };
+        let entry = DeadLetterEntry {
+            envelope_id: envelope_id.to_string(),
+            source_id: source_id.to_string(),
+            payload_ref: payload_ref.to_string(),
+            parse_plan: plan.to_string(),
+            error: error.to_string(),
+            failed_at: chrono::Utc::now(),
+            attempts: 1,
+        };
+        match dlq.record(entry).await {
+            Ok(()) => crate::observability::metrics::parser::dead_lettered(),
+            Err(e) => tracing::warn!("parser: failed to dead-letter envelope_id={} err={}", envelope_id, e),
+        }
+    }
+}
+
+fn tag_endpoint(lines: Vec<String>, endpoint_id: &str) -> Result<Vec<String>, String> {
+    lines
+        .into_iter()
+        .map(|line| {
+            let mut record: ParsedRecord = serde_json::from_str(&line).map_err(|e| e.to_string())?;
+            record.endpoint_id = Some(endpoint_id.to_string());
+            serde_json::to_string(&record).map_err(|e| e.to_string())
+        })
+        .collect()
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::ports::ParserPort;
+    use crate::infra::dead_letter_store::FileDeadLetterStore;
+    use async_trait::async_trait;
+
+    struct FixedPlan;
+    #[async_trait]
+    impl RegistryPort for FixedPlan {
+        async fn load_parse_plan(&self, _source_id: &str) -> Result<String, String> {
+            Ok("parse_plan:test_v1".to_string())
+        }
+    }
+
+    struct InlinePayloads;
+    #[async_trait]
+    impl PayloadStorePort for InlinePayloads {
+        async fn get(&self, _payload_ref: &str) -> Result<Vec<u8>, String> {
+            Ok(b"{}".to_vec())
+        }
+    }
+
+    struct FailingParser;
+    #[async_trait]
+    impl ParserPort for FailingParser {
+        async fn parse(&self, _: &str, _: &str, _: &str, _: &[u8]) -> Result<Vec<String>, String> {
+            Err("unexpected_shape".to_string())
+        }
+    }
+
+    struct FailingFactory;
+    impl ParserFactory for FailingFactory {
+        fn for_plan(&self, _plan: &str) -> Option<Box<dyn ParserPort>> {
+            Some(Box::new(FailingParser))
+        }
+    }
+
+    #[tokio::test]
+    async fn parser_failure_is_dead_lettered() {
+        let tmp = tempfile::tempdir().unwrap();
+        let uc = ParseUseCase::new(Box::new(FixedPlan), Box::new(InlinePayloads), Box::new(FailingFactory))
+            .with_dead_letters(Box::new(FileDeadLetterStore::new(tmp.path())));
+
+        let err = uc.parse_one("kexp", "env-1", "cas:sha256:abcd").await.unwrap_err();
+        assert_eq!(err, "unexpected_shape");
+
+        let entries = FileDeadLetterStore::new(tmp.path()).list().await.unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].envelope_id, "env-1");
+        assert_eq!(entries[0].payload_ref, "cas:sha256:abcd");
+        assert_eq!(entries[0].parse_plan, "parse_plan:test_v1");
+        assert_eq!(entries[0].error, "unexpected_shape");
+    }
+
+    struct LicensedPlan;
+    #[async_trait]
+    impl RegistryPort for LicensedPlan {
+        async fn load_parse_plan(&self, _source_id: &str) -> Result<String, String> {
+            Ok("parse_plan:test_v1".to_string())
+        }
+
+        async fn load_attribution(&self, source_id: &str) -> Result<Option<sms_core::domain::Attribution>, String> {
+            Ok(Some(sms_core::domain::Attribution {
+                source_id: source_id.to_string(),
+                license_id: "cc-by-4.0".to_string(),
+                text: Some("Listings courtesy of KEXP".to_string()),
+            }))
+        }
+    }
+
+    struct OneRecordParser;
+    #[async_trait]
+    impl ParserPort for OneRecordParser {
+        async fn parse(&self, source_id: &str, envelope_id: &str, payload_ref: &str, _: &[u8]) -> Result<Vec<String>, String> {
+            let record = ParsedRecord {
+                source_id: source_id.to_string(),
+                envelope_id: envelope_id.to_string(),
+                payload_ref: payload_ref.to_string(),
+                record_path: "$[0]".to_string(),
+                record: serde_json::json!({"title": "Show"}),
+                attribution: None,
+                change: None,
+                endpoint_id: None,
+                external_id: None,
+            };
+            Ok(vec![serde_json::to_string(&record).unwrap()])
+        }
+    }
+
+    struct OneRecordFactory;
+    impl ParserFactory for OneRecordFactory {
+        fn for_plan(&self, _plan: &str) -> Option<Box<dyn ParserPort>> {
+            Some(Box::new(OneRecordParser))
+        }
+    }
+
+    #[tokio::test]
+    async fn parsed_records_carry_source_attribution() {
+        let uc = ParseUseCase::new(Box::new(LicensedPlan), Box::new(InlinePayloads), Box::new(OneRecordFactory));
+
+        let lines = uc.parse_one("kexp", "env-1", "cas:sha256:abcd").await.unwrap();
+        let record: ParsedRecord = serde_json::from_str(&lines[0]).unwrap();
+        let attribution = record.attribution.unwrap();
+        assert_eq!(attribution.source_id, "kexp");
+        assert_eq!(attribution.license_id, "cc-by-4.0");
+        assert_eq!(record.endpoint_id, None);
+    }
+
+    #[tokio::test]
+    async fn records_from_an_endpoint_are_tagged_with_its_id() {
+        let uc = ParseUseCase::new(Box::new(LicensedPlan), Box::new(InlinePayloads), Box::new(OneRecordFactory));
+
+        let lines = uc.parse_endpoint("kexp", "env-1", Some("main_stage"), "cas:sha256:abcd").await.unwrap();
+        let record: ParsedRecord = serde_json::from_str(&lines[0]).unwrap();
+        assert_eq!(record.endpoint_id.as_deref(), Some("main_stage"));
+        assert!(record.attribution.is_some());
+    }
+
+    /// Counts reads, so a fallback that fetches the payload a second time shows
+    #[derive(Default)]
+    struct HtmlPayloads {
+        reads: Arc<std::sync::atomic::AtomicUsize>,
+    }
+    #[async_trait]
+    impl PayloadStorePort for HtmlPayloads {
+        async fn get(&self, _payload_ref: &str) -> Result<Vec<u8>, String> {
+            self.reads.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            Ok(b"<html><body><h2>The Band</h2><p>Fri, May 2, 8pm</p></body></html>".to_vec())
+        }
+    }
+
+    struct PlaceholderParser;
+    #[async_trait]
+    impl ParserPort for PlaceholderParser {
+        async fn parse(&self, source_id: &str, envelope_id: &str, payload_ref: &str, bytes: &[u8]) -> Result<Vec<String>, String> {
+            let record = ParsedRecord {
+                source_id: source_id.to_string(),
+                envelope_id: envelope_id.to_string(),
+                payload_ref: payload_ref.to_string(),
+                record_path: "$".to_string(),
+                record: serde_json::json!({"html_len": bytes.len()}),
+                attribution: None,
+                change: None,
+                endpoint_id: None,
+                external_id: None,
+            };
+            Ok(vec![serde_json::to_string(&record).unwrap()])
+        }
+    }
+
+    struct PlaceholderFactory;
+    impl ParserFactory for PlaceholderFactory {
+        fn for_plan(&self, _plan: &str) -> Option<Box<dyn ParserPort>> {
+            Some(Box::new(PlaceholderParser))
+        }
+    }
+
+    struct FixedLlm;
+    #[async_trait]
+    impl LlmParserPort for FixedLlm {
+        fn estimate_cost_usd(&self, _text: &str) -> f64 {
+            0.03
+        }
+
+        async fn extract_events(&self, _source_id: &str, text: &str) -> Result<crate::app::ports::LlmParseOutput, String> {
+            assert!(text.contains("## The Band"));
+            Ok(crate::app::ports::LlmParseOutput {
+                events: vec![serde_json::json!({"title": "The Band", "event_day": "2025-05-02"})],
+                rejected: 0,
+                cost_usd: 0.02,
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn empty_parses_fall_back_to_the_llm_within_budget() {
+        let payloads = HtmlPayloads::default();
+        let reads = payloads.reads.clone();
+        let uc = ParseUseCase::new(Box::new(FixedPlan), Box::new(payloads), Box::new(PlaceholderFactory))
+            .with_llm_fallback(Box::new(FixedLlm), LlmBudget::new(0.04));
+
+        let lines = uc.parse_one("neumos", "env-1", "cas:sha256:abcd").await.unwrap();
+        let record: ParsedRecord = serde_json::from_str(&lines[0]).unwrap();
+        assert_eq!(record.record_path, "$.llm_fallback[0]");
+        assert_eq!(reads.load(std::sync::atomic::Ordering::SeqCst), 1, "the fallback reuses the parsed bytes");
+        assert_eq!(record.record["title"], "The Band");
+        assert_eq!(record.record["format"], LLM_FALLBACK_FORMAT);
+
+        // $0.02 spent; another $0.03 estimate would exceed the $0.04 budget
+        let lines = uc.parse_one("neumos", "env-2", "cas:sha256:abcd").await.unwrap();
+        let record: ParsedRecord = serde_json::from_str(&lines[0]).unwrap();
+        assert!(record.record.get("html_len").is_some());
+    }
+
+    #[test]
+    fn budget_refuses_calls_that_would_overspend() {
+        let budget = LlmBudget::new(0.05);
+        assert!(budget.reserve(0.04));
+        assert!(!budget.reserve(0.02));
+        budget.settle(0.04, 0.03);
+        assert!(budget.reserve(0.02));
+        assert!((budget.spent_usd() - 0.05).abs() < 1e-9);
+    }
+
+    #[test]
+    fn concurrent_reservations_never_overspend() {
+        let budget = std::sync::Arc::new(LlmBudget::new(1.0));
+        let granted: usize = (0..20)
+            .map(|_| {
+                let budget = budget.clone();
+                std::thread::spawn(move || budget.reserve(0.25))
+            })
+            .collect::<Vec<_>>()
+            .into_iter()
+            .map(|handle| handle.join().unwrap() as usize)
+            .sum();
+        assert_eq!(granted, 4);
+    }
+}
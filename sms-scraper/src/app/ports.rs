@@ -1,22 +1,138 @@
 use async_trait::async_trait;
 
+/// A payload being read from storage
+pub type PayloadReader = Box<dyn tokio::io::AsyncRead + Send + Unpin>;
+
 #[async_trait]
 pub trait PayloadStorePort: Send + Sync {
     async fn get(&self, payload_ref: &str) -> Result<Vec<u8>, String>;
+
+    /// Read a payload incrementally; stores that can stream override this so multi-MB
+    /// payloads aren't buffered whole
+    async fn open(&self, payload_ref: &str) -> Result<PayloadReader, String> {
+        Ok(Box::new(std::io::Cursor::new(self.get(payload_ref).await?)))
+    }
 }
 
 #[async_trait]
 pub trait RegistryPort: Send + Sync {
     async fn load_parse_plan(&self, source_id: &str) -> Result<String, String>;
+
+    /// License/attribution to carry on records from this source, if the registry declares one
+    async fn load_attribution(&self, _source_id: &str) -> Result<Option<sms_core::domain::Attribution>, String> {
+        Ok(None)
+    }
+
+    /// Time zone and locale this source's listings write dates in, if the registry declares them
+    #[cfg(feature = "scraping")]
+    async fn load_date_hints(&self, _source_id: &str) -> Result<Option<sms_parsers::DateHints>, String> {
+        Ok(None)
+    }
 }
 
 #[async_trait]
 pub trait ParserPort: Send + Sync {
     async fn parse(&self, source_id: &str, envelope_id: &str, payload_ref: &str, bytes: &[u8]) -> Result<Vec<String>, String>;
+
+    /// Parse a payload as it's read; by default it's buffered and handed to `parse`
+    async fn parse_stream(
+        &self,
+        source_id: &str,
+        envelope_id: &str,
+        payload_ref: &str,
+        mut reader: PayloadReader,
+    ) -> Result<Vec<String>, String> {
+        use tokio::io::AsyncReadExt;
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await.map_err(|e| e.to_string())?;
+        self.parse(source_id, envelope_id, payload_ref, &bytes).await
+    }
 }
 
 pub trait ParserFactory: Send + Sync {
     fn for_plan(&self, plan: &str) -> Option<Box<dyn ParserPort>>;
+
+    /// Like `for_plan`, for a parser that reads year-less dates with `dates`; parsers that
+    /// don't need them are built as `for_plan` builds them
+    #[cfg(feature = "scraping")]
+    fn for_source(&self, plan: &str, dates: &sms_parsers::DateHints) -> Option<Box<dyn ParserPort>> {
+        let _ = dates;
+        self.for_plan(plan)
+    }
+}
+
+/// Events a language model read out of a page, with what the call cost
+#[derive(Clone, Debug, Default)]
+pub struct LlmParseOutput {
+    /// Event objects that passed schema validation
+    pub events: Vec<serde_json::Value>,
+    /// Objects the model returned that failed validation
+    pub rejected: usize,
+    pub cost_usd: f64,
+}
+
+/// Experimental fallback that extracts events from a page's sanitized text
+#[async_trait]
+pub trait LlmParserPort: Send + Sync {
+    /// Upper bound on what parsing `text` may cost, checked against the run budget first
+    fn estimate_cost_usd(&self, text: &str) -> f64;
+    async fn extract_events(&self, source_id: &str, text: &str) -> Result<LlmParseOutput, String>;
+}
+
+/// An envelope whose parse failed, kept so it can be re-parsed after a fix
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct DeadLetterEntry {
+    pub envelope_id: String,
+    pub source_id: String,
+    pub payload_ref: String,
+    pub parse_plan: String,
+    pub error: String,
+    pub failed_at: chrono::DateTime<chrono::Utc>,
+    pub attempts: u32,
+}
+
+impl DeadLetterEntry {
+    /// An entry for raw data whose parse failed; raw data a crawler stored without the
+    /// gateway has no envelope to re-parse and gets none
+    pub fn for_raw_data(source_id: &str, raw_data: &sms_core::domain::RawData, parse_plan: &str, error: &str) -> Option<Self> {
+        let origin = raw_data.origin.as_ref()?;
+        Some(Self {
+            envelope_id: origin.envelope_id.clone(),
+            source_id: source_id.to_string(),
+            payload_ref: origin.payload_ref.clone(),
+            parse_plan: parse_plan.to_string(),
+            error: error.to_string(),
+            failed_at: chrono::Utc::now(),
+            attempts: 1,
+        })
+    }
+
+    /// The failed envelope as a whole; parse failures have no record of their own
+    pub fn key(&self) -> sms_core::common::record_key::RecordKey {
+        sms_core::common::record_key::RecordKey::envelope(&self.source_id, &self.envelope_id)
+    }
+}
+
+#[async_trait]
+pub trait DeadLetterPort: Send + Sync {
+    /// Record a failure; an existing entry for the same envelope is replaced and its attempts bumped.
+    async fn record(&self, entry: DeadLetterEntry) -> Result<(), String>;
+    async fn list(&self) -> Result<Vec<DeadLetterEntry>, String>;
+    /// Remove the entry for an envelope, returning whether one existed.
+    async fn remove(&self, envelope_id: &str) -> Result<bool, String>;
+}
+
+/// Operator-facing alert, e.g. a source whose parser appears to have silently broken
+#[derive(Clone, Debug, PartialEq, serde::Serialize)]
+pub struct Notification {
+    pub kind: String,
+    pub source_id: String,
+    pub message: String,
+}
+
+#[async_trait]
+pub trait NotificationPort: Send + Sync {
+    async fn notify(&self, notification: &Notification) -> Result<(), String>;
 }
 
 // Ingest-side ports
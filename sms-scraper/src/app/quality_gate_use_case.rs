@@ -1,15 +1,23 @@
 use anyhow::Result;
+use std::sync::Arc;
+use tracing::Instrument;
 use crate::app::ports::QualityGateOutputPort;
+use crate::observability::logging;
+use sms_core::pipeline_api::plugins;
 use crate::pipeline::processing::normalize::NormalizedRecord;
 use crate::pipeline::processing::quality_gate::{
     QualityGate, QualityAssessedRecord, QualityDecision, DefaultQualityGate, MetricsQualityGate
 };
+use crate::pipeline::processing::quality_gate::outcomes::QualityOutcomeStore;
+use crate::pipeline::processing::quality_gate::shadow::ShadowQualityGate;
 
 /// Use case for assessing quality of normalized records through the Quality Gate
 pub struct QualityGateUseCase {
     quality_gate: Box<dyn QualityGate + Send + Sync>,
     accepted_output: Box<dyn QualityGateOutputPort>,
     quarantined_output: Box<dyn QualityGateOutputPort>,
+    outcomes: Option<QualityOutcomeStore>,
+    shadow: Option<Arc<ShadowQualityGate>>,
 }
 
 impl QualityGateUseCase {
@@ -22,25 +30,54 @@ impl QualityGateUseCase {
             quality_gate,
             accepted_output,
             quarantined_output,
+            outcomes: None,
+            shadow: None,
         }
     }
 
-    /// Create a use case with the default quality gate
+    /// Create a use case with the default quality gate, or the registered plugin gate if any
     pub fn with_default_quality_gate(
         accepted_output: Box<dyn QualityGateOutputPort>,
         quarantined_output: Box<dyn QualityGateOutputPort>,
     ) -> Self {
         Self {
-            quality_gate: Box::new(MetricsQualityGate::new(DefaultQualityGate::new())),
+            quality_gate: match plugins().quality_gate() {
+                Some(gate) => Box::new(MetricsQualityGate::new(gate)),
+                None => Box::new(MetricsQualityGate::new(DefaultQualityGate::new())),
+            },
             accepted_output,
             quarantined_output,
+            outcomes: None,
+            shadow: None,
         }
     }
 
+    /// Also record every decision in `store`, for quality stats and quarantine review
+    pub fn with_outcome_store(mut self, store: QualityOutcomeStore) -> Self {
+        self.outcomes = Some(store);
+        self
+    }
+
+    /// Also assess every record with `shadow`'s candidate config and count where it would
+    /// decide differently; routing still follows the active gate
+    pub fn with_shadow(mut self, shadow: Arc<ShadowQualityGate>) -> Self {
+        self.shadow = Some(shadow);
+        self
+    }
+
     /// Assess quality of a single normalized record
     pub async fn assess_record(&self, record: &NormalizedRecord) -> Result<QualityAssessedRecord> {
+        self.assess_and_route(record)
+            .instrument(logging::envelope_span(&record.provenance.envelope_id))
+            .await
+    }
+
+    async fn assess_and_route(&self, record: &NormalizedRecord) -> Result<QualityAssessedRecord> {
         // Apply quality assessment logic (metrics are handled by MetricsQualityGate wrapper)
         let assessed_record = self.quality_gate.assess(record)?;
+        if let Some(shadow) = &self.shadow {
+            shadow.compare(record, &assessed_record.quality_assessment.decision);
+        }
 
         // Route to appropriate output based on decision
         match assessed_record.quality_assessment.decision {
@@ -52,6 +89,13 @@ impl QualityGateUseCase {
             }
         }
 
+        // Stats are best-effort; a store failure must not drop the record
+        if let Some(store) = &self.outcomes {
+            if let Err(e) = store.record(&assessed_record) {
+                tracing::warn!("quality_gate: failed to record outcome: {}", e);
+            }
+        }
+
         Ok(assessed_record)
     }
 
@@ -200,13 +244,13 @@ mod tests {
         // Create a test normalized record (using a helper from quality_gate tests)
         use sms_core::domain::Event;
         use crate::pipeline::processing::normalize::{NormalizedEntity, NormalizedRecord, NormalizationMetadata, RecordProvenance};
-        use chrono::{NaiveDate, Utc};
+        use chrono::Utc;
         use uuid::Uuid;
 
         let event = Event {
             id: None,
             title: "Test Concert".to_string(),
-            event_day: NaiveDate::from_ymd_opt(2025, 8, 20).unwrap(),
+            event_day: Utc::now().date_naive() + chrono::Duration::days(7),
             start_time: None,
             event_url: None,
             description: None,
@@ -216,6 +260,15 @@ mod tests {
             show_event: true,
             finalized: false,
             created_at: Utc::now(),
+            attributions: Vec::new(),
+            tags: Vec::new(),
+            price: None,
+            doors_time: None,
+            series_id: None,
+            age_restriction: None,
+            accessibility_notes: None,
+            moderation: None,
+            external_ids: Vec::new(),
         };
 
         let normalized_record = NormalizedRecord {
@@ -226,6 +279,11 @@ mod tests {
                 payload_ref: "test_payload".to_string(),
                 record_path: "$.events[0]".to_string(),
                 normalized_at: Utc::now(),
+                attribution: None,
+                change: None,
+                record_key: None,
+                endpoint_id: None,
+                external_id: None,
             },
             normalization: NormalizationMetadata {
                 confidence: 0.8,
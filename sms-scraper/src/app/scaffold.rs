@@ -0,0 +1,296 @@
+use crate::app::ports::ParserFactory;
+use crate::pipeline::ingestion::registry::ParserPlanSpec;
+use anyhow::{bail, Context};
+use std::path::{Path, PathBuf};
+
+const NORMALIZER_TEMPLATE: &str = include_str!("../../templates/scaffold/normalizer.rs.tmpl");
+const NORMALIZERS_DIR: &str = "sms-scraper/src/pipeline/processing/normalize/normalizers";
+const NORMALIZATION_REGISTRY: &str = "sms-scraper/src/pipeline/processing/normalize/registry.rs";
+const SCAFFOLDED_VENUES: &str = "sms-scraper/src/apis/scaffolded.rs";
+
+/// What `scaffold source` needs to know about a new venue
+#[derive(Debug, Clone)]
+pub struct SourceScaffold {
+    /// snake_case source id, used for the registry file, module and normalizer key
+    pub source_id: String,
+    /// Parser plan without the `parse_plan:` prefix, e.g. `wix_calendar_v1`
+    pub parser: String,
+    /// Endpoint the gateway fetches
+    pub url: String,
+    /// Venue name; defaults to the title-cased source id
+    pub name: Option<String>,
+}
+
+impl SourceScaffold {
+    fn venue_name(&self) -> String {
+        self.name.clone().unwrap_or_else(|| {
+            self.source_id
+                .split('_')
+                .map(|w| {
+                    let mut chars = w.chars();
+                    chars.next().map(|c| c.to_uppercase().chain(chars).collect()).unwrap_or_default()
+                })
+                .collect::<Vec<String>>()
+                .join(" ")
+        })
+    }
+
+    fn type_name(&self) -> String {
+        let camel: String = self
+            .source_id
+            .split('_')
+            .flat_map(|w| {
+                let mut chars = w.chars();
+                chars.next().map(|c| c.to_ascii_uppercase()).into_iter().chain(chars)
+            })
+            .collect();
+        format!("{}Normalizer", camel)
+    }
+
+    fn is_html(&self) -> bool {
+        self.parser.contains("html")
+    }
+}
+
+/// Files written or edited by a scaffold run, relative to the workspace root
+#[derive(Debug, Default)]
+pub struct ScaffoldReport {
+    pub created: Vec<PathBuf>,
+    pub updated: Vec<PathBuf>,
+}
+
+/// Generate the registry spec, a normalizer stub, fixture placeholders and the wire-up of the
+/// normalizer and of the venue's crawler (served by the factory through its parse plan) for a
+/// new source under the workspace at `root`. Nothing is written unless every
+/// check passes, and existing files are never overwritten.
+pub fn scaffold_source(root: &Path, scaffold: &SourceScaffold, parsers: &dyn ParserFactory) -> anyhow::Result<ScaffoldReport> {
+    let id = scaffold.source_id.as_str();
+    if id.is_empty() || !id.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_') {
+        bail!("source id '{}' must be snake_case (a-z, 0-9, _)", id);
+    }
+    let plan_ref = format!("parse_plan:{}", scaffold.parser);
+    let Some(plan) = ParserPlanSpec::from_plan_ref(&plan_ref) else {
+        bail!("parser '{}' must look like <plan>_v<version>, e.g. wix_calendar_v1", scaffold.parser);
+    };
+    if parsers.for_plan(&plan_ref).is_none() {
+        bail!("no parser is registered for {}", plan_ref);
+    }
+    let url = reqwest::Url::parse(&scaffold.url).with_context(|| format!("invalid url {}", scaffold.url))?;
+    let site = url.origin().ascii_serialization();
+
+    let spec_path = PathBuf::from("registry/sources").join(format!("{}.json", id));
+    let normalizer_path = PathBuf::from(NORMALIZERS_DIR).join(format!("{}.rs", id));
+    let fixture_dir = PathBuf::from("tests/resources/sources").join(id);
+    let payload_path = fixture_dir.join(if scaffold.is_html() { "payload.html" } else { "payload.json" });
+    let expected_path = fixture_dir.join("expected_records.json");
+    let mod_path = PathBuf::from(NORMALIZERS_DIR).join("mod.rs");
+    let registry_path = PathBuf::from(NORMALIZATION_REGISTRY);
+    let venues_path = PathBuf::from(SCAFFOLDED_VENUES);
+
+    for path in [&spec_path, &normalizer_path, &payload_path, &expected_path] {
+        if root.join(path).exists() {
+            bail!("{} already exists", path.display());
+        }
+    }
+    let mod_rs = wire_module(&read(root, &mod_path)?, id, &scaffold.type_name())?;
+    let registry_rs = wire_registry(&read(root, &registry_path)?, id, &scaffold.type_name())?;
+    let name = scaffold.venue_name();
+    let venues_rs = wire_venue(&read(root, &venues_path)?, id, &name, &plan_ref)?;
+
+    let content_type = if scaffold.is_html() { "text/html" } else { "application/json" };
+    let spec = serde_json::json!({
+        "source_id": id,
+        "identity": { "name": name, "owner_team": "ingestion", "contact_email": "dataops@example.com" },
+        "enabled": true,
+        "endpoints": [{ "url": scaffold.url, "method": "GET" }],
+        "auth": { "method": "none" },
+        "rate_limits": { "requests_per_min": 6, "bytes_per_min": 20000000, "concurrency": 1 },
+        "content": { "allowed_mime_types": [content_type], "max_payload_size_bytes": 20000000 },
+        "policy": { "license_id": "terms-unknown", "robots_tos_posture": "respect", "pii_risk_class": "low", "pii_action": "allow" },
+        "change_detection": { "strategy": "snapshot" },
+        "parse_plan_ref": plan_ref,
+        "parser_plan": { "id": plan.id, "version": plan.version },
+        "pipeline": {
+            "parser_id": scaffold.parser,
+            "normalizer_id": id,
+            "content_type": content_type,
+            "parser_type": plan.id
+        }
+    });
+    let normalizer = NORMALIZER_TEMPLATE
+        .replace("{{TYPE}}", &scaffold.type_name())
+        .replace("{{NAME}}", &name)
+        .replace("{{ID}}", id)
+        .replace("{{PLAN}}", &plan_ref)
+        .replace("{{SITE}}", &site);
+    let payload = if scaffold.is_html() {
+        format!("<!-- Replace with a response captured from {} -->\n", scaffold.url)
+    } else {
+        // JSON has no comments; an empty object keeps the placeholder parseable
+        "{}\n".to_string()
+    };
+
+    let mut report = ScaffoldReport::default();
+    write_new(root, &spec_path, &format!("{}\n", serde_json::to_string_pretty(&spec)?), &mut report)?;
+    write_new(root, &normalizer_path, &normalizer, &mut report)?;
+    write_new(root, &payload_path, &payload, &mut report)?;
+    write_new(root, &expected_path, "[]\n", &mut report)?;
+    std::fs::write(root.join(&mod_path), mod_rs)?;
+    std::fs::write(root.join(&registry_path), registry_rs)?;
+    std::fs::write(root.join(&venues_path), venues_rs)?;
+    report.updated.extend([mod_path, registry_path, venues_path]);
+    Ok(report)
+}
+
+fn read(root: &Path, path: &Path) -> anyhow::Result<String> {
+    std::fs::read_to_string(root.join(path)).with_context(|| format!("reading {}", path.display()))
+}
+
+fn write_new(root: &Path, path: &Path, contents: &str, report: &mut ScaffoldReport) -> anyhow::Result<()> {
+    let full = root.join(path);
+    if let Some(parent) = full.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(&full, contents)?;
+    report.created.push(path.to_path_buf());
+    Ok(())
+}
+
+/// Declare and re-export the normalizer module after the last existing ones
+fn wire_module(mod_rs: &str, id: &str, type_name: &str) -> anyhow::Result<String> {
+    let mut lines: Vec<String> = mod_rs.lines().map(str::to_string).collect();
+    let last_mod = lines.iter().rposition(|l| l.starts_with("pub mod ")).context("normalizers/mod.rs has no modules")?;
+    lines.insert(last_mod + 1, format!("pub mod {};", id));
+    let last_use = lines.iter().rposition(|l| l.starts_with("pub use ")).context("normalizers/mod.rs has no re-exports")?;
+    lines.insert(last_use + 1, format!("pub use {}::{};", id, type_name));
+    Ok(lines.join("\n") + "\n")
+}
+
+/// Import the normalizer and register it under the source id in `NormalizationRegistry::new`
+fn wire_registry(registry_rs: &str, id: &str, type_name: &str) -> anyhow::Result<String> {
+    let import_end = registry_rs
+        .find("use super::normalizers::{")
+        .and_then(|start| registry_rs[start..].find("};").map(|end| start + end))
+        .context("registry.rs does not import super::normalizers")?;
+    let mut out = format!("{}, {}{}", &registry_rs[..import_end], type_name, &registry_rs[import_end..]);
+
+    let anchor = out.find("        let mut registry = Self {\n            normalizers,").context("registry.rs has no NormalizationRegistry constructor")?;
+    let insert_at = out[..anchor].trim_end_matches([' ', '\n']).len() + 1;
+    out.insert_str(
+        insert_at,
+        &format!(
+            "        normalizers.insert(\"{}\".to_string(),\n            Box::new(MetricsNormalizer::new({}::new())));\n",
+            id, type_name
+        ),
+    );
+    Ok(out)
+}
+
+/// Add the venue to the scaffolded venue pack, so `create_crawler`/`create_parser` serve it
+fn wire_venue(venues_rs: &str, id: &str, name: &str, plan_ref: &str) -> anyhow::Result<String> {
+    let table = venues_rs.find("pub const VENUES").context("scaffolded.rs has no VENUES table")?;
+    let end = venues_rs[table..].find("];").map(|end| table + end).context("scaffolded.rs VENUES table is not closed")?;
+    Ok(format!("{}    ({:?}, {:?}, {:?}),\n{}", &venues_rs[..end], id, name, plan_ref, &venues_rs[end..]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infra::parser_factory::DefaultParserFactory;
+    use crate::pipeline::ingestion::registry_watch::load_registry;
+
+    fn workspace() -> tempfile::TempDir {
+        let tmp = tempfile::tempdir().unwrap();
+        let crate_root = Path::new(env!("CARGO_MANIFEST_DIR"));
+        for (from, to) in [
+            ("src/pipeline/processing/normalize/normalizers/mod.rs", format!("{}/mod.rs", NORMALIZERS_DIR)),
+            ("src/pipeline/processing/normalize/registry.rs", NORMALIZATION_REGISTRY.to_string()),
+            ("src/apis/scaffolded.rs", SCAFFOLDED_VENUES.to_string()),
+        ] {
+            let dest = tmp.path().join(to);
+            std::fs::create_dir_all(dest.parent().unwrap()).unwrap();
+            std::fs::copy(crate_root.join(from), dest).unwrap();
+        }
+        tmp
+    }
+
+    fn new_venue() -> SourceScaffold {
+        SourceScaffold {
+            source_id: "new_venue".to_string(),
+            parser: "wix_calendar_v1".to_string(),
+            url: "https://newvenue.example.com/_api/getEvents?compId=1".to_string(),
+            name: None,
+        }
+    }
+
+    #[test]
+    fn scaffolds_a_valid_spec_stub_fixtures_and_wire_up() {
+        let root = workspace();
+        let report = scaffold_source(root.path(), &new_venue(), &DefaultParserFactory).unwrap();
+        assert_eq!(report.created.len(), 4);
+
+        let specs = load_registry(&root.path().join("registry/sources")).unwrap();
+        assert_eq!(specs[0].resolved_parse_plan().as_deref(), Some("parse_plan:wix_calendar_v1"));
+        assert!(root.path().join("tests/resources/sources/new_venue/payload.json").exists());
+
+        let stub = std::fs::read_to_string(root.path().join(NORMALIZERS_DIR).join("new_venue.rs")).unwrap();
+        assert!(stub.contains("impl SourceNormalizer for NewVenueNormalizer"));
+        assert!(stub.contains("\"https://newvenue.example.com\""));
+        assert!(stub.contains("name: \"New Venue\".to_string()"));
+
+        let mod_rs = std::fs::read_to_string(root.path().join(NORMALIZERS_DIR).join("mod.rs")).unwrap();
+        assert!(mod_rs.contains("pub mod new_venue;\n") && mod_rs.contains("pub use new_venue::NewVenueNormalizer;\n"));
+        let registry = std::fs::read_to_string(root.path().join(NORMALIZATION_REGISTRY)).unwrap();
+        assert!(registry.contains(", NewVenueNormalizer};"));
+        assert!(registry.contains("normalizers.insert(\"new_venue\".to_string(),\n            Box::new(MetricsNormalizer::new(NewVenueNormalizer::new())));\n"));
+        let venues = std::fs::read_to_string(root.path().join(SCAFFOLDED_VENUES)).unwrap();
+        assert!(venues.contains("    (\"new_venue\", \"New Venue\", \"parse_plan:wix_calendar_v1\"),\n];"));
+    }
+
+    #[test]
+    fn refuses_unknown_parsers_and_existing_sources() {
+        let root = workspace();
+        let unknown = SourceScaffold { parser: "myspace_v1".to_string(), ..new_venue() };
+        assert!(scaffold_source(root.path(), &unknown, &DefaultParserFactory).is_err());
+
+        scaffold_source(root.path(), &new_venue(), &DefaultParserFactory).unwrap();
+        let mod_before = std::fs::read_to_string(root.path().join(NORMALIZERS_DIR).join("mod.rs")).unwrap();
+        assert!(scaffold_source(root.path(), &new_venue(), &DefaultParserFactory).is_err());
+        let mod_after = std::fs::read_to_string(root.path().join(NORMALIZERS_DIR).join("mod.rs")).unwrap();
+        assert_eq!(mod_before, mod_after);
+    }
+
+    fn copy_tree(from: &Path, to: &Path) {
+        std::fs::create_dir_all(to).unwrap();
+        for entry in std::fs::read_dir(from).unwrap() {
+            let entry = entry.unwrap();
+            let name = entry.file_name();
+            if [".git", "target", "data"].iter().any(|skip| name == *skip) {
+                continue;
+            }
+            if entry.file_type().unwrap().is_dir() {
+                copy_tree(&entry.path(), &to.join(&name));
+            } else {
+                std::fs::copy(entry.path(), to.join(&name)).unwrap();
+            }
+        }
+    }
+
+    #[test]
+    #[ignore = "copies the workspace and runs cargo check on it"]
+    fn scaffolded_source_compiles() {
+        let workspace_root = Path::new(env!("CARGO_MANIFEST_DIR")).parent().unwrap();
+        let root = tempfile::tempdir().unwrap();
+        copy_tree(workspace_root, root.path());
+        scaffold_source(root.path(), &new_venue(), &DefaultParserFactory).unwrap();
+
+        // A target dir of its own, so the check neither waits on nor invalidates the main build
+        let output = std::process::Command::new(env!("CARGO"))
+            .current_dir(root.path())
+            .args(["check", "-p", "sms-scraper", "--lib", "--target-dir"])
+            .arg(workspace_root.join("target").join("scaffold-check"))
+            .output()
+            .unwrap();
+        assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    }
+}
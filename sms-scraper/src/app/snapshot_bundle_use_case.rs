@@ -0,0 +1,465 @@
+use crate::app::parse_compare_use_case::{compare_records, EnvelopeComparison};
+use crate::app::ports::{ParserFactory, PayloadStorePort};
+use crate::pipeline::ingestion::envelope::read_log_line;
+use crate::pipeline::ingestion::registry::SourceSpecV1;
+use crate::pipeline::processing::normalize::NormalizationRegistry;
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sms_parsers::ParsedRecord;
+use std::collections::HashMap;
+use std::io::Read;
+use std::path::Path;
+
+/// Bumped when the bundle layout changes
+pub const BUNDLE_FORMAT_VERSION: u32 = 1;
+
+/// Fields stamped with the time of the run, ignored when comparing a replay with the bundle
+const VOLATILE_FIELDS: &[&str] = &["scraped_at", "created_at", "normalized_at"];
+
+/// One bundled envelope and what the stages made of it when the bundle was created
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BundledEnvelope {
+    pub envelope_id: String,
+    pub payload_refs: Vec<String>,
+    pub parsed_records: usize,
+    pub normalized_records: usize,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// `manifest.json` at the root of a bundle
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BundleManifest {
+    pub format_version: u32,
+    pub source_id: String,
+    pub parse_plan: String,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub envelopes: Vec<BundledEnvelope>,
+}
+
+/// A snapshot bundle read back into memory. Layout of the `.tar.gz`:
+///
+/// ```text
+/// manifest.json
+/// registry/<source_id>.json
+/// envelopes.ndjson                       ingest log lines, as read
+/// payloads/<sha256>                      decrypted CAS payloads
+/// stages/<envelope_id>/parsed.ndjson
+/// stages/<envelope_id>/normalized.ndjson
+/// ```
+#[derive(Debug, Clone)]
+pub struct SnapshotBundle {
+    pub manifest: BundleManifest,
+    pub spec: String,
+    pub envelopes: Vec<String>,
+    pub payloads: HashMap<String, Vec<u8>>,
+    pub parsed: HashMap<String, Vec<String>>,
+    pub normalized: HashMap<String, Vec<String>>,
+}
+
+impl SnapshotBundle {
+    pub fn read(path: &Path) -> Result<Self, String> {
+        let file = std::fs::File::open(path).map_err(|e| format!("open {}: {}", path.display(), e))?;
+        let mut archive = tar::Archive::new(flate2::read::GzDecoder::new(file));
+        let mut files: HashMap<String, Vec<u8>> = HashMap::new();
+        for entry in archive.entries().map_err(|e| e.to_string())? {
+            let mut entry = entry.map_err(|e| e.to_string())?;
+            let name = entry.path().map_err(|e| e.to_string())?.to_string_lossy().into_owned();
+            let mut bytes = Vec::new();
+            entry.read_to_end(&mut bytes).map_err(|e| format!("read {}: {}", name, e))?;
+            files.insert(name, bytes);
+        }
+
+        let manifest: BundleManifest = serde_json::from_slice(files.get("manifest.json").ok_or("bundle has no manifest.json")?)
+            .map_err(|e| format!("bad manifest.json: {}", e))?;
+        if manifest.format_version > BUNDLE_FORMAT_VERSION {
+            return Err(format!("bundle format {} is newer than this build supports ({})", manifest.format_version, BUNDLE_FORMAT_VERSION));
+        }
+        let text = |name: &str| files.get(name).map(|b| String::from_utf8_lossy(b).into_owned());
+        let lines = |name: &str| text(name).map(|t| t.lines().map(str::to_string).collect::<Vec<_>>()).unwrap_or_default();
+
+        let spec = text(&format!("registry/{}.json", manifest.source_id)).ok_or("bundle has no registry spec")?;
+        let mut parsed = HashMap::new();
+        let mut normalized = HashMap::new();
+        for envelope in &manifest.envelopes {
+            parsed.insert(envelope.envelope_id.clone(), lines(&format!("stages/{}/parsed.ndjson", envelope.envelope_id)));
+            normalized.insert(envelope.envelope_id.clone(), lines(&format!("stages/{}/normalized.ndjson", envelope.envelope_id)));
+        }
+        let payloads = files
+            .iter()
+            .filter_map(|(name, bytes)| name.strip_prefix("payloads/").map(|hex| (format!("cas:sha256:{}", hex), bytes.clone())))
+            .collect();
+
+        Ok(Self { spec, envelopes: lines("envelopes.ndjson"), payloads, parsed, normalized, manifest })
+    }
+
+    /// The bundled payloads, served by ref like the CAS would
+    pub fn payload_store(&self) -> BundlePayloads {
+        BundlePayloads(self.payloads.clone())
+    }
+}
+
+/// Payloads out of a bundle instead of the CAS
+pub struct BundlePayloads(pub HashMap<String, Vec<u8>>);
+
+#[async_trait]
+impl PayloadStorePort for BundlePayloads {
+    async fn get(&self, payload_ref: &str) -> Result<Vec<u8>, String> {
+        self.0.get(payload_ref).cloned().ok_or_else(|| format!("payload {} not in bundle", payload_ref))
+    }
+}
+
+/// How one envelope replayed against what was recorded in the bundle
+#[derive(Debug, Clone, Serialize)]
+pub struct EnvelopeReplay {
+    pub envelope_id: String,
+    pub parse: EnvelopeComparison,
+    pub recorded_normalized: usize,
+    pub replayed_normalized: usize,
+    /// Replayed normalized records with no identical recorded one (timestamps aside)
+    pub normalized_changed: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    #[serde(skip)]
+    pub parsed_lines: Vec<String>,
+    #[serde(skip)]
+    pub normalized_lines: Vec<String>,
+}
+
+impl EnvelopeReplay {
+    pub fn is_identical(&self) -> bool {
+        self.error.is_none()
+            && self.parse.is_identical()
+            && self.recorded_normalized == self.replayed_normalized
+            && self.normalized_changed == 0
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ReplayReport {
+    pub source_id: String,
+    pub parse_plan: String,
+    pub bundle_created_at: chrono::DateTime<chrono::Utc>,
+    pub envelopes: Vec<EnvelopeReplay>,
+}
+
+impl ReplayReport {
+    pub fn changed_envelopes(&self) -> usize {
+        self.envelopes.iter().filter(|e| !e.is_identical()).count()
+    }
+}
+
+/// Stage outputs for one envelope: parsed lines, normalized lines, and the first error
+struct StageOutputs {
+    parsed: Vec<String>,
+    normalized: Vec<String>,
+    error: Option<String>,
+}
+
+/// Bundles a source's recent envelopes with their payloads, registry spec and stage outputs
+/// into one `.tar.gz`, and replays such a bundle with the local parsers and normalizers, so a
+/// production parse bug can be reproduced without database or CAS access.
+pub struct SnapshotBundleUseCase<S: PayloadStorePort + ?Sized, F: ParserFactory + ?Sized> {
+    pub payloads: Box<S>,
+    pub parsers: Box<F>,
+}
+
+impl<S: PayloadStorePort + ?Sized, F: ParserFactory + ?Sized> SnapshotBundleUseCase<S, F> {
+    pub fn new(payloads: Box<S>, parsers: Box<F>) -> Self {
+        Self { payloads, parsers }
+    }
+
+    /// Write `envelopes` (raw ingest log lines, oldest first) and everything needed to replay
+    /// them to `out`, a `.tar.gz`. `spec` is the source's registry JSON.
+    pub async fn create(&self, spec: &str, envelopes: &[String], out: &Path) -> Result<BundleManifest, String> {
+        let parsed_spec: SourceSpecV1 = serde_json::from_str(spec).map_err(|e| format!("bad registry spec: {}", e))?;
+        let source_id = parsed_spec.source_id.clone();
+        let parse_plan = plan_of(&parsed_spec);
+        let normalizers = NormalizationRegistry::new();
+
+        if let Some(parent) = out.parent().filter(|p| !p.as_os_str().is_empty()) {
+            std::fs::create_dir_all(parent).map_err(|e| format!("create {}: {}", parent.display(), e))?;
+        }
+        let file = std::fs::File::create(out).map_err(|e| format!("create {}: {}", out.display(), e))?;
+        let mut tar = tar::Builder::new(flate2::write::GzEncoder::new(file, flate2::Compression::default()));
+
+        let mut bundled = Vec::with_capacity(envelopes.len());
+        for line in envelopes {
+            let envelope = read_log_line(line).map_err(|e| format!("bad ingest log line: {}", e))?;
+            let envelope_id = envelope.envelope_id.as_str();
+            let payload_refs = envelope.payload_refs();
+
+            let mut payloads = Vec::with_capacity(payload_refs.len());
+            let mut fetch_error = None;
+            for payload_ref in &payload_refs {
+                match self.payloads.get(payload_ref).await {
+                    Ok(bytes) => {
+                        let hex = payload_ref.strip_prefix("cas:sha256:").unwrap_or(payload_ref);
+                        append(&mut tar, &format!("payloads/{}", hex), &bytes)?;
+                        payloads.push((payload_ref.clone(), bytes));
+                    }
+                    Err(e) => fetch_error = Some(format!("payload_unavailable:{}", e)),
+                }
+            }
+            let mut outputs = stage_outputs(&*self.parsers, &normalizers, &source_id, &parse_plan, envelope_id, &payloads).await;
+            outputs.error = fetch_error.or(outputs.error);
+            append(&mut tar, &format!("stages/{}/parsed.ndjson", envelope_id), ndjson(&outputs.parsed).as_bytes())?;
+            append(&mut tar, &format!("stages/{}/normalized.ndjson", envelope_id), ndjson(&outputs.normalized).as_bytes())?;
+
+            bundled.push(BundledEnvelope {
+                envelope_id: envelope_id.to_string(),
+                payload_refs,
+                parsed_records: outputs.parsed.len(),
+                normalized_records: outputs.normalized.len(),
+                error: outputs.error,
+            });
+        }
+
+        let manifest = BundleManifest {
+            format_version: BUNDLE_FORMAT_VERSION,
+            source_id: source_id.clone(),
+            parse_plan,
+            created_at: chrono::Utc::now(),
+            envelopes: bundled,
+        };
+        append(&mut tar, &format!("registry/{}.json", source_id), spec.as_bytes())?;
+        append(&mut tar, "envelopes.ndjson", ndjson(envelopes).as_bytes())?;
+        let manifest_json = serde_json::to_vec_pretty(&manifest).map_err(|e| e.to_string())?;
+        append(&mut tar, "manifest.json", &manifest_json)?;
+        tar.into_inner()
+            .and_then(|gz| gz.finish())
+            .map_err(|e| format!("write {}: {}", out.display(), e))?;
+        Ok(manifest)
+    }
+
+    /// Re-run parse and normalize over a bundle's envelopes (with `self.payloads` serving the
+    /// bundled payloads) and compare with the outputs recorded in it
+    pub async fn replay(&self, bundle: &SnapshotBundle) -> Result<ReplayReport, String> {
+        let spec: SourceSpecV1 = serde_json::from_str(&bundle.spec).map_err(|e| format!("bad registry spec: {}", e))?;
+        let source_id = &bundle.manifest.source_id;
+        let parse_plan = plan_of(&spec);
+        let normalizers = NormalizationRegistry::new();
+
+        let mut replayed = Vec::with_capacity(bundle.manifest.envelopes.len());
+        for envelope in &bundle.manifest.envelopes {
+            let mut payloads = Vec::with_capacity(envelope.payload_refs.len());
+            let mut fetch_error = None;
+            for payload_ref in &envelope.payload_refs {
+                match self.payloads.get(payload_ref).await {
+                    Ok(bytes) => payloads.push((payload_ref.clone(), bytes)),
+                    Err(e) => fetch_error = Some(format!("payload_unavailable:{}", e)),
+                }
+            }
+            let outputs = stage_outputs(&*self.parsers, &normalizers, source_id, &parse_plan, &envelope.envelope_id, &payloads).await;
+            let recorded_parsed = bundle.parsed.get(&envelope.envelope_id).cloned().unwrap_or_default();
+            let recorded_normalized = bundle.normalized.get(&envelope.envelope_id).cloned().unwrap_or_default();
+
+            let replay_result = match &outputs.error {
+                Some(e) if outputs.parsed.is_empty() => Err(e.clone()),
+                _ => Ok(outputs.parsed.iter().map(|l| stable_line(l)).collect()),
+            };
+            let recorded_result = match &envelope.error {
+                Some(e) if recorded_parsed.is_empty() => Err(e.clone()),
+                _ => Ok(recorded_parsed.iter().map(|l| stable_line(l)).collect()),
+            };
+            let mut recorded_stable: Vec<String> = recorded_normalized.iter().map(|l| stable_line(l)).collect();
+            let normalized_changed = outputs
+                .normalized
+                .iter()
+                .filter(|line| {
+                    let stable = stable_line(line);
+                    match recorded_stable.iter().position(|r| *r == stable) {
+                        Some(i) => {
+                            recorded_stable.swap_remove(i);
+                            false
+                        }
+                        None => true,
+                    }
+                })
+                .count();
+
+            replayed.push(EnvelopeReplay {
+                envelope_id: envelope.envelope_id.clone(),
+                parse: compare_records(&envelope.envelope_id, recorded_result, replay_result),
+                recorded_normalized: recorded_normalized.len(),
+                replayed_normalized: outputs.normalized.len(),
+                normalized_changed,
+                error: fetch_error.or(outputs.error),
+                parsed_lines: outputs.parsed,
+                normalized_lines: outputs.normalized,
+            });
+        }
+
+        Ok(ReplayReport {
+            source_id: source_id.clone(),
+            parse_plan,
+            bundle_created_at: bundle.manifest.created_at,
+            envelopes: replayed,
+        })
+    }
+}
+
+/// The parse plan a registry spec resolves to, with the same default `JsonRegistry` uses
+fn plan_of(spec: &SourceSpecV1) -> String {
+    spec.resolved_parse_plan().unwrap_or_else(|| "parse_plan:wix_calendar_v1".to_string())
+}
+
+/// Parse each payload part, then normalize every parsed record
+async fn stage_outputs<F: ParserFactory + ?Sized>(
+    parsers: &F,
+    normalizers: &NormalizationRegistry,
+    source_id: &str,
+    parse_plan: &str,
+    envelope_id: &str,
+    payloads: &[(String, Vec<u8>)],
+) -> StageOutputs {
+    let mut outputs = StageOutputs { parsed: Vec::new(), normalized: Vec::new(), error: None };
+    let Some(parser) = parsers.for_plan(parse_plan) else {
+        outputs.error = Some(format!("no_parser_for_plan:{}", parse_plan));
+        return outputs;
+    };
+    for (payload_ref, bytes) in payloads {
+        match parser.parse(source_id, envelope_id, payload_ref, bytes).await {
+            Ok(lines) => outputs.parsed.extend(lines),
+            Err(e) => {
+                outputs.error.get_or_insert(format!("parse_error:{}", e));
+            }
+        }
+    }
+    for line in &outputs.parsed {
+        let normalized = serde_json::from_str::<ParsedRecord>(line)
+            .map_err(|e| e.to_string())
+            .and_then(|record| normalizers.normalize(&record).map_err(|e| e.to_string()));
+        match normalized {
+            Ok(records) => outputs.normalized.extend(records.iter().filter_map(|r| serde_json::to_string(r).ok())),
+            Err(e) => {
+                outputs.error.get_or_insert(format!("normalize_error:{}", e));
+            }
+        }
+    }
+    outputs
+}
+
+/// A record line without the `VOLATILE_FIELDS` that differ on every run
+fn stable_line(line: &str) -> String {
+    fn strip(value: &mut Value) {
+        match value {
+            Value::Object(map) => {
+                for field in VOLATILE_FIELDS {
+                    map.remove(*field);
+                }
+                map.values_mut().for_each(strip);
+            }
+            Value::Array(items) => items.iter_mut().for_each(strip),
+            _ => {}
+        }
+    }
+    match serde_json::from_str::<Value>(line) {
+        Ok(mut value) => {
+            strip(&mut value);
+            value.to_string()
+        }
+        Err(_) => line.to_string(),
+    }
+}
+
+fn ndjson(lines: &[String]) -> String {
+    lines.iter().map(|l| format!("{}\n", l)).collect()
+}
+
+fn append<W: std::io::Write>(tar: &mut tar::Builder<W>, name: &str, bytes: &[u8]) -> Result<(), String> {
+    let mut header = tar::Header::new_gnu();
+    header.set_size(bytes.len() as u64);
+    header.set_mode(0o644);
+    header.set_mtime(chrono::Utc::now().timestamp().max(0) as u64);
+    header.set_cksum();
+    tar.append_data(&mut header, name, bytes).map_err(|e| format!("add {}: {}", name, e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::ports::ParserPort;
+    use crate::pipeline::ingestion::envelope::StampedEnvelopeV1;
+    use serde_json::json;
+
+    /// One record per `<li>` in the payload
+    struct ListParser;
+    #[async_trait]
+    impl ParserPort for ListParser {
+        async fn parse(&self, source_id: &str, envelope_id: &str, payload_ref: &str, bytes: &[u8]) -> Result<Vec<String>, String> {
+            let html = String::from_utf8_lossy(bytes);
+            Ok(html
+                .split("<li>")
+                .skip(1)
+                .enumerate()
+                .map(|(i, item)| {
+                    json!({
+                        "source_id": source_id,
+                        "envelope_id": envelope_id,
+                        "payload_ref": payload_ref,
+                        "record_path": format!("$.events[{}]", i),
+                        "record": {
+                            "title": item.split("</li>").next().unwrap_or_default(),
+                            "scraped_at": chrono::Utc::now().to_rfc3339(),
+                        },
+                    })
+                    .to_string()
+                })
+                .collect())
+        }
+    }
+
+    struct Parsers;
+    impl ParserFactory for Parsers {
+        fn for_plan(&self, _plan: &str) -> Option<Box<dyn ParserPort>> {
+            Some(Box::new(ListParser))
+        }
+    }
+
+    const SPEC: &str = r#"{
+        "source_id": "kexp",
+        "enabled": true,
+        "endpoints": [{ "url": "https://www.kexp.org/events/", "method": "GET" }],
+        "content": { "allowed_mime_types": ["text/html"], "max_payload_size_bytes": 1024 },
+        "policy": { "license_id": "test" },
+        "parse_plan_ref": "parse_plan:test_v1"
+    }"#;
+
+    #[tokio::test]
+    async fn bundles_replay_without_the_cas_and_flag_parser_changes() {
+        let tmp = tempfile::tempdir().unwrap();
+        let payload_ref = "cas:sha256:abcd".to_string();
+        let envelope = StampedEnvelopeV1 { envelope_id: "env-1".to_string(), ..StampedEnvelopeV1::fixture("kexp", &payload_ref) };
+        let envelope = serde_json::to_string(&envelope).unwrap();
+        let cas = BundlePayloads(HashMap::from([(payload_ref.clone(), b"<ul><li>A</li><li>B</li></ul>".to_vec())]));
+        let bundle_path = tmp.path().join("bundle.tar.gz");
+        let manifest = SnapshotBundleUseCase::new(Box::new(cas), Box::new(Parsers))
+            .create(SPEC, &[envelope], &bundle_path)
+            .await
+            .unwrap();
+        assert_eq!(manifest.parse_plan, "parse_plan:test_v1");
+        assert_eq!(manifest.envelopes[0].parsed_records, 2);
+
+        // Replay reads everything from the bundle; fresh timestamps don't count as changes
+        let bundle = SnapshotBundle::read(&bundle_path).unwrap();
+        assert_eq!(bundle.envelopes.len(), 1);
+        assert!(bundle.spec.contains("kexp.org"));
+        let replay = SnapshotBundleUseCase::new(Box::new(bundle.payload_store()), Box::new(Parsers));
+        let report = replay.replay(&bundle).await.unwrap();
+        assert_eq!(report.changed_envelopes(), 0);
+        assert_eq!(report.envelopes[0].parsed_lines.len(), 2);
+
+        // A payload that now parses differently shows up as a change
+        let mut changed = bundle.clone();
+        changed.payloads.insert(payload_ref, b"<ul><li>A</li><li>C</li><li>D</li></ul>".to_vec());
+        let replay = SnapshotBundleUseCase::new(Box::new(changed.payload_store()), Box::new(Parsers));
+        let report = replay.replay(&changed).await.unwrap();
+        assert_eq!(report.changed_envelopes(), 1);
+        let parse = &report.envelopes[0].parse;
+        assert_eq!((parse.baseline_records, parse.candidate_records), (2, 3));
+        assert_eq!(parse.only_in_candidate, vec!["$.events[2]".to_string()]);
+        assert_eq!(parse.field_diffs.len(), 1);
+    }
+}
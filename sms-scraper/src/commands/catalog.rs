@@ -0,0 +1,201 @@
+//! Catalog maintenance: rollback, moderation, merges, stats and audits
+
+use std::sync::Arc;
+use sms_scraper::observability::push::PushConfig;
+use sms_scraper::pipeline::ingestion::gateway_all::enabled_sources;
+use super::{open_storage, print_json};
+use crate::{CatalogAction, MergeAction, ModerateAction, StorageMode};
+
+pub async fn run_catalog_action(action: &CatalogAction, storage_mode: StorageMode, json: bool) -> anyhow::Result<()> {
+    use sms_scraper::app::catalog_rollback_use_case::{CatalogRollbackUseCase, RollbackAction};
+
+    let storage = open_storage(storage_mode).await?;
+    let CatalogAction::Rollback { run_id, dry_run } = action;
+    let rollback = CatalogRollbackUseCase::new(storage);
+    let plan = rollback.plan(*run_id).await?;
+    if json && *dry_run {
+        return print_json(&plan);
+    }
+    if !json {
+        println!("⏪ Rollback of run {} ({}, started {})", run_id, plan.run.name, plan.run.created_at);
+        for step in &plan.steps {
+            let name = step.name.as_deref().unwrap_or("?");
+            let what = match &step.action {
+                RollbackAction::Hide => "hide".to_string(),
+                RollbackAction::Restore { .. } => "restore previous state".to_string(),
+                RollbackAction::Skip { reason } => format!("skip: {}", reason),
+            };
+            println!("   {} {} {} \"{}\" → {}", step.change_type, step.entity_type, step.entity_id, name, what);
+        }
+        println!(
+            "   {} to hide, {} to restore, {} skipped",
+            plan.count(|a| matches!(a, RollbackAction::Hide)),
+            plan.count(|a| matches!(a, RollbackAction::Restore { .. })),
+            plan.count(|a| matches!(a, RollbackAction::Skip { .. })),
+        );
+    }
+    if *dry_run {
+        if !json {
+            println!("🔍 Dry run: nothing was changed");
+        }
+        return Ok(());
+    }
+    let summary = rollback.apply(&plan).await?;
+    if json {
+        return print_json(&summary);
+    }
+    println!("✅ Hid {}, restored {}, skipped {}", summary.hidden, summary.restored, summary.skipped);
+    Ok(())
+}
+
+pub async fn run_moderate(action: ModerateAction, storage_mode: StorageMode, json: bool) -> anyhow::Result<()> {
+    use sms_scraper::app::moderation_use_case::{ModeratedEntity, ModerationUseCase};
+
+    let storage = open_storage(storage_mode).await?;
+    let moderation = ModerationUseCase::new(storage);
+    let outcome = match action {
+        ModerateAction::HideEvent { id, reason } => moderation.hide(ModeratedEntity::Event, id, &reason).await?,
+        ModerateAction::ShowEvent { id, reason } => moderation.show(ModeratedEntity::Event, id, reason.as_deref()).await?,
+        ModerateAction::HideVenue { id, reason } => moderation.hide(ModeratedEntity::Venue, id, &reason).await?,
+        ModerateAction::ShowVenue { id, reason } => moderation.show(ModeratedEntity::Venue, id, reason.as_deref()).await?,
+    };
+    if json {
+        return print_json(&outcome);
+    }
+    println!(
+        "{} {} {} \"{}\"{}",
+        if outcome.hidden { "🙈 Hid" } else { "👀 Showed" },
+        outcome.entity.as_str(),
+        outcome.entity_id,
+        outcome.name,
+        outcome.reason.as_deref().map(|r| format!(" ({})", r)).unwrap_or_default(),
+    );
+    println!("   Recorded in process run {}", outcome.audit_run_id);
+    Ok(())
+}
+
+pub async fn run_merge(action: MergeAction, storage_mode: StorageMode, data_root: &str, json: bool) -> anyhow::Result<()> {
+    use sms_scraper::app::merge_use_case::{MergeUseCase, MergedEntity};
+    use sms_scraper::pipeline::processing::resolution_index::ResolutionIndex;
+
+    let storage = open_storage(storage_mode).await?;
+    let index = ResolutionIndex::open_at_root(data_root)?;
+    let merge = MergeUseCase::new(storage).with_resolution_index(Arc::new(index));
+    let outcome = match action {
+        MergeAction::Artists { keep, remove, reason } => merge.merge(MergedEntity::Artist, keep, remove, reason.as_deref()).await?,
+        MergeAction::Venues { keep, remove, reason } => merge.merge(MergedEntity::Venue, keep, remove, reason.as_deref()).await?,
+    };
+    if json {
+        return print_json(&outcome);
+    }
+    println!(
+        "🔗 Merged {} \"{}\" ({}) into \"{}\" ({}){}",
+        outcome.entity.as_str(),
+        outcome.removed_name,
+        outcome.removed_id,
+        outcome.kept_name,
+        outcome.kept_id,
+        outcome.reason.as_deref().map(|r| format!(" ({})", r)).unwrap_or_default(),
+    );
+    println!("   Events re-linked: {}", outcome.events_relinked);
+    if outcome.entity == MergedEntity::Venue {
+        println!("   Recurring series moved: {}", outcome.series_relinked);
+    }
+    println!("   Resolution keys re-pointed: {}", outcome.resolution_keys_repointed);
+    println!("   Recorded in process run {}", outcome.audit_run_id);
+    Ok(())
+}
+
+pub async fn run_stats(storage_mode: StorageMode, registry_dir: &str, days: i64, json: bool) -> anyhow::Result<()> {
+    use sms_scraper::pipeline::processing::catalog::stats::CatalogStats;
+
+    let storage = open_storage(storage_mode).await?;
+    let sources: Vec<String> = match enabled_sources(std::path::Path::new(registry_dir)) {
+        Ok(sources) => sources.into_iter().map(|(source_id, _host)| source_id).collect(),
+        Err(e) => {
+            tracing::warn!("Could not read registry {}: {}", registry_dir, e);
+            Vec::new()
+        }
+    };
+    let stats = CatalogStats::collect(storage.as_ref(), &sources, days).await?;
+    if json {
+        print_json(&stats)?;
+        return Ok(());
+    }
+
+    println!("📊 Catalog: {} venues, {} artists, {} events", stats.venues, stats.artists, stats.events);
+    println!("📅 Events: {} upcoming, {} past", stats.upcoming_events, stats.past_events);
+    println!(
+        "🆕 Cataloged in the last {} days: {} venues, {} artists, {} events",
+        stats.recent_days, stats.recent.venues, stats.recent.artists, stats.recent.events
+    );
+    println!("🏟️  Events per venue:");
+    for venue in &stats.events_per_venue {
+        println!("   {:>5}  {}", venue.events, venue.name);
+    }
+    if stats.quiet_sources.is_empty() {
+        println!("✅ Every source has events from the last {} days", stats.recent_days);
+    } else {
+        println!("⚠️  Sources with no events in the last {} days: {}", stats.recent_days, stats.quiet_sources.join(", "));
+    }
+    Ok(())
+}
+
+pub async fn run_audit(
+    storage_mode: StorageMode,
+    data_root: &str,
+    inactive_days: i64,
+    json: bool,
+    push: &PushConfig,
+) -> anyhow::Result<()> {
+    use sms_scraper::observability::metrics;
+    use sms_scraper::pipeline::ingestion::ingest_meta::MetaStore;
+    use sms_scraper::pipeline::processing::catalog::audit::CatalogAudit;
+
+    let storage = open_storage(storage_mode).await?;
+    let meta = MetaStore::at_root(data_root);
+    let audit = CatalogAudit::collect(storage.as_ref(), &meta, inactive_days).await?;
+    let report_path = audit.write_report(&std::path::Path::new(data_root).join("audit"))?;
+
+    let counts = audit.counts();
+    if let Err(e) = metrics::init_with_push_config(push, Some("sms_audit"), None) {
+        tracing::warn!("Metrics disabled: {}", e);
+    }
+    metrics::audit::completed(counts.iter().map(|(check, n)| (*check, *n)));
+    if push.enabled() {
+        if let Err(e) = metrics::push_all_metrics_with_instance("audit").await {
+            tracing::warn!("Failed to push audit metrics: {}", e);
+        }
+    }
+
+    if json {
+        print_json(&serde_json::json!({
+            "command": "audit",
+            "report": report_path,
+            "findings": audit.findings(),
+            "checks": counts,
+        }))?;
+        return Ok(());
+    }
+
+    println!("🔎 Catalog audit: {} findings", audit.findings());
+    println!("   📅 Past events never finalized: {}", audit.stale_upcoming_events.len());
+    for event in audit.stale_upcoming_events.iter().take(10) {
+        println!("      {}  {} ({})", event.event_day, event.title, event.event_id);
+    }
+    println!("   🏟️  Venues with no events in {} days: {}", audit.inactive_venue_days, audit.inactive_venues.len());
+    for venue in audit.inactive_venues.iter().take(10) {
+        let last = venue.last_event_day.map(|d| d.to_string()).unwrap_or_else(|| "never".to_string());
+        println!("      {} (last event: {})", venue.name, last);
+    }
+    println!("   🎤 Artists with no events: {}", audit.orphan_artists.len());
+    println!("   🧾 Run reports disagreeing with the catalog: {}", audit.run_report_mismatches.len());
+    for mismatch in &audit.run_report_mismatches {
+        println!(
+            "      {}: run {} cataloged {} records, catalog has no events from it",
+            mismatch.source_id, mismatch.run_id, mismatch.records_cataloged
+        );
+    }
+    println!("📝 Report written to {}", report_path.display());
+    Ok(())
+}
@@ -0,0 +1,170 @@
+//! Envelope snapshots and replayable snapshot bundles
+
+use super::{print_json, summarize_failure};
+use crate::{DebugAction, SnapshotAction};
+
+pub async fn run_debug(action: DebugAction) -> anyhow::Result<()> {
+    use sms_scraper::app::debug_snapshot_use_case::DebugSnapshotUseCase;
+    use sms_scraper::infra::{parser_factory::DefaultParserFactory, payload_store::CasPayloadStore, registry_adapter::JsonRegistry};
+    use sms_scraper::pipeline::ingestion::ingest_log_reader::IngestLogReader;
+
+    match action {
+        DebugAction::Snapshot { envelope_id, data_root, out_dir } => {
+            let reader = IngestLogReader::new(&data_root);
+            let Some(envelope) = reader.find_envelope(&envelope_id)? else {
+                println!("❌ Envelope {} not found in the ingest log under {}", envelope_id, data_root);
+                return Ok(());
+            };
+            // A dedupe marker carries no payload of its own; snapshot the original's
+            let original = match envelope.dedupe_of.as_deref() {
+                Some(dedupe_of) => match reader.find_envelope(dedupe_of)? {
+                    Some(original) => Some(original),
+                    None => {
+                        println!("❌ Envelope {} duplicates {}, which is no longer in the ingest log", envelope_id, dedupe_of);
+                        return Ok(());
+                    }
+                },
+                None => None,
+            };
+            let out_dir = out_dir.unwrap_or_else(|| std::path::Path::new(&data_root).join("snapshots"));
+
+            let snapshot_uc = DebugSnapshotUseCase::new(Box::new(JsonRegistry), Box::new(CasPayloadStore), Box::new(DefaultParserFactory));
+            let snapshot = match snapshot_uc.snapshot(&envelope, original.as_ref(), &out_dir).await {
+                Ok(snapshot) => snapshot,
+                Err(e) => {
+                    tracing::error!("Snapshot of {} failed: {}", envelope_id, e);
+                    println!("❌ Snapshot of {} failed: {}", envelope_id, e);
+                    return Ok(());
+                }
+            };
+
+            println!("📸 {} ({}) parsed with {}", snapshot.envelope_id, snapshot.source_id, snapshot.parse_plan);
+            if let Some(original) = &snapshot.payload_from {
+                println!("   ↪️  payload from original envelope {}", original);
+            }
+            for part in &snapshot.parts {
+                let marker = if part.failure.is_some() { "⚠️ " } else { "✅" };
+                println!("{} {} ({} bytes): {} records", marker, part.payload_ref, part.size_bytes, part.records);
+                if let Some(failure) = &part.failure {
+                    println!("   {}", failure);
+                }
+                for file in &part.files {
+                    println!("   📄 {}", file.display());
+                }
+            }
+            println!("📁 Snapshot: {}", snapshot.dir.display());
+        }
+    }
+    Ok(())
+}
+
+pub async fn run_snapshot(action: SnapshotAction, json: bool) -> anyhow::Result<()> {
+    use sms_scraper::app::snapshot_bundle_use_case::{SnapshotBundle, SnapshotBundleUseCase};
+    use sms_scraper::infra::{parser_factory::DefaultParserFactory, payload_store::CasPayloadStore};
+    use sms_scraper::pipeline::ingestion::ingest_log_reader::IngestLogReader;
+
+    match action {
+        SnapshotAction::Create { source_id, last, data_root, registry_dir, out } => {
+            let spec_path = std::path::Path::new(&registry_dir).join(format!("{}.json", source_id));
+            let spec = match std::fs::read_to_string(&spec_path) {
+                Ok(spec) => spec,
+                Err(e) => summarize_failure(json, "snapshot create", &format!("read {}: {}", spec_path.display(), e)),
+            };
+            let reader = IngestLogReader::new(&data_root);
+            let mut envelope_ids: Vec<String> = Vec::new();
+            for (envelope_id, _) in reader.envelopes_for_source(&source_id, usize::MAX)? {
+                if envelope_ids.last() != Some(&envelope_id) {
+                    envelope_ids.push(envelope_id);
+                }
+            }
+            let skip = envelope_ids.len().saturating_sub(last);
+            let mut envelopes = Vec::new();
+            for envelope_id in &envelope_ids[skip..] {
+                envelopes.extend(reader.find_envelope_by_id(envelope_id)?);
+            }
+            if envelopes.is_empty() {
+                summarize_failure(json, "snapshot create", &format!("no envelopes for {} in the ingest log under {}", source_id, data_root));
+            }
+            let out = out.unwrap_or_else(|| {
+                let stamp = chrono::Utc::now().format("%Y%m%dT%H%M%SZ");
+                std::path::Path::new(&data_root).join("snapshots").join(format!("{}-{}.tar.gz", source_id, stamp))
+            });
+
+            let bundler = SnapshotBundleUseCase::new(Box::new(CasPayloadStore), Box::new(DefaultParserFactory));
+            let manifest = match bundler.create(&spec, &envelopes, &out).await {
+                Ok(manifest) => manifest,
+                Err(e) => summarize_failure(json, "snapshot create", &format!("snapshot of {} failed: {}", source_id, e)),
+            };
+            if json {
+                return print_json(&serde_json::json!({ "bundle": out, "manifest": manifest }));
+            }
+            println!("📦 {} envelope(s) of {} parsed with {}", manifest.envelopes.len(), source_id, manifest.parse_plan);
+            for envelope in &manifest.envelopes {
+                let marker = if envelope.error.is_some() { "⚠️ " } else { "✅" };
+                println!(
+                    "{} {}: {} parsed, {} normalized",
+                    marker, envelope.envelope_id, envelope.parsed_records, envelope.normalized_records
+                );
+                if let Some(error) = &envelope.error {
+                    println!("   {}", error);
+                }
+            }
+            println!("📁 Bundle: {}", out.display());
+        }
+        SnapshotAction::Run { bundle, out_dir } => {
+            let loaded = match SnapshotBundle::read(&bundle) {
+                Ok(loaded) => loaded,
+                Err(e) => summarize_failure(json, "snapshot run", &format!("read {}: {}", bundle.display(), e)),
+            };
+            let replayer = SnapshotBundleUseCase::new(Box::new(loaded.payload_store()), Box::new(DefaultParserFactory));
+            let report = match replayer.replay(&loaded).await {
+                Ok(report) => report,
+                Err(e) => summarize_failure(json, "snapshot run", &format!("replay failed: {}", e)),
+            };
+            if let Some(out_dir) = &out_dir {
+                for envelope in &report.envelopes {
+                    let dir = out_dir.join(&envelope.envelope_id);
+                    std::fs::create_dir_all(&dir)?;
+                    std::fs::write(dir.join("parsed.ndjson"), envelope.parsed_lines.iter().map(|l| format!("{}\n", l)).collect::<String>())?;
+                    std::fs::write(dir.join("normalized.ndjson"), envelope.normalized_lines.iter().map(|l| format!("{}\n", l)).collect::<String>())?;
+                }
+            }
+            if json {
+                return print_json(&report);
+            }
+            println!(
+                "🔁 Replayed {} envelope(s) of {} with {} (bundled {})",
+                report.envelopes.len(), report.source_id, report.parse_plan, report.bundle_created_at
+            );
+            for envelope in &report.envelopes {
+                let parse = &envelope.parse;
+                let marker = if envelope.is_identical() { "✅" } else { "⚠️ " };
+                println!(
+                    "{} {}: parsed {} → {}, normalized {} → {}",
+                    marker, envelope.envelope_id, parse.baseline_records, parse.candidate_records,
+                    envelope.recorded_normalized, envelope.replayed_normalized
+                );
+                for path in &parse.only_in_baseline {
+                    println!("   - {}", path);
+                }
+                for path in &parse.only_in_candidate {
+                    println!("   + {}", path);
+                }
+                for diff in &parse.field_diffs {
+                    println!("   ~ {} {}: {:?} → {:?}", diff.record_path, diff.field, diff.baseline, diff.candidate);
+                }
+                if envelope.normalized_changed > 0 {
+                    println!("   ~ {} normalized record(s) differ", envelope.normalized_changed);
+                }
+                if let Some(error) = &envelope.error {
+                    println!("   {}", error);
+                }
+            }
+            if let Some(out_dir) = &out_dir {
+                println!("📁 Replayed outputs: {}", out_dir.display());
+            }
+            println!("{} of {} envelope(s) changed", report.changed_envelopes(), report.envelopes.len());
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,438 @@
+//! Ingest-side inspection and upkeep: envelopes, the ingest log and its metadata, the
+//! dead-letter queue, CAS garbage collection and registry sources
+
+use super::{exit_failed, open_storage, print_json, summarize_failure};
+use crate::{CasAction, DlqAction, EnvelopeAction, IngestLogAction, IngestMetaAction, SourcesAction};
+
+pub fn run_envelope(action: EnvelopeAction, json: bool) -> anyhow::Result<()> {
+    use sms_scraper::pipeline::ingestion::envelope_state::{stuck_after_secs, stuck_envelopes};
+    use sms_scraper::pipeline::ingestion::ingest_meta::IngestMeta;
+
+    let at = |ts: i64| {
+        chrono::DateTime::from_timestamp(ts, 0).map(|t| t.to_rfc3339()).unwrap_or_else(|| ts.to_string())
+    };
+    match action {
+        EnvelopeAction::Status { envelope_id, data_root } => {
+            let meta = IngestMeta::open_at_root(&data_root)?;
+            let Some(current) = meta.get_envelope_state(&envelope_id)? else {
+                summarize_failure(json, "envelope status", &format!("No state recorded for envelope {}", envelope_id));
+            };
+            let history = meta.envelope_state_history(&envelope_id)?;
+            if json {
+                return print_json(&serde_json::json!({
+                    "command": "envelope status",
+                    "envelope_id": current.envelope_id,
+                    "source_id": current.source_id,
+                    "state": current.state,
+                    "detail": current.detail,
+                    "updated_at": at(current.updated_at),
+                    "history": history.iter().map(|(state, detail, ts)| serde_json::json!({
+                        "state": state,
+                        "detail": detail,
+                        "at": at(*ts),
+                    })).collect::<Vec<_>>(),
+                }));
+            }
+            println!("✉️  Envelope {} ({}): {}", current.envelope_id, current.source_id, current.state);
+            if let Some(detail) = &current.detail {
+                println!("   📝 {}", detail);
+            }
+            for (state, detail, ts) in &history {
+                match detail {
+                    Some(detail) => println!("   {} {} ({})", at(*ts), state, detail),
+                    None => println!("   {} {}", at(*ts), state),
+                }
+            }
+        }
+        EnvelopeAction::Stuck { data_root, older_than_secs } => {
+            let meta = IngestMeta::open_at_root(&data_root)?;
+            let threshold = older_than_secs.unwrap_or_else(stuck_after_secs);
+            let stuck = stuck_envelopes(&meta, threshold, chrono::Utc::now().timestamp())?;
+            if json {
+                return print_json(&serde_json::json!({
+                    "command": "envelope stuck",
+                    "older_than_secs": threshold,
+                    "envelopes": stuck.iter().map(|e| serde_json::json!({
+                        "envelope_id": e.envelope_id,
+                        "source_id": e.source_id,
+                        "state": e.state,
+                        "since": at(e.updated_at),
+                    })).collect::<Vec<_>>(),
+                }));
+            }
+            if stuck.is_empty() {
+                println!("✅ No envelopes stuck longer than {}s", threshold);
+            } else {
+                println!("⏳ {} envelopes stuck longer than {}s:", stuck.len(), threshold);
+            }
+            for e in &stuck {
+                println!("   {} ({}) {} since {}", e.envelope_id, e.source_id, e.state, at(e.updated_at));
+            }
+        }
+    }
+    Ok(())
+}
+
+pub fn run_ingest_log(action: IngestLogAction, json: bool) -> anyhow::Result<()> {
+    use sms_scraper::pipeline::ingestion::ingest_log_reader::IngestLogReader;
+
+    match action {
+        IngestLogAction::Status { data_root, consumer } => {
+            let reader = IngestLogReader::new(&data_root);
+            let (offset, end, lag) = reader.status(&consumer)?;
+            let pending: std::collections::BTreeMap<String, u64> =
+                reader.pending_by_source(&consumer)?.into_iter().collect();
+            if json {
+                return print_json(&serde_json::json!({
+                    "command": "ingest-log status",
+                    "consumer": consumer,
+                    "byte_offset": offset.byte_offset,
+                    "last_envelope_id": offset.envelope_id,
+                    "end_offset": end,
+                    "lag_bytes": lag,
+                    "pending_by_source": pending,
+                }));
+            }
+            println!("📜 Ingest log status for consumer {}:", consumer);
+            println!("   📍 Offset: {} of {} bytes (lag {} bytes)", offset.byte_offset, end, lag);
+            if let Some(envelope_id) = &offset.envelope_id {
+                println!("   ✅ Last acked envelope: {}", envelope_id);
+            }
+            if pending.is_empty() {
+                println!("   💤 Nothing pending");
+            }
+            for (source_id, count) in pending {
+                println!("   ⏳ {}: {} pending", source_id, count);
+            }
+        }
+    }
+    Ok(())
+}
+
+pub fn run_ingest_meta(action: IngestMetaAction, json: bool) -> anyhow::Result<()> {
+    use sms_scraper::pipeline::ingestion::ingest_meta::{dedupe_horizon_secs, IngestMeta};
+
+    let format_ts = |ts: Option<i64>| {
+        ts.and_then(|ts| chrono::DateTime::from_timestamp(ts, 0))
+            .map(|at| at.to_rfc3339())
+            .unwrap_or_else(|| "-".to_string())
+    };
+    match action {
+        IngestMetaAction::Stats { data_root } => {
+            let stats = IngestMeta::open_at_root(&data_root)?.dedupe_stats()?;
+            if json {
+                return print_json(&serde_json::json!({
+                    "command": "ingest-meta stats",
+                    "dedupe_horizon_secs": dedupe_horizon_secs(),
+                    "stats": stats,
+                }));
+            }
+            println!("🔑 Dedupe index: {} idempotency keys", stats.keys);
+            println!("   🕰️  Oldest: {}, newest: {}", format_ts(stats.oldest_at), format_ts(stats.newest_at));
+            match dedupe_horizon_secs() {
+                Some(secs) => println!("   ⏳ Keys expire after {}s", secs),
+                None => println!("   ♾️  Automatic expiry is off"),
+            }
+            println!("   💾 meta.db: {} bytes ({} reclaimable by vacuum)", stats.db_bytes, stats.free_bytes);
+            for (source_id, keys) in &stats.by_source {
+                println!("   {:>7}  {}", keys, source_id);
+            }
+        }
+        IngestMetaAction::Vacuum { data_root } => {
+            let (before, after) = IngestMeta::open_at_root(&data_root)?.vacuum()?;
+            if json {
+                return print_json(&serde_json::json!({
+                    "command": "ingest-meta vacuum",
+                    "bytes_before": before,
+                    "bytes_after": after,
+                }));
+            }
+            println!("🧹 Vacuumed meta.db: {} → {} bytes", before, after);
+        }
+        IngestMetaAction::Expire { data_root, older_than } => {
+            let cutoff = chrono::Utc::now().timestamp() - older_than;
+            let expired = IngestMeta::open_at_root(&data_root)?.expire_dedupe_keys(cutoff)?;
+            if json {
+                return print_json(&serde_json::json!({
+                    "command": "ingest-meta expire",
+                    "older_than_secs": older_than,
+                    "expired": expired,
+                }));
+            }
+            println!("🗑️  Expired {} idempotency keys recorded before {}", expired, format_ts(Some(cutoff)));
+        }
+    }
+    Ok(())
+}
+
+pub async fn run_dlq(data_root: &std::path::Path, action: DlqAction, json: bool) -> anyhow::Result<()> {
+    use sms_scraper::app::parse_use_case::ParseUseCase;
+    use sms_scraper::app::ports::DeadLetterPort;
+    use sms_scraper::infra::dead_letter_store::FileDeadLetterStore;
+    use sms_scraper::infra::{parser_factory::DefaultParserFactory, payload_store::CasPayloadStore, registry_adapter::JsonRegistry};
+
+    let store = FileDeadLetterStore::new(data_root);
+    match action {
+        DlqAction::List { source } => {
+            let entries: Vec<_> = store
+                .list()
+                .await
+                .map_err(anyhow::Error::msg)?
+                .into_iter()
+                .filter(|e| source.as_ref().is_none_or(|s| &e.source_id == s))
+                .collect();
+            if json {
+                return print_json(&serde_json::json!({ "command": "dlq list", "entries": entries }));
+            }
+            if entries.is_empty() {
+                println!("📭 Dead-letter queue is empty");
+            }
+            for e in &entries {
+                println!(
+                    "💀 {} source={} plan={} attempts={} failed_at={}",
+                    e.envelope_id, e.source_id, e.parse_plan, e.attempts, e.failed_at.to_rfc3339()
+                );
+                println!("   payload_ref={}", e.payload_ref);
+                println!("   error={}", e.error);
+            }
+        }
+        DlqAction::Retry { envelope_id, all } => {
+            let entries: Vec<_> = match (envelope_id, all) {
+                (Some(id), _) => store.list().await.map_err(anyhow::Error::msg)?.into_iter().filter(|e| e.envelope_id == id).collect(),
+                (None, true) => store.list().await.map_err(anyhow::Error::msg)?,
+                (None, false) => summarize_failure(json, "dlq retry", "Please specify --envelope-id <id> or --all"),
+            };
+            if entries.is_empty() {
+                if json {
+                    return print_json(&serde_json::json!({ "command": "dlq retry", "success": true, "results": [] }));
+                }
+                println!("📭 Nothing to retry");
+                return Ok(());
+            }
+
+            let parse_uc = ParseUseCase::new(Box::new(JsonRegistry), Box::new(CasPayloadStore), Box::new(DefaultParserFactory))
+                .with_dead_letters(Box::new(FileDeadLetterStore::new(data_root)));
+            let output_dir = std::path::Path::new("output");
+            std::fs::create_dir_all(output_dir)?;
+            let out_path = output_dir.join(format!("{}_dlq_retry.ndjson", chrono::Utc::now().format("%Y%m%d_%H%M%S")));
+            let mut out = std::fs::File::create(&out_path)?;
+
+            let (mut recovered, mut still_failing) = (0usize, 0usize);
+            let mut results = Vec::new();
+            for e in entries {
+                match parse_uc.parse_one(&e.source_id, &e.envelope_id, &e.payload_ref).await {
+                    Ok(lines) => {
+                        use std::io::Write;
+                        for line in &lines {
+                            writeln!(out, "{}", line)?;
+                        }
+                        store.remove(&e.envelope_id).await.map_err(anyhow::Error::msg)?;
+                        if !json {
+                            println!("✅ {} re-parsed into {} records", e.envelope_id, lines.len());
+                        }
+                        results.push(serde_json::json!({ "envelope_id": e.envelope_id, "success": true, "records": lines.len() }));
+                        recovered += 1;
+                    }
+                    Err(err) => {
+                        if !json {
+                            println!("❌ {} still failing: {}", e.envelope_id, err);
+                        }
+                        results.push(serde_json::json!({ "envelope_id": e.envelope_id, "success": false, "error": err }));
+                        still_failing += 1;
+                    }
+                }
+            }
+            if json {
+                print_json(&serde_json::json!({
+                    "command": "dlq retry",
+                    "success": still_failing == 0,
+                    "recovered": recovered,
+                    "still_failing": still_failing,
+                    "output": out_path,
+                    "results": results,
+                }))?;
+            } else {
+                println!("📊 Recovered: {}, still failing: {}", recovered, still_failing);
+                println!("📁 Output: {}", out_path.display());
+            }
+            if still_failing > 0 {
+                exit_failed();
+            }
+        }
+        DlqAction::Discard { envelope_id } => {
+            if !store.remove(&envelope_id).await.map_err(anyhow::Error::msg)? {
+                summarize_failure(json, "dlq discard", &format!("No dead-lettered envelope with id {}", envelope_id));
+            }
+            if json {
+                return print_json(&serde_json::json!({ "command": "dlq discard", "envelope_id": envelope_id, "success": true }));
+            }
+            println!("🗑️  Discarded {}", envelope_id);
+        }
+    }
+    Ok(())
+}
+
+pub async fn run_cas(action: CasAction, json: bool) -> anyhow::Result<()> {
+    use sms_scraper::pipeline::ingestion::gateway::cas_gc::{self, GcOptions};
+
+    match action {
+        CasAction::Gc { data_root, storage_mode, retention_days, min_age_hours, dry_run } => {
+            let data_root = std::path::Path::new(&data_root);
+            let storage = open_storage(storage_mode).await?;
+            let now = chrono::Utc::now();
+            let opts = GcOptions {
+                retention: retention_days.map(chrono::Duration::days),
+                min_age: chrono::Duration::hours(min_age_hours),
+                dry_run,
+            };
+            let live = cas_gc::collect_references(data_root, &*storage, opts.retention, now).await?;
+            if !json {
+                println!("🔗 {} payloads still referenced", live.len());
+            }
+
+            let report = if cas_gc::supabase_configured() {
+                cas_gc::gc_supabase(&live, &opts, now).await?
+            } else {
+                cas_gc::gc_local(&data_root.join("cas"), &live, &opts, now)?
+            };
+
+            for error in &report.errors {
+                tracing::error!("cas gc: {}", error);
+            }
+            if json {
+                print_json(&serde_json::json!({
+                    "command": "cas gc",
+                    "success": report.errors.is_empty(),
+                    "dry_run": dry_run,
+                    "live_references": live.len(),
+                    "report": report,
+                }))?;
+                if !report.errors.is_empty() {
+                    exit_failed();
+                }
+                return Ok(());
+            }
+
+            let verb = if dry_run { "Would delete" } else { "Deleted" };
+            println!("🗑️  {} ({}): scanned {}, referenced {}, too recent {}", report.backend, if dry_run { "dry run" } else { "gc" }, report.scanned, report.referenced, report.too_recent);
+            println!("✅ {} {} payloads, reclaiming {} bytes", verb, report.deleted, report.reclaimed_bytes);
+            for error in &report.errors {
+                println!("❌ {}", error);
+            }
+            if !report.errors.is_empty() {
+                exit_failed();
+            }
+        }
+    }
+    Ok(())
+}
+
+pub fn run_sources(action: SourcesAction, json: bool) -> anyhow::Result<()> {
+    use sms_scraper::pipeline::ingestion::cadence::source_schedules;
+
+    match action {
+        SourcesAction::List { registry_dir, data_root } => {
+            let now = chrono::Utc::now();
+            let schedules = source_schedules(std::path::Path::new(&registry_dir), std::path::Path::new(&data_root), now)?;
+            if json {
+                return print_json(&serde_json::json!({ "command": "sources list", "sources": schedules }));
+            }
+            println!("📋 {} sources in {}", schedules.len(), registry_dir);
+            for s in &schedules {
+                let last = s.last_fetched_at.map(|t| t.to_rfc3339()).unwrap_or_else(|| "never".to_string());
+                let next = match s.next_eligible_at {
+                    Some(t) if t <= now => "now".to_string(),
+                    Some(t) => t.to_rfc3339(),
+                    None => "never".to_string(),
+                };
+                let state = if s.enabled { "✅" } else { "⏸️ " };
+                println!("{} {:<20} {:<40} last: {:<25} next: {}", state, s.source_id, s.cadence, last, next);
+            }
+        }
+        SourcesAction::Describe { source_id, registry_dir, data_root } => {
+            use sms_scraper::pipeline::ingestion::cadence::CadencePolicy;
+            use sms_scraper::pipeline::ingestion::ingest_meta::IngestMeta;
+            use sms_scraper::pipeline::ingestion::quota::quota_status;
+            use sms_scraper::pipeline::ingestion::registry::load_source_spec;
+
+            let path = std::path::Path::new(&registry_dir).join(format!("{}.json", source_id));
+            let spec = load_source_spec(&path).map_err(|e| anyhow::anyhow!("invalid registry entry {}: {}", path.display(), e))?;
+            let meta = IngestMeta::open_at_root(&data_root)?;
+            let now = chrono::Utc::now();
+
+            let last = meta.get_last_fetched_at(&spec.source_id)?.and_then(|ts| chrono::DateTime::from_timestamp(ts, 0));
+            let cadence = CadencePolicy::from_spec(spec.cadence.as_ref());
+            let fetch_status = meta.get_fetch_status(&spec.source_id)?;
+            let quota = quota_status(&meta, &spec.source_id, spec.quota.as_ref(), now)?;
+            if json {
+                let (cadence, next_fetch, cadence_error) = match &cadence {
+                    Ok(policy) => (Some(policy.describe()), policy.next_eligible(last, now), None),
+                    Err(e) => (None, None, Some(e.to_string())),
+                };
+                return print_json(&serde_json::json!({
+                    "command": "sources describe",
+                    "source_id": spec.source_id,
+                    "enabled": spec.enabled,
+                    "endpoints": spec.endpoints.iter().map(|ep| serde_json::json!({ "method": ep.method, "url": ep.url })).collect::<Vec<_>>(),
+                    "rate_limits": {
+                        "requests_per_min": spec.rate_limits.requests_per_min,
+                        "bytes_per_min": spec.rate_limits.bytes_per_min,
+                        "concurrency": spec.rate_limits.concurrency,
+                    },
+                    "cadence": cadence,
+                    "cadence_error": cadence_error,
+                    "next_fetch_at": next_fetch,
+                    "last_fetched_at": last,
+                    "consecutive_failures": fetch_status.as_ref().map(|s| s.consecutive_failures),
+                    "last_error": fetch_status.as_ref().and_then(|s| s.last_error.clone()),
+                    "quota": quota,
+                    "quota_exceeded": quota.exceeded_reason(&spec.source_id),
+                }));
+            }
+
+            println!("📋 {} ({})", spec.source_id, if spec.enabled { "enabled" } else { "disabled" });
+            for ep in &spec.endpoints {
+                println!("   🔗 {} {}", ep.method, ep.url);
+            }
+            let limits = &spec.rate_limits;
+            let show = |v: Option<u64>| v.map(|v| v.to_string()).unwrap_or_else(|| "-".to_string());
+            println!(
+                "   ⏱️  Rate limits: {} req/min, {} bytes/min, concurrency {}",
+                show(limits.requests_per_min),
+                show(limits.bytes_per_min),
+                show(limits.concurrency.map(u64::from)),
+            );
+
+            match cadence {
+                Ok(policy) => {
+                    let next = match policy.next_eligible(last, now) {
+                        Some(t) if t <= now => "now".to_string(),
+                        Some(t) => t.to_rfc3339(),
+                        None => "never".to_string(),
+                    };
+                    println!("   📅 Cadence: {} (next fetch: {})", policy.describe(), next);
+                }
+                Err(e) => println!("   📅 Cadence: invalid: {}", e),
+            }
+            println!("   🕒 Last fetched: {}", last.map(|t| t.to_rfc3339()).unwrap_or_else(|| "never".to_string()));
+            if let Some(status) = fetch_status {
+                println!("   🔁 Consecutive failures: {}", status.consecutive_failures);
+                if let Some(err) = status.last_error {
+                    println!("   ❌ Last error: {}", err);
+                }
+            }
+
+            let budget = |used: u64, limit: Option<u64>, remaining: Option<u64>| match (limit, remaining) {
+                (Some(limit), Some(remaining)) => format!("{} of {} ({} left)", used, limit, remaining),
+                _ => format!("{} (no limit)", used),
+            };
+            println!("   📦 Usage in {}:", quota.month);
+            println!("      requests: {}", budget(quota.requests, quota.monthly_requests, quota.remaining_requests()));
+            println!("      bytes:    {}", budget(quota.bytes, quota.monthly_bytes, quota.remaining_bytes()));
+            if let Some(reason) = quota.exceeded_reason(&spec.source_id) {
+                println!("   🚫 {}", reason);
+            }
+        }
+    }
+    Ok(())
+}
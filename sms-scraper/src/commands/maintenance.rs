@@ -0,0 +1,234 @@
+//! Tooling around the pipeline: migrations, the doctor, contract tests, the metric lint and
+//! source scaffolding
+
+use sms_core::database::DatabaseManager;
+use sms_core::migrations;
+use sms_scraper::observability::push::PushConfig;
+use super::{print_json, summarize_failure};
+use crate::{ContractAction, MetricsAction, MigrateAction, ScaffoldAction};
+
+pub async fn run_migrate(action: MigrateAction, json: bool) -> anyhow::Result<()> {
+    let db = DatabaseManager::new().await?;
+    match action {
+        MigrateAction::Status => {
+            let status = db.migration_status().await?;
+            if json {
+                return print_json(&serde_json::json!({
+                    "command": "migrate status",
+                    "latest_version": migrations::latest_version(),
+                    "migrations": status.iter().map(|m| serde_json::json!({
+                        "version": m.version,
+                        "name": m.name,
+                        "applied_at": m.applied_at,
+                    })).collect::<Vec<_>>(),
+                }));
+            }
+            println!("📋 Schema migrations (latest v{}):", migrations::latest_version());
+            for m in status {
+                match m.applied_at {
+                    Some(at) => println!("   ✅ {:03}_{} (applied {})", m.version, m.name, at),
+                    None => println!("   ⏳ {:03}_{} (pending)", m.version, m.name),
+                }
+            }
+        }
+        MigrateAction::Up { to } => {
+            let applied = db.migrate_up(to).await?;
+            if json {
+                return print_json(&serde_json::json!({ "command": "migrate up", "applied": applied }));
+            }
+            if applied.is_empty() {
+                println!("✅ Schema already up to date");
+            } else {
+                println!("✅ Applied migrations: {:?}", applied);
+            }
+        }
+        MigrateAction::Down { to, yes } => {
+            let status = db.migration_status().await?;
+            let applied: Vec<u32> = status.iter().filter(|m| m.applied_at.is_some()).map(|m| m.version).collect();
+            let target = match (to, applied.last()) {
+                (Some(v), _) => v,
+                (None, Some(newest)) => newest - 1,
+                (None, None) if json => {
+                    return print_json(&serde_json::json!({ "command": "migrate down", "reverted": [] }));
+                }
+                (None, None) => {
+                    println!("ℹ️  No applied migrations to revert");
+                    return Ok(());
+                }
+            };
+            if !yes {
+                let pending: Vec<u32> = migrations::pending_down(&applied, target).iter().map(|m| m.version).collect();
+                anyhow::bail!("refusing to revert migrations {:?} without --yes; their down scripts drop tables and data", pending);
+            }
+            let reverted = db.migrate_down(target).await?;
+            if json {
+                return print_json(&serde_json::json!({ "command": "migrate down", "reverted": reverted, "version": target }));
+            }
+            println!("↩️  Reverted migrations: {:?} (schema now at v{})", reverted, target);
+        }
+    }
+    Ok(())
+}
+
+/// Print every doctor check and return whether none failed
+pub async fn run_doctor(data_root: &str, registry_dir: &str, push: &PushConfig, json: bool) -> bool {
+    use sms_scraper::app::doctor::{self, CheckStatus};
+    use sms_scraper::infra::parser_factory::DefaultParserFactory;
+
+    if !json {
+        println!("🩺 Checking the scraper environment...");
+    }
+    let mut checks = doctor::check_env_vars(|name| std::env::var(name).ok());
+    checks.push(doctor::check_database().await);
+    checks.extend(doctor::check_registry(std::path::Path::new(registry_dir), &DefaultParserFactory));
+    checks.push(doctor::check_cas(std::path::Path::new(data_root)));
+    checks.push(doctor::check_encryption(|name| std::env::var(name).ok()));
+    if let Some(pushgateway_url) = push.url() {
+        checks.push(doctor::check_pushgateway(pushgateway_url).await);
+    }
+
+    let count = |status| checks.iter().filter(|c| c.status == status).count();
+    let failed = count(CheckStatus::Fail);
+    if json {
+        let _ = print_json(&serde_json::json!({
+            "command": "doctor",
+            "success": failed == 0,
+            "passed": count(CheckStatus::Pass),
+            "warnings": count(CheckStatus::Warn),
+            "failed": failed,
+            "checks": checks,
+        }));
+        return failed == 0;
+    }
+
+    for check in &checks {
+        let marker = match check.status {
+            CheckStatus::Pass => "✅",
+            CheckStatus::Warn => "⚠️ ",
+            CheckStatus::Fail => "❌",
+        };
+        println!("{} {}: {}", marker, check.name, check.detail);
+        if let Some(hint) = &check.hint {
+            println!("   💡 {}", hint);
+        }
+    }
+
+    println!("📊 Passed: {}, warnings: {}, failed: {}", count(CheckStatus::Pass), count(CheckStatus::Warn), failed);
+    failed == 0
+}
+
+pub async fn run_contract(action: ContractAction, json: bool) -> anyhow::Result<()> {
+    use sms_scraper::app::contract_test::ContractTestUseCase;
+    use sms_scraper::infra::parser_factory::DefaultParserFactory;
+
+    match action {
+        ContractAction::Test { source_id, registry_dir, fixtures_dir, junit } => {
+            let use_case = ContractTestUseCase::new(Box::new(DefaultParserFactory));
+            let report = match use_case.run(&source_id, &registry_dir, &fixtures_dir).await {
+                Ok(report) => report,
+                Err(e) => {
+                    summarize_failure(json, "contract test", &format!("Contract test for {} failed to run: {}", source_id, e));
+                }
+            };
+            let junit = junit.unwrap_or_else(|| std::path::Path::new("contract-reports").join(format!("{}.xml", source_id)));
+            if let Some(parent) = junit.parent().filter(|p| !p.as_os_str().is_empty()) {
+                std::fs::create_dir_all(parent)?;
+            }
+            std::fs::write(&junit, report.to_junit_xml())?;
+
+            if json {
+                print_json(&serde_json::json!({
+                    "command": "contract test",
+                    "success": report.passed(),
+                    "junit": junit,
+                    "report": report,
+                }))?;
+            } else {
+                println!("📜 Contract test for {} ({})", report.source_id, report.parse_plan);
+                for case in &report.cases {
+                    match &case.failure {
+                        None => println!("   ✅ {}: {}", case.fixture, case.invariant),
+                        Some(failure) => {
+                            println!("   ❌ {}: {}", case.fixture, case.invariant);
+                            for line in failure.lines() {
+                                println!("      {}", line);
+                            }
+                        }
+                    }
+                }
+                println!("📄 JUnit report: {}", junit.display());
+            }
+            if !report.passed() {
+                // Non-zero exit so CI can gate on source contracts
+                std::process::exit(1);
+            }
+        }
+    }
+    Ok(())
+}
+
+pub fn run_metrics(action: MetricsAction, json: bool) -> anyhow::Result<()> {
+    use sms_scraper::observability::metrics::lint;
+
+    match action {
+        MetricsAction::Lint => {
+            let report = lint::lint();
+            if json {
+                print_json(&serde_json::json!({
+                    "command": "metrics lint",
+                    "success": report.passed(),
+                    "report": report,
+                }))?;
+            } else {
+                println!("📏 Linted {} metrics", report.checked);
+                for issue in &report.grandfathered {
+                    println!("   ⚠️  {} [{}]: {} (grandfathered)", issue.metric, issue.rule, issue.message);
+                }
+                for issue in &report.issues {
+                    println!("   ❌ {} [{}]: {}", issue.metric, issue.rule, issue.message);
+                }
+                if report.passed() {
+                    println!("✅ Metric names are consistent");
+                }
+            }
+            if !report.passed() {
+                // Non-zero exit so CI fails on metric drift
+                std::process::exit(1);
+            }
+        }
+    }
+    Ok(())
+}
+
+pub fn run_scaffold(action: ScaffoldAction, json: bool) -> anyhow::Result<()> {
+    use sms_scraper::app::scaffold::{scaffold_source, SourceScaffold};
+    use sms_scraper::infra::parser_factory::DefaultParserFactory;
+
+    match action {
+        ScaffoldAction::Source { id, parser, url, name, root } => {
+            let scaffold = SourceScaffold { source_id: id.clone(), parser, url, name };
+            let report = match scaffold_source(&root, &scaffold, &DefaultParserFactory) {
+                Ok(report) => report,
+                Err(e) => summarize_failure(json, "scaffold source", &format!("Scaffolding {} failed: {}", id, e)),
+            };
+            if json {
+                return print_json(&serde_json::json!({
+                    "command": "scaffold source",
+                    "source_id": id,
+                    "created": report.created,
+                    "updated": report.updated,
+                }));
+            }
+            println!("🧱 Scaffolded source {}:", id);
+            for path in &report.created {
+                println!("   ➕ {}", path.display());
+            }
+            for path in &report.updated {
+                println!("   ✏️  {}", path.display());
+            }
+            println!("💡 Next: capture a payload into the fixture, map its fields in the normalizer, then run");
+            println!("   cargo run --bin sms-scraper -- full-pipeline --source-id {} --bypass-cadence", id);
+        }
+    }
+    Ok(())
+}
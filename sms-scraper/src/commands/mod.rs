@@ -0,0 +1,76 @@
+//! The subcommands' bodies. `main.rs` parses arguments and dispatches here; the helpers
+//! below keep every command's human and `--json` output consistent.
+
+pub mod catalog;
+pub mod debug;
+pub mod ingest;
+pub mod maintenance;
+pub mod pipeline;
+pub mod stages;
+
+use std::sync::Arc;
+
+use sms_core::storage::database::DatabaseStorage;
+use sms_core::storage::traits::Storage;
+use sms_core::storage::InMemoryStorage;
+use sms_scraper::observability::shutdown_tracing;
+use sms_scraper::pipeline::FullPipelineOrchestrator;
+
+use crate::StorageMode;
+
+/// Write one JSON document to stdout for `--json` callers
+pub fn print_json<T: serde::Serialize + ?Sized>(value: &T) -> anyhow::Result<()> {
+    println!("{}", serde_json::to_string_pretty(value)?);
+    Ok(())
+}
+
+/// The storage a command runs against
+pub async fn open_storage(mode: StorageMode) -> anyhow::Result<Arc<dyn Storage>> {
+    Ok(match mode {
+        StorageMode::Database => Arc::new(DatabaseStorage::new().await?),
+        StorageMode::Memory => Arc::new(InMemoryStorage::new()),
+    })
+}
+
+/// Report a command that could not run: a JSON summary with `--json`, the usual ❌ line
+/// otherwise. Exits non-zero so scripts and CI see the failure.
+pub fn summarize_failure(json: bool, command: &str, error: &str) -> ! {
+    if json {
+        let _ = print_json(&serde_json::json!({ "command": command, "success": false, "error": error }));
+    } else {
+        println!("❌ {}", error);
+    }
+    exit_failed()
+}
+
+/// Exit non-zero after a command that ran but reported failures
+pub fn exit_failed() -> ! {
+    shutdown_tracing();
+    std::process::exit(1)
+}
+
+/// The orchestrator behind the single-stage commands, or a reported failure if it can't start
+pub async fn stage_orchestrator(data_root: &str, json: bool, command: &str) -> FullPipelineOrchestrator {
+    match FullPipelineOrchestrator::new(data_root).await {
+        Ok(orchestrator) => orchestrator,
+        Err(e) => {
+            tracing::error!("Failed to create pipeline orchestrator: {}", e);
+            summarize_failure(json, command, &format!("Failed to initialize pipeline: {}", e))
+        }
+    }
+}
+
+/// Report a single-source stage command: a JSON summary with `--json`, the ✅ line otherwise
+pub fn report_stage(json: bool, command: &str, stage: &str, source_id: &str, result: anyhow::Result<()>) -> anyhow::Result<()> {
+    match result {
+        Ok(()) if json => print_json(&serde_json::json!({ "command": command, "source_id": source_id, "success": true })),
+        Ok(()) => {
+            println!("✅ {} completed successfully for {}", stage, source_id);
+            Ok(())
+        }
+        Err(e) => {
+            tracing::error!("{} failed for {}: {}", stage, source_id, e);
+            summarize_failure(json, command, &format!("{} failed for {}: {}", stage, source_id, e))
+        }
+    }
+}
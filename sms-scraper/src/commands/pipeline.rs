@@ -0,0 +1,455 @@
+//! Whole-pipeline runs: ingestion, full and modular pipelines, reprocessing and gateway rounds
+
+use sms_scraper::infra::sink_registry::SinksConfig;
+use sms_scraper::pipeline::{PipelineConfig, PipelineOrchestrator, PipelineRunner, ReprocessOptions, RunOptions};
+use sms_scraper::pipeline::ingestion::registry_watch;
+use sms_scraper::pipeline::ingestion::consumer_lag::{spawn_consumer_lag_monitor, ConsumerLagConfig};
+use sms_scraper::pipeline::ingestion::gateway_all::{ingest_all, GatewayAllLimits, SourceIngestStatus};
+use sms_scraper::pipeline::ingestion::ingest_common::IngestOptions;
+use sms_scraper::pipeline::ingestion::registry::SourceSpecV1;
+use sms_scraper::pipeline::processing::duplicate_suppression::DuplicateSuppressionConfig;
+use sms_scraper::pipeline::processing::quality_gate::QualityGateConfig;
+use sms_scraper::pipeline::streaming::StageConcurrency;
+use super::{exit_failed, print_json, summarize_failure};
+use crate::{FullPipelineArgs, StorageMode};
+
+/// Ingest each of the comma-separated `apis`
+pub async fn run_ingester(apis: String, bypass_cadence: bool, data_root: String, json: bool) -> anyhow::Result<()> {
+    if !json {
+        println!("🕷️  Starting SMS scraper ingestion for APIs: {}", apis);
+    }
+
+    let options = RunOptions { bypass_cadence, ..Default::default() };
+    if bypass_cadence && !json {
+        println!("🚀 Bypassing cadence restrictions");
+    }
+
+    // Create runner and run ingestion
+    let runner = PipelineRunner::new(&data_root).await?;
+
+    // Parse the comma-separated API list
+    let api_list: Vec<&str> = apis.split(',').map(|s| s.trim()).collect();
+
+    let mut outcomes = Vec::new();
+    for api_name in api_list {
+        if !json {
+            println!("🔄 Running ingestion for: {}", api_name);
+        }
+        let outcome = runner.run_ingestion(api_name, &options).await;
+        if !json {
+            match &outcome {
+                Err(e) => eprintln!("❌ Ingestion failed for {}: {}", api_name, e),
+                Ok(()) => println!("✅ Ingestion completed for: {}", api_name),
+            }
+        }
+        outcomes.push(serde_json::json!({
+            "api": api_name,
+            "success": outcome.is_ok(),
+            "error": outcome.err().map(|e| e.to_string()),
+        }));
+    }
+    let success = outcomes.iter().all(|o| o["success"] == true);
+    if json {
+        print_json(&serde_json::json!({ "command": "ingester", "success": success, "results": outcomes }))?;
+    }
+    if !success {
+        exit_failed();
+    }
+    Ok(())
+}
+
+/// Run one source through every stage, reporting the run
+pub async fn run_full_pipeline(args: FullPipelineArgs, json: bool) -> anyhow::Result<()> {
+    let FullPipelineArgs {
+        source_id,
+        bypass_cadence,
+        conflator,
+        duplicate_window_days,
+        duplicate_merge_policy,
+        stage_workers,
+        stage_channel_capacity,
+        quality_gate,
+        sinks_config,
+        shadow_quality_gate,
+        storage_mode,
+        data_root,
+    } = args;
+    let in_memory = storage_mode == StorageMode::Memory;
+    if !json {
+        println!("🔄 Running full pipeline for source: {}", source_id);
+    }
+
+    let options = RunOptions {
+        bypass_cadence,
+        conflator: conflator.into_config(),
+        duplicates: DuplicateSuppressionConfig {
+            window_days: duplicate_window_days,
+            merge_policy: duplicate_merge_policy,
+        },
+        concurrency: StageConcurrency { channel_capacity: stage_channel_capacity, ..stage_workers },
+        quality_gate: match quality_gate {
+            Some(path) => QualityGateConfig::from_path(path)?,
+            None => QualityGateConfig::default(),
+        },
+        quality_shadow: match shadow_quality_gate {
+            Some(path) => Some(QualityGateConfig::from_path(path)?),
+            None => QualityGateConfig::shadow_candidate_from_env()?,
+        },
+        sinks: sinks_config.map(SinksConfig::load).transpose()?,
+    };
+    if bypass_cadence && !json {
+        println!("🚀 Bypassing cadence restrictions");
+    }
+
+    // Create the pipeline runner
+    let runner = match storage_mode {
+        StorageMode::Memory => PipelineRunner::in_memory(),
+        StorageMode::Database => PipelineRunner::new(&data_root).await,
+    };
+    match runner {
+        Ok(runner) => {
+            // Process the source through the complete pipeline
+            match runner.run_full(&source_id, &options).await {
+                Ok(result) if json => {
+                    print_json(&serde_json::json!({
+                        "command": "full-pipeline",
+                        "success": result.is_success(),
+                        "success_rate": result.success_rate(),
+                        "duration_ms": result.duration().num_milliseconds(),
+                        "report": result,
+                    }))?;
+                    if !result.is_success() {
+                        exit_failed();
+                    }
+                }
+                Ok(result) => {
+                    println!("📊 Pipeline Results for {} (run {}):", result.source_id, result.run_id);
+                    println!("   📁 Total items: {}", result.total_items);
+                    println!("   ✅ Processed: {}", result.processed_items);
+                    println!("   ❌ Failed: {}", result.failed_items);
+                    println!("   🔎 Parsed: {}", result.records_parsed);
+                    println!("   📚 Cataloged: {}", result.records_cataloged);
+                    println!("   📈 Success rate: {:.1}%", result.success_rate());
+                    println!("   ⏱️  Duration: {}ms", result.duration().num_milliseconds());
+                    for load in &result.assets {
+                        println!(
+                            "   📦 {} from {}: {:.1}ms{}",
+                            load.asset,
+                            load.source,
+                            load.duration_ms,
+                            if load.cached { " (cached)" } else { "" }
+                        );
+                    }
+
+                    if let Some(shadow) = &result.quality_shadow {
+                        println!(
+                            "   🫥 Shadow quality gate {}: {:.1}% would be quarantined vs {:.1}% now ({} newly quarantined, {} newly accepted of {})",
+                            shadow.candidate_rule_version,
+                            shadow.candidate_quarantine_rate() * 100.0,
+                            shadow.active_quarantine_rate() * 100.0,
+                            shadow.newly_quarantined,
+                            shadow.newly_accepted,
+                            shadow.records
+                        );
+                    }
+
+                    if !result.suppressed_duplicates.is_empty() {
+                        println!("   🪞 Suppressed duplicates: {}", result.suppressed_duplicates.len());
+                        for dup in &result.suppressed_duplicates {
+                            println!(
+                                "      - {} on {} → kept \"{}\" on {} ({})",
+                                dup.title,
+                                dup.event_day,
+                                dup.kept_title,
+                                dup.kept_event_day,
+                                if dup.merged { "merged" } else { "unchanged" }
+                            );
+                        }
+                    }
+
+                    if !result.errors.is_empty() {
+                        println!("   🚨 Errors encountered:");
+                        for error in &result.errors {
+                            println!("      - {}", error);
+                        }
+                    }
+
+                    if result.is_success() && in_memory {
+                        println!("✅ Pipeline completed successfully - entities were kept in memory only");
+                    } else if result.is_success() {
+                        println!("✅ Pipeline completed successfully - entities created/updated in database");
+                    } else {
+                        println!("⚠️  Pipeline completed with errors - check logs for details");
+                        exit_failed();
+                    }
+                }
+                Err(e) => {
+                    tracing::error!("Pipeline processing failed for {}: {}", source_id, e);
+                    summarize_failure(json, "full-pipeline", &format!("Pipeline failed for {}: {}", source_id, e));
+                }
+            }
+        }
+        Err(e) => {
+            tracing::error!("Failed to create pipeline orchestrator: {}", e);
+            summarize_failure(json, "full-pipeline", &format!("Failed to initialize pipeline: {}", e));
+        }
+    }
+    Ok(())
+}
+
+/// Re-run a source's stored payloads through the pipeline in batches
+pub async fn run_reprocess_all(
+    source_id: String,
+    since: Option<chrono::NaiveDate>,
+    limit: Option<usize>,
+    batch_size: usize,
+    data_root: String,
+    json: bool,
+) -> anyhow::Result<()> {
+    if !json {
+        println!("🔄 Reprocessing ALL raw data for source: {}", source_id);
+    }
+    let reprocess = ReprocessOptions { since, limit, batch_size };
+    let runner = match PipelineRunner::new(&data_root).await {
+        Ok(runner) => runner,
+        Err(e) => {
+            tracing::error!("Failed to create pipeline runner: {}", e);
+            summarize_failure(json, "reprocess-all", &format!("Failed to initialize pipeline: {}", e));
+        }
+    };
+    let on_batch = |progress: &sms_scraper::pipeline::ReprocessProgress| {
+        if !json {
+            println!(
+                "   📦 Batch {}/{}: {}/{} items, {} failed, {} records cataloged",
+                progress.batch,
+                progress.batches,
+                progress.items_done,
+                progress.items_total,
+                progress.failed_items,
+                progress.records_cataloged
+            );
+        }
+    };
+    match runner.run_reprocess(&source_id, &RunOptions::default(), &reprocess, on_batch).await {
+        Ok(result) if json => {
+            print_json(&serde_json::json!({
+                "command": "reprocess-all",
+                "success": result.is_success(),
+                "duration_ms": result.duration().num_milliseconds(),
+                "report": result,
+            }))?;
+            if !result.is_success() {
+                exit_failed();
+            }
+        }
+        Ok(result) => {
+            println!("📊 Reprocess results for {} (run {}):", result.source_id, result.run_id);
+            println!("   📁 Total items: {}", result.total_items);
+            println!("   ✅ Processed: {}", result.processed_items);
+            println!("   ❌ Failed: {}", result.failed_items);
+            println!("   📚 Cataloged: {}", result.records_cataloged);
+            println!("   ⏱️  Duration: {}ms", result.duration().num_milliseconds());
+            for error in &result.errors {
+                println!("      - {}", error);
+            }
+            if !result.is_success() {
+                exit_failed();
+            }
+        }
+        Err(e) => {
+            tracing::error!("Reprocessing failed for {}: {}", source_id, e);
+            summarize_failure(json, "reprocess-all", &format!("Reprocessing failed for {}: {}", source_id, e));
+        }
+    }
+    Ok(())
+}
+
+/// Delete one venue and its events from the database
+pub async fn run_clear_db(venue_slug: Option<String>, json: bool) -> anyhow::Result<()> {
+    use sms_core::database::DatabaseManager;
+
+    let Some(slug) = venue_slug else {
+        if !json {
+            println!("🗑️  Clearing ALL data from the database...");
+            println!("💡 Tip: Use --venue-slug to delete data for a specific venue only");
+            println!("   Use the database management tools directly if needed");
+        }
+        summarize_failure(json, "clear-db", "Full database clear not implemented in this command");
+    };
+    if !json {
+        println!("🗑️  Deleting data for venue '{}'...", slug);
+        println!("⚠️  WARNING: This will permanently delete the venue and all its events!");
+    }
+
+    // Create a direct database manager instance to access delete methods
+    let db_manager = match DatabaseManager::new().await {
+        Ok(db_manager) => db_manager,
+        Err(e) => {
+            tracing::error!("Failed to create database manager: {}", e);
+            summarize_failure(json, "clear-db", &format!("Failed to connect to database: {}", e));
+        }
+    };
+    // Use the simplified delete method from sms-core
+    match db_manager.delete_venue_data(&slug).await {
+        Ok(()) if json => {
+            print_json(&serde_json::json!({ "command": "clear-db", "venue_slug": slug, "success": true }))?;
+        }
+        Ok(()) => {
+            println!("✅ Successfully deleted all data for venue '{}'!", slug);
+        }
+        Err(e) => {
+            tracing::error!("Failed to delete venue data: {}", e);
+            summarize_failure(json, "clear-db", &format!("Failed to delete venue data: {}", e));
+        }
+    }
+    Ok(())
+}
+
+/// Run a source through the step-based pipeline
+pub async fn run_modular_pipeline(source_id: String, parse_only: bool, ingestion_only: bool, json: bool) -> anyhow::Result<()> {
+    if !json {
+        println!("🚀 Running modular pipeline for source: {}", source_id);
+    }
+
+    let orchestrator = match PipelineOrchestrator::new().await {
+        Ok(orchestrator) => orchestrator,
+        Err(e) => {
+            tracing::error!("Failed to create modular pipeline orchestrator: {}", e);
+            summarize_failure(json, "modular-pipeline", &format!("Failed to initialize modular pipeline: {}", e));
+        }
+    };
+    let (mode, config) = if ingestion_only {
+        ("ingestion only", PipelineConfig::parse_only()) // Will create ingestion-only config later
+    } else if parse_only {
+        ("parse only", PipelineConfig::parse_only())
+    } else {
+        ("full modular pipeline", PipelineConfig::default_full_pipeline())
+    };
+    if !json {
+        println!("🔄 Running {}", mode);
+    }
+
+    match orchestrator.run_pipeline(config, &source_id).await {
+        Ok(result) if json => {
+            print_json(&serde_json::json!({
+                "command": "modular-pipeline",
+                "source_id": source_id,
+                "success": result.total_failed == 0,
+                "total_processed": result.total_processed,
+                "total_failed": result.total_failed,
+                "duration_ms": result.duration().map(|d| d.num_milliseconds()),
+            }))?;
+            if result.total_failed > 0 {
+                exit_failed();
+            }
+        }
+        Ok(result) => {
+            println!("✅ Modular pipeline completed successfully!");
+            println!("📊 Total processed: {}, failed: {}", result.total_processed, result.total_failed);
+            if let Some(duration) = result.duration() {
+                println!("⏱️ Duration: {}ms", duration.num_milliseconds());
+            }
+            if result.total_failed > 0 {
+                exit_failed();
+            }
+        }
+        Err(e) => {
+            tracing::error!("Modular pipeline failed for {}: {}", source_id, e);
+            summarize_failure(json, "modular-pipeline", &format!("Modular pipeline failed for {}: {}", source_id, e));
+        }
+    }
+    Ok(())
+}
+
+/// Ingest every enabled registry source once, or every `every` seconds until interrupted
+pub async fn run_gateway_all(
+    bypass_cadence: bool,
+    concurrency: usize,
+    per_host: usize,
+    report: Option<std::path::PathBuf>,
+    every: Option<u64>,
+    data_root: String,
+    json: bool,
+) -> anyhow::Result<()> {
+    if bypass_cadence && !json {
+        println!("🚀 Bypassing cadence restrictions");
+    }
+    let options = IngestOptions { data_root: data_root.into(), bypass_cadence, ..IngestOptions::default() };
+    let limits = GatewayAllLimits { concurrency, per_host };
+    match every {
+        None => {
+            let snapshot = registry_watch::shared()?.snapshot();
+            run_gateway_round(&snapshot.specs, limits, &options, report.as_deref(), json).await?;
+        }
+        Some(secs) => {
+            let registry = registry_watch::shared()?;
+            let _watcher = registry.watch()?;
+            let _lag = spawn_consumer_lag_monitor("data", ConsumerLagConfig::from_env());
+            loop {
+                let snapshot = registry.snapshot();
+                if !json {
+                    println!("🔁 Registry generation {}", snapshot.generation);
+                }
+                run_gateway_round(&snapshot.specs, limits, &options, report.as_deref(), json).await?;
+                tokio::select! {
+                    _ = tokio::time::sleep(std::time::Duration::from_secs(secs)) => {}
+                    _ = tokio::signal::ctrl_c() => break,
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Ingest `sources` once, print the table and summary, and write the JSON report
+async fn run_gateway_round(
+    specs: &[SourceSpecV1],
+    limits: GatewayAllLimits,
+    options: &IngestOptions,
+    report: Option<&std::path::Path>,
+    json: bool,
+) -> anyhow::Result<()> {
+    if !json {
+        println!(
+            "🕷️  Ingesting {} enabled sources (concurrency {}, per host {})",
+            specs.iter().filter(|spec| spec.enabled).count(),
+            limits.concurrency,
+            limits.per_host
+        );
+    }
+
+    let result = ingest_all(specs, limits, options).await;
+    let report_path = report.map(std::path::Path::to_path_buf).unwrap_or_else(|| {
+        std::path::Path::new("data/reports")
+            .join(format!("gateway_all_{}.json", result.started_at.format("%Y%m%dT%H%M%SZ")))
+    });
+    if let Some(parent) = report_path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(&report_path, serde_json::to_string_pretty(&result)?)?;
+    for failed in result.sources.iter().filter(|s| s.status == SourceIngestStatus::Failed) {
+        tracing::error!("Gateway ingestion failed for {}: {}", failed.source_id, failed.error.as_deref().unwrap_or(""));
+    }
+    if json {
+        return print_json(&serde_json::json!({
+            "command": "gateway-all",
+            "report_path": report_path,
+            "result": result,
+        }));
+    }
+
+    print!("{}", result.render_table());
+    println!(
+        "📊 Ingested: {}, deduplicated: {}, cadence skipped: {}, over quota: {}, failed: {}",
+        result.count(SourceIngestStatus::Ingested),
+        result.count(SourceIngestStatus::Deduplicated),
+        result.count(SourceIngestStatus::CadenceSkipped),
+        result.count(SourceIngestStatus::QuotaExceeded),
+        result.count(SourceIngestStatus::Failed),
+    );
+    for failed in result.sources.iter().filter(|s| s.status == SourceIngestStatus::Failed) {
+        println!("   ❌ {}: {}", failed.source_id, failed.error.as_deref().unwrap_or(""));
+    }
+    println!("📁 Report: {}", report_path.display());
+    Ok(())
+}
@@ -0,0 +1,262 @@
+//! Single-stage commands (parse through catalog) and `parse` subcommands
+
+use super::{exit_failed, print_json, report_stage, stage_orchestrator, summarize_failure};
+use crate::{ConflatorArgs, ParseAction};
+
+/// Parse one source's raw payloads
+pub async fn run_parse(source: Option<String>, data_root: String, json: bool) -> anyhow::Result<()> {
+    if !json {
+        println!("📄 Step 4: Parse - Converting raw data to neutral records");
+    }
+    let Some(source_id) = source else {
+        summarize_failure(json, "parse", "no source given; pass --source <source_name> (e.g. blue_moon, barboza, neumos)");
+    };
+    let orchestrator = stage_orchestrator(&data_root, json, "parse").await;
+    report_stage(json, "parse", "Parse", &source_id, orchestrator.run_parse_for_source(&source_id).await)?;
+    Ok(())
+}
+
+/// Normalize one source's parsed records
+pub async fn run_normalize(source: Option<String>, data_root: String, json: bool) -> anyhow::Result<()> {
+    if !json {
+        println!("🔧 Step 5: Normalize - Standardizing parsed data");
+    }
+    let Some(source_id) = source else {
+        summarize_failure(json, "normalize", "no source given; pass --source <source_name> (e.g. blue_moon, barboza, neumos)");
+    };
+    let orchestrator = stage_orchestrator(&data_root, json, "normalize").await;
+    report_stage(json, "normalize", "Normalize", &source_id, orchestrator.run_normalize_for_source(&source_id).await)?;
+    Ok(())
+}
+
+/// Run one source's normalized records through the quality gate
+pub async fn run_quality_gate(sources: Option<String>, data_root: String, json: bool) -> anyhow::Result<()> {
+    if !json {
+        println!("🛡️ Step 6: Quality Gate - Validating data quality");
+    }
+    let Some(source_id) = sources else {
+        summarize_failure(json, "quality-gate", "no source given; pass --sources <source_name> (e.g. blue_moon, barboza, neumos)");
+    };
+    let orchestrator = stage_orchestrator(&data_root, json, "quality-gate").await;
+    report_stage(json, "quality-gate", "Quality gate", &source_id, orchestrator.run_quality_gate_for_source(&source_id).await)?;
+    Ok(())
+}
+
+/// Enrich one source's accepted records
+pub async fn run_enrich(source: Option<String>, data_root: String, json: bool) -> anyhow::Result<()> {
+    if !json {
+        println!("🌐 Step 7: Enrich - Adding location and metadata");
+    }
+    let Some(source_id) = source else {
+        summarize_failure(json, "enrich", "no source given; pass --source <source_name> (e.g. blue_moon, barboza, neumos)");
+    };
+    let orchestrator = stage_orchestrator(&data_root, json, "enrich").await;
+    report_stage(json, "enrich", "Enrich", &source_id, orchestrator.run_enrich_for_source(&source_id).await)?;
+    Ok(())
+}
+
+/// Conflate each of the comma-separated `sources`
+pub async fn run_conflation(sources: Option<String>, conflator: ConflatorArgs, data_root: String, json: bool) -> anyhow::Result<()> {
+    let config = conflator.into_config();
+    if !json {
+        println!("🔗 Step 8: Conflation - Resolving duplicate entities");
+        println!(
+            "🔧 Confidence thresholds: venue {}, event {}, artist {} (tie-break: {:?})",
+            config.venue_threshold, config.event_threshold, config.artist_threshold, config.tie_break
+        );
+    }
+    let Some(source_list) = sources else {
+        summarize_failure(json, "conflation", "no sources given; pass --sources <source_names> (e.g. --sources blue_moon,barboza)");
+    };
+    let orchestrator = stage_orchestrator(&data_root, json, "conflation").await;
+    let mut outcomes = Vec::new();
+    for source_id in source_list.split(',').map(|s| s.trim()) {
+        let outcome = orchestrator.run_conflation_for_source(source_id, config.clone()).await;
+        match &outcome {
+            Err(e) => {
+                tracing::error!("Conflation failed for {}: {}", source_id, e);
+                if !json {
+                    println!("❌ Conflation failed for {}: {}", source_id, e);
+                }
+            }
+            Ok(()) if !json => println!("✅ Conflation completed successfully for {}", source_id),
+            Ok(()) => {}
+        }
+        outcomes.push(serde_json::json!({
+            "source_id": source_id,
+            "success": outcome.is_ok(),
+            "error": outcome.err().map(|e| e.to_string()),
+        }));
+    }
+    let success = outcomes.iter().all(|o| o["success"] == true);
+    if json {
+        print_json(&serde_json::json!({ "command": "conflation", "success": success, "results": outcomes }))?;
+    }
+    if !success {
+        exit_failed();
+    }
+    Ok(())
+}
+
+/// Catalog conflated records, optionally validating the graph afterwards
+pub async fn run_catalog(validate_graph: bool, conflator: ConflatorArgs, data_root: String, json: bool) -> anyhow::Result<()> {
+    if !json {
+        println!("📚 Step 9: Catalog - Storing entities in graph database");
+        println!("✅ Validate graph: {}", validate_graph);
+    }
+
+    // For now, catalog blue_moon as example - in future could support --sources parameter
+    let source_id = "blue_moon";
+    let orchestrator = stage_orchestrator(&data_root, json, "catalog").await;
+    let report = match orchestrator.run_catalog_for_source(source_id, validate_graph, conflator.into_config()).await {
+        Ok(report) => report,
+        Err(e) => {
+            tracing::error!("Catalog failed for {}: {}", source_id, e);
+            summarize_failure(json, "catalog", &format!("Catalog failed for {}: {}", source_id, e));
+        }
+    };
+    let clean = report.as_ref().is_none_or(|report| report.is_clean());
+    if json {
+        print_json(&serde_json::json!({
+            "command": "catalog",
+            "source_id": source_id,
+            "success": clean,
+            "graph_validation": report,
+        }))?;
+    } else {
+        println!("✅ Catalog completed successfully for {}", source_id);
+        if let Some(report) = &report {
+            if report.is_clean() {
+                println!("✅ Graph validation passed: {} venues, {} artists, {} events", report.venues, report.artists, report.events);
+            } else {
+                println!("❌ Graph validation found {} violations:", report.violations.len());
+                for (kind, count) in report.counts_by_kind() {
+                    println!("   - {}: {}", kind, count);
+                }
+            }
+        }
+    }
+    if !clean {
+        // Non-zero exit so CI can gate on graph integrity
+        exit_failed();
+    }
+    Ok(())
+}
+
+pub async fn run_parse_action(action: ParseAction, json: bool) -> anyhow::Result<()> {
+    use sms_scraper::app::parse_compare_use_case::ParseCompareUseCase;
+    use sms_scraper::infra::{parser_factory::DefaultParserFactory, payload_store::CasPayloadStore};
+    use sms_scraper::pipeline::ingestion::ingest_log_reader::IngestLogReader;
+    use sms_scraper::pipeline::ingestion::registry::load_source_spec;
+
+    match action {
+        ParseAction::Compare { source_id, against_version, limit, data_root, report } => {
+            let spec_path = std::path::Path::new("registry/sources").join(format!("{}.json", source_id));
+            let spec = load_source_spec(&spec_path)
+                .map_err(|e| anyhow::anyhow!("failed to load registry for {}: {}", source_id, e))?;
+            let Some(plan) = spec.parser_plan() else {
+                summarize_failure(json, "parse compare", &format!("{} does not declare a parser_plan", source_id));
+            };
+            let (baseline_plan, candidate_plan) = (plan.plan_ref(), plan.at_version(against_version).plan_ref());
+            if !json {
+                println!("🔬 Comparing {} against {} for {}", baseline_plan, candidate_plan, source_id);
+            }
+
+            let envelopes = IngestLogReader::new(&data_root).envelopes_for_source(&source_id, limit)?;
+            if envelopes.is_empty() {
+                if json {
+                    return print_json(&serde_json::json!({ "command": "parse compare", "source_id": source_id, "envelopes": 0 }));
+                }
+                println!("📭 No ingested envelopes for {}", source_id);
+                return Ok(());
+            }
+
+            let compare_uc = ParseCompareUseCase::new(Box::new(CasPayloadStore), Box::new(DefaultParserFactory));
+            let result = match compare_uc.compare(&source_id, &envelopes, &baseline_plan, &candidate_plan).await {
+                Ok(result) => result,
+                Err(e) => {
+                    tracing::error!("Parse compare failed for {}: {}", source_id, e);
+                    summarize_failure(json, "parse compare", &format!("Parse compare failed for {}: {}", source_id, e));
+                }
+            };
+
+            let report_path = report.unwrap_or_else(|| {
+                std::path::Path::new(&data_root).join("reports").join(format!(
+                    "parse_compare_{}_{}.json",
+                    source_id,
+                    result.compared_at.format("%Y%m%dT%H%M%SZ")
+                ))
+            });
+            if let Some(parent) = report_path.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+            std::fs::write(&report_path, serde_json::to_string_pretty(&result)?)?;
+            if json {
+                return print_json(&serde_json::json!({
+                    "command": "parse compare",
+                    "source_id": source_id,
+                    "report_path": report_path,
+                    "result": result,
+                }));
+            }
+
+            for e in &result.envelopes {
+                let marker = if e.is_identical() { "✅" } else { "⚠️ " };
+                println!(
+                    "{} {} records {} → {} (+{} -{} ~{} fields)",
+                    marker,
+                    e.envelope_id,
+                    e.baseline_records,
+                    e.candidate_records,
+                    e.only_in_candidate.len(),
+                    e.only_in_baseline.len(),
+                    e.field_diffs.len()
+                );
+                for err in [&e.baseline_error, &e.candidate_error].into_iter().flatten() {
+                    println!("   error={}", err);
+                }
+                for d in e.field_diffs.iter().take(5) {
+                    println!("   {} {}: {:?} → {:?}", d.record_path, d.field, d.baseline, d.candidate);
+                }
+            }
+            println!(
+                "📊 Envelopes: {} ({} changed), records: {} → {}, field diffs: {}",
+                result.envelopes.len(),
+                result.changed_envelopes(),
+                result.baseline_records(),
+                result.candidate_records(),
+                result.field_diffs()
+            );
+            println!("📁 Report: {}", report_path.display());
+        }
+        ParseAction::Log { consumer, max, data_root, source_id, output, normalize, quality_gate, sinks_config } => {
+            use sms_scraper::pipeline::tasks::{parse_run, ParseParams};
+
+            let params = ParseParams {
+                consumer: Some(consumer),
+                max: Some(max),
+                data_root: Some(data_root),
+                output: Some(output),
+                source_id,
+                normalize: Some(normalize),
+                quality_gate: Some(quality_gate),
+                sinks_config,
+            };
+            let summary = parse_run(params).await?;
+            if json {
+                return print_json(&serde_json::json!({ "command": "parse-log", "summary": summary }));
+            }
+            println!(
+                "📄 Parsed {} envelopes (run {}): {} records written, {} filtered out, {} with no records",
+                summary.seen, summary.run_id, summary.written_records, summary.filtered_out, summary.empty_record_envelopes
+            );
+            if !summary.output_file.is_empty() {
+                println!("📁 Output: {}", summary.output_file);
+            }
+            if let Some(envelope_id) = &summary.acked_through {
+                println!("📌 Consumer offset advanced through envelope {}", envelope_id);
+            }
+        }
+    }
+    Ok(())
+}
@@ -44,7 +44,15 @@ pub fn api_name_to_internal(api_name: &str) -> String {
     }
 }
 
-/// Get all supported user-friendly API names
+/// Get all supported user-friendly API names, including venues installed from packs
 pub fn get_supported_apis() -> Vec<&'static str> {
-    vec![BLUE_MOON_API, SEA_MONSTER_API, DARRELLS_TAVERN_API, KEXP_API, BARBOZA_API, NEUMOS_API, CONOR_BYRNE_API]
+    #[cfg_attr(not(feature = "scraping"), allow(unused_mut))]
+    let mut apis = vec![BLUE_MOON_API, SEA_MONSTER_API, DARRELLS_TAVERN_API, KEXP_API, BARBOZA_API, NEUMOS_API, CONOR_BYRNE_API];
+    #[cfg(feature = "scraping")]
+    for api in crate::apis::venue_pack::venues().api_names() {
+        if !apis.contains(&api) {
+            apis.push(api);
+        }
+    }
+    apis
 }
@@ -1,21 +1,32 @@
 use crate::app::ports::CadencePort;
+use crate::pipeline::ingestion::cadence::CadencePolicy;
+use crate::pipeline::ingestion::registry_watch;
 use async_trait::async_trait;
 
-pub struct IngestMetaCadence;
+pub struct IngestMetaCadence {
+    /// Report every source as due
+    pub bypass: bool,
+}
 
 #[async_trait]
 impl CadencePort for IngestMetaCadence {
     async fn should_run(&self, source_id: &str, min_interval_secs: i64) -> Result<bool, String> {
         let root = std::path::PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("data");
-        let bypass = std::env::var("SMS_BYPASS_CADENCE").map(|v| v == "1" || v.eq_ignore_ascii_case("true")).unwrap_or(false);
-        if bypass { return Ok(true); }
+        if self.bypass { return Ok(true); }
         let meta = crate::pipeline::ingestion::ingest_meta::IngestMeta::open_at_root(&root).map_err(|e| e.to_string())?;
-        let now = chrono::Utc::now().timestamp();
-        if let Some(last) = meta.get_last_fetched_at(source_id).map_err(|e| e.to_string())? {
-            Ok(now - last >= min_interval_secs)
-        } else {
-            Ok(true)
-        }
+        // A cron cadence in the registry takes precedence over the caller's interval
+        let cadence = registry_watch::shared()
+            .ok()
+            .and_then(|registry| registry.snapshot().get(source_id).and_then(|spec| spec.cadence.clone()));
+        let policy = match cadence {
+            Some(cadence) => CadencePolicy::from_spec(Some(&cadence))?,
+            None => CadencePolicy::interval(min_interval_secs),
+        };
+        let last = meta
+            .get_last_fetched_at(source_id)
+            .map_err(|e| e.to_string())?
+            .and_then(|ts| chrono::DateTime::from_timestamp(ts, 0));
+        Ok(policy.is_due(last, chrono::Utc::now()))
     }
     async fn mark_run(&self, source_id: &str) -> Result<(), String> {
         let root = std::path::PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("data");
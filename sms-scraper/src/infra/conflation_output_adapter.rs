@@ -1,6 +1,6 @@
 use async_trait::async_trait;
 use serde_json;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use tokio::fs::OpenOptions;
 use tokio::io::AsyncWriteExt;
 use tracing::debug;
@@ -54,7 +54,7 @@ impl ConflationOutputAdapter {
     }
 
     /// Ensure the output directory exists
-    async fn ensure_output_directory(&self, file_path: &PathBuf) -> anyhow::Result<()> {
+    async fn ensure_output_directory(&self, file_path: &Path) -> anyhow::Result<()> {
         if let Some(parent_dir) = file_path.parent() {
             if !parent_dir.exists() {
                 tokio::fs::create_dir_all(parent_dir).await
@@ -109,17 +109,6 @@ impl ConflationOutputPort for ConflationOutputAdapter {
     }
 }
 
-// Helper trait to convert EntityType to string (this would normally be an impl block)
-impl ToString for crate::pipeline::processing::conflation::EntityType {
-    fn to_string(&self) -> String {
-        match self {
-            crate::pipeline::processing::conflation::EntityType::Venue => "venue".to_string(),
-            crate::pipeline::processing::conflation::EntityType::Event => "event".to_string(),
-            crate::pipeline::processing::conflation::EntityType::Artist => "artist".to_string(),
-        }
-    }
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -150,6 +139,12 @@ mod tests {
             neighborhood: None,
             show_venue: true,
             created_at: Utc::now(),
+            attributions: Vec::new(),
+            provisional: false,
+            aliases: Vec::new(),
+            age_restriction: None,
+            accessibility_notes: None,
+            moderation: None,
         };
 
         let normalized_record = NormalizedRecord {
@@ -160,6 +155,11 @@ mod tests {
                 payload_ref: "test_payload".to_string(),
                 record_path: "$.venues[0]".to_string(),
                 normalized_at: Utc::now(),
+                attribution: None,
+                change: None,
+                record_key: None,
+                endpoint_id: None,
+                external_id: None,
             },
             normalization: NormalizationMetadata {
                 confidence: 1.0,
@@ -200,6 +200,7 @@ mod tests {
                 admin_boundaries: Some("test_v1".to_string()),
                 spatial_grid: Some("test_v1".to_string()),
                 poi_data: Some("test_v1".to_string()),
+                neighborhoods: None,
             },
             strategy: "test_enrichment".to_string(),
             confidence: 0.9,
@@ -234,6 +235,7 @@ mod tests {
                 key_attributes: vec!["name".to_string(), "location".to_string()],
                 deduplication_signature: Some("test_signature".to_string()),
             },
+            config: None,
         };
 
         ConflatedRecord {
@@ -0,0 +1,141 @@
+use crate::app::ports::{DeadLetterEntry, DeadLetterPort};
+use async_trait::async_trait;
+use std::fs::{self, File, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+/// NDJSON-backed dead-letter queue for envelopes that failed to parse.
+/// Lives at `<data_root>/dlq/parse_failures.ndjson`, one entry per envelope.
+pub struct FileDeadLetterStore {
+    path: PathBuf,
+    lock: Mutex<()>,
+}
+
+impl FileDeadLetterStore {
+    pub fn new(data_root: &Path) -> Self {
+        Self {
+            path: data_root.join("dlq").join("parse_failures.ndjson"),
+            lock: Mutex::new(()),
+        }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    fn read_all(&self) -> std::io::Result<Vec<DeadLetterEntry>> {
+        if !self.path.exists() {
+            return Ok(Vec::new());
+        }
+        let mut entries = Vec::new();
+        for line in BufReader::new(File::open(&self.path)?).lines() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            match serde_json::from_str::<DeadLetterEntry>(&line) {
+                Ok(entry) => entries.push(entry),
+                Err(e) => tracing::warn!("dlq: skipping unreadable entry: {}", e),
+            }
+        }
+        Ok(entries)
+    }
+
+    fn write_all(&self, entries: &[DeadLetterEntry]) -> std::io::Result<()> {
+        if let Some(dir) = self.path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        // Rewrite through a temp file so a crash never leaves a truncated queue
+        let tmp = self.path.with_extension("ndjson.tmp");
+        {
+            let mut f = OpenOptions::new().create(true).write(true).truncate(true).open(&tmp)?;
+            for entry in entries {
+                writeln!(f, "{}", serde_json::to_string(entry)?)?;
+            }
+            f.sync_all()?;
+        }
+        fs::rename(&tmp, &self.path)
+    }
+}
+
+#[async_trait]
+impl DeadLetterPort for FileDeadLetterStore {
+    async fn record(&self, mut entry: DeadLetterEntry) -> Result<(), String> {
+        let _guard = self.lock.lock().map_err(|e| e.to_string())?;
+        let mut entries = self.read_all().map_err(|e| e.to_string())?;
+        let key = entry.key();
+        if let Some(existing) = entries.iter_mut().find(|e| e.key() == key) {
+            entry.attempts = existing.attempts + 1;
+            *existing = entry;
+        } else {
+            entries.push(entry);
+        }
+        self.write_all(&entries).map_err(|e| e.to_string())
+    }
+
+    async fn list(&self) -> Result<Vec<DeadLetterEntry>, String> {
+        let _guard = self.lock.lock().map_err(|e| e.to_string())?;
+        self.read_all().map_err(|e| e.to_string())
+    }
+
+    async fn remove(&self, envelope_id: &str) -> Result<bool, String> {
+        let _guard = self.lock.lock().map_err(|e| e.to_string())?;
+        let mut entries = self.read_all().map_err(|e| e.to_string())?;
+        let before = entries.len();
+        entries.retain(|e| e.envelope_id != envelope_id);
+        if entries.len() == before {
+            return Ok(false);
+        }
+        self.write_all(&entries).map_err(|e| e.to_string())?;
+        Ok(true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(envelope_id: &str, error: &str) -> DeadLetterEntry {
+        DeadLetterEntry {
+            envelope_id: envelope_id.to_string(),
+            source_id: "blue_moon".to_string(),
+            payload_ref: "cas:sha256:abcd".to_string(),
+            parse_plan: "parse_plan:wix_calendar_v1".to_string(),
+            error: error.to_string(),
+            failed_at: chrono::Utc::now(),
+            attempts: 1,
+        }
+    }
+
+    #[tokio::test]
+    async fn record_replaces_and_counts_attempts() {
+        let tmp = tempfile::tempdir().unwrap();
+        let store = FileDeadLetterStore::new(tmp.path());
+        store.record(entry("env-1", "bad json")).await.unwrap();
+        store.record(entry("env-2", "bad json")).await.unwrap();
+        store.record(entry("env-1", "still bad")).await.unwrap();
+
+        let entries = store.list().await.unwrap();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].envelope_id, "env-1");
+        assert_eq!(entries[0].attempts, 2);
+        assert_eq!(entries[0].error, "still bad");
+        assert_eq!(entries[1].attempts, 1);
+    }
+
+    #[tokio::test]
+    async fn remove_drops_only_matching_entry() {
+        let tmp = tempfile::tempdir().unwrap();
+        let store = FileDeadLetterStore::new(tmp.path());
+        assert!(store.list().await.unwrap().is_empty());
+        store.record(entry("env-1", "boom")).await.unwrap();
+        store.record(entry("env-2", "boom")).await.unwrap();
+
+        assert!(store.remove("env-1").await.unwrap());
+        assert!(!store.remove("env-1").await.unwrap());
+        let entries = store.list().await.unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].envelope_id, "env-2");
+    }
+}
@@ -1,5 +1,5 @@
 use async_trait::async_trait;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use tokio::fs::OpenOptions;
 use tokio::io::AsyncWriteExt;
 use tracing::debug;
@@ -27,7 +27,7 @@ impl FileEnrichOutputAdapter {
         path
     }
 
-    async fn ensure_dir(&self, file_path: &PathBuf) -> anyhow::Result<()> {
+    async fn ensure_dir(&self, file_path: &Path) -> anyhow::Result<()> {
         if let Some(parent) = file_path.parent() { tokio::fs::create_dir_all(parent).await?; }
         Ok(())
     }
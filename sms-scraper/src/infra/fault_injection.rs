@@ -0,0 +1,307 @@
+//! Chaos mode: fail HTTP fetches, CAS writes and database calls at random, at probabilities
+//! set by env, to check that the pipeline degrades gracefully. Only built with the `chaos`
+//! feature; every injected fault is logged, counted in `sms_chaos_faults_injected_total` and
+//! carries a stable `chaos.*` error code in its message.
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, OnceLock};
+
+use async_trait::async_trait;
+use chrono::NaiveDate;
+use sms_core::common::error::{Result, ScraperError};
+use sms_core::common::geo::GeoBounds;
+use sms_core::domain::*;
+use sms_core::storage::{BatchWriteStats, Storage, WriteBatch};
+use uuid::Uuid;
+
+/// Env var with the probability (0.0-1.0) that an HTTP fetch times out
+pub const CHAOS_HTTP_TIMEOUT_ENV: &str = "SMS_CHAOS_HTTP_TIMEOUT";
+
+/// Env var with the probability (0.0-1.0) that a CAS payload write fails
+pub const CHAOS_CAS_WRITE_ENV: &str = "SMS_CHAOS_CAS_WRITE";
+
+/// Env var with the probability (0.0-1.0) that a database call fails
+pub const CHAOS_DB_ENV: &str = "SMS_CHAOS_DB";
+
+/// Env var with a seed for the fault rolls, so a chaos run can be replayed
+pub const CHAOS_SEED_ENV: &str = "SMS_CHAOS_SEED";
+
+/// A kind of fault the injector can raise
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Fault {
+    HttpTimeout,
+    CasWrite,
+    Db,
+}
+
+impl Fault {
+    pub const ALL: [Fault; 3] = [Fault::HttpTimeout, Fault::CasWrite, Fault::Db];
+
+    /// Metric label for the fault
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Fault::HttpTimeout => "http_timeout",
+            Fault::CasWrite => "cas_write",
+            Fault::Db => "db",
+        }
+    }
+
+    /// Error code at the start of an injected fault's message
+    pub fn code(self) -> &'static str {
+        match self {
+            Fault::HttpTimeout => "chaos.http_timeout",
+            Fault::CasWrite => "chaos.cas_write",
+            Fault::Db => "chaos.db",
+        }
+    }
+
+    pub fn env_var(self) -> &'static str {
+        match self {
+            Fault::HttpTimeout => CHAOS_HTTP_TIMEOUT_ENV,
+            Fault::CasWrite => CHAOS_CAS_WRITE_ENV,
+            Fault::Db => CHAOS_DB_ENV,
+        }
+    }
+
+    fn index(self) -> usize {
+        self as usize
+    }
+
+    fn describe(self) -> &'static str {
+        match self {
+            Fault::HttpTimeout => "injected HTTP timeout",
+            Fault::CasWrite => "injected CAS write failure",
+            Fault::Db => "injected database error",
+        }
+    }
+}
+
+/// An operation the injector failed on purpose
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InjectedFault {
+    pub fault: Fault,
+    /// What was being done: a URL, a CAS root, a storage method
+    pub target: String,
+}
+
+impl std::fmt::Display for InjectedFault {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {} ({})", self.fault.code(), self.fault.describe(), self.target)
+    }
+}
+
+impl std::error::Error for InjectedFault {}
+
+/// Rolls for faults at per-kind probabilities. Probabilities can be changed while running,
+/// and rolls come from a seeded splitmix64 sequence shared by every caller.
+pub struct FaultInjector {
+    probabilities: [AtomicU64; 3],
+    state: AtomicU64,
+}
+
+impl FaultInjector {
+    /// An injector that injects nothing until probabilities are set
+    pub fn new(seed: u64) -> Self {
+        Self {
+            probabilities: [AtomicU64::new(0), AtomicU64::new(0), AtomicU64::new(0)],
+            state: AtomicU64::new(seed),
+        }
+    }
+
+    /// Probabilities and seed read through `lookup`. A probability that isn't a number in
+    /// 0.0-1.0 is an error; without a seed the clock picks one.
+    pub fn from_lookup(lookup: impl Fn(&str) -> Option<String>) -> std::result::Result<Self, String> {
+        let seed = match lookup(CHAOS_SEED_ENV).map(|s| s.trim().to_string()).filter(|s| !s.is_empty()) {
+            Some(seed) => seed.parse().map_err(|_| format!("{} must be an unsigned integer, got {:?}", CHAOS_SEED_ENV, seed))?,
+            None => chrono::Utc::now().timestamp_nanos_opt().unwrap_or_default() as u64,
+        };
+        let injector = Self::new(seed);
+        for fault in Fault::ALL {
+            let Some(raw) = lookup(fault.env_var()).map(|s| s.trim().to_string()).filter(|s| !s.is_empty()) else {
+                continue;
+            };
+            let probability: f64 = raw
+                .parse()
+                .ok()
+                .filter(|p: &f64| (0.0..=1.0).contains(p))
+                .ok_or_else(|| format!("{} must be a probability between 0 and 1, got {:?}", fault.env_var(), raw))?;
+            injector.set(fault, probability);
+        }
+        Ok(injector)
+    }
+
+    pub fn from_env() -> std::result::Result<Self, String> {
+        Self::from_lookup(|name| std::env::var(name).ok())
+    }
+
+    /// Set the probability of `fault`, clamped to 0.0-1.0
+    pub fn set(&self, fault: Fault, probability: f64) {
+        let probability = if probability.is_nan() { 0.0 } else { probability.clamp(0.0, 1.0) };
+        self.probabilities[fault.index()].store(probability.to_bits(), Ordering::Relaxed);
+    }
+
+    pub fn probability(&self, fault: Fault) -> f64 {
+        f64::from_bits(self.probabilities[fault.index()].load(Ordering::Relaxed))
+    }
+
+    /// Whether any fault has a non-zero probability
+    pub fn is_enabled(&self) -> bool {
+        Fault::ALL.iter().any(|fault| self.probability(*fault) > 0.0)
+    }
+
+    /// Roll for `fault` while doing `target`. An injected fault is logged and counted.
+    pub fn check(&self, fault: Fault, target: &str) -> std::result::Result<(), InjectedFault> {
+        let probability = self.probability(fault);
+        if probability <= 0.0 || self.roll() >= probability {
+            return Ok(());
+        }
+        tracing::warn!(fault = fault.as_str(), "Chaos: {} ({})", fault.describe(), target);
+        crate::observability::metrics::chaos::fault_injected(fault.as_str());
+        Err(InjectedFault { fault, target: target.to_string() })
+    }
+
+    /// Next value in 0.0..1.0
+    fn roll(&self) -> f64 {
+        let mut z = self.state.fetch_add(0x9E37_79B9_7F4A_7C15, Ordering::Relaxed).wrapping_add(0x9E37_79B9_7F4A_7C15);
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^= z >> 31;
+        (z >> 11) as f64 / (1u64 << 53) as f64
+    }
+}
+
+/// The process-wide injector, configured from env on first use. Bad settings are logged and
+/// leave chaos off.
+pub fn injector() -> &'static FaultInjector {
+    static INJECTOR: OnceLock<FaultInjector> = OnceLock::new();
+    INJECTOR.get_or_init(|| {
+        let injector = FaultInjector::from_env().unwrap_or_else(|e| {
+            tracing::error!("Chaos mode disabled: {}", e);
+            FaultInjector::new(0)
+        });
+        if injector.is_enabled() {
+            tracing::warn!(
+                "Chaos mode on: http_timeout={} cas_write={} db={}",
+                injector.probability(Fault::HttpTimeout),
+                injector.probability(Fault::CasWrite),
+                injector.probability(Fault::Db)
+            );
+        }
+        injector
+    })
+}
+
+/// Storage that fails calls at the global injector's `db` probability before handing them on.
+/// A batch fails before any of it is written, the way a refused transaction would.
+pub struct FaultyStorage {
+    inner: Arc<dyn Storage>,
+}
+
+impl FaultyStorage {
+    pub fn wrap(inner: Arc<dyn Storage>) -> Arc<dyn Storage> {
+        Arc::new(Self { inner })
+    }
+
+    fn check(operation: &str) -> Result<()> {
+        injector()
+            .check(Fault::Db, operation)
+            .map_err(|fault| ScraperError::Database { message: fault.to_string() })
+    }
+}
+
+/// Implements `Storage` for `FaultyStorage`, checking for a fault before each delegated call
+macro_rules! faulty_storage {
+    ($(async fn $name:ident(&self $(, $arg:ident: $ty:ty)*) -> $ret:ty;)*) => {
+        #[async_trait]
+        impl Storage for FaultyStorage {
+            $(
+                async fn $name(&self $(, $arg: $ty)*) -> Result<$ret> {
+                    Self::check(stringify!($name))?;
+                    self.inner.$name($($arg),*).await
+                }
+            )*
+        }
+    };
+}
+
+faulty_storage! {
+    async fn create_venue(&self, venue: &mut Venue) -> ();
+    async fn get_venue_by_name(&self, name: &str) -> Option<Venue>;
+    async fn delete_venue(&self, venue_id: Uuid) -> ();
+    async fn create_artist(&self, artist: &mut Artist) -> ();
+    async fn get_artist_by_name(&self, name: &str) -> Option<Artist>;
+    async fn get_artist_by_slug(&self, slug: &str) -> Option<Artist>;
+    async fn update_artist(&self, artist: &Artist) -> ();
+    async fn create_artist_if_slug_free(&self, artist: &mut Artist) -> bool;
+    async fn delete_artist(&self, artist_id: Uuid) -> ();
+    async fn create_event(&self, event: &mut Event) -> ();
+    async fn get_event_by_venue_date_title(&self, venue_id: Uuid, date: NaiveDate, title: &str) -> Option<Event>;
+    async fn update_event(&self, event: &Event) -> ();
+    async fn delete_event(&self, event_id: Uuid) -> ();
+    async fn upsert_event_series(&self, series: &mut EventSeries) -> ();
+    async fn get_event_series_by_id(&self, series_id: Uuid) -> Option<EventSeries>;
+    async fn get_event_series_by_venue_id(&self, venue_id: Uuid) -> Vec<EventSeries>;
+    async fn create_raw_data(&self, raw_data: &mut RawData) -> ();
+    async fn get_unprocessed_raw_data(&self, api_name: &str, min_date: Option<NaiveDate>) -> Vec<RawData>;
+    async fn get_processed_raw_data(&self, api_name: &str, min_date: Option<NaiveDate>) -> Vec<RawData>;
+    async fn mark_raw_data_processed(&self, raw_data_id: Uuid) -> ();
+    async fn get_raw_data_payload_refs(&self) -> Vec<String>;
+    async fn create_process_run(&self, run: &mut ProcessRun) -> ();
+    async fn update_process_run(&self, run: &ProcessRun) -> ();
+    async fn create_process_record(&self, record: &mut ProcessRecord) -> ();
+    async fn get_process_run(&self, run_id: Uuid) -> Option<ProcessRun>;
+    async fn get_process_records_for_run(&self, run_id: Uuid) -> Vec<ProcessRecord>;
+    async fn write_batch(&self, batch: &mut WriteBatch) -> BatchWriteStats;
+    async fn get_venue_by_id(&self, venue_id: Uuid) -> Option<Venue>;
+    async fn get_artist_by_id(&self, artist_id: Uuid) -> Option<Artist>;
+    async fn get_event_by_id(&self, event_id: Uuid) -> Option<Event>;
+    async fn get_all_venues(&self, limit: Option<usize>, offset: Option<usize>) -> Vec<Venue>;
+    async fn get_all_artists(&self, limit: Option<usize>, offset: Option<usize>) -> Vec<Artist>;
+    async fn get_all_events(&self, limit: Option<usize>, offset: Option<usize>) -> Vec<Event>;
+    async fn get_venues_in_bounds(&self, bounds: GeoBounds) -> Vec<Venue>;
+    async fn get_events_by_venue_id(&self, venue_id: Uuid) -> Vec<Event>;
+    async fn count_upcoming_events_by_venue(&self, from: NaiveDate) -> HashMap<Uuid, u64>;
+    async fn get_events_by_artist_id(&self, artist_id: Uuid) -> Vec<Event>;
+    async fn get_events_by_date_range(&self, start_date: NaiveDate, end_date: NaiveDate) -> Vec<Event>;
+    async fn search_artists(&self, query: &str) -> Vec<Artist>;
+    async fn search_venues(&self, query: &str) -> Vec<Venue>;
+    async fn get_venues_by_ids(&self, venue_ids: Vec<Uuid>) -> Vec<Venue>;
+    async fn get_artists_by_ids(&self, artist_ids: Vec<Uuid>) -> Vec<Artist>;
+    async fn get_events_by_ids(&self, event_ids: Vec<Uuid>) -> Vec<Event>;
+    async fn get_event_series_by_ids(&self, series_ids: Vec<Uuid>) -> Vec<EventSeries>;
+    async fn get_events_by_venue_ids(&self, venue_ids: Vec<Uuid>) -> HashMap<Uuid, Vec<Event>>;
+    async fn get_events_by_artist_ids(&self, artist_ids: Vec<Uuid>) -> HashMap<Uuid, Vec<Event>>;
+    async fn get_event_series_by_venue_ids(&self, venue_ids: Vec<Uuid>) -> HashMap<Uuid, Vec<EventSeries>>;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_probabilities_and_rolls_reproducibly() {
+        let env = |vars: &'static [(&'static str, &'static str)]| {
+            move |name: &str| vars.iter().find(|(k, _)| *k == name).map(|(_, v)| v.to_string())
+        };
+        let injector = FaultInjector::from_lookup(env(&[(CHAOS_DB_ENV, "0.25"), (CHAOS_SEED_ENV, "7")])).unwrap();
+        assert_eq!(injector.probability(Fault::Db), 0.25);
+        assert_eq!(injector.probability(Fault::HttpTimeout), 0.0);
+        assert!(injector.is_enabled());
+        assert!(!FaultInjector::from_lookup(env(&[])).unwrap().is_enabled());
+        assert!(FaultInjector::from_lookup(env(&[(CHAOS_CAS_WRITE_ENV, "1.5")])).is_err());
+        assert!(FaultInjector::from_lookup(env(&[(CHAOS_SEED_ENV, "soon")])).is_err());
+
+        let replay = FaultInjector::from_lookup(env(&[(CHAOS_DB_ENV, "0.25"), (CHAOS_SEED_ENV, "7")])).unwrap();
+        let rolls = |i: &FaultInjector| (0..200).map(|_| i.check(Fault::Db, "test").is_err()).collect::<Vec<_>>();
+        let first = rolls(&injector);
+        assert_eq!(first, rolls(&replay));
+        let failed = first.iter().filter(|f| **f).count();
+        assert!((20..=80).contains(&failed), "{} of 200 failed at p=0.25", failed);
+
+        let err = FaultInjector::new(1);
+        err.set(Fault::HttpTimeout, 1.0);
+        let fault = err.check(Fault::HttpTimeout, "https://example.com").unwrap_err();
+        assert_eq!(fault.to_string(), "chaos.http_timeout: injected HTTP timeout (https://example.com)");
+    }
+}
@@ -1,17 +1,124 @@
 use crate::app::ports::{HttpClientPort, HttpGetResult};
+use crate::pipeline::ingestion::registry::TransportSpec;
 use async_trait::async_trait;
-use reqwest::header::{CONTENT_LENGTH, CONTENT_TYPE, ETAG, LAST_MODIFIED};
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue, ACCEPT_ENCODING, CONTENT_LENGTH, CONTENT_TYPE, ETAG, LAST_MODIFIED};
+use std::collections::BTreeMap;
 
-pub struct ReqwestHttp;
+/// Env var replacing the default browser-like user agent sent with every request
+pub const USER_AGENT_ENV: &str = "SMS_USER_AGENT";
+
+/// Env var with a URL appended to the user agent as `(+url)` so site operators can reach us
+pub const USER_AGENT_CONTACT_ENV: &str = "SMS_USER_AGENT_CONTACT";
+
+pub struct ReqwestHttp {
+    client: reqwest::Client,
+}
+
+impl ReqwestHttp {
+    /// Client for an endpoint that needs a proxy, extra CA certificates or relaxed TLS checks
+    pub fn with_transport(transport: &TransportSpec) -> Result<Self, String> {
+        let client = client_builder(transport)?.build().map_err(|e| e.to_string())?;
+        Ok(Self { client })
+    }
+}
+
+impl Default for ReqwestHttp {
+    fn default() -> Self {
+        let client = client_builder(&TransportSpec::default())
+            .and_then(|b| b.build().map_err(|e| e.to_string()))
+            .unwrap_or_default();
+        Self { client }
+    }
+}
+
+/// The user agent for outgoing requests: `SMS_USER_AGENT` or a browser-like default (some
+/// sites, Wix among them, turn away anything else), plus `SMS_USER_AGENT_CONTACT` if set
+pub fn user_agent() -> String {
+    let base = std::env::var(USER_AGENT_ENV)
+        .ok()
+        .map(|ua| ua.trim().to_string())
+        .filter(|ua| !ua.is_empty())
+        .unwrap_or_else(|| BROWSER_USER_AGENT.to_string());
+    match std::env::var(USER_AGENT_CONTACT_ENV).ok().map(|c| c.trim().to_string()) {
+        Some(contact) if !contact.is_empty() => format!("{} (+{})", base, contact),
+        _ => base,
+    }
+}
+
+/// A registry endpoint's extra request headers. A `User-Agent` here overrides the global one;
+/// `Accept-Encoding` is refused because ingestion negotiates and decodes compression itself.
+pub fn endpoint_headers(headers: &BTreeMap<String, String>) -> Result<HeaderMap, String> {
+    let mut map = HeaderMap::with_capacity(headers.len());
+    for (name, value) in headers {
+        let header = HeaderName::from_bytes(name.trim().as_bytes())
+            .map_err(|_| format!("invalid header name {:?}", name))?;
+        if header == ACCEPT_ENCODING {
+            return Err("Accept-Encoding is set by the fetcher and can't be overridden".to_string());
+        }
+        let value = HeaderValue::from_str(value).map_err(|_| format!("invalid value for header {}", name))?;
+        map.insert(header, value);
+    }
+    Ok(map)
+}
+
+/// Client builder with a registry endpoint's proxy and TLS settings and the global user agent
+/// applied. The client keeps a cookie jar so sessions set by bootstrap requests carry over to
+/// the main fetch.
+pub fn client_builder(transport: &TransportSpec) -> Result<reqwest::ClientBuilder, String> {
+    let mut builder = reqwest::Client::builder().cookie_store(true).user_agent(user_agent());
+    if let Some(proxy_url) = &transport.proxy_url {
+        let proxy = reqwest::Proxy::all(proxy_url).map_err(|e| format!("invalid proxy_url {}: {}", proxy_url, e))?;
+        builder = builder.proxy(proxy);
+    }
+    if let Some(path) = &transport.ca_bundle_path {
+        let pem = std::fs::read(path).map_err(|e| format!("failed to read ca_bundle_path {}: {}", path, e))?;
+        let certs = reqwest::Certificate::from_pem_bundle(&pem)
+            .map_err(|e| format!("invalid ca bundle {}: {}", path, e))?;
+        if certs.is_empty() {
+            return Err(format!("ca bundle {} contains no certificates", path));
+        }
+        for cert in certs {
+            builder = builder.add_root_certificate(cert);
+        }
+    }
+    if transport.danger_accept_invalid_certs {
+        tracing::warn!("TLS certificate verification is disabled for this endpoint");
+        builder = builder.danger_accept_invalid_certs(true);
+    }
+    Ok(builder)
+}
+
+/// Visit a warm-up page so the client's cookie jar picks up whatever session it hands out.
+/// The body is discarded and only its size returned; an error status fails the fetch since the
+/// calendar would refuse us anyway.
+pub async fn bootstrap_request(client: &reqwest::Client, url: &str) -> Result<u64, String> {
+    tracing::info!("Bootstrap GET request to: {}", url);
+    let resp = client
+        .get(url)
+        .send()
+        .await
+        .map_err(|e| format!("bootstrap request to {} failed: {}", url, e))?;
+    let status = resp.status();
+    let received = resp.bytes().await.map(|b| b.len() as u64).unwrap_or(0);
+    if !status.is_success() {
+        return Err(format!("bootstrap request to {} returned {}", url, status));
+    }
+    Ok(received)
+}
+
+const BROWSER_USER_AGENT: &str = "Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/114.0.0.0 Safari/537.36";
 
 #[async_trait]
 impl HttpClientPort for ReqwestHttp {
     async fn get(&self, url: &str) -> Result<HttpGetResult, String> {
-        let client = reqwest::Client::new();
         tracing::info!("HTTP GET request to: {}", url);
-        let resp = client
+        #[cfg(feature = "chaos")]
+        crate::infra::fault_injection::injector()
+            .check(crate::infra::fault_injection::Fault::HttpTimeout, url)
+            .map_err(|fault| fault.to_string())?;
+        let resp = self
+            .client
             .get(url)
-            .header("User-Agent", "Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/114.0.0.0 Safari/537.36")
             .send()
             .await
             .map_err(|e| e.to_string())?;
@@ -40,3 +147,101 @@ impl HttpClientPort for ReqwestHttp {
     }
 }
 
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn transport_settings_are_validated() {
+        assert!(client_builder(&TransportSpec::default()).is_ok());
+
+        let proxied = TransportSpec { proxy_url: Some("http://proxy.internal:3128".into()), ..Default::default() };
+        assert!(ReqwestHttp::with_transport(&proxied).is_ok());
+
+        let bad_proxy = TransportSpec { proxy_url: Some("not a url".into()), ..Default::default() };
+        assert!(client_builder(&bad_proxy).unwrap_err().contains("invalid proxy_url"));
+
+        let tmp = tempfile::tempdir().unwrap();
+        let empty = tmp.path().join("empty.pem");
+        std::fs::write(&empty, "").unwrap();
+        let no_certs = TransportSpec { ca_bundle_path: Some(empty.display().to_string()), ..Default::default() };
+        assert!(client_builder(&no_certs).unwrap_err().contains("no certificates"));
+
+        let missing = TransportSpec { ca_bundle_path: Some("/nonexistent/ca.pem".into()), ..Default::default() };
+        assert!(client_builder(&missing).is_err());
+    }
+
+    /// Serves `/` with a session cookie and `/calendar` only to requests that send it back;
+    /// anything else is a 404
+    async fn serve_cookie_gated(listener: tokio::net::TcpListener) {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+        loop {
+            let Ok((mut socket, _)) = listener.accept().await else { return };
+            let mut buf = vec![0u8; 4096];
+            let n = socket.read(&mut buf).await.unwrap_or(0);
+            let request = String::from_utf8_lossy(&buf[..n]).to_ascii_lowercase();
+            let response = if request.starts_with("get / ") {
+                "HTTP/1.1 200 OK\r\nSet-Cookie: session=abc; Path=/\r\nContent-Length: 0\r\nConnection: close\r\n\r\n"
+            } else if !request.starts_with("get /calendar ") {
+                "HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\nConnection: close\r\n\r\n"
+            } else if request.contains("cookie: session=abc") {
+                "HTTP/1.1 200 OK\r\nContent-Length: 2\r\nConnection: close\r\n\r\nok"
+            } else {
+                "HTTP/1.1 403 Forbidden\r\nContent-Length: 0\r\nConnection: close\r\n\r\n"
+            };
+            let _ = socket.write_all(response.as_bytes()).await;
+        }
+    }
+
+    #[tokio::test]
+    async fn bootstrap_cookies_carry_over_to_main_fetch() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let base = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(serve_cookie_gated(listener));
+
+        let client = client_builder(&TransportSpec::default()).unwrap().build().unwrap();
+        let calendar = format!("{}/calendar", base);
+        assert_eq!(client.get(&calendar).send().await.unwrap().status(), 403);
+
+        bootstrap_request(&client, &format!("{}/", base)).await.unwrap();
+        assert_eq!(client.get(&calendar).send().await.unwrap().status(), 200);
+        assert!(bootstrap_request(&client, &format!("{}/missing", base)).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn endpoint_headers_are_sent_and_replace_the_user_agent() {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/calendar", listener.local_addr().unwrap());
+        let server = tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut buf = vec![0u8; 4096];
+            let n = socket.read(&mut buf).await.unwrap();
+            let _ = socket
+                .write_all(b"HTTP/1.1 200 OK\r\nContent-Length: 0\r\nConnection: close\r\n\r\n")
+                .await;
+            String::from_utf8_lossy(&buf[..n]).to_ascii_lowercase()
+        });
+
+        let headers = BTreeMap::from([
+            ("Accept".to_string(), "application/json".to_string()),
+            ("User-Agent".to_string(), "venuebot/1.0".to_string()),
+        ]);
+        let client = client_builder(&TransportSpec::default())
+            .unwrap()
+            .default_headers(endpoint_headers(&headers).unwrap())
+            .build()
+            .unwrap();
+        client.get(&url).send().await.unwrap();
+        let request = server.await.unwrap();
+        assert!(request.contains("accept: application/json"));
+        assert!(request.contains("user-agent: venuebot/1.0"));
+        assert!(!request.contains("mozilla"));
+
+        let encoding = BTreeMap::from([("Accept-Encoding".to_string(), "identity".to_string())]);
+        assert!(endpoint_headers(&encoding).is_err());
+        let bad_name = BTreeMap::from([("Bad Header".to_string(), "x".to_string())]);
+        assert!(endpoint_headers(&bad_name).unwrap_err().contains("invalid header name"));
+    }
+}
@@ -0,0 +1,158 @@
+use crate::app::ports::{LlmParseOutput, LlmParserPort};
+use async_trait::async_trait;
+use once_cell::sync::Lazy;
+use serde_json::{json, Value};
+
+/// Turns the fallback on; anything but `1`/`true` leaves it off
+pub const LLM_FALLBACK_ENV: &str = "SMS_LLM_FALLBACK";
+const DEFAULT_MODEL: &str = "gpt-4o-mini";
+const DEFAULT_USD_PER_1K_TOKENS: f64 = 0.001;
+const MAX_OUTPUT_TOKENS: u32 = 2_000;
+
+/// `format` of every event the fallback returns, so normalization reads it by this shape
+/// rather than by its source's
+pub const LLM_FALLBACK_FORMAT: &str = "llm_fallback";
+
+/// Shape every event the model returns must have
+static EVENT_SCHEMA: Lazy<jsonschema::JSONSchema> = Lazy::new(|| {
+    let schema = json!({
+        "type": "object",
+        "required": ["title", "event_day"],
+        "properties": {
+            "title": { "type": "string", "minLength": 1 },
+            "event_day": { "type": "string", "pattern": "^\\d{4}-\\d{2}-\\d{2}$" },
+            "start_time": { "type": ["string", "null"], "pattern": "^\\d{2}:\\d{2}$" },
+            "venue_name": { "type": ["string", "null"] },
+            "artists": { "type": "array", "items": { "type": "string" } },
+            "event_url": { "type": ["string", "null"] },
+            "description": { "type": ["string", "null"] }
+        }
+    });
+    jsonschema::JSONSchema::compile(&schema).expect("event schema compiles")
+});
+
+const SYSTEM_PROMPT: &str = "You extract music events from the text of a venue's web page. \
+Reply with only a JSON object {\"events\": [...]}, where each event has title, event_day (YYYY-MM-DD), \
+start_time (HH:MM, 24h, or null), venue_name, artists (array of names), event_url and description. \
+Include only events the text states; return {\"events\": []} when there are none.";
+
+/// Keep the events that match the schema, counting the rest
+pub fn validate_events(candidates: Vec<Value>) -> (Vec<Value>, usize) {
+    let total = candidates.len();
+    let events: Vec<Value> = candidates.into_iter().filter(|e| EVENT_SCHEMA.is_valid(e)).collect();
+    let rejected = total - events.len();
+    (events, rejected)
+}
+
+/// Calls an OpenAI-compatible chat completion endpoint
+pub struct CompletionLlmParser {
+    endpoint: String,
+    api_key: Option<String>,
+    model: String,
+    usd_per_1k_tokens: f64,
+    client: reqwest::Client,
+}
+
+impl CompletionLlmParser {
+    pub fn new(endpoint: impl Into<String>, api_key: Option<String>, model: impl Into<String>, usd_per_1k_tokens: f64) -> Self {
+        Self {
+            endpoint: endpoint.into(),
+            api_key,
+            model: model.into(),
+            usd_per_1k_tokens,
+            client: reqwest::Client::new(),
+        }
+    }
+
+    /// Build from `SMS_LLM_ENDPOINT`, `SMS_LLM_API_KEY`, `SMS_LLM_MODEL` and
+    /// `SMS_LLM_USD_PER_1K_TOKENS`, or `None` unless `SMS_LLM_FALLBACK` is on and an endpoint is set
+    pub fn from_env() -> Option<Self> {
+        let enabled = std::env::var(LLM_FALLBACK_ENV)
+            .map(|v| v == "1" || v.eq_ignore_ascii_case("true"))
+            .unwrap_or(false);
+        if !enabled {
+            return None;
+        }
+        let Some(endpoint) = std::env::var("SMS_LLM_ENDPOINT").ok().filter(|e| !e.trim().is_empty()) else {
+            tracing::warn!("{} is set but SMS_LLM_ENDPOINT is not; LLM fallback stays off", LLM_FALLBACK_ENV);
+            return None;
+        };
+        let api_key = std::env::var("SMS_LLM_API_KEY").ok().filter(|k| !k.trim().is_empty());
+        let model = std::env::var("SMS_LLM_MODEL").unwrap_or_else(|_| DEFAULT_MODEL.to_string());
+        let price = std::env::var("SMS_LLM_USD_PER_1K_TOKENS")
+            .ok()
+            .and_then(|p| p.parse().ok())
+            .unwrap_or(DEFAULT_USD_PER_1K_TOKENS);
+        Some(Self::new(endpoint, api_key, model, price))
+    }
+
+    fn cost_of(&self, tokens: u64) -> f64 {
+        tokens as f64 / 1000.0 * self.usd_per_1k_tokens
+    }
+}
+
+#[async_trait]
+impl LlmParserPort for CompletionLlmParser {
+    fn estimate_cost_usd(&self, text: &str) -> f64 {
+        // ~4 characters per token, plus the prompt and the most the reply may use
+        let prompt_tokens = (text.chars().count() + SYSTEM_PROMPT.len()) as u64 / 4;
+        self.cost_of(prompt_tokens + MAX_OUTPUT_TOKENS as u64)
+    }
+
+    async fn extract_events(&self, source_id: &str, text: &str) -> Result<LlmParseOutput, String> {
+        let body = json!({
+            "model": self.model,
+            "temperature": 0,
+            "max_tokens": MAX_OUTPUT_TOKENS,
+            "response_format": { "type": "json_object" },
+            "messages": [
+                { "role": "system", "content": SYSTEM_PROMPT },
+                { "role": "user", "content": format!("Source: {}\n\n{}", source_id, text) }
+            ]
+        });
+        let mut request = self.client.post(&self.endpoint).json(&body);
+        if let Some(key) = &self.api_key {
+            request = request.bearer_auth(key);
+        }
+        let resp = request.send().await.map_err(|e| format!("llm_request_failed: {}", e))?;
+        if !resp.status().is_success() {
+            return Err(format!("llm_http_{}", resp.status().as_u16()));
+        }
+        let reply: Value = resp.json().await.map_err(|e| format!("llm_bad_response: {}", e))?;
+        let cost_usd = match reply.pointer("/usage/total_tokens").and_then(Value::as_u64) {
+            Some(tokens) => self.cost_of(tokens),
+            None => self.estimate_cost_usd(text),
+        };
+        let content = reply
+            .pointer("/choices/0/message/content")
+            .and_then(Value::as_str)
+            .ok_or_else(|| "llm_bad_response: no message content".to_string())?;
+        let parsed: Value = serde_json::from_str(content).map_err(|e| format!("llm_not_json: {}", e))?;
+        let candidates = match parsed {
+            Value::Object(mut o) => match o.remove("events") {
+                Some(Value::Array(events)) => events,
+                _ => return Err("llm_not_json: missing events array".to_string()),
+            },
+            Value::Array(events) => events,
+            _ => return Err("llm_not_json: expected an object".to_string()),
+        };
+        let (events, rejected) = validate_events(candidates);
+        Ok(LlmParseOutput { events, rejected, cost_usd })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn events_off_schema_are_rejected() {
+        let (events, rejected) = validate_events(vec![
+            json!({"title": "The Band", "event_day": "2025-05-02", "start_time": "20:00", "artists": ["The Band"]}),
+            json!({"title": "No Date"}),
+            json!({"title": "Bad Date", "event_day": "May 2"}),
+        ]);
+        assert_eq!(events.len(), 1);
+        assert_eq!(rejected, 2);
+    }
+}
@@ -1,6 +1,9 @@
 pub mod payload_store;
+#[cfg(feature = "scraping")]
 pub mod registry_adapter;
+#[cfg(feature = "scraping")]
 pub mod parser_factory;
+pub mod dead_letter_store;
 pub mod http_client;
 pub mod rate_limiter_adapter;
 pub mod cadence_adapter;
@@ -9,4 +12,9 @@ pub mod normalize_output_adapter;
 pub mod quality_gate_output_adapter;
 pub mod enrich_output_adapter;
 pub mod conflation_output_adapter;
-
+pub mod webhook_notifier;
+pub mod sink_registry;
+#[cfg(feature = "scraping")]
+pub mod llm_parser;
+#[cfg(feature = "chaos")]
+pub mod fault_injection;
@@ -11,7 +11,6 @@ pub struct FileNormalizeOutputAdapter {
     events_file: Mutex<std::io::BufWriter<std::fs::File>>,
     venues_file: Mutex<std::io::BufWriter<std::fs::File>>,
     artists_file: Mutex<std::io::BufWriter<std::fs::File>>,
-    file_path_base: String,
 }
 
 impl FileNormalizeOutputAdapter {
@@ -61,7 +60,6 @@ impl FileNormalizeOutputAdapter {
             events_file: Mutex::new(events_file),
             venues_file: Mutex::new(venues_file),
             artists_file: Mutex::new(artists_file),
-            file_path_base: base_path.to_string(),
         })
     }
 }
@@ -1,5 +1,7 @@
-use crate::app::ports::{ParserFactory, ParserPort};
-use crate::pipeline::processing::parser::{Parser, MetricsParser};
+use crate::app::ports::{ParserFactory, ParserPort, PayloadReader};
+use sms_core::pipeline_api::plugins;
+use sms_parsers::{DateHints, Parser};
+use crate::pipeline::processing::parser::MetricsParser;
 use crate::observability::metrics;
 use async_trait::async_trait;
 
@@ -7,128 +9,180 @@ pub struct DefaultParserFactory;
 
 impl ParserFactory for DefaultParserFactory {
     fn for_plan(&self, plan: &str) -> Option<Box<dyn ParserPort>> {
-        match plan {
-            "parse_plan:wix_calendar_v1" => Some(Box::new(WixCalendarAdapter)),
-            "parse_plan:wix_warmup_v1" => Some(Box::new(WixWarmupAdapter)),
-            "parse_plan:darrells_html_v1" => Some(Box::new(DarrellsHtmlAdapter)),
-            "parse_plan:kexp_html_v1" => Some(Box::new(KexpHtmlAdapter)),
-            "parse_plan:barboza_html_v1" => Some(Box::new(BarbozaHtmlAdapter)),
-            "parse_plan:neumos_html_v1" => Some(Box::new(NeumosHtmlAdapter)),
-            "parse_plan:venuepilot_graphql_v1" => Some(Box::new(VenuePilotGraphQLAdapter)),
-            _ => None,
-        }
+        self.for_source(plan, &DateHints::default())
+    }
+
+    /// Parsers for listings that print days without a year (and calendars that don't name
+    /// their time zone) read them with the source's date hints
+    fn for_source(&self, plan: &str, dates: &DateHints) -> Option<Box<dyn ParserPort>> {
+        let dates = dates.clone();
+        let adapter = match plan {
+            "parse_plan:wix_calendar_v1" => ParserAdapter::new(sms_parsers::WixCalendarV1Parser::new),
+            "parse_plan:wix_warmup_v1" => ParserAdapter::new(sms_parsers::WixWarmupV1Parser::new),
+            "parse_plan:darrells_html_v1" => ParserAdapter::new(move |s, e, p| {
+                sms_parsers::DarrellsHtmlV1Parser::new(s, e, p, dates.clone())
+            }),
+            "parse_plan:kexp_html_v1" => ParserAdapter::new(sms_parsers::KexpHtmlV1Parser::new),
+            "parse_plan:barboza_html_v1" => ParserAdapter::new(move |s, e, p| {
+                sms_parsers::BarbozaHtmlV1Parser::new(s, e, p, dates.clone())
+            }),
+            "parse_plan:neumos_html_v1" => ParserAdapter::new(move |s, e, p| {
+                sms_parsers::NeumosHtmlV1Parser::new(s, e, p, dates.clone())
+            }),
+            "parse_plan:venuepilot_graphql_v1" => ParserAdapter::new(sms_parsers::VenuePilotGraphQLV1Parser::new),
+            "parse_plan:google_calendar_v1" => ParserAdapter::new(move |s, e, p| {
+                sms_parsers::GoogleCalendarV1Parser::new(s, e, p, dates.clone())
+            }),
+            "parse_plan:newsletter_email_v1" => ParserAdapter::new(move |s, e, p| {
+                sms_parsers::NewsletterEmailV1Parser::new(s, e, p, dates.clone())
+            }),
+            _ if plugins().has_parser(plan) => ParserAdapter::plugin(plan.to_string()),
+            _ => return None,
+        };
+        Some(Box::new(adapter))
     }
 }
 
-struct WixCalendarAdapter;
-struct WixWarmupAdapter;
-struct DarrellsHtmlAdapter;
-struct KexpHtmlAdapter;
-struct BarbozaHtmlAdapter;
-struct NeumosHtmlAdapter;
-struct VenuePilotGraphQLAdapter;
+type BuildParser = Box<dyn Fn(&str, &str, &str) -> Result<Box<dyn Parser + Send>, String> + Send + Sync>;
 
-#[async_trait]
-impl ParserPort for WixCalendarAdapter {
-    async fn parse(&self, source_id: &str, envelope_id: &str, payload_ref: &str, bytes: &[u8]) -> Result<Vec<String>, String> {
-        metrics::parser::batch_size(1); // Single parse operation
-        let inner_parser = crate::pipeline::processing::parser::WixCalendarV1Parser::new(
-            source_id.to_string(), 
-            envelope_id.to_string(), 
-            payload_ref.to_string()
-        );
-        let p = MetricsParser::new(inner_parser);
-        let recs = p.parse(bytes).map_err(|e| e.to_string())?;
-        recs.into_iter().map(|r| serde_json::to_string(&r).map_err(|e| e.to_string())).collect()
-    }
+/// Serves any [`Parser`] as a [`ParserPort`], building a fresh one per envelope
+struct ParserAdapter {
+    build: BuildParser,
 }
 
-#[async_trait]
-impl ParserPort for WixWarmupAdapter {
-    async fn parse(&self, source_id: &str, envelope_id: &str, payload_ref: &str, bytes: &[u8]) -> Result<Vec<String>, String> {
-        metrics::parser::batch_size(1); // Single parse operation
-        let inner_parser = crate::pipeline::processing::parser::WixWarmupV1Parser::new(
-            source_id.to_string(), 
-            envelope_id.to_string(), 
-            payload_ref.to_string()
-        );
-        let p = MetricsParser::new(inner_parser);
-        let recs = p.parse(bytes).map_err(|e| e.to_string())?;
-        recs.into_iter().map(|r| serde_json::to_string(&r).map_err(|e| e.to_string())).collect()
+impl ParserAdapter {
+    fn new<P, F>(build: F) -> Self
+    where
+        P: Parser + Send + 'static,
+        F: Fn(String, String, String) -> P + Send + Sync + 'static,
+    {
+        Self {
+            build: Box::new(move |source_id, envelope_id, payload_ref| {
+                Ok(Box::new(build(source_id.to_string(), envelope_id.to_string(), payload_ref.to_string())) as Box<dyn Parser + Send>)
+            }),
+        }
     }
-}
 
-#[async_trait]
-impl ParserPort for DarrellsHtmlAdapter {
-    async fn parse(&self, source_id: &str, envelope_id: &str, payload_ref: &str, bytes: &[u8]) -> Result<Vec<String>, String> {
-        metrics::parser::batch_size(1); // Single parse operation
-        let inner_parser = crate::pipeline::processing::parser::DarrellsHtmlV1Parser::new(
-            source_id.to_string(), 
-            envelope_id.to_string(), 
-            payload_ref.to_string()
-        );
-        let p = MetricsParser::new(inner_parser);
-        let recs = p.parse(bytes).map_err(|e| e.to_string())?;
-        recs.into_iter().map(|r| serde_json::to_string(&r).map_err(|e| e.to_string())).collect()
+    /// Parse plans served by a parser registered through `sms_core::pipeline_api::register`
+    fn plugin(plan: String) -> Self {
+        Self {
+            build: Box::new(move |source_id, envelope_id, payload_ref| {
+                plugins()
+                    .parser(&plan, source_id, envelope_id, payload_ref)
+                    .ok_or_else(|| format!("no parser registered for {}", plan))
+            }),
+        }
     }
 }
 
 #[async_trait]
-impl ParserPort for KexpHtmlAdapter {
+impl ParserPort for ParserAdapter {
     async fn parse(&self, source_id: &str, envelope_id: &str, payload_ref: &str, bytes: &[u8]) -> Result<Vec<String>, String> {
         metrics::parser::batch_size(1); // Single parse operation
-        let inner_parser = crate::pipeline::processing::parser::KexpHtmlV1Parser::new(
-            source_id.to_string(), 
-            envelope_id.to_string(), 
-            payload_ref.to_string()
-        );
-        let p = MetricsParser::new(inner_parser);
+        let p = MetricsParser::new((self.build)(source_id, envelope_id, payload_ref)?);
         let recs = p.parse(bytes).map_err(|e| e.to_string())?;
         recs.into_iter().map(|r| serde_json::to_string(&r).map_err(|e| e.to_string())).collect()
     }
-}
 
-#[async_trait]
-impl ParserPort for BarbozaHtmlAdapter {
-    async fn parse(&self, source_id: &str, envelope_id: &str, payload_ref: &str, bytes: &[u8]) -> Result<Vec<String>, String> {
+    async fn parse_stream(&self, source_id: &str, envelope_id: &str, payload_ref: &str, reader: PayloadReader) -> Result<Vec<String>, String> {
         metrics::parser::batch_size(1); // Single parse operation
-        let inner_parser = crate::pipeline::processing::parser::BarbozaHtmlV1Parser::new(
-            source_id.to_string(), 
-            envelope_id.to_string(), 
-            payload_ref.to_string()
-        );
-        let p = MetricsParser::new(inner_parser);
-        let recs = p.parse(bytes).map_err(|e| e.to_string())?;
-        recs.into_iter().map(|r| serde_json::to_string(&r).map_err(|e| e.to_string())).collect()
+        parse_streamed((self.build)(source_id, envelope_id, payload_ref)?, reader).await
     }
 }
 
-#[async_trait]
-impl ParserPort for NeumosHtmlAdapter {
-    async fn parse(&self, source_id: &str, envelope_id: &str, payload_ref: &str, bytes: &[u8]) -> Result<Vec<String>, String> {
-        metrics::parser::batch_size(1); // Single parse operation
-        let inner_parser = crate::pipeline::processing::parser::NeumosHtmlV1Parser::new(
-            source_id.to_string(), 
-            envelope_id.to_string(), 
-            payload_ref.to_string()
-        );
-        let p = MetricsParser::new(inner_parser);
-        let recs = p.parse(bytes).map_err(|e| e.to_string())?;
-        recs.into_iter().map(|r| serde_json::to_string(&r).map_err(|e| e.to_string())).collect()
-    }
+/// Run `parser` over the payload on a blocking thread as it arrives, so only what the
+/// parser builds from it (DOM or JSON tree) is held, not the raw bytes as well
+async fn parse_streamed<P: Parser + Send + 'static>(parser: P, reader: PayloadReader) -> Result<Vec<String>, String> {
+    let bridge = tokio_util::io::SyncIoBridge::new(reader);
+    let recs = tokio::task::spawn_blocking(move || {
+        let mut reader = std::io::BufReader::new(bridge);
+        MetricsParser::new(parser).parse_reader(&mut reader)
+    })
+    .await
+    .map_err(|e| e.to_string())?
+    .map_err(|e| e.to_string())?;
+    recs.into_iter().map(|r| serde_json::to_string(&r).map_err(|e| e.to_string())).collect()
 }
 
-#[async_trait]
-impl ParserPort for VenuePilotGraphQLAdapter {
-    async fn parse(&self, source_id: &str, envelope_id: &str, payload_ref: &str, bytes: &[u8]) -> Result<Vec<String>, String> {
-        metrics::parser::batch_size(1); // Single parse operation
-        let inner_parser = crate::pipeline::processing::parser::VenuePilotGraphQLV1Parser::new(
-            source_id.to_string(), 
-            envelope_id.to_string(), 
-            payload_ref.to_string()
-        );
-        let p = MetricsParser::new(inner_parser);
-        let recs = p.parse(bytes).map_err(|e| e.to_string())?;
-        recs.into_iter().map(|r| serde_json::to_string(&r).map_err(|e| e.to_string())).collect()
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A payload that arrives a few bytes at a time
+    fn trickle(bytes: &'static [u8]) -> PayloadReader {
+        let chunks = bytes.chunks(7).map(Ok::<_, std::io::Error>).collect::<Vec<_>>();
+        Box::new(tokio_util::io::StreamReader::new(futures_util::stream::iter(chunks)))
+    }
+
+    #[tokio::test]
+    async fn streamed_parses_match_buffered_ones() {
+        let payloads: [(&str, &'static [u8]); 2] = [
+            (
+                "parse_plan:darrells_html_v1",
+                b"<html><body><div class=\"entry-content\"><h1>MUSIC 7.12</h1><p><a href=\"#\">The Band</a></p></div></body></html>",
+            ),
+            (
+                "parse_plan:wix_calendar_v1",
+                br#"{"eventsByDates":{"2025-05-02":[{"title":"The Band"},{"title":"Openers"}]}}"#,
+            ),
+        ];
+        for (plan, bytes) in payloads {
+            let parser = DefaultParserFactory.for_plan(plan).unwrap();
+            let buffered = parser.parse("src", "env-1", "cas:sha256:abcd", bytes).await.unwrap();
+            let streamed = parser.parse_stream("src", "env-1", "cas:sha256:abcd", trickle(bytes)).await.unwrap();
+            assert!(!buffered.iter().any(|line| line.contains("html_len")), "{} found nothing", plan);
+            assert_eq!(streamed, buffered, "{}", plan);
+        }
+    }
+
+    #[tokio::test]
+    async fn source_date_hints_reach_the_parser() {
+        let hints = DateHints::new(chrono_tz::America::Los_Angeles, "en-US").with_reference("2025-12-20".parse().unwrap());
+        let barboza = br#"<div class="eventItem"><h3 class="title"><a href="/e/2">New Year</a></h3><span class="m-date__month">Jan</span><span class="m-date__day">3</span></div>"#;
+        let parser = DefaultParserFactory.for_source("parse_plan:barboza_html_v1", &hints).unwrap();
+        let lines = parser.parse("barboza", "env-1", "cas:sha256:abcd", barboza).await.unwrap();
+        let record: sms_parsers::ParsedRecord = serde_json::from_str(&lines[0]).unwrap();
+        assert_eq!(record.record["event_day"], "2026-01-03");
+    }
+
+    struct LinesParser {
+        source_id: String,
+    }
+
+    impl Parser for LinesParser {
+        fn parse(&self, bytes: &[u8]) -> anyhow::Result<Vec<sms_parsers::ParsedRecord>> {
+            Ok(String::from_utf8_lossy(bytes)
+                .lines()
+                .enumerate()
+                .map(|(i, line)| sms_parsers::ParsedRecord {
+                    source_id: self.source_id.clone(),
+                    envelope_id: "env-1".into(),
+                    payload_ref: "cas:sha256:abcd".into(),
+                    record_path: format!("$.lines[{}]", i),
+                    record: serde_json::json!({ "title": line }),
+                    attribution: None,
+                    change: None,
+                    endpoint_id: None,
+                    external_id: None,
+                })
+                .collect())
+        }
+    }
+
+    #[tokio::test]
+    async fn registered_plugin_parsers_serve_unknown_plans() {
+        assert!(DefaultParserFactory.for_plan("parse_plan:plugin_lines_v1").is_none());
+        sms_core::pipeline_api::register(|plugins| {
+            plugins.register_parser("parse_plan:plugin_lines_v1", |source_id, _, _| {
+                Box::new(LinesParser { source_id: source_id.to_string() })
+            });
+        });
+
+        let parser = DefaultParserFactory.for_plan("parse_plan:plugin_lines_v1").unwrap();
+        let lines = parser.parse("plugin_src", "env-1", "cas:sha256:abcd", b"First Band\nSecond Band").await.unwrap();
+        assert_eq!(lines.len(), 2);
+        assert!(lines[1].contains("Second Band") && lines[1].contains("plugin_src"));
+        let streamed = parser.parse_stream("plugin_src", "env-1", "cas:sha256:abcd", trickle(b"First Band\nSecond Band")).await.unwrap();
+        assert_eq!(streamed, lines);
     }
 }
@@ -1,11 +1,30 @@
-use crate::app::ports::PayloadStorePort;
+use crate::app::ports::{PayloadReader, PayloadStorePort};
+use crate::pipeline::ingestion::encryption::{self, Keyring};
 use async_trait::async_trait;
+use futures_util::TryStreamExt;
+use tokio::io::AsyncReadExt;
 
 pub struct CasPayloadStore;
 
 #[async_trait]
 impl PayloadStorePort for CasPayloadStore {
     async fn get(&self, payload_ref: &str) -> Result<Vec<u8>, String> {
+        let mut reader = self.open(payload_ref).await?;
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await.map_err(|e| e.to_string())?;
+        Ok(bytes)
+    }
+
+    async fn open(&self, payload_ref: &str) -> Result<PayloadReader, String> {
+        let reader = self.open_stored(payload_ref).await?;
+        let keys = encryption::keyring().map_err(|e| e.to_string())?;
+        decrypt_if_sealed(reader, &keys).await
+    }
+}
+
+impl CasPayloadStore {
+    /// The object as stored, which may be encrypted
+    async fn open_stored(&self, payload_ref: &str) -> Result<PayloadReader, String> {
         // payload_ref format: cas:sha256:<hex>
         let prefix = "cas:sha256:";
         let hex = payload_ref.strip_prefix(prefix).ok_or_else(|| "bad_payload_ref".to_string())?;
@@ -44,15 +63,33 @@ impl PayloadStorePort for CasPayloadStore {
             if !resp.status().is_success() {
                 return Err(format!("supabase_fetch_failed: {}", resp.status()));
             }
-            let b = resp.bytes().await.map_err(|e| e.to_string())?.to_vec();
-            return Ok(b);
+            // Hand the body over chunk by chunk as it downloads
+            let body = resp.bytes_stream().map_err(std::io::Error::other);
+            return Ok(Box::new(tokio_util::io::StreamReader::new(body)));
         }
         // Local path: resolve via repo's ingest_log_reader helper
         let reader = crate::pipeline::ingestion::ingest_log_reader::IngestLogReader::new(std::path::PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("data"));
         if let Some(path) = reader.resolve_payload_path(payload_ref) {
-            return std::fs::read(path).map_err(|e| e.to_string());
+            let file = tokio::fs::File::open(path).await.map_err(|e| e.to_string())?;
+            return Ok(Box::new(tokio::io::BufReader::new(file)));
         }
         Err("payload_path_not_found".to_string())
     }
 }
 
+/// Encrypted objects are read whole and decrypted; anything else keeps streaming
+pub(crate) async fn decrypt_if_sealed(mut reader: PayloadReader, keys: &Keyring) -> Result<PayloadReader, String> {
+    let mut head = Vec::with_capacity(encryption::OBJECT_MAGIC.len());
+    (&mut reader)
+        .take(encryption::OBJECT_MAGIC.len() as u64)
+        .read_to_end(&mut head)
+        .await
+        .map_err(|e| e.to_string())?;
+    if head != encryption::OBJECT_MAGIC {
+        return Ok(Box::new(std::io::Cursor::new(head).chain(reader)));
+    }
+    let mut sealed = head;
+    reader.read_to_end(&mut sealed).await.map_err(|e| e.to_string())?;
+    let plaintext = keys.open(&sealed).map_err(|e| e.to_string())?.into_owned();
+    Ok(Box::new(std::io::Cursor::new(plaintext)))
+}
@@ -1,17 +1,33 @@
 use crate::app::ports::RegistryPort;
+use crate::pipeline::ingestion::registry::SourceSpecV1;
 use async_trait::async_trait;
+use sms_core::domain::Attribution;
 
 pub struct JsonRegistry;
 
-#[async_trait]
-impl RegistryPort for JsonRegistry {
-    async fn load_parse_plan(&self, source_id: &str) -> Result<String, String> {
-        // Load the registry JSON and return parse_plan_ref or default
+impl JsonRegistry {
+    fn load_spec(source_id: &str) -> Result<SourceSpecV1, String> {
         let base = std::path::Path::new(env!("CARGO_MANIFEST_DIR"));
         let reg_path = base.join("registry/sources").join(format!("{}.json", source_id));
-        let spec = crate::pipeline::ingestion::registry::load_source_spec(&reg_path)
-            .map_err(|e| format!("load_source_spec_failed: {}", e))?;
-        Ok(spec.parse_plan_ref.unwrap_or_else(|| "parse_plan:wix_calendar_v1".to_string()))
+        crate::pipeline::ingestion::registry::load_source_spec(&reg_path)
+            .map_err(|e| format!("load_source_spec_failed: {}", e))
     }
 }
 
+#[async_trait]
+impl RegistryPort for JsonRegistry {
+    async fn load_parse_plan(&self, source_id: &str) -> Result<String, String> {
+        // Load the registry JSON and return the versioned parser plan, parse_plan_ref or default
+        let spec = Self::load_spec(source_id)?;
+        Ok(spec.resolved_parse_plan().unwrap_or_else(|| "parse_plan:wix_calendar_v1".to_string()))
+    }
+
+    async fn load_attribution(&self, source_id: &str) -> Result<Option<Attribution>, String> {
+        let spec = Self::load_spec(source_id)?;
+        Ok(Some(spec.policy.to_attribution(source_id)))
+    }
+
+    async fn load_date_hints(&self, source_id: &str) -> Result<Option<sms_parsers::DateHints>, String> {
+        Self::load_spec(source_id)?.date_hints().map(Some)
+    }
+}
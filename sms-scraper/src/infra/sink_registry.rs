@@ -0,0 +1,578 @@
+use async_trait::async_trait;
+use rusqlite::{params, Connection};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use tracing::warn;
+
+use crate::app::ports::{ConflationOutputPort, EnrichOutputPort, NormalizeOutputPort, QualityGateOutputPort};
+use crate::infra::conflation_output_adapter::ConflationOutputAdapter;
+use crate::infra::enrich_output_adapter::FileEnrichOutputAdapter;
+use crate::infra::normalize_output_adapter::FileNormalizeOutputAdapter;
+use crate::infra::quality_gate_output_adapter::{FileQualityGateOutputAdapter, QualityPartition};
+use crate::pipeline::processing::conflation::ConflatedRecord;
+use crate::pipeline::processing::enrich::EnrichedRecord;
+use crate::pipeline::processing::normalize::{NormalizedRecord, RecordProvenance};
+use crate::pipeline::processing::quality_gate::QualityAssessedRecord;
+
+/// Processing stages whose outputs can be routed to sinks
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Stage {
+    Normalize,
+    QualityGate,
+    Enrich,
+    Conflation,
+}
+
+impl Stage {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Stage::Normalize => "normalize",
+            Stage::QualityGate => "quality_gate",
+            Stage::Enrich => "enrich",
+            Stage::Conflation => "conflation",
+        }
+    }
+}
+
+/// One output destination for a stage, as declared in config
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum SinkSpec {
+    /// The stage's NDJSON files, under `dir` or the run's output directory
+    File {
+        #[serde(default)]
+        dir: Option<PathBuf>,
+    },
+    /// One JSON line per record on stdout. Records interleave with a command's `--json`
+    /// output there, so runs using `--json` should pick `stderr`.
+    Stdout,
+    /// One JSON line per record on stderr
+    Stderr,
+    /// POST each record as JSON to `url`
+    Webhook { url: String },
+    /// Rows in a SQLite table, at `path` or `<data_root>/sinks/stage_records.db`
+    Db {
+        #[serde(default)]
+        path: Option<PathBuf>,
+    },
+}
+
+fn file_sinks() -> Vec<SinkSpec> {
+    vec![SinkSpec::File { dir: None }]
+}
+
+/// Sinks per stage, read from the `[sinks]` table of a TOML config. A stage left out keeps
+/// its NDJSON files; an empty list turns its output off.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SinksConfig {
+    #[serde(default = "file_sinks")]
+    pub normalize: Vec<SinkSpec>,
+    #[serde(default = "file_sinks")]
+    pub quality_gate: Vec<SinkSpec>,
+    #[serde(default = "file_sinks")]
+    pub enrich: Vec<SinkSpec>,
+    #[serde(default = "file_sinks")]
+    pub conflation: Vec<SinkSpec>,
+}
+
+impl Default for SinksConfig {
+    fn default() -> Self {
+        Self { normalize: file_sinks(), quality_gate: file_sinks(), enrich: file_sinks(), conflation: file_sinks() }
+    }
+}
+
+impl SinksConfig {
+    /// Read the `[sinks]` table from a TOML file; a file without one gets the defaults
+    pub fn load<P: AsRef<Path>>(path: P) -> anyhow::Result<Self> {
+        let text = std::fs::read_to_string(path.as_ref())
+            .map_err(|e| anyhow::anyhow!("failed to read sinks config {}: {}", path.as_ref().display(), e))?;
+        Self::from_toml(&text)
+    }
+
+    pub fn from_toml(text: &str) -> anyhow::Result<Self> {
+        #[derive(Deserialize)]
+        struct ConfigFile {
+            #[serde(default)]
+            sinks: SinksConfig,
+        }
+        Ok(toml::from_str::<ConfigFile>(text)?.sinks)
+    }
+
+    pub fn for_stage(&self, stage: Stage) -> &[SinkSpec] {
+        match stage {
+            Stage::Normalize => &self.normalize,
+            Stage::QualityGate => &self.quality_gate,
+            Stage::Enrich => &self.enrich,
+            Stage::Conflation => &self.conflation,
+        }
+    }
+}
+
+/// A stage output as handed to stdout, stderr, webhook and DB sinks
+#[derive(Debug, Clone, Serialize)]
+pub struct StageRecord<'a> {
+    pub stage: Stage,
+    /// `accepted` or `quarantined` for quality gate output
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub partition: Option<&'static str>,
+    pub source_id: &'a str,
+    pub envelope_id: &'a str,
+    pub record: Value,
+}
+
+/// A destination that takes any stage's records
+#[async_trait]
+pub trait RecordSink: Send + Sync {
+    async fn write(&self, record: &StageRecord<'_>) -> anyhow::Result<()>;
+}
+
+pub struct StdoutSink;
+
+#[async_trait]
+impl RecordSink for StdoutSink {
+    async fn write(&self, record: &StageRecord<'_>) -> anyhow::Result<()> {
+        println!("{}", serde_json::to_string(record)?);
+        Ok(())
+    }
+}
+
+pub struct StderrSink;
+
+#[async_trait]
+impl RecordSink for StderrSink {
+    async fn write(&self, record: &StageRecord<'_>) -> anyhow::Result<()> {
+        eprintln!("{}", serde_json::to_string(record)?);
+        Ok(())
+    }
+}
+
+pub struct WebhookSink {
+    url: String,
+    client: reqwest::Client,
+}
+
+impl WebhookSink {
+    pub fn new(url: impl Into<String>) -> Self {
+        Self { url: url.into(), client: reqwest::Client::new() }
+    }
+}
+
+#[async_trait]
+impl RecordSink for WebhookSink {
+    async fn write(&self, record: &StageRecord<'_>) -> anyhow::Result<()> {
+        let resp = self.client.post(&self.url).json(record).send().await?;
+        if !resp.status().is_success() {
+            anyhow::bail!("webhook sink {} returned {}", self.url, resp.status());
+        }
+        Ok(())
+    }
+}
+
+/// Stage records kept in SQLite, one row per record
+pub struct DbSink {
+    conn: Mutex<Connection>,
+}
+
+impl DbSink {
+    pub fn open<P: AsRef<Path>>(db_path: P) -> anyhow::Result<Self> {
+        if let Some(parent) = db_path.as_ref().parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let conn = Connection::open(db_path)?;
+        conn.execute_batch(
+            r#"
+            PRAGMA journal_mode=WAL;
+            CREATE TABLE IF NOT EXISTS stage_records (
+                id          INTEGER PRIMARY KEY AUTOINCREMENT,
+                stage       TEXT NOT NULL,
+                partition   TEXT,
+                source_id   TEXT NOT NULL,
+                envelope_id TEXT NOT NULL,
+                record      TEXT NOT NULL,
+                written_at  INTEGER NOT NULL
+            );
+            CREATE INDEX IF NOT EXISTS idx_stage_records_stage ON stage_records(stage, source_id);
+            "#,
+        )?;
+        Ok(Self { conn: Mutex::new(conn) })
+    }
+
+    pub fn open_at_root<P: AsRef<Path>>(data_root: P) -> anyhow::Result<Self> {
+        Self::open(data_root.as_ref().join("sinks").join("stage_records.db"))
+    }
+
+    pub fn count(&self, stage: Stage) -> anyhow::Result<usize> {
+        let conn = self.conn.lock().map_err(|_| anyhow::anyhow!("sink db lock poisoned"))?;
+        let n: i64 = conn.query_row(
+            "SELECT COUNT(*) FROM stage_records WHERE stage = ?1",
+            params![stage.as_str()],
+            |row| row.get(0),
+        )?;
+        Ok(n as usize)
+    }
+}
+
+#[async_trait]
+impl RecordSink for DbSink {
+    async fn write(&self, record: &StageRecord<'_>) -> anyhow::Result<()> {
+        let conn = self.conn.lock().map_err(|_| anyhow::anyhow!("sink db lock poisoned"))?;
+        conn.execute(
+            "INSERT INTO stage_records (stage, partition, source_id, envelope_id, record, written_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            params![
+                record.stage.as_str(),
+                record.partition,
+                record.source_id,
+                record.envelope_id,
+                record.record.to_string(),
+                chrono::Utc::now().timestamp(),
+            ],
+        )?;
+        Ok(())
+    }
+}
+
+/// Adapts a `RecordSink` to the stage output ports
+struct StageSink {
+    sink: Arc<dyn RecordSink>,
+    stage: Stage,
+    partition: Option<&'static str>,
+}
+
+impl StageSink {
+    async fn write<T: Serialize>(&self, provenance: &RecordProvenance, record: &T) -> anyhow::Result<()> {
+        self.sink
+            .write(&StageRecord {
+                stage: self.stage,
+                partition: self.partition,
+                source_id: &provenance.source_id,
+                envelope_id: &provenance.envelope_id,
+                record: serde_json::to_value(record)?,
+            })
+            .await
+    }
+}
+
+#[async_trait]
+impl NormalizeOutputPort for StageSink {
+    async fn write_normalized_record(&self, record: &NormalizedRecord) -> anyhow::Result<()> {
+        self.write(&record.provenance, record).await
+    }
+}
+
+#[async_trait]
+impl QualityGateOutputPort for StageSink {
+    async fn write_quality_assessed_record(&self, record: &QualityAssessedRecord) -> anyhow::Result<()> {
+        self.write(&record.normalized_record.provenance, record).await
+    }
+}
+
+#[async_trait]
+impl EnrichOutputPort for StageSink {
+    async fn write_enriched_record(&self, record: &EnrichedRecord) -> anyhow::Result<()> {
+        self.write(&record.quality_assessed_record.normalized_record.provenance, record).await
+    }
+}
+
+#[async_trait]
+impl ConflationOutputPort for StageSink {
+    async fn write_conflated_record(&self, record: &ConflatedRecord) -> anyhow::Result<()> {
+        self.write(&record.enriched_record.quality_assessed_record.normalized_record.provenance, record).await
+    }
+}
+
+/// Writes each record to every sink. A failing sink doesn't stop the others; the first
+/// failure is returned once all have been tried.
+pub struct FanOut<P: ?Sized> {
+    outputs: Vec<Box<P>>,
+}
+
+impl<P: ?Sized> FanOut<P> {
+    pub fn len(&self) -> usize {
+        self.outputs.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.outputs.is_empty()
+    }
+}
+
+fn first_error(results: Vec<anyhow::Result<()>>) -> anyhow::Result<()> {
+    let mut first = None;
+    for result in results {
+        if let Err(e) = result {
+            warn!("sink: write failed: {}", e);
+            first.get_or_insert(e);
+        }
+    }
+    first.map_or(Ok(()), Err)
+}
+
+#[async_trait]
+impl NormalizeOutputPort for FanOut<dyn NormalizeOutputPort> {
+    async fn write_normalized_record(&self, record: &NormalizedRecord) -> anyhow::Result<()> {
+        let mut results = Vec::with_capacity(self.outputs.len());
+        for output in &self.outputs {
+            results.push(output.write_normalized_record(record).await);
+        }
+        first_error(results)
+    }
+}
+
+#[async_trait]
+impl QualityGateOutputPort for FanOut<dyn QualityGateOutputPort> {
+    async fn write_quality_assessed_record(&self, record: &QualityAssessedRecord) -> anyhow::Result<()> {
+        let mut results = Vec::with_capacity(self.outputs.len());
+        for output in &self.outputs {
+            results.push(output.write_quality_assessed_record(record).await);
+        }
+        first_error(results)
+    }
+}
+
+#[async_trait]
+impl EnrichOutputPort for FanOut<dyn EnrichOutputPort> {
+    async fn write_enriched_record(&self, record: &EnrichedRecord) -> anyhow::Result<()> {
+        let mut results = Vec::with_capacity(self.outputs.len());
+        for output in &self.outputs {
+            results.push(output.write_enriched_record(record).await);
+        }
+        first_error(results)
+    }
+}
+
+#[async_trait]
+impl ConflationOutputPort for FanOut<dyn ConflationOutputPort> {
+    async fn write_conflated_record(&self, record: &ConflatedRecord) -> anyhow::Result<()> {
+        let mut results = Vec::with_capacity(self.outputs.len());
+        for output in &self.outputs {
+            results.push(output.write_conflated_record(record).await);
+        }
+        first_error(results)
+    }
+}
+
+/// Builds each stage's output port from the configured sinks. Stderr, webhook and DB sinks
+/// are shared across stages, so one DB file or webhook client serves the whole run.
+pub struct SinkRegistry {
+    config: SinksConfig,
+    output_dir: PathBuf,
+    data_root: PathBuf,
+    shared: Mutex<Vec<(SinkSpec, Arc<dyn RecordSink>)>>,
+}
+
+impl SinkRegistry {
+    pub fn new(config: SinksConfig, output_dir: impl Into<PathBuf>, data_root: impl Into<PathBuf>) -> Self {
+        Self { config, output_dir: output_dir.into(), data_root: data_root.into(), shared: Mutex::new(Vec::new()) }
+    }
+
+    /// Registry for the config at `path`, or the NDJSON-file defaults when there is none
+    pub fn load(path: Option<&Path>, output_dir: impl Into<PathBuf>, data_root: impl Into<PathBuf>) -> anyhow::Result<Self> {
+        let config = match path {
+            Some(path) => SinksConfig::load(path)?,
+            None => SinksConfig::default(),
+        };
+        Ok(Self::new(config, output_dir, data_root))
+    }
+
+    pub fn config(&self) -> &SinksConfig {
+        &self.config
+    }
+
+    fn record_sink(&self, spec: &SinkSpec) -> anyhow::Result<Arc<dyn RecordSink>> {
+        let mut shared = self.shared.lock().map_err(|_| anyhow::anyhow!("sink registry lock poisoned"))?;
+        if let Some((_, sink)) = shared.iter().find(|(s, _)| s == spec) {
+            return Ok(sink.clone());
+        }
+        let sink: Arc<dyn RecordSink> = match spec {
+            SinkSpec::Stdout => Arc::new(StdoutSink),
+            SinkSpec::Stderr => Arc::new(StderrSink),
+            SinkSpec::Webhook { url } => Arc::new(WebhookSink::new(url.clone())),
+            SinkSpec::Db { path: Some(path) } => Arc::new(DbSink::open(path)?),
+            SinkSpec::Db { path: None } => Arc::new(DbSink::open_at_root(&self.data_root)?),
+            SinkSpec::File { .. } => anyhow::bail!("file sinks are stage-specific"),
+        };
+        shared.push((spec.clone(), sink.clone()));
+        Ok(sink)
+    }
+
+    fn stage_sink(&self, spec: &SinkSpec, stage: Stage, partition: Option<&'static str>) -> anyhow::Result<StageSink> {
+        Ok(StageSink { sink: self.record_sink(spec)?, stage, partition })
+    }
+
+    fn file_dir(&self, dir: &Option<PathBuf>) -> PathBuf {
+        dir.clone().unwrap_or_else(|| self.output_dir.clone())
+    }
+
+    /// Normalize output; file sinks write `<file_name>`'s `_events`/`_venues`/`_artists` NDJSON
+    pub fn normalize_output(&self, file_name: &str) -> anyhow::Result<FanOut<dyn NormalizeOutputPort>> {
+        let mut outputs: Vec<Box<dyn NormalizeOutputPort>> = Vec::new();
+        for spec in self.config.for_stage(Stage::Normalize) {
+            match spec {
+                SinkSpec::File { dir } => {
+                    let path = self.file_dir(dir).join(file_name);
+                    let adapter = FileNormalizeOutputAdapter::new(&path.to_string_lossy())
+                        .map_err(|e| anyhow::anyhow!("failed to open normalize output {}: {}", path.display(), e))?;
+                    outputs.push(Box::new(adapter));
+                }
+                other => outputs.push(Box::new(self.stage_sink(other, Stage::Normalize, None)?)),
+            }
+        }
+        Ok(FanOut { outputs })
+    }
+
+    /// Quality gate outputs for accepted and quarantined records
+    pub fn quality_gate_outputs(
+        &self,
+    ) -> anyhow::Result<(FanOut<dyn QualityGateOutputPort>, FanOut<dyn QualityGateOutputPort>)> {
+        let partition = |partition: QualityPartition, name: &'static str| -> anyhow::Result<FanOut<dyn QualityGateOutputPort>> {
+            let mut outputs: Vec<Box<dyn QualityGateOutputPort>> = Vec::new();
+            for spec in self.config.for_stage(Stage::QualityGate) {
+                match spec {
+                    SinkSpec::File { dir } => {
+                        outputs.push(Box::new(FileQualityGateOutputAdapter::new(self.file_dir(dir), partition)))
+                    }
+                    other => outputs.push(Box::new(self.stage_sink(other, Stage::QualityGate, Some(name))?)),
+                }
+            }
+            Ok(FanOut { outputs })
+        };
+        Ok((
+            partition(QualityPartition::Accepted, "accepted")?,
+            partition(QualityPartition::Quarantined, "quarantined")?,
+        ))
+    }
+
+    pub fn enrich_output(&self) -> anyhow::Result<FanOut<dyn EnrichOutputPort>> {
+        let mut outputs: Vec<Box<dyn EnrichOutputPort>> = Vec::new();
+        for spec in self.config.for_stage(Stage::Enrich) {
+            match spec {
+                SinkSpec::File { dir } => outputs.push(Box::new(FileEnrichOutputAdapter::new(self.file_dir(dir)))),
+                other => outputs.push(Box::new(self.stage_sink(other, Stage::Enrich, None)?)),
+            }
+        }
+        Ok(FanOut { outputs })
+    }
+
+    pub fn conflation_output(&self) -> anyhow::Result<FanOut<dyn ConflationOutputPort>> {
+        let mut outputs: Vec<Box<dyn ConflationOutputPort>> = Vec::new();
+        for spec in self.config.for_stage(Stage::Conflation) {
+            match spec {
+                SinkSpec::File { dir } => {
+                    outputs.push(Box::new(ConflationOutputAdapter::new(self.file_dir(dir).join("conflated"))))
+                }
+                other => outputs.push(Box::new(self.stage_sink(other, Stage::Conflation, None)?)),
+            }
+        }
+        Ok(FanOut { outputs })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pipeline::processing::normalize::{NormalizationMetadata, NormalizedEntity};
+    use chrono::Utc;
+    use sms_core::domain::Venue;
+
+    fn normalized_venue() -> NormalizedRecord {
+        NormalizedRecord {
+            entity: NormalizedEntity::Venue(Venue {
+                id: None,
+                name: "Neumos".to_string(),
+                name_lower: "neumos".to_string(),
+                slug: "neumos".to_string(),
+                latitude: 47.6141,
+                longitude: -122.3195,
+                address: "925 E Pike St".to_string(),
+                postal_code: "98122".to_string(),
+                city: "Seattle".to_string(),
+                venue_url: None,
+                venue_image_url: None,
+                description: None,
+                neighborhood: None,
+                show_venue: true,
+                created_at: Utc::now(),
+                attributions: Vec::new(),
+                provisional: false,
+                aliases: Vec::new(),
+                age_restriction: None,
+                accessibility_notes: None,
+                moderation: None,
+            }),
+            provenance: RecordProvenance {
+                envelope_id: "env-1".to_string(),
+                source_id: "neumos".to_string(),
+                payload_ref: "cas:sha256:abc".to_string(),
+                record_path: "$.events[0]".to_string(),
+                normalized_at: Utc::now(),
+                attribution: None,
+                change: None,
+                record_key: None,
+                endpoint_id: None,
+                external_id: None,
+            },
+            normalization: NormalizationMetadata {
+                confidence: 1.0,
+                warnings: Vec::new(),
+                geocoded: false,
+                strategy: "test".to_string(),
+            },
+        }
+    }
+
+    #[test]
+    fn stages_left_out_of_config_keep_their_files() {
+        let config = SinksConfig::from_toml(
+            r#"
+            [sinks]
+            enrich = []
+
+            [[sinks.normalize]]
+            kind = "file"
+
+            [[sinks.normalize]]
+            kind = "db"
+
+            [[sinks.conflation]]
+            kind = "webhook"
+            url = "http://localhost:9000/hook"
+            "#,
+        )
+        .unwrap();
+        assert_eq!(config.normalize, vec![SinkSpec::File { dir: None }, SinkSpec::Db { path: None }]);
+        assert_eq!(config.quality_gate, file_sinks());
+        assert!(config.enrich.is_empty());
+        assert_eq!(config.conflation, vec![SinkSpec::Webhook { url: "http://localhost:9000/hook".to_string() }]);
+        assert_eq!(SinksConfig::from_toml("[ticketmaster]\ndelay_ms = 500\n").unwrap(), SinksConfig::default());
+    }
+
+    #[test]
+    fn stdout_and_stderr_are_separate_sinks() {
+        let config =
+            SinksConfig::from_toml("[[sinks.enrich]]\nkind = \"stdout\"\n\n[[sinks.enrich]]\nkind = \"stderr\"\n").unwrap();
+        assert_eq!(config.enrich, vec![SinkSpec::Stdout, SinkSpec::Stderr]);
+    }
+
+    #[tokio::test]
+    async fn normalized_records_reach_every_configured_sink() {
+        let tmp = tempfile::tempdir().unwrap();
+        let db_path = tmp.path().join("stage.db");
+        let config = SinksConfig {
+            normalize: vec![SinkSpec::File { dir: None }, SinkSpec::Db { path: Some(db_path.clone()) }],
+            ..SinksConfig::default()
+        };
+        let registry = SinkRegistry::new(config, tmp.path().join("output"), tmp.path().join("data"));
+
+        let output = registry.normalize_output("run_normalized.ndjson").unwrap();
+        assert_eq!(output.len(), 2);
+        output.write_normalized_record(&normalized_venue()).await.unwrap();
+
+        let venues = std::fs::read_to_string(tmp.path().join("output/run_normalized_venues.ndjson")).unwrap();
+        assert_eq!(venues.lines().count(), 1);
+        assert_eq!(DbSink::open(&db_path).unwrap().count(Stage::Normalize).unwrap(), 1);
+    }
+}
@@ -0,0 +1,50 @@
+use crate::app::ports::{Notification, NotificationPort};
+use async_trait::async_trait;
+
+/// Posts notifications as JSON to a webhook (Slack-compatible `text` field included)
+pub struct WebhookNotifier {
+    url: String,
+    client: reqwest::Client,
+}
+
+impl WebhookNotifier {
+    pub fn new(url: impl Into<String>) -> Self {
+        Self { url: url.into(), client: reqwest::Client::new() }
+    }
+
+    /// Build from `SMS_ALERT_WEBHOOK_URL`, or `None` when alerts are not configured
+    pub fn from_env() -> Option<Self> {
+        std::env::var("SMS_ALERT_WEBHOOK_URL")
+            .ok()
+            .filter(|u| !u.trim().is_empty())
+            .map(Self::new)
+    }
+}
+
+#[async_trait]
+impl NotificationPort for WebhookNotifier {
+    async fn notify(&self, notification: &Notification) -> Result<(), String> {
+        let body = serde_json::json!({
+            "text": format!("[{}] {}: {}", notification.kind, notification.source_id, notification.message),
+            "kind": notification.kind,
+            "source_id": notification.source_id,
+            "message": notification.message,
+        });
+        let resp = self.client.post(&self.url).json(&body).send().await.map_err(|e| e.to_string())?;
+        if !resp.status().is_success() {
+            return Err(format!("webhook_failed: {}", resp.status()));
+        }
+        Ok(())
+    }
+}
+
+/// Used when no webhook is configured; alerts still reach the logs and metrics
+pub struct LogOnlyNotifier;
+
+#[async_trait]
+impl NotificationPort for LogOnlyNotifier {
+    async fn notify(&self, notification: &Notification) -> Result<(), String> {
+        tracing::warn!("alert [{}] {}: {}", notification.kind, notification.source_id, notification.message);
+        Ok(())
+    }
+}
@@ -1,6 +1,7 @@
 //! Main library crate for the SMS Scraper
 
 // Re-export the main modules needed for integration tests
+#[cfg(feature = "scraping")]
 pub mod apis;
 pub mod app;
 pub mod common;
@@ -11,7 +12,3 @@ pub mod registry;
 
 // Re-export commonly used types
 pub use sms_core::domain::RawData;
-
-// Enable metrics feature if needed
-#[cfg(feature = "metrics")]
-pub use observability::metrics;
@@ -1,18 +1,67 @@
-use clap::{Parser, Subcommand};
+use clap::{Args, CommandFactory, Parser, Subcommand, ValueEnum};
 use std::sync::Arc;
 use tracing::info;
-use dotenv;
 
 use sms_core::storage::database::DatabaseStorage;
 use sms_core::storage::traits::Storage;
 
-use sms_scraper::pipeline::{FullPipelineOrchestrator, PipelineOrchestrator, PipelineConfig};
+use sms_scraper::infra::http_client::{USER_AGENT_CONTACT_ENV, USER_AGENT_ENV};
+use sms_scraper::observability::push::PushConfig;
+use sms_scraper::observability::{init_console_logging, otel, shutdown_tracing, LogFormat};
+use sms_scraper::pipeline::processing::conflation::{ConflatorConfig, TieBreakStrategy};
+use sms_scraper::pipeline::processing::duplicate_suppression::DuplicateMergePolicy;
+use sms_scraper::app::contract_test::DEFAULT_FIXTURES_DIR;
+use sms_scraper::pipeline::assets;
+use sms_scraper::pipeline::processing::neighborhoods::NEIGHBORHOODS_ENV;
+use sms_scraper::pipeline::processing::classification::EVENT_TAG_RULES_ENV;
+use sms_scraper::pipeline::streaming::{StageConcurrency, DEFAULT_CHANNEL_CAPACITY};
+use sms_scraper::pipeline::ingestion::ingest_meta::parse_age_secs;
+use sms_scraper::pipeline::runner::DEFAULT_REPROCESS_BATCH_SIZE;
+
+mod commands;
+
+use commands::{catalog, debug, exit_failed, ingest, maintenance, pipeline, stages, summarize_failure};
 
 #[derive(Parser)]
 #[command(name = "sms-scraper")]
 #[command(about = "SMS scraper with all crawlers and processing pipeline")]
 #[command(version = "0.1.0")]
 struct Cli {
+    /// Console log format: "pretty" or "json"
+    #[arg(long, global = true, default_value = "pretty")]
+    log_format: LogFormat,
+    /// OTLP/HTTP collector URL (e.g. http://localhost:4318) to export pipeline traces to;
+    /// falls back to OTEL_EXPORTER_OTLP_ENDPOINT, disabled when neither is set
+    #[arg(long, global = true)]
+    otlp_endpoint: Option<String>,
+    /// GeoJSON FeatureCollection of neighborhood polygons used by enrichment
+    /// (defaults to the bundled Seattle boundaries)
+    #[arg(long, global = true)]
+    neighborhoods: Option<std::path::PathBuf>,
+    /// TOML keyword rules used to tag events with genres/categories
+    /// (defaults to the bundled rules; SMS_EVENT_TAG_RULES=off disables tagging)
+    #[arg(long, global = true)]
+    event_tag_rules: Option<std::path::PathBuf>,
+    /// Load and validate the source registry, neighborhoods and event tag rules at startup,
+    /// failing before any work if one is invalid; later loads in this process reuse them
+    #[arg(long, global = true)]
+    preload: bool,
+    /// User agent sent with every fetch (overrides SMS_USER_AGENT; endpoint headers may replace it)
+    #[arg(long, global = true)]
+    user_agent: Option<String>,
+    /// Contact URL appended to the user agent as `(+url)` (overrides SMS_USER_AGENT_CONTACT)
+    #[arg(long, global = true)]
+    user_agent_contact: Option<String>,
+    /// Print command summaries as JSON on stdout (logs move to stderr)
+    #[arg(long, global = true)]
+    json: bool,
+    /// Push metrics to the Pushgateway, failing at startup if none is configured
+    /// (overrides SMS_METRICS_PUSH)
+    #[arg(long, global = true, overrides_with = "no_push")]
+    push: bool,
+    /// Never push metrics, even when SMS_PUSHGATEWAY_URL is set
+    #[arg(long, global = true, overrides_with = "push")]
+    no_push: bool,
     #[command(subcommand)]
     command: Commands,
 }
@@ -27,15 +76,13 @@ enum Commands {
         /// Bypass cadence (fetch even if fetched within the last interval)
         #[arg(long)]
         bypass_cadence: bool,
+        /// Data root holding the CAS, ingest log and pipeline metadata
+        #[arg(long, default_value = "data")]
+        data_root: String,
     },
     /// Run a full pipeline for a source
     #[command(name = "full-pipeline")]
-    FullPipeline {
-        #[arg(long)]
-        source_id: String,
-        #[arg(long, default_value = "false")]
-        bypass_cadence: bool,
-    },
+    FullPipeline(FullPipelineArgs),
     /// Run a modular pipeline for a source (new architecture)
     #[command(name = "modular-pipeline")]
     ModularPipeline {
@@ -51,6 +98,18 @@ enum Commands {
         /// Source ID to reprocess
         #[arg(long)]
         source_id: String,
+        /// Only raw data fetched on or after this day (YYYY-MM-DD)
+        #[arg(long)]
+        since: Option<chrono::NaiveDate>,
+        /// At most this many rows, the most recently fetched
+        #[arg(long)]
+        limit: Option<usize>,
+        /// Rows processed per batch; progress is printed after each
+        #[arg(long, default_value_t = DEFAULT_REPROCESS_BATCH_SIZE)]
+        batch_size: usize,
+        /// Data root holding the CAS, ingest log and pipeline metadata
+        #[arg(long, default_value = "data")]
+        data_root: String,
     },
     /// Clear data from the database
     ClearDb {
@@ -60,7 +119,10 @@ enum Commands {
         venue_slug: Option<String>,
     },
     /// Step 4: Parse envelopes from ingest log into neutral records
+    #[command(args_conflicts_with_subcommands = true)]
     Parse {
+        #[command(subcommand)]
+        action: Option<ParseAction>,
         /// Explicit input file path(s), comma-separated
         #[arg(long)]
         input: Option<String>,
@@ -73,6 +135,9 @@ enum Commands {
         /// Output file path
         #[arg(long)]
         output: Option<String>,
+        /// Data root holding the CAS, ingest log and pipeline metadata
+        #[arg(long, default_value = "data")]
+        data_root: String,
     },
     /// Step 5: Normalize parsed records into standardized entities
     Normalize {
@@ -88,6 +153,9 @@ enum Commands {
         /// Output file path
         #[arg(long)]
         output: Option<String>,
+        /// Data root holding the CAS, ingest log and pipeline metadata
+        #[arg(long, default_value = "data")]
+        data_root: String,
     },
     /// Step 6: Apply quality gates to normalized data
     QualityGate {
@@ -103,6 +171,9 @@ enum Commands {
         /// Output file path
         #[arg(long)]
         output: Option<String>,
+        /// Data root holding the CAS, ingest log and pipeline metadata
+        #[arg(long, default_value = "data")]
+        data_root: String,
     },
     /// Step 7: Enrich data with additional information
     Enrich {
@@ -118,6 +189,9 @@ enum Commands {
         /// Output file path
         #[arg(long)]
         output: Option<String>,
+        /// Data root holding the CAS, ingest log and pipeline metadata
+        #[arg(long, default_value = "data")]
+        data_root: String,
     },
     /// Step 8: Conflate entities across sources to resolve duplicates
     Conflation {
@@ -130,12 +204,14 @@ enum Commands {
         /// Process all enriched files
         #[arg(long)]
         all_enriched: bool,
-        /// Confidence threshold for matching (0.0-1.0)
-        #[arg(long, default_value = "0.8")]
-        confidence_threshold: f64,
+        #[command(flatten)]
+        conflator: ConflatorArgs,
         /// Output file path
         #[arg(long)]
         output: Option<String>,
+        /// Data root holding the CAS, ingest log and pipeline metadata
+        #[arg(long, default_value = "data")]
+        data_root: String,
     },
     /// Step 9: Catalog final entities into the graph database
     Catalog {
@@ -148,337 +224,884 @@ enum Commands {
         /// Validate graph integrity after cataloging
         #[arg(long)]
         validate_graph: bool,
-        /// Storage mode: "memory" or "database"
-        #[arg(long, default_value = "database")]
-        storage_mode: String,
+        #[command(flatten)]
+        conflator: ConflatorArgs,
+        /// Storage to run against
+        #[arg(long, value_enum, default_value_t = StorageMode::Database)]
+        storage_mode: StorageMode,
+        #[command(subcommand)]
+        action: Option<CatalogAction>,
+        /// Data root holding the CAS, ingest log and pipeline metadata
+        #[arg(long, default_value = "data")]
+        data_root: String,
+    },
+    /// Ingest every enabled registry source through the gateway and print a summary
+    #[command(name = "gateway-all")]
+    GatewayAll {
+        /// Bypass cadence (fetch even if fetched within the last interval)
+        #[arg(long)]
+        bypass_cadence: bool,
+        /// Maximum sources ingesting at once
+        #[arg(long, default_value = "4")]
+        concurrency: usize,
+        /// Maximum endpoints fetching at once from the same host
+        #[arg(long, default_value = "1")]
+        per_host: usize,
+        /// Where to write the JSON report (defaults to data/reports/gateway_all_<timestamp>.json)
+        #[arg(long)]
+        report: Option<std::path::PathBuf>,
+        /// Keep running, starting a round every SECS seconds; registry edits are picked up
+        /// between rounds without a restart
+        #[arg(long, value_name = "SECS")]
+        every: Option<u64>,
+        /// Data root holding the CAS, ingest log and pipeline metadata
+        #[arg(long, default_value = "data")]
+        data_root: String,
+    },
+    /// Show, apply or revert versioned database schema migrations
+    Migrate {
+        #[command(subcommand)]
+        action: MigrateAction,
+    },
+    /// Inspect, retry or discard envelopes that failed to parse
+    Dlq {
+        /// Data root holding the dead-letter queue
+        #[arg(long, default_value = "data")]
+        data_root: String,
+        #[command(subcommand)]
+        action: DlqAction,
+    },
+    /// Check database access, registry entries, CAS storage, the Pushgateway and required
+    /// environment variables, printing a fix for anything that fails
+    Doctor {
+        /// Data root holding the CAS and ingest log
+        #[arg(long, default_value = "data")]
+        data_root: String,
+        /// Directory of registry source specs
+        #[arg(long, default_value = "registry/sources")]
+        registry_dir: String,
+    },
+    /// Maintain the content-addressed payload store
+    Cas {
+        #[command(subcommand)]
+        action: CasAction,
+    },
+    /// Inspect registry sources
+    Sources {
+        #[command(subcommand)]
+        action: SourcesAction,
+    },
+    /// Tools for investigating parser problems
+    Debug {
+        #[command(subcommand)]
+        action: DebugAction,
+    },
+    /// Bundle a source's recent envelopes into a tarball that reproduces its parsing locally
+    Snapshot {
+        #[command(subcommand)]
+        action: SnapshotAction,
+    },
+    /// Hide events and venues from public listings or show them again, recording each
+    /// decision with its reason as a process run
+    Moderate {
+        /// Storage to run against
+        #[arg(long, value_enum, default_value_t = StorageMode::Database)]
+        storage_mode: StorageMode,
+        #[command(subcommand)]
+        action: ModerateAction,
+    },
+    /// Merge two artists or venues conflation wrongly kept apart: events move to the kept
+    /// one, which answers to the removed one's name, and the removed id is tombstoned so
+    /// later runs resolve it to the kept id
+    Merge {
+        /// Storage to run against
+        #[arg(long, value_enum, default_value_t = StorageMode::Database)]
+        storage_mode: StorageMode,
+        /// Data root holding conflation/resolution.db
+        #[arg(long, default_value = "data")]
+        data_root: String,
+        #[command(subcommand)]
+        action: MergeAction,
+    },
+    /// Summarize catalog contents: entity counts, events per venue, upcoming vs past
+    /// events, recent additions and sources that have gone quiet
+    Stats {
+        /// Storage to run against
+        #[arg(long, value_enum, default_value_t = StorageMode::Database)]
+        storage_mode: StorageMode,
+        /// Directory of registry source specs; enabled sources are checked for recent events
+        #[arg(long, default_value = "registry/sources")]
+        registry_dir: String,
+        /// Days counted as recent
+        #[arg(long, default_value = "7")]
+        days: i64,
+    },
+    /// Nightly consistency audit: past events never finalized, venues without events for
+    /// 90 days, artists with no events and sources whose latest run report disagrees with
+    /// the catalog. Writes a JSON report under <data-root>/audit and sets audit metrics,
+    /// pushing them when a Pushgateway is configured (see --push/--no-push).
+    Audit {
+        /// Storage to run against
+        #[arg(long, value_enum, default_value_t = StorageMode::Database)]
+        storage_mode: StorageMode,
+        /// Data root holding ingest_log/meta.db; the report is written to its audit/ directory
+        #[arg(long, default_value = "data")]
+        data_root: String,
+        /// Days without an event after which a venue is reported inactive
+        #[arg(long, default_value = "90")]
+        inactive_days: i64,
+    },
+    /// Inspect the ingest log
+    #[command(name = "ingest-log")]
+    IngestLog {
+        #[command(subcommand)]
+        action: IngestLogAction,
+    },
+    /// Maintain the ingest metadata db (idempotency keys, offsets, run history)
+    #[command(name = "ingest-meta")]
+    IngestMeta {
+        #[command(subcommand)]
+        action: IngestMetaAction,
+    },
+    /// Track envelopes through processing
+    Envelope {
+        #[command(subcommand)]
+        action: EnvelopeAction,
+    },
+    /// Generate the files for onboarding a new source
+    Scaffold {
+        #[command(subcommand)]
+        action: ScaffoldAction,
+    },
+    /// Check sources' parsers and normalizers against committed fixture payloads
+    Contract {
+        #[command(subcommand)]
+        action: ContractAction,
+    },
+    /// Check the metric name table
+    Metrics {
+        #[command(subcommand)]
+        action: MetricsAction,
+    },
+    /// Print a shell completion script to stdout
+    Completions {
+        /// Shell to generate completions for
+        shell: clap_complete::Shell,
+    },
+}
+
+/// Options of `full-pipeline`
+#[derive(Args)]
+struct FullPipelineArgs {
+    #[arg(long)]
+    source_id: String,
+    #[arg(long, default_value = "false")]
+    bypass_cadence: bool,
+    #[command(flatten)]
+    conflator: ConflatorArgs,
+    /// Days either side of an event's date to look for a cataloged duplicate (0 = same day)
+    #[arg(long, default_value = "0")]
+    duplicate_window_days: u32,
+    /// How a suppressed duplicate is merged: "fill_missing" or "keep_existing"
+    #[arg(long, default_value = "fill_missing")]
+    duplicate_merge_policy: DuplicateMergePolicy,
+    /// Workers per pipelined stage as stage=count pairs, e.g. "parse=2,enrich=4"
+    /// (stages: parse, normalize, quality_gate, enrich, conflate; default 1 each)
+    #[arg(long, default_value = "")]
+    stage_workers: StageConcurrency,
+    /// Records buffered between two pipeline stages
+    #[arg(long, default_value_t = DEFAULT_CHANNEL_CAPACITY)]
+    stage_channel_capacity: usize,
+    /// TOML quality gate config the active gate decides with (built-in rules by default)
+    #[arg(long, value_name = "PATH")]
+    quality_gate: Option<std::path::PathBuf>,
+    /// TOML config whose `[sinks]` table routes the normalize and quality gate stages'
+    /// records (none are written by default)
+    #[arg(long, value_name = "PATH")]
+    sinks_config: Option<std::path::PathBuf>,
+    /// TOML quality gate config to score events with in shadow mode beside the active
+    /// gate (defaults to SMS_QUALITY_GATE_SHADOW); only counted in the run report
+    #[arg(long, value_name = "PATH")]
+    shadow_quality_gate: Option<std::path::PathBuf>,
+    /// Storage to run against; "memory" runs end to end with no database, env vars or
+    /// `data/` writes (demos, CI)
+    #[arg(long = "storage", value_enum, default_value_t = StorageMode::Database)]
+    storage_mode: StorageMode,
+    /// Data root holding the CAS, ingest log and pipeline metadata
+    #[arg(long, default_value = "data")]
+    data_root: String,
+}
+
+/// Where a command reads and writes the catalog
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum StorageMode {
+    /// The libSQL database configured by the environment
+    Database,
+    /// A fresh in-memory store, dropped on exit
+    Memory,
+}
+
+/// Conflation thresholds and tie-breaking, shared by every command that conflates
+#[derive(Args)]
+struct ConflatorArgs {
+    /// Confidence threshold for matching (0.0-1.0), for entity types without their own
+    #[arg(long, default_value = "0.8")]
+    confidence_threshold: f64,
+    /// Threshold for venue matches, overriding --confidence-threshold
+    #[arg(long)]
+    venue_threshold: Option<f64>,
+    /// Threshold for event matches, overriding --confidence-threshold
+    #[arg(long)]
+    event_threshold: Option<f64>,
+    /// Threshold for artist matches, overriding --confidence-threshold
+    #[arg(long)]
+    artist_threshold: Option<f64>,
+    /// How to resolve candidates tied for the best score: "lowest_id" or "uncertain"
+    #[arg(long, default_value = "lowest_id")]
+    tie_break: TieBreakStrategy,
+    /// Venues within this many meters of each other count as the same location
+    #[arg(long, default_value = "50")]
+    venue_radius_m: f64,
+}
+
+impl ConflatorArgs {
+    fn into_config(self) -> ConflatorConfig {
+        ConflatorConfig {
+            venue_threshold: self.venue_threshold.unwrap_or(self.confidence_threshold),
+            event_threshold: self.event_threshold.unwrap_or(self.confidence_threshold),
+            artist_threshold: self.artist_threshold.unwrap_or(self.confidence_threshold),
+            tie_break: self.tie_break,
+            venue_radius_m: self.venue_radius_m,
+        }
+    }
+}
+
+#[derive(Subcommand)]
+enum DlqAction {
+    /// List dead-lettered envelopes
+    List {
+        /// Only show entries for this source
+        #[arg(long)]
+        source: Option<String>,
+    },
+    /// Re-parse dead-lettered envelopes, removing the ones that now succeed
+    Retry {
+        /// Retry a single envelope
+        #[arg(long)]
+        envelope_id: Option<String>,
+        /// Retry every entry in the queue
+        #[arg(long)]
+        all: bool,
+    },
+    /// Drop an envelope from the queue without re-parsing it
+    Discard {
+        #[arg(long)]
+        envelope_id: String,
+    },
+}
+
+#[derive(Subcommand)]
+enum CasAction {
+    /// Delete payloads no longer referenced by the ingest log, recorded lineage or the
+    /// dead-letter queue, from local storage or Supabase, and report the bytes reclaimed
+    Gc {
+        /// Data root holding the CAS, ingest log and lineage DB
+        #[arg(long, default_value = "data")]
+        data_root: String,
+        /// Storage holding the raw data whose payloads are kept
+        #[arg(long, value_enum, default_value_t = StorageMode::Database)]
+        storage_mode: StorageMode,
+        /// Also expire payloads whose ingest log lines are older than this many days
+        #[arg(long)]
+        retention_days: Option<i64>,
+        /// Never delete objects written in the last N hours
+        #[arg(long, default_value = "24")]
+        min_age_hours: i64,
+        /// Report what would be deleted without deleting anything
+        #[arg(long)]
+        dry_run: bool,
+    },
+}
+
+#[derive(Subcommand)]
+enum SourcesAction {
+    /// List registry sources with their cadence and next eligible fetch time
+    List {
+        #[arg(long, default_value = "registry/sources")]
+        registry_dir: String,
+        #[arg(long, default_value = "data")]
+        data_root: String,
+    },
+    /// Show one source's registry settings, fetch history and remaining monthly quota
+    Describe {
+        #[arg(long)]
+        source_id: String,
+        #[arg(long, default_value = "registry/sources")]
+        registry_dir: String,
+        #[arg(long, default_value = "data")]
+        data_root: String,
+    },
+}
+
+#[derive(Subcommand)]
+enum CatalogAction {
+    /// Undo a catalog run from its process records: hide the venues and events it created and
+    /// restore the entities it updated
+    Rollback {
+        #[arg(long)]
+        run_id: uuid::Uuid,
+        /// List the affected entities without changing anything
+        #[arg(long)]
+        dry_run: bool,
+    },
+}
+
+#[derive(Subcommand)]
+enum ModerateAction {
+    /// Hide an event; later scrapes of it keep it hidden
+    #[command(name = "hide-event")]
+    HideEvent {
+        id: uuid::Uuid,
+        #[arg(long)]
+        reason: String,
+    },
+    /// Show an event hidden by a moderator or a catalog rollback
+    #[command(name = "show-event")]
+    ShowEvent {
+        id: uuid::Uuid,
+        #[arg(long)]
+        reason: Option<String>,
+    },
+    /// Hide a venue; its events are moderated separately
+    #[command(name = "hide-venue")]
+    HideVenue {
+        id: uuid::Uuid,
+        #[arg(long)]
+        reason: String,
+    },
+    /// Show a hidden venue again
+    #[command(name = "show-venue")]
+    ShowVenue {
+        id: uuid::Uuid,
+        #[arg(long)]
+        reason: Option<String>,
+    },
+}
+
+#[derive(Subcommand)]
+enum MergeAction {
+    /// Merge artist <REMOVE> into artist <KEEP>
+    Artists {
+        keep: uuid::Uuid,
+        remove: uuid::Uuid,
+        #[arg(long)]
+        reason: Option<String>,
+    },
+    /// Merge venue <REMOVE> into venue <KEEP>, with its events and recurring series
+    Venues {
+        keep: uuid::Uuid,
+        remove: uuid::Uuid,
+        #[arg(long)]
+        reason: Option<String>,
+    },
+}
+
+#[derive(Subcommand)]
+enum DebugAction {
+    /// Write an envelope's payload to local files (raw bytes, pretty JSON or a browser-openable
+    /// HTML copy) with a meta.json saying which parser ran and why it found no events
+    Snapshot {
+        #[arg(long)]
+        envelope_id: String,
+        #[arg(long, default_value = "data")]
+        data_root: String,
+        /// Directory the snapshot folder is created in (defaults to <data-root>/snapshots)
+        #[arg(long)]
+        out_dir: Option<std::path::PathBuf>,
+    },
+}
+
+#[derive(Subcommand)]
+enum SnapshotAction {
+    /// Bundle a source's last N envelopes with their payloads, registry spec and parse and
+    /// normalize outputs into a .tar.gz that replays without database or CAS access
+    Create {
+        #[arg(long)]
+        source_id: String,
+        /// Number of most recent envelopes to bundle
+        #[arg(long, default_value = "10")]
+        last: usize,
+        #[arg(long, default_value = "data")]
+        data_root: String,
+        #[arg(long, default_value = "registry/sources")]
+        registry_dir: String,
+        /// Bundle path (defaults to <data-root>/snapshots/<source-id>-<timestamp>.tar.gz)
+        #[arg(long)]
+        out: Option<std::path::PathBuf>,
+    },
+    /// Replay a bundle with this build's parsers and normalizers and report what changed
+    Run {
+        bundle: std::path::PathBuf,
+        /// Write the replayed parse and normalize outputs under this directory
+        #[arg(long)]
+        out_dir: Option<std::path::PathBuf>,
+    },
+}
+
+#[derive(Subcommand)]
+enum ParseAction {
+    /// Re-parse a source's recent envelopes with its registered parser plan and another
+    /// version of it, reporting record-count and field-level differences
+    Compare {
+        #[arg(long)]
+        source_id: String,
+        /// Parser plan version to compare against the registered one
+        #[arg(long)]
+        against_version: u32,
+        /// Number of most recent envelopes to compare
+        #[arg(long, default_value = "10")]
+        limit: usize,
+        /// Data root holding the ingest log
+        #[arg(long, default_value = "data")]
+        data_root: String,
+        /// Where to write the JSON report (defaults to data/reports/parse_compare_<source>_<timestamp>.json)
+        #[arg(long)]
+        report: Option<std::path::PathBuf>,
+    },
+    /// Parse the envelopes past a consumer's offset in the ingest log, writing records to
+    /// output/<timestamp>_<output>
+    Log {
+        /// Consumer whose offset to read from and ack
+        #[arg(long, default_value = "parser")]
+        consumer: String,
+        /// Envelopes to read at most
+        #[arg(long, default_value = "50")]
+        max: usize,
+        /// Data root holding the ingest log and CAS
+        #[arg(long, default_value = "data")]
+        data_root: std::path::PathBuf,
+        /// Only parse this source's envelopes
+        #[arg(long)]
+        source_id: Option<String>,
+        #[arg(long, default_value = "parsed.ndjson")]
+        output: String,
+        /// Normalize the parsed records
+        #[arg(long)]
+        normalize: bool,
+        /// Assess normalized records with the quality gate (requires --normalize)
+        #[arg(long)]
+        quality_gate: bool,
+        /// TOML config whose `[sinks]` table routes stage outputs (NDJSON files by default)
+        #[arg(long, value_name = "PATH")]
+        sinks_config: Option<std::path::PathBuf>,
+    },
+}
+
+#[derive(Subcommand)]
+enum ContractAction {
+    /// Parse and normalize a source's fixtures and check that each yields at least one event,
+    /// no event more than a year from the fixture's capture date, and named venues. Writes a
+    /// JUnit XML report and exits non-zero when an invariant fails.
+    Test {
+        #[arg(long)]
+        source_id: String,
+        /// Directory of registry source specs
+        #[arg(long, default_value = "registry/sources")]
+        registry_dir: std::path::PathBuf,
+        /// Directory holding a `<source_id>/` folder of fixture payloads
+        #[arg(long, default_value = DEFAULT_FIXTURES_DIR)]
+        fixtures_dir: std::path::PathBuf,
+        /// Where to write the JUnit XML report (defaults to contract-reports/<source_id>.xml)
+        #[arg(long, value_name = "PATH")]
+        junit: Option<std::path::PathBuf>,
+    },
+}
+
+#[derive(Subcommand)]
+enum MetricsAction {
+    /// Check every metric name against the naming conventions for its kind and unit
+    /// (`_total`, `_seconds`, `_bytes`), look for duplicates, and self-test that each
+    /// renders under its declared kind. Exits non-zero on any violation.
+    Lint,
+}
+
+#[derive(Subcommand)]
+enum EnvelopeAction {
+    /// Show an envelope's processing state and every state it has been through
+    Status {
+        /// Envelope id
+        envelope_id: String,
+        /// Data root holding the ingest log
+        #[arg(long, default_value = "data")]
+        data_root: String,
+    },
+    /// List envelopes that have sat in received, parsed or normalized too long
+    Stuck {
+        /// Data root holding the ingest log
+        #[arg(long, default_value = "data")]
+        data_root: String,
+        /// Seconds in one state before an envelope counts as stuck
+        /// (defaults to SMS_ENVELOPE_STUCK_AFTER_SECS, else 3600)
+        #[arg(long)]
+        older_than_secs: Option<i64>,
+    },
+}
+
+#[derive(Subcommand)]
+enum ScaffoldAction {
+    /// Write a registry spec, normalizer stub and fixture placeholders for a new source and
+    /// register the normalizer
+    Source {
+        /// Source id (snake_case)
+        #[arg(long)]
+        id: String,
+        /// Parser plan to parse its payloads with, e.g. wix_calendar_v1
+        #[arg(long)]
+        parser: String,
+        /// Endpoint to fetch
+        #[arg(long)]
+        url: String,
+        /// Venue name (defaults to the title-cased id)
+        #[arg(long)]
+        name: Option<String>,
+        /// Workspace root to generate into
+        #[arg(long, default_value = ".")]
+        root: std::path::PathBuf,
+    },
+}
+
+#[derive(Subcommand)]
+enum IngestLogAction {
+    /// Show a consumer's position in the log, its lag and pending envelopes per source
+    Status {
+        /// Data root holding the ingest log
+        #[arg(long, default_value = "data")]
+        data_root: String,
+        /// Consumer whose offset to report
+        #[arg(long, default_value = "parser")]
+        consumer: String,
+    },
+}
+
+#[derive(Subcommand)]
+enum IngestMetaAction {
+    /// Show how many idempotency keys the dedupe index holds, their age and per-source counts
+    Stats {
+        /// Data root holding ingest_log/meta.db
+        #[arg(long, default_value = "data")]
+        data_root: String,
+    },
+    /// Rebuild meta.db to reclaim the space of deleted rows
+    Vacuum {
+        #[arg(long, default_value = "data")]
+        data_root: String,
+    },
+    /// Drop idempotency keys recorded longer ago than an age like 30d or 12h
+    Expire {
+        #[arg(long, default_value = "data")]
+        data_root: String,
+        /// Age past which keys are dropped (s, m, h, d or w; a bare number is days)
+        #[arg(long, value_parser = parse_age_secs)]
+        older_than: i64,
+    },
+}
+
+#[derive(Subcommand)]
+enum MigrateAction {
+    /// List known migrations and whether each is applied
+    Status,
+    /// Apply pending migrations
+    Up {
+        /// Stop at this version instead of the latest
+        #[arg(long)]
+        to: Option<u32>,
+    },
+    /// Revert applied migrations
+    Down {
+        /// Revert everything newer than this version (defaults to reverting only the latest)
+        #[arg(long)]
+        to: Option<u32>,
+        /// Confirm the revert; down scripts drop tables and the data in them
+        #[arg(long)]
+        yes: bool,
     },
 }
 
 #[tokio::main]
-async fn main() -> anyhow::Result<()> {
+async fn main() {
     let cli = Cli::parse();
-    
+    let json = cli.json;
+    // Errors that escape a command are reported like any other failure
+    if let Err(e) = run(cli).await {
+        tracing::error!("{:#}", e);
+        summarize_failure(json, "sms-scraper", &format!("{:#}", e));
+    }
+}
+
+async fn run(cli: Cli) -> anyhow::Result<()> {
     // Load environment variables
     dotenv::dotenv().ok();
     
-    // Initialize logging
-    tracing_subscriber::fmt::init();
-    
-    // Initialize database storage
-    info!("Initializing database storage...");
-    let storage: Arc<dyn Storage> = Arc::new(DatabaseStorage::new().await?);
-    info!("Database storage initialized successfully");
+    // Initialize logging (and trace export when an OTLP endpoint is configured)
+    let otlp_endpoint = otel::otlp_endpoint(cli.otlp_endpoint.as_deref());
+    if let Err(e) = init_console_logging(cli.log_format, otlp_endpoint.as_deref(), cli.json) {
+        summarize_failure(cli.json, "startup", &format!("Failed to initialize OTLP trace export: {:#}", e));
+    }
+    if let Some(endpoint) = &otlp_endpoint {
+        info!("Exporting traces to {}", otel::traces_url(endpoint));
+    }
 
-    match cli.command {
-        Commands::Ingester { apis, bypass_cadence } => {
-            println!("🕷️  Starting SMS scraper ingestion for APIs: {}", apis);
-            
-            // Set bypass cadence environment variable if requested
-            if bypass_cadence {
-                std::env::set_var("SMS_BYPASS_CADENCE", "1");
-                println!("🚀 Bypassing cadence restrictions");
-            }
-            
-            // Create orchestrator and run ingestion
-            let orchestrator = FullPipelineOrchestrator::new().await?;
-            
-            // Parse the comma-separated API list
-            let api_list: Vec<&str> = apis.split(',').map(|s| s.trim()).collect();
-            
-            for api_name in api_list {
-                println!("🔄 Running ingestion for: {}", api_name);
-                if let Err(e) = orchestrator.run_ingestion_for_source(api_name).await {
-                    eprintln!("❌ Ingestion failed for {}: {}", api_name, e);
-                } else {
-                    println!("✅ Ingestion completed for: {}", api_name);
+    // Metrics go to SMS_PUSHGATEWAY_URL, the environment's default or nowhere; never a guess
+    let push_flag = if cli.push { Some(true) } else if cli.no_push { Some(false) } else { None };
+    let push = match PushConfig::resolve(push_flag, |name| std::env::var(name).ok()) {
+        Ok(push) => push,
+        Err(e) => summarize_failure(cli.json, "startup", &format!("Invalid metrics push configuration: {:#}", e)),
+    };
+
+    // Enrichers pick the boundaries up from the environment wherever they are built
+    if let Some(path) = &cli.neighborhoods {
+        match assets::neighborhoods_from_path(path) {
+            Ok(index) => {
+                if !cli.json {
+                    println!("🗺️  Using {} neighborhoods from {}", index.len(), path.display());
                 }
+                std::env::set_var(NEIGHBORHOODS_ENV, path);
+            }
+            Err(e) => {
+                tracing::error!("Failed to load neighborhoods GeoJSON: {:#}", e);
+                summarize_failure(cli.json, "startup", &format!("Failed to load neighborhoods GeoJSON {}: {:#}", path.display(), e));
             }
         }
-        Commands::FullPipeline { source_id, bypass_cadence } => {
-            println!("🔄 Running full pipeline for source: {}", source_id);
-            
-            // Set bypass cadence environment variable if requested
-            if bypass_cadence {
-                std::env::set_var("SMS_BYPASS_CADENCE", "1");
-                println!("🚀 Bypassing cadence restrictions");
+    }
+    if let Some(path) = &cli.event_tag_rules {
+        match assets::event_classifier_from_path(path) {
+            Ok(classifier) => {
+                if !cli.json {
+                    println!("🏷️  Using {} event tag rules from {}", classifier.len(), path.display());
+                }
+                std::env::set_var(EVENT_TAG_RULES_ENV, path);
+            }
+            Err(e) => {
+                tracing::error!("Failed to load event tag rules: {:#}", e);
+                summarize_failure(cli.json, "startup", &format!("Failed to load event tag rules {}: {:#}", path.display(), e));
             }
-            
-            // Create the full pipeline orchestrator
-            match FullPipelineOrchestrator::new().await {
-                Ok(orchestrator) => {
-                    // Process the source through the complete pipeline
-                    match orchestrator.process_source(&source_id).await {
-                        Ok(result) => {
-                            println!("📊 Pipeline Results for {}:", result.source_id);
-                            println!("   📁 Total items: {}", result.total_items);
-                            println!("   ✅ Processed: {}", result.processed_items);
-                            println!("   ❌ Failed: {}", result.failed_items);
-                            println!("   📈 Success rate: {:.1}%", result.success_rate());
-                            
-                            if !result.errors.is_empty() {
-                                println!("   🚨 Errors encountered:");
-                                for error in &result.errors {
-                                    println!("      - {}", error);
-                                }
-                            }
-                            
-                            if result.is_success() {
-                                println!("✅ Pipeline completed successfully - entities created/updated in database");
-                            } else {
-                                println!("⚠️  Pipeline completed with errors - check logs for details");
-                            }
-                        }
-                        Err(e) => {
-                            tracing::error!("Pipeline processing failed for {}: {}", source_id, e);
-                            println!("❌ Pipeline failed for {}: {}", source_id, e);
-                        }
-                    }
+        }
+    }
+    if cli.preload {
+        match assets::preload() {
+            Ok(loads) => {
+                for load in &loads {
+                    info!("Preloaded {} from {} in {:.1}ms", load.asset, load.source, load.duration_ms);
                 }
-                Err(e) => {
-                    tracing::error!("Failed to create pipeline orchestrator: {}", e);
-                    println!("❌ Failed to initialize pipeline: {}", e);
+                if !cli.json {
+                    let total: f64 = loads.iter().map(|l| l.duration_ms).sum();
+                    println!("📦 Preloaded {} assets in {:.1}ms", loads.len(), total);
                 }
             }
+            Err(e) => {
+                tracing::error!("Failed to preload assets: {:#}", e);
+                summarize_failure(cli.json, "startup", &format!("Failed to preload assets: {:#}", e));
+            }
         }
-        Commands::ReprocessAll { source_id } => {
-            println!("🔄 Reprocessing ALL raw data for source: {}", source_id);
-            
-            println!("❌ ReprocessAll command temporarily disabled due to compilation issues");
-            println!("💡 Use the clear-db and full-pipeline commands instead:");
-            println!("   1. cargo run --bin sms-scraper -- clear-db --venue-slug {}", source_id);
-            println!("   2. cargo run --bin sms-scraper -- full-pipeline --source-id {}", source_id);
+    }
+    // HTTP clients read the user agent from the environment wherever they are built
+    if let Some(user_agent) = &cli.user_agent {
+        std::env::set_var(USER_AGENT_ENV, user_agent);
+    }
+    if let Some(contact) = &cli.user_agent_contact {
+        std::env::set_var(USER_AGENT_CONTACT_ENV, contact);
+    }
+    
+    // Completions only need the command definition
+    if let Commands::Completions { shell } = cli.command {
+        clap_complete::generate(shell, &mut Cli::command(), "sms-scraper", &mut std::io::stdout());
+        return Ok(());
+    }
+
+    // Migrations run before storage init, which would otherwise apply everything pending
+    if let Commands::Migrate { action } = cli.command {
+        let result = maintenance::run_migrate(action, cli.json).await;
+        shutdown_tracing();
+        return result;
+    }
+
+    // Parser comparisons only read the ingest log and CAS, so they don't need the database
+    if let Commands::Parse { action: Some(action), .. } = cli.command {
+        let result = stages::run_parse_action(action, cli.json).await;
+        shutdown_tracing();
+        return result;
+    }
+
+    // The doctor reports a missing or unreachable database instead of failing on it
+    if let Commands::Doctor { data_root, registry_dir } = cli.command {
+        let healthy = maintenance::run_doctor(&data_root, &registry_dir, &push, cli.json).await;
+        if !healthy {
+            exit_failed();
         }
-        Commands::ClearDb { venue_slug } => {
-            use sms_core::database::DatabaseManager;
-            
-            println!("🗑️  Clearing database data...");
-            
-            if let Some(slug) = venue_slug {
-                println!("🗑️  Deleting data for venue '{}'...", slug);
-                println!("⚠️  WARNING: This will permanently delete the venue and all its events!");
-                
-                // Create a direct database manager instance to access delete methods
-                match DatabaseManager::new().await {
-                    Ok(db_manager) => {
-                        // Use the simplified delete method from sms-core
-                        match db_manager.delete_venue_data(&slug).await {
-                            Ok(()) => {
-                                println!("✅ Successfully deleted all data for venue '{}'!", slug);
-                            }
-                            Err(e) => {
-                                tracing::error!("Failed to delete venue data: {}", e);
-                                println!("❌ Failed to delete venue data: {}", e);
-                            }
-                        }
-                    }
-                    Err(e) => {
-                        tracing::error!("Failed to create database manager: {}", e);
-                        println!("❌ Failed to connect to database: {}", e);
-                    }
-                }
-            } else {
-                println!("🗑️  Clearing ALL data from the database...");
-                println!("⚠️  WARNING: This will permanently delete ALL data!");
-                println!("💡 Tip: Use --venue-slug to delete data for a specific venue only");
-                println!("❌ Full database clear not implemented in this command");
-                println!("   Use the database management tools directly if needed");
-            }
+        shutdown_tracing();
+        return Ok(());
+    }
+
+    // CAS maintenance works on the data root, the bucket and the raw data it keeps payloads for
+    if let Commands::Cas { action } = cli.command {
+        let result = ingest::run_cas(action, cli.json).await;
+        shutdown_tracing();
+        return result;
+    }
+
+    // Listing sources reads the registry and ingest metadata only
+    if let Commands::Sources { action } = cli.command {
+        let result = ingest::run_sources(action, cli.json);
+        shutdown_tracing();
+        return result;
+    }
+
+    // Snapshots read the ingest log and CAS only
+    if let Commands::Debug { action } = cli.command {
+        let result = debug::run_debug(action).await;
+        shutdown_tracing();
+        return result;
+    }
+
+    // Snapshot bundles are built from the ingest log and CAS, and replay from the bundle alone
+    if let Commands::Snapshot { action } = cli.command {
+        let result = debug::run_snapshot(action, cli.json).await;
+        shutdown_tracing();
+        return result;
+    }
+
+    // Rollback picks its own storage backend
+    if let Commands::Catalog { action: Some(action), storage_mode, .. } = &cli.command {
+        let result = catalog::run_catalog_action(action, *storage_mode, cli.json).await;
+        shutdown_tracing();
+        return result;
+    }
+
+    // Moderation picks its own storage backend
+    if let Commands::Moderate { storage_mode, action } = cli.command {
+        let result = catalog::run_moderate(action, storage_mode, cli.json).await;
+        shutdown_tracing();
+        return result;
+    }
+
+    // Merges pick their own storage backend
+    if let Commands::Merge { storage_mode, data_root, action } = cli.command {
+        let result = catalog::run_merge(action, storage_mode, &data_root, cli.json).await;
+        shutdown_tracing();
+        return result;
+    }
+
+    // Stats pick their own storage backend
+    if let Commands::Stats { storage_mode, registry_dir, days } = cli.command {
+        let result = catalog::run_stats(storage_mode, &registry_dir, days, cli.json).await;
+        shutdown_tracing();
+        return result;
+    }
+
+    // The audit picks its own storage backend
+    if let Commands::Audit { storage_mode, data_root, inactive_days } = cli.command {
+        let result = catalog::run_audit(storage_mode, &data_root, inactive_days, cli.json, &push).await;
+        shutdown_tracing();
+        return result;
+    }
+
+    // Envelope state lives in the ingest metadata
+    if let Commands::Envelope { action } = cli.command {
+        let result = ingest::run_envelope(action, cli.json);
+        shutdown_tracing();
+        return result;
+    }
+
+    // Contract tests only read fixtures and the registry
+    if let Commands::Contract { action } = cli.command {
+        let result = maintenance::run_contract(action, cli.json).await;
+        shutdown_tracing();
+        return result;
+    }
+
+    // The metric lint only inspects the metric table
+    if let Commands::Metrics { action } = cli.command {
+        let result = maintenance::run_metrics(action, cli.json);
+        shutdown_tracing();
+        return result;
+    }
+
+    // Scaffolding only writes source files
+    if let Commands::Scaffold { action } = cli.command {
+        let result = maintenance::run_scaffold(action, cli.json);
+        shutdown_tracing();
+        return result;
+    }
+
+    // Log status reads the ingest log backend only
+    if let Commands::IngestLog { action } = cli.command {
+        let result = ingest::run_ingest_log(action, cli.json);
+        shutdown_tracing();
+        return result;
+    }
+
+    // The dead-letter queue lives under the data root and re-parses from CAS
+    if let Commands::Dlq { data_root, action } = cli.command {
+        let result = ingest::run_dlq(std::path::Path::new(&data_root), action, cli.json).await;
+        shutdown_tracing();
+        return result;
+    }
+
+    // Dedupe index maintenance only touches meta.db
+    if let Commands::IngestMeta { action } = cli.command {
+        let result = ingest::run_ingest_meta(action, cli.json);
+        shutdown_tracing();
+        return result;
+    }
+
+    // Initialize database storage, unless the command runs entirely in memory
+    let in_memory = matches!(&cli.command, Commands::FullPipeline(args) if args.storage_mode == StorageMode::Memory);
+    if !in_memory {
+        info!("Initializing database storage...");
+        let _storage: Arc<dyn Storage> = Arc::new(DatabaseStorage::new().await?);
+        info!("Database storage initialized successfully");
+    }
+
+    let json = cli.json;
+    match cli.command {
+        Commands::Ingester { apis, bypass_cadence, data_root } => pipeline::run_ingester(apis, bypass_cadence, data_root, json).await?,
+        Commands::FullPipeline(args) => pipeline::run_full_pipeline(args, json).await?,
+        Commands::ReprocessAll { source_id, since, limit, batch_size, data_root } => {
+            pipeline::run_reprocess_all(source_id, since, limit, batch_size, data_root, json).await?
         }
-        Commands::Parse { input, source, all_sources, output } => {
-            println!("📄 Step 4: Parse - Converting raw data to neutral records");
-            
-            if let Some(source_id) = source {
-                match FullPipelineOrchestrator::new().await {
-                    Ok(orchestrator) => {
-                        match orchestrator.run_parse_for_source(&source_id).await {
-                            Ok(()) => {
-                                println!("✅ Parse completed successfully for {}", source_id);
-                            }
-                            Err(e) => {
-                                tracing::error!("Parse failed for {}: {}", source_id, e);
-                                println!("❌ Parse failed for {}: {}", source_id, e);
-                            }
-                        }
-                    }
-                    Err(e) => {
-                        tracing::error!("Failed to create pipeline orchestrator: {}", e);
-                        println!("❌ Failed to initialize pipeline: {}", e);
-                    }
-                }
-            } else {
-                println!("❌ Please specify a source with --source <source_name>");
-                println!("📋 Available sources: blue_moon, barboza, neumos, etc.");
-            }
+        Commands::ClearDb { venue_slug } => pipeline::run_clear_db(venue_slug, json).await?,
+        Commands::Parse { action: Some(_), .. } => unreachable!("handled before storage init"),
+        Commands::Parse { action: None, input: _, source, all_sources: _, output: _, data_root } => {
+            stages::run_parse(source, data_root, json).await?
         }
-        Commands::Normalize { input, source, all_sources, output } => {
-            println!("🔧 Step 5: Normalize - Standardizing parsed data");
-            
-            if let Some(source_id) = source {
-                match FullPipelineOrchestrator::new().await {
-                    Ok(orchestrator) => {
-                        match orchestrator.run_normalize_for_source(&source_id).await {
-                            Ok(()) => {
-                                println!("✅ Normalize completed successfully for {}", source_id);
-                            }
-                            Err(e) => {
-                                tracing::error!("Normalize failed for {}: {}", source_id, e);
-                                println!("❌ Normalize failed for {}: {}", source_id, e);
-                            }
-                        }
-                    }
-                    Err(e) => {
-                        tracing::error!("Failed to create pipeline orchestrator: {}", e);
-                        println!("❌ Failed to initialize pipeline: {}", e);
-                    }
-                }
-            } else {
-                println!("❌ Please specify a source with --source <source_name>");
-                println!("📋 Available sources: blue_moon, barboza, neumos, etc.");
-            }
+        Commands::Normalize { input: _, source, all_sources: _, output: _, data_root } => {
+            stages::run_normalize(source, data_root, json).await?
         }
-        Commands::QualityGate { input: _, sources, all_sources: _, output: _ } => {
-            println!("🛡️ Step 6: Quality Gate - Validating data quality");
-            
-            if let Some(source_id) = sources {
-                match FullPipelineOrchestrator::new().await {
-                    Ok(orchestrator) => {
-                        match orchestrator.run_quality_gate_for_source(&source_id).await {
-                            Ok(()) => {
-                                println!("✅ Quality gate completed successfully for {}", source_id);
-                            }
-                            Err(e) => {
-                                tracing::error!("Quality gate failed for {}: {}", source_id, e);
-                                println!("❌ Quality gate failed for {}: {}", source_id, e);
-                            }
-                        }
-                    }
-                    Err(e) => {
-                        tracing::error!("Failed to create pipeline orchestrator: {}", e);
-                        println!("❌ Failed to initialize pipeline: {}", e);
-                    }
-                }
-            } else {
-                println!("❌ Please specify a source with --sources <source_name>");
-                println!("📋 Available sources: blue_moon, barboza, neumos, etc.");
-            }
+        Commands::QualityGate { input: _, sources, all_sources: _, output: _, data_root } => {
+            stages::run_quality_gate(sources, data_root, json).await?
         }
-        Commands::Enrich { input: _, source, all_sources: _, output: _ } => {
-            println!("🌐 Step 7: Enrich - Adding location and metadata");
-            
-            if let Some(source_id) = source {
-                match FullPipelineOrchestrator::new().await {
-                    Ok(orchestrator) => {
-                        match orchestrator.run_enrich_for_source(&source_id).await {
-                            Ok(()) => {
-                                println!("✅ Enrich completed successfully for {}", source_id);
-                            }
-                            Err(e) => {
-                                tracing::error!("Enrich failed for {}: {}", source_id, e);
-                                println!("❌ Enrich failed for {}: {}", source_id, e);
-                            }
-                        }
-                    }
-                    Err(e) => {
-                        tracing::error!("Failed to create pipeline orchestrator: {}", e);
-                        println!("❌ Failed to initialize pipeline: {}", e);
-                    }
-                }
-            } else {
-                println!("❌ Please specify a source with --source <source_name>");
-                println!("📋 Available sources: blue_moon, barboza, neumos, etc.");
-            }
+        Commands::Enrich { input: _, source, all_sources: _, output: _, data_root } => {
+            stages::run_enrich(source, data_root, json).await?
         }
-        Commands::Conflation { input: _, sources, all_enriched: _, confidence_threshold, output: _ } => {
-            println!("🔗 Step 8: Conflation - Resolving duplicate entities");
-            println!("🔧 Confidence threshold: {}", confidence_threshold);
-            
-            if let Some(source_list) = sources {
-                for source_id in source_list.split(',').map(|s| s.trim()) {
-                    match FullPipelineOrchestrator::new().await {
-                        Ok(orchestrator) => {
-                            match orchestrator.run_conflation_for_source(source_id, confidence_threshold).await {
-                                Ok(()) => {
-                                    println!("✅ Conflation completed successfully for {}", source_id);
-                                }
-                                Err(e) => {
-                                    tracing::error!("Conflation failed for {}: {}", source_id, e);
-                                    println!("❌ Conflation failed for {}: {}", source_id, e);
-                                }
-                            }
-                        }
-                        Err(e) => {
-                            tracing::error!("Failed to create pipeline orchestrator: {}", e);
-                            println!("❌ Failed to initialize pipeline: {}", e);
-                        }
-                    }
-                }
-            } else {
-                println!("❌ Please specify sources with --sources <source_names>");
-                println!("📋 Available sources: blue_moon, barboza, neumos, etc.");
-                println!("💡 Example: --sources blue_moon,barboza");
-            }
+        Commands::Conflation { input: _, sources, all_enriched: _, conflator, output: _, data_root } => {
+            stages::run_conflation(sources, conflator, data_root, json).await?
         }
-        Commands::Catalog { input: _, latest: _, validate_graph, storage_mode: _ } => {
-            println!("📚 Step 9: Catalog - Storing entities in graph database");
-            println!("✅ Validate graph: {}", validate_graph);
-            
-            // For now, catalog blue_moon as example - in future could support --sources parameter
-            let source_id = "blue_moon";
-            match FullPipelineOrchestrator::new().await {
-                Ok(orchestrator) => {
-                    match orchestrator.run_catalog_for_source(source_id, validate_graph).await {
-                        Ok(()) => {
-                            println!("✅ Catalog completed successfully for {}", source_id);
-                        }
-                        Err(e) => {
-                            tracing::error!("Catalog failed for {}: {}", source_id, e);
-                            println!("❌ Catalog failed for {}: {}", source_id, e);
-                        }
-                    }
-                }
-                Err(e) => {
-                    tracing::error!("Failed to create pipeline orchestrator: {}", e);
-                    println!("❌ Failed to initialize pipeline: {}", e);
-                }
-            }
+        Commands::Catalog { input: _, latest: _, validate_graph, conflator, storage_mode: _, action: _, data_root } => {
+            stages::run_catalog(validate_graph, conflator, data_root, json).await?
         }
         Commands::ModularPipeline { source_id, parse_only, ingestion_only } => {
-            println!("🚀 Running modular pipeline for source: {}", source_id);
-            
-            match PipelineOrchestrator::new().await {
-                Ok(orchestrator) => {
-                    let config = if ingestion_only {
-                        println!("🔄 Running ingestion only");
-                        PipelineConfig::parse_only() // Will create ingestion-only config later
-                    } else if parse_only {
-                        println!("📄 Running parse only");
-                        PipelineConfig::parse_only()
-                    } else {
-                        println!("🔄 Running full modular pipeline");
-                        PipelineConfig::default_full_pipeline()
-                    };
-                    
-                    match orchestrator.run_pipeline(config, &source_id).await {
-                        Ok(result) => {
-                            println!("✅ Modular pipeline completed successfully!");
-                            println!("📊 Total processed: {}, failed: {}", result.total_processed, result.total_failed);
-                            if let Some(duration) = result.duration() {
-                                println!("⏱️ Duration: {}ms", duration.num_milliseconds());
-                            }
-                        }
-                        Err(e) => {
-                            tracing::error!("Modular pipeline failed for {}: {}", source_id, e);
-                            println!("❌ Modular pipeline failed for {}: {}", source_id, e);
-                        }
-                    }
-                }
-                Err(e) => {
-                    tracing::error!("Failed to create modular pipeline orchestrator: {}", e);
-                    println!("❌ Failed to initialize modular pipeline: {}", e);
-                }
-            }
+            pipeline::run_modular_pipeline(source_id, parse_only, ingestion_only, json).await?
+        }
+        Commands::GatewayAll { bypass_cadence, concurrency, per_host, report, every, data_root } => {
+            pipeline::run_gateway_all(bypass_cadence, concurrency, per_host, report, every, data_root, json).await?
+        }
+        Commands::Migrate { .. } | Commands::Doctor { .. } | Commands::Cas { .. } | Commands::Sources { .. } | Commands::Debug { .. } | Commands::Snapshot { .. }
+        | Commands::Moderate { .. } | Commands::Merge { .. } | Commands::Stats { .. } | Commands::Audit { .. } | Commands::IngestLog { .. } | Commands::IngestMeta { .. } | Commands::Envelope { .. } | Commands::Scaffold { .. }
+        | Commands::Contract { .. } | Commands::Metrics { .. } | Commands::Completions { .. } | Commands::Dlq { .. } => {
+            unreachable!("handled before storage init")
         }
     }
-    
+
+    shutdown_tracing();
     Ok(())
 }
@@ -1,4 +1,10 @@
 use std::fs;
+#[cfg(feature = "scraping")]
+use super::otel;
+#[cfg(feature = "scraping")]
+use tracing_subscriber::fmt::writer::BoxMakeWriter;
+#[cfg(feature = "scraping")]
+use tracing_subscriber::{Layer, Registry};
 use tracing_subscriber::{fmt, prelude::*, EnvFilter};
 
 /// Initializes the logging system with both console and file output.
@@ -34,3 +40,85 @@ pub fn init_logging() {
     // We need to keep the guard in scope to ensure logs are flushed on exit
     std::mem::forget(_guard);
 }
+
+/// Output format for console logs
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LogFormat {
+    /// Human-readable lines
+    #[default]
+    Pretty,
+    /// One JSON object per line, with span fields (run_id, source_id, envelope_id) flattened in for Loki
+    Json,
+}
+
+impl std::str::FromStr for LogFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "pretty" | "text" => Ok(LogFormat::Pretty),
+            "json" => Ok(LogFormat::Json),
+            other => Err(format!("unknown log format '{}', expected 'pretty' or 'json'", other)),
+        }
+    }
+}
+
+/// Initializes console-only logging in the given format, filtered by RUST_LOG.
+/// With an OTLP endpoint, `sms_scraper` spans at info and above are also exported
+/// as traces, independently of RUST_LOG. `to_stderr` keeps stdout free for
+/// machine-readable command output.
+#[cfg(feature = "scraping")]
+pub fn init_console_logging(format: LogFormat, otlp_endpoint: Option<&str>, to_stderr: bool) -> anyhow::Result<()> {
+    let writer = || if to_stderr { BoxMakeWriter::new(std::io::stderr) } else { BoxMakeWriter::new(std::io::stdout) };
+    let console: Box<dyn Layer<Registry> + Send + Sync> = match format {
+        LogFormat::Pretty => fmt::layer().with_writer(writer()).boxed(),
+        LogFormat::Json => fmt::layer()
+            .json()
+            .flatten_event(true)
+            .with_current_span(false)
+            .with_span_list(true)
+            .with_writer(writer())
+            .boxed(),
+    };
+    let otel = match otlp_endpoint {
+        Some(endpoint) => Some(
+            tracing_opentelemetry::layer()
+                .with_tracer(otel::otlp_tracer(endpoint)?)
+                .with_filter(EnvFilter::new("sms_scraper=info")),
+        ),
+        None => None,
+    };
+
+    tracing_subscriber::registry()
+        .with(console.with_filter(EnvFilter::from_default_env()))
+        .with(otel)
+        .init();
+    Ok(())
+}
+
+/// Span for one pipeline run; every stage log line inside it carries `run_id` and `source_id`.
+pub fn run_span(run_id: &str, source_id: &str) -> tracing::Span {
+    tracing::info_span!("pipeline_run", run_id = %run_id, source_id = %source_id)
+}
+
+/// Span for work on a single envelope, so downstream stage logs can be joined back to it.
+pub fn envelope_span(envelope_id: &str) -> tracing::Span {
+    tracing::info_span!("envelope", envelope_id = %envelope_id)
+}
+
+/// Span for one pipeline stage (parse, normalize, ...) so traces show per-stage latency.
+pub fn stage_span(stage: &'static str) -> tracing::Span {
+    tracing::info_span!("stage", stage = stage)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_log_format() {
+        assert_eq!("json".parse::<LogFormat>(), Ok(LogFormat::Json));
+        assert_eq!("Pretty".parse::<LogFormat>(), Ok(LogFormat::Pretty));
+        assert!("yaml".parse::<LogFormat>().is_err());
+    }
+}
@@ -4,371 +4,190 @@
 //! the standard Prometheus naming conventions.
 
 pub mod dashboard;
+#[cfg(feature = "metrics")]
+pub mod lint;
 
 use std::fmt;
 
-/// Enum representing all metric names used in the system
-/// This eliminates magic strings and provides compile-time safety
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
-pub enum MetricName {
-    // Heartbeat
-    Heartbeat,
-    
-    // Sources metrics
-    SourcesRequestsSuccess,
-    SourcesRequestsError,
-    SourcesRequestDuration,
-    SourcesPayloadBytes,
-    SourcesRegistryLoadsSuccess,
-    SourcesRegistryLoadsError,
-    
-    // Gateway metrics
-    GatewayEnvelopesAccepted,
-    GatewayEnvelopesDeduplicated,
-    GatewayCasWritesSuccess,
-    GatewayCasWritesError,
-    GatewayRecordsIngested,
-    GatewayProcessingDuration,
-    GatewayIngestSuccess,
-    GatewayIngestError,
-    GatewayBytesIngested,
-    GatewayIngestDuration,
-    GatewayEnvelopeCreated,
-    
-    // Ingest log metrics
-    IngestLogWritesSuccess,
-    IngestLogWritesError,
-    IngestLogWriteBytes,
-    IngestLogRotations,
-    IngestLogCurrentFileBytes,
-    IngestLogActiveConsumers,
-    
-    // Parser metrics
-    ParserParseSuccess,
-    ParserParseError,
-    ParserDuration,
-    ParserRecordsExtracted,
-    ParserBytesProcessed,
-    ParserBatchSize,
-    
-    // Normalize metrics
-    NormalizeRecordsProcessed,
-    NormalizeConfidence,
-    NormalizeGeocoding,
-    NormalizeWarnings,
-    NormalizeBatchesProcessed,
-    NormalizeBatchSize,
-    
-    // Quality Gate metrics
-    QualityGateRecordsAccepted,
-    QualityGateRecordsAcceptedWithWarnings,
-    QualityGateRecordsQuarantined,
-    QualityGateQualityScore,
-    QualityGateIssuesDetected,
-    QualityGateBatchesProcessed,
-    QualityGateBatchSize,
-    
-    // Enrich metrics
-    EnrichRecordsProcessed,
-    EnrichConfidence,
-    EnrichSpatialBinning,
-    EnrichCityTagging,
-    EnrichTagsAdded,
-    EnrichWarnings,
-    EnrichBatchesProcessed,
-    EnrichBatchSize,
-    
-    // Conflation metrics
-    ConflationRecordsProcessed,
-    ConflationRecordsSuccessful,
-    ConflationRecordsFailed,
-    ConflationConfidenceScore,
-    ConflationNewEntities,
-    ConflationMatchedExisting,
-    ConflationUpdatedExisting,
-    ConflationDuplicates,
-    ConflationUncertainResolutions,
-    ConflationWarnings,
-    ConflationPotentialDuplicates,
-    ConflationAlternativeMatches,
-    ConflationBatchesProcessed,
-    ConflationBatchesSuccessful,
-    ConflationBatchSize,
-    ConflationBatchProcessingDuration,
-    ConflationBatchRecordsSuccessful,
-    ConflationBatchRecordsFailed,
-    
+use dashboard::MetricType;
+
+/// Declares `MetricName` from one variant => name table, so the enum, its string
+/// names and the list returned by `all_metrics` cannot drift apart
+macro_rules! metric_names {
+    ($(#[$meta:meta])* pub enum $ty:ident { $($variant:ident => $name:literal,)+ }) => {
+        $(#[$meta])*
+        pub enum $ty {
+            $($variant,)+
+        }
+
+        impl $ty {
+            /// Every metric, in declaration order
+            pub const ALL: &'static [$ty] = &[$($ty::$variant,)+];
+
+            /// Get the metric name as a string (convenience method)
+            pub fn as_str(&self) -> &'static str {
+                match self {
+                    $($ty::$variant => $name,)+
+                }
+            }
+        }
+    };
+}
+
+metric_names! {
+    /// Enum representing all metric names used in the system
+    /// This eliminates magic strings and provides compile-time safety
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub enum MetricName {
+        // Heartbeat
+        Heartbeat => "sms_heartbeat_total",
+
+        // Sources metrics
+        SourcesRequestsSuccess => "sms_sources_requests_success_total",
+        SourcesRequestsError => "sms_sources_requests_error_total",
+        SourcesRequestDuration => "sms_sources_request_duration_seconds",
+        SourcesPayloadBytes => "sms_sources_payload_bytes",
+        SourcesRegistryLoadsSuccess => "sms_sources_registry_loads_success_total",
+        SourcesRegistryLoadsError => "sms_sources_registry_loads_error_total",
+        SourcesSiteChangeDetected => "sms_sources_site_change_detected_total",
+        SourcesQuotaExceeded => "sms_sources_quota_exceeded_total",
+        SourcesQuotaRemainingRequests => "sms_sources_quota_remaining_requests",
+        SourcesQuotaRemainingBytes => "sms_sources_quota_remaining_bytes",
+        SourcesRegistryReloads => "sms_sources_registry_reloads_total",
+        SourcesSchemaDriftDetected => "sms_sources_schema_drift_detected_total",
+        SourcesSchemaDriftScore => "sms_sources_schema_drift_score",
+
+        // Gateway metrics
+        GatewayEnvelopesAccepted => "sms_gateway_envelopes_accepted_total",
+        GatewayEnvelopesDeduplicated => "sms_gateway_envelopes_deduplicated_total",
+        GatewayCasWritesSuccess => "sms_gateway_cas_writes_success_total",
+        GatewayCasWritesError => "sms_gateway_cas_writes_error_total",
+        GatewayRecordsIngested => "sms_gateway_records_ingested_total",
+        GatewayProcessingDuration => "sms_gateway_processing_duration_seconds",
+        GatewayPayloadsOversize => "sms_gateway_payloads_oversize_total",
+        GatewayPayloadCompressedBytes => "sms_gateway_payload_compressed_bytes",
+        GatewayPayloadUncompressedBytes => "sms_gateway_payload_uncompressed_bytes",
+        GatewayIngestSuccess => "sms_gateway_ingest_success_total",
+        GatewayIngestError => "sms_gateway_ingest_error_total",
+        GatewayBytesIngested => "sms_gateway_bytes_ingested",
+        GatewayIngestDuration => "sms_gateway_ingest_duration_seconds",
+        GatewayEnvelopeCreated => "sms_gateway_envelope_created",
+
+        // Ingest log metrics
+        IngestLogWritesSuccess => "sms_ingest_log_writes_success_total",
+        IngestLogWritesError => "sms_ingest_log_writes_error_total",
+        IngestLogWriteBytes => "sms_ingest_log_write_bytes",
+        IngestLogRotations => "sms_ingest_log_rotations_total",
+        IngestLogCurrentFileBytes => "sms_ingest_log_current_file_bytes",
+        IngestLogActiveConsumers => "sms_ingest_log_active_consumers",
+        IngestLogEnvelopeTransitions => "sms_ingest_log_envelope_transitions_total",
+        IngestLogEnvelopeStuck => "sms_ingest_log_envelopes_stuck",
+        IngestLogConsumerLag => "sms_ingest_log_consumer_lag_bytes",
+        IngestLogConsumerOffset => "sms_ingest_log_consumer_offset_bytes",
+        IngestLogEndOffset => "sms_ingest_log_end_offset_bytes",
+        IngestLogTornLines => "sms_ingest_log_torn_lines_total",
+
+        // Parser metrics
+        ParserParseSuccess => "sms_parser_parse_success_total",
+        ParserParseError => "sms_parser_parse_error_total",
+        ParserDuration => "sms_parser_duration_seconds",
+        ParserRecordsExtracted => "sms_parser_records_extracted_total",
+        ParserBytesProcessed => "sms_parser_bytes_processed",
+        ParserBatchSize => "sms_parser_batch_size",
+        ParserDeadLettered => "sms_parser_dead_lettered_total",
+        ParserDeltaRecords => "sms_parser_delta_records_total",
+        ParserLlmFallbackCalls => "sms_parser_llm_fallback_calls_total",
+        ParserLlmFallbackCost => "sms_parser_llm_fallback_cost_usd",
+
+        // Normalize metrics
+        NormalizeRecordsProcessed => "sms_normalize_records_processed_total",
+        NormalizeConfidence => "sms_normalize_confidence",
+        NormalizeGeocoding => "sms_normalize_geocoding_total",
+        NormalizeWarnings => "sms_normalize_warnings_total",
+        NormalizeBatchesProcessed => "sms_normalize_batches_processed_total",
+        NormalizeBatchSize => "sms_normalize_batch_size",
+        NormalizeShadowComparisons => "sms_normalize_shadow_comparisons_total",
+
+        // Quality Gate metrics
+        QualityGateRecordsAccepted => "sms_quality_gate_records_accepted_total",
+        QualityGateRecordsAcceptedWithWarnings => "sms_quality_gate_records_accepted_with_warnings_total",
+        QualityGateRecordsQuarantined => "sms_quality_gate_records_quarantined_total",
+        QualityGateQualityScore => "sms_quality_gate_quality_score",
+        QualityGateIssuesDetected => "sms_quality_gate_issues_detected_total",
+        QualityGateBatchesProcessed => "sms_quality_gate_batches_processed_total",
+        QualityGateBatchSize => "sms_quality_gate_batch_size",
+        QualityGateShadowDecisions => "sms_quality_gate_shadow_decisions_total",
+
+        // Enrich metrics
+        EnrichRecordsProcessed => "sms_enrich_records_processed_total",
+        EnrichConfidence => "sms_enrich_confidence",
+        EnrichSpatialBinning => "sms_enrich_spatial_binning_total",
+        EnrichCityTagging => "sms_enrich_city_tagging_total",
+        EnrichTagsAdded => "sms_enrich_tags_added",
+        EnrichWarnings => "sms_enrich_warnings_total",
+        EnrichBatchesProcessed => "sms_enrich_batches_processed_total",
+        EnrichBatchSize => "sms_enrich_batch_size",
+
+        // Conflation metrics
+        ConflationRecordsProcessed => "sms_conflation_records_processed_total",
+        ConflationRecordsSuccessful => "sms_conflation_records_successful_total",
+        ConflationRecordsFailed => "sms_conflation_records_failed_total",
+        ConflationConfidenceScore => "sms_conflation_confidence_score",
+        ConflationNewEntities => "sms_conflation_new_entities_total",
+        ConflationMatchedExisting => "sms_conflation_matched_existing_total",
+        ConflationUpdatedExisting => "sms_conflation_updated_existing_total",
+        ConflationDuplicates => "sms_conflation_duplicates_total",
+        ConflationUncertainResolutions => "sms_conflation_uncertain_resolutions_total",
+        ConflationWarnings => "sms_conflation_warnings_total",
+        ConflationPotentialDuplicates => "sms_conflation_potential_duplicates_total",
+        ConflationAlternativeMatches => "sms_conflation_alternative_matches_total",
+        ConflationBatchesProcessed => "sms_conflation_batches_processed_total",
+        ConflationBatchesSuccessful => "sms_conflation_batches_successful_total",
+        ConflationBatchSize => "sms_conflation_batch_size",
+        ConflationBatchProcessingDuration => "sms_conflation_batch_processing_duration_seconds",
+        ConflationBatchRecordsSuccessful => "sms_conflation_batch_records_successful_total",
+        ConflationBatchRecordsFailed => "sms_conflation_batch_records_failed_total",
+
+        // Catalog metrics
+        CatalogDuplicatesSuppressed => "sms_catalog_duplicates_suppressed_total",
+        CatalogBatchesWritten => "sms_catalog_batches_written_total",
+        CatalogBatchRetries => "sms_catalog_batch_retries_total",
+        CatalogEntitiesWritten => "sms_catalog_entities_written_total",
+        CatalogBatchDuration => "sms_catalog_batch_duration_seconds",
+        CatalogWriteThroughput => "sms_catalog_write_throughput_per_second",
+
+        // Catalog audit metrics
+        AuditFindings => "sms_audit_findings",
+        AuditLastRun => "sms_audit_last_run_timestamp_seconds",
+
+        // GraphQL API metrics
+        GraphqlRequests => "sms_graphql_requests_total",
+        GraphqlRequestDuration => "sms_graphql_request_duration_seconds",
+        GraphqlCacheLookups => "sms_graphql_cache_lookups_total",
+        GraphqlLoaderBatchSize => "sms_graphql_loader_batch_size",
+
+        // Run bookkeeping metrics
+        RunsStarted => "sms_run_started_timestamp_seconds",
+        RunsCompleted => "sms_runs_completed_total",
+        RunsDuration => "sms_run_duration_seconds",
+        RunsStageDuration => "sms_run_stage_duration_seconds",
+        RunsLastSuccess => "sms_run_last_success_timestamp_seconds",
+
+        // Fault injection (chaos feature)
+        ChaosFaultsInjected => "sms_chaos_faults_injected_total",
+    }
 }
 
 impl fmt::Display for MetricName {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let name = match self {
-            // Heartbeat
-            MetricName::Heartbeat => "sms_heartbeat_total",
-            
-            // Sources metrics
-            MetricName::SourcesRequestsSuccess => "sms_sources_requests_success_total",
-            MetricName::SourcesRequestsError => "sms_sources_requests_error_total",
-            MetricName::SourcesRequestDuration => "sms_sources_request_duration_seconds",
-            MetricName::SourcesPayloadBytes => "sms_sources_payload_bytes",
-            MetricName::SourcesRegistryLoadsSuccess => "sms_sources_registry_loads_success_total",
-            MetricName::SourcesRegistryLoadsError => "sms_sources_registry_loads_error_total",
-            
-            // Gateway metrics
-            MetricName::GatewayEnvelopesAccepted => "sms_gateway_envelopes_accepted_total",
-            MetricName::GatewayEnvelopesDeduplicated => "sms_gateway_envelopes_deduplicated_total",
-            MetricName::GatewayCasWritesSuccess => "sms_gateway_cas_writes_success_total",
-            MetricName::GatewayCasWritesError => "sms_gateway_cas_writes_error_total",
-            MetricName::GatewayRecordsIngested => "sms_gateway_records_ingested_total",
-            MetricName::GatewayProcessingDuration => "sms_gateway_processing_duration_seconds",
-            MetricName::GatewayIngestSuccess => "sms_gateway_ingest_success_total",
-            MetricName::GatewayIngestError => "sms_gateway_ingest_error_total",
-            MetricName::GatewayBytesIngested => "sms_gateway_bytes_ingested",
-            MetricName::GatewayIngestDuration => "sms_gateway_ingest_duration_seconds",
-            MetricName::GatewayEnvelopeCreated => "sms_gateway_envelope_created",
-            
-            // Ingest log metrics
-            MetricName::IngestLogWritesSuccess => "sms_ingest_log_writes_success_total",
-            MetricName::IngestLogWritesError => "sms_ingest_log_writes_error_total",
-            MetricName::IngestLogWriteBytes => "sms_ingest_log_write_bytes",
-            MetricName::IngestLogRotations => "sms_ingest_log_rotations_total",
-            MetricName::IngestLogCurrentFileBytes => "sms_ingest_log_current_file_bytes",
-            MetricName::IngestLogActiveConsumers => "sms_ingest_log_active_consumers",
-            
-            // Parser metrics
-            MetricName::ParserParseSuccess => "sms_parser_parse_success_total",
-            MetricName::ParserParseError => "sms_parser_parse_error_total",
-            MetricName::ParserDuration => "sms_parser_duration_seconds",
-            MetricName::ParserRecordsExtracted => "sms_parser_records_extracted_total",
-            MetricName::ParserBytesProcessed => "sms_parser_bytes_processed",
-            MetricName::ParserBatchSize => "sms_parser_batch_size",
-            
-            // Normalize metrics
-            MetricName::NormalizeRecordsProcessed => "sms_normalize_records_processed_total",
-            MetricName::NormalizeConfidence => "sms_normalize_confidence",
-            MetricName::NormalizeGeocoding => "sms_normalize_geocoding_total",
-            MetricName::NormalizeWarnings => "sms_normalize_warnings_total",
-            MetricName::NormalizeBatchesProcessed => "sms_normalize_batches_processed_total",
-            MetricName::NormalizeBatchSize => "sms_normalize_batch_size",
-            
-            // Quality Gate metrics
-            MetricName::QualityGateRecordsAccepted => "sms_quality_gate_records_accepted_total",
-            MetricName::QualityGateRecordsAcceptedWithWarnings => "sms_quality_gate_records_accepted_with_warnings_total",
-            MetricName::QualityGateRecordsQuarantined => "sms_quality_gate_records_quarantined_total",
-            MetricName::QualityGateQualityScore => "sms_quality_gate_quality_score",
-            MetricName::QualityGateIssuesDetected => "sms_quality_gate_issues_detected_total",
-            MetricName::QualityGateBatchesProcessed => "sms_quality_gate_batches_processed_total",
-            MetricName::QualityGateBatchSize => "sms_quality_gate_batch_size",
-            
-            // Enrich metrics
-            MetricName::EnrichRecordsProcessed => "sms_enrich_records_processed_total",
-            MetricName::EnrichConfidence => "sms_enrich_confidence",
-            MetricName::EnrichSpatialBinning => "sms_enrich_spatial_binning_total",
-            MetricName::EnrichCityTagging => "sms_enrich_city_tagging_total",
-            MetricName::EnrichTagsAdded => "sms_enrich_tags_added",
-            MetricName::EnrichWarnings => "sms_enrich_warnings_total",
-            MetricName::EnrichBatchesProcessed => "sms_enrich_batches_processed_total",
-            MetricName::EnrichBatchSize => "sms_enrich_batch_size",
-            
-            // Conflation metrics
-            MetricName::ConflationRecordsProcessed => "sms_conflation_records_processed_total",
-            MetricName::ConflationRecordsSuccessful => "sms_conflation_records_successful_total",
-            MetricName::ConflationRecordsFailed => "sms_conflation_records_failed_total",
-            MetricName::ConflationConfidenceScore => "sms_conflation_confidence_score",
-            MetricName::ConflationNewEntities => "sms_conflation_new_entities_total",
-            MetricName::ConflationMatchedExisting => "sms_conflation_matched_existing_total",
-            MetricName::ConflationUpdatedExisting => "sms_conflation_updated_existing_total",
-            MetricName::ConflationDuplicates => "sms_conflation_duplicates_total",
-            MetricName::ConflationUncertainResolutions => "sms_conflation_uncertain_resolutions_total",
-            MetricName::ConflationWarnings => "sms_conflation_warnings_total",
-            MetricName::ConflationPotentialDuplicates => "sms_conflation_potential_duplicates_total",
-            MetricName::ConflationAlternativeMatches => "sms_conflation_alternative_matches_total",
-            MetricName::ConflationBatchesProcessed => "sms_conflation_batches_processed_total",
-            MetricName::ConflationBatchesSuccessful => "sms_conflation_batches_successful_total",
-            MetricName::ConflationBatchSize => "sms_conflation_batch_size",
-            MetricName::ConflationBatchProcessingDuration => "sms_conflation_batch_processing_duration_seconds",
-            MetricName::ConflationBatchRecordsSuccessful => "sms_conflation_batch_records_successful_total",
-            MetricName::ConflationBatchRecordsFailed => "sms_conflation_batch_records_failed_total",
-            
-        };
-        write!(f, "{}", name)
+        f.write_str(self.as_str())
     }
 }
 
 impl MetricName {
-    /// Get the metric name as a string (convenience method)
-    pub fn as_str(&self) -> &'static str {
-        match self {
-            // Heartbeat
-            MetricName::Heartbeat => "sms_heartbeat_total",
-            
-            // Sources metrics
-            MetricName::SourcesRequestsSuccess => "sms_sources_requests_success_total",
-            MetricName::SourcesRequestsError => "sms_sources_requests_error_total",
-            MetricName::SourcesRequestDuration => "sms_sources_request_duration_seconds",
-            MetricName::SourcesPayloadBytes => "sms_sources_payload_bytes",
-            MetricName::SourcesRegistryLoadsSuccess => "sms_sources_registry_loads_success_total",
-            MetricName::SourcesRegistryLoadsError => "sms_sources_registry_loads_error_total",
-            
-            // Gateway metrics
-            MetricName::GatewayEnvelopesAccepted => "sms_gateway_envelopes_accepted_total",
-            MetricName::GatewayEnvelopesDeduplicated => "sms_gateway_envelopes_deduplicated_total",
-            MetricName::GatewayCasWritesSuccess => "sms_gateway_cas_writes_success_total",
-            MetricName::GatewayCasWritesError => "sms_gateway_cas_writes_error_total",
-            MetricName::GatewayRecordsIngested => "sms_gateway_records_ingested_total",
-            MetricName::GatewayProcessingDuration => "sms_gateway_processing_duration_seconds",
-            MetricName::GatewayIngestSuccess => "sms_gateway_ingest_success_total",
-            MetricName::GatewayIngestError => "sms_gateway_ingest_error_total",
-            MetricName::GatewayBytesIngested => "sms_gateway_bytes_ingested",
-            MetricName::GatewayIngestDuration => "sms_gateway_ingest_duration_seconds",
-            MetricName::GatewayEnvelopeCreated => "sms_gateway_envelope_created",
-            
-            // Ingest log metrics
-            MetricName::IngestLogWritesSuccess => "sms_ingest_log_writes_success_total",
-            MetricName::IngestLogWritesError => "sms_ingest_log_writes_error_total",
-            MetricName::IngestLogWriteBytes => "sms_ingest_log_write_bytes",
-            MetricName::IngestLogRotations => "sms_ingest_log_rotations_total",
-            MetricName::IngestLogCurrentFileBytes => "sms_ingest_log_current_file_bytes",
-            MetricName::IngestLogActiveConsumers => "sms_ingest_log_active_consumers",
-            
-            // Parser metrics
-            MetricName::ParserParseSuccess => "sms_parser_parse_success_total",
-            MetricName::ParserParseError => "sms_parser_parse_error_total",
-            MetricName::ParserDuration => "sms_parser_duration_seconds",
-            MetricName::ParserRecordsExtracted => "sms_parser_records_extracted_total",
-            MetricName::ParserBytesProcessed => "sms_parser_bytes_processed",
-            MetricName::ParserBatchSize => "sms_parser_batch_size",
-            
-            // Normalize metrics
-            MetricName::NormalizeRecordsProcessed => "sms_normalize_records_processed_total",
-            MetricName::NormalizeConfidence => "sms_normalize_confidence",
-            MetricName::NormalizeGeocoding => "sms_normalize_geocoding_total",
-            MetricName::NormalizeWarnings => "sms_normalize_warnings_total",
-            MetricName::NormalizeBatchesProcessed => "sms_normalize_batches_processed_total",
-            MetricName::NormalizeBatchSize => "sms_normalize_batch_size",
-            
-            // Quality Gate metrics
-            MetricName::QualityGateRecordsAccepted => "sms_quality_gate_records_accepted_total",
-            MetricName::QualityGateRecordsAcceptedWithWarnings => "sms_quality_gate_records_accepted_with_warnings_total",
-            MetricName::QualityGateRecordsQuarantined => "sms_quality_gate_records_quarantined_total",
-            MetricName::QualityGateQualityScore => "sms_quality_gate_quality_score",
-            MetricName::QualityGateIssuesDetected => "sms_quality_gate_issues_detected_total",
-            MetricName::QualityGateBatchesProcessed => "sms_quality_gate_batches_processed_total",
-            MetricName::QualityGateBatchSize => "sms_quality_gate_batch_size",
-            
-            // Enrich metrics
-            MetricName::EnrichRecordsProcessed => "sms_enrich_records_processed_total",
-            MetricName::EnrichConfidence => "sms_enrich_confidence",
-            MetricName::EnrichSpatialBinning => "sms_enrich_spatial_binning_total",
-            MetricName::EnrichCityTagging => "sms_enrich_city_tagging_total",
-            MetricName::EnrichTagsAdded => "sms_enrich_tags_added",
-            MetricName::EnrichWarnings => "sms_enrich_warnings_total",
-            MetricName::EnrichBatchesProcessed => "sms_enrich_batches_processed_total",
-            MetricName::EnrichBatchSize => "sms_enrich_batch_size",
-            
-            // Conflation metrics
-            MetricName::ConflationRecordsProcessed => "sms_conflation_records_processed_total",
-            MetricName::ConflationRecordsSuccessful => "sms_conflation_records_successful_total",
-            MetricName::ConflationRecordsFailed => "sms_conflation_records_failed_total",
-            MetricName::ConflationConfidenceScore => "sms_conflation_confidence_score",
-            MetricName::ConflationNewEntities => "sms_conflation_new_entities_total",
-            MetricName::ConflationMatchedExisting => "sms_conflation_matched_existing_total",
-            MetricName::ConflationUpdatedExisting => "sms_conflation_updated_existing_total",
-            MetricName::ConflationDuplicates => "sms_conflation_duplicates_total",
-            MetricName::ConflationUncertainResolutions => "sms_conflation_uncertain_resolutions_total",
-            MetricName::ConflationWarnings => "sms_conflation_warnings_total",
-            MetricName::ConflationPotentialDuplicates => "sms_conflation_potential_duplicates_total",
-            MetricName::ConflationAlternativeMatches => "sms_conflation_alternative_matches_total",
-            MetricName::ConflationBatchesProcessed => "sms_conflation_batches_processed_total",
-            MetricName::ConflationBatchesSuccessful => "sms_conflation_batches_successful_total",
-            MetricName::ConflationBatchSize => "sms_conflation_batch_size",
-            MetricName::ConflationBatchProcessingDuration => "sms_conflation_batch_processing_duration_seconds",
-            MetricName::ConflationBatchRecordsSuccessful => "sms_conflation_batch_records_successful_total",
-            MetricName::ConflationBatchRecordsFailed => "sms_conflation_batch_records_failed_total",
-            
-        }
-    }
-
-
     /// Get all metric names as an iterator (for dynamic dashboard generation)
     pub fn all_metrics() -> impl Iterator<Item = MetricName> {
-        use MetricName::*;
-        [
-            // Heartbeat
-            Heartbeat,
-            
-            // Sources metrics
-            SourcesRequestsSuccess,
-            SourcesRequestsError,
-            SourcesRequestDuration,
-            SourcesPayloadBytes,
-            SourcesRegistryLoadsSuccess,
-            SourcesRegistryLoadsError,
-            
-            // Gateway metrics
-            GatewayEnvelopesAccepted,
-            GatewayEnvelopesDeduplicated,
-            GatewayCasWritesSuccess,
-            GatewayCasWritesError,
-            GatewayRecordsIngested,
-            GatewayProcessingDuration,
-            GatewayIngestSuccess,
-            GatewayIngestError,
-            GatewayBytesIngested,
-            GatewayIngestDuration,
-            GatewayEnvelopeCreated,
-            
-            // Ingest log metrics
-            IngestLogWritesSuccess,
-            IngestLogWritesError,
-            IngestLogWriteBytes,
-            IngestLogRotations,
-            IngestLogCurrentFileBytes,
-            IngestLogActiveConsumers,
-            
-            // Parser metrics
-            ParserParseSuccess,
-            ParserParseError,
-            ParserDuration,
-            ParserRecordsExtracted,
-            ParserBytesProcessed,
-            ParserBatchSize,
-            
-            // Normalize metrics
-            NormalizeRecordsProcessed,
-            NormalizeConfidence,
-            NormalizeGeocoding,
-            NormalizeWarnings,
-            NormalizeBatchesProcessed,
-            NormalizeBatchSize,
-
-            // Quality Gate metrics
-            QualityGateRecordsAccepted,
-            QualityGateRecordsAcceptedWithWarnings,
-            QualityGateRecordsQuarantined,
-            QualityGateQualityScore,
-            QualityGateIssuesDetected,
-            QualityGateBatchesProcessed,
-            QualityGateBatchSize,
-            
-            // Enrich metrics
-            EnrichRecordsProcessed,
-            EnrichConfidence,
-            EnrichSpatialBinning,
-            EnrichCityTagging,
-            EnrichTagsAdded,
-            EnrichWarnings,
-            EnrichBatchesProcessed,
-            EnrichBatchSize,
-            
-            // Push gateway metrics (usually not displayed)
-            // IngestTimestamp,
-            // IngestBytes,
-            // IngestDurationSeconds,
-            // IngestSuccess,
-            // PushTimestamp,
-            // MetricsInitialized,
-        ].into_iter()
+        Self::ALL.iter().copied()
     }
 
     /// Get metric metadata for dashboard generation
@@ -385,6 +204,13 @@ impl MetricName {
             MetricName::SourcesPayloadBytes => ("sources", "Payload size in bytes", Some("bytes")),
             MetricName::SourcesRegistryLoadsSuccess => ("sources", "Successful registry loads", None),
             MetricName::SourcesRegistryLoadsError => ("sources", "Failed registry loads", None),
+            MetricName::SourcesSiteChangeDetected => ("sources", "Sources whose parsed record count collapsed versus their trailing average", None),
+            MetricName::SourcesQuotaExceeded => ("sources", "Fetches refused because the source's monthly quota is spent", None),
+            MetricName::SourcesQuotaRemainingRequests => ("sources", "Requests left in the source's monthly quota", None),
+            MetricName::SourcesQuotaRemainingBytes => ("sources", "Bytes left in the source's monthly quota", Some("bytes")),
+            MetricName::SourcesRegistryReloads => ("sources", "Registry hot-reloads, by outcome (applied or rejected)", None),
+            MetricName::SourcesSchemaDriftDetected => ("sources", "Runs whose payload structure drifted past the threshold from the source's previous run", None),
+            MetricName::SourcesSchemaDriftScore => ("sources", "Share of payload keys and selectors that changed since the source's previous run", None),
             
             // Gateway metrics
             MetricName::GatewayEnvelopesAccepted => ("gateway", "Total envelopes accepted", None),
@@ -393,6 +219,9 @@ impl MetricName {
             MetricName::GatewayCasWritesError => ("gateway", "Failed CAS writes", None),
             MetricName::GatewayRecordsIngested => ("gateway", "Total records ingested", None),
             MetricName::GatewayProcessingDuration => ("gateway", "Gateway processing duration", Some("s")),
+            MetricName::GatewayPayloadsOversize => ("gateway", "Fetches aborted for exceeding max_payload_size_bytes", None),
+            MetricName::GatewayPayloadCompressedBytes => ("gateway", "Payload size as received on the wire", Some("bytes")),
+            MetricName::GatewayPayloadUncompressedBytes => ("gateway", "Payload size after content-encoding is decoded", Some("bytes")),
             MetricName::GatewayIngestSuccess => ("gateway", "Successful ingests by source", None),
             MetricName::GatewayIngestError => ("gateway", "Failed ingests by source", None),
             MetricName::GatewayBytesIngested => ("gateway", "Bytes ingested per source", Some("bytes")),
@@ -406,6 +235,12 @@ impl MetricName {
             MetricName::IngestLogRotations => ("ingest_log", "Log rotations", None),
             MetricName::IngestLogCurrentFileBytes => ("ingest_log", "Current log file size", Some("bytes")),
             MetricName::IngestLogActiveConsumers => ("ingest_log", "Active log consumers", None),
+            MetricName::IngestLogEnvelopeTransitions => ("ingest_log", "Envelope processing state changes, by new state", None),
+            MetricName::IngestLogEnvelopeStuck => ("ingest_log", "Envelopes in a non-terminal processing state longer than the stuck threshold, by state", None),
+            MetricName::IngestLogConsumerLag => ("ingest_log", "Bytes between a consumer's offset and the end of the log", Some("bytes")),
+            MetricName::IngestLogConsumerOffset => ("ingest_log", "Byte offset a consumer has committed", Some("bytes")),
+            MetricName::IngestLogEndOffset => ("ingest_log", "Byte position of the end of the ingest log", Some("bytes")),
+            MetricName::IngestLogTornLines => ("ingest_log", "Partial log lines left by a crashed writer, skipped on read", None),
             
             // Parser metrics
             MetricName::ParserParseSuccess => ("parser", "Successful parses", None),
@@ -414,6 +249,10 @@ impl MetricName {
             MetricName::ParserRecordsExtracted => ("parser", "Records extracted", None),
             MetricName::ParserBytesProcessed => ("parser", "Bytes processed", Some("bytes")),
             MetricName::ParserBatchSize => ("parser", "Parse batch size", None),
+            MetricName::ParserDeadLettered => ("parser", "Envelopes sent to the parse dead-letter queue", None),
+            MetricName::ParserDeltaRecords => ("parser", "Parsed records by change against the source's previous payload", None),
+            MetricName::ParserLlmFallbackCalls => ("parser", "LLM fallback parser calls by outcome", None),
+            MetricName::ParserLlmFallbackCost => ("parser", "Estimated spend on LLM fallback parsing in USD", None),
             
             // Normalize metrics
             MetricName::NormalizeRecordsProcessed => ("normalize", "Records processed with normalization", None),
@@ -422,6 +261,7 @@ impl MetricName {
             MetricName::NormalizeWarnings => ("normalize", "Normalization warnings", None),
             MetricName::NormalizeBatchesProcessed => ("normalize", "Batches processed", None),
             MetricName::NormalizeBatchSize => ("normalize", "Normalization batch size", None),
+            MetricName::NormalizeShadowComparisons => ("normalize", "Shadow normalizer comparisons by source and outcome (identical, different, shadow_failed)", None),
             
             // Quality Gate metrics
             MetricName::QualityGateRecordsAccepted => ("quality_gate", "Records accepted by quality gate", None),
@@ -431,6 +271,7 @@ impl MetricName {
             MetricName::QualityGateIssuesDetected => ("quality_gate", "Quality issues detected", None),
             MetricName::QualityGateBatchesProcessed => ("quality_gate", "Batches processed through quality gate", None),
             MetricName::QualityGateBatchSize => ("quality_gate", "Quality gate batch size", None),
+            MetricName::QualityGateShadowDecisions => ("quality_gate", "Active and shadow candidate quality gate decisions by source (accept, accept_with_warnings, quarantine, failed)", None),
             
             // Enrich metrics
             MetricName::EnrichRecordsProcessed => ("enrich", "Records processed with enrichment", None),
@@ -461,28 +302,148 @@ impl MetricName {
             MetricName::ConflationBatchProcessingDuration => ("conflation", "Batch processing duration", Some("s")),
             MetricName::ConflationBatchRecordsSuccessful => ("conflation", "Records successfully processed in batch", None),
             MetricName::ConflationBatchRecordsFailed => ("conflation", "Records failed in batch processing", None),
+            MetricName::CatalogDuplicatesSuppressed => ("catalog", "Events suppressed as duplicates of cataloged events", None),
+            MetricName::CatalogBatchesWritten => ("catalog", "Catalog write batches committed", None),
+            MetricName::CatalogBatchRetries => ("catalog", "Catalog batch transactions retried after a write conflict", None),
+            MetricName::CatalogEntitiesWritten => ("catalog", "Entities and process records written by catalog batches", None),
+            MetricName::CatalogBatchDuration => ("catalog", "Time to commit one catalog write batch", Some("s")),
+            MetricName::CatalogWriteThroughput => ("catalog", "Entities written per second by the last catalog batch", None),
+
+            // Catalog audit metrics
+            MetricName::AuditFindings => ("audit", "Findings of the last catalog audit, by check", None),
+            MetricName::AuditLastRun => ("audit", "Unix time the catalog audit last ran", Some("seconds")),
+            MetricName::GraphqlRequests => ("graphql", "GraphQL API requests served, by route, method, status and operation", None),
+            MetricName::GraphqlRequestDuration => ("graphql", "Time to serve a GraphQL API request", Some("seconds")),
+            MetricName::GraphqlCacheLookups => ("graphql", "Query result cache lookups, by cache and result (hit or miss)", None),
+            MetricName::GraphqlLoaderBatchSize => ("graphql", "Keys fetched per DataLoader batch, by loader", None),
+            MetricName::RunsStarted => ("runs", "Unix time the most recent run of a kind started", Some("seconds")),
+            MetricName::RunsCompleted => ("runs", "Runs finished, by kind and outcome", None),
+            MetricName::RunsDuration => ("runs", "Wall-clock duration of a finished run", Some("seconds")),
+            MetricName::RunsStageDuration => ("runs", "Time spent in one stage of a run", Some("seconds")),
+            MetricName::RunsLastSuccess => ("runs", "Unix time a run of a kind last succeeded", Some("seconds")),
+            MetricName::ChaosFaultsInjected => ("chaos", "Faults injected by chaos mode, by fault kind", None),
             
         }
     }
 
-    /// Infer metric type from metric name patterns
-    pub fn infer_metric_type(&self) -> crate::observability::metrics::dashboard::MetricType {
-        use crate::observability::metrics::dashboard::MetricType;
-        let name = self.as_str();
-        
-        if name.contains("_total") || name.contains("success") || name.contains("error") || name.contains("extracted") || name.contains("accepted") || name.contains("quarantined") || name.contains("detected") || name.contains("processed") {
-            MetricType::Counter
-        } else if name.contains("_seconds") || name.contains("_bytes") || name.contains("_duration") || name.contains("_size") || name.contains("confidence") || name.contains("score") {
-            MetricType::Histogram
-        } else if name.contains("current_") || name.contains("active_") || name.contains("initialized") {
-            MetricType::Gauge
-        } else {
-            // Default to counter for unknown patterns
-            MetricType::Counter
+    /// How the metric is recorded at its call sites. Exhaustive on purpose: a new
+    /// variant does not compile until its kind is stated here.
+    pub fn kind(&self) -> MetricType {
+        match self {
+            MetricName::Heartbeat
+            | MetricName::SourcesRequestsSuccess
+            | MetricName::SourcesRequestsError
+            | MetricName::SourcesRegistryLoadsSuccess
+            | MetricName::SourcesRegistryLoadsError
+            | MetricName::SourcesSiteChangeDetected
+            | MetricName::SourcesQuotaExceeded
+            | MetricName::SourcesRegistryReloads
+            | MetricName::SourcesSchemaDriftDetected
+            | MetricName::GatewayEnvelopesAccepted
+            | MetricName::GatewayEnvelopesDeduplicated
+            | MetricName::GatewayCasWritesSuccess
+            | MetricName::GatewayCasWritesError
+            | MetricName::GatewayRecordsIngested
+            | MetricName::GatewayPayloadsOversize
+            | MetricName::GatewayIngestSuccess
+            | MetricName::GatewayIngestError
+            | MetricName::GatewayEnvelopeCreated
+            | MetricName::IngestLogWritesSuccess
+            | MetricName::IngestLogWritesError
+            | MetricName::IngestLogRotations
+            | MetricName::IngestLogEnvelopeTransitions
+            | MetricName::IngestLogTornLines
+            | MetricName::ParserParseSuccess
+            | MetricName::ParserParseError
+            | MetricName::ParserRecordsExtracted
+            | MetricName::ParserDeadLettered
+            | MetricName::ParserDeltaRecords
+            | MetricName::ParserLlmFallbackCalls
+            | MetricName::NormalizeRecordsProcessed
+            | MetricName::NormalizeGeocoding
+            | MetricName::NormalizeWarnings
+            | MetricName::NormalizeBatchesProcessed
+            | MetricName::NormalizeShadowComparisons
+            | MetricName::QualityGateRecordsAccepted
+            | MetricName::QualityGateRecordsAcceptedWithWarnings
+            | MetricName::QualityGateRecordsQuarantined
+            | MetricName::QualityGateIssuesDetected
+            | MetricName::QualityGateBatchesProcessed
+            | MetricName::QualityGateShadowDecisions
+            | MetricName::EnrichRecordsProcessed
+            | MetricName::EnrichSpatialBinning
+            | MetricName::EnrichCityTagging
+            | MetricName::EnrichWarnings
+            | MetricName::EnrichBatchesProcessed
+            | MetricName::ConflationRecordsProcessed
+            | MetricName::ConflationRecordsSuccessful
+            | MetricName::ConflationRecordsFailed
+            | MetricName::ConflationNewEntities
+            | MetricName::ConflationMatchedExisting
+            | MetricName::ConflationUpdatedExisting
+            | MetricName::ConflationDuplicates
+            | MetricName::ConflationUncertainResolutions
+            | MetricName::ConflationWarnings
+            | MetricName::ConflationPotentialDuplicates
+            | MetricName::ConflationAlternativeMatches
+            | MetricName::ConflationBatchesProcessed
+            | MetricName::ConflationBatchesSuccessful
+            | MetricName::ConflationBatchRecordsSuccessful
+            | MetricName::ConflationBatchRecordsFailed
+            | MetricName::CatalogDuplicatesSuppressed
+            | MetricName::CatalogBatchesWritten
+            | MetricName::CatalogBatchRetries
+            | MetricName::CatalogEntitiesWritten
+            | MetricName::GraphqlRequests
+            | MetricName::GraphqlCacheLookups
+            | MetricName::RunsCompleted
+            | MetricName::ChaosFaultsInjected => MetricType::Counter,
+            MetricName::SourcesRequestDuration
+            | MetricName::SourcesPayloadBytes
+            | MetricName::GatewayProcessingDuration
+            | MetricName::GatewayPayloadCompressedBytes
+            | MetricName::GatewayPayloadUncompressedBytes
+            | MetricName::GatewayBytesIngested
+            | MetricName::GatewayIngestDuration
+            | MetricName::IngestLogWriteBytes
+            | MetricName::ParserDuration
+            | MetricName::ParserBytesProcessed
+            | MetricName::ParserBatchSize
+            | MetricName::NormalizeConfidence
+            | MetricName::NormalizeBatchSize
+            | MetricName::QualityGateQualityScore
+            | MetricName::QualityGateBatchSize
+            | MetricName::EnrichConfidence
+            | MetricName::EnrichTagsAdded
+            | MetricName::EnrichBatchSize
+            | MetricName::ConflationConfidenceScore
+            | MetricName::ConflationBatchSize
+            | MetricName::ConflationBatchProcessingDuration
+            | MetricName::CatalogBatchDuration
+            | MetricName::GraphqlRequestDuration
+            | MetricName::GraphqlLoaderBatchSize
+            | MetricName::RunsStageDuration => MetricType::Histogram,
+            MetricName::SourcesQuotaRemainingRequests
+            | MetricName::SourcesQuotaRemainingBytes
+            | MetricName::SourcesSchemaDriftScore
+            | MetricName::IngestLogCurrentFileBytes
+            | MetricName::IngestLogActiveConsumers
+            | MetricName::IngestLogEnvelopeStuck
+            | MetricName::IngestLogConsumerLag
+            | MetricName::IngestLogConsumerOffset
+            | MetricName::IngestLogEndOffset
+            | MetricName::ParserLlmFallbackCost
+            | MetricName::CatalogWriteThroughput
+            | MetricName::AuditFindings
+            | MetricName::AuditLastRun
+            | MetricName::RunsStarted
+            | MetricName::RunsDuration
+            | MetricName::RunsLastSuccess => MetricType::Gauge,
         }
     }
 }
 
+use super::push::PushConfig;
 use tracing::{info, warn};
 use std::sync::Arc;
 
@@ -491,32 +452,49 @@ pub fn init() -> Result<(), Box<dyn std::error::Error>> {
     init_with_push_options(None, None)
 }
 
-/// Initialize with push gateway configuration
+/// Initialize with push gateway configuration taken from the environment (see
+/// [`PushConfig::resolve`]). Built without the `metrics` feature no recorder is installed,
+/// so recorded values go nowhere and only direct pushes are sent.
 pub fn init_with_push_options(
     job_name: Option<&str>,
     instance: Option<&str>,
 ) -> Result<(), Box<dyn std::error::Error>> {
-    let builder = metrics_exporter_prometheus::PrometheusBuilder::new();
-    
-    // Install the recorder and get the handle
-    let handle = builder
-        .install_recorder()
-        .map_err(|e| format!("Failed to install Prometheus recorder: {}", e))?;
-    
-    // If push gateway is configured, store the handle for later pushing
-    if let Ok(pushgateway_url) = std::env::var("SMS_PUSHGATEWAY_URL") {
+    let push = PushConfig::from_env().unwrap_or_else(|e| {
+        warn!("Not pushing metrics: {:#}", e);
+        PushConfig::disabled()
+    });
+    init_with_push_config(&push, job_name, instance)
+}
+
+/// Initialize with already resolved push settings, e.g. from `--push`/`--no-push`
+pub fn init_with_push_config(
+    push: &PushConfig,
+    job_name: Option<&str>,
+    instance: Option<&str>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    #[cfg(feature = "metrics")]
+    {
+        let handle = metrics_exporter_prometheus::PrometheusBuilder::new()
+            .install_recorder()
+            .map_err(|e| format!("Failed to install Prometheus recorder: {}", e))?;
+        PROMETHEUS_HANDLE.set(handle).ok();
+    }
+    #[cfg(not(feature = "metrics"))]
+    info!("Built without the metrics feature; no Prometheus recorder installed");
+    
+    // If push gateway is configured, keep its settings for later pushing
+    if let Some(pushgateway_url) = push.url() {
         let job = job_name.unwrap_or("sms_scraper");
         let inst = instance.unwrap_or("default");
         
-        // Store handle for push_all_metrics function
+        // Store settings for push_all_metrics function
         METRICS_HANDLE.set(Arc::new(MetricsState {
-            handle,
-            pushgateway_url,
+            pushgateway_url: pushgateway_url.to_string(),
             job: job.to_string(),
             instance: inst.to_string(),
         })).ok();
         
-        info!("Metrics system initialized with push gateway support");
+        info!("Metrics system initialized with push gateway support ({})", pushgateway_url);
     } else {
         info!("Metrics system initialized (no push gateway)");
     }
@@ -527,20 +505,39 @@ pub fn init_with_push_options(
 // Global state for metrics pushing
 use std::sync::OnceLock;
 static METRICS_HANDLE: OnceLock<Arc<MetricsState>> = OnceLock::new();
+#[cfg(feature = "metrics")]
+static PROMETHEUS_HANDLE: OnceLock<metrics_exporter_prometheus::PrometheusHandle> = OnceLock::new();
+
+/// Everything recorded so far in Prometheus text format, for processes that serve `/metrics`
+pub fn render() -> Option<String> {
+    #[cfg(feature = "metrics")]
+    return PROMETHEUS_HANDLE.get().map(|handle| handle.render());
+    #[cfg(not(feature = "metrics"))]
+    None
+}
 
 /// Get access to the metrics handle for rendering
 #[allow(dead_code)]
 pub fn get_metrics_handle() -> Option<String> {
-    METRICS_HANDLE.get().map(|state| state.handle.render())
+    METRICS_HANDLE.get().and_then(|_| render())
 }
 
 struct MetricsState {
-    handle: metrics_exporter_prometheus::PrometheusHandle,
     pushgateway_url: String,
     job: String,
     instance: String,
 }
 
+/// Spawn a push onto the current runtime; skipped when called outside one (e.g. sync tests)
+fn spawn_push<F>(fut: F)
+where
+    F: std::future::Future<Output = ()> + Send + 'static,
+{
+    if let Ok(handle) = tokio::runtime::Handle::try_current() {
+        handle.spawn(fut);
+    }
+}
+
 /// Internal function to push a single metric immediately
 async fn push_single_metric(name: &str, value: f64, metric_type: &str) -> Result<(), Box<dyn std::error::Error>> {
     if let Some(state) = METRICS_HANDLE.get() {
@@ -631,14 +628,14 @@ macro_rules! counter_and_push {
     ($name:expr) => {{
         ::metrics::counter!($name).increment(1);
         let name = $name.to_string();
-        tokio::spawn(async move {
+        spawn_push(async move {
             let _ = push_single_metric(&name, 1.0, "counter").await;
         });
     }};
     ($name:expr, $($label_key:expr => $label_value:expr),+) => {{
         ::metrics::counter!($name, $($label_key => $label_value),+).increment(1);
         let name = $name.to_string();
-        tokio::spawn(async move {
+        spawn_push(async move {
             let _ = push_single_metric(&name, 1.0, "counter").await;
         });
     }};
@@ -650,7 +647,7 @@ macro_rules! counter_and_push {
 pub fn heartbeat() {
     let metric_name = MetricName::Heartbeat.as_str();
     ::metrics::counter!(metric_name).increment(1);
-    tokio::spawn(async move {
+    spawn_push(async move {
         let _ = push_single_metric(metric_name, 1.0, "counter").await;
     });
 }
@@ -661,14 +658,14 @@ pub fn heartbeat() {
 // ============================================================================
 
 pub mod sources {
-    use super::{push_single_metric, push_histogram_metric, MetricName};
+    use super::{push_single_metric, push_histogram_metric, spawn_push, MetricName};
     
     /// Record a successful request
     pub fn request_success() {
         let metric_name = MetricName::SourcesRequestsSuccess.as_str();
         ::metrics::counter!(metric_name).increment(1);
         // Immediately push this metric
-        tokio::spawn(async move {
+        spawn_push(async move {
             let _ = push_single_metric(metric_name, 1.0, "counter").await;
         });
     }
@@ -677,7 +674,7 @@ pub mod sources {
     pub fn request_error() {
         let metric_name = MetricName::SourcesRequestsError.as_str();
         ::metrics::counter!(metric_name).increment(1);
-        tokio::spawn(async move {
+        spawn_push(async move {
             let _ = push_single_metric(metric_name, 1.0, "counter").await;
         });
     }
@@ -686,7 +683,7 @@ pub mod sources {
     pub fn request_duration(secs: f64) {
         let metric_name = MetricName::SourcesRequestDuration.as_str();
         ::metrics::histogram!(metric_name).record(secs);
-        tokio::spawn(async move {
+        spawn_push(async move {
             let _ = push_single_metric(metric_name, secs, "gauge").await;
         });
     }
@@ -696,7 +693,7 @@ pub mod sources {
         let b = bytes as f64;
         let metric_name = MetricName::SourcesPayloadBytes.as_str();
         ::metrics::histogram!(metric_name).record(b);
-        tokio::spawn(async move {
+        spawn_push(async move {
             // Push histogram with buckets instead of single value
             let _ = push_histogram_metric(metric_name, b).await;
         });
@@ -711,6 +708,60 @@ pub mod sources {
     pub fn registry_load_error() {
         counter_and_push!(MetricName::SourcesRegistryLoadsError.as_str());
     }
+
+    /// Record a suspected site redesign: parsed record count collapsed for a source
+    pub fn site_change_detected(source_id: &str, reason: &str) {
+        counter_and_push!(MetricName::SourcesSiteChangeDetected.as_str(),
+            "source_id" => source_id.to_string(),
+            "reason" => reason.to_string()
+        );
+    }
+
+    /// Record a run whose payloads changed shape past the drift threshold
+    pub fn schema_drift_detected(source_id: &str) {
+        counter_and_push!(MetricName::SourcesSchemaDriftDetected.as_str(),
+            "source_id" => source_id.to_string()
+        );
+    }
+
+    /// Set how far a source's latest payload structure moved from its previous run's
+    pub fn schema_drift_score(source_id: &str, score: f64) {
+        let metric_name = MetricName::SourcesSchemaDriftScore.as_str();
+        ::metrics::gauge!(metric_name, "source_id" => source_id.to_string()).set(score);
+        spawn_push(async move {
+            let _ = push_single_metric(metric_name, score, "gauge").await;
+        });
+    }
+
+    /// Record a fetch refused because the source's monthly quota is spent
+    pub fn quota_exceeded(source_id: &str) {
+        counter_and_push!(MetricName::SourcesQuotaExceeded.as_str(),
+            "source_id" => source_id.to_string()
+        );
+    }
+
+    /// Set the requests and bytes left in a source's monthly quota (only the limits it has)
+    pub fn quota_remaining(source_id: &str, requests: Option<u64>, bytes: Option<u64>) {
+        for (metric, remaining) in [
+            (MetricName::SourcesQuotaRemainingRequests, requests),
+            (MetricName::SourcesQuotaRemainingBytes, bytes),
+        ] {
+            let Some(remaining) = remaining else { continue };
+            let metric_name = metric.as_str();
+            ::metrics::gauge!(metric_name, "source_id" => source_id.to_string()).set(remaining as f64);
+            spawn_push(async move {
+                let _ = push_single_metric(metric_name, remaining as f64, "gauge").await;
+            });
+        }
+    }
+
+    /// Record a registry hot-reload: `applied` when the new specs replaced the old, `rejected`
+    /// when they failed validation and the previous registry stayed in use
+    pub fn registry_reloaded(outcome: &str) {
+        counter_and_push!(MetricName::SourcesRegistryReloads.as_str(),
+            "outcome" => outcome.to_string()
+        );
+    }
 }
 
 // ============================================================================
@@ -718,13 +769,13 @@ pub mod sources {
 // ============================================================================
 
 pub mod gateway {
-    use super::{push_single_metric, MetricName};
+    use super::{push_histogram_metric, push_single_metric, spawn_push, MetricName};
     
     /// Record an accepted envelope
     pub fn envelope_accepted() {
         let metric_name = MetricName::GatewayEnvelopesAccepted.as_str();
         ::metrics::counter!(metric_name).increment(1);
-        tokio::spawn(async move {
+        spawn_push(async move {
             let _ = push_single_metric(metric_name, 1.0, "counter").await;
         });
     }
@@ -733,7 +784,7 @@ pub mod gateway {
     pub fn envelope_deduplicated() {
         let metric_name = MetricName::GatewayEnvelopesDeduplicated.as_str();
         ::metrics::counter!(metric_name).increment(1);
-        tokio::spawn(async move {
+        spawn_push(async move {
             let _ = push_single_metric(metric_name, 1.0, "counter").await;
         });
     }
@@ -742,7 +793,7 @@ pub mod gateway {
     pub fn cas_write_success() {
         let metric_name = MetricName::GatewayCasWritesSuccess.as_str();
         ::metrics::counter!(metric_name).increment(1);
-        tokio::spawn(async move {
+        spawn_push(async move {
             let _ = push_single_metric(metric_name, 1.0, "counter").await;
         });
     }
@@ -751,7 +802,7 @@ pub mod gateway {
     pub fn cas_write_error() {
         let metric_name = MetricName::GatewayCasWritesError.as_str();
         ::metrics::counter!(metric_name).increment(1);
-        tokio::spawn(async move {
+        spawn_push(async move {
             let _ = push_single_metric(metric_name, 1.0, "counter").await;
         });
     }
@@ -762,7 +813,7 @@ pub mod gateway {
         let metric_name = MetricName::GatewayRecordsIngested.as_str();
         ::metrics::counter!(metric_name).increment(count);
         let c = count as f64;
-        tokio::spawn(async move {
+        spawn_push(async move {
             let _ = push_single_metric(metric_name, c, "counter").await;
         });
     }
@@ -771,11 +822,29 @@ pub mod gateway {
     pub fn processing_duration(secs: f64) {
         let metric_name = MetricName::GatewayProcessingDuration.as_str();
         ::metrics::histogram!(metric_name).record(secs);
-        tokio::spawn(async move {
+        spawn_push(async move {
             let _ = push_single_metric(metric_name, secs, "gauge").await;
         });
     }
     
+    /// Record wire and decoded sizes of a fetched payload
+    pub fn payload_sizes(compressed: usize, uncompressed: usize) {
+        let compressed_name = MetricName::GatewayPayloadCompressedBytes.as_str();
+        let uncompressed_name = MetricName::GatewayPayloadUncompressedBytes.as_str();
+        let (c, u) = (compressed as f64, uncompressed as f64);
+        ::metrics::histogram!(compressed_name).record(c);
+        ::metrics::histogram!(uncompressed_name).record(u);
+        spawn_push(async move {
+            let _ = push_histogram_metric(compressed_name, c).await;
+            let _ = push_histogram_metric(uncompressed_name, u).await;
+        });
+    }
+
+    /// Record a fetch aborted for exceeding the source's payload size limit
+    pub fn payload_oversize() {
+        counter_and_push!(MetricName::GatewayPayloadsOversize.as_str());
+    }
+
     /// Record successful ingest for a source
     pub fn ingest_success(source_id: &str) {
         ::metrics::counter!(MetricName::GatewayIngestSuccess.as_str(), "source_id" => source_id.to_string()).increment(1);
@@ -805,13 +874,13 @@ pub mod gateway {
 // ============================================================================
 
 pub mod ingest_log {
-    use super::{push_single_metric, MetricName};
+    use super::{push_single_metric, spawn_push, MetricName};
     
     /// Record successful write
     pub fn write_success() {
         let metric_name = MetricName::IngestLogWritesSuccess.as_str();
         ::metrics::counter!(metric_name).increment(1);
-        tokio::spawn(async move {
+        spawn_push(async move {
             let _ = push_single_metric(metric_name, 1.0, "counter").await;
         });
     }
@@ -820,7 +889,7 @@ pub mod ingest_log {
     pub fn write_error() {
         let metric_name = MetricName::IngestLogWritesError.as_str();
         ::metrics::counter!(metric_name).increment(1);
-        tokio::spawn(async move {
+        spawn_push(async move {
             let _ = push_single_metric(metric_name, 1.0, "counter").await;
         });
     }
@@ -844,16 +913,49 @@ pub mod ingest_log {
         let metric_name = MetricName::IngestLogCurrentFileBytes.as_str();
         ::metrics::gauge!(metric_name).set(bytes as f64);
         let b = bytes as f64;
-        tokio::spawn(async move {
+        spawn_push(async move {
             let _ = push_single_metric(metric_name, b, "gauge").await;
         });
     }
     
+    /// Record a partial line left by a crashed writer, skipped on read
+    pub fn torn_line() {
+        ::metrics::counter!(MetricName::IngestLogTornLines.as_str()).increment(1);
+    }
+
     /// Set active consumers count
-    #[allow(dead_code)]
     pub fn active_consumers(count: usize) {
         ::metrics::gauge!(MetricName::IngestLogActiveConsumers.as_str()).set(count as f64);
     }
+
+    /// Record an envelope moving to `state` (received, parsed, normalized, cataloged, failed, quarantined)
+    pub fn envelope_transition(state: &str) {
+        counter_and_push!(MetricName::IngestLogEnvelopeTransitions.as_str(),
+            "state" => state.to_string()
+        );
+    }
+
+    /// Set a consumer's committed offset, the log end, and the lag between them
+    pub fn consumer_position(consumer: &str, offset: u64, end: u64) {
+        let lag = end.saturating_sub(offset);
+        ::metrics::gauge!(MetricName::IngestLogConsumerLag.as_str(), "consumer" => consumer.to_string()).set(lag as f64);
+        ::metrics::gauge!(MetricName::IngestLogConsumerOffset.as_str(), "consumer" => consumer.to_string()).set(offset as f64);
+        ::metrics::gauge!(MetricName::IngestLogEndOffset.as_str()).set(end as f64);
+        spawn_push(async move {
+            let _ = push_single_metric(MetricName::IngestLogConsumerLag.as_str(), lag as f64, "gauge").await;
+            let _ = push_single_metric(MetricName::IngestLogConsumerOffset.as_str(), offset as f64, "gauge").await;
+            let _ = push_single_metric(MetricName::IngestLogEndOffset.as_str(), end as f64, "gauge").await;
+        });
+    }
+
+    /// Set how many envelopes have sat in `state` past the stuck threshold
+    pub fn envelopes_stuck(state: &str, count: u64) {
+        let metric_name = MetricName::IngestLogEnvelopeStuck.as_str();
+        ::metrics::gauge!(metric_name, "state" => state.to_string()).set(count as f64);
+        spawn_push(async move {
+            let _ = push_single_metric(metric_name, count as f64, "gauge").await;
+        });
+    }
 }
 
 // ============================================================================
@@ -861,13 +963,13 @@ pub mod ingest_log {
 // ============================================================================
 
 pub mod parser {
-    use super::{push_single_metric, MetricName};
+    use super::{push_single_metric, spawn_push, MetricName};
     
     /// Record successful parse
     pub fn parse_success() {
         let metric_name = MetricName::ParserParseSuccess.as_str();
         ::metrics::counter!(metric_name).increment(1);
-        tokio::spawn(async move {
+        spawn_push(async move {
             let _ = push_single_metric(metric_name, 1.0, "counter").await;
         });
     }
@@ -876,11 +978,49 @@ pub mod parser {
     pub fn parse_error() {
         let metric_name = MetricName::ParserParseError.as_str();
         ::metrics::counter!(metric_name).increment(1);
-        tokio::spawn(async move {
+        spawn_push(async move {
             let _ = push_single_metric(metric_name, 1.0, "counter").await;
         });
     }
     
+    /// Record an envelope sent to the dead-letter queue
+    pub fn dead_lettered() {
+        let metric_name = MetricName::ParserDeadLettered.as_str();
+        ::metrics::counter!(metric_name).increment(1);
+        spawn_push(async move {
+            let _ = push_single_metric(metric_name, 1.0, "counter").await;
+        });
+    }
+
+    /// Record how many of a fetch's records were added, changed, unchanged or removed
+    pub fn delta_records(source_id: &str, change: &str, count: usize) {
+        if count == 0 {
+            return;
+        }
+        let metric_name = MetricName::ParserDeltaRecords.as_str();
+        ::metrics::counter!(metric_name, "source_id" => source_id.to_string(), "change" => change.to_string())
+            .increment(count as u64);
+        spawn_push(async move {
+            let _ = push_single_metric(metric_name, count as f64, "counter").await;
+        });
+    }
+    
+    /// Record one LLM fallback attempt (`parsed`, `empty`, `failed` or `over_budget`) and what it cost
+    pub fn llm_fallback(source_id: &str, outcome: &str, cost_usd: f64) {
+        let calls = MetricName::ParserLlmFallbackCalls.as_str();
+        ::metrics::counter!(calls, "source_id" => source_id.to_string(), "outcome" => outcome.to_string()).increment(1);
+        let cost = MetricName::ParserLlmFallbackCost.as_str();
+        if cost_usd > 0.0 {
+            ::metrics::gauge!(cost, "source_id" => source_id.to_string()).increment(cost_usd);
+        }
+        spawn_push(async move {
+            let _ = push_single_metric(calls, 1.0, "counter").await;
+            if cost_usd > 0.0 {
+                let _ = push_single_metric(cost, cost_usd, "gauge").await;
+            }
+        });
+    }
+
     /// Record parse duration
     pub fn duration(secs: f64) {
         ::metrics::histogram!(MetricName::ParserDuration.as_str()).record(secs);
@@ -891,7 +1031,7 @@ pub mod parser {
         let metric_name = MetricName::ParserRecordsExtracted.as_str();
         ::metrics::counter!(metric_name).increment(count);
         let c = count as f64;
-        tokio::spawn(async move {
+        spawn_push(async move {
             let _ = push_single_metric(metric_name, c, "counter").await;
         });
     }
@@ -913,47 +1053,56 @@ pub mod parser {
 // ============================================================================
 
 pub mod normalize {
-    use super::push_single_metric;
+    use super::{push_single_metric, spawn_push, MetricName};
     
     /// Record that a record was normalized with a specific strategy
     pub fn record_normalized(strategy: &str) {
-        let metric_name = "sms_normalize_records_processed_total";
+        let metric_name = MetricName::NormalizeRecordsProcessed.as_str();
         ::metrics::counter!(metric_name, "strategy" => strategy.to_string()).increment(1);
-        tokio::spawn(async move {
+        spawn_push(async move {
             let _ = push_single_metric(metric_name, 1.0, "counter").await;
         });
     }
     
     /// Record the confidence level of normalization
     pub fn confidence_recorded(confidence: f64) {
-        ::metrics::histogram!("sms_normalize_confidence").record(confidence);
+        ::metrics::histogram!(MetricName::NormalizeConfidence.as_str()).record(confidence);
         // Don't push histograms to pushgateway - let Prometheus handle aggregation
     }
     
     /// Record that geocoding was performed
     pub fn geocoding_performed() {
-        let metric_name = "sms_normalize_geocoding_total";
+        let metric_name = MetricName::NormalizeGeocoding.as_str();
         ::metrics::counter!(metric_name).increment(1);
-        tokio::spawn(async move {
+        spawn_push(async move {
             let _ = push_single_metric(metric_name, 1.0, "counter").await;
         });
     }
     
     /// Record a warning during normalization
     pub fn warning_logged(warning: &str) {
-        let metric_name = "sms_normalize_warnings_total";
+        let metric_name = MetricName::NormalizeWarnings.as_str();
         ::metrics::counter!(metric_name, "warning_type" => warning.to_string()).increment(1);
-        tokio::spawn(async move {
+        spawn_push(async move {
             let _ = push_single_metric(metric_name, 1.0, "counter").await;
         });
     }
     
     /// Record that a batch was processed
     pub fn batch_processed(batch_size: usize) {
-        ::metrics::histogram!("sms_normalize_batch_size").record(batch_size as f64);
-        let metric_name = "sms_normalize_batches_processed_total";
+        ::metrics::histogram!(MetricName::NormalizeBatchSize.as_str()).record(batch_size as f64);
+        let metric_name = MetricName::NormalizeBatchesProcessed.as_str();
         ::metrics::counter!(metric_name).increment(1);
-        tokio::spawn(async move {
+        spawn_push(async move {
+            let _ = push_single_metric(metric_name, 1.0, "counter").await;
+        });
+    }
+
+    /// Record how a shadow normalizer's output compared with the primary's for one record
+    pub fn shadow_compared(source_id: &str, outcome: &str) {
+        let metric_name = MetricName::NormalizeShadowComparisons.as_str();
+        ::metrics::counter!(metric_name, "source_id" => source_id.to_string(), "outcome" => outcome.to_string()).increment(1);
+        spawn_push(async move {
             let _ = push_single_metric(metric_name, 1.0, "counter").await;
         });
     }
@@ -964,13 +1113,13 @@ pub mod normalize {
 // ============================================================================
 
 pub mod quality_gate {
-    use super::{push_single_metric, MetricName};
+    use super::{push_single_metric, spawn_push, MetricName};
     
     /// Record that a record was accepted by the quality gate
     pub fn record_accepted() {
         let metric_name = MetricName::QualityGateRecordsAccepted.as_str();
         ::metrics::counter!(metric_name).increment(1);
-        tokio::spawn(async move {
+        spawn_push(async move {
             let _ = push_single_metric(metric_name, 1.0, "counter").await;
         });
     }
@@ -979,7 +1128,7 @@ pub mod quality_gate {
     pub fn record_accepted_with_warnings() {
         let metric_name = MetricName::QualityGateRecordsAcceptedWithWarnings.as_str();
         ::metrics::counter!(metric_name).increment(1);
-        tokio::spawn(async move {
+        spawn_push(async move {
             let _ = push_single_metric(metric_name, 1.0, "counter").await;
         });
     }
@@ -988,7 +1137,7 @@ pub mod quality_gate {
     pub fn record_quarantined() {
         let metric_name = MetricName::QualityGateRecordsQuarantined.as_str();
         ::metrics::counter!(metric_name).increment(1);
-        tokio::spawn(async move {
+        spawn_push(async move {
             let _ = push_single_metric(metric_name, 1.0, "counter").await;
         });
     }
@@ -1007,7 +1156,20 @@ pub mod quality_gate {
             "issue_type" => issue_type.to_string(),
             "severity" => severity.to_string()
         ).increment(1);
-        tokio::spawn(async move {
+        spawn_push(async move {
+            let _ = push_single_metric(metric_name, 1.0, "counter").await;
+        });
+    }
+    
+    /// Record the active gate's and a shadow candidate config's decisions for one record
+    pub fn shadow_compared(source_id: &str, active: &str, candidate: &str) {
+        let metric_name = MetricName::QualityGateShadowDecisions.as_str();
+        ::metrics::counter!(metric_name,
+            "source_id" => source_id.to_string(),
+            "active" => active.to_string(),
+            "candidate" => candidate.to_string()
+        ).increment(1);
+        spawn_push(async move {
             let _ = push_single_metric(metric_name, 1.0, "counter").await;
         });
     }
@@ -1026,7 +1188,7 @@ pub mod quality_gate {
             "quarantined" => quarantined_count.to_string()
         ).increment(1);
         
-        tokio::spawn(async move {
+        spawn_push(async move {
             let _ = push_single_metric(batch_metric, 1.0, "counter").await;
         });
     }
@@ -1064,7 +1226,7 @@ pub async fn push_ingest_metrics(
     }
 }
 
-/// Push metrics to Pushgateway
+/// Push metrics to Pushgateway; a no-op unless metrics were initialized with one
 #[allow(dead_code)]
 pub async fn push_to_pushgateway(
     instance: &str,
@@ -1072,12 +1234,14 @@ pub async fn push_to_pushgateway(
     duration_secs: f64,
     success: bool,
 ) -> Result<(), Box<dyn std::error::Error>> {
-    let base = std::env::var("SMS_PUSHGATEWAY_URL")
-        .unwrap_or_else(|_| "http://localhost:9091".to_string());
+    let Some(state) = METRICS_HANDLE.get() else {
+        return Ok(());
+    };
     
     let push_url = format!(
-        "{}/metrics/job/sms_scraper/instance/{}",
-        base.trim_end_matches('/'),
+        "{}/metrics/job/{}/instance/{}",
+        state.pushgateway_url.trim_end_matches('/'),
+        state.job,
         instance
     );
     
@@ -1123,14 +1287,18 @@ pub async fn push_all_metrics() -> Result<(), Box<dyn std::error::Error>> {
     push_all_metrics_with_instance("default").await
 }
 
-/// Push ALL collected metrics to Pushgateway with custom instance label
+/// Push ALL collected metrics to Pushgateway with custom instance label; a no-op unless
+/// metrics were initialized with a Pushgateway
 pub async fn push_all_metrics_with_instance(instance: &str) -> Result<(), Box<dyn std::error::Error>> {
-    let pushgateway_url = std::env::var("SMS_PUSHGATEWAY_URL")
-        .unwrap_or_else(|_| "http://localhost:9091".to_string());
+    let Some(state) = METRICS_HANDLE.get() else {
+        warn!("Metrics not initialized with push gateway support; nothing pushed");
+        return Ok(());
+    };
     
     let push_url = format!(
-        "{}/metrics/job/sms_scraper/instance/{}",
-        pushgateway_url.trim_end_matches('/'),
+        "{}/metrics/job/{}/instance/{}",
+        state.pushgateway_url.trim_end_matches('/'),
+        state.job,
         instance
     );
     
@@ -1149,9 +1317,8 @@ pub async fn push_all_metrics_with_instance(instance: &str) -> Result<(), Box<dy
     ));
     
     // Try to render from the handle if available
-    if let Some(state) = METRICS_HANDLE.get() {
+    if let Some(rendered) = get_metrics_handle() {
         // Try to get rendered metrics directly
-        let rendered = state.handle.render();
         if !rendered.is_empty() {
             info!("Rendered {} bytes of metrics directly", rendered.len());
             metrics_text.push_str(&rendered);
@@ -1164,7 +1331,7 @@ pub async fn push_all_metrics_with_instance(instance: &str) -> Result<(), Box<dy
             );
         }
     } else {
-        warn!("Metrics not initialized with push gateway support.");
+        warn!("No Prometheus recorder installed; pushing only the initialization marker");
         metrics_text.push_str(
             "# HELP sms_metrics_initialized Whether metrics system is initialized\n\
              # TYPE sms_metrics_initialized gauge\n\
@@ -1195,13 +1362,13 @@ pub async fn push_all_metrics_with_instance(instance: &str) -> Result<(), Box<dy
 // ============================================================================
 
 pub mod enrich {
-    use super::{push_single_metric, MetricName};
+    use super::{push_single_metric, spawn_push, MetricName};
     
     /// Record that a record was enriched with a specific strategy
     pub fn record_enriched(strategy: &str) {
         let metric_name = MetricName::EnrichRecordsProcessed.as_str();
         ::metrics::counter!(metric_name, "strategy" => strategy.to_string()).increment(1);
-        tokio::spawn(async move {
+        spawn_push(async move {
             let _ = push_single_metric(metric_name, 1.0, "counter").await;
         });
     }
@@ -1217,7 +1384,7 @@ pub mod enrich {
     pub fn spatial_binning_performed() {
         let metric_name = MetricName::EnrichSpatialBinning.as_str();
         ::metrics::counter!(metric_name).increment(1);
-        tokio::spawn(async move {
+        spawn_push(async move {
             let _ = push_single_metric(metric_name, 1.0, "counter").await;
         });
     }
@@ -1226,7 +1393,7 @@ pub mod enrich {
     pub fn city_tagging_performed() {
         let metric_name = MetricName::EnrichCityTagging.as_str();
         ::metrics::counter!(metric_name).increment(1);
-        tokio::spawn(async move {
+        spawn_push(async move {
             let _ = push_single_metric(metric_name, 1.0, "counter").await;
         });
     }
@@ -1242,7 +1409,7 @@ pub mod enrich {
     pub fn warning_logged(warning: &str) {
         let metric_name = MetricName::EnrichWarnings.as_str();
         ::metrics::counter!(metric_name, "warning_type" => warning.to_string()).increment(1);
-        tokio::spawn(async move {
+        spawn_push(async move {
             let _ = push_single_metric(metric_name, 1.0, "counter").await;
         });
     }
@@ -1258,7 +1425,7 @@ pub mod enrich {
         let batch_metric = MetricName::EnrichBatchesProcessed.as_str();
         ::metrics::counter!(batch_metric).increment(1);
         
-        tokio::spawn(async move {
+        spawn_push(async move {
             let _ = push_single_metric(batch_metric, 1.0, "counter").await;
         });
     }
@@ -1269,13 +1436,13 @@ pub mod enrich {
 // ============================================================================
 
 pub mod conflation {
-    use super::{push_single_metric, MetricName};
+    use super::{push_single_metric, spawn_push, MetricName};
     
     /// Record that a record was processed through conflation
     pub fn records_processed() {
         let metric_name = MetricName::ConflationRecordsProcessed.as_str();
         ::metrics::counter!(metric_name).increment(1);
-        tokio::spawn(async move {
+        spawn_push(async move {
             let _ = push_single_metric(metric_name, 1.0, "counter").await;
         });
     }
@@ -1284,7 +1451,7 @@ pub mod conflation {
     pub fn records_successful() {
         let metric_name = MetricName::ConflationRecordsSuccessful.as_str();
         ::metrics::counter!(metric_name).increment(1);
-        tokio::spawn(async move {
+        spawn_push(async move {
             let _ = push_single_metric(metric_name, 1.0, "counter").await;
         });
     }
@@ -1293,7 +1460,7 @@ pub mod conflation {
     pub fn records_failed() {
         let metric_name = MetricName::ConflationRecordsFailed.as_str();
         ::metrics::counter!(metric_name).increment(1);
-        tokio::spawn(async move {
+        spawn_push(async move {
             let _ = push_single_metric(metric_name, 1.0, "counter").await;
         });
     }
@@ -1309,7 +1476,7 @@ pub mod conflation {
     pub fn new_entity_created() {
         let metric_name = MetricName::ConflationNewEntities.as_str();
         ::metrics::counter!(metric_name).increment(1);
-        tokio::spawn(async move {
+        spawn_push(async move {
             let _ = push_single_metric(metric_name, 1.0, "counter").await;
         });
     }
@@ -1318,7 +1485,7 @@ pub mod conflation {
     pub fn matched_existing() {
         let metric_name = MetricName::ConflationMatchedExisting.as_str();
         ::metrics::counter!(metric_name).increment(1);
-        tokio::spawn(async move {
+        spawn_push(async move {
             let _ = push_single_metric(metric_name, 1.0, "counter").await;
         });
     }
@@ -1327,7 +1494,7 @@ pub mod conflation {
     pub fn updated_existing() {
         let metric_name = MetricName::ConflationUpdatedExisting.as_str();
         ::metrics::counter!(metric_name).increment(1);
-        tokio::spawn(async move {
+        spawn_push(async move {
             let _ = push_single_metric(metric_name, 1.0, "counter").await;
         });
     }
@@ -1336,7 +1503,7 @@ pub mod conflation {
     pub fn duplicate_detected() {
         let metric_name = MetricName::ConflationDuplicates.as_str();
         ::metrics::counter!(metric_name).increment(1);
-        tokio::spawn(async move {
+        spawn_push(async move {
             let _ = push_single_metric(metric_name, 1.0, "counter").await;
         });
     }
@@ -1345,7 +1512,7 @@ pub mod conflation {
     pub fn uncertain_resolution() {
         let metric_name = MetricName::ConflationUncertainResolutions.as_str();
         ::metrics::counter!(metric_name).increment(1);
-        tokio::spawn(async move {
+        spawn_push(async move {
             let _ = push_single_metric(metric_name, 1.0, "counter").await;
         });
     }
@@ -1354,7 +1521,7 @@ pub mod conflation {
     pub fn warning_logged(warning: &str) {
         let metric_name = MetricName::ConflationWarnings.as_str();
         ::metrics::counter!(metric_name, "warning_type" => warning.to_string()).increment(1);
-        tokio::spawn(async move {
+        spawn_push(async move {
             let _ = push_single_metric(metric_name, 1.0, "counter").await;
         });
     }
@@ -1364,7 +1531,7 @@ pub mod conflation {
         let metric_name = MetricName::ConflationPotentialDuplicates.as_str();
         ::metrics::counter!(metric_name).increment(count as u64);
         let c = count as f64;
-        tokio::spawn(async move {
+        spawn_push(async move {
             let _ = push_single_metric(metric_name, c, "counter").await;
         });
     }
@@ -1374,7 +1541,7 @@ pub mod conflation {
         let metric_name = MetricName::ConflationAlternativeMatches.as_str();
         ::metrics::counter!(metric_name).increment(count as u64);
         let c = count as f64;
-        tokio::spawn(async move {
+        spawn_push(async move {
             let _ = push_single_metric(metric_name, c, "counter").await;
         });
     }
@@ -1393,7 +1560,7 @@ pub mod conflation {
         if failed_count == 0 {
             let batches_successful = MetricName::ConflationBatchesSuccessful.as_str();
             ::metrics::counter!(batches_successful).increment(1);
-            tokio::spawn(async move {
+            spawn_push(async move {
                 let _ = push_single_metric(batches_successful, 1.0, "counter").await;
             });
         }
@@ -1405,7 +1572,7 @@ pub mod conflation {
         let records_failed = MetricName::ConflationBatchRecordsFailed.as_str();
         ::metrics::counter!(records_failed).increment(failed_count as u64);
         
-        tokio::spawn(async move {
+        spawn_push(async move {
             let _ = push_single_metric(batches_processed, 1.0, "counter").await;
             let _ = push_single_metric(records_successful, successful_count as f64, "counter").await;
             let _ = push_single_metric(records_failed, failed_count as f64, "counter").await;
@@ -1419,3 +1586,176 @@ pub mod conflation {
         // Don't push histograms to pushgateway - let Prometheus handle aggregation
     }
 }
+
+// ============================================================================
+// Catalog Metrics
+// ============================================================================
+
+pub mod catalog {
+    use super::{push_single_metric, spawn_push, MetricName};
+
+    /// Record that an event was folded into an already-cataloged duplicate
+    pub fn duplicate_suppressed() {
+        let metric_name = MetricName::CatalogDuplicatesSuppressed.as_str();
+        ::metrics::counter!(metric_name).increment(1);
+        spawn_push(async move {
+            let _ = push_single_metric(metric_name, 1.0, "counter").await;
+        });
+    }
+
+    /// Record a committed write batch: its size, how long it took, and any conflict retries
+    pub fn batch_written(entities: usize, attempts: u32, secs: f64) {
+        let batches_name = MetricName::CatalogBatchesWritten.as_str();
+        let retries_name = MetricName::CatalogBatchRetries.as_str();
+        let entities_name = MetricName::CatalogEntitiesWritten.as_str();
+        let duration_name = MetricName::CatalogBatchDuration.as_str();
+        let throughput_name = MetricName::CatalogWriteThroughput.as_str();
+        let retries = attempts.saturating_sub(1) as f64;
+        let throughput = if secs > 0.0 { entities as f64 / secs } else { 0.0 };
+        ::metrics::counter!(batches_name).increment(1);
+        ::metrics::counter!(retries_name).increment(retries as u64);
+        ::metrics::counter!(entities_name).increment(entities as u64);
+        ::metrics::histogram!(duration_name).record(secs);
+        ::metrics::gauge!(throughput_name).set(throughput);
+        spawn_push(async move {
+            let _ = push_single_metric(batches_name, 1.0, "counter").await;
+            let _ = push_single_metric(retries_name, retries, "counter").await;
+            let _ = push_single_metric(entities_name, entities as f64, "counter").await;
+            let _ = push_single_metric(duration_name, secs, "gauge").await;
+            let _ = push_single_metric(throughput_name, throughput, "gauge").await;
+        });
+    }
+}
+
+// ============================================================================
+// Catalog Audit Metrics
+// ============================================================================
+
+/// Set once per `audit` run; the command pushes them itself before exiting
+pub mod audit {
+    use super::MetricName;
+
+    /// Record the finding count of each audit check and the time the audit ran
+    pub fn completed<'a>(counts: impl IntoIterator<Item = (&'a str, usize)>) {
+        for (check, findings) in counts {
+            ::metrics::gauge!(MetricName::AuditFindings.as_str(), "check" => check.to_string()).set(findings as f64);
+        }
+        ::metrics::gauge!(MetricName::AuditLastRun.as_str()).set(chrono::Utc::now().timestamp() as f64);
+    }
+}
+
+// ============================================================================
+// GraphQL API Metrics
+// ============================================================================
+
+/// Recorded per request and scraped from the server's `/metrics`, not pushed: a
+/// push per request would put the gateway on the API's hot path
+pub mod graphql {
+    use super::MetricName;
+
+    /// Record one served request
+    pub fn request(route: &str, method: &str, status: u16, operation: &str, secs: f64) {
+        let labels = [
+            ("route", route.to_string()),
+            ("method", method.to_string()),
+            ("status", status.to_string()),
+            ("operation", operation.to_string()),
+        ];
+        ::metrics::counter!(MetricName::GraphqlRequests.as_str(), &labels).increment(1);
+        ::metrics::histogram!(MetricName::GraphqlRequestDuration.as_str(), &labels).record(secs);
+    }
+
+    /// Record a lookup in a shared query result cache
+    pub fn cache_lookup(cache: &str, hit: bool) {
+        let result = if hit { "hit" } else { "miss" };
+        ::metrics::counter!(MetricName::GraphqlCacheLookups.as_str(), "cache" => cache.to_string(), "result" => result).increment(1);
+    }
+
+    /// Record one DataLoader batch and how many keys it fetched
+    pub fn loader_batch(loader: &str, keys: usize) {
+        ::metrics::histogram!(MetricName::GraphqlLoaderBatchSize.as_str(), "loader" => loader.to_string()).record(keys as f64);
+    }
+}
+
+// ============================================================================
+// Run Bookkeeping Metrics
+// ============================================================================
+
+/// Recorded by `RunTracker` as runs open, move through stages and close
+pub mod runs {
+    use super::{push_histogram_metric, push_single_metric, spawn_push, MetricName};
+
+    /// Record that a run of `kind` opened now
+    pub fn started(kind: &str) {
+        let metric_name = MetricName::RunsStarted.as_str();
+        let now = chrono::Utc::now().timestamp() as f64;
+        ::metrics::gauge!(metric_name, "kind" => kind.to_string()).set(now);
+        spawn_push(async move {
+            let _ = push_single_metric(metric_name, now, "gauge").await;
+        });
+        super::heartbeat();
+    }
+
+    /// Record the time one stage of a run took
+    pub fn stage_duration(kind: &str, stage: &str, secs: f64) {
+        let metric_name = MetricName::RunsStageDuration.as_str();
+        ::metrics::histogram!(metric_name, "kind" => kind.to_string(), "stage" => stage.to_string()).record(secs);
+        spawn_push(async move {
+            let _ = push_histogram_metric(metric_name, secs).await;
+        });
+    }
+
+    /// Record a run closing with `outcome` (success, failure or abandoned) after `secs`
+    pub fn completed(kind: &str, outcome: &str, secs: f64) {
+        counter_and_push!(MetricName::RunsCompleted.as_str(),
+            "kind" => kind.to_string(),
+            "outcome" => outcome.to_string()
+        );
+        let duration_name = MetricName::RunsDuration.as_str();
+        ::metrics::gauge!(duration_name, "kind" => kind.to_string()).set(secs);
+        let last_success = (outcome == "success").then(|| chrono::Utc::now().timestamp() as f64);
+        if let Some(ts) = last_success {
+            ::metrics::gauge!(MetricName::RunsLastSuccess.as_str(), "kind" => kind.to_string()).set(ts);
+        }
+        spawn_push(async move {
+            let _ = push_single_metric(duration_name, secs, "gauge").await;
+            if let Some(ts) = last_success {
+                let _ = push_single_metric(MetricName::RunsLastSuccess.as_str(), ts, "gauge").await;
+            }
+        });
+        super::heartbeat();
+    }
+}
+
+// ============================================================================
+// Fault Injection Metrics
+// ============================================================================
+
+/// Recorded by the chaos-mode fault injector each time it fails an operation on purpose
+pub mod chaos {
+    use super::{push_single_metric, spawn_push, MetricName};
+
+    /// Record an injected fault of `kind` (http_timeout, cas_write or db)
+    pub fn fault_injected(kind: &str) {
+        counter_and_push!(MetricName::ChaosFaultsInjected.as_str(), "fault" => kind.to_string());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn catalog_batch_metrics_are_listed_under_the_catalog_phase() {
+        let catalog: Vec<_> = MetricName::all_metrics().filter(|metric| metric.metadata().0 == "catalog").collect();
+        for metric in [
+            MetricName::CatalogBatchesWritten,
+            MetricName::CatalogBatchRetries,
+            MetricName::CatalogEntitiesWritten,
+            MetricName::CatalogBatchDuration,
+            MetricName::CatalogWriteThroughput,
+        ] {
+            assert!(catalog.contains(&metric), "{} is missing from the dashboard", metric.as_str());
+        }
+    }
+}
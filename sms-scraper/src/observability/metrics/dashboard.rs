@@ -319,7 +319,7 @@ impl DashboardBuilder {
         // Iterate through all metrics in the enum
         for metric in super::MetricName::all_metrics() {
             let (phase, description, unit) = metric.metadata();
-            let metric_type = metric.infer_metric_type();
+            let metric_type = metric.kind();
             
             builder = builder.add_metric(MetricDef {
                 name: metric.to_string(),
@@ -545,7 +545,7 @@ impl DashboardBuilder {
         // Group metrics by phase
         let mut phases: HashMap<String, Vec<&MetricDef>> = HashMap::new();
         for metric in &self.metrics {
-            phases.entry(metric.phase.clone()).or_insert_with(Vec::new).push(metric);
+            phases.entry(metric.phase.clone()).or_default().push(metric);
         }
         
         // Sort phases for consistent ordering
@@ -1,11 +1,16 @@
 use std::time::Duration;
+use super::push::PushConfig;
 use tracing::info;
 
-/// Start a temporary HTTP server to collect metrics and push them to the gateway
+/// Start a temporary HTTP server to collect metrics and push them to the gateway; a no-op
+/// when the environment doesn't configure a Pushgateway
 #[allow(dead_code)]
 pub async fn collect_and_push_metrics(instance: &str) -> Result<(), Box<dyn std::error::Error>> {
-    let pushgateway_url = std::env::var("SMS_PUSHGATEWAY_URL")
-        .unwrap_or_else(|_| "http://localhost:9091".to_string());
+    let push = PushConfig::from_env()?;
+    let Some(pushgateway_url) = push.url() else {
+        info!("No Pushgateway configured; not pushing metrics for instance={}", instance);
+        return Ok(());
+    };
     
     // Start a temporary server on a random port to collect metrics
     let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await?;
@@ -69,7 +74,7 @@ async fn handle_metrics_request(mut stream: tokio::net::TcpStream) {
     
     // Get metrics from the global registry
     let metrics = crate::observability::metrics::get_metrics_handle()
-        .unwrap_or_else(|| String::new());
+        .unwrap_or_default();
     
     let response = format!(
         "HTTP/1.1 200 OK\r\nContent-Type: text/plain; version=0.0.4\r\nContent-Length: {}\r\n\r\n{}",
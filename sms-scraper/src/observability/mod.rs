@@ -3,9 +3,18 @@
 pub mod logging;
 pub mod metrics;
 pub mod metrics_push;
+#[cfg(feature = "scraping")]
+pub mod otel;
+pub mod push;
+pub mod run_tracker;
 
 // Re-export main functions for ease of use
-pub use logging::init_logging;
+pub use logging::{init_logging, LogFormat};
+#[cfg(feature = "scraping")]
+pub use logging::init_console_logging;
+#[cfg(feature = "scraping")]
+pub use otel::shutdown_tracing;
+pub use run_tracker::{RunSummary, RunTracker, StageTiming};
 pub use metrics::{
     heartbeat, init,
 };
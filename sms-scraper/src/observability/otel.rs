@@ -0,0 +1,66 @@
+use anyhow::Result;
+use opentelemetry::trace::TracerProvider as _;
+use opentelemetry::KeyValue;
+use opentelemetry_otlp::WithExportConfig;
+use opentelemetry_sdk::{runtime, trace, Resource};
+
+/// Standard OTLP env var; used when `--otlp-endpoint` is not given
+pub const OTLP_ENDPOINT_ENV: &str = "OTEL_EXPORTER_OTLP_ENDPOINT";
+
+const SERVICE_NAME: &str = "sms-scraper";
+
+/// The OTLP collector base URL from the CLI flag, else the environment. `None` disables span export.
+pub fn otlp_endpoint(cli_endpoint: Option<&str>) -> Option<String> {
+    cli_endpoint
+        .map(str::to_string)
+        .or_else(|| std::env::var(OTLP_ENDPOINT_ENV).ok())
+        .map(|e| e.trim().to_string())
+        .filter(|e| !e.is_empty())
+}
+
+/// Traces URL for an OTLP/HTTP collector base URL such as `http://localhost:4318`
+pub fn traces_url(endpoint: &str) -> String {
+    let endpoint = endpoint.trim_end_matches('/');
+    if endpoint.ends_with("/v1/traces") {
+        endpoint.to_string()
+    } else {
+        format!("{}/v1/traces", endpoint)
+    }
+}
+
+/// Build a batching OTLP/HTTP tracer and register its provider globally so
+/// `shutdown_tracing` can flush it. Must be called inside a tokio runtime.
+pub fn otlp_tracer(endpoint: &str) -> Result<trace::Tracer> {
+    let provider = opentelemetry_otlp::new_pipeline()
+        .tracing()
+        .with_exporter(
+            opentelemetry_otlp::new_exporter()
+                .http()
+                .with_endpoint(traces_url(endpoint)),
+        )
+        .with_trace_config(
+            trace::Config::default()
+                .with_resource(Resource::new(vec![KeyValue::new("service.name", SERVICE_NAME)])),
+        )
+        .install_batch(runtime::Tokio)?;
+    let tracer = provider.tracer(SERVICE_NAME);
+    opentelemetry::global::set_tracer_provider(provider);
+    Ok(tracer)
+}
+
+/// Flush buffered spans; call before the process exits
+pub fn shutdown_tracing() {
+    opentelemetry::global::shutdown_tracer_provider();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn traces_url_appends_signal_path_once() {
+        assert_eq!(traces_url("http://localhost:4318"), "http://localhost:4318/v1/traces");
+        assert_eq!(traces_url("http://localhost:4318/"), "http://localhost:4318/v1/traces");
+        assert_eq!(traces_url("http://tempo:4318/v1/traces"), "http://tempo:4318/v1/traces");
+    }
+}
@@ -0,0 +1,124 @@
+//! Whether and where metrics are pushed to a Prometheus Pushgateway
+//!
+//! `--push`/`--no-push` win over `SMS_METRICS_PUSH`, which wins over the default for the
+//! deployment environment: development pushes to a local Pushgateway unless told otherwise,
+//! production pushes only to an explicitly configured `SMS_PUSHGATEWAY_URL`.
+
+use anyhow::{bail, Result};
+
+pub const PUSHGATEWAY_URL_ENV: &str = "SMS_PUSHGATEWAY_URL";
+/// `1`/`true` to push, `0`/`false` not to
+pub const PUSH_ENV: &str = "SMS_METRICS_PUSH";
+/// Deployment environment, `development` or `production` (the default)
+pub const DEPLOY_ENV: &str = "SMS_ENV";
+
+/// Pushgateway of the local docker compose stack, the development default
+pub const DEFAULT_PUSHGATEWAY_URL: &str = "http://localhost:9091";
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Environment {
+    Development,
+    #[default]
+    Production,
+}
+
+impl Environment {
+    pub fn parse(value: &str) -> Result<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "development" | "dev" | "local" => Ok(Self::Development),
+            "production" | "prod" => Ok(Self::Production),
+            other => bail!("unknown {} {:?} (expected \"development\" or \"production\")", DEPLOY_ENV, other),
+        }
+    }
+
+    /// Pushgateway used when `SMS_PUSHGATEWAY_URL` isn't set
+    pub fn default_pushgateway_url(self) -> Option<&'static str> {
+        match self {
+            Self::Development => Some(DEFAULT_PUSHGATEWAY_URL),
+            Self::Production => None,
+        }
+    }
+}
+
+/// Resolved push settings; `url` is set exactly when metrics are pushed
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PushConfig {
+    pub environment: Environment,
+    url: Option<String>,
+}
+
+impl PushConfig {
+    pub fn disabled() -> Self {
+        Self::default()
+    }
+
+    /// Push settings from the environment alone, as for processes without the CLI flags
+    pub fn from_env() -> Result<Self> {
+        Self::resolve(None, |name| std::env::var(name).ok())
+    }
+
+    /// Push settings from `--push` (`Some(true)`) or `--no-push` (`Some(false)`) and the
+    /// environment. Asking to push with no Pushgateway to push to is an error rather than a
+    /// silent fallback. `lookup` is `std::env::var(..).ok()` outside tests.
+    pub fn resolve(push_flag: Option<bool>, lookup: impl Fn(&str) -> Option<String>) -> Result<Self> {
+        let set = |name: &str| lookup(name).map(|v| v.trim().to_string()).filter(|v| !v.is_empty());
+        let environment = set(DEPLOY_ENV).map(|v| Environment::parse(&v)).transpose()?.unwrap_or_default();
+        let url = set(PUSHGATEWAY_URL_ENV).or_else(|| environment.default_pushgateway_url().map(str::to_string));
+        let push = match push_flag {
+            Some(push) => push,
+            None => match set(PUSH_ENV).as_deref().map(str::to_ascii_lowercase).as_deref() {
+                None => url.is_some(),
+                Some("1" | "true" | "yes" | "on") => true,
+                Some("0" | "false" | "no" | "off") => false,
+                Some(other) => bail!("invalid {} {:?} (expected true or false)", PUSH_ENV, other),
+            },
+        };
+        if push && url.is_none() {
+            bail!("metrics push requested but {} is not set", PUSHGATEWAY_URL_ENV);
+        }
+        Ok(Self { environment, url: url.filter(|_| push) })
+    }
+
+    /// Pushgateway base URL, when pushing
+    pub fn url(&self) -> Option<&str> {
+        self.url.as_deref()
+    }
+
+    pub fn enabled(&self) -> bool {
+        self.url.is_some()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn resolve(flag: Option<bool>, vars: &[(&str, &str)]) -> Result<PushConfig> {
+        let vars: HashMap<String, String> = vars.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect();
+        PushConfig::resolve(flag, |name| vars.get(name).cloned())
+    }
+
+    #[test]
+    fn flags_override_env_which_overrides_environment_defaults() {
+        // Production pushes only to a configured Pushgateway, never to localhost
+        assert_eq!(resolve(None, &[]).unwrap().url(), None);
+        assert_eq!(resolve(None, &[(PUSHGATEWAY_URL_ENV, "http://pg:9091")]).unwrap().url(), Some("http://pg:9091"));
+        assert!(resolve(Some(true), &[]).is_err());
+        assert!(resolve(None, &[(PUSH_ENV, "1")]).is_err());
+
+        // Development falls back to the local Pushgateway
+        let dev = resolve(None, &[(DEPLOY_ENV, "development")]).unwrap();
+        assert_eq!((dev.environment, dev.url()), (Environment::Development, Some(DEFAULT_PUSHGATEWAY_URL)));
+
+        // An operator's URL is never replaced, and opting out always wins
+        let configured = [(DEPLOY_ENV, "dev"), (PUSHGATEWAY_URL_ENV, "http://pg:9091")];
+        assert_eq!(resolve(None, &configured).unwrap().url(), Some("http://pg:9091"));
+        assert!(!resolve(Some(false), &configured).unwrap().enabled());
+        assert!(!resolve(None, &[(DEPLOY_ENV, "dev"), (PUSH_ENV, "off")]).unwrap().enabled());
+        assert!(resolve(Some(true), &[(PUSHGATEWAY_URL_ENV, "http://pg:9091"), (PUSH_ENV, "0")]).unwrap().enabled());
+
+        assert!(resolve(None, &[(DEPLOY_ENV, "staging")]).is_err());
+        assert!(resolve(None, &[(PUSH_ENV, "maybe"), (PUSHGATEWAY_URL_ENV, "http://pg:9091")]).is_err());
+    }
+}
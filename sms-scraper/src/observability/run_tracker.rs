@@ -0,0 +1,183 @@
+use crate::observability::{logging, metrics};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::future::Future;
+use std::sync::Mutex;
+use std::time::Instant;
+use tracing::Instrument;
+
+/// Time spent in one named stage of a run, summed over every call
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct StageTiming {
+    pub stage: String,
+    pub calls: u64,
+    pub duration_ms: u64,
+}
+
+/// How a run closed, for reports and `--json` output
+#[derive(Debug, Clone, Serialize)]
+pub struct RunSummary {
+    pub run_id: String,
+    pub kind: String,
+    pub source_id: Option<String>,
+    pub started_at: DateTime<Utc>,
+    pub finished_at: DateTime<Utc>,
+    pub duration_ms: u64,
+    pub success: bool,
+    pub error: Option<String>,
+    pub stages: Vec<StageTiming>,
+}
+
+/// Bookkeeping for one run of a CLI command or orchestrator: assigns the run id, records the
+/// start gauge and heartbeat, times each stage, and closes with success or failure. A tracker
+/// dropped without being finished is recorded as abandoned.
+#[derive(Debug)]
+pub struct RunTracker {
+    run_id: String,
+    kind: &'static str,
+    source_id: Option<String>,
+    started_at: DateTime<Utc>,
+    clock: Instant,
+    stages: Mutex<Vec<(String, u64, f64)>>,
+    finished: bool,
+}
+
+impl RunTracker {
+    /// Open a run of `kind` (e.g. `full_pipeline`, `parse`) with a fresh run id
+    pub fn open(kind: &'static str, source_id: Option<&str>) -> Self {
+        Self::with_run_id(kind, source_id, uuid::Uuid::new_v4().to_string())
+    }
+
+    /// Open a run under a run id the caller already handed out
+    pub fn with_run_id(kind: &'static str, source_id: Option<&str>, run_id: String) -> Self {
+        metrics::runs::started(kind);
+        Self {
+            run_id,
+            kind,
+            source_id: source_id.map(str::to_string),
+            started_at: Utc::now(),
+            clock: Instant::now(),
+            stages: Mutex::new(Vec::new()),
+            finished: false,
+        }
+    }
+
+    pub fn run_id(&self) -> &str {
+        &self.run_id
+    }
+
+    pub fn kind(&self) -> &'static str {
+        self.kind
+    }
+
+    pub fn started_at(&self) -> DateTime<Utc> {
+        self.started_at
+    }
+
+    /// `pipeline_run` span carrying the run id, for instrumenting the whole run
+    pub fn span(&self) -> tracing::Span {
+        logging::run_span(&self.run_id, self.source_id.as_deref().unwrap_or(self.kind))
+    }
+
+    /// Run `fut` as stage `stage` of this run: inside a stage span, with its duration added
+    /// to the stage's total
+    pub async fn stage<F: Future>(&self, stage: &'static str, fut: F) -> F::Output {
+        let t = Instant::now();
+        let output = fut.instrument(logging::stage_span(stage)).await;
+        self.record_stage(stage, t.elapsed().as_secs_f64());
+        output
+    }
+
+    /// Add `secs` to the total for `stage`, for work not shaped as a single future
+    pub fn record_stage(&self, stage: &str, secs: f64) {
+        metrics::runs::stage_duration(self.kind, stage, secs);
+        let mut stages = self.stages.lock().unwrap_or_else(|e| e.into_inner());
+        match stages.iter_mut().find(|(name, _, _)| name == stage) {
+            Some((_, calls, total)) => {
+                *calls += 1;
+                *total += secs;
+            }
+            None => stages.push((stage.to_string(), 1, secs)),
+        }
+    }
+
+    /// Per-stage totals so far, in the order stages first ran
+    pub fn stage_timings(&self) -> Vec<StageTiming> {
+        let stages = self.stages.lock().unwrap_or_else(|e| e.into_inner());
+        stages
+            .iter()
+            .map(|(stage, calls, secs)| StageTiming {
+                stage: stage.clone(),
+                calls: *calls,
+                duration_ms: (secs * 1000.0) as u64,
+            })
+            .collect()
+    }
+
+    pub fn succeed(self) -> RunSummary {
+        self.finish(None)
+    }
+
+    pub fn fail(self, error: impl std::fmt::Display) -> RunSummary {
+        self.finish(Some(error.to_string()))
+    }
+
+    /// Close the run from a command's result, passing the result through
+    pub fn close<T, E: std::fmt::Display>(self, result: Result<T, E>) -> Result<T, E> {
+        match &result {
+            Ok(_) => self.succeed(),
+            Err(e) => self.fail(e),
+        };
+        result
+    }
+
+    fn finish(mut self, error: Option<String>) -> RunSummary {
+        self.finished = true;
+        let elapsed = self.clock.elapsed();
+        let success = error.is_none();
+        metrics::runs::completed(self.kind, if success { "success" } else { "failure" }, elapsed.as_secs_f64());
+        RunSummary {
+            run_id: self.run_id.clone(),
+            kind: self.kind.to_string(),
+            source_id: self.source_id.clone(),
+            started_at: self.started_at,
+            finished_at: Utc::now(),
+            duration_ms: elapsed.as_millis() as u64,
+            success,
+            error,
+            stages: self.stage_timings(),
+        }
+    }
+}
+
+impl Drop for RunTracker {
+    fn drop(&mut self) {
+        if !self.finished {
+            metrics::runs::completed(self.kind, "abandoned", self.clock.elapsed().as_secs_f64());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn sums_stage_durations_and_closes_with_the_outcome() {
+        let tracker = RunTracker::open("parse", Some("neumos"));
+        assert_eq!(tracker.stage("parse", async { 7 }).await, 7);
+        tracker.stage("parse", async {}).await;
+        tracker.record_stage("normalize", 0.25);
+
+        let timings = tracker.stage_timings();
+        assert_eq!(timings.iter().map(|t| (t.stage.as_str(), t.calls)).collect::<Vec<_>>(), [("parse", 2), ("normalize", 1)]);
+        assert_eq!(timings[1].duration_ms, 250);
+
+        let run_id = tracker.run_id().to_string();
+        let summary = tracker.fail("normalize failed");
+        assert_eq!(summary.run_id, run_id);
+        assert!(!summary.success && summary.error.as_deref() == Some("normalize failed"));
+        assert_eq!(summary.source_id.as_deref(), Some("neumos"));
+        assert!(summary.finished_at >= summary.started_at);
+    }
+}
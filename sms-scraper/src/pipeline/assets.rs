@@ -0,0 +1,236 @@
+//! Process-wide cache of the registry and rule assets the pipeline reads on startup.
+//!
+//! Each asset is keyed by where it came from and the SHA-256 of its bytes: loading it again
+//! re-reads the file but only re-parses it when the content changed. The cache lives in
+//! memory only, so it saves repeat loads within a long-running process, not a fresh
+//! process's first load. `preload` loads and validates everything up front so a bad file
+//! fails the run before any work starts.
+
+use anyhow::{anyhow, Context, Result};
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use std::any::Any;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::Instant;
+
+use crate::pipeline::processing::classification::{EventClassifier, BUNDLED_RULES, EVENT_TAG_RULES_ENV};
+use crate::pipeline::processing::neighborhoods::{NeighborhoodIndex, BUNDLED_SEATTLE, NEIGHBORHOODS_ENV};
+use crate::registry::source_loader::{SourceConfig, SourceRegistry};
+
+/// Where the pipeline reads source configs from
+pub const SOURCE_REGISTRY_DIR: &str = "registry/sources";
+
+/// How one asset load went, for run reports
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct AssetLoad {
+    /// `source_registry`, `neighborhoods` or `event_tag_rules`
+    pub asset: String,
+    /// File or directory it was read from, or `bundled`
+    pub source: String,
+    /// SHA-256 of the asset's bytes, hex
+    pub checksum: String,
+    pub duration_ms: f64,
+    /// Whether an earlier load with the same checksum was reused instead of parsing again
+    pub cached: bool,
+}
+
+struct CachedAsset {
+    checksum: String,
+    value: Arc<dyn Any + Send + Sync>,
+}
+
+#[derive(Default)]
+struct AssetCache {
+    entries: HashMap<(&'static str, String), CachedAsset>,
+    /// Latest load of each asset, in the order assets were first loaded
+    loads: Vec<AssetLoad>,
+}
+
+fn cache() -> &'static Mutex<AssetCache> {
+    static CACHE: OnceLock<Mutex<AssetCache>> = OnceLock::new();
+    CACHE.get_or_init(Default::default)
+}
+
+/// The parsed `asset` from `source`, reusing the cached value when `bytes` hash the same
+fn load_cached<T, F>(asset: &'static str, source: &str, bytes: &[u8], started: Instant, parse: F) -> Result<Arc<T>>
+where
+    T: Send + Sync + 'static,
+    F: FnOnce() -> Result<T>,
+{
+    let checksum = hex::encode(Sha256::digest(bytes));
+    let key = (asset, source.to_string());
+    let hit = {
+        let cache = cache().lock().unwrap_or_else(|e| e.into_inner());
+        cache
+            .entries
+            .get(&key)
+            .filter(|entry| entry.checksum == checksum)
+            .and_then(|entry| entry.value.clone().downcast::<T>().ok())
+    };
+    let cached = hit.is_some();
+    let value = match hit {
+        Some(value) => value,
+        None => Arc::new(parse().with_context(|| format!("loading {} from {}", asset, source))?),
+    };
+
+    let load = AssetLoad {
+        asset: asset.to_string(),
+        source: source.to_string(),
+        checksum: checksum.clone(),
+        duration_ms: started.elapsed().as_secs_f64() * 1000.0,
+        cached,
+    };
+    let mut cache = cache().lock().unwrap_or_else(|e| e.into_inner());
+    if !cached {
+        cache.entries.insert(key, CachedAsset { checksum, value: value.clone() });
+    }
+    match cache.loads.iter_mut().find(|l| l.asset == load.asset) {
+        Some(existing) => *existing = load,
+        None => cache.loads.push(load),
+    }
+    Ok(value)
+}
+
+/// The latest load of each asset so far in this process
+pub fn recorded_loads() -> Vec<AssetLoad> {
+    cache().lock().unwrap_or_else(|e| e.into_inner()).loads.clone()
+}
+
+/// Every `*.json` source config in `dir`
+pub fn source_registry<P: AsRef<Path>>(dir: P) -> Result<Arc<SourceRegistry>> {
+    let started = Instant::now();
+    let dir = dir.as_ref();
+    let mut paths: Vec<PathBuf> = std::fs::read_dir(dir)
+        .with_context(|| format!("reading registry directory {}", dir.display()))?
+        .map(|entry| entry.map(|e| e.path()))
+        .collect::<std::io::Result<_>>()?;
+    paths.retain(|p| p.extension().and_then(|s| s.to_str()) == Some("json"));
+    paths.sort();
+
+    let mut bytes = Vec::new();
+    let mut files = Vec::with_capacity(paths.len());
+    for path in paths {
+        let raw = std::fs::read(&path).with_context(|| format!("reading source file {}", path.display()))?;
+        bytes.extend_from_slice(path.file_name().map(|n| n.as_encoded_bytes()).unwrap_or_default());
+        bytes.extend_from_slice(&raw);
+        files.push((path, raw));
+    }
+
+    load_cached("source_registry", &dir.display().to_string(), &bytes, started, || {
+        let configs = files
+            .iter()
+            .map(|(path, raw)| {
+                serde_json::from_slice::<SourceConfig>(raw)
+                    .with_context(|| format!("parsing source config {}", path.display()))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        Ok(SourceRegistry::from_configs(configs))
+    })
+}
+
+/// The neighborhoods named by `SMS_NEIGHBORHOODS_GEOJSON`, else the bundled Seattle boundaries
+pub fn neighborhoods() -> Result<Arc<NeighborhoodIndex>> {
+    match std::env::var(NEIGHBORHOODS_ENV) {
+        Ok(path) if !path.trim().is_empty() => neighborhoods_from_path(path.trim()),
+        _ => load_cached("neighborhoods", "bundled", BUNDLED_SEATTLE.as_bytes(), Instant::now(), NeighborhoodIndex::bundled_seattle),
+    }
+}
+
+pub fn neighborhoods_from_path<P: AsRef<Path>>(path: P) -> Result<Arc<NeighborhoodIndex>> {
+    let started = Instant::now();
+    let path = path.as_ref();
+    let raw = std::fs::read_to_string(path)
+        .with_context(|| format!("reading neighborhoods GeoJSON {}", path.display()))?;
+    load_cached("neighborhoods", &path.display().to_string(), raw.as_bytes(), started, || {
+        NeighborhoodIndex::from_geojson_str(&raw)
+    })
+}
+
+/// The rules named by `SMS_EVENT_TAG_RULES`, else the bundled rules; `None` when the
+/// variable is `off`
+pub fn event_classifier() -> Result<Option<Arc<EventClassifier>>> {
+    match std::env::var(EVENT_TAG_RULES_ENV) {
+        Ok(value) if value.trim().eq_ignore_ascii_case("off") => Ok(None),
+        Ok(path) if !path.trim().is_empty() => event_classifier_from_path(path.trim()).map(Some),
+        _ => load_cached("event_tag_rules", "bundled", BUNDLED_RULES.as_bytes(), Instant::now(), EventClassifier::bundled)
+            .map(Some),
+    }
+}
+
+pub fn event_classifier_from_path<P: AsRef<Path>>(path: P) -> Result<Arc<EventClassifier>> {
+    let started = Instant::now();
+    let path = path.as_ref();
+    let raw = std::fs::read_to_string(path)
+        .with_context(|| format!("reading event tag rules {}", path.display()))?;
+    load_cached("event_tag_rules", &path.display().to_string(), raw.as_bytes(), started, || {
+        EventClassifier::from_toml_str(&raw)
+    })
+}
+
+/// Load and validate the source registry, neighborhoods and event tag rules now, so later
+/// loads in this process are cache hits and a bad asset fails before any work starts
+pub fn preload() -> Result<Vec<AssetLoad>> {
+    let registry = source_registry(SOURCE_REGISTRY_DIR)?;
+    if let Some(source_id) = registry
+        .get_enabled_sources()
+        .into_iter()
+        .find(|id| registry.get_source_url(id).is_err())
+    {
+        return Err(anyhow!("enabled source {} in {} has no endpoint", source_id, SOURCE_REGISTRY_DIR));
+    }
+    neighborhoods()?;
+    event_classifier()?;
+    Ok(recorded_loads())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reparses_only_when_the_checksum_changes() {
+        let tmp = tempfile::tempdir().unwrap();
+        let path = tmp.path().join("rules.toml");
+        std::fs::write(&path, "[[rule]]\ntag = \"jazz\"\nkeywords = [\"jazz\"]\n").unwrap();
+
+        let first = event_classifier_from_path(&path).unwrap();
+        let again = event_classifier_from_path(&path).unwrap();
+        assert!(Arc::ptr_eq(&first, &again));
+        let load = recorded_loads().into_iter().find(|l| l.asset == "event_tag_rules").unwrap();
+        assert!(load.cached);
+
+        std::fs::write(&path, "[[rule]]\ntag = \"jazz\"\nkeywords = [\"jazz\", \"bebop\"]\n").unwrap();
+        let changed = event_classifier_from_path(&path).unwrap();
+        assert!(!Arc::ptr_eq(&first, &changed));
+
+        std::fs::write(&path, "[[rule]]\ntag = \"jazz\"\nkeywords = []\n").unwrap();
+        let err = event_classifier_from_path(&path).unwrap_err();
+        assert!(format!("{:#}", err).contains("rule jazz has no keywords"));
+    }
+
+    #[test]
+    fn source_registry_is_read_from_every_json_file() {
+        let tmp = tempfile::tempdir().unwrap();
+        let config = |id: &str| {
+            serde_json::json!({
+                "source_id": id,
+                "enabled": true,
+                "endpoints": [{ "url": format!("https://{}.example", id), "method": "GET" }],
+                "parse_plan_ref": null,
+                "pipeline": null
+            })
+            .to_string()
+        };
+        std::fs::write(tmp.path().join("a.json"), config("a")).unwrap();
+        std::fs::write(tmp.path().join("b.json"), config("b")).unwrap();
+        std::fs::write(tmp.path().join("notes.txt"), "ignored").unwrap();
+
+        let registry = source_registry(tmp.path()).unwrap();
+        let mut enabled = registry.get_enabled_sources();
+        enabled.sort();
+        assert_eq!(enabled, ["a", "b"]);
+        assert!(Arc::ptr_eq(&registry, &source_registry(tmp.path()).unwrap()));
+    }
+}
@@ -1,10 +1,53 @@
 use anyhow::Result;
-use std::sync::Arc;
-use tracing::{info, error, debug};
-use sms_core::storage::{Storage, DatabaseStorage};
-use sms_core::domain::{RawData, Event, Venue, Artist};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, MutexGuard};
+use tracing::{info, error, debug, warn, Instrument};
+use sms_core::storage::{allocate_artist, canonical_slug, DatabaseStorage, InMemoryStorage, SlugAllocation, Storage, WriteBatch};
+use sms_core::domain::{RawData, Event, EventPrice, AgeRestriction, Venue, Artist, Attribution, ExternalId, ProcessRecord, ProcessRun};
 use crate::registry::source_loader::SourceRegistry;
 use crate::pipeline::steps::PipelineStep;
+use crate::app::parse_use_case::{LlmBudget, LlmFallback};
+use crate::app::ports::{DeadLetterEntry, DeadLetterPort, LlmParserPort};
+use crate::infra::dead_letter_store::FileDeadLetterStore;
+use crate::infra::llm_parser::CompletionLlmParser;
+use crate::pipeline::ingestion::delta::{self, DeltaStore, FetchDiff};
+use crate::pipeline::ingestion::envelope_state::{EnvelopeState, EnvelopeStates};
+use crate::pipeline::ingestion::ingest_common::IngestOptions;
+use crate::pipeline::ingestion::ingest_meta::{MetaStore, RunReportEntry, MAX_RUN_REPORT_ERRORS};
+use crate::pipeline::ingestion::registry_watch::{self, RegistrySnapshot};
+use crate::observability::RunTracker;
+use crate::app::ports::NotificationPort;
+use crate::infra::webhook_notifier::{LogOnlyNotifier, WebhookNotifier};
+use crate::pipeline::ingestion::site_watchdog::SiteChangeWatchdog;
+use crate::pipeline::ingestion::schema_drift::{PayloadFingerprint, SchemaDriftDetector};
+use crate::pipeline::processing::conflation::{ConflatedRecord, Conflator, ConflatorConfig, DefaultConflator, EntityType, ResolutionDecision};
+use crate::pipeline::processing::enrich::{EnrichedRecord, EnrichmentMetadata, GeoProperties, PopulationDensity, ReferenceVersions};
+use crate::pipeline::processing::resolution_index::ResolutionIndex;
+use crate::pipeline::processing::catalog::idempotency::{catalog_key, CatalogKeyIndex};
+use crate::pipeline::processing::venue_resolution::{bind_provisional_venues, CatalogedVenues};
+use crate::pipeline::processing::artist_enrichment::enrich_headliner;
+use crate::pipeline::processing::classification::EventClassifier;
+use crate::pipeline::processing::admission::{parse_accessibility_notes, parse_age_restriction};
+use crate::pipeline::processing::price::parse_price;
+use crate::pipeline::processing::duplicate_suppression::{suppress_duplicate, SuppressedDuplicate};
+use crate::pipeline::processing::normalize::{NormalizationMetadata, NormalizedEntity, NormalizedRecord, RecordProvenance};
+use crate::pipeline::processing::normalize::normalizers::base::NormalizerUtils;
+use crate::pipeline::processing::quality_gate::{
+    DefaultQualityGate, MetricsQualityGate, QualityAssessedRecord, QualityAssessment, QualityDecision, QualityGate, QualityIssue,
+    QualityIssueType, QualitySeverity,
+};
+use crate::app::ports::{NormalizeOutputPort, QualityGateOutputPort};
+use crate::infra::sink_registry::{FanOut, SinkRegistry};
+use crate::pipeline::processing::quality_gate::outcomes::QualityOutcomeStore;
+use crate::pipeline::processing::catalog::provenance::{ConflationLineage, LineageStore, RecordLineage};
+use crate::pipeline::processing::quality_gate::shadow::{ShadowGateReport, ShadowQualityGate};
+use crate::pipeline::runner::{ReprocessOptions, ReprocessProgress, RunOptions};
+use crate::pipeline::streaming::{run_stage, stage_channel};
+use crate::pipeline::processing::recurrence::link_recurring_series;
+use crate::pipeline::assets;
+use sms_parsers::{ParsedRecord, RecordChange};
+use std::collections::{BTreeSet, HashMap};
+use std::sync::atomic::{AtomicUsize, Ordering};
 
 /// Orchestrator for running the complete data processing pipeline
 /// 
@@ -13,33 +56,322 @@ use crate::pipeline::steps::PipelineStep;
 pub struct FullPipelineOrchestrator {
     storage: Arc<dyn Storage>,
     source_registry: SourceRegistry,
+    /// Tags events from keyword rules; `None` when tagging is switched off
+    classifier: Option<Arc<EventClassifier>>,
+    /// Where run reports and the site change watchdog's parse counts go
+    meta: MetaStore,
+    /// Specs gateway ingestion fetches with; `None` uses the shared registry snapshot
+    source_specs: Option<Arc<RegistrySnapshot>>,
+    /// Reads events off payloads their parser found none in; off unless configured
+    llm_fallback: Option<LlmFallback>,
 }
 
 impl FullPipelineOrchestrator {
-    /// Create a new pipeline orchestrator
-    pub async fn new() -> Result<Self> {
-        let storage = Arc::new(DatabaseStorage::new().await?);
-        let source_registry = SourceRegistry::load_from_directory("registry/sources")?;
-        Ok(Self { storage, source_registry })
+    /// Create a new pipeline orchestrator whose CAS, ingest log and run bookkeeping live
+    /// under `data_root`
+    pub async fn new(data_root: impl Into<PathBuf>) -> Result<Self> {
+        let storage: Arc<dyn Storage> = Arc::new(DatabaseStorage::new().await?);
+        #[cfg(feature = "chaos")]
+        let storage = crate::infra::fault_injection::FaultyStorage::wrap(storage);
+        let orchestrator = Self::with_storage(storage, MetaStore::at_root(data_root))?;
+        // Experimental: only with SMS_LLM_FALLBACK=1 and an endpoint configured
+        Ok(match CompletionLlmParser::from_env() {
+            Some(llm) => {
+                info!("LLM fallback enabled for payloads that parse to no events");
+                orchestrator.with_llm_fallback(Box::new(llm), LlmBudget::from_env())
+            }
+            None => orchestrator,
+        })
+    }
+
+    /// Run everything in memory: entities in `InMemoryStorage` and run bookkeeping in a
+    /// private meta store, so no database, env vars or `data/` writes are needed. The crawl
+    /// stores payloads through `Storage` as raw data, so no CAS or ingest log is involved.
+    pub fn in_memory() -> Result<Self> {
+        Self::with_storage(Arc::new(InMemoryStorage::new()), MetaStore::in_memory()?)
+    }
+
+    pub fn with_storage(storage: Arc<dyn Storage>, meta: MetaStore) -> Result<Self> {
+        let source_registry = SourceRegistry::clone(&*assets::source_registry(assets::SOURCE_REGISTRY_DIR)?);
+        Self::with_registry(storage, meta, source_registry)
+    }
+
+    /// Like `with_storage`, with a source registry the caller already loaded instead of the
+    /// one in `registry/sources`
+    pub fn with_registry(storage: Arc<dyn Storage>, meta: MetaStore, source_registry: SourceRegistry) -> Result<Self> {
+        let classifier = assets::event_classifier()?;
+        Ok(Self { storage, source_registry, classifier, meta, source_specs: None, llm_fallback: None })
+    }
+
+    /// Ingest with the specs in `specs` instead of the shared registry snapshot
+    pub fn with_source_specs(mut self, specs: Arc<RegistrySnapshot>) -> Self {
+        self.source_specs = Some(specs);
+        self
+    }
+
+    /// When a payload's parser fails on it or finds no events in it, ask `parser` to read
+    /// them from the page's text, spending at most what `budget` allows across every run
+    pub fn with_llm_fallback(mut self, parser: Box<dyn LlmParserPort>, budget: LlmBudget) -> Self {
+        self.llm_fallback = Some(LlmFallback { parser, budget });
+        self
     }
 
     /// Process all unprocessed raw data for a given source through the complete pipeline
     pub async fn process_source(&self, source_id: &str) -> Result<ProcessingResult> {
+        let tracker = RunTracker::open("full_pipeline", Some(source_id));
+        let result = self
+            .process_source_tracked(source_id, &tracker, &RunOptions::default())
+            .await;
+        tracker.close(result)
+    }
+
+    /// Same as `process_source`, but as part of a run the caller tracks (its run id goes into
+    /// logs and the run report, and stage timings land on the tracker), with caller-chosen
+    /// conflation settings, duplicate suppression, stage concurrency and shadow quality gate
+    pub async fn process_source_tracked(
+        &self,
+        source_id: &str,
+        tracker: &RunTracker,
+        options: &RunOptions,
+    ) -> Result<ProcessingResult> {
+        let result = self
+            .process_source_stages(source_id, tracker, options)
+            .instrument(tracker.span())
+            .await;
+        self.record_run_report(tracker, source_id, &result);
+        let result = result?;
+        if result.total_items > 0 {
+            self.watch_for_site_change(&result).await;
+        }
+        Ok(result)
+    }
+
+    /// Run parse → catalog again over a source's stored raw data, processed or not, in
+    /// batches of `reprocess.batch_size`. Catalog writes find and update the entities earlier
+    /// runs created from the same listings, so reprocessing twice doesn't duplicate anything.
+    pub async fn reprocess_source_tracked(
+        &self,
+        source_id: &str,
+        tracker: &RunTracker,
+        options: &RunOptions,
+        reprocess: &ReprocessOptions,
+        on_batch: impl FnMut(&ReprocessProgress),
+    ) -> Result<ProcessingResult> {
+        let result = self
+            .reprocess_stages(source_id, tracker, options, reprocess, on_batch)
+            .instrument(tracker.span())
+            .await;
+        self.record_run_report(tracker, source_id, &result);
+        result
+    }
+
+    async fn reprocess_stages(
+        &self,
+        source_id: &str,
+        tracker: &RunTracker,
+        options: &RunOptions,
+        reprocess: &ReprocessOptions,
+        mut on_batch: impl FnMut(&ReprocessProgress),
+    ) -> Result<ProcessingResult> {
+        let internal_api_name = crate::common::constants::api_name_to_internal(source_id);
+        let mut rows = self.storage.get_unprocessed_raw_data(&internal_api_name, None).await?;
+        rows.extend(self.storage.get_processed_raw_data(&internal_api_name, None).await?);
+        let rows = reprocess.select(rows);
+        info!("🔁 Reprocessing {} raw data items for {}", rows.len(), source_id);
+
+        let run = self.run_context(source_id, tracker, options).await?;
+        let mut result = ProcessingResult {
+            source_id: source_id.to_string(),
+            total_items: rows.len(),
+            processed_items: 0,
+            failed_items: 0,
+            records_parsed: 0,
+            records_cataloged: 0,
+            suppressed_duplicates: Vec::new(),
+            errors: Vec::new(),
+            quality_shadow: None,
+            notes: Vec::new(),
+        };
+        let mut venues = BTreeSet::new();
+        let batch_size = reprocess.batch_size.max(1);
+        let batches = rows.len().div_ceil(batch_size);
+        for (i, batch) in rows.chunks(batch_size).enumerate() {
+            self.process_batch(batch, &run, &mut result, &mut venues).await;
+            let progress = ReprocessProgress {
+                batch: i + 1,
+                batches,
+                items_done: (i * batch_size + batch.len()).min(rows.len()),
+                items_total: rows.len(),
+                processed_items: result.processed_items,
+                failed_items: result.failed_items,
+                records_cataloged: result.records_cataloged,
+            };
+            info!(
+                "🔁 Batch {}/{}: {}/{} items, {} failed, {} records cataloged",
+                progress.batch, progress.batches, progress.items_done, progress.items_total,
+                progress.failed_items, progress.records_cataloged
+            );
+            on_batch(&progress);
+        }
+        self.finish_items(venues, &run, &mut result).await;
+
+        info!("✅ Reprocessing completed for {}: {} processed, {} failed",
+              source_id, result.processed_items, result.failed_items);
+        Ok(result)
+    }
+
+    /// Alert when a source's parsed record count collapses, which usually means the site changed
+    async fn watch_for_site_change(&self, result: &ProcessingResult) {
+        let notifier: Box<dyn NotificationPort> = match WebhookNotifier::from_env() {
+            Some(webhook) => Box::new(webhook),
+            None => Box::new(LogOnlyNotifier),
+        };
+        let watchdog = SiteChangeWatchdog::with_store(self.meta.clone(), notifier);
+        if let Err(e) = watchdog.observe(&result.source_id, result.records_parsed as u64).await {
+            error!("Site change watchdog failed for {}: {}", result.source_id, e);
+        }
+    }
+
+    /// Compare the structure of this run's payloads with the source's previous run and return
+    /// a note when it drifted, before a redesign shows up as a parser failure
+    fn watch_for_schema_drift(&self, source_id: &str, raw_data_items: &[RawData]) -> Option<String> {
+        let mut fingerprint = PayloadFingerprint::default();
+        for raw_data in raw_data_items {
+            fingerprint.add_payload(&raw_data.data);
+        }
+        match SchemaDriftDetector::with_store(self.meta.clone()).observe(source_id, &fingerprint) {
+            Ok(drift) => drift.map(|drift| drift.note()),
+            Err(e) => {
+                error!("Schema drift detection failed for {}: {}", source_id, e);
+                None
+            }
+        }
+    }
+
+    /// Detect recurring series at the venues a run cataloged events for
+    async fn link_series_for_venues(&self, venue_ids: &BTreeSet<Uuid>) -> Result<()> {
+        let venue_ids: Vec<Uuid> = venue_ids.iter().copied().collect();
+        let report = link_recurring_series(&*self.storage, &venue_ids).await?;
+        if report.series > 0 {
+            info!("🔁 {} recurring series, {} events newly linked", report.series, report.events_linked);
+        }
+        Ok(())
+    }
+
+    /// Persist a run report so crawl status and run history can show what the run did
+    fn record_run_report(&self, tracker: &RunTracker, source_id: &str, result: &Result<ProcessingResult>) {
+        let mut report = RunReportEntry {
+            run_id: tracker.run_id().to_string(),
+            source_id: source_id.to_string(),
+            started_at: tracker.started_at().timestamp(),
+            finished_at: chrono::Utc::now().timestamp(),
+            stages: tracker.stage_timings(),
+            ..Default::default()
+        };
+        match result {
+            Ok(result) => {
+                report.items_total = result.total_items as u64;
+                report.records_parsed = result.records_parsed as u64;
+                report.records_cataloged = result.records_cataloged as u64;
+                report.records_failed = result.failed_items as u64;
+                report.duplicates_suppressed = result.suppressed_duplicates.len() as u64;
+                report.errors = result.errors.iter().take(MAX_RUN_REPORT_ERRORS).cloned().collect();
+                report.notes = result.notes.clone();
+            }
+            Err(e) => report.error = Some(e.to_string()),
+        }
+        if let Err(e) = self.meta.with(|meta| meta.put_run_report(&report)) {
+            error!("Failed to record run report for {}: {}", source_id, e);
+        }
+    }
+
+    /// Per-run state shared by every batch of the run
+    async fn run_context<'a>(&self, source_id: &str, tracker: &'a RunTracker, options: &'a RunOptions) -> Result<RunContext<'a>> {
+        let outputs = match &options.sinks {
+            Some(config) => {
+                let data_root = self.meta.data_root().map(Path::to_path_buf).unwrap_or_else(|| PathBuf::from("data"));
+                let sinks = SinkRegistry::new(config.clone(), "output", data_root);
+                let (accepted, quarantined) = sinks.quality_gate_outputs()?;
+                let normalized = sinks.normalize_output(&format!("full_pipeline_{}_normalized.ndjson", tracker.run_id()))?;
+                Some(StageOutputs { normalized, accepted, quarantined })
+            }
+            None => None,
+        };
+        // Decisions are kept beside the run's other bookkeeping; in-memory runs keep none
+        let outcomes = self.meta.data_root().map(QualityOutcomeStore::open_at_root).transpose()?;
+        let lineage = self.meta.data_root().map(LineageStore::open_at_root).transpose()?;
+        let deltas = self.meta.data_root().map(DeltaStore::open_at_root).transpose()?.map(RunDeltas::new);
+        let envelope_states = self.meta.data_root().map(EnvelopeStates::new);
+        // Canonical ids are kept from run to run beside the other bookkeeping
+        let mut conflator = DefaultConflator::new().with_config(options.conflator.clone());
+        if let Some(root) = self.meta.data_root() {
+            conflator = conflator.with_resolution_index(Arc::new(ResolutionIndex::open_at_root(root)?));
+        }
+        // Venues cataloged before, so listings of them match them within the venue radius
+        let cataloged: Vec<Venue> = self.storage.get_all_venues(None, None).await?.into_iter().filter(|v| !v.provisional).collect();
+        for venue in &cataloged {
+            if let Some(id) = venue.id {
+                conflator.remember_cataloged(venue_record(venue.clone(), cataloged_provenance(id)), id);
+            }
+        }
+        let catalog_keys = self.meta.data_root().map(CatalogKeyIndex::open_at_root).transpose()?;
+        let process_run = match Uuid::parse_str(tracker.run_id()) {
+            Ok(id) => Some(match self.storage.get_process_run(id).await? {
+                Some(run) => run,
+                None => {
+                    let mut run = ProcessRun {
+                        id: Some(id),
+                        name: format!("full_pipeline {}", source_id),
+                        created_at: tracker.started_at(),
+                        finished_at: None,
+                    };
+                    self.storage.create_process_run(&mut run).await?;
+                    run
+                }
+            }),
+            Err(_) => None,
+        };
+        let dead_letters = self.meta.data_root().map(|root| RunDeadLetters {
+            store: FileDeadLetterStore::new(root),
+            parse_plan: self.parse_plan(source_id),
+        });
+        Ok(RunContext {
+            source_id: source_id.to_string(),
+            tracker,
+            options,
+            attribution: self.source_registry.get_attribution(source_id),
+            active_gate: MetricsQualityGate::new(DefaultQualityGate { config: options.quality_gate.clone() }),
+            shadow_gate: options.quality_shadow.clone().map(ShadowQualityGate::new),
+            outcomes,
+            lineage,
+            deltas,
+            envelope_states,
+            dead_letters,
+            conflator: Mutex::new(conflator),
+            venues: CatalogedVenues::with_venues(cataloged),
+            catalog_keys,
+            process_run,
+            outputs,
+        })
+    }
+
+    async fn process_source_stages(
+        &self,
+        source_id: &str,
+        tracker: &RunTracker,
+        options: &RunOptions,
+    ) -> Result<ProcessingResult> {
         info!("🔄 Starting full pipeline processing for source: {}", source_id);
 
-        // Check if bypass-cadence is set via environment variable to force fresh ingestion
-        let force_fresh_ingestion = std::env::var("BYPASS_CADENCE").is_ok() || 
-                                   std::env::var("FORCE_FRESH_INGESTION").is_ok();
-        
-        info!("🔍 Force fresh ingestion: {}", force_fresh_ingestion);
-        info!("🔍 BYPASS_CADENCE env var: {:?}", std::env::var("BYPASS_CADENCE"));
-        info!("🔍 FORCE_FRESH_INGESTION env var: {:?}", std::env::var("FORCE_FRESH_INGESTION"));
+        // Bypassing cadence fetches fresh data even when unprocessed raw data is waiting
+        let force_fresh_ingestion = options.bypass_cadence;
 
         // Get all unprocessed raw data for this source
         // Convert user-friendly source_id to internal API name for database lookup
         let internal_api_name = crate::common::constants::api_name_to_internal(source_id);
         let mut raw_data_items = self.storage.get_unprocessed_raw_data(&internal_api_name, None).await?;
-        let mut just_ingested_fresh_data = false;
+        // When ingestion ran, the time it started
+        let mut just_ingested_fresh_data = None;
         
         if raw_data_items.is_empty() || force_fresh_ingestion {
             if force_fresh_ingestion {
@@ -50,12 +382,13 @@ impl FullPipelineOrchestrator {
             }
             
             // Run ingestion to fetch fresh data
-            match self.run_ingestion_for_source(source_id).await {
+            let ingestion_started = chrono::Utc::now();
+            match tracker.stage("ingestion", self.run_ingestion_for_source(source_id, options.bypass_cadence)).await {
                 Ok(_) => {
                     info!("✅ Ingestion completed, checking for new raw data...");
                     // Get the newly ingested raw data
                     raw_data_items = self.storage.get_unprocessed_raw_data(&internal_api_name, None).await?;
-                    just_ingested_fresh_data = true;
+                    just_ingested_fresh_data = Some(ingestion_started);
                     
                     if raw_data_items.is_empty() {
                         info!("⚠️  No raw data found even after ingestion - source may be empty or have issues");
@@ -64,7 +397,12 @@ impl FullPipelineOrchestrator {
                             total_items: 0,
                             processed_items: 0,
                             failed_items: 0,
+                            records_parsed: 0,
+                        records_cataloged: 0,
+                            suppressed_duplicates: Vec::new(),
                             errors: vec!["No data available after ingestion".to_string()],
+                        quality_shadow: None,
+                        notes: Vec::new(),
                         });
                     }
                 }
@@ -75,36 +413,78 @@ impl FullPipelineOrchestrator {
                         total_items: 0,
                         processed_items: 0,
                         failed_items: 1,
+                        records_parsed: 0,
+                        records_cataloged: 0,
+                        suppressed_duplicates: Vec::new(),
                         errors: vec![format!("Ingestion failed: {}", e)],
+                        quality_shadow: None,
+                        notes: Vec::new(),
                     });
                 }
             }
         }
 
-        // If we just ingested fresh data, only process the most recent item (the fresh HTML)
-        // to avoid processing old cached items without wix-warmup-data
-        if just_ingested_fresh_data && raw_data_items.len() > 1 {
-            info!("🔄 Just ingested fresh data - processing only the most recent item to avoid old cached data");
-            // Sort by created_at descending and take only the first (most recent) item
-            raw_data_items.sort_by(|a, b| b.created_at.cmp(&a.created_at));
-            raw_data_items.truncate(1);
+        // If we just ingested fresh data, only process what this ingestion stored (one row per
+        // endpoint, envelope page or message) to avoid processing old cached items without
+        // wix-warmup-data
+        if let Some(started) = just_ingested_fresh_data.filter(|_| raw_data_items.len() > 1) {
+            info!("🔄 Just ingested fresh data - processing only the items it stored to avoid old cached data");
+            raw_data_items.retain(|item| item.created_at >= started);
         }
 
         info!("📊 Found {} unprocessed raw data items for {}", raw_data_items.len(), source_id);
 
+        let run = self.run_context(source_id, tracker, options).await?;
         let mut result = ProcessingResult {
             source_id: source_id.to_string(),
             total_items: raw_data_items.len(),
             processed_items: 0,
             failed_items: 0,
+            records_parsed: 0,
+            records_cataloged: 0,
+            suppressed_duplicates: Vec::new(),
             errors: Vec::new(),
+            quality_shadow: None,
+            notes: Vec::new(),
         };
 
-        // For now, we'll process by creating entities directly from the structured data
-        // The ingester already did the parsing work by extracting meaningful data from raw sources
-        for raw_data in &raw_data_items {
-            match self.process_raw_data_item(raw_data).await {
-                Ok(()) => {
+        result.notes.extend(self.watch_for_schema_drift(source_id, &raw_data_items));
+
+        let mut venues = BTreeSet::new();
+        self.process_batch(&raw_data_items, &run, &mut result, &mut venues).await;
+        self.finish_items(venues, &run, &mut result).await;
+
+        info!("✅ Pipeline processing completed for {}: {} processed, {} failed", 
+              source_id, result.processed_items, result.failed_items);
+
+        Ok(result)
+    }
+
+    /// Run `raw_data_items` through parse → catalog, marking each item that made it through as
+    /// processed and adding its counts, errors and venues to `result` and `venues`
+    async fn process_batch(
+        &self,
+        raw_data_items: &[RawData],
+        run: &RunContext<'_>,
+        result: &mut ProcessingResult,
+        venues: &mut BTreeSet<Uuid>,
+    ) {
+        let outcomes = self.process_items_streaming(raw_data_items, run).await;
+        for (raw_data, outcome) in raw_data_items.iter().zip(outcomes) {
+            match outcome {
+                Ok(outcome) => {
+                    if outcome.parsed > 0 && outcome.quarantined == outcome.parsed {
+                        let detail = format!("quality gate quarantined all {} events", outcome.parsed);
+                        run.record_state(raw_data, EnvelopeState::Quarantined, Some(&detail));
+                    } else if outcome.parsed == 0 {
+                        run.record_state(raw_data, EnvelopeState::Cataloged, Some("no events"));
+                    } else {
+                        run.record_state(raw_data, EnvelopeState::Cataloged, None);
+                    }
+                    venues.extend(outcome.venues);
+                    result.records_parsed += outcome.parsed;
+                    result.records_cataloged += outcome.cataloged;
+                    result.suppressed_duplicates.extend(outcome.suppressed);
                     // Mark as processed
                     if let Some(id) = raw_data.id {
                         if let Err(e) = self.storage.mark_raw_data_processed(id).await {
@@ -116,6 +496,7 @@ impl FullPipelineOrchestrator {
                     }
                 }
                 Err(e) => {
+                    run.record_state(raw_data, EnvelopeState::Failed, Some(&e));
                     error!("Failed to process raw data item {}: {}", 
                         raw_data.id.map(|id| id.to_string()).unwrap_or("unknown".to_string()), e);
                     result.failed_items += 1;
@@ -123,54 +504,231 @@ impl FullPipelineOrchestrator {
                 }
             }
         }
+    }
 
-        info!("✅ Pipeline processing completed for {}: {} processed, {} failed", 
-              source_id, result.processed_items, result.failed_items);
+    /// Run-wide steps once every item is through: venue binding, series detection, the
+    /// shadow quality gate report, the fetch deltas and closing the process run
+    async fn finish_items(
+        &self,
+        venues: BTreeSet<Uuid>,
+        run: &RunContext<'_>,
+        result: &mut ProcessingResult,
+    ) {
+        // Venues cataloged by this run may be the real home of events parked on placeholders
+        if let Err(e) = bind_provisional_venues(&*self.storage).await {
+            error!("Failed to bind provisional venues: {}", e);
+            result.errors.push(format!("Venue resolution failed: {}", e));
+        }
 
-        Ok(result)
+        // Weekly open mics and trivia nights become series linked to their instances
+        if let Err(e) = self.link_series_for_venues(&venues).await {
+            error!("Failed to detect recurring series: {}", e);
+            result.errors.push(format!("Recurrence detection failed: {}", e));
+        }
+
+        if let Some(shadow) = &run.shadow_gate {
+            let report = shadow.report();
+            info!(
+                "🫥 Shadow quality gate {}: {:.1}% quarantined vs {:.1}% active ({} newly quarantined, {} newly accepted)",
+                report.candidate_rule_version,
+                report.candidate_quarantine_rate() * 100.0,
+                report.active_quarantine_rate() * 100.0,
+                report.newly_quarantined,
+                report.newly_accepted
+            );
+            result.quality_shadow = Some(report);
+        }
+
+        if let Some(deltas) = &run.deltas {
+            deltas.finish(result);
+        }
+
+        if let Some(process_run) = &run.process_run {
+            let finished = ProcessRun { finished_at: Some(chrono::Utc::now()), ..process_run.clone() };
+            if let Err(e) = self.storage.update_process_run(&finished).await {
+                error!("Failed to finish process run {}: {}", run.tracker.run_id(), e);
+            }
+        }
     }
 
-    /// Process a single raw data item through the complete pipeline stages
-    async fn process_raw_data_item(&self, raw_data: &RawData) -> Result<()> {
-        debug!("Processing raw data item: {} ({})", raw_data.event_name, raw_data.api_name);
-        
-        // Step 1: Parse - Convert raw HTML/JSON to structured events
-        info!("📄 Step 1: Parse");
-        let parsed_events = self.parse_raw_data(raw_data).await?;
-        
-        info!("✅ Parsed {} events from raw data", parsed_events.len());
-        
-        // Process each parsed event through the pipeline
-        for parsed_data in parsed_events {
-            info!("🔄 Processing event: {}", parsed_data.event_args.title);
-            
-            // Step 2: Normalize - Standardize data format
-            info!("📝 Step 2: Normalize");
-            let normalized_data = self.normalize_parsed_data(&parsed_data).await?;
-            
-            // Step 3: Quality Gate - Check data quality and completeness
-            info!("✅ Step 3: Quality Gate");
-            let quality_result = self.quality_gate_check(&normalized_data).await?;
-            if !quality_result.passed {
-                info!("❌ Quality gate failed for {}: {}", normalized_data.title, quality_result.reason);
-                continue; // Skip this event, continue with next
+    /// Stream the raw data items through parse → normalize → quality gate → enrich → conflate
+    /// → catalog. Each stage runs its own workers over a bounded channel, so a slow stage only
+    /// backs up the stages before it; catalog runs on this task, one event at a time.
+    async fn process_items_streaming(
+        &self,
+        raw_data_items: &[RawData],
+        run: &RunContext<'_>,
+    ) -> Vec<Result<ItemOutcome, String>> {
+        let RunContext { tracker, options, .. } = run;
+        let RunOptions { concurrency, .. } = options;
+        let capacity = concurrency.channel_capacity;
+        let (raw_tx, raw_rx) = stage_channel::<&RawData>(capacity);
+        let (parsed_tx, parsed_rx) = stage_channel(capacity);
+        let (normalized_tx, normalized_rx) = stage_channel(capacity);
+        let (passed_tx, passed_rx) = stage_channel(capacity);
+        let (enriched_tx, enriched_rx) = stage_channel(capacity);
+        let (conflated_tx, mut conflated_rx) = stage_channel::<ConflatedEventData>(capacity);
+        let parsed_counts: Vec<AtomicUsize> = raw_data_items.iter().map(|_| AtomicUsize::new(0)).collect();
+        let normalized_counts: Vec<AtomicUsize> = raw_data_items.iter().map(|_| AtomicUsize::new(0)).collect();
+        let quarantined_counts: Vec<AtomicUsize> = raw_data_items.iter().map(|_| AtomicUsize::new(0)).collect();
+
+        let feed = async move {
+            for (item, raw_data) in raw_data_items.iter().enumerate() {
+                if raw_tx.send((item, Ok(raw_data))).await.is_err() {
+                    break;
+                }
+            }
+        };
+        let parse = run_stage(concurrency.parse, raw_rx, parsed_tx, |item, raw_data: &RawData| {
+            let parsed_counts = &parsed_counts;
+            async move {
+                let raw_data_id = raw_data.id.map(|id| id.to_string()).unwrap_or_default();
+                debug!("Processing raw data item: {} ({})", raw_data.event_name, raw_data.api_name);
+                let mut parsed = match tracker
+                    .stage("parse", self.parse_raw_data(raw_data))
+                    .instrument(tracing::info_span!("raw_data", raw_data_id = %raw_data_id))
+                    .await
+                {
+                    Ok(parsed) => parsed,
+                    Err(e) => {
+                        run.dead_letter(raw_data, &e.to_string()).await;
+                        return Err(e);
+                    }
+                };
+                if let Some(deltas) = &run.deltas {
+                    if let Err(e) = deltas.observe(&run.source_id, raw_data, &mut parsed) {
+                        warn!("Failed to diff raw data {} against the previous fetch: {}", raw_data_id, e);
+                    }
+                }
+                info!("✅ Parsed {} events from raw data", parsed.len());
+                run.record_state(raw_data, EnvelopeState::Parsed, None);
+                parsed_counts[item].fetch_add(parsed.len(), Ordering::Relaxed);
+                Ok(parsed)
+            }
+        });
+        let normalize = run_stage(concurrency.normalize, parsed_rx, normalized_tx, |item, parsed: ParsedEventData| {
+            let normalized_counts = &normalized_counts;
+            async move {
+                debug!("📝 Normalize: {}", parsed.event_args.title);
+                let normalized = tracker.stage("normalize", self.normalize_parsed_data(&parsed)).await?;
+                // The envelope moves on with its first normalized event
+                if normalized_counts[item].fetch_add(1, Ordering::Relaxed) == 0 {
+                    run.record_state(&raw_data_items[item], EnvelopeState::Normalized, None);
+                }
+                Ok(vec![normalized])
+            }
+        });
+        let quality_gate = run_stage(concurrency.quality_gate, normalized_rx, passed_tx, |item, normalized: NormalizedEventData| {
+            let quarantined_counts = &quarantined_counts;
+            async move {
+                let record = gate_record(&normalized, &run.source_id, &raw_data_items[item]);
+                let mut assessed = tracker.stage("quality_gate", async { run.active_gate.assess(&record) }).await?;
+                if normalized.venue_name.trim().is_empty() {
+                    // Nothing to catalog the event under
+                    assessed.quality_assessment.decision = QualityDecision::Quarantine;
+                    assessed.quality_assessment.issues.push(QualityIssue {
+                        issue_type: QualityIssueType::MissingData,
+                        severity: QualitySeverity::Critical,
+                        description: "Venue name is missing".to_string(),
+                        field: Some("venue_name".to_string()),
+                        suggestion: None,
+                    });
+                }
+                if let Some(outcomes) = &run.outcomes {
+                    if let Err(e) = outcomes.record(&assessed) {
+                        error!("Failed to record quality outcome for {}: {}", normalized.title, e);
+                    }
+                }
+                if let Some(outputs) = &run.outputs {
+                    outputs.write(&record, &assessed).await;
+                }
+                let decision = &assessed.quality_assessment.decision;
+                if let Some(shadow) = &run.shadow_gate {
+                    shadow.compare(&record, decision);
+                }
+                if *decision == QualityDecision::Quarantine {
+                    let reasons: Vec<&str> =
+                        assessed.quality_assessment.issues.iter().map(|i| i.description.as_str()).collect();
+                    info!("❌ Quality gate quarantined {}: {}", normalized.title, reasons.join("; "));
+                    quarantined_counts[item].fetch_add(1, Ordering::Relaxed);
+                    return Ok(Vec::new());
+                }
+                Ok(vec![(normalized, assessed)])
+            }
+        });
+        let enrich = run_stage(concurrency.enrich, passed_rx, enriched_tx, |_, (normalized, assessed): (NormalizedEventData, QualityAssessedRecord)| async move {
+            let enriched = tracker.stage("enrich", self.enrich_data(&normalized, assessed)).await?;
+            // Tag genre/category from keyword rules (when enabled)
+            Ok(vec![tracker.stage("classify", self.classify_event(enriched)).await?])
+        });
+        let conflate = run_stage(concurrency.conflate, enriched_rx, conflated_tx, |_, enriched: EnrichedEventData| async move {
+            Ok(vec![tracker.stage("conflation", self.conflate_entities(enriched, run)).await?])
+        });
+        let catalog = async {
+            let mut outcomes: Vec<Result<ItemOutcome, String>> =
+                raw_data_items.iter().map(|_| Ok(ItemOutcome::default())).collect();
+            while let Some((item, conflated)) = conflated_rx.recv().await {
+                // Events from an item that already failed are dropped, as the sequential
+                // pipeline stopped at an item's first error
+                let Ok(outcome) = &mut outcomes[item] else { continue };
+                let conflated = match conflated {
+                    Ok(conflated) => conflated,
+                    Err(e) => {
+                        outcomes[item] = Err(e);
+                        continue;
+                    }
+                };
+                let title = conflated.enriched_data.normalized_data.title.clone();
+                let cataloged = match tracker.stage("catalog", self.catalog_entities(&conflated, run)).await {
+                    Ok((venue_id, cataloged)) => {
+                        outcome.venues.insert(venue_id);
+                        cataloged
+                    }
+                    Err(e) => {
+                        outcomes[item] = Err(e.to_string());
+                        continue;
+                    }
+                };
+                match cataloged {
+                    Cataloged::Suppressed(duplicate) => {
+                        info!("🪞 Suppressed duplicate: {} (kept {})", duplicate.title, duplicate.kept_title);
+                        outcome.suppressed.push(duplicate);
+                    }
+                    Cataloged::Event { id, created } => {
+                        info!("✅ Event cataloged: {}", title);
+                        outcome.cataloged += 1;
+                        if let Some(lineage) = &run.lineage {
+                            let entry = event_lineage(id, created, &conflated.enriched_data.quality);
+                            if let Err(e) = lineage.record(&entry) {
+                                warn!("Failed to record lineage for {}: {}", id, e);
+                            }
+                        }
+                    }
+                }
+            }
+            outcomes
+        };
+
+        let (.., mut outcomes) = tokio::join!(feed, parse, normalize, quality_gate, enrich, conflate, catalog);
+        for ((outcome, parsed), quarantined) in outcomes.iter_mut().zip(&parsed_counts).zip(&quarantined_counts) {
+            if let Ok(outcome) = outcome {
+                outcome.parsed = parsed.load(Ordering::Relaxed);
+                outcome.quarantined = quarantined.load(Ordering::Relaxed);
             }
-            
-            // Step 4: Enrich - Add additional data and context
-            info!("🔍 Step 4: Enrich");
-            let enriched_data = self.enrich_data(&normalized_data).await?;
-            
-            // Step 5: Conflation - Resolve entity relationships
-            info!("🔗 Step 5: Conflation");
-            let conflated_data = self.conflate_entities(&enriched_data).await?;
-            
-            // Step 6: Catalog - Store final entities in database
-            info!("📚 Step 6: Catalog");
-            self.catalog_entities(&conflated_data).await?;
-            info!("✅ Event cataloged: {}", normalized_data.title);
         }
+        outcomes
+    }
 
-        Ok(())
+    /// The events the LLM fallback reads off a payload its parser couldn't, when it's on
+    async fn llm_events(&self, source_id: &str, raw_data: &RawData, bytes: &[u8]) -> Option<Vec<ParsedEventData>> {
+        let fallback = self.llm_fallback.as_ref()?;
+        let origin = raw_data.origin.as_ref();
+        let envelope_id = origin
+            .map(|o| o.envelope_id.clone())
+            .unwrap_or_else(|| raw_data.id.map(|id| id.to_string()).unwrap_or_default());
+        let payload_ref = origin.map(|o| o.payload_ref.as_str()).unwrap_or_default();
+        let events = fallback.extract(source_id, &envelope_id, payload_ref, bytes).await?;
+        Some(events.into_iter().filter_map(|record| llm_event(raw_data, record)).collect())
     }
 
     /// Parse raw HTML/JSON data into structured format
@@ -189,7 +747,7 @@ impl FullPipelineOrchestrator {
         };
         
         // Create parser directly
-        let parser = super::super::apis::factory::create_parser(api_name)
+        let parser = super::super::apis::factory::create_parser(api_name, &self.source_registry.date_hints(api_name))
             .ok_or_else(|| anyhow::anyhow!("Unknown parser: {}", api_name))?;
         
         let mut parsed_data_list = Vec::new();
@@ -211,6 +769,8 @@ impl FullPipelineOrchestrator {
                     raw_data_info,
                     event_args,
                     source_api: raw_data.api_name.clone(),
+                    record: event_json,
+                    change: None,
                 });
             }
         } else {
@@ -223,7 +783,13 @@ impl FullPipelineOrchestrator {
                 json_string.as_bytes()
             };
             
-            let parsed_events = parser.parse_events(bytes_slice).await?;
+            let parsed_events = match parser.parse_events(bytes_slice).await {
+                Ok(events) if !events.iter().all(delta::is_fallback) => events,
+                parsed => match self.llm_events(api_name, raw_data, bytes_slice).await {
+                    Some(events) => return Ok(events),
+                    None => parsed?,
+                },
+            };
             
             for event_json in parsed_events {
                 let raw_data_info = parser.extract_raw_data_info(&event_json)?;
@@ -233,6 +799,8 @@ impl FullPipelineOrchestrator {
                     raw_data_info,
                     event_args,
                     source_api: raw_data.api_name.clone(),
+                    record: event_json,
+                    change: None,
                 });
             }
         }
@@ -248,34 +816,31 @@ impl FullPipelineOrchestrator {
             venue_name: parsed.raw_data_info.venue_name.clone(),
             event_day: parsed.event_args.event_day,
             start_time: parsed.event_args.start_time,
+            doors_time: parsed.event_args.doors_time,
             description: parsed.event_args.description.clone(),
             event_url: parsed.event_args.event_url.clone(),
             image_url: parsed.event_args.event_image_url.clone(),
+            price: parsed.event_args.description.as_deref().and_then(parse_price),
+            age_restriction: parsed.event_args.description.as_deref().and_then(parse_age_restriction),
+            accessibility_notes: parsed.event_args.description.as_deref().and_then(parse_accessibility_notes),
+            show_event: !NormalizerUtils::is_cancelled(&parsed.record),
             source_api: parsed.source_api.clone(),
+            change: parsed.change,
+            record_key: match parsed.raw_data_info.event_api_id.trim() {
+                "" => delta::record_key(&parsed.record),
+                id => format!("id:{}", id),
+            },
+            external_id: parsed.record.get("id").and_then(|id| id.as_str()).map(str::trim).filter(|id| !id.is_empty()).map(str::to_string),
         })
     }
     
-    /// Quality gate validation
-    async fn quality_gate_check(&self, normalized: &NormalizedEventData) -> Result<QualityResult> {
-        // Basic quality checks
-        if normalized.title.trim().is_empty() {
-            return Ok(QualityResult { passed: false, reason: "Empty title".to_string() });
-        }
-        
-        if normalized.venue_name.trim().is_empty() {
-            return Ok(QualityResult { passed: false, reason: "Empty venue name".to_string() });
-        }
-        
-        // Could add more sophisticated quality checks here
-        Ok(QualityResult { passed: true, reason: "Passed".to_string() })
-    }
-    
     /// Enrich data with additional context
     /// DEPRECATED: Use the new modular pipeline architecture in steps/enrich.rs
-    async fn enrich_data(&self, normalized: &NormalizedEventData) -> Result<EnrichedEventData> {
+    async fn enrich_data(&self, normalized: &NormalizedEventData, quality: QualityAssessedRecord) -> Result<EnrichedEventData> {
         // Placeholder implementation - real enrichment is now handled by EnrichStep
         Ok(EnrichedEventData {
             normalized_data: normalized.clone(),
+            quality,
             location_info: None,
             artist_info: vec![],
             event_metadata: EventMetadata {
@@ -287,51 +852,158 @@ impl FullPipelineOrchestrator {
                 external_links: vec![],
             },
             categories: vec!["Music".to_string()],
+            tags: Vec::new(),
         })
     }
+
+    /// Tag the event from the configured keyword rules
+    async fn classify_event(&self, mut enriched: EnrichedEventData) -> Result<EnrichedEventData> {
+        if let Some(classifier) = &self.classifier {
+            let normalized = &enriched.normalized_data;
+            enriched.tags = classifier.classify(&normalized.title, normalized.description.as_deref());
+            if !enriched.tags.is_empty() {
+                debug!("Tagged {} with {:?}", normalized.title, enriched.tags);
+            }
+        }
+        Ok(enriched)
+    }
     
-    /// Conflate entities to resolve duplicates and relationships
-    async fn conflate_entities(&self, enriched: &EnrichedEventData) -> Result<ConflatedEventData> {
-        // Entity resolution and relationship mapping
+    /// Resolve the event to its canonical id: the one this source's listing was given in an
+    /// earlier run, or a fresh one to catalog it under
+    async fn conflate_entities(&self, enriched: EnrichedEventData, run: &RunContext<'_>) -> Result<ConflatedEventData> {
+        let conflator = run.conflator();
+        let conflation = conflator.conflate(&conflation_record(&enriched))?;
         Ok(ConflatedEventData {
-            enriched_data: enriched.clone(),
+            enriched_data: enriched,
             resolved_venue_id: None, // Will be resolved in catalog step
             resolved_artist_ids: Vec::new(), // Will be resolved in catalog step
+            conflator_config: conflator.config.thresholds.clone(),
+            conflation,
         })
     }
     
-    /// Catalog final entities in database
-    async fn catalog_entities(&self, conflated: &ConflatedEventData) -> Result<()> {
+    /// Catalog final entities in database, crediting newly created ones to the source, and
+    /// return the event's venue id with what became of it
+    async fn catalog_entities(&self, conflated: &ConflatedEventData, run: &RunContext<'_>) -> Result<(Uuid, Cataloged)> {
         let normalized = &conflated.enriched_data.normalized_data;
+        let attribution = run.attribution.as_ref();
         
         // Create or find the venue
-        self.ensure_venue(&normalized.venue_name).await?;
+        let provenance = &conflated.enriched_data.quality.normalized_record.provenance;
+        let venue = self.ensure_venue(&normalized.venue_name, attribution, provenance, run).await?;
+        let venue_id = venue.id.ok_or_else(|| anyhow::anyhow!("Venue ID missing"))?;
         
         // Create or find artists from the event title
-        self.ensure_artists_from_title(&normalized.title).await?;
-        
-        // Create the event entity
-        self.create_event_entity_from_normalized(normalized).await?;
+        self.ensure_artists_from_title(&normalized.title, &conflated.conflator_config, run).await?;
         
-        Ok(())
+        // An event whose source record was cataloged before, or resolved to one cataloged
+        // before, is that event, even if its listing changed since
+        let conflation = &conflated.conflation;
+        let known_id = match &conflation.conflation.resolution_decision {
+            ResolutionDecision::MatchedExisting(id) => Some(id.id),
+            _ => None,
+        };
+        let known = match run.cataloged_as(conflation).or(known_id) {
+            Some(id) => self.storage.get_event_by_id(id).await?,
+            None => None,
+        };
+        let cataloged = self
+            .create_event_entity_from_normalized(normalized, venue_id, &conflated.enriched_data.tags, known, conflation.canonical_entity_id.id, run)
+            .await?;
+        // Matched by venue, day and title to an event cataloged under another id
+        if let Cataloged::Event { id, .. } = &cataloged {
+            run.record_cataloged(conflation, *id);
+            if *id != conflation.canonical_entity_id.id {
+                let entity_id = sms_core::pipeline_api::conflation::EntityId { id: *id, entity_type: EntityType::Event, version: 1 };
+                run.conflator().remember_resolution(&conflation.enriched_record, &entity_id);
+            }
+        }
+        Ok((venue_id, cataloged))
     }
     
-    /// Create event entity from normalized data
-    async fn create_event_entity_from_normalized(&self, normalized: &NormalizedEventData) -> Result<()> {
-        // Get the venue
-        let venue = self.storage.get_venue_by_name(&normalized.venue_name).await?
-            .ok_or_else(|| anyhow::anyhow!("Venue not found: {}", normalized.venue_name))?;
-
-        let venue_id = venue.id.ok_or_else(|| anyhow::anyhow!("Venue ID missing"))?;
-
+    /// Create event entity from normalized data at `venue_id` under `new_id`, unless it is
+    /// `known` or duplicates a cataloged event
+    async fn create_event_entity_from_normalized(
+        &self,
+        normalized: &NormalizedEventData,
+        venue_id: Uuid,
+        tags: &[String],
+        known: Option<Event>,
+        new_id: Uuid,
+        run: &RunContext<'_>,
+    ) -> Result<Cataloged> {
         // Check if event already exists
-        if let Ok(Some(_)) = self.storage.get_event_by_venue_date_title(
-            venue_id, 
-            normalized.event_day, 
-            &normalized.title
-        ).await {
+        let (known, new_id) = match known {
+            Some(known) if known.venue_id == venue_id => (Some(known), new_id),
+            // The same listing at another of the source's venues is another event
+            Some(_) => (None, Uuid::new_v4()),
+            None => (None, new_id),
+        };
+        let existing = match known {
+            Some(known) => Some(known),
+            None => self.storage.get_event_by_venue_date_title(venue_id, normalized.event_day, &normalized.title).await.ok().flatten(),
+        };
+        let external_id = normalized.external_id.clone().map(|id| ExternalId { source_id: run.source_id.clone(), id });
+        if let Some(mut existing) = existing {
             debug!("Event already exists: {} on {}", normalized.title, normalized.event_day);
-            return Ok(());
+            // Tags from rules added since the event was cataloged
+            let missing: Vec<String> = tags.iter().filter(|t| !existing.has_tag(t)).cloned().collect();
+            let price_changed = normalized.price.is_some() && existing.price != normalized.price;
+            let doors_changed = normalized.doors_time.is_some() && existing.doors_time != normalized.doors_time;
+            let age_changed =
+                normalized.age_restriction.is_some() && existing.age_restriction != normalized.age_restriction;
+            let accessibility_changed = normalized.accessibility_notes.is_some()
+                && existing.accessibility_notes != normalized.accessibility_notes;
+            // A cancellation (or its reversal) is the listing's call, unless a moderator hid the event
+            let show_changed = existing.moderation.is_none() && existing.show_event != normalized.show_event;
+            let external_id_added = external_id.as_ref().is_some_and(|id| !existing.external_ids.contains(id));
+            if !missing.is_empty()
+                || price_changed
+                || doors_changed
+                || age_changed
+                || accessibility_changed
+                || show_changed
+                || external_id_added
+            {
+                let previous_state = serde_json::to_string(&existing).ok();
+                let fields: Vec<&str> = [
+                    (!missing.is_empty(), "tags"),
+                    (price_changed, "price"),
+                    (doors_changed, "doors_time"),
+                    (age_changed, "age_restriction"),
+                    (accessibility_changed, "accessibility_notes"),
+                    (show_changed, "show_event"),
+                    (external_id_added, "external_ids"),
+                ]
+                .into_iter()
+                .filter_map(|(changed, field)| changed.then_some(field))
+                .collect();
+                existing.tags.extend(missing);
+                if price_changed {
+                    existing.price = normalized.price.clone();
+                }
+                if doors_changed {
+                    existing.doors_time = normalized.doors_time;
+                }
+                if age_changed {
+                    existing.age_restriction = normalized.age_restriction;
+                }
+                if accessibility_changed {
+                    existing.accessibility_notes = normalized.accessibility_notes.clone();
+                }
+                if show_changed {
+                    existing.show_event = normalized.show_event;
+                }
+                if let Some(id) = external_id.filter(|_| external_id_added) {
+                    id.add_to(&mut existing.external_ids);
+                }
+                let change = run.change("UPDATE", format!("Updated event: {}", existing.title), &fields.join(", "), previous_state);
+                let change = change.map(|c| ProcessRecord { event_id: existing.id, venue_id: Some(venue_id), ..c });
+                self.write_with_change(WriteBatch { event_updates: vec![existing.clone()], ..WriteBatch::new() }, change).await?;
+            }
+            self.enrich_headliner(&existing, run).await;
+            let id = existing.id.ok_or_else(|| anyhow::anyhow!("Event ID missing"))?;
+            return Ok(Cataloged::Event { id, created: false });
         }
 
         // Extract and link artists from the event title
@@ -339,8 +1011,8 @@ impl FullPipelineOrchestrator {
         debug!("Found {} artist IDs for event: {}", artist_ids.len(), normalized.title);
 
         // Create new event
-        let mut event = Event {
-            id: None,
+        let event = Event {
+            id: Some(new_id),
             title: normalized.title.clone(),
             event_day: normalized.event_day,
             start_time: normalized.start_time,
@@ -349,49 +1021,128 @@ impl FullPipelineOrchestrator {
             event_image_url: normalized.image_url.clone(),
             venue_id,
             artist_ids,
-            show_event: true,
+            show_event: normalized.show_event,
             finalized: false,
             created_at: chrono::Utc::now(),
+            attributions: run.attribution.clone().into_iter().collect(),
+            tags: tags.to_vec(),
+            price: normalized.price.clone(),
+            doors_time: normalized.doors_time,
+            series_id: None,
+            age_restriction: normalized.age_restriction,
+            accessibility_notes: normalized.accessibility_notes.clone(),
+            moderation: None,
+            external_ids: external_id.into_iter().collect(),
         };
 
-        self.storage.create_event(&mut event).await?;
+        // The same show listed twice (e.g. a matinee mis-parsed as its own event) is merged, not added
+        if let Some(suppressed) = suppress_duplicate(&*self.storage, &event, &run.options.duplicates).await? {
+            if suppressed.merged {
+                let log = format!("Merged duplicate '{}' into event: {}", suppressed.title, suppressed.kept_title);
+                let change = run.change("UPDATE", log, "merged", suppressed.previous_state.clone());
+                self.record_change(change.map(|c| ProcessRecord { event_id: Some(suppressed.kept_event_id), venue_id: Some(venue_id), ..c })).await;
+            }
+            return Ok(Cataloged::Suppressed(suppressed));
+        }
+
+        let change = run.change("CREATE", format!("Created new event: {}", event.title), "all", None);
+        let change = change.map(|c| ProcessRecord { event_id: event.id, venue_id: Some(venue_id), ..c });
+        let mut written = self.write_with_change(WriteBatch { events: vec![event], ..WriteBatch::new() }, change).await?;
+        let event = written.events.remove(0);
         debug!("Created event: {} on {} with {} artists", normalized.title, normalized.event_day, event.artist_ids.len());
-        Ok(())
+        self.enrich_headliner(&event, run).await;
+        let id = event.id.ok_or_else(|| anyhow::anyhow!("Event ID missing"))?;
+        Ok(Cataloged::Event { id, created: true })
     }
 
-    /// Ensure a venue exists in the database
-    async fn ensure_venue(&self, venue_name: &str) -> Result<()> {
-        // Check if venue already exists
-        if let Ok(Some(_)) = self.storage.get_venue_by_name(venue_name).await {
-            return Ok(());
+    /// Carry the event's image and description over to its headliner; a failure here
+    /// never fails the event
+    async fn enrich_headliner(&self, event: &Event, run: &RunContext<'_>) {
+        match enrich_headliner(&*self.storage, event).await {
+            Ok(Some(previous)) => {
+                let log = format!("Enriched headliner {} from event: {}", previous.name, event.title);
+                let change = run.change("UPDATE", log, "artist_image_url, bio", serde_json::to_string(&previous).ok());
+                self.record_change(change.map(|c| ProcessRecord { artist_id: previous.id, event_id: event.id, ..c })).await;
+            }
+            Ok(None) => {}
+            Err(e) => error!("Failed to enrich headliner of '{}': {}", event.title, e),
         }
+    }
 
-        // Create new venue with default Seattle coordinates and required fields
-        let mut venue = Venue {
-            id: None,
-            name: venue_name.to_string(),
-            name_lower: venue_name.to_lowercase(),
-            slug: venue_name.to_lowercase().replace(" ", "-"),
-            latitude: 47.6062, // Default Seattle latitude
-            longitude: -122.3321, // Default Seattle longitude  
-            address: "Seattle, WA".to_string(), // Default address
-            postal_code: "98101".to_string(), // Default Seattle postal code
-            city: "Seattle".to_string(),
-            venue_url: None,
-            venue_image_url: None,
-            description: None,
-            neighborhood: None,
-            show_venue: true,
-            created_at: chrono::Utc::now(),
+    /// Write catalog entities together with the process record that lets `catalog rollback
+    /// --run-id` undo them, in one transaction where the backend supports it. Returns the
+    /// batch as written.
+    async fn write_with_change(&self, mut batch: WriteBatch, change: Option<ProcessRecord>) -> Result<WriteBatch> {
+        batch.process_records.extend(change);
+        let started = std::time::Instant::now();
+        let written = self.storage.write_batch(&mut batch).await?;
+        crate::observability::metrics::catalog::batch_written(written.entities, written.attempts, started.elapsed().as_secs_f64());
+        Ok(batch)
+    }
+
+    /// Record a catalog write so `catalog rollback --run-id` can undo it; a failure here
+    /// never fails the event
+    async fn record_change(&self, change: Option<ProcessRecord>) {
+        let Some(mut change) = change else { return };
+        if let Err(e) = self.storage.create_process_record(&mut change).await {
+            error!("Failed to record {} for rollback: {}", change.change_log, e);
+        }
+    }
+
+    /// The venue an event listing names: the one cataloged under that name, else the one
+    /// conflation matches the listing to within the venue radius, else a new venue under the
+    /// listing's canonical id
+    async fn ensure_venue(
+        &self,
+        venue_name: &str,
+        attribution: Option<&Attribution>,
+        provenance: &RecordProvenance,
+        run: &RunContext<'_>,
+    ) -> Result<Venue> {
+        if let Some(venue) = self.storage.get_venue_by_name(venue_name).await? {
+            return Ok(run.venues.resolve(&*self.storage, venue).await?);
+        }
+
+        // A venue the resolver knows comes with its real coordinates; any other listing gets
+        // default Seattle ones and is kept as a placeholder until its real venue is cataloged
+        let known = run.conflator().venue_resolver.by_name(venue_name);
+        let listing = match known {
+            Some(known) => Venue { attributions: attribution.cloned().into_iter().collect(), ..known.to_venue() },
+            // Unlike a normalizer's TBA placeholder, it is listed and gets a catalog id
+            None => Venue {
+                id: None,
+                slug: venue_name.to_lowercase().replace(" ", "-"),
+                address: "Seattle, WA".to_string(),
+                postal_code: "98101".to_string(),
+                show_venue: true,
+                attributions: attribution.cloned().into_iter().collect(),
+                ..Venue::placeholder(venue_name)
+            },
         };
+        let conflation = run.conflator().conflate(&venue_record(listing.clone(), provenance.clone()))?;
+        let id = run.cataloged_as(&conflation).unwrap_or(conflation.canonical_entity_id.id);
+        if let Some(venue) = self.storage.get_venue_by_id(id).await? {
+            run.record_cataloged(&conflation, id);
+            return Ok(run.venues.resolve(&*self.storage, venue).await?);
+        }
 
-        self.storage.create_venue(&mut venue).await?;
-        debug!("Created venue: {}", venue_name);
-        Ok(())
+        let venue = Venue { id: Some(id), ..listing };
+        let change = run.change("CREATE", format!("Created new venue: {}", venue.name), "all", None);
+        let change = change.map(|c| ProcessRecord { venue_id: venue.id, ..c });
+        let mut written = self.write_with_change(WriteBatch { venues: vec![venue], ..WriteBatch::new() }, change).await?;
+        let venue = written.venues.remove(0);
+        debug!("Created venue: {}", venue.name);
+        run.venues.add(&venue).await;
+        run.record_cataloged(&conflation, id);
+        run.conflator().remember(conflation);
+        Ok(venue)
     }
 
-    /// Extract and ensure artists exist from event title
-    async fn ensure_artists_from_title(&self, title: &str) -> Result<()> {
+    /// Extract and ensure artists exist from event title. Names whose slug is already taken
+    /// merge into that artist when conflation judges them the same, and get a suffixed slug
+    /// otherwise.
+    async fn ensure_artists_from_title(&self, title: &str, conflator: &ConflatorConfig, run: &RunContext<'_>) -> Result<()> {
+        let conflator = DefaultConflator::new().with_config(conflator.clone());
         let mut potential_artists = Vec::new();
         
         // Handle KEXP-specific format: "Artist Name LIVE on KEXP (OPEN TO THE PUBLIC)"
@@ -419,38 +1170,37 @@ impl FullPipelineOrchestrator {
                 continue;
             }
 
-            // Generate consistent slug
-            let artist_slug = self.generate_artist_slug(artist_name);
-            
-            // Check if artist already exists by name OR by slug to avoid duplicates
+            // Check if artist already exists by name to avoid duplicates
             if let Ok(Some(_)) = self.storage.get_artist_by_name(artist_name).await {
                 continue;
             }
-            
-            // Also check by slug to prevent UNIQUE constraint errors
-            if let Ok(Some(_)) = self.get_artist_by_slug(&artist_slug).await {
-                debug!("Artist with slug '{}' already exists, skipping: {}", artist_slug, artist_name);
-                continue;
-            }
 
-            // Create new artist
+            // Create new artist; the allocator settles its slug
             let mut artist = Artist {
                 id: None,
                 name: artist_name.to_string(),
-                name_slug: artist_slug,
+                name_slug: String::new(),
                 bio: None,
                 artist_image_url: None,
                 created_at: chrono::Utc::now(),
+                attributions: run.attribution.clone().into_iter().collect(),
+                detail_origins: Default::default(),
+                aliases: Vec::new(),
             };
 
-            match self.storage.create_artist(&mut artist).await {
-                Ok(_) => {
-                    debug!("Created artist: {} (slug: {})", artist_name, artist.name_slug);
+            let same_artist = |existing: &Artist, new: &Artist| conflator.same_artist_name(&existing.name, &new.name);
+            match allocate_artist(self.storage.as_ref(), &mut artist, same_artist).await {
+                Ok(SlugAllocation::Created { id, slug, .. }) => {
+                    debug!("Created artist: {} (slug: {})", artist_name, slug);
+                    let change = run.change("CREATE", format!("Created new artist: {}", artist_name), "all", None);
+                    self.record_change(change.map(|c| ProcessRecord { artist_id: Some(id), ..c })).await;
+                },
+                Ok(SlugAllocation::Merged { slug, .. }) => {
+                    debug!("Artist '{}' is the one already cataloged as '{}'", artist_name, slug);
                 },
                 Err(e) => {
-                    // If we still get a constraint error, log it but don't fail the entire event
-                    error!("Failed to create artist '{}' (slug: '{}'): {}", artist_name, artist.name_slug, e);
-                    // Continue processing instead of failing
+                    // Log it but don't fail the entire event
+                    error!("Failed to create artist '{}': {}", artist_name, e);
                 }
             }
         }
@@ -458,18 +1208,6 @@ impl FullPipelineOrchestrator {
         Ok(())
     }
     
-    /// Generate a consistent, URL-safe slug from artist name
-    fn generate_artist_slug(&self, name: &str) -> String {
-        name.to_lowercase()
-            .chars()
-            .map(|c| if c.is_alphanumeric() { c } else { '-' })
-            .collect::<String>()
-            .split('-')
-            .filter(|s| !s.is_empty())
-            .collect::<Vec<&str>>()
-            .join("-")
-    }
-    
     /// Get artist by slug (helper method)
     async fn get_artist_by_slug(&self, slug: &str) -> Result<Option<Artist>> {
         self.storage.get_artist_by_slug(slug).await.map_err(|e| anyhow::anyhow!("Database error: {}", e))
@@ -515,7 +1253,7 @@ impl FullPipelineOrchestrator {
             }
             
             // Try to find artist by slug as backup
-            let artist_slug = self.generate_artist_slug(artist_name);
+            let artist_slug = canonical_slug(artist_name);
             if let Ok(Some(artist)) = self.get_artist_by_slug(&artist_slug).await {
                 if let Some(id) = artist.id {
                     artist_ids.push(id);
@@ -526,74 +1264,31 @@ impl FullPipelineOrchestrator {
         Ok(artist_ids)
     }
 
-    /// Create an event entity from raw data
-    async fn create_event_entity(&self, raw_data: &RawData, event_data: &serde_json::Value) -> Result<()> {
-        // Get the venue
-        let venue = self.storage.get_venue_by_name(&raw_data.venue_name).await?
-            .ok_or_else(|| anyhow::anyhow!("Venue not found: {}", raw_data.venue_name))?;
-
-        let venue_id = venue.id.ok_or_else(|| anyhow::anyhow!("Venue ID missing"))?;
-
-        // Extract event details
-        let title = event_data.get("title")
-            .and_then(|t| t.as_str())
-            .unwrap_or(&raw_data.event_name);
-
-        let description = event_data.get("description")
-            .and_then(|d| d.as_str())
-            .map(|s| s.to_string());
-
-        let start_time = event_data.get("start_time")
-            .and_then(|t| t.as_str())
-            .and_then(|s| chrono::NaiveTime::parse_from_str(s, "%H:%M:%S").ok());
-
-        let event_url = event_data.get("event_url")
-            .and_then(|u| u.as_str())
-            .map(|s| s.to_string());
-
-        let event_image_url = event_data.get("event_image_url")
-            .or_else(|| event_data.get("image_url"))
-            .and_then(|u| u.as_str())
-            .map(|s| s.to_string());
-
-        // Check if event already exists
-        if let Ok(Some(_)) = self.storage.get_event_by_venue_date_title(
-            venue_id, 
-            raw_data.event_day, 
-            title
-        ).await {
-            debug!("Event already exists: {} on {}", title, raw_data.event_day);
-            return Ok(());
+    fn specs(&self) -> Result<Arc<RegistrySnapshot>> {
+        match &self.source_specs {
+            Some(specs) => Ok(specs.clone()),
+            None => Ok(registry_watch::shared()?.snapshot()),
         }
+    }
 
-        // Extract and link artists from the event title
-        let artist_ids = self.get_artist_ids_from_title(title).await?;
-
-        // Create new event
-        let mut event = Event {
-            id: None,
-            title: title.to_string(),
-            event_day: raw_data.event_day,
-            start_time,
-            event_url,
-            description,
-            event_image_url,
-            venue_id,
-            artist_ids,
-            show_event: true,
-            finalized: false,
-            created_at: chrono::Utc::now(),
-        };
-
-        self.storage.create_event(&mut event).await?;
-        debug!("Created event: {} on {} with {} artists", title, raw_data.event_day, event.artist_ids.len());
-        Ok(())
+    /// The parse plan a source's payloads are re-parsed with when retried from the dead-letter queue
+    fn parse_plan(&self, source_id: &str) -> String {
+        self.specs()
+            .ok()
+            .and_then(|specs| specs.get(source_id).and_then(|spec| spec.resolved_parse_plan()))
+            .unwrap_or_else(|| "parse_plan:wix_calendar_v1".to_string())
     }
 
     /// Run ingestion for a specific source to fetch fresh raw data
     /// DEPRECATED: Use the new modular pipeline architecture in steps/ingestion.rs
-    pub async fn run_ingestion_for_source(&self, source_id: &str) -> Result<()> {
-        let ingestion_step = crate::pipeline::steps::IngestionStep::new(self.source_registry.clone());
+    pub async fn run_ingestion_for_source(&self, source_id: &str, bypass_cadence: bool) -> Result<()> {
+        let mut ingestion_step = crate::pipeline::steps::IngestionStep::new(self.source_registry.clone());
+        // With a data root, fetch every endpoint through the gateway like `gateway-all`, so
+        // payloads are in CAS and each raw data row knows its envelope
+        if let Some(data_root) = self.meta.data_root() {
+            let specs = self.specs()?;
+            ingestion_step = ingestion_step.through_gateway(specs, IngestOptions { data_root: data_root.to_path_buf(), bypass_cadence, host_limits: None });
+        }
         let result = ingestion_step.execute(source_id, &*self.storage).await?;
         info!("✅ {}", result.message);
         Ok(())
@@ -602,7 +1297,12 @@ impl FullPipelineOrchestrator {
     /// Run parse step independently on raw data from database
     /// DEPRECATED: Use the new modular pipeline architecture in steps/parse.rs
     pub async fn run_parse_for_source(&self, source_id: &str) -> Result<()> {
-        let parse_step = crate::pipeline::steps::ParseStep::new(self.source_registry.clone());
+        let mut parse_step = crate::pipeline::steps::ParseStep::new(self.source_registry.clone());
+        if let Some(data_root) = self.meta.data_root() {
+            parse_step = parse_step
+                .with_envelope_states(EnvelopeStates::new(data_root))
+                .with_dead_letters(Box::new(FileDeadLetterStore::new(data_root)), self.parse_plan(source_id));
+        }
         let result = parse_step.execute(source_id, &*self.storage).await?;
         info!("✅ {}", result.message);
         Ok(())
@@ -637,8 +1337,8 @@ impl FullPipelineOrchestrator {
 
     /// Run conflation step independently on enriched data
     /// DEPRECATED: Use the new modular pipeline architecture in steps/conflation.rs
-    pub async fn run_conflation_for_source(&self, source_id: &str, confidence_threshold: f64) -> Result<()> {
-        let conflation_step = crate::pipeline::steps::ConflationStep::new(confidence_threshold);
+    pub async fn run_conflation_for_source(&self, source_id: &str, config: ConflatorConfig) -> Result<()> {
+        let conflation_step = crate::pipeline::steps::ConflationStep::new(config);
         let result = conflation_step.execute(source_id, &*self.storage).await?;
         info!("✅ {}", result.message);
         Ok(())
@@ -646,11 +1346,16 @@ impl FullPipelineOrchestrator {
 
     /// Run catalog step independently on conflated data
     /// DEPRECATED: Use the new modular pipeline architecture in steps/catalog.rs
-    pub async fn run_catalog_for_source(&self, source_id: &str, validate_graph: bool) -> Result<()> {
-        let catalog_step = crate::pipeline::steps::CatalogStep::new(validate_graph);
-        let result = catalog_step.execute(source_id, &*self.storage).await?;
+    pub async fn run_catalog_for_source(
+        &self,
+        source_id: &str,
+        validate_graph: bool,
+        conflator: ConflatorConfig,
+    ) -> Result<Option<crate::pipeline::processing::catalog::graph_validation::GraphValidationReport>> {
+        let catalog_step = crate::pipeline::steps::CatalogStep::new(validate_graph).with_conflator(conflator);
+        let (result, report) = catalog_step.execute_with_report(source_id, &*self.storage).await?;
         info!("✅ {}", result.message);
-        Ok(())
+        Ok(report)
     }
 
     // NOTE: Utility methods have been moved to pipeline/utils.rs
@@ -659,6 +1364,403 @@ impl FullPipelineOrchestrator {
     // All utility methods have been moved to pipeline/utils.rs and are used by the modular pipeline steps
 }
 
+/// One event the LLM fallback returned, read the way a venue parser reads its own records;
+/// `None` when it lacks the title or date the fallback's schema requires
+fn llm_event(raw_data: &RawData, record: serde_json::Value) -> Option<ParsedEventData> {
+    let text = |key: &str| {
+        record.get(key).and_then(|v| v.as_str()).map(str::trim).filter(|s| !s.is_empty()).map(str::to_string)
+    };
+    let title = text("title")?;
+    let event_day = chrono::NaiveDate::parse_from_str(&text("event_day")?, "%Y-%m-%d").ok()?;
+    let event_url = text("event_url");
+    Some(ParsedEventData {
+        raw_data_info: RawDataInfo {
+            event_api_id: event_url.clone().unwrap_or_else(|| format!("{}|{}", title.to_lowercase(), event_day)),
+            event_name: title.clone(),
+            venue_name: text("venue_name").unwrap_or_else(|| raw_data.venue_name.clone()),
+            event_day,
+        },
+        event_args: EventArgs {
+            title,
+            event_day,
+            start_time: text("start_time").and_then(|t| chrono::NaiveTime::parse_from_str(&t, "%H:%M").ok()),
+            doors_time: None,
+            event_url,
+            description: text("description"),
+            event_image_url: None,
+        },
+        source_api: raw_data.api_name.clone(),
+        change: None,
+        record,
+    })
+}
+
+/// What a run carries across its batches: how it was started and the gates and outputs its
+/// stages share
+struct RunContext<'a> {
+    source_id: String,
+    tracker: &'a RunTracker,
+    options: &'a RunOptions,
+    attribution: Option<Attribution>,
+    /// Decides which events go on to be cataloged; the shadow candidate is compared against
+    /// its decision on the same record
+    active_gate: MetricsQualityGate<DefaultQualityGate>,
+    shadow_gate: Option<ShadowQualityGate>,
+    /// Where every gate decision is recorded for the quality stats and quarantine review
+    outcomes: Option<QualityOutcomeStore>,
+    /// Where each cataloged event's stage lineage is recorded, for its provenance
+    lineage: Option<LineageStore>,
+    /// This run's fetches being diffed against the previous ones; in-memory runs keep none
+    deltas: Option<RunDeltas>,
+    /// Where the gateway envelopes the raw data came in are moved through the stages
+    envelope_states: Option<EnvelopeStates>,
+    /// Where envelopes whose parse failed are kept for `dlq retry`; in-memory runs keep none
+    dead_letters: Option<RunDeadLetters>,
+    /// Resolves each event and venue to its canonical id, through the resolution index when
+    /// the run has a data root; knows the run's cataloged venues
+    conflator: Mutex<DefaultConflator>,
+    /// The cataloged venues placeholders resolve to, listed once at the start of the run
+    venues: CatalogedVenues,
+    /// Which event and venue each of the source's records was cataloged as, so reruns update
+    /// them; in-memory runs keep none
+    catalog_keys: Option<CatalogKeyIndex>,
+    /// The process run the run's catalog writes are recorded under, by the run's own id, so
+    /// `catalog rollback --run-id` can undo them
+    process_run: Option<ProcessRun>,
+    outputs: Option<StageOutputs>,
+}
+
+struct RunDeadLetters {
+    store: FileDeadLetterStore,
+    parse_plan: String,
+}
+
+impl RunContext<'_> {
+    /// A process record of one catalog write, when the run has a process run to record it under
+    fn change(&self, change_type: &str, change_log: String, field_changed: &str, previous_state: Option<String>) -> Option<ProcessRecord> {
+        Some(ProcessRecord {
+            id: None,
+            process_run_id: self.process_run.as_ref()?.id?,
+            api_name: self.source_id.clone(),
+            raw_data_id: None,
+            change_type: change_type.to_string(),
+            change_log,
+            field_changed: field_changed.to_string(),
+            event_id: None,
+            venue_id: None,
+            artist_id: None,
+            created_at: chrono::Utc::now(),
+            previous_state,
+        })
+    }
+
+    /// The entity `record` was cataloged as by an earlier run, by its source record key
+    fn cataloged_as(&self, record: &ConflatedRecord) -> Option<Uuid> {
+        let (index, (entity_type, key)) = (self.catalog_keys.as_ref()?, catalog_key(record)?);
+        index.lookup(&key, entity_type).unwrap_or_else(|e| {
+            warn!("Catalog key lookup failed for {}: {}", key, e);
+            None
+        })
+    }
+
+    /// Key `record` to the entity it was cataloged as
+    fn record_cataloged(&self, record: &ConflatedRecord, id: Uuid) {
+        let (Some(index), Some((entity_type, key))) = (&self.catalog_keys, catalog_key(record)) else { return };
+        if let Err(e) = index.record(&key, entity_type, id, chrono::Utc::now()) {
+            warn!("Failed to record catalog key {} for {}: {}", key, id, e);
+        }
+    }
+
+    fn conflator(&self) -> MutexGuard<'_, DefaultConflator> {
+        self.conflator.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Move the envelope `raw_data` was accepted in to `state`; raw data a crawler stored
+    /// without the gateway has no envelope to move
+    fn record_state(&self, raw_data: &RawData, state: EnvelopeState, detail: Option<&str>) {
+        if let (Some(states), Some(origin)) = (&self.envelope_states, &raw_data.origin) {
+            states.record(&origin.envelope_id, &self.source_id, state, detail);
+        }
+    }
+
+    /// Keep the envelope `raw_data` came in for re-parsing after its parse failed
+    async fn dead_letter(&self, raw_data: &RawData, error: &str) {
+        let Some(dead_letters) = &self.dead_letters else { return };
+        let Some(entry) = DeadLetterEntry::for_raw_data(&self.source_id, raw_data, &dead_letters.parse_plan, error) else {
+            return;
+        };
+        match dead_letters.store.record(entry).await {
+            Ok(()) => crate::observability::metrics::parser::dead_lettered(),
+            Err(e) => warn!("Failed to dead-letter raw data {}: {}", raw_data_id(raw_data), e),
+        }
+    }
+}
+
+/// The fetches a run is diffing, one per endpoint of the source, so every page and envelope
+/// of a fetch is compared together and removals are only worked out once all are parsed
+struct RunDeltas {
+    store: DeltaStore,
+    fetches: std::sync::Mutex<HashMap<String, FetchDiff>>,
+}
+
+impl RunDeltas {
+    fn new(store: DeltaStore) -> Self {
+        Self { store, fetches: std::sync::Mutex::new(HashMap::new()) }
+    }
+
+    /// Tag each of `parsed` with how it differs from the previous fetch of its endpoint
+    fn observe(&self, source_id: &str, raw_data: &RawData, parsed: &mut [ParsedEventData]) -> Result<()> {
+        let origin = raw_data.origin.as_ref();
+        let endpoint_id = origin.and_then(|o| o.endpoint_id.as_deref());
+        let recorded_at = raw_data.created_at.timestamp();
+        let mut fetches = self.fetches.lock().map_err(|_| anyhow::anyhow!("delta fetches lock poisoned"))?;
+        let fetch = match fetches.entry(crate::pipeline::ingestion::delta::delta_key(source_id, endpoint_id)) {
+            std::collections::hash_map::Entry::Occupied(entry) => entry.into_mut(),
+            std::collections::hash_map::Entry::Vacant(entry) => {
+                entry.insert(self.store.begin(source_id, endpoint_id, recorded_at)?)
+            }
+        };
+        for event in parsed.iter_mut() {
+            let mut record = ParsedRecord {
+                source_id: source_id.to_string(),
+                envelope_id: origin
+                    .map(|o| o.envelope_id.clone())
+                    .unwrap_or_else(|| raw_data.id.map(|id| id.to_string()).unwrap_or_default()),
+                payload_ref: origin.map(|o| o.payload_ref.clone()).unwrap_or_default(),
+                record_path: "$".to_string(),
+                record: event.record.clone(),
+                attribution: None,
+                change: None,
+                endpoint_id: endpoint_id.map(str::to_string),
+                external_id: Some(event.raw_data_info.event_api_id.clone()),
+            };
+            fetch.observe(&mut record, recorded_at)?;
+            event.change = record.change;
+        }
+        Ok(())
+    }
+
+    /// Finish every fetch: make each the new snapshot of its endpoint and say what it removed
+    fn finish(&self, result: &mut ProcessingResult) {
+        let fetches = match self.fetches.lock() {
+            Ok(mut fetches) => std::mem::take(&mut *fetches),
+            Err(_) => return,
+        };
+        let mut fetches: Vec<_> = fetches.into_iter().collect();
+        fetches.sort_by(|a, b| a.0.cmp(&b.0));
+        for (key, fetch) in fetches {
+            match self.store.finish(fetch) {
+                Ok(delta) => {
+                    if let Some(reason) = &delta.skipped {
+                        info!("Δ {} not diffed: {}", key, reason);
+                        continue;
+                    }
+                    info!(
+                        "Δ {}: {} added, {} changed, {} unchanged, {} removed since {}",
+                        key,
+                        delta.added,
+                        delta.changed,
+                        delta.unchanged,
+                        delta.removed.len(),
+                        delta.previous_envelope_id.as_deref().unwrap_or("-")
+                    );
+                    if !delta.removed.is_empty() {
+                        result.notes.push(format!("{} events no longer listed by {}", delta.removed.len(), key));
+                    }
+                }
+                Err(e) => {
+                    warn!("Failed to record the delta for {}: {}", key, e);
+                    result.errors.push(format!("Delta failed for {}: {}", key, e));
+                }
+            }
+        }
+    }
+}
+
+/// What cataloging one event came to
+enum Cataloged {
+    /// Written as a new event, or matched to the one already cataloged for it
+    Event { id: Uuid, created: bool },
+    /// Folded into an already-cataloged duplicate
+    Suppressed(SuppressedDuplicate),
+}
+
+/// The normalize and quality gate stages' records, routed by the run's `[sinks]` config. The
+/// enrich and conflation stages here work on event data rather than records, so they have no
+/// sink output.
+struct StageOutputs {
+    normalized: FanOut<dyn NormalizeOutputPort>,
+    accepted: FanOut<dyn QualityGateOutputPort>,
+    quarantined: FanOut<dyn QualityGateOutputPort>,
+}
+
+impl StageOutputs {
+    /// Hand a gated record to the sinks; a failing sink is logged and never fails the event
+    async fn write(&self, record: &NormalizedRecord, assessed: &QualityAssessedRecord) {
+        if let Err(e) = self.normalized.write_normalized_record(record).await {
+            warn!("Normalize sink failed for {}: {}", record.provenance.record_path, e);
+        }
+        let partition = match assessed.quality_assessment.decision {
+            QualityDecision::Quarantine => &self.quarantined,
+            _ => &self.accepted,
+        };
+        if let Err(e) = partition.write_quality_assessed_record(assessed).await {
+            warn!("Quality gate sink failed for {}: {}", record.provenance.record_path, e);
+        }
+    }
+}
+
+/// Lineage of an event cataloged from its gated record. Events here are matched by venue,
+/// day and title rather than by conflation scores, so the match is recorded as certain.
+fn event_lineage(id: Uuid, created: bool, quality: &QualityAssessedRecord) -> RecordLineage {
+    let cataloged_at = chrono::Utc::now();
+    let provenance = &quality.normalized_record.provenance;
+    let conflation = ConflationLineage {
+        conflated_at: cataloged_at,
+        entity_version: 1,
+        decision: if created { "new_entity" } else { "matched_existing" }.to_string(),
+        confidence: 1.0,
+        strategy: "venue_day_title".to_string(),
+        contributing_sources: vec![provenance.source_id.clone()],
+    };
+    RecordLineage::from_assessed(id, "event", quality, conflation, cataloged_at)
+}
+
+/// The id the parse, gate and catalog stages refer to a raw data item by
+fn raw_data_id(raw_data: &RawData) -> String {
+    raw_data.id.map(|id| id.to_string()).unwrap_or_default()
+}
+
+/// An event as the record-based quality gate sees it, assessed by the active gate and the
+/// shadow candidate alike. This path keeps no normalization confidence, so records count as
+/// fully confident.
+/// The record the quality gates score; its provenance points at the envelope and payload the
+/// raw data was ingested in, or at the raw data row when it didn't come through the gateway
+/// The event as conflation sees it. The enrich stage here adds no geography, so the
+/// record carries none.
+fn conflation_record(enriched: &EnrichedEventData) -> EnrichedRecord {
+    unenriched(enriched.quality.clone(), enriched.tags.clone())
+}
+
+/// A venue as conflation sees it, accepted as-is
+fn venue_record(venue: Venue, provenance: RecordProvenance) -> EnrichedRecord {
+    let quality_assessed_record = QualityAssessedRecord {
+        normalized_record: NormalizedRecord {
+            entity: NormalizedEntity::Venue(venue),
+            provenance,
+            normalization: NormalizationMetadata {
+                confidence: 1.0,
+                warnings: Vec::new(),
+                geocoded: false,
+                strategy: "full_pipeline".to_string(),
+            },
+        },
+        quality_assessment: QualityAssessment {
+            decision: QualityDecision::Accept,
+            quality_score: 1.0,
+            issues: Vec::new(),
+            rule_version: "full_pipeline".to_string(),
+        },
+        assessed_at: chrono::Utc::now(),
+    };
+    unenriched(quality_assessed_record, Vec::new())
+}
+
+/// Provenance for a venue the catalog already holds under `id`
+fn cataloged_provenance(id: Uuid) -> RecordProvenance {
+    RecordProvenance {
+        envelope_id: String::new(),
+        source_id: "catalog".to_string(),
+        payload_ref: String::new(),
+        record_path: id.to_string(),
+        normalized_at: chrono::Utc::now(),
+        attribution: None,
+        change: None,
+        record_key: None,
+        endpoint_id: None,
+        external_id: None,
+    }
+}
+
+/// A record carried into conflation without geo enrichment
+fn unenriched(quality_assessed_record: QualityAssessedRecord, tags: Vec<String>) -> EnrichedRecord {
+    EnrichedRecord {
+        quality_assessed_record,
+        enrichment: EnrichmentMetadata {
+            city: None,
+            district: None,
+            region: None,
+            spatial_bin: None,
+            tags,
+            geo_properties: GeoProperties {
+                within_city_bounds: false,
+                distance_from_center_km: None,
+                population_density: PopulationDensity::Unknown,
+                transit_accessibility: None,
+                nearby_landmarks: Vec::new(),
+            },
+            reference_versions: ReferenceVersions {
+                city_boundaries: None,
+                admin_boundaries: None,
+                spatial_grid: None,
+                poi_data: None,
+                neighborhoods: None,
+            },
+            strategy: "full_pipeline".to_string(),
+            confidence: 1.0,
+            warnings: Vec::new(),
+        },
+        enriched_at: chrono::Utc::now(),
+    }
+}
+
+fn gate_record(normalized: &NormalizedEventData, source_id: &str, raw_data: &RawData) -> NormalizedRecord {
+    let event = Event {
+        id: None,
+        title: normalized.title.clone(),
+        event_day: normalized.event_day,
+        start_time: normalized.start_time,
+        event_url: normalized.event_url.clone(),
+        description: normalized.description.clone(),
+        event_image_url: normalized.image_url.clone(),
+        venue_id: Uuid::nil(),
+        artist_ids: Vec::new(),
+        show_event: true,
+        finalized: false,
+        created_at: chrono::Utc::now(),
+        attributions: Vec::new(),
+        tags: Vec::new(),
+        price: normalized.price.clone(),
+        doors_time: normalized.doors_time,
+        series_id: None,
+        age_restriction: normalized.age_restriction,
+        accessibility_notes: normalized.accessibility_notes.clone(),
+        moderation: None,
+        external_ids: Vec::new(),
+    };
+    NormalizedRecord {
+        entity: NormalizedEntity::Event(event),
+        provenance: RecordProvenance {
+            envelope_id: raw_data.origin.as_ref().map_or_else(|| raw_data_id(raw_data), |o| o.envelope_id.clone()),
+            source_id: source_id.to_string(),
+            payload_ref: raw_data.origin.as_ref().map(|o| o.payload_ref.clone()).unwrap_or_default(),
+            record_path: normalized.title.clone(),
+            normalized_at: chrono::Utc::now(),
+            attribution: None,
+            change: normalized.change,
+            record_key: Some(normalized.record_key.clone()),
+            endpoint_id: raw_data.origin.as_ref().and_then(|o| o.endpoint_id.clone()),
+            external_id: None,
+        },
+        normalization: NormalizationMetadata {
+            confidence: 1.0,
+            warnings: Vec::new(),
+            geocoded: false,
+            strategy: "full_pipeline".to_string(),
+        },
+    }
+}
+
 // Data structures have been moved to pipeline/utils.rs for shared use
 // Re-export them here for backward compatibility
 pub use crate::pipeline::utils::{QualityResult, CatalogEntry, LocationInfo};
@@ -671,6 +1773,10 @@ pub struct ParsedEventData {
     pub raw_data_info: RawDataInfo,
     pub event_args: EventArgs,
     pub source_api: String,
+    /// The event as the parser extracted it
+    pub record: serde_json::Value,
+    /// How the event differs from the source's previous fetch, when the run keeps deltas
+    pub change: Option<RecordChange>,
 }
 
 #[derive(Debug, Clone)]
@@ -679,19 +1785,38 @@ pub struct NormalizedEventData {
     pub venue_name: String,
     pub event_day: chrono::NaiveDate,
     pub start_time: Option<chrono::NaiveTime>,
+    /// When doors open, for listings that give it separately from the show
+    pub doors_time: Option<chrono::NaiveTime>,
     pub description: Option<String>,
     pub event_url: Option<String>,
     pub image_url: Option<String>,
+    /// Ticket price advertised in the description
+    pub price: Option<EventPrice>,
+    /// Age policy and accessibility details the description gives
+    pub age_restriction: Option<AgeRestriction>,
+    pub accessibility_notes: Option<String>,
+    /// False for listings the source marks cancelled, which are cataloged hidden
+    pub show_event: bool,
     pub source_api: String,
+    pub change: Option<RecordChange>,
+    /// The source's own identity for the event, which reruns catalog it by
+    pub record_key: String,
+    /// The source's own id for the event, when its parser extracted one; kept on the
+    /// cataloged event so it can be looked up by it
+    pub external_id: Option<String>,
 }
 
 #[derive(Debug, Clone)]
 pub struct EnrichedEventData {
     pub normalized_data: NormalizedEventData,
+    /// The quality gate's assessment of the event, kept for its lineage
+    pub quality: QualityAssessedRecord,
     pub location_info: Option<LocationInfo>,
     pub artist_info: Vec<ArtistInfo>,
     pub event_metadata: EventMetadata,
     pub categories: Vec<String>,
+    /// Genre/category tags from classification
+    pub tags: Vec<String>,
 }
 
 /// Artist information
@@ -720,6 +1845,10 @@ pub struct ConflatedEventData {
     pub enriched_data: EnrichedEventData,
     pub resolved_venue_id: Option<Uuid>,
     pub resolved_artist_ids: Vec<Uuid>,
+    /// Thresholds and tie-break strategy this data was conflated under
+    pub conflator_config: ConflatorConfig,
+    /// The canonical id conflation resolved the event to, and how
+    pub conflation: ConflatedRecord,
 }
 
 
@@ -730,7 +1859,27 @@ pub struct ProcessingResult {
     pub total_items: usize,
     pub processed_items: usize,
     pub failed_items: usize,
+    pub records_parsed: usize,
+    pub records_cataloged: usize,
+    /// Events folded into an already-cataloged duplicate instead of being created
+    pub suppressed_duplicates: Vec<SuppressedDuplicate>,
     pub errors: Vec<String>,
+    /// How the shadow quality gate config's decisions compared with the active gate's
+    pub quality_shadow: Option<ShadowGateReport>,
+    /// Warnings about the run as a whole, such as payload schema drift
+    pub notes: Vec<String>,
+}
+
+/// Counts for one raw data item
+#[derive(Debug, Default)]
+struct ItemOutcome {
+    parsed: usize,
+    /// Of the parsed events, those the quality gate held back
+    quarantined: usize,
+    cataloged: usize,
+    suppressed: Vec<SuppressedDuplicate>,
+    /// Venues of the events it cataloged or matched, by id
+    venues: BTreeSet<Uuid>,
 }
 
 impl ProcessingResult {
@@ -747,4 +1896,556 @@ impl ProcessingResult {
             (self.processed_items as f64 / self.total_items as f64) * 100.0
         }
     }
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pipeline::ingestion::gateway::cas_gc::{collect_references, gc_local, GcOptions};
+    use crate::pipeline::processing::quality_gate::QualityGateConfig;
+    use sms_core::common::record_key::SourceKey;
+    use sms_core::domain::RawDataOrigin;
+
+    /// The in-memory orchestrator over an empty source registry, since tests don't run from
+    /// the workspace root where `registry/sources` lives
+    fn in_memory_orchestrator() -> (FullPipelineOrchestrator, Arc<InMemoryStorage>) {
+        orchestrator_with_meta(MetaStore::in_memory().unwrap())
+    }
+
+    fn orchestrator_with_meta(meta: MetaStore) -> (FullPipelineOrchestrator, Arc<InMemoryStorage>) {
+        let storage = Arc::new(InMemoryStorage::new());
+        let orchestrator =
+            FullPipelineOrchestrator::with_registry(storage.clone(), meta, SourceRegistry::from_configs([])).unwrap();
+        (orchestrator, storage)
+    }
+
+    /// Stores a Blue Moon listing page as unprocessed raw data, one entry per `(id, title)`
+    async fn seed_blue_moon(storage: &InMemoryStorage, events: &[(&str, &str)]) -> RawData {
+        seed_blue_moon_from(storage, None, events).await
+    }
+
+    /// Like `seed_blue_moon`, as if accepted through the gateway in `origin`
+    async fn seed_blue_moon_from(storage: &InMemoryStorage, origin: Option<RawDataOrigin>, events: &[(&str, &str)]) -> RawData {
+        let event_day = chrono::Utc::now().date_naive() + chrono::Duration::days(7);
+        let data = events
+            .iter()
+            .map(|(id, title)| serde_json::json!({"id": id, "title": title, "event_day": event_day.to_string()}))
+            .collect();
+        let mut raw_data = RawData {
+            id: None,
+            api_name: crate::common::constants::api_name_to_internal("blue_moon"),
+            event_api_id: "listing".to_string(),
+            event_name: "listing".to_string(),
+            venue_name: "Blue Moon Tavern".to_string(),
+            event_day,
+            data: serde_json::Value::Array(data),
+            processed: false,
+            event_id: None,
+            created_at: chrono::Utc::now(),
+            origin,
+        };
+        storage.create_raw_data(&mut raw_data).await.unwrap();
+        raw_data
+    }
+
+    #[tokio::test]
+    async fn active_gate_decides_with_the_configured_rules() {
+        let (orchestrator, storage) = in_memory_orchestrator();
+        seed_blue_moon(&storage, &[("1", "The Moondogs"), ("2", "Late Shift Trio")]).await;
+        // Every event this path builds carries an informational issue, so no event scores 1.0
+        let options = RunOptions {
+            quality_gate: QualityGateConfig { min_quality_score: 1.0, ..Default::default() },
+            quality_shadow: Some(QualityGateConfig { rule_version: "candidate".to_string(), ..Default::default() }),
+            ..Default::default()
+        };
+
+        let tracker = RunTracker::open("full_pipeline", Some("blue_moon"));
+        let result = orchestrator.process_source_tracked("blue_moon", &tracker, &options).await.unwrap();
+
+        assert_eq!(result.records_parsed, 2);
+        assert_eq!(result.records_cataloged, 0);
+        assert!(storage.get_all_events(None, None).await.unwrap().is_empty());
+        let shadow = result.quality_shadow.unwrap();
+        assert_eq!(shadow.records, 2);
+        assert_eq!(shadow.active_quarantined, 2);
+        assert_eq!(shadow.candidate_quarantined, 0);
+        assert_eq!(shadow.newly_accepted, 2);
+    }
+
+    #[tokio::test]
+    async fn default_gate_catalogs_well_formed_events() {
+        let (orchestrator, storage) = in_memory_orchestrator();
+        seed_blue_moon(&storage, &[("1", "The Moondogs")]).await;
+
+        let tracker = RunTracker::open("full_pipeline", Some("blue_moon"));
+        let options = RunOptions { quality_shadow: Some(QualityGateConfig::default()), ..Default::default() };
+        let result = orchestrator.process_source_tracked("blue_moon", &tracker, &options).await.unwrap();
+
+        assert_eq!(result.records_cataloged, 1);
+        let shadow = result.quality_shadow.unwrap();
+        assert_eq!((shadow.records, shadow.agreed, shadow.active_quarantined), (1, 1, 0));
+    }
+
+    #[tokio::test]
+    async fn stage_records_go_to_the_configured_sinks() {
+        use crate::infra::sink_registry::{DbSink, SinkSpec, SinksConfig, Stage};
+
+        let tmp = tempfile::tempdir().unwrap();
+        let (orchestrator, storage) = orchestrator_with_meta(MetaStore::at_root(tmp.path()));
+        seed_blue_moon(&storage, &[("1", "The Moondogs"), ("2", "Late Shift Trio")]).await;
+        let db = vec![SinkSpec::Db { path: None }];
+        let options = RunOptions {
+            sinks: Some(SinksConfig { normalize: db.clone(), quality_gate: db, enrich: Vec::new(), conflation: Vec::new() }),
+            ..Default::default()
+        };
+
+        let tracker = RunTracker::open("full_pipeline", Some("blue_moon"));
+        let result = orchestrator.process_source_tracked("blue_moon", &tracker, &options).await.unwrap();
+
+        assert_eq!(result.records_cataloged, 2);
+        let sink = DbSink::open_at_root(tmp.path()).unwrap();
+        assert_eq!(sink.count(Stage::Normalize).unwrap(), 2);
+        assert_eq!(sink.count(Stage::QualityGate).unwrap(), 2);
+    }
+
+    #[tokio::test]
+    async fn quality_gate_decisions_are_recorded_under_the_data_root() {
+        let tmp = tempfile::tempdir().unwrap();
+        let (orchestrator, storage) = orchestrator_with_meta(MetaStore::at_root(tmp.path()));
+        seed_blue_moon(&storage, &[("1", "The Moondogs"), ("2", "")]).await;
+
+        let tracker = RunTracker::open("full_pipeline", Some("blue_moon"));
+        orchestrator.process_source_tracked("blue_moon", &tracker, &RunOptions::default()).await.unwrap();
+
+        let stats = QualityOutcomeStore::open_at_root(tmp.path()).unwrap().stats(Some("blue_moon"), None).unwrap();
+        assert_eq!((stats.total, stats.quarantined), (2, 1));
+        assert!(stats.top_issues.iter().any(|(issue, _)| issue == "Event title is missing"));
+    }
+
+    #[tokio::test]
+    async fn cataloged_events_have_lineage_back_to_their_raw_data() {
+        let tmp = tempfile::tempdir().unwrap();
+        let (orchestrator, storage) = orchestrator_with_meta(MetaStore::at_root(tmp.path()));
+        seed_blue_moon(&storage, &[("1", "The Moondogs")]).await;
+        let tracker = RunTracker::open("full_pipeline", Some("blue_moon"));
+        orchestrator.process_source_tracked("blue_moon", &tracker, &RunOptions::default()).await.unwrap();
+        // A second run over the same listing matches the event it cataloged
+        seed_blue_moon(&storage, &[("1", "The Moondogs")]).await;
+        let raw_data_id = storage.get_unprocessed_raw_data("crawler_blue_moon", None).await.unwrap()[0].id.unwrap();
+        orchestrator.process_source_tracked("blue_moon", &tracker, &RunOptions::default()).await.unwrap();
+
+        let event = storage.get_all_events(None, None).await.unwrap().remove(0);
+        let lineage = LineageStore::open_at_root(tmp.path()).unwrap().latest(event.id.unwrap()).unwrap().unwrap();
+        assert_eq!(lineage.entity_type, "event");
+        assert_eq!(lineage.parsed.source_id, "blue_moon");
+        assert_eq!(lineage.parsed.envelope_id, raw_data_id.to_string());
+        assert_eq!(lineage.conflation.decision, "matched_existing");
+        assert_eq!(lineage.quality.decision, "accept");
+    }
+
+    #[tokio::test]
+    async fn each_run_is_diffed_against_the_previous_fetch() {
+        let tmp = tempfile::tempdir().unwrap();
+        let (orchestrator, storage) = orchestrator_with_meta(MetaStore::at_root(tmp.path()));
+        let tracker = RunTracker::open("full_pipeline", Some("blue_moon"));
+        seed_blue_moon(&storage, &[("1", "The Moondogs"), ("2", "Late Shift Trio")]).await;
+        let first = orchestrator.process_source_tracked("blue_moon", &tracker, &RunOptions::default()).await.unwrap();
+        assert!(first.notes.iter().all(|note| !note.contains("no longer listed")));
+
+        seed_blue_moon(&storage, &[("1", "The Moondogs"), ("3", "Rain City Ramblers")]).await;
+        let second = orchestrator.process_source_tracked("blue_moon", &tracker, &RunOptions::default()).await.unwrap();
+        assert!(second.notes.contains(&"1 events no longer listed by blue_moon".to_string()));
+
+        // The second fetch is now the snapshot a later one is tagged against
+        let mut third = seed_blue_moon(&storage, &[("1", "The Moondogs"), ("3", "Rain City Ramblers!")]).await;
+        third.created_at += chrono::Duration::seconds(1);
+        let deltas = RunDeltas::new(DeltaStore::open_at_root(tmp.path()).unwrap());
+        let mut parsed = orchestrator.parse_raw_data(&third).await.unwrap();
+        deltas.observe("blue_moon", &third, &mut parsed).unwrap();
+        let changes: Vec<_> = parsed.iter().map(|event| event.change).collect();
+        assert_eq!(changes, vec![Some(RecordChange::Unchanged), Some(RecordChange::Changed)]);
+    }
+
+    #[tokio::test]
+    async fn events_keep_their_canonical_id_across_runs() {
+        let tmp = tempfile::tempdir().unwrap();
+        let (orchestrator, storage) = orchestrator_with_meta(MetaStore::at_root(tmp.path()));
+        let tracker = RunTracker::open("full_pipeline", Some("blue_moon"));
+        seed_blue_moon(&storage, &[("1", "The Moondogs")]).await;
+        orchestrator.process_source_tracked("blue_moon", &tracker, &RunOptions::default()).await.unwrap();
+        let mut event = storage.get_all_events(None, None).await.unwrap().remove(0);
+
+        // A curator retitles the event; the source's listing still resolves to it
+        event.title = "The Moondogs (record release)".to_string();
+        storage.update_event(&event).await.unwrap();
+        seed_blue_moon(&storage, &[("1", "The Moondogs")]).await;
+        let second = orchestrator.process_source_tracked("blue_moon", &tracker, &RunOptions::default()).await.unwrap();
+        assert_eq!(second.records_cataloged, 1);
+        let events = storage.get_all_events(None, None).await.unwrap();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].id, event.id);
+        assert_eq!(events[0].title, "The Moondogs (record release)");
+    }
+
+    #[tokio::test]
+    async fn consecutive_runs_upsert_events_by_source_record_key() {
+        let tmp = tempfile::tempdir().unwrap();
+        let (orchestrator, storage) = orchestrator_with_meta(MetaStore::at_root(tmp.path()));
+        let tracker = RunTracker::open("full_pipeline", Some("blue_moon"));
+        seed_blue_moon(&storage, &[("1", "The Moondogs")]).await;
+        orchestrator.process_source_tracked("blue_moon", &tracker, &RunOptions::default()).await.unwrap();
+        let cataloged = storage.get_all_events(None, None).await.unwrap();
+
+        // The source retitled the show; its id is unchanged
+        seed_blue_moon(&storage, &[("1", "The Moondogs (sold out)")]).await;
+        let second = orchestrator.process_source_tracked("blue_moon", &tracker, &RunOptions::default()).await.unwrap();
+        assert_eq!(second.records_cataloged, 1);
+
+        let events = storage.get_all_events(None, None).await.unwrap();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].id, cataloged[0].id);
+        assert_eq!(storage.get_all_venues(None, None).await.unwrap().len(), 1);
+        let keys = CatalogKeyIndex::open_at_root(tmp.path()).unwrap();
+        assert_eq!(keys.lookup(&SourceKey::new("blue_moon", "id:1"), "event").unwrap(), cataloged[0].id);
+        assert_eq!(keys.lookup(&SourceKey::new("blue_moon", "venue:bluemoontavern"), "venue").unwrap(), Some(events[0].venue_id));
+    }
+
+    #[tokio::test]
+    async fn events_keep_the_source_external_id() {
+        let tmp = tempfile::tempdir().unwrap();
+        let (orchestrator, storage) = orchestrator_with_meta(MetaStore::at_root(tmp.path()));
+        let tracker = RunTracker::open("full_pipeline", Some("blue_moon"));
+        seed_blue_moon(&storage, &[("bm-1", "The Moondogs")]).await;
+        orchestrator.process_source_tracked("blue_moon", &tracker, &RunOptions::default()).await.unwrap();
+        let mut event = storage.get_all_events(None, None).await.unwrap().remove(0);
+        let blue_moon_id = ExternalId { source_id: "blue_moon".to_string(), id: "bm-1".to_string() };
+        assert_eq!(event.external_ids, std::slice::from_ref(&blue_moon_id));
+
+        // An event cataloged before ids were kept picks its id up on the next run, next to
+        // the ids other sources list it under
+        let other = ExternalId { source_id: "tickets".to_string(), id: "t-9".to_string() };
+        event.external_ids = vec![other.clone()];
+        storage.update_event(&event).await.unwrap();
+        seed_blue_moon(&storage, &[("bm-1", "The Moondogs")]).await;
+        orchestrator.process_source_tracked("blue_moon", &tracker, &RunOptions::default()).await.unwrap();
+        let events = storage.get_all_events(None, None).await.unwrap();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].external_ids, [other, blue_moon_id]);
+    }
+
+    /// Stores a Conor Byrne listing as unprocessed raw data, one entry per `(id, title, ticket_status)`
+    async fn seed_conor_byrne(storage: &InMemoryStorage, events: &[(&str, &str, &str)]) {
+        let event_day = chrono::Utc::now().date_naive() + chrono::Duration::days(7);
+        let data = events
+            .iter()
+            .map(|(id, title, status)| {
+                serde_json::json!({"id": id, "title": title, "event_day": event_day.to_string(), "ticket_status": status})
+            })
+            .collect();
+        let mut raw_data = RawData {
+            id: None,
+            api_name: crate::common::constants::api_name_to_internal("conor_byrne"),
+            event_api_id: "listing".to_string(),
+            event_name: "listing".to_string(),
+            venue_name: "Conor Byrne Pub".to_string(),
+            event_day,
+            data: serde_json::Value::Array(data),
+            processed: false,
+            event_id: None,
+            created_at: chrono::Utc::now(),
+            origin: None,
+        };
+        storage.create_raw_data(&mut raw_data).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn cancelled_shows_are_cataloged_hidden() {
+        let tmp = tempfile::tempdir().unwrap();
+        let (orchestrator, storage) = orchestrator_with_meta(MetaStore::at_root(tmp.path()));
+        let tracker = RunTracker::open("full_pipeline", Some("conor_byrne"));
+        seed_conor_byrne(&storage, &[("1", "The Dip", "on_sale"), ("2", "Kingdom of Birds", "canceled")]).await;
+        orchestrator.process_source_tracked("conor_byrne", &tracker, &RunOptions::default()).await.unwrap();
+
+        let shown = |events: Vec<Event>| {
+            let mut shown: Vec<_> = events.into_iter().map(|e| (e.title, e.show_event)).collect();
+            shown.sort();
+            shown
+        };
+        let events = storage.get_all_events(None, None).await.unwrap();
+        assert_eq!(shown(events), [("Kingdom of Birds".to_string(), false), ("The Dip".to_string(), true)]);
+
+        // A show cancelled after it was cataloged is hidden on the next run
+        seed_conor_byrne(&storage, &[("1", "The Dip", "cancelled"), ("2", "Kingdom of Birds", "canceled")]).await;
+        orchestrator.process_source_tracked("conor_byrne", &tracker, &RunOptions::default()).await.unwrap();
+        let events = storage.get_all_events(None, None).await.unwrap();
+        assert_eq!(shown(events), [("Kingdom of Birds".to_string(), false), ("The Dip".to_string(), false)]);
+    }
+
+    #[tokio::test]
+    async fn catalog_rollback_undoes_a_full_pipeline_run() {
+        use crate::app::catalog_rollback_use_case::{CatalogRollbackUseCase, RollbackAction};
+
+        let (orchestrator, storage) = in_memory_orchestrator();
+        seed_blue_moon(&storage, &[("1", "The Moondogs")]).await;
+        let tracker = RunTracker::open("full_pipeline", Some("blue_moon"));
+        orchestrator.process_source_tracked("blue_moon", &tracker, &RunOptions::default()).await.unwrap();
+
+        let run_id = Uuid::parse_str(tracker.run_id()).unwrap();
+        let rollback = CatalogRollbackUseCase::new(storage.clone());
+        let plan = rollback.plan(run_id).await.unwrap();
+        assert!(plan.run.finished_at.is_some());
+        let step = |entity_type: &str| plan.steps.iter().find(|s| s.entity_type == entity_type).unwrap();
+        assert_eq!(step("event").action, RollbackAction::Hide);
+        assert_eq!(step("venue").action, RollbackAction::Hide);
+        assert_eq!(step("artist").change_type, "CREATE");
+
+        rollback.apply(&plan).await.unwrap();
+        let events = storage.get_all_events(None, None).await.unwrap();
+        assert!(!events[0].show_event);
+    }
+
+    #[tokio::test]
+    async fn listings_match_cataloged_venues_within_the_venue_radius() {
+        use crate::pipeline::processing::venue_resolver::BLUE_MOON;
+
+        // Cataloged as "Blue Moon", geocoded ~80 m from where the resolver has it
+        async fn run(options: RunOptions) -> (Uuid, Vec<Venue>, Vec<Event>) {
+            let (orchestrator, storage) = in_memory_orchestrator();
+            let mut cataloged =
+                Venue { id: None, name: "Blue Moon".to_string(), latitude: BLUE_MOON.latitude + 0.0007, ..BLUE_MOON.to_venue() };
+            storage.create_venue(&mut cataloged).await.unwrap();
+            seed_blue_moon(&storage, &[("1", "The Moondogs")]).await;
+            let tracker = RunTracker::open("full_pipeline", Some("blue_moon"));
+            orchestrator.process_source_tracked("blue_moon", &tracker, &options).await.unwrap();
+            let venues = storage.get_all_venues(None, None).await.unwrap();
+            let events = storage.get_all_events(None, None).await.unwrap();
+            (cataloged.id.unwrap(), venues, events)
+        }
+
+        let (cataloged, venues, events) = run(RunOptions::default()).await;
+        assert_eq!(venues.len(), 1);
+        assert_eq!(events[0].venue_id, cataloged);
+
+        // Outside a 5 m geofence the listing is a venue of its own
+        let strict = ConflatorConfig { venue_radius_m: 5.0, ..ConflatorConfig::default() };
+        let (cataloged, venues, events) = run(RunOptions { conflator: strict, ..RunOptions::default() }).await;
+        assert_eq!(venues.len(), 2);
+        assert_ne!(events[0].venue_id, cataloged);
+        assert_eq!(events[0].venue_id, BLUE_MOON.id());
+    }
+
+    #[tokio::test]
+    async fn envelopes_that_fail_to_parse_are_dead_lettered() {
+        let tmp = tempfile::tempdir().unwrap();
+        let (orchestrator, storage) = orchestrator_with_meta(MetaStore::at_root(tmp.path()));
+        let tracker = RunTracker::open("full_pipeline", Some("blue_moon"));
+        let mut broken = seed_blue_moon_from(&storage, None, &[]).await;
+        broken.id = None;
+        broken.data = serde_json::Value::String("<html>no listing here</html>".to_string());
+        broken.origin = Some(RawDataOrigin {
+            envelope_id: "env-broken".to_string(),
+            payload_ref: "cas:sha256:abcd".to_string(),
+            endpoint_id: None,
+        });
+        storage.create_raw_data(&mut broken).await.unwrap();
+
+        let result = orchestrator.process_source_tracked("blue_moon", &tracker, &RunOptions::default()).await.unwrap();
+        assert_eq!(result.failed_items, 1);
+        let entries = FileDeadLetterStore::new(tmp.path()).list().await.unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].envelope_id, "env-broken");
+        assert_eq!(entries[0].source_id, "blue_moon");
+        assert_eq!(entries[0].payload_ref, "cas:sha256:abcd");
+    }
+
+    #[tokio::test]
+    async fn envelopes_whose_events_are_all_quarantined_end_quarantined() {
+        let tmp = tempfile::tempdir().unwrap();
+        let (orchestrator, storage) = orchestrator_with_meta(MetaStore::at_root(tmp.path()));
+        let origin = |envelope_id: &str| RawDataOrigin {
+            envelope_id: envelope_id.to_string(),
+            payload_ref: format!("cas:sha256:{}", envelope_id),
+            endpoint_id: None,
+        };
+        seed_blue_moon_from(&storage, Some(origin("env-bad")), &[("1", ""), ("2", "")]).await;
+        seed_blue_moon_from(&storage, Some(origin("env-mixed")), &[("3", "The Moondogs"), ("4", "")]).await;
+
+        let tracker = RunTracker::open("full_pipeline", Some("blue_moon"));
+        orchestrator.process_source_tracked("blue_moon", &tracker, &RunOptions::default()).await.unwrap();
+
+        let states = crate::pipeline::ingestion::ingest_meta::IngestMeta::open_at_root(tmp.path()).unwrap();
+        let bad = states.get_envelope_state("env-bad").unwrap().unwrap();
+        assert_eq!((bad.state.as_str(), bad.detail.as_deref()), ("quarantined", Some("quality gate quarantined all 2 events")));
+        assert_eq!(states.get_envelope_state("env-mixed").unwrap().unwrap().state, "cataloged");
+    }
+
+    /// Reads one event off any page, at a fixed price
+    struct OneEventLlm;
+    #[async_trait::async_trait]
+    impl LlmParserPort for OneEventLlm {
+        fn estimate_cost_usd(&self, _text: &str) -> f64 {
+            0.03
+        }
+
+        async fn extract_events(&self, _source_id: &str, text: &str) -> Result<crate::app::ports::LlmParseOutput, String> {
+            assert!(text.contains("The Band"));
+            let event_day = chrono::Utc::now().date_naive() + chrono::Duration::days(7);
+            Ok(crate::app::ports::LlmParseOutput {
+                events: vec![serde_json::json!({"title": "The Band", "event_day": event_day.to_string(), "start_time": "20:00"})],
+                rejected: 0,
+                cost_usd: 0.02,
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn pages_the_parser_cannot_read_fall_back_to_the_llm_within_budget() {
+        let (orchestrator, storage) = in_memory_orchestrator();
+        let orchestrator = orchestrator.with_llm_fallback(Box::new(OneEventLlm), LlmBudget::new(0.04));
+        let mut raw_data = seed_blue_moon(&storage, &[]).await;
+        raw_data.data = serde_json::Value::String("<html><body><h2>The Band</h2><p>Friday, 8pm</p></body></html>".to_string());
+
+        let parsed = orchestrator.parse_raw_data(&raw_data).await.unwrap();
+        assert_eq!(parsed.len(), 1);
+        assert_eq!(parsed[0].event_args.title, "The Band");
+        assert_eq!(parsed[0].event_args.start_time, chrono::NaiveTime::from_hms_opt(20, 0, 0));
+        assert_eq!(parsed[0].raw_data_info.venue_name, "Blue Moon Tavern");
+
+        // $0.02 spent; another $0.03 estimate would exceed the $0.04 budget, so the parse error stands
+        assert!(orchestrator.parse_raw_data(&raw_data).await.is_err());
+    }
+
+    /// Serves a Wix-style Blue Moon listing at `/main` and `/lounge`, one event each
+    async fn serve_two_stages(listener: tokio::net::TcpListener) {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+        let event_day = chrono::Utc::now().date_naive() + chrono::Duration::days(7);
+        loop {
+            let Ok((mut socket, _)) = listener.accept().await else { return };
+            let mut buf = vec![0u8; 4096];
+            let n = socket.read(&mut buf).await.unwrap_or(0);
+            let request = String::from_utf8_lossy(&buf[..n]).to_string();
+            let (id, title) = if request.starts_with("GET /lounge") { ("2", "Lounge Act") } else { ("1", "Main Act") };
+            let body = serde_json::json!({"eventsByDates": {event_day.to_string(): [{"id": id, "title": title}]}}).to_string();
+            let response = format!(
+                "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                body.len(),
+                body
+            );
+            let _ = socket.write_all(response.as_bytes()).await;
+        }
+    }
+
+    #[tokio::test]
+    async fn ingestion_goes_through_the_gateway_for_every_endpoint() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let base = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(serve_two_stages(listener));
+        let spec: crate::pipeline::ingestion::registry::SourceSpecV1 = serde_json::from_value(serde_json::json!({
+            "source_id": "blue_moon",
+            "enabled": true,
+            "endpoints": [
+                {"id": "main", "url": format!("{}/main", base), "method": "GET"},
+                {"id": "lounge", "url": format!("{}/lounge", base), "method": "GET"}
+            ],
+            "content": {"allowed_mime_types": ["application/json"], "max_payload_size_bytes": 10000},
+            "policy": {"license_id": "test"},
+            "parser_plan": {"id": "blue_moon", "version": 1}
+        }))
+        .unwrap();
+        let specs = Arc::new(RegistrySnapshot { specs: vec![spec], generation: 1, loaded_at: chrono::Utc::now() });
+        let tmp = tempfile::tempdir().unwrap();
+        let (orchestrator, storage) = orchestrator_with_meta(MetaStore::at_root(tmp.path()));
+        let orchestrator = orchestrator.with_source_specs(specs);
+
+        let tracker = RunTracker::open("full_pipeline", Some("blue_moon"));
+        let result = orchestrator.process_source_tracked("blue_moon", &tracker, &RunOptions::default()).await.unwrap();
+        assert_eq!((result.total_items, result.records_cataloged), (2, 2));
+
+        let mut origins: Vec<_> = storage
+            .get_processed_raw_data("crawler_blue_moon", None)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|raw| raw.origin.unwrap())
+            .collect();
+        origins.sort_by(|a, b| a.endpoint_id.cmp(&b.endpoint_id));
+        assert_eq!(origins.iter().map(|o| o.endpoint_id.as_deref()).collect::<Vec<_>>(), [Some("lounge"), Some("main")]);
+        assert!(origins.iter().all(|o| o.payload_ref.starts_with("cas:sha256:")));
+
+        let event = storage.get_all_events(None, None).await.unwrap().remove(0);
+        let lineage = LineageStore::open_at_root(tmp.path()).unwrap().latest(event.id.unwrap()).unwrap().unwrap();
+        assert!(origins.iter().any(|o| o.envelope_id == lineage.parsed.envelope_id && o.payload_ref == lineage.parsed.payload_ref));
+
+        let meta = crate::pipeline::ingestion::ingest_meta::IngestMeta::open_at_root(tmp.path()).unwrap();
+        for origin in &origins {
+            let history: Vec<String> =
+                meta.envelope_state_history(&origin.envelope_id).unwrap().into_iter().map(|(state, _, _)| state).collect();
+            assert_eq!(history, ["received", "parsed", "normalized", "cataloged"]);
+        }
+
+        // Once the ingest log lines expire, the lineage the pipeline recorded keeps both payloads
+        let now = chrono::Utc::now() + chrono::Duration::seconds(1);
+        let opts = GcOptions { retention: Some(chrono::Duration::zero()), min_age: chrono::Duration::zero(), dry_run: false };
+        let live = collect_references(tmp.path(), &*storage, opts.retention, now).await.unwrap();
+        let report = gc_local(&tmp.path().join("cas"), &live, &opts, now).unwrap();
+        assert_eq!((report.scanned, report.referenced, report.deleted), (2, 2, 0));
+    }
+
+    /// Serves a Wix-style Blue Moon listing paginated over `/events?page=N` for N in 1..=3,
+    /// one event per page, each page linking to the next
+    async fn serve_listing_pages(listener: tokio::net::TcpListener) {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+        let event_day = chrono::Utc::now().date_naive() + chrono::Duration::days(7);
+        loop {
+            let Ok((mut socket, _)) = listener.accept().await else { return };
+            let mut buf = vec![0u8; 4096];
+            let n = socket.read(&mut buf).await.unwrap_or(0);
+            let request = String::from_utf8_lossy(&buf[..n]).to_string();
+            let page: u32 = request
+                .split_whitespace()
+                .nth(1)
+                .and_then(|path| path.rsplit("page=").next())
+                .and_then(|p| p.parse().ok())
+                .unwrap_or(1);
+            let link = if page < 3 { format!("Link: </events?page={}>; rel=\"next\"\r\n", page + 1) } else { String::new() };
+            let event = serde_json::json!({"id": page.to_string(), "title": format!("Act {}", page)});
+            let body = serde_json::json!({"eventsByDates": {event_day.to_string(): [event]}}).to_string();
+            let response = format!(
+                "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\n{}Content-Length: {}\r\nConnection: close\r\n\r\n{}",
+                link,
+                body.len(),
+                body
+            );
+            let _ = socket.write_all(response.as_bytes()).await;
+        }
+    }
+
+    #[tokio::test]
+    async fn every_page_of_a_paginated_source_is_cataloged() {
+        for mode in ["multi_part", "envelope_per_page"] {
+            let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+            let url = format!("http://{}/events?page=1", listener.local_addr().unwrap());
+            tokio::spawn(serve_listing_pages(listener));
+            let spec: crate::pipeline::ingestion::registry::SourceSpecV1 = serde_json::from_value(serde_json::json!({
+                "source_id": "blue_moon",
+                "enabled": true,
+                "endpoints": [{"url": url, "method": "GET", "pagination": {"max_pages": 3, "mode": mode}}],
+                "content": {"allowed_mime_types": ["application/json"], "max_payload_size_bytes": 10000},
+                "policy": {"license_id": "test"},
+                "parser_plan": {"id": "blue_moon", "version": 1}
+            }))
+            .unwrap();
+            let specs = Arc::new(RegistrySnapshot { specs: vec![spec], generation: 1, loaded_at: chrono::Utc::now() });
+            let tmp = tempfile::tempdir().unwrap();
+            let (orchestrator, storage) = orchestrator_with_meta(MetaStore::at_root(tmp.path()));
+            let orchestrator = orchestrator.with_source_specs(specs);
+
+            let tracker = RunTracker::open("full_pipeline", Some("blue_moon"));
+            let result = orchestrator.process_source_tracked("blue_moon", &tracker, &RunOptions::default()).await.unwrap();
+            assert_eq!((result.total_items, result.records_cataloged), (3, 3), "{}", mode);
+            let mut titles: Vec<_> =
+                storage.get_all_events(None, None).await.unwrap().into_iter().map(|event| event.title).collect();
+            titles.sort();
+            assert_eq!(titles, ["Act 1", "Act 2", "Act 3"], "{}", mode);
+        }
+    }
+}
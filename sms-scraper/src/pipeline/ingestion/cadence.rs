@@ -0,0 +1,382 @@
+use crate::pipeline::ingestion::ingest_meta::IngestMeta;
+use crate::pipeline::ingestion::registry::{load_source_spec, BlackoutSpec, CadenceSpec};
+use chrono::{DateTime, Datelike, Duration, NaiveDate, NaiveDateTime, NaiveTime, TimeZone, Timelike, Utc, Weekday};
+use chrono_tz::Tz;
+use serde::Serialize;
+use std::path::Path;
+
+/// Minimum time between fetches for sources without a cadence in the registry
+pub const DEFAULT_MIN_INTERVAL_SECS: i64 = 12 * 60 * 60;
+
+/// How far ahead to look for the next cron tick before giving up on an expression
+/// that can never fire (e.g. "0 0 31 2 *")
+const MAX_LOOKAHEAD_DAYS: i64 = 366 * 5;
+
+/// When a source may be fetched, built from its registry `cadence`
+#[derive(Debug, Clone)]
+pub struct CadencePolicy {
+    trigger: Trigger,
+    timezone: Tz,
+    blackouts: Vec<Blackout>,
+}
+
+#[derive(Debug, Clone)]
+enum Trigger {
+    /// Fetch again once this long has passed since the last fetch
+    Interval(Duration),
+    /// Fetch again once a scheduled tick has passed since the last fetch
+    Cron(CronSchedule),
+}
+
+impl CadencePolicy {
+    /// The registry's cadence, or the default interval when the source has none
+    pub fn from_spec(spec: Option<&CadenceSpec>) -> Result<Self, String> {
+        let (cron, timezone, blackouts) = match spec {
+            None => return Ok(Self::interval(DEFAULT_MIN_INTERVAL_SECS)),
+            Some(CadenceSpec::Cron(cron)) => (cron.as_str(), None, &[][..]),
+            Some(CadenceSpec::Policy(policy)) => {
+                (policy.cron.as_str(), policy.timezone.as_deref(), policy.blackouts.as_slice())
+            }
+        };
+        let timezone = match timezone {
+            Some(name) => name.parse::<Tz>().map_err(|_| format!("unknown cadence timezone '{}'", name))?,
+            None => Tz::UTC,
+        };
+        Ok(Self {
+            trigger: Trigger::Cron(CronSchedule::parse(cron)?),
+            timezone,
+            blackouts: blackouts.iter().map(Blackout::from_spec).collect::<Result<_, _>>()?,
+        })
+    }
+
+    /// At most one fetch per `min_interval_secs`, at any time of day
+    pub fn interval(min_interval_secs: i64) -> Self {
+        Self {
+            trigger: Trigger::Interval(Duration::seconds(min_interval_secs)),
+            timezone: Tz::UTC,
+            blackouts: Vec::new(),
+        }
+    }
+
+    /// Earliest time at or after `now` the source may be fetched, or `None` if the schedule
+    /// never fires. A source that has never been fetched is due immediately.
+    pub fn next_eligible(&self, last_fetched: Option<DateTime<Utc>>, now: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        let due = match (last_fetched, &self.trigger) {
+            (None, _) => now,
+            (Some(last), Trigger::Interval(interval)) => last + *interval,
+            (Some(last), Trigger::Cron(schedule)) => {
+                let local = last.with_timezone(&self.timezone).naive_local();
+                let tick = schedule.next_after(local)?;
+                to_utc(&self.timezone, tick)?
+            }
+        };
+        self.after_blackouts(due.max(now))
+    }
+
+    /// Whether the source may be fetched right now
+    pub fn is_due(&self, last_fetched: Option<DateTime<Utc>>, now: DateTime<Utc>) -> bool {
+        self.next_eligible(last_fetched, now).is_some_and(|t| t <= now)
+    }
+
+    /// The `cadence_skip` error message when the source isn't due, `None` when it is
+    pub fn skip_reason(&self, last_fetched: Option<DateTime<Utc>>, now: DateTime<Utc>) -> Option<String> {
+        match self.next_eligible(last_fetched, now) {
+            Some(at) if at <= now => None,
+            Some(at) => Some(format!("cadence_skip: next eligible at {}", at.to_rfc3339())),
+            None => Some("cadence_skip: cadence schedule never fires".to_string()),
+        }
+    }
+
+    /// `at`, or the end of the blackout it falls in (repeatedly, for back-to-back windows)
+    fn after_blackouts(&self, mut at: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        for _ in 0..=self.blackouts.len() * 8 {
+            let local = at.with_timezone(&self.timezone).naive_local();
+            let Some(end) = self.blackouts.iter().find_map(|b| b.end_if_inside(local)) else {
+                return Some(at);
+            };
+            at = to_utc(&self.timezone, end)?;
+        }
+        None
+    }
+
+    /// Short human-readable form for listings
+    pub fn describe(&self) -> String {
+        match &self.trigger {
+            Trigger::Interval(interval) => format!("every {}h", interval.num_hours()),
+            Trigger::Cron(schedule) => {
+                let mut out = format!("cron \"{}\" {}", schedule.expression, self.timezone);
+                if !self.blackouts.is_empty() {
+                    out.push_str(&format!(" ({} blackout(s))", self.blackouts.len()));
+                }
+                out
+            }
+        }
+    }
+}
+
+/// A registry source's cadence and when it may next be fetched
+#[derive(Debug, Clone, Serialize)]
+pub struct SourceSchedule {
+    pub source_id: String,
+    pub enabled: bool,
+    /// `CadencePolicy::describe`, or why the registry cadence is invalid
+    pub cadence: String,
+    pub last_fetched_at: Option<DateTime<Utc>>,
+    /// `None` when the cadence is invalid or never fires
+    pub next_eligible_at: Option<DateTime<Utc>>,
+}
+
+/// Schedules for every source in `registry_dir`, sorted by source id
+pub fn source_schedules(registry_dir: &Path, data_root: &Path, now: DateTime<Utc>) -> anyhow::Result<Vec<SourceSchedule>> {
+    let meta = IngestMeta::open_at_root(data_root)?;
+    let mut schedules = Vec::new();
+    for entry in std::fs::read_dir(registry_dir)? {
+        let path = entry?.path();
+        if path.extension().and_then(|e| e.to_str()) != Some("json") {
+            continue;
+        }
+        let spec = load_source_spec(&path)
+            .map_err(|e| anyhow::anyhow!("invalid registry entry {}: {}", path.display(), e))?;
+        let last_fetched_at = meta
+            .get_last_fetched_at(&spec.source_id)?
+            .and_then(|ts| DateTime::from_timestamp(ts, 0));
+        let (cadence, next_eligible_at) = match CadencePolicy::from_spec(spec.cadence.as_ref()) {
+            Ok(policy) => (policy.describe(), policy.next_eligible(last_fetched_at, now)),
+            Err(e) => (format!("invalid: {}", e), None),
+        };
+        schedules.push(SourceSchedule {
+            source_id: spec.source_id,
+            enabled: spec.enabled,
+            cadence,
+            last_fetched_at,
+            next_eligible_at,
+        });
+    }
+    schedules.sort_by(|a, b| a.source_id.cmp(&b.source_id));
+    Ok(schedules)
+}
+
+fn to_utc(tz: &Tz, local: NaiveDateTime) -> Option<DateTime<Utc>> {
+    // A local time skipped by a DST change resolves to an hour later
+    tz.from_local_datetime(&local)
+        .earliest()
+        .or_else(|| tz.from_local_datetime(&(local + Duration::hours(1))).earliest())
+        .map(|t| t.with_timezone(&Utc))
+}
+
+/// Standard five-field cron expression: minute hour day-of-month month day-of-week
+#[derive(Debug, Clone)]
+struct CronSchedule {
+    expression: String,
+    minutes: Vec<bool>,
+    hours: Vec<bool>,
+    days_of_month: Vec<bool>,
+    months: Vec<bool>,
+    /// Indexed from Sunday = 0
+    days_of_week: Vec<bool>,
+    /// Whether day-of-month / day-of-week were restricted; when both are, a day matching
+    /// either fires (classic cron semantics)
+    dom_restricted: bool,
+    dow_restricted: bool,
+}
+
+impl CronSchedule {
+    fn parse(expression: &str) -> Result<Self, String> {
+        let fields: Vec<&str> = expression.split_whitespace().collect();
+        let [minute, hour, dom, month, dow] = fields[..] else {
+            return Err(format!("cron expression '{}' must have 5 fields", expression));
+        };
+        let mut days_of_week = parse_field(dow, 0, 7)?;
+        // 7 is an alias for Sunday
+        if days_of_week[7] {
+            days_of_week[0] = true;
+        }
+        days_of_week.truncate(7);
+        Ok(Self {
+            expression: expression.to_string(),
+            minutes: parse_field(minute, 0, 59)?,
+            hours: parse_field(hour, 0, 23)?,
+            days_of_month: parse_field(dom, 1, 31)?,
+            months: parse_field(month, 1, 12)?,
+            days_of_week,
+            dom_restricted: dom != "*",
+            dow_restricted: dow != "*",
+        })
+    }
+
+    fn matches_day(&self, date: NaiveDate) -> bool {
+        if !self.months[date.month() as usize] {
+            return false;
+        }
+        let dom = self.days_of_month[date.day() as usize];
+        let dow = self.days_of_week[date.weekday().num_days_from_sunday() as usize];
+        match (self.dom_restricted, self.dow_restricted) {
+            (true, true) => dom || dow,
+            (true, false) => dom,
+            (false, true) => dow,
+            (false, false) => true,
+        }
+    }
+
+    /// First tick strictly after `after`
+    fn next_after(&self, after: NaiveDateTime) -> Option<NaiveDateTime> {
+        let start = after.with_second(0)?.with_nanosecond(0)? + Duration::minutes(1);
+        let mut date = start.date();
+        for day in 0..MAX_LOOKAHEAD_DAYS {
+            if self.matches_day(date) {
+                let (first_hour, first_minute) = if day == 0 { (start.hour(), start.minute()) } else { (0, 0) };
+                for hour in first_hour..24 {
+                    if !self.hours[hour as usize] {
+                        continue;
+                    }
+                    let from_minute = if hour == first_hour { first_minute } else { 0 };
+                    if let Some(minute) = (from_minute..60).find(|m| self.minutes[*m as usize]) {
+                        return date.and_hms_opt(hour, minute, 0);
+                    }
+                }
+            }
+            date = date.succ_opt()?;
+        }
+        None
+    }
+}
+
+/// Parse one cron field into a lookup table indexed by value (`*`, `n`, `a-b`, `*/s`, `a-b/s`,
+/// comma-separated)
+fn parse_field(field: &str, min: u32, max: u32) -> Result<Vec<bool>, String> {
+    let mut allowed = vec![false; max as usize + 1];
+    for part in field.split(',') {
+        let (range, step) = match part.split_once('/') {
+            Some((range, step)) => {
+                let step: u32 = step.parse().map_err(|_| format!("invalid cron step in '{}'", part))?;
+                if step == 0 {
+                    return Err(format!("invalid cron step in '{}'", part));
+                }
+                (range, step)
+            }
+            None => (part, 1),
+        };
+        let (lo, hi) = match range {
+            "*" => (min, max),
+            _ => match range.split_once('-') {
+                Some((lo, hi)) => (parse_value(lo, part)?, parse_value(hi, part)?),
+                None => {
+                    let value = parse_value(range, part)?;
+                    // "5/15" means every 15 starting at 5
+                    (value, if step > 1 { max } else { value })
+                }
+            },
+        };
+        if lo < min || hi > max || lo > hi {
+            return Err(format!("cron value out of range in '{}' (expected {}-{})", part, min, max));
+        }
+        for value in (lo..=hi).step_by(step as usize) {
+            allowed[value as usize] = true;
+        }
+    }
+    Ok(allowed)
+}
+
+fn parse_value(value: &str, part: &str) -> Result<u32, String> {
+    value.parse().map_err(|_| format!("invalid cron value in '{}'", part))
+}
+
+/// A recurring local-time window in which fetching is not allowed
+#[derive(Debug, Clone)]
+struct Blackout {
+    /// Empty means every day
+    days: Vec<Weekday>,
+    start: NaiveTime,
+    end: NaiveTime,
+}
+
+impl Blackout {
+    fn from_spec(spec: &BlackoutSpec) -> Result<Self, String> {
+        let time = |s: &str| {
+            NaiveTime::parse_from_str(s, "%H:%M").map_err(|_| format!("invalid blackout time '{}'", s))
+        };
+        let days = spec
+            .days
+            .iter()
+            .map(|d| d.parse::<Weekday>().map_err(|_| format!("invalid blackout day '{}'", d)))
+            .collect::<Result<_, _>>()?;
+        Ok(Self { days, start: time(&spec.start)?, end: time(&spec.end)? })
+    }
+
+    fn applies_on(&self, day: Weekday) -> bool {
+        self.days.is_empty() || self.days.contains(&day)
+    }
+
+    /// End of this window if `local` falls inside it. Days name the day a window starts on.
+    fn end_if_inside(&self, local: NaiveDateTime) -> Option<NaiveDateTime> {
+        let (date, time) = (local.date(), local.time());
+        if self.start < self.end {
+            return (self.applies_on(date.weekday()) && time >= self.start && time < self.end)
+                .then(|| date.and_time(self.end));
+        }
+        // Window runs past midnight
+        if self.applies_on(date.weekday()) && time >= self.start {
+            return Some(date.succ_opt()?.and_time(self.end));
+        }
+        let yesterday = date.pred_opt()?;
+        (self.applies_on(yesterday.weekday()) && time < self.end).then(|| date.and_time(self.end))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pipeline::ingestion::registry::CadencePolicySpec;
+
+    fn utc(s: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(s).unwrap().with_timezone(&Utc)
+    }
+
+    #[test]
+    fn cron_cadence_waits_for_the_next_tick() {
+        let policy = CadencePolicy::from_spec(Some(&CadenceSpec::Cron("0 6,18 * * *".into()))).unwrap();
+        let last = Some(utc("2025-08-15T06:00:30Z"));
+
+        assert!(policy.is_due(None, utc("2025-08-15T07:00:00Z")));
+        assert!(!policy.is_due(last, utc("2025-08-15T17:59:00Z")));
+        assert_eq!(policy.next_eligible(last, utc("2025-08-15T12:00:00Z")), Some(utc("2025-08-15T18:00:00Z")));
+        // A missed tick leaves the source due until it's fetched
+        assert!(policy.is_due(last, utc("2025-08-16T02:00:00Z")));
+
+        let weekdays = CadencePolicy::from_spec(Some(&CadenceSpec::Cron("30 9 * * 1-5".into()))).unwrap();
+        // Friday 2025-08-15 → Monday
+        assert_eq!(
+            weekdays.next_eligible(Some(utc("2025-08-15T10:00:00Z")), utc("2025-08-15T10:00:00Z")),
+            Some(utc("2025-08-18T09:30:00Z"))
+        );
+
+        assert!(CadencePolicy::from_spec(Some(&CadenceSpec::Cron("0 6 * *".into()))).is_err());
+        assert!(CadencePolicy::from_spec(Some(&CadenceSpec::Cron("0 25 * * *".into()))).is_err());
+        let never = CadencePolicy::from_spec(Some(&CadenceSpec::Cron("0 0 31 2 *".into()))).unwrap();
+        assert_eq!(never.next_eligible(Some(utc("2025-01-01T00:00:00Z")), utc("2025-01-01T00:00:00Z")), None);
+    }
+
+    #[test]
+    fn blackouts_push_the_next_eligible_time_back() {
+        let spec = CadenceSpec::Policy(CadencePolicySpec {
+            cron: "0 * * * *".into(),
+            timezone: Some("America/Los_Angeles".into()),
+            blackouts: vec![BlackoutSpec { days: vec![], start: "22:00".into(), end: "06:00".into() }],
+        });
+        let policy = CadencePolicy::from_spec(Some(&spec)).unwrap();
+
+        // 23:30 PDT is inside the overnight blackout, which ends at 06:00 PDT (13:00Z)
+        let now = utc("2025-08-16T06:30:00Z");
+        assert!(!policy.is_due(None, now));
+        assert_eq!(policy.next_eligible(None, now), Some(utc("2025-08-16T13:00:00Z")));
+        assert!(policy.is_due(None, utc("2025-08-16T18:00:00Z")));
+    }
+
+    #[test]
+    fn sources_without_cadence_use_the_default_interval() {
+        let policy = CadencePolicy::from_spec(None).unwrap();
+        let last = utc("2025-08-15T00:00:00Z");
+        assert!(!policy.is_due(Some(last), utc("2025-08-15T11:59:00Z")));
+        assert!(policy.is_due(Some(last), utc("2025-08-15T12:00:00Z")));
+    }
+}
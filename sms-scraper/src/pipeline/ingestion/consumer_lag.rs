@@ -0,0 +1,104 @@
+use crate::observability::metrics;
+use crate::pipeline::ingestion::ingest_log_reader::IngestLogReader;
+use crate::pipeline::ingestion::source_status::PARSE_CONSUMER;
+use std::path::PathBuf;
+use std::time::Duration;
+use tracing::{info, warn};
+
+/// The `parse log` consumer, which acks each envelope it handles
+pub const DEFAULT_CONSUMERS: &str = PARSE_CONSUMER;
+pub const DEFAULT_INTERVAL_SECS: u64 = 30;
+pub const CONSUMERS_ENV: &str = "SMS_INGEST_LOG_CONSUMERS";
+pub const INTERVAL_ENV: &str = "SMS_CONSUMER_LAG_INTERVAL_SECS";
+
+/// Which consumers to watch and how often, from `SMS_INGEST_LOG_CONSUMERS` (comma-separated)
+/// and `SMS_CONSUMER_LAG_INTERVAL_SECS`
+#[derive(Debug, Clone)]
+pub struct ConsumerLagConfig {
+    pub consumers: Vec<String>,
+    pub interval: Duration,
+}
+
+impl ConsumerLagConfig {
+    pub fn from_env() -> Self {
+        let consumers = std::env::var(CONSUMERS_ENV).unwrap_or_else(|_| DEFAULT_CONSUMERS.to_string());
+        let secs = std::env::var(INTERVAL_ENV)
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .filter(|secs| *secs > 0)
+            .unwrap_or(DEFAULT_INTERVAL_SECS);
+        Self {
+            consumers: consumers.split(',').map(str::trim).filter(|c| !c.is_empty()).map(str::to_string).collect(),
+            interval: Duration::from_secs(secs),
+        }
+    }
+}
+
+/// Read each consumer's offset and the log end once and publish them as gauges; returns
+/// (consumer, lag bytes) for the consumers that could be read
+pub fn publish_consumer_lag(reader: &IngestLogReader, consumers: &[String]) -> Vec<(String, u64)> {
+    let mut published = Vec::new();
+    for consumer in consumers {
+        match reader.status(consumer) {
+            Ok((offset, end, lag)) => {
+                metrics::ingest_log::consumer_position(consumer, offset.byte_offset, end);
+                published.push((consumer.clone(), lag));
+            }
+            Err(e) => warn!("consumer_lag: failed to read position for consumer={}: {}", consumer, e),
+        }
+    }
+    metrics::ingest_log::active_consumers(published.len());
+    published
+}
+
+/// Spawn a background task publishing consumer lag every `config.interval` for long-running
+/// processes (the API server, `gateway-all --every`); dropping the monitor stops it
+pub fn spawn_consumer_lag_monitor(data_root: impl Into<PathBuf>, config: ConsumerLagConfig) -> ConsumerLagMonitor {
+    let data_root = data_root.into();
+    info!(
+        "Publishing ingest log lag for {:?} every {}s",
+        config.consumers,
+        config.interval.as_secs()
+    );
+    let task = tokio::spawn(async move {
+        let reader = IngestLogReader::new(data_root);
+        let mut ticker = tokio::time::interval(config.interval);
+        loop {
+            ticker.tick().await;
+            publish_consumer_lag(&reader, &config.consumers);
+        }
+    });
+    ConsumerLagMonitor { task }
+}
+
+/// Keeps consumer lag published; dropping it stops the background task
+pub struct ConsumerLagMonitor {
+    task: tokio::task::JoinHandle<()>,
+}
+
+impl Drop for ConsumerLagMonitor {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pipeline::ingestion::ingest_log_backend::{IngestLogBackend, LocalIngestLog};
+    use std::sync::Arc;
+
+    #[test]
+    fn publishes_lag_between_each_consumer_offset_and_the_log_end() {
+        let tmp = tempfile::tempdir().unwrap();
+        let log = Arc::new(LocalIngestLog::new(tmp.path()));
+        log.append("{\"envelope_id\":\"a\"}").unwrap();
+        log.append("{\"envelope_id\":\"b\"}").unwrap();
+        let end = log.end_offset().unwrap();
+        log.save_offset("parser", 10, Some("a")).unwrap();
+
+        let reader = IngestLogReader::with_backend(tmp.path(), log);
+        let lag = publish_consumer_lag(&reader, &["parser".to_string(), "fresh".to_string()]);
+        assert_eq!(lag, [("parser".to_string(), end - 10), ("fresh".to_string(), end)]);
+    }
+}
@@ -0,0 +1,110 @@
+use std::io::Read;
+
+/// Why a response body could not be turned into payload bytes
+#[derive(Debug, thiserror::Error)]
+pub enum BodyError {
+    #[error("payload_too_large: exceeds {limit} bytes")]
+    TooLarge { limit: u64 },
+    #[error("unsupported content-encoding: {0}")]
+    UnsupportedEncoding(String),
+    #[error("failed to decode {encoding} body: {source}")]
+    Decode { encoding: String, source: std::io::Error },
+    #[error("failed to read response body: {0}")]
+    Http(#[from] reqwest::Error),
+}
+
+/// Response body as received and after content-encoding was removed
+#[derive(Debug)]
+pub struct DecodedBody {
+    pub compressed_len: usize,
+    pub bytes: Vec<u8>,
+}
+
+/// Read a response body chunk by chunk, giving up as soon as it exceeds `max_bytes`
+/// (before or after decompression) instead of buffering an arbitrarily large download.
+pub async fn read_body_limited(mut resp: reqwest::Response, max_bytes: u64) -> Result<DecodedBody, BodyError> {
+    if resp.content_length().is_some_and(|len| len > max_bytes) {
+        return Err(BodyError::TooLarge { limit: max_bytes });
+    }
+    let encoding = resp
+        .headers()
+        .get(reqwest::header::CONTENT_ENCODING)
+        .and_then(|v| v.to_str().ok())
+        .map(|s| s.to_string());
+
+    let mut raw = Vec::new();
+    while let Some(chunk) = resp.chunk().await? {
+        if (raw.len() + chunk.len()) as u64 > max_bytes {
+            return Err(BodyError::TooLarge { limit: max_bytes });
+        }
+        raw.extend_from_slice(&chunk);
+    }
+    let compressed_len = raw.len();
+    let bytes = decode(encoding.as_deref(), raw, max_bytes)?;
+    Ok(DecodedBody { compressed_len, bytes })
+}
+
+/// Undo a `Content-Encoding` (gzip, deflate, br or identity), capping the decoded size
+pub fn decode(encoding: Option<&str>, raw: Vec<u8>, max_bytes: u64) -> Result<Vec<u8>, BodyError> {
+    let encoding = encoding.map(|e| e.trim().to_ascii_lowercase()).unwrap_or_default();
+    match encoding.as_str() {
+        "" | "identity" => Ok(raw),
+        "gzip" | "x-gzip" => read_capped(&encoding, flate2::read::MultiGzDecoder::new(&raw[..]), max_bytes),
+        // Servers disagree on whether deflate means zlib-wrapped or raw; try zlib first
+        "deflate" => read_capped(&encoding, flate2::read::ZlibDecoder::new(&raw[..]), max_bytes)
+            .or_else(|_| read_capped(&encoding, flate2::read::DeflateDecoder::new(&raw[..]), max_bytes)),
+        "br" => read_capped(&encoding, brotli::Decompressor::new(&raw[..], 4096), max_bytes),
+        other => Err(BodyError::UnsupportedEncoding(other.to_string())),
+    }
+}
+
+fn read_capped(encoding: &str, reader: impl Read, max_bytes: u64) -> Result<Vec<u8>, BodyError> {
+    let mut out = Vec::new();
+    reader
+        .take(max_bytes + 1)
+        .read_to_end(&mut out)
+        .map_err(|source| BodyError::Decode { encoding: encoding.to_string(), source })?;
+    if out.len() as u64 > max_bytes {
+        return Err(BodyError::TooLarge { limit: max_bytes });
+    }
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    fn gzip(bytes: &[u8]) -> Vec<u8> {
+        let mut enc = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+        enc.write_all(bytes).unwrap();
+        enc.finish().unwrap()
+    }
+
+    #[test]
+    fn decodes_each_supported_encoding() {
+        let body = b"<html>events</html>".repeat(20);
+
+        assert_eq!(decode(None, body.clone(), 10_000).unwrap(), body);
+        assert_eq!(decode(Some("gzip"), gzip(&body), 10_000).unwrap(), body);
+
+        let mut zlib = flate2::write::ZlibEncoder::new(Vec::new(), flate2::Compression::default());
+        zlib.write_all(&body).unwrap();
+        assert_eq!(decode(Some("deflate"), zlib.finish().unwrap(), 10_000).unwrap(), body);
+
+        let mut br = Vec::new();
+        brotli::CompressorWriter::new(&mut br, 4096, 5, 22).write_all(&body).unwrap();
+        assert_eq!(decode(Some("br"), br, 10_000).unwrap(), body);
+
+        assert!(matches!(decode(Some("zstd"), body, 10_000), Err(BodyError::UnsupportedEncoding(_))));
+    }
+
+    #[test]
+    fn decoded_size_is_capped() {
+        // Highly compressible body that is small on the wire but large once decoded
+        let body = vec![b'a'; 50_000];
+        let compressed = gzip(&body);
+        assert!(compressed.len() < 1_000);
+        assert!(matches!(decode(Some("gzip"), compressed, 10_000), Err(BodyError::TooLarge { limit: 10_000 })));
+    }
+}
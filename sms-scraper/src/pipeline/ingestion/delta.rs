@@ -0,0 +1,389 @@
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::Serialize;
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use sms_core::common::record_key::SourceKey;
+use sms_parsers::{ParsedRecord, RecordChange};
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::Mutex;
+
+/// Fields that identify a record on their own, in order of preference
+const IDENTITY_FIELDS: &[&str] = &["id", "event_id", "eventId", "uid", "event_url", "eventUrl", "url", "link", "href"];
+const TITLE_FIELDS: &[&str] = &["title", "name", "event_name"];
+const DATE_FIELDS: &[&str] = &["event_day", "date", "start_date", "startDate", "start", "event_date"];
+
+/// What changed between a source's previous payload and the fetch that superseded it
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct PayloadDelta {
+    pub source_id: String,
+    /// Set when only this endpoint of a multi-endpoint source was diffed
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub endpoint_id: Option<String>,
+    /// The latest envelope of the fetch; a fetch paginated into one envelope per page has several
+    pub envelope_id: String,
+    /// The envelope this one superseded; `None` for a source's first diffed payload
+    pub previous_envelope_id: Option<String>,
+    pub added: usize,
+    pub changed: usize,
+    pub unchanged: usize,
+    /// Records from the previous payload that are gone, tagged `Removed`
+    pub removed: Vec<ParsedRecord>,
+    /// Why the fetch wasn't diffed, when it wasn't
+    pub skipped: Option<String>,
+}
+
+/// The snapshot a fetch is diffed against: the source's, or for a multi-endpoint source the
+/// endpoint's, so endpoints never read as removing each other's records
+pub fn delta_key(source_id: &str, endpoint_id: Option<&str>) -> String {
+    match endpoint_id {
+        Some(endpoint_id) => format!("{}#{}", source_id, endpoint_id),
+        None => source_id.to_string(),
+    }
+}
+
+/// Keeps each source's latest parsed records so the next fetch for that source can be
+/// diffed against them. Lives next to the gateway's ingest metadata.
+pub struct DeltaStore {
+    conn: Mutex<Connection>,
+}
+
+/// One fetch being diffed: records are tagged as they're observed, across every envelope and
+/// page of the fetch, and removals are worked out by `DeltaStore::finish` once all are seen
+pub struct FetchDiff {
+    key: String,
+    /// When the fetch was accepted; the latest of its envelopes
+    recorded_at: i64,
+    /// The snapshot's own time, when there is one
+    snapshot_at: Option<i64>,
+    previous: HashMap<SourceKey, (String, String)>,
+    current: HashMap<SourceKey, (String, String)>,
+    delta: PayloadDelta,
+}
+
+impl FetchDiff {
+    /// Tag `record` against the previous snapshot and add it to the new one. Fallback records
+    /// (an `html_len` placeholder) are left untagged, as is everything in a fetch accepted
+    /// before the snapshot it would replace.
+    pub fn observe(&mut self, record: &mut ParsedRecord, recorded_at: i64) -> anyhow::Result<()> {
+        self.recorded_at = self.recorded_at.max(recorded_at);
+        self.delta.envelope_id = record.envelope_id.clone();
+        if is_fallback(&record.record) || self.is_stale() {
+            return Ok(());
+        }
+        let key = parsed_record_key(record);
+        let print = fingerprint(&record.record);
+        let change = match self.previous.get(&key) {
+            None => RecordChange::Added,
+            Some((old, _)) if *old == print => RecordChange::Unchanged,
+            Some(_) => RecordChange::Changed,
+        };
+        match change {
+            RecordChange::Added => self.delta.added += 1,
+            RecordChange::Changed => self.delta.changed += 1,
+            _ => self.delta.unchanged += 1,
+        }
+        let mut stored = record.clone();
+        stored.change = None;
+        self.current.insert(key, (print, serde_json::to_string(&stored)?));
+        record.change = Some(change);
+        Ok(())
+    }
+
+    fn is_stale(&self) -> bool {
+        self.snapshot_at.is_some_and(|at| at > self.recorded_at)
+    }
+}
+
+impl DeltaStore {
+    pub fn open_at_root<P: AsRef<Path>>(data_root: P) -> anyhow::Result<Self> {
+        let db_path = data_root.as_ref().join("ingest_log").join("delta.db");
+        if let Some(parent) = db_path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let conn = Connection::open(db_path)?;
+        conn.execute_batch(
+            r#"
+            PRAGMA journal_mode=WAL;
+            CREATE TABLE IF NOT EXISTS source_snapshot (
+                source_id   TEXT PRIMARY KEY,
+                envelope_id TEXT NOT NULL,
+                recorded_at INTEGER NOT NULL
+            );
+            CREATE TABLE IF NOT EXISTS snapshot_records (
+                source_id   TEXT NOT NULL,
+                record_key  TEXT NOT NULL,
+                fingerprint TEXT NOT NULL,
+                record      TEXT NOT NULL,
+                PRIMARY KEY (source_id, record_key)
+            );
+            "#,
+        )?;
+        Ok(Self { conn: Mutex::new(conn) })
+    }
+
+    /// Start diffing a fetch of `source_id` (or one endpoint of it) accepted at `recorded_at`
+    pub fn begin(&self, source_id: &str, endpoint_id: Option<&str>, recorded_at: i64) -> anyhow::Result<FetchDiff> {
+        let key = delta_key(source_id, endpoint_id);
+        let conn = self.conn.lock().map_err(|_| anyhow::anyhow!("delta store lock poisoned"))?;
+        let snapshot: Option<(String, i64)> = conn
+            .query_row(
+                "SELECT envelope_id, recorded_at FROM source_snapshot WHERE source_id = ?1",
+                params![key],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .optional()?;
+
+        let mut previous: HashMap<SourceKey, (String, String)> = HashMap::new();
+        let mut stmt = conn.prepare(
+            "SELECT record_key, fingerprint, record FROM snapshot_records WHERE source_id = ?1",
+        )?;
+        let rows = stmt.query_map(params![key], |row| {
+            Ok((row.get::<_, String>(0)?, (row.get::<_, String>(1)?, row.get::<_, String>(2)?)))
+        })?;
+        for row in rows {
+            let (record_key, value) = row?;
+            previous.insert(SourceKey::new(source_id, record_key), value);
+        }
+
+        Ok(FetchDiff {
+            key,
+            recorded_at,
+            snapshot_at: snapshot.as_ref().map(|(_, at)| *at),
+            previous,
+            current: HashMap::new(),
+            delta: PayloadDelta {
+                source_id: source_id.to_string(),
+                endpoint_id: endpoint_id.map(str::to_string),
+                previous_envelope_id: snapshot.map(|(envelope_id, _)| envelope_id),
+                ..Default::default()
+            },
+        })
+    }
+
+    /// Work out which of the snapshot's records the fetch no longer has and make the fetch the
+    /// new snapshot. A fetch that produced no real records (a failed parse or only `html_len`
+    /// fallbacks) doesn't replace the snapshot, so a broken fetch never reads as every event
+    /// removed, and neither does one accepted before the snapshot was.
+    pub fn finish(&self, fetch: FetchDiff) -> anyhow::Result<PayloadDelta> {
+        let FetchDiff { key, recorded_at, snapshot_at, previous, current, mut delta } = fetch;
+        if let Some(at) = snapshot_at.filter(|at| *at > recorded_at) {
+            delta.added = 0;
+            delta.changed = 0;
+            delta.unchanged = 0;
+            delta.skipped = Some(format!(
+                "accepted at {} before the snapshot of {} at {}",
+                recorded_at,
+                delta.previous_envelope_id.as_deref().unwrap_or("-"),
+                at
+            ));
+            return Ok(delta);
+        }
+        if current.is_empty() {
+            delta.skipped = Some("no records extracted".to_string());
+            return Ok(delta);
+        }
+
+        let mut removed_keys: Vec<&SourceKey> = previous.keys().filter(|k| !current.contains_key(*k)).collect();
+        removed_keys.sort();
+        for record_key in removed_keys {
+            match serde_json::from_str::<ParsedRecord>(&previous[record_key].1) {
+                Ok(mut record) => {
+                    record.change = Some(RecordChange::Removed);
+                    delta.removed.push(record);
+                }
+                Err(e) => tracing::warn!("delta: unreadable snapshot record {} for {}: {}", record_key, key, e),
+            }
+        }
+
+        let mut conn = self.conn.lock().map_err(|_| anyhow::anyhow!("delta store lock poisoned"))?;
+        let tx = conn.transaction()?;
+        tx.execute("DELETE FROM snapshot_records WHERE source_id = ?1", params![key])?;
+        for (record_key, (print, record)) in &current {
+            tx.execute(
+                "INSERT INTO snapshot_records (source_id, record_key, fingerprint, record) VALUES (?1, ?2, ?3, ?4)",
+                params![key, record_key.key(), print, record],
+            )?;
+        }
+        tx.execute(
+            "INSERT INTO source_snapshot (source_id, envelope_id, recorded_at) VALUES (?1, ?2, ?3)
+             ON CONFLICT(source_id) DO UPDATE SET envelope_id = excluded.envelope_id, recorded_at = excluded.recorded_at",
+            params![key, delta.envelope_id, recorded_at],
+        )?;
+        tx.commit()?;
+
+        publish(&delta);
+        Ok(delta)
+    }
+
+    /// Diff `records` (every record parsed from one envelope) against the snapshot of its
+    /// source, or of its endpoint when it has one, tag each with its change and make them the
+    /// new snapshot
+    pub fn diff(
+        &self,
+        source_id: &str,
+        envelope_id: &str,
+        records: &mut [ParsedRecord],
+        recorded_at: i64,
+    ) -> anyhow::Result<PayloadDelta> {
+        let endpoint_id = records.first().and_then(|r| r.endpoint_id.clone());
+        let mut fetch = self.begin(source_id, endpoint_id.as_deref(), recorded_at)?;
+        fetch.delta.envelope_id = envelope_id.to_string();
+        for record in records.iter_mut() {
+            fetch.observe(record, recorded_at)?;
+        }
+        self.finish(fetch)
+    }
+}
+
+/// The key a parsed record is tracked by within its source: the id its parser extracted,
+/// else one read from its fields (see [`record_key`])
+pub fn parsed_record_key(record: &ParsedRecord) -> SourceKey {
+    let key = match &record.external_id {
+        Some(id) => format!("id:{}", id),
+        None => record_key(&record.record),
+    };
+    SourceKey::new(&record.source_id, key)
+}
+
+/// The source's own identity for a parsed record: an id or URL field when it has one,
+/// else its title and date, else its content
+pub fn record_key(record: &Value) -> String {
+    let field = |names: &[&str]| {
+        names.iter().find_map(|name| match record.get(*name) {
+            Some(Value::String(s)) if !s.trim().is_empty() => Some(s.trim().to_string()),
+            Some(Value::Number(n)) => Some(n.to_string()),
+            _ => None,
+        })
+    };
+    if let Some(id) = field(IDENTITY_FIELDS) {
+        return format!("id:{}", id);
+    }
+    if let Some(title) = field(TITLE_FIELDS) {
+        let date = field(DATE_FIELDS).unwrap_or_default();
+        return format!("title:{}|{}", title.to_lowercase(), date);
+    }
+    format!("content:{}", fingerprint(record))
+}
+
+/// Content hash of a record; object keys serialize sorted, so equal records hash equally
+pub fn fingerprint(record: &Value) -> String {
+    hex::encode(Sha256::digest(record.to_string().as_bytes()))
+}
+
+/// A parser's `{"html_len": n}` placeholder for a page it found no events on
+pub fn is_fallback(record: &Value) -> bool {
+    record.as_object().is_some_and(|o| o.len() == 1 && o.contains_key("html_len"))
+}
+
+fn publish(delta: &PayloadDelta) {
+    use crate::observability::metrics::parser::delta_records;
+    delta_records(&delta.source_id, "added", delta.added);
+    delta_records(&delta.source_id, "changed", delta.changed);
+    delta_records(&delta.source_id, "unchanged", delta.unchanged);
+    delta_records(&delta.source_id, "removed", delta.removed.len());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn parsed(envelope_id: &str, record: Value) -> ParsedRecord {
+        ParsedRecord {
+            source_id: "neumos".to_string(),
+            envelope_id: envelope_id.to_string(),
+            payload_ref: format!("cas:sha256:{}", envelope_id),
+            record_path: "$.events[*]".to_string(),
+            record,
+            attribution: None,
+            change: None,
+            endpoint_id: None,
+            external_id: None,
+        }
+    }
+
+    #[test]
+    fn superseding_envelope_is_tagged_against_the_previous_one() {
+        let tmp = tempfile::tempdir().unwrap();
+        let store = DeltaStore::open_at_root(tmp.path()).unwrap();
+
+        let mut first = vec![
+            parsed("env-1", json!({"id": 1, "title": "Show A", "price": "$15"})),
+            parsed("env-1", json!({"id": 2, "title": "Show B"})),
+            parsed("env-1", json!({"id": 3, "title": "Show C"})),
+        ];
+        let delta = store.diff("neumos", "env-1", &mut first, 100).unwrap();
+        assert_eq!((delta.added, delta.previous_envelope_id), (3, None));
+
+        let mut second = vec![
+            parsed("env-2", json!({"id": 1, "title": "Show A", "price": "$20"})),
+            parsed("env-2", json!({"id": 2, "title": "Show B"})),
+            parsed("env-2", json!({"id": 4, "title": "Show D"})),
+        ];
+        let delta = store.diff("neumos", "env-2", &mut second, 200).unwrap();
+        assert_eq!(delta.previous_envelope_id.as_deref(), Some("env-1"));
+        assert_eq!((delta.added, delta.changed, delta.unchanged), (1, 1, 1));
+        let changes: Vec<_> = second.iter().map(|r| r.change.unwrap()).collect();
+        assert_eq!(changes, vec![RecordChange::Changed, RecordChange::Unchanged, RecordChange::Added]);
+        assert_eq!(delta.removed.len(), 1);
+        assert_eq!(delta.removed[0].record["id"], 3);
+        assert_eq!(delta.removed[0].envelope_id, "env-1");
+        assert_eq!(delta.removed[0].change, Some(RecordChange::Removed));
+    }
+
+    #[test]
+    fn fallback_payloads_keep_the_previous_snapshot() {
+        let tmp = tempfile::tempdir().unwrap();
+        let store = DeltaStore::open_at_root(tmp.path()).unwrap();
+        let mut first = vec![parsed("env-1", json!({"title": "Show A", "event_day": "2025-05-01"}))];
+        store.diff("neumos", "env-1", &mut first, 100).unwrap();
+
+        let mut broken = vec![parsed("env-2", json!({"html_len": 5120}))];
+        let delta = store.diff("neumos", "env-2", &mut broken, 200).unwrap();
+        assert!(delta.skipped.is_some());
+        assert!(delta.removed.is_empty());
+        assert_eq!(broken[0].change, None);
+
+        let mut third = vec![parsed("env-3", json!({"title": "show a", "event_day": "2025-05-01"}))];
+        let delta = store.diff("neumos", "env-3", &mut third, 300).unwrap();
+        assert_eq!(delta.previous_envelope_id.as_deref(), Some("env-1"));
+        assert_eq!(third[0].change, Some(RecordChange::Changed));
+    }
+
+    #[test]
+    fn a_fetch_older_than_the_snapshot_is_not_diffed() {
+        let tmp = tempfile::tempdir().unwrap();
+        let store = DeltaStore::open_at_root(tmp.path()).unwrap();
+        let mut newer = vec![parsed("env-2", json!({"id": 1, "title": "Show A"}))];
+        store.diff("neumos", "env-2", &mut newer, 200).unwrap();
+
+        let mut older = vec![parsed("env-1", json!({"id": 2, "title": "Show B"}))];
+        let delta = store.diff("neumos", "env-1", &mut older, 100).unwrap();
+        assert!(delta.skipped.unwrap().contains("before the snapshot of env-2"));
+        assert!(delta.removed.is_empty());
+        assert_eq!(older[0].change, None);
+
+        let mut next = vec![parsed("env-3", json!({"id": 1, "title": "Show A"}))];
+        let delta = store.diff("neumos", "env-3", &mut next, 300).unwrap();
+        assert_eq!(delta.previous_envelope_id.as_deref(), Some("env-2"));
+        assert_eq!(next[0].change, Some(RecordChange::Unchanged));
+    }
+
+    #[test]
+    fn endpoints_are_diffed_against_their_own_snapshots() {
+        let tmp = tempfile::tempdir().unwrap();
+        let store = DeltaStore::open_at_root(tmp.path()).unwrap();
+        let at = |endpoint: &str, envelope_id: &str, id: u32| ParsedRecord {
+            endpoint_id: Some(endpoint.to_string()),
+            ..parsed(envelope_id, json!({"id": id}))
+        };
+        store.diff("neumos", "env-1", &mut [at("main", "env-1", 1)], 100).unwrap();
+        store.diff("neumos", "env-2", &mut [at("lounge", "env-2", 2)], 100).unwrap();
+
+        let delta = store.diff("neumos", "env-3", &mut [at("main", "env-3", 1)], 200).unwrap();
+        assert_eq!(delta.endpoint_id.as_deref(), Some("main"));
+        assert_eq!((delta.unchanged, delta.removed.len()), (1, 0));
+        assert_eq!(delta_key("neumos", Some("lounge")), "neumos#lounge");
+    }
+}
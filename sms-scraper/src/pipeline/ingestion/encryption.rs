@@ -0,0 +1,321 @@
+//! Optional AES-256-GCM encryption of CAS payloads and ingest log lines at rest.
+//!
+//! The active key comes from `SMS_ENCRYPTION_KEY` (64 hex chars) or, for keys held in a KMS,
+//! from the output of `SMS_ENCRYPTION_KEY_COMMAND`. Everything sealed records the id of the key
+//! that sealed it, so rotating keys only needs the old ones listed in
+//! `SMS_ENCRYPTION_RETIRED_KEYS` for readers to keep decrypting older data. Readers pass
+//! plaintext through untouched, so a log or CAS written before encryption was turned on still
+//! reads. Builds without the `encryption` feature (the minimal GraphQL server) read plaintext
+//! the same way but refuse to seal or open anything.
+
+use crate::pipeline::ingestion::envelope::EncryptionMeta;
+use anyhow::{anyhow, Context, Result};
+#[cfg(feature = "encryption")]
+use ring::aead::{Aad, LessSafeKey, Nonce, UnboundKey, AES_256_GCM, NONCE_LEN};
+#[cfg(feature = "encryption")]
+use ring::rand::{SecureRandom, SystemRandom};
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::io;
+use std::sync::{Arc, OnceLock};
+
+/// The active data key, 32 bytes as hex
+pub const ENCRYPTION_KEY_ENV: &str = "SMS_ENCRYPTION_KEY";
+/// Id recorded with everything the active key seals; defaults to `default`
+pub const ENCRYPTION_KEY_ID_ENV: &str = "SMS_ENCRYPTION_KEY_ID";
+/// Shell command printing the active key as hex, e.g. a KMS or Vault decrypt call.
+/// Used when `SMS_ENCRYPTION_KEY` is not set.
+pub const ENCRYPTION_KEY_COMMAND_ENV: &str = "SMS_ENCRYPTION_KEY_COMMAND";
+/// Decrypt-only keys from earlier rotations, as `id:hex,id:hex`
+pub const ENCRYPTION_RETIRED_KEYS_ENV: &str = "SMS_ENCRYPTION_RETIRED_KEYS";
+
+pub const ALG: &str = "AES-256-GCM";
+/// AES-GCM's 96-bit nonce, as `ring` defines it
+#[cfg(not(feature = "encryption"))]
+const NONCE_LEN: usize = 12;
+
+/// Prefix of an encrypted CAS object, followed by the key id length (one byte), the key id,
+/// the nonce and the ciphertext with its tag
+pub(crate) const OBJECT_MAGIC: &[u8] = b"SMSAEAD1";
+/// Start of an encrypted ingest log line
+const LINE_PREFIX: &str = "{\"sealed\":";
+
+#[derive(Serialize, Deserialize)]
+struct SealedLine {
+    sealed: SealedHeader,
+    /// Hex
+    ciphertext: String,
+}
+
+#[derive(Serialize, Deserialize)]
+struct SealedHeader {
+    alg: String,
+    key_id: String,
+    /// Hex
+    nonce: String,
+}
+
+/// Keys by id, and which one seals new data. With no active key nothing is encrypted.
+#[derive(Default)]
+pub struct Keyring {
+    active: Option<String>,
+    keys: HashMap<String, [u8; 32]>,
+}
+
+impl Keyring {
+    /// Seal new data with `key`, recording `key_id`
+    pub fn new(key_id: impl Into<String>, key: [u8; 32]) -> Self {
+        let key_id = key_id.into();
+        Self { active: Some(key_id.clone()), keys: HashMap::from([(key_id, key)]) }
+    }
+
+    /// Also decrypt data sealed with a retired key
+    pub fn with_retired(mut self, key_id: impl Into<String>, key: [u8; 32]) -> Self {
+        self.keys.insert(key_id.into(), key);
+        self
+    }
+
+    /// The keyring described by the `SMS_ENCRYPTION_*` variables. `lookup` is
+    /// `std::env::var(..).ok()` outside tests.
+    pub fn from_lookup(lookup: impl Fn(&str) -> Option<String>) -> Result<Self> {
+        let var = |name: &str| lookup(name).map(|v| v.trim().to_string()).filter(|v| !v.is_empty());
+
+        let mut keyring = Keyring::default();
+        if let Some(retired) = var(ENCRYPTION_RETIRED_KEYS_ENV) {
+            for entry in retired.split(',').map(str::trim).filter(|e| !e.is_empty()) {
+                let (id, hex_key) = entry
+                    .split_once(':')
+                    .ok_or_else(|| anyhow!("{} entry {:?} is not id:hex", ENCRYPTION_RETIRED_KEYS_ENV, entry))?;
+                let key = parse_key(hex_key).with_context(|| format!("retired key {}", id.trim()))?;
+                keyring = keyring.with_retired(id.trim(), key);
+            }
+        }
+
+        let hex_key = match (var(ENCRYPTION_KEY_ENV), var(ENCRYPTION_KEY_COMMAND_ENV)) {
+            (Some(key), _) => key,
+            (None, Some(command)) => run_key_command(&command)?,
+            (None, None) => return Ok(keyring),
+        };
+        let key_id = var(ENCRYPTION_KEY_ID_ENV).unwrap_or_else(|| "default".to_string());
+        let key = parse_key(&hex_key).context("active encryption key")?;
+        keyring.keys.insert(key_id.clone(), key);
+        keyring.active = Some(key_id);
+        Ok(keyring)
+    }
+
+    pub fn from_env() -> Result<Self> {
+        Self::from_lookup(|name| std::env::var(name).ok())
+    }
+
+    /// What new payloads are sealed with, `None` when encryption is off
+    pub fn meta(&self) -> Option<EncryptionMeta> {
+        self.active.as_ref().map(|key_id| EncryptionMeta { alg: ALG.to_string(), key_id: key_id.clone() })
+    }
+
+    /// Ids of every key this keyring can decrypt with, sorted
+    pub fn key_ids(&self) -> Vec<&str> {
+        let mut ids: Vec<&str> = self.keys.keys().map(String::as_str).collect();
+        ids.sort();
+        ids
+    }
+
+    /// `bytes` encrypted with the active key, or unchanged when encryption is off
+    pub fn seal<'a>(&self, bytes: &'a [u8]) -> Result<Cow<'a, [u8]>> {
+        let Some(key_id) = &self.active else {
+            return Ok(Cow::Borrowed(bytes));
+        };
+        let id_len = u8::try_from(key_id.len()).map_err(|_| anyhow!("encryption key id {} is too long", key_id))?;
+        let (nonce, ciphertext) = self.seal_with(key_id, bytes)?;
+        let mut out = Vec::with_capacity(OBJECT_MAGIC.len() + 1 + key_id.len() + NONCE_LEN + ciphertext.len());
+        out.extend_from_slice(OBJECT_MAGIC);
+        out.push(id_len);
+        out.extend_from_slice(key_id.as_bytes());
+        out.extend_from_slice(&nonce);
+        out.extend_from_slice(&ciphertext);
+        Ok(Cow::Owned(out))
+    }
+
+    /// The plaintext of a stored CAS object; objects that were never sealed pass through
+    pub fn open<'a>(&self, bytes: &'a [u8]) -> io::Result<Cow<'a, [u8]>> {
+        let Some(rest) = bytes.strip_prefix(OBJECT_MAGIC) else {
+            return Ok(Cow::Borrowed(bytes));
+        };
+        let truncated = || invalid("encrypted payload is truncated");
+        let (&id_len, rest) = rest.split_first().ok_or_else(truncated)?;
+        let id_len = id_len as usize;
+        if rest.len() < id_len + NONCE_LEN {
+            return Err(truncated());
+        }
+        let key_id = std::str::from_utf8(&rest[..id_len]).map_err(|_| invalid("encrypted payload has a bad key id"))?;
+        let nonce = &rest[id_len..id_len + NONCE_LEN];
+        self.open_with(key_id, nonce, rest[id_len + NONCE_LEN..].to_vec()).map(Cow::Owned)
+    }
+
+    /// One ingest log record, sealed into a line carrying its key id and nonce, or unchanged
+    /// when encryption is off
+    pub fn seal_line<'a>(&self, record: &'a str) -> Result<Cow<'a, str>> {
+        let Some(key_id) = &self.active else {
+            return Ok(Cow::Borrowed(record));
+        };
+        let (nonce, ciphertext) = self.seal_with(key_id, record.as_bytes())?;
+        let sealed = SealedLine {
+            sealed: SealedHeader { alg: ALG.to_string(), key_id: key_id.clone(), nonce: hex::encode(nonce) },
+            ciphertext: hex::encode(ciphertext),
+        };
+        Ok(Cow::Owned(serde_json::to_string(&sealed)?))
+    }
+
+    /// The record in one ingest log line; plaintext lines pass through
+    pub fn open_line<'a>(&self, line: &'a str) -> io::Result<Cow<'a, str>> {
+        if !line.trim_start().starts_with(LINE_PREFIX) {
+            return Ok(Cow::Borrowed(line));
+        }
+        let sealed: SealedLine =
+            serde_json::from_str(line.trim_end()).map_err(|e| invalid(&format!("bad encrypted log line: {}", e)))?;
+        if sealed.sealed.alg != ALG {
+            return Err(invalid(&format!("unsupported encryption {}", sealed.sealed.alg)));
+        }
+        let nonce = hex::decode(&sealed.sealed.nonce).map_err(|_| invalid("encrypted log line has a bad nonce"))?;
+        let ciphertext =
+            hex::decode(&sealed.ciphertext).map_err(|_| invalid("encrypted log line has bad ciphertext"))?;
+        let plaintext = self.open_with(&sealed.sealed.key_id, &nonce, ciphertext)?;
+        String::from_utf8(plaintext).map(Cow::Owned).map_err(|_| invalid("decrypted log line is not UTF-8"))
+    }
+
+    #[cfg(feature = "encryption")]
+    fn key(&self, key_id: &str) -> Option<LessSafeKey> {
+        let bytes = self.keys.get(key_id)?;
+        Some(LessSafeKey::new(UnboundKey::new(&AES_256_GCM, bytes).ok()?))
+    }
+
+    /// (nonce, ciphertext with tag); the key id is bound in as associated data
+    #[cfg(feature = "encryption")]
+    fn seal_with(&self, key_id: &str, plaintext: &[u8]) -> Result<([u8; NONCE_LEN], Vec<u8>)> {
+        let key = self.key(key_id).ok_or_else(|| anyhow!("no encryption key {}", key_id))?;
+        let mut nonce = [0u8; NONCE_LEN];
+        SystemRandom::new().fill(&mut nonce).map_err(|_| anyhow!("no randomness for an encryption nonce"))?;
+        let mut in_out = plaintext.to_vec();
+        key.seal_in_place_append_tag(Nonce::assume_unique_for_key(nonce), Aad::from(key_id.as_bytes()), &mut in_out)
+            .map_err(|_| anyhow!("encryption failed"))?;
+        Ok((nonce, in_out))
+    }
+
+    #[cfg(feature = "encryption")]
+    fn open_with(&self, key_id: &str, nonce: &[u8], mut ciphertext: Vec<u8>) -> io::Result<Vec<u8>> {
+        let key = self.key(key_id).ok_or_else(|| {
+            io::Error::other(format!(
+                "data is encrypted with key {} which is not configured; set {} or list it in {}",
+                key_id, ENCRYPTION_KEY_ENV, ENCRYPTION_RETIRED_KEYS_ENV
+            ))
+        })?;
+        let nonce = Nonce::try_assume_unique_for_key(nonce).map_err(|_| invalid("bad encryption nonce"))?;
+        let len = key
+            .open_in_place(nonce, Aad::from(key_id.as_bytes()), &mut ciphertext)
+            .map_err(|_| invalid(&format!("decryption with key {} failed; wrong key or corrupted data", key_id)))?
+            .len();
+        ciphertext.truncate(len);
+        Ok(ciphertext)
+    }
+
+    #[cfg(not(feature = "encryption"))]
+    fn seal_with(&self, key_id: &str, _plaintext: &[u8]) -> Result<([u8; NONCE_LEN], Vec<u8>)> {
+        Err(anyhow!("cannot encrypt with key {}: built without the encryption feature", key_id))
+    }
+
+    #[cfg(not(feature = "encryption"))]
+    fn open_with(&self, key_id: &str, _nonce: &[u8], _ciphertext: Vec<u8>) -> io::Result<Vec<u8>> {
+        Err(io::Error::other(format!(
+            "data is encrypted with key {} but this build has no encryption feature",
+            key_id
+        )))
+    }
+}
+
+fn invalid(msg: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg.to_string())
+}
+
+fn parse_key(hex_key: &str) -> Result<[u8; 32]> {
+    let bytes = hex::decode(hex_key.trim()).map_err(|_| anyhow!("encryption key is not hex"))?;
+    bytes
+        .try_into()
+        .map_err(|b: Vec<u8>| anyhow!("encryption key is {} bytes, AES-256 needs 32", b.len()))
+}
+
+fn run_key_command(command: &str) -> Result<String> {
+    let output = std::process::Command::new("sh")
+        .arg("-c")
+        .arg(command)
+        .output()
+        .with_context(|| format!("running {}", ENCRYPTION_KEY_COMMAND_ENV))?;
+    if !output.status.success() {
+        return Err(anyhow!(
+            "{} exited with {}: {}",
+            ENCRYPTION_KEY_COMMAND_ENV,
+            output.status,
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// The process-wide keyring from the environment, read once. A bad configuration is an error
+/// on every use rather than a silent fall back to plaintext.
+pub fn keyring() -> io::Result<Arc<Keyring>> {
+    static KEYRING: OnceLock<std::result::Result<Arc<Keyring>, String>> = OnceLock::new();
+    KEYRING
+        .get_or_init(|| Keyring::from_env().map(Arc::new).map_err(|e| format!("{:#}", e)))
+        .clone()
+        .map_err(io::Error::other)
+}
+
+#[cfg(all(test, feature = "encryption"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn seals_objects_and_lines_and_reads_plaintext_and_retired_keys() {
+        let old = Keyring::new("k1", [1; 32]);
+        let object = old.seal(b"<html>events</html>").unwrap().into_owned();
+        assert!(object.starts_with(OBJECT_MAGIC));
+        let line = old.seal_line(r#"{"envelope_id":"e1"}"#).unwrap().into_owned();
+        assert!(!line.contains("envelope_id"));
+
+        let rotated = Keyring::new("k2", [2; 32]).with_retired("k1", [1; 32]);
+        assert_eq!(rotated.open(&object).unwrap().as_ref(), b"<html>events</html>");
+        assert_eq!(rotated.open_line(&line).unwrap(), r#"{"envelope_id":"e1"}"#);
+        assert_eq!(rotated.open(b"plain").unwrap().as_ref(), b"plain");
+        assert_eq!(rotated.open_line(r#"{"envelope_id":"e0"}"#).unwrap(), r#"{"envelope_id":"e0"}"#);
+
+        let err = Keyring::new("k2", [2; 32]).open_line(&line).unwrap_err();
+        assert!(err.to_string().contains("key k1 which is not configured"));
+        let mut tampered = object.clone();
+        *tampered.last_mut().unwrap() ^= 1;
+        assert!(rotated.open(&tampered).is_err());
+    }
+
+    #[test]
+    fn keyring_comes_from_env_or_a_key_command() {
+        let from = |vars: &[(&str, String)]| {
+            let vars: HashMap<String, String> = vars.iter().map(|(k, v)| (k.to_string(), v.clone())).collect();
+            Keyring::from_lookup(|name| vars.get(name).cloned())
+        };
+        let key = "00".repeat(32);
+        assert!(from(&[]).unwrap().meta().is_none());
+
+        let keyring = from(&[(ENCRYPTION_KEY_ENV, key.clone()), (ENCRYPTION_KEY_ID_ENV, "2025-q3".to_string())]).unwrap();
+        assert_eq!(keyring.meta().unwrap().key_id, "2025-q3");
+
+        let keyring = from(&[
+            (ENCRYPTION_KEY_COMMAND_ENV, format!("echo {}", key)),
+            (ENCRYPTION_RETIRED_KEYS_ENV, format!("2025-q2:{}", "11".repeat(32))),
+        ])
+        .unwrap();
+        assert_eq!(keyring.meta().unwrap().key_id, "default");
+        assert_eq!(keyring.key_ids(), ["2025-q2", "default"]);
+
+        let err = from(&[(ENCRYPTION_KEY_ENV, "abcd".to_string())]).err().unwrap();
+        assert!(format!("{:#}", err).contains("AES-256 needs 32"));
+    }
+}
@@ -20,6 +20,9 @@ pub struct RequestMeta {
     pub status: Option<u16>,
     pub etag: Option<String>,
     pub last_modified: Option<String>,
+    /// Which of the source's endpoints was fetched, on multi-endpoint sources
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub endpoint_id: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -44,6 +47,15 @@ pub struct EnvelopeSubmissionV1 {
     pub legal: LegalMeta,
 }
 
+/// How the payloads an envelope references are encrypted at rest
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct EncryptionMeta {
+    /// `AES-256-GCM`
+    pub alg: String,
+    /// Which key sealed the payloads; readers look it up in their keyring
+    pub key_id: String,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct StampedEnvelopeV1 {
     pub envelope_version: String,
@@ -52,4 +64,261 @@ pub struct StampedEnvelopeV1 {
     pub payload_ref: String,
     pub dedupe_of: Option<String>,
     pub envelope: EnvelopeSubmissionV1,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub encryption: Option<EncryptionMeta>,
+}
+
+/// One payload of a multi-part envelope, e.g. a single page of a paginated fetch
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct PayloadPart {
+    /// Position in fetch order, starting at 0
+    pub index: u32,
+    pub payload_ref: String,
+    pub payload_meta: PayloadMeta,
+    pub request: RequestMeta,
+}
+
+/// Like V1, but with ordered payload parts instead of a single `payload_ref`
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct StampedEnvelopeV2 {
+    pub envelope_version: String, // "2.0.0"
+    pub envelope_id: String,
+    pub accepted_at: DateTime<Utc>,
+    pub payload_parts: Vec<PayloadPart>,
+    pub dedupe_of: Option<String>,
+    pub envelope: EnvelopeSubmissionV1,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub encryption: Option<EncryptionMeta>,
+}
+
+pub const ENVELOPE_VERSION_V1: &str = "1.0.0";
+pub const ENVELOPE_VERSION_V2: &str = "2.0.0";
+
+impl From<StampedEnvelopeV1> for StampedEnvelopeV2 {
+    fn from(v1: StampedEnvelopeV1) -> Self {
+        let payload_parts = if v1.payload_ref.is_empty() {
+            Vec::new()
+        } else {
+            vec![PayloadPart {
+                index: 0,
+                payload_ref: v1.payload_ref,
+                payload_meta: v1.envelope.payload_meta.clone(),
+                request: v1.envelope.request.clone(),
+            }]
+        };
+        Self {
+            envelope_version: ENVELOPE_VERSION_V2.to_string(),
+            envelope_id: v1.envelope_id,
+            accepted_at: v1.accepted_at,
+            payload_parts,
+            dedupe_of: v1.dedupe_of,
+            envelope: v1.envelope,
+            encryption: v1.encryption,
+        }
+    }
+}
+
+impl StampedEnvelopeV2 {
+    /// Payload refs in part order; empty for dedupe markers
+    pub fn payload_refs(&self) -> Vec<String> {
+        let mut parts: Vec<&PayloadPart> = self.payload_parts.iter().filter(|p| !p.payload_ref.is_empty()).collect();
+        parts.sort_by_key(|p| p.index);
+        parts.into_iter().map(|p| p.payload_ref.clone()).collect()
+    }
+}
+
+#[cfg(test)]
+impl StampedEnvelopeV1 {
+    /// An accepted single-payload envelope as the gateway logs it, for tests of log readers
+    pub(crate) fn fixture(source_id: &str, payload_ref: &str) -> Self {
+        let now = Utc::now();
+        Self {
+            envelope_version: ENVELOPE_VERSION_V1.to_string(),
+            envelope_id: uuid::Uuid::new_v4().to_string(),
+            accepted_at: now,
+            payload_ref: payload_ref.to_string(),
+            dedupe_of: None,
+            envelope: EnvelopeSubmissionV1 {
+                envelope_version: ENVELOPE_VERSION_V1.to_string(),
+                source_id: source_id.to_string(),
+                idempotency_key: format!("{}:{}", source_id, now.timestamp()),
+                payload_meta: PayloadMeta {
+                    mime_type: "text/html".to_string(),
+                    size_bytes: 0,
+                    checksum: ChecksumMeta { sha256: String::new() },
+                },
+                request: RequestMeta {
+                    url: format!("https://{}.example/events", source_id),
+                    method: "GET".to_string(),
+                    status: Some(200),
+                    etag: None,
+                    last_modified: None,
+                    endpoint_id: None,
+                },
+                timing: TimingMeta { fetched_at: now, gateway_received_at: Some(now) },
+                legal: LegalMeta { license_id: "test".to_string() },
+            },
+            encryption: None,
+        }
+    }
+}
+
+/// Any stamped envelope found in the ingest log, told apart by `envelope_version`. Reading one
+/// migrates older line shapes first (see [`migrate_legacy`]), so every line that was ever
+/// appended deserializes into a typed version.
+#[derive(Debug, Clone, Serialize)]
+#[serde(untagged)]
+pub enum StampedEnvelope {
+    V1(StampedEnvelopeV1),
+    V2(StampedEnvelopeV2),
+}
+
+impl<'de> Deserialize<'de> for StampedEnvelope {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        use serde::de::Error;
+        let mut val = serde_json::Value::deserialize(deserializer)?;
+        migrate_legacy(&mut val);
+        let major = val.get("envelope_version").and_then(|v| v.as_str()).and_then(|v| v.split('.').next());
+        // Paginated lines were written as V2 before every V2 line carried its version
+        let envelope = if major == Some("2") || val.get("payload_parts").is_some() {
+            serde_json::from_value(val).map(StampedEnvelope::V2)
+        } else {
+            serde_json::from_value(val).map(StampedEnvelope::V1)
+        };
+        envelope.map_err(D::Error::custom)
+    }
+}
+
+/// Bring a raw line written by an older gateway up to the V1 shape: the payload ref used to be
+/// nested under `envelope`, and the very first lines carried no `envelope_version`
+fn migrate_legacy(val: &mut serde_json::Value) {
+    let Some(line) = val.as_object_mut() else {
+        return;
+    };
+    if !line.contains_key("payload_ref") && !line.contains_key("payload_parts") {
+        let nested = line.get_mut("envelope").and_then(|e| e.as_object_mut()).and_then(|e| e.remove("payload_ref"));
+        line.insert("payload_ref".to_string(), nested.unwrap_or_else(|| serde_json::Value::String(String::new())));
+    }
+    line.entry("envelope_version").or_insert_with(|| serde_json::Value::String(ENVELOPE_VERSION_V1.to_string()));
+}
+
+impl StampedEnvelope {
+    /// Parse one ingest log line in whichever version it was written
+    pub fn from_log_line(line: &str) -> serde_json::Result<Self> {
+        serde_json::from_str(line)
+    }
+
+    pub fn into_v2(self) -> StampedEnvelopeV2 {
+        match self {
+            StampedEnvelope::V1(v1) => v1.into(),
+            StampedEnvelope::V2(v2) => v2,
+        }
+    }
+}
+
+/// Read an ingest log line of any version as the current envelope shape
+pub fn read_log_line(line: &str) -> serde_json::Result<StampedEnvelopeV2> {
+    StampedEnvelope::from_log_line(line).map(StampedEnvelope::into_v2)
+}
+
+/// The fields every stamped envelope version shares, for scans over the log that only need to
+/// find or count envelopes and shouldn't fail on lines they don't otherwise understand
+#[derive(Debug, Deserialize, Clone)]
+pub struct EnvelopeHeader {
+    pub envelope_id: String,
+    #[serde(default)]
+    pub dedupe_of: Option<String>,
+    #[serde(default)]
+    pub envelope: Option<SubmissionHeader>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct SubmissionHeader {
+    pub source_id: String,
+}
+
+impl EnvelopeHeader {
+    pub fn from_log_line(line: &str) -> serde_json::Result<Self> {
+        serde_json::from_str(line)
+    }
+
+    pub fn source_id(&self) -> Option<&str> {
+        self.envelope.as_ref().map(|e| e.source_id.as_str())
+    }
+
+    pub fn is_dedupe(&self) -> bool {
+        self.dedupe_of.is_some()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn submission() -> EnvelopeSubmissionV1 {
+        EnvelopeSubmissionV1 {
+            envelope_version: "1.0.0".to_string(),
+            source_id: "kexp".to_string(),
+            idempotency_key: "kexp:2025-06-01".to_string(),
+            payload_meta: PayloadMeta {
+                mime_type: "text/html".to_string(),
+                size_bytes: 3,
+                checksum: ChecksumMeta { sha256: "abcd".to_string() },
+            },
+            request: RequestMeta {
+                url: "https://www.kexp.org/events/".to_string(),
+                method: "GET".to_string(),
+                status: Some(200),
+                etag: None,
+                last_modified: None,
+                endpoint_id: None,
+            },
+            timing: TimingMeta { fetched_at: Utc::now(), gateway_received_at: None },
+            legal: LegalMeta { license_id: "test".to_string() },
+        }
+    }
+
+    #[test]
+    fn reads_v1_and_v2_log_lines_as_ordered_parts() {
+        let v1 = StampedEnvelopeV1 {
+            envelope_version: "1.0.0".to_string(),
+            envelope_id: "env-1".to_string(),
+            accepted_at: Utc::now(),
+            payload_ref: "cas:sha256:abcd".to_string(),
+            dedupe_of: None,
+            envelope: submission(),
+            encryption: None,
+        };
+        let line = serde_json::to_string(&v1).unwrap();
+        assert!(matches!(StampedEnvelope::from_log_line(&line).unwrap(), StampedEnvelope::V1(_)));
+        let upgraded = read_log_line(&line).unwrap();
+        assert_eq!(upgraded.envelope_version, ENVELOPE_VERSION_V2);
+        assert_eq!(upgraded.payload_refs(), vec!["cas:sha256:abcd"]);
+
+        // Early lines nested the payload ref in the submission and had no version
+        let mut legacy: serde_json::Value = serde_json::from_str(&line).unwrap();
+        let line_fields = legacy.as_object_mut().unwrap();
+        line_fields.remove("payload_ref");
+        line_fields.remove("envelope_version");
+        legacy["envelope"]["payload_ref"] = "cas:sha256:abcd".into();
+        let migrated = read_log_line(&legacy.to_string()).unwrap();
+        assert_eq!(migrated.payload_refs(), vec!["cas:sha256:abcd"]);
+        assert_eq!(migrated.envelope_id, "env-1");
+        let header = EnvelopeHeader::from_log_line(&legacy.to_string()).unwrap();
+        assert_eq!((header.source_id(), header.is_dedupe()), (Some("kexp"), false));
+
+        let part = |index: u32, r: &str| PayloadPart {
+            index,
+            payload_ref: r.to_string(),
+            payload_meta: submission().payload_meta,
+            request: submission().request,
+        };
+        let v2 = StampedEnvelopeV2 {
+            payload_parts: vec![part(1, "cas:sha256:page2"), part(0, "cas:sha256:page1")],
+            ..upgraded
+        };
+        let line = serde_json::to_string(&v2).unwrap();
+        assert!(matches!(StampedEnvelope::from_log_line(&line).unwrap(), StampedEnvelope::V2(_)));
+        assert_eq!(read_log_line(&line).unwrap().payload_refs(), vec!["cas:sha256:page1", "cas:sha256:page2"]);
+    }
 }
@@ -0,0 +1,184 @@
+use crate::observability::metrics;
+use crate::pipeline::ingestion::ingest_meta::{EnvelopeStateEntry, IngestMeta};
+use std::path::PathBuf;
+use tracing::{debug, warn};
+
+/// How long an envelope may sit in a non-terminal state before it counts as stuck
+pub const DEFAULT_STUCK_AFTER_SECS: i64 = 3600;
+pub const STUCK_AFTER_ENV: &str = "SMS_ENVELOPE_STUCK_AFTER_SECS";
+
+/// Processing state of one envelope: received → parsed → normalized → cataloged, or
+/// failed / quarantined along the way
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EnvelopeState {
+    Received,
+    Parsed,
+    Normalized,
+    Cataloged,
+    Failed,
+    Quarantined,
+}
+
+impl EnvelopeState {
+    pub const ALL: [EnvelopeState; 6] = [
+        EnvelopeState::Received,
+        EnvelopeState::Parsed,
+        EnvelopeState::Normalized,
+        EnvelopeState::Cataloged,
+        EnvelopeState::Failed,
+        EnvelopeState::Quarantined,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            EnvelopeState::Received => "received",
+            EnvelopeState::Parsed => "parsed",
+            EnvelopeState::Normalized => "normalized",
+            EnvelopeState::Cataloged => "cataloged",
+            EnvelopeState::Failed => "failed",
+            EnvelopeState::Quarantined => "quarantined",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|state| state.as_str() == s)
+    }
+
+    /// Cataloged, failed and quarantined envelopes are done; anything else is waiting on a stage
+    pub fn is_terminal(&self) -> bool {
+        matches!(self, EnvelopeState::Cataloged | EnvelopeState::Failed | EnvelopeState::Quarantined)
+    }
+
+    fn rank(&self) -> u8 {
+        match self {
+            EnvelopeState::Received => 0,
+            EnvelopeState::Parsed => 1,
+            EnvelopeState::Normalized => 2,
+            EnvelopeState::Cataloged => 3,
+            EnvelopeState::Failed | EnvelopeState::Quarantined => 4,
+        }
+    }
+
+    /// Whether an envelope in `self` may move to `next`. Stages only move it forward, and
+    /// failure can happen from any non-terminal state; re-parsing (a consumer replaying the
+    /// log) starts it over at `parsed` from anywhere.
+    pub fn can_become(&self, next: EnvelopeState) -> bool {
+        match next {
+            EnvelopeState::Parsed => true,
+            EnvelopeState::Failed | EnvelopeState::Quarantined => !self.is_terminal(),
+            _ => !self.is_terminal() && next.rank() > self.rank(),
+        }
+    }
+}
+
+impl std::fmt::Display for EnvelopeState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Records envelope state changes in IngestMeta on behalf of the pipeline stages. Tracking
+/// is best-effort: a failed write is logged and never fails the stage itself.
+#[derive(Debug, Clone)]
+pub struct EnvelopeStates {
+    data_root: PathBuf,
+}
+
+impl EnvelopeStates {
+    pub fn new(data_root: impl Into<PathBuf>) -> Self {
+        Self { data_root: data_root.into() }
+    }
+
+    /// Move `envelope_id` to `state` if the transition is allowed; returns whether it moved
+    pub fn record(&self, envelope_id: &str, source_id: &str, state: EnvelopeState, detail: Option<&str>) -> bool {
+        if envelope_id.is_empty() {
+            return false;
+        }
+        match self.try_record(envelope_id, source_id, state, detail) {
+            Ok(moved) => moved,
+            Err(e) => {
+                warn!("envelope_state: failed to record {} for envelope_id={}: {}", state, envelope_id, e);
+                false
+            }
+        }
+    }
+
+    fn try_record(&self, envelope_id: &str, source_id: &str, state: EnvelopeState, detail: Option<&str>) -> anyhow::Result<bool> {
+        let meta = IngestMeta::open_at_root(&self.data_root)?;
+        let current = meta.get_envelope_state(envelope_id)?;
+        if let Some(from) = current.as_ref().and_then(|c| EnvelopeState::parse(&c.state)) {
+            if from == state || !from.can_become(state) {
+                debug!("envelope_state: envelope_id={} stays {} (not moving to {})", envelope_id, from, state);
+                return Ok(false);
+            }
+        }
+        meta.put_envelope_state(&EnvelopeStateEntry {
+            envelope_id: envelope_id.to_string(),
+            source_id: source_id.to_string(),
+            state: state.as_str().to_string(),
+            detail: detail.map(str::to_string),
+            updated_at: chrono::Utc::now().timestamp(),
+        })?;
+        metrics::ingest_log::envelope_transition(state.as_str());
+        Ok(true)
+    }
+}
+
+/// Stuck threshold from `SMS_ENVELOPE_STUCK_AFTER_SECS`, else the default
+pub fn stuck_after_secs() -> i64 {
+    std::env::var(STUCK_AFTER_ENV)
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(DEFAULT_STUCK_AFTER_SECS)
+}
+
+/// Envelopes that have sat in a non-terminal state for longer than `stuck_after_secs` as of
+/// `now`, oldest first. Also sets the stuck gauge for each non-terminal state.
+pub fn stuck_envelopes(meta: &IngestMeta, stuck_after_secs: i64, now: i64) -> anyhow::Result<Vec<EnvelopeStateEntry>> {
+    let waiting: Vec<EnvelopeState> = EnvelopeState::ALL.into_iter().filter(|s| !s.is_terminal()).collect();
+    let names: Vec<&str> = waiting.iter().map(EnvelopeState::as_str).collect();
+    let stuck = meta.envelopes_in_states_since(&names, now - stuck_after_secs)?;
+    for state in names {
+        let count = stuck.iter().filter(|e| e.state == state).count();
+        metrics::ingest_log::envelopes_stuck(state, count as u64);
+    }
+    Ok(stuck)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stages_only_move_envelopes_forward() {
+        use EnvelopeState::*;
+        assert!(Received.can_become(Parsed) && Parsed.can_become(Normalized) && Normalized.can_become(Cataloged));
+        assert!(Received.can_become(Failed) && Normalized.can_become(Quarantined));
+        assert!(!Normalized.can_become(Received));
+        assert!(!Cataloged.can_become(Failed) && !Failed.can_become(Normalized));
+        // A replayed envelope is parsed again even after it finished
+        assert!(Cataloged.can_become(Parsed) && Failed.can_become(Parsed));
+    }
+
+    #[test]
+    fn records_history_and_reports_envelopes_stuck_past_the_threshold() {
+        let tmp = tempfile::tempdir().unwrap();
+        let states = EnvelopeStates::new(tmp.path());
+        assert!(states.record("env-1", "neumos", EnvelopeState::Received, None));
+        assert!(states.record("env-1", "neumos", EnvelopeState::Parsed, None));
+        assert!(!states.record("env-1", "neumos", EnvelopeState::Received, None));
+        assert!(states.record("env-2", "barboza", EnvelopeState::Received, None));
+        assert!(states.record("env-2", "barboza", EnvelopeState::Failed, Some("parse_failed: no events")));
+
+        let meta = IngestMeta::open_at_root(tmp.path()).unwrap();
+        let current = meta.get_envelope_state("env-2").unwrap().unwrap();
+        assert_eq!((current.state.as_str(), current.detail.as_deref()), ("failed", Some("parse_failed: no events")));
+        let history: Vec<String> = meta.envelope_state_history("env-1").unwrap().into_iter().map(|(s, _, _)| s).collect();
+        assert_eq!(history, ["received", "parsed"]);
+
+        let now = chrono::Utc::now().timestamp();
+        assert!(stuck_envelopes(&meta, 60, now).unwrap().is_empty());
+        let stuck = stuck_envelopes(&meta, 60, now + 120).unwrap();
+        assert_eq!(stuck.iter().map(|e| e.envelope_id.as_str()).collect::<Vec<_>>(), ["env-1"]);
+    }
+}
@@ -1,8 +1,10 @@
+use crate::pipeline::ingestion::encryption::Keyring;
 use sha2::{Digest, Sha256};
 use std::fs;
 use std::path::Path;
 
-pub fn write_cas(root: &Path, bytes: &[u8]) -> anyhow::Result<String> {
+/// Store `bytes` under the hash of their plaintext, sealed with `keys` when encryption is on
+pub fn write_cas(root: &Path, bytes: &[u8], keys: &Keyring) -> anyhow::Result<String> {
     let mut hasher = Sha256::new();
     hasher.update(bytes);
     let digest = hasher.finalize();
@@ -11,7 +13,7 @@ pub fn write_cas(root: &Path, bytes: &[u8]) -> anyhow::Result<String> {
     fs::create_dir_all(&dir)?;
     let path = dir.join(&hex);
     if !path.exists() {
-        fs::write(&path, bytes)?;
+        fs::write(&path, keys.seal(bytes)?)?;
     }
     Ok(format!("cas:sha256:{}", hex))
 }
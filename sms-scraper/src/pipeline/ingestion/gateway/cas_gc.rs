@@ -0,0 +1,386 @@
+use crate::app::ports::DeadLetterPort;
+use crate::infra::dead_letter_store::FileDeadLetterStore;
+use crate::pipeline::ingestion::encryption;
+use crate::pipeline::ingestion::envelope::read_log_line;
+use crate::pipeline::ingestion::gateway::ingest_log;
+use crate::pipeline::ingestion::ingest_log_backend;
+use crate::pipeline::processing::catalog::provenance::LineageStore;
+use chrono::{DateTime, Duration, Utc};
+use serde::Serialize;
+use sms_core::storage::Storage;
+use std::collections::HashSet;
+use std::fs;
+use std::io::BufReader;
+use std::path::Path;
+
+const CAS_PREFIX: &str = "cas:sha256:";
+/// Supabase caps list pages and bulk deletes well above this; smaller batches keep requests fast
+const SUPABASE_PAGE: usize = 1000;
+const SUPABASE_DELETE_BATCH: usize = 100;
+
+#[derive(Debug, Clone)]
+pub struct GcOptions {
+    /// Payloads referenced only by ingest log lines older than this are expired.
+    /// `None` keeps everything the log references, however old.
+    pub retention: Option<Duration>,
+    /// Objects younger than this are never deleted, so a payload written just before its
+    /// log line is appended can't be collected in between
+    pub min_age: Duration,
+    pub dry_run: bool,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct GcReport {
+    pub backend: String,
+    pub scanned: usize,
+    pub referenced: usize,
+    pub too_recent: usize,
+    pub deleted: usize,
+    pub reclaimed_bytes: u64,
+    pub errors: Vec<String>,
+}
+
+/// Hashes of every payload that must survive collection: those referenced by ingest log lines
+/// within retention (including the checksum dedupe markers resolve to), by recorded lineage,
+/// by raw data rows in `storage`, and by the dead-letter queue awaiting retry. Log lines that
+/// can't be decrypted or read as an envelope fail collection instead of leaving their payloads
+/// unreferenced.
+pub async fn collect_references(
+    data_root: &Path,
+    storage: &dyn Storage,
+    retention: Option<Duration>,
+    now: DateTime<Utc>,
+) -> anyhow::Result<HashSet<String>> {
+    let mut live = HashSet::new();
+    let keys = encryption::keyring()?;
+    let cutoff = retention.map(|r| now - r);
+
+    let log_dir = data_root.join("ingest_log");
+    if log_dir.exists() {
+        for entry in fs::read_dir(&log_dir)? {
+            let path = entry?.path();
+            // The ingest.ndjson symlink points at one of the dated files
+            let is_log = path
+                .file_name()
+                .and_then(|f| f.to_str())
+                .is_some_and(|f| f.starts_with("ingest_") && f.ends_with(".ndjson"));
+            if !is_log {
+                continue;
+            }
+            for line in ingest_log::records(Box::new(BufReader::new(fs::File::open(&path)?))) {
+                collect_log_line(&keys.open_line(&line?)?, cutoff, &mut live)?;
+            }
+        }
+    }
+
+    // An object-backed log is never rotated, so reading it from the start covers its history
+    let log = ingest_log_backend::from_env(data_root);
+    if !log.is_local() {
+        for line in ingest_log::records(log.reader_from(0)?) {
+            collect_log_line(&keys.open_line(&line?)?, cutoff, &mut live)?;
+        }
+    }
+
+    if data_root.join("catalog").join("lineage.db").exists() {
+        for payload_ref in LineageStore::open_at_root(data_root)?.payload_refs()? {
+            live.extend(hash_of(&payload_ref).map(str::to_string));
+        }
+    }
+
+    // Raw data is kept however old its log line is, so its payload can be reparsed
+    for payload_ref in storage.get_raw_data_payload_refs().await? {
+        live.extend(hash_of(&payload_ref).map(str::to_string));
+    }
+
+    let dlq = FileDeadLetterStore::new(data_root);
+    for entry in dlq.list().await.map_err(|e| anyhow::anyhow!("reading dead-letter queue: {}", e))? {
+        live.extend(hash_of(&entry.payload_ref).map(str::to_string));
+    }
+
+    Ok(live)
+}
+
+/// Add the payloads one ingest log line keeps alive, unless it's older than `cutoff`
+fn collect_log_line(line: &str, cutoff: Option<DateTime<Utc>>, live: &mut HashSet<String>) -> anyhow::Result<()> {
+    let envelope = read_log_line(line).map_err(|e| anyhow::anyhow!("unreadable ingest log line: {}", e))?;
+    if cutoff.is_some_and(|cutoff| envelope.accepted_at < cutoff) {
+        return Ok(());
+    }
+    for payload_ref in envelope.payload_refs() {
+        live.extend(hash_of(&payload_ref).map(str::to_string));
+    }
+    let sha = &envelope.envelope.payload_meta.checksum.sha256;
+    if !sha.is_empty() {
+        live.insert(sha.clone());
+    }
+    Ok(())
+}
+
+fn hash_of(payload_ref: &str) -> Option<&str> {
+    payload_ref.strip_prefix(CAS_PREFIX).filter(|h| !h.is_empty())
+}
+
+/// Delete unreferenced payloads from the local CAS under `cas_root` (`<data_root>/cas`)
+pub fn gc_local(cas_root: &Path, live: &HashSet<String>, opts: &GcOptions, now: DateTime<Utc>) -> anyhow::Result<GcReport> {
+    let mut report = GcReport { backend: format!("local {}", cas_root.display()), ..Default::default() };
+    let sha_root = cas_root.join("sha256");
+    if !sha_root.exists() {
+        return Ok(report);
+    }
+
+    let mut files = Vec::new();
+    for l1 in fs::read_dir(&sha_root)? {
+        let l1 = l1?.path();
+        if !l1.is_dir() {
+            continue;
+        }
+        for l2 in fs::read_dir(&l1)? {
+            let l2 = l2?.path();
+            if !l2.is_dir() {
+                continue;
+            }
+            for object in fs::read_dir(&l2)? {
+                files.push(object?.path());
+            }
+        }
+    }
+
+    for path in files {
+        let Some(hash) = path.file_name().and_then(|f| f.to_str()).map(str::to_string) else {
+            continue;
+        };
+        report.scanned += 1;
+        if live.contains(&hash) {
+            report.referenced += 1;
+            continue;
+        }
+        let meta = fs::metadata(&path)?;
+        let modified: DateTime<Utc> = meta.modified().map(DateTime::from).unwrap_or(now);
+        if now - modified < opts.min_age {
+            report.too_recent += 1;
+            continue;
+        }
+        if !opts.dry_run {
+            if let Err(e) = fs::remove_file(&path) {
+                report.errors.push(format!("{}: {}", path.display(), e));
+                continue;
+            }
+            // The payload's text extract, if any, goes with it
+            let _ = fs::remove_file(cas_root.join("text").join(format!("{}.json", hash)));
+        }
+        report.deleted += 1;
+        report.reclaimed_bytes += meta.len();
+    }
+    Ok(report)
+}
+
+/// Supabase Storage settings, read from the same variables the gateway writes with
+struct SupabaseCas {
+    base: String,
+    key: String,
+    bucket: String,
+    prefix: String,
+}
+
+impl SupabaseCas {
+    fn from_env() -> Option<Self> {
+        let base = std::env::var("SUPABASE_URL")
+            .ok()
+            .or_else(|| std::env::var("SUPABASE_PROJECT_REF").ok().map(|r| format!("https://{}.supabase.co", r)))?;
+        Some(Self {
+            base: base.trim_end_matches('/').to_string(),
+            key: std::env::var("SUPABASE_SERVICE_ROLE_KEY").ok()?,
+            bucket: std::env::var("SUPABASE_BUCKET").ok()?,
+            prefix: std::env::var("SUPABASE_PREFIX").unwrap_or_default().trim_end_matches('/').to_string(),
+        })
+    }
+
+    fn root(&self) -> String {
+        if self.prefix.is_empty() {
+            "sha256".to_string()
+        } else {
+            format!("{}/sha256", self.prefix)
+        }
+    }
+
+    async fn list(&self, client: &reqwest::Client, prefix: &str, offset: usize) -> anyhow::Result<Vec<serde_json::Value>> {
+        let resp = client
+            .post(format!("{}/storage/v1/object/list/{}", self.base, self.bucket))
+            .header("Authorization", format!("Bearer {}", self.key))
+            .header("apikey", self.key.clone())
+            .json(&serde_json::json!({ "prefix": prefix, "limit": SUPABASE_PAGE, "offset": offset }))
+            .send()
+            .await?;
+        if !resp.status().is_success() {
+            return Err(anyhow::anyhow!("Supabase list {} failed: {}", prefix, resp.status()));
+        }
+        Ok(resp.json().await?)
+    }
+
+    async fn delete(&self, client: &reqwest::Client, paths: &[String]) -> anyhow::Result<()> {
+        let resp = client
+            .delete(format!("{}/storage/v1/object/{}", self.base, self.bucket))
+            .header("Authorization", format!("Bearer {}", self.key))
+            .header("apikey", self.key.clone())
+            .json(&serde_json::json!({ "prefixes": paths }))
+            .send()
+            .await?;
+        if !resp.status().is_success() {
+            return Err(anyhow::anyhow!("Supabase delete failed: {}", resp.status()));
+        }
+        Ok(())
+    }
+}
+
+/// Whether Supabase Storage is configured as the CAS backend
+pub fn supabase_configured() -> bool {
+    SupabaseCas::from_env().is_some()
+}
+
+/// Delete unreferenced payloads from the Supabase bucket the gateway uploads to
+pub async fn gc_supabase(live: &HashSet<String>, opts: &GcOptions, now: DateTime<Utc>) -> anyhow::Result<GcReport> {
+    let cas = SupabaseCas::from_env().ok_or_else(|| anyhow::anyhow!("Supabase CAS is not configured"))?;
+    let client = reqwest::Client::new();
+    let mut report = GcReport { backend: format!("supabase {}/{}", cas.bucket, cas.root()), ..Default::default() };
+
+    // Walk sha256/<xx>/<yy>/<hash>; folders come back without an id
+    let mut folders = vec![cas.root()];
+    let mut doomed = Vec::new();
+    while let Some(folder) = folders.pop() {
+        let mut offset = 0;
+        loop {
+            let page = cas.list(&client, &folder, offset).await?;
+            for item in &page {
+                let Some(name) = item.get("name").and_then(|n| n.as_str()) else { continue };
+                let path = format!("{}/{}", folder, name);
+                if item.get("id").is_none_or(|id| id.is_null()) {
+                    folders.push(path);
+                    continue;
+                }
+                report.scanned += 1;
+                if live.contains(name) {
+                    report.referenced += 1;
+                    continue;
+                }
+                let created = item
+                    .get("created_at")
+                    .and_then(|v| v.as_str())
+                    .and_then(|s| DateTime::parse_from_rfc3339(s).ok())
+                    .map(|t| t.with_timezone(&Utc))
+                    .unwrap_or(now);
+                if now - created < opts.min_age {
+                    report.too_recent += 1;
+                    continue;
+                }
+                let size = item.pointer("/metadata/size").and_then(|s| s.as_u64()).unwrap_or(0);
+                doomed.push((path, size));
+            }
+            if page.len() < SUPABASE_PAGE {
+                break;
+            }
+            offset += page.len();
+        }
+    }
+
+    for batch in doomed.chunks(SUPABASE_DELETE_BATCH) {
+        if !opts.dry_run {
+            let paths: Vec<String> = batch.iter().map(|(p, _)| p.clone()).collect();
+            if let Err(e) = cas.delete(&client, &paths).await {
+                report.errors.push(e.to_string());
+                continue;
+            }
+        }
+        report.deleted += batch.len();
+        report.reclaimed_bytes += batch.iter().map(|(_, size)| size).sum::<u64>();
+    }
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pipeline::ingestion::encryption::Keyring;
+    use crate::pipeline::ingestion::envelope::StampedEnvelopeV1;
+    use crate::pipeline::ingestion::gateway::cas_fs::write_cas;
+    use sms_core::domain::{RawData, RawDataOrigin};
+    use sms_core::storage::InMemoryStorage;
+    use std::io::Write;
+
+    fn log_line(payload_ref: &str, accepted_at: DateTime<Utc>) -> String {
+        let envelope = StampedEnvelopeV1 { accepted_at, ..StampedEnvelopeV1::fixture("neumos", payload_ref) };
+        serde_json::to_string(&envelope).unwrap()
+    }
+
+    fn cas_path(cas: &Path, payload_ref: &str) -> std::path::PathBuf {
+        let hex = hash_of(payload_ref).unwrap();
+        cas.join("sha256").join(&hex[0..2]).join(&hex[2..4]).join(hex)
+    }
+
+    #[tokio::test]
+    async fn collects_unreferenced_and_expired_payloads() {
+        let tmp = tempfile::tempdir().unwrap();
+        let cas = tmp.path().join("cas");
+        let fresh = write_cas(&cas, b"fresh page", &Keyring::default()).unwrap();
+        let stale = write_cas(&cas, b"stale page", &Keyring::default()).unwrap();
+        let orphan = write_cas(&cas, b"orphan", &Keyring::default()).unwrap();
+
+        let now = Utc::now();
+        fs::create_dir_all(tmp.path().join("ingest_log")).unwrap();
+        let mut log = fs::File::create(tmp.path().join("ingest_log/ingest_2025-06-01.ndjson")).unwrap();
+        writeln!(log, "{}", log_line(&fresh, now)).unwrap();
+        writeln!(log, "{}", log_line(&stale, now - Duration::days(30))).unwrap();
+
+        let opts = GcOptions { retention: None, min_age: Duration::zero(), dry_run: true };
+        let live = collect_references(tmp.path(), &InMemoryStorage::new(), None, now).await.unwrap();
+        let report = gc_local(&cas, &live, &opts, now).unwrap();
+        assert_eq!((report.scanned, report.referenced, report.deleted), (3, 2, 1));
+        assert_eq!(report.reclaimed_bytes, b"orphan".len() as u64);
+        assert!(cas_path(&cas, &orphan).exists(), "dry run deletes nothing");
+
+        // The orphan is too new to touch under a one-hour minimum age
+        let guarded = GcOptions { min_age: Duration::hours(1), ..opts.clone() };
+        assert_eq!(gc_local(&cas, &live, &guarded, now).unwrap().too_recent, 1);
+
+        let retention = Some(Duration::days(7));
+        let opts = GcOptions { retention, dry_run: false, ..opts };
+        let live = collect_references(tmp.path(), &InMemoryStorage::new(), retention, now).await.unwrap();
+        let report = gc_local(&cas, &live, &opts, now).unwrap();
+        assert_eq!(report.deleted, 2);
+        assert!(cas_path(&cas, &fresh).exists());
+        assert!(!cas_path(&cas, &stale).exists());
+        assert!(!cas_path(&cas, &orphan).exists());
+    }
+
+    #[tokio::test]
+    async fn payloads_raw_data_came_from_outlive_their_log_lines() {
+        let tmp = tempfile::tempdir().unwrap();
+        let cas = tmp.path().join("cas");
+        let kept = write_cas(&cas, b"listing page", &Keyring::default()).unwrap();
+        let orphan = write_cas(&cas, b"orphan", &Keyring::default()).unwrap();
+
+        // No ingest log, lineage or dead letter mentions either payload; a raw data row does
+        let storage = InMemoryStorage::new();
+        let mut raw = RawData {
+            id: None,
+            api_name: "neumos".to_string(),
+            event_api_id: "42".to_string(),
+            event_name: "The Band".to_string(),
+            venue_name: "Neumos".to_string(),
+            event_day: chrono::NaiveDate::from_ymd_opt(2025, 6, 1).unwrap(),
+            data: serde_json::json!({}),
+            processed: true,
+            event_id: None,
+            created_at: Utc::now(),
+            origin: Some(RawDataOrigin { envelope_id: "env-1".to_string(), payload_ref: kept.clone(), endpoint_id: None }),
+        };
+        storage.create_raw_data(&mut raw).await.unwrap();
+
+        let now = Utc::now();
+        let opts = GcOptions { retention: Some(Duration::zero()), min_age: Duration::zero(), dry_run: false };
+        let live = collect_references(tmp.path(), &storage, opts.retention, now).await.unwrap();
+        let report = gc_local(&cas, &live, &opts, now).unwrap();
+        assert_eq!((report.referenced, report.deleted), (1, 1));
+        assert!(cas_path(&cas, &kept).exists());
+        assert!(!cas_path(&cas, &orphan).exists());
+    }
+}
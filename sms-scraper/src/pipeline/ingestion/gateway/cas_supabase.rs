@@ -1,3 +1,4 @@
+use crate::pipeline::ingestion::encryption::Keyring;
 use sha2::{Digest, Sha256};
 
 /// Uploads bytes to Supabase Storage in a content-addressed path and returns payload_ref "cas:sha256:<hex>".
@@ -6,7 +7,9 @@ use sha2::{Digest, Sha256};
 /// - SUPABASE_SERVICE_ROLE_KEY (service role key)
 /// - SUPABASE_BUCKET (bucket name)
 /// - SUPABASE_PREFIX (optional path prefix inside bucket)
-pub fn write_cas_supabase(bytes: &[u8]) -> anyhow::Result<String> {
+///
+/// The hash is of the plaintext; the uploaded object is sealed with `keys` when encryption is on.
+pub fn write_cas_supabase(bytes: &[u8], keys: &Keyring) -> anyhow::Result<String> {
     // Allow either a full URL or a project ref
     let url = match std::env::var("SUPABASE_URL") {
         Ok(u) => u,
@@ -44,6 +47,8 @@ pub fn write_cas_supabase(bytes: &[u8]) -> anyhow::Result<String> {
         path
     );
 
+    let body = keys.seal(bytes)?.into_owned();
+
     // Execute the HTTP call using the async client within a safe blocking section of the Tokio runtime
     let result = tokio::task::block_in_place(|| {
         let fut = async move {
@@ -54,7 +59,7 @@ pub fn write_cas_supabase(bytes: &[u8]) -> anyhow::Result<String> {
                 .header("apikey", key.clone())
                 .header(reqwest::header::CONTENT_TYPE, "application/octet-stream")
                 .query(&[("upsert", "true")])
-                .body(bytes.to_vec())
+                .body(body)
                 .send()
                 .await?;
             let status = resp.status();
@@ -1,12 +1,15 @@
-use crate::pipeline::ingestion::envelope::StampedEnvelopeV1;
+use serde::Serialize;
 use chrono::Utc;
-use std::fs::{self, OpenOptions};
-use std::io::Write;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, BufRead, Read, Seek, SeekFrom, Write};
 use std::path::Path;
 
+/// Lock file in the log directory that appending processes take turns holding
+pub const APPEND_LOCK_FILE: &str = ".append.lock";
+
 /// Backward-compatible append to a fixed path (no rotation)
 #[allow(dead_code)]
-pub fn append(path: &Path, stamped: &StampedEnvelopeV1) -> anyhow::Result<()> {
+pub fn append<T: Serialize>(path: &Path, stamped: &T) -> anyhow::Result<()> {
     if let Some(parent) = path.parent() {
         fs::create_dir_all(parent)?;
     }
@@ -16,12 +19,28 @@ pub fn append(path: &Path, stamped: &StampedEnvelopeV1) -> anyhow::Result<()> {
     Ok(())
 }
 
-/// Append to a daily-rotated ingest log file under `log_dir`.
+/// Append a stamped envelope (V1 or V2) to a daily-rotated ingest log file under `log_dir`.
 /// Pattern: ingest_YYYY-MM-DD.ndjson and a symlink `ingest.ndjson` pointing to current.
-pub fn append_rotating(log_dir: &Path, stamped: &StampedEnvelopeV1) -> anyhow::Result<()> {
+pub fn append_rotating<T: Serialize>(log_dir: &Path, stamped: &T) -> anyhow::Result<()> {
+    append_line_rotating(log_dir, &serde_json::to_string(stamped)?)?;
+    Ok(())
+}
+
+/// Append one already-serialized record (without its newline) to today's log file.
+/// Gateway processes sharing `log_dir` serialize rotation and the write on an advisory
+/// lock, so records from concurrent appenders never interleave.
+pub fn append_line_rotating(log_dir: &Path, record: &str) -> std::io::Result<()> {
     // Ensure directory exists
     fs::create_dir_all(log_dir)?;
 
+    // Held until this function returns; the OS releases it if the process dies
+    let lock = OpenOptions::new()
+        .create(true)
+        .truncate(false)
+        .write(true)
+        .open(log_dir.join(APPEND_LOCK_FILE))?;
+    lock.lock()?;
+
     // Compute today's file name
     let date_str = Utc::now().format("%Y-%m-%d");
     let file_name = format!("ingest_{}.ndjson", date_str);
@@ -34,17 +53,23 @@ pub fn append_rotating(log_dir: &Path, stamped: &StampedEnvelopeV1) -> anyhow::R
     // Append to the target file
     let mut file = OpenOptions::new()
         .create(true)
+        .read(true)
         .append(true)
         .open(&target_path)?;
-    let line = serde_json::to_string(stamped)?;
-    match writeln!(file, "{}", line) {
+    let mut line = format!("{}\n", record);
+    // A writer that crashed mid-record left an unterminated line: end it so this record
+    // starts on a line of its own, and readers skip the torn one
+    if ends_mid_line(&mut file)? {
+        line.insert(0, '\n');
+    }
+    match file.write_all(line.as_bytes()) {
         Ok(_) => {
             crate::observability::metrics::ingest_log::write_success();
             crate::observability::metrics::ingest_log::write_bytes(line.len());
         }
         Err(e) => {
             crate::observability::metrics::ingest_log::write_error();
-            return Err(e.into());
+            return Err(e);
         }
     }
 
@@ -56,7 +81,64 @@ pub fn append_rotating(log_dir: &Path, stamped: &StampedEnvelopeV1) -> anyhow::R
     Ok(())
 }
 
-fn ensure_symlink_to_current(link_path: &Path, target_path: &Path) -> anyhow::Result<()> {
+/// Whether the file's last byte is something other than a newline
+fn ends_mid_line(file: &mut File) -> io::Result<bool> {
+    if file.metadata()?.len() == 0 {
+        return Ok(false);
+    }
+    file.seek(SeekFrom::End(-1))?;
+    let mut last = [0u8; 1];
+    file.read_exact(&mut last)?;
+    Ok(last[0] != b'\n')
+}
+
+/// One line read back from the log
+#[derive(Debug, PartialEq, Eq)]
+pub enum LogLine {
+    /// A whole record, without its newline
+    Record(String),
+    /// A line that is not one JSON document: what was written before a writer crashed
+    /// mid-record, ended by the next append
+    Torn,
+}
+
+/// Read the next line of the log and the number of bytes it spans. `None` at the end of
+/// the log, including when only an unterminated line remains: that record is still being
+/// written, or its writer crashed, and it is left for a later read.
+pub fn read_line(reader: &mut dyn BufRead) -> io::Result<Option<(LogLine, usize)>> {
+    let mut buf = Vec::new();
+    let read = reader.read_until(b'\n', &mut buf)?;
+    if read == 0 || buf.last() != Some(&b'\n') {
+        return Ok(None);
+    }
+    buf.pop();
+    let line = match String::from_utf8(buf) {
+        Ok(text) if text.trim().is_empty() || serde_json::from_str::<serde::de::IgnoredAny>(&text).is_ok() => {
+            LogLine::Record(text)
+        }
+        _ => {
+            tracing::warn!("Skipping a torn ingest log line ({} bytes)", read);
+            crate::observability::metrics::ingest_log::torn_line();
+            LogLine::Torn
+        }
+    };
+    Ok(Some((line, read)))
+}
+
+/// The log's complete records from `reader` on, skipping torn lines and stopping before an
+/// unterminated last line
+pub fn records(mut reader: Box<dyn BufRead + Send>) -> impl Iterator<Item = io::Result<String>> + Send {
+    std::iter::from_fn(move || loop {
+        match read_line(&mut *reader) {
+            Ok(Some((LogLine::Record(record), _))) => return Some(Ok(record)),
+            Ok(Some((LogLine::Torn, _))) => continue,
+            Ok(None) => return None,
+            Err(e) => return Some(Err(e)),
+        }
+    })
+}
+
+fn ensure_symlink_to_current(link_path: &Path, target_path: &Path) -> std::io::Result<()> {
     // If link exists, check if it already points to target; otherwise, replace it.
     if link_path.exists() {
         // Try to read current link; if not a symlink, remove it
@@ -75,7 +157,7 @@ fn ensure_symlink_to_current(link_path: &Path, target_path: &Path) -> anyhow::Re
                 let _ = fs::remove_dir_all(link_path);
                 // If both removals fail, surface the original error
                 if link_path.exists() {
-                    return Err(e.into());
+                    return Err(e);
                 }
             }
         } else {
@@ -99,7 +181,7 @@ fn ensure_symlink_to_current(link_path: &Path, target_path: &Path) -> anyhow::Re
                 let _ = fs::remove_dir_all(link_path);
                 std::os::unix::fs::symlink(target_path, link_path)?;
             }
-            Err(e) => return Err(e.into()),
+            Err(e) => return Err(e),
         }
     }
     #[cfg(windows)]
@@ -116,7 +198,7 @@ fn ensure_symlink_to_current(link_path: &Path, target_path: &Path) -> anyhow::Re
                 let _ = fs::remove_dir_all(link_path);
                 std::os::windows::fs::symlink_file(target_path, link_path)?;
             }
-            Err(e) => return Err(e.into()),
+            Err(e) => return Err(e),
         }
     }
     Ok(())
@@ -129,3 +211,81 @@ fn paths_equivalent(a: &Path, b: &Path) -> bool {
         _ => a == b,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pipeline::ingestion::ingest_log_backend::{IngestLogBackend, LocalIngestLog};
+    use crate::pipeline::ingestion::ingest_log_reader::IngestLogReader;
+    use std::io::BufReader;
+    use std::sync::Arc;
+
+    fn today_log(log_dir: &Path) -> std::path::PathBuf {
+        log_dir.join(format!("ingest_{}.ndjson", Utc::now().format("%Y-%m-%d")))
+    }
+
+    #[test]
+    fn concurrent_appenders_never_interleave_records() {
+        let tmp = tempfile::tempdir().unwrap();
+        let log_dir = tmp.path().join("ingest_log");
+        let padding = "x".repeat(64 * 1024);
+
+        // Each writer opens its own handles, so they contend on the lock file as separate
+        // gateway processes would
+        let writers: Vec<_> = (0..8)
+            .map(|writer| {
+                let log_dir = log_dir.clone();
+                let padding = padding.clone();
+                std::thread::spawn(move || {
+                    for n in 0..25 {
+                        let record = serde_json::json!({ "writer": writer, "n": n, "padding": padding });
+                        append_line_rotating(&log_dir, &record.to_string()).unwrap();
+                    }
+                })
+            })
+            .collect();
+        for writer in writers {
+            writer.join().unwrap();
+        }
+
+        let file = File::open(log_dir.join("ingest.ndjson")).unwrap();
+        let mut reader = BufReader::new(file);
+        let mut seen = std::collections::HashSet::new();
+        while let Some((line, _)) = read_line(&mut reader).unwrap() {
+            let LogLine::Record(record) = line else {
+                panic!("concurrent appends tore a line");
+            };
+            let value: serde_json::Value = serde_json::from_str(&record).unwrap();
+            assert!(seen.insert((value["writer"].as_u64().unwrap(), value["n"].as_u64().unwrap())));
+        }
+        assert_eq!(seen.len(), 8 * 25);
+    }
+
+    #[test]
+    fn partial_lines_are_left_unread_then_skipped_once_torn() {
+        let tmp = tempfile::tempdir().unwrap();
+        let log = LocalIngestLog::new(tmp.path());
+        let log_dir = tmp.path().join("ingest_log");
+        log.append(r#"{"envelope_id":"a"}"#).unwrap();
+
+        // A writer dies partway through its record
+        let mut file = OpenOptions::new().append(true).open(today_log(&log_dir)).unwrap();
+        file.write_all(br#"{"envelope_id":"b","envel"#).unwrap();
+
+        let reader = IngestLogReader::with_backend(tmp.path(), Arc::new(LocalIngestLog::new(tmp.path())));
+        let (lines, last) = reader.read_next("parser", 10).unwrap();
+        assert_eq!(lines, vec![r#"{"envelope_id":"a"}"#.to_string()]);
+        assert_eq!(last.as_deref(), Some("a"));
+        let acked = reader.ack_through("parser", "a").unwrap();
+        assert_eq!(acked.byte_offset, r#"{"envelope_id":"a"}"#.len() as u64 + 1);
+
+        // The next append ends the torn line first; readers skip it and pick up the new record
+        log.append(r#"{"envelope_id":"c"}"#).unwrap();
+        let (lines, last) = reader.read_next("parser", 10).unwrap();
+        assert_eq!(lines, vec![r#"{"envelope_id":"c"}"#.to_string()]);
+        assert_eq!(last.as_deref(), Some("c"));
+        let acked = reader.ack_through("parser", "c").unwrap();
+        assert_eq!(acked.byte_offset, fs::metadata(today_log(&log_dir)).unwrap().len());
+        assert!(reader.find_envelope_by_id("b").unwrap().is_none());
+    }
+}
@@ -1,16 +1,32 @@
 pub mod cas_fs;
+pub mod cas_gc;
 pub mod cas_supabase;
 pub mod ingest_log;
 
-use crate::pipeline::ingestion::envelope::{EnvelopeSubmissionV1, StampedEnvelopeV1};
-use crate::pipeline::ingestion::ingest_meta::IngestMeta;
+use crate::pipeline::ingestion::envelope::{
+    ChecksumMeta, EnvelopeSubmissionV1, PayloadMeta, PayloadPart, RequestMeta, StampedEnvelopeV1,
+    StampedEnvelopeV2, TimingMeta, ENVELOPE_VERSION_V2,
+};
+use sha2::{Digest, Sha256};
+use crate::pipeline::ingestion::encryption::{self, Keyring};
+use crate::pipeline::ingestion::envelope_state::{EnvelopeState, EnvelopeStates};
+use crate::pipeline::ingestion::ingest_log_backend::{self, IngestLogBackend};
+use crate::pipeline::ingestion::ingest_meta::{dedupe_horizon_secs, IngestMeta};
 use chrono::Utc;
 use std::fs;
 use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
 use uuid::Uuid;
 
+/// The dedupe check, CAS write and log append must not interleave when sources are
+/// ingested concurrently in one process
+static ACCEPT_LOCK: Mutex<()> = Mutex::new(());
+
 pub struct Gateway {
     root: PathBuf,
+    log: Arc<dyn IngestLogBackend>,
+    keyring: Option<Arc<Keyring>>,
+    dedupe: bool,
 }
 
 impl Gateway {
@@ -20,7 +36,54 @@ impl Gateway {
         let log_dir = root.join("ingest_log");
         let _ = fs::create_dir_all(&cas_dir);
         let _ = fs::create_dir_all(&log_dir);
-        Self { root }
+        let log = ingest_log_backend::from_env(&root);
+        Self { root, log, keyring: None, dedupe: true }
+    }
+
+    /// Append accepted envelopes to `log` instead of the backend chosen from the environment
+    pub fn with_log(mut self, log: Arc<dyn IngestLogBackend>) -> Self {
+        self.log = log;
+        self
+    }
+
+    /// Seal payloads and log lines with `keyring` instead of the one configured by `SMS_ENCRYPTION_*`
+    pub fn with_keyring(mut self, keyring: Arc<Keyring>) -> Self {
+        self.keyring = Some(keyring);
+        self
+    }
+
+    /// Whether a payload whose idempotency key was already accepted is recorded as a duplicate
+    /// instead of stored again; on by default, off when cadence is bypassed
+    pub fn deduplicating(mut self, dedupe: bool) -> Self {
+        self.dedupe = dedupe;
+        self
+    }
+
+    fn keys(&self) -> std::io::Result<Arc<Keyring>> {
+        match &self.keyring {
+            Some(keyring) => Ok(keyring.clone()),
+            None => encryption::keyring(),
+        }
+    }
+
+    /// The ingest metadata db, with idempotency keys past the dedupe horizon dropped first so a
+    /// refetch after that long is stored again rather than deduplicated against a stale key
+    fn open_meta(&self) -> anyhow::Result<IngestMeta> {
+        let meta = IngestMeta::open_at_root(&self.root)?;
+        if let Some(horizon) = dedupe_horizon_secs() {
+            let expired = meta.expire_dedupe_keys(Utc::now().timestamp() - horizon)?;
+            if expired > 0 {
+                tracing::debug!("Expired {} idempotency keys older than {}s", expired, horizon);
+            }
+        }
+        Ok(meta)
+    }
+
+    /// Append one stamped envelope, sealed when encryption is on
+    fn append<T: serde::Serialize>(&self, keys: &Keyring, stamped: &T) -> anyhow::Result<()> {
+        let record = serde_json::to_string(stamped)?;
+        self.log.append(&keys.seal_line(&record)?)?;
+        Ok(())
     }
 
     // Dedupe index now stored in SQLite (ingest_log/meta.db) via IngestMeta
@@ -30,17 +93,14 @@ impl Gateway {
         env: EnvelopeSubmissionV1,
         payload_bytes: &[u8],
     ) -> anyhow::Result<StampedEnvelopeV1> {
+        let _guard = ACCEPT_LOCK.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
         let t0 = std::time::Instant::now();
+        let keys = self.keys()?;
 
-        // Check if cadence is bypassed
-        let bypass_cadence = std::env::var("SMS_BYPASS_CADENCE")
-            .map(|v| v == "1" || v.eq_ignore_ascii_case("true"))
-            .unwrap_or(false);
-
-        // Dedupe by idempotency_key (SQLite-backed) - only if not bypassing cadence
-        let meta = IngestMeta::open_at_root(&self.root)?;
+        // Dedupe by idempotency_key (SQLite-backed) - unless switched off
+        let meta = self.open_meta()?;
         let idk = env.idempotency_key.clone();
-        if !bypass_cadence {
+        if self.dedupe {
             if let Some(existing_id) = meta.get_envelope_by_idk(&idk)? {
                 crate::observability::metrics::gateway::envelope_deduplicated();
                 let accepted_at = Utc::now();
@@ -52,14 +112,16 @@ impl Gateway {
                     payload_ref: String::new(),
                     dedupe_of: Some(existing_id.clone()),
                     envelope: EnvelopeSubmissionV1 {
-                        timing: crate::pipeline::ingestion::envelope::TimingMeta {
+                        timing: TimingMeta {
                             gateway_received_at: Some(accepted_at),
                             ..env.timing.clone()
                         },
                         ..env.clone()
                     },
+                    encryption: None,
                 };
-                ingest_log::append_rotating(&self.root.join("ingest_log"), &dup)?;
+                self.append(&keys, &dup)?;
+                self.received(&dup.envelope_id, &dup.envelope.source_id, dup.dedupe_of.as_deref());
                 let dur = t0.elapsed().as_secs_f64();
                 crate::observability::metrics::gateway::processing_duration(dur);
                 return Ok(dup);
@@ -71,27 +133,7 @@ impl Gateway {
         let accepted_at = Utc::now();
         let envelope_id = Uuid::new_v4().to_string();
 
-        // Write payload to CAS (Supabase if configured, otherwise local FS)
-        let _cas_t0 = std::time::Instant::now();
-        let payload_ref = if (std::env::var("SUPABASE_URL").is_ok()
-            || std::env::var("SUPABASE_PROJECT_REF").is_ok())
-            && std::env::var("SUPABASE_SERVICE_ROLE_KEY").is_ok()
-            && std::env::var("SUPABASE_BUCKET").is_ok()
-        {
-            let result = cas_supabase::write_cas_supabase(payload_bytes);
-            match &result {
-                Ok(_) => crate::observability::metrics::gateway::cas_write_success(),
-                Err(_) => crate::observability::metrics::gateway::cas_write_error(),
-            }
-            result?
-        } else {
-            let result = cas_fs::write_cas(&self.root.join("cas"), payload_bytes);
-            match &result {
-                Ok(_) => crate::observability::metrics::gateway::cas_write_success(),
-                Err(_) => crate::observability::metrics::gateway::cas_write_error(),
-            }
-            result?
-        };
+        let payload_ref = self.write_payload(payload_bytes, &keys)?;
 
         let stamped = StampedEnvelopeV1 {
             envelope_version: env.envelope_version.clone(),
@@ -100,20 +142,231 @@ impl Gateway {
             payload_ref: payload_ref.clone(),
             dedupe_of: None,
             envelope: EnvelopeSubmissionV1 {
-                timing: crate::pipeline::ingestion::envelope::TimingMeta {
+                timing: TimingMeta {
                     gateway_received_at: Some(accepted_at),
                     ..env.timing.clone()
                 },
                 ..env.clone()
             },
+            encryption: keys.meta(),
         };
 
         // First time: append log and index
-        ingest_log::append_rotating(&self.root.join("ingest_log"), &stamped)?;
+        self.append(&keys, &stamped)?;
         meta.put_dedupe_mapping(&idk, &envelope_id)?;
+        self.received(&envelope_id, &stamped.envelope.source_id, None);
 
         let dur = t0.elapsed().as_secs_f64();
         crate::observability::metrics::gateway::processing_duration(dur);
         Ok(stamped)
     }
+
+    /// Accept a paginated fetch as one V2 envelope. `parts` are (request, bytes) per page in
+    /// fetch order; each page lands in CAS separately and is referenced by its index.
+    pub fn accept_parts(
+        &self,
+        env: EnvelopeSubmissionV1,
+        parts: &[(RequestMeta, Vec<u8>)],
+    ) -> anyhow::Result<StampedEnvelopeV2> {
+        let _guard = ACCEPT_LOCK.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        let t0 = std::time::Instant::now();
+        let keys = self.keys()?;
+
+        let meta = self.open_meta()?;
+        let idk = env.idempotency_key.clone();
+        let accepted_at = Utc::now();
+        let envelope_id = Uuid::new_v4().to_string();
+        let envelope = EnvelopeSubmissionV1 {
+            envelope_version: ENVELOPE_VERSION_V2.to_string(),
+            timing: TimingMeta {
+                gateway_received_at: Some(accepted_at),
+                ..env.timing.clone()
+            },
+            ..env
+        };
+
+        if self.dedupe {
+            if let Some(existing_id) = meta.get_envelope_by_idk(&idk)? {
+                crate::observability::metrics::gateway::envelope_deduplicated();
+                let dup = StampedEnvelopeV2 {
+                    envelope_version: ENVELOPE_VERSION_V2.to_string(),
+                    envelope_id,
+                    accepted_at,
+                    payload_parts: Vec::new(),
+                    dedupe_of: Some(existing_id),
+                    envelope,
+                    encryption: None,
+                };
+                self.append(&keys, &dup)?;
+                self.received(&dup.envelope_id, &dup.envelope.source_id, dup.dedupe_of.as_deref());
+                crate::observability::metrics::gateway::processing_duration(t0.elapsed().as_secs_f64());
+                return Ok(dup);
+            }
+        }
+
+        crate::observability::metrics::gateway::envelope_accepted();
+        let mut payload_parts = Vec::with_capacity(parts.len());
+        for (index, (request, bytes)) in parts.iter().enumerate() {
+            let payload_ref = self.write_payload(bytes, &keys)?;
+            payload_parts.push(PayloadPart {
+                index: index as u32,
+                payload_ref,
+                payload_meta: PayloadMeta {
+                    mime_type: envelope.payload_meta.mime_type.clone(),
+                    size_bytes: bytes.len() as u64,
+                    checksum: ChecksumMeta { sha256: hex::encode(Sha256::digest(bytes)) },
+                },
+                request: request.clone(),
+            });
+        }
+
+        let stamped = StampedEnvelopeV2 {
+            envelope_version: ENVELOPE_VERSION_V2.to_string(),
+            envelope_id: envelope_id.clone(),
+            accepted_at,
+            payload_parts,
+            dedupe_of: None,
+            envelope,
+            encryption: keys.meta(),
+        };
+        self.append(&keys, &stamped)?;
+        meta.put_dedupe_mapping(&idk, &envelope_id)?;
+        self.received(&envelope_id, &stamped.envelope.source_id, None);
+
+        crate::observability::metrics::gateway::processing_duration(t0.elapsed().as_secs_f64());
+        Ok(stamped)
+    }
+
+    /// Start tracking an appended envelope's processing state
+    fn received(&self, envelope_id: &str, source_id: &str, dedupe_of: Option<&str>) {
+        let detail = dedupe_of.map(|original| format!("duplicate of {}", original));
+        EnvelopeStates::new(&self.root).record(envelope_id, source_id, EnvelopeState::Received, detail.as_deref());
+    }
+
+    /// Write payload to CAS (Supabase if configured, otherwise local FS)
+    fn write_payload(&self, payload_bytes: &[u8], keys: &Keyring) -> anyhow::Result<String> {
+        #[cfg(feature = "chaos")]
+        if let Err(fault) = crate::infra::fault_injection::injector()
+            .check(crate::infra::fault_injection::Fault::CasWrite, &self.root.display().to_string())
+        {
+            crate::observability::metrics::gateway::cas_write_error();
+            return Err(fault.into());
+        }
+        if (std::env::var("SUPABASE_URL").is_ok()
+            || std::env::var("SUPABASE_PROJECT_REF").is_ok())
+            && std::env::var("SUPABASE_SERVICE_ROLE_KEY").is_ok()
+            && std::env::var("SUPABASE_BUCKET").is_ok()
+        {
+            let result = cas_supabase::write_cas_supabase(payload_bytes, keys);
+            match &result {
+                Ok(_) => crate::observability::metrics::gateway::cas_write_success(),
+                Err(_) => crate::observability::metrics::gateway::cas_write_error(),
+            }
+            result
+        } else {
+            let result = cas_fs::write_cas(&self.root.join("cas"), payload_bytes, keys);
+            match &result {
+                Ok(_) => crate::observability::metrics::gateway::cas_write_success(),
+                Err(_) => crate::observability::metrics::gateway::cas_write_error(),
+            }
+            result
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pipeline::ingestion::envelope::{read_log_line, LegalMeta};
+
+    fn request(page: u32) -> RequestMeta {
+        RequestMeta {
+            url: format!("https://www.kexp.org/events/?page={}", page),
+            method: "GET".to_string(),
+            status: Some(200),
+            etag: None,
+            last_modified: None,
+            endpoint_id: None,
+        }
+    }
+
+    fn submission(idempotency_key: &str) -> EnvelopeSubmissionV1 {
+        EnvelopeSubmissionV1 {
+            envelope_version: "1.0.0".to_string(),
+            source_id: "kexp".to_string(),
+            idempotency_key: idempotency_key.to_string(),
+            payload_meta: PayloadMeta {
+                mime_type: "text/html".to_string(),
+                size_bytes: 10,
+                checksum: ChecksumMeta { sha256: String::new() },
+            },
+            request: request(1),
+            timing: TimingMeta { fetched_at: Utc::now(), gateway_received_at: None },
+            legal: LegalMeta { license_id: "test".to_string() },
+        }
+    }
+
+    #[test]
+    fn accept_parts_stores_each_page_and_logs_one_v2_envelope() {
+        let tmp = tempfile::tempdir().unwrap();
+        let env = submission("kexp:pages");
+
+        let gw = Gateway::new(tmp.path());
+        let stamped = gw
+            .accept_parts(env, &[(request(1), b"page one".to_vec()), (request(2), b"page two".to_vec())])
+            .unwrap();
+        assert_eq!(stamped.payload_parts.len(), 2);
+        assert_eq!(stamped.payload_parts[1].request.url, "https://www.kexp.org/events/?page=2");
+        for part in &stamped.payload_parts {
+            let hex = part.payload_ref.trim_start_matches("cas:sha256:");
+            assert_eq!(hex, part.payload_meta.checksum.sha256);
+            assert!(tmp.path().join("cas/sha256").join(&hex[0..2]).join(&hex[2..4]).join(hex).exists());
+        }
+
+        let log = std::fs::read_to_string(tmp.path().join("ingest_log/ingest.ndjson")).unwrap();
+        let logged = read_log_line(log.lines().next().unwrap()).unwrap();
+        assert_eq!(logged.envelope_version, ENVELOPE_VERSION_V2);
+        assert_eq!(logged.payload_refs(), stamped.payload_parts.iter().map(|p| p.payload_ref.clone()).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn encrypts_payloads_and_log_lines_that_readers_decrypt() {
+        use crate::infra::payload_store::decrypt_if_sealed;
+        use crate::pipeline::ingestion::ingest_log_reader::IngestLogReader;
+        use tokio::io::AsyncReadExt;
+
+        let tmp = tempfile::tempdir().unwrap();
+        let keys = Arc::new(Keyring::new("2025-q3", [7; 32]));
+        let gw = Gateway::new(tmp.path()).with_keyring(keys.clone());
+        let stamped = gw.accept(submission("kexp:sealed"), b"<html>secret lineup</html>").unwrap();
+        assert_eq!(stamped.encryption.as_ref().unwrap().key_id, "2025-q3");
+
+        let hex = stamped.payload_ref.trim_start_matches("cas:sha256:");
+        assert_eq!(hex, hex::encode(Sha256::digest(b"<html>secret lineup</html>")));
+        let stored = fs::read(tmp.path().join("cas/sha256").join(&hex[0..2]).join(&hex[2..4]).join(hex)).unwrap();
+        assert!(!String::from_utf8_lossy(&stored).contains("secret lineup"));
+        assert_eq!(keys.open(&stored).unwrap().as_ref(), b"<html>secret lineup</html>");
+
+        let raw = fs::read_to_string(tmp.path().join("ingest_log/ingest.ndjson")).unwrap();
+        assert!(!raw.contains("kexp"));
+
+        let reader = IngestLogReader::new(tmp.path()).with_keyring(keys.clone());
+        let (lines, last) = reader.read_next("parse", 10).unwrap();
+        assert_eq!(last.as_deref(), Some(stamped.envelope_id.as_str()));
+        assert_eq!(read_log_line(&lines[0]).unwrap().encryption.unwrap().key_id, "2025-q3");
+        assert_eq!(reader.pending_by_source("parse").unwrap().get("kexp"), Some(&1));
+        let off = reader.ack_through("parse", &stamped.envelope_id).unwrap();
+        assert_eq!(off.byte_offset, raw.len() as u64);
+
+        // Without the key the log is unreadable rather than silently empty
+        assert!(IngestLogReader::new(tmp.path()).with_keyring(Arc::new(Keyring::default())).read_next("other", 10).is_err());
+
+        let plaintext = tokio::runtime::Runtime::new().unwrap().block_on(async {
+            let mut opened = decrypt_if_sealed(Box::new(std::io::Cursor::new(stored)), &keys).await.unwrap();
+            let mut bytes = Vec::new();
+            opened.read_to_end(&mut bytes).await.unwrap();
+            bytes
+        });
+        assert_eq!(plaintext, b"<html>secret lineup</html>");
+    }
 }
@@ -0,0 +1,253 @@
+use crate::observability::RunTracker;
+use crate::pipeline::ingestion::ingest_common::{ingest_spec, is_cadence_skip, is_quota_skip, HostLimits, IngestOptions};
+use crate::pipeline::ingestion::registry::{load_source_spec, SourceSpecV1};
+use serde::Serialize;
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Instant;
+use tokio::sync::Semaphore;
+use tokio::task::JoinSet;
+
+/// How many sources run at once overall, and how many endpoints fetch at once from any single host
+#[derive(Debug, Clone, Copy)]
+pub struct GatewayAllLimits {
+    pub concurrency: usize,
+    pub per_host: usize,
+}
+
+impl Default for GatewayAllLimits {
+    fn default() -> Self {
+        Self { concurrency: 4, per_host: 1 }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SourceIngestStatus {
+    Ingested,
+    Deduplicated,
+    CadenceSkipped,
+    QuotaExceeded,
+    Failed,
+}
+
+impl SourceIngestStatus {
+    fn label(&self) -> &'static str {
+        match self {
+            SourceIngestStatus::Ingested => "ingested",
+            SourceIngestStatus::Deduplicated => "deduplicated",
+            SourceIngestStatus::CadenceSkipped => "cadence_skip",
+            SourceIngestStatus::QuotaExceeded => "quota_exceeded",
+            SourceIngestStatus::Failed => "failed",
+        }
+    }
+}
+
+/// One row of the batch report
+#[derive(Debug, Clone, Serialize)]
+pub struct SourceIngestSummary {
+    pub source_id: String,
+    pub host: String,
+    pub status: SourceIngestStatus,
+    pub bytes: usize,
+    /// First endpoint's envelope
+    pub envelope_id: Option<String>,
+    /// Every envelope the run produced, one per endpoint
+    pub envelope_ids: Vec<String>,
+    pub dedupe_of: Option<String>,
+    pub error: Option<String>,
+    pub duration_ms: u64,
+}
+
+/// Machine-readable result of a `gateway-all` run
+#[derive(Debug, Clone, Serialize)]
+pub struct GatewayAllReport {
+    pub run_id: String,
+    pub started_at: chrono::DateTime<chrono::Utc>,
+    pub finished_at: chrono::DateTime<chrono::Utc>,
+    pub sources: Vec<SourceIngestSummary>,
+}
+
+impl GatewayAllReport {
+    pub fn count(&self, status: SourceIngestStatus) -> usize {
+        self.sources.iter().filter(|s| s.status == status).count()
+    }
+
+    /// Fixed-width table of source, status, bytes and dedupe target
+    pub fn render_table(&self) -> String {
+        let width = self
+            .sources
+            .iter()
+            .map(|s| s.source_id.len())
+            .max()
+            .unwrap_or(0)
+            .max("SOURCE".len());
+        let mut out = format!("{:<width$}  {:<12}  {:>10}  DEDUPE_OF\n", "SOURCE", "STATUS", "BYTES");
+        for s in &self.sources {
+            out.push_str(&format!(
+                "{:<width$}  {:<12}  {:>10}  {}\n",
+                s.source_id,
+                s.status.label(),
+                s.bytes,
+                s.dedupe_of.as_deref().unwrap_or("-"),
+            ));
+        }
+        out
+    }
+}
+
+/// Enabled registry sources as (source_id, host of first endpoint), sorted by id
+pub fn enabled_sources(registry_dir: &Path) -> anyhow::Result<Vec<(String, String)>> {
+    let mut specs = Vec::new();
+    for entry in std::fs::read_dir(registry_dir)? {
+        let path = entry?.path();
+        if path.extension().and_then(|e| e.to_str()) != Some("json") {
+            continue;
+        }
+        let spec = load_source_spec(&path)
+            .map_err(|e| anyhow::anyhow!("invalid registry entry {}: {}", path.display(), e))?;
+        specs.push(spec);
+    }
+    Ok(enabled_from_specs(&specs))
+}
+
+/// The enabled specs as (source_id, host) pairs, sorted by source_id
+pub fn enabled_from_specs(specs: &[SourceSpecV1]) -> Vec<(String, String)> {
+    enabled_specs(specs).into_iter().map(|spec| (spec.source_id.clone(), first_host(spec))).collect()
+}
+
+fn enabled_specs(specs: &[SourceSpecV1]) -> Vec<&SourceSpecV1> {
+    let mut enabled: Vec<&SourceSpecV1> = specs.iter().filter(|spec| spec.enabled).collect();
+    enabled.sort_by(|a, b| a.source_id.cmp(&b.source_id));
+    enabled
+}
+
+/// Host of the first endpoint, which reports list the source under
+fn first_host(spec: &SourceSpecV1) -> String {
+    spec.endpoints
+        .first()
+        .and_then(|ep| reqwest::Url::parse(&ep.url).ok())
+        .and_then(|url| url.host_str().map(str::to_string))
+        .unwrap_or_default()
+}
+
+/// Ingest every enabled source of a registry snapshot through the gateway, honoring cadence
+/// (unless `options` bypass it) and the overall and per-host concurrency limits. Rows come
+/// back sorted by source id.
+pub async fn ingest_all(specs: &[SourceSpecV1], limits: GatewayAllLimits, options: &IngestOptions) -> GatewayAllReport {
+    let tracker = RunTracker::open("gateway_all", None);
+    let overall = Arc::new(Semaphore::new(limits.concurrency.max(1)));
+    // Each endpoint takes a permit for its own host, so a source whose endpoints span hosts
+    // is limited on every one of them
+    let options = IngestOptions { host_limits: Some(Arc::new(HostLimits::new(limits.per_host))), ..options.clone() };
+
+    let mut tasks = JoinSet::new();
+    for (index, spec) in enabled_specs(specs).into_iter().enumerate() {
+        let spec = spec.clone();
+        let host = first_host(&spec);
+        let overall = overall.clone();
+        let options = options.clone();
+        tasks.spawn(async move {
+            let _slot = overall.acquire_owned().await;
+            (index, ingest_one(spec, host, &options).await)
+        });
+    }
+
+    let mut rows = Vec::new();
+    while let Some(joined) = tasks.join_next().await {
+        match joined {
+            Ok(row) => rows.push(row),
+            Err(e) => tracing::error!("gateway-all task panicked: {}", e),
+        }
+    }
+    rows.sort_by_key(|(index, _)| *index);
+    for (_, row) in &rows {
+        tracker.record_stage("ingest_source", row.duration_ms as f64 / 1000.0);
+    }
+
+    let failed = rows.iter().filter(|(_, row)| row.status == SourceIngestStatus::Failed).count();
+    let run = if failed == 0 {
+        tracker.succeed()
+    } else {
+        tracker.fail(format!("{} of {} sources failed", failed, rows.len()))
+    };
+    GatewayAllReport {
+        run_id: run.run_id,
+        started_at: run.started_at,
+        finished_at: run.finished_at,
+        sources: rows.into_iter().map(|(_, row)| row).collect(),
+    }
+}
+
+async fn ingest_one(spec: SourceSpecV1, host: String, options: &IngestOptions) -> SourceIngestSummary {
+    let t0 = Instant::now();
+    let result = ingest_spec(&spec, options).await;
+    let duration_ms = t0.elapsed().as_millis() as u64;
+    let mut summary = SourceIngestSummary {
+        source_id: spec.source_id,
+        host,
+        status: SourceIngestStatus::Failed,
+        bytes: 0,
+        envelope_id: None,
+        envelope_ids: Vec::new(),
+        dedupe_of: None,
+        error: None,
+        duration_ms,
+    };
+    match result {
+        Ok(ingested) => {
+            // Deduplicated only when no endpoint brought anything new
+            summary.status = if ingested.iter().all(|i| i.dedupe_of.is_some()) {
+                SourceIngestStatus::Deduplicated
+            } else {
+                SourceIngestStatus::Ingested
+            };
+            summary.bytes = ingested.iter().flat_map(|i| &i.parts).map(|part| part.payload.len()).sum();
+            summary.envelope_ids = ingested.iter().map(|i| i.envelope_id.clone()).collect();
+            if let Some(first) = ingested.into_iter().next() {
+                summary.envelope_id = Some(first.envelope_id);
+                summary.dedupe_of = first.dedupe_of;
+            }
+        }
+        Err(e) if is_cadence_skip(&e) => summary.status = SourceIngestStatus::CadenceSkipped,
+        Err(e) if is_quota_skip(&e) => {
+            summary.status = SourceIngestStatus::QuotaExceeded;
+            summary.error = Some(e.to_string());
+        }
+        Err(e) => summary.error = Some(e.to_string()),
+    }
+    summary
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn enabled_sources_skips_disabled_entries() {
+        let tmp = tempfile::tempdir().unwrap();
+        let entry = |id: &str, enabled: bool, url: &str| {
+            serde_json::json!({
+                "source_id": id,
+                "enabled": enabled,
+                "endpoints": [{ "url": url, "method": "GET" }],
+                "content": { "allowed_mime_types": ["text/html"], "max_payload_size_bytes": 1024 },
+                "policy": { "license_id": "test" }
+            })
+            .to_string()
+        };
+        std::fs::write(tmp.path().join("b.json"), entry("b", true, "https://venue.example/events")).unwrap();
+        std::fs::write(tmp.path().join("a.json"), entry("a", true, "https://venue.example/other")).unwrap();
+        std::fs::write(tmp.path().join("c.json"), entry("c", false, "https://c.example/")).unwrap();
+        std::fs::write(tmp.path().join("notes.txt"), "ignored").unwrap();
+
+        let sources = enabled_sources(tmp.path()).unwrap();
+        assert_eq!(
+            sources,
+            vec![
+                ("a".to_string(), "venue.example".to_string()),
+                ("b".to_string(), "venue.example".to_string()),
+            ]
+        );
+    }
+}
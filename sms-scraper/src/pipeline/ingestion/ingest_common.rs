@@ -1,3 +1,4 @@
+use crate::pipeline::ingestion::cadence::CadencePolicy;
 use crate::pipeline::ingestion::envelope::{
     ChecksumMeta, EnvelopeSubmissionV1, LegalMeta, PayloadMeta, RequestMeta, TimingMeta,
 };
@@ -5,189 +6,393 @@ use sms_core::common::error::{Result, ScraperError};
 use crate::pipeline::ingestion::gateway::Gateway;
 use crate::pipeline::ingestion::idempotency::compute_idempotency_key;
 use crate::pipeline::ingestion::ingest_meta::IngestMeta;
+use crate::pipeline::ingestion::mailbox::{fetch_new, MailboxSpec, MESSAGE_MIME};
+use crate::pipeline::ingestion::quota::{check_quota, record_usage, UsageCounter, QUOTA_SKIP_PREFIX};
 use crate::pipeline::ingestion::rate_limiter::{Limits, RateLimiter};
-use crate::pipeline::ingestion::registry::load_source_spec;
-use reqwest::header::{CONTENT_LENGTH, CONTENT_TYPE, ETAG, LAST_MODIFIED};
-use std::path::Path;
+use crate::pipeline::ingestion::pagination::{next_link, PaginationMode, PaginationSpec};
+use crate::pipeline::ingestion::registry::{EndpointSpec, SourceSpecV1, WindowingSpec};
+use crate::pipeline::ingestion::registry_watch;
+use crate::pipeline::ingestion::windowing::{merge_wix_payloads, month_windows, window_url};
+use crate::pipeline::ingestion::content_encoding::{read_body_limited, BodyError};
+use crate::infra::http_client::{bootstrap_request, client_builder, endpoint_headers};
+use reqwest::header::{ACCEPT_ENCODING, CONTENT_TYPE, ETAG, LAST_MODIFIED};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
 use std::time::Instant;
-use tracing::debug;
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+use tracing::{debug, instrument};
 
 /// Fetch payload bytes for a source defined in the registry and persist an ingest envelope via the gateway.
 ///
 /// This centralizes the new ingestion behavior (registry lookup, cadence enforcement, rate limiting,
 /// safety checks, idempotency, gateway accept, and cadence update) so individual ingestors can focus on parsing.
-pub async fn fetch_payload_and_log(source_id: &str) -> Result<Vec<u8>> {
-    // 1) Load registry entry
-    let reg_path = Path::new(".")
-        .join("registry/sources")
-        .join(format!("{}.json", source_id));
-
-    let spec = match load_source_spec(&reg_path) {
-        Ok(spec) => {
-            crate::observability::metrics::sources::registry_load_success();
-            spec
-        }
-        Err(e) => {
-            crate::observability::metrics::sources::registry_load_error();
-            return Err(ScraperError::Api {
-                message: format!("Failed to load registry for {}: {}", source_id, e),
-            });
-        }
+/// Returns every payload the fetch produced, in registry order: one per endpoint, envelope page or message.
+pub async fn fetch_payloads_and_log(source_id: &str) -> Result<Vec<Vec<u8>>> {
+    ingest_source(source_id)
+        .await
+        .map(|ingested| ingested.into_iter().flat_map(|i| i.parts).map(|part| part.payload).collect())
+}
+
+/// Where gateway ingestion keeps its CAS, ingest log and metadata, and whether it waits for
+/// the source's cadence
+#[derive(Debug, Clone)]
+pub struct IngestOptions {
+    pub data_root: PathBuf,
+    /// Fetch even if the source was fetched within its cadence, and store the payload again
+    /// even if it's unchanged; the monthly quota still applies
+    pub bypass_cadence: bool,
+    /// Caps concurrent fetches per host across every source sharing these options
+    pub host_limits: Option<Arc<HostLimits>>,
+}
+
+impl Default for IngestOptions {
+    fn default() -> Self {
+        Self { data_root: Path::new(".").join("data"), bypass_cadence: false, host_limits: None }
+    }
+}
+
+/// A semaphore per host, created on first use, so sources whose endpoints share a host take
+/// turns fetching from it
+#[derive(Debug)]
+pub struct HostLimits {
+    per_host: usize,
+    hosts: Mutex<HashMap<String, Arc<Semaphore>>>,
+}
+
+impl HostLimits {
+    pub fn new(per_host: usize) -> Self {
+        Self { per_host: per_host.max(1), hosts: Mutex::new(HashMap::new()) }
+    }
+
+    /// Wait for a slot on `url`'s host; URLs without one share a single slot pool
+    pub async fn acquire(&self, url: &str) -> OwnedSemaphorePermit {
+        let host = reqwest::Url::parse(url).ok().and_then(|u| u.host_str().map(str::to_string)).unwrap_or_default();
+        let semaphore = self
+            .hosts
+            .lock()
+            .unwrap()
+            .entry(host)
+            .or_insert_with(|| Arc::new(Semaphore::new(self.per_host)))
+            .clone();
+        semaphore.acquire_owned().await.expect("host semaphores are never closed")
+    }
+}
+
+/// Outcome of one endpoint's gateway ingestion
+#[derive(Debug, Clone)]
+pub struct GatewayIngest {
+    /// Set on multi-endpoint sources (see `SourceSpecV1::endpoint_id`)
+    pub endpoint_id: Option<String>,
+    pub envelope_id: String,
+    /// Envelope this fetch duplicated, when the payload was already seen
+    pub dedupe_of: Option<String>,
+    /// The fetched payloads: one, or one per page of a multi-part envelope
+    pub parts: Vec<IngestedPart>,
+}
+
+/// One payload of an ingested envelope
+#[derive(Debug, Clone)]
+pub struct IngestedPart {
+    /// CAS reference of `payload`; empty when the fetch was deduplicated
+    pub payload_ref: String,
+    /// The fetched bytes
+    pub payload: Vec<u8>,
+}
+
+/// Like `fetch_payloads_and_log`, but fetches every endpoint of the source and reports the
+/// envelope the gateway stamped for each, in registry order. The spec comes from the shared
+/// registry snapshot (`registry_watch::shared`).
+pub async fn ingest_source(source_id: &str) -> Result<Vec<GatewayIngest>> {
+    let result = match registry_spec(source_id) {
+        Ok(spec) => return ingest_spec(&spec, &IngestOptions::default()).await,
+        Err(e) => Err(e),
+    };
+    record_fetch_outcome(source_id, &IngestOptions::default().data_root, &result);
+    result
+}
+
+/// Ingest a source whose spec the caller already holds, e.g. from a registry snapshot
+#[instrument(name = "ingest", skip(spec), fields(source_id = %spec.source_id, envelope_id = tracing::field::Empty))]
+pub async fn ingest_spec(spec: &SourceSpecV1, options: &IngestOptions) -> Result<Vec<GatewayIngest>> {
+    let result = fetch_and_accept(spec, options).await;
+    record_fetch_outcome(&spec.source_id, &options.data_root, &result);
+    result
+}
+
+fn registry_spec(source_id: &str) -> Result<SourceSpecV1> {
+    let registry = registry_watch::shared().map_err(|e| {
+        crate::observability::metrics::sources::registry_load_error();
+        ScraperError::Api { message: format!("Failed to load registry for {}: {}", source_id, e) }
+    })?;
+    crate::observability::metrics::sources::registry_load_success();
+    registry.snapshot().get(source_id).cloned().ok_or_else(|| ScraperError::Api {
+        message: format!("Source {} is not in the registry", source_id),
+    })
+}
+
+/// True for the error returned when a source was fetched too recently
+pub fn is_cadence_skip(err: &ScraperError) -> bool {
+    matches!(err, ScraperError::Api { message } if message.starts_with("cadence_skip"))
+}
+
+/// True for the error returned when a source's monthly quota is spent
+pub fn is_quota_skip(err: &ScraperError) -> bool {
+    matches!(err, ScraperError::Api { message } if message.starts_with(QUOTA_SKIP_PREFIX))
+}
+
+/// Track last success / consecutive failures in IngestMeta; cadence and quota skips are not fetch attempts.
+fn record_fetch_outcome(source_id: &str, data_root: &Path, result: &Result<Vec<GatewayIngest>>) {
+    let Ok(meta) = IngestMeta::open_at_root(data_root) else {
+        return;
+    };
+    let recorded = match result {
+        Ok(_) => meta.record_fetch_success(source_id, chrono::Utc::now().timestamp()),
+        Err(e) if is_cadence_skip(e) || is_quota_skip(e) => Ok(()),
+        Err(e) => meta.record_fetch_failure(source_id, &e.to_string()),
     };
+    if let Err(e) = recorded {
+        debug!("Failed to record fetch outcome for {}: {}", source_id, e);
+    }
+}
 
+async fn fetch_and_accept(spec: &SourceSpecV1, options: &IngestOptions) -> Result<Vec<GatewayIngest>> {
+    // 1) The registry entry was validated when the snapshot was loaded
+    let source_id = spec.source_id.as_str();
+    let data_root = options.data_root.as_path();
     if !spec.enabled {
         return Err(ScraperError::Api {
             message: format!("Source {} is disabled in registry", source_id),
         });
     }
-    let ep = spec.endpoints.first().ok_or_else(|| ScraperError::Api {
-        message: "No endpoint in registry".into(),
-    })?;
-
-    // 2) Cadence: enforce at most twice/day per source (unless bypassed)
-    let data_root = Path::new(".").join("data");
-    let bypass_cadence = std::env::var("SMS_BYPASS_CADENCE")
-        .map(|v| v == "1" || v.eq_ignore_ascii_case("true"))
-        .unwrap_or(false);
+    if spec.endpoints.is_empty() {
+        return Err(ScraperError::Api {
+            message: "No endpoint in registry".into(),
+        });
+    }
 
-    if !bypass_cadence {
-        let meta = IngestMeta::open_at_root(&data_root).map_err(|e| ScraperError::Api {
+    // 2) Cadence: the registry's cron policy, or at most twice/day per source (unless bypassed)
+    if !options.bypass_cadence {
+        let meta = IngestMeta::open_at_root(data_root).map_err(|e| ScraperError::Api {
             message: format!("meta open failed: {}", e),
         })?;
-        let now = chrono::Utc::now().timestamp();
-        let min_interval_secs: i64 = 12 * 60 * 60;
-        if let Some(last) =
-            meta.get_last_fetched_at(&spec.source_id)
-                .map_err(|e| ScraperError::Api {
-                    message: format!("meta read failed: {}", e),
-                })?
-        {
-            if now - last < min_interval_secs {
-                // Cadence skipped
-                return Err(ScraperError::Api {
-                    message: "cadence_skip: fetched within last 12h".into(),
-                });
-            }
+        let policy = CadencePolicy::from_spec(spec.cadence.as_ref()).map_err(|e| ScraperError::Api {
+            message: format!("Invalid cadence for {}: {}", source_id, e),
+        })?;
+        let last = meta
+            .get_last_fetched_at(&spec.source_id)
+            .map_err(|e| ScraperError::Api {
+                message: format!("meta read failed: {}", e),
+            })?
+            .and_then(|ts| chrono::DateTime::from_timestamp(ts, 0));
+        if let Some(reason) = policy.skip_reason(last, chrono::Utc::now()) {
+            // Cadence skipped
+            return Err(ScraperError::Api { message: reason });
         }
         // Cadence allowed
     } else {
         // Cadence bypassed
     }
 
-    // 3) Fetch bytes and headers with rate limiting per registry
-    let rl = RateLimiter::new(Limits {
-        requests_per_min: spec.rate_limits.requests_per_min,
-        bytes_per_min: spec.rate_limits.bytes_per_min,
-        concurrency: spec.rate_limits.concurrency.map(|c| c.max(1)),
-    });
-    // Build client - reqwest will automatically handle gzip/deflate decompression
-    // when the "gzip" and "deflate" features are enabled
-    let client = reqwest::Client::new();
-    rl.acquire(0).await; // acquire for RPM/concurrency before send
-    let fetch_t0 = Instant::now();
-    
-    // Add browser-like User-Agent header for sites that require it (like Wix)
-    let resp = client
-        .get(&ep.url)
-        .header("User-Agent", "Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/114.0.0.0 Safari/537.36")
-        .send()
-        .await?;
-    let status = resp.status().as_u16();
-    let headers = resp.headers().clone();
-    let bytes = resp.bytes().await?;
-    let payload = bytes.to_vec();
-    rl.acquire(payload.len() as u64).await; // account for bytes after size known
+    // 2b) Monthly quota from the registry; bypassing cadence does not lift it
+    {
+        let meta = IngestMeta::open_at_root(data_root).map_err(|e| ScraperError::Api {
+            message: format!("meta open failed: {}", e),
+        })?;
+        let exceeded = check_quota(&meta, &spec.source_id, spec.quota.as_ref(), chrono::Utc::now())
+            .map_err(|e| ScraperError::Api {
+                message: format!("meta read failed: {}", e),
+            })?;
+        if let Some(reason) = exceeded {
+            return Err(ScraperError::Api { message: reason });
+        }
+    }
 
-    // Record metrics
-    let dur = fetch_t0.elapsed().as_secs_f64();
-    if (200..=299).contains(&status) {
-        crate::observability::metrics::sources::request_success();
-        crate::observability::metrics::sources::request_duration(dur);
-        crate::observability::metrics::sources::payload_bytes(payload.len());
-    } else {
-        crate::observability::metrics::sources::request_error();
+    // 3) Fetch bytes and headers with rate limiting per registry; budgets and request
+    // spacing persist across runs
+    let rl = RateLimiter::persistent(Limits::from_spec(&spec.rate_limits), data_root, &spec.source_id);
+
+    // Every endpoint is fetched into its own envelope. Envelopes already accepted stay in the
+    // log if a later endpoint fails, but the cadence marker only moves once all of them
+    // succeed, so the whole source is retried (and unchanged payloads dedupe).
+    let mut ingested = Vec::with_capacity(spec.endpoints.len());
+    for index in 0..spec.endpoints.len() {
+        let _host = match &options.host_limits {
+            Some(limits) => Some(limits.acquire(&spec.endpoints[index].url).await),
+            None => None,
+        };
+        let endpoint = accept_endpoint(spec, index, &rl, options).await.map_err(|e| match spec.endpoint_id(index) {
+            Some(endpoint_id) => ScraperError::Api {
+                message: format!("Endpoint {} of {} failed: {}", endpoint_id, source_id, e),
+            },
+            None => e,
+        })?;
+        ingested.extend(endpoint);
     }
 
-    let content_type = headers
-        .get(CONTENT_TYPE)
-        .and_then(|v| v.to_str().ok())
-        .unwrap_or("application/octet-stream")
-        .to_string();
-    let content_length: u64 = headers
-        .get(CONTENT_LENGTH)
-        .and_then(|v| v.to_str().ok())
-        .and_then(|s| s.parse().ok())
-        .unwrap_or(payload.len() as u64);
-    let etag = headers
-        .get(ETAG)
-        .and_then(|v| v.to_str().ok())
-        .map(|s| s.to_string());
-    let last_modified = headers
-        .get(LAST_MODIFIED)
-        .and_then(|v| v.to_str().ok())
-        .map(|s| s.to_string());
+    // 7) Update cadence marker
+    {
+        let meta = IngestMeta::open_at_root(data_root).map_err(|e| ScraperError::Api {
+            message: format!("meta open failed: {}", e),
+        })?;
+        let now = chrono::Utc::now().timestamp();
+        let _ = meta.set_last_fetched_at(&spec.source_id, now);
+    }
+
+    Ok(ingested)
+}
+
+/// Fetch the endpoint at `index`, check it against the registry and accept it through the
+/// gateway tagged with the endpoint id: one envelope, or one per page for endpoints paginated
+/// with `envelope_per_page`
+async fn accept_endpoint(spec: &SourceSpecV1, index: usize, rl: &RateLimiter, options: &IngestOptions) -> Result<Vec<GatewayIngest>> {
+    let source_id = spec.source_id.as_str();
+    let data_root = options.data_root.as_path();
+    let ep = &spec.endpoints[index];
+    if ep.pagination.is_some() && spec.windowing.is_some() {
+        return Err(ScraperError::Api {
+            message: format!("{} combines pagination with windowing, which is not supported", source_id),
+        });
+    }
+    if let Some(mailbox) = &ep.mailbox {
+        return accept_mailbox(spec, index, mailbox, rl, options).await;
+    }
 
-    // 4) Safety checks against registry
-    if content_length > spec.content.max_payload_size_bytes {
+    // Decompression is done by hand so the size limit applies while streaming and
+    // both wire and decoded sizes can be recorded
+    let headers = endpoint_headers(&ep.headers)
+        .map_err(|e| ScraperError::Api { message: format!("Invalid headers for {}: {}", source_id, e) })?;
+    let client = client_builder(&ep.transport)
+        .map_err(|e| ScraperError::Api { message: format!("Invalid transport settings for {}: {}", source_id, e) })?
+        .default_headers(headers)
+        .no_gzip()
+        .no_deflate()
+        .build()
+        .map_err(|e| ScraperError::Api { message: format!("Failed to build HTTP client: {}", e) })?;
+    let usage = UsageCounter::default();
+    let fetched = fetch_source(&client, rl, spec, ep, &usage).await;
+    // Requests count against the quota even when the fetch failed part-way
+    if let Err(e) = IngestMeta::open_at_root(data_root).and_then(|meta| {
+        record_usage(&meta, &spec.source_id, spec.quota.as_ref(), usage.usage(), chrono::Utc::now())
+    }) {
+        debug!("Failed to record usage for {}: {}", source_id, e);
+    }
+    let pages = fetched?;
+
+    // 4) Safety checks against registry (single fetches were already capped while streaming;
+    // this catches merged windowed payloads)
+    for page in &pages {
+        check_content(spec, page)?;
+    }
+
+    let per_page = ep.pagination.as_ref().is_some_and(|p| p.mode == PaginationMode::EnvelopePerPage);
+    if pages.len() > 1 && !per_page {
+        return accept_pages(spec, index, pages, options).map(|ingested| vec![ingested]);
+    }
+    pages
+        .into_iter()
+        .map(|page| accept_page(spec, index, page, options))
+        .collect()
+}
+
+/// Fetch the messages a mailbox endpoint received since its cursor and accept each as its own
+/// envelope of raw MIME. The cursor only moves once every message was accepted, so a failed
+/// fetch is retried from the same message (and the messages already accepted dedupe).
+async fn accept_mailbox(
+    spec: &SourceSpecV1,
+    index: usize,
+    mailbox: &MailboxSpec,
+    rl: &RateLimiter,
+    options: &IngestOptions,
+) -> Result<Vec<GatewayIngest>> {
+    let source_id = spec.source_id.as_str();
+    let data_root = options.data_root.as_path();
+    let url = spec.endpoints[index].url.as_str();
+    let meta = IngestMeta::open_at_root(data_root).map_err(|e| ScraperError::Api {
+        message: format!("meta open failed: {}", e),
+    })?;
+    let cursor = meta.get_mailbox_cursor(source_id, url).map_err(|e| ScraperError::Api {
+        message: format!("meta read failed: {}", e),
+    })?;
+
+    let usage = UsageCounter::default();
+    let fetched = fetch_new(url, mailbox, cursor, spec.content.max_payload_size_bytes, rl, &usage).await;
+    if let Err(e) = record_usage(&meta, source_id, spec.quota.as_ref(), usage.usage(), chrono::Utc::now()) {
+        debug!("Failed to record usage for {}: {}", source_id, e);
+    }
+    let fetched = fetched.map_err(|e| ScraperError::Api {
+        message: format!("Mailbox fetch for {} failed: {}", source_id, e),
+    })?;
+    debug!("Fetched {} new messages for {}", fetched.messages.len(), source_id);
+
+    let mut ingested = Vec::with_capacity(fetched.messages.len());
+    for message in fetched.messages {
+        let page = FetchedPayload {
+            // The mailbox, not the message, so a newsletter delivered twice dedupes
+            url: url.to_string(),
+            status: 200,
+            content_type: MESSAGE_MIME.to_string(),
+            content_length: message.raw.len() as u64,
+            etag: None,
+            last_modified: None,
+            next_page: None,
+            payload: message.raw,
+        };
+        check_content(spec, &page)?;
+        ingested.push(accept_page(spec, index, page, options)?);
+    }
+    meta.put_mailbox_cursor(source_id, url, fetched.cursor).map_err(|e| ScraperError::Api {
+        message: format!("meta write failed: {}", e),
+    })?;
+    Ok(ingested)
+}
+
+/// Reject payloads over the registry's size cap or outside its MIME allow-list
+fn check_content(spec: &SourceSpecV1, page: &FetchedPayload) -> Result<()> {
+    if page.content_length > spec.content.max_payload_size_bytes {
+        crate::observability::metrics::gateway::payload_oversize();
         return Err(ScraperError::Api {
             message: format!(
                 "Payload too large: {} > {}",
-                content_length, spec.content.max_payload_size_bytes
+                page.content_length, spec.content.max_payload_size_bytes
             ),
         });
     }
-    let content_type_base = content_type
-        .split(';')
-        .next()
-        .unwrap_or("")
-        .trim()
-        .to_string();
+    let content_type_base = mime_base(&page.content_type);
     if !spec
         .content
         .allowed_mime_types
         .iter()
-        .any(|m| m == &content_type_base)
+        .any(|m| m == content_type_base)
     {
         return Err(ScraperError::Api {
             message: format!(
                 "MIME '{}' not in allow-list {:?}",
-                content_type, spec.content.allowed_mime_types
+                page.content_type, spec.content.allowed_mime_types
             ),
         });
     }
+    Ok(())
+}
 
-    // 5) Compute checksum and idempotency key
-    let sha_hex = {
-        use sha2::{Digest, Sha256};
-        let mut h = Sha256::new();
-        h.update(&payload);
-        hex::encode(h.finalize())
-    };
-    let idk = compute_idempotency_key(
-        &spec.source_id,
-        &ep.url,
-        etag.as_deref(),
-        last_modified.as_deref(),
-        &sha_hex,
-    );
+fn mime_base(content_type: &str) -> &str {
+    content_type.split(';').next().unwrap_or("").trim()
+}
 
-    // 6) Build envelope and accept via gateway (persist CAS + log)
-    let env = EnvelopeSubmissionV1 {
+fn sha256_hex(bytes: &[u8]) -> String {
+    use sha2::{Digest, Sha256};
+    hex::encode(Sha256::digest(bytes))
+}
+
+/// Envelope submission for a fetch of `ep`; `payload_meta` and the idempotency key describe the
+/// whole payload, `request` the page it came from
+fn submission(spec: &SourceSpecV1, index: usize, page: &FetchedPayload, payload_meta: PayloadMeta, idempotency_key: String) -> EnvelopeSubmissionV1 {
+    EnvelopeSubmissionV1 {
         envelope_version: "1.0.0".to_string(),
         source_id: spec.source_id.clone(),
-        idempotency_key: idk,
-        payload_meta: PayloadMeta {
-            mime_type: content_type,
-            size_bytes: content_length,
-            checksum: ChecksumMeta { sha256: sha_hex },
-        },
-        request: RequestMeta {
-            url: ep.url.clone(),
-            method: ep.method.clone(),
-            status: Some(status),
-            etag,
-            last_modified,
-        },
+        idempotency_key,
+        payload_meta,
+        request: request_meta(spec, index, page),
         timing: TimingMeta {
             fetched_at: chrono::Utc::now(),
             gateway_received_at: None,
@@ -195,39 +400,460 @@ pub async fn fetch_payload_and_log(source_id: &str) -> Result<Vec<u8>> {
         legal: LegalMeta {
             license_id: spec.policy.license_id.clone(),
         },
+    }
+}
+
+fn request_meta(spec: &SourceSpecV1, index: usize, page: &FetchedPayload) -> RequestMeta {
+    RequestMeta {
+        url: page.url.clone(),
+        method: spec.endpoints[index].method.clone(),
+        status: Some(page.status),
+        etag: page.etag.clone(),
+        last_modified: page.last_modified.clone(),
+        endpoint_id: spec.endpoint_id(index),
+    }
+}
+
+/// Accept one fetched payload as a V1 envelope
+fn accept_page(spec: &SourceSpecV1, index: usize, page: FetchedPayload, options: &IngestOptions) -> Result<GatewayIngest> {
+    let data_root = options.data_root.as_path();
+    // 5) Compute checksum and idempotency key
+    let sha_hex = sha256_hex(&page.payload);
+    let idk = compute_idempotency_key(
+        &spec.source_id,
+        &page.url,
+        page.etag.as_deref(),
+        page.last_modified.as_deref(),
+        &sha_hex,
+    );
+
+    // 6) Build envelope and accept via gateway (persist CAS + log)
+    let payload_meta = PayloadMeta {
+        mime_type: page.content_type.clone(),
+        size_bytes: page.content_length,
+        checksum: ChecksumMeta { sha256: sha_hex },
     };
+    let env = submission(spec, index, &page, payload_meta, idk);
 
-    let gw = Gateway::new(data_root.clone());
+    let gw = Gateway::new(options.data_root.clone()).deduplicating(!options.bypass_cadence);
     let accept_start = Instant::now();
-    let stamped = gw.accept(env, &payload).map_err(|e| {
+    let stamped = gw.accept(env, &page.payload).map_err(|e| {
         crate::observability::metrics::gateway::cas_write_error();
         ScraperError::Api {
             message: format!("Gateway accept failed: {}", e),
         }
     })?;
+    record_accepted(&stamped.envelope_id, accept_start, page.payload.len());
 
-    let accept_duration = accept_start.elapsed().as_secs_f64();
+    debug!(
+        "Accepted envelope {} with payload {}",
+        stamped.envelope_id, stamped.payload_ref
+    );
+
+    // 6b) Optional text rendition for HTML payloads; a failure here never fails the fetch
+    if let Some(text_spec) = &spec.content.text_extract {
+        if mime_base(&page.content_type).contains("html") && !stamped.payload_ref.is_empty() {
+            match crate::pipeline::ingestion::text_extract::store(data_root, &stamped.payload_ref, &page.payload, text_spec) {
+                Ok(extracted) => debug!(
+                    "Stored text extract for {}: {} chars from {} bytes (truncated: {})",
+                    stamped.payload_ref, extracted.chars, extracted.source_bytes, extracted.truncated
+                ),
+                Err(e) => tracing::warn!("Text extract failed for {}: {}", stamped.payload_ref, e),
+            }
+        }
+    }
+
+    Ok(GatewayIngest {
+        endpoint_id: spec.endpoint_id(index),
+        envelope_id: stamped.envelope_id,
+        dedupe_of: stamped.dedupe_of,
+        parts: vec![IngestedPart { payload_ref: stamped.payload_ref, payload: page.payload }],
+    })
+}
+
+/// Accept the pages of a paginated fetch as one multi-part V2 envelope. The idempotency key
+/// covers every page's checksum, so the envelope dedupes only when no page changed.
+fn accept_pages(spec: &SourceSpecV1, index: usize, pages: Vec<FetchedPayload>, options: &IngestOptions) -> Result<GatewayIngest> {
+    let checksums: Vec<String> = pages.iter().map(|page| sha256_hex(&page.payload)).collect();
+    let combined = sha256_hex(checksums.join("\n").as_bytes());
+    let first = &pages[0];
+    let idk = compute_idempotency_key(&spec.source_id, &first.url, None, None, &combined);
+    let payload_meta = PayloadMeta {
+        mime_type: first.content_type.clone(),
+        size_bytes: pages.iter().map(|page| page.content_length).sum(),
+        checksum: ChecksumMeta { sha256: combined },
+    };
+    let env = submission(spec, index, first, payload_meta, idk);
+    let parts: Vec<(RequestMeta, Vec<u8>)> = pages
+        .iter()
+        .map(|page| (request_meta(spec, index, page), page.payload.clone()))
+        .collect();
+
+    let gw = Gateway::new(options.data_root.clone()).deduplicating(!options.bypass_cadence);
+    let accept_start = Instant::now();
+    let stamped = gw.accept_parts(env, &parts).map_err(|e| {
+        crate::observability::metrics::gateway::cas_write_error();
+        ScraperError::Api {
+            message: format!("Gateway accept failed: {}", e),
+        }
+    })?;
+    let total_bytes = parts.iter().map(|(_, bytes)| bytes.len()).sum();
+    record_accepted(&stamped.envelope_id, accept_start, total_bytes);
+    debug!("Accepted envelope {} with {} pages", stamped.envelope_id, stamped.payload_parts.len());
+
+    // A deduplicated envelope stores no parts of its own
+    let parts = parts
+        .into_iter()
+        .enumerate()
+        .map(|(i, (_, payload))| IngestedPart {
+            payload_ref: stamped.payload_parts.get(i).map(|part| part.payload_ref.clone()).unwrap_or_default(),
+            payload,
+        })
+        .collect();
+    Ok(GatewayIngest {
+        endpoint_id: spec.endpoint_id(index),
+        envelope_id: stamped.envelope_id,
+        dedupe_of: stamped.dedupe_of,
+        parts,
+    })
+}
 
-    // Record successful gateway and ingest log metrics
+/// Record successful gateway and ingest log metrics
+fn record_accepted(envelope_id: &str, accept_start: Instant, bytes: usize) {
+    let accept_duration = accept_start.elapsed().as_secs_f64();
+    tracing::Span::current().record("envelope_id", envelope_id);
     crate::observability::metrics::gateway::envelope_accepted();
     crate::observability::metrics::gateway::processing_duration(accept_duration);
     crate::observability::metrics::gateway::cas_write_success();
     crate::observability::metrics::ingest_log::write_success();
-    crate::observability::metrics::ingest_log::write_bytes(payload.len());
+    crate::observability::metrics::ingest_log::write_bytes(bytes);
+}
 
-    debug!(
-        "Accepted envelope {} with payload {}",
-        stamped.envelope_id, stamped.payload_ref
-    );
+/// Response bytes plus the headers the envelope records
+struct FetchedPayload {
+    /// The URL this payload was fetched from (for later pages, the followed next link)
+    url: String,
+    status: u16,
+    content_type: String,
+    content_length: u64,
+    etag: Option<String>,
+    last_modified: Option<String>,
+    /// Target of the response's `Link: rel="next"` header
+    next_page: Option<String>,
+    payload: Vec<u8>,
+}
 
-    // 7) Update cadence marker
-    {
-        let meta = IngestMeta::open_at_root(&data_root).map_err(|e| ScraperError::Api {
-            message: format!("meta open failed: {}", e),
+/// Bootstrap pages, then the endpoint itself: one request, one per window, or one per page
+async fn fetch_source(
+    client: &reqwest::Client,
+    rl: &RateLimiter,
+    spec: &SourceSpecV1,
+    ep: &EndpointSpec,
+    usage: &UsageCounter,
+) -> Result<Vec<FetchedPayload>> {
+    // Some sites refuse the calendar until a session cookie has been set by an earlier page
+    if let Some(bootstrap) = &spec.bootstrap {
+        for bootstrap_url in &bootstrap.urls {
+            rl.acquire(0).await;
+            usage.add(1, 0);
+            let received = bootstrap_request(client, bootstrap_url)
+                .await
+                .map_err(|e| ScraperError::Api { message: e })?;
+            usage.add(0, received);
+        }
+    }
+    let max_bytes = spec.content.max_payload_size_bytes;
+    match (&spec.windowing, &ep.pagination) {
+        (Some(windowing), _) => Ok(vec![fetch_windowed(client, rl, &ep.url, windowing, max_bytes, usage).await?]),
+        (None, Some(pagination)) => fetch_paginated(client, rl, &ep.url, pagination, max_bytes, usage).await,
+        (None, None) => Ok(vec![fetch_url(client, rl, &ep.url, max_bytes, usage).await?]),
+    }
+}
+
+/// Fetch the first page and follow next links until there are none or `max_pages` is reached.
+/// A page that fails or returns an error status fails the whole fetch.
+async fn fetch_paginated(
+    client: &reqwest::Client,
+    rl: &RateLimiter,
+    url: &str,
+    pagination: &PaginationSpec,
+    max_bytes: u64,
+    usage: &UsageCounter,
+) -> Result<Vec<FetchedPayload>> {
+    let max_pages = pagination.max_pages.max(1) as usize;
+    let mut pages: Vec<FetchedPayload> = Vec::new();
+    let mut next = Some(url.to_string());
+    while let Some(page_url) = next.take() {
+        if pages.iter().any(|page| page.url == page_url) {
+            tracing::warn!("Next link {} points back at a page already fetched; stopping", page_url);
+            break;
+        }
+        let page = fetch_url(client, rl, &page_url, max_bytes, usage).await?;
+        if !(200..=299).contains(&page.status) {
+            return Err(ScraperError::Api {
+                message: format!("Page {} ({}) returned HTTP {}", pages.len() + 1, page_url, page.status),
+            });
+        }
+        debug!("Fetched page {} ({} bytes)", pages.len() + 1, page.payload.len());
+        next = page.next_page.clone();
+        pages.push(page);
+        if pages.len() >= max_pages {
+            if next.is_some() {
+                tracing::warn!("Stopped following next links for {} at the {}-page cap", url, max_pages);
+            }
+            break;
+        }
+    }
+    Ok(pages)
+}
+
+#[instrument(name = "http_fetch", skip(client, rl, max_bytes, usage), fields(url = %url, status = tracing::field::Empty))]
+async fn fetch_url(
+    client: &reqwest::Client,
+    rl: &RateLimiter,
+    url: &str,
+    max_bytes: u64,
+    usage: &UsageCounter,
+) -> Result<FetchedPayload> {
+    rl.acquire(0).await; // acquire for RPM/concurrency before send
+    usage.add(1, 0);
+    let fetch_t0 = Instant::now();
+    #[cfg(feature = "chaos")]
+    if let Err(fault) = crate::infra::fault_injection::injector().check(crate::infra::fault_injection::Fault::HttpTimeout, url) {
+        crate::observability::metrics::sources::request_error();
+        return Err(ScraperError::Api { message: format!("Fetch of {} failed: {}", url, fault) });
+    }
+    
+    // The user agent and any endpoint headers are client defaults, see accept_endpoint
+    let resp = client
+        .get(url)
+        .header(ACCEPT_ENCODING, "gzip, deflate, br")
+        .send()
+        .await?;
+    let status = resp.status().as_u16();
+    tracing::Span::current().record("status", status);
+    let headers = resp.headers().clone();
+    let body = match read_body_limited(resp, max_bytes).await {
+        Ok(body) => body,
+        Err(e) => {
+            if matches!(e, BodyError::TooLarge { .. }) {
+                crate::observability::metrics::gateway::payload_oversize();
+            }
+            crate::observability::metrics::sources::request_error();
+            return Err(ScraperError::Api { message: format!("Fetch of {} failed: {}", url, e) });
+        }
+    };
+    let payload = body.bytes;
+    usage.add(0, body.compressed_len as u64);
+    rl.acquire(body.compressed_len as u64).await; // account for bytes after size known
+    crate::observability::metrics::gateway::payload_sizes(body.compressed_len, payload.len());
+
+    // Record metrics
+    let dur = fetch_t0.elapsed().as_secs_f64();
+    if (200..=299).contains(&status) {
+        crate::observability::metrics::sources::request_success();
+        crate::observability::metrics::sources::request_duration(dur);
+        crate::observability::metrics::sources::payload_bytes(payload.len());
+    } else {
+        crate::observability::metrics::sources::request_error();
+    }
+
+    let content_type = headers
+        .get(CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or("application/octet-stream")
+        .to_string();
+    // Content-Length describes the encoded body; the envelope records what is stored
+    let content_length = payload.len() as u64;
+    let etag = headers
+        .get(ETAG)
+        .and_then(|v| v.to_str().ok())
+        .map(|s| s.to_string());
+    let last_modified = headers
+        .get(LAST_MODIFIED)
+        .and_then(|v| v.to_str().ok())
+        .map(|s| s.to_string());
+
+    let next_page = next_link(&headers, url);
+
+    Ok(FetchedPayload {
+        url: url.to_string(),
+        status,
+        content_type,
+        content_length,
+        etag,
+        last_modified,
+        next_page,
+        payload,
+    })
+}
+
+/// Fetch one request per month window and merge the JSON bodies into a single payload,
+/// so events beyond the endpoint's default window are not silently missed.
+async fn fetch_windowed(
+    client: &reqwest::Client,
+    rl: &RateLimiter,
+    base_url: &str,
+    windowing: &WindowingSpec,
+    max_bytes: u64,
+    usage: &UsageCounter,
+) -> Result<FetchedPayload> {
+    let windows = month_windows(chrono::Utc::now().date_naive(), windowing.lookahead_months);
+    let mut bodies = Vec::with_capacity(windows.len());
+    let mut content_type = None;
+    for (from, to) in windows {
+        let url = window_url(base_url, windowing, from, to).map_err(|e| ScraperError::Api {
+            message: format!("Invalid window url for {}: {}", base_url, e),
         })?;
-        let now = chrono::Utc::now().timestamp();
-        let _ = meta.set_last_fetched_at(&stamped.envelope.source_id, now);
+        let fetched = fetch_url(client, rl, &url, max_bytes, usage).await?;
+        if !(200..=299).contains(&fetched.status) {
+            return Err(ScraperError::Api {
+                message: format!("Window {}..{} returned HTTP {}", from, to, fetched.status),
+            });
+        }
+        debug!("Fetched window {}..{} ({} bytes)", from, to, fetched.payload.len());
+        content_type.get_or_insert(fetched.content_type);
+        bodies.push(serde_json::from_slice::<serde_json::Value>(&fetched.payload)?);
+    }
+
+    let payload = serde_json::to_vec(&merge_wix_payloads(&bodies))?;
+    Ok(FetchedPayload {
+        url: base_url.to_string(),
+        status: 200,
+        content_type: content_type.unwrap_or_else(|| "application/json".to_string()),
+        content_length: payload.len() as u64,
+        // Validators from individual windows don't describe the merged document
+        etag: None,
+        last_modified: None,
+        next_page: None,
+        payload,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    /// Serves `/events?page=N` for N in 1..=3, each linking to the next page
+    async fn serve_pages(listener: tokio::net::TcpListener) {
+        loop {
+            let Ok((mut socket, _)) = listener.accept().await else { return };
+            let mut buf = vec![0u8; 4096];
+            let n = socket.read(&mut buf).await.unwrap_or(0);
+            let request = String::from_utf8_lossy(&buf[..n]).to_string();
+            let page: u32 = request
+                .split_whitespace()
+                .nth(1)
+                .and_then(|path| path.rsplit("page=").next())
+                .and_then(|p| p.parse().ok())
+                .unwrap_or(0);
+            let link = if page < 3 { format!("Link: </events?page={}>; rel=\"next\"\r\n", page + 1) } else { String::new() };
+            let body = format!("{{\"page\":{}}}", page);
+            let response = format!(
+                "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\n{}Content-Length: {}\r\nConnection: close\r\n\r\n{}",
+                link,
+                body.len(),
+                body
+            );
+            let _ = socket.write_all(response.as_bytes()).await;
+        }
     }
 
-    Ok(payload)
+    #[tokio::test]
+    async fn paginated_fetch_follows_next_links_up_to_the_cap() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/events?page=1", listener.local_addr().unwrap());
+        tokio::spawn(serve_pages(listener));
+        let client = reqwest::Client::new();
+        let rl = RateLimiter::new(Limits::default());
+        let usage = UsageCounter::default();
+
+        let all = PaginationSpec { max_pages: 10, ..Default::default() };
+        let pages = fetch_paginated(&client, &rl, &url, &all, 1024, &usage).await.unwrap();
+        let bodies: Vec<_> = pages.iter().map(|p| String::from_utf8_lossy(&p.payload).to_string()).collect();
+        assert_eq!(bodies, [r#"{"page":1}"#, r#"{"page":2}"#, r#"{"page":3}"#]);
+        assert!(pages[2].url.ends_with("/events?page=3"));
+        assert_eq!(usage.usage().requests, 3);
+
+        let capped = PaginationSpec { max_pages: 2, ..Default::default() };
+        assert_eq!(fetch_paginated(&client, &rl, &url, &capped, 1024, &usage).await.unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn bypassing_cadence_refetches_and_stores_again() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/events?page=3", listener.local_addr().unwrap());
+        tokio::spawn(serve_pages(listener));
+        let spec: SourceSpecV1 = serde_json::from_value(serde_json::json!({
+            "source_id": "neumos",
+            "enabled": true,
+            "endpoints": [{"url": url, "method": "GET"}],
+            "content": {"allowed_mime_types": ["application/json"], "max_payload_size_bytes": 1024},
+            "policy": {"license_id": "test"}
+        }))
+        .unwrap();
+        let tmp = tempfile::tempdir().unwrap();
+        let options = IngestOptions { data_root: tmp.path().to_path_buf(), ..IngestOptions::default() };
+
+        let first = ingest_spec(&spec, &options).await.unwrap();
+        assert!(first[0].parts[0].payload_ref.starts_with("cas:sha256:"));
+        assert!(is_cadence_skip(&ingest_spec(&spec, &options).await.unwrap_err()));
+
+        let bypass = IngestOptions { bypass_cadence: true, ..options };
+        let again = ingest_spec(&spec, &bypass).await.unwrap();
+        assert_eq!(again[0].dedupe_of, None);
+        assert_ne!(again[0].envelope_id, first[0].envelope_id);
+    }
+
+    fn paginated_spec(url: &str, mode: &str) -> SourceSpecV1 {
+        serde_json::from_value(serde_json::json!({
+            "source_id": "neumos",
+            "enabled": true,
+            "endpoints": [{"url": url, "method": "GET", "pagination": {"max_pages": 3, "mode": mode}}],
+            "content": {"allowed_mime_types": ["application/json"], "max_payload_size_bytes": 1024},
+            "policy": {"license_id": "test"}
+        }))
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn every_page_of_a_paginated_fetch_is_handed_on() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/events?page=1", listener.local_addr().unwrap());
+        tokio::spawn(serve_pages(listener));
+        let bodies = |ingested: &[GatewayIngest]| -> Vec<String> {
+            ingested
+                .iter()
+                .flat_map(|i| &i.parts)
+                .map(|part| String::from_utf8_lossy(&part.payload).to_string())
+                .collect()
+        };
+
+        let tmp = tempfile::tempdir().unwrap();
+        let options = IngestOptions { data_root: tmp.path().to_path_buf(), ..IngestOptions::default() };
+        let multi_part = ingest_spec(&paginated_spec(&url, "multi_part"), &options).await.unwrap();
+        assert_eq!(multi_part.len(), 1);
+        assert_eq!(bodies(&multi_part), [r#"{"page":1}"#, r#"{"page":2}"#, r#"{"page":3}"#]);
+        assert!(multi_part[0].parts.iter().all(|part| part.payload_ref.starts_with("cas:sha256:")));
+
+        let tmp = tempfile::tempdir().unwrap();
+        let options = IngestOptions { data_root: tmp.path().to_path_buf(), ..IngestOptions::default() };
+        let per_page = ingest_spec(&paginated_spec(&url, "envelope_per_page"), &options).await.unwrap();
+        assert_eq!(per_page.len(), 3);
+        assert_eq!(bodies(&per_page), bodies(&multi_part));
+    }
+
+    #[tokio::test]
+    async fn host_limits_queue_requests_to_the_same_host_only() {
+        let limits = HostLimits::new(1);
+        let held = limits.acquire("https://venue.example/events").await;
+        let wait = std::time::Duration::from_millis(50);
+
+        // Another endpoint of the same host waits; a different host goes ahead
+        assert!(tokio::time::timeout(wait, limits.acquire("https://venue.example/calendar")).await.is_err());
+        assert!(tokio::time::timeout(wait, limits.acquire("https://tickets.example/venue")).await.is_ok());
+        drop(held);
+        assert!(tokio::time::timeout(wait, limits.acquire("https://venue.example/calendar")).await.is_ok());
+    }
 }
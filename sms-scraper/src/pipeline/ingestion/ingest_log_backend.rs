@@ -0,0 +1,592 @@
+use crate::pipeline::ingestion::gateway::ingest_log;
+use crate::pipeline::ingestion::ingest_meta::IngestMeta;
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use std::io::{self, BufRead, BufReader, Cursor, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+/// Selects the ingest log backend: `local` (default) or `supabase`
+pub const INGEST_LOG_BACKEND_ENV: &str = "SMS_INGEST_LOG_BACKEND";
+
+/// Where the gateway appends stamped envelopes and where consumers read them back and keep
+/// their offsets. Offsets are byte positions in the log as one continuous stream.
+pub trait IngestLogBackend: Send + Sync {
+    /// Append one serialized record (without its trailing newline)
+    fn append(&self, record: &str) -> io::Result<()>;
+    /// Byte length of the log as consumers see it
+    fn end_offset(&self) -> io::Result<u64>;
+    /// The log from `offset` onwards
+    fn reader_from(&self, offset: u64) -> io::Result<Box<dyn BufRead + Send>>;
+    /// A consumer's committed (byte_offset, last envelope_id); (0, None) if it has none
+    fn load_offset(&self, consumer: &str) -> io::Result<(u64, Option<String>)>;
+    fn save_offset(&self, consumer: &str, byte_offset: u64, envelope_id: Option<&str>) -> io::Result<()>;
+    /// Whether the log lives in the dated files under `data/ingest_log`
+    fn is_local(&self) -> bool;
+    fn describe(&self) -> String;
+}
+
+/// The backend named by `SMS_INGEST_LOG_BACKEND`. Falls back to the local log, with a
+/// warning, when an object store is asked for but not configured.
+pub fn from_env(data_root: &Path) -> Arc<dyn IngestLogBackend> {
+    match std::env::var(INGEST_LOG_BACKEND_ENV).unwrap_or_default().to_ascii_lowercase().as_str() {
+        "supabase" | "s3" | "object" => match SupabaseObjectStore::from_env() {
+            Some(store) => Arc::new(ObjectIngestLog::new(store)),
+            None => {
+                tracing::warn!(
+                    "{} asks for object storage but SUPABASE_URL/SUPABASE_SERVICE_ROLE_KEY/SUPABASE_BUCKET are not set; using the local ingest log",
+                    INGEST_LOG_BACKEND_ENV
+                );
+                Arc::new(LocalIngestLog::new(data_root))
+            }
+        },
+        _ => Arc::new(LocalIngestLog::new(data_root)),
+    }
+}
+
+/// Daily-rotated NDJSON files under `<data_root>/ingest_log`, read through the
+/// `ingest.ndjson` symlink, with offsets in the SQLite meta store
+pub struct LocalIngestLog {
+    root: PathBuf,
+}
+
+impl LocalIngestLog {
+    pub fn new(data_root: impl Into<PathBuf>) -> Self {
+        Self { root: data_root.into() }
+    }
+
+    fn current_path(&self) -> PathBuf {
+        self.root.join("ingest_log").join("ingest.ndjson")
+    }
+}
+
+impl IngestLogBackend for LocalIngestLog {
+    fn append(&self, record: &str) -> io::Result<()> {
+        ingest_log::append_line_rotating(&self.root.join("ingest_log"), record)
+    }
+
+    fn end_offset(&self) -> io::Result<u64> {
+        Ok(std::fs::metadata(self.current_path()).map(|m| m.len()).unwrap_or(0))
+    }
+
+    fn reader_from(&self, offset: u64) -> io::Result<Box<dyn BufRead + Send>> {
+        let mut file = match std::fs::File::open(self.current_path()) {
+            Ok(file) => file,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Box::new(Cursor::new(Vec::new()))),
+            Err(e) => return Err(e),
+        };
+        file.seek(SeekFrom::Start(offset))?;
+        Ok(Box::new(BufReader::new(file)))
+    }
+
+    fn load_offset(&self, consumer: &str) -> io::Result<(u64, Option<String>)> {
+        let meta = IngestMeta::open_at_root(&self.root).map_err(io::Error::other)?;
+        meta.get_offset(consumer).map_err(io::Error::other)
+    }
+
+    fn save_offset(&self, consumer: &str, byte_offset: u64, envelope_id: Option<&str>) -> io::Result<()> {
+        let meta = IngestMeta::open_at_root(&self.root).map_err(io::Error::other)?;
+        meta.set_offset(consumer, byte_offset, envelope_id).map_err(io::Error::other)
+    }
+
+    fn is_local(&self) -> bool {
+        true
+    }
+
+    fn describe(&self) -> String {
+        format!("local {}", self.root.join("ingest_log").display())
+    }
+}
+
+/// Minimal blob store the object-backed log is written to
+pub trait ObjectStore: Send + Sync {
+    /// `None` when there is no object at `key`
+    fn get(&self, key: &str) -> io::Result<Option<Vec<u8>>>;
+    fn put(&self, key: &str, bytes: &[u8], content_type: &str) -> io::Result<()>;
+    fn describe(&self) -> String;
+}
+
+impl<T: ObjectStore + ?Sized> ObjectStore for Arc<T> {
+    fn get(&self, key: &str) -> io::Result<Option<Vec<u8>>> {
+        (**self).get(key)
+    }
+
+    fn put(&self, key: &str, bytes: &[u8], content_type: &str) -> io::Result<()> {
+        (**self).put(key, bytes, content_type)
+    }
+
+    fn describe(&self) -> String {
+        (**self).describe()
+    }
+}
+
+const MANIFEST_HEAD_KEY: &str = "ingest_log/manifest/head.json";
+
+/// Parts listed per manifest segment; an append rewrites only the head and the open segment
+const SEGMENT_PARTS: usize = 256;
+
+fn segment_key(segment: u64) -> String {
+    format!("ingest_log/manifest/{:010}.json", segment)
+}
+
+/// Which manifest segment is open and how many bytes the sealed ones before it cover
+#[derive(Debug, Default, Clone, Copy, Serialize, Deserialize)]
+struct ManifestHead {
+    open_segment: u64,
+    sealed_bytes: u64,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct ManifestSegment {
+    /// Log offset of the segment's first part
+    start: u64,
+    parts: Vec<ManifestPart>,
+}
+
+impl ManifestSegment {
+    fn bytes(&self) -> u64 {
+        self.parts.iter().map(|p| p.bytes).sum()
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct ManifestPart {
+    key: String,
+    bytes: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct StoredOffset {
+    byte_offset: u64,
+    envelope_id: Option<String>,
+}
+
+fn get_json<T: serde::de::DeserializeOwned + Default>(store: &dyn ObjectStore, key: &str) -> io::Result<T> {
+    match store.get(key)? {
+        Some(bytes) => serde_json::from_slice(&bytes).map_err(io::Error::other),
+        None => Ok(T::default()),
+    }
+}
+
+fn put_json<T: Serialize>(store: &dyn ObjectStore, key: &str, value: &T) -> io::Result<()> {
+    let body = serde_json::to_vec(value).map_err(io::Error::other)?;
+    store.put(key, &body, "application/json")
+}
+
+/// The ingest log as immutable part objects, one per append, listed in order by a manifest
+/// split into fixed-size segments, so the gateway and the parse stage can run in separate
+/// containers without a shared disk. Assumes a single writer: two gateways appending at once
+/// can each rewrite the open segment without the other's part.
+pub struct ObjectIngestLog<S: ObjectStore> {
+    store: Arc<S>,
+}
+
+impl<S: ObjectStore> ObjectIngestLog<S> {
+    pub fn new(store: S) -> Self {
+        Self { store: Arc::new(store) }
+    }
+
+    fn head(&self) -> io::Result<ManifestHead> {
+        get_json(&*self.store, MANIFEST_HEAD_KEY)
+    }
+}
+
+impl<S: ObjectStore + 'static> IngestLogBackend for ObjectIngestLog<S> {
+    fn append(&self, record: &str) -> io::Result<()> {
+        let now = Utc::now();
+        let key = format!(
+            "ingest_log/parts/{}/{}-{}.ndjson",
+            now.format("%Y-%m-%d"),
+            now.format("%H%M%S%3f"),
+            uuid::Uuid::new_v4()
+        );
+        let line = format!("{}\n", record);
+        let written = self.store.put(&key, line.as_bytes(), "application/x-ndjson").and_then(|_| {
+            let mut head = self.head()?;
+            let mut segment: ManifestSegment = get_json(&*self.store, &segment_key(head.open_segment))?;
+            if segment.parts.len() >= SEGMENT_PARTS {
+                // Seal the full segment and open the next one, starting where it ends
+                head = ManifestHead { open_segment: head.open_segment + 1, sealed_bytes: segment.start + segment.bytes() };
+                segment = ManifestSegment { start: head.sealed_bytes, parts: Vec::new() };
+            }
+            segment.start = head.sealed_bytes;
+            segment.parts.push(ManifestPart { key: key.clone(), bytes: line.len() as u64 });
+            put_json(&*self.store, &segment_key(head.open_segment), &segment)?;
+            // The head only changes when a segment is sealed, or on the log's first append
+            if segment.parts.len() == 1 {
+                put_json(&*self.store, MANIFEST_HEAD_KEY, &head)?;
+            }
+            Ok(())
+        });
+        match written {
+            Ok(()) => {
+                crate::observability::metrics::ingest_log::write_success();
+                crate::observability::metrics::ingest_log::write_bytes(line.len());
+                Ok(())
+            }
+            Err(e) => {
+                crate::observability::metrics::ingest_log::write_error();
+                Err(e)
+            }
+        }
+    }
+
+    fn end_offset(&self) -> io::Result<u64> {
+        let head = self.head()?;
+        let segment: ManifestSegment = get_json(&*self.store, &segment_key(head.open_segment))?;
+        Ok(head.sealed_bytes + segment.bytes())
+    }
+
+    fn reader_from(&self, offset: u64) -> io::Result<Box<dyn BufRead + Send>> {
+        let head = self.head()?;
+        // Segment starts only grow, so find the one holding `offset` by bisecting
+        let (mut lo, mut hi) = (0, head.open_segment);
+        while lo < hi {
+            let mid = lo + (hi - lo).div_ceil(2);
+            let segment: ManifestSegment = get_json(&*self.store, &segment_key(mid))?;
+            if segment.start <= offset {
+                lo = mid;
+            } else {
+                hi = mid - 1;
+            }
+        }
+        Ok(Box::new(PartReader {
+            store: self.store.clone(),
+            offset,
+            next_segment: lo,
+            last_segment: head.open_segment,
+            parts: Default::default(),
+            current: Cursor::new(Vec::new()),
+        }))
+    }
+
+    fn load_offset(&self, consumer: &str) -> io::Result<(u64, Option<String>)> {
+        match self.store.get(&offset_key(consumer))? {
+            Some(bytes) => {
+                let stored: StoredOffset = serde_json::from_slice(&bytes).map_err(io::Error::other)?;
+                Ok((stored.byte_offset, stored.envelope_id))
+            }
+            None => Ok((0, None)),
+        }
+    }
+
+    fn save_offset(&self, consumer: &str, byte_offset: u64, envelope_id: Option<&str>) -> io::Result<()> {
+        let stored = StoredOffset { byte_offset, envelope_id: envelope_id.map(str::to_string) };
+        put_json(&*self.store, &offset_key(consumer), &stored)
+    }
+
+    fn is_local(&self) -> bool {
+        false
+    }
+
+    fn describe(&self) -> String {
+        format!("object {}", self.store.describe())
+    }
+}
+
+/// Reads the log from an offset one part object at a time, fetching each manifest segment
+/// and part only once the reader gets to it
+struct PartReader<S: ObjectStore> {
+    store: Arc<S>,
+    /// Log offset of the next byte to hand out
+    offset: u64,
+    next_segment: u64,
+    last_segment: u64,
+    /// Parts of the current segment not read yet, with their log offsets
+    parts: std::collections::VecDeque<(u64, ManifestPart)>,
+    current: Cursor<Vec<u8>>,
+}
+
+impl<S: ObjectStore> PartReader<S> {
+    /// Load the next part holding bytes at or after `offset`; false at the end of the log
+    fn next_part(&mut self) -> io::Result<bool> {
+        loop {
+            while let Some((start, part)) = self.parts.pop_front() {
+                if start + part.bytes <= self.offset {
+                    continue;
+                }
+                let bytes = self
+                    .store
+                    .get(&part.key)?
+                    .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, format!("ingest log part {} is missing", part.key)))?;
+                let mut current = Cursor::new(bytes);
+                current.set_position(self.offset.saturating_sub(start));
+                self.current = current;
+                return Ok(true);
+            }
+            if self.next_segment > self.last_segment {
+                return Ok(false);
+            }
+            let segment: ManifestSegment = get_json(&*self.store, &segment_key(self.next_segment))?;
+            self.next_segment += 1;
+            let mut start = segment.start;
+            for part in segment.parts {
+                let bytes = part.bytes;
+                self.parts.push_back((start, part));
+                start += bytes;
+            }
+        }
+    }
+}
+
+impl<S: ObjectStore> io::Read for PartReader<S> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let n = io::Read::read(&mut self.fill_buf()?, buf)?;
+        self.consume(n);
+        Ok(n)
+    }
+}
+
+impl<S: ObjectStore> BufRead for PartReader<S> {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        while self.current.position() as usize >= self.current.get_ref().len() {
+            if !self.next_part()? {
+                break;
+            }
+        }
+        self.current.fill_buf()
+    }
+
+    fn consume(&mut self, amt: usize) {
+        self.current.consume(amt);
+        self.offset += amt as u64;
+    }
+}
+
+fn offset_key(consumer: &str) -> String {
+    let safe: String = consumer
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() || c == '-' || c == '_' { c } else { '_' })
+        .collect();
+    format!("ingest_log/offsets/{}.json", safe)
+}
+
+/// Supabase Storage bucket, configured by the same variables as the Supabase CAS
+/// (`SUPABASE_URL` or `SUPABASE_PROJECT_REF`, `SUPABASE_SERVICE_ROLE_KEY`, `SUPABASE_BUCKET`,
+/// optional `SUPABASE_PREFIX`)
+pub struct SupabaseObjectStore {
+    base: String,
+    key: String,
+    bucket: String,
+    prefix: String,
+    client: reqwest::Client,
+}
+
+impl SupabaseObjectStore {
+    pub fn from_env() -> Option<Self> {
+        let base = std::env::var("SUPABASE_URL")
+            .ok()
+            .or_else(|| std::env::var("SUPABASE_PROJECT_REF").ok().map(|r| format!("https://{}.supabase.co", r)))?;
+        Some(Self {
+            base: base.trim_end_matches('/').to_string(),
+            key: std::env::var("SUPABASE_SERVICE_ROLE_KEY").ok()?,
+            bucket: std::env::var("SUPABASE_BUCKET").ok()?,
+            prefix: std::env::var("SUPABASE_PREFIX").unwrap_or_default().trim_end_matches('/').to_string(),
+            client: reqwest::Client::new(),
+        })
+    }
+
+    fn url(&self, key: &str) -> String {
+        if self.prefix.is_empty() {
+            format!("{}/storage/v1/object/{}/{}", self.base, self.bucket, key)
+        } else {
+            format!("{}/storage/v1/object/{}/{}/{}", self.base, self.bucket, self.prefix, key)
+        }
+    }
+
+}
+
+/// Run a request from synchronous code on a runtime of its own, so it works from any thread:
+/// a current-thread runtime, a multi-thread worker or no runtime at all
+fn block_on_io<T: Send + 'static>(fut: impl std::future::Future<Output = io::Result<T>> + Send + 'static) -> io::Result<T> {
+    static IO_RUNTIME: std::sync::OnceLock<io::Result<tokio::runtime::Runtime>> = std::sync::OnceLock::new();
+    let runtime = IO_RUNTIME.get_or_init(|| {
+        tokio::runtime::Builder::new_multi_thread()
+            .worker_threads(1)
+            .thread_name("ingest-log-io")
+            .enable_all()
+            .build()
+    });
+    let runtime = runtime.as_ref().map_err(|e| io::Error::other(format!("ingest log runtime: {}", e)))?;
+    let (tx, rx) = std::sync::mpsc::channel();
+    runtime.spawn(async move {
+        let _ = tx.send(fut.await);
+    });
+    rx.recv().map_err(|_| io::Error::other("ingest log request was dropped"))?
+}
+
+impl ObjectStore for SupabaseObjectStore {
+    fn get(&self, key: &str) -> io::Result<Option<Vec<u8>>> {
+        let (url, key, token, client) = (self.url(key), key.to_string(), self.key.clone(), self.client.clone());
+        block_on_io(async move {
+            let resp = client
+                .get(&url)
+                .header("Authorization", format!("Bearer {}", token))
+                .header("apikey", token.clone())
+                .send()
+                .await
+                .map_err(io::Error::other)?;
+            let status = resp.status();
+            let body = resp.bytes().await.map_err(io::Error::other)?;
+            // Storage answers a missing object with 404, or 400 and a not_found body
+            let not_found = status == reqwest::StatusCode::NOT_FOUND
+                || (status == reqwest::StatusCode::BAD_REQUEST
+                    && String::from_utf8_lossy(&body).to_ascii_lowercase().contains("not found"));
+            if not_found {
+                return Ok(None);
+            }
+            if !status.is_success() {
+                return Err(io::Error::other(format!("Supabase get {} failed: {}", key, status)));
+            }
+            Ok(Some(body.to_vec()))
+        })
+    }
+
+    fn put(&self, key: &str, bytes: &[u8], content_type: &str) -> io::Result<()> {
+        let (url, key, token, client) = (self.url(key), key.to_string(), self.key.clone(), self.client.clone());
+        let (bytes, content_type) = (bytes.to_vec(), content_type.to_string());
+        block_on_io(async move {
+            let resp = client
+                .put(&url)
+                .header("Authorization", format!("Bearer {}", token))
+                .header("apikey", token.clone())
+                .header(reqwest::header::CONTENT_TYPE, content_type)
+                .query(&[("upsert", "true")])
+                .body(bytes)
+                .send()
+                .await
+                .map_err(io::Error::other)?;
+            if !resp.status().is_success() {
+                let status = resp.status();
+                let body = resp.text().await.unwrap_or_default();
+                return Err(io::Error::other(format!("Supabase put {} failed: {} - {}", key, status, body)));
+            }
+            Ok(())
+        })
+    }
+
+    fn describe(&self) -> String {
+        if self.prefix.is_empty() {
+            format!("supabase {}", self.bucket)
+        } else {
+            format!("supabase {}/{}", self.bucket, self.prefix)
+        }
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Mutex;
+
+    /// Object store held in memory
+    #[derive(Default)]
+    pub(crate) struct MemoryObjectStore {
+        pub(crate) objects: Mutex<HashMap<String, Vec<u8>>>,
+        gets: AtomicUsize,
+    }
+
+    impl ObjectStore for MemoryObjectStore {
+        fn get(&self, key: &str) -> io::Result<Option<Vec<u8>>> {
+            self.gets.fetch_add(1, Ordering::SeqCst);
+            Ok(self.objects.lock().unwrap().get(key).cloned())
+        }
+
+        fn put(&self, key: &str, bytes: &[u8], _content_type: &str) -> io::Result<()> {
+            self.objects.lock().unwrap().insert(key.to_string(), bytes.to_vec());
+            Ok(())
+        }
+
+        fn describe(&self) -> String {
+            "memory".to_string()
+        }
+    }
+
+    #[test]
+    fn object_log_reads_across_parts_from_any_offset() {
+        let log = ObjectIngestLog::new(MemoryObjectStore::default());
+        log.append(r#"{"envelope_id":"a"}"#).unwrap();
+        log.append(r#"{"envelope_id":"b"}"#).unwrap();
+        log.append(r#"{"envelope_id":"c"}"#).unwrap();
+
+        let first_line = r#"{"envelope_id":"a"}"#.len() as u64 + 1;
+        assert_eq!(log.end_offset().unwrap(), first_line * 3);
+        let lines: Vec<String> = log.reader_from(first_line).unwrap().lines().map(Result::unwrap).collect();
+        assert_eq!(lines, [r#"{"envelope_id":"b"}"#, r#"{"envelope_id":"c"}"#]);
+
+        assert_eq!(log.load_offset("parse").unwrap(), (0, None));
+        log.save_offset("parse", first_line, Some("a")).unwrap();
+        assert_eq!(log.load_offset("parse").unwrap(), (first_line, Some("a".to_string())));
+    }
+
+    #[test]
+    fn a_consumer_elsewhere_reads_and_acks_what_the_gateway_appended() {
+        use crate::pipeline::ingestion::ingest_log_reader::IngestLogReader;
+
+        let bucket = Arc::new(MemoryObjectStore::default());
+        let gateway = ObjectIngestLog::new(bucket.clone());
+        for id in ["e1", "e2"] {
+            gateway.append(&format!(r#"{{"envelope_id":"{}","envelope":{{"source_id":"neumos"}}}}"#, id)).unwrap();
+        }
+
+        // The parse container has its own empty data root and only shares the bucket
+        let scratch = tempfile::tempdir().unwrap();
+        let consumer = || IngestLogReader::with_backend(scratch.path(), Arc::new(ObjectIngestLog::new(bucket.clone())));
+        let (lines, last) = consumer().read_next("parse", 10).unwrap();
+        assert_eq!(lines.len(), 2);
+        assert_eq!(last.as_deref(), Some("e2"));
+        consumer().ack_through("parse", "e1").unwrap();
+
+        let (_, _, lag) = consumer().status("parse").unwrap();
+        assert_eq!(lag, lines[1].len() as u64 + 1);
+        assert_eq!(consumer().pending_by_source("parse").unwrap().get("neumos"), Some(&1));
+    }
+
+    #[test]
+    fn appends_touch_one_bounded_manifest_segment_and_reads_stream_part_by_part() {
+        let bucket = Arc::new(MemoryObjectStore::default());
+        let log = ObjectIngestLog::new(bucket.clone());
+        let line = |i: usize| format!(r#"{{"envelope_id":"e{:04}"}}"#, i);
+        let count = SEGMENT_PARTS * 2 + 5;
+        for i in 0..count {
+            log.append(&line(i)).unwrap();
+        }
+
+        let objects = bucket.objects.lock().unwrap().clone();
+        let segments: Vec<ManifestSegment> = objects
+            .iter()
+            .filter(|(key, _)| key.starts_with("ingest_log/manifest/") && *key != MANIFEST_HEAD_KEY)
+            .map(|(_, bytes)| serde_json::from_slice(bytes).unwrap())
+            .collect();
+        assert_eq!(segments.len(), 3);
+        assert!(segments.iter().all(|s| s.parts.len() <= SEGMENT_PARTS));
+
+        let width = line(0).len() as u64 + 1;
+        assert_eq!(log.end_offset().unwrap(), width * count as u64);
+        // From the middle of a line in the second segment onwards
+        let offset = width * (SEGMENT_PARTS as u64 + 3) + 4;
+        let mut reader = log.reader_from(offset).unwrap();
+        let gets = bucket.gets.load(Ordering::SeqCst);
+        let mut first = String::new();
+        reader.read_line(&mut first).unwrap();
+        assert_eq!(first, format!("{}\n", &line(SEGMENT_PARTS + 3)[4..]));
+        // One segment and one part, not the whole log
+        assert_eq!(bucket.gets.load(Ordering::SeqCst) - gets, 2);
+
+        let rest: Vec<String> = reader.lines().map(Result::unwrap).collect();
+        assert_eq!(rest.len(), count - SEGMENT_PARTS - 4);
+        assert_eq!(rest.last(), Some(&line(count - 1)));
+        assert_eq!(log.reader_from(log.end_offset().unwrap()).unwrap().lines().count(), 0);
+    }
+
+    #[test]
+    fn object_store_requests_run_outside_the_callers_runtime() {
+        // block_in_place panics on a current-thread runtime, which is what #[tokio::test] runs
+        let rt = tokio::runtime::Builder::new_current_thread().enable_all().build().unwrap();
+        let answer = rt.block_on(async { block_on_io(async { Ok(42) }) }).unwrap();
+        assert_eq!(answer, 42);
+        assert_eq!(block_on_io(async { Ok("no runtime") }).unwrap(), "no runtime");
+    }
+}
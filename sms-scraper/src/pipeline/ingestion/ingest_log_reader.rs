@@ -1,8 +1,11 @@
-use crate::pipeline::ingestion::ingest_meta::IngestMeta;
+use crate::pipeline::ingestion::encryption::{self, Keyring};
+use crate::pipeline::ingestion::envelope::{read_log_line, EnvelopeHeader, StampedEnvelopeV2};
+use crate::pipeline::ingestion::gateway::ingest_log::{self, LogLine};
+use crate::pipeline::ingestion::ingest_log_backend::{self, IngestLogBackend};
 use serde::{Deserialize, Serialize};
-use std::fs::{self, File};
-use std::io::{BufRead, BufReader, Seek, SeekFrom};
+use std::collections::HashMap;
 use std::path::PathBuf;
+use std::sync::Arc;
 
 #[derive(Debug, Serialize, Deserialize, Clone, Default)]
 pub struct ConsumerOffset {
@@ -11,95 +14,145 @@ pub struct ConsumerOffset {
     pub envelope_id: Option<String>,
 }
 
+/// Reads the ingest log for consumers. Encrypted lines are decrypted on the way out, while
+/// offsets stay byte positions in the log as stored.
 pub struct IngestLogReader {
     root: PathBuf,
+    log: Arc<dyn IngestLogBackend>,
+    keyring: Option<Arc<Keyring>>,
 }
 
 impl IngestLogReader {
+    /// Read the log from the backend chosen by `SMS_INGEST_LOG_BACKEND`
     pub fn new<P: Into<PathBuf>>(data_root: P) -> Self {
         let root = data_root.into();
-        Self { root }
+        let log = ingest_log_backend::from_env(&root);
+        Self { root, log, keyring: None }
     }
 
-    fn log_path(&self) -> PathBuf {
-        self.root.join("ingest_log").join("ingest.ndjson")
+    pub fn with_backend<P: Into<PathBuf>>(data_root: P, log: Arc<dyn IngestLogBackend>) -> Self {
+        Self { root: data_root.into(), log, keyring: None }
     }
 
+    /// Decrypt with `keyring` instead of the one configured by `SMS_ENCRYPTION_*`
+    pub fn with_keyring(mut self, keyring: Arc<Keyring>) -> Self {
+        self.keyring = Some(keyring);
+        self
+    }
 
-    fn load_offset(&self, consumer: &str) -> ConsumerOffset {
-        // Read from SQLite meta
-        if let Ok(meta) = IngestMeta::open_at_root(&self.root) {
-            if let Ok((byte_offset, envelope_id)) = meta.get_offset(consumer) {
-                return ConsumerOffset {
-                    file: "ingest.ndjson".to_string(),
-                    byte_offset,
-                    envelope_id,
-                };
-            }
+    fn keys(&self) -> std::io::Result<Arc<Keyring>> {
+        match &self.keyring {
+            Some(keyring) => Ok(keyring.clone()),
+            None => encryption::keyring(),
         }
+    }
+
+    /// Every complete record from `offset` onwards, decrypted
+    fn records_from(&self, offset: u64) -> std::io::Result<impl Iterator<Item = std::io::Result<String>>> {
+        let keys = self.keys()?;
+        let lines = ingest_log::records(self.log.reader_from(offset)?);
+        Ok(lines.map(move |line| line.and_then(|l| keys.open_line(&l).map(|r| r.into_owned()))))
+    }
+
+    fn load_offset(&self, consumer: &str) -> ConsumerOffset {
+        let (byte_offset, envelope_id) = self.log.load_offset(consumer).unwrap_or_default();
         ConsumerOffset {
             file: "ingest.ndjson".to_string(),
-            byte_offset: 0,
-            envelope_id: None,
+            byte_offset,
+            envelope_id,
         }
     }
 
     fn save_offset(&self, consumer: &str, off: &ConsumerOffset) -> std::io::Result<()> {
-        // Write to SQLite meta
-        if let Ok(meta) = IngestMeta::open_at_root(&self.root) {
-            let _ = meta.set_offset(consumer, off.byte_offset, off.envelope_id.as_deref());
-        }
-        Ok(())
+        self.log.save_offset(consumer, off.byte_offset, off.envelope_id.as_deref())
     }
 
-    pub fn status(&self, consumer: &str) -> std::io::Result<(ConsumerOffset, u64, u64)> {
+    /// The consumer's offset, reset to the start when the log has rotated underneath it
+    fn current_offset(&self, consumer: &str) -> std::io::Result<(ConsumerOffset, u64)> {
         let mut off = self.load_offset(consumer);
-        let log_path = self.log_path();
-        let end = fs::metadata(&log_path).map(|m| m.len()).unwrap_or(0);
+        let end = self.log.end_offset()?;
         if off.byte_offset > end {
             off.byte_offset = 0;
         }
+        Ok((off, end))
+    }
+
+    pub fn status(&self, consumer: &str) -> std::io::Result<(ConsumerOffset, u64, u64)> {
+        let (off, end) = self.current_offset(consumer)?;
         let lag = end.saturating_sub(off.byte_offset);
         Ok((off, end, lag))
     }
 
+    /// Count envelopes past the consumer's offset, grouped by source_id.
+    /// Dedupe markers carry no payload and are not counted.
+    pub fn pending_by_source(&self, consumer: &str) -> std::io::Result<HashMap<String, u64>> {
+        let mut pending = HashMap::new();
+        let (off, _end) = self.current_offset(consumer)?;
+        for line in self.records_from(off.byte_offset)? {
+            let Ok(header) = EnvelopeHeader::from_log_line(&line?) else {
+                continue;
+            };
+            if header.is_dedupe() {
+                continue;
+            }
+            if let Some(source_id) = header.source_id() {
+                *pending.entry(source_id.to_string()).or_insert(0) += 1;
+            }
+        }
+        Ok(pending)
+    }
+
+    /// The most recent `limit` envelope parts for a source as (envelope_id, payload_ref), oldest first.
+    /// Dedupe markers point at an earlier payload and are skipped.
+    pub fn envelopes_for_source(&self, source_id: &str, limit: usize) -> std::io::Result<Vec<(String, String)>> {
+        let mut envelopes = Vec::new();
+        for line in self.records_from(0)? {
+            let line = line?;
+            let Ok(header) = EnvelopeHeader::from_log_line(&line) else {
+                continue;
+            };
+            if header.is_dedupe() || header.source_id() != Some(source_id) {
+                continue;
+            }
+            let Ok(envelope) = read_log_line(&line) else {
+                continue;
+            };
+            // Multi-part envelopes contribute one entry per part, in order
+            for payload_ref in envelope.payload_refs() {
+                envelopes.push((envelope.envelope_id.clone(), payload_ref));
+            }
+        }
+        let skip = envelopes.len().saturating_sub(limit);
+        Ok(envelopes.split_off(skip))
+    }
+
     pub fn read_next(
         &self,
         consumer: &str,
         max: usize,
     ) -> std::io::Result<(Vec<String>, Option<String>)> {
-        let mut off = self.load_offset(consumer);
-        let path = self.log_path();
-        let file = File::open(&path)?;
-        let mut reader = BufReader::new(file);
-        // Handle rotation: if stored offset is beyond current file end, reset to 0
-        let end = fs::metadata(&path).map(|m| m.len()).unwrap_or(0);
-        if off.byte_offset > end {
-            off.byte_offset = 0;
-        }
-        reader.seek(SeekFrom::Start(off.byte_offset))?;
+        let (off, _end) = self.current_offset(consumer)?;
+        let keys = self.keys()?;
+        let mut reader = self.log.reader_from(off.byte_offset)?;
 
         let mut lines = Vec::new();
         let mut last_env: Option<String> = None;
 
         for _ in 0..max {
-            let mut buf = String::new();
-            let bytes = reader.read_line(&mut buf)?;
-            if bytes == 0 {
+            // Stops before a line still being written
+            let Some((line, _bytes)) = ingest_log::read_line(&mut *reader)? else {
                 break;
-            } // EOF
-              // Trim newline for cleaner output but we keep byte math using bytes
-            if let Some(stripped) = buf.strip_suffix('\n') {
-                buf = stripped.to_string();
-            }
+            };
+            let LogLine::Record(buf) = line else {
+                continue;
+            };
             if buf.trim().is_empty() {
                 continue;
             }
+            let buf = keys.open_line(&buf)?.into_owned();
             // Capture envelope_id for ack convenience
-            if let Ok(val) = serde_json::from_str::<serde_json::Value>(&buf) {
-                if let Some(id) = val.get("envelope_id").and_then(|v| v.as_str()) {
-                    last_env = Some(id.to_string());
-                }
+            if let Ok(header) = EnvelopeHeader::from_log_line(&buf) {
+                last_env = Some(header.envelope_id);
             }
             lines.push(buf);
         }
@@ -114,26 +167,19 @@ impl IngestLogReader {
     ) -> std::io::Result<ConsumerOffset> {
         // Advance from current offset up to and including the line with envelope_id
         let mut off = self.load_offset(consumer);
-        let path = self.log_path();
-        let mut file = File::open(&path)?;
-        file.seek(SeekFrom::Start(off.byte_offset))?;
-        let mut reader = BufReader::new(file);
+        let keys = self.keys()?;
+        let mut reader = self.log.reader_from(off.byte_offset)?;
 
         let mut cur = off.byte_offset;
-        let mut buf = String::new();
         let mut found = false;
-        loop {
-            buf.clear();
-            let read = reader.read_line(&mut buf)?;
-            if read == 0 {
-                break;
-            } // EOF
+        while let Some((line, read)) = ingest_log::read_line(&mut *reader)? {
             cur += read as u64;
-            if let Ok(val) = serde_json::from_str::<serde_json::Value>(&buf) {
-                if val.get("envelope_id").and_then(|v| v.as_str()) == Some(envelope_id) {
-                    found = true;
-                    break;
-                }
+            let LogLine::Record(buf) = line else {
+                continue;
+            };
+            if EnvelopeHeader::from_log_line(&keys.open_line(&buf)?).is_ok_and(|h| h.envelope_id == envelope_id) {
+                found = true;
+                break;
             }
         }
         if !found {
@@ -167,24 +213,24 @@ impl IngestLogReader {
     }
 
     pub fn find_envelope_by_id(&self, envelope_id: &str) -> std::io::Result<Option<String>> {
-        // Linear scan of active log file (sufficient for now)
-        let path = self.log_path();
-        if !path.exists() {
-            return Ok(None);
-        }
-        let file = File::open(&path)?;
-        let reader = BufReader::new(file);
-        for line in reader.lines() {
+        // Linear scan of the log (sufficient for now)
+        for line in self.records_from(0)? {
             let l = line?;
             if l.contains(envelope_id) {
                 // Quick filter; confirm
-                if let Ok(val) = serde_json::from_str::<serde_json::Value>(&l) {
-                    if val.get("envelope_id").and_then(|v| v.as_str()) == Some(envelope_id) {
-                        return Ok(Some(l));
-                    }
+                if EnvelopeHeader::from_log_line(&l).is_ok_and(|h| h.envelope_id == envelope_id) {
+                    return Ok(Some(l));
                 }
             }
         }
         Ok(None)
     }
+
+    /// The envelope with `envelope_id`, migrated to the current shape
+    pub fn find_envelope(&self, envelope_id: &str) -> std::io::Result<Option<StampedEnvelopeV2>> {
+        match self.find_envelope_by_id(envelope_id)? {
+            Some(line) => read_log_line(&line).map(Some).map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e)),
+            None => Ok(None),
+        }
+    }
 }
@@ -1,10 +1,194 @@
-use rusqlite::{params, Connection};
-use std::path::Path;
+use crate::observability::run_tracker::StageTiming;
+use crate::pipeline::ingestion::mailbox::MailboxCursor;
+use rusqlite::{params, Connection, OptionalExtension, Row, Transaction, TransactionBehavior};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+/// Outcome history of fetch attempts for a single source
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FetchStatus {
+    pub last_success_at: Option<i64>,
+    pub consecutive_failures: u32,
+    pub last_error: Option<String>,
+}
+
+/// Summary of a single pipeline run for a source
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RunReportEntry {
+    pub run_id: String,
+    pub source_id: String,
+    pub started_at: i64,
+    pub finished_at: i64,
+    pub records_cataloged: u64,
+    pub records_failed: u64,
+    /// Raw data items the run picked up
+    pub items_total: u64,
+    pub records_parsed: u64,
+    pub duplicates_suppressed: u64,
+    /// Time spent per stage, in the order stages first ran
+    pub stages: Vec<StageTiming>,
+    /// Per-item errors, at most `MAX_RUN_REPORT_ERRORS` of them
+    pub errors: Vec<String>,
+    /// Why the run failed outright; `None` for runs that completed
+    pub error: Option<String>,
+    /// Warnings about the run that aren't item errors, such as payload schema drift
+    pub notes: Vec<String>,
+}
+
+/// Per-item errors kept on a run report; the rest are only counted in `records_failed`
+pub const MAX_RUN_REPORT_ERRORS: usize = 50;
+
+const RUN_REPORT_COLUMNS: &str = "run_id, source_id, started_at, finished_at, records_cataloged, records_failed, \
+     items_total, records_parsed, duplicates_suppressed, stages, errors, error, notes";
+
+fn add_column_if_missing(conn: &Connection, table: &str, column: &str, decl: &str) -> rusqlite::Result<()> {
+    let exists: bool = conn.query_row(
+        &format!("SELECT EXISTS (SELECT 1 FROM pragma_table_info('{table}') WHERE name = ?1)"),
+        params![column],
+        |row| row.get(0),
+    )?;
+    if !exists {
+        conn.execute_batch(&format!("ALTER TABLE {table} ADD COLUMN {column} {decl}"))?;
+    }
+    Ok(())
+}
+
+fn run_report_from_row(row: &Row<'_>) -> rusqlite::Result<RunReportEntry> {
+    let stages: Option<String> = row.get(9)?;
+    let errors: Option<String> = row.get(10)?;
+    let notes: Option<String> = row.get(12)?;
+    Ok(RunReportEntry {
+        run_id: row.get(0)?,
+        source_id: row.get(1)?,
+        started_at: row.get(2)?,
+        finished_at: row.get(3)?,
+        records_cataloged: row.get::<_, i64>(4)? as u64,
+        records_failed: row.get::<_, i64>(5)? as u64,
+        items_total: row.get::<_, i64>(6)? as u64,
+        records_parsed: row.get::<_, i64>(7)? as u64,
+        duplicates_suppressed: row.get::<_, i64>(8)? as u64,
+        stages: stages.and_then(|s| serde_json::from_str(&s).ok()).unwrap_or_default(),
+        errors: errors.and_then(|s| serde_json::from_str(&s).ok()).unwrap_or_default(),
+        error: row.get(11)?,
+        notes: notes.and_then(|s| serde_json::from_str(&s).ok()).unwrap_or_default(),
+    })
+}
+
+/// Requests made and wire bytes received for a source in one calendar month
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SourceUsage {
+    pub requests: u64,
+    pub bytes: u64,
+}
+
+/// Where an envelope stands in processing, as last recorded by a stage
+#[derive(Debug, Clone, PartialEq)]
+pub struct EnvelopeStateEntry {
+    pub envelope_id: String,
+    pub source_id: String,
+    pub state: String,
+    /// Why it failed or was quarantined, or other stage notes
+    pub detail: Option<String>,
+    pub updated_at: i64,
+}
+
+/// Env var with how long, in seconds, an idempotency key dedupes refetches; 0 keeps keys forever
+pub const DEDUPE_HORIZON_ENV: &str = "SMS_DEDUPE_HORIZON_SECS";
+
+/// A week: well past the 12-hour default cadence and any daily cron, so a refetch within a
+/// source's normal cadence window still finds its key
+pub const DEFAULT_DEDUPE_HORIZON_SECS: i64 = 7 * 24 * 60 * 60;
+
+/// How long idempotency keys are kept before the gateway expires them; `None` when
+/// `SMS_DEDUPE_HORIZON_SECS=0` turns expiry off. An unparsable value falls back to the default.
+pub fn dedupe_horizon_secs() -> Option<i64> {
+    let secs = std::env::var(DEDUPE_HORIZON_ENV)
+        .ok()
+        .and_then(|v| v.trim().parse::<i64>().ok())
+        .unwrap_or(DEFAULT_DEDUPE_HORIZON_SECS);
+    (secs > 0).then_some(secs)
+}
+
+/// An age such as `30d`, `12h`, `90m`, `2w` or `3600s` in seconds; a bare number is days
+pub fn parse_age_secs(text: &str) -> Result<i64, String> {
+    let text = text.trim();
+    let split = text.find(|c: char| !c.is_ascii_digit()).unwrap_or(text.len());
+    let (number, unit) = text.split_at(split);
+    let number: i64 = number.parse().map_err(|_| format!("invalid age {:?} (expected e.g. 30d or 12h)", text))?;
+    let unit_secs = match unit.trim() {
+        "s" => 1,
+        "m" => 60,
+        "h" => 60 * 60,
+        "" | "d" => 24 * 60 * 60,
+        "w" => 7 * 24 * 60 * 60,
+        other => return Err(format!("unknown unit {:?} in age {:?} (use s, m, h, d or w)", other, text)),
+    };
+    number.checked_mul(unit_secs).ok_or_else(|| format!("age {:?} is too large", text))
+}
+
+/// Size and spread of the dedupe index
+#[derive(Debug, Clone, Default, PartialEq, serde::Serialize)]
+pub struct DedupeIndexStats {
+    pub keys: u64,
+    /// When the oldest and newest keys were recorded
+    pub oldest_at: Option<i64>,
+    pub newest_at: Option<i64>,
+    /// Keys per source, from the `source_id:` prefix of each idempotency key
+    pub by_source: Vec<(String, u64)>,
+    /// Size of meta.db as allocated pages, and how much of that is free pages a vacuum reclaims
+    pub db_bytes: u64,
+    pub free_bytes: u64,
+}
+
+/// Persisted token bucket: tokens left as of the last refill (wall-clock millis)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RateBucketState {
+    pub tokens: f64,
+    pub refilled_at_ms: i64,
+}
 
 pub struct IngestMeta {
     conn: Connection,
 }
 
+/// Where run bookkeeping (run reports, parse counts) is kept: `meta.db` under a data root,
+/// opened per use, or one in-memory database shared by every clone
+#[derive(Clone)]
+pub enum MetaStore {
+    Root(PathBuf),
+    Memory(Arc<Mutex<IngestMeta>>),
+}
+
+impl MetaStore {
+    pub fn at_root(data_root: impl Into<PathBuf>) -> Self {
+        MetaStore::Root(data_root.into())
+    }
+
+    pub fn in_memory() -> anyhow::Result<Self> {
+        Ok(MetaStore::Memory(Arc::new(Mutex::new(IngestMeta::open_in_memory()?))))
+    }
+
+    /// The data root `meta.db` lives under; `None` for an in-memory store, whose runs write
+    /// nothing under `data/`
+    pub fn data_root(&self) -> Option<&Path> {
+        match self {
+            MetaStore::Root(root) => Some(root),
+            MetaStore::Memory(_) => None,
+        }
+    }
+
+    /// Run `f` against the store's database
+    pub fn with<T>(&self, f: impl FnOnce(&IngestMeta) -> anyhow::Result<T>) -> anyhow::Result<T> {
+        match self {
+            MetaStore::Root(root) => f(&IngestMeta::open_at_root(root)?),
+            MetaStore::Memory(meta) => {
+                let meta = meta.lock().map_err(|_| anyhow::anyhow!("in-memory meta store poisoned"))?;
+                f(&meta)
+            }
+        }
+    }
+}
+
 impl IngestMeta {
     pub fn open_at_root<P: AsRef<Path>>(data_root: P) -> anyhow::Result<Self> {
         let db_path = data_root.as_ref().join("ingest_log").join("meta.db");
@@ -12,6 +196,18 @@ impl IngestMeta {
             std::fs::create_dir_all(parent)?;
         }
         let conn = Connection::open(db_path)?;
+        // Concurrent ingestion opens several connections; wait on locks instead of failing
+        conn.busy_timeout(std::time::Duration::from_secs(5))?;
+        Self::with_schema(conn)
+    }
+
+    /// A private database that lives only as long as this value, for runs that must not
+    /// touch `data/` (demos, CI)
+    pub fn open_in_memory() -> anyhow::Result<Self> {
+        Self::with_schema(Connection::open_in_memory()?)
+    }
+
+    fn with_schema(conn: Connection) -> anyhow::Result<Self> {
         conn.execute_batch(
             r#"
             PRAGMA journal_mode=WAL;
@@ -28,8 +224,100 @@ impl IngestMeta {
                 source_id        TEXT PRIMARY KEY,
                 last_fetched_at  INTEGER NOT NULL
             );
+            CREATE TABLE IF NOT EXISTS fetch_status (
+                source_id             TEXT PRIMARY KEY,
+                last_success_at       INTEGER,
+                consecutive_failures  INTEGER NOT NULL DEFAULT 0,
+                last_error            TEXT
+            );
+            CREATE TABLE IF NOT EXISTS run_reports (
+                run_id             TEXT PRIMARY KEY,
+                source_id          TEXT NOT NULL,
+                started_at         INTEGER NOT NULL,
+                finished_at        INTEGER NOT NULL,
+                records_cataloged  INTEGER NOT NULL,
+                records_failed     INTEGER NOT NULL,
+                items_total            INTEGER NOT NULL DEFAULT 0,
+                records_parsed         INTEGER NOT NULL DEFAULT 0,
+                duplicates_suppressed  INTEGER NOT NULL DEFAULT 0,
+                stages                 TEXT,
+                errors                 TEXT,
+                error                  TEXT,
+                notes                  TEXT
+            );
+            CREATE INDEX IF NOT EXISTS idx_run_reports_source
+                ON run_reports (source_id, finished_at);
+            CREATE TABLE IF NOT EXISTS parse_counts (
+                source_id     TEXT NOT NULL,
+                recorded_at   INTEGER NOT NULL,
+                record_count  INTEGER NOT NULL
+            );
+            CREATE INDEX IF NOT EXISTS idx_parse_counts_source
+                ON parse_counts (source_id, recorded_at);
+            CREATE TABLE IF NOT EXISTS payload_fingerprints (
+                source_id    TEXT PRIMARY KEY,
+                fingerprint  TEXT NOT NULL,
+                recorded_at  INTEGER NOT NULL
+            );
+            CREATE TABLE IF NOT EXISTS rate_limit_buckets (
+                source_id       TEXT NOT NULL,
+                bucket          TEXT NOT NULL,
+                tokens          REAL NOT NULL,
+                refilled_at_ms  INTEGER NOT NULL,
+                PRIMARY KEY (source_id, bucket)
+            );
+            CREATE TABLE IF NOT EXISTS source_usage (
+                source_id  TEXT NOT NULL,
+                month      TEXT NOT NULL,
+                requests   INTEGER NOT NULL DEFAULT 0,
+                bytes      INTEGER NOT NULL DEFAULT 0,
+                PRIMARY KEY (source_id, month)
+            );
+            CREATE TABLE IF NOT EXISTS mailbox_cursors (
+                source_id     TEXT NOT NULL,
+                mailbox       TEXT NOT NULL,
+                uid_validity  INTEGER NOT NULL,
+                last_uid      INTEGER NOT NULL,
+                PRIMARY KEY (source_id, mailbox)
+            );
+            CREATE TABLE IF NOT EXISTS envelope_state (
+                envelope_id  TEXT PRIMARY KEY,
+                source_id    TEXT NOT NULL,
+                state        TEXT NOT NULL,
+                detail       TEXT,
+                updated_at   INTEGER NOT NULL
+            );
+            CREATE INDEX IF NOT EXISTS idx_envelope_state_state
+                ON envelope_state (state, updated_at);
+            CREATE TABLE IF NOT EXISTS envelope_state_history (
+                envelope_id  TEXT NOT NULL,
+                state        TEXT NOT NULL,
+                detail       TEXT,
+                at           INTEGER NOT NULL
+            );
+            CREATE INDEX IF NOT EXISTS idx_envelope_state_history
+                ON envelope_state_history (envelope_id, at);
             "#,
         )?;
+        // meta.db files from before run reports carried stage detail lack these columns
+        for (column, decl) in [
+            ("items_total", "INTEGER NOT NULL DEFAULT 0"),
+            ("records_parsed", "INTEGER NOT NULL DEFAULT 0"),
+            ("duplicates_suppressed", "INTEGER NOT NULL DEFAULT 0"),
+            ("stages", "TEXT"),
+            ("errors", "TEXT"),
+            ("error", "TEXT"),
+            ("notes", "TEXT"),
+        ] {
+            add_column_if_missing(&conn, "run_reports", column, decl)?;
+        }
+        // Keys written before keys were timestamped start aging from the first open that sees them
+        add_column_if_missing(&conn, "dedupe_index", "created_at", "INTEGER")?;
+        conn.execute_batch("CREATE INDEX IF NOT EXISTS idx_dedupe_index_created ON dedupe_index (created_at)")?;
+        conn.execute(
+            "UPDATE dedupe_index SET created_at = ?1 WHERE created_at IS NULL",
+            params![chrono::Utc::now().timestamp()],
+        )?;
         Ok(Self { conn })
     }
 
@@ -49,12 +337,55 @@ impl IngestMeta {
 
     pub fn put_dedupe_mapping(&self, idk: &str, envelope_id: &str) -> anyhow::Result<()> {
         self.conn.execute(
-            "INSERT OR REPLACE INTO dedupe_index (idempotency_key, envelope_id) VALUES (?1, ?2)",
-            params![idk, envelope_id],
+            "INSERT OR REPLACE INTO dedupe_index (idempotency_key, envelope_id, created_at) VALUES (?1, ?2, ?3)",
+            params![idk, envelope_id, chrono::Utc::now().timestamp()],
         )?;
         Ok(())
     }
 
+    /// Drop idempotency keys recorded before `before` (unix seconds); returns how many went
+    pub fn expire_dedupe_keys(&self, before: i64) -> anyhow::Result<usize> {
+        Ok(self.conn.execute("DELETE FROM dedupe_index WHERE created_at < ?1", params![before])?)
+    }
+
+    pub fn dedupe_stats(&self) -> anyhow::Result<DedupeIndexStats> {
+        let (keys, oldest_at, newest_at) = self.conn.query_row(
+            "SELECT COUNT(*), MIN(created_at), MAX(created_at) FROM dedupe_index",
+            [],
+            |row| Ok((row.get::<_, i64>(0)? as u64, row.get(1)?, row.get(2)?)),
+        )?;
+        let mut stmt = self.conn.prepare(
+            "SELECT CASE WHEN instr(idempotency_key, ':') > 0
+                         THEN substr(idempotency_key, 1, instr(idempotency_key, ':') - 1)
+                         ELSE idempotency_key END AS source_id,
+                    COUNT(*)
+             FROM dedupe_index GROUP BY source_id ORDER BY COUNT(*) DESC, source_id",
+        )?;
+        let by_source = stmt
+            .query_map([], |row| Ok((row.get::<_, String>(0)?, row.get::<_, i64>(1)? as u64)))?
+            .collect::<Result<Vec<_>, _>>()?;
+        let (db_bytes, free_bytes) = self.page_bytes()?;
+        Ok(DedupeIndexStats { keys, oldest_at, newest_at, by_source, db_bytes, free_bytes })
+    }
+
+    /// Rebuild meta.db to hand free pages back to the filesystem; returns its size before and
+    /// after in bytes
+    pub fn vacuum(&self) -> anyhow::Result<(u64, u64)> {
+        let (before, _) = self.page_bytes()?;
+        self.conn.execute_batch("PRAGMA wal_checkpoint(TRUNCATE); VACUUM;")?;
+        let (after, _) = self.page_bytes()?;
+        Ok((before, after))
+    }
+
+    /// Allocated and free bytes of the database file
+    fn page_bytes(&self) -> anyhow::Result<(u64, u64)> {
+        let pragma = |name: &str| -> rusqlite::Result<i64> {
+            self.conn.query_row(&format!("PRAGMA {name}"), [], |row| row.get(0))
+        };
+        let page_size = pragma("page_size")? as u64;
+        Ok((pragma("page_count")? as u64 * page_size, pragma("freelist_count")? as u64 * page_size))
+    }
+
     // Consumer offsets
     pub fn get_offset(&self, consumer: &str) -> anyhow::Result<(u64, Option<String>)> {
         let mut stmt = self
@@ -106,4 +437,428 @@ impl IngestMeta {
         )?;
         Ok(())
     }
+
+    /// Read-modify-write a source's token bucket in an immediate transaction, so separate
+    /// processes sharing this DB draw from one budget. `f` gets the stored state (if any)
+    /// and returns the state to store plus its result.
+    pub fn update_rate_bucket<R>(
+        &self,
+        source_id: &str,
+        bucket: &str,
+        f: impl FnOnce(Option<RateBucketState>) -> (RateBucketState, R),
+    ) -> anyhow::Result<R> {
+        let tx = Transaction::new_unchecked(&self.conn, TransactionBehavior::Immediate)?;
+        let current = tx
+            .query_row(
+                "SELECT tokens, refilled_at_ms FROM rate_limit_buckets WHERE source_id = ?1 AND bucket = ?2",
+                params![source_id, bucket],
+                |row| Ok(RateBucketState { tokens: row.get(0)?, refilled_at_ms: row.get(1)? }),
+            )
+            .optional()?;
+        let (next, result) = f(current);
+        tx.execute(
+            "INSERT INTO rate_limit_buckets (source_id, bucket, tokens, refilled_at_ms) VALUES (?1, ?2, ?3, ?4)
+             ON CONFLICT(source_id, bucket) DO UPDATE SET tokens=excluded.tokens, refilled_at_ms=excluded.refilled_at_ms",
+            params![source_id, bucket, next.tokens, next.refilled_at_ms],
+        )?;
+        tx.commit()?;
+        Ok(result)
+    }
+
+    // Fetch outcome tracking (last success, consecutive failures)
+    pub fn record_fetch_success(&self, source_id: &str, ts: i64) -> anyhow::Result<()> {
+        self.conn.execute(
+            "INSERT INTO fetch_status (source_id, last_success_at, consecutive_failures, last_error) VALUES (?1, ?2, 0, NULL)
+             ON CONFLICT(source_id) DO UPDATE SET last_success_at=excluded.last_success_at, consecutive_failures=0, last_error=NULL",
+            params![source_id, ts],
+        )?;
+        Ok(())
+    }
+
+    pub fn record_fetch_failure(&self, source_id: &str, error: &str) -> anyhow::Result<()> {
+        self.conn.execute(
+            "INSERT INTO fetch_status (source_id, last_success_at, consecutive_failures, last_error) VALUES (?1, NULL, 1, ?2)
+             ON CONFLICT(source_id) DO UPDATE SET consecutive_failures=consecutive_failures + 1, last_error=excluded.last_error",
+            params![source_id, error],
+        )?;
+        Ok(())
+    }
+
+    pub fn get_fetch_status(&self, source_id: &str) -> anyhow::Result<Option<FetchStatus>> {
+        let status = self
+            .conn
+            .query_row(
+                "SELECT last_success_at, consecutive_failures, last_error FROM fetch_status WHERE source_id = ?1",
+                params![source_id],
+                |row| {
+                    Ok(FetchStatus {
+                        last_success_at: row.get(0)?,
+                        consecutive_failures: row.get::<_, i64>(1)? as u32,
+                        last_error: row.get(2)?,
+                    })
+                },
+            )
+            .optional()?;
+        Ok(status)
+    }
+
+    // Run reports
+    pub fn put_run_report(&self, report: &RunReportEntry) -> anyhow::Result<()> {
+        self.conn.execute(
+            &format!("INSERT OR REPLACE INTO run_reports ({RUN_REPORT_COLUMNS}) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13)"),
+            params![
+                report.run_id,
+                report.source_id,
+                report.started_at,
+                report.finished_at,
+                report.records_cataloged as i64,
+                report.records_failed as i64,
+                report.items_total as i64,
+                report.records_parsed as i64,
+                report.duplicates_suppressed as i64,
+                serde_json::to_string(&report.stages)?,
+                serde_json::to_string(&report.errors)?,
+                report.error,
+                serde_json::to_string(&report.notes)?
+            ],
+        )?;
+        Ok(())
+    }
+
+    pub fn latest_run_report(&self, source_id: &str) -> anyhow::Result<Option<RunReportEntry>> {
+        let report = self
+            .conn
+            .query_row(
+                &format!(
+                    "SELECT {RUN_REPORT_COLUMNS} FROM run_reports WHERE source_id = ?1 ORDER BY finished_at DESC LIMIT 1"
+                ),
+                params![source_id],
+                run_report_from_row,
+            )
+            .optional()?;
+        Ok(report)
+    }
+
+    pub fn get_run_report(&self, run_id: &str) -> anyhow::Result<Option<RunReportEntry>> {
+        let report = self
+            .conn
+            .query_row(
+                &format!("SELECT {RUN_REPORT_COLUMNS} FROM run_reports WHERE run_id = ?1"),
+                params![run_id],
+                run_report_from_row,
+            )
+            .optional()?;
+        Ok(report)
+    }
+
+    /// The `limit` most recent run reports, newest first, for one source or all of them
+    pub fn recent_run_reports(&self, source_id: Option<&str>, limit: usize) -> anyhow::Result<Vec<RunReportEntry>> {
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT {RUN_REPORT_COLUMNS} FROM run_reports
+             WHERE ?1 IS NULL OR source_id = ?1 ORDER BY finished_at DESC, run_id LIMIT ?2"
+        ))?;
+        let reports = stmt
+            .query_map(params![source_id, limit as i64], run_report_from_row)?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(reports)
+    }
+
+    // Payload fingerprints, used by schema drift detection
+    /// The fingerprint last recorded for a source, as stored JSON, with when it was recorded
+    pub fn get_payload_fingerprint(&self, source_id: &str) -> anyhow::Result<Option<(String, i64)>> {
+        let row = self
+            .conn
+            .query_row(
+                "SELECT fingerprint, recorded_at FROM payload_fingerprints WHERE source_id = ?1",
+                params![source_id],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .optional()?;
+        Ok(row)
+    }
+
+    pub fn put_payload_fingerprint(&self, source_id: &str, fingerprint: &str, recorded_at: i64) -> anyhow::Result<()> {
+        self.conn.execute(
+            "INSERT INTO payload_fingerprints (source_id, fingerprint, recorded_at) VALUES (?1, ?2, ?3)
+             ON CONFLICT(source_id) DO UPDATE SET fingerprint=excluded.fingerprint, recorded_at=excluded.recorded_at",
+            params![source_id, fingerprint, recorded_at],
+        )?;
+        Ok(())
+    }
+
+    // Parsed record counts, used by the site change watchdog
+    pub fn record_parse_count(&self, source_id: &str, recorded_at: i64, record_count: u64) -> anyhow::Result<()> {
+        self.conn.execute(
+            "INSERT INTO parse_counts (source_id, recorded_at, record_count) VALUES (?1, ?2, ?3)",
+            params![source_id, recorded_at, record_count as i64],
+        )?;
+        Ok(())
+    }
+
+    /// Most recent parsed record counts for a source, newest first
+    pub fn recent_parse_counts(&self, source_id: &str, limit: usize) -> anyhow::Result<Vec<u64>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT record_count FROM parse_counts WHERE source_id = ?1
+             ORDER BY recorded_at DESC, rowid DESC LIMIT ?2",
+        )?;
+        let rows = stmt.query_map(params![source_id, limit as i64], |row| row.get::<_, i64>(0))?;
+        let mut counts = Vec::new();
+        for r in rows {
+            counts.push(r? as u64);
+        }
+        Ok(counts)
+    }
+
+    // Monthly usage, for per-source quotas
+    pub fn add_usage(&self, source_id: &str, month: &str, usage: SourceUsage) -> anyhow::Result<()> {
+        self.conn.execute(
+            "INSERT INTO source_usage (source_id, month, requests, bytes) VALUES (?1, ?2, ?3, ?4)
+             ON CONFLICT(source_id, month) DO UPDATE SET requests=requests + excluded.requests, bytes=bytes + excluded.bytes",
+            params![source_id, month, usage.requests as i64, usage.bytes as i64],
+        )?;
+        Ok(())
+    }
+
+    pub fn get_usage(&self, source_id: &str, month: &str) -> anyhow::Result<SourceUsage> {
+        let usage = self
+            .conn
+            .query_row(
+                "SELECT requests, bytes FROM source_usage WHERE source_id = ?1 AND month = ?2",
+                params![source_id, month],
+                |row| {
+                    Ok(SourceUsage {
+                        requests: row.get::<_, i64>(0)? as u64,
+                        bytes: row.get::<_, i64>(1)? as u64,
+                    })
+                },
+            )
+            .optional()?;
+        Ok(usage.unwrap_or_default())
+    }
+
+    // Mailbox cursors, so newsletter sources only fetch messages they haven't accepted
+    pub fn get_mailbox_cursor(&self, source_id: &str, mailbox: &str) -> anyhow::Result<Option<MailboxCursor>> {
+        let cursor = self
+            .conn
+            .query_row(
+                "SELECT uid_validity, last_uid FROM mailbox_cursors WHERE source_id = ?1 AND mailbox = ?2",
+                params![source_id, mailbox],
+                |row| {
+                    Ok(MailboxCursor {
+                        uid_validity: row.get::<_, i64>(0)? as u64,
+                        last_uid: row.get::<_, i64>(1)? as u64,
+                    })
+                },
+            )
+            .optional()?;
+        Ok(cursor)
+    }
+
+    pub fn put_mailbox_cursor(&self, source_id: &str, mailbox: &str, cursor: MailboxCursor) -> anyhow::Result<()> {
+        self.conn.execute(
+            "INSERT INTO mailbox_cursors (source_id, mailbox, uid_validity, last_uid) VALUES (?1, ?2, ?3, ?4)
+             ON CONFLICT(source_id, mailbox) DO UPDATE SET uid_validity=excluded.uid_validity, last_uid=excluded.last_uid",
+            params![source_id, mailbox, cursor.uid_validity as i64, cursor.last_uid as i64],
+        )?;
+        Ok(())
+    }
+
+    // Envelope processing state
+    /// Record that an envelope reached `state`, keeping the previous states as history
+    pub fn put_envelope_state(&self, entry: &EnvelopeStateEntry) -> anyhow::Result<()> {
+        let tx = self.conn.unchecked_transaction()?;
+        tx.execute(
+            "INSERT OR REPLACE INTO envelope_state (envelope_id, source_id, state, detail, updated_at)
+             VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![entry.envelope_id, entry.source_id, entry.state, entry.detail, entry.updated_at],
+        )?;
+        tx.execute(
+            "INSERT INTO envelope_state_history (envelope_id, state, detail, at) VALUES (?1, ?2, ?3, ?4)",
+            params![entry.envelope_id, entry.state, entry.detail, entry.updated_at],
+        )?;
+        tx.commit()?;
+        Ok(())
+    }
+
+    pub fn get_envelope_state(&self, envelope_id: &str) -> anyhow::Result<Option<EnvelopeStateEntry>> {
+        let entry = self
+            .conn
+            .query_row(
+                "SELECT envelope_id, source_id, state, detail, updated_at FROM envelope_state WHERE envelope_id = ?1",
+                params![envelope_id],
+                |row| {
+                    Ok(EnvelopeStateEntry {
+                        envelope_id: row.get(0)?,
+                        source_id: row.get(1)?,
+                        state: row.get(2)?,
+                        detail: row.get(3)?,
+                        updated_at: row.get(4)?,
+                    })
+                },
+            )
+            .optional()?;
+        Ok(entry)
+    }
+
+    /// Every state an envelope has been in as (state, detail, at), oldest first
+    pub fn envelope_state_history(&self, envelope_id: &str) -> anyhow::Result<Vec<(String, Option<String>, i64)>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT state, detail, at FROM envelope_state_history WHERE envelope_id = ?1 ORDER BY at, rowid",
+        )?;
+        let rows = stmt
+            .query_map(params![envelope_id], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)))?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(rows)
+    }
+
+    /// Envelopes whose current state is one of `states` and was set before `updated_before`, oldest first
+    pub fn envelopes_in_states_since(&self, states: &[&str], updated_before: i64) -> anyhow::Result<Vec<EnvelopeStateEntry>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT envelope_id, source_id, state, detail, updated_at FROM envelope_state
+             WHERE state = ?1 AND updated_at < ?2 ORDER BY updated_at",
+        )?;
+        let mut entries = Vec::new();
+        for state in states {
+            let rows = stmt.query_map(params![state, updated_before], |row| {
+                Ok(EnvelopeStateEntry {
+                    envelope_id: row.get(0)?,
+                    source_id: row.get(1)?,
+                    state: row.get(2)?,
+                    detail: row.get(3)?,
+                    updated_at: row.get(4)?,
+                })
+            })?;
+            for r in rows {
+                entries.push(r?);
+            }
+        }
+        entries.sort_by_key(|e| e.updated_at);
+        Ok(entries)
+    }
+
+    /// All source ids that have any cadence, fetch or run history
+    pub fn known_source_ids(&self) -> anyhow::Result<Vec<String>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT source_id FROM fetch_cadence
+             UNION SELECT source_id FROM fetch_status
+             UNION SELECT source_id FROM run_reports
+             ORDER BY source_id",
+        )?;
+        let ids = stmt
+            .query_map([], |row| row.get::<_, String>(0))?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(ids)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn run_reports_keep_stage_detail_and_migrate_old_tables() {
+        let tmp = tempfile::tempdir().unwrap();
+        let db_dir = tmp.path().join("ingest_log");
+        std::fs::create_dir_all(&db_dir).unwrap();
+        Connection::open(db_dir.join("meta.db"))
+            .unwrap()
+            .execute_batch(
+                "CREATE TABLE run_reports (run_id TEXT PRIMARY KEY, source_id TEXT NOT NULL, started_at INTEGER NOT NULL,
+                 finished_at INTEGER NOT NULL, records_cataloged INTEGER NOT NULL, records_failed INTEGER NOT NULL);
+                 INSERT INTO run_reports VALUES ('old', 'neumos', 100, 160, 8, 0);",
+            )
+            .unwrap();
+
+        let meta = IngestMeta::open_at_root(tmp.path()).unwrap();
+        meta.put_run_report(&RunReportEntry {
+            run_id: "new".into(),
+            source_id: "neumos".into(),
+            started_at: 200,
+            finished_at: 230,
+            records_cataloged: 5,
+            records_failed: 1,
+            items_total: 6,
+            records_parsed: 7,
+            stages: vec![StageTiming { stage: "parse".into(), calls: 6, duration_ms: 420 }],
+            errors: vec!["item 3: bad date".into()],
+            notes: vec!["payload schema drift 80%".into()],
+            ..Default::default()
+        })
+        .unwrap();
+        meta.put_run_report(&RunReportEntry {
+            run_id: "kexp-run".into(),
+            source_id: "kexp".into(),
+            started_at: 300,
+            finished_at: 301,
+            error: Some("registry entry missing".into()),
+            ..Default::default()
+        })
+        .unwrap();
+
+        let neumos = meta.recent_run_reports(Some("neumos"), 10).unwrap();
+        assert_eq!(neumos.iter().map(|r| r.run_id.as_str()).collect::<Vec<_>>(), ["new", "old"]);
+        assert_eq!(neumos[0].stages[0].duration_ms, 420);
+        assert_eq!(neumos[0].errors, ["item 3: bad date"]);
+        assert_eq!(neumos[0].notes, ["payload schema drift 80%"]);
+        assert_eq!((neumos[1].records_cataloged, neumos[1].records_parsed), (8, 0));
+        assert!(neumos[1].stages.is_empty() && neumos[1].notes.is_empty());
+
+        assert_eq!(meta.recent_run_reports(None, 1).unwrap()[0].run_id, "kexp-run");
+        let failed = meta.get_run_report("kexp-run").unwrap().unwrap();
+        assert_eq!(failed.error.as_deref(), Some("registry entry missing"));
+        assert!(meta.get_run_report("missing").unwrap().is_none());
+    }
+
+    #[test]
+    fn dedupe_keys_age_out_and_report_per_source() {
+        let tmp = tempfile::tempdir().unwrap();
+        let db_dir = tmp.path().join("ingest_log");
+        std::fs::create_dir_all(&db_dir).unwrap();
+        Connection::open(db_dir.join("meta.db"))
+            .unwrap()
+            .execute_batch(
+                "CREATE TABLE dedupe_index (idempotency_key TEXT PRIMARY KEY, envelope_id TEXT NOT NULL);
+                 INSERT INTO dedupe_index VALUES ('kexp:https://kexp.org/events::abc', 'e0');",
+            )
+            .unwrap();
+
+        let meta = IngestMeta::open_at_root(tmp.path()).unwrap();
+        meta.put_dedupe_mapping("neumos:https://neumos.com::def", "e1").unwrap();
+        meta.put_dedupe_mapping("neumos:https://neumos.com::ghi", "e2").unwrap();
+        meta.conn
+            .execute("UPDATE dedupe_index SET created_at = 100 WHERE envelope_id = 'e1'", [])
+            .unwrap();
+
+        let stats = meta.dedupe_stats().unwrap();
+        assert_eq!(stats.keys, 3);
+        assert_eq!(stats.oldest_at, Some(100));
+        assert_eq!(stats.by_source, [("neumos".to_string(), 2), ("kexp".to_string(), 1)]);
+        assert!(stats.db_bytes > 0);
+
+        // The legacy key was stamped at open, so only the back-dated one is past the cutoff
+        assert_eq!(meta.expire_dedupe_keys(1_000).unwrap(), 1);
+        assert!(meta.get_envelope_by_idk("neumos:https://neumos.com::def").unwrap().is_none());
+        assert_eq!(meta.get_envelope_by_idk("kexp:https://kexp.org/events::abc").unwrap().as_deref(), Some("e0"));
+        let (before, after) = meta.vacuum().unwrap();
+        assert!(after <= before);
+
+        assert_eq!(parse_age_secs("30d"), Ok(30 * 86_400));
+        assert_eq!(parse_age_secs("12h"), Ok(43_200));
+        assert_eq!(parse_age_secs("7"), Ok(7 * 86_400));
+        assert!(parse_age_secs("soon").is_err());
+        assert!(parse_age_secs("3y").is_err());
+    }
+
+    #[test]
+    fn in_memory_store_is_shared_by_clones() {
+        let store = MetaStore::in_memory().unwrap();
+        let clone = store.clone();
+        store.with(|meta| meta.record_parse_count("blue_moon", 100, 12)).unwrap();
+        clone.with(|meta| meta.record_parse_count("blue_moon", 200, 9)).unwrap();
+
+        let counts = store.with(|meta| meta.recent_parse_counts("blue_moon", 5)).unwrap();
+        assert_eq!(counts, [9, 12]);
+        let other = MetaStore::in_memory().unwrap();
+        assert!(other.with(|meta| meta.recent_parse_counts("blue_moon", 5)).unwrap().is_empty());
+    }
 }
@@ -0,0 +1,555 @@
+// Mailbox endpoints: venues that only announce shows in an email newsletter are subscribed to
+// with a dedicated address, and the gateway reads that mailbox instead of a web page. The
+// endpoint URL names the mailbox:
+//
+//   imaps://imap.example.com/INBOX       IMAP over TLS (port 993 unless given)
+//   imap://127.0.0.1:1143/Newsletters    plain IMAP, for local bridges such as Proton's
+//   file:///var/mail/newsletters.mbox    an mbox file a mail filter appends to
+//
+// Only the IMAP commands a read-only fetch needs are spoken (LOGIN, SELECT, UID SEARCH,
+// UID FETCH BODY.PEEK[], LOGOUT), so messages are never marked read or moved.
+
+use crate::pipeline::ingestion::quota::UsageCounter;
+use crate::pipeline::ingestion::rate_limiter::RateLimiter;
+use anyhow::{anyhow, bail, Context};
+use reqwest::Url;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::io::{AsyncBufReadExt, AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt, BufReader};
+use tracing::{debug, warn};
+
+/// MIME type of the envelopes a mailbox endpoint produces: one raw message each
+pub const MESSAGE_MIME: &str = "message/rfc822";
+
+/// Read new messages from the mailbox the endpoint's URL names instead of fetching it over HTTP
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct MailboxSpec {
+    /// Env vars holding the IMAP login; credentials never live in the registry
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub username_env: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub password_env: Option<String>,
+    /// Only messages whose `From` contains one of these (case-insensitive); every message when empty
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub from: Vec<String>,
+    /// Messages accepted per fetch, oldest first; the rest wait for the next fetch
+    #[serde(default = "default_max_messages")]
+    pub max_messages: u32,
+}
+
+fn default_max_messages() -> u32 {
+    20
+}
+
+impl Default for MailboxSpec {
+    fn default() -> Self {
+        Self { username_env: None, password_env: None, from: Vec::new(), max_messages: default_max_messages() }
+    }
+}
+
+/// How far a mailbox has been read: the highest UID seen under the mailbox's UIDVALIDITY. An
+/// mbox file counts its messages from 1 and has a UIDVALIDITY of 0.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MailboxCursor {
+    pub uid_validity: u64,
+    pub last_uid: u64,
+}
+
+/// One message, as raw MIME
+#[derive(Debug, Clone)]
+pub struct MailMessage {
+    pub uid: u64,
+    pub raw: Vec<u8>,
+}
+
+/// Messages received since the cursor, and the cursor to store once they're accepted
+#[derive(Debug)]
+pub struct MailboxFetch {
+    pub messages: Vec<MailMessage>,
+    pub cursor: MailboxCursor,
+}
+
+/// Whole IMAP sessions that take longer than this are abandoned
+const SESSION_TIMEOUT: Duration = Duration::from_secs(120);
+
+/// Read the messages the mailbox at `url` received after `cursor` (all of them without one).
+/// Messages over `max_bytes` are skipped with a warning rather than failing every later fetch.
+pub async fn fetch_new(
+    url: &str,
+    spec: &MailboxSpec,
+    cursor: Option<MailboxCursor>,
+    max_bytes: u64,
+    rl: &RateLimiter,
+    usage: &UsageCounter,
+) -> anyhow::Result<MailboxFetch> {
+    let mut fetch = match MailboxUrl::parse(url)? {
+        MailboxUrl::Mbox(path) => read_mbox(&path, spec, cursor, usage)?,
+        MailboxUrl::Imap(server) => tokio::time::timeout(SESSION_TIMEOUT, fetch_imap(&server, spec, cursor, rl, usage))
+            .await
+            .map_err(|_| anyhow!("IMAP session with {} timed out", server.host))??,
+    };
+    fetch.messages.retain(|message| {
+        let fits = message.raw.len() as u64 <= max_bytes;
+        if !fits {
+            warn!("Skipping message {} of {}: {} bytes is over the {} byte cap", message.uid, url, message.raw.len(), max_bytes);
+        }
+        fits
+    });
+    Ok(fetch)
+}
+
+enum MailboxUrl {
+    Imap(ImapServer),
+    Mbox(PathBuf),
+}
+
+struct ImapServer {
+    tls: bool,
+    host: String,
+    port: u16,
+    mailbox: String,
+}
+
+impl MailboxUrl {
+    fn parse(url: &str) -> anyhow::Result<Self> {
+        let parsed = Url::parse(url).with_context(|| format!("invalid mailbox URL {}", url))?;
+        let tls = match parsed.scheme() {
+            "imaps" => true,
+            "imap" => false,
+            "file" => {
+                let path = parsed.to_file_path().map_err(|_| anyhow!("invalid mbox path in {}", url))?;
+                return Ok(Self::Mbox(path));
+            }
+            other => bail!("unsupported mailbox scheme '{}' (expected imaps, imap or file)", other),
+        };
+        let host = parsed.host_str().filter(|h| !h.is_empty()).ok_or_else(|| anyhow!("mailbox URL {} has no host", url))?;
+        let mailbox = percent_decode(parsed.path().trim_matches('/'));
+        Ok(Self::Imap(ImapServer {
+            tls,
+            host: host.to_string(),
+            port: parsed.port().unwrap_or(if tls { 993 } else { 143 }),
+            mailbox: if mailbox.is_empty() { "INBOX".to_string() } else { mailbox },
+        }))
+    }
+}
+
+fn percent_decode(text: &str) -> String {
+    let bytes = text.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let hex = (bytes[i] == b'%').then(|| text.get(i + 1..i + 3)).flatten();
+        match hex.and_then(|h| u8::from_str_radix(h, 16).ok()) {
+            Some(byte) => {
+                out.push(byte);
+                i += 3;
+            }
+            None => {
+                out.push(bytes[i]);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&out).into_owned()
+}
+
+/// Messages of an mbox file after the cursor. A file with fewer messages than the cursor was
+/// rotated or rewritten, so it is read from the start again.
+fn read_mbox(path: &std::path::Path, spec: &MailboxSpec, cursor: Option<MailboxCursor>, usage: &UsageCounter) -> anyhow::Result<MailboxFetch> {
+    let bytes = match std::fs::read(path) {
+        Ok(bytes) => bytes,
+        // Mail filters create the file with the first delivery
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Vec::new(),
+        Err(e) => return Err(e).with_context(|| format!("failed to read mbox {}", path.display())),
+    };
+    usage.add(1, bytes.len() as u64);
+    let messages = split_mbox(&bytes);
+    let mut cursor = cursor.unwrap_or_default();
+    if cursor.last_uid > messages.len() as u64 {
+        debug!("mbox {} shrank below its cursor; reading it from the start", path.display());
+        cursor.last_uid = 0;
+    }
+
+    let max = spec.max_messages.max(1) as usize;
+    let mut taken = Vec::new();
+    for (uid, raw) in (1u64..).zip(messages).skip(cursor.last_uid as usize) {
+        if taken.len() == max {
+            break;
+        }
+        cursor.last_uid = uid;
+        if sender_matches(&raw, &spec.from) {
+            taken.push(MailMessage { uid, raw });
+        }
+    }
+    Ok(MailboxFetch { messages: taken, cursor })
+}
+
+/// Split an mbox file on its `From ` separator lines, undoing mboxrd `>From ` quoting
+fn split_mbox(bytes: &[u8]) -> Vec<Vec<u8>> {
+    let mut messages: Vec<Vec<u8>> = Vec::new();
+    let mut previous_blank = true;
+    for line in bytes.split_inclusive(|b| *b == b'\n') {
+        if previous_blank && line.starts_with(b"From ") {
+            messages.push(Vec::new());
+            previous_blank = false;
+            continue;
+        }
+        previous_blank = line == b"\n" || line == b"\r\n";
+        let Some(message) = messages.last_mut() else { continue };
+        let quoted = line.iter().take_while(|b| **b == b'>').count();
+        if quoted > 0 && line[quoted..].starts_with(b"From ") {
+            message.extend_from_slice(&line[1..]);
+        } else {
+            message.extend_from_slice(line);
+        }
+    }
+    // The blank line before each separator belongs to the file format, not the message
+    for message in &mut messages {
+        if message.ends_with(b"\r\n\r\n") {
+            message.truncate(message.len() - 2);
+        } else if message.ends_with(b"\n\n") {
+            message.truncate(message.len() - 1);
+        }
+    }
+    messages
+}
+
+/// True when no senders are configured or the message's `From` header names one of them
+fn sender_matches(raw: &[u8], from: &[String]) -> bool {
+    if from.is_empty() {
+        return true;
+    }
+    let Some(sender) = header(raw, "from") else { return false };
+    let sender = sender.to_lowercase();
+    from.iter().any(|f| sender.contains(&f.to_lowercase()))
+}
+
+/// A header's unfolded value, read from the message's header block
+fn header(raw: &[u8], name: &str) -> Option<String> {
+    let text = String::from_utf8_lossy(raw);
+    let mut value: Option<String> = None;
+    for line in text.lines() {
+        if line.is_empty() {
+            break;
+        }
+        match (line.starts_with([' ', '\t']), value.as_mut()) {
+            (true, Some(value)) => value.push_str(line),
+            (true, None) => {}
+            (false, Some(_)) => break,
+            (false, None) => {
+                if let Some((key, rest)) = line.split_once(':') {
+                    if key.trim().eq_ignore_ascii_case(name) {
+                        value = Some(rest.trim().to_string());
+                    }
+                }
+            }
+        }
+    }
+    value
+}
+
+/// Log in, find the messages after the cursor (from the configured senders, searched for on
+/// the server) and fetch them one at a time
+async fn fetch_imap(
+    server: &ImapServer,
+    spec: &MailboxSpec,
+    cursor: Option<MailboxCursor>,
+    rl: &RateLimiter,
+    usage: &UsageCounter,
+) -> anyhow::Result<MailboxFetch> {
+    let credential = |env: &Option<String>, what: &str| -> anyhow::Result<String> {
+        let name = env.as_deref().ok_or_else(|| anyhow!("mailbox has no {}_env configured", what))?;
+        std::env::var(name).map_err(|_| anyhow!("{} for {} is not set", name, server.host))
+    };
+    let username = credential(&spec.username_env, "username")?;
+    let password = credential(&spec.password_env, "password")?;
+
+    rl.acquire(0).await;
+    let mut session = ImapSession::connect(server).await?;
+    usage.add(1, 0);
+    session.command(&format!("LOGIN {} {}", quote(&username), quote(&password)), "LOGIN").await?;
+
+    let selected = session.command(&format!("SELECT {}", quote(&server.mailbox)), "SELECT").await?;
+    let uid_validity = selected.iter().find_map(|r| bracketed_number(&r.text, "UIDVALIDITY")).unwrap_or(0);
+    let uid_next = selected.iter().find_map(|r| bracketed_number(&r.text, "UIDNEXT"));
+    let mut cursor = match cursor {
+        Some(c) if c.uid_validity == uid_validity => c,
+        Some(_) => {
+            debug!("UIDVALIDITY of {} changed; reading it from the start", server.mailbox);
+            MailboxCursor { uid_validity, last_uid: 0 }
+        }
+        None => MailboxCursor { uid_validity, last_uid: 0 },
+    };
+
+    let mut search = format!("UID SEARCH UID {}:*", cursor.last_uid + 1);
+    if let Some(from) = from_criteria(&spec.from) {
+        search.push(' ');
+        search.push_str(&from);
+    }
+    // `n:*` always matches the newest message, even when it is older than n
+    let mut uids: Vec<u64> = session
+        .command(&search, "SEARCH")
+        .await?
+        .iter()
+        .filter_map(|r| r.text.strip_prefix("* SEARCH"))
+        .flat_map(|ids| ids.split_whitespace().filter_map(|id| id.parse().ok()).collect::<Vec<u64>>())
+        .filter(|uid| *uid > cursor.last_uid)
+        .collect();
+    uids.sort_unstable();
+    uids.dedup();
+
+    let max = spec.max_messages.max(1) as usize;
+    let truncated = uids.len() > max;
+    uids.truncate(max);
+    let mut messages = Vec::with_capacity(uids.len());
+    for uid in &uids {
+        rl.acquire(0).await;
+        let fetched = session.command(&format!("UID FETCH {} BODY.PEEK[]", uid), "FETCH").await?;
+        let Some(raw) = fetched.into_iter().find_map(|r| r.literals.into_iter().next()) else {
+            warn!("IMAP server returned no body for message {} of {}", uid, server.mailbox);
+            continue;
+        };
+        usage.add(1, raw.len() as u64);
+        messages.push(MailMessage { uid: *uid, raw });
+    }
+    if let Err(e) = session.command("LOGOUT", "LOGOUT").await {
+        debug!("IMAP LOGOUT from {} failed: {}", server.host, e);
+    }
+
+    // Past everything searched, unless messages were left for the next fetch
+    let last_taken = uids.last().copied().unwrap_or(cursor.last_uid);
+    cursor.last_uid = match (truncated, uid_next) {
+        (false, Some(next)) => last_taken.max(next.saturating_sub(1)),
+        _ => last_taken,
+    };
+    Ok(MailboxFetch { messages, cursor })
+}
+
+/// `FROM a`, or `OR FROM a OR FROM b FROM c` for several senders
+fn from_criteria(from: &[String]) -> Option<String> {
+    let (last, rest) = from.split_last()?;
+    let mut criteria: String = rest.iter().map(|f| format!("OR FROM {} ", quote(f))).collect();
+    criteria.push_str(&format!("FROM {}", quote(last)));
+    Some(criteria)
+}
+
+/// An IMAP quoted string
+fn quote(text: &str) -> String {
+    format!("\"{}\"", text.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+/// `n` from a response code like `[UIDVALIDITY n]`
+fn bracketed_number(text: &str, code: &str) -> Option<u64> {
+    let start = text.find(&format!("[{} ", code))? + code.len() + 2;
+    text[start..].split(']').next()?.trim().parse().ok()
+}
+
+trait Connection: AsyncRead + AsyncWrite + Unpin + Send {}
+impl<T: AsyncRead + AsyncWrite + Unpin + Send> Connection for T {}
+
+/// An untagged response (`* ...`): its text and the literals (`{n}` followed by n bytes) it carried
+struct Untagged {
+    text: String,
+    literals: Vec<Vec<u8>>,
+}
+
+struct ImapSession {
+    stream: BufReader<Box<dyn Connection>>,
+    next_tag: u32,
+}
+
+impl ImapSession {
+    async fn connect(server: &ImapServer) -> anyhow::Result<Self> {
+        let tcp = tokio::net::TcpStream::connect((server.host.as_str(), server.port))
+            .await
+            .with_context(|| format!("failed to connect to {}:{}", server.host, server.port))?;
+        let stream: Box<dyn Connection> = if server.tls {
+            let name = tokio_rustls::rustls::ServerName::try_from(server.host.as_str())
+                .map_err(|_| anyhow!("invalid IMAP host name {}", server.host))?;
+            Box::new(tls_connector().connect(name, tcp).await.with_context(|| format!("TLS handshake with {} failed", server.host))?)
+        } else {
+            Box::new(tcp)
+        };
+        let mut session = Self { stream: BufReader::new(stream), next_tag: 1 };
+        let (greeting, _) = session.read_response().await?;
+        if !(greeting.starts_with("* OK") || greeting.starts_with("* PREAUTH")) {
+            bail!("IMAP server {} refused the connection: {}", server.host, greeting);
+        }
+        Ok(session)
+    }
+
+    /// Send a command and collect its untagged responses, failing unless it completes with OK.
+    /// `name` stands in for the command in errors, which must never echo a LOGIN.
+    async fn command(&mut self, command: &str, name: &str) -> anyhow::Result<Vec<Untagged>> {
+        let tag = format!("a{}", self.next_tag);
+        self.next_tag += 1;
+        let stream = self.stream.get_mut();
+        stream.write_all(format!("{} {}\r\n", tag, command).as_bytes()).await?;
+        stream.flush().await?;
+
+        let mut untagged = Vec::new();
+        loop {
+            let (text, literals) = self.read_response().await?;
+            if let Some(status) = text.strip_prefix(&tag).map(str::trim_start) {
+                if status.starts_with("OK") {
+                    return Ok(untagged);
+                }
+                bail!("IMAP {} failed: {}", name, status);
+            }
+            if text.starts_with('+') {
+                bail!("IMAP {} unexpectedly asked for more input", name);
+            }
+            untagged.push(Untagged { text, literals });
+        }
+    }
+
+    /// Read one response line, pulling in the literals it announces
+    async fn read_response(&mut self) -> anyhow::Result<(String, Vec<Vec<u8>>)> {
+        /// Literals larger than this end the session rather than being buffered
+        const MAX_LITERAL: usize = 64 * 1024 * 1024;
+
+        let mut text = String::new();
+        let mut literals = Vec::new();
+        loop {
+            let mut line = Vec::new();
+            if self.stream.read_until(b'\n', &mut line).await? == 0 {
+                bail!("IMAP server closed the connection");
+            }
+            let line = String::from_utf8_lossy(&line);
+            let line = line.trim_end_matches(['\r', '\n']);
+            match literal_len(line) {
+                Some((head, len)) => {
+                    if len > MAX_LITERAL {
+                        bail!("IMAP literal of {} bytes is over the {} byte limit", len, MAX_LITERAL);
+                    }
+                    let mut literal = vec![0u8; len];
+                    self.stream.read_exact(&mut literal).await?;
+                    text.push_str(head);
+                    literals.push(literal);
+                }
+                None => {
+                    text.push_str(line);
+                    return Ok((text, literals));
+                }
+            }
+        }
+    }
+}
+
+/// A line ending in a literal announcement `{n}`: the text before it and n
+fn literal_len(line: &str) -> Option<(&str, usize)> {
+    let open = line.strip_suffix('}')?.rfind('{')?;
+    let len = line[open + 1..line.len() - 1].parse().ok()?;
+    Some((&line[..open], len))
+}
+
+fn tls_connector() -> tokio_rustls::TlsConnector {
+    use tokio_rustls::rustls::{ClientConfig, OwnedTrustAnchor, RootCertStore};
+    let mut roots = RootCertStore::empty();
+    roots.add_trust_anchors(webpki_roots::TLS_SERVER_ROOTS.iter().map(|anchor| {
+        OwnedTrustAnchor::from_subject_spki_name_constraints(anchor.subject, anchor.spki, anchor.name_constraints)
+    }));
+    let config = ClientConfig::builder().with_safe_defaults().with_root_certificates(roots).with_no_client_auth();
+    tokio_rustls::TlsConnector::from(Arc::new(config))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pipeline::ingestion::rate_limiter::Limits;
+
+    fn message(from: &str, subject: &str, body: &str) -> String {
+        format!("From: {}\nSubject: {}\n\n{}\n", from, subject, body)
+    }
+
+    #[tokio::test]
+    async fn mbox_fetches_resume_after_the_cursor() {
+        let tmp = tempfile::tempdir().unwrap();
+        let path = tmp.path().join("newsletters.mbox");
+        let mbox = [
+            message("Sunset Tavern <news@sunsettavern.com>", "This week", ">From the booker: hi"),
+            message("Someone Else <x@example.com>", "Spam", "nope"),
+            message("news@SunsetTavern.com", "Next week", "more shows"),
+        ]
+        .iter()
+        .map(|m| format!("From news@sunsettavern.com Thu May  1 10:00:00 2025\n{}\n", m))
+        .collect::<String>();
+        std::fs::write(&path, mbox).unwrap();
+        let url = Url::from_file_path(&path).unwrap().to_string();
+        let spec = MailboxSpec { from: vec!["sunsettavern.com".into()], max_messages: 1, ..Default::default() };
+        let rl = RateLimiter::new(Limits::default());
+        let usage = UsageCounter::default();
+
+        let first = fetch_new(&url, &spec, None, 1024, &rl, &usage).await.unwrap();
+        assert_eq!(first.messages.len(), 1);
+        assert_eq!(String::from_utf8_lossy(&first.messages[0].raw), message("Sunset Tavern <news@sunsettavern.com>", "This week", "From the booker: hi"));
+        assert_eq!(first.cursor, MailboxCursor { uid_validity: 0, last_uid: 1 });
+
+        // The other sender's message is passed over, not left for later
+        let second = fetch_new(&url, &spec, Some(first.cursor), 1024, &rl, &usage).await.unwrap();
+        assert_eq!(second.messages.iter().map(|m| m.uid).collect::<Vec<_>>(), [3]);
+        assert_eq!(second.cursor.last_uid, 3);
+        assert!(fetch_new(&url, &spec, Some(second.cursor), 1024, &rl, &usage).await.unwrap().messages.is_empty());
+
+        // A rotated file starts over
+        std::fs::write(&path, format!("From x\n{}", message("news@sunsettavern.com", "Fresh", "new"))).unwrap();
+        let rotated = fetch_new(&url, &spec, Some(second.cursor), 1024, &rl, &usage).await.unwrap();
+        assert_eq!(rotated.cursor.last_uid, 1);
+        assert_eq!(rotated.messages.len(), 1);
+    }
+
+    /// Plays the server side of one session, answering each command from `script` in order
+    async fn serve_imap(listener: tokio::net::TcpListener, script: Vec<(&'static str, String)>) -> Vec<String> {
+        let (socket, _) = listener.accept().await.unwrap();
+        let mut socket = BufReader::new(socket);
+        socket.get_mut().write_all(b"* OK fake IMAP ready\r\n").await.unwrap();
+        let mut commands = Vec::new();
+        for (expected, untagged) in script {
+            let mut line = String::new();
+            socket.read_line(&mut line).await.unwrap();
+            let (tag, command) = line.trim_end().split_once(' ').unwrap();
+            assert!(command.starts_with(expected), "expected {} but got {}", expected, command);
+            commands.push(command.to_string());
+            socket.get_mut().write_all(format!("{}{} OK done\r\n", untagged, tag).as_bytes()).await.unwrap();
+        }
+        commands
+    }
+
+    #[tokio::test]
+    async fn imap_fetches_only_unseen_messages_from_the_senders() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("imap://{}/Venue%20News", listener.local_addr().unwrap());
+        let body = "From: news@sunsettavern.com\r\nSubject: Shows\r\n\r\nMay 2 - The Dip\r\n";
+        let server = tokio::spawn(serve_imap(listener, vec![
+            ("LOGIN", String::new()),
+            ("SELECT", "* 9 EXISTS\r\n* OK [UIDVALIDITY 77] UIDs valid\r\n* OK [UIDNEXT 13] Predicted next UID\r\n".to_string()),
+            ("UID SEARCH", "* SEARCH 10 12\r\n".to_string()),
+            ("UID FETCH 10", format!("* 1 FETCH (UID 10 BODY[] {{{}}}\r\n{})\r\n", body.len(), body)),
+            ("UID FETCH 12", "* 3 FETCH (UID 12 BODY[] {5}\r\nhello)\r\n".to_string()),
+            ("LOGOUT", "* BYE\r\n".to_string()),
+        ]));
+        std::env::set_var("SMS_TEST_IMAP_USER", "shows@example.com");
+        std::env::set_var("SMS_TEST_IMAP_PASSWORD", "p\"w");
+        let spec = MailboxSpec {
+            username_env: Some("SMS_TEST_IMAP_USER".into()),
+            password_env: Some("SMS_TEST_IMAP_PASSWORD".into()),
+            from: vec!["sunsettavern.com".into(), "neumos.com".into()],
+            ..Default::default()
+        };
+        let usage = UsageCounter::default();
+
+        let cursor = MailboxCursor { uid_validity: 77, last_uid: 9 };
+        let fetch = fetch_new(&url, &spec, Some(cursor), 1024, &RateLimiter::new(Limits::default()), &usage).await.unwrap();
+        assert_eq!(fetch.messages.iter().map(|m| m.uid).collect::<Vec<_>>(), [10, 12]);
+        assert_eq!(fetch.messages[0].raw, body.as_bytes());
+        assert_eq!(fetch.cursor, MailboxCursor { uid_validity: 77, last_uid: 12 });
+        assert_eq!(usage.usage().requests, 3);
+
+        let commands = server.await.unwrap();
+        assert_eq!(commands[0], r#"LOGIN "shows@example.com" "p\"w""#);
+        assert_eq!(commands[1], r#"SELECT "Venue News""#);
+        assert_eq!(commands[2], r#"UID SEARCH UID 10:* OR FROM "sunsettavern.com" FROM "neumos.com""#);
+    }
+}
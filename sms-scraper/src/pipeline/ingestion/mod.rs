@@ -1,12 +1,34 @@
 // Pipeline ingestion: data fetching, gateway operations, rate limiting, and registry
 
+pub mod cadence;
+pub mod consumer_lag;
+#[cfg(feature = "scraping")]
+pub mod content_encoding;
+#[cfg(feature = "scraping")]
+pub mod delta;
+pub mod encryption;
 pub mod envelope;
+pub mod envelope_state;
 pub mod gateway;
+#[cfg(feature = "scraping")]
+pub mod gateway_all;
 pub mod idempotency;
+#[cfg(feature = "scraping")]
 pub mod ingest_common;
+pub mod ingest_log_backend;
 pub mod ingest_log_reader;
 pub mod ingest_meta;
+pub mod mailbox;
+pub mod pagination;
+pub mod quota;
 pub mod rate_limiter;
 pub mod registry;
+pub mod registry_watch;
+#[cfg(feature = "scraping")]
+pub mod schema_drift;
+pub mod site_watchdog;
+pub mod source_status;
+pub mod text_extract;
+pub mod windowing;
 
 // Re-export key types and functions for external use
@@ -0,0 +1,133 @@
+// Link-header pagination (RFC 8288): APIs that page their results point at the next page with
+// `Link: <https://api.example.com/events?page=2>; rel="next"`
+
+use reqwest::header::{HeaderMap, LINK};
+use reqwest::Url;
+use serde::{Deserialize, Serialize};
+
+/// Follow `rel="next"` links from an endpoint's responses to fetch every page
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct PaginationSpec {
+    /// Pages fetched at most, the first included; further next links are ignored
+    #[serde(default = "default_max_pages")]
+    pub max_pages: u32,
+    #[serde(default)]
+    pub mode: PaginationMode,
+}
+
+fn default_max_pages() -> u32 {
+    10
+}
+
+impl Default for PaginationSpec {
+    fn default() -> Self {
+        Self { max_pages: default_max_pages(), mode: PaginationMode::default() }
+    }
+}
+
+/// How the pages of one fetch are stored
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum PaginationMode {
+    /// One V2 envelope with a payload part per page, parsed together
+    #[default]
+    MultiPart,
+    /// One envelope per page, each deduplicated and parsed on its own
+    EnvelopePerPage,
+}
+
+/// The `rel="next"` target among a response's `Link` headers, resolved against the URL that
+/// was requested
+pub fn next_link(headers: &HeaderMap, request_url: &str) -> Option<String> {
+    let base = Url::parse(request_url).ok()?;
+    headers
+        .get_all(LINK)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(split_links)
+        .find_map(|(target, params)| {
+            let is_next = params.iter().any(|(name, value)| {
+                name.eq_ignore_ascii_case("rel") && value.split_whitespace().any(|rel| rel.eq_ignore_ascii_case("next"))
+            });
+            if !is_next {
+                return None;
+            }
+            base.join(target).ok().map(String::from)
+        })
+}
+
+/// Split a `Link` header value into (target, params). Commas inside `<...>` or quoted strings
+/// don't separate links.
+fn split_links(value: &str) -> Vec<(&str, Vec<(&str, String)>)> {
+    let mut links = Vec::new();
+    let mut rest = value.trim_start();
+    while let Some(open) = rest.find('<') {
+        let Some(close) = rest[open..].find('>').map(|i| open + i) else { break };
+        let target = rest[open + 1..close].trim();
+        rest = &rest[close + 1..];
+
+        // Params run until the next comma outside quotes
+        let mut end = rest.len();
+        let mut quoted = false;
+        for (i, c) in rest.char_indices() {
+            match c {
+                '"' => quoted = !quoted,
+                ',' if !quoted => {
+                    end = i;
+                    break;
+                }
+                _ => {}
+            }
+        }
+        let params = rest[..end]
+            .split(';')
+            .filter_map(|param| {
+                let (name, value) = param.split_once('=')?;
+                Some((name.trim(), value.trim().trim_matches('"').to_string()))
+            })
+            .collect();
+        links.push((target, params));
+        rest = rest.get(end + 1..).unwrap_or("");
+    }
+    links
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqwest::header::HeaderValue;
+
+    fn headers(values: &[&str]) -> HeaderMap {
+        let mut map = HeaderMap::new();
+        for value in values {
+            map.append(LINK, HeaderValue::from_str(value).unwrap());
+        }
+        map
+    }
+
+    #[test]
+    fn finds_next_among_links_and_resolves_relative_targets() {
+        let url = "https://api.example.com/v3/events?page=1";
+        let h = headers(&[
+            r#"<https://api.example.com/v3/events?page=1&sort=a,b>; rel="first", </v3/events?page=2>; rel="next""#,
+        ]);
+        assert_eq!(next_link(&h, url).as_deref(), Some("https://api.example.com/v3/events?page=2"));
+
+        // Several headers, several rel values, unquoted rel
+        let h = headers(&[r#"<?page=1>; rel="prev""#, r#"<https://cdn.example.com/p/3>; rel="last next""#]);
+        assert_eq!(next_link(&h, url).as_deref(), Some("https://cdn.example.com/p/3"));
+        let h = headers(&["<page2>; rel=next; title=\"a, b\""]);
+        assert_eq!(next_link(&h, url).as_deref(), Some("https://api.example.com/v3/page2"));
+
+        assert_eq!(next_link(&headers(&[r#"<https://x.example/1>; rel="prev""#]), url), None);
+        assert_eq!(next_link(&HeaderMap::new(), url), None);
+    }
+
+    #[test]
+    fn spec_defaults_to_multi_part_with_a_page_cap() {
+        let spec: PaginationSpec = serde_json::from_str("{}").unwrap();
+        assert_eq!(spec, PaginationSpec { max_pages: 10, mode: PaginationMode::MultiPart });
+        let spec: PaginationSpec = serde_json::from_str(r#"{"max_pages": 3, "mode": "envelope_per_page"}"#).unwrap();
+        assert_eq!(spec.mode, PaginationMode::EnvelopePerPage);
+    }
+}
@@ -0,0 +1,176 @@
+use chrono::{DateTime, Datelike, Utc};
+use serde::Serialize;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use crate::pipeline::ingestion::ingest_meta::{IngestMeta, SourceUsage};
+use crate::pipeline::ingestion::registry::QuotaSpec;
+
+/// Prefix of the error returned when a source's monthly quota is spent
+pub const QUOTA_SKIP_PREFIX: &str = "quota_exceeded";
+
+/// Calendar month (UTC) usage is tracked under, as "YYYY-MM"
+pub fn usage_month(now: DateTime<Utc>) -> String {
+    format!("{:04}-{:02}", now.year(), now.month())
+}
+
+/// Requests and wire bytes spent by one ingestion, including bootstrap and window requests
+#[derive(Debug, Default)]
+pub struct UsageCounter {
+    requests: AtomicU64,
+    bytes: AtomicU64,
+}
+
+impl UsageCounter {
+    pub fn add(&self, requests: u64, bytes: u64) {
+        self.requests.fetch_add(requests, Ordering::Relaxed);
+        self.bytes.fetch_add(bytes, Ordering::Relaxed);
+    }
+
+    pub fn usage(&self) -> SourceUsage {
+        SourceUsage {
+            requests: self.requests.load(Ordering::Relaxed),
+            bytes: self.bytes.load(Ordering::Relaxed),
+        }
+    }
+}
+
+/// A source's usage this month against its registry quota
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct QuotaStatus {
+    pub month: String,
+    pub requests: u64,
+    pub bytes: u64,
+    pub monthly_requests: Option<u64>,
+    pub monthly_bytes: Option<u64>,
+}
+
+impl QuotaStatus {
+    pub fn new(month: String, usage: SourceUsage, quota: Option<&QuotaSpec>) -> Self {
+        Self {
+            month,
+            requests: usage.requests,
+            bytes: usage.bytes,
+            monthly_requests: quota.and_then(|q| q.monthly_requests),
+            monthly_bytes: quota.and_then(|q| q.monthly_bytes),
+        }
+    }
+
+    pub fn remaining_requests(&self) -> Option<u64> {
+        self.monthly_requests.map(|limit| limit.saturating_sub(self.requests))
+    }
+
+    pub fn remaining_bytes(&self) -> Option<u64> {
+        self.monthly_bytes.map(|limit| limit.saturating_sub(self.bytes))
+    }
+
+    /// Why the source may not be fetched again this month, if either budget is spent
+    pub fn exceeded_reason(&self, source_id: &str) -> Option<String> {
+        if let Some(limit) = self.monthly_requests.filter(|limit| self.requests >= *limit) {
+            return Some(format!(
+                "{}: {} used {} of {} requests in {}",
+                QUOTA_SKIP_PREFIX, source_id, self.requests, limit, self.month
+            ));
+        }
+        if let Some(limit) = self.monthly_bytes.filter(|limit| self.bytes >= *limit) {
+            return Some(format!(
+                "{}: {} used {} of {} bytes in {}",
+                QUOTA_SKIP_PREFIX, source_id, self.bytes, limit, self.month
+            ));
+        }
+        None
+    }
+
+    fn publish(&self, source_id: &str) {
+        crate::observability::metrics::sources::quota_remaining(
+            source_id,
+            self.remaining_requests(),
+            self.remaining_bytes(),
+        );
+    }
+}
+
+pub fn quota_status(
+    meta: &IngestMeta,
+    source_id: &str,
+    quota: Option<&QuotaSpec>,
+    now: DateTime<Utc>,
+) -> anyhow::Result<QuotaStatus> {
+    let month = usage_month(now);
+    let usage = meta.get_usage(source_id, &month)?;
+    Ok(QuotaStatus::new(month, usage, quota))
+}
+
+/// Checked before fetching: the reason to skip when the month's quota is spent
+pub fn check_quota(
+    meta: &IngestMeta,
+    source_id: &str,
+    quota: Option<&QuotaSpec>,
+    now: DateTime<Utc>,
+) -> anyhow::Result<Option<String>> {
+    if quota.is_none() {
+        return Ok(None);
+    }
+    let status = quota_status(meta, source_id, quota, now)?;
+    status.publish(source_id);
+    let reason = status.exceeded_reason(source_id);
+    if reason.is_some() {
+        crate::observability::metrics::sources::quota_exceeded(source_id);
+    }
+    Ok(reason)
+}
+
+/// Add an ingestion's usage to the month's totals, whether or not the fetch succeeded.
+/// A fetch already under way when the quota runs out is allowed to finish, so usage can
+/// overshoot by one ingestion.
+pub fn record_usage(
+    meta: &IngestMeta,
+    source_id: &str,
+    quota: Option<&QuotaSpec>,
+    usage: SourceUsage,
+    now: DateTime<Utc>,
+) -> anyhow::Result<QuotaStatus> {
+    let month = usage_month(now);
+    meta.add_usage(source_id, &month, usage)?;
+    let status = quota_status(meta, source_id, quota, now)?;
+    if quota.is_some() {
+        status.publish(source_id);
+    }
+    Ok(status)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn usage_accumulates_per_month_until_the_quota_is_spent() {
+        let tmp = tempfile::tempdir().unwrap();
+        let meta = IngestMeta::open_at_root(tmp.path()).unwrap();
+        let quota = QuotaSpec { monthly_requests: Some(3), monthly_bytes: Some(1_000) };
+        let march = Utc.with_ymd_and_hms(2025, 3, 31, 23, 0, 0).unwrap();
+
+        assert_eq!(check_quota(&meta, "neumos", Some(&quota), march).unwrap(), None);
+        let status = record_usage(&meta, "neumos", Some(&quota), SourceUsage { requests: 2, bytes: 400 }, march).unwrap();
+        assert_eq!(status.remaining_requests(), Some(1));
+        assert_eq!(status.remaining_bytes(), Some(600));
+        record_usage(&meta, "neumos", Some(&quota), SourceUsage { requests: 1, bytes: 100 }, march).unwrap();
+        let reason = check_quota(&meta, "neumos", Some(&quota), march).unwrap().unwrap();
+        assert!(reason.starts_with(QUOTA_SKIP_PREFIX));
+        assert!(reason.contains("3 of 3 requests in 2025-03"));
+
+        // A new month starts with a fresh budget; sources without a quota are never refused
+        let april = Utc.with_ymd_and_hms(2025, 4, 1, 0, 0, 0).unwrap();
+        assert_eq!(check_quota(&meta, "neumos", Some(&quota), april).unwrap(), None);
+        assert_eq!(check_quota(&meta, "neumos", None, march).unwrap(), None);
+    }
+
+    #[test]
+    fn byte_budget_is_enforced_on_its_own() {
+        let quota = QuotaSpec { monthly_requests: None, monthly_bytes: Some(500) };
+        let status = QuotaStatus::new("2025-03".into(), SourceUsage { requests: 40, bytes: 512 }, Some(&quota));
+        assert_eq!(status.remaining_requests(), None);
+        assert_eq!(status.remaining_bytes(), Some(0));
+        assert!(status.exceeded_reason("kexp").unwrap().contains("512 of 500 bytes"));
+    }
+}
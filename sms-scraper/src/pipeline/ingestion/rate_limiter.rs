@@ -1,3 +1,7 @@
+use crate::pipeline::ingestion::ingest_meta::{IngestMeta, RateBucketState};
+use crate::pipeline::ingestion::registry::RateLimitsSpec;
+use rand::Rng;
+use std::path::PathBuf;
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 use tokio::sync::{Mutex, Semaphore};
@@ -7,6 +11,22 @@ pub struct Limits {
     pub requests_per_min: Option<u64>,
     pub bytes_per_min: Option<u64>,
     pub concurrency: Option<u32>,
+    /// Politeness delay: at least this long between request starts...
+    pub min_interval_ms: Option<u64>,
+    /// ...plus up to this much at random
+    pub jitter_ms: Option<u64>,
+}
+
+impl Limits {
+    pub fn from_spec(spec: &RateLimitsSpec) -> Self {
+        Self {
+            requests_per_min: spec.requests_per_min,
+            bytes_per_min: spec.bytes_per_min,
+            concurrency: spec.concurrency.map(|c| c.max(1)),
+            min_interval_ms: spec.min_interval_ms,
+            jitter_ms: spec.jitter_ms,
+        }
+    }
 }
 
 #[derive(Debug)]
@@ -21,10 +41,65 @@ struct Inner {
     rpm_tokens: Mutex<(f64, Instant)>,
     bpm_tokens: Mutex<(f64, Instant)>,
     sem: Option<Semaphore>,
+    /// Wall-clock ms before which the next request may not start
+    next_slot_ms: Mutex<i64>,
+    persisted: Option<Arc<PersistedBuckets>>,
+}
+
+/// Where a source's buckets live in IngestMeta when they outlive the process. The store is
+/// opened on first use and kept for the limiter's lifetime.
+struct PersistedBuckets {
+    data_root: PathBuf,
+    source_id: String,
+    meta: std::sync::Mutex<Option<IngestMeta>>,
+}
+
+impl std::fmt::Debug for PersistedBuckets {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PersistedBuckets")
+            .field("data_root", &self.data_root)
+            .field("source_id", &self.source_id)
+            .finish_non_exhaustive()
+    }
+}
+
+impl PersistedBuckets {
+    /// Update one bucket, opening the store if this is the first update or the last open failed.
+    /// Blocks on SQLite, so it runs off the async runtime.
+    fn update<T>(&self, name: &str, update: impl FnOnce(Option<RateBucketState>) -> (RateBucketState, T)) -> anyhow::Result<T> {
+        let mut meta = self.meta.lock().unwrap_or_else(|e| e.into_inner());
+        let meta = match &mut *meta {
+            Some(meta) => meta,
+            empty => empty.insert(IngestMeta::open_at_root(&self.data_root)?),
+        };
+        meta.update_rate_bucket(&self.source_id, name, update)
+    }
 }
 
+const RPM_BUCKET: &str = "requests_per_min";
+const BPM_BUCKET: &str = "bytes_per_min";
+/// Stores the next request slot (in `refilled_at_ms`) rather than tokens
+const SPACING_BUCKET: &str = "request_spacing";
+
 impl RateLimiter {
     pub fn new(limits: Limits) -> Self {
+        Self::build(limits, None)
+    }
+
+    /// Keep this source's token buckets in the IngestMeta DB under `data_root`, so
+    /// back-to-back short-lived runs (e.g. cron) share one per-minute budget
+    pub fn persistent<P: Into<PathBuf>>(limits: Limits, data_root: P, source_id: &str) -> Self {
+        Self::build(
+            limits,
+            Some(Arc::new(PersistedBuckets {
+                data_root: data_root.into(),
+                source_id: source_id.to_string(),
+                meta: std::sync::Mutex::new(None),
+            })),
+        )
+    }
+
+    fn build(limits: Limits, persisted: Option<Arc<PersistedBuckets>>) -> Self {
         let now = Instant::now();
         let rpm_capacity = limits.requests_per_min.unwrap_or(0) as f64;
         let bpm_capacity = limits.bytes_per_min.unwrap_or(0) as f64;
@@ -35,6 +110,8 @@ impl RateLimiter {
                 rpm_tokens: Mutex::new((rpm_capacity, now)),
                 bpm_tokens: Mutex::new((bpm_capacity, now)),
                 sem,
+                next_slot_ms: Mutex::new(0),
+                persisted,
             }),
         }
     }
@@ -51,46 +128,216 @@ impl RateLimiter {
         // Requests per minute bucket
         if let Some(rpm) = self.inner.limits.requests_per_min {
             if rpm > 0 {
-                self.consume_tokens(&self.inner.rpm_tokens, rpm as f64, 60.0, 1.0)
+                self.consume_tokens(&self.inner.rpm_tokens, RPM_BUCKET, rpm as f64, 60.0, 1.0)
                     .await;
             }
         }
         // Bytes per minute bucket
         if let Some(bpm) = self.inner.limits.bytes_per_min {
             if bpm > 0 {
-                self.consume_tokens(&self.inner.bpm_tokens, bpm as f64, 60.0, bytes as f64)
+                self.consume_tokens(&self.inner.bpm_tokens, BPM_BUCKET, bpm as f64, 60.0, bytes as f64)
                     .await;
             }
         }
+        // Politeness delay last, so it spaces when requests are actually sent
+        self.space_requests().await;
         // _permit dropped here when function returns, releasing concurrency
     }
 
+    /// Wait for this request's slot: `min_interval_ms` plus up to `jitter_ms` after the
+    /// previous request's. Slots are reserved up front, so concurrent callers queue one gap
+    /// apart instead of all waking at once.
+    async fn space_requests(&self) {
+        let interval_ms = self.inner.limits.min_interval_ms.unwrap_or(0);
+        let jitter_ms = self.inner.limits.jitter_ms.unwrap_or(0);
+        if interval_ms == 0 && jitter_ms == 0 {
+            return;
+        }
+        let gap_ms = interval_ms + rand::thread_rng().gen_range(0..=jitter_ms);
+        let now_ms = chrono::Utc::now().timestamp_millis();
+        let persisted = self
+            .update_persisted(SPACING_BUCKET, move |stored| {
+                let mut next = stored.map(|s| s.refilled_at_ms).unwrap_or(0);
+                let wait = reserve_slot(&mut next, now_ms, gap_ms);
+                (RateBucketState { tokens: 0.0, refilled_at_ms: next }, wait)
+            })
+            .await;
+        let wait_ms = match persisted {
+            Some(wait_ms) => wait_ms,
+            None => reserve_slot(&mut *self.inner.next_slot_ms.lock().await, now_ms, gap_ms),
+        };
+        if wait_ms > 0 {
+            tokio::time::sleep(Duration::from_millis(wait_ms)).await;
+        }
+    }
+
     async fn consume_tokens(
         &self,
         bucket: &Mutex<(f64, Instant)>,
+        name: &'static str,
         capacity: f64,
         period_secs: f64,
         cost: f64,
     ) {
         // Basic token bucket: refill continuously, wait until enough tokens accumulate
         loop {
-            let mut guard = bucket.lock().await;
-            let (ref mut tokens, ref mut last) = *guard;
-            let now = Instant::now();
-            let elapsed = now.duration_since(*last).as_secs_f64();
-            let refill_rate = capacity / period_secs; // tokens per second
-            *tokens = (*tokens + elapsed * refill_rate).min(capacity);
-            *last = now;
-            if *tokens >= cost {
-                *tokens -= cost;
-                break;
-            } else {
-                // compute needed time to get enough tokens
-                let need = cost - *tokens;
-                let secs = need / refill_rate;
-                drop(guard);
-                tokio::time::sleep(Duration::from_secs_f64(secs.max(0.001))).await;
+            let wait = match self.take_persisted(name, capacity, period_secs, cost).await {
+                Some(wait) => wait,
+                None => {
+                    let mut guard = bucket.lock().await;
+                    let (ref mut tokens, ref mut last) = *guard;
+                    let now = Instant::now();
+                    let elapsed = now.duration_since(*last).as_secs_f64();
+                    *last = now;
+                    take_tokens(tokens, elapsed, capacity, period_secs, cost)
+                }
+            };
+            match wait {
+                None => break,
+                Some(secs) => tokio::time::sleep(Duration::from_secs_f64(secs.max(0.001))).await,
             }
         }
     }
+
+    /// Take tokens from the persisted bucket. `None` when the limiter isn't persistent or the
+    /// DB is unavailable, in which case the in-memory bucket is used instead.
+    async fn take_persisted(&self, name: &'static str, capacity: f64, period_secs: f64, cost: f64) -> Option<Option<f64>> {
+        let now_ms = chrono::Utc::now().timestamp_millis();
+        self.update_persisted(name, move |stored| {
+            let (mut tokens, last_ms) = stored
+                .map(|s| (s.tokens, s.refilled_at_ms))
+                .unwrap_or((capacity, now_ms));
+            // Clock steps backwards count as no time passed
+            let elapsed = (now_ms - last_ms).max(0) as f64 / 1000.0;
+            let wait = take_tokens(&mut tokens, elapsed, capacity, period_secs, cost);
+            (RateBucketState { tokens, refilled_at_ms: now_ms }, wait)
+        })
+        .await
+    }
+
+    /// Update one of the source's persisted buckets on the blocking pool. `None` when the
+    /// limiter isn't persistent or the DB is unavailable.
+    async fn update_persisted<T: Send + 'static>(
+        &self,
+        name: &'static str,
+        update: impl FnOnce(Option<RateBucketState>) -> (RateBucketState, T) + Send + 'static,
+    ) -> Option<T> {
+        let persisted = self.inner.persisted.clone()?;
+        let store = persisted.clone();
+        let result = tokio::task::spawn_blocking(move || store.update(name, update))
+            .await
+            .unwrap_or_else(|e| Err(anyhow::anyhow!("bucket update panicked: {}", e)));
+        match result {
+            Ok(value) => Some(value),
+            Err(e) => {
+                tracing::warn!(
+                    "rate limiter: persisted bucket unavailable for source_id={}, using in-memory: {}",
+                    persisted.source_id,
+                    e
+                );
+                None
+            }
+        }
+    }
+}
+
+/// Reserve the next request slot: now, or the slot already reserved if that's later. Moves
+/// `next_slot_ms` a gap past it and returns how many ms to wait for it.
+fn reserve_slot(next_slot_ms: &mut i64, now_ms: i64, gap_ms: u64) -> u64 {
+    let slot = (*next_slot_ms).max(now_ms);
+    *next_slot_ms = slot + gap_ms as i64;
+    (slot - now_ms) as u64
+}
+
+/// Refill `tokens` for `elapsed_secs` and take `cost` if available. Returns `None` when the
+/// tokens were taken, otherwise how long to wait before there will be enough.
+fn take_tokens(tokens: &mut f64, elapsed_secs: f64, capacity: f64, period_secs: f64, cost: f64) -> Option<f64> {
+    let refill_rate = capacity / period_secs; // tokens per second
+    *tokens = (*tokens + elapsed_secs * refill_rate).min(capacity);
+    // A single request larger than the whole bucket waits for a full bucket, not forever
+    let cost = cost.min(capacity);
+    if *tokens >= cost {
+        *tokens -= cost;
+        None
+    } else {
+        Some((cost - *tokens) / refill_rate)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn persisted_budget_carries_over_to_the_next_limiter() {
+        let tmp = tempfile::tempdir().unwrap();
+        let limits = Limits { requests_per_min: Some(2), ..Default::default() };
+
+        let first_run = RateLimiter::persistent(limits.clone(), tmp.path(), "neumos");
+        first_run.acquire(0).await;
+        first_run.acquire(0).await;
+        drop(first_run);
+
+        // A fresh in-memory limiter would start full; the persisted one is empty for ~30s
+        let second_run = RateLimiter::persistent(limits.clone(), tmp.path(), "neumos");
+        let blocked = tokio::time::timeout(Duration::from_millis(200), second_run.acquire(0)).await;
+        assert!(blocked.is_err());
+
+        // Other sources keep their own budget
+        let other = RateLimiter::persistent(limits, tmp.path(), "kexp");
+        tokio::time::timeout(Duration::from_millis(200), other.acquire(0)).await.unwrap();
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn concurrent_callers_share_one_open_store() {
+        let tmp = tempfile::tempdir().unwrap();
+        let limits = Limits { requests_per_min: Some(2), ..Default::default() };
+        let rl = Arc::new(RateLimiter::persistent(limits, tmp.path(), "neumos"));
+
+        let callers: Vec<_> = (0..3)
+            .map(|_| {
+                let rl = rl.clone();
+                tokio::spawn(async move { tokio::time::timeout(Duration::from_millis(300), rl.acquire(0)).await.is_ok() })
+            })
+            .collect();
+        let mut admitted = 0;
+        for caller in callers {
+            admitted += caller.await.unwrap() as usize;
+        }
+        assert_eq!(admitted, 2);
+        let persisted = rl.inner.persisted.as_ref().unwrap();
+        assert!(persisted.meta.lock().unwrap().is_some(), "the store stays open between acquires");
+    }
+
+    #[tokio::test]
+    async fn requests_are_spaced_by_the_interval_plus_jitter() {
+        let limits = Limits { min_interval_ms: Some(40), jitter_ms: Some(20), ..Default::default() };
+        let rl = RateLimiter::new(limits.clone());
+        let start = Instant::now();
+        for _ in 0..4 {
+            rl.acquire(0).await;
+        }
+        // The first request goes straight out; each later one waits 40-60ms
+        let elapsed = start.elapsed();
+        assert!(elapsed >= Duration::from_millis(120), "{:?}", elapsed);
+        assert!(elapsed < Duration::from_millis(1000), "{:?}", elapsed);
+
+        // The next run picks up where the last left off
+        let tmp = tempfile::tempdir().unwrap();
+        let first_run = RateLimiter::persistent(Limits { min_interval_ms: Some(5_000), ..Default::default() }, tmp.path(), "neumos");
+        first_run.acquire(0).await;
+        let second_run = RateLimiter::persistent(Limits { min_interval_ms: Some(5_000), ..Default::default() }, tmp.path(), "neumos");
+        assert!(tokio::time::timeout(Duration::from_millis(200), second_run.acquire(0)).await.is_err());
+    }
+
+    #[test]
+    fn slots_queue_one_gap_apart() {
+        let mut next = 0;
+        assert_eq!(reserve_slot(&mut next, 1_000, 50), 0);
+        assert_eq!(reserve_slot(&mut next, 1_010, 50), 40);
+        assert_eq!(reserve_slot(&mut next, 1_010, 50), 90);
+        // A slot long past doesn't bank credit
+        assert_eq!(reserve_slot(&mut next, 5_000, 50), 0);
+        assert_eq!(next, 5_050);
+    }
 }
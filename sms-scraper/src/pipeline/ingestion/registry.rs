@@ -1,22 +1,72 @@
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::fs;
 use std::path::Path;
+use crate::pipeline::ingestion::mailbox::MailboxSpec;
+use crate::pipeline::ingestion::pagination::PaginationSpec;
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct EndpointSpec {
+    /// Distinguishes this endpoint's envelopes and records when a source has several (e.g.
+    /// one calendar page per stage); defaults to `endpoint_<index>` on multi-endpoint sources
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
     pub url: String,
     pub method: String,
+    /// Proxy and TLS settings; the default is a direct connection with full certificate checks
+    #[serde(default)]
+    pub transport: TransportSpec,
+    /// Extra request headers (e.g. `Accept`) sent with this endpoint's requests, bootstrap
+    /// pages included; a `User-Agent` here replaces the global one
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub headers: BTreeMap<String, String>,
+    /// Follow `Link: rel="next"` headers to fetch later pages; not combinable with windowing
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pagination: Option<PaginationSpec>,
+    /// Read new messages from the IMAP mailbox or mbox file `url` names (`imaps://`, `imap://`,
+    /// `file://`), one envelope per message; `method`, `transport` and `headers` don't apply
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mailbox: Option<MailboxSpec>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq)]
+pub struct TransportSpec {
+    /// HTTP(S) proxy to send this endpoint's requests through, e.g. `http://proxy.internal:3128`
+    #[serde(default)]
+    pub proxy_url: Option<String>,
+    /// PEM bundle of extra CA certificates trusted alongside the built-in roots
+    #[serde(default)]
+    pub ca_bundle_path: Option<String>,
+    /// Skip certificate verification entirely; prefer `ca_bundle_path` whenever the chain can be supplied
+    #[serde(default)]
+    pub danger_accept_invalid_certs: bool,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct ContentSpec {
     pub allowed_mime_types: Vec<String>,
     pub max_payload_size_bytes: u64,
+    /// Store a sanitized, size-bounded text rendition of HTML payloads next to CAS
+    #[serde(default)]
+    pub text_extract: Option<crate::pipeline::ingestion::text_extract::TextExtractSpec>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct PolicySpec {
     pub license_id: String,
+    /// Credit line the source requires wherever its data is shown
+    #[serde(default)]
+    pub attribution: Option<String>,
+}
+
+impl PolicySpec {
+    pub fn to_attribution(&self, source_id: &str) -> sms_core::domain::Attribution {
+        sms_core::domain::Attribution {
+            source_id: source_id.to_string(),
+            license_id: self.license_id.clone(),
+            text: self.attribution.clone(),
+        }
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, Default)]
@@ -24,6 +74,56 @@ pub struct RateLimitsSpec {
     pub requests_per_min: Option<u64>,
     pub bytes_per_min: Option<u64>,
     pub concurrency: Option<u32>,
+    /// Least time between the starts of two requests (pages, windows, bootstrap URLs), however
+    /// much of the per-minute budget is left
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_interval_ms: Option<u64>,
+    /// Up to this much random extra delay on top of `min_interval_ms`, so requests don't land
+    /// on a fixed beat
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub jitter_ms: Option<u64>,
+}
+
+/// Multi-window fetching for calendar APIs (e.g. Wix) that only return one month per request
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct WindowingSpec {
+    /// Months to fetch after the current one
+    #[serde(default = "default_lookahead_months")]
+    pub lookahead_months: u32,
+    #[serde(default = "default_from_param")]
+    pub from_param: String,
+    #[serde(default = "default_to_param")]
+    pub to_param: String,
+    /// chrono format for the window bounds
+    #[serde(default = "default_date_format")]
+    pub date_format: String,
+}
+
+fn default_lookahead_months() -> u32 {
+    3
+}
+
+fn default_from_param() -> String {
+    "from".to_string()
+}
+
+fn default_to_param() -> String {
+    "to".to_string()
+}
+
+fn default_date_format() -> String {
+    "%Y-%m-%d".to_string()
+}
+
+impl Default for WindowingSpec {
+    fn default() -> Self {
+        Self {
+            lookahead_months: default_lookahead_months(),
+            from_param: default_from_param(),
+            to_param: default_to_param(),
+            date_format: default_date_format(),
+        }
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -35,8 +135,166 @@ pub struct SourceSpecV1 {
     pub policy: PolicySpec,
     #[serde(default)]
     pub parse_plan_ref: Option<String>,
+    /// Versioned parser plan; takes precedence over `parse_plan_ref` when present
+    #[serde(default)]
+    pub parser_plan: Option<ParserPlanSpec>,
     #[serde(default)]
     pub rate_limits: RateLimitsSpec,
+    #[serde(default)]
+    pub windowing: Option<WindowingSpec>,
+    #[serde(default)]
+    pub bootstrap: Option<BootstrapSpec>,
+    /// When the source may be fetched; without one it's at most every 12 hours
+    #[serde(default)]
+    pub cadence: Option<CadenceSpec>,
+    /// Monthly caps on how much is pulled from the source; unlimited when absent
+    #[serde(default)]
+    pub quota: Option<QuotaSpec>,
+    /// Time zone and locale the source's listings write dates in; UTC and English when absent
+    #[serde(default)]
+    pub dates: Option<DatesSpec>,
+}
+
+/// How a source's pages write dates, for parsers reading days listed without a year
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq)]
+pub struct DatesSpec {
+    /// IANA time zone whose "today" decides which year a listed day falls in
+    #[serde(default)]
+    pub timezone: Option<String>,
+    /// BCP 47 locale the month names are written in, e.g. "en-US" or "es-MX"
+    #[serde(default)]
+    pub locale: Option<String>,
+}
+
+impl DatesSpec {
+    /// Parser date hints, UTC and English where the spec leaves them out
+    #[cfg(feature = "scraping")]
+    pub fn hints(&self) -> Result<sms_parsers::DateHints, String> {
+        let mut hints = sms_parsers::DateHints::default();
+        if let Some(timezone) = &self.timezone {
+            hints.timezone = timezone.parse().map_err(|_| format!("unknown dates timezone '{}'", timezone))?;
+        }
+        if let Some(locale) = &self.locale {
+            hints.locale = locale.clone();
+        }
+        Ok(hints)
+    }
+}
+
+/// Per calendar month (UTC) budget across every request made for the source
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq)]
+pub struct QuotaSpec {
+    #[serde(default)]
+    pub monthly_requests: Option<u64>,
+    /// Counted as received on the wire, before decompression
+    #[serde(default)]
+    pub monthly_bytes: Option<u64>,
+}
+
+/// A bare cron expression (`"0 6,18 * * *"`, UTC) or a policy with a timezone and blackouts
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(untagged)]
+pub enum CadenceSpec {
+    Cron(String),
+    Policy(CadencePolicySpec),
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct CadencePolicySpec {
+    /// Five-field cron expression (minute hour day-of-month month day-of-week)
+    pub cron: String,
+    /// IANA timezone the expression and blackouts are read in; UTC when absent
+    #[serde(default)]
+    pub timezone: Option<String>,
+    #[serde(default)]
+    pub blackouts: Vec<BlackoutSpec>,
+}
+
+/// A recurring local-time window in which the source must not be fetched
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct BlackoutSpec {
+    /// "Mon".."Sun"; every day when empty
+    #[serde(default)]
+    pub days: Vec<String>,
+    /// "HH:MM"; a window ending before it starts runs past midnight
+    pub start: String,
+    pub end: String,
+}
+
+/// Warm-up pages for sites that refuse the calendar until a session cookie is set
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq)]
+pub struct BootstrapSpec {
+    /// Fetched in order before the main endpoint; cookies they set are sent with it
+    #[serde(default)]
+    pub urls: Vec<String>,
+}
+
+/// A parser plan family and the version of it this source is parsed with
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct ParserPlanSpec {
+    pub id: String,
+    pub version: u32,
+}
+
+impl ParserPlanSpec {
+    /// Split a legacy `parse_plan:<id>_v<N>` reference into id and version
+    pub fn from_plan_ref(plan_ref: &str) -> Option<Self> {
+        let name = plan_ref.strip_prefix("parse_plan:")?;
+        let (id, version) = name.rsplit_once("_v")?;
+        Some(Self { id: id.to_string(), version: version.parse().ok()? })
+    }
+
+    /// The same plan family at another version
+    pub fn at_version(&self, version: u32) -> Self {
+        Self { id: self.id.clone(), version }
+    }
+
+    /// Plan reference understood by the parser factory
+    pub fn plan_ref(&self) -> String {
+        format!("parse_plan:{}_v{}", self.id, self.version)
+    }
+}
+
+impl SourceSpecV1 {
+    /// Date-reading hints for the source's parsers
+    #[cfg(feature = "scraping")]
+    pub fn date_hints(&self) -> Result<sms_parsers::DateHints, String> {
+        self.dates.as_ref().map_or_else(|| Ok(sms_parsers::DateHints::default()), DatesSpec::hints)
+    }
+
+    /// The declared parser plan, falling back to one derived from `parse_plan_ref`
+    pub fn parser_plan(&self) -> Option<ParserPlanSpec> {
+        self.parser_plan
+            .clone()
+            .or_else(|| self.parse_plan_ref.as_deref().and_then(ParserPlanSpec::from_plan_ref))
+    }
+
+    /// Plan reference to parse this source's payloads with
+    pub fn resolved_parse_plan(&self) -> Option<String> {
+        self.parser_plan
+            .as_ref()
+            .map(ParserPlanSpec::plan_ref)
+            .or_else(|| self.parse_plan_ref.clone())
+    }
+
+    /// Id of the endpoint at `index`: its declared id, else `endpoint_<index>` when the source
+    /// has several endpoints. Single-endpoint sources without an id have none, so their
+    /// envelopes and records look as they always have.
+    pub fn endpoint_id(&self, index: usize) -> Option<String> {
+        let endpoint = self.endpoints.get(index)?;
+        endpoint
+            .id
+            .clone()
+            .or_else(|| (self.endpoints.len() > 1).then(|| format!("endpoint_{}", index)))
+    }
+
+    /// The first endpoint id declared (or defaulted) more than once
+    pub fn duplicate_endpoint_id(&self) -> Option<String> {
+        let mut seen = std::collections::HashSet::new();
+        (0..self.endpoints.len())
+            .filter_map(|i| self.endpoint_id(i))
+            .find(|id| !seen.insert(id.clone()))
+    }
 }
 
 pub fn load_source_spec(path: &Path) -> anyhow::Result<SourceSpecV1> {
@@ -44,3 +302,40 @@ pub fn load_source_spec(path: &Path) -> anyhow::Result<SourceSpecV1> {
     let spec: SourceSpecV1 = serde_json::from_str(&raw)?;
     Ok(spec)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parser_plan_round_trips_legacy_plan_refs() {
+        let plan = ParserPlanSpec::from_plan_ref("parse_plan:venuepilot_graphql_v1").unwrap();
+        assert_eq!(plan, ParserPlanSpec { id: "venuepilot_graphql".into(), version: 1 });
+        assert_eq!(plan.at_version(2).plan_ref(), "parse_plan:venuepilot_graphql_v2");
+        assert!(ParserPlanSpec::from_plan_ref("parse_plan:no_version").is_none());
+        assert!(ParserPlanSpec::from_plan_ref("neumos_html_v1").is_none());
+    }
+
+    #[test]
+    fn date_hints_read_the_sources_timezone_and_locale() {
+        let mut spec: SourceSpecV1 = serde_json::from_value(serde_json::json!({
+            "source_id": "sala",
+            "enabled": true,
+            "endpoints": [{ "url": "https://sala.example/agenda", "method": "GET" }],
+            "content": { "allowed_mime_types": ["text/html"], "max_payload_size_bytes": 1000 },
+            "policy": { "license_id": "terms-unknown" },
+            "dates": { "timezone": "America/Mexico_City", "locale": "es-MX" }
+        }))
+        .unwrap();
+        let hints = spec.date_hints().unwrap().with_reference("2025-12-15".parse().unwrap());
+        assert_eq!(hints.timezone, chrono_tz::America::Mexico_City);
+        assert_eq!(hints.resolve_named("ene.", 4), "2026-01-04".parse().ok());
+        assert_eq!(hints.resolve_named("dic", 1), "2025-12-01".parse().ok());
+        assert_eq!(hints.month("Jan"), None);
+
+        spec.dates = None;
+        assert_eq!(spec.date_hints().unwrap(), sms_parsers::DateHints::default());
+        spec.dates = Some(DatesSpec { timezone: Some("Mars/Olympus".into()), locale: None });
+        assert!(spec.date_hints().unwrap_err().contains("Mars/Olympus"));
+    }
+}
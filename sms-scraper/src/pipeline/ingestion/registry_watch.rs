@@ -0,0 +1,236 @@
+use crate::observability::metrics;
+use crate::pipeline::ingestion::cadence::CadencePolicy;
+use crate::pipeline::ingestion::registry::{load_source_spec, SourceSpecV1};
+use chrono::{DateTime, Utc};
+#[cfg(feature = "scraping")]
+use notify::{EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, OnceLock, RwLock};
+#[cfg(feature = "scraping")]
+use std::time::Duration;
+use tracing::{info, warn};
+
+/// Editors save in bursts (temp file, rename, chmod); changes this close together reload once
+#[cfg(feature = "scraping")]
+const DEBOUNCE: Duration = Duration::from_millis(300);
+
+/// One validated load of the registry directory
+#[derive(Debug, Clone)]
+pub struct RegistrySnapshot {
+    /// Specs sorted by source id
+    pub specs: Vec<SourceSpecV1>,
+    /// Bumped on every applied reload; the initial load is 1
+    pub generation: u64,
+    pub loaded_at: DateTime<Utc>,
+}
+
+impl RegistrySnapshot {
+    pub fn get(&self, source_id: &str) -> Option<&SourceSpecV1> {
+        self.specs.iter().find(|s| s.source_id == source_id)
+    }
+}
+
+/// Load every spec in `registry_dir`, failing on the first problem so a half-edited
+/// registry is never used: unparseable JSON, a duplicate or misnamed source_id, an
+/// invalid cadence, or an enabled source without a parser plan
+pub fn load_registry(registry_dir: &Path) -> anyhow::Result<Vec<SourceSpecV1>> {
+    let mut paths: Vec<PathBuf> = std::fs::read_dir(registry_dir)?
+        .filter_map(|e| e.ok().map(|e| e.path()))
+        .filter(|p| is_spec_file(p))
+        .collect();
+    paths.sort();
+
+    let mut seen: HashMap<String, PathBuf> = HashMap::new();
+    let mut specs = Vec::new();
+    for path in paths {
+        let spec = load_source_spec(&path)
+            .map_err(|e| anyhow::anyhow!("invalid registry entry {}: {}", path.display(), e))?;
+        if path.file_stem().and_then(|s| s.to_str()) != Some(spec.source_id.as_str()) {
+            anyhow::bail!("{}: source_id {} does not match the file name", path.display(), spec.source_id);
+        }
+        if let Some(other) = seen.insert(spec.source_id.clone(), path.clone()) {
+            anyhow::bail!("{}: source_id {} is also declared in {}", path.display(), spec.source_id, other.display());
+        }
+        CadencePolicy::from_spec(spec.cadence.as_ref())
+            .map_err(|e| anyhow::anyhow!("{}: invalid cadence: {}", path.display(), e))?;
+        if spec.enabled && spec.resolved_parse_plan().is_none() {
+            anyhow::bail!("{}: enabled but declares no parser_plan", path.display());
+        }
+        if let Some(id) = spec.duplicate_endpoint_id() {
+            anyhow::bail!("{}: endpoint id {} is used more than once", path.display(), id);
+        }
+        specs.push(spec);
+    }
+    specs.sort_by(|a, b| a.source_id.cmp(&b.source_id));
+    Ok(specs)
+}
+
+fn is_spec_file(path: &Path) -> bool {
+    path.extension().and_then(|e| e.to_str()) == Some("json")
+}
+
+/// The registry as last validated, swapped whole when the directory changes so readers
+/// never see a mix of old and new specs
+pub struct LiveRegistry {
+    registry_dir: PathBuf,
+    current: RwLock<Arc<RegistrySnapshot>>,
+}
+
+impl LiveRegistry {
+    /// Load the registry; unlike a reload, an invalid registry here is an error
+    pub fn load(registry_dir: impl Into<PathBuf>) -> anyhow::Result<Arc<Self>> {
+        let registry_dir = registry_dir.into();
+        let specs = load_registry(&registry_dir)?;
+        let snapshot = RegistrySnapshot { specs, generation: 1, loaded_at: Utc::now() };
+        Ok(Arc::new(Self { registry_dir, current: RwLock::new(Arc::new(snapshot)) }))
+    }
+
+    pub fn snapshot(&self) -> Arc<RegistrySnapshot> {
+        self.current.read().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+
+    /// Re-read the directory and swap in the result if it validates; otherwise keep serving
+    /// the current registry. Returns the registry in use afterwards.
+    pub fn reload(&self) -> anyhow::Result<Arc<RegistrySnapshot>> {
+        match load_registry(&self.registry_dir) {
+            Ok(specs) => {
+                let mut current = self.current.write().unwrap_or_else(|e| e.into_inner());
+                let snapshot = Arc::new(RegistrySnapshot {
+                    specs,
+                    generation: current.generation + 1,
+                    loaded_at: Utc::now(),
+                });
+                *current = snapshot.clone();
+                drop(current);
+                metrics::sources::registry_reloaded("applied");
+                info!(
+                    "Reloaded registry {}: {} sources (generation {})",
+                    self.registry_dir.display(),
+                    snapshot.specs.len(),
+                    snapshot.generation
+                );
+                Ok(snapshot)
+            }
+            Err(e) => {
+                metrics::sources::registry_reloaded("rejected");
+                warn!("Registry change rejected, keeping generation {}: {}", self.snapshot().generation, e);
+                Err(e)
+            }
+        }
+    }
+
+    /// Reload whenever a spec file in the directory changes, until the returned watcher is
+    /// dropped. Must be called from within a tokio runtime.
+    #[cfg(feature = "scraping")]
+    pub fn watch(self: &Arc<Self>) -> notify::Result<RegistryWatcher> {
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel::<()>();
+        let mut watcher = notify::recommended_watcher(move |event: notify::Result<notify::Event>| {
+            let Ok(event) = event else { return };
+            if matches!(event.kind, EventKind::Access(_)) || !event.paths.iter().any(|p| is_spec_file(p)) {
+                return;
+            }
+            let _ = tx.send(());
+        })?;
+        watcher.watch(&self.registry_dir, RecursiveMode::NonRecursive)?;
+
+        let registry = Arc::clone(self);
+        let task = tokio::spawn(async move {
+            while rx.recv().await.is_some() {
+                tokio::time::sleep(DEBOUNCE).await;
+                while rx.try_recv().is_ok() {}
+                let _ = registry.reload();
+            }
+        });
+        info!("Watching {} for registry changes", self.registry_dir.display());
+        Ok(RegistryWatcher { _watcher: watcher, task })
+    }
+}
+
+static SHARED: OnceLock<Arc<LiveRegistry>> = OnceLock::new();
+
+/// The process's registry of `assets::SOURCE_REGISTRY_DIR`, loaded and validated on first use.
+/// Ingestion looks sources up here, so a `watch` on it reaches every caller.
+pub fn shared() -> anyhow::Result<Arc<LiveRegistry>> {
+    if let Some(registry) = SHARED.get() {
+        return Ok(registry.clone());
+    }
+    let loaded = LiveRegistry::load(crate::pipeline::assets::SOURCE_REGISTRY_DIR)?;
+    Ok(SHARED.get_or_init(|| loaded).clone())
+}
+
+/// Keeps a registry directory watched; dropping it stops reloads
+#[cfg(feature = "scraping")]
+pub struct RegistryWatcher {
+    _watcher: RecommendedWatcher,
+    task: tokio::task::JoinHandle<()>,
+}
+
+#[cfg(feature = "scraping")]
+impl Drop for RegistryWatcher {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn spec(source_id: &str, cadence: Option<&str>) -> String {
+        let mut spec = serde_json::json!({
+            "source_id": source_id,
+            "enabled": true,
+            "endpoints": [{"url": format!("https://{}.example.com/events", source_id), "method": "GET"}],
+            "content": {"allowed_mime_types": ["text/html"], "max_payload_size_bytes": 1000},
+            "policy": {"license_id": "test"},
+            "parser_plan": {"id": "generic_html", "version": 1}
+        });
+        if let Some(cron) = cadence {
+            spec["cadence"] = serde_json::json!(cron);
+        }
+        spec.to_string()
+    }
+
+    #[test]
+    fn invalid_edits_keep_the_previous_registry() {
+        let tmp = tempfile::tempdir().unwrap();
+        std::fs::write(tmp.path().join("neumos.json"), spec("neumos", None)).unwrap();
+        let live = LiveRegistry::load(tmp.path()).unwrap();
+        assert_eq!(live.snapshot().generation, 1);
+
+        std::fs::write(tmp.path().join("barboza.json"), spec("barboza", Some("0 9 * * *"))).unwrap();
+        let reloaded = live.reload().unwrap();
+        assert_eq!(reloaded.generation, 2);
+        assert!(reloaded.get("barboza").is_some());
+
+        std::fs::write(tmp.path().join("barboza.json"), spec("barboza", Some("not a cron"))).unwrap();
+        assert!(live.reload().is_err());
+        std::fs::write(tmp.path().join("sunset.json"), spec("neumos", None)).unwrap();
+        assert!(live.reload().is_err());
+
+        let current = live.snapshot();
+        assert_eq!(current.generation, 2);
+        assert_eq!(current.specs.iter().map(|s| s.source_id.as_str()).collect::<Vec<_>>(), ["barboza", "neumos"]);
+    }
+
+    #[test]
+    fn multi_endpoint_sources_get_distinct_endpoint_ids() {
+        let tmp = tempfile::tempdir().unwrap();
+        let mut two_stages: serde_json::Value = serde_json::from_str(&spec("tractor", None)).unwrap();
+        two_stages["endpoints"] = serde_json::json!([
+            {"id": "main_stage", "url": "https://tractor.example.com/main", "method": "GET"},
+            {"url": "https://tractor.example.com/lounge", "method": "GET"}
+        ]);
+        std::fs::write(tmp.path().join("tractor.json"), two_stages.to_string()).unwrap();
+        std::fs::write(tmp.path().join("neumos.json"), spec("neumos", None)).unwrap();
+        let specs = load_registry(tmp.path()).unwrap();
+        assert_eq!(specs[0].endpoint_id(0), None);
+        assert_eq!(specs[1].endpoint_id(0).as_deref(), Some("main_stage"));
+        assert_eq!(specs[1].endpoint_id(1).as_deref(), Some("endpoint_1"));
+
+        two_stages["endpoints"][1]["id"] = serde_json::json!("main_stage");
+        std::fs::write(tmp.path().join("tractor.json"), two_stages.to_string()).unwrap();
+        assert!(load_registry(tmp.path()).is_err());
+    }
+}
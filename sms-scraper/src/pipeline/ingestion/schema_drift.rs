@@ -0,0 +1,223 @@
+use crate::pipeline::ingestion::ingest_meta::MetaStore;
+use scraper::Html;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::PathBuf;
+use tracing::warn;
+
+/// Object nesting below this depth is left out of JSON fingerprints
+const MAX_JSON_DEPTH: usize = 8;
+
+/// Added and removed features listed in a drift note
+const NOTE_FEATURES: usize = 5;
+
+/// Structural outline of a source's payloads: JSON key paths (`$.events[].title`, with array
+/// elements collapsed to `[]`) and HTML `tag.class` selectors, each with how often it occurs
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PayloadFingerprint {
+    pub features: BTreeMap<String, u64>,
+}
+
+impl PayloadFingerprint {
+    /// Fingerprint a stored payload: structured JSON as-is, and strings as the JSON they
+    /// contain or else as HTML
+    pub fn of_payload(payload: &Value) -> Self {
+        let mut fingerprint = Self::default();
+        fingerprint.add_payload(payload);
+        fingerprint
+    }
+
+    /// Fold another payload of the same run into this fingerprint
+    pub fn add_payload(&mut self, payload: &Value) {
+        match payload {
+            Value::String(text) => match serde_json::from_str::<Value>(text) {
+                Ok(json @ (Value::Object(_) | Value::Array(_))) => self.add_json("$", &json, 0),
+                _ => self.add_html(text),
+            },
+            json => self.add_json("$", json, 0),
+        }
+    }
+
+    fn add_json(&mut self, path: &str, value: &Value, depth: usize) {
+        if depth > MAX_JSON_DEPTH {
+            return;
+        }
+        match value {
+            Value::Object(map) => {
+                for (key, child) in map {
+                    let child_path = format!("{}.{}", path, key);
+                    *self.features.entry(child_path.clone()).or_insert(0) += 1;
+                    self.add_json(&child_path, child, depth + 1);
+                }
+            }
+            Value::Array(items) => {
+                let item_path = format!("{}[]", path);
+                for item in items {
+                    self.add_json(&item_path, item, depth + 1);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn add_html(&mut self, html: &str) {
+        let doc = Html::parse_document(html);
+        for node in doc.tree.nodes() {
+            let Some(element) = node.value().as_element() else { continue };
+            for class in element.classes() {
+                *self.features.entry(format!("{}.{}", element.name(), class)).or_insert(0) += 1;
+            }
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.features.is_empty()
+    }
+}
+
+/// How far a source's payload structure moved from its previous fingerprint
+#[derive(Debug, Clone, PartialEq)]
+pub struct SchemaDrift {
+    /// Share of the two fingerprints' features not present in both (0.0 = same keys and
+    /// selectors, 1.0 = nothing in common)
+    pub score: f64,
+    pub added: Vec<String>,
+    pub removed: Vec<String>,
+}
+
+impl SchemaDrift {
+    /// One-line description for run reports and logs
+    pub fn note(&self) -> String {
+        let list = |features: &[String]| {
+            let mut shown = features.iter().take(NOTE_FEATURES).cloned().collect::<Vec<_>>().join(", ");
+            if features.len() > NOTE_FEATURES {
+                shown.push_str(&format!(", +{} more", features.len() - NOTE_FEATURES));
+            }
+            shown
+        };
+        let mut note = format!("payload schema drift {:.0}%", self.score * 100.0);
+        if !self.removed.is_empty() {
+            note.push_str(&format!("; removed: {}", list(&self.removed)));
+        }
+        if !self.added.is_empty() {
+            note.push_str(&format!("; added: {}", list(&self.added)));
+        }
+        note
+    }
+}
+
+/// Compare two fingerprints by which features they contain, ignoring how often each occurs
+/// (counts move with the number of listings)
+pub fn compare(previous: &PayloadFingerprint, current: &PayloadFingerprint) -> SchemaDrift {
+    let before: BTreeSet<&String> = previous.features.keys().collect();
+    let after: BTreeSet<&String> = current.features.keys().collect();
+    let union = before.union(&after).count();
+    let shared = before.intersection(&after).count();
+    SchemaDrift {
+        score: if union == 0 { 0.0 } else { 1.0 - shared as f64 / union as f64 },
+        added: after.difference(&before).map(|f| f.to_string()).collect(),
+        removed: before.difference(&after).map(|f| f.to_string()).collect(),
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct DriftConfig {
+    /// Report drift when at least this share of features changed
+    pub threshold: f64,
+}
+
+impl Default for DriftConfig {
+    fn default() -> Self {
+        Self { threshold: 0.5 }
+    }
+}
+
+/// Keeps each source's latest payload fingerprint and flags runs whose payloads changed
+/// shape, an early warning that the site changed before its parser stops finding events.
+pub struct SchemaDriftDetector {
+    meta: MetaStore,
+    config: DriftConfig,
+}
+
+impl SchemaDriftDetector {
+    pub fn new(data_root: impl Into<PathBuf>) -> Self {
+        Self::with_store(MetaStore::at_root(data_root.into()))
+    }
+
+    /// Keep fingerprints in `meta` rather than under a data root
+    pub fn with_store(meta: MetaStore) -> Self {
+        Self { meta, config: DriftConfig::default() }
+    }
+
+    pub fn with_config(mut self, config: DriftConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Record this run's fingerprint and return the drift from the previous one when it
+    /// crosses the threshold. The first fingerprint of a source and empty fingerprints are
+    /// never drift.
+    pub fn observe(&self, source_id: &str, fingerprint: &PayloadFingerprint) -> anyhow::Result<Option<SchemaDrift>> {
+        if fingerprint.is_empty() {
+            return Ok(None);
+        }
+        let stored = serde_json::to_string(fingerprint)?;
+        let previous = self.meta.with(|meta| {
+            let previous = meta.get_payload_fingerprint(source_id)?;
+            meta.put_payload_fingerprint(source_id, &stored, chrono::Utc::now().timestamp())?;
+            Ok(previous)
+        })?;
+        let Some(previous) = previous.and_then(|(json, _)| serde_json::from_str::<PayloadFingerprint>(&json).ok()) else {
+            return Ok(None);
+        };
+
+        let drift = compare(&previous, fingerprint);
+        crate::observability::metrics::sources::schema_drift_score(source_id, drift.score);
+        if drift.score < self.config.threshold {
+            return Ok(None);
+        }
+        crate::observability::metrics::sources::schema_drift_detected(source_id);
+        warn!("schema drift suspected for {}: {}", source_id, drift.note());
+        Ok(Some(drift))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn fingerprints_json_paths_and_html_selectors_and_flags_drift() {
+        let api = PayloadFingerprint::of_payload(&json!({
+            "events": [{"title": "A", "venue": {"name": "Neumos"}}, {"title": "B", "venue": {"name": "Barboza"}}]
+        }));
+        assert_eq!(api.features.get("$.events[].title"), Some(&2));
+        assert_eq!(api.features.get("$.events[].venue.name"), Some(&2));
+        // JSON text stored as a string fingerprints the same as the parsed value
+        let text = PayloadFingerprint::of_payload(&json!(r#"{"events": [{"title": "A", "venue": {"name": "X"}}]}"#));
+        assert_eq!(compare(&api, &text).score, 0.0);
+
+        let page = |cards: &str| {
+            PayloadFingerprint::of_payload(&json!(format!("<html><body><ul class=\"listing\">{}</ul></body></html>", cards)))
+        };
+        let old = page(r#"<li class="event-card"><h2 class="title">A</h2></li><li class="event-card"><h2 class="title">B</h2></li>"#);
+        assert_eq!(old.features.get("li.event-card"), Some(&2));
+        let more_listings = page(r#"<li class="event-card"><h2 class="title">C</h2></li>"#);
+        let redesign = page(r#"<div class="show-tile"><h3 class="show-name">A</h3></div>"#);
+        assert_eq!(compare(&old, &more_listings).score, 0.0);
+        let drift = compare(&old, &redesign);
+        assert!(drift.score > 0.5);
+        assert_eq!(drift.removed, vec!["h2.title".to_string(), "li.event-card".to_string()]);
+
+        let detector = SchemaDriftDetector::with_store(MetaStore::in_memory().unwrap());
+        assert_eq!(detector.observe("neumos", &old).unwrap(), None);
+        assert_eq!(detector.observe("neumos", &more_listings).unwrap(), None);
+        let flagged = detector.observe("neumos", &redesign).unwrap().expect("redesign is drift");
+        assert!(flagged.note().contains("removed: h2.title, li.event-card"));
+        // The redesign is now the baseline
+        assert_eq!(detector.observe("neumos", &redesign).unwrap(), None);
+        assert_eq!(detector.observe("neumos", &PayloadFingerprint::default()).unwrap(), None);
+    }
+}
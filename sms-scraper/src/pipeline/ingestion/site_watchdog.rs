@@ -0,0 +1,168 @@
+use crate::app::ports::{Notification, NotificationPort};
+use crate::pipeline::ingestion::ingest_meta::MetaStore;
+use std::path::PathBuf;
+use tracing::warn;
+
+/// Why a source looks like it changed underneath its parser
+#[derive(Debug, Clone, PartialEq)]
+pub enum SiteChangeReason {
+    /// Parsed nothing after previously producing records
+    DroppedToZero { trailing_average: f64 },
+    /// Parsed far fewer records than the trailing average
+    SharpDrop { count: u64, trailing_average: f64 },
+}
+
+impl SiteChangeReason {
+    pub fn label(&self) -> &'static str {
+        match self {
+            SiteChangeReason::DroppedToZero { .. } => "dropped_to_zero",
+            SiteChangeReason::SharpDrop { .. } => "sharp_drop",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct WatchdogConfig {
+    /// Number of previous runs averaged for the baseline
+    pub trailing_runs: usize,
+    /// Runs required before a partial drop is judged (a drop to zero only needs one)
+    pub min_history: usize,
+    /// Alert when the count falls by more than this fraction of the baseline
+    pub drop_threshold: f64,
+}
+
+impl Default for WatchdogConfig {
+    fn default() -> Self {
+        Self { trailing_runs: 7, min_history: 3, drop_threshold: 0.8 }
+    }
+}
+
+/// Compare a run's parsed record count against previous runs (newest first)
+pub fn assess(count: u64, history: &[u64], config: &WatchdogConfig) -> Option<SiteChangeReason> {
+    let window = &history[..history.len().min(config.trailing_runs)];
+    if window.is_empty() {
+        return None;
+    }
+    let trailing_average = window.iter().sum::<u64>() as f64 / window.len() as f64;
+    if trailing_average <= 0.0 {
+        return None;
+    }
+    if count == 0 {
+        return Some(SiteChangeReason::DroppedToZero { trailing_average });
+    }
+    if window.len() >= config.min_history && (count as f64) < trailing_average * (1.0 - config.drop_threshold) {
+        return Some(SiteChangeReason::SharpDrop { count, trailing_average });
+    }
+    None
+}
+
+/// Tracks parsed record counts per source and raises an alert when they collapse,
+/// which usually means the venue redesigned their site and the parser silently broke.
+pub struct SiteChangeWatchdog {
+    meta: MetaStore,
+    notifier: Box<dyn NotificationPort>,
+    config: WatchdogConfig,
+}
+
+impl SiteChangeWatchdog {
+    pub fn new(data_root: impl Into<PathBuf>, notifier: Box<dyn NotificationPort>) -> Self {
+        Self::with_store(MetaStore::at_root(data_root.into()), notifier)
+    }
+
+    /// Keep parse counts in `meta` rather than under a data root
+    pub fn with_store(meta: MetaStore, notifier: Box<dyn NotificationPort>) -> Self {
+        Self { meta, notifier, config: WatchdogConfig::default() }
+    }
+
+    pub fn with_config(mut self, config: WatchdogConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Record this run's count and alert if it looks like a site change
+    pub async fn observe(&self, source_id: &str, parsed_count: u64) -> anyhow::Result<Option<SiteChangeReason>> {
+        let history = self.meta.with(|meta| {
+            let history = meta.recent_parse_counts(source_id, self.config.trailing_runs)?;
+            meta.record_parse_count(source_id, chrono::Utc::now().timestamp(), parsed_count)?;
+            Ok(history)
+        })?;
+
+        let Some(reason) = assess(parsed_count, &history, &self.config) else {
+            return Ok(None);
+        };
+        crate::observability::metrics::sources::site_change_detected(source_id, reason.label());
+        let message = match &reason {
+            SiteChangeReason::DroppedToZero { trailing_average } => format!(
+                "parsed 0 records (trailing average {:.1}); the site may have been redesigned",
+                trailing_average
+            ),
+            SiteChangeReason::SharpDrop { count, trailing_average } => format!(
+                "parsed {} records vs trailing average {:.1}; the site may have been redesigned",
+                count, trailing_average
+            ),
+        };
+        warn!("site change suspected for {}: {}", source_id, message);
+        let notification = Notification {
+            kind: "site_change".to_string(),
+            source_id: source_id.to_string(),
+            message,
+        };
+        if let Err(e) = self.notifier.notify(&notification).await {
+            warn!("Failed to send site change notification for {}: {}", source_id, e);
+        }
+        Ok(Some(reason))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_trait::async_trait;
+    use std::sync::{Arc, Mutex};
+
+    #[test]
+    fn assess_flags_zero_and_sharp_drops() {
+        let config = WatchdogConfig::default();
+        assert_eq!(assess(0, &[], &config), None);
+        assert_eq!(assess(0, &[0, 0], &config), None);
+        assert_eq!(
+            assess(0, &[10], &config),
+            Some(SiteChangeReason::DroppedToZero { trailing_average: 10.0 })
+        );
+        // Partial drops wait for enough history
+        assert_eq!(assess(1, &[20, 20], &config), None);
+        assert_eq!(
+            assess(3, &[20, 20, 20], &config),
+            Some(SiteChangeReason::SharpDrop { count: 3, trailing_average: 20.0 })
+        );
+        assert_eq!(assess(5, &[20, 20, 20], &config), None);
+    }
+
+    struct Captured(Arc<Mutex<Vec<Notification>>>);
+
+    #[async_trait]
+    impl NotificationPort for Captured {
+        async fn notify(&self, notification: &Notification) -> Result<(), String> {
+            self.0.lock().unwrap().push(notification.clone());
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn observe_records_history_and_notifies() {
+        let tmp = tempfile::tempdir().unwrap();
+        let sent = Arc::new(Mutex::new(Vec::new()));
+        let watchdog = SiteChangeWatchdog::new(tmp.path(), Box::new(Captured(sent.clone())));
+
+        for _ in 0..3 {
+            assert_eq!(watchdog.observe("neumos", 40).await.unwrap(), None);
+        }
+        let reason = watchdog.observe("neumos", 0).await.unwrap();
+        assert!(matches!(reason, Some(SiteChangeReason::DroppedToZero { .. })));
+
+        let sent = sent.lock().unwrap();
+        assert_eq!(sent.len(), 1);
+        assert_eq!(sent[0].source_id, "neumos");
+        assert_eq!(sent[0].kind, "site_change");
+    }
+}
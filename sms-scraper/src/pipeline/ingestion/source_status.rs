@@ -0,0 +1,138 @@
+use crate::pipeline::ingestion::ingest_log_reader::IngestLogReader;
+use crate::pipeline::ingestion::ingest_meta::IngestMeta;
+use chrono::{DateTime, TimeZone, Utc};
+use serde::Serialize;
+use std::collections::BTreeSet;
+use std::path::Path;
+
+/// Ingest log consumer name used by the parse stage
+pub const PARSE_CONSUMER: &str = "parser";
+
+/// Crawl status for a single source, aggregated from IngestMeta, ingest log offsets and run reports
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct SourceStatus {
+    pub source_id: String,
+    pub last_fetched_at: Option<DateTime<Utc>>,
+    pub last_success_at: Option<DateTime<Utc>>,
+    pub consecutive_failures: u32,
+    pub last_error: Option<String>,
+    pub envelopes_pending_parse: u64,
+    pub records_cataloged_last_run: Option<u64>,
+    pub last_run_finished_at: Option<DateTime<Utc>>,
+}
+
+fn ts_to_utc(ts: i64) -> Option<DateTime<Utc>> {
+    Utc.timestamp_opt(ts, 0).single()
+}
+
+/// Build status rows for the given sources, or for every source with recorded history when `None`.
+pub fn collect_source_statuses(
+    data_root: &Path,
+    source_ids: Option<&[String]>,
+) -> anyhow::Result<Vec<SourceStatus>> {
+    let meta = IngestMeta::open_at_root(data_root)?;
+    let pending = IngestLogReader::new(data_root).pending_by_source(PARSE_CONSUMER)?;
+
+    let ids: BTreeSet<String> = match source_ids {
+        Some(ids) => ids.iter().cloned().collect(),
+        None => meta
+            .known_source_ids()?
+            .into_iter()
+            .chain(pending.keys().cloned())
+            .collect(),
+    };
+
+    let mut statuses = Vec::with_capacity(ids.len());
+    for source_id in ids {
+        let fetch = meta.get_fetch_status(&source_id)?.unwrap_or_default();
+        let last_run = meta.latest_run_report(&source_id)?;
+        statuses.push(SourceStatus {
+            last_fetched_at: meta.get_last_fetched_at(&source_id)?.and_then(ts_to_utc),
+            last_success_at: fetch.last_success_at.and_then(ts_to_utc),
+            consecutive_failures: fetch.consecutive_failures,
+            last_error: fetch.last_error,
+            envelopes_pending_parse: pending.get(&source_id).copied().unwrap_or(0),
+            records_cataloged_last_run: last_run.as_ref().map(|r| r.records_cataloged),
+            last_run_finished_at: last_run.and_then(|r| ts_to_utc(r.finished_at)),
+            source_id,
+        });
+    }
+    Ok(statuses)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pipeline::ingestion::ingest_meta::RunReportEntry;
+    use std::io::Write;
+
+    fn write_log_line(root: &Path, source_id: &str, dedupe_of: Option<&str>) {
+        let dir = root.join("ingest_log");
+        std::fs::create_dir_all(&dir).unwrap();
+        let mut f = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(dir.join("ingest.ndjson"))
+            .unwrap();
+        let line = serde_json::json!({
+            "envelope_id": uuid::Uuid::new_v4().to_string(),
+            "dedupe_of": dedupe_of,
+            "envelope": { "source_id": source_id }
+        });
+        writeln!(f, "{}", line).unwrap();
+    }
+
+    #[test]
+    fn aggregates_fetch_pending_and_run_data() {
+        let tmp = tempfile::tempdir().unwrap();
+        let root = tmp.path();
+        let meta = IngestMeta::open_at_root(root).unwrap();
+        meta.set_last_fetched_at("neumos", 1_700_000_100).unwrap();
+        meta.record_fetch_success("neumos", 1_700_000_000).unwrap();
+        meta.record_fetch_failure("neumos", "timeout").unwrap();
+        meta.record_fetch_failure("neumos", "timeout").unwrap();
+        meta.put_run_report(&RunReportEntry {
+            run_id: "r1".into(),
+            source_id: "neumos".into(),
+            started_at: 1_700_000_000,
+            finished_at: 1_700_000_050,
+            records_cataloged: 12,
+            records_failed: 1,
+            ..Default::default()
+        })
+        .unwrap();
+        write_log_line(root, "neumos", None);
+        write_log_line(root, "neumos", None);
+        write_log_line(root, "neumos", Some("earlier"));
+        write_log_line(root, "kexp", None);
+
+        let statuses = collect_source_statuses(root, None).unwrap();
+        assert_eq!(statuses.len(), 2);
+
+        let kexp = &statuses[0];
+        assert_eq!(kexp.source_id, "kexp");
+        assert_eq!(kexp.envelopes_pending_parse, 1);
+        assert_eq!(kexp.records_cataloged_last_run, None);
+
+        let neumos = &statuses[1];
+        assert_eq!(neumos.envelopes_pending_parse, 2);
+        assert_eq!(neumos.consecutive_failures, 2);
+        assert_eq!(neumos.last_error.as_deref(), Some("timeout"));
+        assert_eq!(neumos.last_success_at, ts_to_utc(1_700_000_000));
+        assert_eq!(neumos.last_fetched_at, ts_to_utc(1_700_000_100));
+        assert_eq!(neumos.records_cataloged_last_run, Some(12));
+    }
+
+    #[test]
+    fn success_resets_failure_streak() {
+        let tmp = tempfile::tempdir().unwrap();
+        let meta = IngestMeta::open_at_root(tmp.path()).unwrap();
+        meta.record_fetch_failure("kexp", "503").unwrap();
+        meta.record_fetch_success("kexp", 42).unwrap();
+
+        let status = meta.get_fetch_status("kexp").unwrap().unwrap();
+        assert_eq!(status.consecutive_failures, 0);
+        assert_eq!(status.last_error, None);
+        assert_eq!(status.last_success_at, Some(42));
+    }
+}
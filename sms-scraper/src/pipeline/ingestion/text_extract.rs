@@ -0,0 +1,212 @@
+#[cfg(feature = "scraping")]
+use scraper::node::Node;
+#[cfg(feature = "scraping")]
+use scraper::{ElementRef, Html};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+/// Bumped whenever the output format changes, so stored extracts can be told apart
+pub const EXTRACTOR_VERSION: u32 = 1;
+pub const DEFAULT_MAX_CHARS: usize = 16_000;
+#[cfg(feature = "scraping")]
+const TRUNCATION_MARKER: &str = "\n[truncated]";
+
+#[cfg(feature = "scraping")]
+/// Elements whose contents never render as text
+const SKIPPED: &[&str] = &["script", "style", "noscript", "template", "svg", "iframe", "head", "canvas", "object"];
+#[cfg(feature = "scraping")]
+/// Elements that start a new line
+const BLOCKS: &[&str] = &[
+    "p", "div", "section", "article", "header", "footer", "main", "aside", "nav", "ul", "ol", "table", "tr",
+    "br", "hr", "h1", "h2", "h3", "h4", "h5", "h6", "li", "dt", "dd", "blockquote", "pre", "figure", "time",
+];
+
+/// Registry opt-in for storing a compact text rendition of each HTML payload
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct TextExtractSpec {
+    #[serde(default = "default_max_chars")]
+    pub max_chars: usize,
+}
+
+fn default_max_chars() -> usize {
+    DEFAULT_MAX_CHARS
+}
+
+impl Default for TextExtractSpec {
+    fn default() -> Self {
+        Self { max_chars: DEFAULT_MAX_CHARS }
+    }
+}
+
+/// Visible text of an HTML payload with light structure hints: `#` headings, `-` list items,
+/// `|`-separated table cells, link targets in angle brackets and image alt text
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExtractedText {
+    pub payload_ref: String,
+    pub extractor_version: u32,
+    pub source_bytes: usize,
+    pub chars: usize,
+    pub truncated: bool,
+    pub text: String,
+}
+
+#[cfg(feature = "scraping")]
+/// Render `html` as sanitized text of at most `max_chars` characters
+pub fn extract(payload_ref: &str, html: &str, max_chars: usize) -> ExtractedText {
+    let doc = Html::parse_document(html);
+    let mut out = String::new();
+    render(doc.root_element(), &mut out);
+    let text = tidy(&out);
+    let (text, truncated) = bound(&text, max_chars);
+    ExtractedText {
+        payload_ref: payload_ref.to_string(),
+        extractor_version: EXTRACTOR_VERSION,
+        source_bytes: html.len(),
+        chars: text.chars().count(),
+        truncated,
+        text,
+    }
+}
+
+#[cfg(feature = "scraping")]
+fn render(element: ElementRef, out: &mut String) {
+    let name = element.value().name();
+    if SKIPPED.contains(&name) {
+        return;
+    }
+    let block = BLOCKS.contains(&name);
+    if block {
+        out.push('\n');
+    }
+    match name {
+        "h1" | "h2" | "h3" | "h4" | "h5" | "h6" => {
+            let level = name[1..].parse::<usize>().unwrap_or(1);
+            out.push_str(&"#".repeat(level));
+            out.push(' ');
+        }
+        "li" => out.push_str("- "),
+        "td" | "th" => out.push_str(" | "),
+        "img" => {
+            if let Some(alt) = element.value().attr("alt").filter(|a| !a.trim().is_empty()) {
+                out.push_str(&format!(" [image: {}] ", alt.trim()));
+            }
+        }
+        _ => {}
+    }
+    for child in element.children() {
+        match child.value() {
+            Node::Text(text) => out.push_str(text),
+            Node::Element(_) => {
+                if let Some(child) = ElementRef::wrap(child) {
+                    render(child, out);
+                }
+            }
+            _ => {}
+        }
+    }
+    if name == "a" {
+        if let Some(href) = element.value().attr("href").filter(|h| h.starts_with("http") || h.starts_with('/')) {
+            out.push_str(&format!(" <{}>", href));
+        }
+    }
+    if block {
+        out.push('\n');
+    }
+}
+
+/// Collapse runs of whitespace and drop blank lines
+#[cfg(feature = "scraping")]
+fn tidy(raw: &str) -> String {
+    raw.lines()
+        .map(|line| line.split_whitespace().collect::<Vec<_>>().join(" "))
+        .filter(|line| !line.is_empty() && line != "-" && line != "|")
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Cut to `max_chars`, preferring the last line break before the limit
+#[cfg(feature = "scraping")]
+fn bound(text: &str, max_chars: usize) -> (String, bool) {
+    if text.chars().count() <= max_chars {
+        return (text.to_string(), false);
+    }
+    let budget = max_chars.saturating_sub(TRUNCATION_MARKER.len());
+    let cut = text.char_indices().nth(budget).map(|(i, _)| i).unwrap_or(text.len());
+    let head = &text[..cut];
+    let head = head.rfind('\n').filter(|&i| i > cut / 2).map_or(head, |i| &head[..i]);
+    (format!("{}{}", head, TRUNCATION_MARKER), true)
+}
+
+/// Where the extract for a CAS payload lives: beside the CAS tree, keyed by payload hash
+pub fn extract_path(data_root: &Path, payload_ref: &str) -> Option<PathBuf> {
+    let hash = payload_ref.strip_prefix("cas:sha256:")?;
+    Some(data_root.join("cas").join("text").join(format!("{}.json", hash)))
+}
+
+#[cfg(feature = "scraping")]
+/// Extract and store the text for an accepted payload; an existing extract of the same
+/// version is kept, since identical payloads share a hash
+pub fn store(data_root: &Path, payload_ref: &str, bytes: &[u8], spec: &TextExtractSpec) -> anyhow::Result<ExtractedText> {
+    let path = extract_path(data_root, payload_ref)
+        .ok_or_else(|| anyhow::anyhow!("not a CAS payload ref: {}", payload_ref))?;
+    if let Some(existing) = load_path(&path).filter(|e| e.extractor_version == EXTRACTOR_VERSION) {
+        return Ok(existing);
+    }
+    let extracted = extract(payload_ref, &String::from_utf8_lossy(bytes), spec.max_chars);
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(&path, serde_json::to_vec(&extracted)?)?;
+    Ok(extracted)
+}
+
+/// The stored extract for a payload, if one was made
+pub fn load(data_root: &Path, payload_ref: &str) -> Option<ExtractedText> {
+    load_path(&extract_path(data_root, payload_ref)?)
+}
+
+fn load_path(path: &Path) -> Option<ExtractedText> {
+    serde_json::from_slice(&std::fs::read(path).ok()?).ok()
+}
+
+#[cfg(all(test, feature = "scraping"))]
+mod tests {
+    use super::*;
+
+    const PAGE: &str = r#"<html><head><title>Neumos</title><style>.x{color:red}</style></head>
+        <body><script>var tracking = 1;</script>
+        <nav><ul><li><a href="/events">Events</a></li></ul></nav>
+        <h2>Upcoming   Shows</h2>
+        <div class="event"><h3>The Band</h3><p>Fri, May 2 &middot; Doors 7pm</p>
+          <a href="https://neumos.com/e/1">Tickets</a><img src="x.jpg" alt="The Band poster"></div>
+        <table><tr><th>Date</th><th>Price</th></tr><tr><td>5/2</td><td>$20</td></tr></table>
+        <noscript>Enable JS</noscript></body></html>"#;
+
+    #[test]
+    fn keeps_visible_text_with_structure_hints() {
+        let extracted = extract("cas:sha256:abc", PAGE, DEFAULT_MAX_CHARS);
+        let text = &extracted.text;
+        assert!(!text.contains("tracking") && !text.contains("color:red") && !text.contains("Enable JS"));
+        assert!(text.contains("- Events </events>"));
+        assert!(text.contains("## Upcoming Shows"));
+        assert!(text.contains("### The Band"));
+        assert!(text.contains("Fri, May 2 · Doors 7pm"));
+        assert!(text.contains("Tickets <https://neumos.com/e/1>"));
+        assert!(text.contains("[image: The Band poster]"));
+        assert!(text.contains("| 5/2 | $20"));
+        assert!(!extracted.truncated);
+    }
+
+    #[test]
+    fn output_is_bounded_and_stored_by_payload_hash() {
+        let extracted = extract("cas:sha256:abc", PAGE, 60);
+        assert!(extracted.truncated);
+        assert!(extracted.chars <= 60);
+        assert!(extracted.text.ends_with("[truncated]"));
+
+        let tmp = tempfile::tempdir().unwrap();
+        let stored = store(tmp.path(), "cas:sha256:abc", PAGE.as_bytes(), &TextExtractSpec::default()).unwrap();
+        assert!(tmp.path().join("cas/text/abc.json").exists());
+        assert_eq!(load(tmp.path(), "cas:sha256:abc"), Some(stored));
+    }
+}
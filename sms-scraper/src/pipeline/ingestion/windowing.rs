@@ -0,0 +1,121 @@
+use crate::pipeline::ingestion::registry::WindowingSpec;
+use chrono::{Datelike, NaiveDate};
+use serde_json::{Map, Value};
+
+/// Consecutive calendar-month windows `[from, to)` starting at the month containing `today`.
+/// `lookahead_months` counts the months after the current one, so 0 yields a single window.
+pub fn month_windows(today: NaiveDate, lookahead_months: u32) -> Vec<(NaiveDate, NaiveDate)> {
+    let mut windows = Vec::with_capacity(lookahead_months as usize + 1);
+    let mut start = NaiveDate::from_ymd_opt(today.year(), today.month(), 1).expect("first of month is valid");
+    for _ in 0..=lookahead_months {
+        let end = next_month(start);
+        windows.push((start, end));
+        start = end;
+    }
+    windows
+}
+
+fn next_month(first: NaiveDate) -> NaiveDate {
+    let (y, m) = if first.month() == 12 { (first.year() + 1, 1) } else { (first.year(), first.month() + 1) };
+    NaiveDate::from_ymd_opt(y, m, 1).expect("first of month is valid")
+}
+
+/// Append the window bounds to the endpoint URL using the registry's parameter names.
+pub fn window_url(base: &str, spec: &WindowingSpec, from: NaiveDate, to: NaiveDate) -> anyhow::Result<String> {
+    let mut url = reqwest::Url::parse(base)?;
+    url.query_pairs_mut()
+        .append_pair(&spec.from_param, &from.format(&spec.date_format).to_string())
+        .append_pair(&spec.to_param, &to.format(&spec.date_format).to_string());
+    Ok(url.into())
+}
+
+/// Merge per-window Wix calendar payloads into one document the existing parsers understand.
+///
+/// `eventsByDates` maps are unioned by day and `events` arrays concatenated; events that show up in
+/// more than one window (multi-day events straddling a month boundary) are kept once.
+pub fn merge_wix_payloads(payloads: &[Value]) -> Value {
+    let mut by_dates: Map<String, Value> = Map::new();
+    let mut events: Vec<Value> = Vec::new();
+    let mut saw_by_dates = false;
+    let mut saw_events = false;
+
+    for payload in payloads {
+        if let Some(obj) = payload.get("eventsByDates").and_then(|v| v.as_object()) {
+            saw_by_dates = true;
+            for (day, day_events) in obj {
+                let slot = by_dates.entry(day.clone()).or_insert_with(|| Value::Array(Vec::new()));
+                if let (Some(existing), Some(incoming)) = (slot.as_array_mut(), day_events.as_array()) {
+                    for ev in incoming {
+                        if !existing.contains(ev) {
+                            existing.push(ev.clone());
+                        }
+                    }
+                }
+            }
+        }
+        if let Some(arr) = payload.get("events").and_then(|v| v.as_array()) {
+            saw_events = true;
+            for ev in arr {
+                let duplicate = match ev.get("id") {
+                    Some(id) => events.iter().any(|e| e.get("id") == Some(id)),
+                    None => events.contains(ev),
+                };
+                if !duplicate {
+                    events.push(ev.clone());
+                }
+            }
+        }
+    }
+
+    let mut merged = Map::new();
+    if saw_by_dates {
+        merged.insert("eventsByDates".to_string(), Value::Object(by_dates));
+    }
+    if saw_events {
+        merged.insert("events".to_string(), Value::Array(events));
+    }
+    Value::Object(merged)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn windows_roll_over_year_end() {
+        let windows = month_windows(NaiveDate::from_ymd_opt(2024, 11, 17).unwrap(), 2);
+        let d = |y, m| NaiveDate::from_ymd_opt(y, m, 1).unwrap();
+        assert_eq!(windows, vec![(d(2024, 11), d(2024, 12)), (d(2024, 12), d(2025, 1)), (d(2025, 1), d(2025, 2))]);
+    }
+
+    #[test]
+    fn window_url_appends_bounds() {
+        let spec = WindowingSpec::default();
+        let d = |m| NaiveDate::from_ymd_opt(2024, m, 1).unwrap();
+        let url = window_url("https://example.com/_api/getEvents?compId=c1", &spec, d(3), d(4)).unwrap();
+        assert_eq!(url, "https://example.com/_api/getEvents?compId=c1&from=2024-03-01&to=2024-04-01");
+    }
+
+    #[test]
+    fn merges_windows_without_duplicates() {
+        let straddling = json!({"title": "Residency", "start_time": "20:00:00"});
+        let merged = merge_wix_payloads(&[
+            json!({"eventsByDates": {"2024-03-30": [straddling.clone()]}}),
+            json!({"eventsByDates": {
+                "2024-03-30": [straddling.clone()],
+                "2024-04-02": [{"title": "Open Mic", "start_time": "19:00:00"}]
+            }}),
+        ]);
+        let by_dates = merged["eventsByDates"].as_object().unwrap();
+        assert_eq!(by_dates.len(), 2);
+        assert_eq!(by_dates["2024-03-30"].as_array().unwrap().len(), 1);
+        assert!(merged.get("events").is_none());
+
+        let merged = merge_wix_payloads(&[
+            json!({"events": [{"id": "a"}, {"id": "b"}]}),
+            json!({"events": [{"id": "b"}, {"id": "c"}]}),
+        ]);
+        assert_eq!(merged["events"].as_array().unwrap().len(), 3);
+    }
+}
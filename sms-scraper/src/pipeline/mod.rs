@@ -1,9 +1,19 @@
 // Pipeline orchestration and processing modules
 
+pub mod assets;
+#[cfg(feature = "scraping")]
 pub mod full_pipeline_orchestrator;
+#[cfg(feature = "scraping")]
+pub mod runner;
+#[cfg(feature = "scraping")]
+pub mod streaming;
 pub mod ingestion;
+#[cfg(feature = "scraping")]
 pub mod steps;
+#[cfg(feature = "scraping")]
+pub mod tasks;
 pub mod pipeline_config;
+#[cfg(feature = "scraping")]
 pub mod orchestrator;
 pub mod utils;
 pub mod storage; // Storage traits and implementations
@@ -12,10 +22,13 @@ pub mod processing; // Legacy processing module for backward compatibility
 
 // Re-export key types for convenience
 pub use pipeline_config::{PipelineConfig, PipelineStepConfig, ErrorHandlingStrategy};
+#[cfg(feature = "scraping")]
 pub use orchestrator::PipelineOrchestrator;
+#[cfg(feature = "scraping")]
 pub use steps::{PipelineStep, StepResult};
 
 // Re-export all step types
+#[cfg(feature = "scraping")]
 pub use steps::{
     IngestionStep,
     ParseStep,
@@ -39,4 +52,9 @@ pub use utils::{
 };
 
 // Re-export full pipeline orchestrator for backward compatibility
+#[cfg(feature = "scraping")]
 pub use full_pipeline_orchestrator::FullPipelineOrchestrator;
+
+// Programmatic entry point for embedding the pipeline in other services
+#[cfg(feature = "scraping")]
+pub use runner::{PipelineRunner, ReprocessOptions, ReprocessProgress, RunOptions, RunReport};
@@ -1,9 +1,11 @@
 use anyhow::Result;
 use std::sync::Arc;
-use tracing::{info, error, warn};
+use tracing::{info, error, warn, Instrument};
+use crate::observability::RunTracker;
 use sms_core::storage::{Storage, DatabaseStorage};
 use crate::registry::source_loader::SourceRegistry;
 use super::pipeline_config::{PipelineConfig, PipelineStepConfig, ErrorHandlingStrategy};
+use super::processing::conflation::ConflatorConfig;
 use super::steps::{
     PipelineStep, StepResult,
     IngestionStep, ParseStep, NormalizeStep, QualityGateStep, 
@@ -19,13 +21,28 @@ pub struct PipelineOrchestrator {
 impl PipelineOrchestrator {
     /// Create a new pipeline orchestrator
     pub async fn new() -> Result<Self> {
-        let storage = Arc::new(DatabaseStorage::new().await?);
+        let storage: Arc<dyn Storage> = Arc::new(DatabaseStorage::new().await?);
+        #[cfg(feature = "chaos")]
+        let storage = crate::infra::fault_injection::FaultyStorage::wrap(storage);
         let source_registry = SourceRegistry::load_from_directory("registry/sources")?;
         Ok(Self { storage, source_registry })
     }
 
     /// Run a complete pipeline based on configuration
     pub async fn run_pipeline(&self, config: PipelineConfig, source_id: &str) -> Result<PipelineExecutionResult> {
+        let tracker = RunTracker::open("declarative_pipeline", Some(source_id));
+        let result = self.run_pipeline_steps(config, source_id, &tracker)
+            .instrument(tracker.span())
+            .await;
+        match &result {
+            Ok(execution) if execution.success => tracker.succeed(),
+            Ok(execution) => tracker.fail(format!("pipeline '{}' failed", execution.pipeline_name)),
+            Err(e) => tracker.fail(e),
+        };
+        result
+    }
+
+    async fn run_pipeline_steps(&self, config: PipelineConfig, source_id: &str, tracker: &RunTracker) -> Result<PipelineExecutionResult> {
         info!("🚀 Starting pipeline '{}' for source: {}", config.name, source_id);
         info!("📋 Pipeline description: {}", config.description);
         
@@ -34,6 +51,7 @@ impl PipelineOrchestrator {
         
         let mut execution_result = PipelineExecutionResult::new(config.name.clone(), source_id.to_string());
         let mut should_continue = true;
+        let conflator = config.conflator();
         
         for (step_index, step_config) in config.steps.iter().enumerate() {
             if !should_continue {
@@ -43,9 +61,9 @@ impl PipelineOrchestrator {
             
             info!("🔄 Executing step {}/{}: {}", step_index + 1, config.steps.len(), step_config.step_name());
             
-            let step = self.create_step(step_config.clone())?;
+            let step = self.create_step(step_config.clone(), &conflator)?;
             
-            match step.execute(source_id, &*self.storage).await {
+            match tracker.stage(step_config.step_name(), step.execute(source_id, &*self.storage)).await {
                 Ok(step_result) => {
                     info!("✅ Step '{}' completed: {}", step_config.step_name(), step_result.message);
                     execution_result.add_step_result(step_config.step_name().to_string(), step_result.clone());
@@ -111,12 +129,13 @@ impl PipelineOrchestrator {
     pub async fn run_step(&self, step_config: PipelineStepConfig, source_id: &str) -> Result<StepResult> {
         info!("🔄 Running single step '{}' for source: {}", step_config.step_name(), source_id);
         
-        let step = self.create_step(step_config)?;
+        let step = self.create_step(step_config, &ConflatorConfig::default())?;
         step.execute(source_id, &*self.storage).await
     }
     
-    /// Create a step instance from configuration
-    fn create_step(&self, step_config: PipelineStepConfig) -> Result<Box<dyn PipelineStep>> {
+    /// Create a step instance from configuration; `conflator` is what cataloging judges artist
+    /// slug collisions with
+    fn create_step(&self, step_config: PipelineStepConfig, conflator: &ConflatorConfig) -> Result<Box<dyn PipelineStep>> {
         let step: Box<dyn PipelineStep> = match step_config {
             PipelineStepConfig::Ingestion => {
                 Box::new(IngestionStep::new(self.source_registry.clone()))
@@ -133,11 +152,11 @@ impl PipelineOrchestrator {
             PipelineStepConfig::Enrich => {
                 Box::new(EnrichStep::new())
             }
-            PipelineStepConfig::Conflation { confidence_threshold } => {
-                Box::new(ConflationStep::new(confidence_threshold.unwrap_or(0.85)))
+            PipelineStepConfig::Conflation { config } => {
+                Box::new(ConflationStep::new(config.clone()))
             }
             PipelineStepConfig::Catalog { validate_graph } => {
-                Box::new(CatalogStep::new(validate_graph))
+                Box::new(CatalogStep::new(validate_graph).with_conflator(conflator.clone()))
             }
         };
         
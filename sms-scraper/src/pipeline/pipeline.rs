@@ -1,6 +1,7 @@
 use sms_core::domain::RawData;
 use sms_core::common::error::Result;
 use crate::pipeline::storage::Storage;
+use crate::observability::RunTracker;
 use sms_core::common::types::{EventApi, EventArgs, RawDataInfo};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
@@ -73,14 +74,22 @@ impl Pipeline {
         println!("📥 Starting minimal ingestion for {}", api_name);
         // metrics: count pipeline runs
         crate::observability::metrics::sources::registry_load_success();
+        let tracker = RunTracker::open("ingestion", Some(&api_name));
         let t_pipeline = std::time::Instant::now();
 
         // Step 1: Fetch raw events (raw JSON/HTML bytes only)
         info!("📡 Fetching raw bytes from {}...", api_name);
         println!("📡 Fetching raw bytes from {}...", api_name);
         let t_fetch = std::time::Instant::now();
-        let raw_events = api.get_event_list().await?;
+        let raw_events = match api.get_event_list().await {
+            Ok(events) => events,
+            Err(e) => {
+                tracker.fail(&e);
+                return Err(e);
+            }
+        };
         let fetch_secs = t_fetch.elapsed().as_secs_f64();
+        tracker.record_stage("fetch", fetch_secs);
         crate::observability::metrics::sources::request_duration(fetch_secs);
         info!("✅ Fetched {} raw event records", raw_events.len());
         println!("✅ Fetched {} raw event records", raw_events.len());
@@ -92,6 +101,7 @@ impl Pipeline {
         println!("💾 Storing raw bytes to database...");
         let mut stored_count = 0;
         let mut errors = Vec::new();
+        let t_store = std::time::Instant::now();
 
         for (i, raw_event) in raw_events.iter().enumerate() {
             // Store raw JSON bytes directly without any processing
@@ -106,6 +116,7 @@ impl Pipeline {
                 processed: false, // Mark as unprocessed for full pipeline
                 event_id: None,
                 created_at: chrono::Utc::now(),
+                origin: None,
             };
             
             match storage.create_raw_data(&mut raw_data).await {
@@ -124,11 +135,18 @@ impl Pipeline {
             }
         }
 
+        tracker.record_stage("store", t_store.elapsed().as_secs_f64());
         info!("✅ Stored {} raw records ({} errors)", stored_count, errors.len());
         println!("✅ Stored {} raw records ({} errors)", stored_count, errors.len());
         
         // Step 3: Persist to JSON (legacy) - store raw events
-        let output_file = Self::persist_raw_to_json(&raw_events, &api_name, output_dir)?;
+        let output_file = match tracker.stage("persist_json", async { Self::persist_raw_to_json(&raw_events, &api_name, output_dir) }).await {
+            Ok(path) => path,
+            Err(e) => {
+                tracker.fail(&e);
+                return Err(e);
+            }
+        };
         info!("💾 Saved raw events to {}", output_file);
         println!("💾 Saved raw events to {}", output_file);
 
@@ -136,8 +154,11 @@ impl Pipeline {
         let total_secs = t_pipeline.elapsed().as_secs_f64();
         crate::observability::metrics::sources::request_duration(total_secs);
 
-        // Ensure snapshot is non-empty at push time
-        crate::observability::heartbeat();
+        if errors.is_empty() {
+            tracker.succeed();
+        } else {
+            tracker.fail(format!("{} of {} raw events failed to store", errors.len(), raw_events.len()));
+        }
 
         Ok(PipelineResult {
             api_name,
@@ -2,6 +2,8 @@ use anyhow::Result;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
+use crate::pipeline::processing::conflation::ConflatorConfig;
+
 /// Configuration for a complete pipeline execution
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PipelineConfig {
@@ -24,7 +26,7 @@ pub enum PipelineStepConfig {
     },
     Enrich,
     Conflation { 
-        confidence_threshold: Option<f64> 
+        config: ConflatorConfig 
     },
     Catalog { 
         validate_graph: bool 
@@ -54,7 +56,7 @@ impl PipelineConfig {
                 PipelineStepConfig::Normalize,
                 PipelineStepConfig::QualityGate { threshold: Some(0.8) },
                 PipelineStepConfig::Enrich,
-                PipelineStepConfig::Conflation { confidence_threshold: Some(0.85) },
+                PipelineStepConfig::Conflation { config: ConflatorConfig::uniform(0.85) },
                 PipelineStepConfig::Catalog { validate_graph: true },
             ],
             error_handling: ErrorHandlingStrategy::ContinueOnError,
@@ -112,11 +114,11 @@ impl PipelineConfig {
     }
     
     /// Create a conflation-only pipeline configuration
-    pub fn conflation_only(confidence_threshold: Option<f64>) -> Self {
+    pub fn conflation_only(config: ConflatorConfig) -> Self {
         Self {
             name: "conflation_only".to_string(),
             description: "Resolve duplicate entities and create canonical IDs".to_string(),
-            steps: vec![PipelineStepConfig::Conflation { confidence_threshold }],
+            steps: vec![PipelineStepConfig::Conflation { config }],
             error_handling: ErrorHandlingStrategy::ContinueOnError,
             parallel_execution: false,
             metadata: HashMap::new(),
@@ -135,6 +137,17 @@ impl PipelineConfig {
         }
     }
     
+    /// Thresholds of the pipeline's conflation step, the defaults when it has none
+    pub fn conflator(&self) -> ConflatorConfig {
+        self.steps
+            .iter()
+            .find_map(|step| match step {
+                PipelineStepConfig::Conflation { config } => Some(config.clone()),
+                _ => None,
+            })
+            .unwrap_or_default()
+    }
+
     /// Validate the pipeline configuration
     pub fn validate(&self) -> Result<()> {
         if self.steps.is_empty() {
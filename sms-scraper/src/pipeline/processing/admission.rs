@@ -0,0 +1,163 @@
+use regex::Regex;
+use std::sync::OnceLock;
+
+use sms_core::domain::AgeRestriction;
+
+/// Record fields sources put an age policy in, checked before falling back to the description
+const AGE_FIELDS: &[&str] = &["age_restriction", "ages", "age", "age_limit", "minimum_age", "minimumAge"];
+
+/// Record fields sources put accessibility details in
+const ACCESSIBILITY_FIELDS: &[&str] = &["accessibility", "accessibility_notes", "accessibilityInfo", "ada"];
+
+/// Minors admitted with an adult: "All ages with guardian", "Under 21 w/ parent",
+/// "minors must be accompanied"
+fn guardian_pattern() -> &'static Regex {
+    static PATTERN: OnceLock<Regex> = OnceLock::new();
+    PATTERN.get_or_init(|| {
+        Regex::new(
+            r"(?i)\b(?:all[\s-]*ages?|minors?|under\s*\d{2}|kids|children)\b[^.|;]*?\b(?:with|w/|accompanied by|must be accompanied)\s*(?:an?\s+|their\s+)?(?:legal\s+)?(?:parent|guardian|adult)",
+        )
+        .unwrap()
+    })
+}
+
+fn all_ages_pattern() -> &'static Regex {
+    static PATTERN: OnceLock<Regex> = OnceLock::new();
+    PATTERN.get_or_init(|| Regex::new(r"(?i)\ball[\s-]*ages?\b|\bfamily[\s-]friendly\b").unwrap())
+}
+
+/// A minimum age: "21+", "18 and over", "ages 16 & up", "over 21". A `$` or digit in front
+/// rules out prices like "$21+".
+fn minimum_age_pattern() -> &'static Regex {
+    static PATTERN: OnceLock<Regex> = OnceLock::new();
+    PATTERN.get_or_init(|| {
+        Regex::new(
+            r"(?i)(?:^|[^$\d.])(?P<n>\d{1,2})\s*(?:\+|plus\b|(?:years?\s*(?:old\s*)?)?(?:and|&)\s*(?:over|up|older)\b)|\b(?:over|ages?:?)\s*(?P<m>\d{1,2})\b",
+        )
+        .unwrap()
+    })
+}
+
+/// Accessibility wording worth surfacing from a description
+fn accessibility_pattern() -> &'static Regex {
+    static PATTERN: OnceLock<Regex> = OnceLock::new();
+    PATTERN.get_or_init(|| {
+        Regex::new(
+            r"(?i)\b(?:wheelchair|ada\b|accessib|asl\b|sign language|step[\s-]free|elevator|ramp\b|hearing loop|captioned|sensory[\s-]friendly|seating available)",
+        )
+        .unwrap()
+    })
+}
+
+/// The age policy in listing text such as "All Ages", "21+", "18 and over w/ ID" or
+/// "All ages with guardian". All-ages wording wins over a minimum next to it, since "All ages,
+/// 21+ to drink" is an all-ages show. `None` when the text names no policy we model.
+pub fn parse_age_restriction(text: &str) -> Option<AgeRestriction> {
+    if guardian_pattern().is_match(text) {
+        return Some(AgeRestriction::AllAgesWithGuardian);
+    }
+    if all_ages_pattern().is_match(text) {
+        return Some(AgeRestriction::AllAges);
+    }
+    minimum_age_pattern()
+        .captures_iter(text)
+        .filter_map(|caps| caps.name("n").or_else(|| caps.name("m"))?.as_str().parse().ok())
+        .find_map(from_minimum_age)
+}
+
+/// 0 is all ages; 16, 18 and 21 are the minimums venues use. Other ages aren't modeled.
+pub fn from_minimum_age(age: u64) -> Option<AgeRestriction> {
+    match age {
+        0 => Some(AgeRestriction::AllAges),
+        16 => Some(AgeRestriction::SixteenPlus),
+        18 => Some(AgeRestriction::EighteenPlus),
+        21 => Some(AgeRestriction::TwentyOnePlus),
+        _ => None,
+    }
+}
+
+/// The age policy in a parsed record: the first age field that holds one (a bare number
+/// there is a minimum age), otherwise whatever the description says
+pub fn extract_age_restriction(record: &serde_json::Value) -> Option<AgeRestriction> {
+    for field in AGE_FIELDS {
+        let age = match record.get(*field) {
+            Some(serde_json::Value::Number(n)) => n.as_u64().and_then(from_minimum_age),
+            Some(serde_json::Value::String(s)) => {
+                parse_age_restriction(s).or_else(|| s.trim().parse().ok().and_then(from_minimum_age))
+            }
+            _ => None,
+        };
+        if age.is_some() {
+            return age;
+        }
+    }
+    record.get("description").and_then(|d| d.as_str()).and_then(parse_age_restriction)
+}
+
+/// Sentences of `text` that mention access needs (wheelchair access, ASL, step-free entry...),
+/// joined; `None` when there are none
+pub fn parse_accessibility_notes(text: &str) -> Option<String> {
+    let notes: Vec<&str> = text
+        .split(['.', '!', '?', '|', '\n'])
+        .map(str::trim)
+        .filter(|sentence| accessibility_pattern().is_match(sentence))
+        .collect();
+    (!notes.is_empty()).then(|| notes.join(". "))
+}
+
+/// Accessibility details in a parsed record: an accessibility field's text as given,
+/// otherwise the matching sentences of the description
+pub fn extract_accessibility_notes(record: &serde_json::Value) -> Option<String> {
+    for field in ACCESSIBILITY_FIELDS {
+        if let Some(text) = record.get(*field).and_then(|v| v.as_str()).map(str::trim).filter(|t| !t.is_empty()) {
+            return Some(text.to_string());
+        }
+    }
+    record.get("description").and_then(|d| d.as_str()).and_then(parse_accessibility_notes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use AgeRestriction::*;
+
+    #[test]
+    fn maps_listing_text_to_an_age_restriction() {
+        assert_eq!(parse_age_restriction("All Ages"), Some(AllAges));
+        assert_eq!(parse_age_restriction("ALL-AGES SHOW"), Some(AllAges));
+        assert_eq!(parse_age_restriction("21+"), Some(TwentyOnePlus));
+        assert_eq!(parse_age_restriction("Age: 21 & Over"), Some(TwentyOnePlus));
+        assert_eq!(parse_age_restriction("18 and over w/ valid ID"), Some(EighteenPlus));
+        assert_eq!(parse_age_restriction("Ages 16+"), Some(SixteenPlus));
+        assert_eq!(parse_age_restriction("All ages with parent or guardian"), Some(AllAgesWithGuardian));
+        assert_eq!(parse_age_restriction("Minors must be accompanied by a guardian"), Some(AllAgesWithGuardian));
+        assert_eq!(parse_age_restriction("All ages, 21+ to drink"), Some(AllAges));
+        assert_eq!(parse_age_restriction("Tickets $21+ fees"), None);
+        assert_eq!(parse_age_restriction("Doors 7pm, show 8pm"), None);
+    }
+
+    #[test]
+    fn extracts_from_age_fields_before_description() {
+        assert_eq!(extract_age_restriction(&json!({ "age_restriction": "21+" })), Some(TwentyOnePlus));
+        assert_eq!(extract_age_restriction(&json!({ "minimumAge": 0 })), Some(AllAges));
+        assert_eq!(extract_age_restriction(&json!({ "age_restriction": "0+" })), Some(AllAges));
+        assert_eq!(
+            extract_age_restriction(&json!({ "promoter": "x", "description": "Barboza Presents | Age: 18+" })),
+            Some(EighteenPlus)
+        );
+        assert_eq!(extract_age_restriction(&json!({ "title": "Band" })), None);
+    }
+
+    #[test]
+    fn picks_accessibility_sentences() {
+        let record = json!({ "description": "Doors at 7. ASL interpretation provided! Venue is wheelchair accessible via the Pike St entrance." });
+        assert_eq!(
+            extract_accessibility_notes(&record).as_deref(),
+            Some("ASL interpretation provided. Venue is wheelchair accessible via the Pike St entrance")
+        );
+        let record = json!({ "accessibility": " Elevator to the balcony ", "description": "Wheelchair seating" });
+        assert_eq!(extract_accessibility_notes(&record).as_deref(), Some("Elevator to the balcony"));
+        assert_eq!(extract_accessibility_notes(&json!({ "description": "Rock show, 21+" })), None);
+    }
+}
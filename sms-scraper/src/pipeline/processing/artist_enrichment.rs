@@ -0,0 +1,110 @@
+use tracing::debug;
+
+use sms_core::common::error::Result;
+use sms_core::domain::{Artist, ArtistDetailSource, Event};
+use sms_core::storage::Storage;
+
+/// Longest event description carried over as a headliner's bio
+pub const MAX_BIO_CHARS: usize = 2_000;
+
+/// Give an event's headliner (its first linked artist, taken from the start of the title)
+/// the event's image and description, under the precedence rules of
+/// [`sms_core::domain::Artist::offer_details`]: they only fill details the artist lacks and
+/// never replace curated ones. Returns the artist as it was before, when it was updated.
+pub async fn enrich_headliner(storage: &dyn Storage, event: &Event) -> Result<Option<Artist>> {
+    let Some(&headliner_id) = event.artist_ids.first() else {
+        return Ok(None);
+    };
+    let Some(mut artist) = storage.get_artist_by_id(headliner_id).await? else {
+        return Ok(None);
+    };
+    let previous = artist.clone();
+    let bio = event.description.as_deref().map(bounded_bio);
+    if !artist.offer_details(event.event_image_url.as_deref(), bio.as_deref(), ArtistDetailSource::HeadlinerEvent) {
+        return Ok(None);
+    }
+    storage.update_artist(&artist).await?;
+    debug!("Enriched headliner {} from event '{}'", artist.name, event.title);
+    Ok(Some(previous))
+}
+
+/// Cut a description to `MAX_BIO_CHARS`, at the last sentence or word end before the limit
+fn bounded_bio(description: &str) -> String {
+    let description = description.trim();
+    let Some((cut, _)) = description.char_indices().nth(MAX_BIO_CHARS) else {
+        return description.to_string();
+    };
+    let head = &description[..cut];
+    let end = head
+        .rfind(". ")
+        .map(|i| i + 1)
+        .or_else(|| head.rfind(char::is_whitespace))
+        .unwrap_or(cut);
+    format!("{}…", head[..end].trim_end())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+    use sms_core::storage::InMemoryStorage;
+    use uuid::Uuid;
+
+    async fn artist(storage: &InMemoryStorage, name: &str) -> Uuid {
+        let mut artist = Artist::fixture(name);
+        storage.create_artist(&mut artist).await.unwrap();
+        artist.id.unwrap()
+    }
+
+    fn event(artist_ids: Vec<Uuid>, image: &str, description: &str) -> Event {
+        Event {
+            description: Some(description.to_string()),
+            event_image_url: Some(image.to_string()),
+            artist_ids,
+            ..Event::fixture("Headliner, Support", NaiveDate::from_ymd_opt(2025, 5, 2).unwrap(), Uuid::new_v4())
+        }
+    }
+
+    #[tokio::test]
+    async fn only_the_headliner_takes_event_details_and_the_first_event_wins() {
+        let storage = InMemoryStorage::new();
+        let headliner = artist(&storage, "Headliner").await;
+        let support = artist(&storage, "Support").await;
+
+        let first = event(vec![headliner, support], "https://img/1.jpg", "Headliner's first tour.");
+        assert!(enrich_headliner(&storage, &first).await.unwrap().is_some());
+        let later = event(vec![headliner], "https://img/2.jpg", "Another night.");
+        assert!(enrich_headliner(&storage, &later).await.unwrap().is_none());
+
+        let enriched = storage.get_artist_by_id(headliner).await.unwrap().unwrap();
+        assert_eq!(enriched.artist_image_url.as_deref(), Some("https://img/1.jpg"));
+        assert_eq!(enriched.bio.as_deref(), Some("Headliner's first tour."));
+        assert_eq!(enriched.detail_origins.image, Some(ArtistDetailSource::HeadlinerEvent));
+        let untouched = storage.get_artist_by_id(support).await.unwrap().unwrap();
+        assert!(untouched.artist_image_url.is_none() && untouched.bio.is_none());
+    }
+
+    #[tokio::test]
+    async fn curated_details_are_kept() {
+        let storage = InMemoryStorage::new();
+        let headliner = artist(&storage, "Headliner").await;
+        let mut curated = storage.get_artist_by_id(headliner).await.unwrap().unwrap();
+        assert!(curated.offer_details(Some("https://img/press.jpg"), None, ArtistDetailSource::Curated));
+        storage.update_artist(&curated).await.unwrap();
+
+        let show = event(vec![headliner], "https://img/flyer.jpg", "Flyer copy.");
+        assert!(enrich_headliner(&storage, &show).await.unwrap().is_some());
+
+        let artist = storage.get_artist_by_id(headliner).await.unwrap().unwrap();
+        assert_eq!(artist.artist_image_url.as_deref(), Some("https://img/press.jpg"));
+        assert_eq!(artist.bio.as_deref(), Some("Flyer copy."));
+    }
+
+    #[test]
+    fn long_descriptions_are_cut_at_a_sentence() {
+        let description = format!("{} Second sentence runs on. {}", "word ".repeat(300), "more ".repeat(200));
+        let bio = bounded_bio(&description);
+        assert!(bio.chars().count() <= MAX_BIO_CHARS + 1);
+        assert!(bio.ends_with("runs on.…"));
+    }
+}
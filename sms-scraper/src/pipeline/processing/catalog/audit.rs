@@ -0,0 +1,244 @@
+use anyhow::Result;
+use chrono::{DateTime, Duration, NaiveDate, TimeZone, Utc};
+use serde::Serialize;
+use sms_core::domain::{Artist, Event, Venue};
+use sms_core::storage::Storage;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use uuid::Uuid;
+
+use crate::pipeline::ingestion::ingest_meta::{MetaStore, RunReportEntry};
+
+/// Venues whose latest event is older than this many days are reported as inactive
+pub const DEFAULT_INACTIVE_VENUE_DAYS: i64 = 90;
+
+/// A listed event whose day has passed but which the catalog never finalized
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct StaleEvent {
+    pub event_id: Uuid,
+    pub title: String,
+    pub event_day: NaiveDate,
+    pub venue_id: Uuid,
+}
+
+/// A listed venue with no event inside the inactivity window
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct InactiveVenue {
+    pub venue_id: Uuid,
+    pub name: String,
+    /// Day of its most recent event, if it ever had one
+    pub last_event_day: Option<NaiveDate>,
+}
+
+/// An artist no cataloged event links to
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct OrphanArtist {
+    pub artist_id: Uuid,
+    pub name: String,
+}
+
+/// A source whose latest successful run reports cataloged records while the catalog holds no
+/// event attributed to it
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct RunReportMismatch {
+    pub source_id: String,
+    pub run_id: String,
+    pub finished_at: DateTime<Utc>,
+    pub records_cataloged: u64,
+}
+
+/// Result of a full-catalog consistency audit
+#[derive(Debug, Clone, Serialize)]
+pub struct CatalogAudit {
+    pub generated_at: DateTime<Utc>,
+    pub inactive_venue_days: i64,
+    /// Listed events before today (UTC) still not finalized, so they read as upcoming
+    pub stale_upcoming_events: Vec<StaleEvent>,
+    /// Listed venues without an event in the last `inactive_venue_days` days; venues
+    /// cataloged within the window are left out until they've had time to list one
+    pub inactive_venues: Vec<InactiveVenue>,
+    pub orphan_artists: Vec<OrphanArtist>,
+    pub run_report_mismatches: Vec<RunReportMismatch>,
+}
+
+impl CatalogAudit {
+    /// Load every venue, artist and event from storage and the latest run report of every
+    /// source in the ingest metadata, then check them against each other
+    pub async fn collect(storage: &dyn Storage, meta: &MetaStore, inactive_venue_days: i64) -> Result<Self> {
+        let venues = storage.get_all_venues(None, None).await?;
+        let artists = storage.get_all_artists(None, None).await?;
+        let events = storage.get_all_events(None, None).await?;
+        let latest_runs = meta.with(|meta| {
+            let mut reports = Vec::new();
+            for source_id in meta.known_source_ids()? {
+                reports.extend(meta.latest_run_report(&source_id)?);
+            }
+            Ok(reports)
+        })?;
+        Ok(Self::check(&venues, &artists, &events, &latest_runs, inactive_venue_days, Utc::now()))
+    }
+
+    pub fn check(
+        venues: &[Venue],
+        artists: &[Artist],
+        events: &[Event],
+        latest_runs: &[RunReportEntry],
+        inactive_venue_days: i64,
+        now: DateTime<Utc>,
+    ) -> Self {
+        let today = now.date_naive();
+        let window_start = today - Duration::days(inactive_venue_days);
+
+        let mut stale_upcoming_events: Vec<StaleEvent> = events
+            .iter()
+            .filter(|e| e.show_event && e.event_day < today && !e.finalized)
+            .filter_map(|e| {
+                e.id.map(|id| StaleEvent { event_id: id, title: e.title.clone(), event_day: e.event_day, venue_id: e.venue_id })
+            })
+            .collect();
+        stale_upcoming_events.sort_by(|a, b| a.event_day.cmp(&b.event_day).then_with(|| a.title.cmp(&b.title)));
+
+        let mut last_event_day: HashMap<Uuid, NaiveDate> = HashMap::new();
+        for event in events {
+            let day = last_event_day.entry(event.venue_id).or_insert(event.event_day);
+            *day = (*day).max(event.event_day);
+        }
+        let mut inactive_venues: Vec<InactiveVenue> = venues
+            .iter()
+            .filter(|v| v.show_venue && v.created_at.date_naive() < window_start)
+            .filter_map(|v| v.id.map(|id| (id, v)))
+            .filter_map(|(id, v)| {
+                let last = last_event_day.get(&id).copied();
+                (last.is_none_or(|day| day < window_start))
+                    .then(|| InactiveVenue { venue_id: id, name: v.name.clone(), last_event_day: last })
+            })
+            .collect();
+        inactive_venues.sort_by(|a, b| a.last_event_day.cmp(&b.last_event_day).then_with(|| a.name.cmp(&b.name)));
+
+        let linked: HashSet<Uuid> = events.iter().flat_map(|e| e.artist_ids.iter().copied()).collect();
+        let mut orphan_artists: Vec<OrphanArtist> = artists
+            .iter()
+            .filter_map(|a| a.id.map(|id| (id, a)))
+            .filter(|(id, _)| !linked.contains(id))
+            .map(|(id, a)| OrphanArtist { artist_id: id, name: a.name.clone() })
+            .collect();
+        orphan_artists.sort_by(|a, b| a.name.cmp(&b.name));
+
+        let cataloged_sources: HashSet<&str> =
+            events.iter().flat_map(|e| e.attributions.iter().map(|a| a.source_id.as_str())).collect();
+        let mut run_report_mismatches: Vec<RunReportMismatch> = latest_runs
+            .iter()
+            .filter(|r| r.error.is_none() && r.records_cataloged > 0)
+            .filter(|r| !cataloged_sources.contains(r.source_id.as_str()))
+            .map(|r| RunReportMismatch {
+                source_id: r.source_id.clone(),
+                run_id: r.run_id.clone(),
+                finished_at: Utc.timestamp_opt(r.finished_at, 0).single().unwrap_or_default(),
+                records_cataloged: r.records_cataloged,
+            })
+            .collect();
+        run_report_mismatches.sort_by(|a, b| a.source_id.cmp(&b.source_id));
+
+        CatalogAudit {
+            generated_at: now,
+            inactive_venue_days,
+            stale_upcoming_events,
+            inactive_venues,
+            orphan_artists,
+            run_report_mismatches,
+        }
+    }
+
+    /// Number of findings per check, keyed by the check's name
+    pub fn counts(&self) -> BTreeMap<&'static str, usize> {
+        BTreeMap::from([
+            ("stale_upcoming_events", self.stale_upcoming_events.len()),
+            ("inactive_venues", self.inactive_venues.len()),
+            ("orphan_artists", self.orphan_artists.len()),
+            ("run_report_mismatches", self.run_report_mismatches.len()),
+        ])
+    }
+
+    pub fn findings(&self) -> usize {
+        self.counts().values().sum()
+    }
+
+    /// Write the audit as pretty JSON to `audit-<timestamp>.json` in `dir`, creating it if needed
+    pub fn write_report(&self, dir: &Path) -> Result<PathBuf> {
+        std::fs::create_dir_all(dir)?;
+        let path = dir.join(format!("audit-{}.json", self.generated_at.format("%Y%m%dT%H%M%SZ")));
+        std::fs::write(&path, serde_json::to_vec_pretty(self)?)?;
+        Ok(path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sms_core::domain::Attribution;
+
+    fn venue(name: &str, created_days_ago: i64, now: DateTime<Utc>) -> Venue {
+        Venue { id: Some(Uuid::new_v4()), created_at: now - Duration::days(created_days_ago), ..Venue::fixture(name) }
+    }
+
+    fn artist(name: &str) -> Artist {
+        Artist { id: Some(Uuid::new_v4()), ..Artist::fixture(name) }
+    }
+
+    fn event(venue_id: Uuid, day: NaiveDate, finalized: bool, artist_ids: Vec<Uuid>) -> Event {
+        Event {
+            id: Some(Uuid::new_v4()),
+            artist_ids,
+            finalized,
+            attributions: vec![Attribution { source_id: "neumos".to_string(), license_id: "test".to_string(), text: None }],
+            ..Event::fixture("Show", day, venue_id)
+        }
+    }
+
+    fn run(source_id: &str, records_cataloged: u64) -> RunReportEntry {
+        RunReportEntry {
+            run_id: format!("run-{}", source_id),
+            source_id: source_id.to_string(),
+            records_cataloged,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn flags_stale_events_inactive_venues_orphan_artists_and_run_mismatches() {
+        let now = Utc::now();
+        let today = now.date_naive();
+        let neumos = venue("Neumos", 400, now);
+        let closed = venue("Closed Club", 400, now);
+        let empty = venue("Empty Hall", 400, now);
+        let opening = venue("New Room", 10, now);
+        let linked = artist("Linked");
+        let orphan = artist("Orphan");
+        let events = vec![
+            event(neumos.id.unwrap(), today + Duration::days(5), false, vec![linked.id.unwrap()]),
+            event(neumos.id.unwrap(), today - Duration::days(2), false, Vec::new()),
+            event(neumos.id.unwrap(), today - Duration::days(3), true, Vec::new()),
+            event(closed.id.unwrap(), today - Duration::days(120), true, Vec::new()),
+        ];
+        let runs = vec![run("neumos", 4), run("kexp", 12), run("barboza", 0)];
+
+        let audit = CatalogAudit::check(
+            &[neumos, closed, empty, opening],
+            &[linked, orphan],
+            &events,
+            &runs,
+            DEFAULT_INACTIVE_VENUE_DAYS,
+            now,
+        );
+
+        assert_eq!(audit.stale_upcoming_events.len(), 1);
+        assert_eq!(audit.stale_upcoming_events[0].event_day, today - Duration::days(2));
+        let inactive: Vec<(&str, Option<NaiveDate>)> =
+            audit.inactive_venues.iter().map(|v| (v.name.as_str(), v.last_event_day)).collect();
+        assert_eq!(inactive, vec![("Empty Hall", None), ("Closed Club", Some(today - Duration::days(120)))]);
+        assert_eq!(audit.orphan_artists.iter().map(|a| a.name.as_str()).collect::<Vec<_>>(), vec!["Orphan"]);
+        assert_eq!(audit.run_report_mismatches.len(), 1);
+        assert_eq!((audit.run_report_mismatches[0].source_id.as_str(), audit.run_report_mismatches[0].records_cataloged), ("kexp", 12));
+        assert_eq!(audit.findings(), 5);
+    }
+}
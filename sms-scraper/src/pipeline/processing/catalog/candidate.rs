@@ -1,6 +1,7 @@
 use serde::{Deserialize, Serialize};
 
 use sms_core::domain::{Artist, Event, Venue};
+use uuid::Uuid;
 use crate::pipeline::processing::conflation::{EntityId, EntityType};
 
 /// Represents an entity that is a candidate for cataloging
@@ -28,12 +29,34 @@ pub enum ProposedEntity {
     Artist(Artist),
 }
 
+impl ProposedEntity {
+    /// Id the entity is written under; an already cataloged entity's id when one was found
+    pub fn id(&self) -> Option<Uuid> {
+        match self {
+            ProposedEntity::Venue(venue) => venue.id,
+            ProposedEntity::Event(event) => event.id,
+            ProposedEntity::Artist(artist) => artist.id,
+        }
+    }
+}
+
 /// The current persisted state (if it exists)
 #[derive(Debug, Clone)]
 pub enum PersistedEntity {
-    Venue,
-    Event,
-    Artist,
+    Venue(Venue),
+    Event(Event),
+    Artist(Artist),
+}
+
+impl PersistedEntity {
+    /// The stored entity as JSON, kept on UPDATE process records so a run can be rolled back
+    pub fn snapshot(&self) -> Option<String> {
+        match self {
+            PersistedEntity::Venue(venue) => serde_json::to_string(venue).ok(),
+            PersistedEntity::Event(event) => serde_json::to_string(event).ok(),
+            PersistedEntity::Artist(artist) => serde_json::to_string(artist).ok(),
+        }
+    }
 }
 
 /// Describes what changes were detected
@@ -1,22 +1,55 @@
 use chrono::Utc;
+use std::borrow::Cow;
+use std::collections::HashMap;
 use std::sync::Arc;
 use uuid::Uuid;
 use tracing::{debug, info, warn};
 
-use sms_core::common::error::Result;
+use sms_core::common::error::{Result, ScraperError};
 use sms_core::domain::ProcessRun;
-use crate::pipeline::processing::conflation::ConflatedRecord;
-use crate::pipeline::storage::Storage;
+use crate::pipeline::ingestion::envelope_state::{EnvelopeState, EnvelopeStates};
+use crate::pipeline::processing::conflation::{ConflatedRecord, EntityType};
+use crate::pipeline::processing::normalize::NormalizedEntity;
+use sms_core::storage::{Storage, WriteBatch};
+use sms_parsers::RecordChange;
 
 use super::handlers::{ArtistHandler, EventHandler, VenueHandler};
+use super::idempotency::{catalog_key, CatalogKeyIndex};
 use super::registry::EntityRegistry;
 use super::mapper::MapperRegistry;
+use super::provenance::{LineageStore, RecordLineage};
+use crate::pipeline::processing::venue_resolution::CatalogedVenues;
+
+/// Entities (process records included) written per storage transaction by default
+pub const DEFAULT_CATALOG_BATCH_SIZE: usize = 100;
+
+/// Times a batch is restaged after another run created one of its entities first
+const DUPLICATE_RETRIES: usize = 3;
+
+/// Totals for one `catalog_all` call
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct CatalogSummary {
+    pub entities_created: usize,
+    pub entities_updated: usize,
+    pub entities_unchanged: usize,
+    /// Entities whose record disappeared from the source's latest payload; left in place
+    pub entities_removed: usize,
+    pub errors: usize,
+    pub batches: usize,
+    pub entities_written: usize,
+}
 
 /// Registry-based catalogger that uses handlers for entity processing
 pub struct Catalogger {
     storage: Arc<dyn Storage>,
     registry: EntityRegistry,
     process_run_id: Option<Uuid>,
+    lineage: Option<Arc<LineageStore>>,
+    key_index: Option<Arc<CatalogKeyIndex>>,
+    envelope_states: Option<EnvelopeStates>,
+    /// Cataloged venues the handlers match placeholders against, listed once per catalogger
+    venues: Arc<CatalogedVenues>,
+    batch_size: usize,
 }
 
 impl Catalogger {
@@ -29,10 +62,11 @@ impl Catalogger {
     pub fn new(storage: Arc<dyn Storage>) -> Self {
         let mut registry = EntityRegistry::new();
         let mappers = Arc::new(MapperRegistry::default());
+        let venues = Arc::new(CatalogedVenues::new());
         
         // Register default handlers wired with mappers
-        registry.register(Arc::new(VenueHandler::with_mappers(mappers.clone())));
-        registry.register(Arc::new(EventHandler::with_mappers(mappers.clone())));
+        registry.register(Arc::new(VenueHandler::with_mappers(mappers.clone()).with_venues(venues.clone())));
+        registry.register(Arc::new(EventHandler::with_mappers(mappers.clone()).with_venues(venues.clone())));
         registry.register(Arc::new(ArtistHandler::with_mappers(mappers.clone())));
         
         info!("Initialized Catalogger with {} handlers", registry.handler_count());
@@ -41,14 +75,53 @@ impl Catalogger {
             storage,
             registry,
             process_run_id: None,
+            lineage: None,
+            key_index: None,
+            envelope_states: None,
+            venues,
+            batch_size: DEFAULT_CATALOG_BATCH_SIZE,
         }
     }
 
+    /// Record each cataloged record's stage lineage so it can be traced back to its envelope
+    pub fn with_lineage_store(mut self, lineage: Arc<LineageStore>) -> Self {
+        self.lineage = Some(lineage);
+        self
+    }
+
+    /// Upsert by each record's (source, record key) from provenance, so rerunning a source
+    /// updates the entities its records were cataloged as instead of adding new ones
+    pub fn with_key_index(mut self, key_index: Arc<CatalogKeyIndex>) -> Self {
+        self.key_index = Some(key_index);
+        self
+    }
+
+    /// Mark the envelopes records came from as cataloged once their entities are written
+    pub fn with_envelope_states(mut self, states: EnvelopeStates) -> Self {
+        self.envelope_states = Some(states);
+        self
+    }
+
+    /// Flush staged writes to storage every `batch_size` entities (at least 1)
+    pub fn with_batch_size(mut self, batch_size: usize) -> Self {
+        self.batch_size = batch_size.max(1);
+        self
+    }
+
     /// Test-only: create with custom registry
     #[cfg(test)]
     pub fn with_registry(storage: Arc<dyn Storage>, registry: EntityRegistry) -> Self {
         info!("Initialized Catalogger with custom registry containing {} handlers", registry.handler_count());
-        Self { storage, registry, process_run_id: None }
+        Self {
+            storage,
+            registry,
+            process_run_id: None,
+            lineage: None,
+            key_index: None,
+            envelope_states: None,
+            venues: Arc::new(CatalogedVenues::new()),
+            batch_size: DEFAULT_CATALOG_BATCH_SIZE,
+        }
     }
     
     /// Start a new catalog processing run
@@ -87,13 +160,189 @@ impl Catalogger {
     
     /// Process a conflated record using registered handlers
     pub async fn catalog(&self, conflated_record: &ConflatedRecord) -> Result<()> {
-        debug!(
-            "Cataloging conflated record with entity {:?}", 
-            conflated_record.canonical_entity_id
-        );
-        
-        // Get current process run for provenance
-        let process_run = if let Some(run_id) = self.process_run_id {
+        self.catalog_all(std::slice::from_ref(conflated_record)).await?;
+        Ok(())
+    }
+
+    /// Catalog `records`, writing their entities in transactional batches of `batch_size`.
+    /// Venues are written before artists and events so event handlers can resolve them, and
+    /// within each type records new to their source go first.
+    pub async fn catalog_all(&self, records: &[ConflatedRecord]) -> Result<CatalogSummary> {
+        let process_run = self.current_process_run();
+        let mut ordered: Vec<Cow<'_, ConflatedRecord>> = records.iter().map(|r| self.pin_to_cataloged(r)).collect();
+        ordered.sort_by_key(|r| (write_order(&r.canonical_entity_id.entity_type), change_priority(r)));
+
+        let mut summary = CatalogSummary::default();
+        let mut batch = WriteBatch::new();
+        let mut staged: Vec<(usize, Uuid)> = Vec::new();
+        // Ids of records that were cataloged onto an entity with another id
+        let mut redirects: HashMap<Uuid, Uuid> = HashMap::new();
+        let mut batch_start = 0;
+        let mut summary_at_start = summary.clone();
+        for i in 0..ordered.len() {
+            redirect_references(&mut ordered[i], &redirects);
+            self.stage(&ordered, i, &process_run, &mut batch, &mut staged, &mut summary).await?;
+
+            // Later entity types look up earlier ones, so never let a batch span types
+            let record = &ordered[i];
+            let type_ends = ordered.get(i + 1).is_none_or(|next| {
+                write_order(&next.canonical_entity_id.entity_type) != write_order(&record.canonical_entity_id.entity_type)
+            });
+            if batch.len() < self.batch_size && !type_ends {
+                continue;
+            }
+
+            let mut retries = 0;
+            loop {
+                let moved: Vec<(Uuid, Uuid)> = staged
+                    .iter()
+                    .map(|&(j, entity_id)| (ordered[j].canonical_entity_id.id, entity_id))
+                    .filter(|(canonical, entity_id)| canonical != entity_id)
+                    .collect();
+                match self.flush(&ordered, &mut batch, &mut staged, &mut summary).await {
+                    Ok(()) => {
+                        redirects.extend(moved);
+                        break;
+                    }
+                    // Another run created one of this batch's entities first; restaging
+                    // finds it by name and writes onto it instead
+                    Err(ScraperError::Duplicate { entity, name, existing_id }) if retries < DUPLICATE_RETRIES => {
+                        retries += 1;
+                        warn!(
+                            "{} {:?} was cataloged concurrently as {}, restaging {} records (retry {}/{})",
+                            entity, name, existing_id, i + 1 - batch_start, retries, DUPLICATE_RETRIES
+                        );
+                        summary = summary_at_start.clone();
+                        batch = WriteBatch::new();
+                        staged.clear();
+                        for j in batch_start..=i {
+                            self.stage(&ordered, j, &process_run, &mut batch, &mut staged, &mut summary).await?;
+                        }
+                    }
+                    Err(e) => return Err(e),
+                }
+            }
+            batch_start = i + 1;
+            summary_at_start = summary.clone();
+        }
+
+        if summary.entities_created > 0 || summary.entities_updated > 0 {
+            info!(
+                "Cataloged {} records: {} entities created, {} updated, {} unchanged, {} removed at source in {} batches",
+                records.len(), summary.entities_created, summary.entities_updated, summary.entities_unchanged, summary.entities_removed, summary.batches
+            );
+        } else if summary.entities_unchanged > 0 {
+            debug!("No changes detected in {} entities", summary.entities_unchanged);
+        }
+        Ok(summary)
+    }
+
+    /// Stage `ordered[i]`'s writes into `batch`, adding its stats to `summary`
+    async fn stage(
+        &self,
+        ordered: &[Cow<'_, ConflatedRecord>],
+        i: usize,
+        process_run: &ProcessRun,
+        batch: &mut WriteBatch,
+        staged: &mut Vec<(usize, Uuid)>,
+        summary: &mut CatalogSummary,
+    ) -> Result<()> {
+        let record = &ordered[i];
+        debug!("Cataloging conflated record with entity {:?}", record.canonical_entity_id);
+        let stats = self.registry.process_record(
+            record,
+            self.storage.as_ref(),
+            batch,
+            process_run,
+            Utc::now()
+        ).await?;
+        summary.entities_created += stats.entities_created;
+        summary.entities_updated += stats.entities_updated;
+        summary.entities_unchanged += stats.entities_unchanged;
+        summary.entities_removed += stats.entities_removed;
+        summary.errors += stats.errors;
+        if stats.entities_created > 0 || stats.entities_updated > 0 || stats.entities_unchanged > 0 {
+            // Handlers write onto an already cataloged entity's id when they find one
+            staged.push((i, stats.entity_id.unwrap_or(record.canonical_entity_id.id)));
+        }
+        Ok(())
+    }
+
+    /// Swap in the entity id this record's source key was cataloged under, if it's known
+    fn pin_to_cataloged<'a>(&self, record: &'a ConflatedRecord) -> Cow<'a, ConflatedRecord> {
+        let (Some(index), Some((entity_type, key))) = (&self.key_index, catalog_key(record)) else {
+            return Cow::Borrowed(record);
+        };
+        match index.lookup(&key, entity_type) {
+            Ok(Some(id)) if id != record.canonical_entity_id.id => {
+                debug!("{} {} was cataloged as {}", entity_type, key, id);
+                let mut pinned = record.clone();
+                pinned.canonical_entity_id.id = id;
+                Cow::Owned(pinned)
+            }
+            Ok(_) => Cow::Borrowed(record),
+            Err(e) => {
+                warn!("Catalog key lookup failed for {}: {}", key, e);
+                Cow::Borrowed(record)
+            }
+        }
+    }
+
+    /// Write the staged batch, then record lineage and source keys for the records it covered
+    async fn flush(
+        &self,
+        ordered: &[Cow<'_, ConflatedRecord>],
+        batch: &mut WriteBatch,
+        staged: &mut Vec<(usize, Uuid)>,
+        summary: &mut CatalogSummary,
+    ) -> Result<()> {
+        if !batch.is_empty() {
+            let started = std::time::Instant::now();
+            let written = self.storage.write_batch(batch).await?;
+            let secs = started.elapsed().as_secs_f64();
+            crate::observability::metrics::catalog::batch_written(written.entities, written.attempts, secs);
+            debug!("Flushed catalog batch of {} entities in {:.3}s", written.entities, secs);
+            summary.batches += 1;
+            summary.entities_written += written.entities;
+            for venue in &batch.venues {
+                self.venues.add(venue).await;
+            }
+            *batch = WriteBatch::new();
+        }
+
+        if let Some(lineage) = &self.lineage {
+            for &(i, entity_id) in staged.iter() {
+                let mut entry = RecordLineage::from_conflated(&ordered[i], Utc::now());
+                entry.entity_id = entity_id;
+                if let Err(e) = lineage.record(&entry) {
+                    warn!("Failed to record lineage for {}: {}", entry.entity_id, e);
+                }
+            }
+        }
+        if let Some(index) = &self.key_index {
+            for &(i, entity_id) in staged.iter() {
+                let Some((entity_type, key)) = catalog_key(&ordered[i]) else { continue };
+                if let Err(e) = index.record(&key, entity_type, entity_id, Utc::now()) {
+                    warn!("Failed to record catalog key {} for {}: {}", key, entity_id, e);
+                }
+            }
+        }
+        if let Some(states) = &self.envelope_states {
+            let mut marked = std::collections::HashSet::new();
+            for &(i, _) in staged.iter() {
+                let provenance = &ordered[i].enriched_record.quality_assessed_record.normalized_record.provenance;
+                if marked.insert(provenance.envelope_id.as_str()) {
+                    states.record(&provenance.envelope_id, &provenance.source_id, EnvelopeState::Cataloged, None);
+                }
+            }
+        }
+        staged.clear();
+        Ok(())
+    }
+
+    /// The active process run, or a temporary one when no run was started
+    fn current_process_run(&self) -> ProcessRun {
+        if let Some(run_id) = self.process_run_id {
             ProcessRun {
                 id: Some(run_id),
                 name: String::new(),
@@ -102,43 +351,317 @@ impl Catalogger {
             }
         } else {
             warn!("No active process run for cataloging");
-            // Create a temporary run for this operation
             ProcessRun {
                 id: Some(Uuid::new_v4()),
                 name: "adhoc".to_string(),
                 created_at: Utc::now(),
                 finished_at: None,
             }
-        };
-        
-        // Process through all applicable handlers
-        let stats = self.registry.process_record(
-            conflated_record,
-            self.storage.as_ref(),
-            &process_run,
-            Utc::now()
-        ).await?;
-        
-        // Log summary
-        if stats.entities_created > 0 || stats.entities_updated > 0 {
-            info!(
-                "Cataloged record: {} entities created, {} updated, {} unchanged",
-                stats.entities_created, stats.entities_updated, stats.entities_unchanged
-            );
-        } else if stats.entities_unchanged > 0 {
-            debug!("No changes detected in {} entities", stats.entities_unchanged);
         }
-        
-        Ok(())
     }
-    
+}
+
+/// Point an event record at the venue and artists its references were cataloged as
+fn redirect_references(record: &mut Cow<'_, ConflatedRecord>, redirects: &HashMap<Uuid, Uuid>) {
+    let NormalizedEntity::Event(event) = &record.enriched_record.quality_assessed_record.normalized_record.entity else {
+        return;
+    };
+    if !std::iter::once(&event.venue_id).chain(&event.artist_ids).any(|id| redirects.contains_key(id)) {
+        return;
+    }
+    if let NormalizedEntity::Event(event) = &mut record.to_mut().enriched_record.quality_assessed_record.normalized_record.entity {
+        if let Some(&venue_id) = redirects.get(&event.venue_id) {
+            event.venue_id = venue_id;
+        }
+        let mut artist_ids = Vec::with_capacity(event.artist_ids.len());
+        for id in &event.artist_ids {
+            let id = redirects.get(id).copied().unwrap_or(*id);
+            if !artist_ids.contains(&id) {
+                artist_ids.push(id);
+            }
+        }
+        event.artist_ids = artist_ids;
+    }
+}
+
+/// New records first, then changed, then unchanged or untagged; removals last
+fn change_priority(record: &ConflatedRecord) -> u8 {
+    match record.enriched_record.quality_assessed_record.normalized_record.provenance.change {
+        Some(RecordChange::Added) => 0,
+        Some(RecordChange::Changed) => 1,
+        Some(RecordChange::Unchanged) | None => 2,
+        Some(RecordChange::Removed) => 3,
+    }
+}
+
+/// Batches are flushed in this order so lookups see what earlier batches wrote
+fn write_order(entity_type: &EntityType) -> u8 {
+    match entity_type {
+        EntityType::Venue => 0,
+        EntityType::Artist => 1,
+        EntityType::Event => 2,
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::pipeline::storage::in_memory::InMemoryStorage;
+    use crate::pipeline::processing::conflation::{ConflationMetadata, DeduplicationMetadata, EntityId, ResolutionDecision};
+    use crate::pipeline::processing::enrich::{EnrichedRecord, EnrichmentMetadata, GeoProperties, PopulationDensity, ReferenceVersions};
+    use crate::pipeline::processing::normalize::{NormalizationMetadata, NormalizedEntity, NormalizedRecord, RecordProvenance};
+    use crate::pipeline::processing::quality_gate::{QualityAssessedRecord, QualityAssessment, QualityDecision};
+    use crate::pipeline::processing::catalog::mapper::EntityUtils;
+    use sms_core::common::record_key::SourceKey;
+    use sms_core::domain::*;
+    use sms_core::storage::InMemoryStorage;
+    use std::collections::HashMap;
+
+    fn venue_record(name: &str) -> ConflatedRecord {
+        let venue = Venue { slug: EntityUtils::generate_slug(name), ..Venue::fixture(name) };
+        let normalized_record = NormalizedRecord {
+            entity: NormalizedEntity::Venue(venue),
+            provenance: RecordProvenance {
+                envelope_id: "env".to_string(),
+                source_id: "test_source".to_string(),
+                payload_ref: "payload".to_string(),
+                record_path: "$.venues[0]".to_string(),
+                normalized_at: Utc::now(),
+                attribution: None,
+                change: None,
+                record_key: None,
+                endpoint_id: None,
+                external_id: None,
+            },
+            normalization: NormalizationMetadata { confidence: 1.0, warnings: Vec::new(), geocoded: false, strategy: "test".to_string() },
+        };
+        let enrichment = EnrichmentMetadata {
+            city: None,
+            district: None,
+            region: None,
+            spatial_bin: None,
+            tags: Vec::new(),
+            geo_properties: GeoProperties {
+                within_city_bounds: true,
+                distance_from_center_km: None,
+                population_density: PopulationDensity::Urban,
+                transit_accessibility: None,
+                nearby_landmarks: Vec::new(),
+            },
+            reference_versions: ReferenceVersions {
+                city_boundaries: None,
+                admin_boundaries: None,
+                spatial_grid: None,
+                poi_data: None,
+                neighborhoods: None,
+            },
+            strategy: "test".to_string(),
+            confidence: 1.0,
+            warnings: Vec::new(),
+        };
+        ConflatedRecord {
+            canonical_entity_id: EntityId { id: Uuid::new_v4(), entity_type: EntityType::Venue, version: 1 },
+            enriched_record: EnrichedRecord {
+                quality_assessed_record: QualityAssessedRecord {
+                    normalized_record,
+                    quality_assessment: QualityAssessment {
+                        decision: QualityDecision::Accept,
+                        quality_score: 1.0,
+                        issues: Vec::new(),
+                        rule_version: "v1".to_string(),
+                    },
+                    assessed_at: Utc::now(),
+                },
+                enrichment,
+                enriched_at: Utc::now(),
+            },
+            conflation: ConflationMetadata {
+                resolution_decision: ResolutionDecision::NewEntity,
+                confidence: 1.0,
+                strategy: "test".to_string(),
+                alternatives: Vec::new(),
+                previous_entity_id: None,
+                contributing_sources: vec!["test_source".to_string()],
+                similarity_scores: HashMap::new(),
+                warnings: Vec::new(),
+                deduplication: DeduplicationMetadata {
+                    is_potential_duplicate: false,
+                    potential_duplicates: Vec::new(),
+                    deduplication_strategy: "test".to_string(),
+                    key_attributes: Vec::new(),
+                    deduplication_signature: None,
+                },
+                config: None,
+            },
+            conflated_at: Utc::now(),
+        }
+    }
+
+    /// An event record as a fresh pipeline run would conflate it: new canonical id each time
+    fn event_record(title: &str, record_key: &str) -> ConflatedRecord {
+        let mut record = venue_record("unused");
+        let normalized = &mut record.enriched_record.quality_assessed_record.normalized_record;
+        let day = chrono::NaiveDate::from_ymd_opt(2025, 5, 2).unwrap();
+        normalized.entity = NormalizedEntity::Event(Event::fixture(title, day, Uuid::nil()));
+        normalized.provenance.record_key = Some(record_key.to_string());
+        record.canonical_entity_id.entity_type = EntityType::Event;
+        record
+    }
+
+    #[tokio::test]
+    async fn consecutive_runs_upsert_events_by_source_record_key() {
+        let tmp = tempfile::tempdir().unwrap();
+        let storage = Arc::new(InMemoryStorage::new());
+        let index = Arc::new(CatalogKeyIndex::open_at_root(tmp.path()).unwrap());
+
+        let first = Catalogger::new(storage.clone()).with_key_index(index.clone());
+        let summary = first.catalog_all(&[event_record("The Band", "id:42")]).await.unwrap();
+        assert_eq!(summary.entities_created, 1);
+        let cataloged = storage.get_all_events(None, None).await.unwrap();
+        assert_eq!(cataloged.len(), 1);
+
+        // The source retitled the show; its record key is unchanged
+        let second = Catalogger::new(storage.clone()).with_key_index(index.clone());
+        let summary = second.catalog_all(&[event_record("The Band (Sold Out)", "id:42")]).await.unwrap();
+        assert_eq!((summary.entities_created, summary.entities_updated), (0, 1));
+
+        let events = storage.get_all_events(None, None).await.unwrap();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].id, cataloged[0].id);
+        assert_eq!(events[0].title, "The Band (Sold Out)");
+        assert_eq!(index.lookup(&SourceKey::new("test_source", "id:42"), "event").unwrap(), cataloged[0].id);
+    }
+
+    #[tokio::test]
+    async fn venues_are_keyed_apart_from_the_listing_that_named_them() {
+        let tmp = tempfile::tempdir().unwrap();
+        let storage = Arc::new(InMemoryStorage::new());
+        let index = Arc::new(CatalogKeyIndex::open_at_root(tmp.path()).unwrap());
+
+        // The venue comes from the same listing record as the event
+        let mut venue = venue_record("Neumos");
+        venue.enriched_record.quality_assessed_record.normalized_record.provenance.record_key = Some("id:42".to_string());
+        let catalogger = Catalogger::new(storage.clone()).with_key_index(index.clone());
+        catalogger.catalog_all(&[venue, event_record("The Band", "id:42")]).await.unwrap();
+
+        let venue_id = storage.get_venue_by_name("Neumos").await.unwrap().unwrap().id;
+        let event_id = storage.get_all_events(None, None).await.unwrap()[0].id;
+        assert_eq!(index.lookup(&SourceKey::new("test_source", "venue:neumos"), "venue").unwrap(), venue_id);
+        assert_eq!(index.lookup(&SourceKey::new("test_source", "id:42"), "event").unwrap(), event_id);
+    }
+
+    #[tokio::test]
+    async fn rerun_without_key_updates_the_matching_event_in_place() {
+        let storage = Arc::new(InMemoryStorage::new());
+        let catalogger = Catalogger::new(storage.clone());
+
+        let mut first = event_record("The Band", "id:42");
+        first.enriched_record.quality_assessed_record.normalized_record.provenance.record_key = None;
+        catalogger.catalog_all(&[first.clone()]).await.unwrap();
+
+        let mut rerun = event_record("The Band", "id:42");
+        rerun.enriched_record.quality_assessed_record.normalized_record.provenance.record_key = None;
+        if let NormalizedEntity::Event(event) = &mut rerun.enriched_record.quality_assessed_record.normalized_record.entity {
+            event.description = Some("Doors at 7".to_string());
+        }
+        let summary = catalogger.catalog_all(&[rerun]).await.unwrap();
+
+        assert_eq!(summary.entities_updated, 1);
+        let events = storage.get_all_events(None, None).await.unwrap();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].description.as_deref(), Some("Doors at 7"));
+    }
+
+    #[tokio::test]
+    async fn catalog_all_flushes_in_batches_of_the_configured_size() {
+        let storage = Arc::new(InMemoryStorage::new());
+        let mut catalogger = Catalogger::new(storage.clone()).with_batch_size(4);
+        catalogger.start_run("batched").await.unwrap();
+
+        // Each new venue stages the venue plus its CREATE process record
+        let records: Vec<_> = ["Neumos", "Barboza", "Sunset Tavern"].iter().map(|n| venue_record(n)).collect();
+        let summary = catalogger.catalog_all(&records).await.unwrap();
+
+        assert_eq!(summary.entities_created, 3);
+        assert_eq!(summary.entities_written, 6);
+        assert_eq!(summary.batches, 2);
+        assert_eq!(storage.get_all_venues(None, None).await.unwrap().len(), 3);
+        assert!(storage.get_venue_by_name("Sunset Tavern").await.unwrap().is_some());
+    }
+
+    #[tokio::test]
+    async fn removed_records_are_reported_but_not_written() {
+        let storage = Arc::new(InMemoryStorage::new());
+        let catalogger = Catalogger::new(storage.clone());
+
+        let mut gone = venue_record("Closed Venue");
+        gone.enriched_record.quality_assessed_record.normalized_record.provenance.change = Some(RecordChange::Removed);
+        let mut new = venue_record("New Venue");
+        new.enriched_record.quality_assessed_record.normalized_record.provenance.change = Some(RecordChange::Added);
+        let summary = catalogger.catalog_all(&[gone, new]).await.unwrap();
+
+        assert_eq!((summary.entities_created, summary.entities_removed), (1, 1));
+        assert!(storage.get_venue_by_name("Closed Venue").await.unwrap().is_none());
+        assert!(storage.get_venue_by_name("New Venue").await.unwrap().is_some());
+    }
     
+    #[tokio::test]
+    async fn written_records_mark_their_envelope_cataloged() {
+        let tmp = tempfile::tempdir().unwrap();
+        let states = EnvelopeStates::new(tmp.path());
+        let record = venue_record("Tractor Tavern");
+        let provenance = &record.enriched_record.quality_assessed_record.normalized_record.provenance;
+        states.record(&provenance.envelope_id, &provenance.source_id, EnvelopeState::Normalized, None);
+
+        let catalogger = Catalogger::new(Arc::new(InMemoryStorage::new())).with_envelope_states(states);
+        catalogger.catalog_all(std::slice::from_ref(&record)).await.unwrap();
+
+        let meta = crate::pipeline::ingestion::ingest_meta::IngestMeta::open_at_root(tmp.path()).unwrap();
+        let entry = meta.get_envelope_state(&provenance.envelope_id).unwrap().unwrap();
+        assert_eq!(entry.state, "cataloged");
+    }
+
+    fn artist_record(name: &str) -> ConflatedRecord {
+        let mut record = venue_record("unused");
+        let artist = Artist { name_slug: EntityUtils::generate_slug(name), ..Artist::fixture(name) };
+        record.enriched_record.quality_assessed_record.normalized_record.entity = NormalizedEntity::Artist(artist);
+        record.canonical_entity_id.entity_type = EntityType::Artist;
+        record
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn concurrent_runs_sharing_a_venue_and_artist_catalog_each_once() {
+        let storage = Arc::new(InMemoryStorage::new());
+
+        // Two sources list different shows by the same artist at the same venue, and each
+        // run conflated them under ids of its own; whichever writes second restages
+        let run = |title: &'static str| {
+            let catalogger = Catalogger::new(storage.clone());
+            tokio::spawn(async move {
+                let venue = venue_record("Neumos");
+                let artist = artist_record("The Band");
+                let mut event = event_record(title, title);
+                if let NormalizedEntity::Event(e) = &mut event.enriched_record.quality_assessed_record.normalized_record.entity {
+                    e.venue_id = venue.canonical_entity_id.id;
+                    e.artist_ids = vec![artist.canonical_entity_id.id];
+                }
+                catalogger.catalog_all(&[venue, artist, event]).await
+            })
+        };
+        let (first, second) = tokio::join!(run("The Band"), run("The Band (Late Show)"));
+        let (first, second) = (first.unwrap().unwrap(), second.unwrap().unwrap());
+        assert_eq!(first.entities_created + second.entities_created, 4);
+
+        let venues = storage.get_all_venues(None, None).await.unwrap();
+        let artists = storage.get_all_artists(None, None).await.unwrap();
+        assert_eq!((venues.len(), artists.len()), (1, 1));
+        let events = storage.get_all_events(None, None).await.unwrap();
+        assert_eq!(events.len(), 2);
+        for event in &events {
+            assert_eq!(Some(event.venue_id), venues[0].id);
+            assert_eq!(event.artist_ids, vec![artists[0].id.unwrap()]);
+        }
+    }
+
     #[tokio::test]
     async fn test_catalogger_creation() {
         let storage = Arc::new(InMemoryStorage::new());
@@ -181,4 +704,36 @@ mod tests {
         catalogger.finish_run().await.unwrap();
         assert!(catalogger.process_run_id.is_none());
     }
+
+    #[tokio::test]
+    async fn cancelled_calendar_entries_are_cataloged_hidden() {
+        use crate::pipeline::processing::conflation::{Conflator, DefaultConflator};
+        use crate::pipeline::processing::enrich::{DefaultEnricher, Enricher};
+        use crate::pipeline::processing::normalize::normalizers::{GoogleCalendarNormalizer, SourceNormalizer};
+        use crate::pipeline::processing::quality_gate::{DefaultQualityGate, QualityGate};
+        use sms_parsers::{GoogleCalendarV1Parser, Parser};
+
+        let day = Utc::now().date_naive() + chrono::Duration::days(7);
+        let calendar = format!(
+            r#"{{"kind":"calendar#events","summary":"Ballard Corner Bar","timeZone":"America/Los_Angeles","items":[
+                {{"id":"abc123","status":"confirmed","summary":"The Dip","location":"Ballard Corner Bar, 5300 Ballard Ave NW","start":{{"date":"{day}"}}}},
+                {{"id":"def456","status":"cancelled","summary":"Kingdom of Birds","location":"Ballard Corner Bar, 5300 Ballard Ave NW","start":{{"date":"{day}"}}}}]}}"#
+        );
+        let parser = GoogleCalendarV1Parser::new("ballard_corner_bar".to_string(), "env-1".to_string(), "cas:sha256:abcd".to_string(), sms_parsers::DateHints::default());
+        let normalizer = GoogleCalendarNormalizer::new();
+        let (gate, enricher, conflator) = (DefaultQualityGate::new(), DefaultEnricher::new(), DefaultConflator::new());
+        let storage = Arc::new(InMemoryStorage::new());
+        let catalogger = Catalogger::new(storage.clone());
+        for parsed in parser.parse(calendar.as_bytes()).unwrap() {
+            for normalized in normalizer.normalize(&parsed).unwrap() {
+                let conflated = conflator.conflate(&enricher.enrich(&gate.assess(&normalized).unwrap()).unwrap()).unwrap();
+                catalogger.catalog(&conflated).await.unwrap();
+            }
+        }
+
+        let mut events = storage.get_all_events(None, None).await.unwrap();
+        events.sort_by(|a, b| a.title.cmp(&b.title));
+        let shown: Vec<_> = events.iter().map(|e| (e.title.as_str(), e.show_event)).collect();
+        assert_eq!(shown, [("Kingdom of Birds", false), ("The Dip", true)]);
+    }
 }
@@ -0,0 +1,199 @@
+use anyhow::Result;
+use serde::Serialize;
+use sms_core::domain::{Artist, Event, Venue};
+use sms_core::storage::Storage;
+use std::collections::{BTreeMap, HashSet};
+use std::path::{Path, PathBuf};
+use uuid::Uuid;
+
+/// A single integrity problem in the cataloged graph
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum GraphViolation {
+    /// Event points at a venue id that does not exist
+    EventMissingVenue { event_id: Uuid, title: String, venue_id: Uuid },
+    /// Event was stored with the nil venue id
+    EventNilVenue { event_id: Uuid, title: String },
+    /// Event lists an artist id that does not exist
+    EventMissingArtist { event_id: Uuid, title: String, artist_id: Uuid },
+    /// Artist not referenced by any event
+    OrphanedArtist { artist_id: Uuid, name: String },
+    /// More than one venue shares a slug
+    DuplicateVenueSlug { slug: String, venue_ids: Vec<Uuid> },
+    /// More than one artist shares a slug
+    DuplicateArtistSlug { slug: String, artist_ids: Vec<Uuid> },
+}
+
+/// Result of a full graph integrity check
+#[derive(Debug, Clone, Serialize)]
+pub struct GraphValidationReport {
+    pub checked_at: chrono::DateTime<chrono::Utc>,
+    pub venues: usize,
+    pub artists: usize,
+    pub events: usize,
+    pub violations: Vec<GraphViolation>,
+}
+
+impl GraphValidationReport {
+    pub fn is_clean(&self) -> bool {
+        self.violations.is_empty()
+    }
+
+    /// Violation counts keyed by kind, for summaries
+    pub fn counts_by_kind(&self) -> BTreeMap<&'static str, usize> {
+        let mut counts = BTreeMap::new();
+        for v in &self.violations {
+            let kind = match v {
+                GraphViolation::EventMissingVenue { .. } => "event_missing_venue",
+                GraphViolation::EventNilVenue { .. } => "event_nil_venue",
+                GraphViolation::EventMissingArtist { .. } => "event_missing_artist",
+                GraphViolation::OrphanedArtist { .. } => "orphaned_artist",
+                GraphViolation::DuplicateVenueSlug { .. } => "duplicate_venue_slug",
+                GraphViolation::DuplicateArtistSlug { .. } => "duplicate_artist_slug",
+            };
+            *counts.entry(kind).or_insert(0) += 1;
+        }
+        counts
+    }
+
+    /// Write the report as pretty JSON to `<dir>/<ts>_graph_validation.json`
+    pub fn write_to_dir(&self, dir: &Path) -> Result<PathBuf> {
+        std::fs::create_dir_all(dir)?;
+        let path = dir.join(format!("{}_graph_validation.json", self.checked_at.format("%Y%m%d_%H%M%S")));
+        std::fs::write(&path, serde_json::to_vec_pretty(self)?)?;
+        Ok(path)
+    }
+}
+
+/// Integrity checks over the whole venue/artist/event graph
+pub struct GraphValidator;
+
+impl GraphValidator {
+    /// Load every venue, artist and event from storage and check them
+    pub async fn validate(storage: &dyn Storage) -> Result<GraphValidationReport> {
+        let venues = storage.get_all_venues(None, None).await?;
+        let artists = storage.get_all_artists(None, None).await?;
+        let events = storage.get_all_events(None, None).await?;
+        Ok(Self::check(&venues, &artists, &events))
+    }
+
+    pub fn check(venues: &[Venue], artists: &[Artist], events: &[Event]) -> GraphValidationReport {
+        let mut violations = Vec::new();
+        let venue_ids: HashSet<Uuid> = venues.iter().filter_map(|v| v.id).collect();
+        let artist_ids: HashSet<Uuid> = artists.iter().filter_map(|a| a.id).collect();
+        let mut referenced_artists = HashSet::new();
+
+        for event in events {
+            let event_id = event.id.unwrap_or_default();
+            if event.venue_id.is_nil() {
+                violations.push(GraphViolation::EventNilVenue { event_id, title: event.title.clone() });
+            } else if !venue_ids.contains(&event.venue_id) {
+                violations.push(GraphViolation::EventMissingVenue {
+                    event_id,
+                    title: event.title.clone(),
+                    venue_id: event.venue_id,
+                });
+            }
+            for artist_id in &event.artist_ids {
+                referenced_artists.insert(*artist_id);
+                if !artist_ids.contains(artist_id) {
+                    violations.push(GraphViolation::EventMissingArtist {
+                        event_id,
+                        title: event.title.clone(),
+                        artist_id: *artist_id,
+                    });
+                }
+            }
+        }
+
+        for artist in artists {
+            if let Some(id) = artist.id {
+                if !referenced_artists.contains(&id) {
+                    violations.push(GraphViolation::OrphanedArtist { artist_id: id, name: artist.name.clone() });
+                }
+            }
+        }
+
+        for (slug, venue_ids) in duplicates(venues.iter().map(|v| (v.slug.as_str(), v.id))) {
+            violations.push(GraphViolation::DuplicateVenueSlug { slug, venue_ids });
+        }
+        for (slug, artist_ids) in duplicates(artists.iter().map(|a| (a.name_slug.as_str(), a.id))) {
+            violations.push(GraphViolation::DuplicateArtistSlug { slug, artist_ids });
+        }
+
+        GraphValidationReport {
+            checked_at: chrono::Utc::now(),
+            venues: venues.len(),
+            artists: artists.len(),
+            events: events.len(),
+            violations,
+        }
+    }
+}
+
+fn duplicates<'a>(items: impl Iterator<Item = (&'a str, Option<Uuid>)>) -> Vec<(String, Vec<Uuid>)> {
+    let mut by_slug: BTreeMap<&str, Vec<Uuid>> = BTreeMap::new();
+    for (slug, id) in items {
+        by_slug.entry(slug).or_default().push(id.unwrap_or_default());
+    }
+    by_slug
+        .into_iter()
+        .filter(|(_, ids)| ids.len() > 1)
+        .map(|(slug, ids)| (slug.to_string(), ids))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn venue(slug: &str) -> Venue {
+        Venue { id: Some(Uuid::new_v4()), ..Venue::fixture(slug) }
+    }
+
+    fn artist(slug: &str) -> Artist {
+        Artist { id: Some(Uuid::new_v4()), ..Artist::fixture(slug) }
+    }
+
+    fn event(venue_id: Uuid, artist_ids: Vec<Uuid>) -> Event {
+        let day = chrono::NaiveDate::from_ymd_opt(2024, 5, 1).unwrap();
+        Event { id: Some(Uuid::new_v4()), artist_ids, ..Event::fixture("Show", day, venue_id) }
+    }
+
+    #[test]
+    fn clean_graph_has_no_violations() {
+        let v = venue("neumos");
+        let a = artist("band");
+        let e = event(v.id.unwrap(), vec![a.id.unwrap()]);
+        let report = GraphValidator::check(&[v], &[a], &[e]);
+        assert!(report.is_clean());
+        assert_eq!((report.venues, report.artists, report.events), (1, 1, 1));
+    }
+
+    #[test]
+    fn detects_each_violation_kind() {
+        let v1 = venue("neumos");
+        let v2 = venue("neumos");
+        let a1 = artist("band");
+        let a2 = artist("band");
+        let ghost_artist = Uuid::new_v4();
+        let events = vec![
+            event(v1.id.unwrap(), vec![a1.id.unwrap(), ghost_artist]),
+            event(Uuid::new_v4(), vec![]),
+            event(Uuid::nil(), vec![]),
+        ];
+        let report = GraphValidator::check(&[v1, v2], &[a1, a2.clone()], &events);
+
+        let counts = report.counts_by_kind();
+        assert_eq!(counts.get("event_missing_venue"), Some(&1));
+        assert_eq!(counts.get("event_nil_venue"), Some(&1));
+        assert_eq!(counts.get("event_missing_artist"), Some(&1));
+        assert_eq!(counts.get("orphaned_artist"), Some(&1));
+        assert_eq!(counts.get("duplicate_venue_slug"), Some(&1));
+        assert_eq!(counts.get("duplicate_artist_slug"), Some(&1));
+        assert!(report.violations.contains(&GraphViolation::OrphanedArtist {
+            artist_id: a2.id.unwrap(),
+            name: "band".to_string(),
+        }));
+    }
+}
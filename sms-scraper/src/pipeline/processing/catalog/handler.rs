@@ -4,7 +4,7 @@ use chrono::{DateTime, Utc};
 use sms_core::common::error::Result;
 use sms_core::domain::{ProcessRecord, ProcessRun};
 use crate::pipeline::processing::conflation::ConflatedRecord;
-use crate::pipeline::storage::Storage;
+use sms_core::storage::{Storage, WriteBatch};
 
 use super::candidate::CatalogCandidate;
 
@@ -26,12 +26,12 @@ pub trait EntityHandler: Send + Sync {
         storage: &dyn Storage,
     ) -> Result<Option<CatalogCandidate>>;
 
-    /// Persist the catalog candidate if it should be saved
-    /// Returns true if something was persisted
-    async fn persist_candidate(
+    /// Stage the catalog candidate's writes in `batch` if it should be saved
+    /// Returns true if something will be persisted when the batch is flushed
+    fn stage_candidate(
         &self,
         candidate: &CatalogCandidate,
-        storage: &dyn Storage,
+        batch: &mut WriteBatch,
     ) -> Result<bool>;
 
     /// Generate process records for audit trail
@@ -10,7 +10,7 @@ use crate::pipeline::processing::catalog::candidate::{
 };
 use crate::pipeline::processing::catalog::handler::EntityHandler;
 use crate::pipeline::processing::conflation::{ConflatedRecord, EntityType};
-use crate::pipeline::storage::Storage;
+use sms_core::storage::{Storage, WriteBatch};
 
 use std::sync::Arc;
 use crate::pipeline::processing::catalog::mapper::{EntityUtils, MapperRegistry};
@@ -19,6 +19,13 @@ pub struct ArtistHandler {
     mappers: Arc<MapperRegistry>,
 }
 
+#[cfg(test)]
+impl Default for ArtistHandler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl ArtistHandler {
     #[cfg(test)]
     pub fn new() -> Self {
@@ -98,27 +105,30 @@ impl EntityHandler for ArtistHandler {
         };
 
         // Step 2: Prepare the artist for persistence - need to convert from normalized to domain
-        let proposed_artist = Artist {
+        let mut proposed_artist = Artist {
             id: Some(record.canonical_entity_id.id),
             name: normalized_artist.name.clone(),
             name_slug: EntityUtils::generate_slug(&normalized_artist.name),
             bio: normalized_artist.bio,
             artist_image_url: None, // Not available in normalized artist
             created_at: Utc::now(),
+            attributions: normalized_artist.attributions,
+            detail_origins: Default::default(),
+            aliases: Vec::new(),
         };
-        let proposed_entity = ProposedEntity::Artist(proposed_artist.clone());
 
         // Step 3: Check if artist already exists
         match storage.get_artist_by_name(&proposed_artist.name).await {
             Ok(Some(existing_artist)) => {
-                // Artist exists - check for changes
+                // Artist exists - check for changes against it, keeping its id
+                proposed_artist.id = existing_artist.id.or(proposed_artist.id);
                 let changes = self.detect_artist_changes(&proposed_artist, &existing_artist);
-let current_entity = PersistedEntity::Artist;
-                
+                let current_entity = PersistedEntity::Artist(existing_artist);
+
                 Ok(Some(CatalogCandidate::existing_entity(
                     EntityType::Artist,
                     record.canonical_entity_id.clone(),
-                    proposed_entity,
+                    ProposedEntity::Artist(proposed_artist),
                     current_entity,
                     changes,
                 )))
@@ -128,7 +138,7 @@ let current_entity = PersistedEntity::Artist;
                 Ok(Some(CatalogCandidate::new_entity(
                     EntityType::Artist,
                     record.canonical_entity_id.clone(),
-                    proposed_entity,
+                    ProposedEntity::Artist(proposed_artist),
                 )))
             }
             Err(e) => {
@@ -138,10 +148,10 @@ let current_entity = PersistedEntity::Artist;
         }
     }
 
-    async fn persist_candidate(
+    fn stage_candidate(
         &self,
         candidate: &CatalogCandidate,
-        storage: &dyn Storage,
+        batch: &mut WriteBatch,
     ) -> Result<bool> {
         if !candidate.should_persist {
             return Ok(false);
@@ -154,9 +164,8 @@ let current_entity = PersistedEntity::Artist;
 
         if candidate.is_new() {
             // Create new artist
-            let mut artist = artist.clone();
-            storage.create_artist(&mut artist).await?;
-            debug!("Created new artist: {}", artist.name);
+            batch.artists.push(artist.clone());
+            debug!("Staged new artist: {}", artist.name);
             Ok(true)
         } else {
             // Update existing artist - note: there's no update_artist method in Storage trait
@@ -176,8 +185,8 @@ let current_entity = PersistedEntity::Artist;
             return vec![];
         };
 
-        let process_run_id = process_run.id.unwrap_or_else(|| Uuid::new_v4());
-        let artist_id = artist.id.unwrap_or_else(|| Uuid::new_v4());
+        let process_run_id = process_run.id.unwrap_or_else(Uuid::new_v4);
+        let artist_id = artist.id.unwrap_or_else(Uuid::new_v4);
 
         let (change_type, change_log, field_changed) = if candidate.is_new() {
             (
@@ -203,6 +212,13 @@ let current_entity = PersistedEntity::Artist;
             )
         };
 
+        // Kept for UPDATEs so the run can be rolled back
+        let previous_state = candidate
+            .current_state
+            .as_ref()
+            .filter(|_| candidate.has_changes())
+            .and_then(PersistedEntity::snapshot);
+
         vec![ProcessRecord {
             id: Some(Uuid::new_v4()),
             process_run_id,
@@ -215,6 +231,7 @@ let current_entity = PersistedEntity::Artist;
             venue_id: None,
             artist_id: Some(artist_id),
             created_at: timestamp,
+            previous_state,
         }]
     }
 
@@ -235,6 +252,9 @@ mod tests {
             bio: Some("A test band".to_string()),
             artist_image_url: Some("https://example.com/image.jpg".to_string()),
             created_at: Utc::now(),
+            attributions: Vec::new(),
+            detail_origins: Default::default(),
+            aliases: Vec::new(),
         };
         
         let mut artist2 = artist1.clone();
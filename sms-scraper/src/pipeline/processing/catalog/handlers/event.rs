@@ -11,23 +11,39 @@ use crate::pipeline::processing::catalog::candidate::{
 use crate::pipeline::processing::catalog::handler::EntityHandler;
 use crate::pipeline::processing::conflation::{ConflatedRecord, EntityType};
 use crate::pipeline::processing::normalize::NormalizedEntity;
-use crate::pipeline::storage::Storage;
+use sms_core::storage::{Storage, WriteBatch};
+use crate::pipeline::processing::venue_resolution::CatalogedVenues;
+use crate::pipeline::processing::venue_resolver::VenueResolver;
 
 use std::sync::Arc;
 use crate::pipeline::processing::catalog::mapper::MapperRegistry;
 
 pub struct EventHandler {
     mappers: Arc<MapperRegistry>,
+    venues: Arc<CatalogedVenues>,
+}
+
+#[cfg(test)]
+impl Default for EventHandler {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl EventHandler {
     #[cfg(test)]
     pub fn new() -> Self {
-        Self { mappers: Arc::new(MapperRegistry::default()) }
+        Self::with_mappers(Arc::new(MapperRegistry::default()))
     }
 
     pub fn with_mappers(mappers: Arc<MapperRegistry>) -> Self {
-        Self { mappers }
+        Self { mappers, venues: Arc::new(CatalogedVenues::new()) }
+    }
+
+    /// Match placeholders against the run's shared list of cataloged venues
+    pub fn with_venues(mut self, venues: Arc<CatalogedVenues>) -> Self {
+        self.venues = venues;
+        self
     }
 
     /// Detect changes between proposed and current event
@@ -75,6 +91,30 @@ impl EventHandler {
             );
         }
         
+        if proposed.show_event != current.show_event {
+            changeset.add_change(
+                "show_event",
+                Some(current.show_event.to_string()),
+                Some(proposed.show_event.to_string())
+            );
+        }
+
+        if proposed.age_restriction != current.age_restriction {
+            changeset.add_change(
+                "age_restriction",
+                current.age_restriction.map(|a| format!("{:?}", a)),
+                proposed.age_restriction.map(|a| format!("{:?}", a))
+            );
+        }
+
+        if proposed.accessibility_notes != current.accessibility_notes {
+            changeset.add_change(
+                "accessibility_notes",
+                current.accessibility_notes.clone(),
+                proposed.accessibility_notes.clone()
+            );
+        }
+        
         // Check if artist_ids have changed
         if proposed.artist_ids != current.artist_ids {
             changeset.add_change(
@@ -84,6 +124,13 @@ impl EventHandler {
             );
         }
         
+        if proposed.external_ids != current.external_ids {
+            let ids = |event: &Event| {
+                event.external_ids.iter().map(|e| format!("{}:{}", e.source_id, e.id)).collect::<Vec<_>>().join(", ")
+            };
+            changeset.add_change("external_ids", Some(ids(current)), Some(ids(proposed)));
+        }
+
         if changeset.has_changes {
             changeset.change_summary = format!("Updated event: {}", proposed.title);
         }
@@ -116,39 +163,37 @@ impl EntityHandler for EventHandler {
             uuid::Uuid::nil()
         };
         
-        // Step 1.5: If venue_id is nil, try to resolve it based on source
-        if venue_id == uuid::Uuid::nil() {
+        // Step 1.5: Known venues are attached at normalization under their deterministic id (or
+        // implied by a single-venue source). One cataloged before that is found by name instead.
+        let resolver = VenueResolver::new();
+        let known = if venue_id.is_nil() {
             let source_id = &record.enriched_record.quality_assessed_record.normalized_record.provenance.source_id;
-            
-            // Map source to venue name for known single-venue sources
-            let venue_name = match source_id.as_str() {
-                "barboza" => Some("The Barboza"),
-                "kexp" => Some("KEXP Events"),
-                "blue_moon" => Some("Blue Moon Tavern"),
-                "darrells_tavern" => Some("Darrell's Tavern"),
-                "sea_monster" => Some("Sea Monster Lounge"),
-                _ => None,
-            };
-            
-            // Try to find the venue by name
-            if let Some(name) = venue_name {
-                match storage.get_venue_by_name(name).await {
-                    Ok(Some(venue)) => {
-                        if let Some(id) = venue.id {
-                            venue_id = id;
-                            debug!("Resolved venue {} for event from source {}", name, source_id);
-                        }
-                    }
-                    Ok(None) => {
-                        debug!("Could not find venue {} for source {}", name, source_id);
-                    }
-                    Err(e) => {
-                        debug!("Error looking up venue {}: {:?}", name, e);
+            resolver.for_source(source_id)
+        } else {
+            resolver.venues().iter().find(|v| v.id() == venue_id)
+        };
+        if let Some(known) = known {
+            venue_id = known.id();
+            if storage.get_venue_by_id(venue_id).await?.is_none() {
+                match storage.get_venue_by_name(known.name).await? {
+                    Some(venue) => {
+                        venue_id = venue.id.unwrap_or(venue_id);
+                        debug!("Resolved known venue {} by name", known.name);
                     }
+                    None => debug!("Known venue {} not cataloged yet", known.name),
                 }
             }
         }
         
+        // Step 1.6: Events parked on a placeholder go straight to the real venue once it's cataloged
+        if venue_id != uuid::Uuid::nil() {
+            if let Some(venue) = storage.get_venue_by_id(venue_id).await?.filter(|v| v.provisional) {
+                if let Some(id) = self.venues.resolve(storage, venue).await?.id {
+                    venue_id = id;
+                }
+            }
+        }
+
         let Ok(proposed_event_base) = self.mappers.event_mapper.to_event(record, venue_id) else {
             debug!("No event found in conflated record");
             return Ok(None);
@@ -167,50 +212,60 @@ impl EntityHandler for EventHandler {
         } else {
             debug!("WARNING: Event has NO artist IDs!");
         }
-        // Ensure defaults that the mapper may not set for this persistence step
-        proposed_event.show_event = true;
+        // Ensure defaults that the mapper may not set for this persistence step; show_event
+        // is the listing's own, so cancelled events stay hidden
         proposed_event.finalized = false;
         proposed_event.created_at = Utc::now();
-        let proposed_entity = ProposedEntity::Event(proposed_event.clone());
 
-        // Step 3: Check if event already exists
-        match storage.get_event_by_venue_date_title(
-            proposed_event.venue_id,
-            proposed_event.event_day,
-            &proposed_event.title
-        ).await {
-            Ok(Some(existing_event)) => {
-                // Event exists - check for changes
+        // Step 3: Check if event already exists - first under its canonical id (which the
+        // catalogger pins to the entity a source record was cataloged as before), then by
+        // venue, day and title
+        let existing = match storage.get_event_by_id(record.canonical_entity_id.id).await? {
+            Some(event) => Some(event),
+            None => storage
+                .get_event_by_venue_date_title(proposed_event.venue_id, proposed_event.event_day, &proposed_event.title)
+                .await
+                .inspect_err(|e| error!("Error looking up existing event: {:?}", e))?,
+        };
+
+        match existing {
+            Some(existing_event) => {
+                // Event exists - update it in place rather than writing a second copy
+                proposed_event.id = existing_event.id.or(proposed_event.id);
+                proposed_event.created_at = existing_event.created_at;
+                proposed_event.keep_moderation(&existing_event);
+                // Keep the ids other sources list the event under
+                for external_id in &existing_event.external_ids {
+                    if !proposed_event.external_ids.iter().any(|e| e.source_id == external_id.source_id) {
+                        proposed_event.external_ids.push(external_id.clone());
+                    }
+                }
                 let changes = self.detect_event_changes(&proposed_event, &existing_event);
-let current_entity = PersistedEntity::Event;
-                
+                let current_entity = PersistedEntity::Event(existing_event);
+
                 Ok(Some(CatalogCandidate::existing_entity(
                     EntityType::Event,
                     record.canonical_entity_id.clone(),
-                    proposed_entity,
+                    ProposedEntity::Event(proposed_event),
                     current_entity,
                     changes,
                 )))
             }
-            Ok(None) => {
+            None => {
                 // New event
                 Ok(Some(CatalogCandidate::new_entity(
                     EntityType::Event,
                     record.canonical_entity_id.clone(),
-                    proposed_entity,
+                    ProposedEntity::Event(proposed_event),
                 )))
             }
-            Err(e) => {
-                error!("Error looking up existing event: {:?}", e);
-                Err(e)
-            }
         }
     }
 
-    async fn persist_candidate(
+    fn stage_candidate(
         &self,
         candidate: &CatalogCandidate,
-        storage: &dyn Storage,
+        batch: &mut WriteBatch,
     ) -> Result<bool> {
         if !candidate.should_persist {
             return Ok(false);
@@ -223,16 +278,15 @@ let current_entity = PersistedEntity::Event;
 
         if candidate.is_new() {
             // Create new event
-            let mut event = event.clone();
-            storage.create_event(&mut event).await?;
-            debug!("Created new event: {}", event.title);
+            batch.events.push(event.clone());
+            debug!("Staged new event: {}", event.title);
             Ok(true)
         } else {
             // Update existing event
             debug!("Updating event '{}' with {} artist_ids: {:?}", 
                 event.title, event.artist_ids.len(), event.artist_ids);
-            storage.update_event(event).await?;
-            debug!("Updated event: {}", event.title);
+            batch.event_updates.push(event.clone());
+            debug!("Staged event update: {}", event.title);
             Ok(true)
         }
     }
@@ -247,8 +301,8 @@ let current_entity = PersistedEntity::Event;
             return vec![];
         };
 
-        let process_run_id = process_run.id.unwrap_or_else(|| Uuid::new_v4());
-        let event_id = event.id.unwrap_or_else(|| Uuid::new_v4());
+        let process_run_id = process_run.id.unwrap_or_else(Uuid::new_v4);
+        let event_id = event.id.unwrap_or_else(Uuid::new_v4);
 
         let (change_type, change_log, field_changed) = if candidate.is_new() {
             (
@@ -274,6 +328,13 @@ let current_entity = PersistedEntity::Event;
             )
         };
 
+        // Kept for UPDATEs so the run can be rolled back
+        let previous_state = candidate
+            .current_state
+            .as_ref()
+            .filter(|_| candidate.has_changes())
+            .and_then(PersistedEntity::snapshot);
+
         vec![ProcessRecord {
             id: Some(Uuid::new_v4()),
             process_run_id,
@@ -286,6 +347,7 @@ let current_entity = PersistedEntity::Event;
             venue_id: Some(event.venue_id),
             artist_id: None,
             created_at: timestamp,
+            previous_state,
         }]
     }
 
@@ -304,17 +366,10 @@ mod tests {
         
         let event1 = Event {
             id: Some(uuid::Uuid::new_v4()),
-            title: "Test Concert".to_string(),
-            event_day,
             start_time,
             event_url: Some("https://example.com/event".to_string()),
             description: Some("A great concert".to_string()),
-            event_image_url: None,
-            venue_id: uuid::Uuid::new_v4(),
-            artist_ids: vec![],
-            show_event: true,
-            finalized: false,
-            created_at: Utc::now(),
+            ..Event::fixture("Test Concert", event_day, uuid::Uuid::new_v4())
         };
         
         let mut event2 = event1.clone();
@@ -10,30 +10,46 @@ use crate::pipeline::processing::catalog::candidate::{
 };
 use crate::pipeline::processing::catalog::handler::EntityHandler;
 use crate::pipeline::processing::conflation::{ConflatedRecord, EntityType};
-use crate::pipeline::storage::Storage;
+use sms_core::storage::{Storage, WriteBatch};
+use crate::pipeline::processing::venue_resolution::CatalogedVenues;
 
 use std::sync::Arc;
 use crate::pipeline::processing::catalog::mapper::MapperRegistry;
 
 pub struct VenueHandler {
     mappers: Arc<MapperRegistry>,
+    venues: Arc<CatalogedVenues>,
+}
+
+#[cfg(test)]
+impl Default for VenueHandler {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl VenueHandler {
     #[cfg(test)]
     pub fn new() -> Self {
-        Self { mappers: Arc::new(MapperRegistry::default()) }
+        Self::with_mappers(Arc::new(MapperRegistry::default()))
     }
 
     pub fn with_mappers(mappers: Arc<MapperRegistry>) -> Self {
-        Self { mappers }
+        Self { mappers, venues: Arc::new(CatalogedVenues::new()) }
+    }
+
+    /// Match placeholders against the run's shared list of cataloged venues
+    pub fn with_venues(mut self, venues: Arc<CatalogedVenues>) -> Self {
+        self.venues = venues;
+        self
     }
 
     /// Convert venue to the correct domain model format
     fn prepare_venue_for_persistence(&self, venue: &Venue, canonical_id: &uuid::Uuid) -> Venue {
         // The normalized venue is already the correct domain struct, just update ID and timestamps
         Venue {
-            id: Some(*canonical_id),
+            // Placeholder ids are derived from their name so events can point at them up front
+            id: if venue.provisional { venue.id.or(Some(*canonical_id)) } else { Some(*canonical_id) },
             name: venue.name.clone(),
             name_lower: venue.name_lower.clone(),
             slug: venue.slug.clone(),
@@ -46,8 +62,14 @@ impl VenueHandler {
             venue_image_url: venue.venue_image_url.clone(),
             description: venue.description.clone(),
             neighborhood: venue.neighborhood.clone(),
-            show_venue: true,
+            show_venue: !venue.provisional, // placeholders stay out of listings
             created_at: Utc::now(),
+            attributions: venue.attributions.clone(),
+            provisional: venue.provisional,
+            aliases: Vec::new(),
+            age_restriction: venue.age_restriction,
+            accessibility_notes: venue.accessibility_notes.clone(),
+            moderation: None,
         }
     }
 
@@ -95,6 +117,22 @@ impl VenueHandler {
                 Some(proposed.longitude.to_string())
             );
         }
+
+        if proposed.age_restriction != current.age_restriction {
+            changeset.add_change(
+                "age_restriction",
+                current.age_restriction.map(|a| format!("{:?}", a)),
+                proposed.age_restriction.map(|a| format!("{:?}", a))
+            );
+        }
+
+        if proposed.accessibility_notes != current.accessibility_notes {
+            changeset.add_change(
+                "accessibility_notes",
+                current.accessibility_notes.clone(),
+                proposed.accessibility_notes.clone()
+            );
+        }
         
         if changeset.has_changes {
             changeset.change_summary = format!("Updated venue: {}", proposed.name);
@@ -127,44 +165,56 @@ impl EntityHandler for VenueHandler {
             return Ok(None);
         };
 
+        // Step 1.5: A placeholder for a venue we've since cataloged for real isn't needed
+        if normalized_venue.provisional {
+            if let Some(venue) = self.venues.matching(storage, &normalized_venue).await? {
+                debug!("Skipping placeholder {}, already cataloged as {}", normalized_venue.name, venue.name);
+                return Ok(None);
+            }
+        }
+
         // Step 2: Prepare the venue for persistence
-        let proposed_venue = self.prepare_venue_for_persistence(&normalized_venue, &record.canonical_entity_id.id);
-        let proposed_entity = ProposedEntity::Venue(proposed_venue.clone());
+        let mut proposed_venue = self.prepare_venue_for_persistence(&normalized_venue, &record.canonical_entity_id.id);
 
-        // Step 3: Check if venue already exists
-        match storage.get_venue_by_name(&proposed_venue.name).await {
-            Ok(Some(existing_venue)) => {
+        // Step 3: Check if venue already exists, under its canonical id or by name
+        let existing = match storage.get_venue_by_id(record.canonical_entity_id.id).await? {
+            Some(venue) => Some(venue),
+            None => storage
+                .get_venue_by_name(&proposed_venue.name)
+                .await
+                .inspect_err(|e| error!("Error looking up existing venue: {:?}", e))?,
+        };
+
+        match existing {
+            Some(existing_venue) => {
                 // Venue exists - check for changes
+                proposed_venue.id = existing_venue.id.or(proposed_venue.id);
                 let changes = self.detect_venue_changes(&proposed_venue, &existing_venue);
-let current_entity = PersistedEntity::Venue;
-                
+                let current_entity = PersistedEntity::Venue(existing_venue);
+
                 Ok(Some(CatalogCandidate::existing_entity(
                     EntityType::Venue,
                     record.canonical_entity_id.clone(),
-                    proposed_entity,
+                    ProposedEntity::Venue(proposed_venue),
                     current_entity,
                     changes,
                 )))
             }
-            Ok(None) => {
+            None => {
                 // New venue
                 Ok(Some(CatalogCandidate::new_entity(
                     EntityType::Venue,
                     record.canonical_entity_id.clone(),
-                    proposed_entity,
+                    ProposedEntity::Venue(proposed_venue),
                 )))
             }
-            Err(e) => {
-                error!("Error looking up existing venue: {:?}", e);
-                Err(e)
-            }
         }
     }
 
-    async fn persist_candidate(
+    fn stage_candidate(
         &self,
         candidate: &CatalogCandidate,
-        storage: &dyn Storage,
+        batch: &mut WriteBatch,
     ) -> Result<bool> {
         if !candidate.should_persist {
             return Ok(false);
@@ -177,9 +227,8 @@ let current_entity = PersistedEntity::Venue;
 
         if candidate.is_new() {
             // Create new venue
-            let mut venue = venue.clone();
-            storage.create_venue(&mut venue).await?;
-            debug!("Created new venue: {}", venue.name);
+            batch.venues.push(venue.clone());
+            debug!("Staged new venue: {}", venue.name);
             Ok(true)
         } else {
             // Update existing venue - note: there's no update_venue method in Storage trait
@@ -199,8 +248,8 @@ let current_entity = PersistedEntity::Venue;
             return vec![];
         };
 
-        let process_run_id = process_run.id.unwrap_or_else(|| Uuid::new_v4());
-        let venue_id = venue.id.unwrap_or_else(|| Uuid::new_v4());
+        let process_run_id = process_run.id.unwrap_or_else(Uuid::new_v4);
+        let venue_id = venue.id.unwrap_or_else(Uuid::new_v4);
 
         let (change_type, change_log, field_changed) = if candidate.is_new() {
             (
@@ -226,6 +275,13 @@ let current_entity = PersistedEntity::Venue;
             )
         };
 
+        // Kept for UPDATEs so the run can be rolled back
+        let previous_state = candidate
+            .current_state
+            .as_ref()
+            .filter(|_| candidate.has_changes())
+            .and_then(PersistedEntity::snapshot);
+
         vec![ProcessRecord {
             id: Some(Uuid::new_v4()),
             process_run_id,
@@ -238,6 +294,7 @@ let current_entity = PersistedEntity::Venue;
             venue_id: Some(venue_id),
             artist_id: None,
             created_at: timestamp,
+            previous_state,
         }]
     }
 }
@@ -268,6 +325,12 @@ mod tests {
             neighborhood: Some("Downtown".to_string()),
             show_venue: true,
             created_at: Utc::now(),
+            attributions: Vec::new(),
+            provisional: false,
+            aliases: Vec::new(),
+            age_restriction: None,
+            accessibility_notes: None,
+            moderation: None,
         };
         
         let mut venue2 = venue1.clone();
@@ -0,0 +1,111 @@
+use chrono::{DateTime, Utc};
+use rusqlite::{params, Connection, OptionalExtension};
+use sms_core::common::record_key::SourceKey;
+use std::path::Path;
+use std::sync::Mutex;
+use uuid::Uuid;
+
+use crate::pipeline::processing::conflation::{ConflatedRecord, EntityType};
+use crate::pipeline::processing::normalize::NormalizedEntity;
+use crate::pipeline::processing::venue_resolver::venue_key;
+
+/// Which entity each source record was cataloged as, keyed by its source key and entity type,
+/// so a rerun updates that entity instead of adding another.
+pub struct CatalogKeyIndex {
+    conn: Mutex<Connection>,
+}
+
+/// The index key for a conflated record, or `None` when it shouldn't be keyed. An event is
+/// keyed by its source key from provenance. A venue is keyed by its own name, since it shares
+/// the provenance of the listing that named it. Artists are left out: one event record names
+/// several of them, so its key can't identify any one.
+pub fn catalog_key(record: &ConflatedRecord) -> Option<(&'static str, SourceKey)> {
+    let provenance = &record.enriched_record.quality_assessed_record.normalized_record.provenance;
+    match (&record.canonical_entity_id.entity_type, &record.enriched_record.quality_assessed_record.normalized_record.entity) {
+        (EntityType::Venue, NormalizedEntity::Venue(venue)) => {
+            let key = venue_key(&venue.name);
+            (!key.is_empty()).then(|| ("venue", SourceKey::new(&provenance.source_id, format!("venue:{}", key))))
+        }
+        (EntityType::Event, _) => Some(("event", provenance.source_key()?)),
+        _ => None,
+    }
+}
+
+impl CatalogKeyIndex {
+    pub fn open_at_root<P: AsRef<Path>>(data_root: P) -> anyhow::Result<Self> {
+        let db_path = data_root.as_ref().join("catalog").join("source_keys.db");
+        if let Some(parent) = db_path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let conn = Connection::open(db_path)?;
+        conn.execute_batch(
+            r#"
+            PRAGMA journal_mode=WAL;
+            CREATE TABLE IF NOT EXISTS source_record_keys (
+                source_id      TEXT NOT NULL,
+                entity_type    TEXT NOT NULL,
+                record_key     TEXT NOT NULL,
+                entity_id      TEXT NOT NULL,
+                first_seen_at  INTEGER NOT NULL,
+                last_seen_at   INTEGER NOT NULL,
+                PRIMARY KEY (source_id, entity_type, record_key)
+            );
+            "#,
+        )?;
+        Ok(Self { conn: Mutex::new(conn) })
+    }
+
+    /// The entity this source record was last cataloged as
+    pub fn lookup(&self, key: &SourceKey, entity_type: &str) -> anyhow::Result<Option<Uuid>> {
+        let conn = self.conn.lock().map_err(|_| anyhow::anyhow!("catalog key index lock poisoned"))?;
+        let id: Option<String> = conn
+            .query_row(
+                "SELECT entity_id FROM source_record_keys
+                 WHERE source_id = ?1 AND entity_type = ?2 AND record_key = ?3",
+                params![key.source_id(), entity_type, key.key()],
+                |row| row.get(0),
+            )
+            .optional()?;
+        Ok(id.and_then(|id| Uuid::parse_str(&id).ok()))
+    }
+
+    /// Point the source record at `entity_id`, keeping when it was first seen
+    pub fn record(
+        &self,
+        key: &SourceKey,
+        entity_type: &str,
+        entity_id: Uuid,
+        seen_at: DateTime<Utc>,
+    ) -> anyhow::Result<()> {
+        let conn = self.conn.lock().map_err(|_| anyhow::anyhow!("catalog key index lock poisoned"))?;
+        conn.execute(
+            "INSERT INTO source_record_keys (source_id, entity_type, record_key, entity_id, first_seen_at, last_seen_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?5)
+             ON CONFLICT(source_id, entity_type, record_key)
+             DO UPDATE SET entity_id = excluded.entity_id, last_seen_at = excluded.last_seen_at",
+            params![key.source_id(), entity_type, key.key(), entity_id.to_string(), seen_at.timestamp_millis()],
+        )?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keys_are_scoped_by_source_and_entity_type() {
+        let tmp = tempfile::tempdir().unwrap();
+        let index = CatalogKeyIndex::open_at_root(tmp.path()).unwrap();
+        let (first, second) = (Uuid::new_v4(), Uuid::new_v4());
+        let key = SourceKey::new("neumos", "id:42");
+
+        assert!(index.lookup(&key, "event").unwrap().is_none());
+        index.record(&key, "event", first, Utc::now()).unwrap();
+        index.record(&key, "event", second, Utc::now()).unwrap();
+
+        assert_eq!(index.lookup(&key, "event").unwrap(), Some(second));
+        assert!(index.lookup(&SourceKey::new("barboza", "id:42"), "event").unwrap().is_none());
+        assert!(index.lookup(&key, "venue").unwrap().is_none());
+    }
+}
@@ -2,7 +2,7 @@ use std::sync::Arc;
 use uuid::Uuid;
 
 use sms_core::common::error::{Result, ScraperError};
-use sms_core::domain::{Artist, Event, Venue};
+use sms_core::domain::{Artist, Event, ExternalId, Venue};
 use crate::pipeline::processing::conflation::ConflatedRecord;
 use crate::pipeline::processing::normalize::NormalizedEntity;
 
@@ -12,14 +12,7 @@ pub struct EntityUtils;
 impl EntityUtils {
     /// Generate a URL-friendly slug from a name
     pub fn generate_slug(name: &str) -> String {
-        name.to_lowercase()
-            .chars()
-            .map(|c| if c.is_alphanumeric() { c } else { '-' })
-            .collect::<String>()
-            .split('-')
-            .filter(|s| !s.is_empty())
-            .collect::<Vec<_>>()
-            .join("-")
+        sms_core::storage::canonical_slug(name)
     }
 }
 
@@ -88,6 +81,11 @@ impl EventMapper for DefaultEventMapper {
             event.artist_ids.clone()
         };
 
+        let mut external_ids = event.external_ids.clone();
+        if let Some(id) = &normalized.provenance.external_id {
+            ExternalId { source_id: normalized.provenance.source_id.clone(), id: id.clone() }.add_to(&mut external_ids);
+        }
+
         Ok(Event {
             id: None,
             title: event.title.clone(),
@@ -101,6 +99,15 @@ impl EventMapper for DefaultEventMapper {
             show_event: event.show_event,
             finalized: event.finalized,
             created_at: event.created_at,
+            attributions: event.attributions.clone(),
+            tags: Vec::new(),
+            price: event.price.clone(),
+            doors_time: event.doors_time,
+            series_id: event.series_id,
+            age_restriction: event.age_restriction,
+            accessibility_notes: event.accessibility_notes.clone(),
+            moderation: None,
+            external_ids,
         })
     }
 }
@@ -2,13 +2,21 @@
 mod mapper;
 
 // Registry-based modules
+pub mod audit;
 pub mod candidate;
+#[cfg(feature = "scraping")]
 pub mod catalogger;
+pub mod graph_validation;
 pub mod handler;
 pub mod handlers;
+pub mod idempotency;
+pub mod provenance;
+#[cfg(feature = "scraping")]
 pub mod registry;
+pub mod stats;
 
 // Re-export legacy utilities that might still be used elsewhere
 
 // Export the catalogger
+#[cfg(feature = "scraping")]
 pub use catalogger::Catalogger;
@@ -0,0 +1,279 @@
+use chrono::{DateTime, Utc};
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::sync::Mutex;
+use uuid::Uuid;
+
+use crate::pipeline::processing::conflation::{ConflatedRecord, ResolutionDecision};
+use crate::pipeline::processing::quality_gate::{QualityAssessedRecord, QualityDecision};
+
+/// The chain of pipeline stages that produced a cataloged entity, newest stage first:
+/// conflation → quality gate → normalization → parsed record → envelope / CAS payload
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecordLineage {
+    pub entity_id: Uuid,
+    pub entity_type: String,
+    pub cataloged_at: DateTime<Utc>,
+    pub conflation: ConflationLineage,
+    pub quality: QualityLineage,
+    pub normalization: NormalizationLineage,
+    pub parsed: ParsedLineage,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConflationLineage {
+    pub conflated_at: DateTime<Utc>,
+    pub entity_version: u64,
+    pub decision: String,
+    pub confidence: f64,
+    pub strategy: String,
+    pub contributing_sources: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QualityLineage {
+    pub assessed_at: DateTime<Utc>,
+    pub decision: String,
+    pub score: f64,
+    pub rule_version: String,
+    pub issues: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NormalizationLineage {
+    pub normalized_at: DateTime<Utc>,
+    pub confidence: f64,
+    pub strategy: String,
+    pub warnings: Vec<String>,
+}
+
+/// The parsed record is identified by the envelope it came from and its path in the payload
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ParsedLineage {
+    pub source_id: String,
+    pub envelope_id: String,
+    pub payload_ref: String,
+    pub record_path: String,
+}
+
+impl RecordLineage {
+    pub fn from_conflated(record: &ConflatedRecord, cataloged_at: DateTime<Utc>) -> Self {
+        let conflation = ConflationLineage {
+            conflated_at: record.conflated_at,
+            entity_version: record.canonical_entity_id.version,
+            decision: resolution_label(&record.conflation.resolution_decision).to_string(),
+            confidence: record.conflation.confidence,
+            strategy: record.conflation.strategy.clone(),
+            contributing_sources: record.conflation.contributing_sources.clone(),
+        };
+        Self::from_assessed(
+            record.canonical_entity_id.id,
+            &record.canonical_entity_id.entity_type.to_string().to_lowercase(),
+            &record.enriched_record.quality_assessed_record,
+            conflation,
+            cataloged_at,
+        )
+    }
+
+    /// Lineage of an entity cataloged from a quality-assessed record, with the conflation
+    /// step as the cataloging path recorded it
+    pub fn from_assessed(
+        entity_id: Uuid,
+        entity_type: &str,
+        assessed: &QualityAssessedRecord,
+        conflation: ConflationLineage,
+        cataloged_at: DateTime<Utc>,
+    ) -> Self {
+        let normalized = &assessed.normalized_record;
+        let provenance = &normalized.provenance;
+        Self {
+            entity_id,
+            entity_type: entity_type.to_string(),
+            cataloged_at,
+            conflation,
+            quality: QualityLineage {
+                assessed_at: assessed.assessed_at,
+                decision: quality_label(&assessed.quality_assessment.decision).to_string(),
+                score: assessed.quality_assessment.quality_score,
+                rule_version: assessed.quality_assessment.rule_version.clone(),
+                issues: assessed
+                    .quality_assessment
+                    .issues
+                    .iter()
+                    .map(|i| i.description.clone())
+                    .collect(),
+            },
+            normalization: NormalizationLineage {
+                normalized_at: provenance.normalized_at,
+                confidence: normalized.normalization.confidence,
+                strategy: normalized.normalization.strategy.clone(),
+                warnings: normalized.normalization.warnings.clone(),
+            },
+            parsed: ParsedLineage {
+                source_id: provenance.source_id.clone(),
+                envelope_id: provenance.envelope_id.clone(),
+                payload_ref: provenance.payload_ref.clone(),
+                record_path: provenance.record_path.clone(),
+            },
+        }
+    }
+}
+
+fn resolution_label(decision: &ResolutionDecision) -> &'static str {
+    match decision {
+        ResolutionDecision::NewEntity => "new_entity",
+        ResolutionDecision::MatchedExisting(_) => "matched_existing",
+        ResolutionDecision::UpdatedExisting(_) => "updated_existing",
+        ResolutionDecision::Duplicate(_) => "duplicate",
+        ResolutionDecision::Uncertain => "uncertain",
+    }
+}
+
+fn quality_label(decision: &QualityDecision) -> &'static str {
+    match decision {
+        QualityDecision::Accept => "accept",
+        QualityDecision::AcceptWithWarnings => "accept_with_warnings",
+        QualityDecision::Quarantine => "quarantine",
+    }
+}
+
+/// Lineage of every cataloged record, keyed by canonical entity id, so curators can
+/// trace a listing back to the scrape that produced it.
+pub struct LineageStore {
+    conn: Mutex<Connection>,
+}
+
+impl LineageStore {
+    pub fn open_at_root<P: AsRef<Path>>(data_root: P) -> anyhow::Result<Self> {
+        let db_path = data_root.as_ref().join("catalog").join("lineage.db");
+        if let Some(parent) = db_path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let conn = Connection::open(db_path)?;
+        conn.execute_batch(
+            r#"
+            PRAGMA journal_mode=WAL;
+            CREATE TABLE IF NOT EXISTS entity_lineage (
+                entity_id     TEXT NOT NULL,
+                cataloged_at  INTEGER NOT NULL,
+                envelope_id   TEXT NOT NULL,
+                lineage_json  TEXT NOT NULL,
+                PRIMARY KEY (entity_id, cataloged_at, envelope_id)
+            );
+            "#,
+        )?;
+        Ok(Self { conn: Mutex::new(conn) })
+    }
+
+    pub fn record(&self, lineage: &RecordLineage) -> anyhow::Result<()> {
+        let conn = self.conn.lock().map_err(|_| anyhow::anyhow!("lineage store lock poisoned"))?;
+        conn.execute(
+            "INSERT OR REPLACE INTO entity_lineage (entity_id, cataloged_at, envelope_id, lineage_json)
+             VALUES (?1, ?2, ?3, ?4)",
+            params![
+                lineage.entity_id.to_string(),
+                lineage.cataloged_at.timestamp_millis(),
+                lineage.parsed.envelope_id,
+                serde_json::to_string(lineage)?,
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// Lineage of the most recent catalog write for this entity
+    pub fn latest(&self, entity_id: Uuid) -> anyhow::Result<Option<RecordLineage>> {
+        let conn = self.conn.lock().map_err(|_| anyhow::anyhow!("lineage store lock poisoned"))?;
+        let json: Option<String> = conn
+            .query_row(
+                "SELECT lineage_json FROM entity_lineage WHERE entity_id = ?1
+                 ORDER BY cataloged_at DESC LIMIT 1",
+                params![entity_id.to_string()],
+                |row| row.get(0),
+            )
+            .optional()?;
+        Ok(match json {
+            Some(json) => Some(serde_json::from_str(&json)?),
+            None => None,
+        })
+    }
+
+    /// Every CAS payload some recorded lineage points back to
+    pub fn payload_refs(&self) -> anyhow::Result<Vec<String>> {
+        let conn = self.conn.lock().map_err(|_| anyhow::anyhow!("lineage store lock poisoned"))?;
+        let mut stmt = conn.prepare("SELECT lineage_json FROM entity_lineage")?;
+        let rows = stmt.query_map([], |row| row.get::<_, String>(0))?;
+        let mut refs = Vec::new();
+        for json in rows {
+            let lineage: RecordLineage = serde_json::from_str(&json?)?;
+            refs.push(lineage.parsed.payload_ref);
+        }
+        refs.sort();
+        refs.dedup();
+        Ok(refs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pipeline::processing::conflation::{EntityId, EntityType};
+
+    fn lineage(entity_id: Uuid, envelope_id: &str, cataloged_at: DateTime<Utc>) -> RecordLineage {
+        RecordLineage {
+            entity_id,
+            entity_type: "event".into(),
+            cataloged_at,
+            conflation: ConflationLineage {
+                conflated_at: cataloged_at,
+                entity_version: 1,
+                decision: resolution_label(&ResolutionDecision::MatchedExisting(EntityId {
+                    id: entity_id,
+                    entity_type: EntityType::Event,
+                    version: 1,
+                }))
+                .into(),
+                confidence: 1.0,
+                strategy: "test".into(),
+                contributing_sources: vec!["neumos".into()],
+            },
+            quality: QualityLineage {
+                assessed_at: cataloged_at,
+                decision: quality_label(&QualityDecision::Accept).into(),
+                score: 0.9,
+                rule_version: "v1".into(),
+                issues: vec![],
+            },
+            normalization: NormalizationLineage {
+                normalized_at: cataloged_at,
+                confidence: 0.9,
+                strategy: "test".into(),
+                warnings: vec![],
+            },
+            parsed: ParsedLineage {
+                source_id: "neumos".into(),
+                envelope_id: envelope_id.into(),
+                payload_ref: format!("cas:sha256:{}", envelope_id),
+                record_path: "$.events[0]".into(),
+            },
+        }
+    }
+
+    #[test]
+    fn latest_returns_most_recent_catalog_write() {
+        let tmp = tempfile::tempdir().unwrap();
+        let store = LineageStore::open_at_root(tmp.path()).unwrap();
+        let event_id = Uuid::new_v4();
+        let first = Utc::now() - chrono::Duration::days(1);
+
+        assert!(store.latest(event_id).unwrap().is_none());
+        store.record(&lineage(event_id, "env-old", first)).unwrap();
+        store.record(&lineage(event_id, "env-new", Utc::now())).unwrap();
+        store.record(&lineage(Uuid::new_v4(), "env-other", Utc::now())).unwrap();
+
+        let latest = store.latest(event_id).unwrap().unwrap();
+        assert_eq!(latest.parsed.envelope_id, "env-new");
+        assert_eq!(latest.parsed.payload_ref, "cas:sha256:env-new");
+        assert_eq!(latest.conflation.decision, "matched_existing");
+    }
+}
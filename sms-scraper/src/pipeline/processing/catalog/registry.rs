@@ -5,7 +5,9 @@ use tracing::{debug, error, info};
 use sms_core::common::error::Result;
 use sms_core::domain::{ProcessRecord, ProcessRun};
 use crate::pipeline::processing::conflation::ConflatedRecord;
-use crate::pipeline::storage::Storage;
+use sms_core::storage::{Storage, WriteBatch};
+use sms_parsers::RecordChange;
+use uuid::Uuid;
 
 use super::handler::EntityHandler;
 
@@ -16,8 +18,12 @@ pub struct ProcessingStats {
     pub entities_updated: usize,
     pub entities_unchanged: usize,
     pub entities_skipped: usize,
+    /// Entities whose record disappeared from the source's latest payload
+    pub entities_removed: usize,
     pub errors: usize,
     pub process_records: Vec<ProcessRecord>,
+    /// Id of the entity the record was matched to or staged as
+    pub entity_id: Option<Uuid>,
 }
 
 impl ProcessingStats {
@@ -26,7 +32,7 @@ impl ProcessingStats {
     }
     
     pub fn total_processed(&self) -> usize {
-        self.entities_created + self.entities_updated + self.entities_unchanged + self.entities_skipped
+        self.entities_created + self.entities_updated + self.entities_unchanged + self.entities_skipped + self.entities_removed
     }
 }
 
@@ -35,6 +41,12 @@ pub struct EntityRegistry {
     handlers: Vec<Arc<dyn EntityHandler>>,
 }
 
+impl Default for EntityRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl EntityRegistry {
     /// Create a new empty registry
     pub fn new() -> Self {
@@ -49,17 +61,32 @@ impl EntityRegistry {
         self.handlers.push(handler);
     }
 
-    /// Process a conflated record through all applicable handlers
+    /// Process a conflated record through all applicable handlers. Lookups go to `storage`;
+    /// entity and process record writes are staged in `batch` for the caller to flush.
     pub async fn process_record(
         &self,
         record: &ConflatedRecord,
         storage: &dyn Storage,
+        batch: &mut WriteBatch,
         process_run: &ProcessRun,
         timestamp: DateTime<Utc>,
     ) -> Result<ProcessingStats> {
         let mut stats = ProcessingStats::new();
         let mut all_process_records = Vec::new();
 
+        // A record gone from its source's latest payload is reported, not written
+        if record.enriched_record.quality_assessed_record.normalized_record.provenance.change == Some(RecordChange::Removed) {
+            for handler in self.handlers.iter().filter(|h| h.can_handle(record)) {
+                info!(
+                    "{} {} was removed from its source's latest payload",
+                    handler.entity_type(),
+                    record.canonical_entity_id.id
+                );
+                stats.entities_removed += 1;
+            }
+            return Ok(stats);
+        }
+
         for handler in &self.handlers {
             if handler.can_handle(record) {
                 debug!(
@@ -71,6 +98,7 @@ impl EntityRegistry {
                 // Step 1: Prepare the catalog candidate
                 match handler.prepare_candidate(record, storage).await {
                     Ok(Some(candidate)) => {
+                        stats.entity_id = stats.entity_id.or(candidate.proposed_state.id());
                         // Step 2: Generate process records for audit
                         let process_records = handler.generate_process_records(
                             &candidate,
@@ -80,7 +108,7 @@ impl EntityRegistry {
                         
                         // Step 3: Persist if needed
                         if candidate.should_persist {
-                            match handler.persist_candidate(&candidate, storage).await {
+                            match handler.stage_candidate(&candidate, batch) {
                                 Ok(persisted) => {
                                     if persisted {
                                         if candidate.is_new() {
@@ -98,17 +126,12 @@ impl EntityRegistry {
                                         }
                                     }
                                     
-                                    // Store process records
-                                    for mut record in process_records {
-                                        if let Err(e) = storage.create_process_record(&mut record).await {
-                                            error!("Failed to store process record: {:?}", e);
-                                        } else {
-                                            all_process_records.push(record);
-                                        }
-                                    }
+                                    // Stage process records alongside the entity
+                                    all_process_records.extend(process_records.iter().cloned());
+                                    batch.process_records.extend(process_records);
                                 }
                                 Err(e) => {
-                                    error!("Failed to persist {}: {:?}", handler.entity_type(), e);
+                                    error!("Failed to stage {}: {:?}", handler.entity_type(), e);
                                     stats.errors += 1;
                                 }
                             }
@@ -182,10 +205,10 @@ mod tests {
             Ok(None)
         }
 
-        async fn persist_candidate(
+        fn stage_candidate(
             &self,
             _candidate: &CatalogCandidate,
-            _storage: &dyn Storage,
+            _batch: &mut WriteBatch,
         ) -> Result<bool> {
             Ok(false)
         }
@@ -0,0 +1,147 @@
+use anyhow::Result;
+use chrono::{DateTime, Duration, Utc};
+use serde::Serialize;
+use sms_core::domain::{Artist, Event, Venue};
+use sms_core::storage::Storage;
+use std::collections::{BTreeSet, HashMap};
+use uuid::Uuid;
+
+/// Events cataloged at one venue
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct VenueEventCount {
+    pub venue_id: Uuid,
+    pub name: String,
+    pub events: usize,
+}
+
+/// Entities cataloged within the recent window
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct RecentCounts {
+    pub venues: usize,
+    pub artists: usize,
+    pub events: usize,
+}
+
+/// Summary of what's in the catalog
+#[derive(Debug, Clone, Serialize)]
+pub struct CatalogStats {
+    pub generated_at: DateTime<Utc>,
+    pub venues: usize,
+    pub artists: usize,
+    pub events: usize,
+    /// Every venue with its event count, busiest first
+    pub events_per_venue: Vec<VenueEventCount>,
+    /// Events on or after today (UTC)
+    pub upcoming_events: usize,
+    pub past_events: usize,
+    pub recent_days: i64,
+    pub recent: RecentCounts,
+    /// Sources that contributed no event cataloged within the recent window
+    pub quiet_sources: Vec<String>,
+}
+
+impl CatalogStats {
+    /// Load every venue, artist and event from storage and summarize them. `sources` are the
+    /// source ids expected to produce events (e.g. enabled registry entries); sources seen
+    /// in event attributions are checked too.
+    pub async fn collect(storage: &dyn Storage, sources: &[String], recent_days: i64) -> Result<Self> {
+        let venues = storage.get_all_venues(None, None).await?;
+        let artists = storage.get_all_artists(None, None).await?;
+        let events = storage.get_all_events(None, None).await?;
+        Ok(Self::summarize(&venues, &artists, &events, sources, recent_days, Utc::now()))
+    }
+
+    pub fn summarize(
+        venues: &[Venue],
+        artists: &[Artist],
+        events: &[Event],
+        sources: &[String],
+        recent_days: i64,
+        now: DateTime<Utc>,
+    ) -> Self {
+        let today = now.date_naive();
+        let since = now - Duration::days(recent_days);
+
+        let mut by_venue: HashMap<Uuid, usize> = HashMap::new();
+        for event in events {
+            *by_venue.entry(event.venue_id).or_insert(0) += 1;
+        }
+        let mut events_per_venue: Vec<VenueEventCount> = venues
+            .iter()
+            .filter_map(|v| v.id.map(|id| (id, v)))
+            .map(|(id, v)| VenueEventCount { venue_id: id, name: v.name.clone(), events: by_venue.get(&id).copied().unwrap_or(0) })
+            .collect();
+        events_per_venue.sort_by(|a, b| b.events.cmp(&a.events).then_with(|| a.name.cmp(&b.name)));
+
+        let upcoming_events = events.iter().filter(|e| e.event_day >= today).count();
+        let recent_events: Vec<&Event> = events.iter().filter(|e| e.created_at >= since).collect();
+        let recent = RecentCounts {
+            venues: venues.iter().filter(|v| v.created_at >= since).count(),
+            artists: artists.iter().filter(|a| a.created_at >= since).count(),
+            events: recent_events.len(),
+        };
+
+        let mut known: BTreeSet<&str> = sources.iter().map(String::as_str).collect();
+        known.extend(events.iter().flat_map(|e| e.attributions.iter().map(|a| a.source_id.as_str())));
+        let active: BTreeSet<&str> = recent_events
+            .iter()
+            .flat_map(|e| e.attributions.iter().map(|a| a.source_id.as_str()))
+            .collect();
+        let quiet_sources = known.difference(&active).map(|s| s.to_string()).collect();
+
+        CatalogStats {
+            generated_at: now,
+            venues: venues.len(),
+            artists: artists.len(),
+            events: events.len(),
+            events_per_venue,
+            upcoming_events,
+            past_events: events.len() - upcoming_events,
+            recent_days,
+            recent,
+            quiet_sources,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sms_core::domain::Attribution;
+
+    fn venue(name: &str) -> Venue {
+        Venue { id: Some(Uuid::new_v4()), created_at: Utc::now() - Duration::days(30), ..Venue::fixture(name) }
+    }
+
+    fn event(venue_id: Uuid, day: chrono::NaiveDate, created_at: DateTime<Utc>, source_id: &str) -> Event {
+        Event {
+            id: Some(Uuid::new_v4()),
+            created_at,
+            attributions: vec![Attribution { source_id: source_id.to_string(), license_id: "test".to_string(), text: None }],
+            ..Event::fixture("Show", day, venue_id)
+        }
+    }
+
+    #[test]
+    fn summarizes_venues_dates_and_quiet_sources() {
+        let now = Utc::now();
+        let today = now.date_naive();
+        let neumos = venue("Neumos");
+        let barboza = venue("Barboza");
+        let events = vec![
+            event(neumos.id.unwrap(), today + Duration::days(3), now - Duration::days(1), "neumos"),
+            event(neumos.id.unwrap(), today - Duration::days(3), now - Duration::days(2), "neumos"),
+            event(barboza.id.unwrap(), today - Duration::days(20), now - Duration::days(20), "barboza"),
+        ];
+        let sources = vec!["neumos".to_string(), "kexp".to_string()];
+
+        let stats = CatalogStats::summarize(&[barboza, neumos], &[], &events, &sources, 7, now);
+
+        assert_eq!((stats.venues, stats.events), (2, 3));
+        assert_eq!(stats.events_per_venue[0].name, "Neumos");
+        assert_eq!(stats.events_per_venue[0].events, 2);
+        assert_eq!((stats.upcoming_events, stats.past_events), (1, 2));
+        assert_eq!(stats.recent, RecentCounts { venues: 0, artists: 0, events: 2 });
+        assert_eq!(stats.quiet_sources, vec!["barboza".to_string(), "kexp".to_string()]);
+    }
+}
@@ -0,0 +1,192 @@
+use anyhow::{anyhow, Context, Result};
+use serde::Deserialize;
+use std::path::Path;
+
+use sms_core::domain::Event;
+
+/// Keyword rules shipped with the binary
+pub(crate) const BUNDLED_RULES: &str = include_str!("../../../assets/event_tag_rules.toml");
+
+/// Env var pointing at a TOML rules file to use instead of the bundled rules; `off` disables tagging
+pub const EVENT_TAG_RULES_ENV: &str = "SMS_EVENT_TAG_RULES";
+
+/// One tag and the keywords that earn it
+#[derive(Debug, Clone, Deserialize)]
+pub struct TagRule {
+    pub tag: String,
+    pub keywords: Vec<String>,
+    /// Only match the title; for tags whose keywords turn up in unrelated description copy
+    #[serde(default)]
+    pub title_only: bool,
+}
+
+#[derive(Debug, Deserialize)]
+struct RulesFile {
+    #[serde(default, rename = "rule")]
+    rules: Vec<TagRule>,
+}
+
+/// Assigns genre/category tags to events from title and description keyword rules
+#[derive(Debug, Clone)]
+pub struct EventClassifier {
+    rules: Vec<TagRule>,
+}
+
+impl EventClassifier {
+    /// The rules named by `SMS_EVENT_TAG_RULES`, else the bundled rules; `None` when the
+    /// variable is `off`
+    pub fn from_env_or_bundled() -> Result<Option<Self>> {
+        match std::env::var(EVENT_TAG_RULES_ENV) {
+            Ok(value) if value.trim().eq_ignore_ascii_case("off") => Ok(None),
+            Ok(path) if !path.trim().is_empty() => Self::from_path(path.trim()).map(Some),
+            _ => Self::bundled().map(Some),
+        }
+    }
+
+    pub fn bundled() -> Result<Self> {
+        Self::from_toml_str(BUNDLED_RULES)
+    }
+
+    pub fn from_path<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let path = path.as_ref();
+        let raw = std::fs::read_to_string(path)
+            .with_context(|| format!("reading event tag rules {}", path.display()))?;
+        Self::from_toml_str(&raw).with_context(|| format!("parsing event tag rules {}", path.display()))
+    }
+
+    /// Parse `[[rule]]` tables of `tag`, `keywords` and optional `title_only`
+    pub fn from_toml_str(raw: &str) -> Result<Self> {
+        let file: RulesFile = toml::from_str(raw)?;
+        let mut rules = Vec::with_capacity(file.rules.len());
+        for mut rule in file.rules {
+            rule.tag = rule.tag.trim().to_lowercase();
+            if rule.tag.is_empty() {
+                return Err(anyhow!("a rule has an empty tag"));
+            }
+            rule.keywords = rule.keywords.iter().map(|k| words(k)).filter(|k| !k.trim().is_empty()).collect();
+            if rule.keywords.is_empty() {
+                return Err(anyhow!("rule {} has no keywords", rule.tag));
+            }
+            rules.push(rule);
+        }
+        Ok(Self { rules })
+    }
+
+    pub fn len(&self) -> usize {
+        self.rules.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    /// Tags whose keywords appear as whole words in the title or description, in rule order
+    pub fn classify(&self, title: &str, description: Option<&str>) -> Vec<String> {
+        let title = words(title);
+        let description = description.map(words).unwrap_or_default();
+        let mut tags: Vec<String> = Vec::new();
+        for rule in &self.rules {
+            let hit = rule
+                .keywords
+                .iter()
+                .any(|k| title.contains(k.as_str()) || (!rule.title_only && description.contains(k.as_str())));
+            if hit && !tags.contains(&rule.tag) {
+                tags.push(rule.tag.clone());
+            }
+        }
+        tags
+    }
+
+    /// Add the event's classified tags to the ones it already has. Returns whether any were added.
+    pub fn tag_event(&self, event: &mut Event) -> bool {
+        let mut added = false;
+        for tag in self.classify(&event.title, event.description.as_deref()) {
+            if !event.has_tag(&tag) {
+                event.tags.push(tag);
+                added = true;
+            }
+        }
+        added
+    }
+}
+
+/// Lowercase alphanumeric words separated and surrounded by single spaces, so a substring
+/// search for a keyword prepared the same way only matches whole words
+fn words(text: &str) -> String {
+    let mut out = String::with_capacity(text.len() + 2);
+    out.push(' ');
+    for word in text.split(|c: char| !c.is_alphanumeric()).filter(|w| !w.is_empty()) {
+        out.push_str(&word.to_lowercase());
+        out.push(' ');
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keywords_match_whole_words_and_title_only_rules_skip_descriptions() {
+        let classifier = EventClassifier::from_toml_str(
+            r#"
+            [[rule]]
+            tag = "DJ"
+            keywords = ["dj", "Dance Party"]
+            title_only = true
+
+            [[rule]]
+            tag = "open_mic"
+            keywords = ["open mic"]
+
+            [[rule]]
+            tag = "trivia"
+            keywords = ["trivia"]
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(classifier.classify("Open-Mic Night w/ DJ Shadow", None), ["dj", "open_mic"]);
+        assert_eq!(classifier.classify("80s DANCE PARTY!", Some("Trivia at 7")), ["dj", "trivia"]);
+        // "djembe" and a DJ mentioned only in the description are not DJ nights
+        assert!(classifier.classify("Djembe circle", Some("Resident DJ after the show")).is_empty());
+    }
+
+    #[test]
+    fn tagging_keeps_existing_tags_and_reports_additions() {
+        let classifier = EventClassifier::bundled().unwrap();
+        assert!(!classifier.is_empty());
+        let mut event = Event {
+            id: None,
+            title: "Tuesday Trivia".to_string(),
+            event_day: chrono::NaiveDate::from_ymd_opt(2025, 5, 6).unwrap(),
+            start_time: None,
+            event_url: None,
+            description: Some("Pub quiz, then karaoke".to_string()),
+            event_image_url: None,
+            venue_id: uuid::Uuid::new_v4(),
+            artist_ids: Vec::new(),
+            show_event: true,
+            finalized: false,
+            created_at: chrono::Utc::now(),
+            attributions: Vec::new(),
+            tags: vec!["Trivia".to_string()],
+            price: None,
+            doors_time: None,
+            series_id: None,
+            age_restriction: None,
+            accessibility_notes: None,
+            moderation: None,
+            external_ids: Vec::new(),
+        };
+        assert!(classifier.tag_event(&mut event));
+        assert_eq!(event.tags, ["Trivia", "karaoke"]);
+        assert!(!classifier.tag_event(&mut event));
+    }
+
+    #[test]
+    fn rules_without_keywords_are_rejected() {
+        assert!(EventClassifier::from_toml_str("[[rule]]\ntag = \"rock\"\nkeywords = []\n").is_err());
+        assert!(EventClassifier::from_toml_str("[[rule]]\ntag = \" \"\nkeywords = [\"rock\"]\n").is_err());
+    }
+}
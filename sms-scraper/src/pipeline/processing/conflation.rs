@@ -1,116 +1,27 @@
-use chrono::{DateTime, Utc};
+use chrono::Utc;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::sync::Arc;
+use sms_core::common::record_key::{RecordKey, SourceKey};
 use uuid::Uuid;
 
 use crate::pipeline::processing::enrich::EnrichedRecord;
+use crate::pipeline::processing::resolution_index::ResolutionIndex;
+use crate::pipeline::processing::venue_resolver::VenueResolver;
 
-/// A conflated record that represents a stable canonical entity after entity resolution
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct ConflatedRecord {
-    /// The canonical entity ID that this record resolves to
-    pub canonical_entity_id: EntityId,
-    /// The enriched record that was processed
-    pub enriched_record: EnrichedRecord,
-    /// Conflation metadata about the resolution process
-    pub conflation: ConflationMetadata,
-    /// When this conflation was performed
-    pub conflated_at: DateTime<Utc>,
-}
-
-/// A stable, durable entity identifier that persists across data updates
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
-pub struct EntityId {
-    /// The unique identifier for this canonical entity
-    pub id: Uuid,
-    /// The type of entity (venue, event, artist)
-    pub entity_type: EntityType,
-    /// Version of this entity (increments with updates)
-    pub version: u64,
-}
-
-/// Types of entities that can be conflated
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
-pub enum EntityType {
-    Venue,
-    Event,
-    Artist,
-}
-
-/// Metadata about the conflation process and entity resolution decisions
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct ConflationMetadata {
-    /// The resolution decision made during conflation
-    pub resolution_decision: ResolutionDecision,
-    /// Confidence in the entity resolution (0.0 to 1.0)
-    pub confidence: f64,
-    /// The strategy/algorithm used for conflation
-    pub strategy: String,
-    /// Alternative entities that were considered but not matched
-    pub alternatives: Vec<AlternativeMatch>,
-    /// Previous entity ID if this is an update to existing entity
-    pub previous_entity_id: Option<EntityId>,
-    /// Source identifiers that contributed to this canonical entity
-    pub contributing_sources: Vec<String>,
-    /// Similarity scores with matched entities
-    pub similarity_scores: HashMap<String, f64>,
-    /// Warnings or notes from the conflation process
-    pub warnings: Vec<String>,
-    /// Deduplication metadata
-    pub deduplication: DeduplicationMetadata,
-}
-
-/// The decision made during entity resolution
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
-pub enum ResolutionDecision {
-    /// This is a new, previously unseen entity
-    NewEntity,
-    /// This matches an existing canonical entity (ID provided)
-    MatchedExisting(EntityId),
-    /// This updates an existing entity with new information
-    UpdatedExisting(EntityId),
-    /// This is a duplicate of an existing entity (no new information)
-    Duplicate(EntityId),
-    /// Conflation was uncertain - manual review may be needed
-    Uncertain,
-}
-
-/// Information about alternative matches that were considered
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct AlternativeMatch {
-    /// The entity ID that was considered as a match
-    pub entity_id: EntityId,
-    /// Similarity score with this alternative (0.0 to 1.0)
-    pub similarity_score: f64,
-    /// Reason this alternative was not selected
-    pub rejection_reason: String,
-}
-
-/// Metadata about deduplication analysis
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct DeduplicationMetadata {
-    /// Whether this record was identified as a potential duplicate
-    pub is_potential_duplicate: bool,
-    /// Entity IDs of potential duplicates
-    pub potential_duplicates: Vec<EntityId>,
-    /// The deduplication strategy used
-    pub deduplication_strategy: String,
-    /// Key attributes used for deduplication matching
-    pub key_attributes: Vec<String>,
-    /// Hash or signature used for duplicate detection
-    pub deduplication_signature: Option<String>,
-}
+pub use sms_core::pipeline_api::conflation::{
+    AlternativeMatch, ConflatedRecord, ConflationMetadata, ConflatorConfig, Conflator, DeduplicationMetadata,
+    EntityId, EntityType, PotentialMatch, ResolutionDecision, TieBreakStrategy, DEFAULT_VENUE_RADIUS_M,
+};
 
 /// Conflation configuration and matching rules
 #[derive(Debug, Clone)]
 pub struct ConflationConfig {
-    /// Minimum confidence threshold for automatic matching
-    pub min_confidence_threshold: f64,
+    /// Per-entity-type thresholds for automatic matching and the tie-break strategy
+    pub thresholds: ConflatorConfig,
     /// Matching strategies enabled (currently unused)
     #[allow(dead_code)]
     pub enabled_strategies: Vec<MatchingStrategy>,
-    /// Maximum distance in km for venue location matching
-    pub max_venue_distance_km: f64,
     /// Maximum time difference for event matching (in hours)
     pub max_event_time_diff_hours: i64,
 /// Similarity threshold for text matching (names, descriptions) (currently unused)
@@ -133,33 +44,6 @@ pub enum MatchingStrategy {
     CompositeScore,
 }
 
-/// Trait for performing entity conflation and resolution
-pub trait Conflator {
-    /// Conflate an enriched record, resolving it to a canonical entity
-    fn conflate(&self, record: &EnrichedRecord) -> anyhow::Result<ConflatedRecord>;
-    
-    /// Find potential matches for an enriched record
-    fn find_potential_matches(&self, record: &EnrichedRecord) -> anyhow::Result<Vec<PotentialMatch>>;
-    
-    /// Calculate similarity between two records
-    fn calculate_similarity(&self, record1: &EnrichedRecord, record2: &EnrichedRecord) -> f64;
-}
-
-/// A potential match found during conflation
-#[derive(Debug, Clone)]
-pub struct PotentialMatch {
-    /// The entity ID of the potential match
-    pub entity_id: EntityId,
-    /// Similarity score (0.0 to 1.0)
-    pub similarity_score: f64,
-    /// Breakdown of similarity by attribute (currently unused)
-    #[allow(dead_code)]
-    pub similarity_breakdown: HashMap<String, f64>,
-    /// The enriched record of the potential match (currently unused)
-    #[allow(dead_code)]
-    pub matched_record: EnrichedRecord,
-}
-
 /// Default conflator implementation for Seattle music venues
 pub struct DefaultConflator {
     /// Configuration for conflation behavior
@@ -170,26 +54,38 @@ pub struct DefaultConflator {
     /// Lookup index for fast matching by key attributes
     pub name_index: HashMap<String, Vec<EntityId>>,
     pub location_index: HashMap<String, Vec<EntityId>>,
+    /// Canonical entities each remembered source record resolved to; one record can yield
+    /// an event along with its venue and artists
+    pub record_index: HashMap<RecordKey, Vec<EntityId>>,
+    /// Events by (source_id, the source's own id for them), matched before any fuzzy matching
+    pub external_index: HashMap<(String, String), EntityId>,
+    /// Durable source key → canonical id mappings from previous runs
+    pub resolution_index: Option<Arc<ResolutionIndex>>,
+    /// Known venues, so two listings of one venue under different names still match
+    pub venue_resolver: VenueResolver,
 }
 
 impl Default for DefaultConflator {
     fn default() -> Self {
         Self {
             config: ConflationConfig {
-                min_confidence_threshold: 0.8,
+                thresholds: ConflatorConfig::default(),
                 enabled_strategies: vec![
                     MatchingStrategy::ExactMatch,
                     MatchingStrategy::FuzzyTextMatch,
                     MatchingStrategy::LocationProximity,
                     MatchingStrategy::CompositeScore,
                 ],
-                max_venue_distance_km: 0.1, // 100 meters
                 max_event_time_diff_hours: 2,
                 text_similarity_threshold: 0.85,
             },
             entity_store: HashMap::new(),
             name_index: HashMap::new(),
             location_index: HashMap::new(),
+            record_index: HashMap::new(),
+            external_index: HashMap::new(),
+            resolution_index: None,
+            venue_resolver: VenueResolver::default(),
         }
     }
 }
@@ -200,6 +96,191 @@ impl DefaultConflator {
         Self::default()
     }
 
+    /// Use the given thresholds and tie-break strategy instead of the defaults
+    pub fn with_config(mut self, config: ConflatorConfig) -> Self {
+        self.venue_resolver = self.venue_resolver.with_radius_m(config.venue_radius_m);
+        self.config.thresholds = config;
+        self
+    }
+
+    /// Whether two artist names are similar enough to be the same artist under the configured
+    /// artist threshold
+    pub fn same_artist_name(&self, name1: &str, name2: &str) -> bool {
+        self.calculate_text_similarity(name1, name2) >= self.config.thresholds.artist_threshold
+    }
+
+    /// The canonical entities a remembered source record resolved to
+    pub fn entities_for(&self, key: &RecordKey) -> &[EntityId] {
+        self.record_index.get(key).map(Vec::as_slice).unwrap_or_default()
+    }
+
+    /// Reuse canonical ids assigned in earlier runs instead of minting new ones
+    pub fn with_resolution_index(mut self, index: Arc<ResolutionIndex>) -> Self {
+        self.resolution_index = Some(index);
+        self
+    }
+
+    /// Add a conflated record to the known entities, indexed by name and (for venues)
+    /// location, so later records can match it
+    pub fn remember(&mut self, conflated: ConflatedRecord) {
+        let entity_id = conflated.canonical_entity_id.clone();
+        let key = conflated.enriched_record.quality_assessed_record.normalized_record.provenance.key();
+        let resolved = self.record_index.entry(key).or_default();
+        if !resolved.contains(&entity_id) {
+            resolved.push(entity_id.clone());
+        }
+        for name_key in self.name_keys(&conflated.enriched_record) {
+            self.name_index.entry(name_key).or_default().push(entity_id.clone());
+        }
+        if let Some(location_key) = self.extract_location_key(&conflated.enriched_record) {
+            self.location_index.entry(location_key).or_default().push(entity_id.clone());
+        }
+        if let Some((source_id, external_id)) = Self::external_id(&conflated.enriched_record) {
+            self.external_index.insert((source_id.to_string(), external_id.to_string()), entity_id.clone());
+        }
+        self.entity_store.insert(entity_id, conflated);
+    }
+
+    /// Remember an entity already cataloged under `id`, so listings of it this run match it
+    /// instead of minting a new one
+    pub fn remember_cataloged(&mut self, record: EnrichedRecord, id: Uuid) {
+        let entity_id = EntityId { id, entity_type: self.determine_entity_type(&record), version: 1 };
+        let contributing_sources = vec![record.quality_assessed_record.normalized_record.provenance.source_id.clone()];
+        self.remember(ConflatedRecord {
+            canonical_entity_id: entity_id.clone(),
+            enriched_record: record,
+            conflation: ConflationMetadata {
+                resolution_decision: ResolutionDecision::MatchedExisting(entity_id),
+                confidence: 1.0,
+                strategy: "cataloged".to_string(),
+                alternatives: Vec::new(),
+                previous_entity_id: None,
+                contributing_sources,
+                similarity_scores: HashMap::new(),
+                warnings: Vec::new(),
+                deduplication: DeduplicationMetadata {
+                    is_potential_duplicate: false,
+                    potential_duplicates: Vec::new(),
+                    deduplication_strategy: "cataloged".to_string(),
+                    key_attributes: Vec::new(),
+                    deduplication_signature: None,
+                },
+                config: None,
+            },
+            conflated_at: Utc::now(),
+        });
+    }
+
+    /// (source_id, the source's own id) for an event record whose parser extracted one. A
+    /// record's venue and artists share its provenance, so only events are keyed by it.
+    fn external_id(record: &EnrichedRecord) -> Option<(&str, &str)> {
+        use crate::pipeline::processing::normalize::NormalizedEntity;
+
+        let normalized = &record.quality_assessed_record.normalized_record;
+        match &normalized.entity {
+            NormalizedEntity::Event(_) => {
+                Some((normalized.provenance.source_id.as_str(), normalized.provenance.external_id.as_deref()?))
+            }
+            _ => None,
+        }
+    }
+
+    /// The entity of this record's type that the same source record resolved to earlier this
+    /// run, e.g. when a reprocessing pass hands the record over again. Artists are left out:
+    /// one record names several of them, so its key can't identify any one.
+    fn match_record_key(&self, record: &EnrichedRecord, entity_type: &EntityType) -> Option<EntityId> {
+        if *entity_type == EntityType::Artist {
+            return None;
+        }
+        let key = record.quality_assessed_record.normalized_record.provenance.key();
+        self.entities_for(&key).iter().find(|id| id.entity_type == *entity_type).cloned()
+    }
+
+    /// An event already seen this run under the same source id
+    fn match_external_id(&self, record: &EnrichedRecord) -> Option<EntityId> {
+        let (source_id, external_id) = Self::external_id(record)?;
+        self.external_index.get(&(source_id.to_string(), external_id.to_string())).cloned()
+    }
+
+    /// Resolution index keys for a record, most specific first: the source's own id when it
+    /// has one, then the key derived from its content
+    fn resolution_keys(&self, record: &EnrichedRecord) -> Vec<SourceKey> {
+        let source_id = &record.quality_assessed_record.normalized_record.provenance.source_id;
+        let mut keys: Vec<SourceKey> =
+            Self::external_id(record).map(|(_, id)| SourceKey::new(source_id, format!("id:{}", id))).into_iter().collect();
+        keys.push(SourceKey::new(source_id, self.external_key(record)));
+        keys
+    }
+
+    /// The source's own identity for a record, stable across fetches
+    fn external_key(&self, record: &EnrichedRecord) -> String {
+        use crate::pipeline::processing::normalize::NormalizedEntity;
+
+        match &record.quality_assessed_record.normalized_record.entity {
+            NormalizedEntity::Venue(venue) if !venue.slug.is_empty() => venue.slug.clone(),
+            NormalizedEntity::Venue(venue) => self.normalize_name(&venue.name),
+            NormalizedEntity::Artist(artist) if !artist.name_slug.is_empty() => artist.name_slug.clone(),
+            NormalizedEntity::Artist(artist) => self.normalize_name(&artist.name),
+            NormalizedEntity::Event(event) => format!("{}|{}", event.event_day, self.normalize_name(&event.title)),
+        }
+    }
+
+    /// Canonical id this source's record resolved to in a previous run
+    fn lookup_resolved_id(&self, record: &EnrichedRecord, entity_type: &EntityType) -> Option<EntityId> {
+        let index = self.resolution_index.as_ref()?;
+        self.resolution_keys(record).iter().find_map(|key| match index.lookup(key, entity_type) {
+            Ok(id) => id.map(|id| EntityId { id, entity_type: entity_type.clone(), version: 1 }),
+            Err(e) => {
+                tracing::warn!("resolution index lookup failed for {}: {}", key, e);
+                None
+            }
+        })
+    }
+
+    /// The id a curator merged `id` into, or `id` itself when it wasn't merged away
+    fn surviving_id(&self, entity_type: &EntityType, id: Uuid) -> Uuid {
+        let Some(index) = &self.resolution_index else { return id };
+        match index.merged_into(entity_type, id) {
+            Ok(kept) => kept.unwrap_or(id),
+            Err(e) => {
+                tracing::warn!("merge tombstone lookup failed for {}: {}", id, e);
+                id
+            }
+        }
+    }
+
+    /// The record with an event's venue and artists pointing at the entities they were merged into
+    fn with_merges_applied(&self, record: &EnrichedRecord) -> EnrichedRecord {
+        use crate::pipeline::processing::normalize::NormalizedEntity;
+
+        let mut record = record.clone();
+        if let NormalizedEntity::Event(event) = &mut record.quality_assessed_record.normalized_record.entity {
+            if !event.venue_id.is_nil() {
+                event.venue_id = self.surviving_id(&EntityType::Venue, event.venue_id);
+            }
+            let mut artist_ids = Vec::with_capacity(event.artist_ids.len());
+            for id in &event.artist_ids {
+                let id = self.surviving_id(&EntityType::Artist, *id);
+                if !artist_ids.contains(&id) {
+                    artist_ids.push(id);
+                }
+            }
+            event.artist_ids = artist_ids;
+        }
+        record
+    }
+
+    /// Point the record's resolution keys at `entity_id`, e.g. once it was cataloged as an
+    /// entity other than the one conflation picked
+    pub fn remember_resolution(&self, record: &EnrichedRecord, entity_id: &EntityId) {
+        let Some(index) = &self.resolution_index else { return };
+        for key in self.resolution_keys(record) {
+            if let Err(e) = index.record(&key, &entity_id.entity_type, entity_id.id, Utc::now().timestamp()) {
+                tracing::warn!("resolution index update failed for {}: {}", key, e);
+            }
+        }
+    }
+
     /// Extract entity name from enriched record
     fn extract_entity_name(&self, record: &EnrichedRecord) -> Option<String> {
         use crate::pipeline::processing::normalize::NormalizedEntity;
@@ -211,6 +292,23 @@ impl DefaultConflator {
         }
     }
     
+    /// Name index keys for a record: its own name and, for a known venue, the venue's
+    /// canonical name, so aliases of one venue land on the same key
+    fn name_keys(&self, record: &EnrichedRecord) -> Vec<String> {
+        use crate::pipeline::processing::normalize::NormalizedEntity;
+
+        let mut keys: Vec<String> = self.extract_entity_name(record).map(|n| self.normalize_name(&n)).into_iter().collect();
+        if let NormalizedEntity::Venue(venue) = &record.quality_assessed_record.normalized_record.entity {
+            if let Some(known) = self.venue_resolver.resolve_venue(venue) {
+                let key = self.normalize_name(known.name);
+                if !keys.contains(&key) {
+                    keys.push(key);
+                }
+            }
+        }
+        keys
+    }
+
     /// Extract location key for indexing venues
     fn extract_location_key(&self, record: &EnrichedRecord) -> Option<String> {
         use crate::pipeline::processing::normalize::NormalizedEntity;
@@ -258,22 +356,49 @@ impl DefaultConflator {
         intersection as f64 / union as f64
     }
     
+    /// Venue names compared both ways and ignoring punctuation, so "Neumo's" matches
+    /// "Neumos" and a name contained in a longer listing of it scores high
+    fn calculate_venue_name_similarity(&self, name1: &str, name2: &str) -> f64 {
+        VenueResolver::name_similarity(name1, name2).max(self.calculate_text_similarity(name1, name2))
+    }
+
     /// Calculate distance between two coordinates in kilometers
     fn calculate_distance(&self, lat1: f64, lng1: f64, lat2: f64, lng2: f64) -> f64 {
-        // Simple Euclidean distance for small distances (good enough for city-scale)
-        let lat_diff = (lat1 - lat2) * 111.0; // ~111km per degree latitude
-        let lng_diff = (lng1 - lng2) * 85.0;  // ~85km per degree longitude at Seattle's latitude
-        (lat_diff * lat_diff + lng_diff * lng_diff).sqrt()
+        sms_core::common::geo::haversine_km(lat1, lng1, lat2, lng2)
     }
-    
-    /// Generate a new canonical entity ID
-    fn generate_entity_id(&self, entity_type: EntityType) -> EntityId {
-        EntityId {
-            id: Uuid::new_v4(),
-            entity_type,
-            version: 1,
+
+    /// How close two venues are: 1.0 inside the geofence radius, falling to 0.0 at twice it
+    fn calculate_proximity(&self, distance_km: f64) -> f64 {
+        let radius_km = self.config.thresholds.venue_radius_m / 1000.0;
+        if radius_km <= 0.0 {
+            return if distance_km == 0.0 { 1.0 } else { 0.0 };
+        }
+        if distance_km <= radius_km {
+            1.0
+        } else {
+            (2.0 - distance_km / radius_km).max(0.0)
         }
     }
+
+    /// Location index keys of every cell that may hold a venue within proximity range
+    /// (twice the geofence radius) of the given point
+    fn nearby_location_keys(&self, lat: f64, lng: f64) -> Vec<String> {
+        // Cells are 0.001 degrees: ~111 m of latitude, and less longitude away from the equator
+        let reach_km = 2.0 * self.config.thresholds.venue_radius_m / 1000.0;
+        let lat_cell_km = 0.111;
+        let lng_cell_km = (0.111 * lat.to_radians().cos()).max(0.001);
+        let lat_span = (reach_km / lat_cell_km).ceil() as i32;
+        let lng_span = (reach_km / lng_cell_km).ceil() as i32;
+        let (lat_key, lng_key) = ((lat * 1000.0).round() as i32, (lng * 1000.0).round() as i32);
+
+        let mut keys = Vec::new();
+        for dlat in -lat_span..=lat_span {
+            for dlng in -lng_span..=lng_span {
+                keys.push(format!("{}_{}", lat_key + dlat, lng_key + dlng));
+            }
+        }
+        keys
+    }
     
     /// Generate entity ID, preserving existing ID if present in normalized entity
     fn generate_entity_id_from_record(&self, record: &EnrichedRecord) -> EntityId {
@@ -346,6 +471,7 @@ impl Conflator for DefaultConflator {
 
         let conflated_at = Utc::now();
         let entity_type = self.determine_entity_type(record);
+        let threshold = self.config.thresholds.threshold_for(&entity_type);
         let mut warnings = Vec::new();
         
         // Find potential matches
@@ -359,18 +485,41 @@ impl Conflator for DefaultConflator {
         };
         
         // Determine resolution decision based on potential matches
-        let (resolution_decision, canonical_entity_id, confidence) = if potential_matches.is_empty() {
+        let known_id = self
+            .match_record_key(record, &entity_type)
+            .or_else(|| self.match_external_id(record))
+            .or_else(|| self.lookup_resolved_id(record, &entity_type));
+        let (mut resolution_decision, mut canonical_entity_id, confidence) = if let Some(known_id) = known_id {
+            // Seen from this source under the same id, this run or before - keep the id it was given then
+            (ResolutionDecision::MatchedExisting(known_id.clone()), known_id, 1.0)
+        } else if potential_matches.is_empty() {
             // No matches found - create new entity, preserving ID if already set
             let new_id = self.generate_entity_id_from_record(record);
             (ResolutionDecision::NewEntity, new_id, 1.0)
         } else {
-            // Find best match
-            let best_match = potential_matches
+            // Find best match, breaking ties between equally similar candidates per the config
+            let best_score = potential_matches
                 .iter()
-                .max_by(|a, b| a.similarity_score.partial_cmp(&b.similarity_score).unwrap())
-                .unwrap();
-                
-            if best_match.similarity_score >= self.config.min_confidence_threshold {
+                .map(|m| m.similarity_score)
+                .fold(f64::MIN, f64::max);
+            let tied: Vec<&PotentialMatch> = potential_matches
+                .iter()
+                .filter(|m| (m.similarity_score - best_score).abs() < f64::EPSILON)
+                .collect();
+            let best_match = tied.iter().min_by_key(|m| m.entity_id.id).copied().unwrap();
+
+            if best_match.similarity_score >= threshold
+                && tied.len() > 1
+                && self.config.thresholds.tie_break == TieBreakStrategy::Uncertain
+            {
+                let new_id = self.generate_entity_id_from_record(record);
+                warnings.push(format!(
+                    "{} candidates tied at {:.2}; left unresolved for review",
+                    tied.len(),
+                    best_score
+                ));
+                (ResolutionDecision::Uncertain, new_id, best_score)
+            } else if best_match.similarity_score >= threshold {
                 // High confidence match
                 (
                     ResolutionDecision::MatchedExisting(best_match.entity_id.clone()),
@@ -387,7 +536,16 @@ impl Conflator for DefaultConflator {
                 (ResolutionDecision::NewEntity, new_id, 0.6)
             }
         };
+
+        // An id a curator merged away resolves to the entity it was merged into
+        let surviving_id = self.surviving_id(&entity_type, canonical_entity_id.id);
+        if surviving_id != canonical_entity_id.id {
+            canonical_entity_id.id = surviving_id;
+            resolution_decision = ResolutionDecision::MatchedExisting(canonical_entity_id.clone());
+        }
         
+        self.remember_resolution(record, &canonical_entity_id);
+
         // Create alternatives list
         let alternatives: Vec<AlternativeMatch> = potential_matches
             .iter()
@@ -395,7 +553,7 @@ impl Conflator for DefaultConflator {
             .map(|m| AlternativeMatch {
                 entity_id: m.entity_id.clone(),
                 similarity_score: m.similarity_score,
-                rejection_reason: if m.similarity_score < self.config.min_confidence_threshold {
+                rejection_reason: if m.similarity_score < threshold {
                     "Similarity score below threshold".to_string()
                 } else {
                     "Lower similarity than selected match".to_string()
@@ -441,6 +599,7 @@ impl Conflator for DefaultConflator {
             similarity_scores,
             warnings: warnings.clone(),
             deduplication: deduplication.clone(),
+            config: Some(self.config.thresholds.clone()),
         };
 
         // Per-record metrics after decision
@@ -463,24 +622,28 @@ impl Conflator for DefaultConflator {
         
         Ok(ConflatedRecord {
             canonical_entity_id,
-            enriched_record: record.clone(),
+            enriched_record: self.with_merges_applied(record),
             conflation,
             conflated_at,
         })
     }
     
     fn find_potential_matches(&self, record: &EnrichedRecord) -> anyhow::Result<Vec<PotentialMatch>> {
+        use crate::pipeline::processing::normalize::NormalizedEntity;
+
         let mut potential_matches = Vec::new();
         
         // Get entity name for matching
         let entity_name = self.extract_entity_name(record);
         
-        // Find matches by name
+        // Find matches by name (and, for known venues, by the venue's canonical name)
         if let Some(name) = entity_name {
-            let normalized_name = self.normalize_name(&name);
-            
-            if let Some(entity_ids) = self.name_index.get(&normalized_name) {
+            for name_key in self.name_keys(record) {
+                let Some(entity_ids) = self.name_index.get(&name_key) else { continue };
                 for entity_id in entity_ids {
+                    if potential_matches.iter().any(|m: &PotentialMatch| m.entity_id == *entity_id) {
+                        continue;
+                    }
                     if let Some(canonical_record) = self.entity_store.get(entity_id) {
                         let similarity_score = self.calculate_similarity(record, &canonical_record.enriched_record);
                         
@@ -502,30 +665,47 @@ impl Conflator for DefaultConflator {
             }
         }
         
-        // Find matches by location (for venues)
-        if self.determine_entity_type(record) == EntityType::Venue {
-            if let Some(location_key) = self.extract_location_key(record) {
-                if let Some(entity_ids) = self.location_index.get(&location_key) {
-                    for entity_id in entity_ids {
-                        // Skip if already found by name
-                        if potential_matches.iter().any(|m| m.entity_id == *entity_id) {
+        // Find matches by location (for venues): anything within proximity range, even
+        // under a different name, is a candidate. A provisional venue's coordinates are a
+        // placeholder, so it only matches by name.
+        let located_venue = match &record.quality_assessed_record.normalized_record.entity {
+            NormalizedEntity::Venue(venue) if !venue.provisional => Some(venue),
+            _ => None,
+        };
+        if let Some(venue) = located_venue {
+            for location_key in self.nearby_location_keys(venue.latitude, venue.longitude) {
+                let Some(entity_ids) = self.location_index.get(&location_key) else { continue };
+                for entity_id in entity_ids {
+                    // Skip if already found by name
+                    if potential_matches.iter().any(|m| m.entity_id == *entity_id) {
+                        continue;
+                    }
+
+                    if let Some(canonical_record) = self.entity_store.get(entity_id) {
+                        let NormalizedEntity::Venue(known) =
+                            &canonical_record.enriched_record.quality_assessed_record.normalized_record.entity
+                        else {
+                            continue;
+                        };
+                        let distance_km =
+                            self.calculate_distance(venue.latitude, venue.longitude, known.latitude, known.longitude);
+                        let proximity = self.calculate_proximity(distance_km);
+                        if proximity == 0.0 {
                             continue;
                         }
-                        
-                        if let Some(canonical_record) = self.entity_store.get(entity_id) {
-                            let similarity_score = self.calculate_similarity(record, &canonical_record.enriched_record);
-                            
-                            if similarity_score > 0.5 { // Higher threshold for location-only matches
-                                let mut similarity_breakdown = HashMap::new();
-                                similarity_breakdown.insert("location".to_string(), similarity_score);
-                                
-                                potential_matches.push(PotentialMatch {
-                                    entity_id: entity_id.clone(),
-                                    similarity_score,
-                                    similarity_breakdown,
-                                    matched_record: canonical_record.enriched_record.clone(),
-                                });
-                            }
+                        let similarity_score = self.calculate_similarity(record, &canonical_record.enriched_record);
+
+                        if similarity_score > 0.5 { // Higher threshold for location-only matches
+                            let mut similarity_breakdown = HashMap::new();
+                            similarity_breakdown.insert("location".to_string(), proximity);
+                            similarity_breakdown.insert("distance_m".to_string(), distance_km * 1000.0);
+
+                            potential_matches.push(PotentialMatch {
+                                entity_id: entity_id.clone(),
+                                similarity_score,
+                                similarity_breakdown,
+                                matched_record: canonical_record.enriched_record.clone(),
+                            });
                         }
                     }
                 }
@@ -551,17 +731,23 @@ impl Conflator for DefaultConflator {
         
         match (entity1, entity2) {
             (NormalizedEntity::Venue(v1), NormalizedEntity::Venue(v2)) => {
-                let name_similarity = self.calculate_text_similarity(&v1.name, &v2.name);
+                // Both listings of a venue the resolver knows: confirmed, whatever the names say
+                if self.venue_resolver.same_venue(v1, v2) {
+                    return 1.0;
+                }
+                let name_similarity = self.calculate_venue_name_similarity(&v1.name, &v2.name);
+                // A provisional venue's coordinates and address are placeholders, not evidence
+                if v1.provisional || v2.provisional {
+                    return name_similarity;
+                }
                 let location_distance = self.calculate_distance(v1.latitude, v1.longitude, v2.latitude, v2.longitude);
-                let location_similarity = if location_distance <= self.config.max_venue_distance_km {
-                    1.0 - (location_distance / self.config.max_venue_distance_km).min(1.0)
-                } else {
-                    0.0
-                };
+                let location_similarity = self.calculate_proximity(location_distance);
                 
                 let address_similarity = self.calculate_text_similarity(&v1.address, &v2.address);
                 
-                // Weighted average: name (40%), location (40%), address (20%)
+                // Weighted average: name (40%), location (40%), address (20%). Location and
+                // address alone top out at 0.6, so venues sharing a building stay apart
+                // unless their names agree too.
                 (name_similarity * 0.4) + (location_similarity * 0.4) + (address_similarity * 0.2)
             }
             
@@ -601,12 +787,12 @@ impl Conflator for DefaultConflator {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use sms_core::domain::{Event, Venue, Artist};
+    use sms_core::domain::Venue;
     use crate::pipeline::processing::normalize::{NormalizedEntity, NormalizedRecord, NormalizationMetadata, RecordProvenance};
     use crate::pipeline::processing::quality_gate::{QualityAssessment, QualityDecision};
     use crate::pipeline::processing::enrich::{EnrichmentMetadata, GeoProperties, PopulationDensity, ReferenceVersions};
-    use chrono::{NaiveDate, Utc};
-    use uuid::Uuid;
+    use chrono::Utc;
+    
 
     fn create_test_venue_record(name: &str, lat: f64, lng: f64) -> EnrichedRecord {
         let venue = Venue {
@@ -625,6 +811,12 @@ mod tests {
             neighborhood: None,
             show_venue: true,
             created_at: Utc::now(),
+            attributions: Vec::new(),
+            provisional: false,
+            aliases: Vec::new(),
+            age_restriction: None,
+            accessibility_notes: None,
+            moderation: None,
         };
 
         let normalized_record = NormalizedRecord {
@@ -633,8 +825,14 @@ mod tests {
                 envelope_id: "test_envelope".to_string(),
                 source_id: "test_source".to_string(),
                 payload_ref: "test_payload".to_string(),
-                record_path: "$.venues[0]".to_string(),
+                // One record per listed venue, as a source's venue list would have it
+                record_path: format!("$.venues.{}", name.to_lowercase().replace(" ", "-")),
                 normalized_at: Utc::now(),
+                attribution: None,
+                change: None,
+                record_key: None,
+                endpoint_id: None,
+                external_id: None,
             },
             normalization: NormalizationMetadata {
                 confidence: 1.0,
@@ -675,6 +873,7 @@ mod tests {
                 admin_boundaries: Some("test_v1".to_string()),
                 spatial_grid: Some("test_v1".to_string()),
                 poi_data: Some("test_v1".to_string()),
+                neighborhoods: None,
             },
             strategy: "test_enrichment".to_string(),
             confidence: 0.9,
@@ -700,6 +899,248 @@ mod tests {
         assert_eq!(result.canonical_entity_id.version, 1);
     }
 
+    #[test]
+    fn test_resolution_index_keeps_ids_stable_across_runs() {
+        let tmp = tempfile::tempdir().unwrap();
+        let record = create_test_venue_record("Test Venue", 47.6131, -122.3424);
+
+        let first_run = DefaultConflator::new()
+            .with_resolution_index(Arc::new(ResolutionIndex::open_at_root(tmp.path()).unwrap()));
+        let first = first_run.conflate(&record).unwrap();
+        assert_eq!(first.conflation.resolution_decision, ResolutionDecision::NewEntity);
+
+        let second_run = DefaultConflator::new()
+            .with_resolution_index(Arc::new(ResolutionIndex::open_at_root(tmp.path()).unwrap()));
+        let second = second_run.conflate(&record).unwrap();
+        assert_eq!(second.canonical_entity_id, first.canonical_entity_id);
+        assert_eq!(
+            second.conflation.resolution_decision,
+            ResolutionDecision::MatchedExisting(first.canonical_entity_id)
+        );
+    }
+
+    #[test]
+    fn test_merged_ids_resolve_to_the_kept_entity() {
+        let tmp = tempfile::tempdir().unwrap();
+        let index = Arc::new(ResolutionIndex::open_at_root(tmp.path()).unwrap());
+        let record = create_test_venue_record("Test Venue", 47.6131, -122.3424);
+        let first = DefaultConflator::new().with_resolution_index(index.clone()).conflate(&record).unwrap();
+
+        let (kept_venue, kept_artist, removed_artist) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+        index.record_merge(&EntityType::Venue, first.canonical_entity_id.id, kept_venue, 1).unwrap();
+        index.record_merge(&EntityType::Artist, removed_artist, kept_artist, 1).unwrap();
+
+        let conflator = DefaultConflator::new().with_resolution_index(index);
+        let again = conflator.conflate(&record).unwrap();
+        assert_eq!(again.canonical_entity_id.id, kept_venue);
+
+        // Events keep pointing at what their venue and artists were merged into
+        let mut event = event_record("Whitney Ballen", None);
+        if let NormalizedEntity::Event(e) = &mut event.quality_assessed_record.normalized_record.entity {
+            e.venue_id = first.canonical_entity_id.id;
+            e.artist_ids = vec![removed_artist, kept_artist];
+        }
+        let conflated = conflator.conflate(&event).unwrap();
+        let NormalizedEntity::Event(e) = &conflated.enriched_record.quality_assessed_record.normalized_record.entity else {
+            panic!("expected an event");
+        };
+        assert_eq!((e.venue_id, e.artist_ids.clone()), (kept_venue, vec![kept_artist]));
+    }
+
+    fn event_record(title: &str, external_id: Option<&str>) -> EnrichedRecord {
+        let mut record = create_test_venue_record("unused", 47.6131, -122.3424);
+        let normalized = &mut record.quality_assessed_record.normalized_record;
+        normalized.entity = NormalizedEntity::Event(sms_core::domain::Event {
+            id: None,
+            title: title.to_string(),
+            event_day: chrono::NaiveDate::from_ymd_opt(2025, 8, 15).unwrap(),
+            start_time: None,
+            event_url: None,
+            description: None,
+            event_image_url: None,
+            venue_id: Uuid::nil(),
+            artist_ids: Vec::new(),
+            show_event: true,
+            finalized: false,
+            created_at: Utc::now(),
+            attributions: Vec::new(),
+            tags: Vec::new(),
+            price: None,
+            doors_time: None,
+            series_id: None,
+            age_restriction: None,
+            accessibility_notes: None,
+            moderation: None,
+            external_ids: Vec::new(),
+        });
+        normalized.provenance.external_id = external_id.map(str::to_string);
+        record
+    }
+
+    #[test]
+    fn test_external_ids_match_before_fuzzy_matching() {
+        let tmp = tempfile::tempdir().unwrap();
+        let index = || Arc::new(ResolutionIndex::open_at_root(tmp.path()).unwrap());
+
+        let mut conflator = DefaultConflator::new().with_resolution_index(index());
+        let first = conflator.conflate(&event_record("Whitney Ballen", Some("412301"))).unwrap();
+        conflator.remember(first.clone());
+
+        // The source renames the listing: nothing fuzzy would match it, but its id does
+        let renamed = conflator.conflate(&event_record("Whitney Ballen w/ Bad Luck (SOLD OUT)", Some("412301"))).unwrap();
+        assert_eq!(renamed.canonical_entity_id, first.canonical_entity_id);
+        assert_eq!(renamed.conflation.confidence, 1.0);
+
+        // A later run finds it through the resolution index
+        let next_run = DefaultConflator::new().with_resolution_index(index());
+        let rerun = next_run.conflate(&event_record("Ballen, Whitney", Some("412301"))).unwrap();
+        assert_eq!(rerun.canonical_entity_id, first.canonical_entity_id);
+
+        let other = next_run.conflate(&event_record("Bluegrass Jam", Some("412355"))).unwrap();
+        assert_ne!(other.canonical_entity_id, first.canonical_entity_id);
+    }
+
+    #[test]
+    fn test_remembered_records_are_indexed_by_record_key() {
+        let mut conflator = DefaultConflator::new();
+        let record = create_test_venue_record("Test Venue", 47.6131, -122.3424);
+        let conflated = conflator.conflate(&record).unwrap();
+        let entity_id = conflated.canonical_entity_id.clone();
+        conflator.remember(conflated);
+
+        // The key the parse stage would have given the record
+        let key = RecordKey::new("test_source", "test_envelope", "$.venues.test-venue");
+        assert_eq!(conflator.entities_for(&key), std::slice::from_ref(&entity_id));
+        assert!(conflator.entities_for(&RecordKey::new("test_source", "test_envelope", "$.venues.other")).is_empty());
+
+        // The same source record handed over again resolves to the entity it did before,
+        // even once its listing no longer looks like it
+        let mut renamed = create_test_venue_record("Test Venue (Closed)", 40.0, -100.0);
+        renamed.quality_assessed_record.normalized_record.provenance = record.quality_assessed_record.normalized_record.provenance.clone();
+        let again = conflator.conflate(&renamed).unwrap();
+        assert_eq!(again.canonical_entity_id, entity_id);
+        assert!(matches!(again.conflation.resolution_decision, ResolutionDecision::MatchedExisting(_)));
+    }
+
+    #[test]
+    fn test_known_venue_aliases_confirm_a_match() {
+        use crate::pipeline::processing::venue_resolver::SUNSET_TAVERN;
+
+        let mut conflator = DefaultConflator::new();
+        let known = create_test_venue_record(SUNSET_TAVERN.name, SUNSET_TAVERN.latitude, SUNSET_TAVERN.longitude);
+        let conflated = conflator.conflate(&known).unwrap();
+        let entity_id = conflated.canonical_entity_id.clone();
+        conflator.remember(conflated);
+
+        // Another source's listing: an alias, geocoded ~80 m off
+        let listed = create_test_venue_record("The Sunset", SUNSET_TAVERN.latitude + 0.0007, SUNSET_TAVERN.longitude);
+        let result = conflator.conflate(&listed).unwrap();
+        assert_eq!(result.conflation.resolution_decision, ResolutionDecision::MatchedExisting(entity_id));
+
+        // Same name in another town stays separate
+        let elsewhere = create_test_venue_record("The Sunset", 47.2529, -122.4443);
+        let result = conflator.conflate(&elsewhere).unwrap();
+        assert_eq!(result.conflation.resolution_decision, ResolutionDecision::NewEntity);
+    }
+
+    #[test]
+    fn test_config_thresholds_and_tie_break() {
+        let record = create_test_venue_record("Test Venue", 47.6131, -122.3424);
+        let with_two_known_venues = |config: ConflatorConfig| {
+            let mut conflator = DefaultConflator::new().with_config(config);
+            let known = conflator.conflate(&record).unwrap();
+            for id in [Uuid::from_u128(2), Uuid::from_u128(1)] {
+                let entity_id = EntityId { id, entity_type: EntityType::Venue, version: 1 };
+                let mut stored = known.clone();
+                stored.canonical_entity_id = entity_id.clone();
+                conflator.entity_store.insert(entity_id.clone(), stored);
+                conflator.name_index.entry("test venue".to_string()).or_default().push(entity_id);
+            }
+            conflator
+        };
+
+        let result = with_two_known_venues(ConflatorConfig::default()).conflate(&record).unwrap();
+        assert_eq!(result.canonical_entity_id.id, Uuid::from_u128(1));
+        assert_eq!(result.conflation.config, Some(ConflatorConfig::default()));
+
+        let uncertain = ConflatorConfig { tie_break: TieBreakStrategy::Uncertain, ..ConflatorConfig::default() };
+        let result = with_two_known_venues(uncertain).conflate(&record).unwrap();
+        assert_eq!(result.conflation.resolution_decision, ResolutionDecision::Uncertain);
+
+        // Only the venue threshold applies to venues
+        let strict_venues = ConflatorConfig { venue_threshold: 1.01, ..ConflatorConfig::uniform(0.5) };
+        let result = with_two_known_venues(strict_venues).conflate(&record).unwrap();
+        assert_eq!(result.conflation.resolution_decision, ResolutionDecision::NewEntity);
+        assert!(result.conflation.alternatives.iter().all(|a| a.rejection_reason.contains("below threshold")));
+    }
+
+    fn venue_at(name: &str, address: &str, lat: f64, lng: f64) -> EnrichedRecord {
+        let mut record = create_test_venue_record(name, lat, lng);
+        if let NormalizedEntity::Venue(venue) = &mut record.quality_assessed_record.normalized_record.entity {
+            venue.address = address.to_string();
+        }
+        record
+    }
+
+    #[test]
+    fn test_nearby_venues_with_similar_names_conflate() {
+        let mut conflator = DefaultConflator::new();
+        let known = conflator.conflate(&venue_at("Neumos", "925 E Pike St", 47.61395, -122.31945)).unwrap();
+        let known_id = known.canonical_entity_id.clone();
+        conflator.remember(known);
+
+        // Another source spells it differently and geocodes ~20 m away
+        let listing = venue_at("Neumo's", "925 East Pike Street", 47.61412, -122.31938);
+        let result = conflator.conflate(&listing).unwrap();
+        assert_eq!(result.conflation.resolution_decision, ResolutionDecision::MatchedExisting(known_id));
+
+        // The same listing outside a tighter geofence is a separate venue
+        let mut strict = DefaultConflator::new()
+            .with_config(ConflatorConfig { venue_radius_m: 5.0, ..ConflatorConfig::default() });
+        strict.remember(strict.conflate(&venue_at("Neumos", "925 E Pike St", 47.61395, -122.31945)).unwrap());
+        let result = strict.conflate(&listing).unwrap();
+        assert_eq!(result.conflation.resolution_decision, ResolutionDecision::NewEntity);
+    }
+
+    #[test]
+    fn test_venues_sharing_a_building_stay_separate() {
+        // Barboza is downstairs from Neumos: same address, same coordinates
+        let mut conflator = DefaultConflator::new();
+        let neumos = conflator.conflate(&venue_at("Neumos", "925 E Pike St", 47.61395, -122.31945)).unwrap();
+        let neumos_id = neumos.canonical_entity_id.clone();
+        conflator.remember(neumos);
+
+        let barboza = venue_at("Barboza", "925 E Pike St", 47.61395, -122.31945);
+        let result = conflator.conflate(&barboza).unwrap();
+        assert_eq!(result.conflation.resolution_decision, ResolutionDecision::NewEntity);
+        assert_ne!(result.canonical_entity_id, neumos_id);
+        let considered = &result.conflation.alternatives;
+        assert!(considered.iter().any(|a| a.entity_id == neumos_id && a.similarity_score < 0.8));
+    }
+
+    #[test]
+    fn test_provisional_listings_match_cataloged_venues_by_name_only() {
+        let mut conflator = DefaultConflator::new();
+        let cataloged = Uuid::new_v4();
+        conflator.remember_cataloged(venue_at("Rabbit Box", "94 Pike St", 47.6087, -122.3404), cataloged);
+
+        // Listings without real coordinates carry placeholder ones
+        let provisional = |name: &str, address: &str, lat: f64, lng: f64| {
+            let mut record = venue_at(name, address, lat, lng);
+            if let NormalizedEntity::Venue(venue) = &mut record.quality_assessed_record.normalized_record.entity {
+                venue.provisional = true;
+            }
+            record
+        };
+        let result = conflator.conflate(&provisional("Rabbit Box", "Seattle, WA", 47.6062, -122.3321)).unwrap();
+        let expected = EntityId { id: cataloged, entity_type: EntityType::Venue, version: 1 };
+        assert_eq!(result.conflation.resolution_decision, ResolutionDecision::MatchedExisting(expected));
+
+        let result = conflator.conflate(&provisional("Chop Suey", "94 Pike St", 47.6087, -122.3404)).unwrap();
+        assert_eq!(result.conflation.resolution_decision, ResolutionDecision::NewEntity);
+        assert!(result.conflation.alternatives.is_empty());
+    }
+
     #[test]
     fn test_text_similarity_calculation() {
         let conflator = DefaultConflator::new();
@@ -729,6 +1170,11 @@ mod tests {
         // Different locations (should be > 0)
         let distance = conflator.calculate_distance(47.6131, -122.3424, 47.6200, -122.3500);
         assert!(distance > 0.0);
+
+        // Full proximity inside the 50 m geofence, none past twice the radius
+        assert_eq!(conflator.calculate_proximity(0.04), 1.0);
+        assert!((conflator.calculate_proximity(0.075) - 0.5).abs() < 1e-9);
+        assert_eq!(conflator.calculate_proximity(0.2), 0.0);
     }
 
     #[test]
@@ -739,4 +1185,80 @@ mod tests {
         let entity_type = conflator.determine_entity_type(&venue_record);
         assert_eq!(entity_type, EntityType::Venue);
     }
+
+    #[tokio::test]
+    async fn test_artist_slug_collisions_merge_or_suffix() {
+        use sms_core::domain::{Artist, Attribution};
+        use sms_core::storage::{allocate_artist, InMemoryStorage, SlugAllocation, Storage};
+
+        fn artist(name: &str, source_id: &str) -> Artist {
+            Artist {
+                id: None,
+                name: name.to_string(),
+                name_slug: String::new(),
+                bio: None,
+                artist_image_url: None,
+                created_at: Utc::now(),
+                attributions: vec![Attribution { source_id: source_id.to_string(), license_id: "cc-by".to_string(), text: None }],
+                detail_origins: Default::default(),
+                aliases: Vec::new(),
+            }
+        }
+
+        let storage = InMemoryStorage::new();
+        let conflator = DefaultConflator::new();
+        let same = |a: &Artist, b: &Artist| conflator.same_artist_name(&a.name, &b.name);
+
+        let first = allocate_artist(&storage, &mut artist("Tim & Eric", "neumos"), same).await.unwrap();
+        assert!(matches!(&first, SlugAllocation::Created { slug, suffixed: false, .. } if slug == "tim-eric"));
+
+        // Same slug, same artist by conflation: folded in, credited to both sources
+        let merged = allocate_artist(&storage, &mut artist("TIM & ERIC", "kexp"), same).await.unwrap();
+        assert_eq!(merged, SlugAllocation::Merged { id: first.id(), slug: "tim-eric".to_string() });
+        let stored = storage.get_artist_by_id(first.id()).await.unwrap().unwrap();
+        assert_eq!(stored.attributions.len(), 2);
+
+        // Same slug, different artist: suffixed instead of racing for "tim-eric"
+        let mut other = artist("Tim Eric", "barboza");
+        let distinct = allocate_artist(&storage, &mut other, same).await.unwrap();
+        assert!(matches!(&distinct, SlugAllocation::Created { slug, suffixed: true, .. } if slug == "tim-eric-2"));
+        assert_ne!(distinct.id(), first.id());
+        assert_eq!(other.name_slug, "tim-eric-2");
+        assert_eq!(storage.get_artist_by_slug("tim-eric").await.unwrap().unwrap().name, "Tim & Eric");
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn test_concurrent_writers_claim_a_slug_once() {
+        use sms_core::domain::Artist;
+        use sms_core::storage::{allocate_artist, InMemoryStorage, Storage};
+
+        let storage = std::sync::Arc::new(InMemoryStorage::new());
+        let writers: Vec<_> = (0..8)
+            .map(|i| {
+                let storage = storage.clone();
+                tokio::spawn(async move {
+                    let mut artist = Artist {
+                        id: None,
+                        name: if i % 2 == 0 { "Tim & Eric".to_string() } else { "TIM & ERIC".to_string() },
+                        name_slug: String::new(),
+                        bio: None,
+                        artist_image_url: None,
+                        created_at: Utc::now(),
+                        attributions: Vec::new(),
+                        detail_origins: Default::default(),
+                        aliases: Vec::new(),
+                    };
+                    let conflator = DefaultConflator::new();
+                    allocate_artist(&*storage, &mut artist, |a, b| conflator.same_artist_name(&a.name, &b.name)).await.unwrap().id()
+                })
+            })
+            .collect();
+
+        let mut ids = Vec::new();
+        for writer in writers {
+            ids.push(writer.await.unwrap());
+        }
+        assert!(ids.iter().all(|id| *id == ids[0]));
+        assert_eq!(storage.get_all_artists(None, None).await.unwrap().len(), 1);
+    }
 }
@@ -0,0 +1,229 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::sync::OnceLock;
+use uuid::Uuid;
+
+use sms_core::common::error::Result;
+use sms_core::domain::Event;
+use sms_core::storage::Storage;
+
+/// Words that only say which showing of a night an event is, so "Band (Matinee)" and
+/// "Band - Late Show" both key as "band"
+const SHOWING_WORDS: &[&str] = &["matinee", "early", "late", "evening", "show", "showing"];
+
+/// A parenthesized or bracketed part of a title: "(Matinee)", "[Late Show]"
+fn bracketed_pattern() -> &'static Regex {
+    static PATTERN: OnceLock<Regex> = OnceLock::new();
+    PATTERN.get_or_init(|| Regex::new(r"\(([^()]*)\)|\[([^\[\]]*)\]").unwrap())
+}
+
+/// The part of a title after its last separator: "Band - Late Show", "Band: Early"
+fn trailing_pattern() -> &'static Regex {
+    static PATTERN: OnceLock<Regex> = OnceLock::new();
+    PATTERN.get_or_init(|| Regex::new(r"\s[-–—|/]\s*([^-–—:|/]*)$|[:,]\s*([^-–—:|,/]*)$").unwrap())
+}
+
+fn title_words(title: &str) -> Vec<&str> {
+    title.split(|c: char| !c.is_alphanumeric()).filter(|w| !w.is_empty()).collect()
+}
+
+/// Whether `text` is nothing but showing qualifiers
+fn is_showing_marker(text: &str) -> bool {
+    let words = title_words(text);
+    !words.is_empty() && words.iter().all(|w| SHOWING_WORDS.contains(w))
+}
+
+/// Title key used to spot the same show listed twice: case and punctuation are ignored, as
+/// are showing qualifiers in brackets or after a trailing separator. Qualifier words inside
+/// the title itself ("Late Night Jazz", "The Early November") are kept.
+pub fn title_key(title: &str) -> String {
+    let lower = title.to_lowercase();
+    let unbracketed = bracketed_pattern().replace_all(&lower, |caps: &regex::Captures| {
+        if is_showing_marker(&caps[0]) { " ".to_string() } else { caps[0].to_string() }
+    });
+    let mut key = title_words(&unbracketed);
+    if let Some(caps) = trailing_pattern().captures(&unbracketed) {
+        let head = &unbracketed[..caps.get(0).map_or(0, |m| m.start())];
+        if is_showing_marker(&caps[0]) && !title_words(head).is_empty() {
+            key = title_words(head);
+        }
+    }
+    // A title made only of qualifiers ("Late Show") keys on itself
+    if key.is_empty() { title_words(&lower).join(" ") } else { key.join(" ") }
+}
+
+/// What happens to a suppressed duplicate's details
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DuplicateMergePolicy {
+    /// Copy fields the cataloged event is missing (description, image, link, start time,
+    /// artists) from the duplicate
+    #[default]
+    FillMissing,
+    /// Leave the cataloged event untouched
+    KeepExisting,
+}
+
+impl std::str::FromStr for DuplicateMergePolicy {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s {
+            "fill_missing" | "fill-missing" => Ok(Self::FillMissing),
+            "keep_existing" | "keep-existing" => Ok(Self::KeepExisting),
+            other => Err(format!("unknown merge policy '{}' (expected fill_missing or keep_existing)", other)),
+        }
+    }
+}
+
+/// When an incoming event counts as a duplicate of one already cataloged
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DuplicateSuppressionConfig {
+    /// Days either side of the event's date to look for a duplicate; 0 means the same day
+    pub window_days: u32,
+    pub merge_policy: DuplicateMergePolicy,
+}
+
+/// An incoming event that was folded into an existing one instead of being cataloged
+#[derive(Debug, Clone, Serialize)]
+pub struct SuppressedDuplicate {
+    pub title: String,
+    pub event_day: NaiveDate,
+    pub venue_id: Uuid,
+    pub kept_event_id: Uuid,
+    pub kept_title: String,
+    pub kept_event_day: NaiveDate,
+    /// Whether the kept event picked up fields from the duplicate
+    pub merged: bool,
+    pub suppressed_at: DateTime<Utc>,
+    /// The kept event as JSON before it picked up fields, so the merge can be rolled back
+    #[serde(skip)]
+    pub previous_state: Option<String>,
+}
+
+/// The cataloged event at the same venue, within the window and with the same title key
+pub fn find_duplicate<'a>(
+    candidate: &Event,
+    existing: &'a [Event],
+    config: &DuplicateSuppressionConfig,
+) -> Option<&'a Event> {
+    let key = title_key(&candidate.title);
+    existing
+        .iter()
+        .filter(|e| e.venue_id == candidate.venue_id && e.id.is_some() && e.id != candidate.id)
+        .filter(|e| (e.event_day - candidate.event_day).num_days().unsigned_abs() <= config.window_days as u64)
+        .filter(|e| title_key(&e.title) == key)
+        .min_by_key(|e| (e.event_day - candidate.event_day).num_days().abs())
+}
+
+/// Apply the merge policy, returning whether `kept` changed
+pub fn merge_duplicate(kept: &mut Event, duplicate: &Event, policy: DuplicateMergePolicy) -> bool {
+    if policy == DuplicateMergePolicy::KeepExisting {
+        return false;
+    }
+    let mut changed = false;
+    for (field, incoming) in [
+        (&mut kept.description, &duplicate.description),
+        (&mut kept.event_image_url, &duplicate.event_image_url),
+        (&mut kept.event_url, &duplicate.event_url),
+    ] {
+        if field.is_none() && incoming.is_some() {
+            *field = incoming.clone();
+            changed = true;
+        }
+    }
+    if kept.start_time.is_none() && duplicate.start_time.is_some() {
+        kept.start_time = duplicate.start_time;
+        changed = true;
+    }
+    for artist_id in &duplicate.artist_ids {
+        if !kept.artist_ids.contains(artist_id) {
+            kept.artist_ids.push(*artist_id);
+            changed = true;
+        }
+    }
+    changed
+}
+
+/// Fold `candidate` into a cataloged duplicate if there is one. Returns `None` when the
+/// event is not a duplicate and should be created as usual.
+pub async fn suppress_duplicate(
+    storage: &dyn Storage,
+    candidate: &Event,
+    config: &DuplicateSuppressionConfig,
+) -> Result<Option<SuppressedDuplicate>> {
+    let existing = storage.get_events_by_venue_id(candidate.venue_id).await?;
+    let Some(kept) = find_duplicate(candidate, &existing, config) else {
+        return Ok(None);
+    };
+    let previous_state = serde_json::to_string(kept).ok();
+    let mut kept = kept.clone();
+    let merged = merge_duplicate(&mut kept, candidate, config.merge_policy);
+    if merged {
+        storage.update_event(&kept).await?;
+    }
+    crate::observability::metrics::catalog::duplicate_suppressed();
+    Ok(Some(SuppressedDuplicate {
+        title: candidate.title.clone(),
+        event_day: candidate.event_day,
+        venue_id: candidate.venue_id,
+        kept_event_id: kept.id.unwrap_or_default(),
+        kept_title: kept.title,
+        kept_event_day: kept.event_day,
+        merged,
+        suppressed_at: Utc::now(),
+        previous_state: previous_state.filter(|_| merged),
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sms_core::storage::InMemoryStorage;
+
+    fn event(title: &str, day: u32, venue_id: Uuid) -> Event {
+        Event::fixture(title, NaiveDate::from_ymd_opt(2025, 8, day).unwrap(), venue_id)
+    }
+
+    #[test]
+    fn title_key_ignores_showing_qualifiers() {
+        assert_eq!(title_key("The Thermals (Matinee)"), title_key("the thermals - Evening Show"));
+        assert_ne!(title_key("The Thermals"), title_key("The Thermals II"));
+        assert_eq!(title_key("Late Show"), "late show");
+        assert_eq!(title_key("Sleater-Kinney [Early Show]"), title_key("Sleater-Kinney: Late"));
+        assert_eq!(title_key("Sleater-Kinney"), "sleater kinney");
+
+        // Qualifier words that are part of the title stay in the key
+        assert_eq!(title_key("Late Night Jazz"), "late night jazz");
+        assert_ne!(title_key("The Early November"), title_key("The November"));
+        assert_eq!(title_key("The Early November (Evening Show)"), "the early november");
+        assert_eq!(title_key("Show Me The Body - Matinee"), "show me the body");
+    }
+
+    #[tokio::test]
+    async fn duplicate_is_merged_into_the_cataloged_event() {
+        let storage = InMemoryStorage::new();
+        let venue_id = Uuid::new_v4();
+        let mut kept = event("The Thermals", 15, venue_id);
+        storage.create_event(&mut kept).await.unwrap();
+
+        let mut matinee = event("The Thermals (Matinee)", 15, venue_id);
+        matinee.description = Some("All ages".to_string());
+        let config = DuplicateSuppressionConfig::default();
+        let suppressed = suppress_duplicate(&storage, &matinee, &config).await.unwrap().unwrap();
+        assert_eq!(suppressed.kept_event_id, kept.id.unwrap());
+        assert!(suppressed.merged);
+        let stored = storage.get_event_by_id(kept.id.unwrap()).await.unwrap().unwrap();
+        assert_eq!(stored.description.as_deref(), Some("All ages"));
+
+        // Outside the window, or at another venue, it's a different show
+        let next_day = event("The Thermals", 16, venue_id);
+        assert!(suppress_duplicate(&storage, &next_day, &config).await.unwrap().is_none());
+        let widened = DuplicateSuppressionConfig { window_days: 1, merge_policy: DuplicateMergePolicy::KeepExisting };
+        let suppressed = suppress_duplicate(&storage, &next_day, &widened).await.unwrap().unwrap();
+        assert!(!suppressed.merged);
+        let elsewhere = event("The Thermals", 15, Uuid::new_v4());
+        assert!(suppress_duplicate(&storage, &elsewhere, &config).await.unwrap().is_none());
+    }
+}
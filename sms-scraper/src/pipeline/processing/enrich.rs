@@ -1,95 +1,14 @@
-use chrono::{DateTime, Utc};
-use serde::{Deserialize, Serialize};
+use chrono::Utc;
 
+use crate::pipeline::assets;
+use crate::pipeline::processing::neighborhoods::NeighborhoodIndex;
 use crate::pipeline::processing::quality_gate::QualityAssessedRecord;
+use std::sync::Arc;
 use crate::observability::metrics;
 
-/// An enriched record that has passed through quality gate and been enhanced
-/// with contextual information like spatial bins, city tags, and routing labels
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct EnrichedRecord {
-    /// The original quality-assessed record
-    pub quality_assessed_record: QualityAssessedRecord,
-    /// Enrichment metadata and contextual additions
-    pub enrichment: EnrichmentMetadata,
-    /// When this enrichment was performed
-    pub enriched_at: DateTime<Utc>,
-}
-
-/// Enrichment metadata containing contextual additions to the record
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct EnrichmentMetadata {
-    /// The city or municipal area this record belongs to
-    pub city: Option<String>,
-    /// District or neighborhood within the city
-    pub district: Option<String>,
-    /// Administrative region (county, state, etc.)
-    pub region: Option<String>,
-    /// Spatial bin for quick geographic lookups (e.g., "seattle_grid_42_13")
-    pub spatial_bin: Option<String>,
-    /// Tags for partitioning and routing (e.g., ["music", "nightlife", "downtown"])
-    pub tags: Vec<String>,
-    /// Computed geographical properties
-    pub geo_properties: GeoProperties,
-    /// Reference data versions used for enrichment
-    pub reference_versions: ReferenceVersions,
-    /// The enrichment strategy used
-    pub strategy: String,
-    /// Confidence in the enrichment (0.0 to 1.0)
-    pub confidence: f64,
-    /// Any warnings from the enrichment process
-    pub warnings: Vec<String>,
-}
-
-/// Geographical properties computed during enrichment
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct GeoProperties {
-    /// Whether coordinates are within expected city bounds
-    pub within_city_bounds: bool,
-    /// Distance from city center in kilometers
-    pub distance_from_center_km: Option<f64>,
-    /// Population density category for the area
-    pub population_density: PopulationDensity,
-    /// Transit accessibility score (0.0 to 1.0)
-    pub transit_accessibility: Option<f64>,
-    /// Nearby landmarks or points of interest
-    pub nearby_landmarks: Vec<String>,
-}
-
-/// Population density categories for spatial enrichment
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
-pub enum PopulationDensity {
-    /// Rural or very low density
-    Rural,
-    /// Suburban or low density
-    Suburban,
-    /// Urban or medium density
-    Urban,
-    /// Dense urban or high density
-    Dense,
-    /// Unknown density
-    Unknown,
-}
-
-/// Reference data versions used during enrichment
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct ReferenceVersions {
-    /// City boundaries data version
-    pub city_boundaries: Option<String>,
-    /// Administrative boundaries version
-    pub admin_boundaries: Option<String>,
-    /// Spatial grid version
-    pub spatial_grid: Option<String>,
-    /// Points of interest data version
-    pub poi_data: Option<String>,
-}
-
-/// Trait for enriching quality-assessed records with contextual information
-pub trait Enricher {
-    /// Enrich a quality-assessed record with contextual metadata
-    fn enrich(&self, record: &QualityAssessedRecord) -> anyhow::Result<EnrichedRecord>;
-}
-
+pub use sms_core::pipeline_api::enrich::{
+    EnrichedRecord, Enricher, EnrichmentMetadata, GeoProperties, PopulationDensity, ReferenceVersions,
+};
 /// A wrapper that adds metrics to any enricher implementation
 pub struct MetricsEnricher<E: Enricher> {
     inner: E,
@@ -152,10 +71,18 @@ pub struct DefaultEnricher {
     pub spatial_grid_size: f64,
     /// Reference data versions
     pub reference_versions: ReferenceVersions,
+    /// Neighborhood boundaries for district lookup
+    pub neighborhoods: Option<Arc<NeighborhoodIndex>>,
 }
 
 impl Default for DefaultEnricher {
     fn default() -> Self {
+        let neighborhoods = assets::neighborhoods()
+            .or_else(|e| {
+                tracing::warn!("Falling back to bundled Seattle neighborhoods: {:#}", e);
+                NeighborhoodIndex::bundled_seattle().map(Arc::new)
+            })
+            .ok();
         Self {
             city_center: (47.6062, -122.3321), // Seattle center
             spatial_grid_size: 0.01, // ~1km grid
@@ -164,7 +91,9 @@ impl Default for DefaultEnricher {
                 admin_boundaries: Some("wa_king_county_v1.0".to_string()),
                 spatial_grid: Some("grid_1km_v1.0".to_string()),
                 poi_data: Some("seattle_poi_v1.0".to_string()),
+                neighborhoods: neighborhoods.as_ref().map(|n| n.version.clone()),
             },
+            neighborhoods,
         }
     }
 }
@@ -195,7 +124,7 @@ impl DefaultEnricher {
     /// Determine if coordinates are within Seattle city bounds (simplified)
     fn is_within_city_bounds(&self, lat: f64, lng: f64) -> bool {
         // Simple bounding box for Seattle (production would use proper polygons)
-        lat >= 47.48 && lat <= 47.74 && lng >= -122.46 && lng <= -122.22
+        (47.48..=47.74).contains(&lat) && (-122.46..=-122.22).contains(&lng)
     }
 
     /// Classify population density based on distance from city center
@@ -251,17 +180,22 @@ impl DefaultEnricher {
         }
     }
 
-    /// Determine district/neighborhood (simplified)
+    /// Use a different set of neighborhood boundaries (e.g. for another city)
+    pub fn with_neighborhoods(mut self, neighborhoods: NeighborhoodIndex) -> Self {
+        self.reference_versions.neighborhoods = Some(neighborhoods.version.clone());
+        self.neighborhoods = Some(Arc::new(neighborhoods));
+        self
+    }
+
+    /// Determine district/neighborhood by point-in-polygon lookup
     fn determine_district(&self, lat: f64, lng: f64) -> Option<String> {
-        // Simplified neighborhood detection based on coordinates
-        match (lat, lng) {
-            (lat, lng) if lat > 47.62 && lng > -122.34 => Some("Capitol Hill".to_string()),
-            (lat, lng) if lat > 47.66 && lng < -122.37 => Some("Ballard".to_string()),
-            (lat, lng) if lat > 47.61 && lng > -122.34 => Some("Belltown".to_string()),
-            (lat, lng) if lat < 47.60 && lng > -122.34 => Some("Pioneer Square".to_string()),
-            (lat, lng) if lat > 47.67 && lng > -122.36 => Some("Fremont".to_string()),
-            _ if self.is_within_city_bounds(lat, lng) => Some("Seattle".to_string()),
-            _ => None,
+        if let Some(name) = self.neighborhoods.as_ref().and_then(|n| n.lookup(lat, lng)) {
+            return Some(name.to_string());
+        }
+        if self.is_within_city_bounds(lat, lng) {
+            Some("Seattle".to_string())
+        } else {
+            None
         }
     }
 
@@ -340,6 +274,8 @@ impl DefaultEnricher {
 
 impl Enricher for DefaultEnricher {
     fn enrich(&self, record: &QualityAssessedRecord) -> anyhow::Result<EnrichedRecord> {
+        use crate::pipeline::processing::normalize::NormalizedEntity;
+
         let enriched_at = Utc::now();
         let mut warnings = Vec::new();
         
@@ -398,6 +334,16 @@ impl Enricher for DefaultEnricher {
             0.6 // Lower confidence without coordinates
         };
 
+        // Attach the neighborhood to venues that didn't come with one
+        let mut quality_assessed_record = record.clone();
+        if let (NormalizedEntity::Venue(venue), Some(name)) =
+            (&mut quality_assessed_record.normalized_record.entity, district.as_deref())
+        {
+            if venue.neighborhood.is_none() && name != "Seattle" {
+                venue.neighborhood = Some(name.to_string());
+            }
+        }
+
         let enrichment = EnrichmentMetadata {
             city,
             district,
@@ -412,7 +358,7 @@ impl Enricher for DefaultEnricher {
         };
 
         Ok(EnrichedRecord {
-            quality_assessed_record: record.clone(),
+            quality_assessed_record,
             enrichment,
             enriched_at,
         })
@@ -422,11 +368,11 @@ impl Enricher for DefaultEnricher {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use sms_core::domain::{Event, Venue};
+    use sms_core::domain::Venue;
     use crate::pipeline::processing::normalize::{NormalizedEntity, NormalizedRecord, NormalizationMetadata, RecordProvenance};
     use crate::pipeline::processing::quality_gate::{QualityAssessment, QualityDecision};
-    use chrono::{NaiveDate, Utc};
-    use uuid::Uuid;
+    use chrono::Utc;
+    
 
     fn create_test_venue_record() -> QualityAssessedRecord {
         let venue = Venue {
@@ -445,6 +391,12 @@ mod tests {
             neighborhood: None,
             show_venue: true,
             created_at: Utc::now(),
+            attributions: Vec::new(),
+            provisional: false,
+            aliases: Vec::new(),
+            age_restriction: None,
+            accessibility_notes: None,
+            moderation: None,
         };
 
         let normalized_record = NormalizedRecord {
@@ -455,6 +407,11 @@ mod tests {
                 payload_ref: "test_payload".to_string(),
                 record_path: "$.venues[0]".to_string(),
                 normalized_at: Utc::now(),
+                attribution: None,
+                change: None,
+                record_key: None,
+                endpoint_id: None,
+                external_id: None,
             },
             normalization: NormalizationMetadata {
                 confidence: 0.9,
@@ -505,6 +462,25 @@ mod tests {
         assert!(tags.contains(&"upscale".to_string()));
     }
 
+    #[test]
+    fn test_enrich_attaches_neighborhood_to_venue() {
+        let raw = r#"{"type": "FeatureCollection", "name": "test_hoods_v1", "features": [{
+            "type": "Feature", "properties": {"name": "Test Hood"},
+            "geometry": {"type": "Polygon", "coordinates": [[[-122.35, 47.61], [-122.33, 47.61], [-122.33, 47.62], [-122.35, 47.62], [-122.35, 47.61]]]}
+        }]}"#;
+        let enricher = DefaultEnricher::new()
+            .with_neighborhoods(NeighborhoodIndex::from_geojson_str(raw).unwrap());
+
+        let result = enricher.enrich(&create_test_venue_record()).unwrap();
+
+        assert_eq!(result.enrichment.district, Some("Test Hood".to_string()));
+        assert_eq!(result.enrichment.reference_versions.neighborhoods, Some("test_hoods_v1".to_string()));
+        let NormalizedEntity::Venue(venue) = &result.quality_assessed_record.normalized_record.entity else {
+            panic!("expected venue");
+        };
+        assert_eq!(venue.neighborhood, Some("Test Hood".to_string()));
+    }
+
     #[test]
     fn test_distance_calculation() {
         let enricher = DefaultEnricher::new();
@@ -1,16 +1,30 @@
 // Pipeline processing: data parsing, validation, and transformation
 
+#[cfg(feature = "scraping")]
 pub mod parser;
 pub mod normalize;
 pub mod quality_gate;
 pub mod enrich;
+pub mod neighborhoods;
 pub mod conflation;
+pub mod resolution_index;
+pub mod venue_resolution;
+pub mod venue_resolver;
+pub mod duplicate_suppression;
+pub mod artist_enrichment;
 pub mod catalog;
+pub mod classification;
+pub mod price;
+pub mod sanitize;
+pub mod admission;
+pub mod recurrence;
+#[cfg(feature = "scraping")]
 pub mod pipeline_steps;
 
 // Re-export key types and functions
 
 // Re-export pipeline steps for easy access
+#[cfg(feature = "scraping")]
 pub use pipeline_steps::{
     process_raw_data,
     ProcessedData,
@@ -0,0 +1,183 @@
+use anyhow::{anyhow, Context, Result};
+use serde_json::Value;
+use std::path::Path;
+
+/// Simplified Seattle neighborhood outlines shipped with the binary
+pub(crate) const BUNDLED_SEATTLE: &str = include_str!("../../../assets/seattle_neighborhoods.geojson");
+
+/// Env var pointing at a GeoJSON FeatureCollection to use instead of the bundled Seattle data
+pub const NEIGHBORHOODS_ENV: &str = "SMS_NEIGHBORHOODS_GEOJSON";
+
+/// A polygon as an exterior ring plus holes, each ring a list of (lng, lat) points
+type Polygon = Vec<Vec<(f64, f64)>>;
+
+#[derive(Debug, Clone)]
+struct Neighborhood {
+    name: String,
+    polygons: Vec<Polygon>,
+}
+
+/// Point-in-polygon lookup over named neighborhood boundaries loaded from GeoJSON
+#[derive(Debug, Clone)]
+pub struct NeighborhoodIndex {
+    /// Identifies the boundary data in enrichment reference versions
+    pub version: String,
+    neighborhoods: Vec<Neighborhood>,
+}
+
+impl NeighborhoodIndex {
+    /// The GeoJSON named by `SMS_NEIGHBORHOODS_GEOJSON`, else the bundled Seattle boundaries
+    pub fn from_env_or_bundled() -> Result<Self> {
+        match std::env::var(NEIGHBORHOODS_ENV) {
+            Ok(path) if !path.trim().is_empty() => Self::from_path(path.trim()),
+            _ => Self::bundled_seattle(),
+        }
+    }
+
+    pub fn bundled_seattle() -> Result<Self> {
+        Self::from_geojson_str(BUNDLED_SEATTLE)
+    }
+
+    pub fn from_path<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let path = path.as_ref();
+        let raw = std::fs::read_to_string(path)
+            .with_context(|| format!("reading neighborhoods GeoJSON {}", path.display()))?;
+        Self::from_geojson_str(&raw).with_context(|| format!("parsing neighborhoods GeoJSON {}", path.display()))
+    }
+
+    /// Parse a FeatureCollection of Polygon/MultiPolygon features named by a
+    /// `name` (or `S_HOOD`/`neighborhood`) property
+    pub fn from_geojson_str(raw: &str) -> Result<Self> {
+        let doc: Value = serde_json::from_str(raw)?;
+        let features = doc
+            .get("features")
+            .and_then(Value::as_array)
+            .ok_or_else(|| anyhow!("expected a GeoJSON FeatureCollection"))?;
+
+        let mut neighborhoods = Vec::new();
+        for feature in features {
+            let properties = feature.get("properties");
+            let Some(name) = ["name", "S_HOOD", "neighborhood"]
+                .iter()
+                .find_map(|key| properties.and_then(|p| p.get(*key)).and_then(Value::as_str))
+            else {
+                continue;
+            };
+            let geometry = feature.get("geometry").ok_or_else(|| anyhow!("feature {} has no geometry", name))?;
+            let coordinates = geometry.get("coordinates").unwrap_or(&Value::Null);
+            let polygons = match geometry.get("type").and_then(Value::as_str) {
+                Some("Polygon") => vec![parse_polygon(coordinates)?],
+                Some("MultiPolygon") => coordinates
+                    .as_array()
+                    .ok_or_else(|| anyhow!("MultiPolygon {} has no coordinates", name))?
+                    .iter()
+                    .map(parse_polygon)
+                    .collect::<Result<_>>()?,
+                _ => continue,
+            };
+            neighborhoods.push(Neighborhood { name: name.to_string(), polygons });
+        }
+
+        let version = doc
+            .get("name")
+            .and_then(Value::as_str)
+            .unwrap_or("custom_geojson")
+            .to_string();
+        Ok(Self { version, neighborhoods })
+    }
+
+    /// Name of the neighborhood containing the point, if any
+    pub fn lookup(&self, lat: f64, lng: f64) -> Option<&str> {
+        self.neighborhoods
+            .iter()
+            .find(|n| n.polygons.iter().any(|p| polygon_contains(p, lng, lat)))
+            .map(|n| n.name.as_str())
+    }
+
+    pub fn len(&self) -> usize {
+        self.neighborhoods.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.neighborhoods.is_empty()
+    }
+}
+
+fn parse_polygon(value: &Value) -> Result<Polygon> {
+    value
+        .as_array()
+        .ok_or_else(|| anyhow!("polygon coordinates must be an array of rings"))?
+        .iter()
+        .map(|ring| {
+            ring.as_array()
+                .ok_or_else(|| anyhow!("ring must be an array of positions"))?
+                .iter()
+                .map(|pos| match (pos.get(0).and_then(Value::as_f64), pos.get(1).and_then(Value::as_f64)) {
+                    (Some(lng), Some(lat)) => Ok((lng, lat)),
+                    _ => Err(anyhow!("position must be [lng, lat]")),
+                })
+                .collect()
+        })
+        .collect()
+}
+
+fn polygon_contains(polygon: &Polygon, x: f64, y: f64) -> bool {
+    let Some((exterior, holes)) = polygon.split_first() else {
+        return false;
+    };
+    ring_contains(exterior, x, y) && !holes.iter().any(|hole| ring_contains(hole, x, y))
+}
+
+/// Even-odd ray casting
+fn ring_contains(ring: &[(f64, f64)], x: f64, y: f64) -> bool {
+    let mut inside = false;
+    let mut j = ring.len().wrapping_sub(1);
+    for i in 0..ring.len() {
+        let (xi, yi) = ring[i];
+        let (xj, yj) = ring[j];
+        if (yi > y) != (yj > y) && x < (xj - xi) * (y - yi) / (yj - yi) + xi {
+            inside = !inside;
+        }
+        j = i;
+    }
+    inside
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bundled_seattle_resolves_known_venues() {
+        let index = NeighborhoodIndex::bundled_seattle().unwrap();
+        assert!(!index.is_empty());
+        assert_eq!(index.lookup(47.6143, -122.3196), Some("Capitol Hill")); // Neumos
+        assert_eq!(index.lookup(47.6684, -122.3840), Some("Ballard")); // Sunset Tavern
+        assert_eq!(index.lookup(47.6614, -122.3197), Some("University District")); // Blue Moon
+        assert_eq!(index.lookup(45.5152, -122.6784), None); // Portland
+    }
+
+    #[test]
+    fn custom_geojson_supports_multipolygons_and_holes() {
+        let raw = r#"{
+            "type": "FeatureCollection",
+            "features": [{
+                "type": "Feature",
+                "properties": { "S_HOOD": "Donut" },
+                "geometry": {
+                    "type": "MultiPolygon",
+                    "coordinates": [
+                        [[[0, 0], [10, 0], [10, 10], [0, 10], [0, 0]],
+                         [[4, 4], [6, 4], [6, 6], [4, 6], [4, 4]]],
+                        [[[20, 20], [21, 20], [21, 21], [20, 20]]]
+                    ]
+                }
+            }]
+        }"#;
+        let index = NeighborhoodIndex::from_geojson_str(raw).unwrap();
+        assert_eq!(index.version, "custom_geojson");
+        assert_eq!(index.lookup(1.0, 1.0), Some("Donut"));
+        assert_eq!(index.lookup(5.0, 5.0), None);
+        assert_eq!(index.lookup(20.2, 20.8), Some("Donut"));
+    }
+}
@@ -1,11 +1,14 @@
-use chrono::{NaiveDate, NaiveTime, Utc};
+use chrono::{NaiveDate, Utc};
 use uuid::Uuid;
 use anyhow::Result;
 
 use super::base::{SourceNormalizer, NormalizerUtils, VenueStateManager, ArtistStateManager};
-use sms_core::domain::{Artist, Event, Venue};
-use crate::pipeline::processing::parser::ParsedRecord;
+use sms_core::domain::{Artist, Event};
+use sms_parsers::ParsedRecord;
 use crate::pipeline::processing::normalize::NormalizedRecord;
+use crate::pipeline::processing::admission::{extract_accessibility_notes, extract_age_restriction};
+use crate::pipeline::processing::price::extract_price;
+use crate::pipeline::processing::venue_resolver::BARBOZA;
 
 /// Normalizer for Barboza events
 /// These are scraped from The Barboza's HTML events page
@@ -43,16 +46,12 @@ impl SourceNormalizer for BarbozaNormalizer {
                 .and_then(|date_str| NaiveDate::parse_from_str(date_str, "%Y-%m-%d").ok())
                 .unwrap_or_else(|| Utc::now().naive_utc().date());
 
-            // Parse start time from event_time field
-            let start_time = data.get("event_time")
+            // time_text reads like "Doors: 7:00 PM", sometimes with a separate show time
+            let times = data.get("time_text")
+                .or_else(|| data.get("event_time"))
                 .and_then(|v| v.as_str())
-                .and_then(|time_str| {
-                    // Try common time formats (e.g., "7:00 PM", "19:00")
-                    NaiveTime::parse_from_str(time_str, "%I:%M %p")
-                        .or_else(|_| NaiveTime::parse_from_str(time_str, "%l:%M %p"))
-                        .or_else(|_| NaiveTime::parse_from_str(time_str, "%H:%M"))
-                        .ok()
-                });
+                .map(NormalizerUtils::parse_show_times)
+                .unwrap_or_default();
 
             // Build event URL from ticket_url or detail_url
             let event_url = data.get("ticket_url")
@@ -121,6 +120,9 @@ impl SourceNormalizer for BarbozaNormalizer {
                     bio: None,
                     artist_image_url: None,
                     created_at: Utc::now(),
+                    attributions: Vec::new(),
+                    detail_origins: Default::default(),
+                    aliases: Vec::new(),
                 };
 
                 results.push(NormalizerUtils::create_artist_record(
@@ -229,15 +231,24 @@ impl SourceNormalizer for BarbozaNormalizer {
                 id: None,
                 title: title.to_string(),
                 event_day,
-                start_time,
+                start_time: times.start_time(),
                 event_url,
                 description,
                 event_image_url,
-                venue_id: Uuid::nil(),
+                venue_id: BARBOZA.id(),
                 artist_ids: event_artist_ids,  // Link the artists!
                 show_event: true,
                 finalized: false,
                 created_at: Utc::now(),
+                attributions: Vec::new(),
+                tags: Vec::new(),
+                price: extract_price(data),
+                doors_time: times.doors,
+                series_id: None,
+                age_restriction: extract_age_restriction(data),
+                accessibility_notes: extract_accessibility_notes(data),
+                moderation: None,
+                external_ids: Vec::new(),
             };
 
             results.push(NormalizerUtils::create_event_record(
@@ -250,23 +261,7 @@ impl SourceNormalizer for BarbozaNormalizer {
 
         // Create The Barboza venue only once
         if self.venue_state.should_create_venue() {
-            let venue = Venue {
-                id: None,
-                name: "The Barboza".to_string(),
-                name_lower: "the barboza".to_string(),
-                slug: "the-barboza".to_string(),
-                latitude: 47.6133,  // The Barboza's location in Capitol Hill
-                longitude: -122.3185,
-                address: "925 E Pike St".to_string(),
-                postal_code: "98122".to_string(),
-                city: "Seattle".to_string(),
-                venue_url: Some("https://www.thebarboza.com".to_string()),
-                venue_image_url: None,
-                description: Some("Underground music venue in Capitol Hill featuring live performances and DJ nights".to_string()),
-                neighborhood: Some("Capitol Hill".to_string()),
-                show_venue: true,
-                created_at: Utc::now(),
-            };
+            let venue = BARBOZA.to_venue();
 
             results.push(NormalizerUtils::create_venue_record(
                 venue, 
@@ -1,24 +1,15 @@
-use chrono::Utc;
+use chrono::{NaiveTime, Utc};
 use std::sync::{Arc, Mutex};
 use anyhow::Result;
+use uuid::Uuid;
 
 use sms_core::domain::{Artist, Event, Venue};
-use crate::pipeline::processing::parser::ParsedRecord;
+use sms_parsers::schedule::{self, ShowTimes};
+use sms_parsers::ParsedRecord;
 use crate::observability::metrics;
 use super::super::{NormalizedRecord, NormalizedEntity, RecordProvenance, NormalizationMetadata};
 
-/// Base trait for source-specific normalizers
-pub trait SourceNormalizer: Send + Sync {
-    /// Normalize a parsed record into normalized entities
-    fn normalize(&self, record: &ParsedRecord) -> Result<Vec<NormalizedRecord>>;
-    
-    /// Get the source ID this normalizer handles
-    fn source_id(&self) -> &str;
-    
-    /// Get a human-readable name for this normalizer
-    fn name(&self) -> &str;
-}
-
+pub use sms_core::pipeline_api::normalize::SourceNormalizer;
 /// A wrapper that adds metrics to any normalizer implementation
 pub struct MetricsNormalizer<N: SourceNormalizer> {
     inner: N,
@@ -78,12 +69,14 @@ impl<N: SourceNormalizer> SourceNormalizer for MetricsNormalizer<N> {
 /// Prevents creating duplicate venue records when processing multiple events from the same venue
 pub struct VenueStateManager {
     venue_created: Arc<Mutex<bool>>,
+    placeholders_created: Arc<Mutex<std::collections::HashSet<String>>>,
 }
 
 impl VenueStateManager {
     pub fn new() -> Self {
         Self {
             venue_created: Arc::new(Mutex::new(false)),
+            placeholders_created: Arc::new(Mutex::new(std::collections::HashSet::new())),
         }
     }
 
@@ -103,12 +96,24 @@ impl VenueStateManager {
         }
     }
 
+    /// Like `should_create_venue`, for the placeholder venues a multi-venue source references by slug
+    pub fn should_create_placeholder(&self, slug: &str) -> bool {
+        if let Ok(mut created_set) = self.placeholders_created.lock() {
+            created_set.insert(slug.to_string())
+        } else {
+            false
+        }
+    }
+
     /// Test-only: reset state
     #[cfg(test)]
     pub fn reset(&self) {
         if let Ok(mut created) = self.venue_created.lock() {
             *created = false;
         }
+        if let Ok(mut created_set) = self.placeholders_created.lock() {
+            created_set.clear();
+        }
     }
 }
 
@@ -142,6 +147,40 @@ impl ArtistStateManager {
         }
     }
 
+    /// Id of the artist `name` names (a v5 UUID of its slug), pushing an artist record onto
+    /// `results` the first time the batch sees it. `None` for names that slug to nothing.
+    pub fn link_artist(
+        &self,
+        name: &str,
+        bio: Option<&str>,
+        confidence: f64,
+        strategy: &str,
+        provenance: &RecordProvenance,
+        results: &mut Vec<NormalizedRecord>,
+    ) -> Option<Uuid> {
+        let name = name.trim();
+        let name_slug = NormalizerUtils::generate_slug(name);
+        if name_slug.is_empty() {
+            return None;
+        }
+        let artist_id = Uuid::new_v5(&Uuid::NAMESPACE_DNS, name_slug.as_bytes());
+        if self.should_create_artist(&name_slug) {
+            let artist = Artist {
+                id: Some(artist_id),
+                name: name.to_string(),
+                name_slug,
+                bio: bio.map(str::trim).filter(|b| !b.is_empty()).map(str::to_string),
+                artist_image_url: None,
+                created_at: Utc::now(),
+                attributions: Vec::new(),
+                detail_origins: Default::default(),
+                aliases: Vec::new(),
+            };
+            results.push(NormalizerUtils::create_artist_record(artist, provenance.clone(), confidence, strategy.to_string()));
+        }
+        Some(artist_id)
+    }
+
     /// Test-only: reset state
     #[cfg(test)]
     pub fn reset(&self) {
@@ -175,8 +214,10 @@ impl NormalizerUtils {
             .chars()
             .filter(|c| c.is_alphanumeric() || *c == '-')
             .collect::<String>()
-            .trim_matches('-')
-            .to_string()
+            .split('-')
+            .filter(|part| !part.is_empty())
+            .collect::<Vec<_>>()
+            .join("-")
     }
 
     /// Create a base record provenance from a parsed record
@@ -187,16 +228,24 @@ impl NormalizerUtils {
             payload_ref: record.payload_ref.clone(),
             record_path: record.record_path.clone(),
             normalized_at: Utc::now(),
+            attribution: record.attribution.clone(),
+            change: record.change,
+            record_key: Some(crate::pipeline::ingestion::delta::parsed_record_key(record).key().to_string()),
+            endpoint_id: record.endpoint_id.clone(),
+            external_id: record.external_id.clone(),
         }
     }
 
     /// Create a normalized venue record with standard metadata
     pub fn create_venue_record(
-        venue: Venue,
+        mut venue: Venue,
         provenance: RecordProvenance,
         confidence: f64,
         strategy: String,
     ) -> NormalizedRecord {
+        if let Some(attribution) = provenance.attribution.clone() {
+            attribution.add_to(&mut venue.attributions);
+        }
         NormalizedRecord {
             entity: NormalizedEntity::Venue(venue),
             provenance,
@@ -211,11 +260,14 @@ impl NormalizerUtils {
 
     /// Create a normalized event record with standard metadata
     pub fn create_event_record(
-        event: Event,
+        mut event: Event,
         provenance: RecordProvenance,
         confidence: f64,
         strategy: String,
     ) -> NormalizedRecord {
+        if let Some(attribution) = provenance.attribution.clone() {
+            attribution.add_to(&mut event.attributions);
+        }
         NormalizedRecord {
             entity: NormalizedEntity::Event(event),
             provenance,
@@ -230,11 +282,14 @@ impl NormalizerUtils {
 
     /// Create a normalized artist record with standard metadata
     pub fn create_artist_record(
-        artist: Artist,
+        mut artist: Artist,
         provenance: RecordProvenance,
         confidence: f64,
         strategy: String,
     ) -> NormalizedRecord {
+        if let Some(attribution) = provenance.attribution.clone() {
+            attribution.add_to(&mut artist.attributions);
+        }
         NormalizedRecord {
             entity: NormalizedEntity::Artist(artist),
             provenance,
@@ -256,6 +311,41 @@ impl NormalizerUtils {
             .map(|s| s.to_string())
     }
 
+    /// Split a lineup string like "A, B & C w/ D" into individual act names
+    pub fn split_artist_names(lineup: &str) -> Vec<String> {
+        let mut normalized = format!(" {} ", lineup);
+        for sep in [" w/ ", " with ", " + ", " & ", " / ", " and more "] {
+            normalized = normalized.replace(sep, ",").replace(&sep.to_uppercase(), ",");
+        }
+        // Titlecase "With" shows up in listings more often than any other casing
+        normalized = normalized.replace(" With ", ",");
+        normalized
+            .split([',', ';'])
+            .map(|s| s.trim().trim_end_matches('.').trim())
+            .filter(|s| !s.is_empty())
+            .map(|s| s.to_string())
+            .collect()
+    }
+
+    /// Parse a show time in any of the formats venue sites use ("19:30:00", "19:30", "7:30 PM", "7pm")
+    pub fn parse_show_time(time_str: &str) -> Option<NaiveTime> {
+        schedule::parse_time(time_str)
+    }
+
+    /// Doors and show times from listing text like "Doors 7pm / Show 8pm"
+    pub fn parse_show_times(text: &str) -> ShowTimes {
+        schedule::split_show_times(text)
+    }
+
+    /// Whether the listing marks the event cancelled, by its `status` (calendars) or
+    /// `ticket_status` (ticketing platforms)
+    pub fn is_cancelled(data: &serde_json::Value) -> bool {
+        ["status", "ticket_status"]
+            .iter()
+            .filter_map(|field| data.get(field).and_then(|v| v.as_str()))
+            .any(|s| s.eq_ignore_ascii_case("cancelled") || s.eq_ignore_ascii_case("canceled"))
+    }
+
     /// Check if a title represents a non-artist event (like open mic, karaoke, etc.)
     pub fn is_non_artist_event(title: &str) -> bool {
         let title_lower = title.to_lowercase();
@@ -333,6 +423,70 @@ mod tests {
         assert!(!NormalizerUtils::is_non_artist_event("Jazz Ensemble"));
     }
 
+    #[test]
+    fn test_records_carry_source_attribution() {
+        let parsed = ParsedRecord {
+            source_id: "kexp".to_string(),
+            envelope_id: "env-1".to_string(),
+            payload_ref: "cas:sha256:abcd".to_string(),
+            record_path: "$[0]".to_string(),
+            record: serde_json::json!({}),
+            attribution: Some(sms_core::domain::Attribution {
+                source_id: "kexp".to_string(),
+                license_id: "cc-by-4.0".to_string(),
+                text: None,
+            }),
+            change: None,
+            endpoint_id: None,
+            external_id: None,
+        };
+        let artist = Artist {
+            id: None,
+            name: "Band".to_string(),
+            name_slug: "band".to_string(),
+            bio: None,
+            artist_image_url: None,
+            created_at: Utc::now(),
+            attributions: Vec::new(),
+            detail_origins: Default::default(),
+            aliases: Vec::new(),
+        };
+
+        let record = NormalizerUtils::create_artist_record(
+            artist,
+            NormalizerUtils::create_provenance(&parsed),
+            1.0,
+            "test".to_string(),
+        );
+        let NormalizedEntity::Artist(artist) = record.entity else { panic!("expected artist") };
+        assert_eq!(artist.attributions.len(), 1);
+        assert_eq!(artist.attributions[0].license_id, "cc-by-4.0");
+
+        let manager = ArtistStateManager::new();
+        let provenance = NormalizerUtils::create_provenance(&parsed);
+        let mut results = Vec::new();
+        let first = manager.link_artist(" Band ", Some("  From Tacoma "), 0.8, "test", &provenance, &mut results);
+        let again = manager.link_artist("Band", None, 0.8, "test", &provenance, &mut results);
+        assert_eq!(first, again);
+        assert!(manager.link_artist("!!", None, 0.8, "test", &provenance, &mut results).is_none());
+        assert_eq!(results.len(), 1);
+        let NormalizedEntity::Artist(artist) = &results[0].entity else { panic!("expected artist") };
+        assert_eq!((artist.id, artist.name.as_str()), (first, "Band"));
+        assert_eq!(artist.bio.as_deref(), Some("From Tacoma"));
+    }
+
+    #[test]
+    fn test_split_artist_names() {
+        assert_eq!(
+            NormalizerUtils::split_artist_names("The Dip, Kingdom of Birds & Jo Sallins w/ DJ Kat"),
+            vec!["The Dip", "Kingdom of Birds", "Jo Sallins", "DJ Kat"]
+        );
+        assert_eq!(NormalizerUtils::split_artist_names("Headliner With Opener"), vec!["Headliner", "Opener"]);
+        assert_eq!(NormalizerUtils::split_artist_names("Band A / Band B + Band C"), vec!["Band A", "Band B", "Band C"]);
+        assert_eq!(NormalizerUtils::split_artist_names("Simon and Garfunkel"), vec!["Simon and Garfunkel"]);
+        assert!(NormalizerUtils::split_artist_names("  ").is_empty());
+    }
+
     #[test]
     fn test_extract_title() {
         let data = serde_json::json!({"title": "Test Event"});
@@ -3,9 +3,12 @@ use uuid::Uuid;
 use anyhow::Result;
 
 use super::base::{SourceNormalizer, NormalizerUtils, VenueStateManager, ArtistStateManager};
-use sms_core::domain::{Artist, Event, Venue};
-use crate::pipeline::processing::parser::ParsedRecord;
+use sms_core::domain::{Artist, Event};
+use sms_parsers::ParsedRecord;
 use crate::pipeline::processing::normalize::NormalizedRecord;
+use crate::pipeline::processing::admission::{extract_accessibility_notes, extract_age_restriction};
+use crate::pipeline::processing::price::extract_price;
+use crate::pipeline::processing::venue_resolver::BLUE_MOON;
 
 /// Normalizer for Blue Moon Tavern events  
 pub struct BlueMoonNormalizer {
@@ -65,6 +68,9 @@ impl SourceNormalizer for BlueMoonNormalizer {
                         bio: None,
                         artist_image_url: None,
                         created_at: Utc::now(),
+                        attributions: Vec::new(),
+                        detail_origins: Default::default(),
+                        aliases: Vec::new(),
                     };
 
                     results.push(NormalizerUtils::create_artist_record(
@@ -85,11 +91,20 @@ impl SourceNormalizer for BlueMoonNormalizer {
                 event_url: Some("https://www.bluemoonseattle.com".to_string()),
                 description: data.get("description").and_then(|v| v.as_str()).map(|s| s.to_string()),
                 event_image_url: None,
-                venue_id: Uuid::nil(),
+                venue_id: BLUE_MOON.id(),
                 artist_ids: event_artist_ids,  // Link the artists!
                 show_event: true,
                 finalized: false,
                 created_at: Utc::now(),
+                attributions: Vec::new(),
+                tags: Vec::new(),
+                price: extract_price(data),
+                doors_time: None,
+                series_id: None,
+                age_restriction: extract_age_restriction(data),
+                accessibility_notes: extract_accessibility_notes(data),
+                moderation: None,
+                external_ids: Vec::new(),
             };
 
             results.push(NormalizerUtils::create_event_record(
@@ -103,23 +118,7 @@ impl SourceNormalizer for BlueMoonNormalizer {
         // Create the Blue Moon venue only once
         // Use the venue state manager to ensure thread safety
         if self.venue_state.should_create_venue() {
-            let venue = Venue {
-                id: None,
-                name: "Blue Moon Tavern".to_string(),
-                name_lower: "blue moon tavern".to_string(),
-                slug: "blue-moon-tavern".to_string(),
-                latitude: 47.6608, // U-District location
-                longitude: -122.3126,
-                address: "712 NE 45th St".to_string(),
-                postal_code: "98105".to_string(),
-                city: "Seattle".to_string(),
-                venue_url: Some("https://www.bluemoonseattle.com".to_string()),
-                venue_image_url: None,
-                description: Some("Historic tavern and live music venue in the University District".to_string()),
-                neighborhood: Some("University District".to_string()),
-                show_venue: true,
-                created_at: Utc::now(),
-            };
+            let venue = BLUE_MOON.to_venue();
 
             results.push(NormalizerUtils::create_venue_record(
                 venue, 
@@ -1,13 +1,17 @@
-use chrono::{NaiveDate, NaiveTime, Utc};
+use chrono::{NaiveDate, Utc};
 use uuid::Uuid;
 use anyhow::Result;
 
 use super::base::{SourceNormalizer, NormalizerUtils, VenueStateManager, ArtistStateManager};
-use sms_core::domain::{Artist, Event, Venue};
-use crate::pipeline::processing::parser::ParsedRecord;
-use crate::pipeline::processing::normalize::NormalizedRecord;
+use sms_core::domain::Event;
+use sms_parsers::ParsedRecord;
+use crate::pipeline::processing::normalize::{NormalizedRecord, RecordProvenance};
+use crate::pipeline::processing::admission::{extract_accessibility_notes, extract_age_restriction};
+use crate::pipeline::processing::price::extract_price;
+use crate::pipeline::processing::venue_resolver::CONOR_BYRNE;
 
 /// Normalizer for Conor Byrne events
+/// These come from the VenuePilot GraphQL API via `VenuePilotGraphQLV1Parser`
 pub struct ConorByrneNormalizer {
     venue_state: VenueStateManager,
     artist_state: ArtistStateManager,
@@ -20,6 +24,23 @@ impl ConorByrneNormalizer {
             artist_state: ArtistStateManager::new(),
         }
     }
+
+    /// Link a lineup name unless it names a non-artist listing
+    fn link_artist(
+        &self,
+        name: &str,
+        bio: Option<&str>,
+        confidence: f64,
+        strategy: &str,
+        provenance: &RecordProvenance,
+        results: &mut Vec<NormalizedRecord>,
+    ) -> Option<Uuid> {
+        let name = name.trim();
+        if NormalizerUtils::is_non_artist_event(name) {
+            return None;
+        }
+        self.artist_state.link_artist(name, bio, confidence, strategy, provenance, results)
+    }
 }
 
 impl Default for ConorByrneNormalizer {
@@ -33,188 +54,150 @@ impl SourceNormalizer for ConorByrneNormalizer {
         let mut results = Vec::new();
         let data = &record.record;
         let provenance = NormalizerUtils::create_provenance(record);
+        let venue_id = CONOR_BYRNE.id();
 
-        // Extract title
-        if let Some(title) = NormalizerUtils::extract_title(data) {
-            // Parse event date and time (assuming similar format to other venues)
-            let event_day = data.get("event_day")
-                .and_then(|v| v.as_str())
-                .and_then(|date_str| NaiveDate::parse_from_str(date_str, "%Y-%m-%d").ok())
-                .unwrap_or_else(|| Utc::now().naive_utc().date());
-
-            let start_time = data.get("event_time")
-                .and_then(|v| v.as_str())
-                .and_then(|time_str| {
-                    // Try common time formats
-                    NaiveTime::parse_from_str(time_str, "%I:%M %p")
-                        .or_else(|_| NaiveTime::parse_from_str(time_str, "%l:%M %p"))
-                        .or_else(|_| NaiveTime::parse_from_str(time_str, "%H:%M"))
-                        .ok()
-                });
-
-            // Build event description from available metadata
-            let mut description_parts = Vec::new();
-            
-            if let Some(supporting) = data.get("supporting_acts")
-                .and_then(|v| v.as_str()) 
-                .filter(|s| !s.is_empty()) {
-                description_parts.push(format!("With {}", supporting));
-            }
+        // Create the Conor Byrne venue only once with the same deterministic ID
+        if self.venue_state.should_create_venue() {
+            let venue = CONOR_BYRNE.to_venue();
 
-            if let Some(age) = data.get("age_restriction")
-                .and_then(|v| v.as_str())
-                .filter(|s| !s.is_empty()) {
-                description_parts.push(format!("Age: {}", age));
-            }
+            results.push(NormalizerUtils::create_venue_record(
+                venue,
+                provenance.clone(),
+                1.0,  // Maximum confidence for known venue
+                "conor_byrne_venue_hardcoded".to_string()
+            ));
+        }
+
+        let Some(title) = NormalizerUtils::extract_title(data) else {
+            return Ok(results);
+        };
+
+        // VenuePilot dates are already local YYYY-MM-DD; a listing without one can't be placed
+        let event_day = data.get("event_day")
+            .and_then(|v| v.as_str())
+            .and_then(|date_str| NaiveDate::parse_from_str(date_str.trim(), "%Y-%m-%d").ok())
+            .ok_or_else(|| anyhow::anyhow!("conor_byrne event '{}' has no valid event_day", title))?;
+
+        // The parser emits HH:MM:SS (start time, falling back to doors)
+        let start_time = data.get("start_time")
+            .and_then(|v| v.as_str())
+            .and_then(NormalizerUtils::parse_show_time);
+        let doors_time = data.get("doors_time")
+            .and_then(|v| v.as_str())
+            .and_then(NormalizerUtils::parse_show_time);
+
+        let supporting_acts = data.get("supporting_acts")
+            .and_then(|v| v.as_str())
+            .map(str::trim)
+            .filter(|s| !s.is_empty());
+
+        // Build event description from the listing text plus available metadata
+        let mut description_parts = Vec::new();
+        if let Some(desc) = data.get("description").and_then(|v| v.as_str()).map(str::trim).filter(|s| !s.is_empty()) {
+            description_parts.push(desc.to_string());
+        }
+        if let Some(supporting) = supporting_acts {
+            description_parts.push(format!("With {}", supporting));
+        }
+        if let Some(age) = data.get("age_restriction").and_then(|v| v.as_str()).filter(|s| !s.is_empty()) {
+            description_parts.push(format!("Age: {}", age));
+        }
+        if let Some(promoter) = data.get("promoter").and_then(|v| v.as_str()).filter(|s| !s.is_empty()) {
+            description_parts.push(format!("Presented by {}", promoter));
+        }
+        let description = if description_parts.is_empty() {
+            None
+        } else {
+            Some(description_parts.join(" | "))
+        };
+
+        let event_url = data.get("ticket_url")
+            .or_else(|| data.get("event_url"))
+            .and_then(|v| v.as_str())
+            .filter(|s| !s.is_empty())
+            .map(|s| s.to_string())
+            .or_else(|| CONOR_BYRNE.venue_url.map(str::to_string));
+
+        let event_image_url = data.get("image_url")
+            .and_then(|v| v.as_str())
+            .map(|s| s.to_string());
+
+        // Cancelled shows stay in the catalog but are hidden from listings
+        let cancelled = NormalizerUtils::is_cancelled(data);
 
-            let description = if !description_parts.is_empty() {
-                Some(description_parts.join(" | "))
-            } else {
-                None
-            };
-
-            // Get event URL if available
-            let event_url = data.get("event_url")
-                .and_then(|v| v.as_str())
-                .map(|s| s.to_string())
-                .or_else(|| Some("https://www.conorbyrnepub.com".to_string()));
-
-            // Get image URL if available
-            let event_image_url = data.get("image_url")
-                .and_then(|v| v.as_str())
-                .map(|s| s.to_string());
-
-            // Collect artist IDs as we create artists
-            let mut event_artist_ids = Vec::new();
-            
-            // Extract artists from title and supporting acts
-            if !NormalizerUtils::is_non_artist_event(&title) {
-                let name_slug = NormalizerUtils::generate_slug(&title);
-                
-                // Generate deterministic UUID from the artist slug
-                let artist_id = Uuid::new_v5(&Uuid::NAMESPACE_DNS, name_slug.as_bytes());
-                
-                // Track this artist ID for the event
-                event_artist_ids.push(artist_id);
-                
-                // Only create the artist entity if we haven't seen it before
-                if self.artist_state.should_create_artist(&name_slug) {
-                    let artist = Artist {
-                        id: Some(artist_id),
-                        name: title.clone(),
-                        name_slug,
-                        bio: None,
-                        artist_image_url: None,
-                        created_at: Utc::now(),
-                    };
-
-                    results.push(NormalizerUtils::create_artist_record(
-                        artist, 
-                        provenance.clone(), 
-                        0.9, 
-                        "conor_byrne_artist_headliner".to_string()
-                    ));
+        // VenuePilot's artist list is the most reliable lineup; the title is the fallback headliner
+        let mut event_artist_ids: Vec<Uuid> = Vec::new();
+        let listed_artists: Vec<(&str, Option<&str>)> = data.get("artists")
+            .and_then(|v| v.as_array())
+            .map(|artists| {
+                artists.iter()
+                    .filter_map(|a| {
+                        let name = a.get("name").and_then(|n| n.as_str())?;
+                        Some((name, a.get("bio").and_then(|b| b.as_str())))
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        if listed_artists.is_empty() {
+            for (i, name) in NormalizerUtils::split_artist_names(&title).iter().enumerate() {
+                let (confidence, strategy) = if i == 0 {
+                    (0.9, "conor_byrne_artist_headliner")
+                } else {
+                    (0.85, "conor_byrne_artist_supporting")
+                };
+                if let Some(id) = self.link_artist(name, None, confidence, strategy, &provenance, &mut results) {
+                    event_artist_ids.push(id);
                 }
             }
-
-            // Extract supporting acts as artists
-            if let Some(supporting) = data.get("supporting_acts").and_then(|v| v.as_str()) {
-                // Split supporting acts by common delimiters
-                let acts: Vec<&str> = supporting.split(&[',', '&'][..])
-                    .map(|s| s.trim())
-                    .filter(|s| !s.is_empty())
-                    .collect();
-                
-                for act in acts {
-                    if !NormalizerUtils::is_non_artist_event(act) {
-                        let name_slug = NormalizerUtils::generate_slug(act);
-                        
-                        // Generate deterministic UUID from the artist slug
-                        let artist_id = Uuid::new_v5(&Uuid::NAMESPACE_DNS, name_slug.as_bytes());
-                        
-                        // Track this artist ID for the event
-                        event_artist_ids.push(artist_id);
-                        
-                        // Only create the artist entity if we haven't seen it before
-                        if self.artist_state.should_create_artist(&name_slug) {
-                            let artist = Artist {
-                                id: Some(artist_id),
-                                name: act.to_string(),
-                                name_slug,
-                                bio: None,
-                                artist_image_url: None,
-                                created_at: Utc::now(),
-                            };
-
-                            results.push(NormalizerUtils::create_artist_record(
-                                artist, 
-                                provenance.clone(), 
-                                0.85, 
-                                "conor_byrne_artist_supporting".to_string()
-                            ));
-                        }
-                    }
+        } else {
+            for (name, bio) in listed_artists {
+                if let Some(id) = self.link_artist(name, bio, 0.95, "conor_byrne_artist_listed", &provenance, &mut results) {
+                    event_artist_ids.push(id);
                 }
             }
+        }
 
-            // Generate deterministic venue ID for Conor Byrne
-            let venue_slug = "conor-byrne-pub";
-            let venue_id = Uuid::new_v5(&Uuid::NAMESPACE_DNS, venue_slug.as_bytes());
-
-            // Now create the event with the linked artist IDs and proper venue ID
-            let event = Event {
-                id: None,
-                title: title.clone(),
-                event_day,
-                start_time,
-                event_url,
-                description,
-                event_image_url,
-                venue_id,  // Use the proper venue ID, not nil!
-                artist_ids: event_artist_ids,  // Link the artists!
-                show_event: true,
-                finalized: false,
-                created_at: Utc::now(),
-            };
-
-            results.push(NormalizerUtils::create_event_record(
-                event, 
-                provenance.clone(), 
-                0.95,  // High confidence for Conor Byrne events
-                "conor_byrne_event".to_string()
-            ));
+        if let Some(supporting) = supporting_acts {
+            for act in NormalizerUtils::split_artist_names(supporting) {
+                if let Some(id) = self.link_artist(&act, None, 0.85, "conor_byrne_artist_supporting", &provenance, &mut results) {
+                    event_artist_ids.push(id);
+                }
+            }
         }
+        let mut seen = std::collections::HashSet::new();
+        event_artist_ids.retain(|id| seen.insert(*id));
 
-        // Create the Conor Byrne venue only once with the same deterministic ID
-        if self.venue_state.should_create_venue() {
-            let venue_slug = "conor-byrne-pub";
-            let venue_id = Uuid::new_v5(&Uuid::NAMESPACE_DNS, venue_slug.as_bytes());
-
-            let venue = Venue {
-                id: Some(venue_id),  // Set the deterministic UUID
-                name: "Conor Byrne Pub".to_string(),
-                name_lower: "conor byrne pub".to_string(),
-                slug: venue_slug.to_string(),
-                latitude: 47.6686,  // Conor Byrne's location in Ballard
-                longitude: -122.3842,
-                address: "5140 Ballard Ave NW".to_string(),
-                postal_code: "98107".to_string(),
-                city: "Seattle".to_string(),
-                venue_url: Some("https://www.conorbyrnepub.com".to_string()),
-                venue_image_url: None,
-                description: Some("Historic Irish pub featuring live music in the heart of Ballard".to_string()),
-                neighborhood: Some("Ballard".to_string()),
-                show_venue: true,
-                created_at: Utc::now(),
-            };
+        let event = Event {
+            id: None,
+            title,
+            event_day,
+            start_time,
+            event_url,
+            description,
+            event_image_url,
+            venue_id,
+            artist_ids: event_artist_ids,
+            show_event: !cancelled,
+            finalized: false,
+            created_at: Utc::now(),
+            attributions: Vec::new(),
+            tags: Vec::new(),
+            price: extract_price(data),
+            doors_time,
+            series_id: None,
+            age_restriction: extract_age_restriction(data),
+            accessibility_notes: extract_accessibility_notes(data),
+            moderation: None,
+            external_ids: Vec::new(),
+        };
 
-            results.push(NormalizerUtils::create_venue_record(
-                venue, 
-                provenance.clone(), 
-                1.0,  // Maximum confidence for known venue
-                "conor_byrne_venue_hardcoded".to_string()
-            ));
-        }
+        results.push(NormalizerUtils::create_event_record(
+            event,
+            provenance,
+            0.95,  // High confidence for Conor Byrne events
+            "conor_byrne_event".to_string()
+        ));
 
         Ok(results)
     }
@@ -227,3 +210,68 @@ impl SourceNormalizer for ConorByrneNormalizer {
         "Conor Byrne Pub Normalizer"
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pipeline::processing::normalize::NormalizedEntity;
+    use serde_json::json;
+
+    fn parsed(record: serde_json::Value) -> ParsedRecord {
+        ParsedRecord {
+            source_id: "conor_byrne".to_string(),
+            envelope_id: "env-1".to_string(),
+            payload_ref: "cas:sha256:abcd".to_string(),
+            record_path: "data.paginatedEvents.collection".to_string(),
+            record,
+            attribution: None,
+            change: None,
+            endpoint_id: None,
+            external_id: None,
+        }
+    }
+
+    #[test]
+    fn normalizes_venuepilot_records() {
+        let normalizer = ConorByrneNormalizer::new();
+        let records = normalizer
+            .normalize(&parsed(json!({
+                "title": "The Dip",
+                "event_day": "2025-03-14",
+                "start_time": "20:30:00",
+                "supporting_acts": "Kingdom of Birds & The Dip",
+                "age_restriction": "21+",
+                "ticket_url": "https://tickets.example/dip",
+                "artists": [{ "name": "The Dip", "bio": "Seattle soul" }]
+            })))
+            .unwrap();
+
+        let venues = records.iter().filter(|r| matches!(r.entity, NormalizedEntity::Venue(_))).count();
+        let artists: Vec<_> = records
+            .iter()
+            .filter_map(|r| match &r.entity {
+                NormalizedEntity::Artist(a) => Some(a),
+                _ => None,
+            })
+            .collect();
+        let event = records
+            .iter()
+            .find_map(|r| match &r.entity {
+                NormalizedEntity::Event(e) => Some(e),
+                _ => None,
+            })
+            .unwrap();
+
+        assert_eq!(venues, 1);
+        assert_eq!(artists.len(), 2);
+        assert_eq!(artists[0].bio.as_deref(), Some("Seattle soul"));
+        assert_eq!(event.event_day, NaiveDate::from_ymd_opt(2025, 3, 14).unwrap());
+        assert_eq!(event.start_time, chrono::NaiveTime::from_hms_opt(20, 30, 0));
+        assert_eq!(event.artist_ids.len(), 2, "headliner repeated in support is linked once");
+        assert_eq!(event.event_url.as_deref(), Some("https://tickets.example/dip"));
+        assert!(event.description.as_deref().unwrap().contains("Age: 21+"));
+
+        // A listing without a date is rejected rather than placed on today
+        assert!(normalizer.normalize(&parsed(json!({ "title": "Someone" }))).is_err());
+    }
+}
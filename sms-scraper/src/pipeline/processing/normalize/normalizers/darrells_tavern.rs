@@ -3,9 +3,12 @@ use uuid::Uuid;
 use anyhow::Result;
 
 use super::base::{SourceNormalizer, NormalizerUtils, VenueStateManager, ArtistStateManager};
-use sms_core::domain::{Artist, Event, Venue};
-use crate::pipeline::processing::parser::ParsedRecord;
+use sms_core::domain::{Artist, Event};
+use sms_parsers::ParsedRecord;
 use crate::pipeline::processing::normalize::NormalizedRecord;
+use crate::pipeline::processing::admission::{extract_accessibility_notes, extract_age_restriction};
+use crate::pipeline::processing::price::extract_price;
+use crate::pipeline::processing::venue_resolver::DARRELLS_TAVERN;
 
 /// Normalizer for Darrell's Tavern events
 /// These have minimal data - just title and event_day
@@ -59,6 +62,9 @@ impl SourceNormalizer for DarrellsTavernNormalizer {
                         bio: None,
                         artist_image_url: None,
                         created_at: Utc::now(),
+                        attributions: Vec::new(),
+                        detail_origins: Default::default(),
+                        aliases: Vec::new(),
                     };
 
                     results.push(NormalizerUtils::create_artist_record(
@@ -79,11 +85,20 @@ impl SourceNormalizer for DarrellsTavernNormalizer {
                 event_url: Some("https://www.darrellstavern.com".to_string()),
                 description: None,
                 event_image_url: None,
-                venue_id: Uuid::nil(),
+                venue_id: DARRELLS_TAVERN.id(),
                 artist_ids: event_artist_ids,  // Link the artists!
                 show_event: true,
                 finalized: false,
                 created_at: Utc::now(),
+                attributions: Vec::new(),
+                tags: Vec::new(),
+                price: extract_price(data),
+                doors_time: None,
+                series_id: None,
+                age_restriction: extract_age_restriction(data),
+                accessibility_notes: extract_accessibility_notes(data),
+                moderation: None,
+                external_ids: Vec::new(),
             };
 
             results.push(NormalizerUtils::create_event_record(
@@ -97,23 +112,7 @@ impl SourceNormalizer for DarrellsTavernNormalizer {
         // Create the venue only once for Darrell's Tavern
         // Use the venue state manager to ensure thread safety
         if self.venue_state.should_create_venue() {
-            let venue = Venue {
-                id: None,
-                name: "Darrell's Tavern".to_string(),
-                name_lower: "darrell's tavern".to_string(),
-                slug: "darrells-tavern".to_string(),
-                latitude: 47.6780, // Approximate location in Shoreline
-                longitude: -122.3460,
-                address: "18041 Aurora Ave N".to_string(),
-                postal_code: "98133".to_string(),
-                city: "Shoreline".to_string(),
-                venue_url: Some("https://www.darrellstavern.com".to_string()),
-                venue_image_url: None,
-                description: Some("Live music venue in Shoreline".to_string()),
-                neighborhood: Some("Shoreline".to_string()),
-                show_venue: true,
-                created_at: Utc::now(),
-            };
+            let venue = DARRELLS_TAVERN.to_venue();
 
             results.push(NormalizerUtils::create_venue_record(
                 venue, 
@@ -0,0 +1,197 @@
+use chrono::{NaiveDate, Utc};
+use anyhow::Result;
+
+use super::base::{SourceNormalizer, NormalizerUtils, VenueStateManager, ArtistStateManager};
+use sms_core::domain::{Event, Venue};
+use sms_parsers::ParsedRecord;
+use crate::pipeline::processing::normalize::NormalizedRecord;
+use crate::pipeline::processing::admission::{extract_accessibility_notes, extract_age_restriction};
+use crate::pipeline::processing::price::extract_price;
+use crate::pipeline::processing::venue_resolver::VenueResolver;
+
+/// Normalizer id sources point `pipeline.normalizer_id` at
+pub const GOOGLE_CALENDAR_NORMALIZER_ID: &str = "google_calendar";
+
+/// Normalizer for venues that only publish a public Google Calendar, fed by
+/// `GoogleCalendarV1Parser`. Shared by every such source: the venue is the known venue for
+/// the source, else the one the event's location or the calendar's name resolves to, else a
+/// provisional placeholder named after the location.
+pub struct GoogleCalendarNormalizer {
+    venue_state: VenueStateManager,
+    artist_state: ArtistStateManager,
+    resolver: VenueResolver,
+}
+
+impl GoogleCalendarNormalizer {
+    pub fn new() -> Self {
+        Self {
+            venue_state: VenueStateManager::new(),
+            artist_state: ArtistStateManager::new(),
+            resolver: VenueResolver::new(),
+        }
+    }
+
+    /// The event's venue and the strategy it was found by
+    fn venue(&self, record: &ParsedRecord) -> Option<(Venue, &'static str)> {
+        let data = &record.record;
+        if let Some(known) = self.resolver.for_source(&record.source_id) {
+            return Some((known.to_venue(), "google_calendar_venue_source"));
+        }
+        // "Name, street, city" is how Google Calendar shows a place
+        let location = data.get("location").and_then(|v| v.as_str()).map(str::trim).filter(|l| !l.is_empty());
+        let place = location.map(|l| l.split_once(',').map_or((l, ""), |(name, rest)| (name.trim(), rest.trim())));
+        let calendar = data.get("calendar").and_then(|v| v.as_str()).map(str::trim).filter(|c| !c.is_empty());
+        let known = place.map(|(name, _)| name).into_iter().chain(calendar).find_map(|name| self.resolver.by_name(name));
+        if let Some(known) = known {
+            return Some((known.to_venue(), "google_calendar_venue_known"));
+        }
+        let (name, address) = place.or(calendar.map(|c| (c, "")))?;
+        let mut venue = Venue::placeholder(name);
+        if !address.is_empty() {
+            venue.address = address.to_string();
+        }
+        Some((venue, "google_calendar_venue_placeholder"))
+    }
+
+}
+
+impl Default for GoogleCalendarNormalizer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SourceNormalizer for GoogleCalendarNormalizer {
+    fn normalize(&self, record: &ParsedRecord) -> Result<Vec<NormalizedRecord>> {
+        let mut results = Vec::new();
+        let data = &record.record;
+        let provenance = NormalizerUtils::create_provenance(record);
+
+        let Some(title) = NormalizerUtils::extract_title(data) else {
+            return Ok(results);
+        };
+        let event_day = data.get("event_day")
+            .and_then(|v| v.as_str())
+            .and_then(|day| NaiveDate::parse_from_str(day, "%Y-%m-%d").ok())
+            .ok_or_else(|| anyhow::anyhow!("calendar event '{}' has no valid event_day", title))?;
+        // All-day events have no start time
+        let start_time = data.get("start_time")
+            .and_then(|v| v.as_str())
+            .and_then(NormalizerUtils::parse_show_time);
+
+        let (venue, strategy) = self.venue(record)
+            .ok_or_else(|| anyhow::anyhow!("calendar event '{}' has no location to place it at", title))?;
+        let venue_id = venue.id.expect("known and placeholder venues have ids");
+        if self.venue_state.should_create_placeholder(&venue.slug) {
+            let confidence = if venue.provisional { 0.6 } else { 1.0 };
+            results.push(NormalizerUtils::create_venue_record(venue, provenance.clone(), confidence, strategy.to_string()));
+        }
+
+        // Calendar entries are titled with the lineup, if they're shows at all
+        let mut event_artist_ids = Vec::new();
+        if !NormalizerUtils::is_non_artist_event(&title) {
+            for (i, name) in NormalizerUtils::split_artist_names(&title).iter().enumerate() {
+                let confidence = if i == 0 { 0.8 } else { 0.75 };
+                if let Some(id) = self.artist_state.link_artist(name, None, confidence, "google_calendar_artist_from_title", &provenance, &mut results) {
+                    if !event_artist_ids.contains(&id) {
+                        event_artist_ids.push(id);
+                    }
+                }
+            }
+        }
+
+        // Cancelled entries stay in the catalog but are hidden from listings
+        let cancelled = NormalizerUtils::is_cancelled(data);
+        let event = Event {
+            id: None,
+            title,
+            event_day,
+            start_time,
+            event_url: data.get("event_url").and_then(|v| v.as_str()).map(str::to_string),
+            description: data.get("description").and_then(|v| v.as_str()).map(str::to_string),
+            event_image_url: None,
+            venue_id,
+            artist_ids: event_artist_ids,
+            show_event: !cancelled,
+            finalized: false,
+            created_at: Utc::now(),
+            attributions: Vec::new(),
+            tags: Vec::new(),
+            price: extract_price(data),
+            doors_time: None,
+            series_id: None,
+            age_restriction: extract_age_restriction(data),
+            accessibility_notes: extract_accessibility_notes(data),
+            moderation: None,
+            external_ids: Vec::new(),
+        };
+        results.push(NormalizerUtils::create_event_record(event, provenance, 0.85, "google_calendar_event".to_string()));
+
+        Ok(results)
+    }
+
+    fn source_id(&self) -> &str {
+        GOOGLE_CALENDAR_NORMALIZER_ID
+    }
+
+    fn name(&self) -> &str {
+        "Google Calendar Normalizer"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pipeline::processing::normalize::NormalizedEntity;
+    use serde_json::json;
+
+    fn parsed(source_id: &str, record: serde_json::Value) -> ParsedRecord {
+        ParsedRecord {
+            source_id: source_id.to_string(),
+            envelope_id: "env-1".to_string(),
+            payload_ref: "cas:sha256:abcd".to_string(),
+            record_path: "VCALENDAR.VEVENT[*]".to_string(),
+            record,
+            attribution: None,
+            change: None,
+            endpoint_id: None,
+            external_id: None,
+        }
+    }
+
+    #[test]
+    fn maps_calendar_entries_to_events_at_their_venue() {
+        let normalizer = GoogleCalendarNormalizer::new();
+        let records = normalizer
+            .normalize(&parsed("ballard_corner_bar", json!({
+                "format": "google_calendar",
+                "calendar": "Ballard Corner Bar Shows",
+                "title": "The Dip / Kingdom of Birds",
+                "event_day": "2025-05-01",
+                "start_time": "20:00:00",
+                "location": "Ballard Corner Bar, 5300 Ballard Ave NW, Seattle, WA",
+                "status": "cancelled",
+                "event_url": "https://www.google.com/calendar/event?eid=abc"
+            })))
+            .unwrap();
+
+        let venue = records.iter().find_map(|r| match &r.entity { NormalizedEntity::Venue(v) => Some(v), _ => None }).unwrap();
+        let event = records.iter().find_map(|r| match &r.entity { NormalizedEntity::Event(e) => Some(e), _ => None }).unwrap();
+        assert_eq!((venue.name.as_str(), venue.address.as_str()), ("Ballard Corner Bar", "5300 Ballard Ave NW, Seattle, WA"));
+        assert!(venue.provisional);
+        assert_eq!(event.venue_id, venue.id.unwrap());
+        assert_eq!(event.artist_ids.len(), 2);
+        assert_eq!(event.start_time, chrono::NaiveTime::from_hms_opt(20, 0, 0));
+        assert!(!event.show_event, "cancelled entries are hidden");
+
+        // A calendar at a venue we know places its events there, whatever the location says
+        let records = normalizer
+            .normalize(&parsed("sunset_tavern", json!({ "title": "Open Mic", "event_day": "2025-05-02", "location": "Back room" })))
+            .unwrap();
+        let event = records.iter().find_map(|r| match &r.entity { NormalizedEntity::Event(e) => Some(e), _ => None }).unwrap();
+        assert_eq!(event.venue_id, crate::pipeline::processing::venue_resolver::SUNSET_TAVERN.id());
+        assert_eq!(event.start_time, None);
+
+        assert!(normalizer.normalize(&parsed("somewhere", json!({ "title": "No Place", "event_day": "2025-05-02" }))).is_err());
+    }
+}
@@ -4,8 +4,11 @@ use anyhow::Result;
 
 use super::base::{SourceNormalizer, NormalizerUtils, VenueStateManager, ArtistStateManager};
 use sms_core::domain::{Artist, Event, Venue};
-use crate::pipeline::processing::parser::ParsedRecord;
+use sms_parsers::ParsedRecord;
 use crate::pipeline::processing::normalize::NormalizedRecord;
+use crate::pipeline::processing::admission::{extract_accessibility_notes, extract_age_restriction};
+use crate::pipeline::processing::price::extract_price;
+use crate::pipeline::processing::venue_resolver::{VenueResolver, KEXP};
 
 /// Normalizer for KEXP events
 /// These are scraped from KEXP's HTML events page
@@ -69,6 +72,28 @@ impl SourceNormalizer for KexpNormalizer {
                 .map(|s| s.to_string())
                 .or_else(|| location.clone()); // Use location as description if no description
 
+            // Listings at venues we know get that venue's id straight away; other venues park
+            // on a provisional placeholder until they're cataloged
+            let resolver = VenueResolver::new();
+            let placeholder = location
+                .as_deref()
+                .filter(|loc| !loc.trim().is_empty() && !loc.trim().to_lowercase().starts_with("kexp"))
+                .map(|loc| match resolver.by_name(loc) {
+                    Some(known) => (known.to_venue(), "kexp_venue_known"),
+                    None => (Venue::placeholder(loc), "kexp_venue_placeholder"),
+                });
+            if let Some((venue, strategy)) = &placeholder {
+                if self.venue_state.should_create_placeholder(&venue.slug) {
+                    let confidence = if venue.provisional { 0.6 } else { 0.9 };
+                    results.push(NormalizerUtils::create_venue_record(
+                        venue.clone(),
+                        provenance.clone(),
+                        confidence,
+                        strategy.to_string()
+                    ));
+                }
+            }
+
             // Collect artist IDs as we create artists
             let mut event_artist_ids = Vec::new();
             
@@ -91,6 +116,9 @@ impl SourceNormalizer for KexpNormalizer {
                         bio: None,
                         artist_image_url: None,
                         created_at: Utc::now(),
+                        attributions: Vec::new(),
+                        detail_origins: Default::default(),
+                        aliases: Vec::new(),
                     };
 
                     results.push(NormalizerUtils::create_artist_record(
@@ -111,11 +139,20 @@ impl SourceNormalizer for KexpNormalizer {
                 event_url: Some("https://www.kexp.org/events/".to_string()),
                 description,
                 event_image_url: None,
-                venue_id: Uuid::nil(),
+                venue_id: placeholder.and_then(|(v, _)| v.id).unwrap_or_else(|| KEXP.id()),
                 artist_ids: event_artist_ids,  // Link the artists!
                 show_event: true,
                 finalized: false,
                 created_at: Utc::now(),
+                attributions: Vec::new(),
+                tags: Vec::new(),
+                price: extract_price(data),
+                doors_time: None,
+                series_id: None,
+                age_restriction: extract_age_restriction(data),
+                accessibility_notes: extract_accessibility_notes(data),
+                moderation: None,
+                external_ids: Vec::new(),
             };
 
             results.push(NormalizerUtils::create_event_record(
@@ -129,23 +166,7 @@ impl SourceNormalizer for KexpNormalizer {
         // Create the KEXP venue only once
         // Use the venue state manager to ensure thread safety
         if self.venue_state.should_create_venue() {
-            let venue = Venue {
-                id: None,
-                name: "KEXP Events".to_string(),
-                name_lower: "kexp events".to_string(),
-                slug: "kexp-events".to_string(),
-                latitude: 47.6205, // KEXP location in Lower Queen Anne
-                longitude: -122.3493,
-                address: "472 1st Ave N".to_string(),
-                postal_code: "98109".to_string(),
-                city: "Seattle".to_string(),
-                venue_url: Some("https://www.kexp.org/events/".to_string()),
-                venue_image_url: None,
-                description: Some("KEXP Radio Station and live event venue in Lower Queen Anne".to_string()),
-                neighborhood: Some("Lower Queen Anne".to_string()),
-                show_venue: true,
-                created_at: Utc::now(),
-            };
+            let venue = KEXP.to_venue();
 
             results.push(NormalizerUtils::create_venue_record(
                 venue, 
@@ -6,9 +6,12 @@ pub mod barboza;
 pub mod blue_moon;
 pub mod conor_byrne;
 pub mod darrells_tavern;
+pub mod google_calendar;
 pub mod kexp;
 pub mod neumos;
+pub mod newsletter;
 pub mod sea_monster;
+pub mod sunset_tavern;
 
 // Re-export the main components
 pub use base::{SourceNormalizer, MetricsNormalizer};
@@ -16,6 +19,18 @@ pub use barboza::BarbozaNormalizer;
 pub use blue_moon::BlueMoonNormalizer;
 pub use conor_byrne::ConorByrneNormalizer;
 pub use darrells_tavern::DarrellsTavernNormalizer;
+pub use google_calendar::{GoogleCalendarNormalizer, GOOGLE_CALENDAR_NORMALIZER_ID};
 pub use kexp::KexpNormalizer;
 pub use neumos::NeumosNormalizer;
+pub use newsletter::{NewsletterNormalizer, NEWSLETTER_NORMALIZER_ID};
 pub use sea_monster::SeaMonsterNormalizer;
+pub use sunset_tavern::SunsetTavernNormalizer;
+
+/// Normalizers being evaluated as replacements. Each runs in shadow mode beside the
+/// registered normalizer for its `source_id()`: the primary's output still goes downstream,
+/// and field-level differences are appended to the shadow NDJSON log
+/// (`SMS_NORMALIZER_SHADOW_LOG`, default `data/shadow/normalize.ndjson`). Add a candidate
+/// here, review the log over a few runs, then swap it into `NormalizationRegistry::new`.
+pub fn shadow_candidates() -> Vec<Box<dyn SourceNormalizer>> {
+    Vec::new()
+}
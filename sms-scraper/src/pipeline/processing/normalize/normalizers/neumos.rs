@@ -1,11 +1,14 @@
-use chrono::{NaiveDate, NaiveTime, Utc};
+use chrono::{NaiveDate, Utc};
 use uuid::Uuid;
 use anyhow::Result;
 
 use super::base::{SourceNormalizer, NormalizerUtils, VenueStateManager, ArtistStateManager};
-use sms_core::domain::{Artist, Event, Venue};
-use crate::pipeline::processing::parser::ParsedRecord;
+use sms_core::domain::{Artist, Event};
+use sms_parsers::ParsedRecord;
 use crate::pipeline::processing::normalize::NormalizedRecord;
+use crate::pipeline::processing::admission::{extract_accessibility_notes, extract_age_restriction};
+use crate::pipeline::processing::price::extract_price;
+use crate::pipeline::processing::venue_resolver::NEUMOS;
 
 /// Normalizer for Neumos events
 /// These are scraped from Neumos' HTML events page
@@ -43,16 +46,12 @@ impl SourceNormalizer for NeumosNormalizer {
                 .and_then(|date_str| NaiveDate::parse_from_str(date_str, "%Y-%m-%d").ok())
                 .unwrap_or_else(|| Utc::now().naive_utc().date());
 
-            // Parse start time from event_time field
-            let start_time = data.get("event_time")
+            // time_text reads like "Doors: 7:00 PM", sometimes with a separate show time
+            let times = data.get("time_text")
+                .or_else(|| data.get("event_time"))
                 .and_then(|v| v.as_str())
-                .and_then(|time_str| {
-                    // Try common time formats (e.g., "7:00 PM", "19:00")
-                    NaiveTime::parse_from_str(time_str, "%I:%M %p")
-                        .or_else(|_| NaiveTime::parse_from_str(time_str, "%l:%M %p"))
-                        .or_else(|_| NaiveTime::parse_from_str(time_str, "%H:%M"))
-                        .ok()
-                });
+                .map(NormalizerUtils::parse_show_times)
+                .unwrap_or_default();
 
             // Build event URL from ticket_url or detail_url
             let event_url = data.get("ticket_url")
@@ -126,6 +125,9 @@ impl SourceNormalizer for NeumosNormalizer {
                     bio: None,
                     artist_image_url: None,
                     created_at: Utc::now(),
+                    attributions: Vec::new(),
+                    detail_origins: Default::default(),
+                    aliases: Vec::new(),
                 };
 
                 results.push(NormalizerUtils::create_artist_record(
@@ -220,29 +222,11 @@ impl SourceNormalizer for NeumosNormalizer {
                 }
             }
 
-            // Generate a deterministic UUID for Neumos venue based on its slug
-            let venue_slug = "neumos";
-            let venue_id = Uuid::new_v5(&Uuid::NAMESPACE_DNS, venue_slug.as_bytes());
+            let venue_id = NEUMOS.id();
             
             // Create the venue (only if not already created)
             if self.venue_state.should_create_venue() {
-                let venue = Venue {
-                    id: Some(venue_id),  // Use deterministic UUID
-                    name: "Neumos".to_string(),
-                    name_lower: "neumos".to_string(),
-                    slug: venue_slug.to_string(),
-                    latitude: 47.614746,  // Neumos' location on Capitol Hill
-                    longitude: -122.319532,
-                    address: "925 E Pike St".to_string(),
-                    postal_code: "98122".to_string(),
-                    city: "Seattle".to_string(),
-                    venue_url: Some("https://www.neumos.com".to_string()),
-                    venue_image_url: None,
-                    description: Some("Legendary Capitol Hill music venue featuring live bands and DJ nights".to_string()),
-                    neighborhood: Some("Capitol Hill".to_string()),
-                    show_venue: true,
-                    created_at: Utc::now(),
-                };
+                let venue = NEUMOS.to_venue();
 
                 results.push(NormalizerUtils::create_venue_record(
                     venue, 
@@ -257,7 +241,7 @@ impl SourceNormalizer for NeumosNormalizer {
                 id: None,
                 title: title.to_string(),
                 event_day,
-                start_time,
+                start_time: times.start_time(),
                 event_url,
                 description,
                 event_image_url,
@@ -266,6 +250,15 @@ impl SourceNormalizer for NeumosNormalizer {
                 show_event: true,
                 finalized: false,
                 created_at: Utc::now(),
+                attributions: Vec::new(),
+                tags: Vec::new(),
+                price: extract_price(data),
+                doors_time: times.doors,
+                series_id: None,
+                age_restriction: extract_age_restriction(data),
+                accessibility_notes: extract_accessibility_notes(data),
+                moderation: None,
+                external_ids: Vec::new(),
             };
 
             results.push(NormalizerUtils::create_event_record(
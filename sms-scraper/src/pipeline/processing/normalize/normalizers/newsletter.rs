@@ -0,0 +1,183 @@
+use chrono::{NaiveDate, Utc};
+use anyhow::Result;
+
+use super::base::{SourceNormalizer, NormalizerUtils, VenueStateManager, ArtistStateManager};
+use sms_core::domain::{Event, Venue};
+use sms_parsers::ParsedRecord;
+use crate::pipeline::processing::normalize::NormalizedRecord;
+use crate::pipeline::processing::admission::{extract_accessibility_notes, extract_age_restriction};
+use crate::pipeline::processing::price::extract_price;
+use crate::pipeline::processing::venue_resolver::VenueResolver;
+
+/// Normalizer id sources point `pipeline.normalizer_id` at
+pub const NEWSLETTER_NORMALIZER_ID: &str = "newsletter_email";
+
+/// Normalizer for venues that announce shows by email, fed by `NewsletterEmailV1Parser`, and
+/// for the events the LLM fallback reads off pages, which have the same free-text shape.
+/// Shared by every newsletter source: the venue is the known venue for the source, else the
+/// one the sender's name resolves to, else a provisional placeholder named after the sender.
+pub struct NewsletterNormalizer {
+    venue_state: VenueStateManager,
+    artist_state: ArtistStateManager,
+    resolver: VenueResolver,
+}
+
+impl NewsletterNormalizer {
+    pub fn new() -> Self {
+        Self {
+            venue_state: VenueStateManager::new(),
+            artist_state: ArtistStateManager::new(),
+            resolver: VenueResolver::new(),
+        }
+    }
+
+    /// The event's venue and the strategy it was found by
+    fn venue(&self, record: &ParsedRecord) -> Option<(Venue, &'static str)> {
+        if let Some(known) = self.resolver.for_source(&record.source_id) {
+            return Some((known.to_venue(), "newsletter_venue_source"));
+        }
+        // A newsletter's sender, or the venue the LLM fallback read off the page
+        let sender = ["sender", "venue_name"]
+            .iter()
+            .find_map(|key| record.record.get(*key).and_then(|v| v.as_str()).map(str::trim).filter(|s| !s.is_empty()))?;
+        if let Some(known) = self.resolver.by_name(sender) {
+            return Some((known.to_venue(), "newsletter_venue_known"));
+        }
+        Some((Venue::placeholder(sender), "newsletter_venue_placeholder"))
+    }
+
+}
+
+impl Default for NewsletterNormalizer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SourceNormalizer for NewsletterNormalizer {
+    fn normalize(&self, record: &ParsedRecord) -> Result<Vec<NormalizedRecord>> {
+        let mut results = Vec::new();
+        let data = &record.record;
+        let provenance = NormalizerUtils::create_provenance(record);
+
+        let Some(title) = NormalizerUtils::extract_title(data) else {
+            return Ok(results);
+        };
+        let event_day = data.get("event_day")
+            .and_then(|v| v.as_str())
+            .and_then(|day| NaiveDate::parse_from_str(day, "%Y-%m-%d").ok())
+            .ok_or_else(|| anyhow::anyhow!("newsletter event '{}' has no valid event_day", title))?;
+        let time = |key: &str| data.get(key).and_then(|v| v.as_str()).and_then(NormalizerUtils::parse_show_time);
+
+        let (venue, strategy) = self.venue(record)
+            .ok_or_else(|| anyhow::anyhow!("newsletter event '{}' has no sender to place it at", title))?;
+        let venue_id = venue.id.expect("known and placeholder venues have ids");
+        if self.venue_state.should_create_placeholder(&venue.slug) {
+            let confidence = if venue.provisional { 0.5 } else { 1.0 };
+            results.push(NormalizerUtils::create_venue_record(venue, provenance.clone(), confidence, strategy.to_string()));
+        }
+
+        // Titles are pulled out of free-form copy, so artists found in them are less certain
+        // than a calendar's
+        let mut event_artist_ids = Vec::new();
+        if !NormalizerUtils::is_non_artist_event(&title) {
+            for (i, name) in NormalizerUtils::split_artist_names(&title).iter().enumerate() {
+                let confidence = if i == 0 { 0.7 } else { 0.65 };
+                if let Some(id) = self.artist_state.link_artist(name, None, confidence, "newsletter_artist_from_title", &provenance, &mut results) {
+                    if !event_artist_ids.contains(&id) {
+                        event_artist_ids.push(id);
+                    }
+                }
+            }
+        }
+
+        let event = Event {
+            id: None,
+            title,
+            event_day,
+            start_time: time("start_time"),
+            event_url: data.get("event_url").and_then(|v| v.as_str()).map(str::to_string),
+            description: data.get("description").and_then(|v| v.as_str()).map(str::to_string),
+            event_image_url: None,
+            venue_id,
+            artist_ids: event_artist_ids,
+            show_event: true,
+            finalized: false,
+            created_at: Utc::now(),
+            attributions: Vec::new(),
+            tags: Vec::new(),
+            price: extract_price(data),
+            doors_time: time("doors_time"),
+            series_id: None,
+            age_restriction: extract_age_restriction(data),
+            accessibility_notes: extract_accessibility_notes(data),
+            moderation: None,
+            external_ids: Vec::new(),
+        };
+        results.push(NormalizerUtils::create_event_record(event, provenance, 0.75, "newsletter_event".to_string()));
+
+        Ok(results)
+    }
+
+    fn source_id(&self) -> &str {
+        NEWSLETTER_NORMALIZER_ID
+    }
+
+    fn name(&self) -> &str {
+        "Newsletter Normalizer"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pipeline::processing::normalize::NormalizedEntity;
+    use serde_json::json;
+
+    fn parsed(source_id: &str, record: serde_json::Value) -> ParsedRecord {
+        ParsedRecord {
+            source_id: source_id.to_string(),
+            envelope_id: "env-1".to_string(),
+            payload_ref: "cas:sha256:abcd".to_string(),
+            record_path: "$.blocks[1]".to_string(),
+            record,
+            attribution: None,
+            change: None,
+            endpoint_id: None,
+            external_id: None,
+        }
+    }
+
+    #[test]
+    fn places_newsletter_events_at_the_sending_venue() {
+        let normalizer = NewsletterNormalizer::new();
+        let records = normalizer
+            .normalize(&parsed("tractor_newsletter", json!({
+                "format": "newsletter_email",
+                "title": "The Dip with Kingdom of Birds",
+                "event_day": "2025-05-02",
+                "start_time": "20:00:00",
+                "doors_time": "19:00:00",
+                "description": "Fri May 2 - The Dip with Kingdom of Birds | Doors 7pm, show 8pm | $15",
+                "sender": "Tractor Tavern"
+            })))
+            .unwrap();
+
+        let venue = records.iter().find_map(|r| match &r.entity { NormalizedEntity::Venue(v) => Some(v), _ => None }).unwrap();
+        let event = records.iter().find_map(|r| match &r.entity { NormalizedEntity::Event(e) => Some(e), _ => None }).unwrap();
+        assert_eq!(venue.name, "Tractor Tavern");
+        assert!(venue.provisional);
+        assert_eq!(event.venue_id, venue.id.unwrap());
+        assert_eq!(event.artist_ids.len(), 2);
+        assert_eq!(event.doors_time, chrono::NaiveTime::from_hms_opt(19, 0, 0));
+
+        // A newsletter source at a venue we know places its events there, whoever sent it
+        let records = normalizer
+            .normalize(&parsed("sunset_tavern", json!({ "title": "Open Mic", "event_day": "2025-05-03", "sender": "Mailchimp" })))
+            .unwrap();
+        let event = records.iter().find_map(|r| match &r.entity { NormalizedEntity::Event(e) => Some(e), _ => None }).unwrap();
+        assert_eq!(event.venue_id, crate::pipeline::processing::venue_resolver::SUNSET_TAVERN.id());
+
+        assert!(normalizer.normalize(&parsed("somewhere", json!({ "title": "No Sender", "event_day": "2025-05-03" }))).is_err());
+    }
+}
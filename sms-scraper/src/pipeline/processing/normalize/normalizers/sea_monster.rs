@@ -4,8 +4,11 @@ use anyhow::Result;
 
 use super::base::{SourceNormalizer, NormalizerUtils, VenueStateManager, ArtistStateManager};
 use sms_core::domain::{Artist, Event, Venue};
-use crate::pipeline::processing::parser::ParsedRecord;
+use sms_parsers::ParsedRecord;
 use crate::pipeline::processing::normalize::NormalizedRecord;
+use crate::pipeline::processing::admission::{extract_accessibility_notes, extract_age_restriction};
+use crate::pipeline::processing::price::extract_price;
+use crate::pipeline::processing::venue_resolver::{VenueResolver, SEA_MONSTER};
 
 /// Normalizer for Sea Monster Lounge events
 /// These have rich location data in nested JSON structure
@@ -29,11 +32,47 @@ impl Default for SeaMonsterNormalizer {
     }
 }
 
+/// The venue from the listing's `location`, falling back to the known Sea Monster details.
+/// It keeps the known venue's id when the name and coordinates still resolve to it.
+fn listed_venue(data: &serde_json::Value) -> Venue {
+    let mut venue = SEA_MONSTER.to_venue();
+    let Some(location) = data.get("location").and_then(|l| l.as_object()) else {
+        return venue;
+    };
+    let text = |v: Option<&serde_json::Value>| v.and_then(|v| v.as_str()).map(str::to_string);
+
+    if let Some(name) = text(location.get("name")) {
+        venue.name_lower = name.to_lowercase();
+        venue.name = name;
+    }
+    if let Some(full_addr) = location.get("fullAddress").and_then(|f| f.as_object()) {
+        venue.address = text(full_addr.get("formattedAddress")).unwrap_or(venue.address);
+        venue.city = text(full_addr.get("city")).unwrap_or(venue.city);
+        venue.postal_code = text(full_addr.get("postalCode")).unwrap_or(venue.postal_code);
+        let subdivision = full_addr.get("subdivisions")
+            .and_then(|s| s.as_array())
+            .and_then(|arr| arr.iter().find(|s| s["type"] == 4))
+            .and_then(|s| s.get("name"));
+        venue.neighborhood = text(subdivision).or(venue.neighborhood);
+    }
+    if let Some(coords) = location.get("coordinates").and_then(|c| c.as_object()) {
+        venue.latitude = coords.get("lat").and_then(|v| v.as_f64()).unwrap_or(venue.latitude);
+        venue.longitude = coords.get("lng").and_then(|v| v.as_f64()).unwrap_or(venue.longitude);
+    }
+
+    if VenueResolver::new().resolve_venue(&venue) != Some(&SEA_MONSTER) {
+        venue.id = None;
+        venue.slug = NormalizerUtils::generate_slug(&venue.name);
+    }
+    venue
+}
+
 impl SourceNormalizer for SeaMonsterNormalizer {
     fn normalize(&self, record: &ParsedRecord) -> Result<Vec<NormalizedRecord>> {
         let mut results = Vec::new();
         let data = &record.record;
         let provenance = NormalizerUtils::create_provenance(record);
+        let venue = listed_venue(data);
 
         // Extract event from title and scheduling fields
         if let Some(title) = NormalizerUtils::extract_title(data) {
@@ -89,6 +128,9 @@ impl SourceNormalizer for SeaMonsterNormalizer {
                         bio: None,
                         artist_image_url: None,
                         created_at: Utc::now(),
+                        attributions: Vec::new(),
+                        detail_origins: Default::default(),
+                        aliases: Vec::new(),
                     };
 
                     results.push(NormalizerUtils::create_artist_record(
@@ -109,11 +151,20 @@ impl SourceNormalizer for SeaMonsterNormalizer {
                 event_url,
                 description,
                 event_image_url,
-                venue_id: Uuid::nil(),
+                venue_id: venue.id.unwrap_or_else(Uuid::nil),
                 artist_ids: event_artist_ids,  // Link the artists!
                 show_event: true,
                 finalized: false,
                 created_at: Utc::now(),
+                attributions: Vec::new(),
+                tags: Vec::new(),
+                price: extract_price(data),
+                doors_time: None,
+                series_id: None,
+                age_restriction: extract_age_restriction(data),
+                accessibility_notes: extract_accessibility_notes(data),
+                moderation: None,
+                external_ids: Vec::new(),
             };
 
             results.push(NormalizerUtils::create_event_record(
@@ -127,87 +178,6 @@ impl SourceNormalizer for SeaMonsterNormalizer {
         // Create the Sea Monster venue only once
         // Use the venue state manager to ensure thread safety
         if self.venue_state.should_create_venue() {
-            // Try to extract venue details from location field if available, otherwise use defaults
-            let (name, address, city, postal_code, neighborhood, latitude, longitude) = 
-                if let Some(location) = data.get("location").and_then(|l| l.as_object()) {
-                    let name = location.get("name")
-                        .and_then(|v| v.as_str())
-                        .unwrap_or("Sea Monster Lounge")
-                        .to_string();
-
-                    // Extract detailed address information
-                    let (address, city, postal_code, neighborhood) = 
-                        if let Some(full_addr) = location.get("fullAddress").and_then(|f| f.as_object()) {
-                            let address = full_addr.get("formattedAddress")
-                                .and_then(|v| v.as_str())
-                                .unwrap_or("2202 N 45th St, Seattle, WA 98103")
-                                .to_string();
-                            let city = full_addr.get("city")
-                                .and_then(|v| v.as_str())
-                                .unwrap_or("Seattle")
-                                .to_string();
-                            let postal_code = full_addr.get("postalCode")
-                                .and_then(|v| v.as_str())
-                                .unwrap_or("98103")
-                                .to_string();
-                            let neighborhood = full_addr.get("subdivisions")
-                                .and_then(|s| s.as_array())
-                                .and_then(|arr| arr.iter().find(|s| s["type"] == 4))
-                                .and_then(|s| s.get("name"))
-                                .and_then(|v| v.as_str())
-                                .map(|s| s.to_string())
-                                .or(Some("Wallingford".to_string()));
-                            (address, city, postal_code, neighborhood)
-                        } else {
-                            ("2202 N 45th St, Seattle, WA 98103".to_string(), 
-                             "Seattle".to_string(), 
-                             "98103".to_string(), 
-                             Some("Wallingford".to_string()))
-                        };
-
-                    // Extract coordinates
-                    let (latitude, longitude) = if let Some(coords) = location.get("coordinates").and_then(|c| c.as_object()) {
-                        (
-                            coords.get("lat").and_then(|v| v.as_f64()).unwrap_or(47.6615064),
-                            coords.get("lng").and_then(|v| v.as_f64()).unwrap_or(-122.3323427),
-                        )
-                    } else {
-                        (47.6615064, -122.3323427) // Sea Monster default coords
-                    };
-
-                    (name, address, city, postal_code, neighborhood, latitude, longitude)
-                } else {
-                    // Default values if no location data is available
-                    ("Sea Monster Lounge".to_string(),
-                     "2202 N 45th St, Seattle, WA 98103".to_string(),
-                     "Seattle".to_string(),
-                     "98103".to_string(),
-                     Some("Wallingford".to_string()),
-                     47.6615064,
-                     -122.3323427)
-                };
-
-            let name_lower = name.to_lowercase();
-            let slug = NormalizerUtils::generate_slug(&name);
-
-            let venue = Venue {
-                id: None,
-                name,
-                name_lower,
-                slug,
-                latitude,
-                longitude,
-                address,
-                postal_code,
-                city,
-                venue_url: Some("https://www.seamonsterlounge.com".to_string()),
-                venue_image_url: None,
-                description: Some("Live music venue in Wallingford".to_string()),
-                neighborhood,
-                show_venue: true,
-                created_at: Utc::now(),
-            };
-
             results.push(NormalizerUtils::create_venue_record(
                 venue, 
                 provenance.clone(), 
@@ -0,0 +1,321 @@
+use chrono::{DateTime, NaiveDate, NaiveTime, Utc};
+use chrono_tz::Tz;
+use uuid::Uuid;
+use anyhow::Result;
+
+use super::base::{SourceNormalizer, NormalizerUtils, VenueStateManager, ArtistStateManager};
+use sms_core::domain::Event;
+use sms_parsers::ParsedRecord;
+use crate::pipeline::processing::normalize::{NormalizedRecord, RecordProvenance};
+use crate::pipeline::processing::admission::{extract_accessibility_notes, extract_age_restriction};
+use crate::pipeline::processing::price::extract_price;
+use crate::pipeline::processing::venue_resolver::SUNSET_TAVERN;
+
+const VENUE_TZ: Tz = chrono_tz::America::Los_Angeles;
+
+/// Normalizer for Sunset Tavern events
+/// These are Dice partner API events, either raw (`attributes` wrapper) or already flattened
+pub struct SunsetTavernNormalizer {
+    venue_state: VenueStateManager,
+    artist_state: ArtistStateManager,
+}
+
+impl SunsetTavernNormalizer {
+    pub fn new() -> Self {
+        Self {
+            venue_state: VenueStateManager::new(),
+            artist_state: ArtistStateManager::new(),
+        }
+    }
+
+    /// Link a lineup name unless it names a non-artist listing or a schedule line
+    fn link_artist(
+        &self,
+        name: &str,
+        confidence: f64,
+        strategy: &str,
+        provenance: &RecordProvenance,
+        results: &mut Vec<NormalizedRecord>,
+    ) -> Option<Uuid> {
+        let name = name.trim();
+        if NormalizerUtils::is_non_artist_event(name) || is_schedule_entry(name) {
+            return None;
+        }
+        self.artist_state.link_artist(name, None, confidence, strategy, provenance, results)
+    }
+}
+
+impl Default for SunsetTavernNormalizer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Dice lineups interleave acts with schedule lines like "Doors open"
+fn is_schedule_entry(name: &str) -> bool {
+    let lower = name.to_lowercase();
+    lower.starts_with("doors") || lower.starts_with("show") || lower.contains("curfew")
+}
+
+/// Local day and time of a show. Dice reports an RFC 3339 instant plus the venue's timezone;
+/// flattened records carry `event_day` and `start_time` directly.
+fn show_day_and_time(data: &serde_json::Value) -> Option<(NaiveDate, Option<NaiveTime>)> {
+    if let Some(instant) = data.get("date")
+        .and_then(|v| v.as_str())
+        .and_then(|d| DateTime::parse_from_rfc3339(d.trim()).ok())
+    {
+        let tz = data.get("timezone")
+            .and_then(|v| v.as_str())
+            .and_then(|name| name.parse::<Tz>().ok())
+            .unwrap_or(VENUE_TZ);
+        let local = instant.with_timezone(&tz);
+        return Some((local.date_naive(), Some(local.time())));
+    }
+
+    let day_str = data.get("event_day").or_else(|| data.get("date")).and_then(|v| v.as_str())?;
+    let day = NaiveDate::parse_from_str(day_str.trim(), "%Y-%m-%d").ok()?;
+    let time = data.get("start_time")
+        .or_else(|| data.get("event_time"))
+        .and_then(|v| v.as_str())
+        .and_then(NormalizerUtils::parse_show_time);
+    Some((day, time))
+}
+
+/// When doors open, from the Dice lineup's "Doors open" entry or a flattened `doors_time`
+fn doors_time(data: &serde_json::Value) -> Option<NaiveTime> {
+    data.get("lineup")
+        .and_then(|v| v.as_array())
+        .and_then(|lineup| {
+            lineup.iter()
+                .find(|l| l.get("details").and_then(|d| d.as_str()).is_some_and(|d| d.trim().to_lowercase().starts_with("doors")))
+                .and_then(|l| l.get("time"))
+        })
+        .or_else(|| data.get("doors_time"))
+        .and_then(|v| v.as_str())
+        .and_then(NormalizerUtils::parse_show_time)
+}
+
+/// Act names in billing order: `artists`, then the Dice `lineup`, then the title
+fn lineup_names(data: &serde_json::Value, title: &str) -> Vec<(String, bool)> {
+    let from_artists: Vec<String> = data.get("artists")
+        .and_then(|v| v.as_array())
+        .map(|artists| {
+            artists.iter()
+                .filter_map(|a| a.as_str().or_else(|| a.get("name").and_then(|n| n.as_str())))
+                .map(str::to_string)
+                .collect()
+        })
+        .unwrap_or_default();
+    if !from_artists.is_empty() {
+        return from_artists.into_iter().map(|name| (name, true)).collect();
+    }
+
+    let from_lineup: Vec<String> = data.get("lineup")
+        .and_then(|v| v.as_array())
+        .map(|lineup| {
+            lineup.iter()
+                .filter_map(|l| l.get("details").and_then(|d| d.as_str()))
+                .flat_map(NormalizerUtils::split_artist_names)
+                .collect()
+        })
+        .unwrap_or_default();
+    if !from_lineup.is_empty() {
+        return from_lineup.into_iter().map(|name| (name, true)).collect();
+    }
+
+    NormalizerUtils::split_artist_names(title).into_iter().map(|name| (name, false)).collect()
+}
+
+impl SourceNormalizer for SunsetTavernNormalizer {
+    fn normalize(&self, record: &ParsedRecord) -> Result<Vec<NormalizedRecord>> {
+        let mut results = Vec::new();
+        // Raw Dice events nest their fields under `attributes`
+        let data = record.record.get("attributes").unwrap_or(&record.record);
+        let provenance = NormalizerUtils::create_provenance(record);
+        let venue_id = SUNSET_TAVERN.id();
+
+        // Create the Sunset Tavern venue only once with the same deterministic ID
+        if self.venue_state.should_create_venue() {
+            let venue = SUNSET_TAVERN.to_venue();
+
+            results.push(NormalizerUtils::create_venue_record(
+                venue,
+                provenance.clone(),
+                1.0,  // Maximum confidence for known venue
+                "sunset_tavern_venue_hardcoded".to_string()
+            ));
+        }
+
+        let Some(title) = NormalizerUtils::extract_title(data)
+            .or_else(|| data.get("name").and_then(|v| v.as_str()).map(str::trim).filter(|s| !s.is_empty()).map(str::to_string))
+        else {
+            return Ok(results);
+        };
+
+        let (event_day, start_time) = show_day_and_time(data)
+            .ok_or_else(|| anyhow::anyhow!("sunset_tavern event '{}' has no valid date", title))?;
+
+        let mut description_parts = Vec::new();
+        if let Some(desc) = data.get("description").and_then(|v| v.as_str()).map(str::trim).filter(|s| !s.is_empty()) {
+            description_parts.push(desc.to_string());
+        }
+        if let Some(age) = data.get("age_limit")
+            .or_else(|| data.get("age_restriction"))
+            .and_then(|v| v.as_str())
+            .filter(|s| !s.is_empty())
+        {
+            description_parts.push(format!("Age: {}", age));
+        }
+        let description = if description_parts.is_empty() {
+            None
+        } else {
+            Some(description_parts.join(" | "))
+        };
+
+        let event_url = data.get("url")
+            .or_else(|| data.get("event_url"))
+            .or_else(|| data.get("ticket_url"))
+            .and_then(|v| v.as_str())
+            .filter(|s| !s.is_empty())
+            .map(|s| s.to_string())
+            .or_else(|| SUNSET_TAVERN.venue_url.map(str::to_string));
+
+        // Dice images are either bare URLs or objects with a `url`
+        let event_image_url = data.get("images")
+            .and_then(|v| v.as_array())
+            .and_then(|images| images.first())
+            .and_then(|img| img.as_str().or_else(|| img.get("url").and_then(|u| u.as_str())))
+            .or_else(|| data.get("image_url").and_then(|v| v.as_str()))
+            .map(|s| s.to_string());
+
+        let mut event_artist_ids: Vec<Uuid> = Vec::new();
+        for (i, (name, listed)) in lineup_names(data, &title).iter().enumerate() {
+            let (confidence, strategy) = match (listed, i) {
+                (true, _) => (0.95, "sunset_tavern_artist_listed"),
+                (false, 0) => (0.9, "sunset_tavern_artist_headliner"),
+                (false, _) => (0.85, "sunset_tavern_artist_supporting"),
+            };
+            if let Some(id) = self.link_artist(name, confidence, strategy, &provenance, &mut results) {
+                if !event_artist_ids.contains(&id) {
+                    event_artist_ids.push(id);
+                }
+            }
+        }
+
+        let event = Event {
+            id: None,
+            title,
+            event_day,
+            start_time,
+            event_url,
+            description,
+            event_image_url,
+            venue_id,
+            artist_ids: event_artist_ids,
+            show_event: true,
+            finalized: false,
+            created_at: Utc::now(),
+            attributions: Vec::new(),
+            tags: Vec::new(),
+            price: extract_price(data),
+            doors_time: doors_time(data),
+            series_id: None,
+            age_restriction: extract_age_restriction(data),
+            accessibility_notes: extract_accessibility_notes(data),
+            moderation: None,
+            external_ids: Vec::new(),
+        };
+
+        results.push(NormalizerUtils::create_event_record(
+            event,
+            provenance,
+            0.95,  // Dice listings are structured and reliable
+            "sunset_tavern_event".to_string()
+        ));
+
+        Ok(results)
+    }
+
+    fn source_id(&self) -> &str {
+        "sunset_tavern"
+    }
+
+    fn name(&self) -> &str {
+        "Sunset Tavern Normalizer"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pipeline::processing::normalize::NormalizedEntity;
+    use serde_json::json;
+
+    fn parsed(record: serde_json::Value) -> ParsedRecord {
+        ParsedRecord {
+            source_id: "sunset_tavern".to_string(),
+            envelope_id: "env-1".to_string(),
+            payload_ref: "cas:sha256:abcd".to_string(),
+            record_path: "$.data[0]".to_string(),
+            record,
+            attribution: None,
+            change: None,
+            endpoint_id: None,
+            external_id: None,
+        }
+    }
+
+    fn event_of(records: &[NormalizedRecord]) -> &Event {
+        records
+            .iter()
+            .find_map(|r| match &r.entity {
+                NormalizedEntity::Event(e) => Some(e),
+                _ => None,
+            })
+            .unwrap()
+    }
+
+    #[test]
+    fn normalizes_dice_events_in_venue_local_time() {
+        let normalizer = SunsetTavernNormalizer::new();
+        let records = normalizer
+            .normalize(&parsed(json!({
+                "id": "abc",
+                "attributes": {
+                    "name": "Late Show w/ Opener",
+                    "date": "2025-07-05T04:30:00Z",
+                    "timezone": "America/Los_Angeles",
+                    "url": "https://dice.fm/event/abc",
+                    "images": ["https://img.example/1.jpg"],
+                    "lineup": [
+                        { "details": "Doors open", "time": "8:30 PM" },
+                        { "details": "Headliner, Opener", "time": "9:30 PM" }
+                    ]
+                }
+            })))
+            .unwrap();
+
+        let artists = records.iter().filter(|r| matches!(r.entity, NormalizedEntity::Artist(_))).count();
+        let event = event_of(&records);
+        assert_eq!(artists, 2);
+        // 04:30 UTC on the 5th is 21:30 PDT on the 4th
+        assert_eq!(event.event_day, NaiveDate::from_ymd_opt(2025, 7, 4).unwrap());
+        assert_eq!(event.start_time, NaiveTime::from_hms_opt(21, 30, 0));
+        assert_eq!(event.doors_time, NaiveTime::from_hms_opt(20, 30, 0));
+        assert_eq!(event.event_image_url.as_deref(), Some("https://img.example/1.jpg"));
+        assert_eq!(event.artist_ids.len(), 2);
+
+        // Flattened records without a lineup fall back to splitting the title
+        let records = normalizer
+            .normalize(&parsed(json!({
+                "title": "Band A & Band B",
+                "event_day": "2025-08-01",
+                "start_time": "8pm"
+            })))
+            .unwrap();
+        assert!(!records.iter().any(|r| matches!(r.entity, NormalizedEntity::Venue(_))));
+        let event = event_of(&records);
+        assert_eq!(event.start_time, NaiveTime::from_hms_opt(20, 0, 0));
+        assert_eq!(event.artist_ids.len(), 2);
+    }
+}
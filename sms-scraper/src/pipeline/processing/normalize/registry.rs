@@ -1,14 +1,28 @@
 use std::collections::HashMap;
+use std::sync::Arc;
 use anyhow::Result;
 
-use super::normalizers::{SourceNormalizer, MetricsNormalizer, SeaMonsterNormalizer, DarrellsTavernNormalizer, BlueMoonNormalizer, KexpNormalizer, BarbozaNormalizer, NeumosNormalizer, ConorByrneNormalizer};
+use super::normalizers::{SourceNormalizer, MetricsNormalizer, SeaMonsterNormalizer, DarrellsTavernNormalizer, BlueMoonNormalizer, KexpNormalizer, BarbozaNormalizer, NeumosNormalizer, ConorByrneNormalizer, SunsetTavernNormalizer, GoogleCalendarNormalizer, GOOGLE_CALENDAR_NORMALIZER_ID, NewsletterNormalizer, NEWSLETTER_NORMALIZER_ID};
+use crate::infra::llm_parser::LLM_FALLBACK_FORMAT;
 use crate::observability::metrics;
-use super::NormalizedRecord;
-use crate::pipeline::processing::parser::ParsedRecord;
+use super::{NormalizedEntity, NormalizedRecord};
+use super::shadow::{ShadowLog, ShadowNormalizer};
+use crate::pipeline::processing::sanitize::{sanitize_description, SanitizeConfig};
+use sms_parsers::ParsedRecord;
 
 /// Registry for source-specific normalization strategies
 pub struct NormalizationRegistry {
     normalizers: HashMap<String, Box<dyn SourceNormalizer>>,
+    /// Candidate normalizers compared against the primary for their source
+    shadows: HashMap<String, ShadowNormalizer>,
+    /// Cleanup applied to every event description the normalizers produce
+    descriptions: SanitizeConfig,
+}
+
+impl Default for NormalizationRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl NormalizationRegistry {
@@ -31,10 +45,61 @@ impl NormalizationRegistry {
             Box::new(MetricsNormalizer::new(NeumosNormalizer::new())));
         normalizers.insert("conor_byrne".to_string(),
             Box::new(MetricsNormalizer::new(ConorByrneNormalizer::new())));
+        normalizers.insert("sunset_tavern".to_string(),
+            Box::new(MetricsNormalizer::new(SunsetTavernNormalizer::new())));
+        // Shared by every venue that only publishes a Google Calendar; see `normalize`
+        normalizers.insert(GOOGLE_CALENDAR_NORMALIZER_ID.to_string(),
+            Box::new(MetricsNormalizer::new(GoogleCalendarNormalizer::new())));
+        // Likewise for venues that announce shows by email newsletter
+        normalizers.insert(NEWSLETTER_NORMALIZER_ID.to_string(),
+            Box::new(MetricsNormalizer::new(NewsletterNormalizer::new())));
+
+        // Normalizers registered by extension crates, taking over their source
+        for (source_id, normalizer) in sms_core::pipeline_api::plugins().normalizers() {
+            normalizers.insert(source_id.to_string(), Box::new(MetricsNormalizer::new(normalizer)));
+        }
         
-        Self {
+        let mut registry = Self {
             normalizers,
+            shadows: HashMap::new(),
+            descriptions: SanitizeConfig::from_env(),
+        };
+
+        let candidates = super::normalizers::shadow_candidates();
+        if !candidates.is_empty() {
+            let log = Arc::new(ShadowLog::from_env_or_default());
+            for candidate in candidates {
+                let source_id = candidate.source_id().to_string();
+                registry = registry.with_shadow(&source_id, candidate, log.clone());
+            }
         }
+        registry
+    }
+
+    /// Use `normalizer` as the primary normalizer for `source_id`, replacing any registered one
+    pub fn with_normalizer(mut self, source_id: &str, normalizer: Box<dyn SourceNormalizer>) -> Self {
+        self.normalizers.insert(source_id.to_string(), normalizer);
+        self
+    }
+
+    /// Sanitize event descriptions with `config` instead of the one read from the environment
+    pub fn with_description_sanitizer(mut self, config: SanitizeConfig) -> Self {
+        self.descriptions = config;
+        self
+    }
+
+    /// Run `normalizer` in shadow mode for `source_id`: it sees every record the primary
+    /// does, differences from the primary's output are appended to `log`, and only the
+    /// primary's output is returned
+    pub fn with_shadow(mut self, source_id: &str, normalizer: Box<dyn SourceNormalizer>, log: Arc<ShadowLog>) -> Self {
+        tracing::info!(
+            "Shadowing {} normalizer with {}; differences go to {}",
+            source_id,
+            normalizer.name(),
+            log.path().display()
+        );
+        self.shadows.insert(source_id.to_string(), ShadowNormalizer { normalizer, log });
+        self
     }
 
     /// Test-only: list registered source IDs
@@ -52,17 +117,59 @@ impl NormalizationRegistry {
 
     /// Normalize a record using the appropriate source-specific normalizer
     pub fn normalize(&self, record: &ParsedRecord) -> Result<Vec<NormalizedRecord>> {
+        self.normalize_with(&record.source_id, record)
+    }
+
+    /// Like `normalize`, with the normalizer registered as `normalizer_id`, for a source whose
+    /// pipeline config names another source's normalizer
+    pub fn normalize_with(&self, normalizer_id: &str, record: &ParsedRecord) -> Result<Vec<NormalizedRecord>> {
         // Record batch processing metrics
         metrics::normalize::batch_processed(1);
         
-        if let Some(normalizer) = self.get_normalizer(&record.source_id) {
-            normalizer.normalize(record)
+        // Events the LLM fallback read off a page share one shape whatever their source, the
+        // free-text one newsletters have
+        let llm = record.record.get("format").and_then(|f| f.as_str()) == Some(LLM_FALLBACK_FORMAT);
+        let normalizer = if llm {
+            self.get_normalizer(NEWSLETTER_NORMALIZER_ID)
+        } else {
+            self.get_normalizer(normalizer_id).or_else(|| self.format_normalizer(record))
+        };
+        if let Some(normalizer) = normalizer {
+            let mut result = normalizer.normalize(record);
+            if let Some(shadow) = self.shadows.get(&record.source_id).filter(|_| !llm) {
+                shadow.compare(record, normalizer.name(), &result);
+            }
+            if let Ok(records) = &mut result {
+                records.iter_mut().for_each(|r| self.sanitize_description(r));
+            }
+            result
         } else {
             metrics::normalize::warning_logged(&format!("no_normalizer_for_source_{}", record.source_id));
             Err(anyhow::anyhow!("No normalizer registered for source: {}", record.source_id))
         }
     }
-    
+
+    /// For a source without a normalizer of its own, the one for its records' shared format
+    fn format_normalizer(&self, record: &ParsedRecord) -> Option<&dyn SourceNormalizer> {
+        match record.record.get("format").and_then(|f| f.as_str()) {
+            Some(sms_parsers::envelope::GOOGLE_CALENDAR_FORMAT) => self.get_normalizer(GOOGLE_CALENDAR_NORMALIZER_ID),
+            Some(sms_parsers::envelope::NEWSLETTER_FORMAT) => self.get_normalizer(NEWSLETTER_NORMALIZER_ID),
+            _ => None,
+        }
+    }
+
+    /// Strip markup and tracking junk from an event's description, noting a truncation
+    fn sanitize_description(&self, record: &mut NormalizedRecord) {
+        let NormalizedEntity::Event(event) = &mut record.entity else { return };
+        let Some(raw) = event.description.take() else { return };
+        let sanitized = sanitize_description(&raw, &self.descriptions);
+        if sanitized.as_ref().is_some_and(|s| s.truncated) {
+            metrics::normalize::warning_logged("description_truncated");
+            record.normalization.warnings.push("description_truncated".to_string());
+        }
+        event.description = sanitized.map(|s| s.text);
+    }
+
 }
 
 #[cfg(test)]
@@ -82,6 +189,7 @@ mod tests {
         assert!(sources.contains(&"barboza"));
         assert!(sources.contains(&"neumos"));
         assert!(sources.contains(&"conor_byrne"));
+        assert!(sources.contains(&"sunset_tavern"));
     }
 
     #[test]
@@ -97,10 +205,89 @@ mod tests {
                 "title": "Test Event",
                 "venue": "Test Venue"
             }),
+            attribution: None,
+            change: None,
+            endpoint_id: None,
+            external_id: None,
         };
 
         // Should return an error for unknown sources
         let result = registry.normalize(&record);
         assert!(result.is_err());
+
+        // ...unless their records are in a format with a shared normalizer
+        let calendar = ParsedRecord {
+            record: json!({ "format": "google_calendar", "title": "Test Event", "event_day": "2025-05-01", "location": "Test Venue" }),
+            ..record
+        };
+        assert!(!registry.normalize(&calendar).unwrap().is_empty());
+    }
+
+    struct SkipEverything;
+
+    impl SourceNormalizer for SkipEverything {
+        fn normalize(&self, _record: &ParsedRecord) -> Result<Vec<NormalizedRecord>> {
+            Ok(Vec::new())
+        }
+
+        fn source_id(&self) -> &str {
+            "plugin_venue"
+        }
+
+        fn name(&self) -> &str {
+            "Skip Everything"
+        }
+    }
+
+    #[test]
+    fn test_registry_picks_up_plugin_normalizers() {
+        sms_core::pipeline_api::register(|plugins| plugins.register_normalizer(SkipEverything));
+        let registry = NormalizationRegistry::new();
+
+        assert!(registry.list_sources().contains(&"plugin_venue"));
+        let record = ParsedRecord {
+            source_id: "plugin_venue".to_string(),
+            envelope_id: "test".to_string(),
+            payload_ref: "test".to_string(),
+            record_path: "test".to_string(),
+            record: json!({ "title": "Test Event" }),
+            attribution: None,
+            change: None,
+            endpoint_id: None,
+            external_id: None,
+        };
+        assert!(registry.normalize(&record).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_llm_fallback_events_are_normalized_by_their_shape() {
+        let registry = NormalizationRegistry::new();
+        let record = ParsedRecord {
+            source_id: "neumos".to_string(),
+            envelope_id: "env-1".to_string(),
+            payload_ref: "cas:sha256:abcd".to_string(),
+            record_path: "$.llm_fallback[0]".to_string(),
+            record: json!({
+                "format": LLM_FALLBACK_FORMAT,
+                "title": "The Band",
+                "event_day": "2025-05-02",
+                "start_time": "20:00",
+                "venue_name": "Neumos",
+                "artists": ["The Band"]
+            }),
+            attribution: None,
+            change: None,
+            endpoint_id: None,
+            external_id: None,
+        };
+
+        let records = registry.normalize(&record).unwrap();
+        let event = records
+            .iter()
+            .find_map(|r| match &r.entity { NormalizedEntity::Event(e) => Some(e), _ => None })
+            .unwrap();
+        assert_eq!(event.title, "The Band");
+        assert_eq!(event.event_day, chrono::NaiveDate::from_ymd_opt(2025, 5, 2).unwrap());
+        assert_eq!(event.start_time, chrono::NaiveTime::from_hms_opt(20, 0, 0));
     }
 }
@@ -0,0 +1,299 @@
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use tracing::warn;
+
+use super::normalizers::SourceNormalizer;
+use super::{NormalizedEntity, NormalizedRecord};
+use crate::app::parse_compare_use_case::{diff_fields, FieldDiff};
+use crate::observability::metrics;
+use sms_parsers::ParsedRecord;
+
+/// Env var naming the NDJSON file shadow comparisons are appended to
+pub const SHADOW_LOG_ENV: &str = "SMS_NORMALIZER_SHADOW_LOG";
+
+/// Where comparisons go when `SMS_NORMALIZER_SHADOW_LOG` is unset
+pub const DEFAULT_SHADOW_LOG: &str = "data/shadow/normalize.ndjson";
+
+/// How a shadow normalizer's output for one parsed record differed from the primary's.
+/// In `field_diffs`, `baseline` is the primary's value and `candidate` the shadow's; fields
+/// are keyed by entity kind and position, e.g. `event[0].title`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ShadowComparison {
+    pub source_id: String,
+    pub envelope_id: String,
+    pub record_path: String,
+    pub primary: String,
+    pub shadow: String,
+    pub compared_at: DateTime<Utc>,
+    pub primary_records: usize,
+    pub shadow_records: usize,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub primary_error: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub shadow_error: Option<String>,
+    pub field_diffs: Vec<FieldDiff>,
+}
+
+impl ShadowComparison {
+    pub fn is_identical(&self) -> bool {
+        self.primary_error.is_none()
+            && self.shadow_error.is_none()
+            && self.primary_records == self.shadow_records
+            && self.field_diffs.is_empty()
+    }
+}
+
+/// Compare two normalizers' outputs for `record`. Timestamps taken at normalization time
+/// are left out so only real differences show.
+pub fn compare_outputs(
+    record: &ParsedRecord,
+    primary_name: &str,
+    shadow_name: &str,
+    primary: Result<&[NormalizedRecord], String>,
+    shadow: Result<&[NormalizedRecord], String>,
+) -> ShadowComparison {
+    let (primary_records, primary_error) = split(primary);
+    let (shadow_records, shadow_error) = split(shadow);
+    let field_diffs = diff_fields(&record.record_path, &comparable(primary_records), &comparable(shadow_records));
+    ShadowComparison {
+        source_id: record.source_id.clone(),
+        envelope_id: record.envelope_id.clone(),
+        record_path: record.record_path.clone(),
+        primary: primary_name.to_string(),
+        shadow: shadow_name.to_string(),
+        compared_at: Utc::now(),
+        primary_records: primary_records.len(),
+        shadow_records: shadow_records.len(),
+        primary_error,
+        shadow_error,
+        field_diffs,
+    }
+}
+
+fn split(result: Result<&[NormalizedRecord], String>) -> (&[NormalizedRecord], Option<String>) {
+    match result {
+        Ok(records) => (records, None),
+        Err(e) => (&[], Some(e)),
+    }
+}
+
+/// Entities keyed `event[0]`, `venue[0]`, `artist[1]`... in output order, without `created_at`
+fn comparable(records: &[NormalizedRecord]) -> Value {
+    let mut counts = std::collections::HashMap::new();
+    let mut out = Map::new();
+    for record in records {
+        let (kind, value) = match &record.entity {
+            NormalizedEntity::Event(e) => ("event", serde_json::to_value(e)),
+            NormalizedEntity::Venue(v) => ("venue", serde_json::to_value(v)),
+            NormalizedEntity::Artist(a) => ("artist", serde_json::to_value(a)),
+        };
+        let mut value = value.unwrap_or(Value::Null);
+        if let Some(fields) = value.as_object_mut() {
+            fields.remove("created_at");
+        }
+        let n = counts.entry(kind).or_insert(0usize);
+        out.insert(format!("{}[{}]", kind, n), value);
+        *n += 1;
+    }
+    Value::Object(out)
+}
+
+/// Append-only NDJSON file of shadow comparisons that found differences
+pub struct ShadowLog {
+    path: PathBuf,
+    lock: Mutex<()>,
+}
+
+impl ShadowLog {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into(), lock: Mutex::new(()) }
+    }
+
+    /// The file named by `SMS_NORMALIZER_SHADOW_LOG`, else `data/shadow/normalize.ndjson`
+    pub fn from_env_or_default() -> Self {
+        match std::env::var(SHADOW_LOG_ENV) {
+            Ok(path) if !path.trim().is_empty() => Self::new(path.trim()),
+            _ => Self::new(DEFAULT_SHADOW_LOG),
+        }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn append(&self, comparison: &ShadowComparison) -> Result<()> {
+        let _guard = self.lock.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(dir) = self.path.parent().filter(|d| !d.as_os_str().is_empty()) {
+            std::fs::create_dir_all(dir)?;
+        }
+        let mut file = std::fs::OpenOptions::new().create(true).append(true).open(&self.path)?;
+        writeln!(file, "{}", serde_json::to_string(comparison)?)?;
+        Ok(())
+    }
+}
+
+/// A candidate normalizer run beside a source's primary one. Its output is only compared,
+/// never passed downstream, and it is not wrapped in `MetricsNormalizer` so it doesn't
+/// count towards the normalize metrics.
+pub struct ShadowNormalizer {
+    pub normalizer: Box<dyn SourceNormalizer>,
+    pub log: Arc<ShadowLog>,
+}
+
+impl ShadowNormalizer {
+    /// Normalize `record` with the shadow, log any difference from `primary`'s output and
+    /// count the outcome. Failures here never affect the primary.
+    pub fn compare(&self, record: &ParsedRecord, primary_name: &str, primary: &Result<Vec<NormalizedRecord>>) {
+        let shadow = self.normalizer.normalize(record);
+        let comparison = compare_outputs(
+            record,
+            primary_name,
+            self.normalizer.name(),
+            primary.as_deref().map_err(|e| e.to_string()),
+            shadow.as_deref().map_err(|e| e.to_string()),
+        );
+        let outcome = if comparison.shadow_error.is_some() {
+            "shadow_failed"
+        } else if comparison.is_identical() {
+            "identical"
+        } else {
+            "different"
+        };
+        metrics::normalize::shadow_compared(&record.source_id, outcome);
+        if comparison.is_identical() {
+            return;
+        }
+        if let Err(e) = self.log.append(&comparison) {
+            warn!("Failed to write shadow comparison to {}: {}", self.log.path().display(), e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pipeline::processing::normalize::{NormalizationRegistry, NormalizationMetadata, RecordProvenance};
+    use serde_json::json;
+    use sms_core::domain::Venue;
+
+    /// Normalizes every record to one venue named by the record's `title`, plus `suffix`
+    struct TitleVenue {
+        suffix: &'static str,
+    }
+
+    impl SourceNormalizer for TitleVenue {
+        fn normalize(&self, record: &ParsedRecord) -> Result<Vec<NormalizedRecord>> {
+            let name = format!("{}{}", record.record["title"].as_str().unwrap_or_default(), self.suffix);
+            let venue = Venue {
+                id: None,
+                name: name.clone(),
+                name_lower: name.to_lowercase(),
+                slug: name.to_lowercase(),
+                latitude: 0.0,
+                longitude: 0.0,
+                address: String::new(),
+                postal_code: String::new(),
+                city: "Seattle".to_string(),
+                venue_url: None,
+                venue_image_url: None,
+                description: None,
+                neighborhood: None,
+                show_venue: true,
+                created_at: Utc::now(),
+                attributions: Vec::new(),
+                provisional: false,
+                aliases: Vec::new(),
+                age_restriction: None,
+                accessibility_notes: None,
+                moderation: None,
+            };
+            Ok(vec![NormalizedRecord {
+                entity: NormalizedEntity::Venue(venue),
+                provenance: RecordProvenance {
+                    envelope_id: record.envelope_id.clone(),
+                    source_id: record.source_id.clone(),
+                    payload_ref: record.payload_ref.clone(),
+                    record_path: record.record_path.clone(),
+                    normalized_at: Utc::now(),
+                    attribution: None,
+                    change: None,
+                    record_key: None,
+                    endpoint_id: None,
+                    external_id: None,
+                },
+                normalization: NormalizationMetadata {
+                    confidence: 1.0,
+                    warnings: Vec::new(),
+                    geocoded: false,
+                    strategy: "test".to_string(),
+                },
+            }])
+        }
+
+        fn source_id(&self) -> &str {
+            "test_source"
+        }
+
+        fn name(&self) -> &str {
+            if self.suffix.is_empty() { "title_venue" } else { "title_venue_v2" }
+        }
+    }
+
+    fn parsed(title: &str) -> ParsedRecord {
+        ParsedRecord {
+            source_id: "test_source".to_string(),
+            envelope_id: "env-1".to_string(),
+            payload_ref: "sha256:abc".to_string(),
+            record_path: "$.events[0]".to_string(),
+            record: json!({ "title": title }),
+            attribution: None,
+            change: None,
+            endpoint_id: None,
+            external_id: None,
+        }
+    }
+
+    #[test]
+    fn only_the_primary_output_proceeds_and_differences_are_logged() {
+        let tmp = tempfile::tempdir().unwrap();
+        let log = Arc::new(ShadowLog::new(tmp.path().join("shadow/normalize.ndjson")));
+        let registry = NormalizationRegistry::new()
+            .with_normalizer("test_source", Box::new(TitleVenue { suffix: "" }))
+            .with_shadow("test_source", Box::new(TitleVenue { suffix: " Tavern" }), log.clone());
+
+        let out = registry.normalize(&parsed("Sunset")).unwrap();
+        assert_eq!(out.len(), 1);
+        assert!(matches!(&out[0].entity, NormalizedEntity::Venue(v) if v.name == "Sunset"));
+
+        let logged: Vec<ShadowComparison> = std::fs::read_to_string(log.path())
+            .unwrap()
+            .lines()
+            .map(|l| serde_json::from_str(l).unwrap())
+            .collect();
+        assert_eq!(logged.len(), 1);
+        assert_eq!((logged[0].primary.as_str(), logged[0].shadow.as_str()), ("title_venue", "title_venue_v2"));
+        let fields: Vec<_> = logged[0].field_diffs.iter().map(|d| d.field.as_str()).collect();
+        assert_eq!(fields, ["venue[0].name", "venue[0].name_lower", "venue[0].slug"]);
+        assert_eq!(logged[0].field_diffs[0].baseline, Some(json!("Sunset")));
+        assert_eq!(logged[0].field_diffs[0].candidate, Some(json!("Sunset Tavern")));
+    }
+
+    #[test]
+    fn identical_outputs_are_not_logged() {
+        let same = TitleVenue { suffix: "" };
+        let out = same.normalize(&parsed("Sunset")).unwrap();
+        let again = same.normalize(&parsed("Sunset")).unwrap();
+        let comparison = compare_outputs(&parsed("Sunset"), "a", "b", Ok(&out), Ok(&again));
+        assert!(comparison.is_identical());
+
+        let failed = compare_outputs(&parsed("Sunset"), "a", "b", Ok(&out), Err("boom".to_string()));
+        assert!(!failed.is_identical());
+        assert_eq!(failed.shadow_records, 0);
+        assert!(!failed.field_diffs.is_empty() && failed.field_diffs.iter().all(|d| d.candidate.is_none()));
+    }
+}
@@ -4,10 +4,7 @@ use sms_core::domain::RawData;
 use tracing::{debug, info};
 
 // App modules
-use crate::app::{
-    normalize_use_case::NormalizeUseCase,
-    quality_gate_use_case::QualityGateUseCase,
-};
+use crate::app::normalize_use_case::NormalizeUseCase;
 
 // Infrastructure adapters
 use crate::infra::{
@@ -15,16 +12,12 @@ use crate::infra::{
     quality_gate_output_adapter::{FileQualityGateOutputAdapter, QualityPartition},
 };
 
-// Test-only imports
-#[cfg(test)]
-use crate::app::quality_gate_use_case::QualityGateUseCase;
-
 // Re-export types for convenience
 pub use crate::pipeline::processing::{
     normalize::NormalizedRecord,
-    parser::ParsedRecord,
     quality_gate::{QualityAssessedRecord, QualityDecision},
 };
+pub use sms_parsers::ParsedRecord;
 
 /// Process a raw data item through the normalization and quality gate steps
 pub async fn process_raw_data(
@@ -64,10 +57,20 @@ pub async fn process_raw_data(
     // Always use the actual quality gate implementation
     let quality_gate = {
         use crate::app::quality_gate_use_case::QualityGateUseCase;
-        Some(QualityGateUseCase::with_default_quality_gate(
+        use crate::pipeline::processing::quality_gate::{shadow::ShadowQualityGate, QualityGateConfig};
+        let use_case = QualityGateUseCase::with_default_quality_gate(
             _accepted_output,
             _quarantined_output,
-        ))
+        );
+        // A candidate config under evaluation is scored beside the active gate (metrics only)
+        Some(match QualityGateConfig::shadow_candidate_from_env() {
+            Ok(Some(candidate)) => use_case.with_shadow(std::sync::Arc::new(ShadowQualityGate::new(candidate))),
+            Ok(None) => use_case,
+            Err(e) => {
+                tracing::warn!("Ignoring shadow quality gate config: {:#}", e);
+                use_case
+            }
+        })
     };
 
     // Step 1: Parse the raw data
@@ -135,6 +138,10 @@ async fn parse_raw_data(raw_data: &RawData) -> Result<ParsedRecord> {
         payload_ref: raw_data.api_name.clone(),
         record_path: format!("/events/{}", raw_data.event_api_id),
         record: raw_data.data.clone(),
+        attribution: None,
+        change: None,
+        endpoint_id: None,
+        external_id: None,
     };
     
     // Log parsing result
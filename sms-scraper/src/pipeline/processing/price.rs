@@ -0,0 +1,164 @@
+use regex::Regex;
+use std::sync::OnceLock;
+
+use sms_core::domain::EventPrice;
+
+/// Record fields sources put a price in, checked before falling back to the description
+const PRICE_FIELDS: &[&str] = &["price", "ticket_price", "ticketPrice", "price_range", "cost", "cover", "admission"];
+
+/// Currency for bare numbers and `$`: every venue we scrape sells in US dollars
+const DEFAULT_CURRENCY: &str = "USD";
+
+/// A symbol- or code-marked amount, optionally followed by the top of a range:
+/// `$15`, `$12.50`, `$10-15`, `€8 to €10`, `20 USD`
+fn amount_pattern() -> &'static Regex {
+    static PATTERN: OnceLock<Regex> = OnceLock::new();
+    PATTERN.get_or_init(|| {
+        Regex::new(
+            r"(?i)(?P<sym>[$€£])\s*(?P<a>\d{1,5}(?:\.\d{1,2})?)(?:\s*(?:-|–|—|to)\s*[$€£]?\s*(?P<a2>\d{1,5}(?:\.\d{1,2})?))?|\b(?P<b>\d{1,5}(?:\.\d{1,2})?)\s*(?P<code>usd|eur|gbp|cad|dollars?)\b",
+        )
+        .unwrap()
+    })
+}
+
+/// Wording that marks a show as free. A lone "free" counts only as a whole price field, since
+/// descriptions say "feel free" and "free parking".
+fn free_pattern() -> &'static Regex {
+    static PATTERN: OnceLock<Regex> = OnceLock::new();
+    PATTERN.get_or_init(|| {
+        Regex::new(r"(?i)\b(?:free\s+(?:show|admission|entry|event|concert|to attend)|free of charge|no cover|admission(?:\s+is)?\s+free)\b")
+            .unwrap()
+    })
+}
+
+/// Price advertised in free text such as "$15 adv / $18 door", "$10-$15", "FREE SHOW" or
+/// "20 USD". Every marked amount counts toward the range; amounts in a currency other than
+/// the first one seen are ignored. `None` when the text names no price.
+pub fn parse_price(text: &str) -> Option<EventPrice> {
+    let mut currency: Option<String> = None;
+    let mut amounts = Vec::new();
+    for caps in amount_pattern().captures_iter(text) {
+        let (code, values) = match (caps.name("sym"), caps.name("code")) {
+            (Some(sym), _) => (symbol_currency(sym.as_str()), [caps.name("a"), caps.name("a2")]),
+            (None, Some(code)) => (code_currency(code.as_str()), [caps.name("b"), None]),
+            (None, None) => continue,
+        };
+        if *currency.get_or_insert_with(|| code.to_string()) != code {
+            continue;
+        }
+        amounts.extend(values.into_iter().flatten().filter_map(|m| to_cents(m.as_str())));
+    }
+
+    let free = free_pattern().is_match(text) || is_just_free(text);
+    if free {
+        amounts.push(0);
+    }
+    price_from(amounts, currency)
+}
+
+/// The price in a parsed record: the first price-like field that holds one (a bare number
+/// there is taken as dollars), otherwise whatever the description advertises
+pub fn extract_price(record: &serde_json::Value) -> Option<EventPrice> {
+    for field in PRICE_FIELDS {
+        let price = match record.get(*field) {
+            Some(serde_json::Value::Number(n)) => n.as_f64().and_then(dollars_to_cents).and_then(|c| price_from(vec![c], None)),
+            Some(serde_json::Value::String(s)) => {
+                parse_price(s).or_else(|| to_cents(s.trim()).and_then(|c| price_from(vec![c], None)))
+            }
+            _ => None,
+        };
+        if price.is_some() {
+            return price;
+        }
+    }
+    record.get("description").and_then(|d| d.as_str()).and_then(parse_price)
+}
+
+fn price_from(amounts: Vec<u32>, currency: Option<String>) -> Option<EventPrice> {
+    let min = *amounts.iter().min()?;
+    let max = *amounts.iter().max()?;
+    Some(EventPrice {
+        min_cents: min,
+        max_cents: (max != min).then_some(max),
+        currency: currency.unwrap_or_else(|| DEFAULT_CURRENCY.to_string()),
+    })
+}
+
+fn is_just_free(text: &str) -> bool {
+    text.trim().trim_matches(|c: char| !c.is_alphanumeric()).eq_ignore_ascii_case("free")
+}
+
+fn symbol_currency(symbol: &str) -> &'static str {
+    match symbol {
+        "€" => "EUR",
+        "£" => "GBP",
+        _ => DEFAULT_CURRENCY,
+    }
+}
+
+fn code_currency(code: &str) -> &'static str {
+    match code.to_ascii_lowercase().as_str() {
+        "eur" => "EUR",
+        "gbp" => "GBP",
+        "cad" => "CAD",
+        _ => "USD",
+    }
+}
+
+/// "15" → 1500, "12.5" → 1250, "12.50" → 1250
+fn to_cents(amount: &str) -> Option<u32> {
+    let (whole, fraction) = amount.split_once('.').unwrap_or((amount, ""));
+    if whole.is_empty() || fraction.len() > 2 || !amount.bytes().all(|b| b.is_ascii_digit() || b == b'.') {
+        return None;
+    }
+    let whole: u32 = whole.parse().ok()?;
+    let fraction: u32 = if fraction.is_empty() { 0 } else { format!("{:0<2}", fraction).parse().ok()? };
+    whole.checked_mul(100)?.checked_add(fraction)
+}
+
+fn dollars_to_cents(amount: f64) -> Option<u32> {
+    (0.0..100_000.0).contains(&amount).then(|| (amount * 100.0).round() as u32)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn range(text: &str) -> Option<(u32, Option<u32>, String)> {
+        parse_price(text).map(|p| (p.min_cents, p.max_cents, p.currency))
+    }
+
+    #[test]
+    fn parses_common_price_formats() {
+        assert_eq!(range("$15 adv / $18 door"), Some((1500, Some(1800), "USD".into())));
+        assert_eq!(range("Tickets $12.50"), Some((1250, None, "USD".into())));
+        assert_eq!(range("$10-15 sliding scale"), Some((1000, Some(1500), "USD".into())));
+        assert_eq!(range("$10 – $15"), Some((1000, Some(1500), "USD".into())));
+        assert_eq!(range("€8 to €10"), Some((800, Some(1000), "EUR".into())));
+        assert_eq!(range("20 USD"), Some((2000, None, "USD".into())));
+        assert_eq!(range("21+ show, doors at 8"), None);
+        assert_eq!(range("Feel free to bring a friend"), None);
+    }
+
+    #[test]
+    fn recognizes_free_shows() {
+        assert!(parse_price("FREE SHOW! All ages").unwrap().is_free());
+        assert!(parse_price("Free").unwrap().is_free());
+        assert!(parse_price("No cover").unwrap().is_free());
+        let early = parse_price("No cover before 9pm, $5 after").unwrap();
+        assert!(!early.is_free());
+        assert_eq!((early.min_cents, early.max_cents), (0, Some(500)));
+    }
+
+    #[test]
+    fn extracts_from_price_fields_before_description() {
+        let record = json!({ "price": "15", "description": "$20 at the door" });
+        assert_eq!(extract_price(&record).unwrap().min_cents, 1500);
+        let record = json!({ "ticket_price": 12.5 });
+        assert_eq!(extract_price(&record).unwrap().min_cents, 1250);
+        let record = json!({ "price": "TBA", "description": "Tickets: $8 adv / $10 dos" });
+        assert_eq!(extract_price(&record).unwrap().max_cents, Some(1000));
+        assert!(extract_price(&json!({ "title": "Band" })).is_none());
+    }
+}
@@ -1,99 +1,16 @@
-use chrono::{DateTime, Utc};
-use serde::{Deserialize, Serialize};
+use chrono::Utc;
+use serde::Deserialize;
 
 use crate::pipeline::processing::normalize::NormalizedRecord;
 use crate::observability::metrics;
 
-/// A quality-assessed record that has passed through the Quality Gate checkpoint
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct QualityAssessedRecord {
-    /// The original normalized record
-    pub normalized_record: NormalizedRecord,
-    /// The quality assessment result
-    pub quality_assessment: QualityAssessment,
-    /// When this quality assessment was performed
-    pub assessed_at: DateTime<Utc>,
-}
-
-/// Quality assessment result from the Quality Gate
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct QualityAssessment {
-    /// The quality gate decision
-    pub decision: QualityDecision,
-    /// Overall quality score (0.0 to 1.0)
-    pub quality_score: f64,
-    /// Specific quality issues found
-    pub issues: Vec<QualityIssue>,
-    /// The quality rule set version used
-    pub rule_version: String,
-}
-
-/// Quality Gate decision for a record
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
-pub enum QualityDecision {
-    /// Record meets quality standards and proceeds to next stage
-    Accept,
-    /// Record has quality concerns but proceeds with warnings
-    AcceptWithWarnings,
-    /// Record fails quality checks and is quarantined for review
-    Quarantine,
-}
-
-/// Individual quality issue found during assessment
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct QualityIssue {
-    /// The type of quality issue
-    pub issue_type: QualityIssueType,
-    /// Severity level of the issue
-    pub severity: QualitySeverity,
-    /// Human-readable description of the issue
-    pub description: String,
-    /// Field or attribute that triggered this issue
-    pub field: Option<String>,
-    /// Expected or suggested value
-    pub suggestion: Option<String>,
-}
-
-/// Types of quality issues that can be detected
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub enum QualityIssueType {
-    /// Missing required data
-    MissingData,
-    /// Invalid format or structure
-    InvalidFormat,
-    /// Data outside expected ranges
-    OutOfRange,
-    /// Suspicious or anomalous values
-    SuspiciousValue,
-    /// Incomplete coordinate information
-    IncompleteGeography,
-    /// Date/time inconsistencies
-    TemporalInconsistency,
-    /// Confidence below threshold
-    LowConfidence,
-    /// Duplicate detection concerns
-    DuplicationConcern,
-}
-
-/// Severity levels for quality issues
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, PartialOrd)]
-pub enum QualitySeverity {
-    /// Minor issue, record can proceed
-    Info,
-    /// Notable issue worth flagging
-    Warning,
-    /// Significant issue requiring attention
-    Error,
-    /// Critical issue requiring quarantine
-    Critical,
-}
-
-/// Trait for implementing Quality Gate assessment logic
-pub trait QualityGate {
-    /// Assess the quality of a normalized record
-    fn assess(&self, record: &NormalizedRecord) -> anyhow::Result<QualityAssessedRecord>;
-}
+pub mod outcomes;
+pub mod shadow;
 
+pub use sms_core::pipeline_api::quality_gate::{
+    QualityAssessedRecord, QualityAssessment, QualityDecision, QualityGate, QualityIssue, QualityIssueType,
+    QualitySeverity,
+};
 /// A wrapper that adds metrics to any quality gate implementation
 pub struct MetricsQualityGate<Q: QualityGate> {
     inner: Q,
@@ -150,7 +67,8 @@ pub struct DefaultQualityGate {
 }
 
 /// Configuration for Quality Gate assessment rules
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
 pub struct QualityGateConfig {
     /// Minimum confidence threshold for acceptance
     pub min_confidence: f64,
@@ -214,8 +132,17 @@ impl DefaultQualityGate {
     fn assess_event_quality(&self, event: &sms_core::domain::Event) -> Vec<QualityIssue> {
         let mut issues = Vec::new();
 
-        // Check if title is meaningful
-        if event.title.trim().is_empty() || event.title.len() < 3 {
+        // Check if title is meaningful. An untitled event can't be listed or matched against
+        // duplicates by title, so it's quarantined outright rather than merely penalized.
+        if event.title.trim().is_empty() {
+            issues.push(QualityIssue {
+                issue_type: QualityIssueType::MissingData,
+                severity: QualitySeverity::Critical,
+                description: "Event title is missing".to_string(),
+                field: Some("title".to_string()),
+                suggestion: Some("Event must have a valid title".to_string()),
+            });
+        } else if event.title.len() < 3 {
             issues.push(QualityIssue {
                 issue_type: QualityIssueType::MissingData,
                 severity: QualitySeverity::Error,
@@ -442,17 +369,17 @@ impl Default for DefaultQualityGate {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use sms_core::domain::{Artist, Event, Venue};
+    use sms_core::domain::Event;
     use crate::pipeline::processing::normalize::{NormalizedEntity, NormalizedRecord, NormalizationMetadata, RecordProvenance};
-    use chrono::{NaiveDate, Utc};
-    use serde_json::json;
+    use chrono::Utc;
+    
     use uuid::Uuid;
 
     fn create_test_event() -> NormalizedRecord {
         let event = Event {
             id: None,
             title: "Test Concert".to_string(),
-            event_day: NaiveDate::from_ymd_opt(2025, 8, 20).unwrap(),
+            event_day: Utc::now().date_naive() + chrono::Duration::days(7),
             start_time: None,
             event_url: None,
             description: None,
@@ -462,6 +389,15 @@ mod tests {
             show_event: true,
             finalized: false,
             created_at: Utc::now(),
+            attributions: Vec::new(),
+            tags: Vec::new(),
+            price: None,
+            doors_time: None,
+            series_id: None,
+            age_restriction: None,
+            accessibility_notes: None,
+            moderation: None,
+            external_ids: Vec::new(),
         };
 
         NormalizedRecord {
@@ -472,6 +408,11 @@ mod tests {
                 payload_ref: "test_payload".to_string(),
                 record_path: "$.events[0]".to_string(),
                 normalized_at: Utc::now(),
+                attribution: None,
+                change: None,
+                record_key: None,
+                endpoint_id: None,
+                external_id: None,
             },
             normalization: NormalizationMetadata {
                 confidence: 0.8,
@@ -496,7 +437,8 @@ mod tests {
     fn test_quality_gate_flags_low_confidence() {
         let gate = DefaultQualityGate::new();
         let mut record = create_test_event();
-        record.normalization.confidence = 0.5; // Below threshold
+        // Below min_confidence, but still above min_quality_score once the warning is deducted
+        record.normalization.confidence = 0.68;
 
         let result = gate.assess(&record).unwrap();
         assert_eq!(result.quality_assessment.decision, QualityDecision::AcceptWithWarnings);
@@ -505,6 +447,12 @@ mod tests {
             .iter()
             .any(|i| matches!(i.issue_type, QualityIssueType::LowConfidence));
         assert!(has_confidence_issue);
+
+        // The score starts from the confidence, so one far below the threshold is quarantined
+        record.normalization.confidence = 0.5;
+        let result = gate.assess(&record).unwrap();
+        assert_eq!(result.quality_assessment.decision, QualityDecision::Quarantine);
+        assert!(result.quality_assessment.issues.iter().any(|i| matches!(i.issue_type, QualityIssueType::LowConfidence)));
     }
 
     #[test]
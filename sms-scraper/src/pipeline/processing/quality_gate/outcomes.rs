@@ -0,0 +1,293 @@
+use chrono::{DateTime, TimeZone, Utc};
+use rusqlite::{params, Connection};
+use serde::{Deserialize, Serialize};
+use sms_core::common::record_key::RecordKey;
+use std::path::Path;
+use std::sync::Mutex;
+
+use super::{QualityAssessedRecord, QualityDecision};
+use crate::pipeline::processing::normalize::NormalizedEntity;
+
+/// Aggregated quality-gate outcomes for one source (or all sources) over a window
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct QualityStats {
+    pub total: u64,
+    pub accepted: u64,
+    pub accepted_with_warnings: u64,
+    pub quarantined: u64,
+    pub average_score: Option<f64>,
+    /// Most frequent issue descriptions with their counts, most frequent first
+    pub top_issues: Vec<(String, u64)>,
+}
+
+impl QualityStats {
+    pub fn quarantine_rate(&self) -> f64 {
+        if self.total == 0 {
+            return 0.0;
+        }
+        self.quarantined as f64 / self.total as f64
+    }
+}
+
+/// A record the quality gate quarantined, as kept for review
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QuarantinedRecord {
+    /// Row id; doubles as the pagination cursor
+    pub id: i64,
+    pub source_id: String,
+    pub envelope_id: String,
+    pub record_path: String,
+    /// Identity shared with the parse, normalize and conflation stages
+    pub record_key: RecordKey,
+    pub entity_type: String,
+    pub score: f64,
+    pub rule_version: String,
+    pub issues: Vec<String>,
+    pub assessed_at: DateTime<Utc>,
+    /// The assessed record as JSON, so reviewers can see what was rejected
+    pub record_json: String,
+}
+
+const TOP_ISSUES: usize = 5;
+
+/// Every quality-gate decision, so accept/quarantine rates can be queried without
+/// scraping Prometheus. Lives at `<data_root>/quality/outcomes.db`.
+pub struct QualityOutcomeStore {
+    conn: Mutex<Connection>,
+}
+
+impl QualityOutcomeStore {
+    pub fn open_at_root<P: AsRef<Path>>(data_root: P) -> anyhow::Result<Self> {
+        let db_path = data_root.as_ref().join("quality").join("outcomes.db");
+        if let Some(parent) = db_path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let conn = Connection::open(db_path)?;
+        conn.execute_batch(
+            r#"
+            PRAGMA journal_mode=WAL;
+            CREATE TABLE IF NOT EXISTS quality_outcomes (
+                id            INTEGER PRIMARY KEY AUTOINCREMENT,
+                source_id     TEXT NOT NULL,
+                envelope_id   TEXT NOT NULL,
+                record_path   TEXT NOT NULL,
+                entity_type   TEXT NOT NULL,
+                decision      TEXT NOT NULL,
+                score         REAL NOT NULL,
+                rule_version  TEXT NOT NULL,
+                issues_json   TEXT NOT NULL,
+                assessed_at   INTEGER NOT NULL,
+                record_json   TEXT
+            );
+            CREATE INDEX IF NOT EXISTS idx_quality_outcomes_source_time
+                ON quality_outcomes (source_id, assessed_at);
+            "#,
+        )?;
+        Ok(Self { conn: Mutex::new(conn) })
+    }
+
+    /// Record one decision. The full record is only kept for quarantined ones.
+    pub fn record(&self, assessed: &QualityAssessedRecord) -> anyhow::Result<()> {
+        let provenance = &assessed.normalized_record.provenance;
+        let assessment = &assessed.quality_assessment;
+        let issues: Vec<&str> = assessment.issues.iter().map(|i| i.description.as_str()).collect();
+        let record_json = match assessment.decision {
+            QualityDecision::Quarantine => Some(serde_json::to_string(assessed)?),
+            _ => None,
+        };
+        let conn = self.conn.lock().map_err(|_| anyhow::anyhow!("quality outcome store lock poisoned"))?;
+        conn.execute(
+            "INSERT INTO quality_outcomes (source_id, envelope_id, record_path, entity_type, decision,
+                score, rule_version, issues_json, assessed_at, record_json)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
+            params![
+                provenance.source_id,
+                provenance.envelope_id,
+                provenance.record_path,
+                entity_label(&assessed.normalized_record.entity),
+                decision_label(&assessment.decision),
+                assessment.quality_score,
+                assessment.rule_version,
+                serde_json::to_string(&issues)?,
+                assessed.assessed_at.timestamp_millis(),
+                record_json,
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// Outcomes since `since` (all time when `None`), for one source or all of them
+    pub fn stats(&self, source_id: Option<&str>, since: Option<DateTime<Utc>>) -> anyhow::Result<QualityStats> {
+        let conn = self.conn.lock().map_err(|_| anyhow::anyhow!("quality outcome store lock poisoned"))?;
+        let since_ms = since.map(|t| t.timestamp_millis()).unwrap_or(i64::MIN);
+        let mut stmt = conn.prepare(
+            "SELECT decision, score, issues_json FROM quality_outcomes
+             WHERE (?1 IS NULL OR source_id = ?1) AND assessed_at >= ?2",
+        )?;
+        let rows = stmt.query_map(params![source_id, since_ms], |row| {
+            Ok((row.get::<_, String>(0)?, row.get::<_, f64>(1)?, row.get::<_, String>(2)?))
+        })?;
+
+        let mut stats = QualityStats::default();
+        let mut score_sum = 0.0;
+        let mut issue_counts: std::collections::HashMap<String, u64> = std::collections::HashMap::new();
+        for row in rows {
+            let (decision, score, issues_json) = row?;
+            stats.total += 1;
+            score_sum += score;
+            match decision.as_str() {
+                "accept" => stats.accepted += 1,
+                "accept_with_warnings" => stats.accepted_with_warnings += 1,
+                _ => stats.quarantined += 1,
+            }
+            for issue in serde_json::from_str::<Vec<String>>(&issues_json).unwrap_or_default() {
+                *issue_counts.entry(issue).or_default() += 1;
+            }
+        }
+        if stats.total > 0 {
+            stats.average_score = Some(score_sum / stats.total as f64);
+        }
+        let mut top: Vec<(String, u64)> = issue_counts.into_iter().collect();
+        top.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        top.truncate(TOP_ISSUES);
+        stats.top_issues = top;
+        Ok(stats)
+    }
+
+    /// Quarantined records newest first. `after` is the id of the last record of the previous
+    /// page; returns up to `first` records and whether more follow.
+    pub fn quarantined(&self, first: usize, after: Option<i64>) -> anyhow::Result<(Vec<QuarantinedRecord>, bool)> {
+        let conn = self.conn.lock().map_err(|_| anyhow::anyhow!("quality outcome store lock poisoned"))?;
+        let mut stmt = conn.prepare(
+            "SELECT id, source_id, envelope_id, record_path, entity_type, score, rule_version,
+                    issues_json, assessed_at, record_json
+             FROM quality_outcomes
+             WHERE decision = 'quarantine' AND id < ?1
+             ORDER BY id DESC LIMIT ?2",
+        )?;
+        let rows = stmt.query_map(params![after.unwrap_or(i64::MAX), first as i64 + 1], |row| {
+            let issues_json: String = row.get(7)?;
+            let assessed_at: i64 = row.get(8)?;
+            let (source_id, envelope_id, record_path): (String, String, String) =
+                (row.get(1)?, row.get(2)?, row.get(3)?);
+            Ok(QuarantinedRecord {
+                id: row.get(0)?,
+                record_key: RecordKey::new(&source_id, &envelope_id, &record_path),
+                source_id,
+                envelope_id,
+                record_path,
+                entity_type: row.get(4)?,
+                score: row.get(5)?,
+                rule_version: row.get(6)?,
+                issues: serde_json::from_str(&issues_json).unwrap_or_default(),
+                assessed_at: Utc.timestamp_millis_opt(assessed_at).single().unwrap_or_default(),
+                record_json: row.get::<_, Option<String>>(9)?.unwrap_or_default(),
+            })
+        })?;
+        let mut records = rows.collect::<Result<Vec<_>, _>>()?;
+        let has_next = records.len() > first;
+        records.truncate(first);
+        Ok((records, has_next))
+    }
+}
+
+fn decision_label(decision: &QualityDecision) -> &'static str {
+    match decision {
+        QualityDecision::Accept => "accept",
+        QualityDecision::AcceptWithWarnings => "accept_with_warnings",
+        QualityDecision::Quarantine => "quarantine",
+    }
+}
+
+fn entity_label(entity: &NormalizedEntity) -> &'static str {
+    match entity {
+        NormalizedEntity::Venue(_) => "venue",
+        NormalizedEntity::Event(_) => "event",
+        NormalizedEntity::Artist(_) => "artist",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pipeline::processing::normalize::{NormalizationMetadata, NormalizedRecord, RecordProvenance};
+    use crate::pipeline::processing::quality_gate::{QualityAssessment, QualityIssue, QualityIssueType, QualitySeverity};
+
+    fn assessed(source_id: &str, decision: QualityDecision, issues: &[&str]) -> QualityAssessedRecord {
+        let now = Utc::now();
+        QualityAssessedRecord {
+            normalized_record: NormalizedRecord {
+                entity: NormalizedEntity::Artist(sms_core::domain::Artist {
+                    id: None,
+                    name: "Test Artist".into(),
+                    name_slug: "test-artist".into(),
+                    bio: None,
+                    artist_image_url: None,
+                    created_at: now,
+                    attributions: Vec::new(),
+                    detail_origins: Default::default(),
+                    aliases: Vec::new(),
+                }),
+                provenance: RecordProvenance {
+                    envelope_id: "env-1".into(),
+                    source_id: source_id.into(),
+                    payload_ref: "cas:sha256:abcd".into(),
+                    record_path: "$.events[0]".into(),
+                    normalized_at: now,
+                    attribution: None,
+                    change: None,
+                    record_key: None,
+                    endpoint_id: None,
+                    external_id: None,
+                },
+                normalization: NormalizationMetadata {
+                    confidence: 0.9,
+                    warnings: Vec::new(),
+                    geocoded: false,
+                    strategy: "test".into(),
+                },
+            },
+            quality_assessment: QualityAssessment {
+                decision,
+                quality_score: 0.5,
+                issues: issues
+                    .iter()
+                    .map(|d| QualityIssue {
+                        issue_type: QualityIssueType::MissingData,
+                        severity: QualitySeverity::Critical,
+                        description: d.to_string(),
+                        field: None,
+                        suggestion: None,
+                    })
+                    .collect(),
+                rule_version: "v1".into(),
+            },
+            assessed_at: now,
+        }
+    }
+
+    #[test]
+    fn aggregates_outcomes_and_pages_quarantined_records() {
+        let tmp = tempfile::tempdir().unwrap();
+        let store = QualityOutcomeStore::open_at_root(tmp.path()).unwrap();
+        store.record(&assessed("neumos", QualityDecision::Accept, &[])).unwrap();
+        store.record(&assessed("neumos", QualityDecision::AcceptWithWarnings, &["no image"])).unwrap();
+        for _ in 0..3 {
+            store.record(&assessed("neumos", QualityDecision::Quarantine, &["missing title", "no image"])).unwrap();
+        }
+        store.record(&assessed("kexp", QualityDecision::Quarantine, &["missing title"])).unwrap();
+
+        let stats = store.stats(Some("neumos"), None).unwrap();
+        assert_eq!((stats.total, stats.accepted, stats.accepted_with_warnings, stats.quarantined), (5, 1, 1, 3));
+        assert_eq!(stats.top_issues[0], ("no image".to_string(), 4));
+        assert_eq!(store.stats(None, None).unwrap().quarantined, 4);
+        assert_eq!(store.stats(None, Some(Utc::now() + chrono::Duration::hours(1))).unwrap().total, 0);
+
+        let (page, has_next) = store.quarantined(3, None).unwrap();
+        assert!(has_next);
+        assert_eq!(page[0].source_id, "kexp");
+        assert!(page[0].record_json.contains("Test Artist"));
+        let (rest, has_next) = store.quarantined(3, Some(page[2].id)).unwrap();
+        assert_eq!((rest.len(), has_next), (1, false));
+    }
+}
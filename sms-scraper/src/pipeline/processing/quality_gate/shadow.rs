@@ -0,0 +1,216 @@
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::sync::Mutex;
+use tracing::warn;
+
+use super::{DefaultQualityGate, QualityDecision, QualityGate, QualityGateConfig};
+use crate::observability::metrics;
+use crate::pipeline::processing::normalize::NormalizedRecord;
+
+/// Env var naming a TOML `QualityGateConfig` to evaluate in shadow mode beside the active gate
+pub const SHADOW_CONFIG_ENV: &str = "SMS_QUALITY_GATE_SHADOW";
+
+/// How a candidate config's decisions compared with the active gate's over a run. Records
+/// the candidate would quarantine but the active gate let through are `newly_quarantined`;
+/// the reverse are `newly_accepted`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct ShadowGateReport {
+    pub candidate_rule_version: String,
+    pub records: u64,
+    /// Records both gates decided the same way
+    pub agreed: u64,
+    pub active_quarantined: u64,
+    pub candidate_quarantined: u64,
+    pub newly_quarantined: u64,
+    pub newly_accepted: u64,
+    /// Records the candidate failed to assess
+    pub candidate_failed: u64,
+}
+
+impl ShadowGateReport {
+    pub fn active_quarantine_rate(&self) -> f64 {
+        rate(self.active_quarantined, self.records)
+    }
+
+    pub fn candidate_quarantine_rate(&self) -> f64 {
+        rate(self.candidate_quarantined, self.records)
+    }
+}
+
+fn rate(count: u64, total: u64) -> f64 {
+    if total == 0 {
+        return 0.0;
+    }
+    count as f64 / total as f64
+}
+
+impl QualityGateConfig {
+    pub fn from_path<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let path = path.as_ref();
+        let raw = std::fs::read_to_string(path)
+            .with_context(|| format!("reading quality gate config {}", path.display()))?;
+        Self::from_toml_str(&raw).with_context(|| format!("parsing quality gate config {}", path.display()))
+    }
+
+    /// The shadow candidate named by `SMS_QUALITY_GATE_SHADOW`, if set
+    pub fn shadow_candidate_from_env() -> Result<Option<Self>> {
+        match std::env::var(SHADOW_CONFIG_ENV) {
+            Ok(path) if !path.trim().is_empty() => Self::from_path(path.trim()).map(Some),
+            _ => Ok(None),
+        }
+    }
+
+    /// Parse a config; fields left out keep their defaults
+    pub fn from_toml_str(raw: &str) -> Result<Self> {
+        Ok(toml::from_str(raw)?)
+    }
+}
+
+/// A candidate quality gate config run beside the active gate. Its decisions are only
+/// counted, never used for routing, and it is not wrapped in `MetricsQualityGate` so it
+/// doesn't count towards the quality gate metrics.
+pub struct ShadowQualityGate {
+    candidate: DefaultQualityGate,
+    report: Mutex<ShadowGateReport>,
+}
+
+impl ShadowQualityGate {
+    pub fn new(candidate: QualityGateConfig) -> Self {
+        let report = ShadowGateReport { candidate_rule_version: candidate.rule_version.clone(), ..Default::default() };
+        Self { candidate: DefaultQualityGate { config: candidate }, report: Mutex::new(report) }
+    }
+
+    /// Assess `record` with the candidate and count how its decision compares with the
+    /// active gate's `active` one. Failures here never affect the active decision.
+    pub fn compare(&self, record: &NormalizedRecord, active: &QualityDecision) -> Option<QualityDecision> {
+        let candidate = match self.candidate.assess(record) {
+            Ok(assessed) => Some(assessed.quality_assessment.decision),
+            Err(e) => {
+                warn!("Shadow quality gate failed on {}: {}", record.provenance.record_path, e);
+                None
+            }
+        };
+        metrics::quality_gate::shadow_compared(
+            &record.provenance.source_id,
+            decision_label(active),
+            candidate.as_ref().map(decision_label).unwrap_or("failed"),
+        );
+
+        let mut report = self.report.lock().unwrap_or_else(|e| e.into_inner());
+        report.records += 1;
+        let active_quarantined = *active == QualityDecision::Quarantine;
+        if active_quarantined {
+            report.active_quarantined += 1;
+        }
+        match &candidate {
+            None => report.candidate_failed += 1,
+            Some(decision) => {
+                if decision == active {
+                    report.agreed += 1;
+                }
+                match (active_quarantined, *decision == QualityDecision::Quarantine) {
+                    (false, true) => report.newly_quarantined += 1,
+                    (true, false) => report.newly_accepted += 1,
+                    _ => {}
+                }
+                if *decision == QualityDecision::Quarantine {
+                    report.candidate_quarantined += 1;
+                }
+            }
+        }
+        candidate
+    }
+
+    /// Counts so far
+    pub fn report(&self) -> ShadowGateReport {
+        self.report.lock().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+}
+
+fn decision_label(decision: &QualityDecision) -> &'static str {
+    match decision {
+        QualityDecision::Accept => "accept",
+        QualityDecision::AcceptWithWarnings => "accept_with_warnings",
+        QualityDecision::Quarantine => "quarantine",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pipeline::processing::normalize::{NormalizationMetadata, NormalizedEntity, RecordProvenance};
+    use chrono::Utc;
+    use sms_core::domain::Event;
+    use uuid::Uuid;
+
+    fn event_record(confidence: f64) -> NormalizedRecord {
+        let event = Event {
+            id: None,
+            title: "Test Concert".to_string(),
+            event_day: Utc::now().date_naive() + chrono::Duration::days(7),
+            start_time: None,
+            event_url: None,
+            description: None,
+            event_image_url: None,
+            venue_id: Uuid::nil(),
+            artist_ids: Vec::new(),
+            show_event: true,
+            finalized: false,
+            created_at: Utc::now(),
+            attributions: Vec::new(),
+            tags: Vec::new(),
+            price: None,
+            doors_time: None,
+            series_id: None,
+            age_restriction: None,
+            accessibility_notes: None,
+            moderation: None,
+            external_ids: Vec::new(),
+        };
+        NormalizedRecord {
+            entity: NormalizedEntity::Event(event),
+            provenance: RecordProvenance {
+                envelope_id: "test_envelope".to_string(),
+                source_id: "test_source".to_string(),
+                payload_ref: "test_payload".to_string(),
+                record_path: "$.events[0]".to_string(),
+                normalized_at: Utc::now(),
+                attribution: None,
+                change: None,
+                record_key: None,
+                endpoint_id: None,
+                external_id: None,
+            },
+            normalization: NormalizationMetadata {
+                confidence,
+                warnings: Vec::new(),
+                geocoded: false,
+                strategy: "default".to_string(),
+            },
+        }
+    }
+
+    #[test]
+    fn counts_decisions_a_stricter_candidate_would_change() {
+        let candidate = QualityGateConfig::from_toml_str("min_quality_score = 0.75\nrule_version = \"v2-candidate\"").unwrap();
+        assert_eq!(candidate.min_confidence, QualityGateConfig::default().min_confidence);
+        let shadow = ShadowQualityGate::new(candidate);
+        let active = DefaultQualityGate::new();
+
+        for confidence in [0.9, 0.72, 0.5] {
+            let record = event_record(confidence);
+            let decision = active.assess(&record).unwrap().quality_assessment.decision;
+            shadow.compare(&record, &decision);
+        }
+
+        let report = shadow.report();
+        assert_eq!(report.candidate_rule_version, "v2-candidate");
+        assert_eq!(report.records, 3);
+        assert_eq!(report.active_quarantined, 1);
+        assert_eq!(report.candidate_quarantined, 2);
+        assert_eq!((report.newly_quarantined, report.newly_accepted), (1, 0));
+        assert_eq!(report.agreed, 2);
+        assert!(report.candidate_quarantine_rate() > report.active_quarantine_rate());
+    }
+}
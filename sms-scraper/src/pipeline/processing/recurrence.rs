@@ -0,0 +1,194 @@
+use chrono::{Datelike, NaiveDate, NaiveTime, Utc};
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
+use tracing::info;
+use uuid::Uuid;
+
+use sms_core::common::error::Result;
+use sms_core::common::fuzzy::normalize_for_search;
+use sms_core::domain::{Event, EventSeries};
+use sms_core::storage::Storage;
+
+/// Fewest dated instances that make a series
+pub const MIN_SERIES_INSTANCES: usize = 3;
+
+/// Longest gap between instances, in weeks, still read as a cadence
+pub const MAX_INTERVAL_WEEKS: u32 = 4;
+
+/// Title key instances of a series share: case, punctuation and a trailing date or number
+/// ("Trivia Night 3/4", "Open Mic #12") are ignored
+pub fn series_key(title: &str) -> String {
+    let normalized = normalize_for_search(title);
+    let words: Vec<&str> = normalized.split_whitespace().collect();
+    let keep = words.iter().rposition(|w| !w.chars().all(|c| c.is_ascii_digit())).map_or(0, |i| i + 1);
+    words[..keep].join(" ")
+}
+
+/// The series among one venue's events: events sharing a title key and weekday whose dates
+/// fall on a whole-week cadence. A missed week or two (holidays) doesn't break a series, but
+/// at least half of the cadence's dates between the first and last instance must be listed.
+pub fn detect_series(venue_id: Uuid, events: &[Event]) -> Vec<EventSeries> {
+    let mut groups: BTreeMap<(String, u32), Vec<&Event>> = BTreeMap::new();
+    for event in events.iter().filter(|e| e.id.is_some()) {
+        let key = series_key(&event.title);
+        if key.is_empty() {
+            continue;
+        }
+        groups.entry((key, event.event_day.weekday().num_days_from_monday())).or_default().push(event);
+    }
+
+    let mut detected = Vec::new();
+    for ((key, _), mut instances) in groups {
+        instances.sort_by_key(|e| e.event_day);
+        instances.dedup_by_key(|e| e.event_day);
+        if instances.len() < MIN_SERIES_INSTANCES {
+            continue;
+        }
+        let days: Vec<NaiveDate> = instances.iter().map(|e| e.event_day).collect();
+        let Some(interval_weeks) = cadence_weeks(&days) else { continue };
+
+        let first = instances[0];
+        let last = instances[instances.len() - 1];
+        let weekday = first.event_day.weekday();
+        detected.push(EventSeries {
+            id: Some(EventSeries::derive_id(venue_id, &key, weekday)),
+            venue_id,
+            title: last.title.clone(),
+            weekday,
+            interval_weeks,
+            start_time: usual_start_time(&instances),
+            first_day: first.event_day,
+            last_day: last.event_day,
+            event_ids: instances.iter().filter_map(|e| e.id).collect(),
+            created_at: Utc::now(),
+        });
+    }
+    detected
+}
+
+/// Weeks between instances for sorted, distinct `days`, if they follow a cadence
+fn cadence_weeks(days: &[NaiveDate]) -> Option<u32> {
+    let gaps: Vec<i64> = days.windows(2).map(|w| (w[1] - w[0]).num_days()).collect();
+    if gaps.iter().any(|gap| gap % 7 != 0) {
+        return None;
+    }
+    let interval = gaps.iter().map(|gap| gap / 7).fold(0, gcd);
+    if interval == 0 || interval > MAX_INTERVAL_WEEKS as i64 {
+        return None;
+    }
+    let slots = (days[days.len() - 1] - days[0]).num_days() / 7 / interval + 1;
+    (days.len() as i64 * 2 >= slots).then_some(interval as u32)
+}
+
+fn gcd(a: i64, b: i64) -> i64 {
+    if b == 0 { a } else { gcd(b, a % b) }
+}
+
+/// The start time most instances share, the earliest on a tie
+fn usual_start_time(instances: &[&Event]) -> Option<NaiveTime> {
+    let mut counts: HashMap<NaiveTime, usize> = HashMap::new();
+    for time in instances.iter().filter_map(|e| e.start_time) {
+        *counts.entry(time).or_default() += 1;
+    }
+    counts.into_iter().max_by(|a, b| a.1.cmp(&b.1).then(b.0.cmp(&a.0))).map(|(time, _)| time)
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct RecurrenceReport {
+    /// Series detected across the venues checked
+    pub series: usize,
+    /// Events newly linked to a series
+    pub events_linked: usize,
+}
+
+/// Detect recurring series among each venue's cataloged events, save them and link their
+/// instances. Re-running updates the same series, since their ids derive from venue, title
+/// key and weekday.
+pub async fn link_recurring_series(storage: &dyn Storage, venue_ids: &[Uuid]) -> Result<RecurrenceReport> {
+    let mut report = RecurrenceReport::default();
+    for &venue_id in venue_ids {
+        let events = storage.get_events_by_venue_id(venue_id).await?;
+        for mut series in detect_series(venue_id, &events) {
+            storage.upsert_event_series(&mut series).await?;
+            report.series += 1;
+            for event in events.iter().filter(|e| e.id.is_some_and(|id| series.event_ids.contains(&id))) {
+                if event.series_id == series.id {
+                    continue;
+                }
+                let mut event = event.clone();
+                event.series_id = series.id;
+                storage.update_event(&event).await?;
+                report.events_linked += 1;
+            }
+            info!("🔁 {} at venue {}: {} ({} instances)", series.title, venue_id, series.cadence(), series.event_ids.len());
+        }
+    }
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sms_core::storage::InMemoryStorage;
+
+    fn event(title: &str, day: NaiveDate, venue_id: Uuid) -> Event {
+        Event { id: Some(Uuid::new_v4()), start_time: NaiveTime::from_hms_opt(19, 0, 0), ..Event::fixture(title, day, venue_id) }
+    }
+
+    fn day(d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2025, 3, d).unwrap()
+    }
+
+    #[test]
+    fn detects_weekly_and_biweekly_series() {
+        let venue = Uuid::new_v4();
+        // Tuesdays 4, 11, (18 skipped), 25; Thursdays 6 and 20 are too few
+        let events = vec![
+            event("Open Mic #41", day(4), venue),
+            event("Open Mic #42", day(11), venue),
+            event("OPEN MIC #44", day(25), venue),
+            event("Trivia Night", day(6), venue),
+            event("Trivia Night", day(20), venue),
+            event("Some Band", day(4), venue),
+        ];
+        let series = detect_series(venue, &events);
+        assert_eq!(series.len(), 1);
+        assert_eq!(series[0].cadence(), "every Tuesday");
+        assert_eq!(series[0].event_ids.len(), 3);
+        assert_eq!(series[0].title, "OPEN MIC #44");
+
+        let mut events = events;
+        events.push(event("Trivia Night", NaiveDate::from_ymd_opt(2025, 4, 3).unwrap(), venue));
+        let series = detect_series(venue, &events);
+        let trivia = series.iter().find(|s| s.title == "Trivia Night").unwrap();
+        assert_eq!(trivia.cadence(), "every other Thursday");
+    }
+
+    #[test]
+    fn sparse_or_irregular_dates_are_not_a_series() {
+        let venue = Uuid::new_v4();
+        let spread: Vec<Event> = [1, 2, 8].iter().map(|w| event("Jazz Jam", day(4) + chrono::Duration::weeks(*w), venue)).collect();
+        assert!(detect_series(venue, &spread).is_empty(), "3 of 8 weekly slots is too sparse");
+        assert_eq!(series_key("Trivia Night 3 14"), "trivia night");
+    }
+
+    #[tokio::test]
+    async fn links_instances_and_is_idempotent() {
+        let storage = InMemoryStorage::new();
+        let venue = Uuid::new_v4();
+        for d in [4, 11, 18] {
+            storage.create_event(&mut event("Karaoke", day(d), venue)).await.unwrap();
+        }
+
+        let report = link_recurring_series(&storage, &[venue]).await.unwrap();
+        assert_eq!((report.series, report.events_linked), (1, 3));
+        let series = storage.get_event_series_by_venue_id(venue).await.unwrap();
+        assert_eq!(series.len(), 1);
+        let events = storage.get_events_by_venue_id(venue).await.unwrap();
+        assert!(events.iter().all(|e| e.series_id == series[0].id));
+
+        let again = link_recurring_series(&storage, &[venue]).await.unwrap();
+        assert_eq!(again.events_linked, 0);
+        assert_eq!(storage.get_event_series_by_venue_id(venue).await.unwrap().len(), 1);
+    }
+}
@@ -0,0 +1,188 @@
+use rusqlite::{params, Connection, OptionalExtension};
+use sms_core::common::record_key::SourceKey;
+use std::path::Path;
+use std::sync::Mutex;
+use uuid::Uuid;
+
+use super::conflation::EntityType;
+
+/// Durable mapping from a source's own key for an entity to the canonical id
+/// conflation assigned it, so ids stay stable across pipeline runs.
+pub struct ResolutionIndex {
+    conn: Mutex<Connection>,
+}
+
+impl ResolutionIndex {
+    pub fn open_at_root<P: AsRef<Path>>(data_root: P) -> anyhow::Result<Self> {
+        let db_path = data_root.as_ref().join("conflation").join("resolution.db");
+        if let Some(parent) = db_path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let conn = Connection::open(db_path)?;
+        conn.execute_batch(
+            r#"
+            PRAGMA journal_mode=WAL;
+            CREATE TABLE IF NOT EXISTS entity_resolution (
+                source_id     TEXT NOT NULL,
+                entity_type   TEXT NOT NULL,
+                external_key  TEXT NOT NULL,
+                canonical_id  TEXT NOT NULL,
+                first_seen_at INTEGER NOT NULL,
+                last_seen_at  INTEGER NOT NULL,
+                PRIMARY KEY (source_id, entity_type, external_key)
+            );
+            CREATE INDEX IF NOT EXISTS entity_resolution_canonical ON entity_resolution (canonical_id);
+            CREATE TABLE IF NOT EXISTS merged_entity (
+                entity_type TEXT NOT NULL,
+                removed_id  TEXT NOT NULL,
+                kept_id     TEXT NOT NULL,
+                merged_at   INTEGER NOT NULL,
+                PRIMARY KEY (entity_type, removed_id)
+            );
+            "#,
+        )?;
+        Ok(Self { conn: Mutex::new(conn) })
+    }
+
+    /// Canonical id previously assigned to this source's entity, if any
+    pub fn lookup(&self, key: &SourceKey, entity_type: &EntityType) -> anyhow::Result<Option<Uuid>> {
+        let conn = self.conn.lock().map_err(|_| anyhow::anyhow!("resolution index lock poisoned"))?;
+        let id: Option<String> = conn
+            .query_row(
+                "SELECT canonical_id FROM entity_resolution
+                 WHERE source_id = ?1 AND entity_type = ?2 AND external_key = ?3",
+                params![key.source_id(), entity_type_key(entity_type), key.key()],
+                |row| row.get(0),
+            )
+            .optional()?;
+        Ok(match id {
+            Some(id) => Some(Uuid::parse_str(&id)?),
+            None => None,
+        })
+    }
+
+    /// Remember (or re-point) the canonical id for this source's entity
+    pub fn record(
+        &self,
+        key: &SourceKey,
+        entity_type: &EntityType,
+        canonical_id: Uuid,
+        seen_at: i64,
+    ) -> anyhow::Result<()> {
+        let conn = self.conn.lock().map_err(|_| anyhow::anyhow!("resolution index lock poisoned"))?;
+        conn.execute(
+            "INSERT INTO entity_resolution (source_id, entity_type, external_key, canonical_id, first_seen_at, last_seen_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?5)
+             ON CONFLICT(source_id, entity_type, external_key)
+             DO UPDATE SET canonical_id = excluded.canonical_id, last_seen_at = excluded.last_seen_at",
+            params![key.source_id(), entity_type_key(entity_type), key.key(), canonical_id.to_string(), seen_at],
+        )?;
+        Ok(())
+    }
+
+    /// Tombstone `removed_id` after a curator merged it into `kept_id`: every key that
+    /// resolved to the removed id now resolves to the kept one, and [`Self::merged_into`]
+    /// redirects the removed id itself. Returns how many keys were re-pointed.
+    pub fn record_merge(
+        &self,
+        entity_type: &EntityType,
+        removed_id: Uuid,
+        kept_id: Uuid,
+        merged_at: i64,
+    ) -> anyhow::Result<usize> {
+        let mut conn = self.conn.lock().map_err(|_| anyhow::anyhow!("resolution index lock poisoned"))?;
+        let tx = conn.transaction()?;
+        let (entity_type, removed, kept) = (entity_type_key(entity_type), removed_id.to_string(), kept_id.to_string());
+        // The kept id is live again if it was merged away before; earlier merges into the
+        // removed id follow it to the kept one
+        tx.execute("DELETE FROM merged_entity WHERE entity_type = ?1 AND removed_id = ?2", params![entity_type, kept])?;
+        tx.execute(
+            "UPDATE merged_entity SET kept_id = ?3 WHERE entity_type = ?1 AND kept_id = ?2",
+            params![entity_type, removed, kept],
+        )?;
+        tx.execute(
+            "INSERT INTO merged_entity (entity_type, removed_id, kept_id, merged_at) VALUES (?1, ?2, ?3, ?4)
+             ON CONFLICT(entity_type, removed_id) DO UPDATE SET kept_id = excluded.kept_id, merged_at = excluded.merged_at",
+            params![entity_type, removed, kept, merged_at],
+        )?;
+        let repointed = tx.execute(
+            "UPDATE entity_resolution SET canonical_id = ?3 WHERE entity_type = ?1 AND canonical_id = ?2",
+            params![entity_type, removed, kept],
+        )?;
+        tx.commit()?;
+        Ok(repointed)
+    }
+
+    /// The id a curator merged `id` into, if it was merged away
+    pub fn merged_into(&self, entity_type: &EntityType, id: Uuid) -> anyhow::Result<Option<Uuid>> {
+        let conn = self.conn.lock().map_err(|_| anyhow::anyhow!("resolution index lock poisoned"))?;
+        let kept: Option<String> = conn
+            .query_row(
+                "SELECT kept_id FROM merged_entity WHERE entity_type = ?1 AND removed_id = ?2",
+                params![entity_type_key(entity_type), id.to_string()],
+                |row| row.get(0),
+            )
+            .optional()?;
+        Ok(match kept {
+            Some(id) => Some(Uuid::parse_str(&id)?),
+            None => None,
+        })
+    }
+}
+
+fn entity_type_key(entity_type: &EntityType) -> &'static str {
+    match entity_type {
+        EntityType::Venue => "venue",
+        EntityType::Event => "event",
+        EntityType::Artist => "artist",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mappings_survive_reopen_and_can_be_repointed() {
+        let tmp = tempfile::tempdir().unwrap();
+        let first = Uuid::new_v4();
+        {
+            let index = ResolutionIndex::open_at_root(tmp.path()).unwrap();
+            assert_eq!(index.lookup(&SourceKey::new("neumos", "neumos"), &EntityType::Venue).unwrap(), None);
+            index.record(&SourceKey::new("neumos", "neumos"), &EntityType::Venue, first, 1).unwrap();
+        }
+
+        let index = ResolutionIndex::open_at_root(tmp.path()).unwrap();
+        assert_eq!(index.lookup(&SourceKey::new("neumos", "neumos"), &EntityType::Venue).unwrap(), Some(first));
+        // Keys are scoped by source and entity type
+        assert_eq!(index.lookup(&SourceKey::new("barboza", "neumos"), &EntityType::Venue).unwrap(), None);
+        assert_eq!(index.lookup(&SourceKey::new("neumos", "neumos"), &EntityType::Artist).unwrap(), None);
+
+        let second = Uuid::new_v4();
+        index.record(&SourceKey::new("neumos", "neumos"), &EntityType::Venue, second, 2).unwrap();
+        assert_eq!(index.lookup(&SourceKey::new("neumos", "neumos"), &EntityType::Venue).unwrap(), Some(second));
+    }
+
+    #[test]
+    fn merges_repoint_keys_and_tombstone_the_removed_id() {
+        let tmp = tempfile::tempdir().unwrap();
+        let index = ResolutionIndex::open_at_root(tmp.path()).unwrap();
+        let (kept, removed, older) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+        index.record(&SourceKey::new("kexp", "the-foo"), &EntityType::Artist, removed, 1).unwrap();
+        index.record(&SourceKey::new("neumos", "foo"), &EntityType::Artist, kept, 1).unwrap();
+        index.record_merge(&EntityType::Artist, older, removed, 2).unwrap();
+
+        assert_eq!(index.record_merge(&EntityType::Artist, removed, kept, 3).unwrap(), 1);
+        assert_eq!(index.lookup(&SourceKey::new("kexp", "the-foo"), &EntityType::Artist).unwrap(), Some(kept));
+        assert_eq!(index.merged_into(&EntityType::Artist, removed).unwrap(), Some(kept));
+        // Chains collapse onto the surviving id
+        assert_eq!(index.merged_into(&EntityType::Artist, older).unwrap(), Some(kept));
+        assert_eq!(index.merged_into(&EntityType::Artist, kept).unwrap(), None);
+        assert_eq!(index.merged_into(&EntityType::Venue, removed).unwrap(), None);
+
+        // Merging back the other way revives the kept id instead of looping
+        index.record_merge(&EntityType::Artist, kept, removed, 4).unwrap();
+        assert_eq!(index.merged_into(&EntityType::Artist, removed).unwrap(), None);
+        assert_eq!(index.merged_into(&EntityType::Artist, kept).unwrap(), Some(removed));
+    }
+}
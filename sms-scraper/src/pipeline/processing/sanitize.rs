@@ -0,0 +1,448 @@
+use regex::Regex;
+use std::sync::OnceLock;
+
+/// Comma-separated tags kept in descriptions, e.g. `b,strong,i,em,br,p`
+pub const DESCRIPTION_ALLOWED_TAGS_ENV: &str = "SMS_DESCRIPTION_ALLOWED_TAGS";
+/// Longest description kept, in visible characters
+pub const DESCRIPTION_MAX_CHARS_ENV: &str = "SMS_DESCRIPTION_MAX_CHARS";
+pub const DEFAULT_DESCRIPTION_MAX_CHARS: usize = 2_000;
+
+/// Tags that may be allow-listed: inline emphasis and simple block structure. Anything else
+/// (links, images, spans carrying styles) is never kept.
+const FORMATTING_TAGS: &[&str] = &["b", "strong", "i", "em", "u", "br", "p", "ul", "ol", "li"];
+/// Tags that never close, so they're never left open
+const VOID_TAGS: &[&str] = &["br", "hr", "img", "wbr", "meta", "link", "input", "source"];
+/// Tags whose contents are never text
+const HIDDEN_TAGS: &[&str] = &["script", "style", "noscript", "template", "iframe", "svg", "object", "head", "title"];
+/// Tags that start a new paragraph or line when they aren't kept
+const PARAGRAPH_TAGS: &[&str] = &["p", "h1", "h2", "h3", "h4", "h5", "h6", "blockquote", "section", "article", "table"];
+const LINE_TAGS: &[&str] = &["div", "tr", "ul", "ol", "li", "hr", "header", "footer", "figure"];
+
+/// Query parameters link trackers add to URLs
+const TRACKING_PARAMS: &[&str] = &["fbclid", "gclid", "dclid", "msclkid", "mc_cid", "mc_eid", "_hsenc", "_hsmi", "igshid"];
+
+const ELLIPSIS: char = '…';
+
+/// How descriptions are cleaned up during normalization
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SanitizeConfig {
+    /// Formatting tags kept (without attributes) in the output. Empty, the default, turns
+    /// descriptions into plain text; otherwise the output is HTML with text escaped.
+    pub allowed_tags: Vec<String>,
+    pub max_chars: usize,
+}
+
+impl Default for SanitizeConfig {
+    fn default() -> Self {
+        Self { allowed_tags: Vec::new(), max_chars: DEFAULT_DESCRIPTION_MAX_CHARS }
+    }
+}
+
+impl SanitizeConfig {
+    /// Defaults overridden by `SMS_DESCRIPTION_ALLOWED_TAGS` and `SMS_DESCRIPTION_MAX_CHARS`;
+    /// allow-listed tags that aren't formatting tags are ignored with a warning
+    pub fn from_env() -> Self {
+        let mut config = Self::default();
+        if let Ok(tags) = std::env::var(DESCRIPTION_ALLOWED_TAGS_ENV) {
+            config = config.with_allowed_tags(tags.split(','));
+        }
+        if let Ok(max) = std::env::var(DESCRIPTION_MAX_CHARS_ENV) {
+            match max.trim().parse() {
+                Ok(max_chars) => config.max_chars = max_chars,
+                Err(_) => tracing::warn!("Ignoring {}={}: not a number", DESCRIPTION_MAX_CHARS_ENV, max),
+            }
+        }
+        config
+    }
+
+    pub fn with_allowed_tags<'a>(mut self, tags: impl IntoIterator<Item = &'a str>) -> Self {
+        self.allowed_tags = tags
+            .into_iter()
+            .map(|tag| tag.trim().to_ascii_lowercase())
+            .filter(|tag| !tag.is_empty())
+            .filter(|tag| {
+                let allowed = FORMATTING_TAGS.contains(&tag.as_str());
+                if !allowed {
+                    tracing::warn!("Ignoring {} in {}: only {:?} can be kept", tag, DESCRIPTION_ALLOWED_TAGS_ENV, FORMATTING_TAGS);
+                }
+                allowed
+            })
+            .collect();
+        self
+    }
+
+    fn keeps(&self, tag: &str) -> bool {
+        self.allowed_tags.iter().any(|t| t == tag)
+    }
+}
+
+/// A cleaned-up description and whether it had to be shortened
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Sanitized {
+    pub text: String,
+    pub truncated: bool,
+}
+
+/// Clean up a scraped description: drop scripts, styles and comments, turn markup into
+/// line breaks (keeping allow-listed formatting tags), decode entities, strip invisible
+/// characters and link tracking parameters, collapse whitespace and cut the result to
+/// `max_chars` visible characters at a word boundary. `None` when nothing readable is left.
+pub fn sanitize_description(raw: &str, config: &SanitizeConfig) -> Option<Sanitized> {
+    let html_mode = !config.allowed_tags.is_empty();
+    // Plain text with line breaks (API descriptions) keeps them; in markup they're spacing
+    let has_markup = tag_pattern().is_match(raw);
+
+    let mut out = Output::new(html_mode, config.max_chars);
+    let mut hidden: Option<String> = None;
+    let mut rest = raw;
+    while !rest.is_empty() && !out.full {
+        let Some(start) = rest.find('<') else {
+            if hidden.is_none() {
+                out.text(rest, has_markup);
+            }
+            break;
+        };
+        if hidden.is_none() {
+            out.text(&rest[..start], has_markup);
+        }
+        let from_tag = &rest[start..];
+        if let Some(comment) = from_tag.strip_prefix("<!--") {
+            rest = comment.find("-->").map_or("", |end| &comment[end + 3..]);
+            continue;
+        }
+        let Some(m) = tag_pattern().find(from_tag).filter(|m| m.start() == 0) else {
+            // A `<` that doesn't open a tag is text
+            if hidden.is_none() {
+                out.text("<", has_markup);
+            }
+            rest = &from_tag[1..];
+            continue;
+        };
+        rest = &from_tag[m.end()..];
+
+        let tag = m.as_str();
+        let closing = tag.starts_with("</");
+        let name = tag_name(tag);
+        if let Some(hidden_name) = &hidden {
+            if closing && name == *hidden_name {
+                hidden = None;
+            }
+            continue;
+        }
+        if HIDDEN_TAGS.contains(&name.as_str()) {
+            if !closing && !tag.ends_with("/>") {
+                hidden = Some(name);
+            }
+            continue;
+        }
+        if config.keeps(&name) {
+            out.tag(&name, closing);
+        } else if name == "li" && !closing {
+            out.line_break();
+            out.text("- ", false);
+        } else if name == "br" {
+            out.hard_break();
+        } else if PARAGRAPH_TAGS.contains(&name.as_str()) {
+            out.paragraph_break();
+        } else if LINE_TAGS.contains(&name.as_str()) {
+            out.line_break();
+        }
+    }
+
+    let truncated = out.full;
+    let text = out.finish();
+    (!text.is_empty()).then_some(Sanitized { text, truncated })
+}
+
+/// An opening, closing or self-closing tag, quoted attribute values allowed
+fn tag_pattern() -> &'static Regex {
+    static PATTERN: OnceLock<Regex> = OnceLock::new();
+    PATTERN.get_or_init(|| Regex::new(r#"</?[A-Za-z][A-Za-z0-9]*(?:[^>"']|"[^"]*"|'[^']*')*>"#).unwrap())
+}
+
+fn tag_name(tag: &str) -> String {
+    tag.trim_start_matches('<')
+        .trim_start_matches('/')
+        .chars()
+        .take_while(|c| c.is_ascii_alphanumeric())
+        .collect::<String>()
+        .to_ascii_lowercase()
+}
+
+/// Description being built, counting visible characters against the limit
+struct Output {
+    html_mode: bool,
+    max_chars: usize,
+    buf: String,
+    visible: usize,
+    open: Vec<String>,
+    full: bool,
+}
+
+impl Output {
+    fn new(html_mode: bool, max_chars: usize) -> Self {
+        Self { html_mode, max_chars, buf: String::new(), visible: 0, open: Vec::new(), full: false }
+    }
+
+    fn text(&mut self, raw: &str, markup_spacing: bool) {
+        let mut text = strip_tracking(&decode_entities(raw));
+        text.retain(|c| !is_invisible(c));
+        if markup_spacing {
+            text = text.replace(['\n', '\r', '\t'], " ");
+            if self.at_line_start() {
+                text = text.trim_start().to_string();
+            }
+        }
+        let count = text.chars().filter(|c| !c.is_whitespace()).count();
+        if self.visible + count > self.max_chars {
+            text = cut_at_word(&text, self.max_chars - self.visible);
+            text.push(ELLIPSIS);
+            self.full = true;
+        }
+        self.visible += text.chars().filter(|c| !c.is_whitespace()).count();
+        if self.html_mode {
+            text = escape(&text);
+        }
+        self.buf.push_str(&text);
+    }
+
+    fn tag(&mut self, name: &str, closing: bool) {
+        if VOID_TAGS.contains(&name) {
+            if !closing {
+                self.buf.push_str(&format!("<{}>", name));
+            }
+        } else if !closing {
+            self.buf.push_str(&format!("<{}>", name));
+            self.open.push(name.to_string());
+        } else if let Some(at) = self.open.iter().rposition(|open| open == name) {
+            // Close anything left open inside it, so the output stays well nested
+            for open in self.open.drain(at..).rev() {
+                self.buf.push_str(&format!("</{}>", open));
+            }
+        }
+    }
+
+    fn at_line_start(&self) -> bool {
+        self.buf.trim_end_matches(' ').is_empty() || self.buf.trim_end_matches(' ').ends_with('\n')
+    }
+
+    /// End the current line; markup breaking an already empty line adds nothing
+    fn line_break(&mut self) {
+        if !self.at_line_start() {
+            self.buf.truncate(self.buf.trim_end_matches(' ').len());
+            self.buf.push('\n');
+        }
+    }
+
+    /// `<br>` always breaks, so `<br><br>` still separates paragraphs
+    fn hard_break(&mut self) {
+        self.buf.truncate(self.buf.trim_end_matches(' ').len());
+        self.buf.push('\n');
+    }
+
+    fn paragraph_break(&mut self) {
+        self.line_break();
+        self.hard_break();
+    }
+
+    fn finish(mut self) -> String {
+        for open in std::mem::take(&mut self.open).into_iter().rev() {
+            self.buf.push_str(&format!("</{}>", open));
+        }
+        tidy(&self.buf)
+    }
+}
+
+/// Collapse runs of spaces, trim lines and keep at most one blank line between paragraphs
+fn tidy(text: &str) -> String {
+    let mut lines: Vec<String> = Vec::new();
+    for line in text.lines() {
+        let line = line.split_whitespace().collect::<Vec<_>>().join(" ");
+        if line.is_empty() && lines.last().is_none_or(|last| last.is_empty()) {
+            continue;
+        }
+        lines.push(line);
+    }
+    while lines.last().is_some_and(|last| last.is_empty()) {
+        lines.pop();
+    }
+    lines.join("\n")
+}
+
+/// At most `budget` non-space characters of `text`, ending at a word boundary when one falls
+/// in the second half of the budget
+fn cut_at_word(text: &str, budget: usize) -> String {
+    let mut seen = 0;
+    let mut end = text.len();
+    for (i, c) in text.char_indices() {
+        if !c.is_whitespace() {
+            if seen == budget {
+                end = i;
+                break;
+            }
+            seen += 1;
+        }
+    }
+    let head = &text[..end];
+    let head = match head.rfind(char::is_whitespace) {
+        Some(space) if head[..space].chars().filter(|c| !c.is_whitespace()).count() * 2 >= budget => &head[..space],
+        _ => head,
+    };
+    head.trim_end().to_string()
+}
+
+fn is_invisible(c: char) -> bool {
+    matches!(c, '\u{200B}'..='\u{200D}' | '\u{2060}' | '\u{FEFF}' | '\u{00AD}')
+}
+
+/// Drop `utm_*` and click-id parameters from URLs in `text`
+fn strip_tracking(text: &str) -> String {
+    static URL: OnceLock<Regex> = OnceLock::new();
+    let url = URL.get_or_init(|| Regex::new(r"https?://[^\s<>]+\?[^\s<>]+").unwrap());
+    url.replace_all(text, |caps: &regex::Captures| {
+        let found = &caps[0];
+        let (base, query) = found.split_once('?').unwrap_or((found, ""));
+        let (query, fragment) = query.split_once('#').map_or((query, None), |(q, f)| (q, Some(f)));
+        let kept: Vec<&str> = query
+            .split('&')
+            .filter(|param| {
+                let key = param.split('=').next().unwrap_or_default().to_ascii_lowercase();
+                !key.is_empty() && !key.starts_with("utm_") && !TRACKING_PARAMS.contains(&key.as_str())
+            })
+            .collect();
+        let mut cleaned = base.to_string();
+        if !kept.is_empty() {
+            cleaned.push('?');
+            cleaned.push_str(&kept.join("&"));
+        }
+        if let Some(fragment) = fragment {
+            cleaned.push('#');
+            cleaned.push_str(fragment);
+        }
+        cleaned
+    })
+    .into_owned()
+}
+
+/// Decode numeric entities and the named ones venue sites use; unknown entities are kept as written
+pub fn decode_entities(text: &str) -> String {
+    if !text.contains('&') {
+        return text.to_string();
+    }
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(amp) = rest.find('&') {
+        out.push_str(&rest[..amp]);
+        let after = &rest[amp + 1..];
+        let decoded = after
+            .find(';')
+            .filter(|&end| end > 0 && end <= 10)
+            .and_then(|end| decode_entity(&after[..end]).map(|c| (c, end)));
+        match decoded {
+            Some((c, end)) => {
+                out.push(c);
+                rest = &after[end + 1..];
+            }
+            None => {
+                out.push('&');
+                rest = after;
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+fn decode_entity(entity: &str) -> Option<char> {
+    if let Some(number) = entity.strip_prefix('#') {
+        let code = match number.strip_prefix(['x', 'X']) {
+            Some(hex) => u32::from_str_radix(hex, 16).ok()?,
+            None => number.parse().ok()?,
+        };
+        return char::from_u32(code).filter(|c| *c != '\0');
+    }
+    Some(match entity {
+        "amp" => '&',
+        "lt" => '<',
+        "gt" => '>',
+        "quot" => '"',
+        "apos" => '\'',
+        "nbsp" => ' ',
+        "ndash" => '–',
+        "mdash" => '—',
+        "hellip" => '…',
+        "lsquo" => '‘',
+        "rsquo" => '’',
+        "ldquo" => '“',
+        "rdquo" => '”',
+        "middot" => '·',
+        "bull" => '•',
+        "copy" => '©',
+        "reg" => '®',
+        "trade" => '™',
+        "eacute" => 'é',
+        "deg" => '°',
+        _ => return None,
+    })
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn plain(raw: &str) -> String {
+        sanitize_description(raw, &SanitizeConfig::default()).unwrap().text
+    }
+
+    #[test]
+    fn turns_venue_markup_into_plain_text() {
+        // Wix rich text from a Blue Moon listing
+        let wix = "<p class=\"font_8\"><span style=\"font-weight:bold;\">Doors 8pm&nbsp;/ Show 9pm</span></p>\
+            <p class=\"font_8\">&#8203;</p><p class=\"font_8\">$12 at the door &amp; 21+</p>\
+            <p class=\"font_8\"><a href=\"https://www.eventbrite.com/e/123?aff=ebdssbdestsearch&amp;utm_source=ig&amp;utm_medium=social\">Tickets</a> \
+            https://www.eventbrite.com/e/123?aff=ebdssbdestsearch&amp;utm_source=ig&amp;fbclid=IwAR0x</p>";
+        assert_eq!(
+            plain(wix),
+            "Doors 8pm / Show 9pm\n\n$12 at the door & 21+\n\nTickets https://www.eventbrite.com/e/123?aff=ebdssbdestsearch"
+        );
+
+        // VenuePilot description with a lineup list, a tracking pixel and an embedded script
+        let venuepilot = "<div>Presented by KEXP<br/>With special guests:</div>\n<ul>\n  <li>The Moss</li>\n  <li>Dreamdecay</li>\n</ul>\
+            <img src=\"https://px.example.com/t.gif?id=9\" width=\"1\" height=\"1\">\
+            <script type=\"text/javascript\">window.dataLayer.push({event: 'view'});</script><!-- tracking end -->";
+        assert_eq!(plain(venuepilot), "Presented by KEXP\nWith special guests:\n- The Moss\n- Dreamdecay");
+
+        // Plain-text API descriptions keep their line breaks and stray angle brackets
+        assert_eq!(plain("Sign-up at 7pm\n\n\n  Comedy   <3 all night  "), "Sign-up at 7pm\n\nComedy <3 all night");
+        assert_eq!(plain("Tickets &lsquo;on sale&rsquo; now &mdash; don&#39;t wait &unknown;"), "Tickets ‘on sale’ now — don't wait &unknown;");
+        assert_eq!(sanitize_description("<p> &nbsp; </p><style>p{}</style>", &SanitizeConfig::default()), None);
+    }
+
+    #[test]
+    fn keeps_allow_listed_formatting_escaped_and_well_nested() {
+        let config = SanitizeConfig::default().with_allowed_tags(["b", "p", "a", " EM "]);
+        assert_eq!(config.allowed_tags, vec!["b".to_string(), "p".to_string(), "em".to_string()]);
+
+        let squarespace = "<p style=\"white-space:pre-wrap;\"><b onclick=\"x()\">Sold out</b> &lt;script&gt; <em>waitlist <a href=\"/w\">here</a></p><p>Last one";
+        let sanitized = sanitize_description(squarespace, &config).unwrap();
+        assert_eq!(sanitized.text, "<p><b>Sold out</b> &lt;script&gt; <em>waitlist here</em></p><p>Last one</p>");
+        assert!(!sanitized.truncated);
+    }
+
+    #[test]
+    fn cuts_long_descriptions_at_a_word_boundary() {
+        let config = SanitizeConfig { max_chars: 22, ..Default::default() };
+        let long = sanitize_description("<p>An evening of indie folk</p><p>in Ballard with friends</p>", &config).unwrap();
+        assert!(long.truncated);
+        assert_eq!(long.text, "An evening of indie folk\n\nin…");
+
+        let html = SanitizeConfig { max_chars: 8, ..SanitizeConfig::default().with_allowed_tags(["b"]) };
+        let cut = sanitize_description("<b>Doors early tonight</b> more", &html).unwrap();
+        assert_eq!(cut.text, "<b>Doors…</b>");
+        assert!(cut.truncated);
+    }
+}
@@ -0,0 +1,213 @@
+use serde::Serialize;
+use tokio::sync::Mutex;
+use tracing::{info, warn};
+
+use sms_core::common::error::Result;
+use sms_core::domain::Venue;
+use sms_core::storage::Storage;
+
+use crate::pipeline::processing::venue_resolver::{venue_key, VenueResolver};
+
+/// The cataloged (non-provisional) venue a placeholder stands in for, if it exists yet: same
+/// name key, or both known to the venue resolver as the same venue under different names
+pub fn matching_venue<'a>(placeholder: &Venue, venues: &'a [Venue]) -> Option<&'a Venue> {
+    let key = venue_key(&placeholder.name);
+    if key.is_empty() || key == "tba" {
+        return None;
+    }
+    let resolver = VenueResolver::default();
+    venues
+        .iter()
+        .filter(|v| !v.provisional)
+        .find(|v| venue_key(&v.name) == key || resolver.same_venue(placeholder, v))
+}
+
+/// The cataloged (non-provisional) venues placeholders are matched against during a run, listed
+/// from storage the first time they're needed and kept current as the run catalogs more
+#[derive(Default)]
+pub struct CatalogedVenues {
+    venues: Mutex<Option<Vec<Venue>>>,
+}
+
+impl CatalogedVenues {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Start from venues the run has already listed
+    pub fn with_venues(venues: impl IntoIterator<Item = Venue>) -> Self {
+        Self { venues: Mutex::new(Some(venues.into_iter().filter(|v| !v.provisional).collect())) }
+    }
+
+    /// The cataloged venue `placeholder` stands in for, if it exists yet
+    pub async fn matching(&self, storage: &dyn Storage, placeholder: &Venue) -> Result<Option<Venue>> {
+        let mut venues = self.venues.lock().await;
+        if venues.is_none() {
+            let listed = storage.get_all_venues(None, None).await?;
+            *venues = Some(listed.into_iter().filter(|v| !v.provisional).collect());
+        }
+        Ok(matching_venue(placeholder, venues.as_deref().unwrap_or_default()).cloned())
+    }
+
+    /// `venue` itself, or the real venue once a provisional one has been cataloged for real
+    pub async fn resolve(&self, storage: &dyn Storage, venue: Venue) -> Result<Venue> {
+        if !venue.provisional {
+            return Ok(venue);
+        }
+        Ok(self.matching(storage, &venue).await?.unwrap_or(venue))
+    }
+
+    /// Note a venue the run has cataloged; placeholders are ignored
+    pub async fn add(&self, venue: &Venue) {
+        if venue.provisional {
+            return;
+        }
+        if let Some(venues) = self.venues.lock().await.as_mut() {
+            venues.retain(|v| v.id.is_none() || v.id != venue.id);
+            venues.push(venue.clone());
+        }
+    }
+}
+
+/// One placeholder whose events were moved onto a real venue
+#[derive(Debug, Clone, Serialize)]
+pub struct BoundPlaceholder {
+    pub placeholder: String,
+    pub venue: String,
+    pub events_moved: usize,
+    pub duplicates_removed: usize,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct VenueResolutionReport {
+    /// Placeholders still waiting for their venue to be cataloged
+    pub unresolved: usize,
+    pub bound: Vec<BoundPlaceholder>,
+}
+
+/// Move events parked on provisional venues to the matching real venue. An event the real
+/// venue already lists (same day and title) is dropped instead of being moved.
+pub async fn bind_provisional_venues(storage: &dyn Storage) -> Result<VenueResolutionReport> {
+    let venues = storage.get_all_venues(None, None).await?;
+    let mut report = VenueResolutionReport::default();
+
+    for placeholder in venues.iter().filter(|v| v.provisional) {
+        let (Some(placeholder_id), Some(venue)) = (placeholder.id, matching_venue(placeholder, &venues)) else {
+            report.unresolved += 1;
+            continue;
+        };
+        let Some(venue_id) = venue.id else { continue };
+
+        let mut bound = BoundPlaceholder {
+            placeholder: placeholder.name.clone(),
+            venue: venue.name.clone(),
+            events_moved: 0,
+            duplicates_removed: 0,
+        };
+        for mut event in storage.get_events_by_venue_id(placeholder_id).await? {
+            let Some(event_id) = event.id else { continue };
+            let existing = storage.get_event_by_venue_date_title(venue_id, event.event_day, &event.title).await?;
+            if existing.is_some_and(|e| e.id != Some(event_id)) {
+                storage.delete_event(event_id).await?;
+                bound.duplicates_removed += 1;
+                continue;
+            }
+            event.venue_id = venue_id;
+            storage.update_event(&event).await?;
+            bound.events_moved += 1;
+        }
+
+        if bound.events_moved + bound.duplicates_removed > 0 {
+            info!(
+                "Bound provisional venue '{}' to '{}': {} events moved, {} duplicates removed",
+                bound.placeholder, bound.venue, bound.events_moved, bound.duplicates_removed
+            );
+            report.bound.push(bound);
+        }
+    }
+
+    if report.unresolved > 0 {
+        warn!("{} provisional venues are still waiting to be cataloged", report.unresolved);
+    }
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{NaiveDate, Utc};
+    use sms_core::domain::Event;
+    use sms_core::storage::InMemoryStorage;
+    use uuid::Uuid;
+
+    fn event_at(venue_id: Uuid, title: &str) -> Event {
+        Event {
+            id: None,
+            title: title.to_string(),
+            event_day: NaiveDate::from_ymd_opt(2025, 6, 1).unwrap(),
+            start_time: None,
+            event_url: None,
+            description: None,
+            event_image_url: None,
+            venue_id,
+            artist_ids: Vec::new(),
+            show_event: true,
+            finalized: false,
+            created_at: Utc::now(),
+            attributions: Vec::new(),
+            tags: Vec::new(),
+            price: None,
+            doors_time: None,
+            series_id: None,
+            age_restriction: None,
+            accessibility_notes: None,
+            moderation: None,
+            external_ids: Vec::new(),
+        }
+    }
+
+    #[tokio::test]
+    async fn binds_placeholder_events_once_the_real_venue_is_cataloged() {
+        let storage = InMemoryStorage::new();
+        let mut placeholder = Venue::placeholder("Crocodile");
+        storage.create_venue(&mut placeholder).await.unwrap();
+        let placeholder_id = placeholder.id.unwrap();
+        for title in ["Early Show", "Late Show"] {
+            storage.create_event(&mut event_at(placeholder_id, title)).await.unwrap();
+        }
+
+        // Nothing to bind to yet
+        let report = bind_provisional_venues(&storage).await.unwrap();
+        assert_eq!((report.unresolved, report.bound.len()), (1, 0));
+
+        let mut real = Venue { provisional: false, show_venue: true, id: None, ..Venue::placeholder("The Crocodile") };
+        storage.create_venue(&mut real).await.unwrap();
+        let real_id = real.id.unwrap();
+        storage.create_event(&mut event_at(real_id, "Late Show")).await.unwrap();
+        assert_eq!(CatalogedVenues::new().resolve(&storage, placeholder.clone()).await.unwrap().id, Some(real_id));
+
+        let report = bind_provisional_venues(&storage).await.unwrap();
+        assert_eq!(report.unresolved, 0);
+        assert_eq!((report.bound[0].events_moved, report.bound[0].duplicates_removed), (1, 1));
+        assert!(storage.get_events_by_venue_id(placeholder_id).await.unwrap().is_empty());
+        assert_eq!(storage.get_events_by_venue_id(real_id).await.unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn cataloged_venues_are_listed_once_and_kept_current() {
+        let storage = InMemoryStorage::new();
+        let venues = CatalogedVenues::new();
+        let placeholder = Venue::placeholder("Crocodile");
+        assert!(venues.matching(&storage, &placeholder).await.unwrap().is_none());
+
+        // Written behind the cache's back: not seen until the run notes it
+        let mut real = Venue { provisional: false, show_venue: true, id: None, ..Venue::placeholder("The Crocodile") };
+        storage.create_venue(&mut real).await.unwrap();
+        assert!(venues.matching(&storage, &placeholder).await.unwrap().is_none());
+
+        venues.add(&real).await;
+        venues.add(&Venue::placeholder("Neumos")).await;
+        assert_eq!(venues.resolve(&storage, placeholder).await.unwrap().id, real.id);
+        assert_eq!(venues.venues.lock().await.as_ref().map(Vec::len), Some(1));
+    }
+}
@@ -0,0 +1,349 @@
+// Known venues and the venue matching rules shared by normalization and conflation
+
+use chrono::Utc;
+use uuid::Uuid;
+
+use sms_core::common::fuzzy::fuzzy_score;
+use sms_core::common::geo::haversine_km;
+use sms_core::domain::Venue;
+use sms_core::pipeline_api::conflation::DEFAULT_VENUE_RADIUS_M;
+
+/// Fuzzy name score at which a name matches a known venue outright
+pub const NAME_MATCH_SCORE: f64 = 0.85;
+
+/// Lower fuzzy name score that is still enough when the coordinates agree too
+pub const NEARBY_NAME_MATCH_SCORE: f64 = 0.5;
+
+/// A venue we already know everything about. Single-venue sources attach its id and
+/// metadata at normalization, and any source naming it resolves to the same venue.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct KnownVenue {
+    /// The source that lists this venue's own calendar, if any
+    pub source_id: Option<&'static str>,
+    pub name: &'static str,
+    pub slug: &'static str,
+    /// Other names listings use for the venue
+    pub aliases: &'static [&'static str],
+    pub latitude: f64,
+    pub longitude: f64,
+    pub address: &'static str,
+    pub postal_code: &'static str,
+    pub city: &'static str,
+    pub venue_url: Option<&'static str>,
+    pub description: Option<&'static str>,
+    pub neighborhood: Option<&'static str>,
+}
+
+impl KnownVenue {
+    /// Deterministic id, the same in every run and from every source
+    pub fn id(&self) -> Uuid {
+        Uuid::new_v5(&Uuid::NAMESPACE_DNS, self.slug.as_bytes())
+    }
+
+    /// Whether `name` is this venue's name or one of its aliases
+    pub fn is_named(&self, name: &str) -> bool {
+        let key = venue_key(name);
+        !key.is_empty() && std::iter::once(self.name).chain(self.aliases.iter().copied()).any(|n| venue_key(n) == key)
+    }
+
+    pub fn to_venue(&self) -> Venue {
+        Venue {
+            id: Some(self.id()),
+            name: self.name.to_string(),
+            name_lower: self.name.to_lowercase(),
+            slug: self.slug.to_string(),
+            latitude: self.latitude,
+            longitude: self.longitude,
+            address: self.address.to_string(),
+            postal_code: self.postal_code.to_string(),
+            city: self.city.to_string(),
+            venue_url: self.venue_url.map(str::to_string),
+            venue_image_url: None,
+            description: self.description.map(str::to_string),
+            neighborhood: self.neighborhood.map(str::to_string),
+            show_venue: true,
+            created_at: Utc::now(),
+            attributions: Vec::new(),
+            provisional: false,
+            aliases: Vec::new(),
+            age_restriction: None,
+            accessibility_notes: None,
+            moderation: None,
+        }
+    }
+}
+
+pub const BARBOZA: KnownVenue = KnownVenue {
+    source_id: Some("barboza"),
+    name: "The Barboza",
+    slug: "the-barboza",
+    aliases: &["Barboza"],
+    latitude: 47.6133, // Downstairs from Neumos on Capitol Hill
+    longitude: -122.3185,
+    address: "925 E Pike St",
+    postal_code: "98122",
+    city: "Seattle",
+    venue_url: Some("https://www.thebarboza.com"),
+    description: Some("Underground music venue in Capitol Hill featuring live performances and DJ nights"),
+    neighborhood: Some("Capitol Hill"),
+};
+
+pub const BLUE_MOON: KnownVenue = KnownVenue {
+    source_id: Some("blue_moon"),
+    name: "Blue Moon Tavern",
+    slug: "blue-moon-tavern",
+    aliases: &["Blue Moon"],
+    latitude: 47.6608, // U-District location
+    longitude: -122.3126,
+    address: "712 NE 45th St",
+    postal_code: "98105",
+    city: "Seattle",
+    venue_url: Some("https://www.bluemoonseattle.com"),
+    description: Some("Historic tavern and live music venue in the University District"),
+    neighborhood: Some("University District"),
+};
+
+pub const CONOR_BYRNE: KnownVenue = KnownVenue {
+    source_id: Some("conor_byrne"),
+    name: "Conor Byrne Pub",
+    slug: "conor-byrne-pub",
+    aliases: &["Conor Byrne", "Conor Byrne's"],
+    latitude: 47.6686, // Ballard Ave
+    longitude: -122.3842,
+    address: "5140 Ballard Ave NW",
+    postal_code: "98107",
+    city: "Seattle",
+    venue_url: Some("https://www.conorbyrnepub.com"),
+    description: Some("Historic Irish pub featuring live music in the heart of Ballard"),
+    neighborhood: Some("Ballard"),
+};
+
+pub const DARRELLS_TAVERN: KnownVenue = KnownVenue {
+    source_id: Some("darrells_tavern"),
+    name: "Darrell's Tavern",
+    slug: "darrells-tavern",
+    aliases: &["Darrell's"],
+    latitude: 47.6780, // Approximate location in Shoreline
+    longitude: -122.3460,
+    address: "18041 Aurora Ave N",
+    postal_code: "98133",
+    city: "Shoreline",
+    venue_url: Some("https://www.darrellstavern.com"),
+    description: Some("Live music venue in Shoreline"),
+    neighborhood: Some("Shoreline"),
+};
+
+pub const KEXP: KnownVenue = KnownVenue {
+    source_id: Some("kexp"),
+    name: "KEXP Events",
+    slug: "kexp-events",
+    aliases: &["KEXP", "KEXP Gathering Space"],
+    latitude: 47.6205, // Seattle Center, Lower Queen Anne
+    longitude: -122.3493,
+    address: "472 1st Ave N",
+    postal_code: "98109",
+    city: "Seattle",
+    venue_url: Some("https://www.kexp.org/events/"),
+    description: Some("KEXP Radio Station and live event venue in Lower Queen Anne"),
+    neighborhood: Some("Lower Queen Anne"),
+};
+
+pub const NEUMOS: KnownVenue = KnownVenue {
+    source_id: Some("neumos"),
+    name: "Neumos",
+    slug: "neumos",
+    aliases: &["Neumo's"],
+    latitude: 47.614746, // Capitol Hill
+    longitude: -122.319532,
+    address: "925 E Pike St",
+    postal_code: "98122",
+    city: "Seattle",
+    venue_url: Some("https://www.neumos.com"),
+    description: Some("Legendary Capitol Hill music venue featuring live bands and DJ nights"),
+    neighborhood: Some("Capitol Hill"),
+};
+
+pub const SEA_MONSTER: KnownVenue = KnownVenue {
+    source_id: Some("sea_monster"),
+    name: "Sea Monster Lounge",
+    slug: "sea-monster-lounge",
+    aliases: &["Sea Monster"],
+    latitude: 47.6615064, // Wallingford
+    longitude: -122.3323427,
+    address: "2202 N 45th St, Seattle, WA 98103",
+    postal_code: "98103",
+    city: "Seattle",
+    venue_url: Some("https://www.seamonsterlounge.com"),
+    description: Some("Live music venue in Wallingford"),
+    neighborhood: Some("Wallingford"),
+};
+
+pub const SUNSET_TAVERN: KnownVenue = KnownVenue {
+    source_id: Some("sunset_tavern"),
+    name: "Sunset Tavern",
+    slug: "sunset-tavern",
+    aliases: &["The Sunset"],
+    latitude: 47.6684, // Ballard Ave
+    longitude: -122.3840,
+    address: "5433 Ballard Ave NW",
+    postal_code: "98107",
+    city: "Seattle",
+    venue_url: Some("https://sunsettavern.com"),
+    description: Some("Ballard bar and music venue hosting local and touring bands nightly"),
+    neighborhood: Some("Ballard"),
+};
+
+pub const KNOWN_VENUES: &[KnownVenue] =
+    &[BARBOZA, BLUE_MOON, CONOR_BYRNE, DARRELLS_TAVERN, KEXP, NEUMOS, SEA_MONSTER, SUNSET_TAVERN];
+
+/// Name key venues are compared by: case, punctuation and a leading "The" are ignored,
+/// so "Crocodile" finds "The Crocodile" and "Neumo's" finds "Neumos"
+pub fn venue_key(name: &str) -> String {
+    let lower = name.trim().to_lowercase();
+    let lower = lower.strip_prefix("the ").unwrap_or(&lower);
+    lower.chars().filter(|c| c.is_alphanumeric()).collect()
+}
+
+/// Matches venue names and coordinates against the known venues: alias table first, then
+/// fuzzy name matching, confirmed by distance when coordinates are given
+#[derive(Debug, Clone, Copy)]
+pub struct VenueResolver {
+    venues: &'static [KnownVenue],
+    radius_m: f64,
+}
+
+impl Default for VenueResolver {
+    fn default() -> Self {
+        Self { venues: KNOWN_VENUES, radius_m: DEFAULT_VENUE_RADIUS_M }
+    }
+}
+
+impl VenueResolver {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Resolve against `venues` instead of the built-in table
+    pub fn with_venues(mut self, venues: &'static [KnownVenue]) -> Self {
+        self.venues = venues;
+        self
+    }
+
+    /// Distance within which coordinates count as the same place
+    pub fn with_radius_m(mut self, radius_m: f64) -> Self {
+        self.radius_m = radius_m;
+        self
+    }
+
+    pub fn venues(&self) -> &'static [KnownVenue] {
+        self.venues
+    }
+
+    /// The venue a single-venue source lists events for
+    pub fn for_source(&self, source_id: &str) -> Option<&'static KnownVenue> {
+        self.venues.iter().find(|v| v.source_id == Some(source_id))
+    }
+
+    /// The known venue a listing's venue name refers to
+    pub fn by_name(&self, name: &str) -> Option<&'static KnownVenue> {
+        self.resolve(name, None)
+    }
+
+    /// The known venue a name (and optionally coordinates) refers to. An exact name or alias
+    /// always matches. Otherwise the best fuzzy match wins if it scores `NAME_MATCH_SCORE`, or
+    /// `NEARBY_NAME_MATCH_SCORE` within the radius. Coordinates outside twice the radius rule
+    /// a venue out, so a same-named venue in another town stays separate.
+    pub fn resolve(&self, name: &str, coordinates: Option<(f64, f64)>) -> Option<&'static KnownVenue> {
+        let key = venue_key(name);
+        if key.is_empty() || key == "tba" {
+            return None;
+        }
+        let radius_km = self.radius_m / 1000.0;
+        let distance = |v: &KnownVenue| coordinates.map(|(lat, lng)| haversine_km(lat, lng, v.latitude, v.longitude));
+
+        if let Some(venue) = self.venues.iter().find(|v| v.is_named(name)) {
+            return (distance(venue).is_none_or(|km| km <= 2.0 * radius_km)).then_some(venue);
+        }
+        self.venues
+            .iter()
+            .filter_map(|venue| {
+                let score = Self::name_similarity(name, venue.name);
+                let required = match distance(venue) {
+                    Some(km) if km > 2.0 * radius_km => return None,
+                    Some(km) if km <= radius_km => NEARBY_NAME_MATCH_SCORE,
+                    _ => NAME_MATCH_SCORE,
+                };
+                (score >= required).then_some((score, venue))
+            })
+            .max_by(|(a, _), (b, _)| a.total_cmp(b))
+            .map(|(_, venue)| venue)
+    }
+
+    /// The known venue a `Venue` is, by its name and coordinates
+    pub fn resolve_venue(&self, venue: &Venue) -> Option<&'static KnownVenue> {
+        if venue.provisional {
+            return self.by_name(&venue.name);
+        }
+        self.resolve(&venue.name, Some((venue.latitude, venue.longitude)))
+    }
+
+    /// Whether two venues both resolve to the same known venue
+    pub fn same_venue(&self, a: &Venue, b: &Venue) -> bool {
+        match (self.resolve_venue(a), self.resolve_venue(b)) {
+            (Some(a), Some(b)) => a.slug == b.slug,
+            _ => false,
+        }
+    }
+
+    /// How alike two venue names are, from 0.0 to 1.0: equal keys score 1.0, otherwise the
+    /// better fuzzy score in either direction, so a name inside a longer listing of it scores high
+    pub fn name_similarity(name1: &str, name2: &str) -> f64 {
+        let (key1, key2) = (venue_key(name1), venue_key(name2));
+        if !key1.is_empty() && key1 == key2 {
+            return 1.0;
+        }
+        fuzzy_score(name1, name2).max(fuzzy_score(name2, name1))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn known_venues_resolve_by_source_alias_and_fuzzy_name() {
+        let resolver = VenueResolver::new();
+        assert_eq!(resolver.for_source("neumos").map(|v| v.slug), Some("neumos"));
+        assert!(resolver.for_source("unknown").is_none());
+
+        assert_eq!(resolver.by_name("Neumo's").map(|v| v.slug), Some("neumos"));
+        assert_eq!(resolver.by_name("barboza").map(|v| v.slug), Some("the-barboza"));
+        assert_eq!(resolver.by_name("Conor Byrne").map(|v| v.slug), Some("conor-byrne-pub"));
+        assert_eq!(resolver.by_name("Sunset Tavern Seattle").map(|v| v.slug), Some("sunset-tavern"));
+        assert!(resolver.by_name("The Crocodile").is_none());
+        assert!(resolver.by_name("TBA").is_none());
+
+        // Ids are the slug-derived ones the normalizers have always used
+        assert_eq!(NEUMOS.id(), Uuid::new_v5(&Uuid::NAMESPACE_DNS, b"neumos"));
+        assert_eq!(NEUMOS.to_venue().id, Some(NEUMOS.id()));
+    }
+
+    #[test]
+    fn coordinates_confirm_or_rule_out_a_name_match() {
+        let resolver = VenueResolver::new();
+        let at_sunset = Some((SUNSET_TAVERN.latitude, SUNSET_TAVERN.longitude));
+        let in_tacoma = Some((47.2529, -122.4443));
+
+        assert_eq!(resolver.resolve("Sunset Tavern", at_sunset).map(|v| v.slug), Some("sunset-tavern"));
+        assert!(resolver.resolve("Sunset Tavern", in_tacoma).is_none());
+        // A looser name is enough on the spot, but not from across town
+        assert_eq!(resolver.resolve("Sunset Tavren", at_sunset).map(|v| v.slug), Some("sunset-tavern"));
+        assert!(resolver.resolve("Sunset Tavren", None).is_none());
+
+        let mut listed = Venue::placeholder("Neumo's");
+        assert!(resolver.same_venue(&listed, &NEUMOS.to_venue()));
+        assert!(!resolver.same_venue(&listed, &BARBOZA.to_venue()));
+        listed = Venue::placeholder("Some New Bar");
+        assert!(!resolver.same_venue(&listed, &listed));
+    }
+}
@@ -0,0 +1,302 @@
+//! Embeddable entry point for running the pipeline from other Rust code
+//! (admin servers, schedulers, tests) without going through the CLI.
+
+use std::collections::HashSet;
+
+use anyhow::Result;
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::Serialize;
+use sms_core::domain::RawData;
+
+use super::assets::{self, AssetLoad};
+use super::full_pipeline_orchestrator::{FullPipelineOrchestrator, ProcessingResult};
+use crate::observability::{RunSummary, RunTracker, StageTiming};
+use super::processing::conflation::ConflatorConfig;
+use super::processing::duplicate_suppression::{DuplicateSuppressionConfig, SuppressedDuplicate};
+use super::processing::quality_gate::QualityGateConfig;
+use super::processing::quality_gate::shadow::ShadowGateReport;
+use super::streaming::StageConcurrency;
+use crate::infra::sink_registry::SinksConfig;
+
+/// Knobs for a single pipeline run
+#[derive(Debug, Clone, Default)]
+pub struct RunOptions {
+    /// Fetch even if the source was fetched within its cadence interval
+    pub bypass_cadence: bool,
+    /// Match thresholds and tie-breaking for the conflation stage
+    pub conflator: ConflatorConfig,
+    /// When an event counts as a duplicate of a cataloged one, and how it's merged
+    pub duplicates: DuplicateSuppressionConfig,
+    /// Workers per pipelined stage and the channel capacity between stages
+    pub concurrency: StageConcurrency,
+    /// Rules of the active quality gate, which decides which events go on to be cataloged
+    pub quality_gate: QualityGateConfig,
+    /// Candidate quality gate config to score every event with beside the active gate,
+    /// without affecting which events pass
+    pub quality_shadow: Option<QualityGateConfig>,
+    /// Where the normalize and quality gate stages' records go besides the catalog; `None`
+    /// writes no stage outputs
+    pub sinks: Option<SinksConfig>,
+}
+
+/// Raw data rows run through the pipeline together by a reprocess before progress is reported
+pub const DEFAULT_REPROCESS_BATCH_SIZE: usize = 25;
+
+/// Which of a source's stored raw data a reprocess covers, and how it's batched
+#[derive(Debug, Clone)]
+pub struct ReprocessOptions {
+    /// Only rows fetched on or after this day
+    pub since: Option<NaiveDate>,
+    /// At most this many rows, the most recently fetched
+    pub limit: Option<usize>,
+    pub batch_size: usize,
+}
+
+impl Default for ReprocessOptions {
+    fn default() -> Self {
+        Self { since: None, limit: None, batch_size: DEFAULT_REPROCESS_BATCH_SIZE }
+    }
+}
+
+impl ReprocessOptions {
+    /// The rows of `rows` to reprocess, oldest first so a listing's latest fetch is written last
+    pub fn select(&self, mut rows: Vec<RawData>) -> Vec<RawData> {
+        let mut seen = HashSet::new();
+        rows.retain(|row| row.id.is_none_or(|id| seen.insert(id)));
+        if let Some(since) = self.since {
+            rows.retain(|row| row.created_at.date_naive() >= since);
+        }
+        rows.sort_by_key(|row| std::cmp::Reverse(row.created_at));
+        if let Some(limit) = self.limit {
+            rows.truncate(limit);
+        }
+        rows.reverse();
+        rows
+    }
+}
+
+/// Running totals after one reprocess batch
+#[derive(Debug, Clone, Serialize)]
+pub struct ReprocessProgress {
+    /// 1-based number of the batch just finished
+    pub batch: usize,
+    pub batches: usize,
+    pub items_done: usize,
+    pub items_total: usize,
+    pub processed_items: usize,
+    pub failed_items: usize,
+    pub records_cataloged: usize,
+}
+
+/// Outcome of a pipeline run for one source
+#[derive(Debug, Clone, Serialize)]
+pub struct RunReport {
+    pub run_id: String,
+    pub source_id: String,
+    pub started_at: DateTime<Utc>,
+    pub finished_at: DateTime<Utc>,
+    pub total_items: usize,
+    pub processed_items: usize,
+    pub failed_items: usize,
+    pub records_parsed: usize,
+    pub records_cataloged: usize,
+    pub suppressed_duplicates: Vec<SuppressedDuplicate>,
+    pub errors: Vec<String>,
+    /// Time spent in each pipeline stage, summed over items
+    pub stages: Vec<StageTiming>,
+    /// Decisions of `RunOptions::quality_shadow` against the active gate's
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub quality_shadow: Option<ShadowGateReport>,
+    /// Registry and rule assets loaded in this process so far, with how long each took
+    pub assets: Vec<AssetLoad>,
+    /// Warnings about the run as a whole, such as payload schema drift
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub notes: Vec<String>,
+}
+
+impl RunReport {
+    fn from_result(run: RunSummary, result: ProcessingResult) -> Self {
+        Self {
+            run_id: run.run_id,
+            source_id: result.source_id,
+            started_at: run.started_at,
+            finished_at: run.finished_at,
+            total_items: result.total_items,
+            processed_items: result.processed_items,
+            failed_items: result.failed_items,
+            records_parsed: result.records_parsed,
+            records_cataloged: result.records_cataloged,
+            suppressed_duplicates: result.suppressed_duplicates,
+            errors: result.errors,
+            stages: run.stages,
+            quality_shadow: result.quality_shadow,
+            assets: assets::recorded_loads(),
+            notes: result.notes,
+        }
+    }
+
+    /// Check if the run was successful (no failed items)
+    pub fn is_success(&self) -> bool {
+        self.failed_items == 0
+    }
+
+    /// Get success rate as percentage
+    pub fn success_rate(&self) -> f64 {
+        if self.total_items == 0 {
+            100.0
+        } else {
+            (self.processed_items as f64 / self.total_items as f64) * 100.0
+        }
+    }
+
+    pub fn duration(&self) -> chrono::Duration {
+        self.finished_at - self.started_at
+    }
+}
+
+/// Facade over the orchestrator for running the pipeline programmatically
+pub struct PipelineRunner {
+    orchestrator: FullPipelineOrchestrator,
+}
+
+impl PipelineRunner {
+    /// Connect to the configured database and load the source registry, keeping the CAS,
+    /// ingest log and run bookkeeping under `data_root`
+    pub async fn new(data_root: impl Into<std::path::PathBuf>) -> Result<Self> {
+        Ok(Self { orchestrator: FullPipelineOrchestrator::new(data_root).await? })
+    }
+
+    /// Run against in-memory storage with no database or `data/` writes
+    pub fn in_memory() -> Result<Self> {
+        Ok(Self { orchestrator: FullPipelineOrchestrator::in_memory()? })
+    }
+
+    pub fn from_orchestrator(orchestrator: FullPipelineOrchestrator) -> Self {
+        Self { orchestrator }
+    }
+
+    /// Run ingestion through catalog for a source
+    pub async fn run_full(&self, source_id: &str, options: &RunOptions) -> Result<RunReport> {
+        let tracker = RunTracker::open("full_pipeline", Some(source_id));
+        let result = match self
+            .orchestrator
+            .process_source_tracked(source_id, &tracker, options)
+            .await
+        {
+            Ok(result) => result,
+            Err(e) => {
+                tracker.fail(&e);
+                return Err(e);
+            }
+        };
+        Ok(Self::report(tracker, result))
+    }
+
+    /// Run parse through catalog again over a source's stored raw data, processed or not,
+    /// calling `on_batch` after each batch
+    pub async fn run_reprocess(
+        &self,
+        source_id: &str,
+        options: &RunOptions,
+        reprocess: &ReprocessOptions,
+        on_batch: impl FnMut(&ReprocessProgress),
+    ) -> Result<RunReport> {
+        let tracker = RunTracker::open("reprocess", Some(source_id));
+        let result = match self
+            .orchestrator
+            .reprocess_source_tracked(source_id, &tracker, options, reprocess, on_batch)
+            .await
+        {
+            Ok(result) => result,
+            Err(e) => {
+                tracker.fail(&e);
+                return Err(e);
+            }
+        };
+        Ok(Self::report(tracker, result))
+    }
+
+    /// Close the run as failed if any item failed
+    fn report(tracker: RunTracker, result: ProcessingResult) -> RunReport {
+        let run = if result.failed_items == 0 {
+            tracker.succeed()
+        } else {
+            tracker.fail(format!("{} of {} items failed", result.failed_items, result.total_items))
+        };
+        RunReport::from_result(run, result)
+    }
+
+    /// Fetch and store raw data for a source without processing it
+    pub async fn run_ingestion(&self, source_id: &str, options: &RunOptions) -> Result<()> {
+        let tracker = RunTracker::open("ingestion", Some(source_id));
+        let result = tracker
+            .stage("ingestion", self.orchestrator.run_ingestion_for_source(source_id, options.bypass_cadence))
+            .await;
+        tracker.close(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn report_carries_processing_counts() {
+        let result = ProcessingResult {
+            source_id: "kexp".to_string(),
+            total_items: 4,
+            processed_items: 3,
+            failed_items: 1,
+            records_parsed: 20,
+            records_cataloged: 18,
+            suppressed_duplicates: Vec::new(),
+            errors: vec!["Processing failed: boom".to_string()],
+            quality_shadow: None,
+            notes: Vec::new(),
+        };
+        let tracker = RunTracker::with_run_id("full_pipeline", Some("kexp"), "run-1".to_string());
+        tracker.record_stage("parse", 0.5);
+        let report = RunReport::from_result(tracker.fail("1 of 4 items failed"), result);
+
+        assert_eq!(report.run_id, "run-1");
+        assert_eq!(report.records_cataloged, 18);
+        assert!(!report.is_success());
+        assert_eq!(report.success_rate(), 75.0);
+        assert!(report.duration() >= chrono::Duration::zero());
+        assert_eq!(report.stages[0].stage, "parse");
+    }
+
+    fn raw(id: u128, fetched: &str) -> RawData {
+        RawData {
+            id: Some(uuid::Uuid::from_u128(id)),
+            api_name: "crawler_kexp".to_string(),
+            event_api_id: id.to_string(),
+            event_name: "Show".to_string(),
+            venue_name: "KEXP".to_string(),
+            event_day: NaiveDate::from_ymd_opt(2025, 9, 1).unwrap(),
+            data: serde_json::Value::Null,
+            processed: id.is_multiple_of(2),
+            event_id: None,
+            created_at: DateTime::parse_from_rfc3339(fetched).unwrap().with_timezone(&Utc),
+            origin: None,
+        }
+    }
+
+    #[test]
+    fn reprocess_selects_recent_rows_oldest_first() {
+        let rows = vec![
+            raw(1, "2025-08-01T10:00:00Z"),
+            raw(2, "2025-08-03T10:00:00Z"),
+            raw(3, "2025-08-02T10:00:00Z"),
+            raw(4, "2025-07-30T10:00:00Z"),
+            raw(2, "2025-08-03T10:00:00Z"),
+        ];
+        let ids = |rows: Vec<RawData>| rows.iter().map(|r| r.id.unwrap().as_u128()).collect::<Vec<_>>();
+
+        assert_eq!(ids(ReprocessOptions::default().select(rows.clone())), vec![4, 1, 3, 2]);
+        let since = ReprocessOptions { since: NaiveDate::from_ymd_opt(2025, 8, 1), ..Default::default() };
+        assert_eq!(ids(since.select(rows.clone())), vec![1, 3, 2]);
+        let limited = ReprocessOptions { limit: Some(2), ..Default::default() };
+        assert_eq!(ids(limited.select(rows)), vec![3, 2]);
+    }
+}
@@ -1,26 +1,56 @@
 use anyhow::Result;
 use async_trait::async_trait;
-use tracing::{info, debug, error};
-use sms_core::storage::Storage;
+use tracing::{info, debug, error, warn};
+use sms_core::storage::{allocate_artist, SlugAllocation, Storage};
 use sms_core::domain::{Event, Venue, Artist};
 use uuid::Uuid;
 use chrono;
+use std::path::Path;
+use crate::pipeline::processing::catalog::graph_validation::{GraphValidationReport, GraphValidator};
+use crate::pipeline::processing::conflation::{ConflatorConfig, DefaultConflator};
 use super::{PipelineStep, StepResult};
 
 /// Pipeline step for storing entities in graph database
 pub struct CatalogStep {
     validate_graph: bool,
+    /// Thresholds an artist whose slug is taken is judged against the slug's owner with
+    conflator: ConflatorConfig,
 }
 
 impl CatalogStep {
     pub fn new(validate_graph: bool) -> Self {
-        Self { validate_graph }
+        Self { validate_graph, conflator: ConflatorConfig::default() }
+    }
+
+    /// Judge artist slug collisions with these thresholds instead of the defaults
+    pub fn with_conflator(mut self, config: ConflatorConfig) -> Self {
+        self.conflator = config;
+        self
     }
 }
 
 #[async_trait]
 impl PipelineStep for CatalogStep {
     async fn execute(&self, source_id: &str, storage: &dyn Storage) -> Result<StepResult> {
+        self.execute_with_report(source_id, storage).await.map(|(result, _)| result)
+    }
+    
+    fn step_name(&self) -> &'static str {
+        "catalog"
+    }
+    
+    fn dependencies(&self) -> Vec<&'static str> {
+        vec!["conflation"]
+    }
+}
+
+impl CatalogStep {
+    /// Run the catalog step, also returning the graph validation report when validation is enabled
+    pub async fn execute_with_report(
+        &self,
+        source_id: &str,
+        storage: &dyn Storage,
+    ) -> Result<(StepResult, Option<GraphValidationReport>)> {
         info!("📚 Running catalog step for source: {} (validate: {})", source_id, self.validate_graph);
         
         // 1. Get all processed raw data for this source (parsed events)
@@ -44,7 +74,7 @@ impl PipelineStep for CatalogStep {
             }
             
             // Create or get venue
-            let (venue_id, venue_created) = match self.ensure_venue_exists(&raw_data.venue_name, storage).await {
+            let (venue_id, _venue_created) = match self.ensure_venue_exists(&raw_data.venue_name, storage).await {
                 Ok((id, created)) => {
                     if created {
                         created_venues += 1;
@@ -61,7 +91,7 @@ impl PipelineStep for CatalogStep {
             // For now, assume single artist from event name parsing
             // In a full implementation, this would parse artist names from the raw data
             let artist_name = &raw_data.event_name; // Simplified - would need proper artist extraction
-            let (artist_id, artist_created) = match self.ensure_artist_exists(artist_name, storage).await {
+            let (artist_id, _artist_created) = match self.ensure_artist_exists(artist_name, storage).await {
                 Ok((id, created)) => {
                     if created {
                         created_artists += 1;
@@ -98,6 +128,15 @@ impl PipelineStep for CatalogStep {
                         show_event: true,
                         finalized: true,
                         created_at: chrono::Utc::now(),
+                        attributions: Vec::new(),
+                        tags: Vec::new(),
+                        price: None,
+                        doors_time: None,
+                        series_id: None,
+                        age_restriction: None,
+                        accessibility_notes: None,
+                        moderation: None,
+                        external_ids: Vec::new(),
                     };
                     
                     // Create the event in the graph database
@@ -120,31 +159,34 @@ impl PipelineStep for CatalogStep {
             }
         }
         
-        // 3. Optionally validate graph integrity
-        if self.validate_graph {
-            debug!("Validating graph integrity for source {}", source_id);
-            // TODO: Add graph validation logic
-        }
-        
         let message = format!(
             "Catalog completed for {}: {} events, {} venues, {} artists created ({} errors)",
             source_id, created_events, created_venues, created_artists, errors
         );
         info!("✅ {}", message);
+        let mut result = StepResult::success(created_events, message);
         
-        Ok(StepResult::success(created_events, message))
-    }
-    
-    fn step_name(&self) -> &'static str {
-        "catalog"
+        // 3. Optionally validate graph integrity
+        let report = if self.validate_graph {
+            debug!("Validating graph integrity for source {}", source_id);
+            let report = GraphValidator::validate(storage).await?;
+            let report_path = report.write_to_dir(Path::new("output"))?;
+            if report.is_clean() {
+                info!("✅ Graph validation passed ({} venues, {} artists, {} events)", report.venues, report.artists, report.events);
+            } else {
+                warn!("❌ Graph validation found {} violations: {:?}", report.violations.len(), report.counts_by_kind());
+                result.success = false;
+            }
+            result.metadata.insert("graph_violations".to_string(), report.violations.len().to_string());
+            result.metadata.insert("graph_report".to_string(), report_path.display().to_string());
+            Some(report)
+        } else {
+            None
+        };
+        
+        Ok((result, report))
     }
     
-    fn dependencies(&self) -> Vec<&'static str> {
-        vec!["conflation"]
-    }
-}
-
-impl CatalogStep {
     /// Ensure a venue exists in the database, creating it if necessary
     /// Returns (venue_id, was_created)
     async fn ensure_venue_exists(&self, venue_name: &str, storage: &dyn Storage) -> Result<(Uuid, bool)> {
@@ -172,6 +214,12 @@ impl CatalogStep {
             neighborhood: None,
             show_venue: true,
             created_at: chrono::Utc::now(),
+            attributions: Vec::new(),
+            provisional: false,
+            aliases: Vec::new(),
+            age_restriction: None,
+            accessibility_notes: None,
+            moderation: None,
         };
         
         storage.create_venue(&mut venue).await?;
@@ -192,13 +240,20 @@ impl CatalogStep {
         let mut artist = Artist {
             id: None,
             name: artist_name.to_string(),
-            name_slug: artist_name.to_lowercase().replace(' ', "-"),
+            name_slug: String::new(),
             bio: None,
             artist_image_url: None,
             created_at: chrono::Utc::now(),
+            attributions: Vec::new(),
+            detail_origins: Default::default(),
+            aliases: Vec::new(),
         };
         
-        storage.create_artist(&mut artist).await?;
-        Ok((artist.id.unwrap(), true)) // Created new artist
+        let conflator = DefaultConflator::new().with_config(self.conflator.clone());
+        let allocation = allocate_artist(storage, &mut artist, |existing, new| {
+            conflator.same_artist_name(&existing.name, &new.name)
+        })
+        .await?;
+        Ok((allocation.id(), matches!(allocation, SlugAllocation::Created { .. })))
     }
 }
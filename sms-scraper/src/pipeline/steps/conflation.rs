@@ -3,22 +3,26 @@ use async_trait::async_trait;
 use tracing::info;
 use sms_core::storage::Storage;
 use super::{PipelineStep, StepResult};
+use crate::pipeline::processing::conflation::ConflatorConfig;
 
 /// Pipeline step for resolving duplicate entities and creating canonical IDs
 pub struct ConflationStep {
-    confidence_threshold: f64,
+    config: ConflatorConfig,
 }
 
 impl ConflationStep {
-    pub fn new(confidence_threshold: f64) -> Self {
-        Self { confidence_threshold }
+    pub fn new(config: ConflatorConfig) -> Self {
+        Self { config }
     }
 }
 
 #[async_trait]
 impl PipelineStep for ConflationStep {
     async fn execute(&self, source_id: &str, _storage: &dyn Storage) -> Result<StepResult> {
-        info!("🔗 Running conflation step for source: {} (threshold: {})", source_id, self.confidence_threshold);
+        info!(
+            "🔗 Running conflation step for source: {} (thresholds venue={} event={} artist={}, tie-break {:?})",
+            source_id, self.config.venue_threshold, self.config.event_threshold, self.config.artist_threshold, self.config.tie_break
+        );
         
         // For now, this is a placeholder that simulates conflation
         // In the full implementation, this would:
@@ -7,6 +7,12 @@ use super::{PipelineStep, StepResult};
 /// Pipeline step for enriching events with additional metadata
 pub struct EnrichStep;
 
+impl Default for EnrichStep {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl EnrichStep {
     pub fn new() -> Self {
         Self
@@ -1,19 +1,77 @@
 use anyhow::Result;
 use async_trait::async_trait;
+use sha2::{Digest, Sha256};
+use std::collections::HashSet;
+use std::sync::Arc;
 use tracing::{info, error};
 use sms_core::storage::Storage;
-use sms_core::domain::RawData;
+use sms_core::domain::{RawData, RawDataOrigin};
+use crate::pipeline::ingestion::ingest_common::{ingest_spec, is_cadence_skip, is_quota_skip, IngestOptions};
+use crate::pipeline::ingestion::registry_watch::RegistrySnapshot;
 use crate::registry::source_loader::SourceRegistry;
 use super::{PipelineStep, StepResult};
 
 /// Pipeline step for ingesting raw data from external sources
 pub struct IngestionStep {
     source_registry: SourceRegistry,
+    gateway: Option<(Arc<RegistrySnapshot>, IngestOptions)>,
 }
 
 impl IngestionStep {
     pub fn new(source_registry: SourceRegistry) -> Self {
-        Self { source_registry }
+        Self { source_registry, gateway: None }
+    }
+
+    /// Fetch every endpoint of the source's spec in `specs` through the gateway, so payloads
+    /// land in CAS and the ingest log and cadence applies, instead of with the venue crawler
+    pub fn through_gateway(mut self, specs: Arc<RegistrySnapshot>, options: IngestOptions) -> Self {
+        self.gateway = Some((specs, options));
+        self
+    }
+
+    /// Raw payloads for the source, each with the envelope it was accepted in when fetched
+    /// through the gateway: one per payload, so every page of a paginated fetch is stored.
+    /// Deduplicated fetches bring nothing new and are left out, unless another envelope of
+    /// the same endpoint changed: a fetch paginated into one envelope per page is then kept
+    /// whole, so an unchanged page doesn't read as its events being gone.
+    async fn fetch(&self, source_id: &str) -> Result<Vec<(serde_json::Value, Option<RawDataOrigin>)>> {
+        let Some((specs, options)) = &self.gateway else {
+            let crawler = crate::apis::factory::create_crawler(source_id, self.source_registry.clone())?
+                .ok_or_else(|| anyhow::anyhow!("Failed to create crawler for source: {}", source_id))?;
+            return Ok(crawler.get_event_list().await?.into_iter().map(|data| (data, None)).collect());
+        };
+        let spec = specs
+            .get(source_id)
+            .ok_or_else(|| anyhow::anyhow!("Source {} is not in the registry", source_id))?;
+        let ingested = match ingest_spec(spec, options).await {
+            Ok(ingested) => ingested,
+            Err(e) if is_cadence_skip(&e) || is_quota_skip(&e) => {
+                info!("⏭️  Skipping ingestion for {}: {}", source_id, e);
+                return Ok(Vec::new());
+            }
+            Err(e) => return Err(e.into()),
+        };
+        let changed: HashSet<Option<String>> =
+            ingested.iter().filter(|i| i.dedupe_of.is_none()).map(|i| i.endpoint_id.clone()).collect();
+        Ok(ingested
+            .into_iter()
+            .filter(|i| changed.contains(&i.endpoint_id))
+            .flat_map(|i| {
+                // An unchanged page points back at the envelope it duplicated
+                let envelope_id = i.dedupe_of.unwrap_or(i.envelope_id);
+                let endpoint_id = i.endpoint_id;
+                i.parts.into_iter().map(move |part| {
+                    let payload_ref = if part.payload_ref.is_empty() {
+                        format!("cas:sha256:{}", hex::encode(Sha256::digest(&part.payload)))
+                    } else {
+                        part.payload_ref
+                    };
+                    let data = serde_json::Value::String(String::from_utf8_lossy(&part.payload).to_string());
+                    let origin = RawDataOrigin { envelope_id: envelope_id.clone(), payload_ref, endpoint_id: endpoint_id.clone() };
+                    (data, Some(origin))
+                })
+            })
+            .collect())
     }
 }
 
@@ -25,12 +83,8 @@ impl PipelineStep for IngestionStep {
         // Map user-friendly source names to internal API names
         let internal_api_name = crate::common::constants::api_name_to_internal(source_id);
         
-        // Create crawler for this source
-        let crawler = crate::apis::factory::create_crawler(source_id, self.source_registry.clone())?
-            .ok_or_else(|| anyhow::anyhow!("Failed to create crawler for source: {}", source_id))?;
-        
         // Fetch raw event data
-        let raw_event_data = crawler.get_event_list().await?;
+        let raw_event_data = self.fetch(source_id).await?;
         
         if raw_event_data.is_empty() {
             let message = format!("No raw data fetched for source: {}", source_id);
@@ -44,7 +98,7 @@ impl PipelineStep for IngestionStep {
         let mut failed_count = 0;
         
         // Store each raw data item
-        for (index, event_data) in raw_event_data.iter().enumerate() {
+        for (index, (event_data, origin)) in raw_event_data.into_iter().enumerate() {
             let timestamp = chrono::Utc::now();
             let raw_data_id = format!("{}_{}_raw_{}", source_id, timestamp.timestamp(), index);
             
@@ -55,10 +109,11 @@ impl PipelineStep for IngestionStep {
                 venue_name: source_id.to_string(),
                 event_day: chrono::Utc::now().date_naive(),
                 api_name: internal_api_name.clone(),
-                data: event_data.clone(),
+                data: event_data,
                 processed: false,
                 event_id: None,
                 created_at: timestamp,
+                origin,
             };
             
             match storage.create_raw_data(&mut raw_data).await {
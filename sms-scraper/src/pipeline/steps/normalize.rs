@@ -3,7 +3,7 @@ use async_trait::async_trait;
 use tracing::{info, debug, error};
 use sms_core::storage::Storage;
 use super::{PipelineStep, StepResult};
-use crate::pipeline::processing::parser::ParsedRecord;
+use sms_parsers::ParsedRecord;
 use crate::registry::UnifiedSourceRegistry;
 
 /// Pipeline step for normalizing parsed events into consistent format
@@ -30,26 +30,30 @@ impl PipelineStep for NormalizeStep {
         
         debug!("Found {} processed raw data items for normalization", processed_raw_data.len());
         
-        // 2. Get the appropriate normalizer from unified registry
-        let normalizer = self.registry.get_normalizer_for_source(source_id)
-            .map_err(|e| anyhow::anyhow!("Failed to get normalizer for source {}: {}", source_id, e))?;
+        // 2. Fail early on a source the unified registry doesn't know
+        self.registry.get_source_config(source_id)?;
         
         let mut normalized_count = 0;
         let mut errors = 0;
         
         // 3. Process each raw data item through normalization
         for raw_data in processed_raw_data {
-            // Convert raw data to ParsedRecord format expected by normalizer
+            // Convert raw data to ParsedRecord format expected by normalizer; shadows and
+            // normalizers are keyed by the registry's source id, not the internal API name
             let parsed_record = ParsedRecord {
-                source_id: raw_data.api_name.clone(),
+                source_id: source_id.to_string(),
                 envelope_id: raw_data.id.map(|id| id.to_string()).unwrap_or_else(|| uuid::Uuid::new_v4().to_string()),
                 payload_ref: if raw_data.event_api_id.is_empty() { "unknown".to_string() } else { raw_data.event_api_id.clone() },
                 record_path: "$.events[*]".to_string(),
                 record: raw_data.data,
+                attribution: None,
+                change: None,
+                endpoint_id: None,
+                external_id: None,
             };
             
             // Apply normalization
-            match normalizer.normalize(&parsed_record) {
+            match self.registry.normalize(source_id, &parsed_record) {
                 Ok(normalized_records) => {
                     normalized_count += normalized_records.len();
                     debug!("Normalized {} records from raw data {}", normalized_records.len(), if raw_data.event_name.is_empty() { "unknown" } else { &raw_data.event_name });
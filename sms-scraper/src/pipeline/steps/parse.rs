@@ -1,20 +1,53 @@
 use anyhow::Result;
 use async_trait::async_trait;
-use tracing::{info, error, debug};
+use tracing::{info, error, debug, warn};
 use sms_core::storage::Storage;
 use sms_core::domain::RawData;
 use sms_core::common::types::{RawDataInfo, EventArgs};
+use crate::app::ports::{DeadLetterEntry, DeadLetterPort};
+use crate::pipeline::ingestion::envelope_state::{EnvelopeState, EnvelopeStates};
 use crate::registry::source_loader::SourceRegistry;
 use super::{PipelineStep, StepResult};
 
 /// Pipeline step for parsing raw data into structured events
 pub struct ParseStep {
     source_registry: SourceRegistry,
+    /// Where the envelopes raw data was accepted in are marked parsed or failed
+    envelope_states: Option<EnvelopeStates>,
+    /// Where envelopes whose parse failed are kept for re-parsing, with the plan to retry them with
+    dead_letters: Option<(Box<dyn DeadLetterPort>, String)>,
 }
 
 impl ParseStep {
     pub fn new(source_registry: SourceRegistry) -> Self {
-        Self { source_registry }
+        Self { source_registry, envelope_states: None, dead_letters: None }
+    }
+
+    /// Mark the envelope each raw data item came in as parsed, or failed, in `states`
+    pub fn with_envelope_states(mut self, states: EnvelopeStates) -> Self {
+        self.envelope_states = Some(states);
+        self
+    }
+
+    /// Send the envelopes whose parse fails to `dead_letters`, to be retried with `parse_plan`
+    pub fn with_dead_letters(mut self, dead_letters: Box<dyn DeadLetterPort>, parse_plan: impl Into<String>) -> Self {
+        self.dead_letters = Some((dead_letters, parse_plan.into()));
+        self
+    }
+
+    async fn dead_letter(&self, source_id: &str, raw_data: &RawData, error: &str) {
+        let Some((dead_letters, plan)) = &self.dead_letters else { return };
+        let Some(entry) = DeadLetterEntry::for_raw_data(source_id, raw_data, plan, error) else { return };
+        match dead_letters.record(entry).await {
+            Ok(()) => crate::observability::metrics::parser::dead_lettered(),
+            Err(e) => warn!("Failed to dead-letter raw data ID {}: {}", raw_data.event_api_id, e),
+        }
+    }
+
+    fn record_state(&self, source_id: &str, raw_data: &RawData, state: EnvelopeState, detail: Option<&str>) {
+        if let (Some(states), Some(origin)) = (&self.envelope_states, &raw_data.origin) {
+            states.record(&origin.envelope_id, source_id, state, detail);
+        }
     }
     
     /// Parse raw data from a single RawData record
@@ -37,7 +70,7 @@ impl ParseStep {
             }
         };
         
-        let parser = crate::apis::factory::create_parser(api_name)
+        let parser = crate::apis::factory::create_parser(api_name, &self.source_registry.date_hints(api_name))
             .ok_or_else(|| anyhow::anyhow!("Failed to create parser for API: {}", api_name))?;
         
         // Handle both pre-parsed JSON objects and raw JSON strings
@@ -114,6 +147,7 @@ impl PipelineStep for ParseStep {
             match self.parse_raw_data(raw_data).await {
                 Ok(parsed_events) => {
                     debug!("✅ Parsed {} events from raw data ID: {}", parsed_events.len(), raw_data.event_api_id);
+                    self.record_state(source_id, raw_data, EnvelopeState::Parsed, None);
                     
                     // Store parsed events (this would typically go to a parsed_events table)
                     // For now, we'll mark the raw data as processed
@@ -121,6 +155,8 @@ impl PipelineStep for ParseStep {
                 }
                 Err(e) => {
                     error!("❌ Failed to parse raw data ID {}: {}", raw_data.event_api_id, e);
+                    self.record_state(source_id, raw_data, EnvelopeState::Failed, Some(&format!("parse: {}", e)));
+                    self.dead_letter(source_id, raw_data, &e.to_string()).await;
                     processing_errors += 1;
                 }
             }
@@ -341,7 +341,7 @@ impl Storage for DatabaseStorage {
             })?;
         
         // Delete old performs_at edges (where event is target)
-        for (edge_id, source_id, target_id, relation, _) in existing_edges {
+        for (edge_id, _source_id, target_id, relation, _) in existing_edges {
             if relation == "performs_at" && target_id == event_id.to_string() {
                 // Note: The database doesn't have a delete_edge method, so we can't delete
                 // Instead, we'll just create new edges (the upsert will overwrite)
@@ -416,7 +416,7 @@ impl Storage for DatabaseStorage {
         }
 
         // Sort by event_day to process chronologically
-        filtered_data.sort_by(|a, b| a.event_day.cmp(&b.event_day));
+        filtered_data.sort_by_key(|a| a.event_day);
         Ok(filtered_data)
     }
 
@@ -641,7 +641,7 @@ impl Storage for DatabaseStorage {
             events.push(Self::node_data_to_event(&id, &data)?);
         }
 
-        events.sort_by(|a, b| a.event_day.cmp(&b.event_day));
+        events.sort_by_key(|a| a.event_day);
 
         let offset = offset.unwrap_or(0);
         let end = if let Some(limit) = limit {
@@ -681,7 +681,7 @@ impl Storage for DatabaseStorage {
             }
         }
 
-        venue_events.sort_by(|a, b| a.event_day.cmp(&b.event_day));
+        venue_events.sort_by_key(|a| a.event_day);
         Ok(venue_events)
     }
 
@@ -713,7 +713,7 @@ impl Storage for DatabaseStorage {
             }
         }
 
-        artist_events.sort_by(|a, b| a.event_day.cmp(&b.event_day));
+        artist_events.sort_by_key(|a| a.event_day);
         Ok(artist_events)
     }
 
@@ -738,7 +738,7 @@ impl Storage for DatabaseStorage {
             }
         }
 
-        filtered_events.sort_by(|a, b| a.event_day.cmp(&b.event_day));
+        filtered_events.sort_by_key(|a| a.event_day);
         Ok(filtered_events)
     }
 
@@ -852,7 +852,7 @@ impl Storage for DatabaseStorage {
         }
 
         // Sort by created_at descending (newest first)
-        result.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+        result.sort_by_key(|item| std::cmp::Reverse(item.created_at));
 
         debug!("Retrieved {} raw data items for source: {}", result.len(), api_name);
         Ok(result)
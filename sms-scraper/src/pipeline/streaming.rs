@@ -0,0 +1,179 @@
+//! Pipelined stage execution: each stage reads from a bounded channel, processes with its own
+//! pool of workers and feeds the next stage, so later stages start on the first records while
+//! earlier ones are still working through the batch.
+
+use std::future::Future;
+use std::str::FromStr;
+
+use futures_util::future::join_all;
+use tokio::sync::{mpsc, Mutex};
+
+/// Default capacity of the channel between two stages
+pub const DEFAULT_CHANNEL_CAPACITY: usize = 64;
+
+/// A record in flight, tagged with the input item it came from; `Err` carries a failure
+/// through the remaining stages so the item can be reported as failed at the end
+pub type Flow<T> = (usize, Result<T, String>);
+
+/// Workers per pipelined stage of the full pipeline. With one worker everywhere records stay in
+/// input order; more workers trade that for throughput. Catalog always runs on a single worker.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StageConcurrency {
+    pub parse: usize,
+    pub normalize: usize,
+    pub quality_gate: usize,
+    pub enrich: usize,
+    pub conflate: usize,
+    /// Records buffered between two stages before the upstream stage waits
+    pub channel_capacity: usize,
+}
+
+impl Default for StageConcurrency {
+    fn default() -> Self {
+        Self {
+            parse: 1,
+            normalize: 1,
+            quality_gate: 1,
+            enrich: 1,
+            conflate: 1,
+            channel_capacity: DEFAULT_CHANNEL_CAPACITY,
+        }
+    }
+}
+
+impl FromStr for StageConcurrency {
+    type Err = String;
+
+    /// Comma-separated `stage=workers` pairs, e.g. `parse=2,enrich=4`; unnamed stages keep one
+    /// worker
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut concurrency = Self::default();
+        for pair in s.split(',').map(str::trim).filter(|p| !p.is_empty()) {
+            let (stage, workers) = pair
+                .split_once('=')
+                .ok_or_else(|| format!("expected stage=workers, got '{}'", pair))?;
+            let workers: usize = workers
+                .trim()
+                .parse()
+                .ok()
+                .filter(|w| *w > 0)
+                .ok_or_else(|| format!("worker count for '{}' must be a positive integer", stage.trim()))?;
+            let slot = match stage.trim() {
+                "parse" => &mut concurrency.parse,
+                "normalize" => &mut concurrency.normalize,
+                "quality_gate" | "quality-gate" => &mut concurrency.quality_gate,
+                "enrich" => &mut concurrency.enrich,
+                "conflate" | "conflation" => &mut concurrency.conflate,
+                other => {
+                    return Err(format!(
+                        "unknown stage '{}' (expected parse, normalize, quality_gate, enrich or conflate)",
+                        other
+                    ))
+                }
+            };
+            *slot = workers;
+        }
+        Ok(concurrency)
+    }
+}
+
+/// Bounded channel between two stages
+pub fn stage_channel<T>(capacity: usize) -> (mpsc::Sender<Flow<T>>, mpsc::Receiver<Flow<T>>) {
+    mpsc::channel(capacity.max(1))
+}
+
+/// Run one stage until its input closes: `workers` workers take records from `input`, apply
+/// `f` and send every output record on. `f` gets the record's item index alongside it and may
+/// return no records (filtered out) or several (fanned out). Failed records and errors from `f`
+/// pass downstream as `Err`. `output` is dropped on return, which closes the next stage's input.
+pub async fn run_stage<I, O, F, Fut>(
+    workers: usize,
+    input: mpsc::Receiver<Flow<I>>,
+    output: mpsc::Sender<Flow<O>>,
+    f: F,
+) where
+    F: Fn(usize, I) -> Fut,
+    Fut: Future<Output = anyhow::Result<Vec<O>>>,
+{
+    let input = Mutex::new(input);
+    let worker = || async {
+        loop {
+            let Some((item, record)) = input.lock().await.recv().await else { break };
+            let outputs = match record {
+                Ok(record) => match f(item, record).await {
+                    Ok(outputs) => outputs.into_iter().map(Ok).collect(),
+                    Err(e) => vec![Err(e.to_string())],
+                },
+                Err(e) => vec![Err(e)],
+            };
+            for out in outputs {
+                if output.send((item, out)).await.is_err() {
+                    return;
+                }
+            }
+        }
+    };
+    join_all((0..workers.max(1)).map(|_| worker())).await;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::Duration;
+
+    #[test]
+    fn stage_workers_parse_from_pairs() {
+        let c: StageConcurrency = "parse=2, enrich=4,quality-gate=3".parse().unwrap();
+        assert_eq!((c.parse, c.normalize, c.quality_gate, c.enrich, c.conflate), (2, 1, 3, 4, 1));
+        assert_eq!("".parse::<StageConcurrency>().unwrap(), StageConcurrency::default());
+        assert!("catalog=2".parse::<StageConcurrency>().unwrap_err().contains("unknown stage"));
+        assert!("parse=0".parse::<StageConcurrency>().is_err());
+        assert!("parse".parse::<StageConcurrency>().is_err());
+    }
+
+    #[tokio::test]
+    async fn stages_overlap_fan_out_and_carry_failures() {
+        let (feed_tx, feed_rx) = stage_channel::<u32>(1);
+        let (split_tx, split_rx) = stage_channel::<u32>(1);
+        let (out_tx, mut out_rx) = stage_channel::<u32>(1);
+        let busy = AtomicUsize::new(0);
+        let peak = AtomicUsize::new(0);
+
+        let feed = async move {
+            for n in 0..4u32 {
+                feed_tx.send((n as usize, Ok(n))).await.unwrap();
+            }
+        };
+        // Item 2 fails; the rest fan out to two records each
+        let split = run_stage(1, feed_rx, split_tx, |_, n| async move {
+            anyhow::ensure!(n != 2, "bad record {}", n);
+            Ok(vec![n * 10, n * 10 + 1])
+        });
+        // Slow stage with three workers: several records should be in it at once
+        let slow = run_stage(3, split_rx, out_tx, |_, n| {
+            let (busy, peak) = (&busy, &peak);
+            async move {
+                peak.fetch_max(busy.fetch_add(1, Ordering::SeqCst) + 1, Ordering::SeqCst);
+                tokio::time::sleep(Duration::from_millis(20)).await;
+                busy.fetch_sub(1, Ordering::SeqCst);
+                Ok(if n % 10 == 1 { vec![] } else { vec![n] })
+            }
+        });
+        let collect = async {
+            let mut got = Vec::new();
+            while let Some(flow) = out_rx.recv().await {
+                got.push(flow);
+            }
+            got
+        };
+        let ((), (), (), mut got) = tokio::join!(feed, split, slow, collect);
+
+        got.sort_by_key(|(item, _)| *item);
+        assert_eq!(
+            got,
+            vec![(0, Ok(0)), (1, Ok(10)), (2, Err("bad record 2".to_string())), (3, Ok(30))]
+        );
+        assert!(peak.load(Ordering::SeqCst) > 1);
+    }
+}
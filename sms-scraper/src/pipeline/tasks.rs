@@ -1,346 +1,203 @@
-use sms_core::common::constants;
-use crate::pipeline::ingestion::envelope::{
-    ChecksumMeta, EnvelopeSubmissionV1, LegalMeta, PayloadMeta, RequestMeta, TimingMeta,
-};
-use crate::pipeline::ingestion::gateway::Gateway;
-use crate::pipeline::ingestion::idempotency::compute_idempotency_key;
-use crate::pipeline::ingestion::ingest_meta::IngestMeta;
-use crate::pipeline::ingestion::rate_limiter::{Limits, RateLimiter};
-use crate::pipeline::ingestion::registry::load_source_spec;
-use crate::pipeline::storage::Storage;
-use chrono::Utc;
-use reqwest::header::{CONTENT_LENGTH, CONTENT_TYPE, ETAG, LAST_MODIFIED};
-use serde::{Deserialize, Serialize};
+//! The ingest log's parse consumer: reads envelopes past the consumer's offset, parses their
+//! payloads from the CAS and optionally normalizes and quality-gates the records.
+
+use crate::pipeline::ingestion::source_status::PARSE_CONSUMER;
+use serde::Serialize;
 use std::path::{Path, PathBuf};
-use std::sync::Arc;
 use tracing::{info, warn};
 
-fn data_root_path_from_arg(data_root: &str) -> PathBuf {
-    let p = PathBuf::from(data_root);
-    if p.is_absolute() {
-        p
-    } else {
-        PathBuf::from(env!("CARGO_MANIFEST_DIR")).join(p)
-    }
-}
-
-#[derive(Debug, Deserialize)]
-pub struct GatewayOnceParams {
-    pub source_id: Option<String>,
-    pub data_root: Option<String>,
-    pub bypass_cadence: Option<bool>,
-}
-
-#[derive(Debug, Serialize)]
-pub struct GatewayOnceResult {
-    pub source_id: String,
-    pub envelope_id: String,
-    pub payload_bytes: usize,
-    pub ingest_log: String,
-    pub cas_root: String,
-}
-
-pub async fn gateway_once(
-    _storage: Arc<dyn Storage>,
-    params: GatewayOnceParams,
-) -> Result<GatewayOnceResult, Box<dyn std::error::Error>> {
-    let source = params
-        .source_id
-        .unwrap_or_else(|| constants::BLUE_MOON_API.to_string());
-    if params.bypass_cadence.unwrap_or(false) {
-        std::env::set_var("SMS_BYPASS_CADENCE", "1");
-    }
-    let base = std::path::Path::new(env!("CARGO_MANIFEST_DIR"));
-    let reg_path = base
-        .join("registry/sources")
-        .join(format!("{}.json", source));
-    let spec = match load_source_spec(&reg_path) {
-        Ok(spec) => {
-            crate::observability::metrics::sources::registry_load_success();
-            spec
-        }
-        Err(e) => {
-            crate::observability::metrics::sources::registry_load_error();
-            return Err(format!("Failed to load registry: {e}").into());
-        }
-    };
-    if !spec.enabled {
-        return Err("Source is disabled".into());
-    }
-    let ep = spec.endpoints.first().ok_or("No endpoint in registry")?;
-
-    // Cadence check
-    let data_root = data_root_path_from_arg(params.data_root.as_deref().unwrap_or("data"));
-    let bypass = std::env::var("SMS_BYPASS_CADENCE")
-        .map(|v| v == "1" || v.eq_ignore_ascii_case("true"))
-        .unwrap_or(false);
-    if !bypass {
-        let meta = IngestMeta::open_at_root(&data_root)?;
-        let now = Utc::now().timestamp();
-        let min_interval_secs: i64 = 12 * 60 * 60;
-        if let Some(last) = meta.get_last_fetched_at(&spec.source_id)? {
-            if now - last < min_interval_secs {
-                return Err("cadence_skip: fetched within last 12h".into());
-            }
-        }
-    }
-
-    // Rate limit and fetch
-    let rl = RateLimiter::new(Limits {
-        requests_per_min: spec.rate_limits.requests_per_min,
-        bytes_per_min: spec.rate_limits.bytes_per_min,
-        concurrency: spec.rate_limits.concurrency.map(|c| c.max(1)),
-    });
-    let client = reqwest::Client::new();
-    rl.acquire(0).await;
-    let t0 = std::time::Instant::now();
-    let resp = client.get(&ep.url).send().await?;
-    let status = resp.status().as_u16();
-    let headers = resp.headers().clone();
-    let bytes = resp.bytes().await?.to_vec();
-    rl.acquire(bytes.len() as u64).await;
-
-    let dur = t0.elapsed().as_secs_f64();
-    if (200..=299).contains(&status) {
-        crate::observability::metrics::sources::request_success();
-        crate::observability::metrics::sources::request_duration(dur);
-        crate::observability::metrics::sources::payload_bytes(bytes.len());
-    } else {
-        crate::observability::metrics::sources::request_error();
-    }
-
-    let content_type = headers
-        .get(CONTENT_TYPE)
-        .and_then(|v| v.to_str().ok())
-        .unwrap_or("application/octet-stream")
-        .to_string();
-    let content_length: u64 = headers
-        .get(CONTENT_LENGTH)
-        .and_then(|v| v.to_str().ok())
-        .and_then(|s| s.parse().ok())
-        .unwrap_or(bytes.len() as u64);
-    let etag = headers
-        .get(ETAG)
-        .and_then(|v| v.to_str().ok())
-        .map(|s| s.to_string());
-    let last_modified = headers
-        .get(LAST_MODIFIED)
-        .and_then(|v| v.to_str().ok())
-        .map(|s| s.to_string());
-
-    if content_length > spec.content.max_payload_size_bytes {
-        return Err(format!(
-            "Payload too large: {} > {}",
-            content_length, spec.content.max_payload_size_bytes
-        )
-        .into());
-    }
-    let content_type_base = content_type
-        .split(';')
-        .next()
-        .unwrap_or("")
-        .trim()
-        .to_string();
-    if !spec
-        .content
-        .allowed_mime_types
-        .iter()
-        .any(|m| m == &content_type_base)
-    {
-        return Err(format!(
-            "MIME '{}' not in allow-list {:?}",
-            content_type, spec.content.allowed_mime_types
-        )
-        .into());
-    }
-
-    let sha_hex = {
-        use sha2::{Digest, Sha256};
-        let mut h = Sha256::new();
-        h.update(&bytes);
-        hex::encode(h.finalize())
-    };
-    let idk = compute_idempotency_key(
-        &spec.source_id,
-        &ep.url,
-        etag.as_deref(),
-        last_modified.as_deref(),
-        &sha_hex,
-    );
-
-    let env = EnvelopeSubmissionV1 {
-        envelope_version: "1.0.0".to_string(),
-        source_id: spec.source_id.clone(),
-        idempotency_key: idk,
-        payload_meta: PayloadMeta {
-            mime_type: content_type,
-            size_bytes: content_length,
-            checksum: ChecksumMeta { sha256: sha_hex },
-        },
-        request: RequestMeta {
-            url: ep.url.clone(),
-            method: ep.method.clone(),
-            status: Some(status),
-            etag,
-            last_modified,
-        },
-        timing: TimingMeta {
-            fetched_at: Utc::now(),
-            gateway_received_at: None,
-        },
-        legal: LegalMeta {
-            license_id: spec.policy.license_id.clone(),
-        },
-    };
-
-    let gw = Gateway::new(data_root.clone());
-    let stamped = gw.accept(env, &bytes)?;
-    let _ = IngestMeta::open_at_root(&data_root)?
-        .set_last_fetched_at(&stamped.envelope.source_id, Utc::now().timestamp());
-
-    Ok(GatewayOnceResult {
-        source_id: spec.source_id,
-        envelope_id: stamped.envelope_id,
-        payload_bytes: bytes.len(),
-        ingest_log: data_root
-            .join("ingest_log/ingest.ndjson")
-            .to_string_lossy()
-            .to_string(),
-        cas_root: data_root.join("cas").to_string_lossy().to_string(),
-    })
-}
-
-#[derive(Debug, Deserialize)]
+/// Options for one `parse log` run
+#[derive(Debug, Default)]
 pub struct ParseParams {
+    /// Consumer whose offset the run reads from and acks; `parser` by default
     pub consumer: Option<String>,
+    /// Envelopes read at most; 50 by default
     pub max: Option<usize>,
-    pub data_root: Option<String>,
+    pub data_root: Option<PathBuf>,
     pub output: Option<String>,
     pub source_id: Option<String>,
     pub normalize: Option<bool>,
     pub quality_gate: Option<bool>,
+    /// TOML file whose `[sinks]` table routes stage outputs; NDJSON files when unset
+    pub sinks_config: Option<PathBuf>,
 }
 
 #[derive(Debug, Serialize)]
 pub struct ParseResultSummary {
+    pub run_id: String,
     pub seen: usize,
     pub filtered_out: usize,
     pub empty_record_envelopes: usize,
     pub written_records: usize,
     pub output_file: String,
+    /// Last envelope the consumer's offset was advanced past
+    pub acked_through: Option<String>,
 }
 
-pub async fn parse_run(
-    _storage: Arc<dyn Storage>,
+pub async fn parse_run(params: ParseParams) -> anyhow::Result<ParseResultSummary> {
+    use crate::observability::RunTracker;
+    use tracing::Instrument;
+
+    let tracker = RunTracker::open("parse", params.source_id.as_deref());
+    let result = parse_batch(params, &tracker).instrument(tracker.span()).await;
+    tracker.close(result)
+}
+
+async fn parse_batch(
     params: ParseParams,
-) -> Result<ParseResultSummary, Box<dyn std::error::Error>> {
+    tracker: &crate::observability::RunTracker,
+) -> anyhow::Result<ParseResultSummary> {
     use crate::pipeline::ingestion::ingest_log_reader::IngestLogReader;
+    use crate::pipeline::ingestion::envelope::read_log_line;
     use crate::app::parse_use_case::ParseUseCase;
     use crate::infra::{payload_store::CasPayloadStore, registry_adapter::JsonRegistry, parser_factory::DefaultParserFactory};
 
-    let consumer = params.consumer.unwrap_or_else(|| "parser".to_string());
+    let consumer = params.consumer.unwrap_or_else(|| PARSE_CONSUMER.to_string());
     let max = params.max.unwrap_or(50);
-    let data_root_s = params.data_root.unwrap_or_else(|| "data".to_string());
+    let data_root = params.data_root.unwrap_or_else(|| PathBuf::from("data"));
     let output = params.output.unwrap_or_else(|| "parsed.ndjson".to_string());
 
     // Validation: quality gate requires normalization to be enabled
     if params.quality_gate.unwrap_or(false) && !params.normalize.unwrap_or(false) {
-        return Err("Quality gate requires normalization to be enabled. Use both --normalize and --quality-gate flags.".into());
+        anyhow::bail!("Quality gate requires normalization to be enabled. Use both --normalize and --quality-gate flags.");
     }
 
-    let reader = IngestLogReader::new(data_root_path_from_arg(&data_root_s));
+    let reader = IngestLogReader::new(&data_root);
     let (lines, _last) = reader.read_next(&consumer, max)?;
     info!("parser: read {} log lines from ingest log", lines.len());
     crate::observability::metrics::parser::batch_size(lines.len());
     if lines.is_empty() {
-        return Ok(ParseResultSummary { seen: 0, filtered_out: 0, empty_record_envelopes: 0, written_records: 0, output_file: "".to_string() });
+        return Ok(ParseResultSummary { run_id: tracker.run_id().to_string(), seen: 0, filtered_out: 0, empty_record_envelopes: 0, written_records: 0, output_file: "".to_string(), acked_through: None });
     }
 
     let ts = chrono::Utc::now().format("%Y%m%d_%H%M%S");
     let base_out = Path::new(&output);
-    let output_dir = Path::new("output");
+    // A bare file name lands under ./output; a path keeps its own directory
+    let output_dir = base_out.parent().filter(|dir| !dir.as_os_str().is_empty()).unwrap_or(Path::new("output"));
     std::fs::create_dir_all(output_dir)?;
     let file = base_out.file_name().unwrap_or_else(|| std::ffi::OsStr::new("parsed.ndjson"));
     let prefixed_path = output_dir.join(format!("{}_{}", ts, file.to_string_lossy()));
     let mut out = std::fs::OpenOptions::new().create(true).write(true).truncate(true).open(&prefixed_path)?;
 
     // Wire ports and use-cases
-    let parse_uc = ParseUseCase::new(Box::new(JsonRegistry), Box::new(CasPayloadStore), Box::new(DefaultParserFactory));
+    let mut parse_uc = ParseUseCase::new(Box::new(JsonRegistry), Box::new(CasPayloadStore), Box::new(DefaultParserFactory))
+        .with_dead_letters(Box::new(crate::infra::dead_letter_store::FileDeadLetterStore::new(&data_root)));
+    // Experimental: only with SMS_LLM_FALLBACK=1 and an endpoint configured
+    if let Some(llm) = crate::infra::llm_parser::CompletionLlmParser::from_env() {
+        info!("parser: LLM fallback enabled for envelopes that parse to no records");
+        parse_uc = parse_uc.with_llm_fallback(Box::new(llm), crate::app::parse_use_case::LlmBudget::from_env());
+    }
+    let sinks = crate::infra::sink_registry::SinkRegistry::load(
+        params.sinks_config.as_deref(),
+        output_dir,
+        &data_root,
+    )?;
     
     // Optionally create normalize use case if normalization is enabled
     let normalize_uc = if params.normalize.unwrap_or(false) {
         use crate::app::normalize_use_case::NormalizeUseCase;
-        use crate::infra::normalize_output_adapter::FileNormalizeOutputAdapter;
         
         let normalized_output = output.replace(".ndjson", "_normalized.ndjson");
-        let normalized_path = output_dir.join(format!("{}_{}", ts, Path::new(&normalized_output).file_name().unwrap_or_else(|| std::ffi::OsStr::new("normalized.ndjson")).to_string_lossy()));
-        let normalize_adapter = FileNormalizeOutputAdapter::new(&normalized_path.to_string_lossy())?;
+        let normalized_file = format!("{}_{}", ts, Path::new(&normalized_output).file_name().unwrap_or_else(|| std::ffi::OsStr::new("normalized.ndjson")).to_string_lossy());
+        let normalize_output = sinks.normalize_output(&normalized_file)?;
         
-        Some((NormalizeUseCase::new(Box::new(normalize_adapter)), normalized_path))
+        Some((NormalizeUseCase::new(Box::new(normalize_output)), output_dir.join(normalized_file)))
     } else {
         None
     };
     
-    // Quality gate use case temporarily disabled (use-case layer removed)
-let _quality_gate_uc: Option<()> = None;
+    // Optionally assess normalized records; outcomes land in <data_root>/quality/outcomes.db for qualityStats
+    let quality_gate_uc = if params.quality_gate.unwrap_or(false) {
+        use crate::app::quality_gate_use_case::QualityGateUseCase;
+        use crate::pipeline::processing::quality_gate::outcomes::QualityOutcomeStore;
+
+        let store = QualityOutcomeStore::open_at_root(&data_root)?;
+        let (accepted, quarantined) = sinks.quality_gate_outputs()?;
+        Some(
+            QualityGateUseCase::with_default_quality_gate(Box::new(accepted), Box::new(quarantined))
+                .with_outcome_store(store),
+        )
+    } else {
+        None
+    };
     
     use crate::app::ports::RegistryPort;
+    use crate::pipeline::ingestion::envelope_state::{EnvelopeState, EnvelopeStates};
+    let states = EnvelopeStates::new(&data_root);
 
     let mut total_seen = 0usize;
     let mut total_filtered = 0usize;
     let mut total_written = 0usize;
     let mut total_empty_records = 0usize;
+    // The offset only moves forward past envelopes this run handled; the first one left for
+    // another run (filtered by source) holds it for the rest of the batch
+    let mut acked_through: Option<String> = None;
+    let mut holding = false;
 
     for line in lines {
         total_seen += 1;
-        let val: serde_json::Value = match serde_json::from_str(&line) { Ok(v) => v, Err(e) => { warn!("parser: skipping invalid JSON line: {}", e); continue; } };
-        let mut payload_ref_s = val.get("payload_ref").and_then(|v| v.as_str()).or_else(|| val.get("envelope").and_then(|e| e.get("payload_ref")).and_then(|v| v.as_str())).unwrap_or("").to_string();
-        let envelope_id = val.get("envelope_id").and_then(|v| v.as_str()).unwrap_or("").to_string();
-        let src_id = val.get("envelope").and_then(|e| e.get("source_id")).and_then(|v| v.as_str()).unwrap_or("").to_string();
+        let envelope = match read_log_line(&line) { Ok(envelope) => envelope, Err(e) => { warn!("parser: skipping unreadable ingest log line: {}", e); continue; } };
+        let mut payload_refs = envelope.payload_refs();
+        let envelope_id = envelope.envelope_id.clone();
+        let src_id = envelope.envelope.source_id.clone();
+        let endpoint_id = envelope.envelope.request.endpoint_id.clone();
 
-        if payload_ref_s.is_empty() {
-            if let Some(dedupe_of) = val.get("dedupe_of").and_then(|v| v.as_str()) {
-                if let Ok(Some(orig_line)) = reader.find_envelope_by_id(dedupe_of) {
-                    if let Ok(orig_val) = serde_json::from_str::<serde_json::Value>(&orig_line) {
-                        if let Some(pr) = orig_val.get("payload_ref").and_then(|v| v.as_str()).or_else(|| orig_val.get("envelope").and_then(|e| e.get("payload_ref")).and_then(|v| v.as_str())) {
-                            info!("parser: resolved dedupe envelope_id={} to original {} with payload_ref present", envelope_id, dedupe_of);
-                            payload_ref_s = pr.to_string();
-                        } else {
+        if payload_refs.is_empty() {
+            if let Some(dedupe_of) = &envelope.dedupe_of {
+                match reader.find_envelope(dedupe_of) {
+                    Ok(Some(original)) => {
+                        payload_refs = original.payload_refs();
+                        if payload_refs.is_empty() {
                             warn!("parser: original dedupe_of={} has no payload_ref", dedupe_of);
+                        } else {
+                            info!("parser: resolved dedupe envelope_id={} to original {} with payload_ref present", envelope_id, dedupe_of);
                         }
                     }
-                } else {
-                    warn!("parser: could not resolve dedupe_of={} for envelope_id={}", dedupe_of, envelope_id);
+                    Ok(None) => warn!("parser: could not resolve dedupe_of={} for envelope_id={}", dedupe_of, envelope_id),
+                    Err(e) => warn!("parser: unreadable original dedupe_of={} for envelope_id={}: {}", dedupe_of, envelope_id, e),
                 }
             }
         }
-        if payload_ref_s.is_empty() {
-            if let Some(sha) = val.get("envelope").and_then(|e| e.get("payload_meta")).and_then(|pm| pm.get("checksum")).and_then(|c| c.get("sha256")).and_then(|s| s.as_str()) {
-                payload_ref_s = format!("cas:sha256:{}", sha);
+        if payload_refs.is_empty() {
+            let sha = &envelope.envelope.payload_meta.checksum.sha256;
+            if !sha.is_empty() {
+                payload_refs.push(format!("cas:sha256:{}", sha));
                 info!("parser: synthesized payload_ref from checksum for envelope_id={}", envelope_id);
             }
         }
-        if let Some(filter) = &params.source_id { if src_id != *filter { total_filtered += 1; continue; } }
-        if payload_ref_s.is_empty() || src_id.is_empty() { warn!("parser: skipping envelope with missing fields: envelope_id='{}' src_id='{}' payload_ref_present={}", envelope_id, src_id, !payload_ref_s.is_empty()); continue; }
+        if let Some(filter) = &params.source_id { if src_id != *filter { total_filtered += 1; holding = true; continue; } }
+        if payload_refs.is_empty() || src_id.is_empty() {
+            warn!("parser: skipping envelope with missing fields: envelope_id='{}' src_id='{}' payload_ref_present={}", envelope_id, src_id, !payload_refs.is_empty());
+            ack(&reader, &consumer, &envelope_id, holding, &mut acked_through);
+            continue;
+        }
 
-        // Use use-case to resolve and parse
+        // Use use-case to resolve and parse; multi-part (paginated) envelopes are parsed part by part in order
         let reg = crate::infra::registry_adapter::JsonRegistry;
         let plan = reg.load_parse_plan(&src_id).await.unwrap_or_else(|_| "parse_plan:wix_calendar_v1".to_string());
-        info!("parser: parsing envelope_id={} src_id={} plan={} payload_ref={} ", envelope_id, src_id, plan, payload_ref_s);
-        let rec_lines = match parse_uc.parse_one(&src_id, &envelope_id, &payload_ref_s).await {
-            Ok(lines) => {
-                crate::observability::metrics::parser::parse_success();
-                lines
-            },
-            Err(e) => { 
-                warn!("parser: parse_failed envelope_id={} err={}", envelope_id, e); 
-                crate::observability::metrics::parser::parse_error();
-                total_empty_records += 1; 
-                Vec::new() 
+        let mut rec_lines = Vec::new();
+        let mut parse_errors = Vec::new();
+        for (part, payload_ref_s) in payload_refs.iter().enumerate() {
+            info!("parser: parsing envelope_id={} part={}/{} src_id={} plan={} payload_ref={} ", envelope_id, part + 1, payload_refs.len(), src_id, plan, payload_ref_s);
+            match tracker.stage("parse", parse_uc.parse_endpoint(&src_id, &envelope_id, endpoint_id.as_deref(), payload_ref_s)).await {
+                Ok(lines) => {
+                    crate::observability::metrics::parser::parse_success();
+                    rec_lines.extend(lines);
+                },
+                Err(e) => {
+                    warn!("parser: parse_failed envelope_id={} part={} err={}", envelope_id, part + 1, e);
+                    crate::observability::metrics::parser::parse_error();
+                    parse_errors.push(format!("part {}: {}", part + 1, e));
+                }
             }
-        };
+        }
+        if parse_errors.len() == payload_refs.len() {
+            states.record(&envelope_id, &src_id, EnvelopeState::Failed, Some(&parse_errors.join("; ")));
+        } else {
+            states.record(&envelope_id, &src_id, EnvelopeState::Parsed, None);
+        }
         if rec_lines.is_empty() { total_empty_records += 1; }
-        
+
         // Write parsed records to output
         for line in rec_lines.iter() { use std::io::Write; writeln!(out, "{}", line)?; }
         total_written += rec_lines.len();
@@ -352,10 +209,10 @@ let _quality_gate_uc: Option<()> = None;
         if let Some((ref norm_uc, _)) = normalize_uc {
             if !rec_lines.is_empty() {
                 // Convert JSON lines to ParsedRecords for normalization
-                let parsed_records: Vec<crate::pipeline::processing::parser::ParsedRecord> = rec_lines
+                let parsed_records: Vec<sms_parsers::ParsedRecord> = rec_lines
                     .iter()
                     .filter_map(|line| {
-                        match serde_json::from_str::<crate::pipeline::processing::parser::ParsedRecord>(line) {
+                        match serde_json::from_str::<sms_parsers::ParsedRecord>(line) {
                             Ok(record) => Some(record),
                             Err(e) => {
                                 warn!("normalize: failed to deserialize parsed record: {}", e);
@@ -367,30 +224,131 @@ let _quality_gate_uc: Option<()> = None;
                 
                 if !parsed_records.is_empty() {
                     info!("normalize: processing {} parsed records from envelope_id={}", parsed_records.len(), envelope_id);
-                    match norm_uc.normalize_batch(&parsed_records).await {
+                    match tracker.stage("normalize", norm_uc.normalize_batch(&parsed_records)).await {
                         Ok(batch_normalized_records) => {
                             info!("normalize: successfully normalized batch from envelope_id={}", envelope_id);
                             normalized_records.extend(batch_normalized_records);
+                            states.record(&envelope_id, &src_id, EnvelopeState::Normalized, None);
                         },
                         Err(e) => {
                             warn!("normalize: failed to normalize batch from envelope_id={}: {}", envelope_id, e);
+                            states.record(&envelope_id, &src_id, EnvelopeState::Failed, Some(&format!("normalize: {}", e)));
                         }
                     }
                 }
             }
         }
         
-        // Quality gate assessment skipped (use-case layer removed)
-        let _ = &normalized_records;
+        if let Some(ref qg_uc) = quality_gate_uc {
+            if !normalized_records.is_empty() {
+                match tracker.stage("quality_gate", qg_uc.assess_batch(&normalized_records)).await {
+                    Ok(assessed) => {
+                        use crate::pipeline::processing::quality_gate::QualityDecision;
+                        if assessed.iter().all(|r| r.quality_assessment.decision == QualityDecision::Quarantine) {
+                            let detail = format!("quality gate quarantined all {} records", assessed.len());
+                            states.record(&envelope_id, &src_id, EnvelopeState::Quarantined, Some(&detail));
+                        }
+                    }
+                    Err(e) => warn!("quality_gate: failed to assess batch from envelope_id={}: {}", envelope_id, e),
+                }
+            }
+        }
         
         // Record gateway metrics for records ingested from this envelope
         if !rec_lines.is_empty() {
             crate::observability::metrics::gateway::records_ingested(rec_lines.len() as u64);
         }
+        ack(&reader, &consumer, &envelope_id, holding, &mut acked_through);
     }
 
     // Record final parsing metrics
     crate::observability::metrics::parser::records_extracted(total_written as u64);
+    if let Ok(meta) = crate::pipeline::ingestion::ingest_meta::IngestMeta::open_at_root(&data_root) {
+        use crate::pipeline::ingestion::envelope_state::{stuck_after_secs, stuck_envelopes};
+        match stuck_envelopes(&meta, stuck_after_secs(), chrono::Utc::now().timestamp()) {
+            Ok(stuck) if !stuck.is_empty() => warn!("envelope_state: {} envelopes stuck longer than {}s", stuck.len(), stuck_after_secs()),
+            Ok(_) => {}
+            Err(e) => warn!("envelope_state: stuck check failed: {}", e),
+        }
+    }
+
+    Ok(ParseResultSummary { run_id: tracker.run_id().to_string(), seen: total_seen, filtered_out: total_filtered, empty_record_envelopes: total_empty_records, written_records: total_written, output_file: prefixed_path.to_string_lossy().to_string(), acked_through })
+}
 
-    Ok(ParseResultSummary { seen: total_seen, filtered_out: total_filtered, empty_record_envelopes: total_empty_records, written_records: total_written, output_file: prefixed_path.to_string_lossy().to_string() })
+/// Advance the consumer past `envelope_id` unless an earlier envelope in the batch was left unhandled
+fn ack(
+    reader: &crate::pipeline::ingestion::ingest_log_reader::IngestLogReader,
+    consumer: &str,
+    envelope_id: &str,
+    holding: bool,
+    acked_through: &mut Option<String>,
+) {
+    if holding {
+        return;
+    }
+    match reader.ack_through(consumer, envelope_id) {
+        Ok(_) => *acked_through = Some(envelope_id.to_string()),
+        Err(e) => warn!("parser: failed to ack envelope_id={} for consumer={}: {}", envelope_id, consumer, e),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pipeline::ingestion::envelope::{ChecksumMeta, EnvelopeSubmissionV1, LegalMeta, PayloadMeta, RequestMeta, TimingMeta};
+    use crate::pipeline::ingestion::gateway::Gateway;
+    use crate::pipeline::ingestion::ingest_log_reader::IngestLogReader;
+
+    fn submission(source_id: &str, key: &str) -> EnvelopeSubmissionV1 {
+        EnvelopeSubmissionV1 {
+            envelope_version: "1.0.0".to_string(),
+            source_id: source_id.to_string(),
+            idempotency_key: key.to_string(),
+            payload_meta: PayloadMeta {
+                mime_type: "text/html".to_string(),
+                size_bytes: 10,
+                checksum: ChecksumMeta { sha256: String::new() },
+            },
+            request: RequestMeta {
+                url: format!("https://example.com/{}", key),
+                method: "GET".to_string(),
+                status: Some(200),
+                etag: None,
+                last_modified: None,
+                endpoint_id: None,
+            },
+            timing: TimingMeta { fetched_at: chrono::Utc::now(), gateway_received_at: None },
+            legal: LegalMeta { license_id: "test".to_string() },
+        }
+    }
+
+    fn params(root: &Path, source_id: Option<&str>) -> ParseParams {
+        ParseParams {
+            data_root: Some(root.to_path_buf()),
+            output: Some(root.join("parsed.ndjson").to_string_lossy().into_owned()),
+            source_id: source_id.map(str::to_string),
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn parse_log_acks_the_envelopes_it_handles() {
+        let tmp = tempfile::tempdir().unwrap();
+        let gw = Gateway::new(tmp.path());
+        gw.accept(submission("neumos", "a"), b"<html>a</html>").unwrap();
+        let held = gw.accept(submission("kexp", "b"), b"<html>b</html>").unwrap();
+        gw.accept(submission("neumos", "c"), b"<html>c</html>").unwrap();
+        let reader = IngestLogReader::new(tmp.path());
+
+        // Another source's envelope holds the offset so a later run still sees it
+        let summary = parse_run(params(tmp.path(), Some("neumos"))).await.unwrap();
+        assert_eq!(summary.filtered_out, 1);
+        assert_ne!(summary.acked_through.as_deref(), Some(held.envelope_id.as_str()));
+        assert_eq!(reader.pending_by_source(PARSE_CONSUMER).unwrap().get("kexp"), Some(&1));
+
+        let summary = parse_run(params(tmp.path(), None)).await.unwrap();
+        assert_eq!(summary.filtered_out, 0);
+        assert!(reader.pending_by_source(PARSE_CONSUMER).unwrap().is_empty());
+        assert_eq!(reader.status(PARSE_CONSUMER).unwrap().2, 0);
+    }
 }
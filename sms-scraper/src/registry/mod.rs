@@ -1,4 +1,6 @@
 pub mod source_loader;
+#[cfg(feature = "scraping")]
 pub mod unified_registry;
 
+#[cfg(feature = "scraping")]
 pub use unified_registry::UnifiedSourceRegistry;
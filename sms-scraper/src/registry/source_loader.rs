@@ -3,6 +3,8 @@ use std::collections::HashMap;
 use std::fs;
 use std::path::Path;
 use sms_core::common::error::{Result, ScraperError};
+use sms_core::domain::Attribution;
+use crate::pipeline::ingestion::registry::{DatesSpec, PolicySpec};
 
 #[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct SourceEndpoint {
@@ -19,6 +21,10 @@ pub struct SourceConfig {
     pub parse_plan_ref: Option<String>,
     // New pipeline configuration
     pub pipeline: Option<PipelineConfig>,
+    #[serde(default)]
+    pub policy: Option<PolicySpec>,
+    #[serde(default)]
+    pub dates: Option<DatesSpec>,
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone)]
@@ -72,8 +78,18 @@ impl SourceRegistry {
         Ok(Self { sources })
     }
 
+    /// Build a registry from already-parsed source configs
+    pub fn from_configs(configs: impl IntoIterator<Item = SourceConfig>) -> Self {
+        Self { sources: configs.into_iter().map(|c| (c.source_id.clone(), c)).collect() }
+    }
+
     /// Get the primary URL for a source
     pub fn get_source_url(&self, source_id: &str) -> Result<String> {
+        Ok(self.get_source_urls(source_id)?.swap_remove(0))
+    }
+
+    /// Every endpoint URL of a source, in registry order
+    pub fn get_source_urls(&self, source_id: &str) -> Result<Vec<String>> {
         let source = self.sources.get(source_id).ok_or_else(|| ScraperError::Api {
             message: format!("Source not found in registry: {}", source_id),
         })?;
@@ -84,16 +100,31 @@ impl SourceRegistry {
             });
         }
 
-        let endpoint = source.endpoints.first().ok_or_else(|| ScraperError::Api {
-            message: format!("No endpoints found for source: {}", source_id),
-        })?;
+        if source.endpoints.is_empty() {
+            return Err(ScraperError::Api {
+                message: format!("No endpoints found for source: {}", source_id),
+            });
+        }
+
+        Ok(source.endpoints.iter().map(|endpoint| endpoint.url.clone()).collect())
+    }
 
-        Ok(endpoint.url.clone())
+    /// How the source's crawler parser reads days listed without a year; the defaults for a
+    /// source that declares none, or declares ones that don't parse
+    #[cfg(feature = "scraping")]
+    pub fn date_hints(&self, source_id: &str) -> sms_parsers::DateHints {
+        let Some(dates) = self.sources.get(source_id).and_then(|source| source.dates.as_ref()) else {
+            return sms_parsers::DateHints::default();
+        };
+        dates.hints().unwrap_or_else(|e| {
+            tracing::warn!("Ignoring date hints of {}: {}", source_id, e);
+            sms_parsers::DateHints::default()
+        })
     }
 
     /// Check if a source is enabled
     pub fn is_source_enabled(&self, source_id: &str) -> bool {
-        self.sources.get(source_id).map_or(false, |s| s.enabled)
+        self.sources.get(source_id).is_some_and(|s| s.enabled)
     }
 
     /// Get all enabled source IDs
@@ -109,4 +140,12 @@ impl SourceRegistry {
     pub fn get_source_config(&self, source_id: &str) -> Option<&SourceConfig> {
         self.sources.get(source_id)
     }
+
+    /// License and attribution to stamp on entities cataloged from a source
+    pub fn get_attribution(&self, source_id: &str) -> Option<Attribution> {
+        self.sources
+            .get(source_id)
+            .and_then(|s| s.policy.as_ref())
+            .map(|p| p.to_attribution(source_id))
+    }
 }
@@ -4,7 +4,8 @@ use super::source_loader::{SourceConfig, SourceRegistry};
 use crate::app::ports::{ParserFactory, ParserPort};
 use crate::infra::parser_factory::DefaultParserFactory;
 use crate::pipeline::processing::normalize::registry::NormalizationRegistry;
-use crate::pipeline::processing::normalize::normalizers::SourceNormalizer;
+use crate::pipeline::processing::normalize::NormalizedRecord;
+use sms_parsers::ParsedRecord;
 
 /// Unified registry that connects sources to their parsers and normalizers
 pub struct UnifiedSourceRegistry {
@@ -55,19 +56,19 @@ impl UnifiedSourceRegistry {
         Err(anyhow::anyhow!("No parser configuration found for source: {}", source_id))
     }
     
-    /// Get normalizer for a source using either new pipeline config or fallback to source_id
-    pub fn get_normalizer_for_source(&self, source_id: &str) -> Result<&dyn SourceNormalizer> {
+    /// Normalize one of a source's records through the normalization registry, so its shadow
+    /// normalizer and description cleanup run as in the gateway pipeline. The normalizer is
+    /// the one the source's pipeline config names, or the source's own.
+    pub fn normalize(&self, source_id: &str, record: &ParsedRecord) -> Result<Vec<NormalizedRecord>> {
         let source_config = self.get_source_config(source_id)?;
-        
-        // Try new pipeline config first
-        if let Some(pipeline) = &source_config.pipeline {
-            return self.normalizer_registry.get_normalizer(&pipeline.normalizer_id)
-                .ok_or_else(|| anyhow::anyhow!("Normalizer not found: {}", pipeline.normalizer_id));
-        }
-        
-        // Fallback to using source_id as normalizer_id (current behavior)
-        self.normalizer_registry.get_normalizer(source_id)
-            .ok_or_else(|| anyhow::anyhow!("Normalizer not found for source: {}", source_id))
+        let normalizer_id = source_config.pipeline.as_ref().map_or(source_id, |pipeline| pipeline.normalizer_id.as_str());
+        self.normalizer_registry.normalize_with(normalizer_id, record)
+    }
+
+    /// Normalize with `registry` instead of the default one
+    pub fn with_normalizer_registry(mut self, registry: NormalizationRegistry) -> Self {
+        self.normalizer_registry = registry;
+        self
     }
     
     /// Check if a source is enabled
@@ -87,3 +88,53 @@ impl UnifiedSourceRegistry {
     }
 }
 
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pipeline::processing::normalize::normalizers::SourceNormalizer;
+    use crate::pipeline::processing::normalize::shadow::ShadowLog;
+    use std::sync::Arc;
+
+    struct Failing;
+
+    impl SourceNormalizer for Failing {
+        fn normalize(&self, _record: &ParsedRecord) -> Result<Vec<NormalizedRecord>> {
+            Err(anyhow::anyhow!("candidate failed"))
+        }
+
+        fn source_id(&self) -> &str {
+            "neumos"
+        }
+
+        fn name(&self) -> &str {
+            "failing"
+        }
+    }
+
+    #[test]
+    fn normalizing_a_source_runs_its_shadow() {
+        let tmp = tempfile::tempdir().unwrap();
+        let log = Arc::new(ShadowLog::new(tmp.path().join("shadow.ndjson")));
+        let sources = concat!(env!("CARGO_MANIFEST_DIR"), "/../registry/sources");
+        let registry = UnifiedSourceRegistry::new(sources)
+            .unwrap()
+            .with_normalizer_registry(NormalizationRegistry::new().with_shadow("neumos", Box::new(Failing), log.clone()));
+        let record = ParsedRecord {
+            source_id: "neumos".to_string(),
+            envelope_id: "env-1".to_string(),
+            payload_ref: "sha256:abc".to_string(),
+            record_path: "$.events[0]".to_string(),
+            record: serde_json::json!({ "title": "Headliner", "date_text": "Nov 2" }),
+            attribution: None,
+            change: None,
+            endpoint_id: None,
+            external_id: None,
+        };
+
+        registry.normalize("neumos", &record).unwrap();
+        let logged = std::fs::read_to_string(log.path()).unwrap();
+        assert!(logged.contains("\"shadow_error\":\"candidate failed\""));
+        assert!(registry.normalize("no_such_source", &record).is_err());
+    }
+}
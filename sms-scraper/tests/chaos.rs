@@ -0,0 +1,194 @@
+//! Resilience checks for chaos mode: with faults forced on, each stage fails cleanly with a
+//! `chaos.*` error code, leaves nothing half-written, counts the fault, and works again once
+//! the faults stop. Run with `cargo test -p sms-scraper --features chaos --test chaos`.
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::Utc;
+use sms_core::domain::{ProcessRun, Venue};
+use sms_core::storage::{InMemoryStorage, Storage, WriteBatch};
+use sms_scraper::app::ingest_use_case::IngestUseCase;
+use sms_scraper::app::ports::{CadencePort, RateLimiterPort};
+use sms_scraper::infra::fault_injection::{injector, Fault, FaultyStorage};
+use sms_scraper::infra::gateway_adapter::GatewayAdapter;
+use sms_scraper::infra::http_client::ReqwestHttp;
+use sms_scraper::observability::metrics;
+use sms_scraper::pipeline::ingestion::envelope::{
+    ChecksumMeta, EnvelopeSubmissionV1, LegalMeta, PayloadMeta, RequestMeta, TimingMeta,
+};
+use sms_scraper::pipeline::ingestion::gateway::Gateway;
+use sms_scraper::pipeline::processing::catalog::Catalogger;
+
+/// The injector is process-wide, so scenarios take turns and start with every fault off
+async fn chaos() -> tokio::sync::MutexGuard<'static, ()> {
+    static SERIAL: tokio::sync::Mutex<()> = tokio::sync::Mutex::const_new(());
+    let guard = SERIAL.lock().await;
+    let _ = metrics::init();
+    for fault in Fault::ALL {
+        injector().set(fault, 0.0);
+    }
+    guard
+}
+
+/// Value of `sms_chaos_faults_injected_total` for `fault` in the Prometheus render
+fn faults_injected(fault: Fault) -> u64 {
+    let needle = format!("sms_chaos_faults_injected_total{{fault=\"{}\"}} ", fault.as_str());
+    metrics::render()
+        .unwrap_or_default()
+        .lines()
+        .find_map(|line| line.strip_prefix(needle.as_str()).and_then(|n| n.trim().parse().ok()))
+        .unwrap_or(0)
+}
+
+fn submission(idempotency_key: &str) -> EnvelopeSubmissionV1 {
+    EnvelopeSubmissionV1 {
+        envelope_version: "1.0.0".to_string(),
+        source_id: "chaos".to_string(),
+        idempotency_key: idempotency_key.to_string(),
+        payload_meta: PayloadMeta {
+            mime_type: "text/html".to_string(),
+            size_bytes: 7,
+            checksum: ChecksumMeta { sha256: String::new() },
+        },
+        request: RequestMeta {
+            url: "https://example.com/events".to_string(),
+            method: "GET".to_string(),
+            status: Some(200),
+            etag: None,
+            last_modified: None,
+            endpoint_id: None,
+        },
+        timing: TimingMeta { fetched_at: Utc::now(), gateway_received_at: None },
+        legal: LegalMeta { license_id: "test".to_string() },
+    }
+}
+
+struct NoLimit;
+
+#[async_trait]
+impl RateLimiterPort for NoLimit {
+    async fn acquire(&self, _bytes: u64) {}
+}
+
+struct AlwaysDue;
+
+#[async_trait]
+impl CadencePort for AlwaysDue {
+    async fn should_run(&self, _source_id: &str, _min_interval_secs: i64) -> Result<bool, String> {
+        Ok(true)
+    }
+    async fn mark_run(&self, _source_id: &str) -> Result<(), String> {
+        Ok(())
+    }
+}
+
+/// Answers every connection with a small HTML page
+async fn serve_page(listener: tokio::net::TcpListener) {
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    loop {
+        let Ok((mut socket, _)) = listener.accept().await else { return };
+        let mut buf = vec![0u8; 4096];
+        let _ = socket.read(&mut buf).await;
+        let _ = socket
+            .write_all(b"HTTP/1.1 200 OK\r\nContent-Type: text/html\r\nContent-Length: 7\r\nConnection: close\r\n\r\n<p></p>")
+            .await;
+    }
+}
+
+#[tokio::test]
+async fn http_timeouts_fail_the_ingest_before_anything_is_stored() {
+    let _chaos = chaos().await;
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}/events", listener.local_addr().unwrap());
+    tokio::spawn(serve_page(listener));
+    let tmp = tempfile::tempdir().unwrap();
+    let ingest = IngestUseCase::new(
+        Box::new(NoLimit),
+        Box::new(AlwaysDue),
+        Box::new(ReqwestHttp::default()),
+        Box::new(GatewayAdapter { root: tmp.path().to_path_buf() }),
+    );
+    let allowed = vec!["text/html".to_string()];
+
+    injector().set(Fault::HttpTimeout, 1.0);
+    let before = faults_injected(Fault::HttpTimeout);
+    let err = ingest.ingest_once("chaos", &url, "GET", 1024, &allowed, "test").await.unwrap_err();
+    assert!(err.starts_with("chaos.http_timeout"), "{}", err);
+    assert_eq!(faults_injected(Fault::HttpTimeout), before + 1);
+    assert!(!tmp.path().join("cas").exists());
+
+    injector().set(Fault::HttpTimeout, 0.0);
+    let (_, payload_ref, size) = ingest.ingest_once("chaos", &url, "GET", 1024, &allowed, "test").await.unwrap();
+    assert!(payload_ref.starts_with("cas:sha256:"));
+    assert_eq!(size, 7);
+}
+
+#[tokio::test]
+async fn cas_write_failures_leave_no_log_entry_and_the_envelope_can_be_retried() {
+    let _chaos = chaos().await;
+    let tmp = tempfile::tempdir().unwrap();
+    let gateway = Gateway::new(tmp.path());
+    let log = tmp.path().join("ingest_log/ingest.ndjson");
+
+    injector().set(Fault::CasWrite, 1.0);
+    let before = faults_injected(Fault::CasWrite);
+    let err = gateway.accept(submission("chaos:cas"), b"<p></p>").unwrap_err();
+    assert!(err.to_string().starts_with("chaos.cas_write"), "{}", err);
+    assert_eq!(faults_injected(Fault::CasWrite), before + 1);
+    assert!(metrics::render().unwrap_or_default().contains("sms_gateway_cas_writes_error_total"));
+    assert!(std::fs::read_to_string(&log).unwrap_or_default().is_empty());
+
+    // The failed attempt didn't claim the idempotency key, so the retry stores the payload
+    injector().set(Fault::CasWrite, 0.0);
+    let stamped = gateway.accept(submission("chaos:cas"), b"<p></p>").unwrap();
+    assert!(stamped.dedupe_of.is_none());
+    assert!(stamped.payload_ref.starts_with("cas:sha256:"));
+    assert_eq!(std::fs::read_to_string(&log).unwrap().lines().count(), 1);
+}
+
+#[tokio::test]
+async fn database_errors_fail_whole_batches_and_catalog_runs_cleanly() {
+    let _chaos = chaos().await;
+    let inner: Arc<dyn Storage> = Arc::new(InMemoryStorage::new());
+    let storage = FaultyStorage::wrap(inner.clone());
+
+    injector().set(Fault::Db, 1.0);
+    let before = faults_injected(Fault::Db);
+    let mut batch = WriteBatch { venues: vec![Venue::fixture("Neumos"), Venue::fixture("Barboza")], ..WriteBatch::new() };
+    let err = storage.write_batch(&mut batch).await.unwrap_err();
+    assert!(err.to_string().contains("chaos.db"), "{}", err);
+    assert!(inner.get_all_venues(None, None).await.unwrap().is_empty());
+
+    let mut catalogger = Catalogger::new(storage.clone());
+    assert!(catalogger.start_run("chaos").await.is_err());
+    let mut run = ProcessRun { id: None, name: "chaos".to_string(), created_at: Utc::now(), finished_at: None };
+    assert!(storage.create_process_run(&mut run).await.is_err());
+    assert!(run.id.is_none());
+    assert_eq!(faults_injected(Fault::Db), before + 3);
+
+    injector().set(Fault::Db, 0.0);
+    assert_eq!(storage.write_batch(&mut batch).await.unwrap().entities, 2);
+    assert!(catalogger.start_run("chaos").await.is_ok());
+    catalogger.finish_run().await.unwrap();
+    assert_eq!(storage.get_all_venues(None, None).await.unwrap().len(), 2);
+}
+
+#[tokio::test]
+async fn partial_fault_rates_fail_some_calls_and_spare_the_rest() {
+    let _chaos = chaos().await;
+    let storage = FaultyStorage::wrap(Arc::new(InMemoryStorage::new()));
+
+    injector().set(Fault::Db, 0.5);
+    let mut failed = 0;
+    for i in 0..100 {
+        let mut batch = WriteBatch { venues: vec![Venue::fixture(&format!("Venue {}", i))], ..WriteBatch::new() };
+        if storage.write_batch(&mut batch).await.is_err() {
+            failed += 1;
+        }
+    }
+    assert!((20..=80).contains(&failed), "{} of 100 failed at p=0.5", failed);
+
+    injector().set(Fault::Db, 0.0);
+    assert_eq!(storage.get_all_venues(None, None).await.unwrap().len(), 100 - failed);
+}
@@ -0,0 +1,61 @@
+//! Runs the built `sms-scraper` binary the way scripts and CI do: `--json` summaries must
+//! be a single JSON document on stdout, and `completions` must print a usable script.
+
+use std::process::{Command, Output};
+
+fn sms_scraper(args: &[&str], cwd: &std::path::Path) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_sms-scraper"))
+        .current_dir(cwd)
+        .args(args)
+        .output()
+        .expect("sms-scraper runs")
+}
+
+fn json_summary(output: &Output) -> serde_json::Value {
+    serde_json::from_slice(&output.stdout).unwrap_or_else(|e| {
+        panic!("stdout is not one JSON document ({}):\n{}", e, String::from_utf8_lossy(&output.stdout))
+    })
+}
+
+#[test]
+fn json_flag_prints_a_structured_summary() {
+    let dir = tempfile::tempdir().unwrap();
+    let data_root = dir.path().join("data");
+    let output = sms_scraper(&["--json", "ingest-log", "status", "--data-root", data_root.to_str().unwrap()], dir.path());
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+
+    let summary = json_summary(&output);
+    assert_eq!(summary["command"], "ingest-log status");
+    assert_eq!(summary["consumer"], "parser");
+    for key in ["byte_offset", "end_offset", "lag_bytes"] {
+        assert_eq!(summary[key], 0, "{}", key);
+    }
+    assert!(summary["last_envelope_id"].is_null());
+    assert_eq!(summary["pending_by_source"], serde_json::json!({}));
+}
+
+#[test]
+fn json_flag_reports_failures_as_a_summary_too() {
+    let dir = tempfile::tempdir().unwrap();
+    let registry_dir = dir.path().join("registry");
+    let output = sms_scraper(
+        &["--json", "sources", "describe", "--source-id", "no_such_source", "--registry-dir", registry_dir.to_str().unwrap()],
+        dir.path(),
+    );
+    assert_eq!(output.status.code(), Some(1));
+
+    let summary = json_summary(&output);
+    assert_eq!(summary["success"], false);
+    assert!(summary["command"].is_string());
+    assert!(summary["error"].as_str().unwrap().contains("no_such_source"));
+}
+
+#[test]
+fn completions_print_a_shell_script() {
+    let dir = tempfile::tempdir().unwrap();
+    let output = sms_scraper(&["completions", "bash"], dir.path());
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    let script = String::from_utf8(output.stdout).unwrap();
+    assert!(!script.trim().is_empty());
+    assert!(script.contains("complete ") && script.contains("sms-scraper"));
+}
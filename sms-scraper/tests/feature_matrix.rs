@@ -0,0 +1,130 @@
+//! Build checks for the workspace's feature sets. Each crate has to compile with only the
+//! features it's checked with here, and the minimal GraphQL server (`cargo minimal`) must not
+//! pull in the scraping stack. `cargo check` runs are slow, so those are ignored by default:
+//! `cargo test -p sms-scraper --test feature_matrix -- --ignored`.
+
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Package and `cargo check` arguments of each supported feature set
+const MATRIX: &[(&str, &[&str])] = &[
+    ("sms-core", &["--no-default-features"]),
+    ("sms-core", &["--no-default-features", "--features", "db"]),
+    ("sms-core", &["--no-default-features", "--features", "http"]),
+    ("sms-core", &["--no-default-features", "--features", "graphql"]),
+    ("sms-scraper", &["--lib", "--no-default-features", "--features", "db"]),
+    ("sms-scraper", &["--lib", "--no-default-features", "--features", "db,metrics"]),
+    ("sms-scraper", &["--lib", "--no-default-features", "--features", "db,encryption"]),
+    ("sms-scraper", &["--lib", "--no-default-features", "--features", "db,scraping"]),
+    ("sms-scraper", &["--all-targets"]),
+    ("sms-graphql", &["--no-default-features"]),
+    ("sms-graphql", &[]),
+];
+
+/// Crates only the scraping CLI needs
+const SCRAPING_ONLY: &[&str] = &[
+    "sms-parsers",
+    "scraper",
+    "select",
+    "notify",
+    "metrics-exporter-prometheus",
+    "tar",
+    "brotli",
+    "jsonschema",
+    "clap_complete",
+    "tracing-opentelemetry",
+    "opentelemetry",
+    "opentelemetry_sdk",
+    "opentelemetry-otlp",
+];
+
+/// Scraping-only crates that TLS (through libsql and reqwest) pulls in anyway, so only
+/// sms-scraper depending on them directly counts
+const SCRAPING_ONLY_DIRECT: &[&str] = &["ring"];
+
+/// reqwest features only the scraping CLI may turn on
+const SCRAPING_ONLY_REQWEST_FEATURES: &[&str] = &["blocking"];
+
+fn workspace_root() -> &'static Path {
+    Path::new(env!("CARGO_MANIFEST_DIR")).parent().expect("sms-scraper lives in the workspace")
+}
+
+fn cargo(args: &[&str]) -> Command {
+    let mut command = Command::new(env!("CARGO"));
+    command.current_dir(workspace_root()).args(args);
+    command
+}
+
+/// `cargo tree` of the minimal GraphQL server, one `<depth><crate> <version>` line per entry
+fn minimal_graphql_tree(edges: &str) -> String {
+    let output = cargo(&["tree", "-p", "sms-graphql", "--no-default-features", "-e", edges, "--prefix", "depth"])
+        .output()
+        .expect("cargo tree runs");
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    String::from_utf8_lossy(&output.stdout).into_owned()
+}
+
+/// (depth, crate name) of a `--prefix depth` line
+fn tree_entry(line: &str) -> Option<(usize, &str)> {
+    let split = line.find(|c: char| !c.is_ascii_digit())?;
+    let depth = line[..split].parse().ok()?;
+    Some((depth, line[split..].split_whitespace().next()?))
+}
+
+#[test]
+fn minimal_graphql_server_leaves_out_the_scraping_stack() {
+    let tree = minimal_graphql_tree("normal");
+    let entries: Vec<(usize, &str)> = tree.lines().filter_map(tree_entry).collect();
+
+    let mut pulled_in: Vec<&str> = SCRAPING_ONLY
+        .iter()
+        .copied()
+        .filter(|name| entries.iter().any(|(_, crate_name)| crate_name == name))
+        .collect();
+
+    // Direct dependencies of sms-scraper: the entries one level below it
+    let mut scraper_deps = Vec::new();
+    let mut scraper_depth = None;
+    for &(depth, name) in &entries {
+        match scraper_depth {
+            Some(d) if depth == d + 1 => scraper_deps.push(name),
+            Some(d) if depth <= d => scraper_depth = None,
+            _ => {}
+        }
+        if name == "sms-scraper" {
+            scraper_depth = Some(depth);
+        }
+    }
+    pulled_in.extend(SCRAPING_ONLY_DIRECT.iter().copied().filter(|name| scraper_deps.contains(name)));
+    assert!(pulled_in.is_empty(), "minimal sms-graphql depends on {:?}", pulled_in);
+
+    let features = minimal_graphql_tree("normal,features");
+    let reqwest_features: Vec<&str> = SCRAPING_ONLY_REQWEST_FEATURES
+        .iter()
+        .copied()
+        .filter(|feature| {
+            let needle = format!("reqwest feature \"{}\"", feature);
+            features.lines().any(|line| line.contains(&needle))
+        })
+        .collect();
+    assert!(reqwest_features.is_empty(), "minimal sms-graphql turns on reqwest features {:?}", reqwest_features);
+}
+
+#[test]
+#[ignore = "runs cargo check once per feature set"]
+fn every_feature_set_checks() {
+    // A target dir of its own, so the matrix neither waits on nor invalidates the main build
+    let target_dir: PathBuf = workspace_root().join("target").join("feature-matrix");
+    let failed: Vec<String> = MATRIX
+        .iter()
+        .filter_map(|(package, args)| {
+            let mut command = cargo(&["check", "-p", package]);
+            command.args(*args).arg("--target-dir").arg(&target_dir);
+            let output = command.output().expect("cargo check runs");
+            (!output.status.success()).then(|| {
+                format!("{} {}:\n{}", package, args.join(" "), String::from_utf8_lossy(&output.stderr))
+            })
+        })
+        .collect();
+    assert!(failed.is_empty(), "feature sets that don't build:\n{}", failed.join("\n"));
+}
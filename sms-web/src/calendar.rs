@@ -0,0 +1,210 @@
+use chrono::{Datelike, Duration, Months, NaiveDate};
+
+use crate::models::{WebEvent, WebEventDay};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CalendarView {
+    Month,
+    Week,
+}
+
+impl CalendarView {
+    pub fn parse(view: Option<&str>) -> Self {
+        match view {
+            Some("week") => CalendarView::Week,
+            _ => CalendarView::Month,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CalendarView::Month => "month",
+            CalendarView::Week => "week",
+        }
+    }
+}
+
+/// The dates a calendar page covers and where prev/next lead
+#[derive(Debug, Clone)]
+pub struct CalendarPeriod {
+    pub title: String,
+    /// First and last day shown; month grids pad out to whole Monday–Sunday weeks
+    pub from: NaiveDate,
+    pub to: NaiveDate,
+    /// First and last day that belong to the period itself
+    pub period_start: NaiveDate,
+    pub period_end: NaiveDate,
+    pub prev: NaiveDate,
+    pub next: NaiveDate,
+}
+
+impl CalendarPeriod {
+    pub fn containing(view: CalendarView, date: NaiveDate) -> Self {
+        match view {
+            CalendarView::Month => {
+                let period_start = date.with_day(1).expect("day 1 exists");
+                let next = period_start + Months::new(1);
+                let period_end = next - Duration::days(1);
+                CalendarPeriod {
+                    title: period_start.format("%B %Y").to_string(),
+                    from: week_start(period_start),
+                    to: week_start(period_end) + Duration::days(6),
+                    period_start,
+                    period_end,
+                    prev: period_start - Months::new(1),
+                    next,
+                }
+            }
+            CalendarView::Week => {
+                let period_start = week_start(date);
+                let period_end = period_start + Duration::days(6);
+                CalendarPeriod {
+                    title: format!(
+                        "{} – {}",
+                        period_start.format("%b %-d"),
+                        period_end.format("%b %-d, %Y")
+                    ),
+                    from: period_start,
+                    to: period_end,
+                    period_start,
+                    period_end,
+                    prev: period_start - Duration::days(7),
+                    next: period_start + Duration::days(7),
+                }
+            }
+        }
+    }
+}
+
+/// Monday on or before `date`
+fn week_start(date: NaiveDate) -> NaiveDate {
+    date - Duration::days(date.weekday().num_days_from_monday() as i64)
+}
+
+/// One day cell in a calendar grid
+#[derive(Debug, Clone)]
+pub struct CalendarDay {
+    pub day_number: u32,
+    pub weekday: String,
+    pub in_period: bool,
+    pub is_today: bool,
+    pub events: Vec<WebEvent>,
+}
+
+/// Lay the grouped days out as Monday–Sunday weeks covering the period
+pub fn build_weeks(period: &CalendarPeriod, days: Vec<WebEventDay>, today: NaiveDate) -> Vec<Vec<CalendarDay>> {
+    let mut by_day: std::collections::HashMap<NaiveDate, Vec<WebEvent>> =
+        days.into_iter().map(|d| (d.day, d.events)).collect();
+
+    let cells: Vec<CalendarDay> = period
+        .from
+        .iter_days()
+        .take_while(|d| *d <= period.to)
+        .map(|day| CalendarDay {
+            day_number: day.day(),
+            weekday: day.format("%a").to_string(),
+            in_period: day >= period.period_start && day <= period.period_end,
+            is_today: day == today,
+            events: by_day.remove(&day).unwrap_or_default(),
+        })
+        .collect();
+
+    cells.chunks(7).map(|week| week.to_vec()).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    fn web_event(title: &str, event_day: NaiveDate) -> WebEvent {
+        WebEvent {
+            id: title.to_lowercase(),
+            title: title.to_string(),
+            event_day,
+            start_time: None,
+            event_url: None,
+            description: None,
+            event_image_url: None,
+            venue: None,
+            artists: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn month_pads_out_to_whole_weeks() {
+        // March 2025 runs Saturday to Monday
+        let period = CalendarPeriod::containing(CalendarView::Month, date(2025, 3, 15));
+        assert_eq!(period.title, "March 2025");
+        assert_eq!((period.period_start, period.period_end), (date(2025, 3, 1), date(2025, 3, 31)));
+        assert_eq!((period.from, period.to), (date(2025, 2, 24), date(2025, 4, 6)));
+        assert_eq!((period.prev, period.next), (date(2025, 2, 1), date(2025, 4, 1)));
+
+        // September 2025 starts on a Monday and needs no leading padding
+        let period = CalendarPeriod::containing(CalendarView::Month, date(2025, 9, 30));
+        assert_eq!(period.from, date(2025, 9, 1));
+        assert_eq!(period.to, date(2025, 10, 5));
+    }
+
+    #[test]
+    fn month_boundaries_cross_years_and_leap_days() {
+        let december = CalendarPeriod::containing(CalendarView::Month, date(2025, 12, 31));
+        assert_eq!(december.period_end, date(2025, 12, 31));
+        assert_eq!((december.prev, december.next), (date(2025, 11, 1), date(2026, 1, 1)));
+
+        let january = CalendarPeriod::containing(CalendarView::Month, date(2026, 1, 1));
+        assert_eq!(january.prev, date(2025, 12, 1));
+        assert_eq!(january.from, date(2025, 12, 29));
+
+        let leap = CalendarPeriod::containing(CalendarView::Month, date(2024, 2, 10));
+        assert_eq!(leap.period_end, date(2024, 2, 29));
+    }
+
+    #[test]
+    fn week_runs_monday_to_sunday() {
+        for day in [date(2025, 3, 10), date(2025, 3, 13), date(2025, 3, 16)] {
+            let period = CalendarPeriod::containing(CalendarView::Week, day);
+            assert_eq!((period.from, period.to), (date(2025, 3, 10), date(2025, 3, 16)), "{day}");
+        }
+
+        let period = CalendarPeriod::containing(CalendarView::Week, date(2026, 1, 1));
+        assert_eq!(period.title, "Dec 29 – Jan 4, 2026");
+        assert_eq!((period.period_start, period.period_end), (date(2025, 12, 29), date(2026, 1, 4)));
+        assert_eq!((period.prev, period.next), (date(2025, 12, 22), date(2026, 1, 5)));
+    }
+
+    #[test]
+    fn weeks_place_events_on_their_day_and_mark_padding() {
+        let period = CalendarPeriod::containing(CalendarView::Month, date(2025, 3, 15));
+        let days = vec![
+            WebEventDay { day: date(2025, 2, 28), events: vec![web_event("Padding show", date(2025, 2, 28))] },
+            WebEventDay { day: date(2025, 3, 1), events: vec![web_event("First", date(2025, 3, 1))] },
+            WebEventDay { day: date(2025, 3, 31), events: vec![web_event("Last", date(2025, 3, 31))] },
+            // Outside the grid, so dropped
+            WebEventDay { day: date(2025, 4, 20), events: vec![web_event("Later", date(2025, 4, 20))] },
+        ];
+        let weeks = build_weeks(&period, days, date(2025, 3, 12));
+
+        assert_eq!(weeks.len(), 6);
+        assert!(weeks.iter().all(|week| week.len() == 7 && week[0].weekday == "Mon" && week[6].weekday == "Sun"));
+        let cells: Vec<&CalendarDay> = weeks.iter().flatten().collect();
+
+        // Feb 24 to Feb 28 pad the first week
+        assert_eq!(cells[0].day_number, 24);
+        assert!(cells[..5].iter().all(|c| !c.in_period));
+        assert!(cells[5].in_period && cells[5].day_number == 1);
+        assert!(cells[35].in_period && cells[35].day_number == 31);
+        assert!(cells[36..].iter().all(|c| !c.in_period));
+
+        assert_eq!(cells[4].events[0].title, "Padding show");
+        assert_eq!(cells[5].events[0].title, "First");
+        assert_eq!(cells[35].events[0].title, "Last");
+        assert_eq!(cells.iter().map(|c| c.events.len()).sum::<usize>(), 3);
+
+        let today: Vec<u32> = cells.iter().filter(|c| c.is_today).map(|c| c.day_number).collect();
+        assert_eq!(today, [12]);
+    }
+}
@@ -5,10 +5,14 @@ use axum::{
 use axum::http::HeaderMap;
 use askama::Template;
 
-use crate::graphql::{fetch_artist, fetch_events, fetch_venue_by_slug, fetch_venues};
-use crate::models::{EventFilter, WebEvent};
+use crate::calendar::{build_weeks, CalendarPeriod, CalendarView};
+use crate::graphql::{fetch_artist, fetch_events, fetch_events_by_day, fetch_venue_by_slug, fetch_venues};
+use crate::models::{CalendarQuery, EventFilter, WebEvent};
 use crate::state::AppState;
-use crate::templates::{ArtistTemplate, EventsListTemplate, IndexTemplate, VenueTemplate, VenuesListTemplate};
+use crate::templates::{
+    ArtistTemplate, CalendarMonthTemplate, CalendarWeekTemplate, EventsListTemplate, IndexTemplate, VenueTemplate,
+    VenuesListTemplate,
+};
 
 pub async fn index(State(state): State<AppState>) -> impl IntoResponse {
     let empty_filter = EventFilter {
@@ -113,10 +117,54 @@ pub async fn venue_page(
 
     let venue_events: Vec<WebEvent> = all_events
         .into_iter()
-        .filter(|event| event.venue.as_ref().map_or(false, |v| v.id == venue.id))
+        .filter(|event| event.venue.as_ref().is_some_and(|v| v.id == venue.id))
         .collect();
 
     let template = VenueTemplate { venue, events: venue_events };
 
     Html(template.render().expect("Template rendering failed"))
 }
+
+pub async fn calendar_page(
+    State(state): State<AppState>,
+    Query(query): Query<CalendarQuery>,
+) -> impl IntoResponse {
+    let today = chrono::Local::now().date_naive();
+    let view = CalendarView::parse(query.view.as_deref());
+    let period = CalendarPeriod::containing(view, query.date.unwrap_or(today));
+
+    let days = match fetch_events_by_day(&state, period.from, period.to).await {
+        Ok(days) => days,
+        Err(e) => return Html(format!("<h1>Error loading calendar: {}</h1>", e)),
+    };
+    let weeks = build_weeks(&period, days, today);
+
+    let link = |view: CalendarView, date: chrono::NaiveDate| format!("/calendar?view={}&date={}", view.as_str(), date);
+    let (title, prev, next, today) = (
+        period.title.clone(),
+        link(view, period.prev),
+        link(view, period.next),
+        link(view, today),
+    );
+    let rendered = match view {
+        CalendarView::Month => CalendarMonthTemplate {
+            title,
+            prev,
+            next,
+            today,
+            week_view: link(CalendarView::Week, period.period_start),
+            weeks,
+        }
+        .render(),
+        CalendarView::Week => CalendarWeekTemplate {
+            title,
+            prev,
+            next,
+            today,
+            month_view: link(CalendarView::Month, period.period_start),
+            days: weeks.into_iter().flatten().collect(),
+        }
+        .render(),
+    };
+    Html(rendered.expect("Template rendering failed"))
+}
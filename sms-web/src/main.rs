@@ -3,6 +3,7 @@
 // New module declarations (code moved out of main.rs)
 mod state;
 mod models;
+mod calendar;
 mod templates;
 mod graphql;
 mod handlers;
@@ -10,21 +11,20 @@ mod router;
 
 // Bring shared state type into scope from module
 use state::AppState;
-use reqwest::Client;
+use sms_client::Client;
 use std::env;
 
 #[tokio::main]
 async fn main() {
-    // Create HTTP client for GraphQL requests
-    let graphql_client = Client::new();
+    // Create the client for GraphQL requests
     let graphql_url = env::var("GRAPHQL_URL").unwrap_or_else(|_| "http://127.0.0.1:8080/graphql".to_string());
 
     let app_state = AppState {
-        graphql_client,
-        graphql_url,
+        client: Client::new(graphql_url),
     };
 
     // Build router from new router module
+    let graphql_url = app_state.client.url().to_string();
     let app = router::app_router(app_state);
 
     // Start the server
@@ -35,7 +35,7 @@ async fn main() {
         .unwrap();
 
     println!("Web server listening on {} (visit http://127.0.0.1:{})", bind_addr, port);
-    println!("GraphQL server URL: {}", env::var("GRAPHQL_URL").unwrap_or_else(|_| "http://127.0.0.1:8080/graphql".to_string()));
+    println!("GraphQL server URL: {}", graphql_url);
     axum::serve(listener, app).await.unwrap();
 }
 // handlers moved to crate::handlers
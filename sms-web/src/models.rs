@@ -3,31 +3,43 @@
 use chrono::{NaiveDate, NaiveTime};
 use serde::{Deserialize, Serialize};
 
-// Web-specific models for GraphQL responses
-#[derive(Debug, Clone, Serialize, Deserialize)]
+// Web-specific view models, built from the GraphQL client's response types
+#[derive(Debug, Clone, Serialize)]
 pub struct WebEvent {
     pub id: String,
     pub title: String,
-    #[serde(rename = "eventDay")]
     pub event_day: NaiveDate,
-    #[serde(rename = "startTime")]
     pub start_time: Option<NaiveTime>,
-    #[serde(rename = "eventUrl")]
     pub event_url: Option<String>,
     pub description: Option<String>,
-    #[serde(rename = "eventImageUrl")]
     pub event_image_url: Option<String>,
     pub venue: Option<WebVenue>,
     pub artists: Vec<WebArtist>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+impl From<sms_client::Event> for WebEvent {
+    fn from(event: sms_client::Event) -> Self {
+        Self {
+            id: event.id,
+            title: event.title,
+            event_day: event.event_day,
+            start_time: event.start_time,
+            event_url: event.event_url,
+            description: event.description,
+            event_image_url: event.event_image_url,
+            venue: event.venue.map(WebVenue::from),
+            artists: event.artists.into_iter().map(WebArtist::from).collect(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
 pub struct WebVenue {
     pub id: String,
     pub name: String,
     pub address: String,
     pub city: String,
-    #[serde(skip)]
+    pub upcoming_events_count: u64,
     pub slug: String,
 }
 
@@ -45,33 +57,53 @@ impl WebVenue {
             .collect::<Vec<&str>>()
             .join("-")
     }
+}
 
-    pub fn with_slug(mut self) -> Self {
-        self.slug = Self::create_slug(&self.name);
-        self
+impl From<sms_client::Venue> for WebVenue {
+    fn from(venue: sms_client::Venue) -> Self {
+        Self {
+            slug: Self::create_slug(&venue.name),
+            id: venue.id,
+            name: venue.name,
+            address: venue.address,
+            city: venue.city,
+            upcoming_events_count: venue.upcoming_events_count.unwrap_or_default().max(0) as u64,
+        }
     }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize)]
 pub struct WebArtist {
     pub id: String,
     pub name: String,
-    #[serde(rename = "nameSlug")]
     pub name_slug: String,
     pub bio: Option<String>,
-    #[serde(rename = "artistImageUrl")]
     pub artist_image_url: Option<String>,
 }
 
-#[derive(Serialize)]
-pub struct GraphQLRequest {
-    pub query: String,
-    pub variables: Option<serde_json::Value>,
+impl From<sms_client::Artist> for WebArtist {
+    fn from(artist: sms_client::Artist) -> Self {
+        Self {
+            id: artist.id,
+            name: artist.name,
+            name_slug: artist.name_slug,
+            bio: artist.bio,
+            artist_image_url: artist.artist_image_url,
+        }
+    }
 }
 
-#[derive(Debug, Deserialize)]
-pub struct GraphQLError {
-    pub message: String,
+/// A calendar day and its events, as grouped by the `eventsByDay` query
+#[derive(Debug, Clone, Serialize)]
+pub struct WebEventDay {
+    pub day: NaiveDate,
+    pub events: Vec<WebEvent>,
+}
+
+impl From<sms_client::EventDay> for WebEventDay {
+    fn from(day: sms_client::EventDay) -> Self {
+        Self { day: day.day, events: day.events.into_iter().map(WebEvent::from).collect() }
+    }
 }
 
 #[derive(Deserialize, Debug, Clone)]
@@ -81,3 +113,11 @@ pub struct EventFilter {
     pub limit: Option<i32>,
     pub offset: Option<i32>,
 }
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct CalendarQuery {
+    /// `month` (default) or `week`
+    pub view: Option<String>,
+    /// Any date inside the period to show; defaults to today
+    pub date: Option<NaiveDate>,
+}
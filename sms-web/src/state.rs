@@ -1,7 +1,6 @@
-use reqwest::Client;
+use sms_client::Client;
 
 #[derive(Clone)]
 pub struct AppState {
-    pub graphql_client: Client,
-    pub graphql_url: String,
+    pub client: Client,
 }
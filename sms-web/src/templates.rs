@@ -1,5 +1,6 @@
 use askama::Template;
 
+use crate::calendar::CalendarDay;
 use crate::models::{WebArtist, WebEvent, WebVenue};
 
 #[derive(Template)]
@@ -33,3 +34,25 @@ pub struct VenueTemplate {
 pub struct VenuesListTemplate {
     pub venues: Vec<WebVenue>,
 }
+
+#[derive(Template)]
+#[template(path = "calendar_month.html")]
+pub struct CalendarMonthTemplate {
+    pub title: String,
+    pub prev: String,
+    pub next: String,
+    pub today: String,
+    pub week_view: String,
+    pub weeks: Vec<Vec<CalendarDay>>,
+}
+
+#[derive(Template)]
+#[template(path = "calendar_week.html")]
+pub struct CalendarWeekTemplate {
+    pub title: String,
+    pub prev: String,
+    pub next: String,
+    pub today: String,
+    pub month_view: String,
+    pub days: Vec<CalendarDay>,
+}